@@ -0,0 +1,64 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+/// The CPU time consumed by the current thread.
+pub fn thread_cpu_time() -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // It never fails with a supported clock and a valid pointer.
+    unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
+
+/// Adds the CPU time spent in polling the future to the counter, in nanoseconds.
+///
+/// A future is polled by one thread at a time, so the CPU time the thread consumed
+/// during a poll is what the poll cost, wherever the task is scheduled.
+pub struct CpuTimeFuture<F> {
+    inner: Pin<Box<F>>,
+    cpu_time_ns: Arc<AtomicU64>,
+}
+
+impl<F: Future> CpuTimeFuture<F> {
+    pub fn create(inner: F, cpu_time_ns: Arc<AtomicU64>) -> Self {
+        CpuTimeFuture {
+            inner: Box::pin(inner),
+            cpu_time_ns,
+        }
+    }
+}
+
+impl<F: Future> Future for CpuTimeFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+        let start = thread_cpu_time();
+        let poll = self.inner.as_mut().poll(ctx);
+        let cost = thread_cpu_time().saturating_sub(start);
+        self.cpu_time_ns
+            .fetch_add(cost.as_nanos() as u64, Ordering::Relaxed);
+        poll
+    }
+}
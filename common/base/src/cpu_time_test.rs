@@ -0,0 +1,39 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_exception::Result;
+
+use crate::*;
+
+#[tokio::test]
+async fn test_cpu_time_future() -> Result<()> {
+    let cpu_time_ns = Arc::new(AtomicU64::new(0));
+    let future = CpuTimeFuture::create(
+        async {
+            let start = thread_cpu_time();
+            while thread_cpu_time() - start < std::time::Duration::from_millis(10) {}
+            1
+        },
+        cpu_time_ns.clone(),
+    );
+
+    assert_eq!(future.await, 1);
+    assert!(cpu_time_ns.load(Ordering::Relaxed) >= 10_000_000);
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_infallible::Mutex;
+
+/// A failure injected into a call to a remote service.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fault {
+    /// The call fails before reaching the service.
+    Error,
+    /// The call is delayed, then goes on as usual.
+    Latency(Duration),
+    /// Only a part of the data is returned, for the calls reading data.
+    PartialRead,
+    /// The call is done by the service, but the response never comes back.
+    LostResponse,
+}
+
+/// The probabilities of the faults, each in [0, 1].
+///
+/// With the same seed, the same sequence of calls gets the same faults.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FaultRates {
+    pub seed: u64,
+    pub error: f64,
+    pub latency: f64,
+    pub latency_duration: Duration,
+    pub partial_read: f64,
+    pub lost_response: f64,
+}
+
+impl FaultRates {
+    pub fn is_enabled(&self) -> bool {
+        self.error > 0.0
+            || self.latency > 0.0
+            || self.partial_read > 0.0
+            || self.lost_response > 0.0
+    }
+}
+
+struct FaultState {
+    random: u64,
+    scripted: VecDeque<(String, Fault)>,
+    injected: Vec<(String, Fault)>,
+}
+
+/// Decides which calls fail and how, shared by the wrappers of the data accessor and the meta client.
+pub struct FaultInjector {
+    rates: FaultRates,
+    state: Mutex<FaultState>,
+}
+
+impl FaultInjector {
+    pub fn create(rates: FaultRates) -> Arc<FaultInjector> {
+        Arc::new(FaultInjector {
+            state: Mutex::new(FaultState {
+                random: rates.seed,
+                scripted: VecDeque::new(),
+                injected: vec![],
+            }),
+            rates,
+        })
+    }
+
+    /// Makes the next call of `operation` fail with `fault`, before any random fault.
+    pub fn inject(&self, operation: &str, fault: Fault) {
+        let mut state = self.state.lock();
+        state.scripted.push_back((operation.to_string(), fault));
+    }
+
+    /// Returns the fault of this call of `operation`, if any.
+    ///
+    /// `PartialRead` is only chosen randomly for the operations which are `reading`.
+    pub fn next_fault(&self, operation: &str, reading: bool) -> Option<Fault> {
+        let mut state = self.state.lock();
+
+        let scripted = state.scripted.iter().position(|(op, _)| op == operation);
+        let fault = match scripted {
+            Some(position) => state.scripted.remove(position).map(|(_, fault)| fault),
+            None => self.random_fault(&mut state.random, reading),
+        };
+
+        if let Some(fault) = &fault {
+            state.injected.push((operation.to_string(), fault.clone()));
+        }
+        fault
+    }
+
+    fn random_fault(&self, random: &mut u64, reading: bool) -> Option<Fault> {
+        let partial_read = if reading {
+            self.rates.partial_read
+        } else {
+            0.0
+        };
+        let candidates = [
+            (self.rates.error, Fault::Error),
+            (self.rates.lost_response, Fault::LostResponse),
+            (partial_read, Fault::PartialRead),
+            (
+                self.rates.latency,
+                Fault::Latency(self.rates.latency_duration),
+            ),
+        ];
+
+        let value = next_random(random);
+        let mut threshold = 0.0;
+        for (rate, fault) in candidates {
+            threshold += rate;
+            if value < threshold {
+                return Some(fault);
+            }
+        }
+        None
+    }
+
+    /// All the faults injected so far, in the order of the calls.
+    pub fn injected(&self) -> Vec<(String, Fault)> {
+        self.state.lock().injected.clone()
+    }
+}
+
+/// splitmix64, returns a value in [0, 1).
+fn next_random(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,104 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_exception::Result;
+
+use crate::*;
+
+#[test]
+fn test_fault_injector_disabled() -> Result<()> {
+    let injector = FaultInjector::create(FaultRates::default());
+    assert!(!FaultRates::default().is_enabled());
+
+    for _ in 0..100 {
+        assert_eq!(None, injector.next_fault("get", true));
+    }
+    assert!(injector.injected().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_fault_injector_scripted() -> Result<()> {
+    let injector = FaultInjector::create(FaultRates::default());
+    injector.inject("put", Fault::LostResponse);
+    injector.inject("get", Fault::PartialRead);
+
+    assert_eq!(Some(Fault::PartialRead), injector.next_fault("get", true));
+    assert_eq!(None, injector.next_fault("get", true));
+    assert_eq!(Some(Fault::LostResponse), injector.next_fault("put", false));
+    assert_eq!(None, injector.next_fault("put", false));
+
+    assert_eq!(injector.injected(), vec![
+        ("get".to_string(), Fault::PartialRead),
+        ("put".to_string(), Fault::LostResponse),
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_fault_injector_rates() -> Result<()> {
+    let rates = FaultRates {
+        seed: 42,
+        error: 0.2,
+        latency: 0.2,
+        latency_duration: Duration::from_millis(10),
+        partial_read: 0.2,
+        lost_response: 0.2,
+    };
+    assert!(rates.is_enabled());
+
+    // The same seed, the same faults.
+    let run = |reading: bool| {
+        let injector = FaultInjector::create(rates.clone());
+        (0..1000)
+            .map(|_| injector.next_fault("op", reading))
+            .collect::<Vec<_>>()
+    };
+    let faults = run(true);
+    assert_eq!(faults, run(true));
+
+    let count = |faults: &[Option<Fault>], expect: Option<Fault>| {
+        faults.iter().filter(|f| **f == expect).count()
+    };
+    for fault in [
+        Some(Fault::Error),
+        Some(Fault::LostResponse),
+        Some(Fault::PartialRead),
+        Some(Fault::Latency(Duration::from_millis(10))),
+        None,
+    ] {
+        let n = count(&faults, fault.clone());
+        assert!((100..300).contains(&n), "{:?}: {}", fault, n);
+    }
+
+    // No partial read for the writes.
+    let faults = run(false);
+    assert_eq!(0, count(&faults, Some(Fault::PartialRead)));
+    Ok(())
+}
+
+#[test]
+fn test_fault_injector_always() -> Result<()> {
+    let injector = FaultInjector::create(FaultRates {
+        error: 1.0,
+        ..Default::default()
+    });
+
+    for _ in 0..100 {
+        assert_eq!(Some(Fault::Error), injector.next_fault("op", false));
+    }
+    Ok(())
+}
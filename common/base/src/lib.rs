@@ -12,15 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod cpu_time_test;
+
 #[cfg(test)]
 mod runtime_test;
 
+#[cfg(test)]
+mod fault_injection_test;
+
 #[cfg(test)]
 mod progress_test;
 
 #[cfg(test)]
 mod stoppable_test;
 
+mod cpu_time;
+mod fault_injection;
 mod profiling;
 mod progress;
 mod runtime;
@@ -29,6 +37,11 @@ mod stop_handle;
 mod stoppable;
 mod uniq_id;
 
+pub use cpu_time::thread_cpu_time;
+pub use cpu_time::CpuTimeFuture;
+pub use fault_injection::Fault;
+pub use fault_injection::FaultInjector;
+pub use fault_injection::FaultRates;
 pub use profiling::Profiling;
 pub use progress::Progress;
 pub use progress::ProgressCallback;
@@ -37,6 +50,7 @@ pub use runtime::BlockingWait;
 pub use runtime::Dropper;
 pub use runtime::Runtime;
 pub use runtime::TrySpawn;
+pub use shutdown_signal::hangup_signal_stream;
 pub use shutdown_signal::signal_stream;
 pub use shutdown_signal::SignalStream;
 pub use shutdown_signal::SignalType;
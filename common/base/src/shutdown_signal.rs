@@ -40,7 +40,6 @@ pub type SignalStream = Pin<Box<dyn Stream<Item = SignalType>>>;
 #[cfg(not(target_os = "windows"))]
 pub fn signal_stream() -> Result<SignalStream> {
     Ok(Box::pin(UnixShutdownSignalStream {
-        hangup_signal: signal(SignalKind::hangup())?,
         sigint_signal: signal(SignalKind::interrupt())?,
         sigterm_signal: signal(SignalKind::terminate())?,
     }))
@@ -51,9 +50,26 @@ pub fn signal_stream() -> Result<SignalStream> {
     Ok(Box::pin(WindowsShutdownSignalStream { ctrl_c: ctrl_c()? }))
 }
 
+/// SIGHUP does not shut the server down, it asks for a config reload.
 #[cfg(not(target_os = "windows"))]
-struct UnixShutdownSignalStream {
+pub fn hangup_signal_stream() -> Result<impl Stream<Item = SignalType> + Send + Unpin> {
+    Ok(UnixHangupSignalStream {
+        hangup_signal: signal(SignalKind::hangup())?,
+    })
+}
+
+#[cfg(target_os = "windows")]
+pub fn hangup_signal_stream() -> Result<impl Stream<Item = SignalType> + Send + Unpin> {
+    Ok(futures::stream::pending::<SignalType>())
+}
+
+#[cfg(not(target_os = "windows"))]
+struct UnixHangupSignalStream {
     hangup_signal: Signal,
+}
+
+#[cfg(not(target_os = "windows"))]
+struct UnixShutdownSignalStream {
     sigint_signal: Signal,
     sigterm_signal: Signal,
 }
@@ -69,10 +85,6 @@ impl Stream for UnixShutdownSignalStream {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut_self = self.get_mut();
-        if let Poll::Ready(res) = mut_self.hangup_signal.poll_recv(cx) {
-            return Poll::Ready(res.map(|_| SignalType::Hangup));
-        }
-
         if let Poll::Ready(res) = mut_self.sigint_signal.poll_recv(cx) {
             return Poll::Ready(res.map(|_| SignalType::Sigint));
         }
@@ -85,6 +97,18 @@ impl Stream for UnixShutdownSignalStream {
     }
 }
 
+#[cfg(not(target_os = "windows"))]
+impl Stream for UnixHangupSignalStream {
+    type Item = SignalType;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut()
+            .hangup_signal
+            .poll_recv(cx)
+            .map(|ready| ready.map(|_| SignalType::Hangup))
+    }
+}
+
 #[cfg(target_os = "windows")]
 impl Stream for WindowsShutdownSignalStream {
     type Item = SignalType;
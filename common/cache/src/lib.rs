@@ -39,3 +39,4 @@ pub use meter::file_meter::FileSize;
 #[cfg(not(target_os = "macos"))]
 pub use meter::heap_meter::HeapSize;
 pub use meter::Meter;
+pub use ritelinked::DefaultHashBuilder;
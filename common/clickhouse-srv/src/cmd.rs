@@ -39,7 +39,7 @@ impl Cmd {
             Packet::Ping => {
                 encoder.uvarint(SERVER_PONG);
             }
-            // todo cancel
+            // Cancel packets of a running query are handled by the query writer.
             Packet::Cancel => {}
             Packet::Hello(hello) => {
                 if !connection.session.authenticate(
@@ -78,6 +78,8 @@ impl Cmd {
             Packet::Query(query) => {
                 ctx.state.query = query.query.clone();
                 ctx.state.compression = query.compression;
+                ctx.state.settings = query.settings;
+                ctx.state.is_cancelled = false;
 
                 let session = connection.session.clone();
                 session.execute_query(ctx, connection).await?;
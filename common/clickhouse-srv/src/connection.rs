@@ -31,6 +31,7 @@ use crate::protocols::ExceptionResponse;
 use crate::protocols::Packet;
 use crate::protocols::SERVER_END_OF_STREAM;
 use crate::types::Block;
+use crate::types::ProfileInfo;
 use crate::types::Progress;
 use crate::CHContext;
 use crate::ClickHouseSession;
@@ -184,6 +185,14 @@ impl Connection {
         Ok(())
     }
 
+    pub async fn write_profile_info(&mut self, profile_info: ProfileInfo) -> Result<()> {
+        let mut encoder = Encoder::new();
+        profile_info.write(&mut encoder);
+        self.stream.write_all(&encoder.get_buffer()).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+
     pub async fn write_end_of_stream(&mut self) -> Result<()> {
         let mut encoder = Encoder::new();
         encoder.uvarint(SERVER_END_OF_STREAM);
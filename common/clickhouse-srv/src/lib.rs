@@ -24,6 +24,7 @@ use tokio::sync::Notify;
 use crate::cmd::Cmd;
 use crate::connection::Connection;
 use crate::protocols::HelloRequest;
+use crate::protocols::SettingValue;
 use crate::types::Block;
 use crate::types::Progress;
 
@@ -87,6 +88,8 @@ pub struct QueryState {
     pub stage: Stage,
     pub compression: u64,
     pub query: String,
+    /// Settings sent by the client with the query.
+    pub settings: Vec<(String, SettingValue)>,
     pub is_cancelled: bool,
     pub is_connection_closed: bool,
     /// empty or not
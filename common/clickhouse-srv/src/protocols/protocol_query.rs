@@ -12,17 +12,95 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
 use std::io::Read;
 
 use super::*;
 use crate::binary::ReadEx;
-use crate::errors::DriverError::UnknownSetting;
-use crate::errors::Error;
 use crate::errors::Result;
 
 const TCP: u8 = 1;
 const HTTP: u8 = 2;
 
+// Settings are sent in the binary format until the server revision reaches
+// DBMS_MIN_REVISION_WITH_SETTINGS_SERIALIZED_AS_STRINGS, which needs the type of
+// a setting to read its value. Numbers, bools and timespans are var uints,
+// floats, enums and strings are sent as strings.
+const STRING_SETTINGS: &[&str] = &[
+    "count_distinct_implementation",
+    "date_time_input_format",
+    "date_time_output_format",
+    "distinct_overflow_mode",
+    "distributed_product_mode",
+    "format_csv_delimiter",
+    "format_custom_escaping_rule",
+    "format_regexp",
+    "format_regexp_escaping_rule",
+    "format_schema",
+    "format_template_resultset",
+    "format_template_row",
+    "format_template_rows_between_delimiter",
+    "group_by_overflow_mode",
+    "join_algorithm",
+    "join_default_strictness",
+    "join_overflow_mode",
+    "load_balancing",
+    "log_comment",
+    "log_queries_min_type",
+    "log_queries_probability",
+    "max_streams_multiplier_for_merge_tables",
+    "max_streams_to_max_threads_ratio",
+    "memory_profiler_sample_probability",
+    "opentelemetry_start_trace_probability",
+    "output_format_avro_codec",
+    "read_overflow_mode",
+    "result_overflow_mode",
+    "send_logs_level",
+    "set_overflow_mode",
+    "sort_overflow_mode",
+    "timeout_overflow_mode",
+    "totals_auto_threshold",
+    "totals_mode",
+    "transfer_overflow_mode",
+    "union_default_mode",
+];
+
+const INT64_SETTINGS: &[&str] = &["network_zstd_compression_level", "os_thread_priority"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SettingValue {
+    UInt64(u64),
+    Int64(i64),
+    String(String),
+}
+
+impl SettingValue {
+    pub fn read_from<R: Read>(reader: &mut R, name: &str) -> Result<SettingValue> {
+        if STRING_SETTINGS.contains(&name) {
+            return Ok(SettingValue::String(reader.read_string()?));
+        }
+
+        let value = reader.read_uvarint()?;
+        if INT64_SETTINGS.contains(&name) {
+            // Zigzag encoded.
+            return Ok(SettingValue::Int64(
+                (value >> 1) as i64 ^ -((value & 1) as i64),
+            ));
+        }
+        Ok(SettingValue::UInt64(value))
+    }
+}
+
+impl fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingValue::UInt64(v) => write!(f, "{}", v),
+            SettingValue::Int64(v) => write!(f, "{}", v),
+            SettingValue::String(v) => write!(f, "{}", v),
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct QueryClientInfo {
     pub query_kind: u8,
@@ -111,6 +189,7 @@ impl QueryClientInfo {
 pub struct QueryRequest {
     pub(crate) query_id: String,
     pub(crate) client_info: QueryClientInfo,
+    pub(crate) settings: Vec<(String, SettingValue)>,
     pub(crate) stage: u64,
     pub(crate) compression: u64,
     pub(crate) query: String,
@@ -139,6 +218,7 @@ impl QueryRequest {
 
         client_info.interface = TCP;
 
+        let mut settings = vec![];
         loop {
             let name = reader.read_string()?;
 
@@ -146,19 +226,14 @@ impl QueryRequest {
                 break;
             }
 
-            match name.as_str() {
-                "max_block_size" | "max_threads" => {
-                    let _ = reader.read_uvarint()?;
-                }
-                _ => {
-                    return Err(Error::Driver(UnknownSetting { name }));
-                }
-            }
+            let value = SettingValue::read_from(reader, &name)?;
+            settings.push((name, value));
         }
 
         let query_protocol = QueryRequest {
             query_id,
             client_info,
+            settings,
             stage: reader.read_uvarint()?,
             compression: reader.read_uvarint()?,
             query: reader.read_string()?,
@@ -167,3 +242,29 @@ impl QueryRequest {
         Ok(query_protocol)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::SettingValue;
+    use crate::binary::Encoder;
+
+    #[test]
+    fn test_read_setting_value() {
+        let mut encoder = Encoder::new();
+        encoder.uvarint(8);
+        encoder.uvarint(3);
+        encoder.string("0.5");
+        let buffer = encoder.get_buffer();
+        let mut reader = Cursor::new(buffer.as_slice());
+
+        let value = SettingValue::read_from(&mut reader, "max_threads").unwrap();
+        assert_eq!(value, SettingValue::UInt64(8));
+        let value = SettingValue::read_from(&mut reader, "os_thread_priority").unwrap();
+        assert_eq!(value, SettingValue::Int64(-2));
+        let value = SettingValue::read_from(&mut reader, "totals_auto_threshold").unwrap();
+        assert_eq!(value, SettingValue::String("0.5".to_string()));
+        assert_eq!(value.to_string(), "0.5");
+    }
+}
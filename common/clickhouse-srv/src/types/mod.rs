@@ -48,6 +48,7 @@ pub use self::value::Value;
 pub use self::value_ref::ValueRef;
 use crate::binary::Encoder;
 use crate::protocols::DBMS_MIN_REVISION_WITH_CLIENT_WRITE_INFO;
+use crate::protocols::SERVER_PROFILE_INFO;
 use crate::protocols::SERVER_PROGRESS;
 
 pub(crate) mod column;
@@ -88,7 +89,7 @@ impl Progress {
 }
 
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
-pub(crate) struct ProfileInfo {
+pub struct ProfileInfo {
     pub rows: u64,
     pub bytes: u64,
     pub blocks: u64,
@@ -97,6 +98,18 @@ pub(crate) struct ProfileInfo {
     pub calculated_rows_before_limit: bool,
 }
 
+impl ProfileInfo {
+    pub fn write(&self, encoder: &mut Encoder) {
+        encoder.uvarint(SERVER_PROFILE_INFO);
+        encoder.uvarint(self.rows);
+        encoder.uvarint(self.blocks);
+        encoder.uvarint(self.bytes);
+        encoder.write_bytes(&[self.applied_limit as u8]);
+        encoder.uvarint(self.rows_before_limit);
+        encoder.write_bytes(&[self.calculated_rows_before_limit as u8]);
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub(crate) struct ServerInfo {
     pub name: String,
@@ -31,6 +31,18 @@ impl<T> AsyncSeekableReader for T where T: AsyncRead + AsyncSeek {}
 
 pub type InputStream = Box<dyn AsyncSeekableReader + Send + Unpin>;
 
+/// An object returned by `DataAccessor::list`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectMeta {
+    /// Path of the object, in the same form as the one accepted by `get`.
+    pub path: String,
+    pub size: u64,
+    /// Seconds since the unix epoch, None if the storage does not tell.
+    pub last_modified: Option<u64>,
+    /// Changes with the content of the object, None if the storage does not tell.
+    pub etag: Option<String>,
+}
+
 pub trait SeekableReader: Read + Seek {}
 
 impl<T> SeekableReader for T where T: Read + Seek {}
@@ -57,6 +69,12 @@ pub trait DataAccessor: Send + Sync {
         stream_len: usize,
     ) -> Result<()>;
 
+    /// Lists all the objects under `prefix`, recursively.
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>>;
+
+    /// Removes the object, removing an object which does not exist is not an error.
+    async fn remove(&self, path: &str) -> Result<()>;
+
     async fn read(&self, location: &str) -> Result<Vec<u8>> {
         let mut input_stream = self.get_input_stream(location, None)?;
         let mut buffer = vec![];
@@ -0,0 +1,680 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use aes_gcm::aead::Aead;
+use aes_gcm::aead::NewAead;
+use aes_gcm::aead::Payload;
+use aes_gcm::Aes256Gcm;
+use aes_gcm::Key;
+use aes_gcm::Nonce;
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use futures::future::BoxFuture;
+use futures::ready;
+use futures::stream::Stream;
+use futures::AsyncRead;
+use futures::AsyncReadExt;
+use futures::AsyncSeek;
+use futures::AsyncSeekExt;
+use futures::Future;
+use futures::StreamExt;
+use rand::RngCore;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+pub const ENCRYPTION_KEY_LEN: usize = 32;
+
+pub type EncryptionKey = [u8; ENCRYPTION_KEY_LEN];
+
+/// Starts an encrypted object, a plaintext object is very unlikely to start with it.
+const MAGIC: &[u8; 8] = b"DBENC\x00\x00\x01";
+/// The magic, the key version and the nonce prefix.
+const HEADER_LEN: usize = 20;
+/// The plaintext of an object is encrypted in chunks, so that a range can be read alone.
+const CHUNK_LEN: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+const SEALED_CHUNK_LEN: usize = CHUNK_LEN + TAG_LEN;
+/// How long `CommandKeyProvider` encrypts with a key before asking for the current one again.
+const CURRENT_KEY_TTL: Duration = Duration::from_secs(300);
+
+/// Where the keys come from, e.g. the config or a KMS.
+#[async_trait::async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// The version and the key to encrypt the new objects with.
+    async fn current_key(&self) -> Result<(u32, EncryptionKey)>;
+
+    /// The key of the version, to decrypt the objects encrypted with it.
+    async fn get_key(&self, version: u32) -> Result<EncryptionKey>;
+}
+
+/// Parses a key in the form of `version:base64 key`.
+fn parse_key(pair: &str) -> Result<(u32, EncryptionKey)> {
+    // The key itself is never in the message.
+    let invalid = || {
+        ErrorCode::DALEncryptionError(format!(
+            "Invalid encryption key, expect version:base64 key of {} bytes",
+            ENCRYPTION_KEY_LEN
+        ))
+    };
+
+    let (version, key) = pair.trim().split_once(':').ok_or_else(invalid)?;
+    let version = version.trim().parse::<u32>().map_err(|_| invalid())?;
+    let key = base64::decode(key.trim()).map_err(|_| invalid())?;
+    let key = EncryptionKey::try_from(key.as_slice()).map_err(|_| invalid())?;
+    Ok((version, key))
+}
+
+/// Keys given by the config, the latest version encrypts the new objects.
+pub struct StaticKeyProvider {
+    keys: BTreeMap<u32, EncryptionKey>,
+}
+
+impl StaticKeyProvider {
+    /// Parses the `version:base64 key` pairs separated by commas.
+    pub fn parse(keys: &str) -> Result<StaticKeyProvider> {
+        let keys = keys
+            .split(',')
+            .filter(|pair| !pair.trim().is_empty())
+            .map(parse_key)
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        if keys.is_empty() {
+            return Err(ErrorCode::DALEncryptionError("No encryption key is given"));
+        }
+        Ok(StaticKeyProvider { keys })
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for StaticKeyProvider {
+    async fn current_key(&self) -> Result<(u32, EncryptionKey)> {
+        let (version, key) = self.keys.iter().next_back().expect("keys are never empty");
+        Ok((*version, *key))
+    }
+
+    async fn get_key(&self, version: u32) -> Result<EncryptionKey> {
+        self.keys.get(&version).copied().ok_or_else(|| {
+            ErrorCode::DALEncryptionError(format!("Unknown encryption key version {}", version))
+        })
+    }
+}
+
+/// Keys printed by a command as `version:base64 key`, e.g. a KMS client which decrypts
+/// the data keys. The command is run with `current` or a key version as its last argument.
+///
+/// The keys of the versions are kept in memory once fetched, the current key is fetched again
+/// every few minutes to follow the rotations.
+pub struct CommandKeyProvider {
+    command: String,
+    current: Mutex<Option<(Instant, u32, EncryptionKey)>>,
+    keys: Mutex<HashMap<u32, EncryptionKey>>,
+}
+
+impl CommandKeyProvider {
+    pub fn create(command: &str) -> CommandKeyProvider {
+        CommandKeyProvider {
+            command: command.to_string(),
+            current: Mutex::new(None),
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn run(&self, arg: String) -> Result<(u32, EncryptionKey)> {
+        let command = self.command.clone();
+        let output = tokio::task::spawn_blocking(move || {
+            let mut words = command.split_whitespace();
+            let program = words.next().unwrap_or_default();
+            std::process::Command::new(program)
+                .args(words)
+                .arg(arg)
+                .output()
+        })
+        .await
+        .map_err(|e| ErrorCode::DALEncryptionError(format!("Encryption key command: {}", e)))??;
+
+        if !output.status.success() {
+            return Err(ErrorCode::DALEncryptionError(format!(
+                "Encryption key command failed with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        parse_key(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for CommandKeyProvider {
+    async fn current_key(&self) -> Result<(u32, EncryptionKey)> {
+        let current = *self.current.lock();
+        if let Some((fetched_at, version, key)) = current {
+            if fetched_at.elapsed() < CURRENT_KEY_TTL {
+                return Ok((version, key));
+            }
+        }
+
+        let (version, key) = self.run("current".to_string()).await?;
+        *self.current.lock() = Some((Instant::now(), version, key));
+        self.keys.lock().insert(version, key);
+        Ok((version, key))
+    }
+
+    async fn get_key(&self, version: u32) -> Result<EncryptionKey> {
+        let key = self.keys.lock().get(&version).copied();
+        if let Some(key) = key {
+            return Ok(key);
+        }
+
+        let (got, key) = self.run(version.to_string()).await?;
+        if got != version {
+            return Err(ErrorCode::DALEncryptionError(format!(
+                "Encryption key command returned version {} for version {}",
+                got, version
+            )));
+        }
+        self.keys.lock().insert(version, key);
+        Ok(key)
+    }
+}
+
+/// The cipher of an object, its chunks are sealed with the nonce prefix of the object and
+/// their index, and whether it is the last chunk, so they can't be reordered or truncated.
+struct ObjectCipher {
+    version: u32,
+    prefix: [u8; 8],
+    cipher: Aes256Gcm,
+}
+
+impl ObjectCipher {
+    fn create(version: u32, key: &EncryptionKey, prefix: [u8; 8]) -> ObjectCipher {
+        ObjectCipher {
+            version,
+            prefix,
+            cipher: Aes256Gcm::new(Key::from_slice(key)),
+        }
+    }
+
+    fn header(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&self.version.to_be_bytes());
+        header.extend_from_slice(&self.prefix);
+        header
+    }
+
+    fn nonce(&self, index: u64) -> Result<[u8; 12]> {
+        let index = u32::try_from(index)
+            .map_err(|_| ErrorCode::DALEncryptionError("Object too large to encrypt"))?;
+        let mut nonce = [0; 12];
+        nonce[..8].copy_from_slice(&self.prefix);
+        nonce[8..].copy_from_slice(&index.to_be_bytes());
+        Ok(nonce)
+    }
+
+    fn seal(&self, index: u64, last: bool, chunk: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.nonce(index)?;
+        let payload = Payload {
+            msg: chunk,
+            aad: &[last as u8],
+        };
+        self.cipher
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| ErrorCode::DALEncryptionError("Failed to encrypt the object"))
+    }
+
+    fn open(&self, index: u64, last: bool, sealed: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.nonce(index)?;
+        let payload = Payload {
+            msg: sealed,
+            aad: &[last as u8],
+        };
+        self.cipher
+            .decrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| {
+                ErrorCode::DALEncryptionError(format!(
+                    "Failed to decrypt chunk {} of the object, it is corrupted or truncated",
+                    index
+                ))
+            })
+    }
+}
+
+/// The key version and the nonce prefix of an encrypted object, None if it is not encrypted.
+fn parse_header(header: &[u8]) -> Option<(u32, [u8; 8])> {
+    if header.len() < HEADER_LEN || &header[..8] != MAGIC {
+        return None;
+    }
+    let version = u32::from_be_bytes(header[8..12].try_into().unwrap());
+    let prefix = header[12..20].try_into().unwrap();
+    Some((version, prefix))
+}
+
+/// The chunks of `plain_len` bytes, an empty object has an empty chunk.
+fn chunk_count(plain_len: u64) -> u64 {
+    let chunk_len = CHUNK_LEN as u64;
+    ((plain_len + chunk_len - 1) / chunk_len).max(1)
+}
+
+/// The size of `plain_len` bytes once encrypted.
+pub fn encrypted_len(plain_len: u64) -> u64 {
+    HEADER_LEN as u64 + plain_len + chunk_count(plain_len) * TAG_LEN as u64
+}
+
+/// The plaintext size of an encrypted object of `stored_len` bytes, None if it is truncated.
+fn decrypted_len(stored_len: u64) -> Option<u64> {
+    let sealed_chunk_len = SEALED_CHUNK_LEN as u64;
+    let body = stored_len.checked_sub(HEADER_LEN as u64)?;
+    let chunks = (body + sealed_chunk_len - 1) / sealed_chunk_len;
+    let last = body.checked_sub(chunks.checked_sub(1)? * sealed_chunk_len)?;
+    if last < TAG_LEN as u64 {
+        return None;
+    }
+    Some(body - chunks * TAG_LEN as u64)
+}
+
+fn truncated() -> ErrorCode {
+    ErrorCode::DALEncryptionError("The encrypted object is truncated")
+}
+
+/// A `DataAccessor` which encrypts the objects with AES-256-GCM before they are written,
+/// and decrypts them when they are read.
+///
+/// The objects without the header of the encrypted ones are read as they are, e.g. the files
+/// uploaded by other tools for `COPY`. `list` returns the sizes of the stored objects, and the
+/// sync reader of `get_reader` is not supported.
+pub struct EncryptionAccessor {
+    inner: Arc<dyn DataAccessor>,
+    keys: Arc<dyn KeyProvider>,
+}
+
+impl EncryptionAccessor {
+    pub fn create(inner: Arc<dyn DataAccessor>, keys: Arc<dyn KeyProvider>) -> EncryptionAccessor {
+        EncryptionAccessor { inner, keys }
+    }
+}
+
+async fn new_cipher(keys: &dyn KeyProvider) -> Result<ObjectCipher> {
+    let (version, key) = keys.current_key().await?;
+    let mut prefix = [0; 8];
+    rand::thread_rng().fill_bytes(&mut prefix);
+    Ok(ObjectCipher::create(version, &key, prefix))
+}
+
+/// Whether the bytes start with the header of the encrypted objects.
+pub fn is_encrypted(stored: &[u8]) -> bool {
+    parse_header(stored).is_some()
+}
+
+/// Encrypts the bytes with the current key, in the same layout as the encrypted objects.
+pub async fn encrypt_bytes(keys: &dyn KeyProvider, plain: &[u8]) -> Result<Vec<u8>> {
+    let cipher = new_cipher(keys).await?;
+    let chunks = chunk_count(plain.len() as u64);
+    let mut sealed = Vec::with_capacity(encrypted_len(plain.len() as u64) as usize);
+    sealed.extend_from_slice(&cipher.header());
+    for index in 0..chunks {
+        let start = index as usize * CHUNK_LEN;
+        let end = (start + CHUNK_LEN).min(plain.len());
+        sealed.extend(cipher.seal(index, index + 1 == chunks, &plain[start..end])?);
+    }
+    Ok(sealed)
+}
+
+/// Decrypts the bytes of `encrypt_bytes`, the bytes without the header are returned as they are.
+pub async fn decrypt_bytes(keys: &dyn KeyProvider, stored: Vec<u8>) -> Result<Vec<u8>> {
+    let (version, prefix) = match parse_header(&stored) {
+        None => return Ok(stored),
+        Some(header) => header,
+    };
+    let plain_len = decrypted_len(stored.len() as u64).ok_or_else(truncated)?;
+    let key = keys.get_key(version).await?;
+    let cipher = ObjectCipher::create(version, &key, prefix);
+
+    let chunks = chunk_count(plain_len);
+    let mut plain = Vec::with_capacity(plain_len as usize);
+    for (index, sealed) in stored[HEADER_LEN..].chunks(SEALED_CHUNK_LEN).enumerate() {
+        let index = index as u64;
+        plain.extend(cipher.open(index, index + 1 == chunks, sealed)?);
+    }
+    Ok(plain)
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for EncryptionAccessor {
+    fn get_reader(&self, path: &str, _len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        Err(ErrorCode::DALEncryptionError(format!(
+            "Sync reader of {} is not supported by the encrypted storage",
+            path
+        )))
+    }
+
+    fn get_input_stream(&self, path: &str, _stream_len: Option<u64>) -> Result<InputStream> {
+        // The length of the caller is of the plaintext, not of the stored object.
+        let inner = self.inner.get_input_stream(path, None)?;
+        Ok(Box::new(DecryptingInputStream {
+            keys: self.keys.clone(),
+            inner: Some(inner),
+            loading: None,
+            layout: None,
+            chunk: None,
+            pos: 0,
+        }))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let stored = self.inner.get(path).await?;
+        decrypt_bytes(self.keys.as_ref(), stored).await
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        let sealed = encrypt_bytes(self.keys.as_ref(), &content).await?;
+        self.inner.put(path, sealed).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>>
+                + Send
+                + Unpin
+                + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        let cipher = new_cipher(self.keys.as_ref()).await?;
+        let sealed_len = encrypted_len(stream_len as u64) as usize;
+        let sealed = seal_stream(cipher, input_stream, stream_len);
+        self.inner
+            .put_stream(path, Box::new(sealed), sealed_len)
+            .await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        self.inner.list(prefix).await
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        self.inner.remove(path).await
+    }
+
+    async fn read(&self, location: &str) -> Result<Vec<u8>> {
+        let stored = self.inner.read(location).await?;
+        decrypt_bytes(self.keys.as_ref(), stored).await
+    }
+}
+
+type ByteStream = Box<
+    dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>> + Send + Unpin + 'static,
+>;
+
+/// Encrypts the stream of `stream_len` bytes a chunk at a time.
+fn seal_stream(
+    cipher: ObjectCipher,
+    input: ByteStream,
+    stream_len: usize,
+) -> Pin<Box<dyn Stream<Item = std::io::Result<bytes::Bytes>> + Send>> {
+    let chunks = chunk_count(stream_len as u64);
+    let header = bytes::Bytes::from(cipher.header());
+    let chunks_stream = futures::stream::unfold(
+        Some((cipher, input, Vec::new(), 0)),
+        move |state| async move {
+            let (cipher, mut input, mut buffer, index) = state?;
+            if index == chunks {
+                return None;
+            }
+
+            // The last chunk takes the rest of the stream.
+            let last = index + 1 == chunks;
+            let chunk_len = match last {
+                true => stream_len - index as usize * CHUNK_LEN,
+                false => CHUNK_LEN,
+            };
+            while buffer.len() < chunk_len {
+                match input.next().await {
+                    Some(Ok(bytes)) => buffer.extend_from_slice(&bytes),
+                    Some(Err(e)) => return Some((Err(e), None)),
+                    None => break,
+                }
+            }
+            if buffer.len() < chunk_len || (last && buffer.len() > chunk_len) {
+                let e = std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("The stream is not of {} bytes", stream_len),
+                );
+                return Some((Err(e), None));
+            }
+
+            let rest = buffer.split_off(chunk_len);
+            match cipher.seal(index, last, &buffer) {
+                Ok(sealed) => {
+                    let state = Some((cipher, input, rest, index + 1));
+                    Some((Ok(bytes::Bytes::from(sealed)), state))
+                }
+                Err(e) => Some((Err(to_io_error(e)), None)),
+            }
+        },
+    );
+    let header_stream = futures::stream::once(async move { Ok::<_, std::io::Error>(header) });
+    Box::pin(header_stream.chain(chunks_stream))
+}
+
+fn to_io_error(e: ErrorCode) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, e.message())
+}
+
+/// Where the chunks of a stored object are, `cipher` is None if it is not encrypted.
+#[derive(Clone)]
+struct StoredLayout {
+    stored_len: u64,
+    cipher: Option<Arc<ObjectCipher>>,
+}
+
+impl StoredLayout {
+    fn plain_len(&self) -> u64 {
+        match self.cipher {
+            None => self.stored_len,
+            Some(_) => decrypted_len(self.stored_len).unwrap_or(0),
+        }
+    }
+
+    fn chunks(&self) -> u64 {
+        chunk_count(self.plain_len())
+    }
+
+    /// The offset and the length of the stored chunk.
+    fn chunk_range(&self, index: u64) -> (u64, usize) {
+        let (base, chunk_len) = match self.cipher {
+            None => (0, CHUNK_LEN as u64),
+            Some(_) => (HEADER_LEN as u64, SEALED_CHUNK_LEN as u64),
+        };
+        let offset = base + index * chunk_len;
+        let len = chunk_len.min(self.stored_len.saturating_sub(offset));
+        (offset, len as usize)
+    }
+}
+
+enum Loaded {
+    Layout(StoredLayout),
+    Chunk(u64, Vec<u8>),
+}
+
+/// Loads a chunk or the layout, and gives the inner stream back.
+type Loading = BoxFuture<'static, (InputStream, Result<Loaded>)>;
+
+async fn load_layout(
+    mut inner: InputStream,
+    keys: Arc<dyn KeyProvider>,
+) -> (InputStream, Result<Loaded>) {
+    let loaded: Result<Loaded> = async {
+        let stored_len = inner.seek(SeekFrom::End(0)).await?;
+        let mut header = vec![0; HEADER_LEN.min(stored_len as usize)];
+        inner.seek(SeekFrom::Start(0)).await?;
+        inner.read_exact(&mut header).await?;
+
+        let cipher = match parse_header(&header) {
+            None => None,
+            Some((version, prefix)) => {
+                decrypted_len(stored_len).ok_or_else(truncated)?;
+                let key = keys.get_key(version).await?;
+                Some(Arc::new(ObjectCipher::create(version, &key, prefix)))
+            }
+        };
+        Ok(Loaded::Layout(StoredLayout { stored_len, cipher }))
+    }
+    .await;
+    (inner, loaded)
+}
+
+async fn load_chunk(
+    mut inner: InputStream,
+    layout: StoredLayout,
+    index: u64,
+) -> (InputStream, Result<Loaded>) {
+    let loaded: Result<Loaded> = async {
+        let (offset, len) = layout.chunk_range(index);
+        inner.seek(SeekFrom::Start(offset)).await?;
+        let mut chunk = vec![0; len];
+        inner.read_exact(&mut chunk).await?;
+        if let Some(cipher) = &layout.cipher {
+            chunk = cipher.open(index, index + 1 == layout.chunks(), &chunk)?;
+        }
+        Ok(Loaded::Chunk(index, chunk))
+    }
+    .await;
+    (inner, loaded)
+}
+
+/// Reads the plaintext of an object a chunk at a time, a seek only loads the chunk it lands in.
+struct DecryptingInputStream {
+    keys: Arc<dyn KeyProvider>,
+    /// None while it is held by `loading`.
+    inner: Option<InputStream>,
+    loading: Option<Loading>,
+    layout: Option<StoredLayout>,
+    chunk: Option<(u64, Vec<u8>)>,
+    pos: u64,
+}
+
+impl DecryptingInputStream {
+    fn poll_loading(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if let Some(loading) = &mut self.loading {
+            let (inner, loaded) = ready!(loading.as_mut().poll(cx));
+            self.loading = None;
+            self.inner = Some(inner);
+            match loaded.map_err(to_io_error)? {
+                Loaded::Layout(layout) => self.layout = Some(layout),
+                Loaded::Chunk(index, chunk) => self.chunk = Some((index, chunk)),
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_loading_layout(&mut self) {
+        let inner = self.inner.take().expect("no loading is running");
+        self.loading = Some(Box::pin(load_layout(inner, self.keys.clone())));
+    }
+}
+
+impl AsyncRead for DecryptingInputStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            ready!(this.poll_loading(cx))?;
+            let layout = match &this.layout {
+                None => {
+                    this.start_loading_layout();
+                    continue;
+                }
+                Some(layout) => layout,
+            };
+            if buf.is_empty() || this.pos >= layout.plain_len() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let index = this.pos / CHUNK_LEN as u64;
+            match &this.chunk {
+                Some((loaded, chunk)) if *loaded == index => {
+                    let start = ((this.pos - index * CHUNK_LEN as u64) as usize).min(chunk.len());
+                    let n = (chunk.len() - start).min(buf.len());
+                    buf[..n].copy_from_slice(&chunk[start..start + n]);
+                    this.pos += n as u64;
+                    return Poll::Ready(Ok(n));
+                }
+                _ => {
+                    let inner = this.inner.take().expect("no loading is running");
+                    let loading = load_chunk(inner, layout.clone(), index);
+                    this.loading = Some(Box::pin(loading));
+                }
+            }
+        }
+    }
+}
+
+impl AsyncSeek for DecryptingInputStream {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        loop {
+            ready!(this.poll_loading(cx))?;
+            let (base, offset) = match pos {
+                SeekFrom::Start(offset) => (offset, 0),
+                SeekFrom::Current(offset) => (this.pos, offset),
+                SeekFrom::End(offset) => match &this.layout {
+                    None => {
+                        this.start_loading_layout();
+                        continue;
+                    }
+                    Some(layout) => (layout.plain_len(), offset),
+                },
+            };
+
+            let new_pos = match offset >= 0 {
+                true => base.checked_add(offset as u64),
+                false => base.checked_sub(offset.unsigned_abs()),
+            };
+            return match new_pos {
+                Some(new_pos) => {
+                    this.pos = new_pos;
+                    Poll::Ready(Ok(new_pos))
+                }
+                None => Poll::Ready(Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "invalid seeking operation",
+                ))),
+            };
+        }
+    }
+}
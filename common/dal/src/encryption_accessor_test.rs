@@ -0,0 +1,164 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+
+use crate::decrypt_bytes;
+use crate::encrypt_bytes;
+use crate::encrypted_len;
+use crate::is_encrypted;
+use crate::DataAccessor;
+use crate::EncryptionAccessor;
+use crate::KeyProvider;
+use crate::Local;
+use crate::StaticKeyProvider;
+
+fn key_pair(version: u32, byte: u8) -> String {
+    format!("{}:{}", version, base64::encode([byte; 32]))
+}
+
+fn encrypted(local: Arc<dyn DataAccessor>, keys: &str) -> Result<EncryptionAccessor> {
+    let keys: Arc<dyn KeyProvider> = Arc::new(StaticKeyProvider::parse(keys)?);
+    Ok(EncryptionAccessor::create(local, keys))
+}
+
+#[tokio::test]
+async fn test_static_key_provider() -> Result<()> {
+    let keys = StaticKeyProvider::parse(&format!("{}, {}", key_pair(2, 2), key_pair(1, 1)))?;
+    assert_eq!(keys.current_key().await?, (2, [2; 32]));
+    assert_eq!(keys.get_key(1).await?, [1; 32]);
+    assert!(keys.get_key(3).await.is_err());
+
+    let code = ErrorCode::DALEncryptionError("").code();
+    for invalid in ["", "1", "x:AAAA", "1:not base64", "1:AAAA"] {
+        let e = StaticKeyProvider::parse(invalid).err().unwrap();
+        assert_eq!(e.code(), code, "{}", invalid);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_encrypt_bytes() -> Result<()> {
+    let keys = StaticKeyProvider::parse(&key_pair(1, 1))?;
+    let sealed = encrypt_bytes(&keys, b"secret").await?;
+    assert!(is_encrypted(&sealed));
+    assert!(!sealed.windows(6).any(|w| w == b"secret"));
+    assert_eq!(
+        decrypt_bytes(&keys, sealed.clone()).await?,
+        b"secret".to_vec()
+    );
+
+    // A tampered byte fails the authentication.
+    let mut tampered = sealed;
+    *tampered.last_mut().unwrap() ^= 1;
+    assert!(decrypt_bytes(&keys, tampered).await.is_err());
+    assert!(!is_encrypted(b"secret"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_encryption_accessor() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_path(dir.path().to_owned()));
+    let accessor = encrypted(local.clone(), &key_pair(1, 1))?;
+
+    // Three chunks, the last one is partial, and an empty object.
+    let content = (0..150_000u32).map(|i| i as u8).collect::<Vec<_>>();
+    accessor.put("a", content.clone()).await?;
+    accessor.put("empty", vec![]).await?;
+
+    let stored = local.get("a").await?;
+    assert_eq!(stored.len() as u64, encrypted_len(content.len() as u64));
+    assert!(!stored.windows(64).any(|w| w == &content[..64]));
+    assert_eq!(accessor.get("a").await?, content);
+    assert_eq!(accessor.read("a").await?, content);
+    assert_eq!(local.get("empty").await?.len() as u64, encrypted_len(0));
+    assert_eq!(accessor.get("empty").await?, Vec::<u8>::new());
+
+    // The plaintext objects are read as they are.
+    local.put("plain", b"plain".to_vec()).await?;
+    assert_eq!(accessor.get("plain").await?, b"plain".to_vec());
+
+    // A rotated key encrypts the new objects, the old ones are decrypted with their key.
+    let rotated = encrypted(
+        local.clone(),
+        &format!("{},{}", key_pair(1, 1), key_pair(2, 2)),
+    )?;
+    assert_eq!(rotated.get("a").await?, content);
+    let unknown = encrypted(local.clone(), &key_pair(2, 2))?;
+    assert!(unknown.get("a").await.is_err());
+
+    // The tampered and the truncated objects are refused.
+    let mut tampered = stored.clone();
+    tampered[100] ^= 1;
+    local.put("tampered", tampered).await?;
+    assert!(accessor.get("tampered").await.is_err());
+    local
+        .put("truncated", stored[..stored.len() - 100].to_vec())
+        .await?;
+    assert!(accessor.get("truncated").await.is_err());
+
+    assert!(accessor.get_reader("a", None).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_encryption_accessor_input_stream() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_path(dir.path().to_owned()));
+    let accessor = encrypted(local.clone(), &key_pair(1, 1))?;
+
+    let content = (0..150_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let chunks = content
+        .chunks(10_000)
+        .map(|chunk| Ok(bytes::Bytes::from(chunk.to_vec())))
+        .collect::<Vec<_>>();
+    let stream = Box::new(futures::stream::iter(chunks));
+    accessor.put_stream("a", stream, content.len()).await?;
+    assert_eq!(accessor.get("a").await?, content);
+
+    let mut stream = accessor.get_input_stream("a", None)?;
+    let mut buf = vec![];
+    stream.read_to_end(&mut buf).await?;
+    assert_eq!(buf, content);
+
+    // Across the chunks, from the end and backwards.
+    let mut buf = vec![0; 1000];
+    stream.seek(SeekFrom::Start(65_000)).await?;
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(buf, &content[65_000..66_000]);
+    assert_eq!(stream.seek(SeekFrom::End(-1000)).await?, 149_000);
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(buf, &content[149_000..]);
+    stream.seek(SeekFrom::Current(-150_000)).await?;
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(buf, &content[..1000]);
+    assert_eq!(stream.read(&mut buf).await?, 1000);
+    assert!(stream.seek(SeekFrom::Current(-10_000)).await.is_err());
+
+    // The plaintext objects are read as they are.
+    local.put("plain", content.clone()).await?;
+    let mut stream = accessor.get_input_stream("plain", None)?;
+    stream.seek(SeekFrom::End(-1000)).await?;
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(buf, &content[149_000..]);
+    Ok(())
+}
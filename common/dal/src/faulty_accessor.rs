@@ -0,0 +1,162 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_base::Fault;
+use common_base::FaultInjector;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::stream::Stream;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+/// A `DataAccessor` which fails on purpose, so that the retry and abort paths can be tested.
+///
+/// The sync methods(`get_reader`, `get_input_stream`) can only fail with an error,
+/// the other faults are ignored by them.
+pub struct FaultyAccessor {
+    inner: Arc<dyn DataAccessor>,
+    injector: Arc<FaultInjector>,
+}
+
+impl FaultyAccessor {
+    pub fn create(inner: Arc<dyn DataAccessor>, injector: Arc<FaultInjector>) -> FaultyAccessor {
+        FaultyAccessor { inner, injector }
+    }
+
+    fn error(operation: &str, path: &str) -> ErrorCode {
+        ErrorCode::DALTransportError(format!(
+            "Injected fault: {} failed, path: {}",
+            operation, path
+        ))
+    }
+
+    fn lost_response(operation: &str, path: &str) -> ErrorCode {
+        ErrorCode::Timeout(format!(
+            "Injected fault: response of {} lost, path: {}",
+            operation, path
+        ))
+    }
+
+    /// Applies the fault which happens before the call reaching the storage,
+    /// returns the fault which happens after.
+    async fn before(&self, operation: &str, path: &str, reading: bool) -> Result<Option<Fault>> {
+        match self.injector.next_fault(operation, reading) {
+            Some(Fault::Error) => Err(Self::error(operation, path)),
+            Some(Fault::Latency(duration)) => {
+                tokio::time::sleep(duration).await;
+                Ok(None)
+            }
+            other => Ok(other),
+        }
+    }
+
+    fn after<T>(operation: &str, path: &str, fault: Option<Fault>, res: T) -> Result<T> {
+        match fault {
+            Some(Fault::LostResponse) => Err(Self::lost_response(operation, path)),
+            _ => Ok(res),
+        }
+    }
+
+    fn partial_bytes(fault: &Option<Fault>, mut bytes: Bytes) -> Bytes {
+        if let Some(Fault::PartialRead) = fault {
+            bytes.truncate(bytes.len() / 2);
+        }
+        bytes
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for FaultyAccessor {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        match self.injector.next_fault("get_reader", false) {
+            Some(Fault::Error) => Err(Self::error("get_reader", path)),
+            _ => self.inner.get_reader(path, len),
+        }
+    }
+
+    fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        match self.injector.next_fault("get_input_stream", false) {
+            Some(Fault::Error) => Err(Self::error("get_input_stream", path)),
+            _ => self.inner.get_input_stream(path, stream_len),
+        }
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let fault = self.before("get", path, true).await?;
+        let bytes = self.inner.get(path).await?;
+        Self::after(
+            "get",
+            path,
+            fault.clone(),
+            Self::partial_bytes(&fault, bytes),
+        )
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        let fault = self.before("put", path, false).await?;
+        self.inner.put(path, content).await?;
+        Self::after("put", path, fault, ())
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>>
+                + Send
+                + Unpin
+                + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        let fault = self.before("put_stream", path, false).await?;
+        self.inner
+            .put_stream(path, input_stream, stream_len)
+            .await?;
+        Self::after("put_stream", path, fault, ())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        let fault = self.before("list", prefix, true).await?;
+        let mut objects = self.inner.list(prefix).await?;
+        if let Some(Fault::PartialRead) = fault {
+            objects.truncate(objects.len() / 2);
+        }
+        Self::after("list", prefix, fault, objects)
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        let fault = self.before("remove", path, false).await?;
+        self.inner.remove(path).await?;
+        Self::after("remove", path, fault, ())
+    }
+
+    async fn read(&self, location: &str) -> Result<Vec<u8>> {
+        let fault = self.before("read", location, true).await?;
+        let bytes = self.inner.read(location).await?;
+        Self::after(
+            "read",
+            location,
+            fault.clone(),
+            Self::partial_bytes(&fault, bytes),
+        )
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_base::Fault;
+use common_base::FaultInjector;
+use common_base::FaultRates;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::DataAccessor;
+use crate::FaultyAccessor;
+use crate::Local;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_faulty_accessor_scripted() -> Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_path(tmp_dir.path().to_owned()));
+    let injector = FaultInjector::create(FaultRates::default());
+    let dal = FaultyAccessor::create(local.clone(), injector.clone());
+
+    // Error: nothing is written.
+    injector.inject("put", Fault::Error);
+    let res = dal.put("a", b"hello".to_vec()).await;
+    assert_eq!(
+        ErrorCode::DALTransportError("").code(),
+        res.unwrap_err().code()
+    );
+    assert!(local.get("a").await.is_err());
+
+    // Lost response: written, but the caller does not know.
+    injector.inject("put", Fault::LostResponse);
+    let res = dal.put("a", b"hello".to_vec()).await;
+    assert_eq!(ErrorCode::Timeout("").code(), res.unwrap_err().code());
+    assert_eq!(b"hello".to_vec(), local.get("a").await?);
+
+    // Partial read.
+    injector.inject("get", Fault::PartialRead);
+    assert_eq!(b"he".to_vec(), dal.get("a").await?);
+    assert_eq!(b"hello".to_vec(), dal.get("a").await?);
+
+    injector.inject("read", Fault::PartialRead);
+    assert_eq!(b"he".to_vec(), dal.read("a").await?);
+
+    // Latency.
+    injector.inject("get", Fault::Latency(Duration::from_millis(100)));
+    let start = Instant::now();
+    assert_eq!(b"hello".to_vec(), dal.get("a").await?);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    // The sync methods.
+    injector.inject("get_input_stream", Fault::Error);
+    assert!(dal.get_input_stream("a", None).is_err());
+    assert!(dal.get_input_stream("a", None).is_ok());
+
+    assert_eq!(injector.injected().len(), 6);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_faulty_accessor_rates() -> Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_path(tmp_dir.path().to_owned()));
+    let injector = FaultInjector::create(FaultRates {
+        seed: 7,
+        error: 0.5,
+        ..Default::default()
+    });
+    let dal = FaultyAccessor::create(local.clone(), injector.clone());
+
+    let mut failed = 0;
+    for i in 0..100 {
+        if dal.put(&format!("obj_{}", i), vec![1, 2, 3]).await.is_err() {
+            failed += 1;
+        }
+    }
+
+    assert_eq!(failed, injector.injected().len());
+    assert!((25..75).contains(&failed), "failed: {}", failed);
+    assert_eq!(100 - failed, local.list("").await?.len());
+    Ok(())
+}
@@ -24,7 +24,10 @@ use rusoto_core::credential::StaticProvider;
 use rusoto_core::ByteStream;
 use rusoto_core::HttpClient;
 use rusoto_core::Region;
+use rusoto_core::RusotoError;
+use rusoto_s3::DeleteObjectRequest;
 use rusoto_s3::GetObjectRequest;
+use rusoto_s3::ListObjectsV2Request;
 use rusoto_s3::PutObjectRequest;
 use rusoto_s3::S3Client;
 use rusoto_s3::S3 as RusotoS3;
@@ -32,6 +35,7 @@ use rusoto_s3::S3 as RusotoS3;
 use crate::Bytes;
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 use crate::S3InputStream;
 use crate::SeekableReader;
 
@@ -92,10 +96,7 @@ impl S3 {
             body: Some(input_stream),
             ..Default::default()
         };
-        self.client
-            .put_object(req)
-            .await
-            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        self.client.put_object(req).await.map_err(s3_error)?;
         Ok(())
     }
 }
@@ -129,11 +130,7 @@ impl DataAccessor for S3 {
             bucket: self.bucket.to_string(),
             ..Default::default()
         };
-        let output = self
-            .client
-            .get_object(req)
-            .await
-            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        let output = self.client.get_object(req).await.map_err(s3_error)?;
         match output.body {
             Some(stream) => {
                 let mut res = vec![];
@@ -163,4 +160,71 @@ impl DataAccessor for S3 {
         self.put_byte_stream(path, ByteStream::new_with_size(s, stream_len))
             .await
     }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        let mut objects = vec![];
+        let mut continuation_token = None;
+        loop {
+            // without a delimiter, keys in all the "sub directories" are returned
+            let req = ListObjectsV2Request {
+                bucket: self.bucket.to_string(),
+                prefix: Some(prefix.to_string()),
+                continuation_token: continuation_token.take(),
+                ..Default::default()
+            };
+            let output = self.client.list_objects_v2(req).await.map_err(s3_error)?;
+
+            for object in output.contents.unwrap_or_default() {
+                if let Some(key) = object.key {
+                    objects.push(ObjectMeta {
+                        path: key,
+                        size: object.size.unwrap_or(0) as u64,
+                        last_modified: object.last_modified.as_deref().and_then(parse_rfc3339),
+                        // Quoted by S3.
+                        etag: object.e_tag.map(|etag| etag.trim_matches('"').to_string()),
+                    });
+                }
+            }
+
+            match (output.is_truncated, output.next_continuation_token) {
+                (Some(true), Some(token)) => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+        Ok(objects)
+    }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        // deleting a missing key succeeds in s3
+        let req = DeleteObjectRequest {
+            key: path.to_string(),
+            bucket: self.bucket.to_string(),
+            ..Default::default()
+        };
+        self.client.delete_object(req).await.map_err(s3_error)?;
+        Ok(())
+    }
+}
+
+/// The failures to reach s3, the throttling and the server errors are transient,
+/// see `RetryAccessor`.
+fn s3_error<E: std::error::Error + 'static>(e: RusotoError<E>) -> ErrorCode {
+    let transient = match &e {
+        RusotoError::HttpDispatch(_) => true,
+        RusotoError::Unknown(response) => {
+            response.status.is_server_error() || response.status.as_u16() == 429
+        }
+        _ => false,
+    };
+    match transient {
+        true => ErrorCode::DALTransientError(e.to_string()),
+        false => ErrorCode::DALTransportError(e.to_string()),
+    }
+}
+
+// e.g. "2021-09-01T08:00:00.000Z"
+fn parse_rfc3339(time: &str) -> Option<u64> {
+    chrono::DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|t| t.timestamp().max(0) as u64)
 }
@@ -27,8 +27,28 @@ use crate::AzureBlobInputStream;
 use crate::Bytes;
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 use crate::SeekableReader;
 
+/// How the requests to Azure Blob Storage are authorized.
+#[derive(Clone, PartialEq)]
+pub enum AzureBlobCredential {
+    /// The key of the storage account, the requests are signed with SharedKey.
+    SharedKey(String),
+    /// A shared access signature of the account or the container,
+    /// e.g. `sv=2020-08-04&ss=b&srt=co&sp=rwdl&se=2022-01-01T00:00:00Z&sig=...`.
+    SasToken(String),
+}
+
+impl std::fmt::Debug for AzureBlobCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AzureBlobCredential::SharedKey(_) => write!(f, "SharedKey(******)"),
+            AzureBlobCredential::SasToken(_) => write!(f, "SasToken(******)"),
+        }
+    }
+}
+
 pub struct AzureBlobAccessor {
     client: Arc<StorageClient>,
     container: String,
@@ -71,6 +91,65 @@ impl AzureBlobAccessor {
         }
     }
 
+    /// Create a azure blob accessor of the container, authorized by the credential.
+    pub fn with_credential(
+        account: impl Into<String>,
+        container: impl Into<String>,
+        credential: &AzureBlobCredential,
+    ) -> Result<Self> {
+        let account = account.into();
+        let container = container.into();
+        if account.is_empty() || container.is_empty() {
+            return Err(ErrorCode::InvalidConfig(
+                "Azure blob storage requires the account and the container",
+            ));
+        }
+
+        let http_client: Arc<Box<dyn HttpClient>> = Arc::new(Box::new(reqwest::Client::new()));
+        let client = match credential {
+            AzureBlobCredential::SharedKey(key) => {
+                StorageAccountClient::new_access_key(http_client, account, key)
+            }
+            AzureBlobCredential::SasToken(token) => {
+                // The token may be copied with the leading '?' of the url.
+                let token = token.trim_start_matches('?');
+                StorageAccountClient::new_sas_token(http_client, account, token).map_err(|e| {
+                    ErrorCode::InvalidConfig(format!(
+                        "Invalid SAS token of azure blob storage, {}",
+                        e
+                    ))
+                })?
+            }
+        };
+
+        Ok(Self {
+            client: client.as_storage_client(),
+            container,
+        })
+    }
+
+    /// The size and the last modified time of a blob.
+    pub async fn stat(&self, path: &str) -> Result<ObjectMeta> {
+        let blob = self
+            .client
+            .as_container_client(&self.container)
+            .as_blob_client(path);
+
+        let response = blob.get_properties().execute().await.map_err(|e| {
+            ErrorCode::DALTransportError(format!(
+                "Failed on azure blob stat operation, {}",
+                e.to_string()
+            ))
+        })?;
+        let properties = &response.blob.properties;
+        Ok(ObjectMeta {
+            path: path.to_string(),
+            size: properties.content_length,
+            last_modified: Some(properties.last_modified.timestamp().max(0) as u64),
+            etag: Some(properties.etag.to_string()),
+        })
+    }
+
     async fn put_blob(&self, blob_name: &str, body: Vec<u8>) -> common_exception::Result<()> {
         let blob = self
             .client
@@ -170,4 +249,56 @@ impl DataAccessor for AzureBlobAccessor {
         }
         self.put_blob(path, data).await
     }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        let container = self.client.as_container_client(&self.container);
+
+        let mut objects = vec![];
+        let mut next_marker = None;
+        loop {
+            // without a delimiter, the blobs in all the "sub directories" are returned
+            let mut request = container.list_blobs().prefix(prefix);
+            if let Some(marker) = next_marker.take() {
+                request = request.next_marker(marker);
+            }
+            let response = request.execute().await.map_err(|e| {
+                ErrorCode::DALTransportError(format!(
+                    "Failed on azure blob list operation, {}",
+                    e.to_string()
+                ))
+            })?;
+
+            for blob in response.blobs.blobs {
+                objects.push(ObjectMeta {
+                    path: blob.name,
+                    size: blob.properties.content_length,
+                    last_modified: Some(blob.properties.last_modified.timestamp().max(0) as u64),
+                    etag: Some(blob.properties.etag.to_string()),
+                });
+            }
+
+            match response.next_marker {
+                Some(marker) => next_marker = Some(marker),
+                None => break,
+            }
+        }
+        Ok(objects)
+    }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        let blob = self
+            .client
+            .as_container_client(&self.container)
+            .as_blob_client(path);
+
+        match blob.delete().execute().await {
+            Ok(_) => Ok(()),
+            // Removing a missing blob succeeds, like the other storages.
+            Err(e) if e.to_string().contains("BlobNotFound") => Ok(()),
+            Err(e) => Err(ErrorCode::DALTransportError(format!(
+                "Failed on azure blob delete operation, {}",
+                e.to_string()
+            ))),
+        }
+    }
 }
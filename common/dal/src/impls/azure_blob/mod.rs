@@ -20,4 +20,5 @@ mod azure_blob_accessor;
 mod azure_blob_input_stream;
 
 pub use azure_blob_accessor::AzureBlobAccessor;
+pub use azure_blob_accessor::AzureBlobCredential;
 pub use azure_blob_input_stream::AzureBlobInputStream;
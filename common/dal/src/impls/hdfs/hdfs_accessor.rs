@@ -0,0 +1,490 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::Stream;
+use futures::StreamExt;
+use reqwest::header::AUTHORIZATION;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::header::LOCATION;
+use reqwest::Method;
+use reqwest::Response;
+use reqwest::StatusCode;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::HdfsInputStream;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+/// How the requests to the WebHDFS endpoint are authenticated.
+#[derive(Clone, PartialEq)]
+pub enum HdfsCredential {
+    /// Pseudo authentication, the requests are made as the user,
+    /// or as the user of the endpoint if it is empty.
+    Simple { user: String },
+    /// A delegation token of the cluster, e.g. fetched by `hdfs fetchdt`.
+    DelegationToken(String),
+    /// The requests are authenticated by SPNEGO, with the tickets of the principal in the keytab.
+    /// Requires the `hdfs-kerberos` feature.
+    Kerberos { principal: String, keytab: String },
+}
+
+impl std::fmt::Debug for HdfsCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HdfsCredential::Simple { user } => write!(f, "Simple({})", user),
+            HdfsCredential::DelegationToken(_) => write!(f, "DelegationToken(******)"),
+            HdfsCredential::Kerberos { principal, keytab } => {
+                write!(f, "Kerberos({}, {})", principal, keytab)
+            }
+        }
+    }
+}
+
+/// A WebHDFS client, the paths are relative to the root directory.
+pub(crate) struct WebHdfsClient {
+    http: reqwest::Client,
+    endpoint: String,
+    root: String,
+    credential: HdfsCredential,
+}
+
+impl WebHdfsClient {
+    /// The url of an operation on the path, with the authentication parameters.
+    pub(crate) fn url(&self, path: &str, op: &str, params: &[(&str, String)]) -> String {
+        let path = format!("{}/{}", self.root, path.trim_start_matches('/'));
+        let mut url = format!(
+            "{}/webhdfs/v1{}?op={}",
+            self.endpoint,
+            percent_encode(&path, b"/="),
+            op
+        );
+        match &self.credential {
+            HdfsCredential::Simple { user } if !user.is_empty() => {
+                url.push_str(&format!("&user.name={}", percent_encode(user, b"")));
+            }
+            HdfsCredential::DelegationToken(token) => {
+                url.push_str(&format!("&delegation={}", percent_encode(token, b"")));
+            }
+            _ => {}
+        }
+        for (key, value) in params {
+            url.push_str(&format!("&{}={}", key, percent_encode(value, b"")));
+        }
+        url
+    }
+
+    /// Sends the request to the name node, and follows the redirection to the data node if any.
+    /// The body is only sent to the data node, as WebHDFS requires.
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        op: &str,
+        params: &[(&str, String)],
+        body: Option<Vec<u8>>,
+    ) -> Result<Response> {
+        let url = self.url(path, op, params);
+        let mut request = self.http.request(method.clone(), &url);
+        if let HdfsCredential::Kerberos { principal, .. } = &self.credential {
+            // The name node is authenticated as the HTTP service of its host.
+            let token = negotiate(principal, endpoint_host(&self.endpoint))?;
+            request = request.header(AUTHORIZATION, format!("Negotiate {}", token));
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| transport_error(op, path, e))?;
+        if !response.status().is_redirection() {
+            return match body {
+                // The callers check the status, a missing file is not always an error.
+                None => Ok(response),
+                Some(_) => {
+                    check_response(op, path, response).await?;
+                    Err(ErrorCode::DALTransportError(format!(
+                        "Failed on hdfs {} operation of {}, the name node did not redirect to a data node",
+                        op, path
+                    )))
+                }
+            };
+        }
+
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .map(|location| location.to_string())
+            .ok_or_else(|| {
+                ErrorCode::DALTransportError(format!(
+                    "Failed on hdfs {} operation of {}, the redirection has no location",
+                    op, path
+                ))
+            })?;
+        let mut request = self.http.request(method, &location);
+        if let Some(body) = body {
+            request = request
+                .header(CONTENT_TYPE, "application/octet-stream")
+                .body(body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| transport_error(op, path, e))?;
+        check_response(op, path, response).await
+    }
+
+    async fn json(
+        &self,
+        method: Method,
+        path: &str,
+        op: &str,
+        params: &[(&str, String)],
+    ) -> Result<serde_json::Value> {
+        let response = self.request(method, path, op, params, None).await?;
+        let response = check_response(op, path, response).await?;
+        response
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| transport_error(op, path, e))
+    }
+
+    pub(crate) async fn read(&self, path: &str, offset: u64, length: Option<u64>) -> Result<Bytes> {
+        let mut params = vec![("offset", offset.to_string())];
+        if let Some(length) = length {
+            params.push(("length", length.to_string()));
+        }
+        let response = self
+            .request(Method::GET, path, "OPEN", &params, None)
+            .await?;
+        let response = check_response("OPEN", path, response).await?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| transport_error("OPEN", path, e))?;
+        Ok(bytes.to_vec())
+    }
+
+    pub(crate) async fn stat(&self, path: &str) -> Result<ObjectMeta> {
+        let status = self.json(Method::GET, path, "GETFILESTATUS", &[]).await?;
+        Ok(file_status_meta(path.to_string(), &status["FileStatus"]))
+    }
+
+    /// The statuses of the directory entries, or of the path itself if it is a file.
+    /// None if the path does not exist.
+    async fn list_status(&self, path: &str) -> Result<Option<Vec<serde_json::Value>>> {
+        let response = self
+            .request(Method::GET, path, "LISTSTATUS", &[], None)
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = check_response("LISTSTATUS", path, response).await?;
+        let mut body = response
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| transport_error("LISTSTATUS", path, e))?;
+        match body["FileStatuses"]["FileStatus"].take() {
+            serde_json::Value::Array(statuses) => Ok(Some(statuses)),
+            _ => Ok(Some(vec![])),
+        }
+    }
+}
+
+pub struct HdfsAccessor {
+    client: Arc<WebHdfsClient>,
+}
+
+impl HdfsAccessor {
+    /// Create a hdfs accessor of the `root` directory, through the WebHDFS endpoint of the
+    /// name node, e.g. `http://127.0.0.1:9870`.
+    ///
+    /// The keytab of the kerberos credential is set to `KRB5_CLIENT_KTNAME`, which is used by
+    /// the whole process.
+    pub fn with_credential(
+        endpoint: impl Into<String>,
+        root: impl Into<String>,
+        credential: &HdfsCredential,
+    ) -> Result<Self> {
+        let endpoint = endpoint.into().trim_end_matches('/').to_string();
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err(ErrorCode::InvalidConfig(format!(
+                "Invalid hdfs name node: {}, expect the http url of WebHDFS, e.g. http://127.0.0.1:9870",
+                endpoint
+            )));
+        }
+        let root = root.into();
+        let root = match root.trim_matches('/') {
+            "" => "".to_string(),
+            root => format!("/{}", root),
+        };
+
+        if let HdfsCredential::Kerberos { principal, keytab } = credential {
+            if principal.is_empty() || keytab.is_empty() {
+                return Err(ErrorCode::InvalidConfig(
+                    "Kerberos authentication of hdfs requires the principal and the keytab",
+                ));
+            }
+            if cfg!(not(feature = "hdfs-kerberos")) {
+                return Err(ErrorCode::InvalidConfig(
+                    "Kerberos authentication of hdfs is disabled, databend-query must be built with the hdfs-kerberos feature",
+                ));
+            }
+            std::env::set_var("KRB5_CLIENT_KTNAME", keytab);
+        }
+
+        // The redirections to the data nodes are followed by hand, to send the body only once.
+        let http = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| {
+                ErrorCode::InvalidConfig(format!("Failed to create the hdfs client, {}", e))
+            })?;
+
+        Ok(Self {
+            client: Arc::new(WebHdfsClient {
+                http,
+                endpoint,
+                root,
+                credential: credential.clone(),
+            }),
+        })
+    }
+
+    /// The size and the last modified time of a file.
+    pub async fn stat(&self, path: &str) -> Result<ObjectMeta> {
+        self.client.stat(path).await
+    }
+
+    #[cfg(test)]
+    pub(crate) fn client(&self) -> &WebHdfsClient {
+        &self.client
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for HdfsAccessor {
+    fn get_reader(&self, _path: &str, _len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        Err(ErrorCode::UnImplement(
+            "Blocking reader of hdfs is not supported, use the input stream instead",
+        ))
+    }
+
+    fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        Ok(Box::new(HdfsInputStream::create(
+            self.client.clone(),
+            path,
+            stream_len,
+        )))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        self.client.read(path, 0, None).await
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        // The missing parent directories are created by the name node.
+        let params = [("overwrite", "true".to_string())];
+        self.client
+            .request(Method::PUT, path, "CREATE", &params, Some(content))
+            .await?;
+        Ok(())
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>>
+                + Send
+                + Unpin
+                + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        let mut data: Vec<u8> = Vec::with_capacity(stream_len);
+        let mut s = Box::pin(input_stream);
+        while let Some(bytes) = s.next().await {
+            match bytes {
+                Err(e) => return Err(ErrorCode::DALTransportError(e.to_string())),
+                Ok(bytes) => data.extend_from_slice(&bytes),
+            }
+        }
+        self.put(path, data).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        let mut objects = vec![];
+        let mut dirs = vec![prefix.trim_end_matches('/').to_string()];
+        while let Some(dir) = dirs.pop() {
+            let statuses = match self.client.list_status(&dir).await? {
+                None => continue,
+                Some(statuses) => statuses,
+            };
+            for status in statuses {
+                // The suffix is empty if the path itself is a file.
+                let path = match status["pathSuffix"].as_str().unwrap_or_default() {
+                    "" => dir.clone(),
+                    suffix if dir.is_empty() => suffix.to_string(),
+                    suffix => format!("{}/{}", dir, suffix),
+                };
+                match status["type"].as_str() {
+                    Some("DIRECTORY") => dirs.push(path),
+                    _ => objects.push(file_status_meta(path, &status)),
+                }
+            }
+        }
+        objects.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(objects)
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        let params = [("recursive", "false".to_string())];
+        // Deleting a missing file returns false, which is not an error.
+        self.client
+            .json(Method::DELETE, path, "DELETE", &params)
+            .await?;
+        Ok(())
+    }
+}
+
+pub(crate) fn file_status_meta(path: String, status: &serde_json::Value) -> ObjectMeta {
+    ObjectMeta {
+        path,
+        size: status["length"].as_u64().unwrap_or_default(),
+        // In milliseconds.
+        last_modified: status["modificationTime"].as_u64().map(|ms| ms / 1000),
+        etag: None,
+    }
+}
+
+/// Converts the failed response to an error, with the message of the remote exception if any.
+pub(crate) async fn check_response(op: &str, path: &str, response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() || status.is_redirection() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    Err(remote_error(op, path, status, &body))
+}
+
+pub(crate) fn remote_error(op: &str, path: &str, status: StatusCode, body: &str) -> ErrorCode {
+    // e.g. {"RemoteException":{"exception":"FileNotFoundException","message":"..."}}
+    let (exception, message) = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(json) if json["RemoteException"].is_object() => {
+            let exception = &json["RemoteException"];
+            let name = exception["exception"].as_str().unwrap_or_default();
+            let message = exception["message"].as_str().unwrap_or_default();
+            (name.to_string(), format!("{}: {}", name, message))
+        }
+        _ => (String::new(), status.to_string()),
+    };
+    let message = format!("Failed on hdfs {} operation of {}, {}", op, path, message);
+
+    // The name node in standby or in safe mode asks the client to retry.
+    let transient = status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || exception == "RetriableException"
+        || exception == "StandbyException";
+    match transient {
+        true => ErrorCode::DALTransientError(message),
+        false => ErrorCode::DALTransportError(message),
+    }
+}
+
+#[cfg(feature = "hdfs-kerberos")]
+fn negotiate(principal: &str, host: &str) -> Result<String> {
+    use libgssapi::context::ClientCtx;
+    use libgssapi::context::CtxFlags;
+    use libgssapi::credential::Cred;
+    use libgssapi::credential::CredUsage;
+    use libgssapi::name::Name;
+    use libgssapi::oid::OidSet;
+    use libgssapi::oid::GSS_MECH_KRB5;
+    use libgssapi::oid::GSS_NT_HOSTBASED_SERVICE;
+    use libgssapi::oid::GSS_NT_KRB5_PRINCIPAL;
+
+    let to_error = |e: libgssapi::error::Error| {
+        ErrorCode::DALTransportError(format!(
+            "Failed on hdfs kerberos authentication of {}, {}",
+            principal, e
+        ))
+    };
+
+    let mut mechs = OidSet::new().map_err(to_error)?;
+    mechs.add(&GSS_MECH_KRB5).map_err(to_error)?;
+    let client = Name::new(principal.as_bytes(), Some(&GSS_NT_KRB5_PRINCIPAL)).map_err(to_error)?;
+    let cred =
+        Cred::acquire(Some(&client), None, CredUsage::Initiate, Some(&mechs)).map_err(to_error)?;
+    let service = format!("HTTP@{}", host);
+    let service =
+        Name::new(service.as_bytes(), Some(&GSS_NT_HOSTBASED_SERVICE)).map_err(to_error)?;
+
+    let mut ctx = ClientCtx::new(
+        cred,
+        service,
+        CtxFlags::GSS_C_MUTUAL_FLAG,
+        Some(&GSS_MECH_KRB5),
+    );
+    match ctx.step(None).map_err(to_error)? {
+        Some(token) => Ok(base64::encode(&*token)),
+        None => Err(ErrorCode::DALTransportError(format!(
+            "Failed on hdfs kerberos authentication of {}, no token is generated",
+            principal
+        ))),
+    }
+}
+
+#[cfg(not(feature = "hdfs-kerberos"))]
+fn negotiate(_principal: &str, _host: &str) -> Result<String> {
+    Err(ErrorCode::UnImplement(
+        "Kerberos authentication of hdfs is disabled, databend-query must be built with the hdfs-kerberos feature",
+    ))
+}
+
+fn transport_error(op: &str, path: &str, e: reqwest::Error) -> ErrorCode {
+    let message = format!("Failed on hdfs {} operation of {}, {}", op, path, e);
+    match e.is_timeout() || e.is_connect() {
+        true => ErrorCode::DALTransientError(message),
+        false => ErrorCode::DALTransportError(message),
+    }
+}
+
+/// The host of an url, e.g. `namenode` of `http://namenode:9870`.
+pub(crate) fn endpoint_host(endpoint: &str) -> &str {
+    let authority = endpoint.split("://").nth(1).unwrap_or(endpoint);
+    let authority = authority.split('/').next().unwrap_or_default();
+    authority.split(':').next().unwrap_or_default()
+}
+
+/// Percent-encodes all the bytes but the unreserved ones of RFC 3986 and the `keep` ones.
+pub(crate) fn percent_encode(s: &str, keep: &[u8]) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(b as char)
+            }
+            b if keep.contains(&b) => encoded.push(b as char),
+            b => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    encoded
+}
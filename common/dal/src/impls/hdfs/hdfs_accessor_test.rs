@@ -0,0 +1,144 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+use reqwest::StatusCode;
+
+use crate::impls::hdfs::hdfs_accessor::endpoint_host;
+use crate::impls::hdfs::hdfs_accessor::percent_encode;
+use crate::impls::hdfs::hdfs_accessor::remote_error;
+use crate::is_transient_error;
+use crate::DataAccessor;
+use crate::HdfsAccessor;
+use crate::HdfsCredential;
+
+#[test]
+fn test_hdfs_url() -> Result<()> {
+    let simple = HdfsCredential::Simple {
+        user: "hadoop".to_string(),
+    };
+    let accessor = HdfsAccessor::with_credential("http://nn:9870/", "/databend/", &simple)?;
+    assert_eq!(
+        accessor
+            .client()
+            .url("t1/year=2021/a b%.csv", "OPEN", &[("offset", "8".to_string())]),
+        "http://nn:9870/webhdfs/v1/databend/t1/year=2021/a%20b%25.csv?op=OPEN&user.name=hadoop&offset=8"
+    );
+
+    // The root directory of the file system.
+    let token = HdfsCredential::DelegationToken("HAAFaGRmcw+=".to_string());
+    let accessor = HdfsAccessor::with_credential("https://nn:9871", "", &token)?;
+    assert_eq!(
+        accessor.client().url("/_ss/1", "GETFILESTATUS", &[]),
+        "https://nn:9871/webhdfs/v1/_ss/1?op=GETFILESTATUS&delegation=HAAFaGRmcw%2B%3D"
+    );
+
+    // The anonymous user.
+    let anonymous = HdfsCredential::Simple {
+        user: "".to_string(),
+    };
+    let accessor = HdfsAccessor::with_credential("http://nn:9870", "data", &anonymous)?;
+    assert_eq!(
+        accessor.client().url("a", "DELETE", &[]),
+        "http://nn:9870/webhdfs/v1/data/a?op=DELETE"
+    );
+
+    // Not a WebHDFS endpoint.
+    let r = HdfsAccessor::with_credential("hdfs://nn:8020", "", &anonymous);
+    assert_eq!(r.err().unwrap().code(), ErrorCode::InvalidConfig("").code());
+
+    // The kerberos credential is incomplete.
+    let kerberos = HdfsCredential::Kerberos {
+        principal: "databend@EXAMPLE.COM".to_string(),
+        keytab: "".to_string(),
+    };
+    let r = HdfsAccessor::with_credential("http://nn:9870", "", &kerberos);
+    assert_eq!(r.err().unwrap().code(), ErrorCode::InvalidConfig("").code());
+
+    Ok(())
+}
+
+#[test]
+fn test_hdfs_helpers() {
+    assert_eq!(percent_encode("a/b=c d", b"/="), "a/b=c%20d");
+    assert_eq!(percent_encode("a/b=c", b""), "a%2Fb%3Dc");
+    assert_eq!(
+        endpoint_host("http://nn.example.com:9870"),
+        "nn.example.com"
+    );
+    assert_eq!(endpoint_host("https://nn/"), "nn");
+
+    let body = r#"{"RemoteException":{"exception":"FileNotFoundException","javaClassName":"java.io.FileNotFoundException","message":"File does not exist: /a"}}"#;
+    let e = remote_error("OPEN", "a", StatusCode::NOT_FOUND, body);
+    assert_eq!(
+        e.message(),
+        "Failed on hdfs OPEN operation of a, FileNotFoundException: File does not exist: /a"
+    );
+    assert!(!is_transient_error(&e));
+    let e = remote_error("OPEN", "a", StatusCode::BAD_GATEWAY, "<html></html>");
+    assert_eq!(
+        e.message(),
+        "Failed on hdfs OPEN operation of a, 502 Bad Gateway"
+    );
+    assert!(is_transient_error(&e));
+    let body = r#"{"RemoteException":{"exception":"StandbyException","message":"in standby"}}"#;
+    let e = remote_error("OPEN", "a", StatusCode::FORBIDDEN, body);
+    assert!(is_transient_error(&e));
+
+    // The secrets are not printed.
+    let token = HdfsCredential::DelegationToken("HAAFaGRmcw".to_string());
+    assert_eq!(format!("{:?}", token), "DelegationToken(******)");
+}
+
+//  Need a WebHDFS endpoint at http://127.0.0.1:9870 to run the test
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore]
+async fn test_hdfs_put_get_list_remove() -> Result<()> {
+    let credential = HdfsCredential::Simple {
+        user: "hadoop".to_string(),
+    };
+    let accessor =
+        HdfsAccessor::with_credential("http://127.0.0.1:9870", "/tmp/databend", &credential)?;
+    let data = b"0123456789abcdefghijklmnopqrstuvwxyz".to_vec();
+
+    accessor.put("t1/a.txt", data.clone()).await?;
+    accessor.put("t1/b/c.txt", data.clone()).await?;
+    assert_eq!(accessor.get("t1/a.txt").await?, data);
+    assert_eq!(accessor.stat("t1/a.txt").await?.size, data.len() as u64);
+
+    let mut stream = accessor.get_input_stream("t1/a.txt", None)?;
+    stream.seek(std::io::SeekFrom::Start(10)).await?;
+    let mut buf = vec![0u8; 6];
+    stream.read_exact(&mut buf).await?;
+    assert_eq!(buf, b"abcdef".to_vec());
+
+    let objects = accessor.list("t1/").await?;
+    let paths = objects.iter().map(|o| o.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["t1/a.txt", "t1/b/c.txt"]);
+    assert_eq!(accessor.list("t1/a.txt").await?.len(), 1);
+    assert!(accessor.list("t2/").await?.is_empty());
+
+    accessor.remove("t1/a.txt").await?;
+    // Removing a missing file is not an error.
+    accessor.remove("t1/a.txt").await?;
+    assert_eq!(accessor.list("t1/").await?.len(), 1);
+    accessor.remove("t1/b/c.txt").await?;
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::future::BoxFuture;
+use futures::ready;
+use futures::FutureExt;
+
+use crate::impls::hdfs::hdfs_accessor::WebHdfsClient;
+
+/// Reads a hdfs file by ranges, each read of the buffer is an OPEN request of WebHDFS.
+pub struct HdfsInputStream {
+    client: Arc<WebHdfsClient>,
+    path: String,
+    cursor: u64,
+    content_length: Option<u64>,
+    read_fut: Option<BoxFuture<'static, std::io::Result<Vec<u8>>>>,
+    stat_fut: Option<BoxFuture<'static, std::io::Result<u64>>>,
+}
+
+impl HdfsInputStream {
+    pub(crate) fn create(
+        client: Arc<WebHdfsClient>,
+        path: impl Into<String>,
+        content_length: Option<u64>,
+    ) -> Self {
+        Self {
+            client,
+            path: path.into(),
+            cursor: 0,
+            content_length,
+            read_fut: None,
+            stat_fut: None,
+        }
+    }
+
+    fn poll_content_length(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        if let Some(len) = self.content_length {
+            return Poll::Ready(Ok(len));
+        }
+
+        let client = self.client.clone();
+        let path = self.path.clone();
+        let fut = self.stat_fut.get_or_insert_with(|| {
+            async move {
+                let meta = client
+                    .stat(&path)
+                    .await
+                    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+                Ok(meta.size)
+            }
+            .boxed()
+        });
+        let res = ready!(fut.as_mut().poll(cx));
+        self.stat_fut = None;
+        if let Ok(len) = res {
+            self.content_length = Some(len);
+        }
+        Poll::Ready(res)
+    }
+}
+
+impl futures::AsyncRead for HdfsInputStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let len = ready!(this.poll_content_length(cx))?;
+        if this.cursor >= len || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let client = this.client.clone();
+        let path = this.path.clone();
+        let offset = this.cursor;
+        let length = (buf.len() as u64).min(len - offset);
+        let fut = this.read_fut.get_or_insert_with(|| {
+            async move {
+                client
+                    .read(&path, offset, Some(length))
+                    .await
+                    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+            }
+            .boxed()
+        });
+        let res = ready!(fut.as_mut().poll(cx));
+        this.read_fut = None;
+
+        let data = res?;
+        // The data node may return less than asked, never more.
+        let n = data.len().min(buf.len());
+        buf[..n].copy_from_slice(&data[..n]);
+        this.cursor += n as u64;
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl futures::AsyncSeek for HdfsInputStream {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => {
+                this.cursor = offset;
+                return Poll::Ready(Ok(offset));
+            }
+            SeekFrom::Current(offset) => (this.cursor, offset),
+            SeekFrom::End(offset) => (ready!(this.poll_content_length(cx))?, offset),
+        };
+
+        // Seeking beyond the end is allowed, but not before the start.
+        let cursor = match offset < 0 {
+            true => base.checked_sub(offset.unsigned_abs()),
+            false => base.checked_add(offset as u64),
+        };
+        match cursor {
+            Some(cursor) => {
+                this.cursor = cursor;
+                Poll::Ready(Ok(cursor))
+            }
+            None => Poll::Ready(Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Seeking {:?} is out of range of {}", pos, this.path),
+            ))),
+        }
+    }
+}
@@ -31,6 +31,7 @@ use tokio::io::AsyncWriteExt;
 use crate::Bytes;
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 use crate::SeekableReader;
 
 pub struct Local {
@@ -120,6 +121,71 @@ impl DataAccessor for Local {
         }
         Ok(())
     }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        let root = normalize_path(&self.root);
+        let path = self.prefix_with_root(prefix)?;
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut objects = vec![];
+        let mut dirs = vec![path];
+        while let Some(dir) = dirs.pop() {
+            if !dir.is_dir() {
+                // the prefix itself is a file
+                let metadata = tokio::fs::metadata(&dir).await?;
+                objects.push(ObjectMeta {
+                    path: relative_path(&root, &dir),
+                    size: metadata.len(),
+                    last_modified: modified_secs(&metadata),
+                    etag: None,
+                });
+                continue;
+            }
+
+            let mut entries = tokio::fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_dir() {
+                    dirs.push(entry.path());
+                } else {
+                    objects.push(ObjectMeta {
+                        path: relative_path(&root, &entry.path()),
+                        size: metadata.len(),
+                        last_modified: modified_secs(&metadata),
+                        etag: None,
+                    });
+                }
+            }
+        }
+        objects.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(objects)
+    }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        let path = self.prefix_with_root(path)?;
+        match tokio::fs::remove_file(path).await {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            res => Ok(res?),
+        }
+    }
+}
+
+fn modified_secs(metadata: &std::fs::Metadata) -> Option<u64> {
+    let modified = metadata.modified().ok()?;
+    let since_epoch = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(since_epoch.as_secs())
+}
+
+// paths returned by `list` are relative to the root, so that they can be fed back to `get`
+fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 // from cargo::util::path
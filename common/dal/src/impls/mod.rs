@@ -14,4 +14,5 @@
 
 pub mod aws_s3;
 pub mod azure_blob;
+pub mod hdfs;
 pub mod local;
@@ -13,12 +13,185 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
 use common_infallible::RwLock;
 
+/// Max bytes of the dictionary of a string column, the values over it are kept as they are.
+const MAX_DICTIONARY_BYTES: usize = 64 * 1024 * 1024;
+
+/// The string dictionaries of a memory table, by column name.
+pub type StringDictionaries = HashMap<String, StringDictionaryRef>;
+
+/// A block of a memory table written out to a local file.
+///
+/// The file is removed once the last reference to the block is gone.
+pub struct SpilledBlock {
+    pub path: PathBuf,
+    pub rows: usize,
+    pub bytes: usize,
+}
+
+impl Drop for SpilledBlock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[derive(Clone)]
+pub enum InMemoryColumn {
+    Plain(DataColumn),
+    Dictionary(DictionaryStringArray),
+}
+
+/// A block of a memory table held in memory.
+///
+/// The string columns of few distinct values are kept as codes of the dictionaries
+/// shared by all the blocks of the table, instead of a copy of the values per row.
+#[derive(Clone)]
+pub struct EncodedBlock {
+    schema: DataSchemaRef,
+    columns: Vec<InMemoryColumn>,
+    rows: usize,
+    data_size: usize,
+    memory_size: usize,
+}
+
+impl EncodedBlock {
+    pub fn encode(block: &DataBlock, dictionaries: &StringDictionaries) -> Result<EncodedBlock> {
+        let mut columns = Vec::with_capacity(block.num_columns());
+        let mut memory_size = 0;
+        for (field, column) in block.schema().fields().iter().zip(block.columns()) {
+            let dictionary = dictionaries.get(field.name());
+            let encoded = match (field.data_type(), column, dictionary) {
+                (DataType::String, DataColumn::Array(array), Some(dictionary)) => {
+                    DictionaryStringArray::try_encode(
+                        array.string()?,
+                        dictionary,
+                        MAX_DICTIONARY_BYTES,
+                    )
+                }
+                _ => None,
+            };
+
+            match encoded {
+                Some((array, grown)) => {
+                    memory_size += array.memory_size() + grown;
+                    columns.push(InMemoryColumn::Dictionary(array));
+                }
+                None => {
+                    memory_size += column.get_array_memory_size();
+                    columns.push(InMemoryColumn::Plain(column.clone()));
+                }
+            }
+        }
+
+        Ok(EncodedBlock {
+            schema: block.schema().clone(),
+            columns,
+            rows: block.num_rows(),
+            data_size: block.memory_size(),
+            memory_size,
+        })
+    }
+
+    pub fn decode(&self) -> DataBlock {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| match column {
+                InMemoryColumn::Plain(column) => column.clone(),
+                InMemoryColumn::Dictionary(array) => array.decode().into_series().into(),
+            })
+            .collect();
+        DataBlock::create(self.schema.clone(), columns)
+    }
+
+    pub fn columns(&self) -> &[InMemoryColumn] {
+        &self.columns
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Size of the block once decoded.
+    pub fn data_size(&self) -> usize {
+        self.data_size
+    }
+
+    /// Size of the codes and the other columns, with the bytes the dictionaries grew by
+    /// when the block was encoded.
+    pub fn memory_size(&self) -> usize {
+        self.memory_size
+    }
+}
+
+#[derive(Clone)]
+pub enum InMemoryBlock {
+    Memory(EncodedBlock),
+    Spilled(Arc<SpilledBlock>),
+}
+
+impl InMemoryBlock {
+    pub fn num_rows(&self) -> usize {
+        match self {
+            InMemoryBlock::Memory(block) => block.num_rows(),
+            InMemoryBlock::Spilled(block) => block.rows,
+        }
+    }
+
+    /// Size of the data once loaded in memory.
+    pub fn data_size(&self) -> usize {
+        match self {
+            InMemoryBlock::Memory(block) => block.data_size(),
+            InMemoryBlock::Spilled(block) => block.bytes,
+        }
+    }
+
+    /// Size of the memory held by the block, the spilled ones hold none.
+    pub fn memory_size(&self) -> usize {
+        match self {
+            InMemoryBlock::Memory(block) => block.memory_size(),
+            InMemoryBlock::Spilled(_) => 0,
+        }
+    }
+}
+
+/// Data of a memory table.
+#[derive(Default)]
+pub struct InMemoryTableData {
+    /// Readers take a snapshot by cloning the `Arc`, writers never change a snapshot in place.
+    pub blocks: Arc<Vec<InMemoryBlock>>,
+    /// Memory held by the blocks, including the bytes reserved by the ongoing inserts.
+    pub memory_bytes: usize,
+    /// Only grow until the table is truncated, the blocks keep the ones they refer to.
+    pub dictionaries: StringDictionaries,
+}
+
+impl InMemoryTableData {
+    /// The dictionaries of the string columns of the schema, created if they are missing.
+    pub fn dictionaries(&mut self, schema: &DataSchema) -> StringDictionaries {
+        schema
+            .fields()
+            .iter()
+            .filter(|field| field.data_type() == &DataType::String)
+            .map(|field| {
+                let dictionary = self
+                    .dictionaries
+                    .entry(field.name().clone())
+                    .or_insert_with(StringDictionary::create);
+                (field.name().clone(), dictionary.clone())
+            })
+            .collect()
+    }
+}
+
 /// Shared store to support memory tables.
 ///
 /// Indexed by table id etc.
-pub type InMemoryData<K> = HashMap<K, Arc<RwLock<Vec<DataBlock>>>>;
+pub type InMemoryData<K> = HashMap<K, Arc<RwLock<InMemoryTableData>>>;
@@ -0,0 +1,77 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::EncodedBlock;
+use crate::InMemoryColumn;
+use crate::InMemoryTableData;
+
+#[test]
+fn test_encoded_block() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", DataType::Int64, false),
+        DataField::new("city", DataType::String, true),
+        DataField::new("name", DataType::String, false),
+    ]);
+    let ids: Vec<i64> = (0..1000).collect();
+    let cities: Vec<Option<&str>> = (0..1000)
+        .map(|i| [None, Some("beijing"), Some("shanghai")][i % 3])
+        .collect();
+    let names: Vec<String> = (0..1000).map(|i| format!("name-{}", i)).collect();
+    let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(ids),
+        Series::new(cities),
+        Series::new(names),
+    ]);
+
+    let mut data = InMemoryTableData::default();
+    let dictionaries = data.dictionaries(&schema);
+    assert_eq!(dictionaries.len(), 2);
+
+    // Only the low cardinality column is encoded.
+    let encoded = EncodedBlock::encode(&block, &dictionaries)?;
+    let is_encoded: Vec<bool> = encoded
+        .columns()
+        .iter()
+        .map(|column| matches!(column, InMemoryColumn::Dictionary(_)))
+        .collect();
+    assert_eq!(is_encoded, vec![false, true, false]);
+    assert_eq!(encoded.num_rows(), 1000);
+    assert_eq!(encoded.data_size(), block.memory_size());
+    assert!(encoded.memory_size() < block.memory_size());
+
+    let decoded = encoded.decode();
+    assert_eq!(decoded.schema(), block.schema());
+    for (decoded, column) in decoded.columns().iter().zip(block.columns()) {
+        assert_eq!(decoded.to_values()?, column.to_values()?);
+    }
+
+    // The blocks of the table share the dictionaries.
+    let dictionaries = data.dictionaries(&schema);
+    let encoded = EncodedBlock::encode(&block, &dictionaries)?;
+    let dictionary = dictionaries.get("city").unwrap();
+    assert_eq!(dictionary.read().len(), 2);
+    let codes_size = block.columns()[0].get_array_memory_size()
+        + block.columns()[2].get_array_memory_size()
+        + match &encoded.columns()[1] {
+            InMemoryColumn::Dictionary(array) => array.memory_size(),
+            InMemoryColumn::Plain(_) => unreachable!(),
+        };
+    assert_eq!(encoded.memory_size(), codes_size);
+    Ok(())
+}
@@ -0,0 +1,117 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_base::tokio::sync::Notify;
+use common_base::tokio::sync::Semaphore;
+use common_base::tokio::sync::SemaphorePermit;
+
+/// Whom a storage request is made for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IOPriority {
+    /// The queries of the users.
+    Foreground,
+    /// The maintenance jobs, e.g. applying the storage policies of the tables.
+    Background,
+}
+
+/// Schedules the storage requests of all the queries and jobs of a node.
+///
+/// Foreground requests are never held back. Background requests are limited to
+/// `max_background_requests` at a time, and wait while there are foreground requests
+/// running, for at most `max_background_wait` so that they are not starved.
+pub struct IOScheduler {
+    max_background_wait: Duration,
+    background_permits: Semaphore,
+    foreground_requests: AtomicUsize,
+    foreground_idle: Notify,
+}
+
+/// The request is running until the permit is dropped.
+pub struct IOPermit<'a> {
+    scheduler: &'a IOScheduler,
+    priority: IOPriority,
+    _background_permit: Option<SemaphorePermit<'a>>,
+}
+
+impl IOScheduler {
+    pub fn create(max_background_requests: usize, max_background_wait: Duration) -> IOScheduler {
+        IOScheduler {
+            max_background_wait,
+            background_permits: Semaphore::new(max_background_requests.max(1)),
+            foreground_requests: AtomicUsize::new(0),
+            foreground_idle: Notify::new(),
+        }
+    }
+
+    pub async fn acquire(&self, priority: IOPriority) -> IOPermit<'_> {
+        let background_permit = match priority {
+            IOPriority::Foreground => {
+                self.foreground_requests.fetch_add(1, Ordering::SeqCst);
+                None
+            }
+            IOPriority::Background => {
+                // The semaphore is never closed.
+                let permit = self.background_permits.acquire().await.ok();
+                self.wait_foreground_idle().await;
+                permit
+            }
+        };
+
+        IOPermit {
+            scheduler: self,
+            priority,
+            _background_permit: background_permit,
+        }
+    }
+
+    pub fn foreground_requests(&self) -> usize {
+        self.foreground_requests.load(Ordering::SeqCst)
+    }
+
+    async fn wait_foreground_idle(&self) {
+        let deadline = Instant::now() + self.max_background_wait;
+        loop {
+            let idle = self.foreground_idle.notified();
+            if self.foreground_requests() == 0 {
+                return;
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return;
+            }
+            let _ = tokio::time::timeout(deadline - now, idle).await;
+        }
+    }
+}
+
+impl Drop for IOPermit<'_> {
+    fn drop(&mut self) {
+        if self.priority == IOPriority::Foreground
+            && self
+                .scheduler
+                .foreground_requests
+                .fetch_sub(1, Ordering::SeqCst)
+                == 1
+        {
+            self.scheduler.foreground_idle.notify_waiters();
+        }
+    }
+}
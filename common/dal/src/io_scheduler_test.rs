@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_exception::Result;
+
+use crate::DataAccessor;
+use crate::IOPriority;
+use crate::IOScheduler;
+use crate::Local;
+use crate::ScheduledAccessor;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_io_scheduler() -> Result<()> {
+    let scheduler = Arc::new(IOScheduler::create(1, Duration::from_secs(10)));
+
+    // Background requests wait for the foreground ones.
+    let foreground = scheduler.acquire(IOPriority::Foreground).await;
+    assert_eq!(scheduler.foreground_requests(), 1);
+    let waiting = {
+        let scheduler = scheduler.clone();
+        tokio::spawn(async move {
+            let start = Instant::now();
+            let _permit = scheduler.acquire(IOPriority::Background).await;
+            start.elapsed()
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    drop(foreground);
+    assert_eq!(scheduler.foreground_requests(), 0);
+    let waited = waiting.await.unwrap();
+    assert!(waited >= Duration::from_millis(50));
+    assert!(waited < Duration::from_secs(10));
+
+    // And are limited.
+    let background = scheduler.acquire(IOPriority::Background).await;
+    let r = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire(IOPriority::Background),
+    )
+    .await;
+    assert!(r.is_err());
+
+    // Foreground requests are never held back.
+    let r = tokio::time::timeout(
+        Duration::from_millis(50),
+        scheduler.acquire(IOPriority::Foreground),
+    )
+    .await;
+    assert!(r.is_ok());
+    drop(background);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_io_scheduler_background_not_starved() -> Result<()> {
+    let scheduler = IOScheduler::create(1, Duration::from_millis(50));
+
+    let _foreground = scheduler.acquire(IOPriority::Foreground).await;
+    let start = Instant::now();
+    let _background = scheduler.acquire(IOPriority::Background).await;
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scheduled_accessor() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_path(dir.path().to_owned()));
+    let scheduler = Arc::new(IOScheduler::create(1, Duration::from_secs(1)));
+    let dal = ScheduledAccessor::create(local, scheduler.clone(), IOPriority::Background);
+
+    dal.put("_b/a", b"data".to_vec()).await?;
+    assert_eq!(b"data".to_vec(), dal.read("_b/a").await?);
+    assert_eq!(dal.list("_b/").await?.len(), 1);
+
+    Ok(())
+}
@@ -18,19 +18,72 @@ pub use data_accessor::Bytes;
 pub use data_accessor::DataAccessor;
 pub use data_accessor::DataAccessorBuilder;
 pub use data_accessor::InputStream;
+pub use data_accessor::ObjectMeta;
 pub use data_accessor::SeekableReader;
+pub use encryption_accessor::decrypt_bytes;
+pub use encryption_accessor::encrypt_bytes;
+pub use encryption_accessor::encrypted_len;
+pub use encryption_accessor::is_encrypted;
+pub use encryption_accessor::CommandKeyProvider;
+pub use encryption_accessor::EncryptionAccessor;
+pub use encryption_accessor::EncryptionKey;
+pub use encryption_accessor::KeyProvider;
+pub use encryption_accessor::StaticKeyProvider;
+pub use faulty_accessor::FaultyAccessor;
 pub use impls::aws_s3::S3InputStream;
 pub use impls::aws_s3::S3;
 pub use impls::azure_blob::AzureBlobAccessor;
+pub use impls::azure_blob::AzureBlobCredential;
 pub use impls::azure_blob::AzureBlobInputStream;
+pub use impls::hdfs::HdfsAccessor;
+pub use impls::hdfs::HdfsCredential;
+pub use impls::hdfs::HdfsInputStream;
 pub use impls::local::Local;
+pub use in_memory_data::EncodedBlock;
+pub use in_memory_data::InMemoryBlock;
+pub use in_memory_data::InMemoryColumn;
 pub use in_memory_data::InMemoryData;
+pub use in_memory_data::InMemoryTableData;
+pub use in_memory_data::SpilledBlock;
+pub use in_memory_data::StringDictionaries;
+pub use io_scheduler::IOPermit;
+pub use io_scheduler::IOPriority;
+pub use io_scheduler::IOScheduler;
+pub use retry_accessor::is_transient_error;
+pub use retry_accessor::RetryAccessor;
+pub use retry_accessor::RetryPolicy;
+pub use scheduled_accessor::ScheduledAccessor;
 pub use schemes::StorageScheme;
+pub use throttle_accessor::Throttle;
+pub use throttle_accessor::ThrottleAccessor;
+pub use throttle_accessor::ThrottleLimits;
+pub use tiered_accessor::TieredAccessor;
 
 mod data_accessor;
+mod encryption_accessor;
+mod faulty_accessor;
 mod impls;
 mod in_memory_data;
+mod io_scheduler;
+mod retry_accessor;
+mod scheduled_accessor;
 mod schemes;
+mod throttle_accessor;
+mod tiered_accessor;
 
+#[cfg(test)]
+mod encryption_accessor_test;
+#[cfg(test)]
+mod faulty_accessor_test;
+#[cfg(test)]
+mod in_memory_data_test;
+#[cfg(test)]
+mod io_scheduler_test;
+#[cfg(test)]
+mod retry_accessor_test;
 #[cfg(test)]
 mod schemes_test;
+#[cfg(test)]
+mod throttle_accessor_test;
+#[cfg(test)]
+mod tiered_accessor_test;
@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::stream::Stream;
+use metrics::counter;
+use rand::Rng;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+pub static METRIC_DAL_RETRIES: &str = "dal.retries";
+
+/// How `RetryAccessor` retries a request failed with a transient error.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// The retries after the first attempt, 0 to never retry.
+    pub max_retries: u32,
+    /// The backoff before the first retry, doubled before each of the next ones.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// The backoff before the `retry`-th (0-based) retry, a random duration between the half
+    /// and the whole of the exponential backoff, so that the failed requests don't come back
+    /// all at once.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .checked_mul(1u32 << retry.min(31))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        let half = backoff / 2;
+        half + half.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+}
+
+/// The errors which may go away by themselves: the timeouts, the lost connections and the
+/// errors of the overloaded or failing servers.
+pub fn is_transient_error(error: &ErrorCode) -> bool {
+    error.code() == ErrorCode::DALTransientError("").code()
+        || error.code() == ErrorCode::Timeout("").code()
+}
+
+/// A `DataAccessor` which retries the requests failed with transient errors, after an
+/// exponential backoff with jitter.
+///
+/// Only the idempotent requests are retried: the reads, `put` which writes the whole object
+/// again and `remove` of which a repeat succeeds. `put_stream` consumes its input,
+/// it is never retried. The sync methods(`get_reader`, `get_input_stream`) and the reads from
+/// the returned readers are not retried either.
+pub struct RetryAccessor {
+    inner: Arc<dyn DataAccessor>,
+    policy: RetryPolicy,
+}
+
+impl RetryAccessor {
+    pub fn create(inner: Arc<dyn DataAccessor>, policy: RetryPolicy) -> RetryAccessor {
+        RetryAccessor { inner, policy }
+    }
+
+    async fn retry<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retry = 0;
+        loop {
+            match request().await {
+                Err(e) if retry < self.policy.max_retries && is_transient_error(&e) => {
+                    counter!(METRIC_DAL_RETRIES, 1);
+                    tokio::time::sleep(self.policy.backoff(retry)).await;
+                    retry += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for RetryAccessor {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        self.inner.get_reader(path, len)
+    }
+
+    fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        self.inner.get_input_stream(path, stream_len)
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        self.retry(|| self.inner.get(path)).await
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        self.retry(|| self.inner.put(path, content.clone())).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>>
+                + Send
+                + Unpin
+                + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        self.inner.put_stream(path, input_stream, stream_len).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        self.retry(|| self.inner.list(prefix)).await
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        self.retry(|| self.inner.remove(path)).await
+    }
+
+    async fn read(&self, location: &str) -> Result<Vec<u8>> {
+        self.retry(|| self.inner.read(location)).await
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::Fault;
+use common_base::FaultInjector;
+use common_base::FaultRates;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::stream;
+
+use crate::DataAccessor;
+use crate::FaultyAccessor;
+use crate::Local;
+use crate::RetryAccessor;
+use crate::RetryPolicy;
+
+fn policy(max_retries: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_retries,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(4),
+    }
+}
+
+#[test]
+fn test_retry_policy_backoff() {
+    let policy = RetryPolicy {
+        max_retries: 10,
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(1000),
+    };
+    for (retry, expect) in [
+        (0, 100),
+        (1, 200),
+        (2, 400),
+        (3, 800),
+        (4, 1000),
+        (40, 1000),
+    ] {
+        let backoff = policy.backoff(retry);
+        let expect = Duration::from_millis(expect);
+        assert!(backoff >= expect / 2 && backoff <= expect, "{}", retry);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_retry_accessor() -> Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_path(tmp_dir.path().to_owned()));
+    let injector = FaultInjector::create(FaultRates::default());
+    let faulty = Arc::new(FaultyAccessor::create(local.clone(), injector.clone()));
+    let dal = RetryAccessor::create(faulty, policy(2));
+
+    // The transient errors are retried, the writes which can be repeated too.
+    injector.inject("put", Fault::LostResponse);
+    dal.put("a", b"hello".to_vec()).await?;
+    injector.inject("get", Fault::LostResponse);
+    injector.inject("get", Fault::LostResponse);
+    assert_eq!(b"hello".to_vec(), dal.get("a").await?);
+    injector.inject("list", Fault::LostResponse);
+    assert_eq!(1, dal.list("").await?.len());
+    injector.inject("read", Fault::LostResponse);
+    assert_eq!(b"hello".to_vec(), dal.read("a").await?);
+    injector.inject("remove", Fault::LostResponse);
+    dal.remove("a").await?;
+    assert!(local.get("a").await.is_err());
+
+    // Out of retries.
+    for _ in 0..3 {
+        injector.inject("get", Fault::LostResponse);
+    }
+    let res = dal.get("a").await;
+    assert_eq!(ErrorCode::Timeout("").code(), res.unwrap_err().code());
+
+    // The other errors are not retried.
+    injector.inject("get", Fault::Error);
+    let res = dal.get("a").await;
+    assert_eq!(
+        ErrorCode::DALTransportError("").code(),
+        res.unwrap_err().code()
+    );
+
+    // The input stream of put_stream is consumed by the failed attempt.
+    injector.inject("put_stream", Fault::LostResponse);
+    let input = stream::iter(vec![Ok::<_, std::io::Error>(bytes::Bytes::from_static(
+        b"hi",
+    ))]);
+    let res = dal.put_stream("b", Box::new(input), 2).await;
+    assert_eq!(ErrorCode::Timeout("").code(), res.unwrap_err().code());
+
+    assert_eq!(injector.injected().len(), 11);
+    Ok(())
+}
@@ -0,0 +1,101 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use futures::stream::Stream;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::IOPriority;
+use crate::IOScheduler;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+/// A `DataAccessor` whose requests are scheduled by an `IOScheduler` with the given priority.
+///
+/// The sync methods(`get_reader`, `get_input_stream`) only open the object,
+/// the reads from the returned readers are not scheduled.
+pub struct ScheduledAccessor {
+    inner: Arc<dyn DataAccessor>,
+    scheduler: Arc<IOScheduler>,
+    priority: IOPriority,
+}
+
+impl ScheduledAccessor {
+    pub fn create(
+        inner: Arc<dyn DataAccessor>,
+        scheduler: Arc<IOScheduler>,
+        priority: IOPriority,
+    ) -> ScheduledAccessor {
+        ScheduledAccessor {
+            inner,
+            scheduler,
+            priority,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for ScheduledAccessor {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        self.inner.get_reader(path, len)
+    }
+
+    fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        self.inner.get_input_stream(path, stream_len)
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let _permit = self.scheduler.acquire(self.priority).await;
+        self.inner.get(path).await
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        let _permit = self.scheduler.acquire(self.priority).await;
+        self.inner.put(path, content).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>>
+                + Send
+                + Unpin
+                + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        let _permit = self.scheduler.acquire(self.priority).await;
+        self.inner.put_stream(path, input_stream, stream_len).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        let _permit = self.scheduler.acquire(self.priority).await;
+        self.inner.list(prefix).await
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        let _permit = self.scheduler.acquire(self.priority).await;
+        self.inner.remove(path).await
+    }
+
+    async fn read(&self, location: &str) -> Result<Vec<u8>> {
+        let _permit = self.scheduler.acquire(self.priority).await;
+        self.inner.read(location).await
+    }
+}
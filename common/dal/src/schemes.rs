@@ -17,6 +17,8 @@ use std::str::FromStr;
 
 use common_exception::ErrorCode;
 
+use self::StorageScheme::AzureBlob;
+use self::StorageScheme::Hdfs;
 use self::StorageScheme::LocalFs;
 use self::StorageScheme::S3;
 
@@ -24,6 +26,8 @@ use self::StorageScheme::S3;
 pub enum StorageScheme {
     LocalFs,
     S3,
+    AzureBlob,
+    Hdfs,
 }
 
 impl FromStr for StorageScheme {
@@ -32,9 +36,11 @@ impl FromStr for StorageScheme {
         let s = s.to_uppercase();
         match s.as_str() {
             "S3" => Ok(S3),
+            "AZBLOB" | "AZUREBLOB" => Ok(AzureBlob),
+            "HDFS" => Ok(Hdfs),
             "LOCAL" | "DISK" => Ok(LocalFs),
             _ => Err(ErrorCode::UnknownStorageSchemeName(format!(
-                "unknown storage scheme [{}], supported schemes are S3 | Disk",
+                "unknown storage scheme [{}], supported schemes are S3 | AzBlob | Hdfs | Disk",
                 s
             ))),
         }
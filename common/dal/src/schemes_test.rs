@@ -17,6 +17,8 @@ use std::str::FromStr;
 
 use common_exception::ErrorCode;
 
+use crate::schemes::StorageScheme::AzureBlob;
+use crate::schemes::StorageScheme::Hdfs;
 use crate::schemes::StorageScheme::LocalFs;
 use crate::schemes::StorageScheme::S3;
 use crate::StorageScheme;
@@ -26,6 +28,10 @@ fn test_scheme_from_str() {
     let valid_schemes = vec![
         ("s3", S3),
         ("S3", S3),
+        ("azblob", AzureBlob),
+        ("AzureBlob", AzureBlob),
+        ("hdfs", Hdfs),
+        ("HDFS", Hdfs),
         ("local", LocalFs),
         ("LOCAL", LocalFs),
         ("Disk", LocalFs),
@@ -0,0 +1,254 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_base::tokio::time::Sleep;
+use common_exception::Result;
+use common_infallible::Mutex;
+use futures::ready;
+use futures::stream::Stream;
+use futures::AsyncRead;
+use futures::AsyncSeek;
+use futures::Future;
+use metrics::counter;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+pub static METRIC_DAL_THROTTLED_MS: &str = "dal.throttled_ms";
+
+/// The throughput a `Throttle` allows, 0 means unlimited.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ThrottleLimits {
+    /// The bytes read and written a second.
+    pub bytes_per_second: u64,
+    pub requests_per_second: u64,
+}
+
+/// A token bucket refilled by `rate` tokens a second, which holds up to a second of them.
+///
+/// A taker may take more tokens than the bucket holds, it waits for the missing ones to be
+/// refilled, and so do the takers after it.
+struct RateLimiter {
+    rate: f64,
+    /// The tokens, negative if they are owed, and when they were counted.
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn create(rate: u64) -> Option<RateLimiter> {
+        match rate {
+            0 => None,
+            _ => Some(RateLimiter {
+                rate: rate as f64,
+                state: Mutex::new((rate as f64, Instant::now())),
+            }),
+        }
+    }
+
+    /// Takes the tokens, returns how long to wait before using them.
+    fn take(&self, tokens: u64) -> Duration {
+        let mut state = self.state.lock();
+        let now = Instant::now();
+        let refilled = now.duration_since(state.1).as_secs_f64() * self.rate;
+        state.0 = (state.0 + refilled).min(self.rate) - tokens as f64;
+        state.1 = now;
+
+        match state.0 < 0.0 {
+            true => Duration::from_secs_f64(-state.0 / self.rate),
+            false => Duration::ZERO,
+        }
+    }
+}
+
+/// Caps the throughput of the `ThrottleAccessor`s sharing it, e.g. the ones of a query or
+/// of the whole node.
+pub struct Throttle {
+    bytes: Option<RateLimiter>,
+    requests: Option<RateLimiter>,
+}
+
+impl Throttle {
+    pub fn create(limits: ThrottleLimits) -> Arc<Throttle> {
+        Arc::new(Throttle {
+            bytes: RateLimiter::create(limits.bytes_per_second),
+            requests: RateLimiter::create(limits.requests_per_second),
+        })
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.bytes.is_none() && self.requests.is_none()
+    }
+
+    /// Takes the requests and the bytes, returns how long to wait before sending them,
+    /// which includes what the previous takers still owe.
+    pub fn take(&self, requests: u64, bytes: u64) -> Duration {
+        let mut wait = Duration::ZERO;
+        if let Some(limiter) = &self.requests {
+            wait = wait.max(limiter.take(requests));
+        }
+        if let Some(limiter) = &self.bytes {
+            wait = wait.max(limiter.take(bytes));
+        }
+        wait
+    }
+
+    async fn wait(&self, requests: u64, bytes: u64) {
+        if let Some(delay) = delay(self.take(requests, bytes)) {
+            delay.await;
+        }
+    }
+}
+
+fn delay(wait: Duration) -> Option<Pin<Box<Sleep>>> {
+    if wait.is_zero() {
+        return None;
+    }
+    counter!(METRIC_DAL_THROTTLED_MS, wait.as_millis() as u64);
+    Some(Box::pin(tokio::time::sleep(wait)))
+}
+
+/// A `DataAccessor` whose requests and bytes are capped by a `Throttle`.
+///
+/// The bytes to write are taken before the request, the bytes read are only known after it,
+/// so they delay the next requests. The reads from the input streams are throttled as well,
+/// the sync reader of `get_reader` is not.
+pub struct ThrottleAccessor {
+    inner: Arc<dyn DataAccessor>,
+    throttle: Arc<Throttle>,
+}
+
+impl ThrottleAccessor {
+    pub fn create(inner: Arc<dyn DataAccessor>, throttle: Arc<Throttle>) -> ThrottleAccessor {
+        ThrottleAccessor { inner, throttle }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for ThrottleAccessor {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        self.inner.get_reader(path, len)
+    }
+
+    fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        let inner = self.inner.get_input_stream(path, stream_len)?;
+        Ok(Box::new(ThrottledInputStream {
+            inner,
+            throttle: self.throttle.clone(),
+            // The stream is opened synchronously, the first read waits for the request.
+            delay: delay(self.throttle.take(1, 0)),
+        }))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        self.throttle.wait(1, 0).await;
+        let bytes = self.inner.get(path).await?;
+        self.throttle.wait(0, bytes.len() as u64).await;
+        Ok(bytes)
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        self.throttle.wait(1, content.len() as u64).await;
+        self.inner.put(path, content).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>>
+                + Send
+                + Unpin
+                + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        self.throttle.wait(1, stream_len as u64).await;
+        self.inner.put_stream(path, input_stream, stream_len).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        self.throttle.wait(1, 0).await;
+        self.inner.list(prefix).await
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        self.throttle.wait(1, 0).await;
+        self.inner.remove(path).await
+    }
+
+    async fn read(&self, location: &str) -> Result<Vec<u8>> {
+        self.throttle.wait(1, 0).await;
+        let bytes = self.inner.read(location).await?;
+        self.throttle.wait(0, bytes.len() as u64).await;
+        Ok(bytes)
+    }
+}
+
+/// Waits for the bytes of a read before the next one, and for a request after a seek, which
+/// makes the next read a new ranged request to the storage.
+struct ThrottledInputStream {
+    inner: InputStream,
+    throttle: Arc<Throttle>,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl ThrottledInputStream {
+    fn poll_delay(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if let Some(delay) = &mut self.delay {
+            ready!(delay.as_mut().poll(cx));
+            self.delay = None;
+        }
+        Poll::Ready(())
+    }
+}
+
+impl AsyncRead for ThrottledInputStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_delay(cx));
+        let n = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.delay = delay(this.throttle.take(0, n as u64));
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncSeek for ThrottledInputStream {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        ready!(this.poll_delay(cx));
+        let offset = ready!(Pin::new(&mut this.inner).poll_seek(cx, pos))?;
+        this.delay = delay(this.throttle.take(1, 0));
+        Poll::Ready(Ok(offset))
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::AsyncReadExt;
+
+use crate::DataAccessor;
+use crate::Local;
+use crate::Throttle;
+use crate::ThrottleAccessor;
+use crate::ThrottleLimits;
+
+#[test]
+fn test_throttle() {
+    let throttle = Throttle::create(ThrottleLimits::default());
+    assert!(throttle.is_unlimited());
+    assert_eq!(throttle.take(1000, 1 << 30), Duration::ZERO);
+
+    // A second of requests is taken at once, the next one waits for a refill.
+    let throttle = Throttle::create(ThrottleLimits {
+        bytes_per_second: 0,
+        requests_per_second: 10,
+    });
+    for _ in 0..10 {
+        assert_eq!(throttle.take(1, 0), Duration::ZERO);
+    }
+    let wait = throttle.take(1, 0);
+    assert!(wait > Duration::ZERO && wait <= Duration::from_millis(100));
+
+    // The bytes over the bucket are owed by the next takers too.
+    let throttle = Throttle::create(ThrottleLimits {
+        bytes_per_second: 1000,
+        requests_per_second: 0,
+    });
+    let wait = throttle.take(1, 3000);
+    assert!(wait > Duration::from_millis(1900) && wait <= Duration::from_secs(2));
+    assert!(throttle.take(1, 0) >= wait - Duration::from_millis(100));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_throttle_accessor() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_path(dir.path().to_owned()));
+    let throttle = Throttle::create(ThrottleLimits {
+        bytes_per_second: 4096,
+        requests_per_second: 0,
+    });
+    let accessor = ThrottleAccessor::create(local, throttle);
+
+    // Within the bucket.
+    let start = Instant::now();
+    accessor.put("a", vec![1; 4096]).await?;
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    // The stream waits for the bytes it read, a second of them.
+    let start = Instant::now();
+    let mut stream = accessor.get_input_stream("a", None)?;
+    let mut buf = vec![];
+    stream.read_to_end(&mut buf).await?;
+    assert_eq!(buf, vec![1; 4096]);
+    assert!(start.elapsed() >= Duration::from_millis(800));
+    Ok(())
+}
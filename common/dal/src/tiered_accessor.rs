@@ -0,0 +1,114 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use futures::stream::Stream;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+/// A `DataAccessor` over a hot and a cold tier of storage.
+///
+/// The objects whose path starts with `cold_prefix` live in the cold tier, the others in the hot
+/// tier, so that the callers read and write the objects of both tiers as if they were in one storage.
+pub struct TieredAccessor {
+    hot: Arc<dyn DataAccessor>,
+    cold: Arc<dyn DataAccessor>,
+    cold_prefix: String,
+}
+
+impl TieredAccessor {
+    pub fn create(
+        hot: Arc<dyn DataAccessor>,
+        cold: Arc<dyn DataAccessor>,
+        cold_prefix: impl Into<String>,
+    ) -> TieredAccessor {
+        TieredAccessor {
+            hot,
+            cold,
+            cold_prefix: cold_prefix.into(),
+        }
+    }
+
+    fn tier(&self, path: &str) -> &Arc<dyn DataAccessor> {
+        match path.starts_with(&self.cold_prefix) {
+            true => &self.cold,
+            false => &self.hot,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for TieredAccessor {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        self.tier(path).get_reader(path, len)
+    }
+
+    fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        self.tier(path).get_input_stream(path, stream_len)
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        self.tier(path).get(path).await
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        self.tier(path).put(path, content).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<bytes::Bytes, std::io::Error>>
+                + Send
+                + Unpin
+                + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        self.tier(path)
+            .put_stream(path, input_stream, stream_len)
+            .await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        // The prefix covers both tiers, e.g. an empty prefix.
+        if self.cold_prefix.starts_with(prefix) {
+            let mut objects = self.hot.list(prefix).await?;
+            let cold_objects = self.cold.list(prefix).await?;
+            objects.extend(
+                cold_objects
+                    .into_iter()
+                    .filter(|object| object.path.starts_with(&self.cold_prefix)),
+            );
+            return Ok(objects);
+        }
+
+        self.tier(prefix).list(prefix).await
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        self.tier(path).remove(path).await
+    }
+
+    async fn read(&self, location: &str) -> Result<Vec<u8>> {
+        self.tier(location).read(location).await
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+
+use crate::DataAccessor;
+use crate::Local;
+use crate::TieredAccessor;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tiered_accessor() -> Result<()> {
+    let hot_dir = tempfile::tempdir()?;
+    let cold_dir = tempfile::tempdir()?;
+    let hot: Arc<dyn DataAccessor> = Arc::new(Local::with_path(hot_dir.path().to_owned()));
+    let cold: Arc<dyn DataAccessor> = Arc::new(Local::with_path(cold_dir.path().to_owned()));
+    let dal = TieredAccessor::create(hot.clone(), cold.clone(), "_cold/");
+
+    dal.put("_b/a", b"hot".to_vec()).await?;
+    dal.put("_cold/_b/a", b"cold".to_vec()).await?;
+
+    // Each object lives in its own tier.
+    assert_eq!(b"hot".to_vec(), hot.get("_b/a").await?);
+    assert!(hot.get("_cold/_b/a").await.is_err());
+    assert_eq!(b"cold".to_vec(), cold.get("_cold/_b/a").await?);
+    assert!(cold.get("_b/a").await.is_err());
+
+    // And is read transparently.
+    assert_eq!(b"hot".to_vec(), dal.read("_b/a").await?);
+    assert_eq!(b"cold".to_vec(), dal.read("_cold/_b/a").await?);
+
+    // Objects of the cold storage out of the cold prefix are not part of the tiers.
+    cold.put("other", b"other".to_vec()).await?;
+
+    let mut paths = dal
+        .list("")
+        .await?
+        .into_iter()
+        .map(|object| object.path)
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert_eq!(paths, vec!["_b/a".to_string(), "_cold/_b/a".to_string()]);
+
+    let paths = dal
+        .list("_cold/")
+        .await?
+        .into_iter()
+        .map(|object| object.path)
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["_cold/_b/a".to_string()]);
+
+    // Removed from its own tier, removing it again is not an error.
+    dal.remove("_cold/_b/a").await?;
+    assert!(cold.get("_cold/_b/a").await.is_err());
+    assert_eq!(b"hot".to_vec(), hot.get("_b/a").await?);
+    dal.remove("_cold/_b/a").await?;
+
+    Ok(())
+}
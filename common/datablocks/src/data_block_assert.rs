@@ -0,0 +1,159 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::DataBlock;
+
+/// Builds a `DataBlock` column by column, for test fixtures.
+///
+/// The data type of each field is taken from its values, and the field is
+/// nullable if any of its values is null.
+#[derive(Default)]
+pub struct DataBlockFixture {
+    fields: Vec<DataField>,
+    columns: Vec<Series>,
+}
+
+impl DataBlockFixture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn column<T, P: ?Sized>(mut self, name: &str, values: T) -> Self
+    where Series: SeriesFrom<T, P> {
+        let series = Series::new(values);
+        let nullable = series.null_count() > 0;
+        self.fields
+            .push(DataField::new(name, series.data_type().clone(), nullable));
+        self.columns.push(series);
+        self
+    }
+
+    pub fn build(self) -> DataBlock {
+        DataBlock::create_by_array(DataSchemaRefExt::create(self.fields), self.columns)
+    }
+}
+
+/// Formats a value along with its type, strings are quoted so that `'1'` and `1` differ.
+pub fn format_typed_value(value: &DataValue) -> String {
+    match value {
+        v if v.is_null() => "NULL".to_string(),
+        DataValue::String(Some(v)) => match std::str::from_utf8(v) {
+            Ok(v) => format!("'{}'", v.replace('\'', "\\'")),
+            Err(_) => format!(
+                "x'{}'",
+                v.iter().map(|c| format!("{:02x}", c)).collect::<String>()
+            ),
+        },
+        DataValue::Float32(Some(v)) => format!("{:?}", v),
+        DataValue::Float64(Some(v)) => format!("{:?}", v),
+        v => format!("{}", v),
+    }
+}
+
+/// Formats each row of the blocks as `(v1, v2, ...)`, in the order of the blocks.
+pub fn format_block_rows(blocks: &[DataBlock]) -> Result<Vec<String>> {
+    let mut rows = vec![];
+    for block in blocks {
+        let columns = block
+            .columns()
+            .iter()
+            .map(|c| c.to_array())
+            .collect::<Result<Vec<_>>>()?;
+
+        for row in 0..block.num_rows() {
+            let values = columns
+                .iter()
+                .map(|c| c.try_get(row).map(|v| format_typed_value(&v)))
+                .collect::<Result<Vec<_>>>()?;
+            rows.push(format!("({})", values.join(", ")));
+        }
+    }
+    Ok(rows)
+}
+
+fn format_schema(blocks: &[DataBlock]) -> Option<String> {
+    blocks.first().map(|block| {
+        let fields = block
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| format!("{}: {}", f.name(), f.data_type()))
+            .collect::<Vec<_>>();
+        format!("({})", fields.join(", "))
+    })
+}
+
+/// Compares the rows of the blocks regardless of their order and of the block boundaries.
+///
+/// Returns `None` if the blocks hold the same rows, otherwise a row level diff:
+/// the rows missing from `actual` prefixed by `-` and the unexpected ones by `+`.
+pub fn diff_blocks_unordered(
+    expected: &[DataBlock],
+    actual: &[DataBlock],
+) -> Result<Option<String>> {
+    let mut diff = vec![];
+
+    if let (Some(expected_schema), Some(actual_schema)) =
+        (format_schema(expected), format_schema(actual))
+    {
+        if expected_schema != actual_schema {
+            diff.push(format!("- schema {}", expected_schema));
+            diff.push(format!("+ schema {}", actual_schema));
+        }
+    }
+
+    // row => number of occurrences in expected minus number of occurrences in actual
+    let mut rows = BTreeMap::<String, i64>::new();
+    for row in format_block_rows(expected)? {
+        *rows.entry(row).or_default() += 1;
+    }
+    for row in format_block_rows(actual)? {
+        *rows.entry(row).or_default() -= 1;
+    }
+
+    for (row, count) in rows {
+        let sign = if count > 0 { "-" } else { "+" };
+        for _ in 0..count.abs() {
+            diff.push(format!("{} {}", sign, row));
+        }
+    }
+
+    match diff.is_empty() {
+        true => Ok(None),
+        false => Ok(Some(diff.join("\n"))),
+    }
+}
+
+/// Assert with order insensitive, comparing the values instead of their pretty printed table.
+pub fn assert_blocks_unordered_eq(expected: &[DataBlock], actual: &[DataBlock]) {
+    assert_blocks_unordered_eq_with_name("", expected, actual)
+}
+
+pub fn assert_blocks_unordered_eq_with_name(
+    test_name: &str,
+    expected: &[DataBlock],
+    actual: &[DataBlock],
+) {
+    if let Some(diff) = diff_blocks_unordered(expected, actual).unwrap() {
+        panic!(
+            "{:#?}\n\nblocks are not equal (-expected +actual):\n\n{}\n\n",
+            test_name, diff
+        );
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::*;
+
+#[test]
+fn test_data_block_fixture() -> Result<()> {
+    let block = DataBlockFixture::new()
+        .column("a", vec![1u64, 2])
+        .column("b", vec![Some("x"), None])
+        .build();
+
+    assert_eq!(2, block.num_rows());
+    assert_eq!(
+        &DataField::new("a", DataType::UInt64, false),
+        block.schema().field(0)
+    );
+    assert_eq!(
+        &DataField::new("b", DataType::String, true),
+        block.schema().field(1)
+    );
+    assert_eq!(vec!["(1, 'x')", "(2, NULL)"], format_block_rows(&[block])?);
+    Ok(())
+}
+
+#[test]
+fn test_diff_blocks_unordered() -> Result<()> {
+    let expected = DataBlockFixture::new()
+        .column("a", vec![1u64, 2, 2])
+        .column("b", vec!["x", "y", "y"])
+        .build();
+
+    // same rows, in another order and split into several blocks
+    let actual = vec![
+        DataBlockFixture::new()
+            .column("a", vec![2u64, 1])
+            .column("b", vec!["y", "x"])
+            .build(),
+        DataBlockFixture::new()
+            .column("a", vec![2u64])
+            .column("b", vec!["y"])
+            .build(),
+    ];
+    assert_eq!(None, diff_blocks_unordered(&[expected.clone()], &actual)?);
+    assert_blocks_unordered_eq(&[expected.clone()], &actual);
+
+    // duplicated rows are counted
+    let actual = DataBlockFixture::new()
+        .column("a", vec![1u64, 2, 3])
+        .column("b", vec!["x", "y", "z"])
+        .build();
+    assert_eq!(
+        Some("- (2, 'y')\n+ (3, 'z')".to_string()),
+        diff_blocks_unordered(&[expected.clone()], &[actual])?
+    );
+
+    // types are compared as well
+    let actual = DataBlockFixture::new()
+        .column("a", vec![1i64, 2, 2])
+        .column("b", vec!["x", "y", "y"])
+        .build();
+    assert_eq!(
+        Some("- schema (a: UInt64, b: String)\n+ schema (a: Int64, b: String)".to_string()),
+        diff_blocks_unordered(&[expected], &[actual])?
+    );
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "blocks are not equal")]
+fn test_assert_blocks_unordered_eq_panic() {
+    let expected = DataBlockFixture::new().column("a", vec![1u64]).build();
+    let actual = DataBlockFixture::new().column("a", vec![2u64]).build();
+    assert_blocks_unordered_eq(&[expected], &[actual]);
+}
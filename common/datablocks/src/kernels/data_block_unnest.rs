@@ -0,0 +1,55 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::DataBlock;
+
+impl DataBlock {
+    /// Expand the list column `column` into one row per element. The other columns
+    /// are repeated for each element and the elements are appended as the last
+    /// column of `schema`. Rows with a null or empty list produce no output.
+    pub fn unnest_block(raw: &DataBlock, column: &str, schema: DataSchemaRef) -> Result<DataBlock> {
+        let series = raw.try_column_by_name(column)?.to_array()?;
+        let list = match series.data_type() {
+            DataType::List(_) => DFListArray::from_arrow_array(series.get_array_ref().as_ref()),
+            other => {
+                return Err(ErrorCode::IllegalDataType(format!(
+                    "UNNEST expects an array column, but column {} is {}",
+                    column, other
+                )))
+            }
+        };
+
+        let mut indices = Vec::with_capacity(list.len());
+        let mut elements = Vec::with_capacity(list.len());
+        for (row, values) in (&list).into_iter().enumerate() {
+            if let Some(values) = values {
+                indices.extend(std::iter::repeat(row as u32).take(values.len()));
+                elements.push(DataColumn::Array(values));
+            }
+        }
+
+        if indices.is_empty() {
+            return Ok(DataBlock::empty_with_schema(schema));
+        }
+
+        let taken = DataBlock::block_take_by_indices(raw, &[], &indices)?;
+        let mut columns = taken.columns().to_vec();
+        columns.push(DataColumnCommon::concat(&elements)?);
+        Ok(DataBlock::create(schema, columns))
+    }
+}
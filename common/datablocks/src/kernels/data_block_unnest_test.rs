@@ -0,0 +1,60 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::*;
+
+#[test]
+fn test_data_block_unnest() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", DataType::Int64, false),
+        DataField::new(
+            "arr",
+            DataType::List(Box::new(DataField::new("item", DataType::Int64, true))),
+            true,
+        ),
+    ]);
+
+    let mut builder = ListPrimitiveArrayBuilder::<i64>::with_capacity(4, 3);
+    builder.append_slice(Some(&[1, 2]));
+    builder.append_slice(None);
+    builder.append_slice(Some(&[3]));
+    let arr = builder.finish();
+
+    let raw = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![10i64, 20, 30]),
+        arr.into_series(),
+    ]);
+
+    let mut fields = schema.fields().clone();
+    fields.push(DataField::new("elem", DataType::Int64, true));
+    let output_schema = DataSchemaRefExt::create(fields);
+
+    let unnested = DataBlock::unnest_block(&raw, "arr", output_schema.clone())?;
+    assert_eq!(unnested.schema(), &output_schema);
+    assert_eq!(unnested.num_rows(), 3);
+
+    let ids = unnested.try_column_by_name("id")?.to_array()?;
+    let elems = unnested.try_column_by_name("elem")?.to_array()?;
+    assert_eq!(ids.i64()?.inner().values().as_slice(), &[10, 10, 30]);
+    assert_eq!(elems.i64()?.inner().values().as_slice(), &[1, 2, 3]);
+
+    // A column that is not an array can not be unnested.
+    let result = DataBlock::unnest_block(&raw, "id", output_schema);
+    assert!(result.is_err());
+
+    Ok(())
+}
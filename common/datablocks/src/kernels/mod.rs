@@ -28,6 +28,8 @@ mod data_block_slice_test;
 mod data_block_sort_test;
 #[cfg(test)]
 mod data_block_take_test;
+#[cfg(test)]
+mod data_block_unnest_test;
 
 mod data_block_concat;
 mod data_block_filter;
@@ -37,6 +39,7 @@ mod data_block_scatter;
 mod data_block_slice;
 mod data_block_sort;
 mod data_block_take;
+mod data_block_unnest;
 
 pub use data_block_group_by_hash::*;
 pub use data_block_sort::SortColumnDescription;
@@ -14,13 +14,17 @@
 
 #![feature(hash_raw_entry)]
 
+#[cfg(test)]
+mod data_block_assert_test;
 #[cfg(test)]
 mod data_block_test;
 
 mod data_block;
+mod data_block_assert;
 mod data_block_debug;
 mod kernels;
 
 pub use data_block::DataBlock;
+pub use data_block_assert::*;
 pub use data_block_debug::*;
 pub use kernels::*;
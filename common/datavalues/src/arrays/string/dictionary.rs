@@ -0,0 +1,169 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_arrow::arrow::compute::aggregate;
+use common_infallible::RwLock;
+
+use crate::prelude::*;
+
+/// Bytes held by an interned value besides its own data: the `Arc` header and
+/// its entries in the value list and the code map.
+const ENTRY_OVERHEAD: usize = 64;
+
+pub type StringDictionaryRef = Arc<RwLock<StringDictionary>>;
+
+/// Distinct string values stored once, and referred to by their `u32` codes.
+///
+/// The dictionary only grows, so the codes it hands out stay valid as long as it lives.
+#[derive(Default)]
+pub struct StringDictionary {
+    values: Vec<Arc<[u8]>>,
+    codes: HashMap<Arc<[u8]>, u32>,
+    memory_size: usize,
+}
+
+impl StringDictionary {
+    pub fn create() -> StringDictionaryRef {
+        Arc::new(RwLock::new(StringDictionary::default()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Estimated memory of the values and their index.
+    pub fn memory_size(&self) -> usize {
+        self.memory_size
+    }
+
+    pub fn code(&self, value: &[u8]) -> Option<u32> {
+        self.codes.get(value).copied()
+    }
+
+    /// # Panics
+    /// If the code is not handed out by this dictionary.
+    pub fn value(&self, code: u32) -> &[u8] {
+        self.values[code as usize].as_ref()
+    }
+
+    /// Returns the code of the value, adds it if it is new.
+    pub fn intern(&mut self, value: &[u8]) -> u32 {
+        if let Some(code) = self.codes.get(value) {
+            return *code;
+        }
+
+        let code = self.values.len() as u32;
+        let value: Arc<[u8]> = Arc::from(value);
+        self.memory_size += value.len() + ENTRY_OVERHEAD;
+        self.values.push(value.clone());
+        self.codes.insert(value, code);
+        code
+    }
+}
+
+/// A string array stored as the codes of its values in a shared dictionary.
+#[derive(Clone)]
+pub struct DictionaryStringArray {
+    dictionary: StringDictionaryRef,
+    codes: DFUInt32Array,
+}
+
+impl DictionaryStringArray {
+    /// Encodes the array if the codes and the values new to the dictionary take less memory
+    /// than the array itself, and the dictionary stays within `max_bytes`.
+    ///
+    /// Returns the encoded array with the bytes the dictionary grew by, or None if it is
+    /// not worth encoding, in which case the dictionary is left untouched.
+    pub fn try_encode(
+        array: &DFStringArray,
+        dictionary: &StringDictionaryRef,
+        max_bytes: usize,
+    ) -> Option<(DictionaryStringArray, usize)> {
+        {
+            let dictionary = dictionary.read();
+            let mut distinct = HashSet::new();
+            let mut new_bytes = 0;
+            for value in array.inner().iter().flatten() {
+                if distinct.insert(value) && dictionary.code(value).is_none() {
+                    new_bytes += value.len() + ENTRY_OVERHEAD;
+                }
+            }
+
+            let codes_bytes = array.len() * std::mem::size_of::<u32>();
+            let plain_bytes = aggregate::estimated_bytes_size(array.inner());
+            if codes_bytes + new_bytes >= plain_bytes
+                || dictionary.memory_size() + new_bytes > max_bytes
+                || dictionary.len() + distinct.len() > u32::MAX as usize
+            {
+                return None;
+            }
+        }
+
+        let mut writer = dictionary.write();
+        let before = writer.memory_size();
+        let codes = DFUInt32Array::new_from_opt_iter(
+            array
+                .inner()
+                .iter()
+                .map(|value| value.map(|value| writer.intern(value))),
+        );
+        let grown = writer.memory_size() - before;
+        drop(writer);
+
+        let encoded = DictionaryStringArray {
+            dictionary: dictionary.clone(),
+            codes,
+        };
+        Some((encoded, grown))
+    }
+
+    pub fn decode(&self) -> DFStringArray {
+        let dictionary = self.dictionary.read();
+        DFStringArray::new_from_opt_iter(
+            self.codes
+                .inner()
+                .iter()
+                .map(|code| code.map(|code| dictionary.value(*code))),
+        )
+    }
+
+    pub fn dictionary(&self) -> &StringDictionaryRef {
+        &self.dictionary
+    }
+
+    pub fn codes(&self) -> &DFUInt32Array {
+        &self.codes
+    }
+
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Memory of the codes, the dictionary is shared with other arrays and not included.
+    pub fn memory_size(&self) -> usize {
+        aggregate::estimated_bytes_size(self.codes.inner())
+    }
+}
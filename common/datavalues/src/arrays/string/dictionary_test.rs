@@ -0,0 +1,78 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::prelude::*;
+
+#[test]
+fn test_dictionary_intern() {
+    let dictionary = StringDictionary::create();
+    let mut dictionary = dictionary.write();
+    assert!(dictionary.is_empty());
+
+    let a = dictionary.intern(b"a");
+    let b = dictionary.intern(b"b");
+    assert_eq!(dictionary.intern(b"a"), a);
+    assert_ne!(a, b);
+    assert_eq!(dictionary.len(), 2);
+    assert_eq!(dictionary.value(b), b"b");
+    assert_eq!(dictionary.code(b"c"), None);
+}
+
+#[test]
+fn test_dictionary_string_array() {
+    let values: Vec<Option<&str>> = (0..1000)
+        .map(|i| match i % 10 {
+            0 => None,
+            n if n % 2 == 0 => Some("beijing"),
+            _ => Some("shanghai"),
+        })
+        .collect();
+    let array = DFStringArray::new_from_opt_slice(&values);
+
+    let dictionary = StringDictionary::create();
+    let (encoded, grown) =
+        DictionaryStringArray::try_encode(&array, &dictionary, 1024 * 1024).unwrap();
+    assert_eq!(dictionary.read().len(), 2);
+    assert_eq!(dictionary.read().memory_size(), grown);
+    assert_eq!(encoded.len(), 1000);
+    assert!(encoded.memory_size() < aggregate_size(&array));
+
+    let decoded = encoded.decode();
+    assert_eq!(decoded.collect_values(), array.collect_values());
+
+    // Another array shares the values already interned.
+    let (_, grown) = DictionaryStringArray::try_encode(&array, &dictionary, 1024 * 1024).unwrap();
+    assert_eq!(grown, 0);
+    assert_eq!(dictionary.read().len(), 2);
+}
+
+#[test]
+fn test_dictionary_string_array_not_encoded() {
+    let dictionary = StringDictionary::create();
+
+    // Distinct values take more memory encoded.
+    let values: Vec<String> = (0..1000).map(|i| format!("{}", i)).collect();
+    let array = DFStringArray::new_from_slice(&values);
+    assert!(DictionaryStringArray::try_encode(&array, &dictionary, 1024 * 1024).is_none());
+    assert!(dictionary.read().is_empty());
+
+    // The dictionary is full.
+    let array = DFStringArray::new_from_slice(&vec!["databend"; 1000]);
+    assert!(DictionaryStringArray::try_encode(&array, &dictionary, 16).is_none());
+    assert!(dictionary.read().is_empty());
+}
+
+fn aggregate_size(array: &DFStringArray) -> usize {
+    array.clone().into_series().get_array_memory_size()
+}
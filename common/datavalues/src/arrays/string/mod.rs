@@ -13,10 +13,13 @@
 // limitations under the License.
 
 mod builder;
+mod dictionary;
 mod iterator;
 
 #[cfg(test)]
 mod builder_test;
+#[cfg(test)]
+mod dictionary_test;
 
 pub use builder::*;
 use common_arrow::arrow::array::*;
@@ -25,6 +28,7 @@ use common_arrow::arrow::compute::cast::binary_to_large_binary;
 use common_arrow::arrow::datatypes::DataType as ArrowDataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
+pub use dictionary::*;
 pub use iterator::*;
 
 use crate::prelude::*;
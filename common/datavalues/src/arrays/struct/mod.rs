@@ -57,14 +57,37 @@ impl DFStructArray {
     /// # Safety
     /// Note this doesn't do any bound checking, for performance reason.
     pub unsafe fn try_get(&self, index: usize) -> Result<DataValue> {
-        let nested_array = self.array.values()[index].clone();
-        let series = nested_array.into_series();
-        let scalar_vec = (0..series.len())
-            .map(|i| series.try_get(i))
+        if self.array.is_null(index) {
+            return Ok(DataValue::Null);
+        }
+
+        // The values of the row, one of each field.
+        let scalar_vec = self
+            .array
+            .values()
+            .iter()
+            .map(|array| array.clone().into_series().try_get(index))
             .collect::<Result<Vec<_>>>()?;
         Ok(DataValue::Struct(scalar_vec))
     }
 
+    /// The values of the field, None if the struct has no such field.
+    pub fn field_by_name(&self, name: &str) -> Option<Series> {
+        let position = match &self.data_type {
+            DataType::Struct(fields) => fields.iter().position(|f| f.name() == name)?,
+            _ => return None,
+        };
+        self.field_by_index(position)
+    }
+
+    /// The values of the field at the 0-based position.
+    pub fn field_by_index(&self, index: usize) -> Option<Series> {
+        self.array
+            .values()
+            .get(index)
+            .map(|array| array.clone().into_series())
+    }
+
     pub fn len(&self) -> usize {
         self.array.len()
     }
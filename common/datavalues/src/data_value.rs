@@ -410,7 +410,16 @@ impl fmt::Display for DataValue {
                         .join(", ")
                 )
             }
-            DataValue::Struct(v) => write!(f, "{:?}", v),
+            DataValue::Struct(v) => {
+                write!(
+                    f,
+                    "({})",
+                    v.iter()
+                        .map(|v| format!("{}", v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
         }
     }
 }
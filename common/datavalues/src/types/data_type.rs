@@ -143,6 +143,10 @@ impl From<&ArrowDataType> for DataType {
                 let f: DataField = (f.as_ref()).into();
                 DataType::List(Box::new(f))
             }
+            ArrowDataType::Struct(fields) => {
+                let fields = fields.iter().map(|f| f.into()).collect();
+                DataType::Struct(fields)
+            }
             ArrowDataType::Binary | ArrowDataType::LargeBinary => DataType::String,
             ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 => DataType::String,
 
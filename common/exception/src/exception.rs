@@ -172,6 +172,17 @@ build_exceptions! {
     UnexpectedError(54),
     DateTimeParseError(55),
     BadPredicateRows(56),
+    UnknownFormat(57),
+    UnknownQueryResult(58),
+    FunctionAlreadyExists(59),
+    QuotaExceeded(60),
+    TooManyRecursiveIterations(61),
+    UnknownIndex(62),
+    IndexAlreadyExists(63),
+    UnknownPreparedStatement(64),
+    MemoryLimitExceeded(65),
+    QueryQueueTimeout(66),
+    LargeResultNotAllowed(67),
 
     // uncategorized
     UnexpectedResponseType(600),
@@ -235,6 +246,37 @@ build_exceptions! {
     UserAlreadyExists(3001),
     IllegalUserInfoFormat(3002),
 
+    // pipe-api error codes
+    UnknownPipe(3100),
+    PipeAlreadyExists(3101),
+    IllegalPipeInfoFormat(3102),
+    PipeCheckpointConflict(3103),
+    PipeSourceError(3104),
+
+    // load-api error codes
+    IllegalLoadProgressFormat(3200),
+    LoadProgressConflict(3201),
+
+    // purge-api error codes
+    IllegalPurgeTaskFormat(3300),
+    PurgeTaskConflict(3301),
+
+    // function-api error codes
+    IllegalFunctionInfoFormat(3400),
+
+    // leader-api error codes
+    LeaderLeaseLost(3500),
+
+    // profile-api error codes
+    UnknownSettingsProfile(3600),
+    SettingsProfileAlreadyExists(3601),
+    IllegalSettingsProfileFormat(3602),
+
+    // connection-api error codes
+    UnknownConnection(3700),
+    ConnectionAlreadyExists(3701),
+    IllegalConnectionFormat(3702),
+
     // meta-api error codes
     DatabaseAlreadyExists(4001),
     TableAlreadyExists(4003),
@@ -263,6 +305,8 @@ build_exceptions! {
     DALTransportError(7000),
     UnknownStorageSchemeName(7001),
     SecretKeyNotSet(7002),
+    DALTransientError(7003),
+    DALEncryptionError(7004),
 
 
     // datasource error
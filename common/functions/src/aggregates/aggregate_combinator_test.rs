@@ -113,6 +113,26 @@ fn test_aggregate_combinator_function() -> Result<()> {
             expect: DataValue::UInt64(Some(3)),
             error: "",
         },
+        Test {
+            name: "count-if-distinct-passed",
+            params: vec![],
+            args: args.clone(),
+            display: "countif",
+            func_name: "countifdistinct",
+            arrays: arrays.clone(),
+            expect: DataValue::UInt64(Some(3)),
+            error: "",
+        },
+        Test {
+            name: "sum-if-distinct-passed",
+            params: vec![],
+            args: args.clone(),
+            display: "sumif",
+            func_name: "sumifdistinct",
+            arrays: arrays.clone(),
+            expect: DataValue::Int64(Some(8)),
+            error: "",
+        },
     ];
 
     for t in tests {
@@ -129,6 +129,14 @@ impl AggregateFunctionFactory {
                 let aggregate_functions_map = &self.case_insensitive_desc;
 
                 match aggregate_functions_map.get(nested_name) {
+                    // The nested function may be a combinator too, like countIf in countIfDistinct.
+                    None if self.check(nested_name) => {
+                        let nested_creator: AggregateFunctionCreator =
+                            Box::new(|name, params, arguments| {
+                                AggregateFunctionFactory::instance().get(name, params, arguments)
+                            });
+                        return (desc.creator)(nested_name, params, arguments, &nested_creator);
+                    }
                     None => {
                         break;
                     }
@@ -161,7 +169,7 @@ impl AggregateFunctionFactory {
         // find suffix
         for (suffix, _) in &self.case_insensitive_combinator_desc {
             if let Some(nested_name) = lowercase_name.strip_suffix(suffix) {
-                if self.case_insensitive_desc.contains_key(nested_name) {
+                if self.check(nested_name) {
                     return true;
                 }
             }
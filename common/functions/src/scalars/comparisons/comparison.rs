@@ -28,6 +28,7 @@ use crate::scalars::ComparisonLtEqFunction;
 use crate::scalars::ComparisonLtFunction;
 use crate::scalars::ComparisonNotEqFunction;
 use crate::scalars::ComparisonNotLikeFunction;
+use crate::scalars::ComparisonNullSafeEqFunction;
 use crate::scalars::Function;
 
 #[derive(Clone)]
@@ -46,6 +47,7 @@ impl ComparisonFunction {
         factory.register("<>", ComparisonNotEqFunction::desc());
         factory.register("like", ComparisonLikeFunction::desc());
         factory.register("not like", ComparisonNotLikeFunction::desc());
+        factory.register("<=>", ComparisonNullSafeEqFunction::desc());
     }
 
     pub fn try_create_func(op: DataValueComparisonOperator) -> Result<Box<dyn Function>> {
@@ -0,0 +1,91 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::*;
+use common_datavalues::DataValueComparisonOperator;
+use common_datavalues::DataValueLogicOperator;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// The null-safe equality `a <=> b`, aka `a IS NOT DISTINCT FROM b`.
+///
+/// Two NULLs are equal and a NULL is not equal to any value, so the result is never NULL.
+#[derive(Clone)]
+pub struct ComparisonNullSafeEqFunction;
+
+impl ComparisonNullSafeEqFunction {
+    pub fn try_create_func(_display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ComparisonNullSafeEqFunction))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create_func))
+            .features(FunctionFeatures::default().deterministic().bool_function())
+    }
+}
+
+impl Function for ComparisonNullSafeEqFunction {
+    fn name(&self) -> &str {
+        "ComparisonNullSafeEqFunction"
+    }
+
+    fn return_type(&self, _args: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let lhs = columns[0].column();
+        let rhs = columns[1].column();
+
+        // A NULL literal can't be compared, it only equals the NULLs of the other side.
+        if lhs.data_type() == DataType::Null {
+            return rhs.is_null();
+        }
+        if rhs.data_type() == DataType::Null {
+            return lhs.is_null();
+        }
+
+        // (lhs IS NULL AND rhs IS NULL) OR (lhs IS NOT NULL AND rhs IS NOT NULL AND lhs = rhs),
+        // the kleene AND turns the NULL of `lhs = rhs` into false when either side is NULL.
+        let both_null = lhs
+            .is_null()?
+            .logic(DataValueLogicOperator::And, &[rhs.is_null()?])?;
+        let both_not_null = lhs
+            .is_not_null()?
+            .logic(DataValueLogicOperator::And, &[rhs.is_not_null()?])?;
+        let eq = lhs.compare(DataValueComparisonOperator::Eq, rhs)?;
+        let both_eq = both_not_null.logic(DataValueLogicOperator::And, &[eq])?;
+        both_null.logic(DataValueLogicOperator::Or, &[both_eq])
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+}
+
+impl fmt::Display for ComparisonNullSafeEqFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<=>")
+    }
+}
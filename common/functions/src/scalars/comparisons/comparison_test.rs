@@ -142,6 +142,19 @@ fn test_comparison_function() -> Result<()> {
             expect: Series::new(vec![false, false, false, true]),
             error: "",
         },
+        Test {
+            name: "null-safe-eq-passed",
+            display: "<=>",
+            nullable: false,
+            func: ComparisonNullSafeEqFunction::try_create_func("")?,
+            arg_names: vec!["a", "b"],
+            columns: vec![
+                Series::new(vec![Some(1i64), None, None, Some(4)]).into(),
+                Series::new(vec![Some(1i64), Some(2), None, Some(5)]).into(),
+            ],
+            expect: Series::new(vec![true, false, true, false]),
+            error: "",
+        },
     ];
 
     for t in tests {
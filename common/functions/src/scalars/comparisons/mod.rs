@@ -24,6 +24,7 @@ mod comparison_lt;
 mod comparison_lt_eq;
 mod comparison_not_eq;
 mod comparison_not_like;
+mod comparison_null_safe_eq;
 
 pub use comparison::ComparisonFunction;
 pub use comparison_eq::ComparisonEqFunction;
@@ -34,3 +35,4 @@ pub use comparison_lt::ComparisonLtFunction;
 pub use comparison_lt_eq::ComparisonLtEqFunction;
 pub use comparison_not_eq::ComparisonNotEqFunction;
 pub use comparison_not_like::ComparisonNotLikeFunction;
+pub use comparison_null_safe_eq::ComparisonNullSafeEqFunction;
@@ -15,6 +15,9 @@
 use common_datavalues::DataValueArithmeticOperator;
 use common_exception::Result;
 
+use super::date_unit_function::DateAddFunction;
+use super::date_unit_function::DateDiffFunction;
+use super::date_unit_function::DateTruncFunction;
 use super::interval_function::MonthsArithmeticFunction;
 use super::interval_function::SecondsArithmeticFunction;
 use super::now::NowFunction;
@@ -172,5 +175,16 @@ impl DateFunction {
             "subtractSeconds",
             Self::seconds_arithmetic_function_creator(-1),
         );
+
+        factory.register(
+            "date_add",
+            DateAddFunction::desc(DataValueArithmeticOperator::Plus),
+        );
+        factory.register(
+            "date_sub",
+            DateAddFunction::desc(DataValueArithmeticOperator::Minus),
+        );
+        factory.register("date_diff", DateDiffFunction::desc());
+        factory.register("date_trunc", DateTruncFunction::desc());
     }
 }
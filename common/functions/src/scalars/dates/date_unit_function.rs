@@ -0,0 +1,347 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::chrono::Datelike;
+use common_datavalues::chrono::NaiveDate;
+use common_datavalues::chrono::NaiveDateTime;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FactoryCreator;
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+use crate::scalars::IntervalFunctionFactory;
+
+const SECONDS_PER_DAY: i64 = 24 * 3600;
+
+/// The unit argument of date_diff and date_trunc, like 'year' or 'day'.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateUnit {
+    Year,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateUnit {
+    pub fn try_from_str(unit: &str) -> Result<DateUnit> {
+        match unit.to_lowercase().as_str() {
+            "year" => Ok(DateUnit::Year),
+            "quarter" => Ok(DateUnit::Quarter),
+            "month" => Ok(DateUnit::Month),
+            "week" => Ok(DateUnit::Week),
+            "day" => Ok(DateUnit::Day),
+            "hour" => Ok(DateUnit::Hour),
+            "minute" => Ok(DateUnit::Minute),
+            "second" => Ok(DateUnit::Second),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unknown date unit: {}, should be one of year, quarter, month, week, day, hour, minute or second",
+                unit
+            ))),
+        }
+    }
+
+    // The unit must be a constant string, it's the first argument of the function.
+    fn try_from_column(column: &DataColumnWithField, function: &str) -> Result<DateUnit> {
+        match column.column() {
+            DataColumn::Constant(DataValue::String(Some(unit)), _) => {
+                Self::try_from_str(&String::from_utf8_lossy(unit))
+            }
+            _ => Err(ErrorCode::BadArguments(format!(
+                "The first argument of function {} must be a constant string of date unit",
+                function
+            ))),
+        }
+    }
+
+    /// The index of the unit which the seconds since Unix epoch is in, counted from the epoch.
+    /// Weeks start on Monday.
+    pub fn index_of(&self, seconds: i64) -> i64 {
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        match self {
+            DateUnit::Year => to_datetime(seconds).year() as i64,
+            DateUnit::Quarter => {
+                let datetime = to_datetime(seconds);
+                datetime.year() as i64 * 4 + (datetime.month0() / 3) as i64
+            }
+            DateUnit::Month => {
+                let datetime = to_datetime(seconds);
+                datetime.year() as i64 * 12 + datetime.month0() as i64
+            }
+            // 1970-01-01 is a Thursday.
+            DateUnit::Week => (days + 3).div_euclid(7),
+            DateUnit::Day => days,
+            DateUnit::Hour => seconds.div_euclid(3600),
+            DateUnit::Minute => seconds.div_euclid(60),
+            DateUnit::Second => seconds,
+        }
+    }
+
+    /// Truncates the seconds since Unix epoch to the start of the unit.
+    pub fn truncate(&self, seconds: i64) -> i64 {
+        let start_of_date = |year: i32, month: u32| {
+            NaiveDate::from_ymd(year, month, 1)
+                .and_hms(0, 0, 0)
+                .timestamp()
+        };
+
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        match self {
+            DateUnit::Year => start_of_date(to_datetime(seconds).year(), 1),
+            DateUnit::Quarter => {
+                let datetime = to_datetime(seconds);
+                start_of_date(datetime.year(), datetime.month0() / 3 * 3 + 1)
+            }
+            DateUnit::Month => {
+                let datetime = to_datetime(seconds);
+                start_of_date(datetime.year(), datetime.month())
+            }
+            DateUnit::Week => (days - (days + 3).rem_euclid(7)) * SECONDS_PER_DAY,
+            DateUnit::Day => days * SECONDS_PER_DAY,
+            DateUnit::Hour => seconds - seconds.rem_euclid(3600),
+            DateUnit::Minute => seconds - seconds.rem_euclid(60),
+            DateUnit::Second => seconds,
+        }
+    }
+}
+
+fn to_datetime(seconds: i64) -> NaiveDateTime {
+    NaiveDateTime::from_timestamp(seconds, 0)
+}
+
+// The seconds since Unix epoch of the date16, date32 or datetime32 column.
+fn to_seconds(column: &DataColumnWithField, function: &str) -> Result<DFInt64Array> {
+    let array = column.column().to_array()?;
+    match column.data_type() {
+        DataType::Date16 => Ok(array
+            .u16()?
+            .apply_cast_numeric(|days| days as i64 * SECONDS_PER_DAY)),
+        DataType::Date32 => Ok(array
+            .i32()?
+            .apply_cast_numeric(|days| days as i64 * SECONDS_PER_DAY)),
+        DataType::DateTime32(_) => Ok(array.u32()?.apply_cast_numeric(|seconds| seconds as i64)),
+        other => Err(ErrorCode::IllegalDataType(format!(
+            "Illegal type {:?} of argument of function {}, should be a date16/date32 or a dateTime32",
+            other, function
+        ))),
+    }
+}
+
+fn from_seconds(seconds: &DFInt64Array, data_type: &DataType) -> DataColumn {
+    match data_type {
+        DataType::Date16 => seconds
+            .apply_cast_numeric(|seconds| seconds.div_euclid(SECONDS_PER_DAY) as u16)
+            .into(),
+        DataType::Date32 => seconds
+            .apply_cast_numeric(|seconds| seconds.div_euclid(SECONDS_PER_DAY) as i32)
+            .into(),
+        _ => seconds.apply_cast_numeric(|seconds| seconds as u32).into(),
+    }
+}
+
+fn check_date_type(data_type: &DataType, function: &str) -> Result<()> {
+    match is_date_or_date_time(data_type) {
+        true => Ok(()),
+        false => Err(ErrorCode::IllegalDataType(format!(
+            "Illegal type {:?} of argument of function {}, should be a date16/date32 or a dateTime32",
+            data_type, function
+        ))),
+    }
+}
+
+/// date_add(date, interval) and date_sub(date, interval), the same as date +/- interval.
+#[derive(Clone, Debug)]
+pub struct DateAddFunction {
+    display_name: String,
+    op: DataValueArithmeticOperator,
+}
+
+impl DateAddFunction {
+    pub fn try_create(
+        display_name: &str,
+        op: DataValueArithmeticOperator,
+    ) -> Result<Box<dyn Function>> {
+        Ok(Box::new(DateAddFunction {
+            display_name: display_name.to_string(),
+            op,
+        }))
+    }
+
+    pub fn desc(op: DataValueArithmeticOperator) -> FunctionDescription {
+        let creator: FactoryCreator =
+            Box::new(move |display_name| Self::try_create(display_name, op.clone()));
+
+        FunctionDescription::creator(creator).features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for DateAddFunction {
+    fn name(&self) -> &str {
+        self.display_name.as_str()
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        check_date_type(&args[0], self.name())?;
+        match &args[1] {
+            DataType::Interval(_) => Ok(args[0].clone()),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "Illegal type {:?} of the second argument of function {}, should be an interval",
+                other,
+                self.name()
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        match IntervalFunctionFactory::try_get_arithmetic_func(columns) {
+            Some(func) => func(&self.op, &columns[0], &columns[1]),
+            None => Err(ErrorCode::IllegalDataType(format!(
+                "Illegal arguments for function {}, should be a date or dateTime and an interval",
+                self.name()
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for DateAddFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}()", self.display_name)
+    }
+}
+
+/// date_diff(unit, start, end) counts the unit boundaries crossed from start to end.
+#[derive(Clone, Debug)]
+pub struct DateDiffFunction {
+    display_name: String,
+}
+
+impl DateDiffFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(DateDiffFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for DateDiffFunction {
+    fn name(&self) -> &str {
+        self.display_name.as_str()
+    }
+
+    fn num_arguments(&self) -> usize {
+        3
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        check_date_type(&args[1], self.name())?;
+        check_date_type(&args[2], self.name())?;
+        Ok(DataType::Int64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let unit = DateUnit::try_from_column(&columns[0], self.name())?;
+        let start = to_seconds(&columns[1], self.name())?;
+        let end = to_seconds(&columns[2], self.name())?;
+
+        let data = start
+            .into_no_null_iter()
+            .zip(end.into_no_null_iter())
+            .map(|(start, end)| unit.index_of(*end) - unit.index_of(*start))
+            .collect::<AlignedVec<i64>>();
+        let validity = combine_validities(start.inner().validity(), end.inner().validity());
+        Ok(DFInt64Array::new_from_owned_with_null_bitmap(data, validity).into())
+    }
+}
+
+impl fmt::Display for DateDiffFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}()", self.display_name)
+    }
+}
+
+/// date_trunc(unit, date) truncates the date or datetime to the start of the unit.
+#[derive(Clone, Debug)]
+pub struct DateTruncFunction {
+    display_name: String,
+}
+
+impl DateTruncFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(DateTruncFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for DateTruncFunction {
+    fn name(&self) -> &str {
+        self.display_name.as_str()
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        check_date_type(&args[1], self.name())?;
+        Ok(args[1].clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let unit = DateUnit::try_from_column(&columns[0], self.name())?;
+        let seconds = to_seconds(&columns[1], self.name())?;
+        let truncated = seconds.apply_cast_numeric(|seconds| unit.truncate(seconds));
+        Ok(from_seconds(&truncated, columns[1].data_type()))
+    }
+}
+
+impl fmt::Display for DateTruncFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}()", self.display_name)
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::chrono::DateTime;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::DateAddFunction;
+use crate::scalars::DateDiffFunction;
+use crate::scalars::DateTruncFunction;
+
+fn unit_column(unit: &str) -> DataColumnWithField {
+    DataColumnWithField::new(
+        DataColumn::Constant(DataValue::String(Some(unit.as_bytes().to_vec())), 1),
+        DataField::new("unit", DataType::String, false),
+    )
+}
+
+fn datetime_column(name: &str, datetime: &str) -> DataColumnWithField {
+    let seconds = DateTime::parse_from_rfc3339(datetime).unwrap().timestamp();
+    DataColumnWithField::new(
+        Series::new(vec![seconds as u32]).into(),
+        DataField::new(name, DataType::DateTime32(None), false),
+    )
+}
+
+#[test]
+fn test_date_diff_function() -> Result<()> {
+    let date_diff = DateDiffFunction::try_create("date_diff")?;
+    let start = datetime_column("start", "2020-12-31T23:59:59Z");
+    let end = datetime_column("end", "2021-01-01T00:00:00Z");
+
+    // 2020-12-31 and 2021-01-01 are in the same week.
+    let tests = vec![
+        ("year", 1),
+        ("quarter", 1),
+        ("Month", 1),
+        ("week", 0),
+        ("day", 1),
+        ("hour", 1),
+        ("minute", 1),
+        ("SECOND", 1),
+    ];
+
+    for (unit, expect) in tests {
+        let columns = vec![unit_column(unit), start.clone(), end.clone()];
+        let result = date_diff.eval(&columns, 1)?;
+        assert_eq!(result.data_type(), DataType::Int64);
+        assert_eq!(
+            result.try_get(0)?,
+            DataValue::Int64(Some(expect)),
+            "{}",
+            unit
+        );
+
+        let columns = vec![unit_column(unit), end.clone(), start.clone()];
+        let result = date_diff.eval(&columns, 1)?;
+        assert_eq!(
+            result.try_get(0)?,
+            DataValue::Int64(Some(-expect)),
+            "{}",
+            unit
+        );
+    }
+
+    let columns = vec![unit_column("decade"), start, end];
+    let result = date_diff.eval(&columns, 1);
+    assert!(result.is_err());
+    assert_eq!(
+        "Code: 6, displayText = Unknown date unit: decade, should be one of year, quarter, month, week, day, hour, minute or second.",
+        result.unwrap_err().to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_date_trunc_function() -> Result<()> {
+    let date_trunc = DateTruncFunction::try_create("date_trunc")?;
+    let datetime = datetime_column("datetime", "2021-08-18T10:20:30Z");
+
+    // 2021-08-18 is a Wednesday.
+    let tests = vec![
+        ("year", "2021-01-01T00:00:00Z"),
+        ("quarter", "2021-07-01T00:00:00Z"),
+        ("month", "2021-08-01T00:00:00Z"),
+        ("week", "2021-08-16T00:00:00Z"),
+        ("day", "2021-08-18T00:00:00Z"),
+        ("hour", "2021-08-18T10:00:00Z"),
+        ("minute", "2021-08-18T10:20:00Z"),
+        ("second", "2021-08-18T10:20:30Z"),
+    ];
+
+    for (unit, expect) in tests {
+        let columns = vec![unit_column(unit), datetime.clone()];
+        let result = date_trunc.eval(&columns, 1)?;
+        let expect = DateTime::parse_from_rfc3339(expect).unwrap().timestamp();
+        assert_eq!(
+            result.try_get(0)?,
+            DataValue::UInt32(Some(expect as u32)),
+            "{}",
+            unit
+        );
+    }
+
+    // Date is truncated to the day at least.
+    let date = DataColumnWithField::new(
+        Series::new(vec![18857u16]).into(),
+        DataField::new("date", DataType::Date16, false),
+    );
+    let result = date_trunc.eval(&[unit_column("hour"), date.clone()], 1)?;
+    assert_eq!(result.try_get(0)?, DataValue::UInt16(Some(18857)));
+    let result = date_trunc.eval(&[unit_column("month"), date], 1)?;
+    assert_eq!(result.try_get(0)?, DataValue::UInt16(Some(18840)));
+
+    Ok(())
+}
+
+#[test]
+fn test_date_add_function() -> Result<()> {
+    let date_add = DateAddFunction::try_create("date_add", DataValueArithmeticOperator::Plus)?;
+    let date_sub = DateAddFunction::try_create("date_sub", DataValueArithmeticOperator::Minus)?;
+
+    let datetime = datetime_column("datetime", "2020-01-31T10:00:00Z");
+    let months = DataColumnWithField::new(
+        Series::new(vec![1i64]).into(),
+        DataField::new("months", DataType::Interval(IntervalUnit::YearMonth), false),
+    );
+    let milliseconds = DataColumnWithField::new(
+        Series::new(vec![3600 * 1000i64]).into(),
+        DataField::new("ms", DataType::Interval(IntervalUnit::DayTime), false),
+    );
+
+    let tests = vec![
+        (&date_add, &months, "2020-02-29T10:00:00Z"),
+        (&date_sub, &months, "2019-12-31T10:00:00Z"),
+        (&date_add, &milliseconds, "2020-01-31T11:00:00Z"),
+        (&date_sub, &milliseconds, "2020-01-31T09:00:00Z"),
+    ];
+
+    for (func, interval, expect) in tests {
+        let args = vec![DataType::DateTime32(None), interval.data_type().clone()];
+        assert_eq!(func.return_type(&args)?, DataType::DateTime32(None));
+
+        let result = func.eval(&[datetime.clone(), interval.clone()], 1)?;
+        let expect = DateTime::parse_from_rfc3339(expect).unwrap().timestamp();
+        assert_eq!(result.try_get(0)?, DataValue::UInt32(Some(expect as u32)));
+    }
+
+    let args = vec![DataType::DateTime32(None), DataType::Int64];
+    assert!(date_add.return_type(&args).is_err());
+
+    Ok(())
+}
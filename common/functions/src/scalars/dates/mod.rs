@@ -17,10 +17,13 @@ mod date;
 mod date_function_test;
 #[cfg(test)]
 mod date_test;
+#[cfg(test)]
+mod date_unit_function_test;
 
 #[cfg(test)]
 mod interval_function_test;
 
+mod date_unit_function;
 mod interval_function;
 mod now;
 mod number_function;
@@ -29,6 +32,10 @@ mod simple_date;
 mod week_date;
 
 pub use date::DateFunction;
+pub use date_unit_function::DateAddFunction;
+pub use date_unit_function::DateDiffFunction;
+pub use date_unit_function::DateTruncFunction;
+pub use date_unit_function::DateUnit;
 pub use interval_function::IntervalArithmeticFunction;
 pub use interval_function::IntervalFunctionFactory;
 pub use interval_function::MonthsArithmeticFunction;
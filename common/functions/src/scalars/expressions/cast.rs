@@ -31,17 +31,23 @@ use common_datavalues::prelude::DataColumnsWithField;
 use common_datavalues::series::IntoSeries;
 use common_datavalues::DataSchema;
 use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::scalars::Function;
 use crate::with_match_primitive_type;
 
+const DATE_FMT: &str = "%Y-%m-%d";
+const TIME_FMT: &str = "%Y-%m-%d %H:%M:%S";
+
 #[derive(Clone)]
 pub struct CastFunction {
     _display_name: String,
     /// The data type to cast to
     cast_type: DataType,
+    /// Returns NULL instead of an error if the values can't be cast, aka `TRY_CAST`
+    is_try: bool,
 }
 
 impl CastFunction {
@@ -49,6 +55,15 @@ impl CastFunction {
         Ok(Box::new(Self {
             _display_name: display_name,
             cast_type,
+            is_try: false,
+        }))
+    }
+
+    pub fn create_try(display_name: String, cast_type: DataType) -> Result<Box<dyn Function>> {
+        Ok(Box::new(Self {
+            _display_name: display_name,
+            cast_type,
+            is_try: true,
         }))
     }
 }
@@ -64,17 +79,38 @@ impl Function for CastFunction {
 
     // TODO
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
-        Ok(false)
+        Ok(self.is_try)
     }
 
     fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        match self.cast(columns, input_rows) {
+            Err(_) if self.is_try => Ok(DataColumn::Constant(
+                DataValue::from(&self.cast_type),
+                input_rows,
+            )),
+            result => result,
+        }
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((1, 2))
+    }
+}
+
+impl CastFunction {
+    fn cast(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
         if columns[0].data_type() == &self.cast_type {
             return Ok(columns[0].column().clone());
         }
 
         let series = columns[0].column().clone().to_minimal_array()?;
-        const DATE_FMT: &str = "%Y-%m-%d";
-        const TIME_FMT: &str = "%Y-%m-%d %H:%M:%S";
+        let format = Self::format_arg(columns)?;
+        let date_fmt = format.as_deref().unwrap_or(DATE_FMT);
+        let time_fmt = format.as_deref().unwrap_or(TIME_FMT);
 
         let error = ErrorCode::BadDataValueType(format!(
             "Unsupported cast_with_type from array: {:?} into data_type: {:?}",
@@ -90,7 +126,7 @@ impl Function for CastFunction {
                match &self.cast_type {
                 Date32 => Ok(arr.apply_cast_numeric(|v| v as i32).into_series()),
                 DateTime32(_) => Ok(arr.apply_cast_numeric(|v|  Utc.timestamp(v as i64 * 24 * 3600, 0_u32).timestamp() ).into_series() ),
-                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64 * 24 * 3600, 0_u32), DATE_FMT))) ).into_series()),
+                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64 * 24 * 3600, 0_u32), date_fmt))) ).into_series()),
                 _ =>  Err(error)
                }
             }),
@@ -102,7 +138,7 @@ impl Function for CastFunction {
                match &self.cast_type {
                 Date32 => Ok(arr.apply_cast_numeric(|v| v as i32).into_series()),
                 DateTime32(_) => Ok(arr.apply_cast_numeric(|v|  Utc.timestamp(v as i64 * 24 * 3600, 0_u32).timestamp() ).into_series() ),
-                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64 * 24 * 3600, 0_u32), DATE_FMT))) ).into_series()),
+                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64 * 24 * 3600, 0_u32), date_fmt))) ).into_series()),
                 _ =>  Err(error)
                }
             }),
@@ -114,7 +150,7 @@ impl Function for CastFunction {
                match &self.cast_type {
                 Date16 => Ok(arr.apply_cast_numeric(|v| (v as i64 / 24/ 3600) as u16).into_series()),
                 Date32 => Ok(arr.apply_cast_numeric(|v| (v as i64 / 24/ 3600) as u32).into_series()),
-                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64, 0_u32), TIME_FMT))) ).into_series()),
+                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64, 0_u32), time_fmt))) ).into_series()),
                 _ =>  Err(error)
                }
             }),
@@ -126,7 +162,7 @@ impl Function for CastFunction {
                match columns[0].data_type() {
                 String => {
                     let it = series.string()?.into_iter().map(|v| {
-                        v.and_then(string_to_date).map(|d| (d.num_days_from_ce() - EPOCH_DAYS_FROM_CE) as u16 )
+                        v.and_then(|v| string_to_date(v, format.as_deref())).map(|d| (d.num_days_from_ce() - EPOCH_DAYS_FROM_CE) as u16 )
                     });
                     Ok(DFUInt16Array::from_iter(it).into_series())
                 },
@@ -140,7 +176,7 @@ impl Function for CastFunction {
                match columns[0].data_type() {
                 String => {
                     let it = series.string()?.into_iter().map(|v| {
-                        v.and_then(string_to_date).map(|d| (d.num_days_from_ce() - EPOCH_DAYS_FROM_CE) as i32 )
+                        v.and_then(|v| string_to_date(v, format.as_deref())).map(|d| (d.num_days_from_ce() - EPOCH_DAYS_FROM_CE) as i32 )
                     });
                     Ok(DFInt32Array::from_iter(it).into_series())
                 },
@@ -155,7 +191,7 @@ impl Function for CastFunction {
                    match columns[0].data_type() {
                     String => {
                         let it = series.string()?.into_iter().map(|v| {
-                            v.and_then(string_to_datetime).map(|t| t.timestamp() as u32)
+                            v.and_then(|v| string_to_datetime(v, time_fmt)).map(|t| t.timestamp() as u32)
                         });
                         Ok(DFUInt32Array::from_iter(it).into_series())
                     },
@@ -171,14 +207,26 @@ impl Function for CastFunction {
         Ok(column.resize_constant(input_rows))
     }
 
-    fn num_arguments(&self) -> usize {
-        1
+    /// The optional second argument is the format of the date/time strings, e.g. `%d/%m/%Y`.
+    fn format_arg(columns: &DataColumnsWithField) -> Result<Option<String>> {
+        match columns.get(1).map(|c| c.column()) {
+            None => Ok(None),
+            Some(DataColumn::Constant(DataValue::String(Some(format)), _)) => {
+                Ok(Some(String::from_utf8(format.clone())?))
+            }
+            Some(_) => Err(ErrorCode::BadArguments(
+                "The format of CAST must be a constant string",
+            )),
+        }
     }
 }
 
 impl fmt::Display for CastFunction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "CAST")
+        match self.is_try {
+            true => write!(f, "TRY_CAST"),
+            false => write!(f, "CAST"),
+        }
     }
 }
 
@@ -190,13 +238,16 @@ fn datetime_to_string(date: DateTime<Utc>, fmt: &str) -> String {
 // currently use UTC by default
 // TODO support timezone
 #[inline]
-fn string_to_datetime(date_str: impl AsRef<[u8]>) -> Option<NaiveDateTime> {
+fn string_to_datetime(date_str: impl AsRef<[u8]>, fmt: &str) -> Option<NaiveDateTime> {
     let s = std::str::from_utf8(date_str.as_ref()).ok();
-    s.and_then(|c| NaiveDateTime::parse_from_str(c, "%Y-%m-%d %H:%M:%S").ok())
+    s.and_then(|c| NaiveDateTime::parse_from_str(c, fmt).ok())
 }
 
 #[inline]
-fn string_to_date(date_str: impl AsRef<[u8]>) -> Option<NaiveDate> {
+fn string_to_date(date_str: impl AsRef<[u8]>, fmt: Option<&str>) -> Option<NaiveDate> {
     let s = std::str::from_utf8(date_str.as_ref()).ok();
-    s.and_then(|c| c.parse::<NaiveDate>().ok())
+    match fmt {
+        None => s.and_then(|c| c.parse::<NaiveDate>().ok()),
+        Some(fmt) => s.and_then(|c| NaiveDate::parse_from_str(c, fmt).ok()),
+    }
 }
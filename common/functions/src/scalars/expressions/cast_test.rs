@@ -133,6 +133,32 @@ fn test_cast_function() -> Result<()> {
             expect: Series::new(vec!["2021-03-05 01:01:01", "2021-10-24 10:10:10"]),
             error: "",
         },
+        Test {
+            name: "cast-string-to-date16-with-format-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![
+                Series::new(vec!["05/03/2021", "24/10/2021"]).into(),
+                DataColumn::Constant(DataValue::String(Some(b"%d/%m/%Y".to_vec())), 2),
+            ],
+            column_types: vec![DataType::String, DataType::String],
+            func: CastFunction::create("cast".to_string(), DataType::Date16),
+            expect: Series::new(vec![18691u16, 18924]),
+            error: "",
+        },
+        Test {
+            name: "cast-datetime-to-string-with-format-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![
+                Series::new(vec![1614906061u32, 1635070210]).into(),
+                DataColumn::Constant(DataValue::String(Some(b"%Y%m%d%H%M%S".to_vec())), 2),
+            ],
+            column_types: vec![DataType::DateTime32(None), DataType::String],
+            func: CastFunction::create("cast".to_string(), DataType::String),
+            expect: Series::new(vec!["20210305010101", "20211024101010"]),
+            error: "",
+        },
     ];
 
     for t in tests {
@@ -168,3 +194,23 @@ fn test_cast_function() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_try_cast_function() -> Result<()> {
+    let columns = vec![DataColumnWithField::new(
+        Series::new(vec![18691u16, 18924]).into(),
+        DataField::new("dummy", DataType::Date16, false),
+    )];
+
+    let func = CastFunction::create("cast".to_string(), DataType::Boolean)?;
+    assert!(func.eval(&columns, 2).is_err());
+
+    let func = CastFunction::create_try("try_cast".to_string(), DataType::Boolean)?;
+    assert_eq!("TRY_CAST", format!("{}", func));
+    assert!(func.nullable(&DataSchema::empty())?);
+
+    let result = func.eval(&columns, 2)?;
+    assert_eq!(DataType::Boolean, result.data_type());
+    assert_eq!(2, result.to_array()?.null_count());
+    Ok(())
+}
@@ -18,6 +18,7 @@ use common_datavalues::columns::DataColumn;
 use common_datavalues::prelude::DataColumnsWithField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::Result;
 use dyn_clone::DynClone;
 
@@ -35,6 +36,18 @@ pub trait Function: fmt::Display + Sync + Send + DynClone {
     }
 
     fn return_type(&self, args: &[DataType]) -> Result<DataType>;
+
+    /// The return type with the values of the constant arguments, `constants[i]` is None if the
+    /// i-th argument is not a constant. Only the functions whose return type depends on the
+    /// value of an argument, like the field name of `tupleElement`, override it.
+    fn return_type_with_constants(
+        &self,
+        args: &[DataType],
+        _constants: &[Option<DataValue>],
+    ) -> Result<DataType> {
+        self.return_type(args)
+    }
+
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool>;
     fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn>;
 }
@@ -30,6 +30,7 @@ use crate::scalars::NullableFunction;
 use crate::scalars::OtherFunction;
 use crate::scalars::StringFunction;
 use crate::scalars::ToCastFunction;
+use crate::scalars::TupleFunction;
 use crate::scalars::UdfFunction;
 
 pub type FactoryCreator = Box<dyn Fn(&str) -> Result<Box<dyn Function>> + Send + Sync>;
@@ -39,6 +40,11 @@ pub struct FunctionFeatures {
     pub is_deterministic: bool,
     pub negative_function_name: Option<String>,
     pub is_bool_func: bool,
+    /// Only called by the expressions the planner builds, e.g. with the definition of a
+    /// function created by `CREATE EXTERNAL FUNCTION`, never by the queries.
+    pub is_internal: bool,
+    /// Waits on the network, the expressions calling it are evaluated on the blocking threads.
+    pub is_blocking: bool,
 }
 
 impl FunctionFeatures {
@@ -47,6 +53,8 @@ impl FunctionFeatures {
             is_deterministic: false,
             negative_function_name: None,
             is_bool_func: false,
+            is_internal: false,
+            is_blocking: false,
         }
     }
 
@@ -64,6 +72,16 @@ impl FunctionFeatures {
         self.is_bool_func = true;
         self
     }
+
+    pub fn internal(mut self) -> FunctionFeatures {
+        self.is_internal = true;
+        self
+    }
+
+    pub fn blocking(mut self) -> FunctionFeatures {
+        self.is_blocking = true;
+        self
+    }
 }
 
 pub struct FunctionDescription {
@@ -105,6 +123,7 @@ lazy_static! {
         ConditionalFunction::register(&mut function_factory);
         DateFunction::register(&mut function_factory);
         OtherFunction::register(&mut function_factory);
+        TupleFunction::register(&mut function_factory);
 
         Arc::new(function_factory)
     };
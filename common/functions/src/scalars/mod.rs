@@ -29,6 +29,7 @@ mod logics;
 mod nullables;
 mod others;
 mod strings;
+mod tuples;
 mod udfs;
 
 pub use arithmetics::*;
@@ -46,4 +47,5 @@ pub use logics::*;
 pub use nullables::*;
 pub use others::*;
 pub use strings::*;
+pub use tuples::*;
 pub use udfs::*;
@@ -12,11 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod string_case_test;
+#[cfg(test)]
+mod string_match_test;
 #[cfg(test)]
 mod substring_test;
 
 mod string;
+mod string_case;
+mod string_match;
 mod substring;
 
 pub use string::StringFunction;
+pub use string_case::StringCaseFunction;
+pub use string_match::StringMatchFunction;
 pub use substring::SubstringFunction;
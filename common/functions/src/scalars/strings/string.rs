@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use crate::scalars::function_factory::FunctionFactory;
+use crate::scalars::StringCaseFunction;
+use crate::scalars::StringMatchFunction;
 use crate::scalars::SubstringFunction;
 
 #[derive(Clone)]
@@ -20,6 +22,11 @@ pub struct StringFunction;
 
 impl StringFunction {
     pub fn register(factory: &mut FunctionFactory) {
-        factory.register("substring", SubstringFunction::desc())
+        factory.register("substring", SubstringFunction::desc());
+        factory.register("lower", StringCaseFunction::lower_desc());
+        factory.register("lcase", StringCaseFunction::lower_desc());
+        factory.register("upper", StringCaseFunction::upper_desc());
+        factory.register("ucase", StringCaseFunction::upper_desc());
+        factory.register("match", StringMatchFunction::desc());
     }
 }
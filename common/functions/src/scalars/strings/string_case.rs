@@ -0,0 +1,104 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `LOWER(str)` and `UPPER(str)`, only the ASCII letters of invalid UTF-8 values are converted.
+/// `LOWER` also makes the keys of the case-insensitive collations.
+#[derive(Clone)]
+pub struct StringCaseFunction {
+    display_name: String,
+    upper: bool,
+}
+
+impl StringCaseFunction {
+    pub fn try_create_lower(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(StringCaseFunction {
+            display_name: display_name.to_string(),
+            upper: false,
+        }))
+    }
+
+    pub fn try_create_upper(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(StringCaseFunction {
+            display_name: display_name.to_string(),
+            upper: true,
+        }))
+    }
+
+    pub fn lower_desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create_lower))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    pub fn upper_desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create_upper))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    pub fn convert(value: &[u8], upper: bool) -> Vec<u8> {
+        match (std::str::from_utf8(value), upper) {
+            (Ok(value), false) => value.to_lowercase().into_bytes(),
+            (Ok(value), true) => value.to_uppercase().into_bytes(),
+            (Err(_), false) => value.to_ascii_lowercase(),
+            (Err(_), true) => value.to_ascii_uppercase(),
+        }
+    }
+}
+
+impl Function for StringCaseFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+
+    // The other types are converted to String first, e.g. `lower(1)` is '1'.
+    fn return_type(&self, _args: &[DataType]) -> Result<DataType> {
+        Ok(DataType::String)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let series = columns[0]
+            .column()
+            .to_minimal_array()?
+            .cast_with_type(&DataType::String)?;
+        let upper = self.upper;
+        let result = series
+            .string()?
+            .apply(|value| Cow::Owned(Self::convert(value, upper)));
+        let result: DataColumn = result.into();
+        Ok(result.resize_constant(input_rows))
+    }
+}
+
+impl fmt::Display for StringCaseFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
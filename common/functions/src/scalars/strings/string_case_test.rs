@@ -0,0 +1,86 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::Function;
+use crate::scalars::StringCaseFunction;
+
+#[test]
+fn test_string_case_function() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        display: &'static str,
+        columns: Vec<DataColumn>,
+        expect: DataColumn,
+        func: Box<dyn Function>,
+    }
+
+    let field = DataField::new("a", DataType::String, true);
+
+    let tests = vec![
+        Test {
+            name: "lower-passed",
+            display: "LOWER",
+            columns: vec![Series::new(vec![Some("AbC"), None, Some("ÀÉÎ straße")]).into()],
+            func: StringCaseFunction::try_create_lower("lower")?,
+            expect: Series::new(vec![Some("abc"), None, Some("àéî straße")]).into(),
+        },
+        Test {
+            name: "upper-passed",
+            display: "UCASE",
+            columns: vec![Series::new(vec![Some("AbC"), None, Some("àéî")]).into()],
+            func: StringCaseFunction::try_create_upper("ucase")?,
+            expect: Series::new(vec![Some("ABC"), None, Some("ÀÉÎ")]).into(),
+        },
+        Test {
+            name: "lower-constant-passed",
+            display: "LOWER",
+            columns: vec![DataColumn::Constant(
+                DataValue::String(Some(b"HeLLo".to_vec())),
+                3,
+            )],
+            func: StringCaseFunction::try_create_lower("lower")?,
+            expect: DataColumn::Constant(DataValue::String(Some(b"hello".to_vec())), 3),
+        },
+        Test {
+            name: "lower-number-passed",
+            display: "LOWER",
+            columns: vec![Series::new(vec![1_i64, 20]).into()],
+            func: StringCaseFunction::try_create_lower("lower")?,
+            expect: Series::new(vec!["1", "20"]).into(),
+        },
+    ];
+
+    for t in tests {
+        let func = t.func;
+        let rows = t.columns[0].len();
+        let columns: Vec<DataColumnWithField> = t
+            .columns
+            .iter()
+            .map(|c| DataColumnWithField::new(c.clone(), field.clone()))
+            .collect();
+
+        let actual_display = format!("{}", func);
+        assert_eq!(t.display, actual_display, "{}", t.name);
+
+        let v = func.eval(&columns, rows)?;
+        let expect_type = func.return_type(&[DataType::String])?;
+        assert_eq!(expect_type, v.data_type(), "{}", t.name);
+        assert_eq!(t.expect.to_values()?, v.to_values()?, "{}", t.name);
+    }
+    Ok(())
+}
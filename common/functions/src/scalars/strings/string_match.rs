@@ -0,0 +1,117 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `MATCH(str, 'query')`, true if the string contains all the tokens of the query.
+/// The tokens are the lowercase runs of alphanumeric characters, which are also the tokens
+/// kept by the inverted indexes of the FUSE tables to prune the blocks.
+#[derive(Clone)]
+pub struct StringMatchFunction {
+    display_name: String,
+}
+
+impl StringMatchFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(StringMatchFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().bool_function())
+    }
+
+    pub fn tokenize(text: &[u8]) -> Vec<String> {
+        String::from_utf8_lossy(text)
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.to_lowercase())
+            .collect()
+    }
+
+    /// The query matches nothing if it has no tokens.
+    pub fn matches(text: &[u8], query_tokens: &[String]) -> bool {
+        if query_tokens.is_empty() {
+            return false;
+        }
+        let tokens: HashSet<String> = Self::tokenize(text).into_iter().collect();
+        query_tokens.iter().all(|token| tokens.contains(token))
+    }
+}
+
+impl Function for StringMatchFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        if args[1] != DataType::String {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "The query of MATCH must be a String, but got {:?}",
+                args[1]
+            )));
+        }
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let query_tokens = match columns[1].column().try_get(0)? {
+            DataValue::String(Some(query)) => Self::tokenize(&query),
+            DataValue::String(None) => vec![],
+            other => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The query of MATCH must be a constant String, but got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let series = columns[0]
+            .column()
+            .to_minimal_array()?
+            .cast_with_type(&DataType::String)?;
+        let result: DFBooleanArray = series
+            .string()?
+            .into_iter()
+            .map(|value| value.map(|value| Self::matches(value, &query_tokens)))
+            .collect();
+        let result: DataColumn = result.into();
+        Ok(result.resize_constant(input_rows))
+    }
+}
+
+impl fmt::Display for StringMatchFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::Function;
+use crate::scalars::StringMatchFunction;
+
+#[test]
+fn test_string_match_function() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        columns: Vec<DataColumn>,
+        expect: DataColumn,
+    }
+
+    let field = DataField::new("a", DataType::String, true);
+    let query = |query: &str| DataColumn::Constant(DataValue::String(Some(query.into())), 3);
+
+    let tests = vec![
+        Test {
+            name: "match-passed",
+            columns: vec![
+                Series::new(vec![
+                    Some("GET /api/v1/users 200"),
+                    None,
+                    Some("POST /api/v1/users 500"),
+                ])
+                .into(),
+                query("USERS 500"),
+            ],
+            expect: Series::new(vec![Some(false), None, Some(true)]).into(),
+        },
+        Test {
+            name: "match-whole-token-passed",
+            columns: vec![
+                Series::new(vec!["error: timeout", "timeouts", "time out"]).into(),
+                query("timeout"),
+            ],
+            expect: Series::new(vec![true, false, false]).into(),
+        },
+        Test {
+            name: "match-empty-query-passed",
+            columns: vec![Series::new(vec!["a", "b", "c"]).into(), query(" -- ")],
+            expect: Series::new(vec![false, false, false]).into(),
+        },
+    ];
+
+    let func = StringMatchFunction::try_create("match")?;
+    assert_eq!("MATCH", format!("{}", func));
+    for t in tests {
+        let columns: Vec<DataColumnWithField> = t
+            .columns
+            .iter()
+            .map(|c| DataColumnWithField::new(c.clone(), field.clone()))
+            .collect();
+
+        let v = func.eval(&columns, 3)?;
+        let expect_type = func.return_type(&[DataType::String, DataType::String])?;
+        assert_eq!(expect_type, v.data_type(), "{}", t.name);
+        assert_eq!(t.expect.to_values()?, v.to_values()?, "{}", t.name);
+    }
+
+    assert_eq!(
+        StringMatchFunction::tokenize("Hello, Wörld_42!".as_bytes()),
+        vec!["hello", "wörld", "42"]
+    );
+    Ok(())
+}
@@ -0,0 +1,159 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// Builds a tuple from its arguments:
+/// `tuple(x, y)` names the fields by their positions, "1", "2" and so on,
+/// `named_struct('a', x, 'b', y)` takes the names from the constant arguments.
+#[derive(Clone)]
+pub struct TupleCreateFunction {
+    display_name: String,
+    named: bool,
+}
+
+impl TupleCreateFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(TupleCreateFunction {
+            display_name: display_name.to_string(),
+            named: false,
+        }))
+    }
+
+    pub fn try_create_named(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(TupleCreateFunction {
+            display_name: display_name.to_string(),
+            named: true,
+        }))
+    }
+
+    // Not deterministic on purpose: the folded constant would lose the field names.
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default())
+    }
+
+    pub fn desc_named() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create_named))
+            .features(FunctionFeatures::default())
+    }
+
+    fn field_name(&self, name: Option<&DataValue>) -> Result<String> {
+        match name {
+            Some(DataValue::String(Some(name))) => Ok(String::from_utf8_lossy(name).to_string()),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "The field names of {} must be constant strings",
+                self.display_name
+            ))),
+        }
+    }
+
+    fn fields(&self, args: &[DataType], constants: &[Option<DataValue>]) -> Result<Vec<DataField>> {
+        if !self.named {
+            return Ok(args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| DataField::new(&(i + 1).to_string(), arg.clone(), true))
+                .collect());
+        }
+
+        if args.len() % 2 != 0 {
+            return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                "{} expect pairs of field name and value, but got {} arguments",
+                self.display_name,
+                args.len()
+            )));
+        }
+
+        args.chunks(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let name = self.field_name(constants.get(i * 2).and_then(|v| v.as_ref()))?;
+                Ok(DataField::new(&name, pair[1].clone(), true))
+            })
+            .collect()
+    }
+}
+
+impl Function for TupleCreateFunction {
+    fn name(&self) -> &str {
+        "TupleCreateFunction"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        self.return_type_with_constants(args, &[])
+    }
+
+    fn return_type_with_constants(
+        &self,
+        args: &[DataType],
+        constants: &[Option<DataValue>],
+    ) -> Result<DataType> {
+        Ok(DataType::Struct(self.fields(args, constants)?))
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let args = columns
+            .iter()
+            .map(|c| c.data_type().clone())
+            .collect::<Vec<_>>();
+        let constants = columns
+            .iter()
+            .map(|c| match c.column() {
+                DataColumn::Constant(v, _) => Some(v.clone()),
+                DataColumn::Array(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let fields = self.fields(&args, &constants)?;
+
+        let values = match self.named {
+            true => columns.iter().skip(1).step_by(2).collect::<Vec<_>>(),
+            false => columns.iter().collect::<Vec<_>>(),
+        };
+
+        let mut arrays = Vec::with_capacity(values.len());
+        for value in values {
+            arrays.push(value.column().to_array()?.get_array_ref());
+        }
+
+        let arrow_fields = fields.iter().map(|f| f.to_arrow()).collect();
+        let array = StructArray::from_data(ArrowType::Struct(arrow_fields), arrays, None);
+        let array: DFStructArray = array.into();
+        Ok(array.into_series().into())
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((1, usize::MAX))
+    }
+}
+
+impl fmt::Display for TupleCreateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
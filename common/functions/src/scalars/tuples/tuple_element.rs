@@ -0,0 +1,139 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// Extracts a field of a tuple, `tupleElement(t, 'name')` by its name or
+/// `tupleElement(t, 1)` by its 1-based position. `t.name` is planned into it.
+#[derive(Clone)]
+pub struct TupleElementFunction {
+    display_name: String,
+}
+
+impl TupleElementFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(TupleElementFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    /// The 0-based position of the field selected by `element`.
+    fn position(&self, fields: &[DataField], element: Option<&DataValue>) -> Result<usize> {
+        let position = match element {
+            Some(DataValue::String(Some(name))) => {
+                let name = String::from_utf8_lossy(name);
+                fields.iter().position(|f| f.name() == name.as_ref())
+            }
+            Some(v) if is_integer(&v.data_type()) && !v.is_null() => {
+                let index = v.as_u64()? as usize;
+                if index >= 1 && index <= fields.len() {
+                    Some(index - 1)
+                } else {
+                    None
+                }
+            }
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The second argument of {} must be a constant field name or index",
+                    self.display_name
+                )))
+            }
+        };
+
+        position.ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "Tuple has no field {}, the fields are: {}",
+                element.map(|v| v.to_string()).unwrap_or_default(),
+                fields
+                    .iter()
+                    .map(|f| f.name().as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+    }
+
+    fn fields<'a>(&self, arg: &'a DataType) -> Result<&'a [DataField]> {
+        match arg {
+            DataType::Struct(fields) => Ok(fields),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "The first argument of {} must be a tuple, but got {:?}",
+                self.display_name, other
+            ))),
+        }
+    }
+}
+
+impl Function for TupleElementFunction {
+    fn name(&self) -> &str {
+        "TupleElementFunction"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        self.return_type_with_constants(args, &[])
+    }
+
+    fn return_type_with_constants(
+        &self,
+        args: &[DataType],
+        constants: &[Option<DataValue>],
+    ) -> Result<DataType> {
+        let fields = self.fields(&args[0])?;
+        let position = self.position(fields, constants.get(1).and_then(|v| v.as_ref()))?;
+        Ok(fields[position].data_type().clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let fields = self.fields(columns[0].data_type())?;
+        let element = columns[1].column().try_get(0)?;
+        let position = self.position(fields, Some(&element))?;
+
+        let array = columns[0].column().to_array()?;
+        let array = DFStructArray::from_arrow_array(array.get_array_ref().as_ref());
+        match array.field_by_index(position) {
+            Some(series) => Ok(series.into()),
+            None => Err(ErrorCode::LogicalError(format!(
+                "Tuple field {} is missing from the array",
+                position + 1
+            ))),
+        }
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+}
+
+impl fmt::Display for TupleElementFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
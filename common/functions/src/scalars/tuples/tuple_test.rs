@@ -0,0 +1,209 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::Function;
+use crate::scalars::TupleCreateFunction;
+use crate::scalars::TupleElementFunction;
+
+fn eval(
+    func: &dyn Function,
+    columns: Vec<(DataColumn, DataType)>,
+) -> Result<(DataType, DataColumn)> {
+    let args = columns.iter().map(|(_, t)| t.clone()).collect::<Vec<_>>();
+    let constants = columns
+        .iter()
+        .map(|(c, _)| match c {
+            DataColumn::Constant(v, _) => Some(v.clone()),
+            DataColumn::Array(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let rows = columns[0].0.len();
+    let columns = columns
+        .into_iter()
+        .enumerate()
+        .map(|(i, (c, t))| {
+            DataColumnWithField::new(c, DataField::new(&format!("c{}", i), t, false))
+        })
+        .collect::<Vec<_>>();
+
+    let return_type = func.return_type_with_constants(&args, &constants)?;
+    let result = func.eval(&columns, rows)?;
+    Ok((return_type, result))
+}
+
+#[test]
+fn test_tuple_create_function() -> Result<()> {
+    let func = TupleCreateFunction::try_create("tuple")?;
+    let (return_type, result) = eval(func.as_ref(), vec![
+        (Series::new(vec![1_i64, 2]).into(), DataType::Int64),
+        (Series::new(vec!["a", "b"]).into(), DataType::String),
+    ])?;
+
+    let expect_type = DataType::Struct(vec![
+        DataField::new("1", DataType::Int64, true),
+        DataField::new("2", DataType::String, true),
+    ]);
+    assert_eq!(expect_type, return_type);
+    assert_eq!(expect_type, result.data_type());
+    assert_eq!("tuple", format!("{}", func));
+    assert_eq!(
+        vec![
+            DataValue::Struct(vec![
+                DataValue::Int64(Some(1)),
+                DataValue::String(Some(b"a".to_vec()))
+            ]),
+            DataValue::Struct(vec![
+                DataValue::Int64(Some(2)),
+                DataValue::String(Some(b"b".to_vec()))
+            ]),
+        ],
+        result.to_values()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_named_struct_function() -> Result<()> {
+    let func = TupleCreateFunction::try_create_named("named_struct")?;
+    let name = |n: &str| {
+        (
+            DataColumn::Constant(DataValue::String(Some(n.as_bytes().to_vec())), 2),
+            DataType::String,
+        )
+    };
+    let (return_type, result) = eval(func.as_ref(), vec![
+        name("id"),
+        (Series::new(vec![1_u32, 2]).into(), DataType::UInt32),
+        name("score"),
+        (Series::new(vec![0.5_f64, 1.5]).into(), DataType::Float64),
+    ])?;
+
+    let expect_type = DataType::Struct(vec![
+        DataField::new("id", DataType::UInt32, true),
+        DataField::new("score", DataType::Float64, true),
+    ]);
+    assert_eq!(expect_type, return_type);
+    assert_eq!(expect_type, result.data_type());
+
+    // The names are only known from the constant arguments.
+    let types = vec![DataType::String, DataType::UInt32];
+    let err = func.return_type(&types).unwrap_err();
+    assert_eq!(
+        "Code: 6, displayText = The field names of named_struct must be constant strings.",
+        err.to_string()
+    );
+
+    let err = eval(func.as_ref(), vec![name("id")]).unwrap_err();
+    assert_eq!(
+        "Code: 28, displayText = named_struct expect pairs of field name and value, but got 1 arguments.",
+        err.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_tuple_element_function() -> Result<()> {
+    let tuple = TupleCreateFunction::try_create_named("named_struct")?;
+    let (tuple_type, tuple_column) = eval(tuple.as_ref(), vec![
+        (
+            DataColumn::Constant(DataValue::String(Some(b"a".to_vec())), 3),
+            DataType::String,
+        ),
+        (Series::new(vec![1_i64, 2, 3]).into(), DataType::Int64),
+        (
+            DataColumn::Constant(DataValue::String(Some(b"b".to_vec())), 3),
+            DataType::String,
+        ),
+        (Series::new(vec!["x", "y", "z"]).into(), DataType::String),
+    ])?;
+
+    struct Test {
+        name: &'static str,
+        element: DataValue,
+        expect_type: DataType,
+        expect: Vec<DataValue>,
+        error: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "by-name-passed",
+            element: DataValue::String(Some(b"b".to_vec())),
+            expect_type: DataType::String,
+            expect: vec![
+                DataValue::String(Some(b"x".to_vec())),
+                DataValue::String(Some(b"y".to_vec())),
+                DataValue::String(Some(b"z".to_vec())),
+            ],
+            error: "",
+        },
+        Test {
+            name: "by-index-passed",
+            element: DataValue::UInt64(Some(1)),
+            expect_type: DataType::Int64,
+            expect: vec![
+                DataValue::Int64(Some(1)),
+                DataValue::Int64(Some(2)),
+                DataValue::Int64(Some(3)),
+            ],
+            error: "",
+        },
+        Test {
+            name: "unknown-name",
+            element: DataValue::String(Some(b"c".to_vec())),
+            expect_type: DataType::Null,
+            expect: vec![],
+            error: "Code: 6, displayText = Tuple has no field c, the fields are: a, b.",
+        },
+        Test {
+            name: "index-out-of-range",
+            element: DataValue::UInt64(Some(3)),
+            expect_type: DataType::Null,
+            expect: vec![],
+            error: "Code: 6, displayText = Tuple has no field 3, the fields are: a, b.",
+        },
+    ];
+
+    let func = TupleElementFunction::try_create("tupleElement")?;
+    for t in tests {
+        let result = eval(func.as_ref(), vec![
+            (tuple_column.clone(), tuple_type.clone()),
+            (
+                DataColumn::Constant(t.element.clone(), 3),
+                t.element.data_type(),
+            ),
+        ]);
+        match result {
+            Ok((return_type, column)) => {
+                assert_eq!(t.expect_type, return_type, "{}", t.name);
+                assert_eq!(t.expect_type, column.data_type(), "{}", t.name);
+                assert_eq!(t.expect, column.to_values()?, "{}", t.name);
+            }
+            Err(e) => assert_eq!(t.error, e.to_string(), "{}", t.name),
+        }
+    }
+
+    let err = func
+        .return_type(&[DataType::Int64, DataType::UInt64])
+        .unwrap_err();
+    assert_eq!(
+        "Code: 7, displayText = The first argument of tupleElement must be a tuple, but got Int64.",
+        err.to_string()
+    );
+    Ok(())
+}
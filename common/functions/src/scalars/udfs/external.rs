@@ -0,0 +1,323 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+#[cfg(feature = "external-function")]
+use std::time::Duration;
+
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::*;
+use common_datavalues::DataSchema;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::json;
+use serde_json::Value as JsonValue;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// How to call the endpoint of a function created by `CREATE EXTERNAL FUNCTION`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ExternalFunctionDefinition {
+    pub endpoint: String,
+    pub arg_types: Vec<DataType>,
+    /// Timeout of one request, in milliseconds.
+    pub timeout_ms: u64,
+    /// How many times a failed request is retried.
+    pub max_retries: u64,
+    /// Max number of rows sent in one request.
+    pub max_batch_rows: u64,
+}
+
+/// Calls the HTTP endpoint of a function created by `CREATE EXTERNAL FUNCTION`.
+///
+/// The planner rewrites `f(a, b)` into `external_float64(f, a, b)`, where the first
+/// argument is a literal holding the JSON encoded `ExternalFunctionDefinition`, named
+/// after the function. The queries can not call `external_float64` directly, the endpoint
+/// always comes from a function created by `CREATE EXTERNAL FUNCTION`.
+///
+/// The requests block, the expressions calling the function are evaluated on the blocking
+/// threads. The rows are posted to the endpoint in batches:
+///
+/// request:  `{"data": [[0, a0, b0], [1, a1, b1], ...]}`
+/// response: `{"data": [[0, r0], [1, r1], ...]}`
+///
+/// where the first value of each row is its number in the batch.
+#[derive(Clone)]
+pub struct ExternalFunction {
+    display_name: String,
+    return_type: DataType,
+}
+
+impl ExternalFunction {
+    pub fn try_create(display_name: &str, return_type: DataType) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ExternalFunction {
+            display_name: display_name.to_string(),
+            return_type,
+        }))
+    }
+
+    pub fn desc(return_type: DataType) -> FunctionDescription {
+        FunctionDescription::creator(Box::new(move |display_name| {
+            Self::try_create(display_name, return_type.clone())
+        }))
+        .features(FunctionFeatures::default().internal().blocking())
+    }
+
+    /// The name of the function which calls an endpoint returning `return_type`.
+    pub fn function_name(return_type: &DataType) -> Result<&'static str> {
+        match return_type {
+            DataType::Boolean => Ok("external_boolean"),
+            DataType::Int64 => Ok("external_int64"),
+            DataType::Float64 => Ok("external_float64"),
+            DataType::String => Ok("external_string"),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "Unsupported external function return type {}, expect Boolean, Int64, Float64 or String",
+                other
+            ))),
+        }
+    }
+
+    pub fn build_request(args: &[Series], start: usize, end: usize) -> Result<JsonValue> {
+        let mut rows = Vec::with_capacity(end - start);
+        for row in start..end {
+            let mut values = Vec::with_capacity(args.len() + 1);
+            values.push(json!(row - start));
+            for arg in args {
+                values.push(Self::to_json(arg.try_get(row)?)?);
+            }
+            rows.push(JsonValue::Array(values));
+        }
+        Ok(json!({ "data": rows }))
+    }
+
+    pub fn parse_response(
+        response: &JsonValue,
+        rows: usize,
+        return_type: &DataType,
+    ) -> Result<Vec<DataValue>> {
+        let data = match response.get("data").and_then(|data| data.as_array()) {
+            Some(data) if data.len() == rows => data,
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "External function response must have a data array of {} rows",
+                    rows
+                )))
+            }
+        };
+
+        let mut values = vec![Self::from_json(&JsonValue::Null, return_type)?; rows];
+        for row in data {
+            let (index, value) = match row.as_array().map(|row| row.as_slice()) {
+                Some([index, value]) => (index.as_u64(), value),
+                _ => (None, &JsonValue::Null),
+            };
+            match index {
+                Some(index) if (index as usize) < rows => {
+                    values[index as usize] = Self::from_json(value, return_type)?
+                }
+                _ => {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "Invalid row of external function response: {}",
+                        row
+                    )))
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    fn to_json(value: DataValue) -> Result<JsonValue> {
+        Ok(match value {
+            DataValue::Null => JsonValue::Null,
+            DataValue::Boolean(v) => json!(v),
+            DataValue::Int8(v) => json!(v),
+            DataValue::Int16(v) => json!(v),
+            DataValue::Int32(v) => json!(v),
+            DataValue::Int64(v) => json!(v),
+            DataValue::UInt8(v) => json!(v),
+            DataValue::UInt16(v) => json!(v),
+            DataValue::UInt32(v) => json!(v),
+            DataValue::UInt64(v) => json!(v),
+            DataValue::Float32(v) => json!(v),
+            DataValue::Float64(v) => json!(v),
+            DataValue::String(v) => json!(v.map(|v| String::from_utf8_lossy(&v).to_string())),
+            other => {
+                return Err(ErrorCode::IllegalDataType(format!(
+                    "Unsupported external function argument type {}",
+                    other.data_type()
+                )))
+            }
+        })
+    }
+
+    fn from_json(value: &JsonValue, data_type: &DataType) -> Result<DataValue> {
+        let data_value = match (value, data_type) {
+            (JsonValue::Null, DataType::Boolean) => Some(DataValue::Boolean(None)),
+            (JsonValue::Null, DataType::Int64) => Some(DataValue::Int64(None)),
+            (JsonValue::Null, DataType::Float64) => Some(DataValue::Float64(None)),
+            (JsonValue::Null, DataType::String) => Some(DataValue::String(None)),
+            (v, DataType::Boolean) => v.as_bool().map(|v| DataValue::Boolean(Some(v))),
+            (v, DataType::Int64) => v.as_i64().map(|v| DataValue::Int64(Some(v))),
+            (v, DataType::Float64) => v.as_f64().map(|v| DataValue::Float64(Some(v))),
+            (JsonValue::String(v), DataType::String) => {
+                Some(DataValue::String(Some(v.clone().into_bytes())))
+            }
+            (v, DataType::String) => Some(DataValue::String(Some(v.to_string().into_bytes()))),
+            _ => None,
+        };
+
+        data_value.ok_or_else(|| {
+            ErrorCode::BadBytes(format!(
+                "Cannot convert {} of external function response to {}",
+                value, data_type
+            ))
+        })
+    }
+
+    #[cfg(feature = "external-function")]
+    fn call(
+        &self,
+        name: &str,
+        definition: &ExternalFunctionDefinition,
+        request: &JsonValue,
+    ) -> Result<JsonValue> {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_millis(definition.timeout_ms))
+            .build();
+
+        let mut attempt = 0;
+        loop {
+            let error = match agent.post(&definition.endpoint).send_json(request.clone()) {
+                Ok(response) => {
+                    return response.into_json::<JsonValue>().map_err(|e| {
+                        ErrorCode::BadBytes(format!(
+                            "Invalid response of external function {}: {}",
+                            name, e
+                        ))
+                    })
+                }
+                // Client errors will not go away by retrying.
+                Err(ureq::Error::Status(code, _)) if (400..500).contains(&code) && code != 429 => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "External function {} failed with status {}",
+                        name, code
+                    )))
+                }
+                Err(e) => e,
+            };
+
+            if attempt >= definition.max_retries {
+                return Err(ErrorCode::CannotConnectNode(format!(
+                    "External function {} failed after {} attempts: {}",
+                    name,
+                    attempt + 1,
+                    error
+                )));
+            }
+
+            std::thread::sleep(Duration::from_millis(100 << attempt.min(6)));
+            attempt += 1;
+        }
+    }
+
+    #[cfg(not(feature = "external-function"))]
+    fn call(&self, name: &str, _: &ExternalFunctionDefinition, _: &JsonValue) -> Result<JsonValue> {
+        Err(ErrorCode::UnImplement(format!(
+            "External function {} is disabled, built without the external-function feature",
+            name
+        )))
+    }
+}
+
+impl Function for ExternalFunction {
+    fn name(&self) -> &str {
+        "ExternalFunction"
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((1, usize::MAX))
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        if args[0] != DataType::String {
+            return Err(ErrorCode::BadArguments(format!(
+                "The first argument of function {} must be an external function definition",
+                self.display_name
+            )));
+        }
+        Ok(self.return_type.clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let (name, definition) = match columns[0].column() {
+            DataColumn::Constant(DataValue::String(Some(definition)), _) => (
+                columns[0].field().name(),
+                serde_json::from_slice::<ExternalFunctionDefinition>(definition)?,
+            ),
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                "The first argument of function {} must be a constant external function definition",
+                self.display_name
+            )))
+            }
+        };
+
+        if definition.arg_types.len() != columns.len() - 1 {
+            return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                "Function {} expect to have {} arguments, but got {}",
+                name,
+                definition.arg_types.len(),
+                columns.len() - 1
+            )));
+        }
+
+        let args = columns[1..]
+            .iter()
+            .zip(definition.arg_types.iter())
+            .map(|(c, ty)| c.column().to_array()?.cast_with_type(ty))
+            .collect::<Result<Vec<_>>>()?;
+
+        let batch_rows = definition.max_batch_rows.max(1) as usize;
+        let mut values = Vec::with_capacity(input_rows);
+        let mut start = 0;
+        while start < input_rows {
+            let end = input_rows.min(start + batch_rows);
+            let request = Self::build_request(&args, start, end)?;
+            let response = self.call(name, &definition, &request)?;
+            values.extend(Self::parse_response(
+                &response,
+                end - start,
+                &self.return_type,
+            )?);
+            start = end;
+        }
+
+        let series = DataValue::try_into_data_array(&values, &self.return_type)?;
+        Ok(series.into())
+    }
+}
+
+impl fmt::Display for ExternalFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
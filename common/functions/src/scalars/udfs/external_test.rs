@@ -0,0 +1,98 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use serde_json::json;
+
+use crate::scalars::*;
+
+#[test]
+fn test_external_function_request() -> Result<()> {
+    let args = vec![
+        Series::new(vec![Some(1i64), None, Some(3)]),
+        Series::new(vec!["a", "b", "c"]),
+    ];
+
+    let request = ExternalFunction::build_request(&args, 1, 3)?;
+    assert_eq!(request, json!({"data": [[0, null, "b"], [1, 3, "c"]]}));
+
+    Ok(())
+}
+
+#[test]
+fn test_external_function_response() -> Result<()> {
+    // The rows may come back in any order.
+    let response = json!({"data": [[1, 0.5], [0, null]]});
+    let values = ExternalFunction::parse_response(&response, 2, &DataType::Float64)?;
+    assert_eq!(values, vec![
+        DataValue::Float64(None),
+        DataValue::Float64(Some(0.5))
+    ]);
+
+    let response = json!({"data": [[0, "x"]]});
+    let result = ExternalFunction::parse_response(&response, 2, &DataType::String);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Code: 46, displayText = External function response must have a data array of 2 rows."
+    );
+
+    let response = json!({"data": [[0, "x"], [5, "y"]]});
+    let result = ExternalFunction::parse_response(&response, 2, &DataType::String);
+    assert!(result.is_err());
+
+    let response = json!({"data": [[0, "x"]]});
+    let result = ExternalFunction::parse_response(&response, 1, &DataType::Int64);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "external-function")]
+#[test]
+fn test_external_function_unreachable() -> Result<()> {
+    let definition = ExternalFunctionDefinition {
+        endpoint: "http://127.0.0.1:1/score".to_string(),
+        arg_types: vec![DataType::Int64],
+        timeout_ms: 1000,
+        max_retries: 0,
+        max_batch_rows: 100,
+    };
+    let definition = DataColumnWithField::new(
+        DataColumn::Constant(DataValue::String(Some(serde_json::to_vec(&definition)?)), 2),
+        DataField::new("score", DataType::String, false),
+    );
+    let arg = DataColumnWithField::new(
+        Series::new(vec![1i64, 2]).into(),
+        DataField::new("a", DataType::Int64, false),
+    );
+
+    let func = FunctionFactory::instance().get("external_float64")?;
+    assert_eq!(
+        func.return_type(&[DataType::String, DataType::Int64])?,
+        DataType::Float64
+    );
+
+    let result = func.eval(&[definition, arg], 2);
+    assert_eq!(
+        result.unwrap_err().code(),
+        common_exception::ErrorCode::CannotConnectNode("").code()
+    );
+
+    Ok(())
+}
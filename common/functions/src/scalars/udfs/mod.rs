@@ -15,25 +15,36 @@
 #[cfg(test)]
 mod database_test;
 #[cfg(test)]
+mod external_test;
+#[cfg(test)]
 mod to_type_name_test;
 #[cfg(test)]
 mod udf_example_test;
 #[cfg(test)]
 mod version_test;
+#[cfg(all(test, feature = "wasm"))]
+mod wasm_test;
 
 mod crash_me;
 mod database;
 mod exists;
+mod external;
 mod sleep;
 mod to_type_name;
 mod udf;
 mod udf_example;
 mod version;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 pub use crash_me::CrashMeFunction;
 pub use database::DatabaseFunction;
+pub use external::ExternalFunction;
+pub use external::ExternalFunctionDefinition;
 pub use sleep::SleepFunction;
 pub use to_type_name::ToTypeNameFunction;
 pub use udf::UdfFunction;
 pub use udf_example::UdfExampleFunction;
 pub use version::VersionFunction;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmFunction;
@@ -12,14 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_datavalues::DataType;
+
 use crate::scalars::function_factory::FunctionFactory;
 use crate::scalars::udfs::exists::ExistsFunction;
 use crate::scalars::CrashMeFunction;
 use crate::scalars::DatabaseFunction;
+use crate::scalars::ExternalFunction;
 use crate::scalars::SleepFunction;
 use crate::scalars::ToTypeNameFunction;
 use crate::scalars::UdfExampleFunction;
 use crate::scalars::VersionFunction;
+#[cfg(feature = "wasm")]
+use crate::scalars::WasmFunction;
 
 #[derive(Clone)]
 pub struct UdfFunction;
@@ -33,5 +38,23 @@ impl UdfFunction {
         factory.register("sleep", SleepFunction::desc());
         factory.register("crashme", CrashMeFunction::desc());
         factory.register("exists", ExistsFunction::desc());
+        factory.register(
+            "external_boolean",
+            ExternalFunction::desc(DataType::Boolean),
+        );
+        factory.register("external_int64", ExternalFunction::desc(DataType::Int64));
+        factory.register(
+            "external_float64",
+            ExternalFunction::desc(DataType::Float64),
+        );
+        factory.register("external_string", ExternalFunction::desc(DataType::String));
+
+        #[cfg(feature = "wasm")]
+        {
+            factory.register("wasm_i32", WasmFunction::desc(DataType::Int32));
+            factory.register("wasm_i64", WasmFunction::desc(DataType::Int64));
+            factory.register("wasm_f32", WasmFunction::desc(DataType::Float32));
+            factory.register("wasm_f64", WasmFunction::desc(DataType::Float64));
+        }
     }
 }
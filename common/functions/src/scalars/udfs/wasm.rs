@@ -0,0 +1,301 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::*;
+use common_datavalues::DataSchema;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use lazy_static::lazy_static;
+use wasmtime::Config;
+use wasmtime::Engine;
+use wasmtime::Instance;
+use wasmtime::Module;
+use wasmtime::Store;
+use wasmtime::Val;
+use wasmtime::ValType;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// Every row may spend this much fuel before the call is aborted, so that a looping
+/// module can not hang the query.
+const FUEL_PER_ROW: u64 = 1_000_000;
+
+lazy_static! {
+    static ref WASM_ENGINE: Engine = {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        Engine::new(&config).expect("create wasm engine")
+    };
+    static ref WASM_MODULES: Mutex<HashMap<Vec<u8>, Module>> = Mutex::new(HashMap::new());
+}
+
+/// Calls a function exported by a WASM module created by `CREATE FUNCTION f LANGUAGE wasm`.
+///
+/// The planner rewrites `f(a, b)` into `wasm_i64(f, a, b)`, where the first argument is a
+/// literal holding the module, named after the exported function. There is one function
+/// per WASM value type, which decides the return type.
+///
+/// A function of a tenant namespace, `CREATE FUNCTION ns.f`, calls the export `f`.
+#[derive(Clone)]
+pub struct WasmFunction {
+    display_name: String,
+    return_type: DataType,
+}
+
+impl WasmFunction {
+    pub fn try_create(display_name: &str, return_type: DataType) -> Result<Box<dyn Function>> {
+        Ok(Box::new(WasmFunction {
+            display_name: display_name.to_string(),
+            return_type,
+        }))
+    }
+
+    pub fn desc(return_type: DataType) -> FunctionDescription {
+        FunctionDescription::creator(Box::new(move |display_name| {
+            Self::try_create(display_name, return_type.clone())
+        }))
+        .features(FunctionFeatures::default().deterministic())
+    }
+
+    /// The name of the function which calls an export returning `return_type`.
+    pub fn function_name(return_type: &DataType) -> Result<&'static str> {
+        match return_type {
+            DataType::Int32 => Ok("wasm_i32"),
+            DataType::Int64 => Ok("wasm_i64"),
+            DataType::Float32 => Ok("wasm_f32"),
+            DataType::Float64 => Ok("wasm_f64"),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "Unsupported WASM return type {}",
+                other
+            ))),
+        }
+    }
+
+    /// Compiles the module and returns the argument and return types of the export `name`.
+    pub fn signature(module: &[u8], name: &str) -> Result<(Vec<DataType>, DataType)> {
+        let module = Self::compile(module)?;
+        if module.imports().next().is_some() {
+            return Err(ErrorCode::BadArguments(
+                "WASM functions are sandboxed and can not import anything",
+            ));
+        }
+
+        let ty = match module.get_export(name).as_ref().and_then(|e| e.func()) {
+            Some(ty) => ty.clone(),
+            None => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "WASM module does not export a function named {}",
+                    name
+                )))
+            }
+        };
+
+        let args = ty
+            .params()
+            .map(|param| Self::to_data_type(&param))
+            .collect::<Result<Vec<_>>>()?;
+        let results = ty.results().collect::<Vec<_>>();
+        if results.len() != 1 {
+            return Err(ErrorCode::BadArguments(format!(
+                "WASM function {} must return exactly one value, but returns {}",
+                name,
+                results.len()
+            )));
+        }
+
+        Ok((args, Self::to_data_type(&results[0])?))
+    }
+
+    fn compile(module: &[u8]) -> Result<Module> {
+        let mut modules = WASM_MODULES.lock();
+        if let Some(compiled) = modules.get(module) {
+            return Ok(compiled.clone());
+        }
+
+        let compiled = Module::new(&WASM_ENGINE, module)
+            .map_err(|e| ErrorCode::BadArguments(format!("Invalid WASM module: {}", e)))?;
+        modules.insert(module.to_vec(), compiled.clone());
+        Ok(compiled)
+    }
+
+    fn to_data_type(ty: &ValType) -> Result<DataType> {
+        match ty {
+            ValType::I32 => Ok(DataType::Int32),
+            ValType::I64 => Ok(DataType::Int64),
+            ValType::F32 => Ok(DataType::Float32),
+            ValType::F64 => Ok(DataType::Float64),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "Unsupported WASM value type {}",
+                other
+            ))),
+        }
+    }
+
+    fn to_values(column: &DataColumn, data_type: &DataType) -> Result<Vec<Option<Val>>> {
+        let series = column.to_array()?.cast_with_type(data_type)?;
+        Ok(match data_type {
+            DataType::Int32 => series
+                .i32()?
+                .into_iter()
+                .map(|v| v.map(|v| Val::I32(*v)))
+                .collect(),
+            DataType::Int64 => series
+                .i64()?
+                .into_iter()
+                .map(|v| v.map(|v| Val::I64(*v)))
+                .collect(),
+            DataType::Float32 => series
+                .f32()?
+                .into_iter()
+                .map(|v| v.map(|v| Val::F32(v.to_bits())))
+                .collect(),
+            DataType::Float64 => series
+                .f64()?
+                .into_iter()
+                .map(|v| v.map(|v| Val::F64(v.to_bits())))
+                .collect(),
+            other => {
+                return Err(ErrorCode::IllegalDataType(format!(
+                    "Unsupported WASM argument type {}",
+                    other
+                )))
+            }
+        })
+    }
+
+    fn collect_results<T>(results: Vec<Option<Val>>, to_native: fn(&Val) -> Option<T>) -> DataColumn
+    where
+        T: DFPrimitiveType,
+        DFPrimitiveArray<T>: IntoSeries,
+    {
+        let array: DFPrimitiveArray<T> = results
+            .iter()
+            .map(|v| v.as_ref().and_then(to_native))
+            .collect();
+        DataColumn::Array(array.into_series())
+    }
+}
+
+impl Function for WasmFunction {
+    fn name(&self) -> &str {
+        "WasmFunction"
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((1, usize::MAX))
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        if args[0] != DataType::String {
+            return Err(ErrorCode::BadArguments(format!(
+                "The first argument of function {} must be a WASM module",
+                self.display_name
+            )));
+        }
+        Ok(self.return_type.clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let (name, module) = match columns[0].column() {
+            DataColumn::Constant(DataValue::String(Some(module)), _) => {
+                let name = columns[0].field().name();
+                (name.rsplit('.').next().unwrap_or(name), module)
+            }
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The first argument of function {} must be a constant WASM module",
+                    self.display_name
+                )))
+            }
+        };
+
+        let (arg_types, return_type) = Self::signature(module, name)?;
+        if arg_types.len() != columns.len() - 1 || return_type != self.return_type {
+            return Err(ErrorCode::BadArguments(format!(
+                "WASM function {} does not match the arguments of {}",
+                name, self.display_name
+            )));
+        }
+
+        let args = columns[1..]
+            .iter()
+            .zip(arg_types.iter())
+            .map(|(c, ty)| Self::to_values(c.column(), ty))
+            .collect::<Result<Vec<_>>>()?;
+
+        // One instance per block, the function is then called row by row.
+        let mut store = Store::new(&WASM_ENGINE, ());
+        store
+            .add_fuel(FUEL_PER_ROW * (input_rows as u64).max(1))
+            .map_err(|e| ErrorCode::LogicalError(e.to_string()))?;
+        let instance = Instance::new(&mut store, &Self::compile(module)?, &[]).map_err(|e| {
+            ErrorCode::BadArguments(format!("Cannot instantiate WASM module: {}", e))
+        })?;
+        let func = instance.get_func(&mut store, name).ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "WASM module does not export a function named {}",
+                name
+            ))
+        })?;
+
+        let mut params = Vec::with_capacity(args.len());
+        let mut results = Vec::with_capacity(input_rows);
+        for row in 0..input_rows {
+            params.clear();
+            for arg in args.iter() {
+                match &arg[row] {
+                    Some(v) => params.push(v.clone()),
+                    None => break,
+                }
+            }
+
+            if params.len() != args.len() {
+                // Any NULL argument makes the result NULL.
+                results.push(None);
+                continue;
+            }
+
+            let returned = func.call(&mut store, &params).map_err(|e| {
+                ErrorCode::BadArguments(format!("WASM function {} failed: {}", name, e))
+            })?;
+            results.push(returned.first().cloned());
+        }
+
+        Ok(match self.return_type {
+            DataType::Int32 => Self::collect_results::<i32>(results, Val::i32),
+            DataType::Int64 => Self::collect_results::<i64>(results, Val::i64),
+            DataType::Float32 => Self::collect_results::<f32>(results, Val::f32),
+            _ => Self::collect_results::<f64>(results, Val::f64),
+        })
+    }
+}
+
+impl fmt::Display for WasmFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
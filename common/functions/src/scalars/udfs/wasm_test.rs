@@ -0,0 +1,72 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::Result;
+
+use crate::scalars::*;
+
+const ADD_ONE: &str = r#"(module
+  (func (export "add_one") (param i64) (result i64)
+    local.get 0
+    i64.const 1
+    i64.add))"#;
+
+#[test]
+fn test_wasm_signature() -> Result<()> {
+    let (args, return_type) = WasmFunction::signature(ADD_ONE.as_bytes(), "add_one")?;
+    assert_eq!(args, vec![DataType::Int64]);
+    assert_eq!(return_type, DataType::Int64);
+    assert_eq!(WasmFunction::function_name(&return_type)?, "wasm_i64");
+
+    let result = WasmFunction::signature(ADD_ONE.as_bytes(), "add_two");
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Code: 6, displayText = WASM module does not export a function named add_two."
+    );
+
+    let result = WasmFunction::signature(b"not a module", "add_one");
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_wasm_function() -> Result<()> {
+    let module = DataColumnWithField::new(
+        DataColumn::Constant(DataValue::String(Some(ADD_ONE.as_bytes().to_vec())), 3),
+        DataField::new("add_one", DataType::String, false),
+    );
+    let arg = DataColumnWithField::new(
+        Series::new(vec![Some(1i32), None, Some(41)]).into(),
+        DataField::new("a", DataType::Int32, true),
+    );
+
+    let func = FunctionFactory::instance().get("wasm_i64")?;
+    assert_eq!(
+        func.return_type(&[DataType::String, DataType::Int32])?,
+        DataType::Int64
+    );
+
+    let result = func.eval(&[module, arg], 3)?.to_array()?;
+    let expect: Vec<Option<i64>> = vec![Some(2), None, Some(42)];
+    let actual: Vec<Option<i64>> = result.i64()?.into_iter().map(|v| v.copied()).collect();
+    assert_eq!(actual, expect);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::SeqValue;
+
+/// The credentials of a storage by `CREATE CONNECTION`, the tables and databases with their
+/// own storage refer to it by name instead of keeping the secrets in their options.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ConnectionInfo {
+    pub name: String,
+    pub storage_type: String,
+    /// The storage options encrypted by the master key of the query nodes, the meta service
+    /// never keeps them in plaintext.
+    pub sealed_options: Vec<u8>,
+    /// Seconds since the unix epoch.
+    pub created_on: u64,
+}
+
+pub trait ConnectionMgrApi: Sync + Send {
+    fn add_connection(&self, connection_info: ConnectionInfo) -> Result<u64>;
+
+    fn get_connection(&self, name: String, seq: Option<u64>) -> Result<SeqValue<ConnectionInfo>>;
+
+    fn get_connections(&self) -> Result<Vec<SeqValue<ConnectionInfo>>>;
+
+    fn drop_connection(&self, name: String, seq: Option<u64>) -> Result<()>;
+}
+
+impl TryFrom<Vec<u8>> for ConnectionInfo {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(connection_info) => Ok(connection_info),
+            Err(serialize_error) => Err(ErrorCode::IllegalConnectionFormat(format!(
+                "Cannot deserialize connection from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
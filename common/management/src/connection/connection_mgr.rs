@@ -0,0 +1,132 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::BlockingWait;
+use common_base::Runtime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_meta_api::KVApi;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::SeqValue;
+use common_meta_types::UpsertKVActionReply;
+
+use crate::connection::connection_api::ConnectionInfo;
+use crate::connection::connection_api::ConnectionMgrApi;
+
+pub static CONNECTION_API_KEY_PREFIX: &str = "__fd_connections";
+
+pub struct ConnectionMgr {
+    kv_api: Arc<dyn KVApi>,
+    connection_prefix: String,
+
+    rt: Arc<Runtime>,
+    rpc_time_out: Option<Duration>,
+}
+
+impl ConnectionMgr {
+    pub fn new(kv_api: Arc<dyn KVApi>, tenant: &str) -> Self {
+        let rt = Runtime::with_worker_threads(1).expect("ConnectionMgr initialization failure");
+
+        ConnectionMgr {
+            kv_api,
+            connection_prefix: format!("{}/{}", CONNECTION_API_KEY_PREFIX, tenant),
+            rt: Arc::new(rt),
+            rpc_time_out: Some(Duration::from_secs(5)),
+        }
+    }
+}
+
+impl ConnectionMgrApi for ConnectionMgr {
+    fn add_connection(&self, connection_info: ConnectionInfo) -> Result<u64> {
+        let match_seq = MatchSeq::Exact(0);
+        let key = format!("{}/{}", self.connection_prefix, connection_info.name);
+        let value = serde_json::to_vec(&connection_info)?;
+
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move { kv_api.upsert_kv(&key, match_seq, Some(value), None).await };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res {
+            UpsertKVActionReply {
+                prev: None,
+                result: Some((s, _)),
+            } => Ok(s),
+            UpsertKVActionReply {
+                prev: Some((s, _)),
+                result: _,
+            } => Err(ErrorCode::ConnectionAlreadyExists(format!(
+                "Connection: '{}' already exists, seq [{}]",
+                connection_info.name, s
+            ))),
+            catch_result @ UpsertKVActionReply { .. } => Err(ErrorCode::UnknownException(format!(
+                "upsert result not expected (using version 0, got {:?})",
+                catch_result
+            ))),
+        }
+    }
+
+    fn get_connection(&self, name: String, seq: Option<u64>) -> Result<SeqValue<ConnectionInfo>> {
+        let key = format!("{}/{}", self.connection_prefix, name);
+        let kv_api = self.kv_api.clone();
+        let get_kv = async move { kv_api.get_kv(&key).await };
+        let res = get_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        let seq_value = res
+            .result
+            .ok_or_else(|| ErrorCode::UnknownConnection(format!("Unknown connection: {}", name)))?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok((seq_value.0, seq_value.1.value.try_into()?)),
+            Err(_) => Err(ErrorCode::UnknownConnection(format!(
+                "connection: {}",
+                name
+            ))),
+        }
+    }
+
+    fn get_connections(&self) -> Result<Vec<SeqValue<ConnectionInfo>>> {
+        let connection_prefix = self.connection_prefix.clone();
+        let kv_api = self.kv_api.clone();
+        let prefix_list_kv = async move { kv_api.prefix_list_kv(connection_prefix.as_str()).await };
+        let values = prefix_list_kv.wait_in(&self.rt, self.rpc_time_out)??;
+
+        let mut r = vec![];
+        for (_key, (s, val)) in values {
+            let c = serde_json::from_slice::<ConnectionInfo>(&val.value)
+                .map_err_to_code(ErrorCode::IllegalConnectionFormat, || "")?;
+
+            r.push((s, c));
+        }
+
+        Ok(r)
+    }
+
+    fn drop_connection(&self, name: String, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.connection_prefix, name);
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move { kv_api.upsert_kv(&key, seq.into(), None, None).await };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        if res.prev.is_none() || res.result.is_some() {
+            return Err(ErrorCode::UnknownConnection(format!(
+                "Unknown connection: {}",
+                name
+            )));
+        }
+        Ok(())
+    }
+}
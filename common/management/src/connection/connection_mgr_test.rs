@@ -0,0 +1,75 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::connection::connection_api::ConnectionInfo;
+use crate::connection::connection_api::ConnectionMgrApi;
+use crate::ConnectionMgr;
+
+fn create_test_connection_info(name: &str) -> ConnectionInfo {
+    ConnectionInfo {
+        name: name.to_string(),
+        storage_type: "s3".to_string(),
+        sealed_options: vec![1, 2, 3],
+        created_on: 1632000000,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_get_drop_connection() -> Result<()> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let connection_api = ConnectionMgr::new(kv_api.clone(), "tenant1");
+
+    let connection_info = create_test_connection_info("warehouse");
+    connection_api.add_connection(connection_info.clone())?;
+    connection_api.add_connection(create_test_connection_info("archive"))?;
+
+    let res = connection_api.add_connection(connection_info.clone());
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::ConnectionAlreadyExists("").code()
+    );
+
+    let name = "warehouse".to_string();
+    assert_eq!(
+        connection_api.get_connection(name.clone(), None)?.1,
+        connection_info
+    );
+    assert_eq!(connection_api.get_connections()?.len(), 2);
+
+    // The connections of the other tenants are not visible.
+    let other_api = ConnectionMgr::new(kv_api, "tenant2");
+    assert!(other_api.get_connections()?.is_empty());
+
+    connection_api.drop_connection(name.clone(), None)?;
+    let res = connection_api.get_connection(name.clone(), None);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UnknownConnection("").code()
+    );
+
+    let res = connection_api.drop_connection(name, None);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UnknownConnection("").code()
+    );
+
+    Ok(())
+}
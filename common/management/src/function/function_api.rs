@@ -0,0 +1,56 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::SeqValue;
+
+/// A function installed for all the sessions of a tenant, by `CREATE FUNCTION ns.name`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct FunctionInfo {
+    /// The qualified name in lower case, e.g. `mylib.score`.
+    pub name: String,
+    /// The WASM module, or the JSON encoded definition of an external function.
+    pub definition: Vec<u8>,
+    /// The scalar function which runs the definition, such as `wasm_i64`.
+    pub function_name: String,
+    pub arg_types: Vec<DataType>,
+}
+
+pub trait FunctionMgrApi: Sync + Send {
+    fn add_function(&self, function_info: FunctionInfo) -> Result<u64>;
+
+    fn get_function(&self, name: String, seq: Option<u64>) -> Result<SeqValue<FunctionInfo>>;
+
+    fn get_functions(&self) -> Result<Vec<SeqValue<FunctionInfo>>>;
+
+    fn drop_function(&self, name: String, seq: Option<u64>) -> Result<()>;
+}
+
+impl TryFrom<Vec<u8>> for FunctionInfo {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(function_info) => Ok(function_info),
+            Err(serialize_error) => Err(ErrorCode::IllegalFunctionInfoFormat(format!(
+                "Cannot deserialize function info from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::function::function_api::FunctionInfo;
+use crate::function::function_api::FunctionMgrApi;
+use crate::FunctionMgr;
+
+fn create_test_function_info(name: &str) -> FunctionInfo {
+    FunctionInfo {
+        name: name.to_string(),
+        definition: b"(module)".to_vec(),
+        function_name: "wasm_i64".to_string(),
+        arg_types: vec![DataType::Int64, DataType::Int64],
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_get_drop_function() -> Result<()> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let function_api = FunctionMgr::new(kv_api.clone(), "tenant1");
+
+    let function_info = create_test_function_info("mylib.add");
+    function_api.add_function(function_info.clone())?;
+    function_api.add_function(create_test_function_info("mylib.sub"))?;
+
+    let res = function_api.add_function(function_info.clone());
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::FunctionAlreadyExists("").code()
+    );
+
+    let name = "mylib.add".to_string();
+    assert_eq!(
+        function_api.get_function(name.clone(), None)?.1,
+        function_info
+    );
+    assert_eq!(function_api.get_functions()?.len(), 2);
+
+    // The functions of the other tenants are not visible.
+    let other_api = FunctionMgr::new(kv_api, "tenant2");
+    assert!(other_api.get_functions()?.is_empty());
+
+    function_api.drop_function(name.clone(), None)?;
+    let res = function_api.get_function(name.clone(), None);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UnknownFunction("").code()
+    );
+
+    let res = function_api.drop_function(name, None);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UnknownFunction("").code()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+/// Elects a single leader among the nodes by a lease in the meta service, the leader keeps it by
+/// renewing it before it expires, otherwise another node takes it over.
+#[async_trait::async_trait]
+pub trait LeaderApi: Sync + Send {
+    /// Takes the lease if no node holds it, returns its seq, or None if another node holds it.
+    async fn try_acquire(&self, node_id: &str) -> Result<Option<u64>>;
+
+    /// Renews the lease of seq `seq`, returns its new seq.
+    /// Err LeaderLeaseLost if the lease has expired or is taken by another node.
+    async fn renew(&self, seq: u64) -> Result<u64>;
+
+    /// Gives up the lease of seq `seq`, so that another node takes it without waiting.
+    async fn resign(&self, seq: u64) -> Result<()>;
+
+    /// The node id of the leader, None if no node holds the lease.
+    async fn get_leader(&self) -> Result<Option<String>>;
+}
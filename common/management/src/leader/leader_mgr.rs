@@ -0,0 +1,111 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Add;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::KVMeta;
+use common_meta_types::MatchSeq;
+
+use crate::leader::leader_api::LeaderApi;
+
+pub static LEADER_API_KEY_PREFIX: &str = "__fd_leaders";
+
+pub struct LeaderMgr {
+    kv_api: Arc<dyn KVApi>,
+    lease: Duration,
+    leader_key: String,
+}
+
+impl LeaderMgr {
+    /// The leader of the election `election` of the tenant.
+    pub fn new(kv_api: Arc<dyn KVApi>, tenant: &str, election: &str, lease: Duration) -> Self {
+        LeaderMgr {
+            kv_api,
+            lease,
+            leader_key: format!("{}/{}/{}", LEADER_API_KEY_PREFIX, tenant, election),
+        }
+    }
+
+    fn new_lease(&self) -> KVMeta {
+        let expire_at = std::time::SystemTime::now()
+            .add(self.lease)
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards");
+
+        KVMeta {
+            expire_at: Some(expire_at.as_secs()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LeaderApi for LeaderMgr {
+    async fn try_acquire(&self, node_id: &str) -> Result<Option<u64>> {
+        // The expired lease is taken as absent.
+        let res = self
+            .kv_api
+            .upsert_kv(
+                &self.leader_key,
+                MatchSeq::Exact(0),
+                Some(node_id.as_bytes().to_vec()),
+                Some(self.new_lease()),
+            )
+            .await?;
+
+        match (res.prev, res.result) {
+            (None, Some((s, _))) => Ok(Some(s)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn renew(&self, seq: u64) -> Result<u64> {
+        let res = self
+            .kv_api
+            .update_kv_meta(
+                &self.leader_key,
+                MatchSeq::Exact(seq),
+                Some(self.new_lease()),
+            )
+            .await?;
+
+        match (res.prev, res.result) {
+            (Some((prev, _)), Some((s, _))) if prev == seq => Ok(s),
+            _ => Err(ErrorCode::LeaderLeaseLost(format!(
+                "Leader lease {} of seq [{}] is expired or taken by another node",
+                self.leader_key, seq
+            ))),
+        }
+    }
+
+    async fn resign(&self, seq: u64) -> Result<()> {
+        self.kv_api
+            .upsert_kv(&self.leader_key, MatchSeq::Exact(seq), None, None)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_leader(&self) -> Result<Option<String>> {
+        let res = self.kv_api.get_kv(&self.leader_key).await?;
+        match res.result {
+            None => Ok(None),
+            Some((_, value)) => Ok(Some(String::from_utf8(value.value)?)),
+        }
+    }
+}
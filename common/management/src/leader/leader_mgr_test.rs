@@ -0,0 +1,77 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::leader::leader_api::LeaderApi;
+use crate::leader::leader_mgr::LeaderMgr;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_leader_mgr() -> Result<()> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let leader_mgr = LeaderMgr::new(kv_api, "tenant1", "jobs", Duration::from_secs(60));
+
+    assert_eq!(leader_mgr.get_leader().await?, None);
+
+    // The first node takes the lease, the others can not.
+    let seq = leader_mgr.try_acquire("node1").await?;
+    assert!(seq.is_some());
+    assert_eq!(leader_mgr.try_acquire("node2").await?, None);
+    assert_eq!(leader_mgr.get_leader().await?, Some("node1".to_string()));
+
+    // Renewing changes the seq of the lease, the old one is lost.
+    let old_seq = seq.unwrap();
+    let seq = leader_mgr.renew(old_seq).await?;
+    assert!(seq > old_seq);
+    let code = ErrorCode::LeaderLeaseLost("").code();
+    match leader_mgr.renew(old_seq).await {
+        Ok(_) => panic!("Renewing the lost lease must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), code),
+    }
+
+    // Another node takes over once the leader resigns.
+    leader_mgr.resign(seq).await?;
+    assert_eq!(leader_mgr.get_leader().await?, None);
+    assert!(leader_mgr.try_acquire("node2").await?.is_some());
+    assert_eq!(leader_mgr.get_leader().await?, Some("node2".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_leader_mgr_expired_lease() -> Result<()> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let leader_mgr = LeaderMgr::new(kv_api, "tenant1", "jobs", Duration::from_secs(1));
+
+    let seq = leader_mgr.try_acquire("node1").await?.unwrap();
+
+    // The leader fails to renew its lease in time, another node takes over.
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert_eq!(leader_mgr.get_leader().await?, None);
+    assert!(leader_mgr.try_acquire("node2").await?.is_some());
+
+    let code = ErrorCode::LeaderLeaseLost("").code();
+    match leader_mgr.renew(seq).await {
+        Ok(_) => panic!("Renewing the expired lease must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), code),
+    }
+
+    Ok(())
+}
@@ -13,12 +13,46 @@
 // limitations under the License.
 //
 
+mod connection;
+mod function;
+mod leader;
+mod load;
 mod namespace;
+mod pipe;
+mod profile;
+mod purge;
 mod user;
 
+pub use connection::connection_api::ConnectionInfo;
+pub use connection::connection_api::ConnectionMgrApi;
+pub use connection::connection_mgr::ConnectionMgr;
+pub use function::function_api::FunctionInfo;
+pub use function::function_api::FunctionMgrApi;
+pub use function::function_mgr::FunctionMgr;
+pub use leader::leader_api::LeaderApi;
+pub use leader::leader_mgr::LeaderMgr;
+pub use load::load_api::LoadFileProgress;
+pub use load::load_api::LoadMgrApi;
+pub use load::load_api::LoadPendingChunk;
+pub use load::load_api::LoadProgress;
+pub use load::load_mgr::LoadMgr;
 pub use namespace::NamespaceApi;
 pub use namespace::NamespaceMgr;
+pub use pipe::pipe_api::KafkaSourceInfo;
+pub use pipe::pipe_api::PipeCheckpoint;
+pub use pipe::pipe_api::PipeInfo;
+pub use pipe::pipe_api::PipeMgrApi;
+pub use pipe::pipe_api::PipePendingBatch;
+pub use pipe::pipe_api::PipeSourceInfo;
+pub use pipe::pipe_mgr::PipeMgr;
+pub use profile::profile_api::ProfileInfo;
+pub use profile::profile_api::ProfileMgrApi;
+pub use profile::profile_mgr::ProfileMgr;
+pub use purge::purge_api::PurgeMgrApi;
+pub use purge::purge_api::PurgeTask;
+pub use purge::purge_mgr::PurgeMgr;
 pub use user::user_api::AuthType;
 pub use user::user_api::UserInfo;
 pub use user::user_api::UserMgrApi;
+pub use user::user_api::UserQuota;
 pub use user::user_mgr::UserMgr;
@@ -0,0 +1,86 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::SeqValue;
+
+/// How much of a file is loaded into the table.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoadFileProgress {
+    /// The size of the file when it was loaded, a file of another size is loaded again.
+    pub size: u64,
+    /// The committed bytes, always the end of a line.
+    pub bytes: u64,
+    /// The committed rows.
+    pub rows: u64,
+}
+
+impl LoadFileProgress {
+    pub fn is_finished(&self) -> bool {
+        self.bytes >= self.size
+    }
+}
+
+/// A chunk of a file written to the table but maybe not committed yet.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoadPendingChunk {
+    pub path: String,
+    /// The progress of the file once the chunk is committed.
+    pub progress: LoadFileProgress,
+    /// The table snapshot which contains the chunk.
+    pub snapshot_location: String,
+}
+
+/// The load progress of the files of a location into a table, file path -> progress.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoadProgress {
+    pub files: BTreeMap<String, LoadFileProgress>,
+    pub pending: Option<LoadPendingChunk>,
+}
+
+pub trait LoadMgrApi: Sync + Send {
+    /// Returns the load progress of the location into the table and its seq,
+    /// seq 0 if nothing was loaded.
+    fn get_load_progress(&self, table_id: u64, location: String) -> Result<SeqValue<LoadProgress>>;
+
+    /// Saves the load progress if its seq is still `seq`, returns the new seq.
+    fn upsert_load_progress(
+        &self,
+        table_id: u64,
+        location: String,
+        progress: LoadProgress,
+        seq: u64,
+    ) -> Result<u64>;
+
+    /// Forgets the load progress, the files of the location are loaded again by the next load.
+    fn drop_load_progress(&self, table_id: u64, location: String) -> Result<()>;
+}
+
+impl TryFrom<Vec<u8>> for LoadProgress {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(progress) => Ok(progress),
+            Err(serialize_error) => Err(ErrorCode::IllegalLoadProgressFormat(format!(
+                "Cannot deserialize load progress from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::BlockingWait;
+use common_base::Runtime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::MatchSeq;
+use common_meta_types::SeqValue;
+
+use crate::load::load_api::LoadMgrApi;
+use crate::load::load_api::LoadProgress;
+
+pub static LOAD_PROGRESS_API_KEY_PREFIX: &str = "__fd_load_progress";
+
+pub struct LoadMgr {
+    kv_api: Arc<dyn KVApi>,
+    progress_prefix: String,
+
+    rt: Arc<Runtime>,
+    rpc_time_out: Option<Duration>,
+}
+
+impl LoadMgr {
+    pub fn new(kv_api: Arc<dyn KVApi>, tenant: &str) -> Self {
+        let rt = Runtime::with_worker_threads(1).expect("LoadMgr initialization failure");
+
+        LoadMgr {
+            kv_api,
+            progress_prefix: format!("{}/{}", LOAD_PROGRESS_API_KEY_PREFIX, tenant),
+            rt: Arc::new(rt),
+            rpc_time_out: Some(Duration::from_secs(5)),
+        }
+    }
+
+    // Keyed by the table id, a table created again with the same name loads from the beginning.
+    fn progress_key(&self, table_id: u64, location: &str) -> String {
+        format!("{}/{}/{}", self.progress_prefix, table_id, location)
+    }
+}
+
+impl LoadMgrApi for LoadMgr {
+    fn get_load_progress(&self, table_id: u64, location: String) -> Result<SeqValue<LoadProgress>> {
+        let key = self.progress_key(table_id, &location);
+        let kv_api = self.kv_api.clone();
+        let get_kv = async move { kv_api.get_kv(&key).await };
+        let res = get_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res.result {
+            None => Ok((0, LoadProgress::default())),
+            Some((s, val)) => Ok((s, val.value.try_into()?)),
+        }
+    }
+
+    fn upsert_load_progress(
+        &self,
+        table_id: u64,
+        location: String,
+        progress: LoadProgress,
+        seq: u64,
+    ) -> Result<u64> {
+        let key = self.progress_key(table_id, &location);
+        let value = serde_json::to_vec(&progress)?;
+
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move {
+            kv_api
+                .upsert_kv(&key, MatchSeq::Exact(seq), Some(value), None)
+                .await
+        };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res.result {
+            Some((s, _)) if res.prev.as_ref().map(|(s, _)| *s).unwrap_or(0) == seq => Ok(s),
+            _ => Err(ErrorCode::LoadProgressConflict(format!(
+                "Load progress of {} is changed by others, expect seq [{}]",
+                location, seq
+            ))),
+        }
+    }
+
+    fn drop_load_progress(&self, table_id: u64, location: String) -> Result<()> {
+        let key = self.progress_key(table_id, &location);
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move { kv_api.upsert_kv(&key, MatchSeq::Any, None, None).await };
+        upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::load::load_api::LoadFileProgress;
+use crate::load::load_api::LoadMgrApi;
+use crate::load::load_api::LoadPendingChunk;
+use crate::load::load_api::LoadProgress;
+use crate::LoadMgr;
+
+async fn new_load_api() -> Result<LoadMgr> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    Ok(LoadMgr::new(kv_api, "tenant1"))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_load_progress() -> Result<()> {
+    let load_api = new_load_api().await?;
+
+    // Never loaded.
+    let (seq, progress) = load_api.get_load_progress(1, "data/".to_string())?;
+    assert_eq!(seq, 0);
+    assert_eq!(progress, LoadProgress::default());
+
+    let mut progress = LoadProgress::default();
+    progress
+        .files
+        .insert("data/a.csv".to_string(), LoadFileProgress {
+            size: 100,
+            bytes: 100,
+            rows: 10,
+        });
+    progress.pending = Some(LoadPendingChunk {
+        path: "data/b.csv".to_string(),
+        progress: LoadFileProgress {
+            size: 200,
+            bytes: 50,
+            rows: 5,
+        },
+        snapshot_location: "_ss/1".to_string(),
+    });
+    let seq = load_api.upsert_load_progress(1, "data/".to_string(), progress.clone(), 0)?;
+    assert_eq!(
+        load_api.get_load_progress(1, "data/".to_string())?,
+        (seq, progress.clone())
+    );
+    assert!(progress.files["data/a.csv"].is_finished());
+
+    // Another table or location has its own progress.
+    assert_eq!(load_api.get_load_progress(2, "data/".to_string())?.0, 0);
+    assert_eq!(load_api.get_load_progress(1, "data2/".to_string())?.0, 0);
+
+    // The progress is changed by others.
+    let res = load_api.upsert_load_progress(1, "data/".to_string(), progress.clone(), 0);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::LoadProgressConflict("").code()
+    );
+
+    load_api.drop_load_progress(1, "data/".to_string())?;
+    assert_eq!(load_api.get_load_progress(1, "data/".to_string())?.0, 0);
+
+    Ok(())
+}
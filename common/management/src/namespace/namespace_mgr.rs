@@ -170,6 +170,11 @@ impl NamespaceApi for NamespaceMgr {
 
             let node_key = Self::unescape_for_key(&node_key)?;
             node_info.id = node_key[self.namespace_prefix.len() + 1..].to_string();
+            // The meta store does not list the expired nodes, a node without lease never expires.
+            node_info.alive_until = match value.meta.as_ref().and_then(|meta| meta.expire_at) {
+                Some(expire_at) => expire_at,
+                None => u64::MAX,
+            };
             nodes_info.push(node_info);
         }
 
@@ -68,6 +68,7 @@ async fn test_already_exists_add_node() -> Result<()> {
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_successfully_get_nodes() -> Result<()> {
+    let current_time = current_seconds_time();
     let (_, namespace_api) = new_namespace_api().await?;
 
     let nodes = namespace_api.get_nodes().await?;
@@ -77,19 +78,42 @@ async fn test_successfully_get_nodes() -> Result<()> {
     namespace_api.add_node(node_info.clone()).await?;
 
     let nodes = namespace_api.get_nodes().await?;
-    assert_eq!(nodes, vec![node_info]);
+    assert_eq!(check_leases(nodes, current_time), vec![node_info]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_expired_node_get_nodes() -> Result<()> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let namespace_api = NamespaceMgr::new(test_api, "", "", Duration::from_secs(1))?;
+
+    let node_info = create_test_node_info();
+    namespace_api.add_node(node_info.clone()).await?;
+    assert_eq!(namespace_api.get_nodes().await?.len(), 1);
+
+    // The node without heartbeats leaves once its lease expires.
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let nodes = namespace_api.get_nodes().await?;
+    assert_eq!(nodes, vec![]);
+
+    // The heartbeat of the node which left fails, it needs to be added again.
+    match namespace_api.heartbeat(node_info.id.clone(), None).await {
+        Ok(_) => panic!("Heartbeat of expired node must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 4058),
+    }
     Ok(())
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_successfully_drop_node() -> Result<()> {
+    let current_time = current_seconds_time();
     let (_, namespace_api) = new_namespace_api().await?;
 
     let node_info = create_test_node_info();
     namespace_api.add_node(node_info.clone()).await?;
 
     let nodes = namespace_api.get_nodes().await?;
-    assert_eq!(nodes, vec![node_info.clone()]);
+    assert_eq!(check_leases(nodes, current_time), vec![node_info.clone()]);
 
     namespace_api.drop_node(node_info.id, None).await?;
 
@@ -145,12 +169,25 @@ fn current_seconds_time() -> u64 {
         .as_secs()
 }
 
+/// Checks the leases of the listed nodes, and clears them to compare the nodes with the added ones.
+fn check_leases(nodes: Vec<NodeInfo>, current_time: u64) -> Vec<NodeInfo> {
+    nodes
+        .into_iter()
+        .map(|mut node| {
+            assert!(node.alive_until - current_time >= 60);
+            node.alive_until = 0;
+            node
+        })
+        .collect()
+}
+
 fn create_test_node_info() -> NodeInfo {
     NodeInfo {
         id: String::from("test_node"),
         cpu_nums: 0,
         version: 0,
         flight_address: String::from("ip:port"),
+        alive_until: 0,
     }
 }
 
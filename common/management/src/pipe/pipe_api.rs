@@ -0,0 +1,104 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::SeqValue;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct KafkaSourceInfo {
+    pub brokers: String,
+    pub topic: String,
+    pub format: String,
+    /// Max number of messages of one micro-batch.
+    pub batch_max_messages: u64,
+    /// Max time to wait for one micro-batch to fill up.
+    pub batch_interval_ms: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum PipeSourceInfo {
+    Kafka(KafkaSourceInfo),
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct PipeInfo {
+    pub name: String,
+    pub db: String,
+    pub table: String,
+    pub source: PipeSourceInfo,
+}
+
+/// A micro-batch written to the table but maybe not committed yet.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PipePendingBatch {
+    /// The offsets to checkpoint once the batch is committed.
+    pub offsets: BTreeMap<i32, i64>,
+    /// The table snapshot which contains the batch.
+    pub snapshot_location: String,
+}
+
+/// The consumed offsets of a pipe, partition id -> the next offset to consume.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PipeCheckpoint {
+    pub offsets: BTreeMap<i32, i64>,
+    pub pending: Option<PipePendingBatch>,
+}
+
+pub trait PipeMgrApi: Sync + Send {
+    fn add_pipe(&self, pipe_info: PipeInfo) -> Result<u64>;
+
+    fn get_pipe(&self, name: String, seq: Option<u64>) -> Result<SeqValue<PipeInfo>>;
+
+    fn get_pipes(&self) -> Result<Vec<SeqValue<PipeInfo>>>;
+
+    fn drop_pipe(&self, name: String, seq: Option<u64>) -> Result<()>;
+
+    /// Returns the checkpoint of the pipe and its seq, seq 0 if the pipe never checkpointed.
+    fn get_checkpoint(&self, name: String) -> Result<SeqValue<PipeCheckpoint>>;
+
+    /// Saves the checkpoint if its seq is still `seq`, returns the new seq.
+    fn upsert_checkpoint(&self, name: String, checkpoint: PipeCheckpoint, seq: u64) -> Result<u64>;
+}
+
+impl TryFrom<Vec<u8>> for PipeInfo {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(pipe_info) => Ok(pipe_info),
+            Err(serialize_error) => Err(ErrorCode::IllegalPipeInfoFormat(format!(
+                "Cannot deserialize pipe info from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Vec<u8>> for PipeCheckpoint {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(checkpoint) => Ok(checkpoint),
+            Err(serialize_error) => Err(ErrorCode::IllegalPipeInfoFormat(format!(
+                "Cannot deserialize pipe checkpoint from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
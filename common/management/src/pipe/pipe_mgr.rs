@@ -0,0 +1,168 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::BlockingWait;
+use common_base::Runtime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_meta_api::KVApi;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::SeqValue;
+use common_meta_types::UpsertKVActionReply;
+
+use crate::pipe::pipe_api::PipeCheckpoint;
+use crate::pipe::pipe_api::PipeInfo;
+use crate::pipe::pipe_api::PipeMgrApi;
+
+pub static PIPE_API_KEY_PREFIX: &str = "__fd_pipes";
+pub static PIPE_CHECKPOINT_API_KEY_PREFIX: &str = "__fd_pipe_checkpoints";
+
+pub struct PipeMgr {
+    kv_api: Arc<dyn KVApi>,
+    pipe_prefix: String,
+    checkpoint_prefix: String,
+
+    rt: Arc<Runtime>,
+    rpc_time_out: Option<Duration>,
+}
+
+impl PipeMgr {
+    pub fn new(kv_api: Arc<dyn KVApi>, tenant: &str) -> Self {
+        let rt = Runtime::with_worker_threads(1).expect("PipeMgr initialization failure");
+
+        PipeMgr {
+            kv_api,
+            pipe_prefix: format!("{}/{}", PIPE_API_KEY_PREFIX, tenant),
+            checkpoint_prefix: format!("{}/{}", PIPE_CHECKPOINT_API_KEY_PREFIX, tenant),
+            rt: Arc::new(rt),
+            rpc_time_out: Some(Duration::from_secs(5)),
+        }
+    }
+}
+
+impl PipeMgrApi for PipeMgr {
+    fn add_pipe(&self, pipe_info: PipeInfo) -> Result<u64> {
+        let match_seq = MatchSeq::Exact(0);
+        let key = format!("{}/{}", self.pipe_prefix, pipe_info.name);
+        let value = serde_json::to_vec(&pipe_info)?;
+
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move { kv_api.upsert_kv(&key, match_seq, Some(value), None).await };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res {
+            UpsertKVActionReply {
+                prev: None,
+                result: Some((s, _)),
+            } => Ok(s),
+            UpsertKVActionReply {
+                prev: Some((s, _)),
+                result: _,
+            } => Err(ErrorCode::PipeAlreadyExists(format!(
+                "Pipe {} already exists, seq [{}]",
+                pipe_info.name, s
+            ))),
+            catch_result @ UpsertKVActionReply { .. } => Err(ErrorCode::UnknownException(format!(
+                "upsert result not expected (using version 0, got {:?})",
+                catch_result
+            ))),
+        }
+    }
+
+    fn get_pipe(&self, name: String, seq: Option<u64>) -> Result<SeqValue<PipeInfo>> {
+        let key = format!("{}/{}", self.pipe_prefix, name);
+        let kv_api = self.kv_api.clone();
+        let get_kv = async move { kv_api.get_kv(&key).await };
+        let res = get_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        let seq_value = res
+            .result
+            .ok_or_else(|| ErrorCode::UnknownPipe(format!("unknown pipe {}", name)))?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok((seq_value.0, seq_value.1.value.try_into()?)),
+            Err(_) => Err(ErrorCode::UnknownPipe(format!("pipe: {}", name))),
+        }
+    }
+
+    fn get_pipes(&self) -> Result<Vec<SeqValue<PipeInfo>>> {
+        let pipe_prefix = self.pipe_prefix.clone();
+        let kv_api = self.kv_api.clone();
+        let prefix_list_kv = async move { kv_api.prefix_list_kv(pipe_prefix.as_str()).await };
+        let values = prefix_list_kv.wait_in(&self.rt, self.rpc_time_out)??;
+
+        let mut r = vec![];
+        for (_key, (s, val)) in values {
+            let p = serde_json::from_slice::<PipeInfo>(&val.value)
+                .map_err_to_code(ErrorCode::IllegalPipeInfoFormat, || "")?;
+
+            r.push((s, p));
+        }
+
+        Ok(r)
+    }
+
+    fn drop_pipe(&self, name: String, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.pipe_prefix, name);
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move { kv_api.upsert_kv(&key, seq.into(), None, None).await };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        if res.prev.is_none() || res.result.is_some() {
+            return Err(ErrorCode::UnknownPipe(format!("unknown pipe {}", name)));
+        }
+
+        // The checkpoint goes with the pipe, a pipe created again with the same name
+        // consumes from the beginning.
+        let key = format!("{}/{}", self.checkpoint_prefix, name);
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move { kv_api.upsert_kv(&key, MatchSeq::Any, None, None).await };
+        upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        Ok(())
+    }
+
+    fn get_checkpoint(&self, name: String) -> Result<SeqValue<PipeCheckpoint>> {
+        let key = format!("{}/{}", self.checkpoint_prefix, name);
+        let kv_api = self.kv_api.clone();
+        let get_kv = async move { kv_api.get_kv(&key).await };
+        let res = get_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res.result {
+            None => Ok((0, PipeCheckpoint::default())),
+            Some((s, val)) => Ok((s, val.value.try_into()?)),
+        }
+    }
+
+    fn upsert_checkpoint(&self, name: String, checkpoint: PipeCheckpoint, seq: u64) -> Result<u64> {
+        let key = format!("{}/{}", self.checkpoint_prefix, name);
+        let value = serde_json::to_vec(&checkpoint)?;
+
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move {
+            kv_api
+                .upsert_kv(&key, MatchSeq::Exact(seq), Some(value), None)
+                .await
+        };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res.result {
+            Some((s, _)) if res.prev.as_ref().map(|(s, _)| *s).unwrap_or(0) == seq => Ok(s),
+            _ => Err(ErrorCode::PipeCheckpointConflict(format!(
+                "Checkpoint of pipe {} is changed by others, expect seq [{}]",
+                name, seq
+            ))),
+        }
+    }
+}
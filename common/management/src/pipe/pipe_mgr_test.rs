@@ -0,0 +1,110 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::pipe::pipe_api::KafkaSourceInfo;
+use crate::pipe::pipe_api::PipeCheckpoint;
+use crate::pipe::pipe_api::PipeInfo;
+use crate::pipe::pipe_api::PipeMgrApi;
+use crate::pipe::pipe_api::PipePendingBatch;
+use crate::pipe::pipe_api::PipeSourceInfo;
+use crate::PipeMgr;
+
+fn create_test_pipe_info(name: &str) -> PipeInfo {
+    PipeInfo {
+        name: name.to_string(),
+        db: "default".to_string(),
+        table: "t".to_string(),
+        source: PipeSourceInfo::Kafka(KafkaSourceInfo {
+            brokers: "localhost:9092".to_string(),
+            topic: "events".to_string(),
+            format: "csv".to_string(),
+            batch_max_messages: 1000,
+            batch_interval_ms: 1000,
+        }),
+    }
+}
+
+async fn new_pipe_api() -> Result<PipeMgr> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    Ok(PipeMgr::new(kv_api, "tenant1"))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_get_drop_pipe() -> Result<()> {
+    let pipe_api = new_pipe_api().await?;
+
+    let pipe_info = create_test_pipe_info("p1");
+    pipe_api.add_pipe(pipe_info.clone())?;
+    pipe_api.add_pipe(create_test_pipe_info("p2"))?;
+
+    let res = pipe_api.add_pipe(pipe_info.clone());
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::PipeAlreadyExists("").code()
+    );
+
+    assert_eq!(pipe_api.get_pipe("p1".to_string(), None)?.1, pipe_info);
+    assert_eq!(pipe_api.get_pipes()?.len(), 2);
+
+    pipe_api.drop_pipe("p1".to_string(), None)?;
+    let res = pipe_api.get_pipe("p1".to_string(), None);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::UnknownPipe("").code());
+
+    let res = pipe_api.drop_pipe("p1".to_string(), None);
+    assert_eq!(res.unwrap_err().code(), ErrorCode::UnknownPipe("").code());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pipe_checkpoint() -> Result<()> {
+    let pipe_api = new_pipe_api().await?;
+    pipe_api.add_pipe(create_test_pipe_info("p1"))?;
+
+    // Never checkpointed.
+    let (seq, checkpoint) = pipe_api.get_checkpoint("p1".to_string())?;
+    assert_eq!(seq, 0);
+    assert_eq!(checkpoint, PipeCheckpoint::default());
+
+    let mut checkpoint = PipeCheckpoint::default();
+    checkpoint.pending = Some(PipePendingBatch {
+        offsets: vec![(0, 10), (1, 5)].into_iter().collect(),
+        snapshot_location: "_ss/1".to_string(),
+    });
+    let seq = pipe_api.upsert_checkpoint("p1".to_string(), checkpoint.clone(), 0)?;
+    assert_eq!(
+        pipe_api.get_checkpoint("p1".to_string())?,
+        (seq, checkpoint.clone())
+    );
+
+    // The checkpoint is changed by others.
+    let res = pipe_api.upsert_checkpoint("p1".to_string(), checkpoint.clone(), 0);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::PipeCheckpointConflict("").code()
+    );
+
+    // Dropped with the pipe.
+    pipe_api.drop_pipe("p1".to_string(), None)?;
+    assert_eq!(pipe_api.get_checkpoint("p1".to_string())?.0, 0);
+
+    Ok(())
+}
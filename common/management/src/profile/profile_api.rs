@@ -0,0 +1,53 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::SeqValue;
+
+/// The settings the sessions start with, by `CREATE SETTINGS PROFILE`.
+/// The profile `default` is of all the users of the tenant, the profile of a user overrides it.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ProfileInfo {
+    pub name: String,
+    /// The values of the settings by their names, as they are `SET`.
+    pub settings: BTreeMap<String, String>,
+}
+
+pub trait ProfileMgrApi: Sync + Send {
+    fn add_profile(&self, profile_info: ProfileInfo) -> Result<u64>;
+
+    fn get_profile(&self, name: String, seq: Option<u64>) -> Result<SeqValue<ProfileInfo>>;
+
+    fn get_profiles(&self) -> Result<Vec<SeqValue<ProfileInfo>>>;
+
+    fn drop_profile(&self, name: String, seq: Option<u64>) -> Result<()>;
+}
+
+impl TryFrom<Vec<u8>> for ProfileInfo {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(profile_info) => Ok(profile_info),
+            Err(serialize_error) => Err(ErrorCode::IllegalSettingsProfileFormat(format!(
+                "Cannot deserialize settings profile from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::profile::profile_api::ProfileInfo;
+use crate::profile::profile_api::ProfileMgrApi;
+use crate::ProfileMgr;
+
+fn create_test_profile_info(name: &str) -> ProfileInfo {
+    let mut settings = BTreeMap::new();
+    settings.insert("max_threads".to_string(), "4".to_string());
+    settings.insert("query_tag".to_string(), name.to_string());
+    ProfileInfo {
+        name: name.to_string(),
+        settings,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_get_drop_profile() -> Result<()> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let profile_api = ProfileMgr::new(kv_api.clone(), "tenant1");
+
+    let profile_info = create_test_profile_info("analyst");
+    profile_api.add_profile(profile_info.clone())?;
+    profile_api.add_profile(create_test_profile_info("default"))?;
+
+    let res = profile_api.add_profile(profile_info.clone());
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::SettingsProfileAlreadyExists("").code()
+    );
+
+    let name = "analyst".to_string();
+    assert_eq!(profile_api.get_profile(name.clone(), None)?.1, profile_info);
+    assert_eq!(profile_api.get_profiles()?.len(), 2);
+
+    // The profiles of the other tenants are not visible.
+    let other_api = ProfileMgr::new(kv_api, "tenant2");
+    assert!(other_api.get_profiles()?.is_empty());
+
+    profile_api.drop_profile(name.clone(), None)?;
+    let res = profile_api.get_profile(name.clone(), None);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UnknownSettingsProfile("").code()
+    );
+
+    let res = profile_api.drop_profile(name, None);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::UnknownSettingsProfile("").code()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::SeqValue;
+
+/// The data of a dropped table, removed from the storage by the background purge job.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PurgeTask {
+    pub db: String,
+    pub table: String,
+    pub table_id: u64,
+    /// The options of the dropped table, which locate its storage.
+    pub table_options: HashMap<String, String>,
+    /// The snapshot to remove next, the older ones are reached from it.
+    /// None once all the snapshots are removed.
+    pub snapshot_location: Option<String>,
+    /// The number of the removed objects so far, the blocks, segments and snapshots.
+    pub removed_objects: u64,
+    /// unix timestamp(in seconds) of the task being added.
+    pub created_on: u64,
+    /// The error of the last try, the task is tried again by the next round of the job.
+    pub last_error: Option<String>,
+}
+
+impl PurgeTask {
+    pub fn is_finished(&self) -> bool {
+        self.snapshot_location.is_none()
+    }
+}
+
+pub trait PurgeMgrApi: Sync + Send {
+    /// Adds the task of a dropped table, returns its seq.
+    fn add_purge_task(&self, task: PurgeTask) -> Result<u64>;
+
+    /// Returns all the tasks with their seqs, the finished ones included.
+    fn get_purge_tasks(&self) -> Result<Vec<SeqValue<PurgeTask>>>;
+
+    /// Saves the progress of the task if its seq is still `seq`, returns the new seq.
+    fn update_purge_task(&self, task: PurgeTask, seq: u64) -> Result<u64>;
+}
+
+impl TryFrom<Vec<u8>> for PurgeTask {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(task) => Ok(task),
+            Err(serialize_error) => Err(ErrorCode::IllegalPurgeTaskFormat(format!(
+                "Cannot deserialize purge task from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
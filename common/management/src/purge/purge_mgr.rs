@@ -0,0 +1,99 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::BlockingWait;
+use common_base::Runtime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::MatchSeq;
+use common_meta_types::SeqValue;
+
+use crate::purge::purge_api::PurgeMgrApi;
+use crate::purge::purge_api::PurgeTask;
+
+pub static PURGE_TASK_API_KEY_PREFIX: &str = "__fd_purge_tasks";
+
+pub struct PurgeMgr {
+    kv_api: Arc<dyn KVApi>,
+    task_prefix: String,
+
+    rt: Arc<Runtime>,
+    rpc_time_out: Option<Duration>,
+}
+
+impl PurgeMgr {
+    pub fn new(kv_api: Arc<dyn KVApi>, tenant: &str) -> Self {
+        let rt = Runtime::with_worker_threads(1).expect("PurgeMgr initialization failure");
+
+        PurgeMgr {
+            kv_api,
+            task_prefix: format!("{}/{}", PURGE_TASK_API_KEY_PREFIX, tenant),
+            rt: Arc::new(rt),
+            rpc_time_out: Some(Duration::from_secs(5)),
+        }
+    }
+
+    // Keyed by the table id, the tables of the same name dropped again have their own tasks.
+    fn task_key(&self, table_id: u64) -> String {
+        format!("{}/{}", self.task_prefix, table_id)
+    }
+
+    fn upsert_task(&self, task: &PurgeTask, seq: u64) -> Result<u64> {
+        let key = self.task_key(task.table_id);
+        let value = serde_json::to_vec(task)?;
+
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move {
+            kv_api
+                .upsert_kv(&key, MatchSeq::Exact(seq), Some(value), None)
+                .await
+        };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res.result {
+            Some((s, _)) if res.prev.as_ref().map(|(s, _)| *s).unwrap_or(0) == seq => Ok(s),
+            _ => Err(ErrorCode::PurgeTaskConflict(format!(
+                "Purge task of table {}.{} is changed by others, expect seq [{}]",
+                task.db, task.table, seq
+            ))),
+        }
+    }
+}
+
+impl PurgeMgrApi for PurgeMgr {
+    fn add_purge_task(&self, task: PurgeTask) -> Result<u64> {
+        self.upsert_task(&task, 0)
+    }
+
+    fn get_purge_tasks(&self) -> Result<Vec<SeqValue<PurgeTask>>> {
+        let task_prefix = self.task_prefix.clone();
+        let kv_api = self.kv_api.clone();
+        let prefix_list_kv = async move { kv_api.prefix_list_kv(task_prefix.as_str()).await };
+        let values = prefix_list_kv.wait_in(&self.rt, self.rpc_time_out)??;
+
+        let mut tasks = Vec::with_capacity(values.len());
+        for (_key, (s, val)) in values {
+            tasks.push((s, val.value.try_into()?));
+        }
+        Ok(tasks)
+    }
+
+    fn update_purge_task(&self, task: PurgeTask, seq: u64) -> Result<u64> {
+        self.upsert_task(&task, seq)
+    }
+}
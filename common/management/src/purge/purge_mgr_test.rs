@@ -0,0 +1,80 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::purge::purge_api::PurgeMgrApi;
+use crate::purge::purge_api::PurgeTask;
+use crate::PurgeMgr;
+
+async fn new_purge_api() -> Result<PurgeMgr> {
+    let kv_api = Arc::new(MetaEmbedded::new_temp().await?);
+    Ok(PurgeMgr::new(kv_api, "tenant1"))
+}
+
+fn create_test_purge_task(table_id: u64) -> PurgeTask {
+    PurgeTask {
+        db: "db1".to_string(),
+        table: format!("t{}", table_id),
+        table_id,
+        snapshot_location: Some("_ss/1".to_string()),
+        created_on: 1632000000,
+        ..Default::default()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_purge_tasks() -> Result<()> {
+    let purge_api = new_purge_api().await?;
+    assert!(purge_api.get_purge_tasks()?.is_empty());
+
+    let seq = purge_api.add_purge_task(create_test_purge_task(1))?;
+    purge_api.add_purge_task(create_test_purge_task(2))?;
+
+    // A table is added once.
+    let res = purge_api.add_purge_task(create_test_purge_task(1));
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::PurgeTaskConflict("").code()
+    );
+
+    let tasks = purge_api.get_purge_tasks()?;
+    assert_eq!(tasks.len(), 2);
+    assert!(tasks.iter().all(|(_, task)| !task.is_finished()));
+
+    // Progress.
+    let mut task = create_test_purge_task(1);
+    task.snapshot_location = None;
+    task.removed_objects = 10;
+    let new_seq = purge_api.update_purge_task(task.clone(), seq)?;
+    assert!(new_seq > seq);
+
+    let res = purge_api.update_purge_task(task.clone(), seq);
+    assert_eq!(
+        res.unwrap_err().code(),
+        ErrorCode::PurgeTaskConflict("").code()
+    );
+
+    let tasks = purge_api.get_purge_tasks()?;
+    let (_, saved) = tasks.iter().find(|(_, t)| t.table_id == 1).unwrap();
+    assert_eq!(saved, &task);
+    assert!(saved.is_finished());
+
+    Ok(())
+}
@@ -27,11 +27,38 @@ pub enum AuthType {
     Sha256 = 3,
 }
 
+/// The limits of every query of the user, 0 means unlimited.
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+)]
+pub struct UserQuota {
+    /// The rows returned to the client.
+    pub max_rows_returned: u64,
+    /// The bytes read from the tables.
+    pub max_bytes_scanned: u64,
+    /// The bytes of the blocks returned to the client.
+    pub max_result_bytes: u64,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct UserInfo {
     pub name: String,
     pub password: Vec<u8>,
     pub auth_type: AuthType,
+    #[serde(default)]
+    pub quota: UserQuota,
+    /// The settings profile the sessions of the user start with, over the `default` profile.
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 impl UserInfo {
@@ -40,6 +67,8 @@ impl UserInfo {
             name,
             password,
             auth_type,
+            quota: UserQuota::default(),
+            profile: None,
         }
     }
 }
@@ -59,6 +88,13 @@ pub trait UserMgrApi: Sync + Send {
         seq: Option<u64>,
     ) -> Result<Option<u64>>;
 
+    fn set_user_profile(
+        &self,
+        username: String,
+        profile: Option<String>,
+        seq: Option<u64>,
+    ) -> Result<u64>;
+
     fn drop_user(&self, username: String, seq: Option<u64>) -> Result<()>;
 }
 
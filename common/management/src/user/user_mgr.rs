@@ -159,6 +159,31 @@ impl UserMgrApi for UserMgr {
         }
     }
 
+    fn set_user_profile(
+        &self,
+        username: String,
+        profile: Option<String>,
+        seq: Option<u64>,
+    ) -> Result<u64> {
+        let (user_seq, mut user_info) = self.get_user(username.clone(), seq)?;
+        user_info.profile = profile;
+
+        let key = format!("{}/{}", self.user_prefix, user_info.name);
+        let value = serde_json::to_vec(&user_info)?;
+        let match_seq = MatchSeq::Exact(user_seq);
+
+        let kv_api = self.kv_api.clone();
+        let upsert_kv = async move { kv_api.upsert_kv(&key, match_seq, Some(value), None).await };
+        let res = upsert_kv.wait_in(&self.rt, self.rpc_time_out)??;
+        match res.result {
+            Some((s, _)) => Ok(s),
+            None => Err(ErrorCode::UnknownUser(format!(
+                "unknown user, or seq not match {}",
+                username
+            ))),
+        }
+    }
+
     fn drop_user(&self, username: String, seq: Option<u64>) -> Result<()> {
         let key = format!("{}/{}", self.user_prefix, username);
         let kv_api = self.kv_api.clone();
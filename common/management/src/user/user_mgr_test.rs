@@ -684,3 +684,90 @@ mod update {
         Ok(())
     }
 }
+
+mod set_profile {
+    use common_meta_types::KVValue;
+
+    use super::*;
+
+    #[test]
+    fn test_set_user_profile_normal() -> common_exception::Result<()> {
+        let test_user_name = "name";
+        let test_key = format!("__fd_users/tenant1/{}", test_user_name);
+
+        let mut user_info = UserInfo::new(
+            test_user_name.to_string(),
+            Vec::from("pass"),
+            AuthType::PlainText,
+        );
+        let prev_value = serde_json::to_vec(&user_info)?;
+
+        let mut kv = MockKV::new();
+        {
+            let test_key = test_key.clone();
+            kv.expect_get_kv()
+                .with(predicate::function(move |v| v == test_key.as_str()))
+                .times(1)
+                .return_once(move |_k| {
+                    Ok(GetKVActionReply {
+                        result: Some((3, KVValue {
+                            meta: None,
+                            value: prev_value,
+                        })),
+                    })
+                });
+        }
+
+        // The user is updated at the seq it is read at, the other fields are kept.
+        user_info.profile = Some("analyst".to_string());
+        let new_value = serde_json::to_vec(&user_info)?;
+        kv.expect_upsert_kv()
+            .with(
+                predicate::function(move |v| v == test_key.as_str()),
+                predicate::eq(MatchSeq::Exact(3)),
+                predicate::eq(Some(new_value)),
+                predicate::eq(None),
+            )
+            .times(1)
+            .return_once(|_, _, _, _meta| {
+                Ok(UpsertKVActionReply {
+                    prev: None,
+                    result: Some((4, KVValue {
+                        meta: None,
+                        value: vec![],
+                    })),
+                })
+            });
+
+        let kv = Arc::new(kv);
+        let user_mgr = UserMgr::new(kv, "tenant1");
+
+        let res = user_mgr.set_user_profile(
+            test_user_name.to_string(),
+            Some("analyst".to_string()),
+            None,
+        );
+        assert_eq!(res?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_user_profile_unknown() -> common_exception::Result<()> {
+        let test_user_name = "name";
+        let test_key = format!("__fd_users/tenant1/{}", test_user_name);
+
+        // upsert should NOT be called
+        let mut kv = MockKV::new();
+        kv.expect_get_kv()
+            .with(predicate::function(move |v| v == test_key.as_str()))
+            .times(1)
+            .return_once(move |_k| Ok(GetKVActionReply { result: None }));
+
+        let kv = Arc::new(kv);
+        let user_mgr = UserMgr::new(kv, "tenant1");
+
+        let res = user_mgr.set_user_profile(test_user_name.to_string(), None, None);
+        assert_eq!(res.unwrap_err().code(), ErrorCode::UnknownUser("").code());
+        Ok(())
+    }
+}
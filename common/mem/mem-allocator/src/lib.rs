@@ -14,12 +14,15 @@
 
 mod allocators;
 mod malloc_size;
+mod malloc_stats;
 // mod sizeof;
 
 pub use allocators::MallocSizeOfExt;
 pub use malloc_size::MallocShallowSizeOf;
 pub use malloc_size::MallocSizeOf;
 pub use malloc_size::MallocSizeOfOps;
+pub use malloc_stats::malloc_stats;
+pub use malloc_stats::MallocStat;
 
 /// Heap size of structure.
 ///
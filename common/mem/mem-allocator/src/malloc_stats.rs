@@ -0,0 +1,108 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::os::raw::c_void;
+use std::ptr;
+
+use tikv_jemalloc_sys as ffi;
+
+/// A statistic of the memory of the process, such as the bytes allocated by the application.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MallocStat {
+    pub name: String,
+    pub value: u64,
+    pub description: String,
+}
+
+impl MallocStat {
+    pub fn create(name: impl Into<String>, value: u64, description: impl Into<String>) -> Self {
+        MallocStat {
+            name: name.into(),
+            value,
+            description: description.into(),
+        }
+    }
+}
+
+// The `mallctl` names of the statistics, they are NUL terminated for the FFI.
+const JEMALLOC_STATS: &[(&str, &str)] = &[
+    ("stats.allocated\0", "Bytes allocated by the application"),
+    (
+        "stats.active\0",
+        "Bytes in the active pages allocated by the application",
+    ),
+    (
+        "stats.metadata\0",
+        "Bytes dedicated to the metadata of the allocator",
+    ),
+    (
+        "stats.resident\0",
+        "Bytes in the physically resident data pages mapped by the allocator",
+    ),
+    (
+        "stats.mapped\0",
+        "Bytes in the active extents mapped by the allocator",
+    ),
+    (
+        "stats.retained\0",
+        "Bytes in the virtual memory mappings kept by the allocator instead of unmapped",
+    ),
+];
+
+/// The statistics of the allocator, named like `jemalloc.stats.allocated`.
+///
+/// The statistics which the allocator can't report are skipped.
+pub fn malloc_stats() -> Vec<MallocStat> {
+    // jemalloc caches the statistics, they are refreshed by advancing the epoch.
+    let mut epoch: u64 = 1;
+    let mut len = std::mem::size_of::<u64>();
+    let epoch_ptr = &mut epoch as *mut u64 as *mut c_void;
+    unsafe {
+        ffi::mallctl(
+            b"epoch\0".as_ptr() as *const _,
+            epoch_ptr,
+            &mut len,
+            epoch_ptr,
+            len,
+        );
+    }
+
+    JEMALLOC_STATS
+        .iter()
+        .filter_map(|(name, description)| {
+            read_stat(name).map(|value| {
+                let name = format!("jemalloc.{}", name.trim_end_matches('\0'));
+                MallocStat::create(name, value as u64, *description)
+            })
+        })
+        .collect()
+}
+
+fn read_stat(name: &str) -> Option<usize> {
+    let mut value: usize = 0;
+    let mut len = std::mem::size_of::<usize>();
+    let code = unsafe {
+        ffi::mallctl(
+            name.as_ptr() as *const _,
+            &mut value as *mut usize as *mut c_void,
+            &mut len,
+            ptr::null_mut(),
+            0,
+        )
+    };
+    match code {
+        0 => Some(value),
+        _ => None,
+    }
+}
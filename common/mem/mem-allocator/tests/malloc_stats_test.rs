@@ -0,0 +1,33 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_mem_allocator::malloc_stats;
+
+#[test]
+fn test_malloc_stats() {
+    let buffer = vec![1u8; 1 << 20];
+
+    let stats = malloc_stats();
+    let stat = |name: &str| {
+        stats
+            .iter()
+            .find(|stat| stat.name == name)
+            .map(|stat| stat.value)
+            .unwrap()
+    };
+    let allocated = stat("jemalloc.stats.allocated");
+    assert!(allocated >= buffer.len() as u64);
+    assert!(stat("jemalloc.stats.active") >= allocated);
+    assert!(stat("jemalloc.stats.resident") >= stat("jemalloc.stats.active"));
+}
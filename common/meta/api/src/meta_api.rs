@@ -15,6 +15,7 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateTableReply;
@@ -22,6 +23,7 @@ use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -64,5 +66,12 @@ pub trait MetaApi: Send + Sync {
         option_value: String,
     ) -> Result<UpsertTableOptionReply>;
 
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply>;
+
     fn name(&self) -> String;
 }
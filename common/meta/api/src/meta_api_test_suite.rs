@@ -38,6 +38,7 @@ impl MetaApiTestSuite {
                 if_not_exists: false,
                 db: "db1".to_string(),
                 options: Default::default(),
+                tables: vec![],
             };
 
             let res = mt.create_database(plan.clone()).await;
@@ -52,6 +53,7 @@ impl MetaApiTestSuite {
                 if_not_exists: false,
                 db: "db1".to_string(),
                 options: Default::default(),
+                tables: vec![],
             };
 
             let res = mt.create_database(plan.clone()).await;
@@ -66,6 +68,7 @@ impl MetaApiTestSuite {
                 if_not_exists: false,
                 db: "db1".to_string(),
                 options: Default::default(),
+                tables: vec![],
             };
 
             let res = mt.create_database(plan.clone()).await;
@@ -89,6 +92,7 @@ impl MetaApiTestSuite {
                 if_not_exists: false,
                 db: "db2".to_string(),
                 options: Default::default(),
+                tables: vec![],
             };
 
             let res = mt.create_database(plan.clone()).await;
@@ -121,6 +125,7 @@ impl MetaApiTestSuite {
             mt.drop_database(DropDatabasePlan {
                 if_exists: false,
                 db: "db2".to_string(),
+                cascade: false,
             })
             .await?;
         }
@@ -137,6 +142,7 @@ impl MetaApiTestSuite {
             mt.drop_database(DropDatabasePlan {
                 if_exists: true,
                 db: "db2".to_string(),
+                cascade: false,
             })
             .await?;
         }
@@ -174,6 +180,7 @@ impl MetaApiTestSuite {
                 if_not_exists: false,
                 db: db_name.to_string(),
                 options: Default::default(),
+                tables: vec![],
             };
 
             let res = mt.create_database(plan.clone()).await?;
@@ -200,6 +207,8 @@ impl MetaApiTestSuite {
                 schema: schema.clone(),
                 options: options.clone(),
                 engine: "JSON".to_string(),
+                temporary: false,
+                as_select: None,
             };
 
             {
@@ -350,6 +359,8 @@ impl MetaApiTestSuite {
                 schema: schema.clone(),
                 options: options.clone(),
                 engine: "JSON".to_string(),
+                temporary: false,
+                as_select: None,
             };
 
             {
@@ -371,6 +382,62 @@ impl MetaApiTestSuite {
 
         Ok(())
     }
+
+    pub async fn database_create_with_tables<MT: MetaApi>(&self, mt: &MT) -> anyhow::Result<()> {
+        let db_name = "db1";
+
+        // Table schema with metadata(due to serde issue).
+        let schema = Arc::new(DataSchema::new(vec![DataField::new(
+            "number",
+            DataType::UInt64,
+            false,
+        )]));
+        let plan = |if_not_exists: bool, tables: &[&str]| CreateDatabasePlan {
+            if_not_exists,
+            db: db_name.to_string(),
+            options: Default::default(),
+            tables: tables
+                .iter()
+                .map(|table| CreateTablePlan {
+                    if_not_exists: false,
+                    db: db_name.to_string(),
+                    table: table.to_string(),
+                    schema: schema.clone(),
+                    options: Default::default(),
+                    engine: "JSON".to_string(),
+                    temporary: false,
+                    as_select: None,
+                })
+                .collect(),
+        };
+        let table_names = |tables: Vec<Arc<TableInfo>>| -> Vec<String> {
+            tables.iter().map(|t| t.name.clone()).collect()
+        };
+
+        tracing::info!("--- create db1 with 2 tables: tb1 tb2");
+        {
+            let res = mt.create_database(plan(false, &["tb1", "tb2"])).await?;
+            assert_eq!(1, res.database_id, "first database id is 1");
+
+            let res = mt.get_tables(db_name).await?;
+            assert_eq!(vec!["tb1", "tb2"], table_names(res.clone()));
+            assert_eq!(schema, res[0].schema);
+        }
+
+        tracing::info!("--- create db1 again, no table is added");
+        {
+            let res = mt.create_database(plan(false, &["tb3"])).await;
+            let err = res.unwrap_err();
+            assert_eq!(ErrorCode::DatabaseAlreadyExists("").code(), err.code());
+
+            mt.create_database(plan(true, &["tb3"])).await?;
+
+            let res = mt.get_tables(db_name).await?;
+            assert_eq!(vec!["tb1", "tb2"], table_names(res));
+        }
+
+        Ok(())
+    }
 }
 
 impl MetaApiTestSuite {
@@ -385,6 +452,7 @@ impl MetaApiTestSuite {
             if_not_exists: false,
             db: db_name.to_string(),
             options: Default::default(),
+            tables: vec![],
         };
 
         let res = mt.create_database(plan.clone()).await?;
@@ -27,5 +27,8 @@ mod meta_embedded;
 mod kv_api_impl_test;
 #[cfg(test)]
 mod meta_api_impl_test;
+#[cfg(test)]
+mod meta_embedded_test;
 
 pub use meta_embedded::MetaEmbedded;
+pub use meta_embedded::DATA_VERSION;
@@ -20,6 +20,7 @@ use common_arrow::arrow::datatypes::Schema;
 use common_arrow::arrow::io::flight::serialize_schema;
 use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
 use common_arrow::arrow_format::flight::data::FlightData;
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api::MetaApi;
@@ -32,6 +33,7 @@ use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::Table;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -44,12 +46,22 @@ use crate::MetaEmbedded;
 #[async_trait]
 impl MetaApi for MetaEmbedded {
     async fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
-        let cmd = Cmd::CreateDatabase {
-            name: plan.db.clone(),
-            db: DatabaseInfo {
-                database_id: 0,
-                db: plan.db.clone(),
-            },
+        let db = DatabaseInfo {
+            database_id: 0,
+            db: plan.db.clone(),
+            options: plan.options.clone(),
+        };
+        let cmd = if plan.tables.is_empty() {
+            Cmd::CreateDatabase {
+                name: plan.db.clone(),
+                db,
+            }
+        } else {
+            Cmd::CreateDatabaseWithTables {
+                name: plan.db.clone(),
+                db,
+                tables: plan.tables.iter().map(table_of_plan).collect(),
+            }
         };
 
         let mut sm = self.inner.lock().await;
@@ -118,26 +130,11 @@ impl MetaApi for MetaEmbedded {
 
         tracing::info!("create table: {:}: {:?}", &db_name, &table_name);
 
-        let options = IpcWriteOptions::default();
-        let flight_data = serialize_schema(&plan.schema.to_arrow(), &options);
-
-        let table = Table {
-            table_id: 0,
-            table_version: 0,
-            table_name: table_name.to_string(),
-            database_id: 0, // this field is unused during the creation of table
-            db_name: db_name.to_string(),
-            schema: flight_data.data_header,
-            table_engine: plan.engine.clone(),
-            table_options: plan.options.clone(),
-            parts: Default::default(),
-        };
-
         let cr = Cmd::CreateTable {
             db_name: db_name.clone(),
             table_name: table_name.clone(),
             if_not_exists,
-            table,
+            table: table_of_plan(&plan),
         };
 
         let mut sm = self.inner.lock().await;
@@ -263,15 +260,89 @@ impl MetaApi for MetaEmbedded {
 
     async fn upsert_table_option(
         &self,
-        _table_id: MetaId,
-        _table_version: MetaVersion,
-        _option_key: String,
-        _option_value: String,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        option_key: String,
+        option_value: String,
     ) -> Result<UpsertTableOptionReply> {
-        todo!()
+        let cmd = Cmd::UpsertTableOption {
+            table_id,
+            table_version,
+            option_key,
+            option_value,
+        };
+
+        let mut sm = self.inner.lock().await;
+        let res = sm.apply_cmd(&cmd).await?;
+
+        match res {
+            AppliedState::Table { prev: None, .. } => Err(ErrorCode::UnknownTable(format!(
+                "Unknown table of id: {}",
+                table_id
+            ))),
+            AppliedState::Table {
+                prev: Some(prev), ..
+            } if prev.table_version != table_version => Err(ErrorCode::CommitTableError(format!(
+                "expecting table version: [{}], but got [{}]. (table_id {})",
+                table_version, prev.table_version, table_id
+            ))),
+            AppliedState::Table { .. } => Ok(()),
+            _ => Err(ErrorCode::MetaNodeInternalError("not a Table result")),
+        }
+    }
+
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        let options = IpcWriteOptions::default();
+        let flight_data = serialize_schema(&schema.to_arrow(), &options);
+
+        let cmd = Cmd::UpdateTableSchema {
+            table_id,
+            table_version,
+            schema: flight_data.data_header,
+        };
+
+        let mut sm = self.inner.lock().await;
+        let res = sm.apply_cmd(&cmd).await?;
+
+        match res {
+            AppliedState::Table { prev: None, .. } => Err(ErrorCode::UnknownTable(format!(
+                "Unknown table of id: {}",
+                table_id
+            ))),
+            AppliedState::Table {
+                prev: Some(prev), ..
+            } if prev.table_version != table_version => Err(ErrorCode::CommitTableError(format!(
+                "expecting table version: [{}], but got [{}]. (table_id {})",
+                prev.table_version, table_version, table_id
+            ))),
+            AppliedState::Table { .. } => Ok(()),
+            _ => Err(ErrorCode::MetaNodeInternalError("not a Table result")),
+        }
     }
 
     fn name(&self) -> String {
         "meta-embedded".to_string()
     }
 }
+
+fn table_of_plan(plan: &CreateTablePlan) -> Table {
+    let options = IpcWriteOptions::default();
+    let flight_data = serialize_schema(&plan.schema.to_arrow(), &options);
+
+    Table {
+        table_id: 0,
+        table_version: 0,
+        table_name: plan.table.clone(),
+        database_id: 0, // this field is unused during the creation of table
+        db_name: plan.db.clone(),
+        schema: flight_data.data_header,
+        table_engine: plan.engine.clone(),
+        table_options: plan.options.clone(),
+        parts: Default::default(),
+    }
+}
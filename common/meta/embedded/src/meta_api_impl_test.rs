@@ -40,3 +40,9 @@ async fn test_meta_embedded_table_list() -> anyhow::Result<()> {
     let mt = MetaEmbedded::new_temp().await?;
     MetaApiTestSuite {}.table_list(&mt).await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_embedded_database_create_with_tables() -> anyhow::Result<()> {
+    let mt = MetaEmbedded::new_temp().await?;
+    MetaApiTestSuite {}.database_create_with_tables(&mt).await
+}
@@ -18,6 +18,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use common_base::tokio::sync::Mutex;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api::KVApi;
 use common_meta_raft_store::config::RaftConfig;
@@ -34,6 +35,12 @@ use common_meta_types::PrefixListReply;
 use common_meta_types::UpsertKVActionReply;
 use common_tracing::tracing;
 
+/// Version of the data layout of a persistent `MetaEmbedded`, bumped on incompatible changes.
+pub const DATA_VERSION: u64 = 1;
+
+const DATA_VERSION_KEY: &str = "__fd_embedded_meta_data_version";
+const PERSISTENT_TREE_PREFIX: &str = "embedded-meta-";
+
 /// Local storage that provides the API defined by `KVApi+MetaApi`.
 ///
 /// It is just a wrapped `StateMachine`, which is the same one used by raft driven metasrv.
@@ -94,6 +101,66 @@ impl MetaEmbedded {
     pub fn sync_new_temp() -> common_exception::Result<MetaEmbedded> {
         futures::executor::block_on(MetaEmbedded::new_temp())
     }
+
+    /// Opens the meta store persisted in `dir`, for a databend-query running without metasrv.
+    ///
+    /// The data written by an older version is upgraded,
+    /// the data written by a newer version is refused instead of being misread.
+    ///
+    /// The sled db is process-wise, it fails if the db is already opened at another dir,
+    /// e.g. a temp one, instead of keeping the data where it does not persist.
+    pub async fn open_persistent(dir: &str) -> common_exception::Result<MetaEmbedded> {
+        common_meta_sled_store::try_init_sled_db(dir.to_string())?;
+
+        let mut config = RaftConfig::empty();
+        config.sled_tree_prefix = PERSISTENT_TREE_PREFIX.to_string();
+
+        let meta = MetaEmbedded {
+            inner: Arc::new(Mutex::new(StateMachine::open(&config, 0).await?)),
+        };
+        meta.upgrade().await?;
+        Ok(meta)
+    }
+
+    /// Brings the data to `DATA_VERSION`, returns the version before the upgrade.
+    pub async fn upgrade(&self) -> common_exception::Result<u64> {
+        let reply = self.get_kv(DATA_VERSION_KEY).await?;
+        let version = match reply.result {
+            // A new store, nothing to upgrade.
+            None => DATA_VERSION,
+            Some(seq_value) => {
+                let bytes: [u8; 8] = seq_value.1.value.as_slice().try_into().map_err(|_| {
+                    ErrorCode::MetaStoreDamaged("Bad version of the embedded meta data")
+                })?;
+                u64::from_be_bytes(bytes)
+            }
+        };
+
+        if version > DATA_VERSION {
+            return Err(ErrorCode::MetaStoreDamaged(format!(
+                "The embedded meta data is of version {}, but this databend-query only understands version {} or older",
+                version, DATA_VERSION
+            )));
+        }
+
+        // The upgrades from each older version go here, one version at a time.
+        if version < DATA_VERSION {
+            tracing::info!(
+                "Upgrade the embedded meta data from version {} to {}",
+                version,
+                DATA_VERSION
+            );
+        }
+
+        self.upsert_kv(
+            DATA_VERSION_KEY,
+            MatchSeq::Any,
+            Some(DATA_VERSION.to_be_bytes().to_vec()),
+            None,
+        )
+        .await?;
+        Ok(version)
+    }
 }
 
 #[async_trait]
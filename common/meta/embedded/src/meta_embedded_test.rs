@@ -0,0 +1,70 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_meta_api::KVApi;
+use common_meta_types::MatchSeq;
+
+use crate::MetaEmbedded;
+use crate::DATA_VERSION;
+
+async fn set_version(meta: &MetaEmbedded, version: u64) -> anyhow::Result<()> {
+    meta.upsert_kv(
+        "__fd_embedded_meta_data_version",
+        MatchSeq::Any,
+        Some(version.to_be_bytes().to_vec()),
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_embedded_upgrade() -> anyhow::Result<()> {
+    let meta = MetaEmbedded::new_temp().await?;
+
+    // A new store.
+    assert_eq!(DATA_VERSION, meta.upgrade().await?);
+    assert_eq!(DATA_VERSION, meta.upgrade().await?);
+
+    // Written by an older version.
+    set_version(&meta, DATA_VERSION - 1).await?;
+    assert_eq!(DATA_VERSION - 1, meta.upgrade().await?);
+    assert_eq!(DATA_VERSION, meta.upgrade().await?);
+
+    // Written by a newer version.
+    set_version(&meta, DATA_VERSION + 1).await?;
+    let res = meta.upgrade().await;
+    assert_eq!(
+        ErrorCode::MetaStoreDamaged("").code(),
+        res.unwrap_err().code()
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_embedded_persistent_after_temp() -> anyhow::Result<()> {
+    // The process-wise sled db is opened at a temp dir.
+    let _meta = MetaEmbedded::new_temp().await?;
+
+    let dir = tempfile::tempdir()?;
+    let res = MetaEmbedded::open_persistent(dir.path().to_str().unwrap()).await;
+    assert_eq!(
+        ErrorCode::InvalidConfig("").code(),
+        res.err().unwrap().code()
+    );
+    Ok(())
+}
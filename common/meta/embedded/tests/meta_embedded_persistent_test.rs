@@ -0,0 +1,44 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::MatchSeq;
+
+// In its own process: the sled db is process-wise, the unit tests open it at temp dirs.
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_embedded_open_persistent() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().to_str().unwrap().to_string();
+
+    let meta = MetaEmbedded::open_persistent(&path).await?;
+    meta.upsert_kv("k", MatchSeq::Any, Some(b"v".to_vec()), None)
+        .await?;
+
+    // The same dir shares the db.
+    let reopened = MetaEmbedded::open_persistent(&path).await?;
+    let reply = reopened.get_kv("k").await?;
+    assert_eq!(b"v".to_vec(), reply.result.unwrap().1.value);
+    assert!(std::fs::read_dir(&path)?.next().is_some());
+
+    // Another dir can not be opened in the same process.
+    let other = tempfile::tempdir()?;
+    let res = MetaEmbedded::open_persistent(other.path().to_str().unwrap()).await;
+    assert_eq!(
+        ErrorCode::InvalidConfig("").code(),
+        res.err().unwrap().code()
+    );
+    Ok(())
+}
@@ -17,6 +17,7 @@ use std::io::Cursor;
 use std::sync::Arc;
 
 use common_arrow::arrow_format::flight::data::Action;
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateTableReply;
@@ -29,6 +30,7 @@ use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::PrefixListReply;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertKVActionReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
@@ -73,6 +75,7 @@ pub enum MetaFlightAction {
     GetTables(GetTablesAction),
     GetDatabases(GetDatabasesAction),
     CommitTable(UpsertTableOptionReq),
+    UpdateTableSchema(UpdateTableSchemaReq),
 
     // general purpose kv
     UpsertKV(UpsertKVAction),
@@ -82,6 +85,30 @@ pub enum MetaFlightAction {
     PrefixListKV(PrefixListReq),
 }
 
+impl MetaFlightAction {
+    /// The name of the action, to label the metrics of the meta RPCs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MetaFlightAction::CreateDatabase(_) => "CreateDatabase",
+            MetaFlightAction::GetDatabase(_) => "GetDatabase",
+            MetaFlightAction::DropDatabase(_) => "DropDatabase",
+            MetaFlightAction::CreateTable(_) => "CreateTable",
+            MetaFlightAction::DropTable(_) => "DropTable",
+            MetaFlightAction::GetTable(_) => "GetTable",
+            MetaFlightAction::GetTableExt(_) => "GetTableExt",
+            MetaFlightAction::GetTables(_) => "GetTables",
+            MetaFlightAction::GetDatabases(_) => "GetDatabases",
+            MetaFlightAction::CommitTable(_) => "CommitTable",
+            MetaFlightAction::UpdateTableSchema(_) => "UpdateTableSchema",
+            MetaFlightAction::UpsertKV(_) => "UpsertKV",
+            MetaFlightAction::UpdateKVMeta(_) => "UpdateKVMeta",
+            MetaFlightAction::GetKV(_) => "GetKV",
+            MetaFlightAction::MGetKV(_) => "MGetKV",
+            MetaFlightAction::PrefixListKV(_) => "PrefixListKV",
+        }
+    }
+}
+
 /// Try convert tonic::Request<Action> to DoActionAction.
 impl TryInto<MetaFlightAction> for Request<Action> {
     type Error = tonic::Status;
@@ -269,6 +296,19 @@ action_declare!(
     MetaFlightAction::CommitTable
 );
 
+// - update table schema
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateTableSchemaReq {
+    pub table_id: MetaId,
+    pub table_version: MetaVersion,
+    pub schema: DataSchemaRef,
+}
+action_declare!(
+    UpdateTableSchemaReq,
+    UpdateTableSchemaReply,
+    MetaFlightAction::UpdateTableSchema
+);
+
 // - get tables
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct GetTablesAction {
@@ -14,6 +14,7 @@
 
 use std::convert::TryInto;
 use std::time::Duration;
+use std::time::Instant;
 
 use common_arrow::arrow_format::flight::data::Action;
 use common_arrow::arrow_format::flight::data::BasicAuth;
@@ -26,6 +27,8 @@ use common_flight_rpc::FlightClientTlsConfig;
 use common_tracing::tracing;
 use futures::stream;
 use futures::StreamExt;
+use metrics::counter;
+use metrics::histogram;
 use prost::Message;
 use serde::de::DeserializeOwned;
 use tonic::codegen::InterceptedService;
@@ -37,6 +40,9 @@ use tonic::Request;
 use crate::flight_action::MetaFlightAction;
 use crate::flight_action::RequestFor;
 use crate::flight_client_conf::MetaFlightClientConf;
+use crate::metrics::METRIC_META_RPC_ERRORS;
+use crate::metrics::METRIC_META_RPC_REQUESTS;
+use crate::metrics::METRIC_META_RPC_USEDTIME;
 
 #[derive(Clone, Debug)]
 pub struct MetaFlightClient {
@@ -127,7 +133,21 @@ impl MetaFlightClient {
         R: DeserializeOwned,
     {
         let act: MetaFlightAction = v.into();
-        let req: Request<Action> = (&act).try_into()?;
+        let action = act.name();
+        counter!(METRIC_META_RPC_REQUESTS, 1, "action" => action);
+
+        let start = Instant::now();
+        let reply = self.send_action(&act).await;
+        histogram!(METRIC_META_RPC_USEDTIME, start.elapsed(), "action" => action);
+        if reply.is_err() {
+            counter!(METRIC_META_RPC_ERRORS, 1, "action" => action);
+        }
+        reply
+    }
+
+    async fn send_action<R>(&self, act: &MetaFlightAction) -> Result<R>
+    where R: DeserializeOwned {
+        let req: Request<Action> = act.try_into()?;
         let req = common_tracing::inject_span_to_tonic_request(req);
 
         let mut stream = self.client.clone().do_action(req).await?.into_inner();
@@ -15,6 +15,7 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_meta_api::MetaApi;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateTableReply;
@@ -22,6 +23,7 @@ use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -38,6 +40,7 @@ use crate::GetTableAction;
 use crate::GetTableExtReq;
 use crate::GetTablesAction;
 use crate::MetaFlightClient;
+use crate::UpdateTableSchemaReq;
 use crate::UpsertTableOptionReq;
 
 #[async_trait::async_trait]
@@ -118,6 +121,20 @@ impl MetaApi for MetaFlightClient {
         .await
     }
 
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> common_exception::Result<UpdateTableSchemaReply> {
+        self.do_action(UpdateTableSchemaReq {
+            table_id,
+            table_version,
+            schema,
+        })
+        .await
+    }
+
     fn name(&self) -> String {
         "MetaFlightClient".to_string()
     }
@@ -19,6 +19,7 @@ mod flight_client;
 #[macro_use]
 mod flight_action;
 mod flight_client_conf;
+mod metrics;
 pub(crate) mod tests;
 
 pub mod impls;
@@ -23,6 +23,10 @@ pub const KVSRV_API_PORT: &str = "KVSRV_API_PORT";
 pub const KVSRV_RAFT_DIR: &str = "KVSRV_RAFT_DIR";
 pub const KVSRV_NO_SYNC: &str = "KVSRV_NO_SYNC";
 pub const KVSRV_SNAPSHOT_LOGS_SINCE_LAST: &str = "KVSRV_SNAPSHOT_LOGS_SINCE_LAST";
+pub const KVSRV_SNAPSHOT_INTERVAL_SECS: &str = "KVSRV_SNAPSHOT_INTERVAL_SECS";
+pub const KVSRV_COMPACTION_LOG_ENTRIES: &str = "KVSRV_COMPACTION_LOG_ENTRIES";
+pub const KVSRV_COMPACTION_LOG_BYTES: &str = "KVSRV_COMPACTION_LOG_BYTES";
+pub const KVSRV_COMPACTION_IDLE_MS: &str = "KVSRV_COMPACTION_IDLE_MS";
 pub const KVSRV_HEARTBEAT_INTERVAL: &str = "KVSRV_HEARTBEAT_INTERVAL";
 pub const KVSRV_INSTALL_SNAPSHOT_TIMEOUT: &str = "KVSRV_INSTALL_SNAPSHOT_TIMEOUT";
 pub const KVSRV_BOOT: &str = "KVSRV_BOOT";
@@ -78,6 +82,42 @@ pub struct RaftConfig {
     )]
     pub snapshot_logs_since_last: u64,
 
+    #[structopt(
+    long,
+    env = KVSRV_SNAPSHOT_INTERVAL_SECS,
+    default_value = "3600",
+    help = concat!("The max seconds between two snapshots. When idle, a node takes a snapshot",
+    " if the last one is older than this and there are logs since it. 0 to disable.")
+    )]
+    pub snapshot_interval_secs: u64,
+
+    #[structopt(
+    long,
+    env = KVSRV_COMPACTION_LOG_ENTRIES,
+    default_value = "100000",
+    help = concat!("When idle, a node compacts the raft log if it holds more entries than this.",
+    " 0 to disable.")
+    )]
+    pub compaction_log_entries: u64,
+
+    #[structopt(
+    long,
+    env = KVSRV_COMPACTION_LOG_BYTES,
+    default_value = "67108864",
+    help = concat!("When idle, a node compacts the raft log if it takes more bytes than this.",
+    " 0 to disable.")
+    )]
+    pub compaction_log_bytes: u64,
+
+    #[structopt(
+    long,
+    env = KVSRV_COMPACTION_IDLE_MS,
+    default_value = "3000",
+    help = concat!("A node is idle if no log is applied in this many milli seconds,",
+    " it checks whether to compact the raft log at this interval. 0 to disable idle compaction.")
+    )]
+    pub compaction_idle_ms: u64,
+
     #[structopt(
     long,
     env = KVSRV_HEARTBEAT_INTERVAL,
@@ -18,3 +18,4 @@ mod raft_log;
 mod raft_log_test;
 
 pub use raft_log::RaftLog;
+pub use raft_log::RaftLogStat;
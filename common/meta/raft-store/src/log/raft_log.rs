@@ -15,6 +15,8 @@
 use std::ops::RangeBounds;
 
 use async_raft::raft::Entry;
+use common_exception::ErrorCode;
+use common_exception::ToErrorCode;
 use common_meta_sled_store::sled;
 use common_meta_sled_store::AsKeySpace;
 use common_meta_sled_store::SledTree;
@@ -27,6 +29,13 @@ use crate::sled_key_spaces::Logs;
 
 const TREE_RAFT_LOG: &str = "raft_log";
 
+/// The number of entries and the bytes of the raft log on disk.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RaftLogStat {
+    pub entries: u64,
+    pub bytes: u64,
+}
+
 /// RaftLog stores the logs of a raft node.
 /// It is part of MetaStore.
 pub struct RaftLog {
@@ -116,6 +125,18 @@ impl RaftLog {
         self.logs().insert_value(log).await
     }
 
+    /// Count the entries and the bytes of the serialized keys and values of the log.
+    /// It scans the whole log, which is small unless the compaction falls behind.
+    pub fn stat(&self) -> common_exception::Result<RaftLogStat> {
+        let mut stat = RaftLogStat::default();
+        for item in self.inner.tree.iter() {
+            let (k, v) = item.map_err_to_code(ErrorCode::MetaStoreDamaged, || "stat raft log")?;
+            stat.entries += 1;
+            stat.bytes += (k.len() + v.len()) as u64;
+        }
+        Ok(stat)
+    }
+
     /// Returns a borrowed key space in sled::Tree for logs
     fn logs(&self) -> AsKeySpace<Logs> {
         self.inner.key_space()
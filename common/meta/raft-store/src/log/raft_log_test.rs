@@ -22,6 +22,7 @@ use common_meta_types::LogEntry;
 
 use crate::init_raft_store_ut;
 use crate::log::RaftLog;
+use crate::log::RaftLogStat;
 use crate::testing::new_raft_test_context;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -280,3 +281,37 @@ async fn test_raft_log_range_remove() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_raft_log_stat() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+    let tc = new_raft_test_context();
+    let db = &tc.db;
+    let rl = RaftLog::open(db, &tc.raft_config).await?;
+
+    assert_eq!(RaftLogStat::default(), rl.stat()?);
+
+    let logs: Vec<Entry<LogEntry>> = vec![
+        Entry {
+            log_id: LogId { term: 1, index: 2 },
+            payload: EntryPayload::Blank,
+        },
+        Entry {
+            log_id: LogId { term: 1, index: 3 },
+            payload: EntryPayload::Blank,
+        },
+    ];
+    rl.append(&logs).await?;
+
+    let stat = rl.stat()?;
+    assert_eq!(2, stat.entries);
+    assert!(stat.bytes > 0);
+
+    rl.range_remove(0..3).await?;
+    let after = rl.stat()?;
+    assert_eq!(1, after.entries);
+    assert!(after.bytes < stat.bytes);
+
+    Ok(())
+}
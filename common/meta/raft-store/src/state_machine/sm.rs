@@ -341,24 +341,27 @@ impl StateMachine {
 
             Cmd::CreateDatabase {
                 ref name, ref db, ..
-            } => {
-                let mut db = db.clone();
-                db.database_id = self.incr_seq(SEQ_DATABASE_ID).await?;
-
-                let dbs = self.databases();
-
-                let (prev, result) = self
-                    .sub_tree_upsert(dbs, name, &MatchSeq::Exact(0), Operation::Update(db), None)
-                    .await?;
+            } => self.apply_create_database(name, db).await,
 
-                // if it is just created
-                if prev.is_none() && result.is_some() {
-                    // TODO(xp): reconsider this impl. it may not be required.
-                    self.incr_seq(SEQ_DATABASE_META_ID).await?;
+            Cmd::CreateDatabaseWithTables {
+                ref name,
+                ref db,
+                ref tables,
+            } => {
+                let res = self.apply_create_database(name, db).await?;
+
+                // The tables are only created along with a new database.
+                if let AppliedState::DataBase {
+                    prev: None,
+                    result: Some(_),
+                } = &res
+                {
+                    for table in tables {
+                        self.apply_create_table(name, &table.table_name, table)
+                            .await?;
+                    }
                 }
-
-                tracing::debug!("applied create Database: {} {:?}", name, result);
-                Ok((prev, result).into())
+                Ok(res)
             }
 
             Cmd::DropDatabase { ref name } => {
@@ -383,44 +386,7 @@ impl StateMachine {
                 ref table_name,
                 if_not_exists: _,
                 ref table,
-            } => {
-                let dbi = self
-                    .databases()
-                    .get(db_name)?
-                    .ok_or_else(|| ErrorCode::UnknownDatabase(db_name.to_string()))?;
-
-                let dbi = dbi.1.value;
-                let curr_table_id = self
-                    .table_lookup
-                    .get(&(dbi.database_id, table_name.clone()));
-
-                if let Some(table_id) = curr_table_id {
-                    let prev = self.tables.get(table_id);
-                    Ok((prev.cloned(), prev.cloned()).into())
-                } else {
-                    let table_id = self.incr_seq(SEQ_TABLE_ID).await?;
-                    let table = Table {
-                        table_id,
-                        table_version: 0,
-                        table_name: table_name.to_string(),
-                        database_id: dbi.database_id,
-                        db_name: db_name.to_string(),
-                        schema: table.schema.clone(),
-                        table_engine: table.table_engine.clone(),
-                        table_options: table.table_options.clone(),
-                        parts: table.parts.clone(),
-                    };
-                    self.incr_seq(SEQ_DATABASE_META_ID).await?;
-
-                    self.table_lookup
-                        .insert((dbi.database_id, table_name.clone()), table_id);
-                    self.tables.insert(table.table_id, table.clone());
-
-                    tracing::debug!("applied CreateTable: {}={:?}", table_name, table);
-
-                    Ok((None, Some(table)).into())
-                }
-            }
+            } => self.apply_create_table(db_name, table_name, table).await,
 
             Cmd::DropTable {
                 ref db_name,
@@ -451,6 +417,53 @@ impl StateMachine {
                 }
             }
 
+            Cmd::UpdateTableSchema {
+                ref table_id,
+                ref table_version,
+                ref schema,
+            } => {
+                let prev = self.tables.get(table_id).cloned();
+                match prev {
+                    Some(ref table) if table.table_version == *table_version => {
+                        let mut table = table.clone();
+                        table.schema = schema.clone();
+                        table.table_version += 1;
+                        self.tables.insert(*table_id, table.clone());
+                        self.incr_seq(SEQ_DATABASE_META_ID).await?;
+
+                        tracing::debug!("applied UpdateTableSchema: {}={:?}", table_id, table);
+                        Ok((prev, Some(table)).into())
+                    }
+                    // The table is missing or changed by others, nothing is applied.
+                    _ => Ok((prev.clone(), prev).into()),
+                }
+            }
+
+            Cmd::UpsertTableOption {
+                ref table_id,
+                ref table_version,
+                ref option_key,
+                ref option_value,
+            } => {
+                let prev = self.tables.get(table_id).cloned();
+                match prev {
+                    Some(ref table) if table.table_version == *table_version => {
+                        let mut table = table.clone();
+                        table
+                            .table_options
+                            .insert(option_key.clone(), option_value.clone());
+                        table.table_version += 1;
+                        self.tables.insert(*table_id, table.clone());
+                        self.incr_seq(SEQ_DATABASE_META_ID).await?;
+
+                        tracing::debug!("applied UpsertTableOption: {}={:?}", table_id, table);
+                        Ok((prev, Some(table)).into())
+                    }
+                    // The table is missing or changed by others, nothing is applied.
+                    _ => Ok((prev.clone(), prev).into()),
+                }
+            }
+
             Cmd::UpsertKV {
                 key,
                 seq,
@@ -467,6 +480,75 @@ impl StateMachine {
         }
     }
 
+    async fn apply_create_database(
+        &mut self,
+        name: &str,
+        db: &DatabaseInfo,
+    ) -> common_exception::Result<AppliedState> {
+        let mut db = db.clone();
+        db.database_id = self.incr_seq(SEQ_DATABASE_ID).await?;
+
+        let dbs = self.databases();
+        let name = name.to_string();
+
+        let (prev, result) = self
+            .sub_tree_upsert(dbs, &name, &MatchSeq::Exact(0), Operation::Update(db), None)
+            .await?;
+
+        // if it is just created
+        if prev.is_none() && result.is_some() {
+            // TODO(xp): reconsider this impl. it may not be required.
+            self.incr_seq(SEQ_DATABASE_META_ID).await?;
+        }
+
+        tracing::debug!("applied create Database: {} {:?}", name, result);
+        Ok((prev, result).into())
+    }
+
+    async fn apply_create_table(
+        &mut self,
+        db_name: &str,
+        table_name: &str,
+        table: &Table,
+    ) -> common_exception::Result<AppliedState> {
+        let dbi = self
+            .databases()
+            .get(&db_name.to_string())?
+            .ok_or_else(|| ErrorCode::UnknownDatabase(db_name.to_string()))?;
+
+        let dbi = dbi.1.value;
+        let curr_table_id = self
+            .table_lookup
+            .get(&(dbi.database_id, table_name.to_string()));
+
+        if let Some(table_id) = curr_table_id {
+            let prev = self.tables.get(table_id);
+            Ok((prev.cloned(), prev.cloned()).into())
+        } else {
+            let table_id = self.incr_seq(SEQ_TABLE_ID).await?;
+            let table = Table {
+                table_id,
+                table_version: 0,
+                table_name: table_name.to_string(),
+                database_id: dbi.database_id,
+                db_name: db_name.to_string(),
+                schema: table.schema.clone(),
+                table_engine: table.table_engine.clone(),
+                table_options: table.table_options.clone(),
+                parts: table.parts.clone(),
+            };
+            self.incr_seq(SEQ_DATABASE_META_ID).await?;
+
+            self.table_lookup
+                .insert((dbi.database_id, table_name.to_string()), table_id);
+            self.tables.insert(table.table_id, table.clone());
+
+            tracing::debug!("applied CreateTable: {}={:?}", table_name, table);
+
+            Ok((None, Some(table)).into())
+        }
+    }
+
     async fn sub_tree_upsert<'s, V, KS>(
         &'s self,
         sub_tree: AsKeySpace<'s, KS>,
@@ -28,6 +28,7 @@ use common_meta_types::LogEntry;
 use common_meta_types::MatchSeq;
 use common_meta_types::Operation;
 use common_meta_types::SeqValue;
+use common_meta_types::Table;
 use common_tracing::tracing;
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
@@ -494,6 +495,190 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_update_table_schema() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    sm.apply_cmd(&Cmd::CreateDatabase {
+        name: "db1".to_string(),
+        db: Default::default(),
+    })
+    .await?;
+    let resp = sm
+        .apply_cmd(&Cmd::CreateTable {
+            db_name: "db1".to_string(),
+            table_name: "t1".to_string(),
+            if_not_exists: false,
+            table: Table {
+                schema: vec![1],
+                ..Default::default()
+            },
+        })
+        .await?;
+    let table_id = match resp {
+        AppliedState::Table {
+            result: Some(table),
+            ..
+        } => table.table_id,
+        _ => panic!("expect AppliedState::Table"),
+    };
+
+    let update = |table_id: u64, table_version: u64, schema: Vec<u8>| Cmd::UpdateTableSchema {
+        table_id,
+        table_version,
+        schema,
+    };
+    let versions = |resp: AppliedState| match resp {
+        AppliedState::Table { prev, result } => (
+            prev.map(|t| (t.table_version, t.schema)),
+            result.map(|t| (t.table_version, t.schema)),
+        ),
+        _ => panic!("expect AppliedState::Table"),
+    };
+
+    // The version matches.
+    let resp = sm.apply_cmd(&update(table_id, 0, vec![2])).await?;
+    assert_eq!((Some((0, vec![1])), Some((1, vec![2]))), versions(resp));
+    assert_eq!(vec![2], sm.tables.get(&table_id).unwrap().schema);
+
+    // The version is stale, nothing is applied.
+    let resp = sm.apply_cmd(&update(table_id, 0, vec![3])).await?;
+    assert_eq!((Some((1, vec![2])), Some((1, vec![2]))), versions(resp));
+
+    // Unknown table.
+    let resp = sm.apply_cmd(&update(table_id + 100, 0, vec![3])).await?;
+    assert_eq!((None, None), versions(resp));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_upsert_table_option() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    sm.apply_cmd(&Cmd::CreateDatabase {
+        name: "db1".to_string(),
+        db: Default::default(),
+    })
+    .await?;
+    let resp = sm
+        .apply_cmd(&Cmd::CreateTable {
+            db_name: "db1".to_string(),
+            table_name: "t1".to_string(),
+            if_not_exists: false,
+            table: Default::default(),
+        })
+        .await?;
+    let table_id = match resp {
+        AppliedState::Table {
+            result: Some(table),
+            ..
+        } => table.table_id,
+        _ => panic!("expect AppliedState::Table"),
+    };
+
+    let upsert = |table_id: u64, table_version: u64, value: &str| Cmd::UpsertTableOption {
+        table_id,
+        table_version,
+        option_key: "k".to_string(),
+        option_value: value.to_string(),
+    };
+    let versions = |resp: AppliedState| match resp {
+        AppliedState::Table { prev, result } => (
+            prev.map(|t| (t.table_version, t.table_options.get("k").cloned())),
+            result.map(|t| (t.table_version, t.table_options.get("k").cloned())),
+        ),
+        _ => panic!("expect AppliedState::Table"),
+    };
+    let v = |s: &str| Some(s.to_string());
+
+    // The version matches.
+    let resp = sm.apply_cmd(&upsert(table_id, 0, "v1")).await?;
+    assert_eq!((Some((0, None)), Some((1, v("v1")))), versions(resp));
+
+    // The version is stale, nothing is applied.
+    let resp = sm.apply_cmd(&upsert(table_id, 0, "v2")).await?;
+    assert_eq!((Some((1, v("v1"))), Some((1, v("v1")))), versions(resp));
+
+    // Overwrite the option with the latest version.
+    let resp = sm.apply_cmd(&upsert(table_id, 1, "v2")).await?;
+    assert_eq!((Some((1, v("v1"))), Some((2, v("v2")))), versions(resp));
+    assert_eq!(
+        v("v2"),
+        sm.tables
+            .get(&table_id)
+            .unwrap()
+            .table_options
+            .get("k")
+            .cloned()
+    );
+
+    // Unknown table.
+    let resp = sm.apply_cmd(&upsert(table_id + 100, 0, "v3")).await?;
+    assert_eq!((None, None), versions(resp));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_create_database_with_tables() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    let create = |tables: &[&str]| Cmd::CreateDatabaseWithTables {
+        name: "db1".to_string(),
+        db: Default::default(),
+        tables: tables
+            .iter()
+            .map(|name| Table {
+                table_name: name.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+    };
+    let table_names = |sm: &StateMachine| -> Vec<String> {
+        sm.table_lookup
+            .keys()
+            .map(|(_, name)| name.clone())
+            .collect()
+    };
+
+    // The database and all of its tables are created by one command.
+    let resp = sm.apply_cmd(&create(&["t1", "t2"])).await?;
+    match resp {
+        AppliedState::DataBase {
+            prev: None,
+            result: Some(_),
+        } => {}
+        _ => panic!("expect a newly created database, got {:?}", resp),
+    }
+    assert_eq!(vec!["t1", "t2"], table_names(&sm));
+
+    // The database exists, no table is added to it.
+    let resp = sm.apply_cmd(&create(&["t3"])).await?;
+    match resp {
+        AppliedState::DataBase {
+            prev: Some(_),
+            result: Some(_),
+        } => {}
+        _ => panic!("expect an existing database, got {:?}", resp),
+    }
+    assert_eq!(vec!["t1", "t2"], table_names(&sm));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_snapshot() -> anyhow::Result<()> {
     // - Feed logs into state machine.
@@ -19,6 +19,8 @@
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use common_exception::ErrorCode;
+use common_exception::Result;
 use lazy_static::lazy_static;
 use tempfile::TempDir;
 
@@ -26,6 +28,7 @@ pub(crate) struct GlobalSledDb {
     /// When opening a db on a temp dir, the temp dir guard must be held.
     #[allow(dead_code)]
     pub(crate) temp_dir: Option<TempDir>,
+    pub(crate) path: String,
     pub(crate) db: sled::Db,
 }
 
@@ -45,7 +48,8 @@ pub fn init_temp_sled_db(temp_dir: TempDir) {
 
     *g = Some(GlobalSledDb {
         temp_dir: Some(temp_dir),
-        db: sled::open(path).expect("open global sled::Db"),
+        db: sled::open(&path).expect("open global sled::Db"),
+        path,
     });
 }
 
@@ -58,10 +62,39 @@ pub fn init_sled_db(path: String) {
 
     *g = Some(GlobalSledDb {
         temp_dir: None,
-        db: sled::open(path).expect("open global sled::Db"),
+        db: sled::open(&path).expect("open global sled::Db"),
+        path,
     });
 }
 
+/// Opens the db at `path`, like `init_sled_db`, but fails instead of panicking.
+///
+/// Since there is only one db in a process, it is an error if the db is already opened
+/// at another path, or at a temp dir.
+pub fn try_init_sled_db(path: String) -> Result<()> {
+    let mut g = GLOBAL_SLED.as_ref().lock().unwrap();
+
+    if let Some(opened) = g.as_ref() {
+        if opened.temp_dir.is_some() || opened.path != path {
+            return Err(ErrorCode::InvalidConfig(format!(
+                "Can not open the sled db at {}, the db of the process is already opened at {}",
+                path, opened.path
+            )));
+        }
+        return Ok(());
+    }
+
+    let db = sled::open(&path).map_err(|e| {
+        ErrorCode::MetaStoreDamaged(format!("Can not open the sled db at {}: {}", path, e))
+    })?;
+    *g = Some(GlobalSledDb {
+        temp_dir: None,
+        path,
+        db,
+    });
+    Ok(())
+}
+
 pub fn get_sled_db() -> sled::Db {
     {
         let guard = GLOBAL_SLED.as_ref().lock().unwrap();
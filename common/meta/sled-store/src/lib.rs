@@ -18,6 +18,7 @@
 pub use db::get_sled_db;
 pub use db::init_sled_db;
 pub use db::init_temp_sled_db;
+pub use db::try_init_sled_db;
 pub use kv::KVMeta;
 pub use kv::KVValue;
 pub use seq_num::SeqNum;
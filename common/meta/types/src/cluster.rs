@@ -60,6 +60,10 @@ pub struct NodeInfo {
     pub version: u32,
     #[serde(default)]
     pub flight_address: String,
+    /// Seconds since 1970 until the node is alive, it is when the lease of the node in the meta
+    /// store expires, the heartbeats of the node renew it.
+    #[serde(default)]
+    pub alive_until: u64,
 }
 
 impl TryFrom<Vec<u8>> for NodeInfo {
@@ -83,6 +87,7 @@ impl NodeInfo {
             cpu_nums,
             version: 0,
             flight_address,
+            alive_until: 0,
         }
     }
 
@@ -91,4 +96,9 @@ impl NodeInfo {
 
         Ok((addr.ip().to_string(), addr.port()))
     }
+
+    /// If the lease of the node is not expired at `now`, in seconds since 1970.
+    pub fn is_alive(&self, now: u64) -> bool {
+        self.alive_until >= now
+    }
 }
@@ -23,6 +23,7 @@ fn test_node_info_ip_port() -> Result<()> {
         cpu_nums: 1,
         version: 1,
         flight_address: "1.2.3.4:123".to_string(),
+        alive_until: 0,
     };
 
     let (ip, port) = n.ip_port()?;
@@ -31,3 +32,15 @@ fn test_node_info_ip_port() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_node_info_is_alive() -> Result<()> {
+    let mut n = NodeInfo::create("".to_string(), 1, "1.2.3.4:123".to_string());
+    n.alive_until = 100;
+
+    assert!(n.is_alive(99));
+    assert!(n.is_alive(100));
+    assert!(!n.is_alive(101));
+
+    Ok(())
+}
@@ -44,6 +44,14 @@ pub enum Cmd {
         db: DatabaseInfo,
     },
 
+    /// Add a database along with its tables if the database is absent.
+    /// Nothing is created if the database already exists.
+    CreateDatabaseWithTables {
+        name: String,
+        db: DatabaseInfo,
+        tables: Vec<Table>,
+    },
+
     /// Drop a database if absent
     DropDatabase {
         // TODO(ariesdevil): add `seq` for distinguish between the results of the execution of
@@ -70,6 +78,22 @@ pub enum Cmd {
         if_exists: bool,
     },
 
+    /// Replace the schema of a table and bump its version, if the version matches
+    UpdateTableSchema {
+        table_id: u64,
+        table_version: u64,
+        /// serialized schema
+        schema: Vec<u8>,
+    },
+
+    /// Update or insert an option of a table and bump its version, if the version matches
+    UpsertTableOption {
+        table_id: u64,
+        table_version: u64,
+        option_key: String,
+        option_value: String,
+    },
+
     /// Update or insert a general purpose kv store
     UpsertKV {
         key: String,
@@ -100,6 +124,16 @@ impl fmt::Display for Cmd {
             Cmd::CreateDatabase { name, db } => {
                 write!(f, "create_db:{}={:?}", name, db)
             }
+            Cmd::CreateDatabaseWithTables { name, db, tables } => {
+                let names = tables.iter().map(|t| t.table_name.as_str());
+                write!(
+                    f,
+                    "create_db_with_tables:{}={:?}, tables:{:?}",
+                    name,
+                    db,
+                    names.collect::<Vec<_>>()
+                )
+            }
             Cmd::DropDatabase { name } => {
                 write!(f, "drop_db:{}", name)
             }
@@ -126,6 +160,29 @@ impl fmt::Display for Cmd {
                     db_name, table_name, if_exists
                 )
             }
+            Cmd::UpdateTableSchema {
+                table_id,
+                table_version,
+                ..
+            } => {
+                write!(
+                    f,
+                    "update_table_schema:{}, table_version:{}",
+                    table_id, table_version
+                )
+            }
+            Cmd::UpsertTableOption {
+                table_id,
+                table_version,
+                option_key,
+                option_value,
+            } => {
+                write!(
+                    f,
+                    "upsert_table_option:{}, table_version:{}, {}={}",
+                    table_id, table_version, option_key, option_value
+                )
+            }
             Cmd::UpsertKV {
                 key,
                 seq,
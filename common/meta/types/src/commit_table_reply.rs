@@ -14,3 +14,5 @@
 //
 
 pub type UpsertTableOptionReply = ();
+
+pub type UpdateTableSchemaReply = ();
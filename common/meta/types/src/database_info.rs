@@ -13,8 +13,13 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
 pub struct DatabaseInfo {
     pub database_id: u64,
     pub db: String,
+    /// Options given by `CREATE DATABASE`, e.g. the storage of the tables in it.
+    #[serde(default)]
+    pub options: HashMap<String, String>,
 }
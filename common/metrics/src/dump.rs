@@ -13,12 +13,13 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::fmt::Write;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 use metrics_exporter_prometheus::PrometheusHandle;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MetricSample {
     pub name: String,
     pub kind: String,
@@ -26,7 +27,7 @@ pub struct MetricSample {
     pub value: MetricValue,
 }
 
-#[derive(Debug, PartialEq, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub enum MetricValue {
     Counter(f64),
     Gauge(f64),
@@ -75,20 +76,24 @@ impl From<prometheus_parse::Value> for MetricValue {
     }
 }
 
-#[derive(Debug, PartialEq, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct HistogramCount {
     pub less_than: f64,
     pub count: f64,
 }
 
-#[derive(Debug, PartialEq, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct SummaryCount {
     pub quantile: f64,
     pub count: f64,
 }
 
 pub fn dump_metric_samples(handle: PrometheusHandle) -> Result<Vec<MetricSample>> {
-    let text = handle.render();
+    parse_metric_samples(&handle.render())
+}
+
+/// Parses the samples in the Prometheus text format, e.g. rendered by another node.
+pub fn parse_metric_samples(text: &str) -> Result<Vec<MetricSample>> {
     let lines = text.lines().map(|s| Ok(s.to_owned()));
     let samples = prometheus_parse::Scrape::parse(lines)
         .map_err(|err| {
@@ -108,3 +113,78 @@ pub fn dump_metric_samples(handle: PrometheusHandle) -> Result<Vec<MetricSample>
         .collect::<Vec<_>>();
     Ok(samples)
 }
+
+/// Renders the samples in the Prometheus text format.
+pub fn render_metric_samples(samples: &[MetricSample]) -> String {
+    let mut text = String::new();
+    let mut last_typed = None;
+    for sample in samples {
+        if sample.kind != "untyped" && last_typed != Some(&sample.name) {
+            let _ = writeln!(text, "# TYPE {} {}", sample.name, sample.kind);
+            last_typed = Some(&sample.name);
+        }
+
+        let name = sample.name.as_str();
+        match &sample.value {
+            MetricValue::Counter(v) | MetricValue::Gauge(v) | MetricValue::Untyped(v) => {
+                render_line(&mut text, name, &sample.labels, None, *v)
+            }
+            MetricValue::Histogram(buckets) => {
+                let name = format!("{}_bucket", name);
+                for bucket in buckets {
+                    let le = Some(("le", bucket.less_than));
+                    render_line(&mut text, &name, &sample.labels, le, bucket.count);
+                }
+            }
+            MetricValue::Summary(quantiles) => {
+                for quantile in quantiles {
+                    let q = Some(("quantile", quantile.quantile));
+                    render_line(&mut text, name, &sample.labels, q, quantile.count);
+                }
+            }
+        }
+    }
+    text
+}
+
+fn render_line(
+    text: &mut String,
+    name: &str,
+    labels: &HashMap<String, String>,
+    extra_label: Option<(&str, f64)>,
+    value: f64,
+) {
+    let mut labels = labels
+        .iter()
+        .map(|(k, v)| {
+            let v = v
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", k, v)
+        })
+        .collect::<Vec<_>>();
+    labels.sort();
+    if let Some((k, v)) = extra_label {
+        labels.push(format!("{}=\"{}\"", k, render_value(v)));
+    }
+
+    let _ = match labels.is_empty() {
+        true => writeln!(text, "{} {}", name, render_value(value)),
+        false => writeln!(
+            text,
+            "{}{{{}}} {}",
+            name,
+            labels.join(","),
+            render_value(value)
+        ),
+    };
+}
+
+fn render_value(value: f64) -> String {
+    match value {
+        v if v.is_infinite() && v.is_sign_positive() => "+Inf".to_string(),
+        v if v.is_infinite() => "-Inf".to_string(),
+        v => v.to_string(),
+    }
+}
@@ -18,6 +18,10 @@ use common_exception::ErrorCode;
 
 use crate::dump_metric_samples;
 use crate::init_default_metrics_recorder;
+use crate::parse_metric_samples;
+use crate::render_metric_samples;
+use crate::HistogramCount;
+use crate::MetricSample;
 use crate::MetricValue;
 
 #[test]
@@ -46,3 +50,37 @@ fn test_dump_metric_samples() -> common_exception::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_render_metric_samples() -> common_exception::Result<()> {
+    let text = "# TYPE query_count counter\n\
+                query_count{node=\"n1\",type=\"select\"} 3\n\
+                # TYPE cache_bytes gauge\n\
+                cache_bytes 1024\n";
+    let samples = parse_metric_samples(text)?;
+    assert_eq!(render_metric_samples(&samples), text);
+
+    let samples = vec![MetricSample {
+        name: "io_seconds".to_string(),
+        kind: "histogram".to_string(),
+        labels: HashMap::new(),
+        value: MetricValue::Histogram(vec![
+            HistogramCount {
+                less_than: 0.5,
+                count: 1.0,
+            },
+            HistogramCount {
+                less_than: f64::INFINITY,
+                count: 2.0,
+            },
+        ]),
+    }];
+    assert_eq!(
+        render_metric_samples(&samples),
+        "# TYPE io_seconds histogram\n\
+         io_seconds_bucket{le=\"0.5\"} 1\n\
+         io_seconds_bucket{le=\"+Inf\"} 2\n"
+    );
+
+    Ok(())
+}
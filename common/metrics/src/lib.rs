@@ -19,7 +19,10 @@ mod dump;
 mod recorder;
 
 pub use dump::dump_metric_samples;
+pub use dump::parse_metric_samples;
+pub use dump::render_metric_samples;
 pub use dump::HistogramCount;
+pub use dump::MetricSample;
 pub use dump::MetricValue;
 pub use dump::SummaryCount;
 pub use metrics_exporter_prometheus::PrometheusHandle;
@@ -45,11 +45,16 @@ mod test;
 
 mod plan_aggregator_final;
 mod plan_aggregator_partial;
+mod plan_analyze_table;
 mod plan_broadcast;
 mod plan_builder;
 mod plan_builder_scan;
+mod plan_connection_create;
+mod plan_connection_drop;
+mod plan_copy;
 mod plan_database_create;
 mod plan_database_drop;
+mod plan_delete;
 mod plan_describe_table;
 mod plan_display;
 mod plan_display_indent;
@@ -66,45 +71,78 @@ mod plan_expression_rewriter;
 mod plan_expression_sort;
 mod plan_expression_validator;
 mod plan_expression_visitor;
+mod plan_external_function_create;
 mod plan_extras;
 mod plan_filter;
+mod plan_fsck_table;
+mod plan_function_create;
+mod plan_function_drop;
 mod plan_having;
+mod plan_index_create;
+mod plan_index_drop;
 mod plan_insert_into;
 mod plan_kill;
 mod plan_limit;
 mod plan_limit_by;
+mod plan_merge;
 mod plan_node;
 mod plan_partition;
+mod plan_pipe_create;
+mod plan_pipe_drop;
+mod plan_profile_create;
+mod plan_profile_drop;
 mod plan_projection;
+mod plan_query_cache_drop;
 mod plan_read_datasource;
+mod plan_recursive_cte;
 mod plan_remote;
 mod plan_rewriter;
 mod plan_scan;
 mod plan_select;
 mod plan_setting;
+mod plan_show_database_create;
 mod plan_show_table_create;
+mod plan_show_table_options;
 mod plan_sort;
 mod plan_stage;
 mod plan_statistics;
+mod plan_storage_policy_set;
 mod plan_subqueries_set;
+mod plan_table_alter;
 mod plan_table_create;
 mod plan_table_drop;
+mod plan_table_options_set;
 mod plan_truncate_table;
+mod plan_unnest;
+mod plan_update;
 mod plan_use_database;
+mod plan_user_alter;
+mod plan_view_create;
+mod plan_view_drop;
+mod plan_virtual_column;
 mod plan_visitor;
+mod plan_window;
+mod plan_working_table;
 
 pub use plan_aggregator_final::AggregatorFinalPlan;
 pub use plan_aggregator_partial::AggregatorPartialPlan;
+pub use plan_analyze_table::AnalyzeTablePlan;
 pub use plan_broadcast::BroadcastPlan;
 pub use plan_builder::PlanBuilder;
 pub use plan_builder_scan::TableScanInfo;
+pub use plan_connection_create::CreateConnectionPlan;
+pub use plan_connection_drop::DropConnectionPlan;
+pub use plan_copy::CopyIntoLocationPlan;
+pub use plan_copy::CopyPlan;
 pub use plan_database_create::CreateDatabasePlan;
 pub use plan_database_create::DatabaseOptions;
 pub use plan_database_drop::DropDatabasePlan;
+pub use plan_delete::DeletePlan;
 pub use plan_describe_table::DescribeTablePlan;
 pub use plan_empty::EmptyPlan;
 pub use plan_explain::ExplainPlan;
 pub use plan_explain::ExplainType;
+pub use plan_expression::is_ranking_function;
 pub use plan_expression::Expression;
 pub use plan_expression::ExpressionPlan;
 pub use plan_expression::Expressions;
@@ -113,14 +151,18 @@ pub use plan_expression_chain::ExpressionChain;
 pub use plan_expression_column::col;
 pub use plan_expression_common::expand_aggregate_arg_exprs;
 pub use plan_expression_common::expand_wildcard;
+pub use plan_expression_common::expand_window_arg_exprs;
 pub use plan_expression_common::expr_as_column_expr;
 pub use plan_expression_common::extract_aliases;
 pub use plan_expression_common::find_aggregate_exprs;
 pub use plan_expression_common::find_columns_not_satisfy_exprs;
+pub use plan_expression_common::find_window_exprs;
 pub use plan_expression_common::rebase_expr;
 pub use plan_expression_common::rebase_expr_from_input;
+pub use plan_expression_common::replace_non_aggregated_exprs;
 pub use plan_expression_common::resolve_aliases_to_exprs;
 pub use plan_expression_common::sort_to_inner_expr;
+pub use plan_expression_common::split_conjunctions;
 pub use plan_expression_common::unwrap_alias_exprs;
 pub use plan_expression_function::add;
 pub use plan_expression_function::avg;
@@ -134,18 +176,34 @@ pub use plan_expression_sort::sort;
 pub use plan_expression_validator::validate_expression;
 pub use plan_expression_visitor::ExpressionVisitor;
 pub use plan_expression_visitor::Recursion;
+pub use plan_external_function_create::CreateExternalFunctionPlan;
 pub use plan_extras::Extras;
 pub use plan_filter::FilterPlan;
+pub use plan_fsck_table::FsckTablePlan;
+pub use plan_function_create::CreateFunctionPlan;
+pub use plan_function_drop::DropFunctionPlan;
 pub use plan_having::HavingPlan;
+pub use plan_index_create::CreateIndexPlan;
+pub use plan_index_drop::DropIndexPlan;
 pub use plan_insert_into::InsertIntoPlan;
 pub use plan_kill::KillPlan;
 pub use plan_limit::LimitPlan;
 pub use plan_limit_by::LimitByPlan;
+pub use plan_merge::MergeInsertClause;
+pub use plan_merge::MergeMatchedAction;
+pub use plan_merge::MergeMatchedClause;
+pub use plan_merge::MergePlan;
 pub use plan_node::PlanNode;
 pub use plan_partition::Part;
 pub use plan_partition::Partitions;
+pub use plan_pipe_create::CreatePipePlan;
+pub use plan_pipe_drop::DropPipePlan;
+pub use plan_profile_create::CreateProfilePlan;
+pub use plan_profile_drop::DropProfilePlan;
 pub use plan_projection::ProjectionPlan;
+pub use plan_query_cache_drop::DropQueryCachePlan;
 pub use plan_read_datasource::ReadDataSourcePlan;
+pub use plan_recursive_cte::RecursiveCtePlan;
 pub use plan_remote::RemotePlan;
 pub use plan_rewriter::PlanRewriter;
 pub use plan_rewriter::RewriteHelper;
@@ -153,15 +211,32 @@ pub use plan_scan::ScanPlan;
 pub use plan_select::SelectPlan;
 pub use plan_setting::SettingPlan;
 pub use plan_setting::VarValue;
+pub use plan_show_database_create::ShowCreateDatabasePlan;
 pub use plan_show_table_create::ShowCreateTablePlan;
+pub use plan_show_table_options::ShowTableOptionsPlan;
 pub use plan_sort::SortPlan;
 pub use plan_stage::StageKind;
 pub use plan_stage::StagePlan;
 pub use plan_statistics::Statistics;
+pub use plan_storage_policy_set::SetStoragePolicyPlan;
 pub use plan_subqueries_set::SubQueriesSetPlan;
+pub use plan_table_alter::AlterTableOperation;
+pub use plan_table_alter::AlterTablePlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableOptions;
 pub use plan_table_drop::DropTablePlan;
+pub use plan_table_options_set::SetTableOptionsPlan;
 pub use plan_truncate_table::TruncateTablePlan;
+pub use plan_unnest::UnnestPlan;
+pub use plan_update::UpdatePlan;
 pub use plan_use_database::UseDatabasePlan;
+pub use plan_user_alter::AlterUserPlan;
+pub use plan_view_create::CreateViewPlan;
+pub use plan_view_drop::DropViewPlan;
+pub use plan_virtual_column::is_virtual_column;
+pub use plan_virtual_column::VIRTUAL_COLUMN_BLOCK_PATH;
+pub use plan_virtual_column::VIRTUAL_COLUMN_FILE_NAME;
+pub use plan_virtual_column::VIRTUAL_COLUMN_ROW_ID;
 pub use plan_visitor::PlanVisitor;
+pub use plan_window::WindowPlan;
+pub use plan_working_table::WorkingTablePlan;
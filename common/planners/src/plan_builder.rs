@@ -20,9 +20,11 @@ use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::col;
+use crate::is_virtual_column;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::validate_expression;
 use crate::AggregatorFinalPlan;
@@ -41,6 +43,8 @@ use crate::ProjectionPlan;
 use crate::RewriteHelper;
 use crate::SelectPlan;
 use crate::SortPlan;
+use crate::UnnestPlan;
+use crate::WindowPlan;
 
 pub enum AggregateMode {
     Partial,
@@ -76,8 +80,10 @@ impl PlanBuilder {
         let mut projection_exprs = vec![];
         exprs.iter().for_each(|v| match v {
             Expression::Wildcard => {
-                for i in 0..input_schema.fields().len() {
-                    projection_exprs.push(col(input_schema.fields()[i].name()))
+                for field in input_schema.fields() {
+                    if !is_virtual_column(field.name()) {
+                        projection_exprs.push(col(field.name()))
+                    }
                 }
             }
             _ => projection_exprs.push(v.clone()),
@@ -244,6 +250,52 @@ impl PlanBuilder {
         })))
     }
 
+    /// Expand the array column into one row per element, appended as `alias`
+    pub fn unnest(&self, column: &str, alias: &str) -> Result<Self> {
+        let input_schema = self.plan.schema();
+        let field = input_schema.field_with_name(column)?;
+        let element_type = match field.data_type() {
+            DataType::List(inner) => inner.data_type().clone(),
+            other => {
+                return Err(ErrorCode::IllegalDataType(format!(
+                    "UNNEST expects an array column, but column {} is {}",
+                    column, other
+                )))
+            }
+        };
+
+        let mut fields = input_schema.fields().clone();
+        fields.push(DataField::new(alias, element_type, true));
+
+        Ok(Self::from(&PlanNode::Unnest(UnnestPlan {
+            column: column.to_string(),
+            alias: alias.to_string(),
+            schema: DataSchemaRefExt::create(fields),
+            input: Arc::new(self.plan.clone()),
+        })))
+    }
+
+    /// Compute the window functions, appended to the input columns
+    pub fn window(&self, exprs: &[Expression]) -> Result<Self> {
+        let input_schema = self.plan.schema();
+        let mut fields = input_schema.fields().clone();
+        for expr in exprs {
+            if !matches!(expr, Expression::WindowFunction { .. }) {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Window expression must be Expression::WindowFunction, but got: {:?}",
+                    expr
+                )));
+            }
+            fields.push(expr.to_data_field(&input_schema)?);
+        }
+
+        Ok(Self::from(&PlanNode::Window(WindowPlan {
+            window_exprs: exprs.to_vec(),
+            schema: DataSchemaRefExt::create(fields),
+            input: Arc::new(self.plan.clone()),
+        })))
+    }
+
     pub fn limit_by(&self, n: usize, exprs: &[Expression]) -> Result<Self> {
         Ok(Self::from(&PlanNode::LimitBy(LimitByPlan {
             limit: n,
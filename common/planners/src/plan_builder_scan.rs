@@ -62,7 +62,9 @@ impl PlanBuilder {
             push_downs: Extras {
                 projection,
                 filters: vec![],
+                prewhere: vec![],
                 limit,
+                virtual_columns: vec![],
             },
         })))
     }
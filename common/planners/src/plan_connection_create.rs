@@ -0,0 +1,46 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+/// `CREATE CONNECTION [IF NOT EXISTS] name STORAGE_TYPE = 's3' STORAGE_S3_BUCKET = 'b' ...`
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct CreateConnectionPlan {
+    pub if_not_exists: bool,
+    pub name: String,
+    /// The storage options by their names in lower case, the credentials among them.
+    pub options: BTreeMap<String, String>,
+}
+
+impl CreateConnectionPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
+
+// The values of the options are never printed.
+impl fmt::Debug for CreateConnectionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CreateConnectionPlan")
+            .field("if_not_exists", &self.if_not_exists)
+            .field("name", &self.name)
+            .field("options", &self.options.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+
+use crate::Expression;
+use crate::PlanNode;
+
+/// `COPY INTO db.table FROM 'location' (format = 'csv')`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct CopyPlan {
+    pub db: String,
+    pub table: String,
+    /// The location of the files, relative to the storage of the query.
+    pub location: String,
+    pub options: HashMap<String, String>,
+}
+
+impl CopyPlan {
+    /// One row for each file of the location.
+    pub fn schema(&self) -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("file", DataType::String, false),
+            DataField::new("status", DataType::String, false),
+            DataField::new("rows_loaded", DataType::UInt64, false),
+            DataField::new("bytes_loaded", DataType::UInt64, false),
+        ])
+    }
+}
+
+/// `COPY INTO 'location' FROM t PARTITION BY (expr, ...) (format = 'csv')`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct CopyIntoLocationPlan {
+    /// The location of the files, relative to the storage of the query.
+    pub location: String,
+    /// The rows to unload.
+    pub input: Arc<PlanNode>,
+    /// Each row is written under the `key=value/` directories given by these expressions,
+    /// the keys are their column names.
+    pub partition_by: Vec<Expression>,
+    pub options: HashMap<String, String>,
+}
+
+impl CopyIntoLocationPlan {
+    /// One row for each file written.
+    pub fn schema(&self) -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("file", DataType::String, false),
+            DataField::new("rows_unloaded", DataType::UInt64, false),
+            DataField::new("bytes_unloaded", DataType::UInt64, false),
+        ])
+    }
+}
@@ -18,6 +18,8 @@ use std::sync::Arc;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 
+use crate::CreateTablePlan;
+
 pub type DatabaseOptions = HashMap<String, String>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
@@ -25,6 +27,10 @@ pub struct CreateDatabasePlan {
     pub if_not_exists: bool,
     pub db: String,
     pub options: DatabaseOptions,
+    /// The tables of `CREATE DATABASE ... WITH TABLES`, created in the same meta write
+    /// as the database, and only if the database did not exist.
+    #[serde(default)]
+    pub tables: Vec<CreateTablePlan>,
 }
 
 impl CreateDatabasePlan {
@@ -21,6 +21,8 @@ use common_datavalues::DataSchemaRef;
 pub struct DropDatabasePlan {
     pub if_exists: bool,
     pub db: String,
+    /// Purge the data of the dropped tables in the background.
+    pub cascade: bool,
 }
 
 impl DropDatabasePlan {
@@ -65,6 +65,10 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::Having(plan) => write!(f, "Having: {:?}", plan.predicate),
             PlanNode::Sort(plan) => Self::format_sort(f, plan),
             PlanNode::Limit(plan) => Self::format_limit(f, plan),
+            PlanNode::Unnest(plan) => write!(f, "Unnest: {} as {}", plan.column, plan.alias),
+            PlanNode::Window(plan) => write!(f, "Window: {:?}", plan.window_exprs),
+            PlanNode::RecursiveCte(plan) => write!(f, "RecursiveCte: {}", plan.name),
+            PlanNode::WorkingTable(plan) => write!(f, "WorkingTable: {}", plan.name),
             PlanNode::SubQueryExpression(plan) => Self::format_subquery_expr(f, plan),
             PlanNode::ReadSource(plan) => Self::format_read_source(f, plan),
             PlanNode::CreateDatabase(plan) => Self::format_create_database(f, plan),
@@ -217,7 +221,12 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
     fn format_create_database(f: &mut Formatter, plan: &CreateDatabasePlan) -> fmt::Result {
         write!(f, "Create database {:},", plan.db)?;
         write!(f, " if_not_exists:{:},", plan.if_not_exists)?;
-        write!(f, " option: {:?}", plan.options)
+        write!(f, " option: {:?}", plan.options)?;
+        if !plan.tables.is_empty() {
+            let tables = plan.tables.iter().map(|t| t.table.as_str());
+            write!(f, ", tables: {:?}", tables.collect::<Vec<_>>())?;
+        }
+        Ok(())
     }
 
     fn format_drop_database(f: &mut Formatter, plan: &DropDatabasePlan) -> fmt::Result {
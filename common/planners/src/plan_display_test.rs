@@ -38,6 +38,8 @@ fn test_plan_display_indent() -> Result<()> {
         schema,
         engine: "JSON".to_string(),
         options,
+        temporary: false,
+        as_select: None,
     });
 
     assert_eq!(
@@ -26,6 +26,8 @@ pub enum ExplainType {
     Syntax,
     Graph,
     Pipeline,
+    /// Executes the query, displays its pipeline with the rows and the time of each processor
+    Analyze,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
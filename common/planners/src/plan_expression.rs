@@ -31,6 +31,16 @@ use crate::PlanNode;
 
 lazy_static! {
     static ref OP_SET: HashSet<&'static str> = ["database", "version",].iter().copied().collect();
+    static ref RANKING_FUNCTIONS: HashSet<&'static str> = ["row_number", "rank", "dense_rank"]
+        .iter()
+        .copied()
+        .collect();
+}
+
+/// Whether `op` is a window function which ranks the rows of the window, like `rank()`,
+/// the other window functions are aggregate functions.
+pub fn is_ranking_function(op: &str) -> bool {
+    RANKING_FUNCTIONS.contains(op.to_lowercase().as_str())
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
@@ -88,6 +98,18 @@ pub enum Expression {
         args: Vec<Expression>,
     },
 
+    /// A ranking or aggregate function computed over the window of each row, such as
+    /// `rank() OVER (PARTITION BY a ORDER BY b)`.
+    /// The window is the partition of the row, up to the last peer of the row if ordered.
+    WindowFunction {
+        op: String,
+        params: Vec<DataValue>,
+        args: Vec<Expression>,
+        partition_by: Vec<Expression>,
+        /// `Expression::Sort`s
+        order_by: Vec<Expression>,
+    },
+
     /// A sort expression, that can be used to sort values.
     Sort {
         /// The expression to sort on
@@ -106,6 +128,8 @@ pub enum Expression {
         expr: Box<Expression>,
         /// The `DataType` the expression will yield
         data_type: DataType,
+        /// Yields NULL instead of the runtime error, by `TRY_CAST`
+        is_try: bool,
     },
     /// Scalar sub query. such as `SELECT (SELECT 1)`
     ScalarSubquery {
@@ -195,16 +219,83 @@ impl Expression {
                     false => format!("{}({})", prefix, args_column_name.join(", ")),
                 }
             }
-            Expression::Sort { expr, .. } => expr.column_name(),
-            Expression::Cast { expr, data_type } => {
-                format!("cast({} as {:?})", expr.column_name(), data_type)
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+            } => {
+                let args_column_name = args.iter().map(Expression::column_name).collect::<Vec<_>>();
+                let params_name = params
+                    .iter()
+                    .map(|v| DataValue::custom_display(v, true))
+                    .collect::<Vec<_>>();
+
+                let prefix = if params.is_empty() {
+                    op.to_string()
+                } else {
+                    format!("{}({})", op, params_name.join(", "))
+                };
+
+                format!(
+                    "{}({}) OVER ({})",
+                    prefix,
+                    args_column_name.join(", "),
+                    Self::window_spec_name(partition_by, order_by)
+                )
             }
+            Expression::Sort { expr, .. } => expr.column_name(),
+            Expression::Cast {
+                expr,
+                data_type,
+                is_try,
+            } => match is_try {
+                true => format!("try_cast({} as {:?})", expr.column_name(), data_type),
+                false => format!("cast({} as {:?})", expr.column_name(), data_type),
+            },
             Expression::Subquery { name, .. } => name.clone(),
             Expression::ScalarSubquery { name, .. } => name.clone(),
             _ => format!("{:?}", self),
         }
     }
 
+    /// `PARTITION BY a ORDER BY b DESC` of a window function.
+    fn window_spec_name(partition_by: &[Expression], order_by: &[Expression]) -> String {
+        let mut spec = vec![];
+        if !partition_by.is_empty() {
+            let names = partition_by
+                .iter()
+                .map(Expression::column_name)
+                .collect::<Vec<_>>();
+            spec.push(format!("PARTITION BY {}", names.join(", ")));
+        }
+        if !order_by.is_empty() {
+            let names = order_by
+                .iter()
+                .map(|expr| match expr {
+                    Expression::Sort {
+                        expr,
+                        asc,
+                        nulls_first,
+                    } => {
+                        let mut name = expr.column_name();
+                        if !asc {
+                            name += " DESC";
+                        }
+                        if !nulls_first {
+                            name += " NULLS LAST";
+                        }
+                        name
+                    }
+                    _ => expr.column_name(),
+                })
+                .collect::<Vec<_>>();
+            spec.push(format!("ORDER BY {}", names.join(", ")));
+        }
+        spec.join(" ")
+    }
+
     pub fn to_data_field(&self, input_schema: &DataSchemaRef) -> Result<DataField> {
         let name = self.column_name();
         self.to_data_type(input_schema).and_then(|return_type| {
@@ -249,6 +340,16 @@ impl Expression {
         }
     }
 
+    /// The values of the literal arguments, None for the others.
+    pub fn constant_values(args: &[Expression]) -> Vec<Option<DataValue>> {
+        args.iter()
+            .map(|arg| match arg {
+                Expression::Literal { value, .. } => Some(value.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn to_data_type(&self, input_schema: &DataSchemaRef) -> Result<DataType> {
         match self {
             Expression::Alias(_, expr) => expr.to_data_type(input_schema),
@@ -279,12 +380,19 @@ impl Expression {
                     arg_types.push(arg.to_data_type(input_schema)?);
                 }
                 let func = FunctionFactory::instance().get(op)?;
-                func.return_type(&arg_types)
+                func.return_type_with_constants(&arg_types, &Self::constant_values(args))
             }
             Expression::AggregateFunction { .. } => {
                 let func = self.to_aggregate_function(input_schema)?;
                 func.return_type()
             }
+            Expression::WindowFunction { op, .. } if is_ranking_function(op) => {
+                Ok(DataType::UInt64)
+            }
+            Expression::WindowFunction { .. } => {
+                let func = self.to_aggregate_function(input_schema)?;
+                func.return_type()
+            }
             Expression::Wildcard => Result::Err(ErrorCode::IllegalDataType(
                 "Wildcard expressions are not valid to get return type",
             )),
@@ -312,6 +420,15 @@ impl Expression {
                 }
                 AggregateFunctionFactory::instance().get(&func_name, params.clone(), fields)
             }
+            Expression::WindowFunction {
+                op, params, args, ..
+            } if !is_ranking_function(op) => {
+                let mut fields = Vec::with_capacity(args.len());
+                for arg in args.iter() {
+                    fields.push(arg.to_data_field(schema)?);
+                }
+                AggregateFunctionFactory::instance().get(op, params.clone(), fields)
+            }
             _ => Err(ErrorCode::LogicalError(
                 "Expression must be aggregated function",
             )),
@@ -320,7 +437,8 @@ impl Expression {
 
     pub fn to_aggregate_function_names(&self) -> Result<Vec<String>> {
         match self {
-            Expression::AggregateFunction { args, .. } => {
+            Expression::AggregateFunction { args, .. }
+            | Expression::WindowFunction { args, .. } => {
                 let mut names = Vec::with_capacity(args.len());
                 for arg in args.iter() {
                     names.push(arg.column_name());
@@ -406,11 +524,17 @@ impl fmt::Debug for Expression {
                 Ok(())
             }
 
+            Expression::WindowFunction { .. } => write!(f, "{}", self.column_name()),
             Expression::Sort { expr, .. } => write!(f, "{:?}", expr),
             Expression::Wildcard => write!(f, "*"),
-            Expression::Cast { expr, data_type } => {
-                write!(f, "cast({:?} as {:?})", expr, data_type)
-            }
+            Expression::Cast {
+                expr,
+                data_type,
+                is_try,
+            } => match is_try {
+                true => write!(f, "try_cast({:?} as {:?})", expr, data_type),
+                false => write!(f, "cast({:?} as {:?})", expr, data_type),
+            },
         }
     }
 }
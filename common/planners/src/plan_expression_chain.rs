@@ -150,6 +150,7 @@ impl ExpressionChain {
                     .iter()
                     .map(|action| action.to_data_type(&self.schema))
                     .collect::<Result<Vec<_>>>()?;
+                let constants = Expression::constant_values(args);
 
                 let function = ActionFunction {
                     name: expr.column_name(),
@@ -160,7 +161,7 @@ impl ExpressionChain {
                     arg_types: arg_types.clone(),
                     arg_fields: vec![],
                     is_nullable: func.nullable(self.schema.as_ref())?,
-                    return_type: func.return_type(&arg_types)?,
+                    return_type: func.return_type_with_constants(&arg_types, &constants)?,
                 };
 
                 self.actions.push(ExpressionAction::Function(function));
@@ -189,6 +190,13 @@ impl ExpressionChain {
 
                 self.actions.push(ExpressionAction::Function(function));
             }
+            Expression::WindowFunction { .. } => {
+                // Window function results are ready in the expression input
+                self.actions.push(ExpressionAction::Input(ActionInput {
+                    name: expr.column_name(),
+                    return_type: expr.to_data_type(&self.schema)?,
+                }));
+            }
             Expression::Sort { expr, .. } => {
                 self.add_expr(expr)?;
             }
@@ -197,17 +205,22 @@ impl ExpressionChain {
             Expression::Cast {
                 expr: sub_expr,
                 data_type,
+                is_try,
             } => {
                 self.add_expr(sub_expr)?;
+                let func_name = match is_try {
+                    true => "try_cast",
+                    false => "cast",
+                };
                 let function = ActionFunction {
                     name: expr.column_name(),
-                    func_name: "cast".to_string(),
+                    func_name: func_name.to_string(),
                     is_aggregated: false,
                     arg_names: vec![sub_expr.column_name()],
                     arg_types: vec![sub_expr.to_data_type(&self.schema)?],
                     params: vec![],
                     arg_fields: vec![],
-                    is_nullable: false,
+                    is_nullable: *is_try,
                     return_type: data_type.clone(),
                 };
 
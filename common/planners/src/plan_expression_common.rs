@@ -18,16 +18,19 @@ use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::is_virtual_column;
 use crate::Expression;
 use crate::ExpressionVisitor;
 use crate::Recursion;
 
 /// Resolves an `Expression::Wildcard` to a collection of `Expression::Column`'s.
+/// The virtual columns are left out.
 pub fn expand_wildcard(expr: &Expression, schema: &DataSchemaRef) -> Vec<Expression> {
     match expr {
         Expression::Wildcard => schema
             .fields()
             .iter()
+            .filter(|f| !is_virtual_column(f.name()))
             .map(|f| Expression::Column(f.name().to_string()))
             .collect::<Vec<Expression>>(),
         _ => vec![expr.clone()],
@@ -43,6 +46,37 @@ pub fn find_aggregate_exprs(exprs: &[Expression]) -> Vec<Expression> {
     })
 }
 
+/// Collect all deeply nested `Expression::WindowFunction`. They are returned in order of
+/// occurrence (depth first), with duplicates omitted.
+pub fn find_window_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    find_exprs_in_exprs(exprs, &|nest_exprs| {
+        matches!(nest_exprs, Expression::WindowFunction { .. })
+    })
+}
+
+/// Collect all arguments, partition and order keys of the window functions
+/// [rank() OVER (PARTITION BY a ORDER BY b + 1)] ---> [ColumnExpr(a), (b + 1)]
+pub fn expand_window_arg_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    let mut res = vec![];
+    for expr in exprs {
+        if let Expression::WindowFunction {
+            args,
+            partition_by,
+            order_by,
+            ..
+        } = expr
+        {
+            let order_by = order_by.iter().map(sort_to_inner_expr);
+            for arg in args.iter().chain(partition_by).cloned().chain(order_by) {
+                if !res.contains(&arg) {
+                    res.push(arg);
+                }
+            }
+        }
+    }
+    res
+}
+
 /// Collect all arguments from aggregation function and append to this exprs
 /// [ColumnExpr(b), Aggr(sum(a, b))] ---> [ColumnExpr(b), ColumnExpr(a)]
 
@@ -75,6 +109,19 @@ pub fn find_column_exprs(exprs: &[Expression]) -> Vec<Expression> {
     })
 }
 
+/// Split the `AND` chain of a predicate into its conjuncts.
+/// a > 1 AND (b < 2 AND c) ---> [a > 1, b < 2, c]
+pub fn split_conjunctions(expr: &Expression) -> Vec<Expression> {
+    match expr {
+        Expression::BinaryExpression { op, left, right } if op.eq_ignore_ascii_case("and") => {
+            let mut conjunctions = split_conjunctions(left);
+            conjunctions.extend(split_conjunctions(right));
+            conjunctions
+        }
+        _ => vec![expr.clone()],
+    }
+}
+
 /// Search the provided `Expression`'s, and all of their nested `Expression`, for any that
 /// pass the provided test. The returned `Expression`'s are deduplicated and returned
 /// in order of appearance (depth first).
@@ -172,6 +219,29 @@ pub fn rebase_expr(expr: &Expression, base_exprs: &[Expression]) -> Result<Expre
     })
 }
 
+/// Rebuilds an `expr` with `replacement_fn` applied to the expressions out of the aggregate
+/// functions, the expressions of `group_by_exprs` are kept as they are.
+///
+/// This is useful in the context of a query like:
+///
+/// SELECT name, count() ... GROUP BY lower(name)
+///
+/// where `name` is not a group key and has to be rewritten as an aggregate, e.g. `min(name)`.
+pub fn replace_non_aggregated_exprs<F>(
+    expr: &Expression,
+    group_by_exprs: &[Expression],
+    replacement_fn: &F,
+) -> Result<Expression>
+where
+    F: Fn(&Expression) -> Option<Expression>,
+{
+    clone_with_replacement(expr, &|nest_exprs| match nest_exprs {
+        Expression::AggregateFunction { .. } => Ok(Some(nest_exprs.clone())),
+        _ if group_by_exprs.contains(nest_exprs) => Ok(Some(nest_exprs.clone())),
+        _ => Ok(replacement_fn(nest_exprs)),
+    })
+}
+
 // Rebuilds an `expr` to ColumnExpr when some expressions already processed in upstream
 // Skip Sort, Alias because we can go into the inner nest_exprs
 pub fn rebase_expr_from_input(expr: &Expression, schema: &DataSchemaRef) -> Result<Expression> {
@@ -297,6 +367,29 @@ where F: Fn(&Expression) -> Result<Option<Expression>> {
                     .collect::<Result<Vec<Expression>>>()?,
             }),
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+            } => Ok(Expression::WindowFunction {
+                op: op.clone(),
+                params: params.clone(),
+                args: args
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                partition_by: partition_by
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+                order_by: order_by
+                    .iter()
+                    .map(|e| clone_with_replacement(e, replacement_fn))
+                    .collect::<Result<Vec<Expression>>>()?,
+            }),
+
             Expression::Sort {
                 expr: nested_expr,
                 asc,
@@ -310,9 +403,11 @@ where F: Fn(&Expression) -> Result<Option<Expression>> {
             Expression::Cast {
                 expr: nested_expr,
                 data_type,
+                is_try,
             } => Ok(Expression::Cast {
                 expr: Box::new(clone_with_replacement(&**nested_expr, replacement_fn)?),
                 data_type: data_type.clone(),
+                is_try: *is_try,
             }),
 
             Expression::Column(_)
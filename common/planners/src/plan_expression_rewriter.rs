@@ -106,11 +106,37 @@ impl Expression {
                     args: new_args,
                 }
             }
-            Expression::Cast { expr, data_type } => {
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+            } => {
+                let rewrite_all = |exprs: Vec<Expression>, rewriter: &mut R| {
+                    exprs
+                        .into_iter()
+                        .map(|expr| expr.rewrite(rewriter))
+                        .collect::<Result<Vec<_>>>()
+                };
+                Expression::WindowFunction {
+                    op,
+                    params,
+                    args: rewrite_all(args, rewriter)?,
+                    partition_by: rewrite_all(partition_by, rewriter)?,
+                    order_by: rewrite_all(order_by, rewriter)?,
+                }
+            }
+            Expression::Cast {
+                expr,
+                data_type,
+                is_try,
+            } => {
                 let expr = expr.rewrite(rewriter)?;
                 Expression::Cast {
                     expr: Box::new(expr),
                     data_type,
+                    is_try,
                 }
             }
             Expression::Sort {
@@ -121,3 +121,22 @@ fn test_expression_validate() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_split_conjunctions() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let expr = col("a")
+        .gt(lit(1))
+        .and(col("b").lt(lit(2)).and(col("c")))
+        .and(col("d").eq(lit(3)).or(col("e")));
+    let conjunctions = split_conjunctions(&expr);
+    let actual = conjunctions
+        .iter()
+        .map(|expr| format!("{:?}", expr))
+        .collect::<Vec<_>>();
+    assert_eq!(actual, vec!["(a > 1)", "(b < 2)", "c", "((d = 3) or e)"]);
+
+    assert_eq!(split_conjunctions(&col("a")), vec![col("a")]);
+    Ok(())
+}
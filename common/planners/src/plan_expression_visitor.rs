@@ -104,6 +104,18 @@ impl Expression {
                 }
                 Ok(visitor)
             }
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let mut visitor = visitor;
+                for arg in args.iter().chain(partition_by).chain(order_by) {
+                    visitor = arg.accept(visitor)?;
+                }
+                Ok(visitor)
+            }
             Expression::Cast { expr, .. } => expr.accept(visitor),
             Expression::Sort { expr, .. } => expr.accept(visitor),
 
@@ -21,8 +21,14 @@ pub struct Extras {
     pub projection: Option<Vec<usize>>,
     /// Optional filter expression plan
     pub filters: Vec<Expression>,
+    /// Optional predicates from the PREWHERE clause, evaluated before reading the other columns
+    #[serde(default)]
+    pub prewhere: Vec<Expression>,
     /// Optional limit to skip read
     pub limit: Option<usize>,
+    /// Names of the virtual columns to be appended to the blocks by the table
+    #[serde(default)]
+    pub virtual_columns: Vec<String>,
 }
 
 impl Extras {
@@ -30,7 +36,9 @@ impl Extras {
         Extras {
             projection: None,
             filters: vec![],
+            prewhere: vec![],
             limit: None,
+            virtual_columns: vec![],
         }
     }
 }
@@ -0,0 +1,39 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+
+/// `FSCK TABLE db.table [REPAIR]`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct FsckTablePlan {
+    pub db: String,
+    /// The table name
+    pub table: String,
+    /// Removes the orphaned objects and marks the table degraded if objects are missing
+    pub repair: bool,
+}
+
+impl FsckTablePlan {
+    /// One row for each orphaned or missing object.
+    pub fn schema(&self) -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("kind", DataType::String, false),
+            DataField::new("location", DataType::String, false),
+            DataField::new("repaired", DataType::Boolean, false),
+        ])
+    }
+}
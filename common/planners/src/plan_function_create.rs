@@ -0,0 +1,36 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+/// `CREATE FUNCTION [ns.]name LANGUAGE wasm AS '<module>'`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct CreateFunctionPlan {
+    pub if_not_exists: bool,
+    /// The namespace of a function of the tenant, None for a function of the session.
+    pub namespace: Option<String>,
+    pub name: String,
+    /// The language of the body, e.g. `wasm`.
+    pub language: String,
+    pub body: String,
+}
+
+impl CreateFunctionPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
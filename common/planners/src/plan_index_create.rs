@@ -0,0 +1,39 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+
+/// `CREATE [INVERTED] INDEX [IF NOT EXISTS] idx ON db.table (expr)`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct CreateIndexPlan {
+    pub if_not_exists: bool,
+    /// An inverted index on a string column, for `MATCH`.
+    pub inverted: bool,
+    pub name: String,
+    pub db: String,
+    pub table: String,
+    /// The expression of the columns of the table the index is on.
+    pub expr: Expression,
+}
+
+impl CreateIndexPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
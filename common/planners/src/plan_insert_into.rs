@@ -16,11 +16,15 @@ use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_exception::Result;
 use common_infallible::Mutex;
 use common_meta_types::MetaId;
 
-type BlockStream =
-    std::pin::Pin<Box<dyn futures::stream::Stream<Item = DataBlock> + Sync + Send + 'static>>;
+use crate::PlanNode;
+
+type BlockStream = std::pin::Pin<
+    Box<dyn futures::stream::Stream<Item = Result<DataBlock>> + Sync + Send + 'static>,
+>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct InsertIntoPlan {
@@ -28,6 +32,9 @@ pub struct InsertIntoPlan {
     pub tbl_name: String,
     pub tbl_id: MetaId,
     pub schema: DataSchemaRef,
+    /// The rows of `INSERT INTO ... SELECT`, streamed into the table when the insert is executed.
+    /// Its columns are already cast to `schema` by position.
+    pub select_plan: Option<Box<PlanNode>>,
 
     #[serde(skip, default = "InsertIntoPlan::empty_stream")]
     pub input_stream: Arc<Mutex<Option<BlockStream>>>,
@@ -38,6 +45,7 @@ impl PartialEq for InsertIntoPlan {
         self.db_name == other.db_name
             && self.tbl_name == other.tbl_name
             && self.schema == other.schema
+            && self.select_plan == other.select_plan
     }
 }
 
@@ -0,0 +1,70 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+use crate::PlanNode;
+
+/// The action of a `WHEN MATCHED` clause on the matched target rows.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum MergeMatchedAction {
+    /// The columns to set and the values, cast to the types of the columns
+    Update(Vec<(String, Expression)>),
+    Delete,
+}
+
+/// `WHEN MATCHED [AND condition] THEN UPDATE SET column = expr, ... | DELETE`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeMatchedClause {
+    pub condition: Option<Expression>,
+    pub action: MergeMatchedAction,
+}
+
+/// `WHEN NOT MATCHED [AND condition] THEN INSERT [(column, ...)] VALUES (expr, ...)`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeInsertClause {
+    pub condition: Option<Expression>,
+    /// The values of all the columns of the table, cast to the types of the columns
+    pub values: Vec<Expression>,
+}
+
+/// `MERGE INTO db.table USING source ON keys WHEN [NOT] MATCHED ...`
+///
+/// The expressions of the clauses refer to the columns of the table by their names, and to the
+/// columns of the source by `<source alias>.<name>`, which are the names of the columns of the
+/// source plan. The expressions of `WHEN NOT MATCHED` refer to the source columns only.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergePlan {
+    pub db: String,
+    /// The table name
+    pub table: String,
+    pub source: Box<PlanNode>,
+    /// The pairs of the expressions on the table and on the source which are equal in the
+    /// matched rows, cast to the same types
+    pub keys: Vec<(Expression, Expression)>,
+    /// The first clause whose condition holds applies to a matched row
+    pub matched: Vec<MergeMatchedClause>,
+    /// The first clause whose condition holds applies to a source row matching no row
+    pub not_matched: Vec<MergeInsertClause>,
+}
+
+impl MergePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
@@ -22,31 +22,62 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTablePlan;
+use crate::AlterUserPlan;
+use crate::AnalyzeTablePlan;
+use crate::CopyIntoLocationPlan;
+use crate::CopyPlan;
+use crate::CreateConnectionPlan;
 use crate::CreateDatabasePlan;
+use crate::CreateExternalFunctionPlan;
+use crate::CreateFunctionPlan;
+use crate::CreateIndexPlan;
+use crate::CreatePipePlan;
+use crate::CreateProfilePlan;
 use crate::CreateTablePlan;
+use crate::CreateViewPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
+use crate::DropConnectionPlan;
 use crate::DropDatabasePlan;
+use crate::DropFunctionPlan;
+use crate::DropIndexPlan;
+use crate::DropPipePlan;
+use crate::DropProfilePlan;
+use crate::DropQueryCachePlan;
 use crate::DropTablePlan;
+use crate::DropViewPlan;
 use crate::EmptyPlan;
 use crate::ExplainPlan;
 use crate::ExpressionPlan;
 use crate::FilterPlan;
+use crate::FsckTablePlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::MergePlan;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::RecursiveCtePlan;
 use crate::RemotePlan;
 use crate::ScanPlan;
 use crate::SelectPlan;
+use crate::SetStoragePolicyPlan;
+use crate::SetTableOptionsPlan;
 use crate::SettingPlan;
+use crate::ShowCreateDatabasePlan;
 use crate::ShowCreateTablePlan;
+use crate::ShowTableOptionsPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UnnestPlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::WindowPlan;
+use crate::WorkingTablePlan;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
 pub enum PlanNode {
@@ -77,8 +108,39 @@ pub enum PlanNode {
     SetVariable(SettingPlan),
     InsertInto(InsertIntoPlan),
     ShowCreateTable(ShowCreateTablePlan),
+    ShowCreateDatabase(ShowCreateDatabasePlan),
     SubQueryExpression(SubQueriesSetPlan),
     Kill(KillPlan),
+    AnalyzeTable(AnalyzeTablePlan),
+    FsckTable(FsckTablePlan),
+    Delete(DeletePlan),
+    Update(UpdatePlan),
+    Merge(MergePlan),
+    CreateExternalFunction(CreateExternalFunctionPlan),
+    CreateFunction(CreateFunctionPlan),
+    DropFunction(DropFunctionPlan),
+    Unnest(UnnestPlan),
+    Window(WindowPlan),
+    RecursiveCte(RecursiveCtePlan),
+    WorkingTable(WorkingTablePlan),
+    Copy(CopyPlan),
+    CopyIntoLocation(CopyIntoLocationPlan),
+    DropPipe(DropPipePlan),
+    CreatePipe(CreatePipePlan),
+    SetStoragePolicy(SetStoragePolicyPlan),
+    SetTableOptions(SetTableOptionsPlan),
+    ShowTableOptions(ShowTableOptionsPlan),
+    DropQueryCache(DropQueryCachePlan),
+    CreateView(CreateViewPlan),
+    DropView(DropViewPlan),
+    AlterTable(AlterTablePlan),
+    CreateIndex(CreateIndexPlan),
+    DropIndex(DropIndexPlan),
+    CreateProfile(CreateProfilePlan),
+    DropProfile(DropProfilePlan),
+    AlterUser(AlterUserPlan),
+    CreateConnection(CreateConnectionPlan),
+    DropConnection(DropConnectionPlan),
 }
 
 impl PlanNode {
@@ -112,8 +174,39 @@ impl PlanNode {
             PlanNode::UseDatabase(v) => v.schema(),
             PlanNode::InsertInto(v) => v.schema(),
             PlanNode::ShowCreateTable(v) => v.schema(),
+            PlanNode::ShowCreateDatabase(v) => v.schema(),
             PlanNode::SubQueryExpression(v) => v.schema(),
             PlanNode::Kill(v) => v.schema(),
+            PlanNode::AnalyzeTable(v) => v.schema(),
+            PlanNode::FsckTable(v) => v.schema(),
+            PlanNode::Delete(v) => v.schema(),
+            PlanNode::Update(v) => v.schema(),
+            PlanNode::Merge(v) => v.schema(),
+            PlanNode::CreateExternalFunction(v) => v.schema(),
+            PlanNode::CreateFunction(v) => v.schema(),
+            PlanNode::DropFunction(v) => v.schema(),
+            PlanNode::Unnest(v) => v.schema(),
+            PlanNode::Window(v) => v.schema(),
+            PlanNode::RecursiveCte(v) => v.schema(),
+            PlanNode::WorkingTable(v) => v.schema(),
+            PlanNode::Copy(v) => v.schema(),
+            PlanNode::CopyIntoLocation(v) => v.schema(),
+            PlanNode::DropPipe(v) => v.schema(),
+            PlanNode::CreatePipe(v) => v.schema(),
+            PlanNode::SetStoragePolicy(v) => v.schema(),
+            PlanNode::SetTableOptions(v) => v.schema(),
+            PlanNode::ShowTableOptions(v) => v.schema(),
+            PlanNode::DropQueryCache(v) => v.schema(),
+            PlanNode::CreateView(v) => v.schema(),
+            PlanNode::DropView(v) => v.schema(),
+            PlanNode::AlterTable(v) => v.schema(),
+            PlanNode::CreateIndex(v) => v.schema(),
+            PlanNode::DropIndex(v) => v.schema(),
+            PlanNode::CreateProfile(v) => v.schema(),
+            PlanNode::DropProfile(v) => v.schema(),
+            PlanNode::CreateConnection(v) => v.schema(),
+            PlanNode::DropConnection(v) => v.schema(),
+            PlanNode::AlterUser(v) => v.schema(),
         }
     }
 
@@ -146,8 +239,39 @@ impl PlanNode {
             PlanNode::UseDatabase(_) => "UseDatabasePlan",
             PlanNode::InsertInto(_) => "InsertIntoPlan",
             PlanNode::ShowCreateTable(_) => "ShowCreateTablePlan",
+            PlanNode::ShowCreateDatabase(_) => "ShowCreateDatabasePlan",
             PlanNode::SubQueryExpression(_) => "CreateSubQueriesSets",
             PlanNode::Kill(_) => "KillQuery",
+            PlanNode::AnalyzeTable(_) => "AnalyzeTablePlan",
+            PlanNode::FsckTable(_) => "FsckTablePlan",
+            PlanNode::Delete(_) => "DeletePlan",
+            PlanNode::Update(_) => "UpdatePlan",
+            PlanNode::Merge(_) => "MergePlan",
+            PlanNode::CreateExternalFunction(_) => "CreateExternalFunctionPlan",
+            PlanNode::CreateFunction(_) => "CreateFunctionPlan",
+            PlanNode::DropFunction(_) => "DropFunctionPlan",
+            PlanNode::Unnest(_) => "UnnestPlan",
+            PlanNode::Window(_) => "WindowPlan",
+            PlanNode::RecursiveCte(_) => "RecursiveCtePlan",
+            PlanNode::WorkingTable(_) => "WorkingTablePlan",
+            PlanNode::Copy(_) => "CopyPlan",
+            PlanNode::CopyIntoLocation(_) => "CopyIntoLocationPlan",
+            PlanNode::DropPipe(_) => "DropPipePlan",
+            PlanNode::CreatePipe(_) => "CreatePipePlan",
+            PlanNode::SetStoragePolicy(_) => "SetStoragePolicyPlan",
+            PlanNode::SetTableOptions(_) => "SetTableOptionsPlan",
+            PlanNode::ShowTableOptions(_) => "ShowTableOptionsPlan",
+            PlanNode::DropQueryCache(_) => "DropQueryCachePlan",
+            PlanNode::CreateView(_) => "CreateViewPlan",
+            PlanNode::DropView(_) => "DropViewPlan",
+            PlanNode::AlterTable(_) => "AlterTablePlan",
+            PlanNode::CreateIndex(_) => "CreateIndexPlan",
+            PlanNode::DropIndex(_) => "DropIndexPlan",
+            PlanNode::CreateProfile(_) => "CreateProfilePlan",
+            PlanNode::DropProfile(_) => "DropProfilePlan",
+            PlanNode::CreateConnection(_) => "CreateConnectionPlan",
+            PlanNode::DropConnection(_) => "DropConnectionPlan",
+            PlanNode::AlterUser(_) => "AlterUserPlan",
         }
     }
 
@@ -165,6 +289,9 @@ impl PlanNode {
             PlanNode::Explain(v) => vec![v.input.clone()],
             PlanNode::Select(v) => vec![v.input.clone()],
             PlanNode::Sort(v) => vec![v.input.clone()],
+            PlanNode::Unnest(v) => vec![v.input.clone()],
+            PlanNode::Window(v) => vec![v.input.clone()],
+            PlanNode::RecursiveCte(v) => vec![v.anchor.clone(), v.recursive.clone()],
             PlanNode::SubQueryExpression(v) => v.get_inputs(),
 
             _ => vec![],
@@ -193,6 +320,9 @@ impl PlanNode {
             PlanNode::Explain(v) => v.set_input(inputs[0]),
             PlanNode::Select(v) => v.set_input(inputs[0]),
             PlanNode::Sort(v) => v.set_input(inputs[0]),
+            PlanNode::Unnest(v) => v.set_input(inputs[0]),
+            PlanNode::Window(v) => v.set_input(inputs[0]),
+            PlanNode::RecursiveCte(v) => v.set_inputs(inputs),
             PlanNode::SubQueryExpression(v) => v.set_inputs(inputs),
             _ => {
                 return Err(ErrorCode::UnImplement(format!(
@@ -0,0 +1,46 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+
+use crate::PlanNode;
+
+/// A common table expression of `WITH RECURSIVE`: the rows of the anchor term are the first
+/// working table, the recursive term reads the working table to produce the next one, until it
+/// is empty. Its rows are the ones of all the working tables.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct RecursiveCtePlan {
+    /// The name of the common table expression, the recursive term reads the working table by it
+    pub name: String,
+    /// Output data schema, the one of the working table too
+    pub schema: DataSchemaRef,
+    /// The logical plan of the anchor term
+    pub anchor: Arc<PlanNode>,
+    /// The logical plan of the recursive term
+    pub recursive: Arc<PlanNode>,
+}
+
+impl RecursiveCtePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn set_inputs(&mut self, inputs: Vec<&PlanNode>) {
+        assert_eq!(inputs.len(), 2);
+        self.anchor = Arc::new(inputs[0].clone());
+        self.recursive = Arc::new(inputs[1].clone());
+    }
+}
@@ -25,35 +25,66 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTablePlan;
+use crate::AlterUserPlan;
+use crate::AnalyzeTablePlan;
+use crate::CopyIntoLocationPlan;
+use crate::CopyPlan;
+use crate::CreateConnectionPlan;
 use crate::CreateDatabasePlan;
+use crate::CreateExternalFunctionPlan;
+use crate::CreateFunctionPlan;
+use crate::CreateIndexPlan;
+use crate::CreatePipePlan;
+use crate::CreateProfilePlan;
 use crate::CreateTablePlan;
+use crate::CreateViewPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
+use crate::DropConnectionPlan;
 use crate::DropDatabasePlan;
+use crate::DropFunctionPlan;
+use crate::DropIndexPlan;
+use crate::DropPipePlan;
+use crate::DropProfilePlan;
+use crate::DropQueryCachePlan;
 use crate::DropTablePlan;
+use crate::DropViewPlan;
 use crate::EmptyPlan;
 use crate::ExplainPlan;
 use crate::Expression;
 use crate::ExpressionPlan;
 use crate::Expressions;
 use crate::FilterPlan;
+use crate::FsckTablePlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::MergePlan;
 use crate::PlanBuilder;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::RecursiveCtePlan;
 use crate::RemotePlan;
 use crate::ScanPlan;
 use crate::SelectPlan;
+use crate::SetStoragePolicyPlan;
+use crate::SetTableOptionsPlan;
 use crate::SettingPlan;
+use crate::ShowCreateDatabasePlan;
 use crate::ShowCreateTablePlan;
+use crate::ShowTableOptionsPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UnnestPlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::WindowPlan;
+use crate::WorkingTablePlan;
 
 /// `PlanRewriter` is a visitor that can help to rewrite `PlanNode`
 /// By default, a `PlanRewriter` will traverse the plan tree in pre-order and return rewritten plan tree.
@@ -103,9 +134,40 @@ pub trait PlanRewriter {
             PlanNode::DropDatabase(plan) => self.rewrite_drop_database(plan),
             PlanNode::InsertInto(plan) => self.rewrite_insert_into(plan),
             PlanNode::ShowCreateTable(plan) => self.rewrite_show_create_table(plan),
+            PlanNode::ShowCreateDatabase(plan) => self.rewrite_show_create_database(plan),
             PlanNode::SubQueryExpression(plan) => self.rewrite_sub_queries_sets(plan),
             PlanNode::TruncateTable(plan) => self.rewrite_truncate_table(plan),
             PlanNode::Kill(plan) => self.rewrite_kill(plan),
+            PlanNode::AnalyzeTable(plan) => self.rewrite_analyze_table(plan),
+            PlanNode::FsckTable(plan) => self.rewrite_fsck_table(plan),
+            PlanNode::Delete(plan) => self.rewrite_delete(plan),
+            PlanNode::Update(plan) => self.rewrite_update(plan),
+            PlanNode::Merge(plan) => self.rewrite_merge(plan),
+            PlanNode::CreateExternalFunction(plan) => self.rewrite_create_external_function(plan),
+            PlanNode::CreateFunction(plan) => self.rewrite_create_function(plan),
+            PlanNode::DropFunction(plan) => self.rewrite_drop_function(plan),
+            PlanNode::Unnest(plan) => self.rewrite_unnest(plan),
+            PlanNode::Window(plan) => self.rewrite_window(plan),
+            PlanNode::RecursiveCte(plan) => self.rewrite_recursive_cte(plan),
+            PlanNode::WorkingTable(plan) => self.rewrite_working_table(plan),
+            PlanNode::Copy(plan) => self.rewrite_copy(plan),
+            PlanNode::CopyIntoLocation(plan) => self.rewrite_copy_into_location(plan),
+            PlanNode::DropPipe(plan) => self.rewrite_drop_pipe(plan),
+            PlanNode::CreatePipe(plan) => self.rewrite_create_pipe(plan),
+            PlanNode::SetStoragePolicy(plan) => self.rewrite_set_storage_policy(plan),
+            PlanNode::SetTableOptions(plan) => self.rewrite_set_table_options(plan),
+            PlanNode::ShowTableOptions(plan) => self.rewrite_show_table_options(plan),
+            PlanNode::DropQueryCache(plan) => self.rewrite_drop_query_cache(plan),
+            PlanNode::CreateView(plan) => self.rewrite_create_view(plan),
+            PlanNode::DropView(plan) => self.rewrite_drop_view(plan),
+            PlanNode::AlterTable(plan) => self.rewrite_alter_table(plan),
+            PlanNode::CreateIndex(plan) => self.rewrite_create_index(plan),
+            PlanNode::DropIndex(plan) => self.rewrite_drop_index(plan),
+            PlanNode::CreateProfile(plan) => self.rewrite_create_profile(plan),
+            PlanNode::DropProfile(plan) => self.rewrite_drop_profile(plan),
+            PlanNode::AlterUser(plan) => self.rewrite_alter_user(plan),
+            PlanNode::CreateConnection(plan) => self.rewrite_create_connection(plan),
+            PlanNode::DropConnection(plan) => self.rewrite_drop_connection(plan),
         }
     }
 
@@ -144,6 +206,19 @@ pub trait PlanRewriter {
                 params: params.clone(),
                 args: self.rewrite_exprs(schema, args)?,
             }),
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+            } => Ok(Expression::WindowFunction {
+                op: op.clone(),
+                params: params.clone(),
+                args: self.rewrite_exprs(schema, args)?,
+                partition_by: self.rewrite_exprs(schema, partition_by)?,
+                order_by: self.rewrite_exprs(schema, order_by)?,
+            }),
             Expression::Sort {
                 expr,
                 asc,
@@ -153,9 +228,14 @@ pub trait PlanRewriter {
                 asc: *asc,
                 nulls_first: *nulls_first,
             }),
-            Expression::Cast { expr, data_type } => Ok(Expression::Cast {
+            Expression::Cast {
+                expr,
+                data_type,
+                is_try,
+            } => Ok(Expression::Cast {
                 expr: Box::new(self.rewrite_expr(schema, expr.as_ref())?),
                 data_type: data_type.clone(),
+                is_try: *is_try,
             }),
             Expression::Wildcard => Ok(Expression::Wildcard),
             Expression::Column(column_name) => Ok(Expression::Column(column_name.clone())),
@@ -344,6 +424,10 @@ pub trait PlanRewriter {
         Ok(PlanNode::ShowCreateTable(plan.clone()))
     }
 
+    fn rewrite_show_create_database(&mut self, plan: &ShowCreateDatabasePlan) -> Result<PlanNode> {
+        Ok(PlanNode::ShowCreateDatabase(plan.clone()))
+    }
+
     fn rewrite_truncate_table(&mut self, plan: &TruncateTablePlan) -> Result<PlanNode> {
         Ok(PlanNode::TruncateTable(plan.clone()))
     }
@@ -351,6 +435,141 @@ pub trait PlanRewriter {
     fn rewrite_kill(&mut self, plan: &KillPlan) -> Result<PlanNode> {
         Ok(PlanNode::Kill(plan.clone()))
     }
+
+    fn rewrite_analyze_table(&mut self, plan: &AnalyzeTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::AnalyzeTable(plan.clone()))
+    }
+
+    fn rewrite_fsck_table(&mut self, plan: &FsckTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::FsckTable(plan.clone()))
+    }
+
+    fn rewrite_delete(&mut self, plan: &DeletePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Delete(plan.clone()))
+    }
+
+    fn rewrite_update(&mut self, plan: &UpdatePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Update(plan.clone()))
+    }
+
+    fn rewrite_merge(&mut self, plan: &MergePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Merge(plan.clone()))
+    }
+
+    fn rewrite_create_external_function(
+        &mut self,
+        plan: &CreateExternalFunctionPlan,
+    ) -> Result<PlanNode> {
+        Ok(PlanNode::CreateExternalFunction(plan.clone()))
+    }
+
+    fn rewrite_create_function(&mut self, plan: &CreateFunctionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateFunction(plan.clone()))
+    }
+
+    fn rewrite_drop_function(&mut self, plan: &DropFunctionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropFunction(plan.clone()))
+    }
+
+    fn rewrite_unnest(&mut self, plan: &UnnestPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        PlanBuilder::from(&new_input)
+            .unnest(&plan.column, &plan.alias)?
+            .build()
+    }
+
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_window_exprs = self.rewrite_exprs(&new_input.schema(), &plan.window_exprs)?;
+        PlanBuilder::from(&new_input)
+            .window(&new_window_exprs)?
+            .build()
+    }
+
+    fn rewrite_recursive_cte(&mut self, plan: &RecursiveCtePlan) -> Result<PlanNode> {
+        Ok(PlanNode::RecursiveCte(RecursiveCtePlan {
+            name: plan.name.clone(),
+            schema: plan.schema.clone(),
+            anchor: Arc::new(self.rewrite_plan_node(plan.anchor.as_ref())?),
+            recursive: Arc::new(self.rewrite_plan_node(plan.recursive.as_ref())?),
+        }))
+    }
+
+    fn rewrite_working_table(&mut self, plan: &WorkingTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::WorkingTable(plan.clone()))
+    }
+
+    fn rewrite_copy(&mut self, plan: &CopyPlan) -> Result<PlanNode> {
+        Ok(PlanNode::Copy(plan.clone()))
+    }
+
+    fn rewrite_copy_into_location(&mut self, plan: &CopyIntoLocationPlan) -> Result<PlanNode> {
+        Ok(PlanNode::CopyIntoLocation(plan.clone()))
+    }
+
+    fn rewrite_drop_pipe(&mut self, plan: &DropPipePlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropPipe(plan.clone()))
+    }
+
+    fn rewrite_create_pipe(&mut self, plan: &CreatePipePlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreatePipe(plan.clone()))
+    }
+
+    fn rewrite_set_storage_policy(&mut self, plan: &SetStoragePolicyPlan) -> Result<PlanNode> {
+        Ok(PlanNode::SetStoragePolicy(plan.clone()))
+    }
+
+    fn rewrite_set_table_options(&mut self, plan: &SetTableOptionsPlan) -> Result<PlanNode> {
+        Ok(PlanNode::SetTableOptions(plan.clone()))
+    }
+
+    fn rewrite_show_table_options(&mut self, plan: &ShowTableOptionsPlan) -> Result<PlanNode> {
+        Ok(PlanNode::ShowTableOptions(plan.clone()))
+    }
+
+    fn rewrite_drop_query_cache(&mut self, plan: &DropQueryCachePlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropQueryCache(plan.clone()))
+    }
+
+    fn rewrite_create_view(&mut self, plan: &CreateViewPlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateView(plan.clone()))
+    }
+
+    fn rewrite_drop_view(&mut self, plan: &DropViewPlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropView(plan.clone()))
+    }
+
+    fn rewrite_alter_table(&mut self, plan: &AlterTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::AlterTable(plan.clone()))
+    }
+
+    fn rewrite_create_index(&mut self, plan: &CreateIndexPlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateIndex(plan.clone()))
+    }
+
+    fn rewrite_drop_index(&mut self, plan: &DropIndexPlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropIndex(plan.clone()))
+    }
+
+    fn rewrite_create_profile(&mut self, plan: &CreateProfilePlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateProfile(plan.clone()))
+    }
+
+    fn rewrite_drop_profile(&mut self, plan: &DropProfilePlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropProfile(plan.clone()))
+    }
+
+    fn rewrite_alter_user(&mut self, plan: &AlterUserPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AlterUser(plan.clone()))
+    }
+
+    fn rewrite_create_connection(&mut self, plan: &CreateConnectionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::CreateConnection(plan.clone()))
+    }
+
+    fn rewrite_drop_connection(&mut self, plan: &DropConnectionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropConnection(plan.clone()))
+    }
 }
 
 pub struct RewriteHelper {}
@@ -495,6 +714,29 @@ impl RewriteHelper {
                 }
             }
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+            } => {
+                let rewrite_all = |exprs: &[Expression], data: &mut QueryAliasData| {
+                    exprs
+                        .iter()
+                        .map(|v| RewriteHelper::expr_rewrite_alias(v, data))
+                        .collect::<Result<Vec<_>>>()
+                };
+
+                Ok(Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: rewrite_all(args, data)?,
+                    partition_by: rewrite_all(partition_by, data)?,
+                    order_by: order_by.clone(),
+                })
+            }
+
             Expression::Alias(alias, plan) => {
                 if data.inside_aliases.contains(alias) {
                     return Result::Err(ErrorCode::SyntaxException(format!(
@@ -512,11 +754,16 @@ impl RewriteHelper {
 
                 Ok(Expression::Alias(alias.clone(), Box::new(new_expr)))
             }
-            Expression::Cast { expr, data_type } => {
+            Expression::Cast {
+                expr,
+                data_type,
+                is_try,
+            } => {
                 let new_expr = RewriteHelper::expr_rewrite_alias(expr, data)?;
                 Ok(Expression::Cast {
                     expr: Box::new(new_expr),
                     data_type: data_type.clone(),
+                    is_try: *is_try,
                 })
             }
             Expression::Wildcard
@@ -584,6 +831,17 @@ impl RewriteHelper {
             }
             Expression::ScalarFunction { args, .. } => args.clone(),
             Expression::AggregateFunction { args, .. } => args.clone(),
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by)
+                .chain(order_by)
+                .cloned()
+                .collect(),
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => vec![expr.as_ref().clone()],
             Expression::Cast { expr, .. } => vec![expr.as_ref().clone()],
@@ -621,6 +879,19 @@ impl RewriteHelper {
                 }
                 v
             }
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let mut v = vec![];
+                for arg in args.iter().chain(partition_by).chain(order_by) {
+                    let mut col = Self::expression_plan_columns(arg)?;
+                    v.append(&mut col);
+                }
+                v
+            }
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => Self::expression_plan_columns(expr)?,
             Expression::Cast { expr, .. } => Self::expression_plan_columns(expr)?,
@@ -693,6 +964,23 @@ impl RewriteHelper {
                 params: params.clone(),
                 args: expressions.to_vec(),
             },
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                ..
+            } => {
+                let (args, rest) = expressions.split_at(args.len());
+                let (partition_by, order_by) = rest.split_at(partition_by.len());
+                Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: args.to_vec(),
+                    partition_by: partition_by.to_vec(),
+                    order_by: order_by.to_vec(),
+                }
+            }
             other => other.clone(),
         }
     }
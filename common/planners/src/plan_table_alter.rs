@@ -0,0 +1,49 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum AlterTableOperation {
+    /// `ADD [COLUMN] c type [DEFAULT expr]`, the rows written before read the new column as
+    /// the value of the constant default, or NULL without a default.
+    AddColumn {
+        field: DataField,
+        default: Option<Expression>,
+    },
+    /// `DROP [COLUMN] c`
+    DropColumn(String),
+    /// `MATERIALIZE COLUMN c`, writes the default of the column into the blocks written
+    /// before the column was added.
+    MaterializeColumn(String),
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct AlterTablePlan {
+    pub db: String,
+    pub table: String,
+    pub operation: AlterTableOperation,
+}
+
+impl AlterTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
@@ -16,6 +16,8 @@ use std::collections::HashMap;
 
 use common_datavalues::DataSchemaRef;
 
+use crate::PlanNode;
+
 pub type TableOptions = HashMap<String, String>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
@@ -29,6 +31,11 @@ pub struct CreateTablePlan {
     /// The file type of physical file
     pub engine: String,
     pub options: TableOptions,
+    /// Temporary table metadata lives only in the session which created it
+    pub temporary: bool,
+    /// The rows of `CREATE TABLE ... AS SELECT`, appended once the table is created.
+    /// Its columns are already cast to `schema` by position.
+    pub as_select: Option<Box<PlanNode>>,
 }
 
 impl CreateTablePlan {
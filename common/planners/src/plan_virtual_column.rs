@@ -0,0 +1,29 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The path of the file a row is read from, by the scans of the tables over files.
+pub const VIRTUAL_COLUMN_FILE_NAME: &str = "_file_name";
+/// The location of the block a row is stored in, by the scans of the fuse tables.
+pub const VIRTUAL_COLUMN_BLOCK_PATH: &str = "_block_path";
+/// The position of a row in its file or block, starting from 0.
+pub const VIRTUAL_COLUMN_ROW_ID: &str = "_row_id";
+
+/// Whether the column is one of the virtual columns, which the scans produce besides the
+/// columns of the table. They are selected only by name, `*` does not expand to them.
+pub fn is_virtual_column(name: &str) -> bool {
+    matches!(
+        name,
+        VIRTUAL_COLUMN_FILE_NAME | VIRTUAL_COLUMN_BLOCK_PATH | VIRTUAL_COLUMN_ROW_ID
+    )
+}
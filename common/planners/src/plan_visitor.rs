@@ -18,33 +18,64 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTablePlan;
+use crate::AlterUserPlan;
+use crate::AnalyzeTablePlan;
+use crate::CopyIntoLocationPlan;
+use crate::CopyPlan;
+use crate::CreateConnectionPlan;
 use crate::CreateDatabasePlan;
+use crate::CreateExternalFunctionPlan;
+use crate::CreateFunctionPlan;
+use crate::CreateIndexPlan;
+use crate::CreatePipePlan;
+use crate::CreateProfilePlan;
 use crate::CreateTablePlan;
+use crate::CreateViewPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
+use crate::DropConnectionPlan;
 use crate::DropDatabasePlan;
+use crate::DropFunctionPlan;
+use crate::DropIndexPlan;
+use crate::DropPipePlan;
+use crate::DropProfilePlan;
+use crate::DropQueryCachePlan;
 use crate::DropTablePlan;
+use crate::DropViewPlan;
 use crate::EmptyPlan;
 use crate::ExplainPlan;
 use crate::Expression;
 use crate::ExpressionPlan;
 use crate::FilterPlan;
+use crate::FsckTablePlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::MergePlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::RecursiveCtePlan;
 use crate::RemotePlan;
 use crate::ScanPlan;
 use crate::SelectPlan;
+use crate::SetStoragePolicyPlan;
+use crate::SetTableOptionsPlan;
 use crate::SettingPlan;
+use crate::ShowCreateDatabasePlan;
 use crate::ShowCreateTablePlan;
+use crate::ShowTableOptionsPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UnnestPlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::WindowPlan;
+use crate::WorkingTablePlan;
 
 /// `PlanVisitor` implements visitor pattern(reference [syn](https://docs.rs/syn/1.0.72/syn/visit/trait.Visit.html)) for `PlanNode`.
 ///
@@ -117,8 +148,39 @@ pub trait PlanVisitor {
             PlanNode::Expression(plan) => self.visit_expression(plan),
             PlanNode::InsertInto(plan) => self.visit_insert_into(plan),
             PlanNode::ShowCreateTable(plan) => self.visit_show_create_table(plan),
+            PlanNode::ShowCreateDatabase(plan) => self.visit_show_create_database(plan),
             PlanNode::SubQueryExpression(plan) => self.visit_sub_queries_sets(plan),
             PlanNode::Kill(plan) => self.visit_kill_query(plan),
+            PlanNode::AnalyzeTable(plan) => self.visit_analyze_table(plan),
+            PlanNode::FsckTable(plan) => self.visit_fsck_table(plan),
+            PlanNode::Delete(plan) => self.visit_delete(plan),
+            PlanNode::Update(plan) => self.visit_update(plan),
+            PlanNode::Merge(plan) => self.visit_merge(plan),
+            PlanNode::CreateExternalFunction(plan) => self.visit_create_external_function(plan),
+            PlanNode::CreateFunction(plan) => self.visit_create_function(plan),
+            PlanNode::DropFunction(plan) => self.visit_drop_function(plan),
+            PlanNode::Unnest(plan) => self.visit_unnest(plan),
+            PlanNode::Window(plan) => self.visit_window(plan),
+            PlanNode::RecursiveCte(plan) => self.visit_recursive_cte(plan),
+            PlanNode::WorkingTable(plan) => self.visit_working_table(plan),
+            PlanNode::Copy(plan) => self.visit_copy(plan),
+            PlanNode::CopyIntoLocation(plan) => self.visit_copy_into_location(plan),
+            PlanNode::DropPipe(plan) => self.visit_drop_pipe(plan),
+            PlanNode::CreatePipe(plan) => self.visit_create_pipe(plan),
+            PlanNode::SetStoragePolicy(plan) => self.visit_set_storage_policy(plan),
+            PlanNode::SetTableOptions(plan) => self.visit_set_table_options(plan),
+            PlanNode::ShowTableOptions(plan) => self.visit_show_table_options(plan),
+            PlanNode::DropQueryCache(plan) => self.visit_drop_query_cache(plan),
+            PlanNode::CreateView(plan) => self.visit_create_view(plan),
+            PlanNode::DropView(plan) => self.visit_drop_view(plan),
+            PlanNode::AlterTable(plan) => self.visit_alter_table(plan),
+            PlanNode::CreateIndex(plan) => self.visit_create_index(plan),
+            PlanNode::DropIndex(plan) => self.visit_drop_index(plan),
+            PlanNode::CreateProfile(plan) => self.visit_create_profile(plan),
+            PlanNode::DropProfile(plan) => self.visit_drop_profile(plan),
+            PlanNode::AlterUser(plan) => self.visit_alter_user(plan),
+            PlanNode::CreateConnection(plan) => self.visit_create_connection(plan),
+            PlanNode::DropConnection(plan) => self.visit_drop_connection(plan),
         }
     }
 
@@ -266,6 +328,10 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_show_create_database(&mut self, _: &ShowCreateDatabasePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_truncate_table(&mut self, _: &TruncateTablePlan) -> Result<()> {
         Ok(())
     }
@@ -273,4 +339,126 @@ pub trait PlanVisitor {
     fn visit_kill_query(&mut self, _: &KillPlan) -> Result<()> {
         Ok(())
     }
+
+    fn visit_drop_query_cache(&mut self, _: &DropQueryCachePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_view(&mut self, _: &CreateViewPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_view(&mut self, _: &DropViewPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_alter_table(&mut self, _: &AlterTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_index(&mut self, _: &CreateIndexPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_index(&mut self, _: &DropIndexPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_profile(&mut self, _: &CreateProfilePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_profile(&mut self, _: &DropProfilePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_alter_user(&mut self, _: &AlterUserPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_connection(&mut self, _: &CreateConnectionPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_connection(&mut self, _: &DropConnectionPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_set_storage_policy(&mut self, _: &SetStoragePolicyPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_set_table_options(&mut self, _: &SetTableOptionsPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_show_table_options(&mut self, _: &ShowTableOptionsPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_pipe(&mut self, _: &CreatePipePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_pipe(&mut self, _: &DropPipePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_copy(&mut self, _: &CopyPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_copy_into_location(&mut self, _: &CopyIntoLocationPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_unnest(&mut self, plan: &UnnestPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())
+    }
+
+    fn visit_window(&mut self, plan: &WindowPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())?;
+        self.visit_exprs(&plan.window_exprs)
+    }
+
+    fn visit_recursive_cte(&mut self, plan: &RecursiveCtePlan) -> Result<()> {
+        self.visit_plan_node(plan.anchor.as_ref())?;
+        self.visit_plan_node(plan.recursive.as_ref())
+    }
+
+    fn visit_working_table(&mut self, _: &WorkingTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_function(&mut self, _: &CreateFunctionPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_function(&mut self, _: &DropFunctionPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_create_external_function(&mut self, _: &CreateExternalFunctionPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_analyze_table(&mut self, _: &AnalyzeTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_fsck_table(&mut self, _: &FsckTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_delete(&mut self, _: &DeletePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_update(&mut self, _: &UpdatePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_merge(&mut self, _: &MergePlan) -> Result<()> {
+        Ok(())
+    }
 }
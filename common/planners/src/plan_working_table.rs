@@ -0,0 +1,30 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataSchemaRef;
+
+/// The rows of the previous iteration of a recursive common table expression, read by its
+/// recursive term.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct WorkingTablePlan {
+    /// The name of the recursive common table expression
+    pub name: String,
+    pub schema: DataSchemaRef,
+}
+
+impl WorkingTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+}
@@ -15,9 +15,15 @@
 #[cfg(test)]
 mod stream_datablock_test;
 
+#[cfg(test)]
+mod stream_profiling_test;
+
 #[cfg(test)]
 mod stream_progress_test;
 
+#[cfg(test)]
+mod stream_quota_test;
+
 #[cfg(test)]
 mod stream_skip_test;
 
@@ -31,7 +37,9 @@ mod stream_correct_with_schema;
 mod stream_datablock;
 mod stream_limit_by;
 mod stream_parquet;
+mod stream_profiling;
 mod stream_progress;
+mod stream_quota;
 mod stream_skip;
 mod stream_sort;
 mod stream_source;
@@ -45,7 +53,11 @@ pub use stream_correct_with_schema::CorrectWithSchemaStream;
 pub use stream_datablock::DataBlockStream;
 pub use stream_limit_by::LimitByStream;
 pub use stream_parquet::ParquetStream;
+pub use stream_profiling::ProfilingStream;
+pub use stream_profiling::StreamProfile;
 pub use stream_progress::ProgressStream;
+pub use stream_quota::QuotaLimit;
+pub use stream_quota::QuotaStream;
 pub use stream_skip::SkipStream;
 pub use stream_sort::SortStream;
 pub use stream_source::SourceStream;
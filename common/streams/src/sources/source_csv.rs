@@ -30,6 +30,7 @@ pub struct CsvSource<R> {
     schema: DataSchemaRef,
     block_size: usize,
     rows: usize,
+    lenient_cast: bool,
 }
 
 impl<R> CsvSource<R>
@@ -43,8 +44,15 @@ where R: io::Read + Sync + Send
             block_size,
             schema,
             rows: 0,
+            lenient_cast: false,
         }
     }
+
+    /// Writes NULL for a value which can't be cast to its column type, instead of failing.
+    pub fn with_lenient_cast(mut self, lenient_cast: bool) -> Self {
+        self.lenient_cast = lenient_cast;
+        self
+    }
 }
 
 impl<R> Source for CsvSource<R>
@@ -73,13 +81,23 @@ where R: io::Read + Sync + Send
                 }
                 break;
             }
-            desers
-                .iter_mut()
-                .enumerate()
-                .for_each(|(col, deser)| match record.get(col) {
-                    Some(bytes) => deser.de_text(bytes).unwrap(),
-                    None => deser.de_null(),
-                });
+            for (col, deser) in desers.iter_mut().enumerate() {
+                let bytes = match record.get(col) {
+                    Some(bytes) => bytes,
+                    None => {
+                        deser.de_null();
+                        continue;
+                    }
+                };
+
+                if let Err(cause) = deser.de_text(bytes) {
+                    if !self.lenient_cast {
+                        return Err(cause
+                            .add_message_back(format!(" (column {} at line {})", col, self.rows)));
+                    }
+                    deser.de_null();
+                }
+            }
 
             self.rows += 1;
         }
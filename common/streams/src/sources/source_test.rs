@@ -78,3 +78,32 @@ fn test_parse_csvs() {
     let block = values_source.read().unwrap();
     assert!(block.is_none());
 }
+
+#[test]
+fn test_parse_csvs_lenient_cast() {
+    let buffer = "1,1.11\nx,2\n3,y\n";
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int8, true),
+        DataField::new("b", DataType::Float64, true),
+    ]);
+
+    // strict by default.
+    let mut csv_source = CsvSource::new(buffer.as_bytes(), schema.clone(), 10);
+    assert!(csv_source.read().is_err());
+
+    let mut csv_source = CsvSource::new(buffer.as_bytes(), schema, 10).with_lenient_cast(true);
+    let block = csv_source.read().unwrap().unwrap();
+    assert_blocks_eq(
+        vec![
+            "+------+------+",
+            "| a    | b    |",
+            "+------+------+",
+            "| 1    | 1.11 |",
+            "| NULL | 2    |",
+            "| 3    | NULL |",
+            "+------+------+",
+        ],
+        &[block],
+    );
+}
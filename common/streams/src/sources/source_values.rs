@@ -27,6 +27,7 @@ pub struct ValueSource<R> {
     schema: DataSchemaRef,
     block_size: usize,
     rows: usize,
+    lenient_cast: bool,
 }
 
 impl<R> ValueSource<R>
@@ -39,8 +40,15 @@ where R: io::Read + Send + Sync
             block_size,
             schema,
             rows: 0,
+            lenient_cast: false,
         }
     }
+
+    /// Writes NULL for a value which can't be cast to its column type, instead of failing.
+    pub fn with_lenient_cast(mut self, lenient_cast: bool) -> Self {
+        self.lenient_cast = lenient_cast;
+        self
+    }
 }
 
 impl<R> Source for ValueSource<R>
@@ -106,7 +114,16 @@ where R: io::Read + Send + Sync
                     }
                 };
                 let bs = bs?;
-                deser.de_text(bs)?;
+                if let Err(cause) = deser.de_text(bs) {
+                    if !self.lenient_cast {
+                        return Err(cause.add_message_back(format!(
+                            " (column {} of row {})",
+                            col,
+                            self.rows + rows
+                        )));
+                    }
+                    deser.de_null();
+                }
             }
             rows += 1;
         }
@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use futures::Stream;
+use pin_project_lite::pin_project;
+
+use crate::SendableDataBlockStream;
+
+/// The blocks pulled from a stream, and the time spent in pulling them.
+#[derive(Debug, Default)]
+pub struct StreamProfile {
+    rows: AtomicUsize,
+    bytes: AtomicUsize,
+    elapsed_nanos: AtomicU64,
+}
+
+impl StreamProfile {
+    pub fn rows(&self) -> usize {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    /// The memory size of the blocks.
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// The time spent in polling the stream, including the inputs it polls.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_nanos.load(Ordering::Relaxed))
+    }
+}
+
+pin_project! {
+    pub struct ProfilingStream {
+        #[pin]
+        input: SendableDataBlockStream,
+        profile: Arc<StreamProfile>,
+    }
+}
+
+impl ProfilingStream {
+    pub fn create(input: SendableDataBlockStream, profile: Arc<StreamProfile>) -> Self {
+        Self { input, profile }
+    }
+}
+
+impl Stream for ProfilingStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        let start = Instant::now();
+        let poll = this.input.poll_next(ctx);
+        let elapsed = start.elapsed().as_nanos() as u64;
+        this.profile
+            .elapsed_nanos
+            .fetch_add(elapsed, Ordering::Relaxed);
+
+        if let Poll::Ready(Some(Ok(block))) = &poll {
+            this.profile
+                .rows
+                .fetch_add(block.num_rows(), Ordering::Relaxed);
+            this.profile
+                .bytes
+                .fetch_add(block.memory_size(), Ordering::Relaxed);
+        }
+        poll
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::*;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::*;
+
+#[tokio::test]
+async fn test_profiling_stream() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1i64, 2, 3])]);
+
+    let input = DataBlockStream::create(schema, None, vec![block.clone(), block.clone()]);
+
+    let profile = Arc::new(StreamProfile::default());
+    let stream = ProfilingStream::create(Box::pin(input), profile.clone());
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 2);
+
+    assert_eq!(profile.rows(), 6);
+    assert_eq!(profile.bytes(), block.memory_size() * 2);
+    Ok(())
+}
@@ -0,0 +1,108 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use common_base::Progress;
+use common_base::ProgressValues;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::Stream;
+use pin_project_lite::pin_project;
+
+use crate::SendableDataBlockStream;
+
+/// A limit of the stream, named after the setting that raised it in the error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuotaLimit {
+    Rows(&'static str, usize),
+    Bytes(&'static str, usize),
+}
+
+pin_project! {
+    /// Counts the blocks into the progress, which may be shared by several streams,
+    /// and fails with `QuotaExceeded` once the progress goes over one of the limits.
+    pub struct QuotaStream {
+        #[pin]
+        input: SendableDataBlockStream,
+        progress: Arc<Progress>,
+        limits: Vec<QuotaLimit>,
+    }
+}
+
+impl QuotaStream {
+    pub fn try_create(
+        input: SendableDataBlockStream,
+        progress: Arc<Progress>,
+        limits: Vec<QuotaLimit>,
+    ) -> Result<Self> {
+        Ok(Self {
+            input,
+            progress,
+            limits,
+        })
+    }
+
+    fn check(progress: &Progress, limits: &[QuotaLimit]) -> Result<()> {
+        let values = progress.get_values();
+        for limit in limits {
+            match *limit {
+                QuotaLimit::Rows(name, max) if values.read_rows > max => {
+                    return Err(ErrorCode::QuotaExceeded(format!(
+                        "Query exceeded the quota {}: {} rows, the limit is {}",
+                        name, values.read_rows, max
+                    )));
+                }
+                QuotaLimit::Bytes(name, max) if values.read_bytes > max => {
+                    return Err(ErrorCode::QuotaExceeded(format!(
+                        "Query exceeded the quota {}: {} bytes, the limit is {}",
+                        name, values.read_bytes, max
+                    )));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Stream for QuotaStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        ctx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        match this.input.poll_next(ctx) {
+            Poll::Ready(Some(Ok(block))) => {
+                this.progress.incr(&ProgressValues {
+                    read_rows: block.num_rows(),
+                    read_bytes: block.memory_size(),
+                    total_rows_to_read: 0,
+                });
+
+                match Self::check(this.progress, this.limits) {
+                    Ok(_) => Poll::Ready(Some(Ok(block))),
+                    Err(cause) => Poll::Ready(Some(Err(cause))),
+                }
+            }
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_base::*;
+use common_datablocks::*;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::*;
+
+fn create_input() -> SendableDataBlockStream {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1i64, 2, 3])]);
+
+    Box::pin(DataBlockStream::create(schema, None, vec![
+        block.clone(),
+        block.clone(),
+        block,
+    ]))
+}
+
+#[tokio::test]
+async fn test_quota_stream() -> Result<()> {
+    // Within the limits.
+    {
+        let limits = vec![QuotaLimit::Rows("max_rows_returned", 9)];
+        let progress = Arc::new(Progress::create());
+        let stream = QuotaStream::try_create(create_input(), progress.clone(), limits)?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert_eq!(result.len(), 3);
+        assert_eq!(progress.get_values().read_rows, 9);
+    }
+
+    // Over the rows.
+    {
+        let limits = vec![QuotaLimit::Rows("max_rows_returned", 5)];
+        let progress = Arc::new(Progress::create());
+        let stream = QuotaStream::try_create(create_input(), progress, limits)?;
+        let result = stream.try_collect::<Vec<_>>().await;
+        let cause = result.unwrap_err();
+        assert_eq!(cause.code(), ErrorCode::QuotaExceeded("").code());
+        assert_eq!(
+            cause.message(),
+            "Query exceeded the quota max_rows_returned: 6 rows, the limit is 5"
+        );
+    }
+
+    // The progress is shared by the streams.
+    {
+        let limits = vec![QuotaLimit::Bytes("max_bytes_scanned", 1)];
+        let progress = Arc::new(Progress::create());
+        progress.incr(&ProgressValues {
+            read_rows: 0,
+            read_bytes: 1,
+            total_rows_to_read: 0,
+        });
+        let stream = QuotaStream::try_create(create_input(), progress, limits)?;
+        let result = stream.try_collect::<Vec<_>>().await;
+        assert_eq!(
+            result.unwrap_err().code(),
+            ErrorCode::QuotaExceeded("").code()
+        );
+    }
+
+    Ok(())
+}
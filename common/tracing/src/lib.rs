@@ -14,7 +14,12 @@
 
 mod logging;
 mod panic_hook;
+mod span_buffer;
+#[cfg(test)]
+mod span_buffer_test;
 mod tracing_to_jaeger;
+#[cfg(test)]
+mod tracing_to_jaeger_test;
 
 pub use logging::init_default_tracing;
 pub use logging::init_default_ut_tracing;
@@ -22,8 +27,12 @@ pub use logging::init_global_tracing;
 pub use logging::init_tracing;
 pub use logging::init_tracing_with_file;
 pub use panic_hook::set_panic_hook;
+pub use span_buffer::SpanBuffer;
+pub use span_buffer::SpanBufferLayer;
+pub use span_buffer::SpanRecord;
 pub use tracing;
 pub use tracing_to_jaeger::extract_remote_span_as_parent;
+pub use tracing_to_jaeger::extract_remote_span_from_http_headers;
 pub use tracing_to_jaeger::inject_span_to_tonic_request;
 
 #[macro_export]
@@ -22,6 +22,10 @@ use std::sync::Once;
 use lazy_static::lazy_static;
 use opentelemetry::global;
 use opentelemetry::sdk::propagation::TraceContextPropagator;
+use opentelemetry::sdk::trace;
+use opentelemetry::sdk::Resource;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
 use tracing::Subscriber;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_appender::rolling::RollingFileAppender;
@@ -35,6 +39,9 @@ use tracing_subscriber::registry::Registry;
 use tracing_subscriber::EnvFilter;
 
 use crate::tracing::subscriber::DefaultGuard;
+use crate::SpanBufferLayer;
+
+const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
 
 /// Write logs to stdout.
 pub fn init_default_tracing() {
@@ -62,17 +69,16 @@ lazy_static! {
 
 /// Init logging and tracing.
 ///
-/// To enable reporting tracing data to jaeger, set env var `DATABEND_JAEGER` to non-empty value.
+/// To enable exporting the spans with OTLP, set env var `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// to the address of an OTLP collector.
 /// A local tracing collection(maybe for testing) can be done with a local jaeger server.
 /// To report tracing data and view it:
-///   docker run -d -p6831:6831/udp -p6832:6832/udp -p16686:16686 jaegertracing/all-in-one:latest
-///   DATABEND_JAEGER=on RUST_LOG=trace cargo test
+///   docker run -d -e COLLECTOR_OTLP_ENABLED=true -p4317:4317 -p16686:16686 jaegertracing/all-in-one:latest
+///   OTEL_EXPORTER_OTLP_ENDPOINT=http://localhost:4317 RUST_LOG=trace cargo test
 ///   open http://localhost:16686/
 ///
 /// To adjust batch sending delay, use `OTEL_BSP_SCHEDULE_DELAY`:
-///   DATABEND_JAEGER=on RUST_LOG=trace OTEL_BSP_SCHEDULE_DELAY=1 cargo test
-///
-// TODO(xp): use DATABEND_JAEGER to assign jaeger server address.
+///   OTEL_EXPORTER_OTLP_ENDPOINT=http://localhost:4317 OTEL_BSP_SCHEDULE_DELAY=1 cargo test
 fn init_tracing_stdout() {
     let fmt_layer = Layer::default()
         .with_thread_ids(true)
@@ -84,24 +90,44 @@ fn init_tracing_stdout() {
     let subscriber = Registry::default()
         .with(EnvFilter::from_default_env())
         .with(fmt_layer)
-        .with(jaeger_layer());
+        .with(SpanBufferLayer)
+        .with(otlp_layer("databend-test", ""));
 
     tracing::subscriber::set_global_default(subscriber)
         .expect("error setting global tracing subscriber");
 }
 
-fn jaeger_layer<
+/// Export the spans to an OTLP collector, e.g. a jaeger with OTLP enabled.
+/// The endpoint falls back to env var `OTEL_EXPORTER_OTLP_ENDPOINT`, no span is exported if
+/// both are empty.
+/// The W3C trace context propagator is installed too, to chain the spans of the remote peers.
+fn otlp_layer<
     S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
->() -> Option<impl tracing_subscriber::layer::Layer<S>> {
-    let fuse_jaeger = env::var("DATABEND_JAEGER").unwrap_or_else(|_| "".to_string());
-
-    if !fuse_jaeger.is_empty() {
+>(
+    service_name: &str,
+    endpoint: &str,
+) -> Option<impl tracing_subscriber::layer::Layer<S>> {
+    let endpoint = match endpoint {
+        "" => env::var(OTLP_ENDPOINT_ENV).unwrap_or_else(|_| "".to_string()),
+        endpoint => endpoint.to_string(),
+    };
+
+    if !endpoint.is_empty() {
         global::set_text_map_propagator(TraceContextPropagator::new());
 
-        let tracer = opentelemetry_jaeger::new_pipeline()
-            .with_service_name("databend-meta")
-            .install_batch(opentelemetry::runtime::Tokio)
-            .expect("install");
+        let tracer =
+            opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .with_trace_config(trace::config().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", service_name.to_string()),
+                ])))
+                .install_batch(opentelemetry::runtime::Tokio)
+                .expect("install");
 
         let ot_layer = tracing_opentelemetry::layer().with_tracer(tracer);
         Some(ot_layer)
@@ -111,7 +137,14 @@ fn jaeger_layer<
 }
 
 /// Write logs to file and rotation by HOUR.
-pub fn init_tracing_with_file(app_name: &str, dir: &str, level: &str) -> Vec<WorkerGuard> {
+/// The spans are exported to `otlp_endpoint` if it is not empty, the `app_name` is the service
+/// name of them.
+pub fn init_tracing_with_file(
+    app_name: &str,
+    dir: &str,
+    level: &str,
+    otlp_endpoint: &str,
+) -> Vec<WorkerGuard> {
     let mut guards = vec![];
 
     let (stdout_writer, stdout_guard) = tracing_appender::non_blocking(std::io::stdout());
@@ -128,7 +161,8 @@ pub fn init_tracing_with_file(app_name: &str, dir: &str, level: &str) -> Vec<Wor
         .with(stdout_logging_layer)
         .with(JsonStorageLayer)
         .with(file_logging_layer)
-        .with(jaeger_layer());
+        .with(SpanBufferLayer)
+        .with(otlp_layer(app_name, otlp_endpoint));
 
     tracing::subscriber::set_global_default(subscriber)
         .expect("error setting global tracing subscriber");
@@ -160,7 +194,8 @@ pub fn init_global_tracing(app_name: &str, dir: &str) -> WorkerGuard {
 /// Create a file based tracing/logging subscriber.
 /// A guard must be held during using the logging.
 /// The format layer logging span/event in plain text, without color, one event per line.
-/// Optionally it adds a layer to send to opentelemetry if env var `DATABEND_JAEGER` is present.
+/// Optionally it adds a layer to send to opentelemetry if env var `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// is present.
 pub fn init_file_subscriber(app_name: &str, dir: &str) -> (WorkerGuard, impl Subscriber) {
     let path_str = dir.to_string() + "/" + app_name;
     let path: &Path = path_str.as_ref();
@@ -194,7 +229,8 @@ pub fn init_file_subscriber(app_name: &str, dir: &str) -> (WorkerGuard, impl Sub
     let subscriber = Registry::default()
         .with(EnvFilter::from_default_env())
         .with(f_layer)
-        .with(jaeger_layer());
+        .with(SpanBufferLayer)
+        .with(otlp_layer(app_name, ""));
 
     (writer_guard, subscriber)
 }
@@ -0,0 +1,201 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use lazy_static::lazy_static;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span::Attributes;
+use tracing::span::Id;
+use tracing::span::Record;
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Spans kept by the `SpanBuffer`, the oldest are dropped first.
+const SPAN_BUFFER_CAPACITY: usize = 10000;
+
+/// Fields of the spans which carry the id of the query, e.g. `fields(ctx.id = ...)`.
+const QUERY_ID_FIELDS: [&str; 2] = ["ctx.id", "query_id"];
+
+lazy_static! {
+    static ref SPAN_BUFFER: SpanBuffer = SpanBuffer::create(SPAN_BUFFER_CAPACITY);
+}
+
+/// A closed span of a query.
+#[derive(Clone, Debug)]
+pub struct SpanRecord {
+    pub query_id: String,
+    pub span_id: u64,
+    /// 0 if the span has no parent.
+    pub parent_id: u64,
+    pub name: String,
+    pub target: String,
+    pub level: String,
+    pub start_time: SystemTime,
+    pub duration: Duration,
+    /// The fields of the span, as `name=value` separated by spaces.
+    pub fields: String,
+}
+
+/// The latest closed spans of the queries of this process, in memory.
+pub struct SpanBuffer {
+    capacity: usize,
+    spans: Mutex<VecDeque<SpanRecord>>,
+}
+
+impl SpanBuffer {
+    pub fn create(capacity: usize) -> SpanBuffer {
+        SpanBuffer {
+            capacity,
+            spans: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// The buffer filled by the `SpanBufferLayer`.
+    pub fn instance() -> &'static SpanBuffer {
+        &SPAN_BUFFER
+    }
+
+    pub fn push(&self, span: SpanRecord) {
+        let mut spans = self.spans.lock().unwrap();
+        if spans.len() >= self.capacity {
+            spans.pop_front();
+        }
+        spans.push_back(span);
+    }
+
+    /// The spans of the query, or of all the queries, from the oldest to the latest.
+    pub fn spans(&self, query_id: Option<&str>) -> Vec<SpanRecord> {
+        let spans = self.spans.lock().unwrap();
+        spans
+            .iter()
+            .filter(|span| query_id.map_or(true, |id| span.query_id == id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Timing of an open span, kept in the extensions of the span.
+struct SpanTiming {
+    query_id: Option<String>,
+    start: Instant,
+    start_time: SystemTime,
+    fields: String,
+}
+
+#[derive(Default)]
+struct FieldsVisitor {
+    query_id: Option<String>,
+    fields: String,
+}
+
+impl Visit for FieldsVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if QUERY_ID_FIELDS.contains(&field.name()) {
+            self.query_id = Some(value.to_string());
+        }
+        self.record_debug(field, &value)
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={:?}", field.name(), value);
+    }
+}
+
+/// Keeps the closed spans of the queries in the `SpanBuffer`.
+///
+/// A span belongs to the query of its `ctx.id` or `query_id` field, or else to the query of its
+/// parent. The spans out of any query are not kept.
+pub struct SpanBufferLayer;
+
+impl<S> Layer<S> for SpanBufferLayer
+where S: Subscriber + for<'a> LookupSpan<'a>
+{
+    fn new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            None => return,
+            Some(span) => span,
+        };
+
+        let mut visitor = FieldsVisitor::default();
+        attrs.record(&mut visitor);
+        let query_id = visitor.query_id.or_else(|| {
+            let parent = span.parent()?;
+            let extensions = parent.extensions();
+            extensions.get::<SpanTiming>()?.query_id.clone()
+        });
+
+        span.extensions_mut().insert(SpanTiming {
+            query_id,
+            start: Instant::now(),
+            start_time: SystemTime::now(),
+            fields: visitor.fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(timing) = extensions.get_mut::<SpanTiming>() {
+                let mut visitor = FieldsVisitor {
+                    query_id: None,
+                    fields: std::mem::take(&mut timing.fields),
+                };
+                values.record(&mut visitor);
+                timing.fields = visitor.fields;
+                if visitor.query_id.is_some() {
+                    timing.query_id = visitor.query_id;
+                }
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(&id) {
+            None => return,
+            Some(span) => span,
+        };
+
+        let extensions = span.extensions();
+        let timing = match extensions.get::<SpanTiming>() {
+            Some(timing) if timing.query_id.is_some() => timing,
+            _ => return,
+        };
+
+        let metadata = span.metadata();
+        SpanBuffer::instance().push(SpanRecord {
+            query_id: timing.query_id.clone().unwrap_or_default(),
+            span_id: id.into_u64(),
+            parent_id: span.parent().map_or(0, |parent| parent.id().into_u64()),
+            name: metadata.name().to_string(),
+            target: metadata.target().to_string(),
+            level: metadata.level().to_string(),
+            start_time: timing.start_time,
+            duration: timing.start.elapsed(),
+            fields: timing.fields.clone(),
+        });
+    }
+}
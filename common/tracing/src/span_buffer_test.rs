@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+use std::time::SystemTime;
+
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::Registry;
+
+use crate::SpanBuffer;
+use crate::SpanBufferLayer;
+use crate::SpanRecord;
+
+#[test]
+fn test_span_buffer_layer() {
+    let subscriber = Registry::default().with(SpanBufferLayer);
+    tracing::subscriber::with_default(subscriber, || {
+        // Out of any query.
+        tracing::info_span!("other").in_scope(|| {});
+
+        let query = tracing::info_span!("query", ctx.id = "span-buffer-test");
+        let _query = query.enter();
+        tracing::info_span!("child", rows = 3).in_scope(|| {});
+    });
+
+    let spans = SpanBuffer::instance().spans(Some("span-buffer-test"));
+    assert_eq!(spans.len(), 2);
+
+    // The children are closed before their parents.
+    assert_eq!(spans[0].name, "child");
+    assert_eq!(spans[0].fields, "rows=3");
+    assert_eq!(spans[0].parent_id, spans[1].span_id);
+    assert_eq!(spans[1].name, "query");
+    assert_eq!(spans[1].fields, "ctx.id=\"span-buffer-test\"");
+    assert_eq!(spans[1].parent_id, 0);
+    assert_eq!(spans[1].level, "INFO");
+    assert!(spans[1].duration >= spans[0].duration);
+}
+
+#[test]
+fn test_span_buffer_capacity() {
+    let buffer = SpanBuffer::create(2);
+    for (idx, query_id) in ["q1", "q2", "q1"].iter().enumerate() {
+        buffer.push(SpanRecord {
+            query_id: query_id.to_string(),
+            span_id: idx as u64,
+            parent_id: 0,
+            name: "span".to_string(),
+            target: "test".to_string(),
+            level: "INFO".to_string(),
+            start_time: SystemTime::now(),
+            duration: Duration::from_millis(1),
+            fields: "".to_string(),
+        });
+    }
+
+    // The oldest span is dropped.
+    assert_eq!(buffer.spans(None).len(), 2);
+    let spans = buffer.spans(Some("q1"));
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].span_id, 2);
+}
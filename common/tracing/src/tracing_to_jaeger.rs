@@ -52,6 +52,21 @@ impl<'a> Extractor for MetadataMapExtractor<'a> {
     }
 }
 
+/// Extract tracing info from the headers of a http request.
+pub(crate) struct HeaderMapExtractor<'a>(pub(crate) &'a http::HeaderMap);
+
+impl<'a> Extractor for HeaderMapExtractor<'a> {
+    /// Get a value for a key from the HeaderMap.  If the value can't be converted to &str, returns None
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    /// Collect all the keys from the HeaderMap.
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect::<Vec<_>>()
+    }
+}
+
 /// Inject current tracing::Span info into tonic request meta
 /// before sending request to a tonic server.
 /// Then the tonic server will be able to chain a distributed tracing.
@@ -87,3 +102,15 @@ pub fn extract_remote_span_as_parent<T>(request: &tonic::Request<T>) {
     let span = tracing::Span::current();
     span.set_parent(parent_cx);
 }
+
+/// Extract tracing context from the headers of a http request, e.g. the W3C `traceparent`,
+/// and set current tracing::Span parent to the context from the client.
+///
+/// A http handler should call this before doing anything else.
+pub fn extract_remote_span_from_http_headers(headers: &http::HeaderMap) {
+    let parent_cx =
+        global::get_text_map_propagator(|prop| prop.extract(&HeaderMapExtractor(headers)));
+
+    let span = tracing::Span::current();
+    span.set_parent(parent_cx);
+}
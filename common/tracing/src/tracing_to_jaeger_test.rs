@@ -0,0 +1,47 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::sdk::propagation::TraceContextPropagator;
+use opentelemetry::trace::TraceContextExt;
+
+use crate::tracing_to_jaeger::HeaderMapExtractor;
+
+#[test]
+fn test_extract_trace_context_from_http_headers() {
+    let propagator = TraceContextPropagator::new();
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert(
+        "traceparent",
+        http::HeaderValue::from_static("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"),
+    );
+    let cx = propagator.extract(&HeaderMapExtractor(&headers));
+    let span_context = cx.span().span_context().clone();
+    assert!(span_context.is_remote());
+    assert!(span_context.is_sampled());
+    assert_eq!(
+        format!("{:032x}", span_context.trace_id()),
+        "0af7651916cd43dd8448eb211c80319c"
+    );
+    assert_eq!(
+        format!("{:016x}", span_context.span_id()),
+        "b7ad6b7169203331"
+    );
+
+    // No trace context from the client.
+    let headers = http::HeaderMap::new();
+    let cx = propagator.extract(&HeaderMapExtractor(&headers));
+    assert!(!cx.span().span_context().is_valid());
+}
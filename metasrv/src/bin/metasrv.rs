@@ -34,6 +34,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "databend-meta",
         conf.log_dir.as_str(),
         conf.log_level.as_str(),
+        conf.tracing_otlp_endpoint.as_str(),
     );
 
     info!("{:?}", conf.clone());
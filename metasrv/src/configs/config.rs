@@ -41,6 +41,7 @@ lazy_static! {
 
 pub const METASRV_LOG_LEVEL: &str = "METASRV_LOG_LEVEL";
 pub const METASRV_LOG_DIR: &str = "METASRV_LOG_DIR";
+pub const METASRV_TRACING_OTLP_ENDPOINT: &str = "METASRV_TRACING_OTLP_ENDPOINT";
 pub const METASRV_METRIC_API_ADDRESS: &str = "METASRV_METRIC_API_ADDRESS";
 pub const ADMIN_API_ADDRESS: &str = "ADMIN_API_ADDRESS";
 pub const ADMIN_TLS_SERVER_CERT: &str = "ADMIN_TLS_SERVER_CERT";
@@ -57,6 +58,9 @@ pub struct Config {
     #[structopt(long, env = METASRV_LOG_DIR, default_value = "./_logs")]
     pub log_dir: String,
 
+    #[structopt(long, env = METASRV_TRACING_OTLP_ENDPOINT, default_value = "")]
+    pub tracing_otlp_endpoint: String,
+
     #[structopt(
     long,
     env = METASRV_METRIC_API_ADDRESS,
@@ -74,6 +74,7 @@ impl ActionHandler {
             MetaFlightAction::GetTables(a) => s.serialize(self.handle(a).await?),
             MetaFlightAction::GetTableExt(a) => s.serialize(self.handle(a).await?),
             MetaFlightAction::CommitTable(a) => s.serialize(self.handle(a).await?),
+            MetaFlightAction::UpdateTableSchema(a) => s.serialize(self.handle(a).await?),
         }
     }
 }
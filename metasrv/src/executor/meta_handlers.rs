@@ -30,19 +30,25 @@ use common_meta_flight::GetDatabasesAction;
 use common_meta_flight::GetTableAction;
 use common_meta_flight::GetTableExtReq;
 use common_meta_flight::GetTablesAction;
+use common_meta_flight::UpdateTableSchemaReq;
 use common_meta_flight::UpsertTableOptionReq;
 use common_meta_raft_store::state_machine::AppliedState;
 use common_meta_types::Cmd::CreateDatabase;
+use common_meta_types::Cmd::CreateDatabaseWithTables;
 use common_meta_types::Cmd::CreateTable;
 use common_meta_types::Cmd::DropDatabase;
 use common_meta_types::Cmd::DropTable;
+use common_meta_types::Cmd::UpdateTableSchema;
+use common_meta_types::Cmd::UpsertTableOption;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateTableReply;
 use common_meta_types::DatabaseInfo;
 use common_meta_types::LogEntry;
 use common_meta_types::Table;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
+use common_planners::CreateTablePlan;
 use log::info;
 
 use crate::executor::action_handler::RequestHandler;
@@ -59,16 +65,25 @@ impl RequestHandler<CreateDatabaseAction> for ActionHandler {
         let db_name = &plan.db;
         let if_not_exists = plan.if_not_exists;
 
-        let cr = LogEntry {
-            txid: None,
-            cmd: CreateDatabase {
+        let db = DatabaseInfo {
+            database_id: 0,
+            db: db_name.clone(),
+            options: plan.options.clone(),
+        };
+        // The tables are created in the same raft log as the database.
+        let cmd = if plan.tables.is_empty() {
+            CreateDatabase {
                 name: db_name.clone(),
-                db: DatabaseInfo {
-                    database_id: 0,
-                    db: db_name.clone(),
-                },
-            },
+                db,
+            }
+        } else {
+            CreateDatabaseWithTables {
+                name: db_name.clone(),
+                db,
+                tables: plan.tables.iter().map(table_of_plan).collect(),
+            }
         };
+        let cr = LogEntry { txid: None, cmd };
 
         let rst = self
             .meta_node
@@ -153,28 +168,13 @@ impl RequestHandler<CreateTableAction> for ActionHandler {
 
         info!("create table: {:}: {:?}", &db_name, &table_name);
 
-        let options = IpcWriteOptions::default();
-        let flight_data = serialize_schema(&plan.schema.to_arrow(), &options);
-
-        let table = Table {
-            table_id: 0,
-            table_version: 0,
-            table_name: table_name.to_string(),
-            database_id: 0, // this field is unused during the creation of table
-            db_name: db_name.to_string(),
-            schema: flight_data.data_header,
-            table_engine: plan.engine.clone(),
-            table_options: plan.options.clone(),
-            parts: Default::default(),
-        };
-
         let cr = LogEntry {
             txid: None,
             cmd: CreateTable {
                 db_name: db_name.clone(),
                 table_name: table_name.clone(),
                 if_not_exists,
-                table,
+                table: table_of_plan(&plan),
             },
         };
 
@@ -357,13 +357,107 @@ impl RequestHandler<UpsertTableOptionReq> for ActionHandler {
         &self,
         req: UpsertTableOptionReq,
     ) -> common_exception::Result<UpsertTableOptionReply> {
-        self.meta_node
-            .upsert_table_opt(
-                req.table_id,
-                req.table_version,
-                req.option_key,
-                req.option_value,
-            )
+        let table_id = req.table_id;
+        let table_version = req.table_version;
+
+        let cr = LogEntry {
+            txid: None,
+            cmd: UpsertTableOption {
+                table_id,
+                table_version,
+                option_key: req.option_key,
+                option_value: req.option_value,
+            },
+        };
+
+        let rst = self
+            .meta_node
+            .write(cr)
             .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))?;
+
+        let prev = match rst {
+            AppliedState::Table { prev, .. } => prev,
+            _ => return Err(ErrorCode::MetaNodeInternalError("not a Table result")),
+        };
+
+        match prev {
+            None => Err(ErrorCode::UnknownTable(format!(
+                "Unknown table of id: {}",
+                table_id
+            ))),
+            Some(prev) if prev.table_version != table_version => {
+                Err(ErrorCode::CommitTableError(format!(
+                    "expecting table version: [{}], but got [{}]. (table_id {})",
+                    table_version, prev.table_version, table_id
+                )))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<UpdateTableSchemaReq> for ActionHandler {
+    async fn handle(
+        &self,
+        req: UpdateTableSchemaReq,
+    ) -> common_exception::Result<UpdateTableSchemaReply> {
+        let table_id = req.table_id;
+        let table_version = req.table_version;
+
+        let options = IpcWriteOptions::default();
+        let flight_data = serialize_schema(&req.schema.to_arrow(), &options);
+
+        let cr = LogEntry {
+            txid: None,
+            cmd: UpdateTableSchema {
+                table_id,
+                table_version,
+                schema: flight_data.data_header,
+            },
+        };
+
+        let rst = self
+            .meta_node
+            .write(cr)
+            .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))?;
+
+        let prev = match rst {
+            AppliedState::Table { prev, .. } => prev,
+            _ => return Err(ErrorCode::MetaNodeInternalError("not a Table result")),
+        };
+
+        match prev {
+            None => Err(ErrorCode::UnknownTable(format!(
+                "Unknown table of id: {}",
+                table_id
+            ))),
+            Some(prev) if prev.table_version != table_version => {
+                Err(ErrorCode::CommitTableError(format!(
+                    "expecting table version: [{}], but got [{}]. (table_id {})",
+                    prev.table_version, table_version, table_id
+                )))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+fn table_of_plan(plan: &CreateTablePlan) -> Table {
+    let options = IpcWriteOptions::default();
+    let flight_data = serialize_schema(&plan.schema.to_arrow(), &options);
+
+    Table {
+        table_id: 0,
+        table_version: 0,
+        table_name: plan.table.clone(),
+        database_id: 0, // this field is unused during the creation of table
+        db_name: plan.db.clone(),
+        schema: flight_data.data_header,
+        table_engine: plan.engine.clone(),
+        table_options: plan.options.clone(),
+        parts: Default::default(),
     }
 }
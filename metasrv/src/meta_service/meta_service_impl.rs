@@ -80,6 +80,30 @@ impl MetaService for MetaServiceImpl {
         Ok(tonic::Response::new(rst))
     }
 
+    /// Compacts the raft log of this node out of the snapshot policy, e.g. before a backup or
+    /// when the log grows too large.
+    #[tracing::instrument(level = "info", skip(self, request))]
+    async fn compact_log(
+        &self,
+        request: tonic::Request<RaftMes>,
+    ) -> Result<tonic::Response<RaftMes>, tonic::Status> {
+        common_tracing::extract_remote_span_as_parent(&request);
+
+        let snapshot_meta = self
+            .meta_node
+            .sto
+            .compact_log()
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+        let data = serde_json::to_string(&snapshot_meta).expect("fail to serialize resp");
+        let mes = RaftMes {
+            data,
+            error: "".to_string(),
+        };
+
+        Ok(tonic::Response::new(mes))
+    }
+
     #[tracing::instrument(level = "info", skip(self, request))]
     async fn append_entries(
         &self,
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use async_raft::SnapshotMeta;
 use async_raft::State;
 use common_base::tokio;
 use common_meta_raft_store::state_machine::AppliedState;
@@ -25,6 +26,7 @@ use pretty_assertions::assert_eq;
 
 use crate::meta_service::MetaNode;
 use crate::meta_service::MetaServiceClient;
+use crate::meta_service::RaftMes;
 use crate::meta_service::RetryableError;
 use crate::tests::assert_meta_connection;
 use crate::tests::service::new_test_context;
@@ -74,6 +76,37 @@ async fn test_meta_server_upsert_kv() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_server_compact_log() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_meta_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_test_context();
+    let addr = tc.config.raft_config.raft_api_addr();
+
+    let mn = MetaNode::boot(0, &tc.config.raft_config).await?;
+    assert_meta_connection(&addr).await?;
+
+    let mut client = MetaServiceClient::connect(format!("http://{}", addr)).await?;
+    let req = RaftMes {
+        data: "".to_string(),
+        error: "".to_string(),
+    };
+
+    // the logs of booting the cluster are compacted
+    let raft_mes = client.compact_log(req.clone()).await?.into_inner();
+    let meta: Option<SnapshotMeta> = serde_json::from_str(&raft_mes.data)?;
+    let meta = meta.unwrap();
+    assert!(meta.last_log_id.index > 0);
+    assert_eq!(1, mn.sto.log.stat()?.entries);
+
+    // nothing to compact
+    let raft_mes = client.compact_log(req).await?.into_inner();
+    assert_eq!("null", raft_mes.data);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_meta_server_incr_seq() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_meta_ut!();
@@ -17,6 +17,8 @@ use std::collections::HashSet;
 use std::io::Cursor;
 use std::ops::Bound;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use async_raft::async_trait::async_trait;
 use async_raft::config::Config;
@@ -41,6 +43,7 @@ use common_base::tokio::sync::RwLockWriteGuard;
 use common_base::tokio::task::JoinHandle;
 use common_exception::prelude::ErrorCode;
 use common_exception::prelude::ToErrorCode;
+use common_infallible::Mutex as SyncMutex;
 use common_meta_raft_store::config::RaftConfig;
 use common_meta_raft_store::log::RaftLog;
 use common_meta_raft_store::state::RaftState;
@@ -113,6 +116,15 @@ pub struct MetaRaftStore {
 
     /// The current snapshot.
     pub current_snapshot: RwLock<Option<Snapshot>>,
+
+    /// Serializes the log compactions of raft and the ones of the idle compaction or an admin.
+    compaction_lock: Mutex<()>,
+
+    /// When a log is applied to the state machine the last time, to tell if the node is idle.
+    last_applied_at: SyncMutex<Instant>,
+
+    /// When the current snapshot is taken, or when the store is opened if there is none.
+    last_snapshot_at: SyncMutex<Instant>,
 }
 
 // TODO(xp): the following is a draft struct when meta storage is migrated to sled based impl.
@@ -185,9 +197,105 @@ impl MetaRaftStore {
             log,
             state_machine: sm,
             current_snapshot,
+            compaction_lock: Mutex::new(()),
+            last_applied_at: SyncMutex::new(Instant::now()),
+            last_snapshot_at: SyncMutex::new(Instant::now()),
         })
     }
 
+    /// Take a snapshot of the state machine and remove the logs included in it.
+    /// The caller must hold `compaction_lock`.
+    async fn take_snapshot_and_remove_logs(
+        &self,
+    ) -> common_exception::Result<(SnapshotMeta, Vec<u8>)> {
+        // TODO(xp): add test of small chunk snapshot transfer and installation
+
+        // TODO(xp): disallow to install a snapshot with smaller last_applied_log
+
+        // 1. Take a serialized snapshot
+
+        let (view, last_applied_log, last_membership, snapshot_id) =
+            self.state_machine.write().await.snapshot()?;
+
+        let data = StateMachine::serialize_snapshot(view)?;
+        let snapshot_size = data.len();
+
+        let snap_meta = SnapshotMeta {
+            last_log_id: last_applied_log,
+            snapshot_id,
+            membership: last_membership.clone(),
+        };
+
+        let snapshot = Snapshot {
+            meta: snap_meta.clone(),
+            data: data.clone(),
+        };
+
+        // 2. Remove logs that are included in snapshot.
+
+        // When encountered a snapshot pointer, raft replication is switched to snapshot replication.
+        self.log
+            .insert(&Entry::new_snapshot_pointer(&snapshot.meta))
+            .await?;
+
+        self.log.range_remove(0..last_applied_log.index).await?;
+
+        tracing::debug!("log range_remove complete");
+
+        // Update the snapshot first.
+        {
+            let mut current_snapshot = self.current_snapshot.write().await;
+            *current_snapshot = Some(snapshot);
+        }
+        *self.last_snapshot_at.lock() = Instant::now();
+
+        tracing::debug!(snapshot_size = snapshot_size, "log compaction complete");
+
+        Ok((snap_meta, data))
+    }
+
+    /// Compact the log out of the snapshot policy of raft: take a snapshot and remove the logs
+    /// included in it.
+    /// Returns None if no log is applied since the current snapshot.
+    #[tracing::instrument(level = "info", skip(self), fields(id=self.id))]
+    pub async fn compact_log(&self) -> common_exception::Result<Option<SnapshotMeta>> {
+        let _guard = self.compaction_lock.lock().await;
+
+        let last_applied = self.state_machine.read().await.get_last_applied()?;
+        let snapshot_index = match &*self.current_snapshot.read().await {
+            Some(snapshot) => snapshot.meta.last_log_id.index,
+            None => 0,
+        };
+        if last_applied.index <= snapshot_index {
+            return Ok(None);
+        }
+
+        let (snap_meta, _) = self.take_snapshot_and_remove_logs().await?;
+        Ok(Some(snap_meta))
+    }
+
+    /// Whether the idle compaction should compact the log now: the node is idle, and the log is
+    /// larger than the thresholds or the last snapshot is older than `snapshot_interval_secs`.
+    pub fn should_compact_when_idle(&self) -> common_exception::Result<bool> {
+        let config = &self.config;
+
+        let idle = Duration::from_millis(config.compaction_idle_ms);
+        if self.last_applied_at.lock().elapsed() < idle {
+            return Ok(false);
+        }
+
+        let stat = self.log.stat()?;
+        if config.compaction_log_entries > 0 && stat.entries > config.compaction_log_entries {
+            return Ok(true);
+        }
+        if config.compaction_log_bytes > 0 && stat.bytes > config.compaction_log_bytes {
+            return Ok(true);
+        }
+
+        let interval = Duration::from_secs(config.snapshot_interval_secs);
+        Ok(config.snapshot_interval_secs > 0 && self.last_snapshot_at.lock().elapsed() >= interval)
+    }
+
     /// Get a handle to the state machine for testing purposes.
     pub async fn get_state_machine(&self) -> RwLockWriteGuard<'_, StateMachine> {
         self.state_machine.write().await
@@ -406,6 +514,7 @@ impl RaftStorage<LogEntry, AppliedState> for MetaRaftStore {
     ) -> anyhow::Result<AppliedState> {
         let mut sm = self.state_machine.write().await;
         let resp = sm.apply(entry).await?;
+        *self.last_applied_at.lock() = Instant::now();
         Ok(resp)
     }
 
@@ -415,54 +524,17 @@ impl RaftStorage<LogEntry, AppliedState> for MetaRaftStore {
         for entry in entries {
             sm.apply(*entry).await?;
         }
+        *self.last_applied_at.lock() = Instant::now();
         Ok(())
     }
 
     #[tracing::instrument(level = "info", skip(self), fields(id=self.id))]
     async fn do_log_compaction(&self) -> anyhow::Result<CurrentSnapshotData<Self::Snapshot>> {
         // NOTE: do_log_compaction is guaranteed to be serialized called by RaftCore.
+        // But it may run along with a compaction out of raft, see `compact_log()`.
+        let _guard = self.compaction_lock.lock().await;
 
-        // TODO(xp): add test of small chunk snapshot transfer and installation
-
-        // TODO(xp): disallow to install a snapshot with smaller last_applied_log
-
-        // 1. Take a serialized snapshot
-
-        let (view, last_applied_log, last_membership, snapshot_id) =
-            self.state_machine.write().await.snapshot()?;
-
-        let data = StateMachine::serialize_snapshot(view)?;
-        let snapshot_size = data.len();
-
-        let snap_meta = SnapshotMeta {
-            last_log_id: last_applied_log,
-            snapshot_id,
-            membership: last_membership.clone(),
-        };
-
-        let snapshot = Snapshot {
-            meta: snap_meta.clone(),
-            data: data.clone(),
-        };
-
-        // 2. Remove logs that are included in snapshot.
-
-        // When encountered a snapshot pointer, raft replication is switched to snapshot replication.
-        self.log
-            .insert(&Entry::new_snapshot_pointer(&snapshot.meta))
-            .await?;
-
-        self.log.range_remove(0..last_applied_log.index).await?;
-
-        tracing::debug!("log range_remove complete");
-
-        // Update the snapshot first.
-        {
-            let mut current_snapshot = self.current_snapshot.write().await;
-            *current_snapshot = Some(snapshot);
-        }
-
-        tracing::debug!(snapshot_size = snapshot_size, "log compaction complete");
+        let (snap_meta, data) = self.take_snapshot_and_remove_logs().await?;
 
         Ok(CurrentSnapshotData {
             meta: snap_meta,
@@ -641,6 +713,8 @@ impl MetaNodeBuilder {
             MetaNode::subscribe_metrics(mn.clone(), metrics_rx).await;
         }
 
+        MetaNode::start_idle_compaction(mn.clone()).await;
+
         let addr = if let Some(a) = self.addr.take() {
             a
         } else {
@@ -869,6 +943,48 @@ impl MetaNode {
         jh.push(h);
     }
 
+    /// Spawn a task to compact the raft log when the node is idle.
+    /// The snapshot policy of raft only counts the logs since the last snapshot, the log of a
+    /// write-heavy node is compacted here when the writes pause.
+    pub async fn start_idle_compaction(mn: Arc<Self>) {
+        let idle_ms = mn.sto.config.compaction_idle_ms;
+        if idle_ms == 0 {
+            return;
+        }
+
+        let mut running_rx = mn.running_rx.clone();
+        let mut jh = mn.join_handles.lock().await;
+        let sto = mn.sto.clone();
+
+        let span = tracing::span!(tracing::Level::INFO, "idle-compaction");
+
+        let h = tokio::task::spawn(
+            async move {
+                loop {
+                    tokio::select! {
+                        _ = running_rx.changed() => {
+                           return Ok::<(), common_exception::ErrorCode>(());
+                        }
+                        _ = tokio::time::sleep(Duration::from_millis(idle_ms)) => {}
+                    };
+
+                    let compacted = match sto.should_compact_when_idle() {
+                        Ok(true) => sto.compact_log().await,
+                        Ok(false) => continue,
+                        Err(e) => Err(e),
+                    };
+                    match compacted {
+                        Ok(Some(meta)) => tracing::info!("idle compaction done: {:?}", meta),
+                        Ok(None) => {}
+                        Err(e) => tracing::warn!("idle compaction failed: {}", e),
+                    }
+                }
+            }
+            .instrument(span),
+        );
+        jh.push(h);
+    }
+
     /// Boot up the first node to create a cluster.
     /// For every cluster this func should be called exactly once.
     /// When a node is initialized with boot or boot_non_voter, start it with databend_meta::new().
@@ -1027,35 +1143,6 @@ impl MetaNode {
         sm.get_table(tid)
     }
 
-    #[tracing::instrument(level = "debug", skip(self))]
-    pub async fn upsert_table_opt(
-        &self,
-        table_id: u64,
-        table_version: u64,
-        opt_key: String,
-        opt_value: String,
-    ) -> common_exception::Result<()> {
-        // non-consensus modification, tobe fixed latter
-        let mut sm = self.sto.state_machine.write().await;
-        if let Some(tbl) = sm.tables.get_mut(&table_id) {
-            if tbl.table_version != table_version {
-                Err(ErrorCode::TableVersionMissMatch(format!(
-                    "targeting version {}, current version {}",
-                    table_version, tbl.table_version,
-                )))
-            } else {
-                tbl.table_options.insert(opt_key, opt_value);
-                tbl.table_version += 1;
-                Ok(())
-            }
-        } else {
-            Err(ErrorCode::UnknownTable(format!(
-                "unknown table of id {}",
-                table_id
-            )))
-        }
-    }
-
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn get_kv(&self, key: &str) -> common_exception::Result<Option<SeqValue<KVValue>>> {
         // inconsistent get: from local state machine
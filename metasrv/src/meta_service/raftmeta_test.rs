@@ -67,8 +67,9 @@ async fn test_meta_node_graceful_shutdown() -> anyhow::Result<()> {
 
     let mut rx0 = mn0.raft.metrics();
 
+    // raft, the metrics watcher, the idle compaction and the grpc service
     let joined = mn0.stop().await?;
-    assert_eq!(3, joined);
+    assert_eq!(4, joined);
 
     // tx closed:
     loop {
@@ -358,6 +359,62 @@ async fn test_meta_node_snapshot_replication() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 5)]
+async fn test_meta_node_idle_compaction() -> anyhow::Result<()> {
+    // - Bring up a single node with a snapshot policy raft never triggers.
+    // - Write more logs than the entries threshold of the idle compaction.
+    // - Check the log is compacted once the writes pause.
+
+    let (_log_guards, ut_span) = init_meta_ut!();
+    let _ent = ut_span.enter();
+
+    let mut tc = new_test_context();
+    tc.config.raft_config.snapshot_logs_since_last = 1_000_000;
+    tc.config.raft_config.compaction_log_entries = 5;
+    tc.config.raft_config.compaction_idle_ms = 100;
+    let addr = tc.config.raft_config.raft_api_addr();
+
+    let mn = MetaNode::boot(0, &tc.config.raft_config).await?;
+    assert_meta_connection(&addr).await?;
+    wait_for_state(&mn, State::Leader).await?;
+
+    for i in 0..10 {
+        let key = format!("test_meta_node_idle_compaction-key-{}", i);
+        mn.write(LogEntry {
+            txid: None,
+            cmd: Cmd::UpsertKV {
+                key,
+                seq: MatchSeq::Any,
+                value: Some(b"v".to_vec()).into(),
+                value_meta: None,
+            },
+        })
+        .await?;
+    }
+
+    tracing::info!("--- check the log is compacted when idle");
+
+    let mut compacted = false;
+    for _ in 0..50 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        if mn.sto.log.stat()?.entries <= 5 {
+            compacted = true;
+            break;
+        }
+    }
+    assert!(compacted, "the log is not compacted when idle");
+    assert!(mn.sto.current_snapshot.read().await.is_some());
+
+    // nothing applied since the snapshot, compacting again is a no-op
+    assert!(mn.sto.compact_log().await?.is_none());
+
+    let got = mn.get_kv("test_meta_node_idle_compaction-key-9").await?;
+    assert_eq!(b"v".to_vec(), got.unwrap().1.value);
+
+    mn.stop().await?;
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 5)]
 async fn test_meta_node_cluster_1_2_2() -> anyhow::Result<()> {
     // - Bring up a cluster with 1 leader, 2 followers and 2 non-voters.
@@ -405,9 +462,9 @@ async fn test_meta_node_restart() -> anyhow::Result<()> {
     tracing::info!("shutting down all");
 
     let n = mn0.stop().await?;
-    assert_eq!(3, n);
+    assert_eq!(4, n);
     let n = mn1.stop().await?;
-    assert_eq!(3, n);
+    assert_eq!(4, n);
 
     tracing::info!("restart all");
 
@@ -252,6 +252,20 @@ async fn test_table_list() -> anyhow::Result<()> {
     MetaApiTestSuite {}.table_list(&client).await
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_database_create_with_tables() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_meta_ut!();
+    let _ent = ut_span.enter();
+
+    let (_tc, addr) = databend_meta::tests::start_metasrv().await?;
+
+    let client = MetaFlightClient::try_create(addr.as_str(), "root", "xxx").await?;
+
+    MetaApiTestSuite {}
+        .database_create_with_tables(&client)
+        .await
+}
+
 // TODO(xp): uncomment following tests when the function is ready
 // ------------------------------------------------------------
 
@@ -269,6 +283,7 @@ async fn test_flight_get_database_meta_ddl_table() -> anyhow::Result<()> {
         db: test_db.to_string(),
         engine: "Local".to_string(),
         options: Default::default(),
+        tables: vec![],
     };
     client.create_database(plan).await?;
 
@@ -288,6 +303,8 @@ async fn test_flight_get_database_meta_ddl_table() -> anyhow::Result<()> {
         schema: schema.clone(),
         options: Default::default(),
         engine: "JSON".to_string(),
+        temporary: false,
+        as_select: None,
     };
 
     client.create_table(plan.clone()).await?;
@@ -361,6 +378,7 @@ async fn test_flight_get_database_meta_ddl_db() -> anyhow::Result<()> {
         db: "db1".to_string(),
         engine: "Local".to_string(),
         options: Default::default(),
+        tables: vec![],
     };
     client.create_database(plan).await?;
 
@@ -387,6 +405,7 @@ async fn test_flight_get_database_meta_ddl_db() -> anyhow::Result<()> {
         db: "db1".to_string(),
         engine: "Local".to_string(),
         options: Default::default(),
+        tables: vec![],
     };
 
     client.create_database(plan).await?;
@@ -397,6 +416,7 @@ async fn test_flight_get_database_meta_ddl_db() -> anyhow::Result<()> {
     let plan = DropDatabasePlan {
         if_exists: true,
         db: "db1".to_string(),
+        cascade: false,
     };
 
     client.drop_database(plan).await?;
@@ -12,10 +12,52 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::Infallible;
+
+use axum::body::Bytes;
+use axum::body::Full;
 use axum::extract::Extension;
+use axum::http::Response;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Json;
+use common_exception::Result;
+
+use crate::sessions::ConfigReloadReport;
+use crate::sessions::SessionManagerRef;
+
+pub async fn config_handler(sessions_extension: Extension<SessionManagerRef>) -> String {
+    format!("{:?}", sessions_extension.0.get_conf())
+}
 
-use crate::configs::Config;
+pub struct ConfigReloadTemplate {
+    result: Result<ConfigReloadReport>,
+}
+
+impl IntoResponse for ConfigReloadTemplate {
+    type Body = Full<Bytes>;
+    type BodyError = Infallible;
+
+    fn into_response(self) -> Response<Self::Body> {
+        match self.result {
+            Ok(report) => Json(report).into_response(),
+            Err(err) => Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Full::from(format!(
+                    "Failed to reload config. Error: {}",
+                    err
+                )))
+                .unwrap(),
+        }
+    }
+}
 
-pub async fn config_handler(cfg: Extension<Config>) -> String {
-    format!("{:?}", cfg.0)
+// reload the config file the server was started with
+pub async fn config_reload_handler(
+    sessions_extension: Extension<SessionManagerRef>,
+) -> ConfigReloadTemplate {
+    let sessions = sessions_extension.0;
+    ConfigReloadTemplate {
+        result: sessions.reload_config_from_file(),
+    }
 }
@@ -29,12 +29,12 @@ async fn test_config() -> common_exception::Result<()> {
     use tower::ServiceExt;
 
     use crate::api::http::v1::config::config_handler;
-    use crate::configs::Config; // for `app.oneshot()`
+    use crate::tests::SessionManagerBuilder; // for `app.oneshot()`
 
-    let conf = Config::default();
+    let sessions = SessionManagerBuilder::create().build()?;
     let cluster_router = Router::new()
         .route("/v1/config", get(config_handler))
-        .layer(AddExtensionLayer::new(conf.clone()));
+        .layer(AddExtensionLayer::new(sessions));
 
     let response = cluster_router
         .clone()
@@ -51,3 +51,39 @@ async fn test_config() -> common_exception::Result<()> {
     assert_eq!(response.status(), StatusCode::OK);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_config_reload() -> common_exception::Result<()> {
+    use axum::body::Body;
+    use axum::handler::post;
+    use axum::http::Request;
+    use axum::http::StatusCode;
+    use axum::http::{self};
+    use axum::AddExtensionLayer;
+    use axum::Router;
+    use pretty_assertions::assert_eq;
+    use tower::ServiceExt;
+
+    use crate::api::http::v1::config::config_reload_handler;
+    use crate::tests::SessionManagerBuilder;
+
+    // Started without a config file, there is nothing to reload from.
+    let sessions = SessionManagerBuilder::create().build()?;
+    let router = Router::new()
+        .route("/v1/config/reload", post(config_reload_handler))
+        .layer(AddExtensionLayer::new(sessions));
+
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri("/v1/config/reload")
+                .method(http::Method::POST)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -20,8 +20,14 @@ mod config_test;
 mod health_test;
 #[cfg(test)]
 mod logs_test;
+#[cfg(test)]
+mod output_format_test;
+#[cfg(test)]
+mod query_test;
 
 pub mod cluster;
 pub mod config;
 pub mod health;
 pub mod logs;
+pub mod output_format;
+pub mod query;
@@ -0,0 +1,225 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use common_datablocks::DataBlock;
+use common_datavalues::is_numeric;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Map;
+use serde_json::Value;
+
+/// The formats of the query results, named after the ClickHouse ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    JSONEachRow,
+    JSONCompact,
+    CSV,
+    CSVWithNames,
+    TSV,
+    TSVWithNames,
+    TSVWithNamesAndTypes,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ErrorCode;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "jsoneachrow" => Ok(OutputFormat::JSONEachRow),
+            "jsoncompact" => Ok(OutputFormat::JSONCompact),
+            "csv" => Ok(OutputFormat::CSV),
+            "csvwithnames" => Ok(OutputFormat::CSVWithNames),
+            "tsv" | "tabseparated" => Ok(OutputFormat::TSV),
+            "tsvwithnames" | "tabseparatedwithnames" => Ok(OutputFormat::TSVWithNames),
+            "tsvwithnamesandtypes" | "tabseparatedwithnamesandtypes" => {
+                Ok(OutputFormat::TSVWithNamesAndTypes)
+            }
+            _ => Err(ErrorCode::UnknownFormat(format!(
+                "Unknown output format: {}, expect one of JSONEachRow, JSONCompact, CSV, CSVWithNames, TSV, TSVWithNames, TSVWithNamesAndTypes",
+                s
+            ))),
+        }
+    }
+}
+
+impl OutputFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OutputFormat::JSONEachRow => "application/x-ndjson",
+            OutputFormat::JSONCompact => "application/json",
+            OutputFormat::CSV | OutputFormat::CSVWithNames => "text/csv; charset=UTF-8",
+            _ => "text/tab-separated-values; charset=UTF-8",
+        }
+    }
+
+    pub fn format(&self, schema: &DataSchemaRef, blocks: &[DataBlock]) -> Result<String> {
+        let rows = Self::rows(schema, blocks)?;
+        let names = schema.fields().iter().map(|f| f.name().clone());
+        match self {
+            OutputFormat::JSONEachRow => Self::format_json_each_row(schema, rows),
+            OutputFormat::JSONCompact => Self::format_json_compact(schema, rows),
+            OutputFormat::CSV => Ok(Self::format_text(rows, ',', Self::escape_csv)),
+            OutputFormat::CSVWithNames => {
+                let mut all = vec![names.map(CellValue::String).collect()];
+                all.extend(rows);
+                Ok(Self::format_text(all, ',', Self::escape_csv))
+            }
+            OutputFormat::TSV => Ok(Self::format_text(rows, '\t', Self::escape_tsv)),
+            OutputFormat::TSVWithNames => {
+                let mut all = vec![names.map(CellValue::String).collect()];
+                all.extend(rows);
+                Ok(Self::format_text(all, '\t', Self::escape_tsv))
+            }
+            OutputFormat::TSVWithNamesAndTypes => {
+                let types = schema
+                    .fields()
+                    .iter()
+                    .map(|f| CellValue::String(format!("{}", f.data_type())));
+                let mut all = vec![names.map(CellValue::String).collect(), types.collect()];
+                all.extend(rows);
+                Ok(Self::format_text(all, '\t', Self::escape_tsv))
+            }
+        }
+    }
+
+    fn rows(schema: &DataSchemaRef, blocks: &[DataBlock]) -> Result<Vec<Vec<CellValue>>> {
+        let mut rows = vec![];
+        for block in blocks {
+            if block.num_columns() == 0 {
+                continue;
+            }
+
+            let mut columns = Vec::with_capacity(block.num_columns());
+            for (col_index, field) in schema.fields().iter().enumerate() {
+                let column = block.column(col_index);
+                let strings = field
+                    .data_type()
+                    .create_serializer(0)?
+                    .serialize_strings(column)?;
+
+                let mut values = Vec::with_capacity(strings.len());
+                for (row_index, s) in strings.into_iter().enumerate() {
+                    let value = match column.try_get(row_index)?.is_null() {
+                        true => CellValue::Null,
+                        false if Self::is_unquoted(field.data_type()) => CellValue::Unquoted(s),
+                        false => CellValue::String(s),
+                    };
+                    values.push(value);
+                }
+                columns.push(values);
+            }
+
+            for row_index in 0..block.num_rows() {
+                rows.push(columns.iter().map(|c| c[row_index].clone()).collect());
+            }
+        }
+        Ok(rows)
+    }
+
+    fn is_unquoted(data_type: &DataType) -> bool {
+        is_numeric(data_type) || data_type == &DataType::Boolean
+    }
+
+    fn format_json_each_row(schema: &DataSchemaRef, rows: Vec<Vec<CellValue>>) -> Result<String> {
+        let mut output = String::new();
+        for row in rows {
+            let mut object = Map::new();
+            for (field, value) in schema.fields().iter().zip(row.into_iter()) {
+                object.insert(field.name().clone(), value.into_json());
+            }
+            output.push_str(&serde_json::to_string(&Value::Object(object))?);
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    fn format_json_compact(schema: &DataSchemaRef, rows: Vec<Vec<CellValue>>) -> Result<String> {
+        let meta: Vec<Value> = schema
+            .fields()
+            .iter()
+            .map(|f| serde_json::json!({"name": f.name(), "type": format!("{}", f.data_type())}))
+            .collect();
+        let num_rows = rows.len();
+        let data: Vec<Value> = rows
+            .into_iter()
+            .map(|row| Value::Array(row.into_iter().map(CellValue::into_json).collect()))
+            .collect();
+
+        let output = serde_json::json!({
+            "meta": meta,
+            "data": data,
+            "rows": num_rows,
+        });
+        Ok(serde_json::to_string(&output)?)
+    }
+
+    fn format_text(
+        rows: Vec<Vec<CellValue>>,
+        delimiter: char,
+        escape: fn(&CellValue) -> String,
+    ) -> String {
+        let mut output = String::new();
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(escape).collect();
+            output.push_str(&cells.join(&delimiter.to_string()));
+            output.push('\n');
+        }
+        output
+    }
+
+    fn escape_csv(value: &CellValue) -> String {
+        match value {
+            CellValue::Null => "\\N".to_string(),
+            CellValue::Unquoted(v) => v.clone(),
+            CellValue::String(v) => format!("\"{}\"", v.replace('"', "\"\"")),
+        }
+    }
+
+    fn escape_tsv(value: &CellValue) -> String {
+        match value {
+            CellValue::Null => "\\N".to_string(),
+            CellValue::Unquoted(v) => v.clone(),
+            CellValue::String(v) => v
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum CellValue {
+    Null,
+    // Numbers and booleans.
+    Unquoted(String),
+    String(String),
+}
+
+impl CellValue {
+    fn into_json(self) -> Value {
+        match self {
+            CellValue::Null => Value::Null,
+            CellValue::Unquoted(v) => match serde_json::from_str::<Value>(&v) {
+                Ok(value) => value,
+                // e.g. NaN and inf
+                Err(_) => Value::String(v),
+            },
+            CellValue::String(v) => Value::String(v),
+        }
+    }
+}
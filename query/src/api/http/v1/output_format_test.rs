@@ -0,0 +1,85 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::api::http::v1::output_format::OutputFormat;
+
+#[test]
+fn test_output_formats() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, true),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![Some(1i32), None]),
+        Series::new(vec!["x", "y\t\"z\""]),
+    ]);
+    let blocks = vec![block];
+
+    // JSON
+    {
+        let output = OutputFormat::JSONEachRow.format(&schema, &blocks)?;
+        let rows = output
+            .lines()
+            .map(serde_json::from_str::<serde_json::Value>)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![
+            serde_json::json!({"a": 1, "b": "x"}),
+            serde_json::json!({"a": null, "b": "y\t\"z\""}),
+        ]);
+
+        let format: OutputFormat = "jsoncompact".parse()?;
+        let output = format.format(&schema, &blocks)?;
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&output)?,
+            serde_json::json!({
+                "meta": [{"name": "a", "type": "Int32"}, {"name": "b", "type": "String"}],
+                "data": [[1, "x"], [null, "y\t\"z\""]],
+                "rows": 2,
+            })
+        );
+    }
+
+    // CSV and TSV
+    let tests = vec![
+        ("CSV", "1,\"x\"\n\\N,\"y\t\"\"z\"\"\"\n"),
+        (
+            "CSVWithNames",
+            "\"a\",\"b\"\n1,\"x\"\n\\N,\"y\t\"\"z\"\"\"\n",
+        ),
+        ("TabSeparated", "1\tx\n\\N\ty\\t\"z\"\n"),
+        ("TSVWithNames", "a\tb\n1\tx\n\\N\ty\\t\"z\"\n"),
+        (
+            "TSVWithNamesAndTypes",
+            "a\tb\nInt32\tString\n1\tx\n\\N\ty\\t\"z\"\n",
+        ),
+    ];
+
+    for (name, expected) in tests {
+        let format: OutputFormat = name.parse()?;
+        assert_eq!(
+            format.format(&schema, &blocks)?,
+            expected,
+            "format {}",
+            name
+        );
+    }
+
+    assert!("XML".parse::<OutputFormat>().is_err());
+    Ok(())
+}
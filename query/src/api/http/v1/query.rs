@@ -0,0 +1,355 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use axum::body::Bytes;
+use axum::body::Full;
+use axum::extract::Extension;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::http::header;
+use axum::http::HeaderMap;
+use axum::http::Response;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+use headers::authorization::Basic;
+use headers::Authorization;
+use headers::HeaderMapExt;
+
+use crate::api::http::v1::output_format::OutputFormat;
+use crate::api::FetchQueryPageAction;
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::QueryPage;
+use crate::sessions::SessionManagerRef;
+use crate::sessions::SessionRef;
+use crate::sql::PlanParser;
+
+pub const QUERY_TOKEN_HEADER: &str = "X-Databend-Query-Token";
+pub const NEXT_PAGE_HEADER: &str = "X-Databend-Next-Page";
+/// Sets the `query_tag` of the query.
+pub const QUERY_TAG_HEADER: &str = "X-Databend-Query-Tag";
+/// A warning of the query, e.g. of a large result, one header per warning.
+pub const WARNING_HEADER: &str = "X-Databend-Warning";
+
+// Timeout in seconds of fetching a page from the node keeping it.
+const FETCH_PAGE_TIMEOUT: u64 = 60;
+
+#[derive(serde::Deserialize, Debug, Default)]
+pub struct QueryParams {
+    /// The output format, JSONCompact by default.
+    pub format: Option<String>,
+    /// The max rows of a page, the result is not paginated by default.
+    pub page_size: Option<usize>,
+}
+
+pub struct QueryOutput {
+    page: QueryPage,
+    /// The token of the paginated result.
+    token: Option<String>,
+    warnings: Vec<String>,
+}
+
+pub struct QueryTemplate {
+    result: Result<QueryOutput>,
+}
+
+impl IntoResponse for QueryTemplate {
+    type Body = Full<Bytes>;
+    type BodyError = Infallible;
+
+    fn into_response(self) -> Response<Self::Body> {
+        match self.result {
+            Ok(output) => {
+                let mut builder = Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, output.page.content_type);
+                if let (Some(token), Some(next_page)) = (output.token, output.page.next_page) {
+                    builder = builder.header(QUERY_TOKEN_HEADER, token.as_str()).header(
+                        NEXT_PAGE_HEADER,
+                        format!("/v1/query/page/{}/{}", token, next_page),
+                    );
+                }
+                for warning in output.warnings {
+                    builder = builder.header(WARNING_HEADER, warning);
+                }
+                builder.body(Full::from(output.page.body)).unwrap()
+            }
+            Err(err) if err.code() == ErrorCode::AuthenticateFailure("").code() => {
+                Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .header(header::WWW_AUTHENTICATE, "Basic")
+                    .body(Full::from(format!(
+                        "Failed to authenticate. Error: {}",
+                        err
+                    )))
+                    .unwrap()
+            }
+            Err(err) => Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Full::from(format!(
+                    "Failed to execute query. Error: {}",
+                    err
+                )))
+                .unwrap(),
+        }
+    }
+}
+
+/// The token of a paginated result is `<node id>.<query id>`, any node behind
+/// a load balancer can tell which node keeps the pages from it.
+pub fn encode_query_token(node_id: &str, query_id: &str) -> String {
+    format!("{}.{}", node_id, query_id)
+}
+
+pub fn decode_query_token(token: &str) -> Result<(String, String)> {
+    match token.split_once('.') {
+        Some((node_id, query_id)) if !node_id.is_empty() && !query_id.is_empty() => {
+            Ok((node_id.to_string(), query_id.to_string()))
+        }
+        _ => Err(ErrorCode::BadArguments(format!(
+            "Invalid query token: {}",
+            token
+        ))),
+    }
+}
+
+// execute the query in the request body as the user of the basic authorization, e.g.
+// curl -u root: -X POST 'http://localhost:8080/v1/query?format=JSONEachRow' -d 'SELECT 1'
+pub async fn query_handler(
+    sessions_extension: Extension<SessionManagerRef>,
+    params: Query<QueryParams>,
+    headers: HeaderMap,
+    query: String,
+) -> QueryTemplate {
+    let sessions = sessions_extension.0;
+    QueryTemplate {
+        result: execute_query(sessions, params.0, headers, query).await,
+    }
+}
+
+// fetch a page of a paginated result from any node, as the user who ran the query, e.g.
+// curl -u root: 'http://localhost:8080/v1/query/page/<token>/1'
+pub async fn query_page_handler(
+    sessions_extension: Extension<SessionManagerRef>,
+    path: Path<(String, usize)>,
+    headers: HeaderMap,
+) -> QueryTemplate {
+    let sessions = sessions_extension.0;
+    let (token, page) = path.0;
+    QueryTemplate {
+        result: fetch_query_page(sessions, headers, token, page).await,
+    }
+}
+
+// The span of the query is a child of the W3C `traceparent` header, if the client sends one.
+#[tracing::instrument(level = "info", skip(sessions, params, headers))]
+async fn execute_query(
+    sessions: SessionManagerRef,
+    params: QueryParams,
+    headers: HeaderMap,
+    query: String,
+) -> Result<QueryOutput> {
+    common_tracing::extract_remote_span_from_http_headers(&headers);
+
+    let format = match &params.format {
+        None => OutputFormat::JSONCompact,
+        Some(format) => OutputFormat::from_str(format)?,
+    };
+
+    let session = sessions.create_session("HTTPQuery")?;
+    authenticate(&session, &headers)?;
+    let context = session.create_context().await?;
+    if let Some(query_tag) = headers.get(QUERY_TAG_HEADER) {
+        let query_tag = query_tag.to_str().map_err(|cause| {
+            ErrorCode::BadArguments(format!("Invalid {}: {}", QUERY_TAG_HEADER, cause))
+        })?;
+        context
+            .get_settings()
+            .set_query_tag(query_tag.to_string())?;
+    }
+    // The query is logged with its tag.
+    context.attach_query_str(&query);
+
+    let query_result = collect_query_blocks(&context, &query).await;
+    if let Err(cause) = &query_result {
+        context.attach_query_error(cause);
+    }
+    let (schema, blocks) = query_result?;
+    let warnings = context.get_query_warnings();
+
+    let page_size = match params.page_size {
+        None => {
+            let page = QueryPage {
+                content_type: format.content_type().to_string(),
+                body: format.format(&schema, &blocks)?,
+                next_page: None,
+            };
+            return Ok(QueryOutput {
+                page,
+                token: None,
+                warnings,
+            });
+        }
+        Some(0) => {
+            return Err(ErrorCode::BadArguments("The page_size must be positive"));
+        }
+        Some(page_size) => page_size,
+    };
+
+    let pages = paginate(&blocks, page_size)
+        .iter()
+        .map(|page| format.format(&schema, page))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Keep the pages on this node, the token tells the other nodes where they are.
+    let query_id = context.get_id();
+    let owner = (session.get_tenant(), session.get_user());
+    let query_pages = sessions.get_query_pages();
+    query_pages.add(
+        query_id.clone(),
+        owner.clone(),
+        format.content_type(),
+        pages,
+    )?;
+
+    let node_id = sessions.get_cluster_discovery().local_id();
+    Ok(QueryOutput {
+        page: query_pages.get_page(&query_id, &owner, 0)?,
+        token: Some(encode_query_token(&node_id, &query_id)),
+        warnings,
+    })
+}
+
+/// Authenticates the `authorization: Basic` header and binds the session to the user, and to
+/// the tenant of a `user@tenant` login, as the MySQL and ClickHouse handlers do.
+fn authenticate(session: &SessionRef, headers: &HeaderMap) -> Result<()> {
+    let authorization = headers.typed_get::<Authorization<Basic>>().ok_or_else(|| {
+        ErrorCode::AuthenticateFailure("The query requires the authorization: Basic header")
+    })?;
+
+    let user_mgr = session.get_user_manager();
+    let (tenant, user_name) = user_mgr.split_login(authorization.username());
+    let user_mgr = user_mgr.for_tenant(&tenant);
+    // An unknown user fails as a wrong password does.
+    let authenticated = user_mgr.auth_user(&user_name, authorization.password(), "");
+    if !matches!(authenticated, Ok(true)) {
+        return Err(ErrorCode::AuthenticateFailure(format!(
+            "HTTP query authenticate failed, user: {}",
+            authorization.username()
+        )));
+    }
+    let user = user_mgr.get_user(&user_name)?;
+    session.set_user_quota(user.quota);
+    session.apply_settings_profile(&user_mgr, &user)?;
+    session.set_user(user_name);
+    session.set_tenant(tenant);
+    Ok(())
+}
+
+async fn collect_query_blocks(
+    context: &DatabendQueryContextRef,
+    query: &str,
+) -> Result<(DataSchemaRef, Vec<DataBlock>)> {
+    let plan = PlanParser::create(context.clone()).build_from_sql(query)?;
+    let schema = plan.schema();
+    context.wait_for_admission().await?;
+    let interpreter = InterpreterFactory::get(context.clone(), plan)?;
+    let stream = context.with_cpu_time(interpreter.execute()).await?;
+    let stream = context.try_create_result_quota(stream)?;
+    let blocks = context
+        .with_cpu_time(stream.try_collect::<Vec<DataBlock>>())
+        .await?;
+
+    // The blocks know better than the plan, e.g. for SHOW statements.
+    let schema = match blocks.first() {
+        Some(block) if block.num_columns() > 0 => block.schema().clone(),
+        _ => schema,
+    };
+    Ok((schema, blocks))
+}
+
+async fn fetch_query_page(
+    sessions: SessionManagerRef,
+    headers: HeaderMap,
+    token: String,
+    page: usize,
+) -> Result<QueryOutput> {
+    let (node_id, query_id) = decode_query_token(&token)?;
+
+    let session = sessions.create_session("HTTPQueryPage")?;
+    authenticate(&session, &headers)?;
+    let (tenant, user) = (session.get_tenant(), session.get_user());
+
+    let discovery = sessions.get_cluster_discovery();
+    let page = match node_id == discovery.local_id() {
+        true => {
+            let owner = (tenant, user);
+            sessions
+                .get_query_pages()
+                .get_page(&query_id, &owner, page)?
+        }
+        false => {
+            // The request landed on another node, forward it to the node keeping the pages.
+            let cluster = discovery.discover().await?;
+            let conf = sessions.get_conf();
+            let mut client = cluster.create_node_conn(&node_id, &conf).await?;
+            let action = FetchQueryPageAction {
+                tenant,
+                user,
+                query_id,
+                page,
+            };
+            client.fetch_query_page(action, FETCH_PAGE_TIMEOUT).await?
+        }
+    };
+
+    Ok(QueryOutput {
+        page,
+        token: Some(token),
+        warnings: vec![],
+    })
+}
+
+fn paginate(blocks: &[DataBlock], page_size: usize) -> Vec<Vec<DataBlock>> {
+    let mut pages = vec![vec![]];
+    let mut page_rows = 0;
+
+    for block in blocks {
+        let mut offset = 0;
+        while offset < block.num_rows() {
+            if page_rows == page_size {
+                pages.push(vec![]);
+                page_rows = 0;
+            }
+
+            let length = (block.num_rows() - offset).min(page_size - page_rows);
+            if let Some(page) = pages.last_mut() {
+                page.push(block.slice(offset, length));
+            }
+            offset += length;
+            page_rows += length;
+        }
+    }
+
+    pages
+}
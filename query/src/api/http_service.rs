@@ -20,6 +20,7 @@ use std::path::Path;
 use std::sync::Arc;
 
 use axum::handler::get;
+use axum::handler::post;
 use axum::routing::BoxRoute;
 use axum::AddExtensionLayer;
 use axum::Router;
@@ -127,7 +128,16 @@ impl HttpService {
         Router::new()
             .route("/v1/health", get(super::http::v1::health::health_handler))
             .route("/v1/config", get(super::http::v1::config::config_handler))
+            .route(
+                "/v1/config/reload",
+                post(super::http::v1::config::config_reload_handler),
+            )
             .route("/v1/logs", get(super::http::v1::logs::logs_handler))
+            .route("/v1/query", post(super::http::v1::query::query_handler))
+            .route(
+                "/v1/query/page/:token/:page",
+                get(super::http::v1::query::query_page_handler),
+            )
             .route(
                 "/v1/cluster/list",
                 get(super::http::v1::cluster::cluster_list_handler),
@@ -147,7 +157,8 @@ impl HttpService {
     async fn start_with_tls(&mut self, listening: SocketAddr) -> Result<SocketAddr> {
         log::info!("Http API TLS enabled");
 
-        let loader = Self::tls_loader(self.sessions.get_conf());
+        let conf = self.sessions.get_conf();
+        let loader = Self::tls_loader(&conf);
 
         let server = axum_server::bind_rustls(listening.to_string())
             .handle(self.abort_handler.clone())
@@ -231,7 +242,8 @@ impl Server for HttpService {
     }
 
     async fn start(&mut self, listening: SocketAddr) -> Result<SocketAddr> {
-        let config = &self.sessions.get_conf().query;
+        let conf = self.sessions.get_conf();
+        let config = &conf.query;
         match config.api_tls_server_key.is_empty() || config.api_tls_server_cert.is_empty() {
             true => self.start_without_tls(listening).await,
             false => self.start_with_tls(listening).await,
@@ -17,9 +17,12 @@
 pub use http_service::HttpService;
 pub use rpc::BroadcastAction;
 pub use rpc::CancelAction;
+pub use rpc::FetchQueryPageAction;
 pub use rpc::FlightAction;
 pub use rpc::FlightClient;
 pub use rpc::FlightTicket;
+pub use rpc::ListMetricsAction;
+pub use rpc::ListProcessesAction;
 pub use rpc::ShuffleAction;
 pub use rpc_service::RpcService;
 
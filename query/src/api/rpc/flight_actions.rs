@@ -41,6 +41,25 @@ pub struct BroadcastAction {
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct CancelAction {
     pub query_id: String,
+    /// The tenant of the killed query, the client sessions of the other tenants are kept.
+    #[serde(default)]
+    pub tenant: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ListProcessesAction {}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ListMetricsAction {}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct FetchQueryPageAction {
+    /// The user authenticated by the node forwarding the request, only the user who ran the
+    /// query can read its pages.
+    pub tenant: String,
+    pub user: String,
+    pub query_id: String,
+    pub page: usize,
 }
 
 impl TryInto<ShuffleAction> for Vec<u8> {
@@ -115,11 +134,86 @@ impl TryInto<Vec<u8>> for CancelAction {
     }
 }
 
+impl TryInto<FetchQueryPageAction> for Vec<u8> {
+    type Error = Status;
+
+    fn try_into(self) -> Result<FetchQueryPageAction, Self::Error> {
+        match std::str::from_utf8(&self) {
+            Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+            Ok(utf8_body) => match serde_json::from_str::<FetchQueryPageAction>(utf8_body) {
+                Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+                Ok(action) => Ok(action),
+            },
+        }
+    }
+}
+
+impl TryInto<Vec<u8>> for FetchQueryPageAction {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(&self).map_err_to_code(ErrorCode::LogicalError, || {
+            "Logical error: cannot serialize FetchQueryPageAction."
+        })
+    }
+}
+
+impl TryInto<ListProcessesAction> for Vec<u8> {
+    type Error = Status;
+
+    fn try_into(self) -> Result<ListProcessesAction, Self::Error> {
+        match std::str::from_utf8(&self) {
+            Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+            Ok(utf8_body) => match serde_json::from_str::<ListProcessesAction>(utf8_body) {
+                Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+                Ok(action) => Ok(action),
+            },
+        }
+    }
+}
+
+impl TryInto<Vec<u8>> for ListProcessesAction {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(&self).map_err_to_code(ErrorCode::LogicalError, || {
+            "Logical error: cannot serialize ListProcessesAction."
+        })
+    }
+}
+
+impl TryInto<ListMetricsAction> for Vec<u8> {
+    type Error = Status;
+
+    fn try_into(self) -> Result<ListMetricsAction, Self::Error> {
+        match std::str::from_utf8(&self) {
+            Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+            Ok(utf8_body) => match serde_json::from_str::<ListMetricsAction>(utf8_body) {
+                Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+                Ok(action) => Ok(action),
+            },
+        }
+    }
+}
+
+impl TryInto<Vec<u8>> for ListMetricsAction {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(&self).map_err_to_code(ErrorCode::LogicalError, || {
+            "Logical error: cannot serialize ListMetricsAction."
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum FlightAction {
     PrepareShuffleAction(ShuffleAction),
     BroadcastAction(BroadcastAction),
     CancelAction(CancelAction),
+    FetchQueryPageAction(FetchQueryPageAction),
+    ListProcessesAction(ListProcessesAction),
+    ListMetricsAction(ListMetricsAction),
 }
 
 impl FlightAction {
@@ -172,6 +266,9 @@ impl TryInto<FlightAction> for Action {
             "PrepareShuffleAction" => Ok(FlightAction::PrepareShuffleAction(self.body.try_into()?)),
             "BroadcastAction" => Ok(FlightAction::BroadcastAction(self.body.try_into()?)),
             "CancelAction" => Ok(FlightAction::CancelAction(self.body.try_into()?)),
+            "FetchQueryPageAction" => Ok(FlightAction::FetchQueryPageAction(self.body.try_into()?)),
+            "ListProcessesAction" => Ok(FlightAction::ListProcessesAction(self.body.try_into()?)),
+            "ListMetricsAction" => Ok(FlightAction::ListMetricsAction(self.body.try_into()?)),
             un_implemented => Err(Status::unimplemented(format!(
                 "UnImplement action {}",
                 un_implemented
@@ -197,6 +294,18 @@ impl TryInto<Action> for FlightAction {
                 r#type: String::from("CancelAction"),
                 body: cancel_action.try_into()?,
             }),
+            FlightAction::FetchQueryPageAction(fetch_action) => Ok(Action {
+                r#type: String::from("FetchQueryPageAction"),
+                body: fetch_action.try_into()?,
+            }),
+            FlightAction::ListProcessesAction(list_action) => Ok(Action {
+                r#type: String::from("ListProcessesAction"),
+                body: list_action.try_into()?,
+            }),
+            FlightAction::ListMetricsAction(list_action) => Ok(Action {
+                r#type: String::from("ListMetricsAction"),
+                body: list_action.try_into()?,
+            }),
         }
     }
 }
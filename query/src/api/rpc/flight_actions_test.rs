@@ -21,6 +21,10 @@ use common_exception::Result;
 use common_planners::Expression;
 
 use crate::api::rpc::flight_actions::FlightAction;
+use crate::api::CancelAction;
+use crate::api::FetchQueryPageAction;
+use crate::api::ListMetricsAction;
+use crate::api::ListProcessesAction;
 use crate::api::ShuffleAction;
 use crate::tests::parse_query;
 
@@ -40,6 +44,9 @@ async fn test_shuffle_action_try_into() -> Result<()> {
     match from_action {
         FlightAction::CancelAction(_) => panic!(),
         FlightAction::BroadcastAction(_) => panic!(),
+        FlightAction::FetchQueryPageAction(_) => panic!(),
+        FlightAction::ListProcessesAction(_) => panic!(),
+        FlightAction::ListMetricsAction(_) => panic!(),
         FlightAction::PrepareShuffleAction(action) => {
             assert_eq!(action.query_id, "query_id");
             assert_eq!(action.stage_id, "stage_id");
@@ -54,3 +61,79 @@ async fn test_shuffle_action_try_into() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_query_page_action_try_into() -> Result<()> {
+    let fetch_action = FetchQueryPageAction {
+        tenant: String::from("tenant"),
+        user: String::from("user"),
+        query_id: String::from("query_id"),
+        page: 2,
+    };
+
+    let from_action = FlightAction::FetchQueryPageAction(fetch_action);
+    let to_action: Action = from_action.try_into()?;
+    let from_action: FlightAction = to_action.try_into()?;
+    match from_action {
+        FlightAction::FetchQueryPageAction(action) => {
+            assert_eq!(action.tenant, "tenant");
+            assert_eq!(action.user, "user");
+            assert_eq!(action.query_id, "query_id");
+            assert_eq!(action.page, 2);
+        }
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cancel_action_try_into() -> Result<()> {
+    let cancel_action = CancelAction {
+        query_id: String::from("query_id"),
+        tenant: String::from("tenant"),
+    };
+
+    let from_action = FlightAction::CancelAction(cancel_action);
+    let to_action: Action = from_action.try_into()?;
+    let from_action: FlightAction = to_action.try_into()?;
+    match from_action {
+        FlightAction::CancelAction(action) => {
+            assert_eq!(action.query_id, "query_id");
+            assert_eq!(action.tenant, "tenant");
+        }
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_list_processes_action_try_into() -> Result<()> {
+    let from_action = FlightAction::ListProcessesAction(ListProcessesAction {});
+    let to_action: Action = from_action.try_into()?;
+    assert_eq!(to_action.r#type, "ListProcessesAction");
+
+    let from_action: FlightAction = to_action.try_into()?;
+    match from_action {
+        FlightAction::ListProcessesAction(_) => {}
+        _ => panic!(),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_list_metrics_action_try_into() -> Result<()> {
+    let from_action = FlightAction::ListMetricsAction(ListMetricsAction {});
+    let to_action: Action = from_action.try_into()?;
+    assert_eq!(to_action.r#type, "ListMetricsAction");
+
+    let from_action: FlightAction = to_action.try_into()?;
+    match from_action {
+        FlightAction::ListMetricsAction(_) => {}
+        _ => panic!(),
+    }
+
+    Ok(())
+}
@@ -22,14 +22,22 @@ use common_base::tokio::time::Duration;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_metrics::MetricSample;
 use common_streams::SendableDataBlockStream;
 use tonic::transport::channel::Channel;
 use tonic::Request;
 use tonic::Streaming;
 
+use crate::api::rpc::flight_actions::CancelAction;
+use crate::api::rpc::flight_actions::FetchQueryPageAction;
 use crate::api::rpc::flight_actions::FlightAction;
+use crate::api::rpc::flight_actions::ListMetricsAction;
+use crate::api::rpc::flight_actions::ListProcessesAction;
 use crate::api::rpc::flight_client_stream::FlightDataStream;
 use crate::api::rpc::flight_tickets::FlightTicket;
+use crate::sessions::NodeProcessInfo;
+use crate::sessions::QueryPage;
 
 pub struct FlightClient {
     inner: FlightServiceClient<Channel>,
@@ -57,9 +65,47 @@ impl FlightClient {
         Ok(())
     }
 
+    pub async fn fetch_query_page(
+        &mut self,
+        action: FetchQueryPageAction,
+        timeout: u64,
+    ) -> Result<QueryPage> {
+        let body = self
+            .do_action(FlightAction::FetchQueryPageAction(action), timeout)
+            .await?;
+        serde_json::from_slice(&body).map_err_to_code(ErrorCode::BadBytes, || {
+            "Cannot deserialize the query page from flight server"
+        })
+    }
+
+    /// Kills the query on the node, returns whether the node ran it.
+    pub async fn kill_query(&mut self, action: CancelAction, timeout: u64) -> Result<bool> {
+        let body = self
+            .do_action(FlightAction::CancelAction(action), timeout)
+            .await?;
+        serde_json::from_slice(&body).map_err_to_code(ErrorCode::BadBytes, || {
+            "Cannot deserialize the kill result from flight server"
+        })
+    }
+
+    pub async fn list_processes(&mut self, timeout: u64) -> Result<Vec<NodeProcessInfo>> {
+        let action = FlightAction::ListProcessesAction(ListProcessesAction {});
+        let body = self.do_action(action, timeout).await?;
+        serde_json::from_slice(&body).map_err_to_code(ErrorCode::BadBytes, || {
+            "Cannot deserialize the processes info from flight server"
+        })
+    }
+
+    pub async fn list_metrics(&mut self, timeout: u64) -> Result<Vec<MetricSample>> {
+        let action = FlightAction::ListMetricsAction(ListMetricsAction {});
+        let body = self.do_action(action, timeout).await?;
+        let text = String::from_utf8(body)?;
+        common_metrics::parse_metric_samples(&text)
+    }
+
     // Execute do_get.
     async fn do_get(&mut self, ticket: Ticket, timeout: u64) -> Result<Streaming<FlightData>> {
-        let mut request = Request::new(ticket);
+        let mut request = common_tracing::inject_span_to_tonic_request(Request::new(ticket));
         request.set_timeout(Duration::from_secs(timeout));
 
         let response = self.inner.do_get(request).await?;
@@ -70,7 +116,7 @@ impl FlightClient {
     async fn do_action(&mut self, action: FlightAction, timeout: u64) -> Result<Vec<u8>> {
         let action: Action = action.try_into()?;
         let action_type = action.r#type.clone();
-        let mut request = Request::new(action);
+        let mut request = common_tracing::inject_span_to_tonic_request(Request::new(action));
         request.set_timeout(Duration::from_secs(timeout));
 
         let response = self.inner.do_action(request).await?;
@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::InsertIntoPlan;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use headers::authorization::Basic;
+use headers::Authorization;
+use headers::HeaderMapExt;
+use tonic::metadata::MetadataMap;
+use tonic::Streaming;
+
+use crate::interpreters::InsertIntoInterpreter;
+use crate::interpreters::SyncBlockStream;
+use crate::sessions::SessionManagerRef;
+
+/// Append the record batches of a DoPut to the table of its descriptor, as `INSERT INTO` does.
+/// Returns the number of the appended rows.
+///
+/// The user logs in with the `authorization: Basic <base64(user:password)>` header. The path of
+/// the descriptor is `[table]` or `[database, table]`, the columns of the record batches are the
+/// columns of the table, in the same order.
+pub async fn do_put_table(
+    sessions: SessionManagerRef,
+    metadata: MetadataMap,
+    mut stream: Streaming<FlightData>,
+) -> Result<usize> {
+    let authorization = metadata
+        .into_headers()
+        .typed_get::<Authorization<Basic>>()
+        .ok_or_else(|| {
+            ErrorCode::AuthenticateFailure("DoPut requires the authorization: Basic header")
+        })?;
+
+    // The descriptor comes with the schema, the first message of the stream.
+    let first = stream
+        .message()
+        .await
+        .map_err(|status| ErrorCode::UnknownException(status.message()))?
+        .ok_or_else(|| ErrorCode::BadArguments("DoPut stream is empty"))?;
+    let (database, table_name) = put_target(first.flight_descriptor.as_ref())?;
+
+    let session = sessions.create_session("FlightPutSession")?;
+    let user_mgr = session.get_user_manager();
+    let (tenant, user_name) = user_mgr.split_login(authorization.username());
+    let user_mgr = user_mgr.for_tenant(&tenant);
+    if !user_mgr.auth_user(&user_name, authorization.password(), "")? {
+        return Err(ErrorCode::AuthenticateFailure(format!(
+            "DoPut authenticate failed, user: {}",
+            authorization.username()
+        )));
+    }
+    let user = user_mgr.get_user(&user_name)?;
+    session.set_user_quota(user.quota);
+    session.apply_settings_profile(&user_mgr, &user)?;
+    session.set_user(user_name);
+    session.set_tenant(tenant);
+
+    let ctx = session.create_context().await?;
+    let database = database.unwrap_or_else(|| ctx.get_current_database());
+    let table = ctx.get_table(&database, &table_name)?;
+    let schema = table.schema();
+    let arrow_schema = Arc::new(schema.to_arrow());
+
+    let rows = Arc::new(AtomicUsize::new(0));
+    let appended_rows = rows.clone();
+    let blocks = futures::stream::iter(vec![Ok::<_, tonic::Status>(first)])
+        .chain(stream)
+        .map_err(|status| ErrorCode::UnknownException(status.message()))
+        // Only the record batches have a body, the schema is the one of the table.
+        .try_filter(|flight_data| futures::future::ready(!flight_data.data_body.is_empty()))
+        .and_then(move |flight_data| {
+            let block = create_data_block(&flight_data, &schema, &arrow_schema);
+            futures::future::ready(block)
+        })
+        .inspect_ok(move |block| {
+            appended_rows.fetch_add(block.num_rows(), Ordering::Relaxed);
+        });
+
+    let plan = InsertIntoPlan {
+        db_name: database,
+        tbl_name: table_name,
+        tbl_id: table.get_id(),
+        schema: table.schema(),
+        select_plan: None,
+        input_stream: InsertIntoPlan::empty_stream(),
+    };
+    plan.set_input_stream(Box::pin(SyncBlockStream::create(Box::pin(blocks))));
+
+    let interpreter = InsertIntoInterpreter::try_create(ctx, plan)?;
+    interpreter.execute().await?;
+    Ok(rows.load(Ordering::Relaxed))
+}
+
+pub fn put_target(descriptor: Option<&FlightDescriptor>) -> Result<(Option<String>, String)> {
+    let path = match descriptor {
+        Some(descriptor) => descriptor.path.as_slice(),
+        None => &[],
+    };
+
+    match path {
+        [table] => Ok((None, table.clone())),
+        [database, table] => Ok((Some(database.clone()), table.clone())),
+        _ => Err(ErrorCode::BadArguments(
+            "The path of the DoPut descriptor must be [table] or [database, table]",
+        )),
+    }
+}
+
+pub fn create_data_block(
+    flight_data: &FlightData,
+    schema: &DataSchemaRef,
+    arrow_schema: &Arc<ArrowSchema>,
+) -> Result<DataBlock> {
+    let record_batch = deserialize_batch(flight_data, arrow_schema.clone(), true, &[])?;
+    let columns = record_batch
+        .columns()
+        .iter()
+        .map(|column| DataColumn::Array(column.clone().into_series()))
+        .collect::<Vec<_>>();
+    Ok(DataBlock::create(schema.clone(), columns))
+}
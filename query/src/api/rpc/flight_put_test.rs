@@ -0,0 +1,86 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::sync::Arc;
+
+use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::api::rpc::flight_put::create_data_block;
+use crate::api::rpc::flight_put::put_target;
+
+#[test]
+fn test_put_target() -> Result<()> {
+    let descriptor = |path: Vec<&str>| FlightDescriptor {
+        r#type: 1,
+        cmd: vec![],
+        path: path.into_iter().map(|p| p.to_string()).collect(),
+    };
+
+    assert_eq!(
+        put_target(Some(&descriptor(vec!["t"])))?,
+        (None, "t".to_string())
+    );
+    assert_eq!(
+        put_target(Some(&descriptor(vec!["db", "t"])))?,
+        (Some("db".to_string()), "t".to_string())
+    );
+
+    for target in [
+        None,
+        Some(descriptor(vec![])),
+        Some(descriptor(vec!["a", "b", "c"])),
+    ] {
+        assert_eq!(
+            put_target(target.as_ref()).unwrap_err().to_string(),
+            "Code: 6, displayText = The path of the DoPut descriptor must be [table] or [database, table]."
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_create_data_block() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1i64, 2, 3]),
+        Series::new(vec!["x", "y", "z"]),
+    ]);
+    let record_batch: RecordBatch = block.try_into()?;
+    let (_, flight_data) = serialize_batch(&record_batch, &IpcWriteOptions::default());
+
+    let block = create_data_block(&flight_data, &schema, &Arc::new(schema.to_arrow()))?;
+    assert_eq!(block.schema(), &schema);
+
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 2 | y |",
+        "| 3 | z |",
+        "+---+---+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, &[block]);
+    Ok(())
+}
@@ -30,6 +30,9 @@ use common_arrow::arrow_format::flight::data::Result as FlightResult;
 use common_arrow::arrow_format::flight::data::SchemaResult;
 use common_arrow::arrow_format::flight::data::Ticket;
 use common_arrow::arrow_format::flight::service::flight_service_server::FlightService;
+use common_exception::ErrorCode;
+use common_exception::ToErrorCode;
+use common_tracing::tracing;
 use tokio_stream::Stream;
 use tonic::Request;
 use tonic::Response as RawResponse;
@@ -39,6 +42,7 @@ use tonic::Streaming;
 use crate::api::rpc::flight_actions::FlightAction;
 use crate::api::rpc::flight_dispatcher::DatabendQueryFlightDispatcher;
 use crate::api::rpc::flight_dispatcher::DatabendQueryFlightDispatcherRef;
+use crate::api::rpc::flight_put::do_put_table;
 use crate::api::rpc::flight_service_stream::FlightDataStream;
 use crate::api::rpc::flight_tickets::FlightTicket;
 use crate::sessions::SessionManagerRef;
@@ -98,7 +102,9 @@ impl FlightService for DatabendQueryFlightService {
 
     type DoGetStream = FlightStream<FlightData>;
 
+    #[tracing::instrument(level = "info", skip(self, request))]
     async fn do_get(&self, request: Request<Ticket>) -> Response<Self::DoGetStream> {
+        common_tracing::extract_remote_span_as_parent(&request);
         let ticket: FlightTicket = request.into_inner().try_into()?;
 
         match ticket {
@@ -114,9 +120,17 @@ impl FlightService for DatabendQueryFlightService {
 
     type DoPutStream = FlightStream<PutResult>;
 
-    async fn do_put(&self, _: StreamReq<FlightData>) -> Response<Self::DoPutStream> {
-        Result::Err(Status::unimplemented(
-            "DatabendQuery does not implement do_put.",
+    async fn do_put(&self, request: StreamReq<FlightData>) -> Response<Self::DoPutStream> {
+        let metadata = request.metadata().clone();
+        let sessions = self.sessions.clone();
+        let rows = do_put_table(sessions, metadata, request.into_inner()).await?;
+
+        let app_metadata = serde_json::to_vec(&serde_json::json!({ "written_rows": rows }))
+            .map_err_to_code(ErrorCode::LogicalError, || {
+                "Logical error: cannot serialize put result."
+            })?;
+        Ok(RawResponse::new(
+            Box::pin(tokio_stream::once(Ok(PutResult { app_metadata }))) as FlightStream<PutResult>,
         ))
     }
 
@@ -130,20 +144,47 @@ impl FlightService for DatabendQueryFlightService {
 
     type DoActionStream = FlightStream<FlightResult>;
 
+    #[tracing::instrument(level = "info", skip(self, request))]
     async fn do_action(&self, request: Request<Action>) -> Response<Self::DoActionStream> {
+        common_tracing::extract_remote_span_as_parent(&request);
         let action = request.into_inner();
         let flight_action: FlightAction = action.try_into()?;
 
         let action_result = match &flight_action {
             FlightAction::CancelAction(action) => {
-                // We only destroy when session is exist
-                let session_id = action.query_id.clone();
-                if let Some(session) = self.sessions.get_session(&session_id) {
-                    // TODO: remove streams
-                    session.force_kill_session();
+                // TODO: remove streams
+                let killed = self.sessions.kill_query(&action.tenant, &action.query_id);
+                let body = serde_json::to_vec(&killed)
+                    .map_err_to_code(ErrorCode::LogicalError, || {
+                        "Logical error: cannot serialize kill result."
+                    })?;
+                FlightResult { body }
+            }
+            FlightAction::FetchQueryPageAction(action) => {
+                let query_pages = self.sessions.get_query_pages();
+                let owner = (action.tenant.clone(), action.user.clone());
+                let page = query_pages.get_page(&action.query_id, &owner, action.page)?;
+                let body = serde_json::to_vec(&page)
+                    .map_err_to_code(ErrorCode::LogicalError, || {
+                        "Logical error: cannot serialize QueryPage."
+                    })?;
+                FlightResult { body }
+            }
+            FlightAction::ListProcessesAction(_) => {
+                let processes_info = self.sessions.node_processes_info();
+                let body = serde_json::to_vec(&processes_info)
+                    .map_err_to_code(ErrorCode::LogicalError, || {
+                        "Logical error: cannot serialize processes info."
+                    })?;
+                FlightResult { body }
+            }
+            FlightAction::ListMetricsAction(_) => {
+                let prometheus_handle = common_metrics::try_handle().ok_or_else(|| {
+                    ErrorCode::InitPrometheusFailure("Prometheus recorder is not initialized yet.")
+                })?;
+                FlightResult {
+                    body: prometheus_handle.render().into_bytes(),
                 }
-
-                FlightResult { body: vec![] }
             }
             FlightAction::BroadcastAction(action) => {
                 let session_id = action.query_id.clone();
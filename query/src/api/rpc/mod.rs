@@ -24,9 +24,15 @@ mod flight_actions_test;
 #[cfg(test)]
 mod flight_tickets_test;
 
+#[cfg(test)]
+mod flight_put_test;
+
 pub use flight_actions::BroadcastAction;
 pub use flight_actions::CancelAction;
+pub use flight_actions::FetchQueryPageAction;
 pub use flight_actions::FlightAction;
+pub use flight_actions::ListMetricsAction;
+pub use flight_actions::ListProcessesAction;
 pub use flight_actions::ShuffleAction;
 pub use flight_client::FlightClient;
 pub use flight_dispatcher::DatabendQueryFlightDispatcher;
@@ -37,6 +43,7 @@ mod flight_actions;
 mod flight_client;
 mod flight_client_stream;
 mod flight_dispatcher;
+mod flight_put;
 mod flight_scatter;
 mod flight_scatter_broadcast;
 mod flight_scatter_hash;
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
+use common_base::hangup_signal_stream;
 use common_base::tokio;
 use common_metrics::init_default_metrics_recorder;
 use common_tracing::init_tracing_with_file;
@@ -25,43 +28,47 @@ use databend_query::servers::MySQLHandler;
 use databend_query::servers::Server;
 use databend_query::servers::ShutdownHandle;
 use databend_query::sessions::SessionManager;
+use futures::StreamExt;
 use log::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // First load configs from args.
-    let mut conf = Config::load_from_args();
-
-    // If config file is not empty: -c xx.toml
-    // Reload configs from the file.
-    if !conf.config_file.is_empty() {
-        info!("Config reload from {:?}", conf.config_file);
-        conf = Config::load_from_toml(conf.config_file.as_str())?;
+    // The defaults, overridden by the config file of `-c xx.toml`, the args and the env variables.
+    // Prefer to use env variable in cloud native deployment.
+    let (conf, sources) = Config::load()?;
+    if conf.print_config {
+        print!("{}", sources.render(&conf)?);
+        return Ok(());
     }
 
-    // Prefer to use env variable in cloud native deployment
-    // Override configs based on env variables
-    conf = Config::load_from_env(&conf)?;
-
-    env_logger::Builder::from_env(
-        env_logger::Env::default().default_filter_or(conf.log.log_level.to_lowercase().as_str()),
-    )
-    .init();
+    // Filter with the max level only, so that the log level can be reloaded at runtime.
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("trace")).init();
+    if let Ok(level) = conf.log.log_level.parse() {
+        log::set_max_level(level);
+    }
     let _guards = init_tracing_with_file(
         "databend-query",
         conf.log.log_dir.as_str(),
         conf.log.log_level.as_str(),
+        conf.log.tracing_otlp_endpoint.as_str(),
     );
 
     init_default_metrics_recorder();
 
     set_panic_hook();
-    info!("{:?}", conf);
+    info!("Config:\n{}", sources.render(&conf)?);
     info!(
         "DatabendQuery v-{}",
         *databend_query::configs::DATABEND_COMMIT_VERSION,
     );
 
+    if conf.meta.meta_embedded {
+        info!(
+            "Meta store is embedded in this process, data dir: {}",
+            conf.meta.meta_embedded_dir
+        );
+    }
+
     let session_manager = SessionManager::from_conf(conf.clone()).await?;
     let mut shutdown_handle = ShutdownHandle::create(session_manager.clone());
 
@@ -133,8 +140,142 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("Databend query has been registered to metastore.");
     }
 
+    // Persist the query log of this node, every node appends its own queries.
+    if !conf.query.query_log_table.is_empty() {
+        let sessions = session_manager.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                if let Err(cause) = sessions.flush_query_log().await {
+                    log::error!("Cannot persist the query log, cause {}", cause);
+                }
+            }
+        });
+    }
+
+    // The background jobs below run on the leader only, elected among the nodes of the tenant,
+    // so do the workers of the pipes.
+
+    // Apply the storage policies of the tables periodically.
+    if conf.storage.storage_policy_interval_secs > 0 {
+        let interval = Duration::from_secs(conf.storage.storage_policy_interval_secs);
+        let sessions = session_manager.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if !sessions.get_cluster_discovery().is_leader().await {
+                    continue;
+                }
+                match sessions.apply_storage_policies().await {
+                    Ok(0) => {}
+                    Ok(moved) => info!("Moved {} segments to the cold storage", moved),
+                    Err(cause) => log::error!("Cannot apply storage policies, cause {}", cause),
+                }
+            }
+        });
+    }
+
+    // Remove the data of the dropped tables and of the abandoned commits in the background.
+    if conf.storage.purge_interval_secs > 0 {
+        let interval = Duration::from_secs(conf.storage.purge_interval_secs);
+        let sessions = session_manager.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if !sessions.get_cluster_discovery().is_leader().await {
+                    continue;
+                }
+                match sessions.purge_dropped_tables().await {
+                    Ok(0) => {}
+                    Ok(removed) => info!("Removed {} objects of the dropped tables", removed),
+                    Err(cause) => log::error!("Cannot purge dropped tables, cause {}", cause),
+                }
+                match sessions.gc_staged_segments().await {
+                    Ok(0) => {}
+                    Ok(removed) => info!("Removed {} objects of the abandoned commits", removed),
+                    Err(cause) => log::error!("Cannot clean up staged segments, cause {}", cause),
+                }
+            }
+        });
+    }
+
+    // Analyze again the tables whose statistics are stale.
+    if conf.query.statistics_refresh_interval_secs > 0 {
+        let interval = Duration::from_secs(conf.query.statistics_refresh_interval_secs);
+        let sessions = session_manager.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if !sessions.get_cluster_discovery().is_leader().await {
+                    continue;
+                }
+                match sessions.refresh_table_statistics().await {
+                    Ok(0) => {}
+                    Ok(refreshed) => info!("Refreshed the statistics of {} tables", refreshed),
+                    Err(cause) => log::error!("Cannot refresh table statistics, cause {}", cause),
+                }
+            }
+        });
+    }
+
+    {
+        let sessions = session_manager.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                let timeout = sessions.get_conf().query.idle_session_timeout_secs;
+                if timeout > 0 {
+                    let closed = sessions.destroy_expired_sessions(Duration::from_secs(timeout));
+                    if closed > 0 {
+                        info!("Closed {} idle sessions", closed);
+                    }
+                }
+            }
+        });
+    }
+
+    // Run the workers of the pipes on the leader, they follow the changes of the leadership.
+    {
+        let sessions = session_manager.clone();
+        tokio::spawn(async move {
+            let pipe_manager = sessions.get_pipe_manager();
+            loop {
+                let is_leader = sessions.get_cluster_discovery().is_leader().await;
+                if is_leader && !pipe_manager.is_running() {
+                    if let Err(cause) = pipe_manager.start_pipes(sessions.clone()) {
+                        log::error!("Cannot start pipes, cause {}", cause);
+                    }
+                } else if !is_leader && pipe_manager.is_running() {
+                    pipe_manager.stop_pipes();
+                }
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        });
+    }
+
+    // Reload config on SIGHUP.
+    {
+        let mut hangup = hangup_signal_stream()?;
+        let sessions = session_manager.clone();
+        tokio::spawn(async move {
+            while hangup.next().await.is_some() {
+                match sessions.reload_config_from_file() {
+                    Ok(report) => info!(
+                        "Config reloaded, applied: {:?}, requires restart: {:?}",
+                        report.applied, report.requires_restart
+                    ),
+                    Err(cause) => log::error!("Cannot reload config, cause {}", cause),
+                }
+            }
+        });
+    }
+
     log::info!("Ready for connections.");
     shutdown_handle.wait_for_termination_request().await;
+    session_manager.get_pipe_manager().stop_pipes();
+    if let Err(cause) = session_manager.flush_query_log().await {
+        log::error!("Cannot persist the query log, cause {}", cause);
+    }
     // TODO: destroy cluster
     log::info!("Shutdown server.");
     Ok(())
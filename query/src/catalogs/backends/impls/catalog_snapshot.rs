@@ -0,0 +1,71 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::Result;
+use common_meta_api::MetaApi;
+use common_meta_types::DatabaseInfo;
+use common_meta_types::MetaId;
+use common_meta_types::TableInfo;
+
+/// The databases and the tables of the meta store, as of some time.
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+pub struct CatalogSnapshot {
+    pub databases: BTreeMap<String, DatabaseInfo>,
+    /// Tables by the database name and the table name.
+    pub tables: BTreeMap<String, BTreeMap<String, TableInfo>>,
+}
+
+impl CatalogSnapshot {
+    pub fn put_table(&mut self, table: &TableInfo) {
+        self.remove_table_by_id(table.table_id);
+        self.tables
+            .entry(table.db.clone())
+            .or_default()
+            .insert(table.name.clone(), table.clone());
+    }
+
+    pub fn table_by_id(&self, table_id: MetaId) -> Option<&TableInfo> {
+        self.tables
+            .values()
+            .flat_map(|tables| tables.values())
+            .find(|table| table.table_id == table_id)
+    }
+
+    pub fn remove_table_by_id(&mut self, table_id: MetaId) {
+        for tables in self.tables.values_mut() {
+            tables.retain(|_, table| table.table_id != table_id);
+        }
+    }
+
+    /// Lists all the databases and the tables.
+    pub async fn fetch(inner: &dyn MetaApi) -> Result<CatalogSnapshot> {
+        let mut snapshot = CatalogSnapshot::default();
+        for db in inner.get_databases().await? {
+            let tables = inner.get_tables(&db.db).await?;
+            snapshot.tables.insert(
+                db.db.clone(),
+                tables
+                    .iter()
+                    .map(|table| (table.name.clone(), table.as_ref().clone()))
+                    .collect(),
+            );
+            snapshot
+                .databases
+                .insert(db.db.clone(), db.as_ref().clone());
+        }
+        Ok(snapshot)
+    }
+}
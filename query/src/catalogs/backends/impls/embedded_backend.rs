@@ -16,6 +16,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_infallible::RwLock;
 use common_meta_types::CreateDatabaseReply;
@@ -24,6 +25,7 @@ use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -103,12 +105,23 @@ impl MetaApiSync for MetaEmbeddedSync {
             // TODO(xp): just let it pass. This file will be removed as soon as common/kv provides full meta-APIs.
             database_id: 0,
             db: db_name.to_string(),
+            options: plan.options.clone(),
         };
 
-        db.insert(
-            plan.db,
-            (Arc::new(database_info), InMemoryTableInfo::create()),
-        );
+        let mut metas = InMemoryTableInfo::create();
+        for table in plan.tables {
+            metas.insert(TableInfo {
+                database_id: 0,
+                db: table.db,
+                table_id: self.next_db_id(),
+                version: 0,
+                name: table.table,
+                schema: table.schema,
+                options: table.options,
+                engine: table.engine,
+            });
+        }
+        db.insert(plan.db, (Arc::new(database_info), metas));
 
         // TODO(xp): just let it pass. This file will be removed as soon as common/kv provides full meta-APIs.
         Ok(CreateDatabaseReply { database_id: 0 })
@@ -355,6 +368,41 @@ impl MetaApiSync for MetaEmbeddedSync {
         )))
     }
 
+    fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> common_exception::Result<UpdateTableSchemaReply> {
+        let mut map = self.databases.write();
+        for (_, tbl_idx) in map.values_mut() {
+            match tbl_idx.id2meta.get(&table_id) {
+                None => {
+                    continue;
+                }
+                Some(tbl) => {
+                    if tbl.version == table_version {
+                        let mut new_tbl_info = tbl.as_ref().clone();
+                        new_tbl_info.schema = schema;
+                        new_tbl_info.version += 1;
+                        tbl_idx.insert(new_tbl_info);
+                        return Ok(());
+                    } else {
+                        return Err(ErrorCode::CommitTableError(format!(
+                            "expecting table version: [{}], but got [{}]. (table_id {})",
+                            tbl.version, table_version, table_id
+                        )));
+                    }
+                }
+            }
+        }
+
+        Err(ErrorCode::UnknownTable(format!(
+            "Unknown table of id: {}",
+            table_id
+        )))
+    }
+
     fn name(&self) -> String {
         "embedded metastore backend".to_owned()
     }
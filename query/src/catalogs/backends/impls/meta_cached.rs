@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_base::tokio::sync::RwLock;
 use common_cache::Cache;
 use common_cache::LruCache;
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_meta_api::MetaApi;
 use common_meta_types::CreateDatabaseReply;
@@ -25,6 +26,7 @@ use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -116,6 +118,17 @@ impl MetaApi for MetaCached {
             .await
     }
 
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        self.inner
+            .update_table_schema(table_id, table_version, schema)
+            .await
+    }
+
     fn name(&self) -> String {
         format!("meta-cached({})", self.inner.name())
     }
@@ -0,0 +1,381 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::Runtime;
+use common_base::TrySpawn;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_infallible::RwLock;
+use common_meta_api::MetaApi;
+use common_meta_types::CreateDatabaseReply;
+use common_meta_types::CreateTableReply;
+use common_meta_types::DatabaseInfo;
+use common_meta_types::MetaId;
+use common_meta_types::MetaVersion;
+use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
+use common_meta_types::UpsertTableOptionReply;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+use common_planners::DropDatabasePlan;
+use common_planners::DropTablePlan;
+use metrics::counter;
+use metrics::gauge;
+
+use crate::catalogs::backends::impls::catalog_snapshot::CatalogSnapshot;
+
+const META_DISK_CACHE_FILE: &str = "catalog.json";
+const META_DISK_CACHE_SYNC_RETRY: Duration = Duration::from_secs(5);
+const META_DISK_CACHE_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+const METRIC_META_DISK_CACHE_STALE: &str = "meta.disk_cache.stale";
+const METRIC_META_DISK_CACHE_STALE_READS: &str = "meta.disk_cache.stale_reads";
+
+struct DiskCacheState {
+    snapshot: CatalogSnapshot,
+    /// Whether the snapshot is synced with the meta service since started.
+    synced: bool,
+    /// Whether the reads are served from the snapshot loaded from disk, until it is synced
+    /// or anything is written through this node.
+    serve_stale: bool,
+    /// Whether the snapshot is changed since it is written to disk.
+    dirty: bool,
+}
+
+/// A `MetaApi` impl which keeps the databases and the tables on the local disk, backed with
+/// another `MetaApi`.
+///
+/// Once restarted, the reads are served from the disk, possibly stale, until the catalog is
+/// synced with the meta service in the background or a write goes through this node, so that
+/// the node does not wait for the meta of all its tables before serving. The
+/// `meta.disk_cache.stale` gauge is 1 meanwhile.
+/// After that, the reads go to the inner `MetaApi`, and their replies are written to disk
+/// periodically for the next restart. The writes always go to the inner `MetaApi`.
+pub struct MetaDiskCached {
+    _rt: Runtime,
+    path: PathBuf,
+    state: Arc<RwLock<DiskCacheState>>,
+    pub inner: Arc<dyn MetaApi>,
+}
+
+impl MetaDiskCached {
+    pub fn create(inner: Arc<dyn MetaApi>, dir: &str) -> Result<MetaDiskCached> {
+        std::fs::create_dir_all(dir)?;
+        let path = Path::new(dir).join(META_DISK_CACHE_FILE);
+        let snapshot = match Self::load(&path) {
+            Ok(snapshot) => snapshot,
+            Err(cause) => {
+                log::warn!("Cannot load the meta disk cache, cause {}", cause);
+                CatalogSnapshot::default()
+            }
+        };
+        let serve_stale = !snapshot.databases.is_empty();
+        if serve_stale {
+            log::info!(
+                "Serve the meta of {} databases from the disk cache until synced",
+                snapshot.databases.len()
+            );
+            gauge!(METRIC_META_DISK_CACHE_STALE, 1.0);
+        }
+
+        let state = Arc::new(RwLock::new(DiskCacheState {
+            snapshot,
+            synced: false,
+            serve_stale,
+            dirty: false,
+        }));
+
+        let rt = Runtime::with_worker_threads(1)?;
+        rt.spawn(Self::sync_and_flush(
+            inner.clone(),
+            state.clone(),
+            path.clone(),
+        ));
+        Ok(MetaDiskCached {
+            _rt: rt,
+            path,
+            state,
+            inner,
+        })
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.state.read().synced
+    }
+
+    /// Writes the snapshot to disk if it is changed.
+    pub fn flush(&self) -> Result<()> {
+        Self::flush_state(&self.state, &self.path)
+    }
+
+    fn load(path: &Path) -> Result<CatalogSnapshot> {
+        match path.exists() {
+            true => Ok(serde_json::from_slice(&std::fs::read(path)?)?),
+            false => Ok(CatalogSnapshot::default()),
+        }
+    }
+
+    fn flush_state(state: &RwLock<DiskCacheState>, path: &Path) -> Result<()> {
+        let bytes = {
+            let mut state = state.write();
+            if !state.dirty {
+                return Ok(());
+            }
+            state.dirty = false;
+            serde_json::to_vec(&state.snapshot)?
+        };
+
+        // Never leave a half written file behind.
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, bytes)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    async fn sync_and_flush(
+        inner: Arc<dyn MetaApi>,
+        state: Arc<RwLock<DiskCacheState>>,
+        path: PathBuf,
+    ) {
+        loop {
+            match CatalogSnapshot::fetch(inner.as_ref()).await {
+                Ok(snapshot) => {
+                    let mut state = state.write();
+                    state.snapshot = snapshot;
+                    state.synced = true;
+                    state.serve_stale = false;
+                    state.dirty = true;
+                    gauge!(METRIC_META_DISK_CACHE_STALE, 0.0);
+                    break;
+                }
+                Err(cause) => {
+                    log::warn!("Cannot sync the meta disk cache, cause {}", cause);
+                    tokio::time::sleep(META_DISK_CACHE_SYNC_RETRY).await;
+                }
+            }
+        }
+
+        loop {
+            if let Err(cause) = Self::flush_state(&state, &path) {
+                log::warn!("Cannot write the meta disk cache, cause {}", cause);
+            }
+            tokio::time::sleep(META_DISK_CACHE_FLUSH_INTERVAL).await;
+        }
+    }
+
+    /// Reads from the snapshot loaded from disk if it is still served.
+    fn read_stale<T>(&self, read: impl FnOnce(&CatalogSnapshot) -> Option<T>) -> Option<T> {
+        let state = self.state.read();
+        if !state.serve_stale {
+            return None;
+        }
+
+        let found = read(&state.snapshot);
+        if found.is_some() {
+            counter!(METRIC_META_DISK_CACHE_STALE_READS, 1);
+        }
+        found
+    }
+
+    fn update(&self, update: impl FnOnce(&mut CatalogSnapshot)) {
+        let mut state = self.state.write();
+        update(&mut state.snapshot);
+        state.dirty = true;
+    }
+
+    /// A write makes the snapshot loaded from disk outdated, the reads go to the meta service
+    /// from now on.
+    fn update_written(&self, update: impl FnOnce(&mut CatalogSnapshot)) {
+        let mut state = self.state.write();
+        update(&mut state.snapshot);
+        state.dirty = true;
+        if state.serve_stale {
+            state.serve_stale = false;
+            gauge!(METRIC_META_DISK_CACHE_STALE, 0.0);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl MetaApi for MetaDiskCached {
+    async fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
+        let reply = self.inner.create_database(plan).await?;
+        self.update_written(|_| {});
+        Ok(reply)
+    }
+
+    async fn drop_database(&self, plan: DropDatabasePlan) -> Result<()> {
+        let db = plan.db.clone();
+        self.inner.drop_database(plan).await?;
+        self.update_written(|snapshot| {
+            snapshot.databases.remove(&db);
+            snapshot.tables.remove(&db);
+        });
+        Ok(())
+    }
+
+    async fn get_database(&self, db_name: &str) -> Result<Arc<DatabaseInfo>> {
+        if let Some(db) = self.read_stale(|snapshot| snapshot.databases.get(db_name).cloned()) {
+            return Ok(Arc::new(db));
+        }
+
+        let reply = self.inner.get_database(db_name).await?;
+        self.update(|snapshot| {
+            snapshot
+                .databases
+                .insert(reply.db.clone(), reply.as_ref().clone());
+        });
+        Ok(reply)
+    }
+
+    async fn get_databases(&self) -> Result<Vec<Arc<DatabaseInfo>>> {
+        let stale = self
+            .read_stale(|snapshot| Some(snapshot.databases.values().cloned().collect::<Vec<_>>()));
+        if let Some(dbs) = stale {
+            return Ok(dbs.into_iter().map(Arc::new).collect());
+        }
+
+        let reply = self.inner.get_databases().await?;
+        self.update(|snapshot| {
+            snapshot.databases = reply
+                .iter()
+                .map(|db| (db.db.clone(), db.as_ref().clone()))
+                .collect();
+            let databases = &snapshot.databases;
+            snapshot.tables.retain(|db, _| databases.contains_key(db));
+        });
+        Ok(reply)
+    }
+
+    async fn create_table(&self, plan: CreateTablePlan) -> Result<CreateTableReply> {
+        let db = plan.db.clone();
+        let reply = self.inner.create_table(plan).await?;
+        // The tables of the database are listed again.
+        self.update_written(|snapshot| {
+            snapshot.tables.remove(&db);
+        });
+        Ok(reply)
+    }
+
+    async fn drop_table(&self, plan: DropTablePlan) -> Result<()> {
+        let (db, table) = (plan.db.clone(), plan.table.clone());
+        self.inner.drop_table(plan).await?;
+        self.update_written(|snapshot| {
+            if let Some(tables) = snapshot.tables.get_mut(&db) {
+                tables.remove(&table);
+            }
+        });
+        Ok(())
+    }
+
+    async fn get_table(&self, db_name: &str, table_name: &str) -> Result<Arc<TableInfo>> {
+        let stale = self.read_stale(|snapshot| {
+            snapshot
+                .tables
+                .get(db_name)
+                .and_then(|tables| tables.get(table_name))
+                .cloned()
+        });
+        if let Some(table) = stale {
+            return Ok(Arc::new(table));
+        }
+
+        let reply = self.inner.get_table(db_name, table_name).await?;
+        self.update(|snapshot| snapshot.put_table(&reply));
+        Ok(reply)
+    }
+
+    async fn get_tables(&self, db_name: &str) -> Result<Vec<Arc<TableInfo>>> {
+        let stale = self.read_stale(|snapshot| {
+            snapshot
+                .tables
+                .get(db_name)
+                .map(|tables| tables.values().cloned().collect::<Vec<_>>())
+        });
+        if let Some(tables) = stale {
+            return Ok(tables.into_iter().map(Arc::new).collect());
+        }
+
+        let reply = self.inner.get_tables(db_name).await?;
+        self.update(|snapshot| {
+            let tables = reply
+                .iter()
+                .map(|table| (table.name.clone(), table.as_ref().clone()))
+                .collect();
+            snapshot.tables.insert(db_name.to_string(), tables);
+        });
+        Ok(reply)
+    }
+
+    async fn get_table_by_id(
+        &self,
+        table_id: MetaId,
+        version: Option<MetaVersion>,
+    ) -> Result<Arc<TableInfo>> {
+        let stale = self.read_stale(|snapshot| {
+            snapshot
+                .table_by_id(table_id)
+                .filter(|table| version.is_none() || version == Some(table.version))
+                .cloned()
+        });
+        if let Some(table) = stale {
+            return Ok(Arc::new(table));
+        }
+
+        let reply = self.inner.get_table_by_id(table_id, version).await?;
+        if version.is_none() {
+            self.update(|snapshot| snapshot.put_table(&reply));
+        }
+        Ok(reply)
+    }
+
+    async fn upsert_table_option(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        option_key: String,
+        option_value: String,
+    ) -> Result<UpsertTableOptionReply> {
+        let reply = self
+            .inner
+            .upsert_table_option(table_id, table_version, option_key, option_value)
+            .await?;
+        self.update_written(|snapshot| snapshot.remove_table_by_id(table_id));
+        Ok(reply)
+    }
+
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        let reply = self
+            .inner
+            .update_table_schema(table_id, table_version, schema)
+            .await?;
+        self.update_written(|snapshot| snapshot.remove_table_by_id(table_id));
+        Ok(reply)
+    }
+
+    fn name(&self) -> String {
+        format!("meta-disk-cached({})", self.inner.name())
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::FaultInjector;
+use common_base::FaultRates;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_api::MetaApi;
+use common_meta_embedded::MetaEmbedded;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+
+use crate::catalogs::backends::impls::MetaDiskCached;
+use crate::common::FaultyMetaClient;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_disk_cached() -> Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let dir = tmp_dir.path().to_str().unwrap();
+
+    let meta = Arc::new(MetaEmbedded::new_temp().await?);
+    meta.create_database(CreateDatabasePlan {
+        if_not_exists: false,
+        db: "db1".to_string(),
+        options: Default::default(),
+        tables: vec![],
+    })
+    .await?;
+    meta.create_table(CreateTablePlan {
+        if_not_exists: false,
+        db: "db1".to_string(),
+        table: "t1".to_string(),
+        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]),
+        engine: "Memory".to_string(),
+        options: Default::default(),
+        temporary: false,
+        as_select: None,
+    })
+    .await?;
+    let t1 = meta.get_table("db1", "t1").await?;
+
+    // The first start syncs with the meta service and keeps the catalog on disk.
+    {
+        let cached = MetaDiskCached::create(meta.clone(), dir)?;
+        for _ in 0..100 {
+            if cached.is_synced() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(cached.is_synced());
+        cached.flush()?;
+        assert_eq!(cached.get_table("db1", "t1").await?, t1);
+    }
+
+    // Restarted while the meta service is unavailable, the reads are served from disk.
+    let injector = FaultInjector::create(FaultRates {
+        error: 1.0,
+        ..Default::default()
+    });
+    let unavailable = Arc::new(FaultyMetaClient::create(meta.clone(), injector));
+    let cached = MetaDiskCached::create(unavailable, dir)?;
+    assert!(!cached.is_synced());
+
+    let dbs = cached.get_databases().await?;
+    assert!(dbs.iter().any(|db| db.db == "db1"));
+    assert_eq!(cached.get_database("db1").await?.db, "db1");
+    assert_eq!(cached.get_table("db1", "t1").await?, t1);
+    assert_eq!(cached.get_tables("db1").await?, vec![t1.clone()]);
+    assert_eq!(cached.get_table_by_id(t1.table_id, None).await?, t1);
+    assert_eq!(
+        cached
+            .get_table_by_id(t1.table_id, Some(t1.version))
+            .await?,
+        t1
+    );
+
+    // Not in the cache, asks the meta service.
+    assert!(cached.get_table("db1", "t2").await.is_err());
+    assert!(cached
+        .get_table_by_id(t1.table_id, Some(t1.version + 1))
+        .await
+        .is_err());
+
+    // The writes are never served by the cache.
+    assert!(cached
+        .upsert_table_option(t1.table_id, t1.version, "k".to_string(), "v".to_string())
+        .await
+        .is_err());
+    assert_eq!(cached.get_table("db1", "t1").await?, t1);
+
+    assert_eq!("meta-disk-cached(faulty(meta-embedded))", cached.name());
+    Ok(())
+}
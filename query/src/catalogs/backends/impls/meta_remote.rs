@@ -16,6 +16,7 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_meta_api::MetaApi;
 use common_meta_types::CreateDatabaseReply;
@@ -24,6 +25,7 @@ use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -138,6 +140,19 @@ impl MetaApi for MetaRemote {
         .await
     }
 
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        self.query_backend(move |cli| async move {
+            cli.update_table_schema(table_id, table_version, schema)
+                .await
+        })
+        .await
+    }
+
     fn name(&self) -> String {
         "meta-remote".to_owned()
     }
@@ -0,0 +1,241 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_base::Runtime;
+use common_base::TrySpawn;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_infallible::RwLock;
+use common_meta_api::MetaApi;
+use common_meta_types::CreateDatabaseReply;
+use common_meta_types::CreateTableReply;
+use common_meta_types::DatabaseInfo;
+use common_meta_types::MetaId;
+use common_meta_types::MetaVersion;
+use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
+use common_meta_types::UpsertTableOptionReply;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+use common_planners::DropDatabasePlan;
+use common_planners::DropTablePlan;
+use metrics::counter;
+
+use crate::catalogs::backends::impls::catalog_snapshot::CatalogSnapshot;
+
+const METRIC_META_REPLICA_READS: &str = "meta.replica.reads";
+const METRIC_META_REPLICA_FALLBACKS: &str = "meta.replica.fallbacks";
+
+#[derive(Default)]
+struct ReplicaState {
+    snapshot: CatalogSnapshot,
+    /// When the fetch of the snapshot started, none until the first refresh or after anything
+    /// is written through this node.
+    refreshed_at: Option<Instant>,
+    /// The number of writes through this node, a refresh racing with a write is discarded.
+    writes: u64,
+}
+
+/// A `MetaApi` impl which serves the database and table listings from a replica of the
+/// catalog, backed with another `MetaApi`.
+///
+/// The replica is refreshed in the background twice per `max_staleness`, a listing is served
+/// from it only if it is at most `max_staleness` stale, so that SHOW TABLES and the
+/// information_schema of BI tools do not list the whole catalog from the meta service on every
+/// query. Otherwise, e.g. the meta service is unavailable, the listings go to the inner
+/// `MetaApi` and `meta.replica.fallbacks` is increased.
+/// A write through this node makes the listings go to the inner `MetaApi` until the next
+/// refresh, so that a session sees its own writes. The point reads and the writes always go to
+/// the inner `MetaApi`.
+pub struct MetaReplica {
+    _rt: Runtime,
+    max_staleness: Duration,
+    state: Arc<RwLock<ReplicaState>>,
+    pub inner: Arc<dyn MetaApi>,
+}
+
+impl MetaReplica {
+    pub fn create(inner: Arc<dyn MetaApi>, max_staleness: Duration) -> Result<MetaReplica> {
+        let state = Arc::new(RwLock::new(ReplicaState::default()));
+
+        let rt = Runtime::with_worker_threads(1)?;
+        rt.spawn(Self::refresh(
+            inner.clone(),
+            state.clone(),
+            max_staleness / 2,
+        ));
+        Ok(MetaReplica {
+            _rt: rt,
+            max_staleness,
+            state,
+            inner,
+        })
+    }
+
+    /// Whether the listings are served from the replica.
+    pub fn is_fresh(&self) -> bool {
+        Self::fresh(&self.state.read(), self.max_staleness)
+    }
+
+    fn fresh(state: &ReplicaState, max_staleness: Duration) -> bool {
+        matches!(state.refreshed_at, Some(at) if at.elapsed() <= max_staleness)
+    }
+
+    async fn refresh(
+        inner: Arc<dyn MetaApi>,
+        state: Arc<RwLock<ReplicaState>>,
+        interval: Duration,
+    ) {
+        loop {
+            let started_at = Instant::now();
+            let writes = state.read().writes;
+            match CatalogSnapshot::fetch(inner.as_ref()).await {
+                Ok(snapshot) => {
+                    let mut state = state.write();
+                    if state.writes == writes {
+                        state.snapshot = snapshot;
+                        state.refreshed_at = Some(started_at);
+                    }
+                }
+                Err(cause) => log::warn!("Cannot refresh the meta replica, cause {}", cause),
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Reads from the replica if it is fresh enough, none to ask the inner `MetaApi`.
+    fn read_fresh<T>(&self, read: impl FnOnce(&CatalogSnapshot) -> Option<T>) -> Option<T> {
+        let state = self.state.read();
+        if !Self::fresh(&state, self.max_staleness) {
+            counter!(METRIC_META_REPLICA_FALLBACKS, 1);
+            return None;
+        }
+
+        let found = read(&state.snapshot);
+        if found.is_some() {
+            counter!(METRIC_META_REPLICA_READS, 1);
+        }
+        found
+    }
+
+    fn written(&self) {
+        let mut state = self.state.write();
+        state.writes += 1;
+        state.refreshed_at = None;
+    }
+}
+
+#[async_trait::async_trait]
+impl MetaApi for MetaReplica {
+    async fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
+        let reply = self.inner.create_database(plan).await?;
+        self.written();
+        Ok(reply)
+    }
+
+    async fn drop_database(&self, plan: DropDatabasePlan) -> Result<()> {
+        self.inner.drop_database(plan).await?;
+        self.written();
+        Ok(())
+    }
+
+    async fn get_database(&self, db_name: &str) -> Result<Arc<DatabaseInfo>> {
+        self.inner.get_database(db_name).await
+    }
+
+    async fn get_databases(&self) -> Result<Vec<Arc<DatabaseInfo>>> {
+        let fresh = self
+            .read_fresh(|snapshot| Some(snapshot.databases.values().cloned().collect::<Vec<_>>()));
+        match fresh {
+            Some(dbs) => Ok(dbs.into_iter().map(Arc::new).collect()),
+            None => self.inner.get_databases().await,
+        }
+    }
+
+    async fn create_table(&self, plan: CreateTablePlan) -> Result<CreateTableReply> {
+        let reply = self.inner.create_table(plan).await?;
+        self.written();
+        Ok(reply)
+    }
+
+    async fn drop_table(&self, plan: DropTablePlan) -> Result<()> {
+        self.inner.drop_table(plan).await?;
+        self.written();
+        Ok(())
+    }
+
+    async fn get_table(&self, db_name: &str, table_name: &str) -> Result<Arc<TableInfo>> {
+        self.inner.get_table(db_name, table_name).await
+    }
+
+    async fn get_tables(&self, db_name: &str) -> Result<Vec<Arc<TableInfo>>> {
+        // An unknown database is reported by the inner `MetaApi`.
+        let fresh = self.read_fresh(|snapshot| {
+            snapshot
+                .tables
+                .get(db_name)
+                .map(|tables| tables.values().cloned().collect::<Vec<_>>())
+        });
+        match fresh {
+            Some(tables) => Ok(tables.into_iter().map(Arc::new).collect()),
+            None => self.inner.get_tables(db_name).await,
+        }
+    }
+
+    async fn get_table_by_id(
+        &self,
+        table_id: MetaId,
+        version: Option<MetaVersion>,
+    ) -> Result<Arc<TableInfo>> {
+        self.inner.get_table_by_id(table_id, version).await
+    }
+
+    async fn upsert_table_option(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        option_key: String,
+        option_value: String,
+    ) -> Result<UpsertTableOptionReply> {
+        let reply = self
+            .inner
+            .upsert_table_option(table_id, table_version, option_key, option_value)
+            .await?;
+        self.written();
+        Ok(reply)
+    }
+
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        let reply = self
+            .inner
+            .update_table_schema(table_id, table_version, schema)
+            .await?;
+        self.written();
+        Ok(reply)
+    }
+
+    fn name(&self) -> String {
+        format!("meta-replica({})", self.inner.name())
+    }
+}
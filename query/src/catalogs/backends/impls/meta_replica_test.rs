@@ -0,0 +1,119 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::FaultInjector;
+use common_base::FaultRates;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_api::MetaApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::TableInfo;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+
+use crate::catalogs::backends::impls::MetaReplica;
+use crate::common::FaultyMetaClient;
+
+fn create_table_plan(table: &str) -> CreateTablePlan {
+    CreateTablePlan {
+        if_not_exists: false,
+        db: "db1".to_string(),
+        table: table.to_string(),
+        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]),
+        engine: "Memory".to_string(),
+        options: Default::default(),
+        temporary: false,
+        as_select: None,
+    }
+}
+
+async fn wait_fresh(replica: &MetaReplica) {
+    for _ in 0..100 {
+        if replica.is_fresh() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_replica() -> Result<()> {
+    let meta = Arc::new(MetaEmbedded::new_temp().await?);
+    meta.create_database(CreateDatabasePlan {
+        if_not_exists: false,
+        db: "db1".to_string(),
+        options: Default::default(),
+        tables: vec![],
+    })
+    .await?;
+    meta.create_table(create_table_plan("t1")).await?;
+
+    // Refreshed once, the next refresh is far beyond the test.
+    let replica = MetaReplica::create(meta.clone(), Duration::from_secs(600))?;
+    wait_fresh(&replica).await;
+    assert!(replica.is_fresh());
+
+    let table_names = |tables: Vec<Arc<TableInfo>>| {
+        let mut names = tables
+            .iter()
+            .map(|table| table.name.clone())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+
+    // Written by another node, not listed until the next refresh.
+    meta.create_table(create_table_plan("t2")).await?;
+    assert_eq!(table_names(replica.get_tables("db1").await?), vec!["t1"]);
+    assert!(replica
+        .get_databases()
+        .await?
+        .iter()
+        .any(|db| db.db == "db1"));
+    // The point reads are never served by the replica.
+    assert_eq!(replica.get_table("db1", "t2").await?.name, "t2");
+    // Unknown to the replica, asks the inner one.
+    assert!(replica.get_tables("db2").await.is_err());
+
+    // Written through this node, listed at once.
+    replica.create_table(create_table_plan("t3")).await?;
+    assert!(!replica.is_fresh());
+    assert_eq!(table_names(replica.get_tables("db1").await?), vec![
+        "t1", "t2", "t3"
+    ]);
+
+    assert_eq!("meta-replica(meta-embedded)", replica.name());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_replica_never_refreshed() -> Result<()> {
+    let meta = Arc::new(MetaEmbedded::new_temp().await?);
+    let injector = FaultInjector::create(FaultRates {
+        error: 1.0,
+        ..Default::default()
+    });
+    let unavailable = Arc::new(FaultyMetaClient::create(meta, injector));
+
+    // The listings are never served from an empty replica.
+    let replica = MetaReplica::create(unavailable, Duration::from_millis(100))?;
+    wait_fresh(&replica).await;
+    assert!(!replica.is_fresh());
+    assert!(replica.get_databases().await.is_err());
+    Ok(())
+}
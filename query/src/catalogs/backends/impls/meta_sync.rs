@@ -17,6 +17,7 @@ use std::time::Duration;
 
 use common_base::BlockingWait;
 use common_base::Runtime;
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_meta_api::MetaApi;
 use common_meta_types::CreateDatabaseReply;
@@ -25,6 +26,7 @@ use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -128,6 +130,17 @@ impl MetaApiSync for MetaSync {
         .wait_in(&self.rt, self.timeout)?
     }
 
+    fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        let x = self.inner.clone();
+        (async move { x.update_table_schema(table_id, table_version, schema).await })
+            .wait_in(&self.rt, self.timeout)?
+    }
+
     fn name(&self) -> String {
         format!("meta-sync({})", self.inner.name())
     }
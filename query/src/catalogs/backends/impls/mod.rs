@@ -13,14 +13,23 @@
 //  limitations under the License.
 //
 
+mod catalog_snapshot;
 mod embedded_backend;
 mod meta_cached;
+mod meta_disk_cached;
+#[cfg(test)]
+mod meta_disk_cached_test;
 mod meta_remote;
+mod meta_replica;
+#[cfg(test)]
+mod meta_replica_test;
 mod meta_sync;
 mod remote_backend;
 
 pub use embedded_backend::MetaEmbeddedSync;
 pub use meta_cached::MetaCached;
+pub use meta_disk_cached::MetaDiskCached;
 pub use meta_remote::MetaRemote;
+pub use meta_replica::MetaReplica;
 pub use meta_sync::MetaSync;
 pub use remote_backend::MetaRemoteSync;
@@ -17,13 +17,16 @@ use std::ops::Deref;
 use std::sync::Arc;
 use std::time::Duration;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
+use common_meta_api::MetaApi;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::CreateTableReply;
 use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -31,7 +34,9 @@ use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
 
 use crate::catalogs::backends::impls::MetaCached;
+use crate::catalogs::backends::impls::MetaDiskCached;
 use crate::catalogs::backends::impls::MetaRemote;
+use crate::catalogs::backends::impls::MetaReplica;
 use crate::catalogs::backends::impls::MetaSync;
 use crate::catalogs::backends::MetaApiSync;
 use crate::common::MetaClientProvider;
@@ -40,26 +45,39 @@ use crate::common::MetaClientProvider;
 ///
 /// The component hierarchy is layered as:
 /// ```text
-///                                        RPC
-/// MetaSync -> MetaCached -> MetaRemote -------> Meta server      Meta server
-///                                               raft <---------> raft <----..
-///                                               MetaEmbedded     MetaEmbedded
+///                                                                            RPC
+/// MetaSync -> [MetaReplica] -> [MetaDiskCached] -> MetaCached -> MetaRemote -------> Meta server
+///                                                                                   raft <------..
+///                                                                                   MetaEmbedded
 /// ```
+/// `MetaReplica` is only there if a replica staleness is configured, `MetaDiskCached` is only
+/// there if a disk cache dir is configured.
 #[derive(Clone)]
 pub struct MetaRemoteSync {
     pub meta_sync: MetaSync,
 }
 
 impl MetaRemoteSync {
-    pub fn create(apis_provider: Arc<MetaClientProvider>) -> MetaRemoteSync {
+    pub fn create(
+        apis_provider: Arc<MetaClientProvider>,
+        disk_cache_dir: &str,
+        replica_staleness_ms: u64,
+    ) -> Result<MetaRemoteSync> {
         // TODO(xp): config rpc timeout
         // TODO(xp): config blocking timeout
 
         let meta_remote = MetaRemote::create(apis_provider);
-        let meta_cached = MetaCached::create(Arc::new(meta_remote));
-        let meta_sync = MetaSync::create(Arc::new(meta_cached), Some(Duration::from_millis(5000)));
-
-        MetaRemoteSync { meta_sync }
+        let mut meta_cached: Arc<dyn MetaApi> = Arc::new(MetaCached::create(Arc::new(meta_remote)));
+        if !disk_cache_dir.is_empty() {
+            meta_cached = Arc::new(MetaDiskCached::create(meta_cached, disk_cache_dir)?);
+        }
+        if replica_staleness_ms > 0 {
+            let max_staleness = Duration::from_millis(replica_staleness_ms);
+            meta_cached = Arc::new(MetaReplica::create(meta_cached, max_staleness)?);
+        }
+        let meta_sync = MetaSync::create(meta_cached, Some(Duration::from_millis(5000)));
+
+        Ok(MetaRemoteSync { meta_sync })
     }
 }
 
@@ -128,6 +146,16 @@ impl<T: MetaApiSync, U: Deref<Target = T> + Send + Sync> MetaApiSync for U {
         )
     }
 
+    fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        self.deref()
+            .update_table_schema(table_id, table_version, schema)
+    }
+
     fn name(&self) -> String {
         self.deref().name()
     }
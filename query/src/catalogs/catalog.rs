@@ -14,10 +14,12 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -28,6 +30,7 @@ use crate::catalogs::Database;
 use crate::catalogs::Table;
 use crate::catalogs::TableFunction;
 use crate::datasources::table_func_engine::TableArgs;
+use crate::datasources::table_options::TableOptionDef;
 
 /// Catalog is the global view of all the databases of the user.
 /// The global view has many engine type: Local-Database(engine=Local), Remote-Database(engine=Remote)
@@ -56,6 +59,11 @@ pub trait Catalog {
 
     fn drop_table(&self, plan: DropTablePlan) -> Result<()>;
 
+    /// The options the tables of the engine may have, empty if the engine is unknown.
+    fn get_table_options(&self, _engine: &str) -> Vec<TableOptionDef> {
+        vec![]
+    }
+
     // Get function by name.
     fn get_table_function(
         &self,
@@ -73,6 +81,13 @@ pub trait Catalog {
         table_option_value: String,
     ) -> common_exception::Result<UpsertTableOptionReply>;
 
+    fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply>;
+
     // Operation with database.
     fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply>;
 
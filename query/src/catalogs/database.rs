@@ -12,7 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
+/// The option of a database created by a tenant other than the one of `--tenant`.
+pub const DB_OPT_KEY_TENANT: &str = "tenant";
+
 pub trait Database: Sync + Send {
     /// Database name.
     fn name(&self) -> &str;
+
+    /// The tenant owning the database, None if it is shared by all the tenants.
+    fn tenant(&self) -> Option<&str> {
+        None
+    }
+
+    /// The options given by `CREATE DATABASE`, along with the ones maintained by the server.
+    fn options(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
 }
@@ -18,6 +18,7 @@ use std::sync::Arc;
 
 use common_context::TableDataContext;
 use common_dal::InMemoryData;
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
@@ -26,6 +27,7 @@ use common_meta_types::DatabaseInfo;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -38,11 +40,17 @@ use crate::catalogs::backends::MetaRemoteSync;
 use crate::catalogs::catalog::Catalog;
 use crate::catalogs::Database;
 use crate::catalogs::Table;
+use crate::catalogs::DB_OPT_KEY_TENANT;
 use crate::common::MetaClientProvider;
 use crate::configs::Config;
+use crate::configs::StorageConfig;
+use crate::datasources::common::inherit_storage_options;
+use crate::datasources::common::storage_config_with_options;
 use crate::datasources::database::default::default_database::DefaultDatabase;
 use crate::datasources::table::register_prelude_tbl_engines;
 use crate::datasources::table_engine_registry::TableEngineRegistry;
+use crate::datasources::table_options::check_table_options;
+use crate::datasources::table_options::TableOptionDef;
 
 /// Catalog based on MetaStore
 /// - System Database NOT included
@@ -67,22 +75,38 @@ pub struct MetaStoreCatalog {
     //
     // if we drop Database Trait, and create tables by using catalog directly, things may be easier
     db_instances: RwLock<HashMap<String, Arc<dyn Database>>>,
+
+    /// The tenant of `--tenant`, owning the databases without the tenant option.
+    default_tenant: String,
 }
 
 impl MetaStoreCatalog {
     pub fn try_create_with_config(conf: Config) -> Result<Self> {
+        let embedded_mode = conf.meta.meta_embedded;
+        if embedded_mode && !conf.meta.meta_address.is_empty() {
+            return Err(ErrorCode::InvalidConfig(
+                "--meta-embedded and --meta-address can not be both set",
+            ));
+        }
+
         let local_mode = conf.meta.meta_address.is_empty();
-        let meta: Arc<dyn MetaApiSync> = if local_mode {
+        let meta: Arc<dyn MetaApiSync> = if local_mode && !embedded_mode {
             Arc::new(MetaEmbeddedSync::create())
         } else {
-            let store_client_provider = Arc::new(MetaClientProvider::new(&conf));
-            Arc::new(MetaRemoteSync::create(store_client_provider))
+            // The embedded meta store is accessed in the same way as metasrv.
+            let store_client_provider = Arc::new(MetaClientProvider::from(&conf));
+            Arc::new(MetaRemoteSync::create(
+                store_client_provider,
+                &conf.meta.meta_cache_dir,
+                conf.meta.meta_replica_staleness_ms,
+            )?)
         };
 
         let plan = CreateDatabasePlan {
             if_not_exists: true,
             db: "default".to_string(),
             options: Default::default(),
+            tables: vec![],
         };
         meta.create_database(plan)?;
 
@@ -95,13 +119,19 @@ impl MetaStoreCatalog {
             meta,
             in_memory_data: Default::default(),
             db_instances: RwLock::new(HashMap::new()),
+            default_tenant: conf.query.tenant.clone(),
         };
 
         Ok(cat)
     }
 
     fn build_db_instance(&self, db_info: &Arc<DatabaseInfo>) -> Result<Arc<dyn Database>> {
-        let db = DefaultDatabase::new(&db_info.db);
+        // The databases created before the tenants are told apart are of the default tenant.
+        let tenant = db_info
+            .options
+            .get(DB_OPT_KEY_TENANT)
+            .unwrap_or(&self.default_tenant);
+        let db = DefaultDatabase::new(&db_info.db, tenant, db_info.options.clone());
 
         let db = Arc::new(db);
 
@@ -146,11 +176,8 @@ impl Catalog for MetaStoreCatalog {
     }
 
     fn get_database(&self, db_name: &str) -> Result<Arc<dyn Database>> {
-        {
-            if let Some(db) = self.db_instances.read().get(db_name) {
-                return Ok(db.clone());
-            }
-        }
+        // Never served by `db_instances`, the database may be dropped and created again by
+        // another tenant through another node.
         let db_info = self.meta.get_database(db_name)?;
         self.build_db_instance(&db_info)
     }
@@ -194,8 +221,27 @@ impl Catalog for MetaStoreCatalog {
         )
     }
 
+    fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        self.meta
+            .update_table_schema(table_id, table_version, schema)
+    }
+
     fn create_table(&self, plan: CreateTablePlan) -> common_exception::Result<()> {
-        // TODO validate table parameters by using TableFactory
+        // The storage options inherited from the database are not checked, they are ignored by
+        // the engines without storage.
+        if let Some(defs) = self.table_engine_registry.get_table_options(&plan.engine) {
+            check_table_options(&plan.engine, &defs, &plan.options, false)?;
+        }
+        let mut plan = plan;
+        let db_info = self.meta.get_database(&plan.db)?;
+        inherit_storage_options(&db_info.options, &mut plan.options);
+        storage_config_with_options(&StorageConfig::default(), &plan.options)?;
+
         self.meta.create_table(plan)?;
         Ok(())
     }
@@ -204,7 +250,22 @@ impl Catalog for MetaStoreCatalog {
         self.meta.drop_table(plan)
     }
 
+    fn get_table_options(&self, engine: &str) -> Vec<TableOptionDef> {
+        self.table_engine_registry
+            .get_table_options(engine)
+            .unwrap_or_default()
+    }
+
     fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
+        storage_config_with_options(&StorageConfig::default(), &plan.options)?;
+        let mut plan = plan;
+        for table in plan.tables.iter_mut() {
+            if let Some(defs) = self.table_engine_registry.get_table_options(&table.engine) {
+                check_table_options(&table.engine, &defs, &table.options, false)?;
+            }
+            inherit_storage_options(&plan.options, &mut table.options);
+            storage_config_with_options(&StorageConfig::default(), &table.options)?;
+        }
         self.meta.create_database(plan)
     }
 
@@ -16,10 +16,12 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -33,6 +35,7 @@ use crate::catalogs::TableFunction;
 use crate::datasources::table_func_engine::TableArgs;
 use crate::datasources::table_func_engine::TableFuncEngine;
 use crate::datasources::table_func_engine_registry::TableFuncEngineRegistry;
+use crate::datasources::table_options::TableOptionDef;
 
 /// Combine two catalogs together
 /// - read/search like operations are always performed at
@@ -133,6 +136,10 @@ impl Catalog for OverlaidCatalog {
         self.bottom.drop_table(plan)
     }
 
+    fn get_table_options(&self, engine: &str) -> Vec<TableOptionDef> {
+        self.bottom.get_table_options(engine)
+    }
+
     fn get_table_function(
         &self,
         func_name: &str,
@@ -163,6 +170,17 @@ impl Catalog for OverlaidCatalog {
         )
     }
 
+    fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> common_exception::Result<UpdateTableSchemaReply> {
+        // update table schema in BOTTOM layer only
+        self.bottom
+            .update_table_schema(table_id, table_version, schema)
+    }
+
     fn create_database(
         &self,
         plan: CreateDatabasePlan,
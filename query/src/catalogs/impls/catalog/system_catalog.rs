@@ -15,11 +15,13 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::CreateDatabaseReply;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
+use common_meta_types::UpdateTableSchemaReply;
 use common_meta_types::UpsertTableOptionReply;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
@@ -69,9 +71,19 @@ impl SystemCatalog {
             Arc::new(system::ClustersTable::create(next_id())),
             Arc::new(system::DatabasesTable::create(next_id())),
             Arc::new(system::TracingTable::create(next_id())),
+            Arc::new(system::LogsTable::create(next_id())),
             Arc::new(system::ProcessesTable::create(next_id())),
             Arc::new(system::ConfigsTable::create(next_id())),
             Arc::new(system::MetricsTable::create(next_id())),
+            Arc::new(system::ClusterMetricsTable::create(next_id())),
+            Arc::new(system::QueryCacheTable::create(next_id())),
+            Arc::new(system::QueryLogTable::create(next_id())),
+            Arc::new(system::QueryTagUsageTable::create(next_id())),
+            Arc::new(system::BuildOptionsTable::create(next_id())),
+            Arc::new(system::ColumnStatisticsTable::create(next_id())),
+            Arc::new(system::PurgesTable::create(next_id())),
+            Arc::new(system::MallocStatsTable::create(next_id())),
+            Arc::new(system::ConnectionsTable::create(next_id())),
         ];
 
         let mut tables = InMemoryMetas::create();
@@ -148,6 +160,18 @@ impl Catalog for SystemCatalog {
         )))
     }
 
+    fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        _table_version: MetaVersion,
+        _schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        Err(ErrorCode::UnImplement(format!(
+            "alter table not allowed for system catalog {}",
+            table_id
+        )))
+    }
+
     fn create_table(&self, _plan: CreateTablePlan) -> Result<()> {
         unimplemented!("programming error: SystemCatalog does not support create table")
     }
@@ -14,6 +14,7 @@
 //
 
 pub use database_catalog::DatabaseCatalog;
+pub use temporary_tables::TemporaryTables;
 
 pub use crate::catalogs::table_id_ranges::LOCAL_TBL_ID_BEGIN;
 pub use crate::catalogs::table_id_ranges::SYS_TBL_ID_BEGIN;
@@ -22,3 +23,4 @@ pub use crate::catalogs::table_id_ranges::SYS_TBL_ID_END;
 mod catalog;
 mod database_catalog;
 pub mod in_memory_meta;
+mod temporary_tables;
@@ -0,0 +1,163 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_context::TableDataContext;
+use common_dal::InMemoryData;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+use common_meta_types::MetaId;
+use common_meta_types::TableInfo;
+use common_planners::CreateTablePlan;
+
+use crate::catalogs::Table;
+use crate::catalogs::TEMP_TBL_ID_BEGIN;
+use crate::catalogs::TEMP_TBL_ID_END;
+use crate::datasources::table::register_prelude_tbl_engines;
+use crate::datasources::table_engine_registry::TableEngineRegistry;
+use crate::datasources::table_options::check_table_options;
+
+type DatabaseAndTable = (String, String);
+
+/// Tables created by `CREATE TEMPORARY TABLE`.
+///
+/// The metadata never reaches the meta service: it is owned by the session which created
+/// the tables, and so is the data, which is kept in a session scoped `InMemoryData`.
+/// Everything is released by `clear` when the session is closed.
+pub struct TemporaryTables {
+    next_id: AtomicU64,
+    table_engine_registry: TableEngineRegistry,
+    in_memory_data: Arc<RwLock<InMemoryData<u64>>>,
+    tables: RwLock<HashMap<DatabaseAndTable, Arc<dyn Table>>>,
+}
+
+impl TemporaryTables {
+    pub fn try_create() -> Result<Self> {
+        let table_engine_registry = TableEngineRegistry::new();
+        register_prelude_tbl_engines(&table_engine_registry)?;
+
+        Ok(TemporaryTables {
+            next_id: AtomicU64::new(TEMP_TBL_ID_BEGIN),
+            table_engine_registry,
+            in_memory_data: Default::default(),
+            tables: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub fn create_table(&self, plan: CreateTablePlan) -> Result<()> {
+        let key = (plan.db.clone(), plan.table.clone());
+        if self.tables.read().contains_key(&key) {
+            return match plan.if_not_exists {
+                true => Ok(()),
+                false => Err(ErrorCode::TableAlreadyExists(format!(
+                    "Temporary table: '{}.{}' already exists.",
+                    plan.db, plan.table
+                ))),
+            };
+        }
+
+        if let Some(defs) = self.table_engine_registry.get_table_options(&plan.engine) {
+            check_table_options(&plan.engine, &defs, &plan.options, false)?;
+        }
+
+        let table_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        if table_id >= TEMP_TBL_ID_END {
+            return Err(ErrorCode::LogicalError("temporary table id used up"));
+        }
+
+        let table_info = TableInfo {
+            table_id,
+            db: plan.db.clone(),
+            name: plan.table.clone(),
+            schema: plan.schema.clone(),
+            engine: plan.engine.clone(),
+            options: plan.options.clone(),
+            ..Default::default()
+        };
+
+        let table = self.build_table_instance(table_info)?;
+        // Tables which are not local commit their data through the meta service,
+        // which knows nothing about temporary tables.
+        if !table.is_local() {
+            return Err(ErrorCode::UnImplement(format!(
+                "Temporary table does not support engine {}",
+                plan.engine
+            )));
+        }
+
+        self.tables.write().insert(key, table);
+        Ok(())
+    }
+
+    /// Returns false if there is no such temporary table.
+    pub fn drop_table(&self, db_name: &str, table_name: &str) -> bool {
+        let key = (db_name.to_string(), table_name.to_string());
+        match self.tables.write().remove(&key) {
+            None => false,
+            Some(table) => {
+                self.in_memory_data.write().remove(&table.get_id());
+                true
+            }
+        }
+    }
+
+    pub fn get_table(&self, db_name: &str, table_name: &str) -> Option<Arc<dyn Table>> {
+        let key = (db_name.to_string(), table_name.to_string());
+        self.tables.read().get(&key).cloned()
+    }
+
+    pub fn get_table_by_id(&self, table_id: MetaId) -> Option<Arc<dyn Table>> {
+        self.tables
+            .read()
+            .values()
+            .find(|table| table.get_id() == table_id)
+            .cloned()
+    }
+
+    pub fn get_tables(&self) -> Vec<Arc<dyn Table>> {
+        self.tables.read().values().cloned().collect()
+    }
+
+    /// Drop all the temporary tables along with their data.
+    pub fn clear(&self) {
+        self.tables.write().clear();
+        self.in_memory_data.write().clear();
+    }
+
+    fn build_table_instance(&self, table_info: TableInfo) -> Result<Arc<dyn Table>> {
+        let engine = &table_info.engine;
+        let factory = self
+            .table_engine_registry
+            .get_table_factory(engine)
+            .ok_or_else(|| {
+                ErrorCode::UnknownTableEngine(format!("unknown table engine {}", engine))
+            })?;
+
+        let table: Arc<dyn Table> = factory
+            .try_create(
+                table_info,
+                Arc::new(TableDataContext {
+                    in_memory_data: self.in_memory_data.clone(),
+                }),
+            )?
+            .into();
+
+        Ok(table)
+    }
+}
@@ -15,6 +15,7 @@
 
 pub use catalog::Catalog;
 pub use database::Database;
+pub use database::DB_OPT_KEY_TENANT;
 pub use impls::in_memory_meta::InMemoryMetas;
 pub use table::Table;
 pub use table::TablePtr;
@@ -16,7 +16,9 @@ use std::any::Any;
 use std::sync::Arc;
 
 use common_context::TableIOContext;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::MetaId;
@@ -74,6 +76,12 @@ pub trait Table: Sync + Send {
         None
     }
 
+    /// The virtual columns the scans of the table append to the columns of the schema, if
+    /// requested by `Extras::virtual_columns`, see `common_planners::is_virtual_column`.
+    fn virtual_columns(&self) -> Vec<DataField> {
+        vec![]
+    }
+
     // Read block data from the underling.
     async fn read(
         &self,
@@ -127,6 +135,16 @@ impl ToReadDataSourcePlan for dyn Table {
             self.read_partitions(io_ctx, push_downs.clone(), partition_num_hint)?;
         let table_info = self.get_table_info();
 
+        // The virtual columns are resolved like the others, and dropped from the scan by the
+        // projection push down unless they are selected.
+        let mut read_table_info = table_info.clone();
+        let virtual_columns = self.virtual_columns();
+        if !virtual_columns.is_empty() {
+            let mut fields = table_info.schema.fields().clone();
+            fields.extend(virtual_columns);
+            read_table_info = read_table_info.schema(DataSchemaRefExt::create(fields));
+        }
+
         let description = if statistics.read_rows > 0 {
             format!(
                 "(Read from {}.{} table, {} Read Rows:{}, Read Bytes:{})",
@@ -145,7 +163,7 @@ impl ToReadDataSourcePlan for dyn Table {
         };
 
         Ok(ReadDataSourcePlan {
-            table_info: table_info.clone(),
+            table_info: read_table_info,
             parts,
             statistics,
             description,
@@ -17,6 +17,8 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
 use common_base::tokio;
@@ -28,6 +30,7 @@ use common_base::GlobalUniqName;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_flight_rpc::ConnectionFactory;
+use common_management::LeaderMgr;
 use common_management::NamespaceApi;
 use common_management::NamespaceMgr;
 use common_meta_api::KVApi;
@@ -39,6 +42,8 @@ use rand::thread_rng;
 use rand::Rng;
 
 use crate::api::FlightClient;
+use crate::clusters::leader::LeaderElection;
+use crate::clusters::leader::BACKGROUND_JOBS_ELECTION;
 use crate::common::MetaClientProvider;
 use crate::configs::Config;
 
@@ -49,11 +54,13 @@ pub struct ClusterDiscovery {
     local_id: String,
     heartbeat: Mutex<ClusterHeartbeat>,
     api_provider: Arc<dyn NamespaceApi>,
+    /// Elects the node running the background jobs among the nodes of the tenant
+    leader_election: Mutex<LeaderElection>,
 }
 
 impl ClusterDiscovery {
     async fn create_meta_client(cfg: &Config) -> Result<Arc<dyn KVApi>> {
-        let meta_api_provider = MetaClientProvider::new(cfg);
+        let meta_api_provider = MetaClientProvider::from(cfg);
         match meta_api_provider.try_get_kv_client().await {
             Ok(client) => Ok(client),
             Err(cause) => Err(cause.add_message_back("(while create namespace api).")),
@@ -63,12 +70,19 @@ impl ClusterDiscovery {
     pub async fn create_global(cfg: Config) -> Result<ClusterDiscoveryRef> {
         let local_id = GlobalUniqName::unique();
         let meta_client = ClusterDiscovery::create_meta_client(&cfg).await?;
-        let (lift_time, provider) = Self::create_provider(&cfg, meta_client)?;
+        let (lift_time, provider) = Self::create_provider(&cfg, meta_client.clone())?;
+        let leader_api = Arc::new(LeaderMgr::new(
+            meta_client,
+            &cfg.query.tenant,
+            BACKGROUND_JOBS_ELECTION,
+            lift_time,
+        ));
 
         Ok(Arc::new(ClusterDiscovery {
             local_id: local_id.clone(),
             api_provider: provider.clone(),
             heartbeat: Mutex::new(ClusterHeartbeat::create(lift_time, provider)),
+            leader_election: Mutex::new(LeaderElection::create(local_id, lift_time, leader_api)),
         }))
     }
 
@@ -79,20 +93,45 @@ impl ClusterDiscovery {
         // TODO: generate if tenant or namespace is empty
         let tenant = &cfg.query.tenant;
         let namespace = &cfg.query.namespace;
-        let lift_time = Duration::from_secs(60);
+        let lift_time = match cfg.query.cluster_heartbeat_timeout_secs {
+            0 => {
+                return Err(ErrorCode::InvalidConfig(
+                    "cluster_heartbeat_timeout_secs must be greater than 0",
+                ))
+            }
+            secs => Duration::from_secs(secs),
+        };
         let namespace_manager = NamespaceMgr::new(api, tenant, namespace, lift_time)?;
 
         Ok((lift_time, Arc::new(namespace_manager)))
     }
 
+    pub fn local_id(&self) -> String {
+        self.local_id.clone()
+    }
+
+    /// If the node is the leader running the background jobs of the tenant. The leader keeps a
+    /// lease in the meta service, once it fails another node takes over after the lease expires.
+    pub async fn is_leader(&self) -> bool {
+        self.leader_election.lock().await.is_leader()
+    }
+
+    /// Discovers the alive nodes of the cluster, the nodes missing their heartbeats are left out
+    /// once their leases expire, so the queries are not scheduled to them.
     pub async fn discover(&self) -> Result<ClusterRef> {
         match self.api_provider.get_nodes().await {
             Err(cause) => Err(cause.add_message_back("(while namespace api get_nodes).")),
             Ok(cluster_nodes) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time went backwards")
+                    .as_secs();
                 let mut res = Vec::with_capacity(cluster_nodes.len());
 
                 for node in &cluster_nodes {
-                    res.push(Arc::new(node.clone()))
+                    if node.is_alive(now) {
+                        res.push(Arc::new(node.clone()))
+                    }
                 }
 
                 Ok(Cluster::create(res, self.local_id.clone()))
@@ -123,6 +162,14 @@ impl ClusterDiscovery {
     }
 
     pub async fn unregister_to_metastore(self: &Arc<Self>) {
+        let mut leader_election = self.leader_election.lock().await;
+        if let Err(shutdown_failure) = leader_election.shutdown().await {
+            log::warn!(
+                "Cannot shutdown leader election, cause {:?}",
+                shutdown_failure
+            );
+        }
+
         let mut heartbeat = self.heartbeat.lock().await;
 
         if let Err(shutdown_failure) = heartbeat.shutdown().await {
@@ -148,15 +195,18 @@ impl ClusterDiscovery {
         let node_info = NodeInfo::create(self.local_id.clone(), cpus, address);
 
         self.drop_invalid_nodes(&node_info).await?;
-        match self.api_provider.add_node(node_info).await {
-            Ok(_) => self.start_heartbeat().await,
+        match self.api_provider.add_node(node_info.clone()).await {
+            Ok(_) => self.start_heartbeat(node_info).await,
             Err(cause) => Err(cause.add_message_back("(while namespace api add_node).")),
         }
     }
 
-    async fn start_heartbeat(self: &Arc<Self>) -> Result<()> {
+    async fn start_heartbeat(self: &Arc<Self>, node_info: NodeInfo) -> Result<()> {
         let mut heartbeat = self.heartbeat.lock().await;
-        heartbeat.start(self.local_id.clone());
+        heartbeat.start(node_info);
+
+        let mut leader_election = self.leader_election.lock().await;
+        leader_election.start().await;
         Ok(())
     }
 }
@@ -242,7 +292,7 @@ impl ClusterHeartbeat {
         }
     }
 
-    fn heartbeat_loop(&self, local_id: String) -> impl Future<Output = ()> + 'static {
+    fn heartbeat_loop(&self, node_info: NodeInfo) -> impl Future<Output = ()> + 'static {
         let shutdown = self.shutdown.clone();
         let shutdown_notify = self.shutdown_notify.clone();
         let namespace_api = self.namespace_api.clone();
@@ -265,9 +315,33 @@ impl ClusterHeartbeat {
                     }
                     Either::Right((_, new_shutdown_notified)) => {
                         shutdown_notified = new_shutdown_notified;
-                        let heartbeat = namespace_api.heartbeat(local_id.clone(), None);
-                        if let Err(failure) = heartbeat.await {
-                            log::error!("Cluster namespace api heartbeat failure: {:?}", failure);
+                        let heartbeat = namespace_api.heartbeat(node_info.id.clone(), None);
+                        match heartbeat.await {
+                            Ok(_) => {}
+                            // The lease expired after missing heartbeats, e.g. the meta store was
+                            // unreachable for a while, so the node joins the cluster again.
+                            Err(cause)
+                                if cause.code() == ErrorCode::NamespaceUnknownNode("").code() =>
+                            {
+                                log::warn!(
+                                    "Cluster node {} left the cluster, adding it again",
+                                    node_info.id
+                                );
+                                if let Err(failure) =
+                                    namespace_api.add_node(node_info.clone()).await
+                                {
+                                    log::error!(
+                                        "Cluster namespace api add_node failure: {:?}",
+                                        failure
+                                    );
+                                }
+                            }
+                            Err(failure) => {
+                                log::error!(
+                                    "Cluster namespace api heartbeat failure: {:?}",
+                                    failure
+                                );
+                            }
                         }
                     }
                 }
@@ -279,8 +353,8 @@ impl ClusterHeartbeat {
         (duration / 3).as_millis()..=((duration / 3) * 2).as_millis()
     }
 
-    pub fn start(&mut self, local_id: String) {
-        self.shutdown_handler = Some(tokio::spawn(self.heartbeat_loop(local_id)));
+    pub fn start(&mut self, node_info: NodeInfo) {
+        self.shutdown_handler = Some(tokio::spawn(self.heartbeat_loop(node_info)));
     }
 
     pub async fn shutdown(&mut self) -> Result<()> {
@@ -30,6 +30,24 @@ async fn test_single_cluster_discovery() -> Result<()> {
     assert_eq!(discover_cluster_nodes.len(), 1);
     assert!(discover_cluster.is_empty());
     assert!(discover_cluster.is_local(&discover_cluster_nodes[0]));
+    assert!(discover_cluster_nodes[0].alive_until > 0);
+
+    // The only node is the leader running the background jobs, until it leaves.
+    assert!(cluster_discovery.is_leader().await);
+    cluster_discovery.unregister_to_metastore().await;
+    assert!(!cluster_discovery.is_leader().await);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_zero_heartbeat_timeout_cluster_discovery() -> Result<()> {
+    let mut config = Config::default();
+    config.query.cluster_heartbeat_timeout_secs = 0;
+
+    match ClusterDiscovery::create_global(config).await {
+        Ok(_) => panic!("Zero heartbeat timeout must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2301),
+    }
     Ok(())
 }
 
@@ -0,0 +1,170 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_base::tokio::sync::Notify;
+use common_base::tokio::task::JoinHandle;
+use common_base::tokio::time::sleep as tokio_async_sleep;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::LeaderApi;
+use futures::future::select;
+use futures::future::Either;
+
+/// The election of the node running the background jobs of the tenant, such as purging the
+/// dropped tables, applying the storage policies, refreshing the table statistics and ingesting
+/// the pipes.
+pub const BACKGROUND_JOBS_ELECTION: &str = "background_jobs";
+
+/// Campaigns for the leader of an election, and keeps the leadership by renewing its lease.
+pub struct LeaderElection {
+    candidate: Arc<Candidate>,
+    shutdown: Arc<AtomicBool>,
+    shutdown_notify: Arc<Notify>,
+    shutdown_handler: Option<JoinHandle<Option<u64>>>,
+}
+
+struct Candidate {
+    node_id: String,
+    lease: Duration,
+    leader_api: Arc<dyn LeaderApi>,
+    is_leader: AtomicBool,
+}
+
+/// The lease held by the leader.
+struct Leadership {
+    seq: u64,
+    renewed_at: Instant,
+}
+
+impl LeaderElection {
+    pub fn create(node_id: String, lease: Duration, leader_api: Arc<dyn LeaderApi>) -> Self {
+        LeaderElection {
+            candidate: Arc::new(Candidate {
+                node_id,
+                lease,
+                leader_api,
+                is_leader: AtomicBool::new(false),
+            }),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            shutdown_notify: Arc::new(Notify::new()),
+            shutdown_handler: None,
+        }
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.candidate.is_leader.load(Ordering::Relaxed)
+    }
+
+    /// Campaigns at once, then campaigns again or renews the lease every 1/3 of it.
+    pub async fn start(&mut self) {
+        let mut leadership = None;
+        self.candidate.campaign(&mut leadership).await;
+
+        let candidate = self.candidate.clone();
+        let shutdown = self.shutdown.clone();
+        let shutdown_notify = self.shutdown_notify.clone();
+        self.shutdown_handler = Some(tokio::spawn(async move {
+            let mut shutdown_notified = Box::pin(shutdown_notify.notified());
+
+            while !shutdown.load(Ordering::Relaxed) {
+                let sleep = tokio_async_sleep(candidate.lease / 3);
+                match select(shutdown_notified, Box::pin(sleep)).await {
+                    Either::Left((_, _)) => break,
+                    Either::Right((_, new_shutdown_notified)) => {
+                        shutdown_notified = new_shutdown_notified;
+                        candidate.campaign(&mut leadership).await;
+                    }
+                }
+            }
+            leadership.map(|leadership| leadership.seq)
+        }));
+    }
+
+    /// Stops campaigning, and resigns to let another node take over at once.
+    pub async fn shutdown(&mut self) -> Result<()> {
+        if let Some(shutdown_handler) = self.shutdown_handler.take() {
+            self.shutdown.store(true, Ordering::Relaxed);
+            self.shutdown_notify.notify_waiters();
+            let leadership = match shutdown_handler.await {
+                Ok(leadership) => leadership,
+                Err(shutdown_failure) => {
+                    return Err(ErrorCode::TokioError(format!(
+                        "Cannot shutdown leader election, cause {:?}",
+                        shutdown_failure
+                    )));
+                }
+            };
+
+            self.candidate.is_leader.store(false, Ordering::Relaxed);
+            if let Some(seq) = leadership {
+                self.candidate.leader_api.resign(seq).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Candidate {
+    async fn campaign(&self, leadership: &mut Option<Leadership>) {
+        *leadership = match leadership.take() {
+            None => match self.leader_api.try_acquire(&self.node_id).await {
+                Ok(Some(seq)) => {
+                    log::info!("Node {} becomes the leader", self.node_id);
+                    Some(Leadership {
+                        seq,
+                        renewed_at: Instant::now(),
+                    })
+                }
+                Ok(None) => None,
+                Err(cause) => {
+                    log::warn!("Cannot campaign for the leader, cause {:?}", cause);
+                    None
+                }
+            },
+            Some(held) => match self.leader_api.renew(held.seq).await {
+                Ok(seq) => Some(Leadership {
+                    seq,
+                    renewed_at: Instant::now(),
+                }),
+                Err(cause) if cause.code() == ErrorCode::LeaderLeaseLost("").code() => {
+                    log::warn!("Node {} is no longer the leader", self.node_id);
+                    None
+                }
+                // Another node takes over once the lease expires, so the leader steps down long
+                // before it when the meta service is unreachable.
+                Err(cause) if held.renewed_at.elapsed() < self.lease / 2 => {
+                    log::warn!("Cannot renew the leader lease, cause {:?}", cause);
+                    Some(held)
+                }
+                Err(cause) => {
+                    log::warn!(
+                        "Node {} is no longer the leader, cannot renew the leader lease, cause {:?}",
+                        self.node_id,
+                        cause
+                    );
+                    None
+                }
+            },
+        };
+        self.is_leader
+            .store(leadership.is_some(), Ordering::Relaxed);
+    }
+}
@@ -16,6 +16,7 @@
 mod cluster_test;
 
 mod cluster;
+mod leader;
 
 pub use cluster::Cluster;
 pub use cluster::ClusterDiscovery;
@@ -17,6 +17,7 @@ use common_flight_rpc::FlightClientConf;
 use common_flight_rpc::FlightClientTlsConfig;
 use common_meta_flight::MetaFlightClientConf;
 
+use crate::common::MetaClientProvider;
 use crate::configs::Config;
 
 // since `store-api-sdk` should not depends on query
@@ -48,3 +49,14 @@ impl From<&Config> for MetaFlightClientConf {
         }
     }
 }
+
+impl From<&Config> for MetaClientProvider {
+    fn from(conf: &Config) -> Self {
+        let provider =
+            MetaClientProvider::new(conf).with_fault_rates(conf.fault_injection.rates("meta"));
+        match conf.meta.meta_embedded {
+            true => provider.with_embedded_dir(conf.meta.meta_embedded_dir.clone()),
+            false => provider,
+        }
+    }
+}
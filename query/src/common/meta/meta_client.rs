@@ -15,11 +15,23 @@
 
 use std::sync::Arc;
 
+use common_base::tokio::sync::Mutex;
+use common_base::FaultInjector;
+use common_base::FaultRates;
 use common_exception::Result;
 use common_meta_api::KVApi;
 use common_meta_api::MetaApi;
+use common_meta_embedded::MetaEmbedded;
 use common_meta_flight::MetaFlightClient;
 use common_meta_flight::MetaFlightClientConf;
+use lazy_static::lazy_static;
+
+use crate::common::meta::FaultyMetaClient;
+
+lazy_static! {
+    // There is only one sled::Db in a process, so is the embedded meta store on it.
+    static ref META_EMBEDDED: Mutex<Option<MetaEmbedded>> = Mutex::new(None);
+}
 
 // Since there is a pending dependency issue,
 // StoreApiProvider is temporarily moved from store-api-sdk
@@ -31,28 +43,77 @@ pub struct MetaClientProvider {
     // do not depend on query::configs::Config in case of moving back to sdk
     // also @see config_converter.rs
     conf: MetaFlightClientConf,
+    fault_injector: Option<Arc<FaultInjector>>,
+    embedded_dir: Option<String>,
 }
 
 impl MetaClientProvider {
     pub fn new(conf: impl Into<MetaFlightClientConf>) -> Self {
-        MetaClientProvider { conf: conf.into() }
+        MetaClientProvider {
+            conf: conf.into(),
+            fault_injector: None,
+            embedded_dir: None,
+        }
+    }
+
+    /// Uses the meta store persisted in `dir` in this process, instead of the remote meta service.
+    pub fn with_embedded_dir(mut self, dir: impl Into<String>) -> Self {
+        self.embedded_dir = Some(dir.into());
+        self
+    }
+
+    /// Injects faults into the clients to the remote meta service, if the rates are enabled.
+    pub fn with_fault_rates(mut self, rates: FaultRates) -> Self {
+        if rates.is_enabled() {
+            self.fault_injector = Some(FaultInjector::create(rates));
+        }
+        self
+    }
+
+    pub fn get_fault_injector(&self) -> Option<Arc<FaultInjector>> {
+        self.fault_injector.clone()
     }
 
     /// Get meta async client, trait is defined in MetaApi.
     pub async fn try_get_meta_client(&self) -> Result<Arc<dyn MetaApi>> {
-        let client = MetaFlightClient::try_new(&self.conf).await?;
-        Ok(Arc::new(client))
+        if let Some(dir) = &self.embedded_dir {
+            return Ok(Arc::new(Self::get_meta_embedded(dir).await?));
+        }
+
+        let client = Arc::new(MetaFlightClient::try_new(&self.conf).await?);
+        match &self.fault_injector {
+            None => Ok(client),
+            Some(injector) => Ok(Arc::new(FaultyMetaClient::create(client, injector.clone()))),
+        }
     }
 
     /// Get kv async client, operations trait defined in KVApi.
     pub async fn try_get_kv_client(&self) -> Result<Arc<dyn KVApi>> {
+        if let Some(dir) = &self.embedded_dir {
+            return Ok(Arc::new(Self::get_meta_embedded(dir).await?));
+        }
+
         let local = self.conf.kv_service_config.address.is_empty();
         if local {
-            let client = common_meta_embedded::MetaEmbedded::new_temp().await?;
+            let client = MetaEmbedded::new_temp().await?;
             Ok(Arc::new(client))
         } else {
-            let client = MetaFlightClient::try_new(&self.conf).await?;
-            Ok(Arc::new(client))
+            let client = Arc::new(MetaFlightClient::try_new(&self.conf).await?);
+            match &self.fault_injector {
+                None => Ok(client),
+                Some(injector) => Ok(Arc::new(FaultyMetaClient::create(client, injector.clone()))),
+            }
         }
     }
+
+    async fn get_meta_embedded(dir: &str) -> Result<MetaEmbedded> {
+        let mut meta = META_EMBEDDED.lock().await;
+        if let Some(meta) = meta.as_ref() {
+            return Ok(meta.clone());
+        }
+
+        let opened = MetaEmbedded::open_persistent(dir).await?;
+        *meta = Some(opened.clone());
+        Ok(opened)
+    }
 }
@@ -0,0 +1,225 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_base::Fault;
+use common_base::FaultInjector;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_api::MetaApi;
+use common_meta_types::CreateDatabaseReply;
+use common_meta_types::CreateTableReply;
+use common_meta_types::DatabaseInfo;
+use common_meta_types::GetKVActionReply;
+use common_meta_types::KVMeta;
+use common_meta_types::MGetKVActionReply;
+use common_meta_types::MatchSeq;
+use common_meta_types::MetaId;
+use common_meta_types::MetaVersion;
+use common_meta_types::PrefixListReply;
+use common_meta_types::TableInfo;
+use common_meta_types::UpdateTableSchemaReply;
+use common_meta_types::UpsertKVActionReply;
+use common_meta_types::UpsertTableOptionReply;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+use common_planners::DropDatabasePlan;
+use common_planners::DropTablePlan;
+
+/// A meta client which fails on purpose, wrapping the real one.
+///
+/// `PartialRead` only applies to the listing calls, which then return half of the items.
+pub struct FaultyMetaClient<T> {
+    inner: Arc<T>,
+    injector: Arc<FaultInjector>,
+}
+
+impl<T> FaultyMetaClient<T> {
+    pub fn create(inner: Arc<T>, injector: Arc<FaultInjector>) -> FaultyMetaClient<T> {
+        FaultyMetaClient { inner, injector }
+    }
+
+    async fn before(&self, operation: &str, reading: bool) -> Result<Option<Fault>> {
+        match self.injector.next_fault(operation, reading) {
+            Some(Fault::Error) => Err(ErrorCode::MetaServiceUnavailable(format!(
+                "Injected fault: {} failed",
+                operation
+            ))),
+            Some(Fault::Latency(duration)) => {
+                tokio::time::sleep(duration).await;
+                Ok(None)
+            }
+            other => Ok(other),
+        }
+    }
+
+    fn after<R>(operation: &str, fault: Option<Fault>, reply: R) -> Result<R> {
+        match fault {
+            Some(Fault::LostResponse) => Err(ErrorCode::Timeout(format!(
+                "Injected fault: response of {} lost",
+                operation
+            ))),
+            _ => Ok(reply),
+        }
+    }
+
+    fn partial<R>(fault: &Option<Fault>, mut items: Vec<R>) -> Vec<R> {
+        if let Some(Fault::PartialRead) = fault {
+            items.truncate(items.len() / 2);
+        }
+        items
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: MetaApi> MetaApi for FaultyMetaClient<T> {
+    async fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
+        let fault = self.before("create_database", false).await?;
+        let reply = self.inner.create_database(plan).await?;
+        Self::after("create_database", fault, reply)
+    }
+
+    async fn drop_database(&self, plan: DropDatabasePlan) -> Result<()> {
+        let fault = self.before("drop_database", false).await?;
+        self.inner.drop_database(plan).await?;
+        Self::after("drop_database", fault, ())
+    }
+
+    async fn get_database(&self, db: &str) -> Result<Arc<DatabaseInfo>> {
+        let fault = self.before("get_database", false).await?;
+        let reply = self.inner.get_database(db).await?;
+        Self::after("get_database", fault, reply)
+    }
+
+    async fn get_databases(&self) -> Result<Vec<Arc<DatabaseInfo>>> {
+        let fault = self.before("get_databases", true).await?;
+        let reply = Self::partial(&fault, self.inner.get_databases().await?);
+        Self::after("get_databases", fault, reply)
+    }
+
+    async fn create_table(&self, plan: CreateTablePlan) -> Result<CreateTableReply> {
+        let fault = self.before("create_table", false).await?;
+        let reply = self.inner.create_table(plan).await?;
+        Self::after("create_table", fault, reply)
+    }
+
+    async fn drop_table(&self, plan: DropTablePlan) -> Result<()> {
+        let fault = self.before("drop_table", false).await?;
+        self.inner.drop_table(plan).await?;
+        Self::after("drop_table", fault, ())
+    }
+
+    async fn get_table(&self, db: &str, table: &str) -> Result<Arc<TableInfo>> {
+        let fault = self.before("get_table", false).await?;
+        let reply = self.inner.get_table(db, table).await?;
+        Self::after("get_table", fault, reply)
+    }
+
+    async fn get_tables(&self, db: &str) -> Result<Vec<Arc<TableInfo>>> {
+        let fault = self.before("get_tables", true).await?;
+        let reply = Self::partial(&fault, self.inner.get_tables(db).await?);
+        Self::after("get_tables", fault, reply)
+    }
+
+    async fn get_table_by_id(
+        &self,
+        table_id: MetaId,
+        table_version: Option<MetaVersion>,
+    ) -> Result<Arc<TableInfo>> {
+        let fault = self.before("get_table_by_id", false).await?;
+        let reply = self.inner.get_table_by_id(table_id, table_version).await?;
+        Self::after("get_table_by_id", fault, reply)
+    }
+
+    async fn upsert_table_option(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        option_key: String,
+        option_value: String,
+    ) -> Result<UpsertTableOptionReply> {
+        let fault = self.before("upsert_table_option", false).await?;
+        let reply = self
+            .inner
+            .upsert_table_option(table_id, table_version, option_key, option_value)
+            .await?;
+        Self::after("upsert_table_option", fault, reply)
+    }
+
+    async fn update_table_schema(
+        &self,
+        table_id: MetaId,
+        table_version: MetaVersion,
+        schema: DataSchemaRef,
+    ) -> Result<UpdateTableSchemaReply> {
+        let fault = self.before("update_table_schema", false).await?;
+        let reply = self
+            .inner
+            .update_table_schema(table_id, table_version, schema)
+            .await?;
+        Self::after("update_table_schema", fault, reply)
+    }
+
+    fn name(&self) -> String {
+        format!("faulty({})", self.inner.name())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: KVApi> KVApi for FaultyMetaClient<T> {
+    async fn upsert_kv(
+        &self,
+        key: &str,
+        seq: MatchSeq,
+        value: Option<Vec<u8>>,
+        value_meta: Option<KVMeta>,
+    ) -> Result<UpsertKVActionReply> {
+        let fault = self.before("upsert_kv", false).await?;
+        let reply = self.inner.upsert_kv(key, seq, value, value_meta).await?;
+        Self::after("upsert_kv", fault, reply)
+    }
+
+    async fn update_kv_meta(
+        &self,
+        key: &str,
+        seq: MatchSeq,
+        value_meta: Option<KVMeta>,
+    ) -> Result<UpsertKVActionReply> {
+        let fault = self.before("update_kv_meta", false).await?;
+        let reply = self.inner.update_kv_meta(key, seq, value_meta).await?;
+        Self::after("update_kv_meta", fault, reply)
+    }
+
+    async fn get_kv(&self, key: &str) -> Result<GetKVActionReply> {
+        let fault = self.before("get_kv", false).await?;
+        let reply = self.inner.get_kv(key).await?;
+        Self::after("get_kv", fault, reply)
+    }
+
+    async fn mget_kv(&self, keys: &[String]) -> Result<MGetKVActionReply> {
+        let fault = self.before("mget_kv", false).await?;
+        let reply = self.inner.mget_kv(keys).await?;
+        Self::after("mget_kv", fault, reply)
+    }
+
+    async fn prefix_list_kv(&self, prefix: &str) -> Result<PrefixListReply> {
+        let fault = self.before("prefix_list_kv", true).await?;
+        let reply = Self::partial(&fault, self.inner.prefix_list_kv(prefix).await?);
+        Self::after("prefix_list_kv", fault, reply)
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_base::Fault;
+use common_base::FaultInjector;
+use common_base::FaultRates;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_api::MetaApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::MatchSeq;
+use common_planners::CreateDatabasePlan;
+
+use crate::common::FaultyMetaClient;
+
+fn create_db_plan(db: &str) -> CreateDatabasePlan {
+    CreateDatabasePlan {
+        if_not_exists: false,
+        db: db.to_string(),
+        options: Default::default(),
+        tables: vec![],
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_faulty_meta_client() -> Result<()> {
+    let meta = Arc::new(MetaEmbedded::new_temp().await?);
+    let injector = FaultInjector::create(FaultRates::default());
+    let client = FaultyMetaClient::create(meta.clone(), injector.clone());
+
+    // Error: the database is not created.
+    injector.inject("create_database", Fault::Error);
+    let res = client.create_database(create_db_plan("db1")).await;
+    assert_eq!(
+        ErrorCode::MetaServiceUnavailable("").code(),
+        res.unwrap_err().code()
+    );
+    assert!(meta.get_database("db1").await.is_err());
+
+    // Lost response: the database is created, a retry finds it exists.
+    injector.inject("create_database", Fault::LostResponse);
+    let res = client.create_database(create_db_plan("db1")).await;
+    assert_eq!(ErrorCode::Timeout("").code(), res.unwrap_err().code());
+    assert!(meta.get_database("db1").await.is_ok());
+    assert!(client.create_database(create_db_plan("db1")).await.is_err());
+
+    for db in ["db2", "db3", "db4"] {
+        client.create_database(create_db_plan(db)).await?;
+    }
+
+    // Partial read: half of the databases.
+    injector.inject("get_databases", Fault::PartialRead);
+    let all = client.get_databases().await?.len();
+    assert!(all >= 4);
+    injector.inject("get_databases", Fault::PartialRead);
+    assert_eq!(all / 2, client.get_databases().await?.len());
+
+    // KV.
+    injector.inject("upsert_kv", Fault::LostResponse);
+    let res = client
+        .upsert_kv("k1", MatchSeq::Any, Some(b"v1".to_vec()), None)
+        .await;
+    assert!(res.is_err());
+    assert!(client.get_kv("k1").await?.result.is_some());
+
+    assert_eq!(5, injector.injected().len());
+    assert_eq!("faulty(meta-embedded)", client.name());
+    Ok(())
+}
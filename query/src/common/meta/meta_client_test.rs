@@ -0,0 +1,56 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_embedded::MetaEmbedded;
+
+use crate::catalogs::impls::DatabaseCatalog;
+use crate::common::MetaClientProvider;
+use crate::configs::Config;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_client_provider_embedded() -> Result<()> {
+    // The tests of this process keep the process-wise sled db in a temp dir, where the
+    // embedded meta store would not persist, it is refused instead.
+    let _meta = MetaEmbedded::new_temp().await?;
+
+    let tmp_dir = tempfile::tempdir()?;
+    let mut conf = Config::default();
+    conf.meta.meta_embedded = true;
+    conf.meta.meta_embedded_dir = tmp_dir.path().to_str().unwrap().to_string();
+
+    let res = MetaClientProvider::from(&conf).try_get_meta_client().await;
+    assert_eq!(
+        ErrorCode::InvalidConfig("").code(),
+        res.err().unwrap().code()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_meta_embedded_with_address() -> Result<()> {
+    let mut conf = Config::default();
+    conf.meta.meta_embedded = true;
+    conf.meta.meta_address = "127.0.0.1:9191".to_string();
+
+    let res = DatabaseCatalog::try_create_with_config(conf);
+    assert_eq!(
+        ErrorCode::InvalidConfig("").code(),
+        res.err().unwrap().code()
+    );
+    Ok(())
+}
@@ -14,5 +14,12 @@
 
 mod config_converter;
 mod meta_client;
+mod meta_client_faulty;
+
+#[cfg(test)]
+mod meta_client_faulty_test;
+#[cfg(test)]
+mod meta_client_test;
 
 pub use meta_client::MetaClientProvider;
+pub use meta_client_faulty::FaultyMetaClient;
@@ -16,4 +16,5 @@ mod hashtable;
 mod meta;
 
 pub use hashtable::*;
+pub use meta::FaultyMetaClient;
 pub use meta::MetaClientProvider;
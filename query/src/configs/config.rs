@@ -19,11 +19,26 @@ use lazy_static::lazy_static;
 use structopt::StructOpt;
 use structopt_toml::StructOptToml;
 
+use crate::configs::config_file_keys;
+use crate::configs::flatten_config;
+use crate::configs::ConfigSource;
+use crate::configs::ConfigSources;
+use crate::configs::FaultInjectionConfig;
 use crate::configs::LogConfig;
 use crate::configs::MetaConfig;
 use crate::configs::QueryConfig;
 use crate::configs::StorageConfig;
 
+/// The cargo features databend-query is built with.
+pub const DATABEND_BUILD_FEATURES: &[&str] = &[
+    #[cfg(feature = "simd")]
+    "simd",
+    #[cfg(feature = "wasm")]
+    "wasm",
+    #[cfg(feature = "kafka")]
+    "kafka",
+];
+
 lazy_static! {
     pub static ref DATABEND_COMMIT_VERSION: String = {
         let build_semver = option_env!("VERGEN_BUILD_SEMVER");
@@ -31,12 +46,14 @@ lazy_static! {
         let rustc_semver = option_env!("VERGEN_RUSTC_SEMVER");
         let timestamp = option_env!("VERGEN_BUILD_TIMESTAMP");
 
+        // e.g. 0.1.0-8e5fd1a-simd-wasm(1.57.0-nightly-2021-09-09T10:36:26.000000Z)
         let ver = match (build_semver, git_sha, rustc_semver, timestamp) {
-            #[cfg(not(feature = "simd"))]
-            (Some(v1), Some(v2), Some(v3), Some(v4)) => format!("{}-{}({}-{})", v1, v2, v3, v4),
-            #[cfg(feature = "simd")]
             (Some(v1), Some(v2), Some(v3), Some(v4)) => {
-                format!("{}-{}-simd({}-{})", v1, v2, v3, v4)
+                let features = DATABEND_BUILD_FEATURES
+                    .iter()
+                    .map(|feature| format!("-{}", feature))
+                    .collect::<String>();
+                format!("{}-{}{}({}-{})", v1, v2, features, v3, v4)
             }
             _ => String::new(),
         };
@@ -55,6 +72,13 @@ pub struct Config {
     #[structopt(long, short = "c", env = CONFIG_FILE, default_value = "")]
     pub config_file: String,
 
+    #[structopt(
+        long,
+        help = "Print the effective config and where each value comes from, then exit"
+    )]
+    #[serde(skip)]
+    pub print_config: bool,
+
     // Query engine config.
     #[structopt(flatten)]
     pub query: QueryConfig,
@@ -69,6 +93,10 @@ pub struct Config {
     // Storage backend config.
     #[structopt(flatten)]
     pub storage: StorageConfig,
+
+    // Fault injection config, for tests.
+    #[structopt(flatten)]
+    pub fault_injection: FaultInjectionConfig,
 }
 
 impl Config {
@@ -76,13 +104,48 @@ impl Config {
     pub fn default() -> Self {
         Config {
             config_file: "".to_string(),
+            print_config: false,
             query: QueryConfig::default(),
             log: LogConfig::default(),
             meta: MetaConfig::default(),
             storage: StorageConfig::default(),
+            fault_injection: FaultInjectionConfig::default(),
         }
     }
 
+    /// Load the config from its layers, each one overrides the former ones: the defaults,
+    /// the config file of `-c`, the command line args and the env variables.
+    pub fn load() -> Result<(Self, ConfigSources)> {
+        let mut sources = ConfigSources::create();
+        let args = Config::from_args();
+
+        let mut cfg = args.clone();
+        if !args.config_file.is_empty() {
+            let txt = std::fs::read_to_string(&args.config_file).map_err(|e| {
+                ErrorCode::CannotReadFile(format!("File: {}, err: {:?}", args.config_file, e))
+            })?;
+            for key in config_file_keys(&txt)? {
+                sources.set(&key, ConfigSource::File);
+            }
+            cfg = Self::load_from_toml_str(&txt)?;
+        }
+
+        // The args which differ from the defaults. structopt reads the env variables of the args
+        // too, so the defaults are read with the same env, the keys of the env are recorded below.
+        let defaults = flatten_config(&<Config as StructOpt>::from_iter(&Vec::<&str>::new()))?;
+        for (key, value) in flatten_config(&args)? {
+            if defaults.get(&key) != Some(&value) {
+                sources.set(&key, ConfigSource::Args);
+            }
+        }
+
+        cfg.apply_env(&mut sources);
+        if cfg.query.num_cpus == 0 {
+            cfg.query.num_cpus = num_cpus::get() as u64;
+        }
+        Ok((cfg, sources))
+    }
+
     /// Load configs from args.
     pub fn load_from_args() -> Self {
         let mut cfg = Config::from_args();
@@ -101,6 +164,7 @@ impl Config {
 
     /// Load configs from toml str.
     pub fn load_from_toml_str(toml_str: &str) -> Result<Self> {
+        config_file_keys(toml_str)?;
         let mut cfg = Config::from_args_with_toml(toml_str)
             .map_err(|e| ErrorCode::BadArguments(format!("{:?}", e)))?;
         if cfg.query.num_cpus == 0 {
@@ -118,19 +182,25 @@ impl Config {
             );
         }
 
+        mut_config.apply_env(&mut ConfigSources::create());
+        Ok(mut_config)
+    }
+
+    fn apply_env(&mut self, sources: &mut ConfigSources) {
         // Log.
-        LogConfig::load_from_env(&mut mut_config);
+        LogConfig::load_from_env(self, sources);
 
         // Meta.
-        MetaConfig::load_from_env(&mut mut_config);
+        MetaConfig::load_from_env(self, sources);
 
         // Storage.
-        StorageConfig::load_from_env(&mut mut_config);
+        StorageConfig::load_from_env(self, sources);
 
         // Query.
-        QueryConfig::load_from_env(&mut mut_config);
+        QueryConfig::load_from_env(self, sources);
 
-        Ok(mut_config)
+        // Fault injection.
+        FaultInjectionConfig::load_from_env(self, sources);
     }
 
     pub fn tls_query_client_conf(&self) -> FlightClientTlsConfig {
@@ -0,0 +1,160 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::FaultRates;
+use structopt::StructOpt;
+use structopt_toml::StructOptToml;
+
+use crate::configs::Config;
+use crate::configs::ConfigSources;
+
+// Fault injection env.
+pub const FAULT_TARGETS: &str = "FAULT_TARGETS";
+pub const FAULT_SEED: &str = "FAULT_SEED";
+pub const FAULT_ERROR_RATE: &str = "FAULT_ERROR_RATE";
+pub const FAULT_LATENCY_RATE: &str = "FAULT_LATENCY_RATE";
+pub const FAULT_LATENCY_MS: &str = "FAULT_LATENCY_MS";
+pub const FAULT_PARTIAL_READ_RATE: &str = "FAULT_PARTIAL_READ_RATE";
+pub const FAULT_LOST_RESPONSE_RATE: &str = "FAULT_LOST_RESPONSE_RATE";
+
+/// Fault injection config group, for tests only.
+/// Faults are injected into the calls to the storage(`dal`) and the meta service(`meta`),
+/// all the rates are 0 by default, which disables the injection.
+#[derive(
+    Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml,
+)]
+pub struct FaultInjectionConfig {
+    #[structopt(long, env = FAULT_TARGETS, default_value = "dal,meta", help = "Where the faults are injected <dal,meta>")]
+    #[serde(default)]
+    pub fault_targets: String,
+
+    #[structopt(long, env = FAULT_SEED, default_value = "0", help = "Seed of the random faults")]
+    #[serde(default)]
+    pub fault_seed: u64,
+
+    #[structopt(long, env = FAULT_ERROR_RATE, default_value = "0", help = "Rate of the calls failing")]
+    #[serde(default)]
+    pub fault_error_rate: f64,
+
+    #[structopt(long, env = FAULT_LATENCY_RATE, default_value = "0", help = "Rate of the calls delayed")]
+    #[serde(default)]
+    pub fault_latency_rate: f64,
+
+    #[structopt(long, env = FAULT_LATENCY_MS, default_value = "0", help = "Delay of the calls delayed, in milliseconds")]
+    #[serde(default)]
+    pub fault_latency_ms: u64,
+
+    #[structopt(long, env = FAULT_PARTIAL_READ_RATE, default_value = "0", help = "Rate of the reads returning partial data")]
+    #[serde(default)]
+    pub fault_partial_read_rate: f64,
+
+    #[structopt(long, env = FAULT_LOST_RESPONSE_RATE, default_value = "0", help = "Rate of the calls losing their responses")]
+    #[serde(default)]
+    pub fault_lost_response_rate: f64,
+}
+
+impl FaultInjectionConfig {
+    pub fn default() -> Self {
+        FaultInjectionConfig {
+            fault_targets: "dal,meta".to_string(),
+            fault_seed: 0,
+            fault_error_rate: 0.0,
+            fault_latency_rate: 0.0,
+            fault_latency_ms: 0,
+            fault_partial_read_rate: 0.0,
+            fault_lost_response_rate: 0.0,
+        }
+    }
+
+    /// The fault rates of `target`, disabled if it is not one of the targets.
+    pub fn rates(&self, target: &str) -> FaultRates {
+        let targeted = self
+            .fault_targets
+            .split(',')
+            .any(|t| t.trim().eq_ignore_ascii_case(target));
+
+        match targeted {
+            false => FaultRates::default(),
+            true => FaultRates {
+                seed: self.fault_seed,
+                error: self.fault_error_rate,
+                latency: self.fault_latency_rate,
+                latency_duration: Duration::from_millis(self.fault_latency_ms),
+                partial_read: self.fault_partial_read_rate,
+                lost_response: self.fault_lost_response_rate,
+            },
+        }
+    }
+
+    pub fn load_from_env(mut_config: &mut Config, sources: &mut ConfigSources) {
+        env_helper!(
+            mut_config,
+            sources,
+            fault_injection,
+            fault_targets,
+            String,
+            FAULT_TARGETS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            fault_injection,
+            fault_seed,
+            u64,
+            FAULT_SEED
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            fault_injection,
+            fault_error_rate,
+            f64,
+            FAULT_ERROR_RATE
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            fault_injection,
+            fault_latency_rate,
+            f64,
+            FAULT_LATENCY_RATE
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            fault_injection,
+            fault_latency_ms,
+            u64,
+            FAULT_LATENCY_MS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            fault_injection,
+            fault_partial_read_rate,
+            f64,
+            FAULT_PARTIAL_READ_RATE
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            fault_injection,
+            fault_lost_response_rate,
+            f64,
+            FAULT_LOST_RESPONSE_RATE
+        );
+    }
+}
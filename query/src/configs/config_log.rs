@@ -16,10 +16,12 @@ use structopt::StructOpt;
 use structopt_toml::StructOptToml;
 
 use crate::configs::Config;
+use crate::configs::ConfigSources;
 
 // Log env.
 pub const LOG_LEVEL: &str = "LOG_LEVEL";
 pub const LOG_DIR: &str = "LOG_DIR";
+pub const TRACING_OTLP_ENDPOINT: &str = "TRACING_OTLP_ENDPOINT";
 
 /// Log config group.
 /// serde(default) make the toml de to default working.
@@ -34,6 +36,10 @@ pub struct LogConfig {
     #[structopt(required = false, long, env = LOG_DIR, default_value = "./_logs", help = "Log file dir")]
     #[serde(default)]
     pub log_dir: String,
+
+    #[structopt(long, env = TRACING_OTLP_ENDPOINT, default_value = "", help = "OTLP endpoint to export the spans to, empty to disable")]
+    #[serde(default)]
+    pub tracing_otlp_endpoint: String,
 }
 
 impl LogConfig {
@@ -41,11 +47,20 @@ impl LogConfig {
         LogConfig {
             log_level: "INFO".to_string(),
             log_dir: "./_logs".to_string(),
+            tracing_otlp_endpoint: "".to_string(),
         }
     }
 
-    pub fn load_from_env(mut_config: &mut Config) {
-        env_helper!(mut_config, log, log_level, String, LOG_LEVEL);
-        env_helper!(mut_config, log, log_dir, String, LOG_DIR);
+    pub fn load_from_env(mut_config: &mut Config, sources: &mut ConfigSources) {
+        env_helper!(mut_config, sources, log, log_level, String, LOG_LEVEL);
+        env_helper!(mut_config, sources, log, log_dir, String, LOG_DIR);
+        env_helper!(
+            mut_config,
+            sources,
+            log,
+            tracing_otlp_endpoint,
+            String,
+            TRACING_OTLP_ENDPOINT
+        );
     }
 }
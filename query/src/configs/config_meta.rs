@@ -18,9 +18,14 @@ use structopt::StructOpt;
 use structopt_toml::StructOptToml;
 
 use crate::configs::Config;
+use crate::configs::ConfigSources;
 
 // Meta env.
 pub const META_ADDRESS: &str = "META_ADDRESS";
+pub const META_EMBEDDED: &str = "META_EMBEDDED";
+pub const META_EMBEDDED_DIR: &str = "META_EMBEDDED_DIR";
+pub const META_CACHE_DIR: &str = "META_CACHE_DIR";
+pub const META_REPLICA_STALENESS_MS: &str = "META_REPLICA_STALENESS_MS";
 pub const META_USERNAME: &str = "META_USERNAME";
 pub const META_PASSWORD: &str = "META_PASSWORD";
 pub const META_RPC_TLS_SERVER_ROOT_CA_CERT: &str = "META_RPC_TLS_SERVER_ROOT_CA_CERT";
@@ -34,6 +39,26 @@ pub struct MetaConfig {
     #[serde(default)]
     pub meta_address: String,
 
+    #[structopt(
+        long,
+        env = META_EMBEDDED,
+        help = "Run the meta store in the query process, instead of connecting to metasrv"
+    )]
+    #[serde(default)]
+    pub meta_embedded: bool,
+
+    #[structopt(long, env = META_EMBEDDED_DIR, default_value = "./_meta_embedded", help = "Directory of the embedded meta store data")]
+    #[serde(default)]
+    pub meta_embedded_dir: String,
+
+    #[structopt(long, env = META_CACHE_DIR, default_value = "", help = "Directory to keep the catalog on the local disk, a restarted node serves it before synced with the meta store, empty to disable")]
+    #[serde(default)]
+    pub meta_cache_dir: String,
+
+    #[structopt(long, env = META_REPLICA_STALENESS_MS, default_value = "0", help = "Serve the database and table listings from a local replica of the catalog at most this stale, 0 to disable")]
+    #[serde(default)]
+    pub meta_replica_staleness_ms: u64,
+
     #[structopt(long, env = META_USERNAME, default_value = "", help = "MetaStore backend user name")]
     #[serde(default)]
     pub meta_username: String,
@@ -72,6 +97,10 @@ impl MetaConfig {
     pub fn default() -> Self {
         MetaConfig {
             meta_address: "".to_string(),
+            meta_embedded: false,
+            meta_embedded_dir: "./_meta_embedded".to_string(),
+            meta_cache_dir: "".to_string(),
+            meta_replica_staleness_ms: 0,
             meta_username: "root".to_string(),
             meta_password: "".to_string(),
             meta_client_timeout_in_second: 10,
@@ -80,12 +109,66 @@ impl MetaConfig {
         }
     }
 
-    pub fn load_from_env(mut_config: &mut Config) {
-        env_helper!(mut_config, meta, meta_address, String, META_ADDRESS);
-        env_helper!(mut_config, meta, meta_username, String, META_USERNAME);
-        env_helper!(mut_config, meta, meta_password, String, META_PASSWORD);
+    pub fn load_from_env(mut_config: &mut Config, sources: &mut ConfigSources) {
+        env_helper!(
+            mut_config,
+            sources,
+            meta,
+            meta_address,
+            String,
+            META_ADDRESS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            meta,
+            meta_embedded,
+            bool,
+            META_EMBEDDED
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            meta,
+            meta_embedded_dir,
+            String,
+            META_EMBEDDED_DIR
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            meta,
+            meta_cache_dir,
+            String,
+            META_CACHE_DIR
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            meta,
+            meta_replica_staleness_ms,
+            u64,
+            META_REPLICA_STALENESS_MS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            meta,
+            meta_username,
+            String,
+            META_USERNAME
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            meta,
+            meta_password,
+            String,
+            META_PASSWORD
+        );
         env_helper!(
             mut_config,
+            sources,
             meta,
             rpc_tls_meta_server_root_ca_cert,
             String,
@@ -93,6 +176,7 @@ impl MetaConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             meta,
             rpc_tls_meta_service_domain_name,
             String,
@@ -105,6 +189,14 @@ impl fmt::Debug for MetaConfig {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{{")?;
         write!(f, "meta_address: \"{}\", ", self.meta_address)?;
+        write!(f, "meta_embedded: {}, ", self.meta_embedded)?;
+        write!(f, "meta_embedded_dir: \"{}\", ", self.meta_embedded_dir)?;
+        write!(f, "meta_cache_dir: \"{}\", ", self.meta_cache_dir)?;
+        write!(
+            f,
+            "meta_replica_staleness_ms: {}, ",
+            self.meta_replica_staleness_ms
+        )?;
         write!(f, "meta_user: \"{}\", ", self.meta_username)?;
         write!(f, "meta_password: \"******\"")?;
         write!(f, "}}")
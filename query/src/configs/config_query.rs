@@ -16,14 +16,27 @@ use structopt::StructOpt;
 use structopt_toml::StructOptToml;
 
 use crate::configs::Config;
+use crate::configs::ConfigSources;
 
 // Query env.
 pub const QUERY_TENANT: &str = "QUERY_TENANT";
+const QUERY_MULTI_TENANT: &str = "QUERY_MULTI_TENANT";
 pub const QUERY_NAMESPACE: &str = "QUERY_NAMESPACE";
+const QUERY_CLUSTER_HEARTBEAT_TIMEOUT_SECS: &str = "QUERY_CLUSTER_HEARTBEAT_TIMEOUT_SECS";
 pub const QUERY_NUM_CPUS: &str = "QUERY_NUM_CPUS";
 pub const QUERY_MYSQL_HANDLER_HOST: &str = "QUERY_MYSQL_HANDLER_HOST";
 pub const QUERY_MYSQL_HANDLER_PORT: &str = "QUERY_MYSQL_HANDLER_PORT";
 pub const QUERY_MAX_ACTIVE_SESSIONS: &str = "QUERY_MAX_ACTIVE_SESSIONS";
+pub const QUERY_IDLE_SESSION_TIMEOUT_SECS: &str = "QUERY_IDLE_SESSION_TIMEOUT_SECS";
+const QUERY_STATISTICS_REFRESH_INTERVAL_SECS: &str = "QUERY_STATISTICS_REFRESH_INTERVAL_SECS";
+const QUERY_STATISTICS_REFRESH_CHANGED_RATIO: &str = "QUERY_STATISTICS_REFRESH_CHANGED_RATIO";
+const QUERY_RESULT_CACHE_SIZE_MB: &str = "QUERY_RESULT_CACHE_SIZE_MB";
+const QUERY_PAGES_SIZE_MB: &str = "QUERY_PAGES_SIZE_MB";
+const QUERY_LOG_TABLE: &str = "QUERY_LOG_TABLE";
+const QUERY_MAX_SERVER_MEMORY_USAGE_MB: &str = "QUERY_MAX_SERVER_MEMORY_USAGE_MB";
+const QUERY_MAX_RUNNING_QUERIES: &str = "QUERY_MAX_RUNNING_QUERIES";
+const QUERY_MAX_RUNNING_QUERIES_PER_USER: &str = "QUERY_MAX_RUNNING_QUERIES_PER_USER";
+const QUERY_QUEUED_QUERY_TIMEOUT_SECS: &str = "QUERY_QUEUED_QUERY_TIMEOUT_SECS";
 pub const QUERY_CLICKHOUSE_HANDLER_HOST: &str = "QUERY_CLICKHOUSE_HANDLER_HOST";
 pub const QUERY_CLICKHOUSE_HANDLER_PORT: &str = "QUERY_CLICKHOUSE_HANDLER_PORT";
 pub const QUERY_FLIGHT_API_ADDRESS: &str = "QUERY_FLIGHT_API_ADDRESS";
@@ -48,10 +61,22 @@ pub struct QueryConfig {
     #[serde(default)]
     pub tenant: String,
 
+    #[structopt(
+        long,
+        env = QUERY_MULTI_TENANT,
+        help = "Serve many tenants, a user logs in as user@tenant, the others log in to the tenant of --tenant"
+    )]
+    #[serde(default)]
+    pub multi_tenant: bool,
+
     #[structopt(long, env = QUERY_NAMESPACE, default_value = "", help = "Namespace for construct the cluster")]
     #[serde(default)]
     pub namespace: String,
 
+    #[structopt(long, env = QUERY_CLUSTER_HEARTBEAT_TIMEOUT_SECS, default_value = "10", help = "Seconds a node stays in the cluster after its last heartbeat, a node heartbeats every 1/3 to 2/3 of it")]
+    #[serde(default)]
+    pub cluster_heartbeat_timeout_secs: u64,
+
     #[structopt(long, env = QUERY_NUM_CPUS, default_value = "0")]
     #[serde(default)]
     pub num_cpus: u64,
@@ -76,6 +101,46 @@ pub struct QueryConfig {
     #[serde(default)]
     pub max_active_sessions: u64,
 
+    #[structopt(long, env = QUERY_IDLE_SESSION_TIMEOUT_SECS, default_value = "3600", help = "Seconds a session can stay without running a query before it is closed, 0 to disable")]
+    #[serde(default)]
+    pub idle_session_timeout_secs: u64,
+
+    #[structopt(long, env = QUERY_STATISTICS_REFRESH_INTERVAL_SECS, default_value = "300", help = "Interval in seconds to refresh the stale statistics of the analyzed tables, 0 to disable")]
+    #[serde(default)]
+    pub statistics_refresh_interval_secs: u64,
+
+    #[structopt(long, env = QUERY_STATISTICS_REFRESH_CHANGED_RATIO, default_value = "0.2", help = "The statistics of a table are stale once the rows changed since ANALYZE exceed this ratio of its rows")]
+    #[serde(default)]
+    pub statistics_refresh_changed_ratio: f64,
+
+    #[structopt(long, env = QUERY_RESULT_CACHE_SIZE_MB, default_value = "256", help = "Max megabytes of the query results cached in memory, 0 to disable")]
+    #[serde(default)]
+    pub result_cache_size_mb: u64,
+
+    #[structopt(long, env = QUERY_PAGES_SIZE_MB, default_value = "256", help = "Max megabytes of the paginated HTTP query results kept in memory, the queries over it fail, 0 means unlimited")]
+    #[serde(default)]
+    pub query_pages_size_mb: u64,
+
+    #[structopt(long, env = QUERY_LOG_TABLE, default_value = "", help = "The FUSE table `database.table` the query log is persisted into, empty to only keep the latest queries in memory")]
+    #[serde(default)]
+    pub query_log_table: String,
+
+    #[structopt(long, env = QUERY_MAX_SERVER_MEMORY_USAGE_MB, default_value = "0", help = "Max megabytes held by the operators of all the queries, the query allocating over it fails, 0 means unlimited")]
+    #[serde(default)]
+    pub max_server_memory_usage_mb: u64,
+
+    #[structopt(long, env = QUERY_MAX_RUNNING_QUERIES, default_value = "0", help = "Max queries of the clients running at the same time, the others are queued, 0 means unlimited")]
+    #[serde(default)]
+    pub max_running_queries: u64,
+
+    #[structopt(long, env = QUERY_MAX_RUNNING_QUERIES_PER_USER, default_value = "0", help = "Max queries of a user running at the same time, the others are queued, 0 means unlimited")]
+    #[serde(default)]
+    pub max_running_queries_per_user: u64,
+
+    #[structopt(long, env = QUERY_QUEUED_QUERY_TIMEOUT_SECS, default_value = "60", help = "Seconds a query is queued at most before it fails, 0 to fail the queries over the limits at once")]
+    #[serde(default)]
+    pub queued_query_timeout_secs: u64,
+
     #[structopt(
     long,
     env = QUERY_CLICKHOUSE_HANDLER_HOST,
@@ -167,11 +232,23 @@ impl QueryConfig {
     pub fn default() -> Self {
         QueryConfig {
             tenant: "".to_string(),
+            multi_tenant: false,
             namespace: "".to_string(),
+            cluster_heartbeat_timeout_secs: 10,
             num_cpus: 8,
             mysql_handler_host: "127.0.0.1".to_string(),
             mysql_handler_port: 3307,
             max_active_sessions: 256,
+            idle_session_timeout_secs: 3600,
+            statistics_refresh_interval_secs: 300,
+            statistics_refresh_changed_ratio: 0.2,
+            result_cache_size_mb: 256,
+            query_pages_size_mb: 256,
+            query_log_table: "".to_string(),
+            max_server_memory_usage_mb: 0,
+            max_running_queries: 0,
+            max_running_queries_per_user: 0,
+            queued_query_timeout_secs: 60,
             clickhouse_handler_host: "127.0.0.1".to_string(),
             clickhouse_handler_port: 9000,
             flight_api_address: "127.0.0.1:9090".to_string(),
@@ -187,12 +264,36 @@ impl QueryConfig {
         }
     }
 
-    pub fn load_from_env(mut_config: &mut Config) {
-        env_helper!(mut_config, query, tenant, String, QUERY_TENANT);
-        env_helper!(mut_config, query, namespace, String, QUERY_NAMESPACE);
-        env_helper!(mut_config, query, num_cpus, u64, QUERY_NUM_CPUS);
+    pub fn load_from_env(mut_config: &mut Config, sources: &mut ConfigSources) {
+        env_helper!(mut_config, sources, query, tenant, String, QUERY_TENANT);
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            multi_tenant,
+            bool,
+            QUERY_MULTI_TENANT
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            namespace,
+            String,
+            QUERY_NAMESPACE
+        );
         env_helper!(
             mut_config,
+            sources,
+            query,
+            cluster_heartbeat_timeout_secs,
+            u64,
+            QUERY_CLUSTER_HEARTBEAT_TIMEOUT_SECS
+        );
+        env_helper!(mut_config, sources, query, num_cpus, u64, QUERY_NUM_CPUS);
+        env_helper!(
+            mut_config,
+            sources,
             query,
             mysql_handler_host,
             String,
@@ -200,6 +301,7 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             query,
             mysql_handler_port,
             u16,
@@ -207,6 +309,7 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             query,
             max_active_sessions,
             u64,
@@ -214,6 +317,87 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
+            query,
+            idle_session_timeout_secs,
+            u64,
+            QUERY_IDLE_SESSION_TIMEOUT_SECS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            statistics_refresh_interval_secs,
+            u64,
+            QUERY_STATISTICS_REFRESH_INTERVAL_SECS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            statistics_refresh_changed_ratio,
+            f64,
+            QUERY_STATISTICS_REFRESH_CHANGED_RATIO
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            result_cache_size_mb,
+            u64,
+            QUERY_RESULT_CACHE_SIZE_MB
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            query_pages_size_mb,
+            u64,
+            QUERY_PAGES_SIZE_MB
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            query_log_table,
+            String,
+            QUERY_LOG_TABLE
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            max_server_memory_usage_mb,
+            u64,
+            QUERY_MAX_SERVER_MEMORY_USAGE_MB
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            max_running_queries,
+            u64,
+            QUERY_MAX_RUNNING_QUERIES
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            max_running_queries_per_user,
+            u64,
+            QUERY_MAX_RUNNING_QUERIES_PER_USER
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            query,
+            queued_query_timeout_secs,
+            u64,
+            QUERY_QUEUED_QUERY_TIMEOUT_SECS
+        );
+        env_helper!(
+            mut_config,
+            sources,
             query,
             clickhouse_handler_host,
             String,
@@ -221,6 +405,7 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             query,
             clickhouse_handler_port,
             u16,
@@ -228,6 +413,7 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             query,
             flight_api_address,
             String,
@@ -235,6 +421,7 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             query,
             http_api_address,
             String,
@@ -242,6 +429,7 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             query,
             metric_api_address,
             String,
@@ -251,6 +439,7 @@ impl QueryConfig {
         // for api http service
         env_helper!(
             mut_config,
+            sources,
             query,
             api_tls_server_cert,
             String,
@@ -259,6 +448,7 @@ impl QueryConfig {
 
         env_helper!(
             mut_config,
+            sources,
             query,
             api_tls_server_key,
             String,
@@ -268,6 +458,7 @@ impl QueryConfig {
         // for query rpc server
         env_helper!(
             mut_config,
+            sources,
             query,
             rpc_tls_server_cert,
             String,
@@ -276,6 +467,7 @@ impl QueryConfig {
 
         env_helper!(
             mut_config,
+            sources,
             query,
             rpc_tls_server_key,
             String,
@@ -285,6 +477,7 @@ impl QueryConfig {
         // for query rpc client
         env_helper!(
             mut_config,
+            sources,
             query,
             rpc_tls_query_server_root_ca_cert,
             String,
@@ -292,6 +485,7 @@ impl QueryConfig {
         );
         env_helper!(
             mut_config,
+            sources,
             query,
             rpc_tls_query_service_domain_name,
             String,
@@ -0,0 +1,160 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::configs::Config;
+
+/// The values of these keys are never printed.
+const SECRET_KEYS: &[&str] = &[
+    "meta.meta_password",
+    "storage.s3.access_key_id",
+    "storage.s3.secret_access_key",
+    "storage.azure_blob.account_key",
+    "storage.azure_blob.sas_token",
+    "storage.hdfs.delegation_token",
+    "storage.encryption.keys",
+    "storage.connection.master_keys",
+];
+
+/// Where the value of a config key comes from, each one overrides the former ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigSource {
+    Default,
+    File,
+    Args,
+    Env,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File => write!(f, "file"),
+            ConfigSource::Args => write!(f, "args"),
+            ConfigSource::Env => write!(f, "env"),
+        }
+    }
+}
+
+/// The source of each key of the effective config, as `section.key`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigSources {
+    sources: BTreeMap<String, ConfigSource>,
+}
+
+impl ConfigSources {
+    pub fn create() -> Self {
+        ConfigSources::default()
+    }
+
+    pub fn set(&mut self, key: &str, source: ConfigSource) {
+        self.sources.insert(key.to_string(), source);
+    }
+
+    /// The keys never set come from the defaults.
+    pub fn get(&self, key: &str) -> ConfigSource {
+        self.sources
+            .get(key)
+            .copied()
+            .unwrap_or(ConfigSource::Default)
+    }
+
+    /// The config as TOML, each key is followed by the comment of its source, e.g.
+    /// `log_level = "DEBUG" # env`. The secrets are masked.
+    pub fn render(&self, conf: &Config) -> Result<String> {
+        let mut sections: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (key, value) in flatten_config(conf)? {
+            let (section, name) = key.rsplit_once('.').unwrap_or(("", key.as_str()));
+            let value = match value {
+                toml::Value::String(s) if !s.is_empty() && SECRET_KEYS.contains(&key.as_str()) => {
+                    "\"******\"".to_string()
+                }
+                value => value.to_string(),
+            };
+            sections
+                .entry(section.to_string())
+                .or_default()
+                .push(format!("{} = {} # {}", name, value, self.get(&key)));
+        }
+
+        let mut rendered = String::new();
+        for (section, lines) in sections {
+            if !section.is_empty() {
+                let _ = writeln!(rendered, "\n[{}]", section);
+            }
+            for line in lines {
+                let _ = writeln!(rendered, "{}", line);
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+/// The keys of the config and their values, as `section.key`.
+pub fn flatten_config(conf: &Config) -> Result<BTreeMap<String, toml::Value>> {
+    let value = toml::Value::try_from(conf)
+        .map_err(|e| ErrorCode::UnknownException(format!("Cannot serialize config: {}", e)))?;
+
+    let mut flattened = BTreeMap::new();
+    flatten_value("", value, &mut flattened);
+    Ok(flattened)
+}
+
+/// The keys of a config file, a key unknown to the config is an error instead of being ignored,
+/// which would silently leave the default of the misspelled key.
+pub fn config_file_keys(toml_str: &str) -> Result<Vec<String>> {
+    let value = toml_str
+        .parse::<toml::Value>()
+        .map_err(|e| ErrorCode::BadArguments(format!("Cannot parse config file: {}", e)))?;
+
+    let mut flattened = BTreeMap::new();
+    flatten_value("", value, &mut flattened);
+
+    let known = flatten_config(&Config::default())?;
+    let unknown = flattened
+        .keys()
+        .filter(|key| !known.contains_key(*key))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !unknown.is_empty() {
+        return Err(ErrorCode::BadArguments(format!(
+            "Unknown config keys: {}",
+            unknown.join(", ")
+        )));
+    }
+    Ok(flattened.into_keys().collect())
+}
+
+fn flatten_value(prefix: &str, value: toml::Value, flattened: &mut BTreeMap<String, toml::Value>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = match prefix.is_empty() {
+                    true => key,
+                    false => format!("{}.{}", prefix, key),
+                };
+                flatten_value(&key, value, flattened);
+            }
+        }
+        value => {
+            flattened.insert(prefix.to_string(), value);
+        }
+    }
+}
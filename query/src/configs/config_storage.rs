@@ -19,8 +19,19 @@ use structopt::StructOpt;
 use structopt_toml::StructOptToml;
 
 use crate::configs::Config;
+use crate::configs::ConfigSources;
 
 pub const STORAGE_TYPE: &str = "STORAGE_TYPE";
+const STORAGE_POLICY_INTERVAL_SECS: &str = "STORAGE_POLICY_INTERVAL_SECS";
+const STORAGE_PURGE_INTERVAL_SECS: &str = "STORAGE_PURGE_INTERVAL_SECS";
+const STORAGE_IO_BACKGROUND_MAX_REQUESTS: &str = "STORAGE_IO_BACKGROUND_MAX_REQUESTS";
+const STORAGE_IO_BACKGROUND_MAX_WAIT_MS: &str = "STORAGE_IO_BACKGROUND_MAX_WAIT_MS";
+const STORAGE_COLUMN_CACHE_SIZE_MB: &str = "STORAGE_COLUMN_CACHE_SIZE_MB";
+const STORAGE_IO_MAX_RETRIES: &str = "STORAGE_IO_MAX_RETRIES";
+const STORAGE_IO_RETRY_INITIAL_BACKOFF_MS: &str = "STORAGE_IO_RETRY_INITIAL_BACKOFF_MS";
+const STORAGE_IO_RETRY_MAX_BACKOFF_MS: &str = "STORAGE_IO_RETRY_MAX_BACKOFF_MS";
+const STORAGE_IO_MAX_BYTES_PER_SECOND: &str = "STORAGE_IO_MAX_BYTES_PER_SECOND";
+const STORAGE_IO_MAX_REQUESTS_PER_SECOND: &str = "STORAGE_IO_MAX_REQUESTS_PER_SECOND";
 
 // Disk Storage env.
 pub const DISK_STORAGE_DATA_PATH: &str = "DISK_STORAGE_DATA_PATH";
@@ -31,10 +42,34 @@ const S3_STORAGE_ACCESS_KEY_ID: &str = "S3_STORAGE_ACCESS_KEY_ID";
 const S3_STORAGE_SECRET_ACCESS_KEY: &str = "S3_STORAGE_SECRET_ACCESS_KEY";
 const S3_STORAGE_BUCKET: &str = "S3_STORAGE_BUCKET";
 
+// Azure Blob Storage env.
+const AZURE_BLOB_STORAGE_ACCOUNT: &str = "AZURE_BLOB_STORAGE_ACCOUNT";
+const AZURE_BLOB_STORAGE_CONTAINER: &str = "AZURE_BLOB_STORAGE_CONTAINER";
+const AZURE_BLOB_STORAGE_ACCOUNT_KEY: &str = "AZURE_BLOB_STORAGE_ACCOUNT_KEY";
+const AZURE_BLOB_STORAGE_SAS_TOKEN: &str = "AZURE_BLOB_STORAGE_SAS_TOKEN";
+
+// HDFS Storage env.
+const HDFS_STORAGE_NAME_NODE: &str = "HDFS_STORAGE_NAME_NODE";
+const HDFS_STORAGE_ROOT: &str = "HDFS_STORAGE_ROOT";
+const HDFS_STORAGE_USER: &str = "HDFS_STORAGE_USER";
+const HDFS_STORAGE_DELEGATION_TOKEN: &str = "HDFS_STORAGE_DELEGATION_TOKEN";
+const HDFS_STORAGE_KERBEROS_PRINCIPAL: &str = "HDFS_STORAGE_KERBEROS_PRINCIPAL";
+const HDFS_STORAGE_KERBEROS_KEYTAB: &str = "HDFS_STORAGE_KERBEROS_KEYTAB";
+
+// Storage encryption env.
+const ENCRYPTION_STORAGE_KEYS: &str = "ENCRYPTION_STORAGE_KEYS";
+const ENCRYPTION_STORAGE_KEY_COMMAND: &str = "ENCRYPTION_STORAGE_KEY_COMMAND";
+
+// Storage connection env.
+const CONNECTION_STORAGE_MASTER_KEYS: &str = "CONNECTION_STORAGE_MASTER_KEYS";
+const CONNECTION_STORAGE_MASTER_KEY_COMMAND: &str = "CONNECTION_STORAGE_MASTER_KEY_COMMAND";
+
 #[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 pub enum StorageType {
     Disk,
     S3,
+    AzureBlob,
+    Hdfs,
 }
 
 // Implement the trait
@@ -45,6 +80,8 @@ impl FromStr for StorageType {
         match s {
             "disk" => Ok(StorageType::Disk),
             "s3" => Ok(StorageType::S3),
+            "azblob" => Ok(StorageType::AzureBlob),
+            "hdfs" => Ok(StorageType::Hdfs),
             _ => Err("no match for storage type"),
         }
     }
@@ -105,16 +142,218 @@ impl fmt::Debug for S3StorageConfig {
     }
 }
 
+#[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
+pub struct AzureBlobStorageConfig {
+    #[structopt(long, env = AZURE_BLOB_STORAGE_ACCOUNT, default_value = "", help = "Account for Azure blob storage")]
+    #[serde(default)]
+    pub account: String,
+
+    #[structopt(long, env = AZURE_BLOB_STORAGE_CONTAINER, default_value = "", help = "Container to use for Azure blob storage")]
+    #[serde(default)]
+    pub container: String,
+
+    #[structopt(long, env = AZURE_BLOB_STORAGE_ACCOUNT_KEY, default_value = "", help = "Account key for Azure blob storage, the requests are signed with SharedKey")]
+    #[serde(default)]
+    pub account_key: String,
+
+    #[structopt(long, env = AZURE_BLOB_STORAGE_SAS_TOKEN, default_value = "", help = "SAS token for Azure blob storage, used instead of the account key if set")]
+    #[serde(default)]
+    pub sas_token: String,
+}
+
+impl AzureBlobStorageConfig {
+    pub fn default() -> Self {
+        AzureBlobStorageConfig {
+            account: "".to_string(),
+            container: "".to_string(),
+            account_key: "".to_string(),
+            sas_token: "".to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for AzureBlobStorageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, "azure_blob.storage.account: \"{}\", ", self.account)?;
+        write!(f, "azure_blob.storage.container: \"{}\", ", self.container)?;
+        write!(f, "}}")
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
+pub struct HdfsStorageConfig {
+    #[structopt(long, env = HDFS_STORAGE_NAME_NODE, default_value = "", help = "WebHDFS endpoint of the name node for HDFS storage, e.g. http://127.0.0.1:9870")]
+    #[serde(default)]
+    pub name_node: String,
+
+    #[structopt(long, env = HDFS_STORAGE_ROOT, default_value = "", help = "Root directory of the data for HDFS storage")]
+    #[serde(default)]
+    pub root: String,
+
+    #[structopt(long, env = HDFS_STORAGE_USER, default_value = "", help = "User for HDFS storage with simple authentication")]
+    #[serde(default)]
+    pub user: String,
+
+    #[structopt(long, env = HDFS_STORAGE_DELEGATION_TOKEN, default_value = "", help = "Delegation token for HDFS storage, used instead of the user if set")]
+    #[serde(default)]
+    pub delegation_token: String,
+
+    #[structopt(long, env = HDFS_STORAGE_KERBEROS_PRINCIPAL, default_value = "", help = "Kerberos principal for HDFS storage, the requests are authenticated by SPNEGO if set")]
+    #[serde(default)]
+    pub kerberos_principal: String,
+
+    #[structopt(long, env = HDFS_STORAGE_KERBEROS_KEYTAB, default_value = "", help = "Keytab file of the kerberos principal for HDFS storage")]
+    #[serde(default)]
+    pub kerberos_keytab: String,
+}
+
+impl HdfsStorageConfig {
+    pub fn default() -> Self {
+        HdfsStorageConfig {
+            name_node: "".to_string(),
+            root: "".to_string(),
+            user: "".to_string(),
+            delegation_token: "".to_string(),
+            kerberos_principal: "".to_string(),
+            kerberos_keytab: "".to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for HdfsStorageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(f, "hdfs.storage.name_node: \"{}\", ", self.name_node)?;
+        write!(f, "hdfs.storage.root: \"{}\", ", self.root)?;
+        write!(f, "hdfs.storage.user: \"{}\", ", self.user)?;
+        write!(
+            f,
+            "hdfs.storage.kerberos_principal: \"{}\", ",
+            self.kerberos_principal
+        )?;
+        write!(f, "}}")
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
+pub struct EncryptionStorageConfig {
+    #[structopt(long, env = ENCRYPTION_STORAGE_KEYS, default_value = "", help = "Keys to encrypt the storage objects with, as version:base64 key pairs separated by commas. The latest version encrypts the new objects, empty to disable")]
+    #[serde(default)]
+    pub keys: String,
+
+    #[structopt(long, env = ENCRYPTION_STORAGE_KEY_COMMAND, default_value = "", help = "Command printing a key to encrypt the storage objects with as version:base64 key, run with `current` or a key version as its argument, e.g. a KMS client. Used instead of the keys if set")]
+    #[serde(default)]
+    pub key_command: String,
+}
+
+impl EncryptionStorageConfig {
+    pub fn default() -> Self {
+        EncryptionStorageConfig {
+            keys: "".to_string(),
+            key_command: "".to_string(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.keys.is_empty() || !self.key_command.is_empty()
+    }
+}
+
+impl fmt::Debug for EncryptionStorageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(
+            f,
+            "encryption.storage.key_command: \"{}\", ",
+            self.key_command
+        )?;
+        write!(f, "}}")
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
+pub struct ConnectionStorageConfig {
+    #[structopt(long, env = CONNECTION_STORAGE_MASTER_KEYS, default_value = "", help = "Master keys to encrypt the credentials of CREATE CONNECTION with, as version:base64 key pairs separated by commas. The latest version encrypts the new connections, empty to disable the connections")]
+    #[serde(default)]
+    pub master_keys: String,
+
+    #[structopt(long, env = CONNECTION_STORAGE_MASTER_KEY_COMMAND, default_value = "", help = "Command printing a master key of the connections as version:base64 key, run with `current` or a key version as its argument, e.g. a KMS client. Used instead of the master keys if set")]
+    #[serde(default)]
+    pub master_key_command: String,
+}
+
+impl ConnectionStorageConfig {
+    pub fn default() -> Self {
+        ConnectionStorageConfig {
+            master_keys: "".to_string(),
+            master_key_command: "".to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for ConnectionStorageConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{")?;
+        write!(
+            f,
+            "connection.storage.master_key_command: \"{}\", ",
+            self.master_key_command
+        )?;
+        write!(f, "}}")
+    }
+}
+
 /// Storage config group.
 /// serde(default) make the toml de to default working.
 #[derive(
     Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml,
 )]
 pub struct StorageConfig {
-    #[structopt(long, env = STORAGE_TYPE, default_value = "", help = "Current storage type: disk|s3")]
+    #[structopt(long, env = STORAGE_TYPE, default_value = "", help = "Current storage type: disk|s3|azblob|hdfs")]
     #[serde(default)]
     pub storage_type: String,
 
+    #[structopt(long, env = STORAGE_POLICY_INTERVAL_SECS, default_value = "3600", help = "Interval in seconds to apply the storage policies of the tables, 0 to disable")]
+    #[serde(default)]
+    pub storage_policy_interval_secs: u64,
+
+    #[structopt(long, env = STORAGE_PURGE_INTERVAL_SECS, default_value = "60", help = "Interval in seconds to remove the data of the dropped tables, 0 to disable")]
+    #[serde(default)]
+    pub purge_interval_secs: u64,
+
+    #[structopt(long, env = STORAGE_IO_BACKGROUND_MAX_REQUESTS, default_value = "4", help = "Max storage requests of the background jobs running at the same time")]
+    #[serde(default)]
+    pub io_background_max_requests: u64,
+
+    #[structopt(long, env = STORAGE_IO_BACKGROUND_MAX_WAIT_MS, default_value = "1000", help = "Max milliseconds a storage request of the background jobs waits for the requests of the queries")]
+    #[serde(default)]
+    pub io_background_max_wait_ms: u64,
+
+    #[structopt(long, env = STORAGE_COLUMN_CACHE_SIZE_MB, default_value = "256", help = "Max megabytes of the decoded columns cached in memory, 0 to disable")]
+    #[serde(default)]
+    pub column_cache_size_mb: u64,
+
+    #[structopt(long, env = STORAGE_IO_MAX_RETRIES, default_value = "3", help = "Max retries of a storage request failed with a transient error, 0 to disable")]
+    #[serde(default)]
+    pub io_max_retries: u32,
+
+    #[structopt(long, env = STORAGE_IO_RETRY_INITIAL_BACKOFF_MS, default_value = "100", help = "Milliseconds to wait before the first retry of a storage request, doubled for each next retry")]
+    #[serde(default)]
+    pub io_retry_initial_backoff_ms: u64,
+
+    #[structopt(long, env = STORAGE_IO_RETRY_MAX_BACKOFF_MS, default_value = "5000", help = "Max milliseconds to wait before a retry of a storage request")]
+    #[serde(default)]
+    pub io_retry_max_backoff_ms: u64,
+
+    #[structopt(long, env = STORAGE_IO_MAX_BYTES_PER_SECOND, default_value = "0", help = "Max bytes all the queries and background jobs of the node read from and write to the storage a second, 0 means unlimited")]
+    #[serde(default)]
+    pub io_max_bytes_per_second: u64,
+
+    #[structopt(long, env = STORAGE_IO_MAX_REQUESTS_PER_SECOND, default_value = "0", help = "Max storage requests all the queries and background jobs of the node send a second, 0 means unlimited")]
+    #[serde(default)]
+    pub io_max_requests_per_second: u64,
+
     // Disk storage backend config.
     #[structopt(flatten)]
     pub disk: DiskStorageConfig,
@@ -122,45 +361,299 @@ pub struct StorageConfig {
     // S3 storage backend config.
     #[structopt(flatten)]
     pub s3: S3StorageConfig,
+
+    // Azure blob storage backend config.
+    #[structopt(flatten)]
+    pub azure_blob: AzureBlobStorageConfig,
+
+    // HDFS storage backend config.
+    #[structopt(flatten)]
+    pub hdfs: HdfsStorageConfig,
+
+    // Client-side encryption of the objects, for all the backends.
+    #[structopt(flatten)]
+    pub encryption: EncryptionStorageConfig,
+
+    // Master keys of the credentials of the connections.
+    #[structopt(flatten)]
+    pub connection: ConnectionStorageConfig,
 }
 
 impl StorageConfig {
     pub fn default() -> Self {
         StorageConfig {
             storage_type: "disk".to_string(),
+            storage_policy_interval_secs: 3600,
+            purge_interval_secs: 60,
+            io_background_max_requests: 4,
+            io_background_max_wait_ms: 1000,
+            column_cache_size_mb: 256,
+            io_max_retries: 3,
+            io_retry_initial_backoff_ms: 100,
+            io_retry_max_backoff_ms: 5000,
+            io_max_bytes_per_second: 0,
+            io_max_requests_per_second: 0,
             disk: DiskStorageConfig::default(),
             s3: S3StorageConfig::default(),
+            azure_blob: AzureBlobStorageConfig::default(),
+            hdfs: HdfsStorageConfig::default(),
+            encryption: EncryptionStorageConfig::default(),
+            connection: ConnectionStorageConfig::default(),
         }
     }
 
-    pub fn load_from_env(mut_config: &mut Config) {
-        env_helper!(mut_config, storage, storage_type, String, STORAGE_TYPE);
+    pub fn load_from_env(mut_config: &mut Config, sources: &mut ConfigSources) {
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            storage_type,
+            String,
+            STORAGE_TYPE
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            storage_policy_interval_secs,
+            u64,
+            STORAGE_POLICY_INTERVAL_SECS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            purge_interval_secs,
+            u64,
+            STORAGE_PURGE_INTERVAL_SECS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            io_background_max_requests,
+            u64,
+            STORAGE_IO_BACKGROUND_MAX_REQUESTS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            io_background_max_wait_ms,
+            u64,
+            STORAGE_IO_BACKGROUND_MAX_WAIT_MS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            column_cache_size_mb,
+            u64,
+            STORAGE_COLUMN_CACHE_SIZE_MB
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            io_max_retries,
+            u32,
+            STORAGE_IO_MAX_RETRIES
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            io_retry_initial_backoff_ms,
+            u64,
+            STORAGE_IO_RETRY_INITIAL_BACKOFF_MS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            io_retry_max_backoff_ms,
+            u64,
+            STORAGE_IO_RETRY_MAX_BACKOFF_MS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            io_max_bytes_per_second,
+            u64,
+            STORAGE_IO_MAX_BYTES_PER_SECOND
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage,
+            io_max_requests_per_second,
+            u64,
+            STORAGE_IO_MAX_REQUESTS_PER_SECOND
+        );
 
         // DISK.
         env_helper!(
-            mut_config.storage,
-            disk,
+            mut_config,
+            sources,
+            storage.disk,
             data_path,
             String,
             DISK_STORAGE_DATA_PATH
         );
 
         // S3.
-        env_helper!(mut_config.storage, s3, region, String, S3_STORAGE_REGION);
         env_helper!(
-            mut_config.storage,
-            s3,
+            mut_config,
+            sources,
+            storage.s3,
+            region,
+            String,
+            S3_STORAGE_REGION
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.s3,
             access_key_id,
             String,
             S3_STORAGE_ACCESS_KEY_ID
         );
         env_helper!(
-            mut_config.storage,
-            s3,
+            mut_config,
+            sources,
+            storage.s3,
             secret_access_key,
             String,
             S3_STORAGE_SECRET_ACCESS_KEY
         );
-        env_helper!(mut_config.storage, s3, bucket, String, S3_STORAGE_BUCKET);
+        env_helper!(
+            mut_config,
+            sources,
+            storage.s3,
+            bucket,
+            String,
+            S3_STORAGE_BUCKET
+        );
+
+        // Azure Blob Storage.
+        env_helper!(
+            mut_config,
+            sources,
+            storage.azure_blob,
+            account,
+            String,
+            AZURE_BLOB_STORAGE_ACCOUNT
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.azure_blob,
+            container,
+            String,
+            AZURE_BLOB_STORAGE_CONTAINER
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.azure_blob,
+            account_key,
+            String,
+            AZURE_BLOB_STORAGE_ACCOUNT_KEY
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.azure_blob,
+            sas_token,
+            String,
+            AZURE_BLOB_STORAGE_SAS_TOKEN
+        );
+
+        // HDFS Storage.
+        env_helper!(
+            mut_config,
+            sources,
+            storage.hdfs,
+            name_node,
+            String,
+            HDFS_STORAGE_NAME_NODE
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.hdfs,
+            root,
+            String,
+            HDFS_STORAGE_ROOT
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.hdfs,
+            user,
+            String,
+            HDFS_STORAGE_USER
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.hdfs,
+            delegation_token,
+            String,
+            HDFS_STORAGE_DELEGATION_TOKEN
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.hdfs,
+            kerberos_principal,
+            String,
+            HDFS_STORAGE_KERBEROS_PRINCIPAL
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.hdfs,
+            kerberos_keytab,
+            String,
+            HDFS_STORAGE_KERBEROS_KEYTAB
+        );
+
+        // Encryption.
+        env_helper!(
+            mut_config,
+            sources,
+            storage.encryption,
+            keys,
+            String,
+            ENCRYPTION_STORAGE_KEYS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.encryption,
+            key_command,
+            String,
+            ENCRYPTION_STORAGE_KEY_COMMAND
+        );
+
+        // Connection.
+        env_helper!(
+            mut_config,
+            sources,
+            storage.connection,
+            master_keys,
+            String,
+            CONNECTION_STORAGE_MASTER_KEYS
+        );
+        env_helper!(
+            mut_config,
+            sources,
+            storage.connection,
+            master_key_command,
+            String,
+            CONNECTION_STORAGE_MASTER_KEY_COMMAND
+        );
     }
 }
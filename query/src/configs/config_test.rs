@@ -16,6 +16,9 @@ use common_exception::Result;
 use pretty_assertions::assert_eq;
 
 use crate::configs::Config;
+use crate::configs::ConfigSource;
+use crate::configs::ConfigSources;
+use crate::configs::FaultInjectionConfig;
 use crate::configs::LogConfig;
 use crate::configs::MetaConfig;
 use crate::configs::QueryConfig;
@@ -29,7 +32,9 @@ fn test_default_config() -> Result<()> {
         meta: MetaConfig::default(),
         storage: StorageConfig::default(),
         query: QueryConfig::default(),
+        fault_injection: FaultInjectionConfig::default(),
         config_file: "".to_string(),
+        print_config: false,
     };
     let actual = Config::default();
     assert_eq!(actual, expect);
@@ -38,11 +43,23 @@ fn test_default_config() -> Result<()> {
 
 [query]
 tenant = \"\"
+multi_tenant = false
 namespace = \"\"
+cluster_heartbeat_timeout_secs = 10
 num_cpus = 8
 mysql_handler_host = \"127.0.0.1\"
 mysql_handler_port = 3307
 max_active_sessions = 256
+idle_session_timeout_secs = 3600
+statistics_refresh_interval_secs = 300
+statistics_refresh_changed_ratio = 0.2
+result_cache_size_mb = 256
+query_pages_size_mb = 256
+query_log_table = \"\"
+max_server_memory_usage_mb = 0
+max_running_queries = 0
+max_running_queries_per_user = 0
+queued_query_timeout_secs = 60
 clickhouse_handler_host = \"127.0.0.1\"
 clickhouse_handler_port = 9000
 flight_api_address = \"127.0.0.1:9090\"
@@ -59,9 +76,14 @@ rpc_tls_query_service_domain_name = \"localhost\"
 [log]
 log_level = \"INFO\"
 log_dir = \"./_logs\"
+tracing_otlp_endpoint = \"\"
 
 [meta]
 meta_address = \"\"
+meta_embedded = false
+meta_embedded_dir = \"./_meta_embedded\"
+meta_cache_dir = \"\"
+meta_replica_staleness_ms = 0
 meta_username = \"root\"
 meta_password = \"\"
 meta_client_timeout_in_second = 10
@@ -70,6 +92,16 @@ rpc_tls_meta_service_domain_name = \"localhost\"
 
 [storage]
 storage_type = \"disk\"
+storage_policy_interval_secs = 3600
+purge_interval_secs = 60
+io_background_max_requests = 4
+io_background_max_wait_ms = 1000
+column_cache_size_mb = 256
+io_max_retries = 3
+io_retry_initial_backoff_ms = 100
+io_retry_max_backoff_ms = 5000
+io_max_bytes_per_second = 0
+io_max_requests_per_second = 0
 
 [storage.disk]
 data_path = \"\"
@@ -79,6 +111,37 @@ region = \"\"
 access_key_id = \"\"
 secret_access_key = \"\"
 bucket = \"\"
+
+[storage.azure_blob]
+account = \"\"
+container = \"\"
+account_key = \"\"
+sas_token = \"\"
+
+[storage.hdfs]
+name_node = \"\"
+root = \"\"
+user = \"\"
+delegation_token = \"\"
+kerberos_principal = \"\"
+kerberos_keytab = \"\"
+
+[storage.encryption]
+keys = \"\"
+key_command = \"\"
+
+[storage.connection]
+master_keys = \"\"
+master_key_command = \"\"
+
+[fault_injection]
+fault_targets = \"dal,meta\"
+fault_seed = 0
+fault_error_rate = 0.0
+fault_latency_rate = 0.0
+fault_latency_ms = 0
+fault_partial_read_rate = 0.0
+fault_lost_response_rate = 0.0
 ";
 
     let tom_actual = toml::to_string(&actual).unwrap();
@@ -155,6 +218,58 @@ fn test_env_config() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_unknown_keys() -> Result<()> {
+    let toml_str = "
+[query]
+tenant = \"tenant-1\"
+max_active_session = 10
+
+[storage.s3]
+bucket = \"bucket-1\"
+buckets = \"bucket-2\"
+";
+    let result = Config::load_from_toml_str(toml_str);
+    assert!(result.is_err());
+    assert_eq!(
+        "Code: 6, displayText = Unknown config keys: query.max_active_session, storage.s3.buckets.",
+        result.unwrap_err().to_string()
+    );
+
+    let keys = crate::configs::config_file_keys("[query]\ntenant = \"tenant-1\"\n")?;
+    assert_eq!(keys, vec!["query.tenant".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn test_config_sources() -> Result<()> {
+    std::env::set_var("LOG_DIR", "/tmp/logs");
+    let mut conf = Config::default();
+    let mut sources = ConfigSources::create();
+    LogConfig::load_from_env(&mut conf, &mut sources);
+    std::env::remove_var("LOG_DIR");
+
+    assert_eq!("/tmp/logs", conf.log.log_dir);
+    assert_eq!(ConfigSource::Env, sources.get("log.log_dir"));
+    assert_eq!(ConfigSource::Default, sources.get("log.log_level"));
+
+    conf.query.tenant = "tenant-1".to_string();
+    conf.meta.meta_password = "password".to_string();
+    sources.set("query.tenant", ConfigSource::File);
+    sources.set("meta.meta_password", ConfigSource::Args);
+
+    let rendered = sources.render(&conf)?;
+    assert!(rendered
+        .contains("\n[log]\nlog_dir = \"/tmp/logs\" # env\nlog_level = \"INFO\" # default\n"));
+    assert!(rendered.contains("\n[query]\n"));
+    assert!(rendered.contains("\ntenant = \"tenant-1\" # file\n"));
+    assert!(rendered.contains("\n[storage.s3]\n"));
+    // The secrets are masked.
+    assert!(rendered.contains("\nmeta_password = \"******\" # args\n"));
+    assert!(!rendered.contains("password\" #"));
+    Ok(())
+}
+
 #[test]
 fn test_fuse_commit_version() -> Result<()> {
     let v = &crate::configs::config::DATABEND_COMMIT_VERSION;
@@ -12,14 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// Override `$config.$struct.$field` by the env variable `$env` if it is set,
+/// the key is recorded in `$sources` as coming from the env.
 macro_rules! env_helper {
-    ($config:expr, $struct: tt, $field:tt, $field_type: ty, $env:expr) => {
-        let env_var = std::env::var_os($env)
-            .unwrap_or($config.$struct.$field.to_string().into())
-            .into_string()
-            .expect(format!("cannot convert {} to string", $env).as_str());
-        $config.$struct.$field = env_var
-            .parse::<$field_type>()
-            .expect(format!("cannot convert {} to {}", $env, stringify!($field_type)).as_str());
+    ($config:expr, $sources:expr, $($struct:ident).+, $field:ident, $field_type: ty, $env:expr) => {
+        if let Some(env_var) = std::env::var_os($env) {
+            let env_var = env_var
+                .into_string()
+                .expect(format!("cannot convert {} to string", $env).as_str());
+            $config.$($struct).+.$field = env_var
+                .parse::<$field_type>()
+                .expect(format!("cannot convert {} to {}", $env, stringify!($field_type)).as_str());
+            $sources.set(
+                concat!($(stringify!($struct), ".",)+ stringify!($field)),
+                crate::configs::ConfigSource::Env,
+            );
+        }
     };
 }
@@ -19,16 +19,28 @@ mod macros;
 mod config_test;
 
 mod config;
+pub mod config_fault_injection;
 pub mod config_log;
 pub mod config_meta;
 pub mod config_query;
+mod config_sources;
 pub mod config_storage;
 
 pub use config::Config;
+pub use config::DATABEND_BUILD_FEATURES;
 pub use config::DATABEND_COMMIT_VERSION;
+pub use config_fault_injection::FaultInjectionConfig;
 pub use config_log::LogConfig;
 pub use config_meta::MetaConfig;
 pub use config_query::QueryConfig;
+pub use config_sources::config_file_keys;
+pub use config_sources::flatten_config;
+pub use config_sources::ConfigSource;
+pub use config_sources::ConfigSources;
+pub use config_storage::AzureBlobStorageConfig;
+pub use config_storage::ConnectionStorageConfig;
 pub use config_storage::DiskStorageConfig;
+pub use config_storage::EncryptionStorageConfig;
+pub use config_storage::HdfsStorageConfig;
 pub use config_storage::S3StorageConfig;
 pub use config_storage::StorageConfig;
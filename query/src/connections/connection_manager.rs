@@ -0,0 +1,243 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_base::BlockingWait;
+use common_base::Runtime;
+use common_dal::decrypt_bytes;
+use common_dal::encrypt_bytes;
+use common_dal::is_encrypted;
+use common_dal::KeyProvider;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+use common_management::ConnectionInfo;
+use common_management::ConnectionMgr;
+use common_management::ConnectionMgrApi;
+use common_meta_api::KVApi;
+
+use crate::common::MetaClientProvider;
+use crate::configs::Config;
+use crate::datasources::common::build_key_provider;
+use crate::datasources::common::COLD_STORAGE_OPT_KEY_PREFIX;
+use crate::datasources::common::STORAGE_OPT_KEY_CONNECTION;
+use crate::datasources::common::STORAGE_OPT_KEY_TYPE;
+
+/// How long the master keys are waited for, e.g. by a KMS client.
+const MASTER_KEY_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub type ConnectionManagerRef = Arc<ConnectionManager>;
+
+/// Connections created by `CREATE CONNECTION`, kept in the meta service with their storage
+/// options encrypted by the master keys of `storage.connection`.
+///
+/// The tables and databases with `STORAGE_CONNECTION = 'name'` take the storage options of
+/// the connection, so that their own options never keep the credentials. The connections are
+/// shared by all the sessions of the tenant which created them, and unknown to the others.
+pub struct ConnectionManager {
+    kv_api: Arc<dyn KVApi>,
+    master_keys: Option<Arc<dyn KeyProvider>>,
+    /// Each connection manager has its own runtime, so they are kept for the tenants.
+    tenants: RwLock<HashMap<String, Arc<dyn ConnectionMgrApi>>>,
+    /// The decrypted options by the tenant and the name of the connections, along with the
+    /// sealed options they are decrypted from.
+    opened: RwLock<HashMap<(String, String), (Vec<u8>, BTreeMap<String, String>)>>,
+    /// Runs the master key provider, e.g. the command of a KMS client.
+    rt: Arc<Runtime>,
+}
+
+impl ConnectionManager {
+    async fn create_kv_client(cfg: &Config) -> Result<Arc<dyn KVApi>> {
+        let store_api_provider = MetaClientProvider::from(cfg);
+        match store_api_provider.try_get_kv_client().await {
+            Ok(client) => Ok(client),
+            Err(cause) => Err(cause.add_message_back("(while create connection api).")),
+        }
+    }
+
+    pub async fn create_global(cfg: Config) -> Result<ConnectionManagerRef> {
+        let kv_api = ConnectionManager::create_kv_client(&cfg).await?;
+        let conf = &cfg.storage.connection;
+        let master_keys = build_key_provider(&conf.master_keys, &conf.master_key_command)?;
+        let rt = Runtime::with_worker_threads(1)?;
+
+        Ok(Arc::new(ConnectionManager {
+            kv_api,
+            master_keys,
+            tenants: Default::default(),
+            opened: Default::default(),
+            rt: Arc::new(rt),
+        }))
+    }
+
+    fn api_provider(&self, tenant: &str) -> Arc<dyn ConnectionMgrApi> {
+        if let Some(api_provider) = self.tenants.read().get(tenant) {
+            return api_provider.clone();
+        }
+
+        self.tenants
+            .write()
+            .entry(tenant.to_string())
+            .or_insert_with(|| Arc::new(ConnectionMgr::new(self.kv_api.clone(), tenant)))
+            .clone()
+    }
+
+    fn master_keys(&self) -> Result<Arc<dyn KeyProvider>> {
+        self.master_keys.clone().ok_or_else(|| {
+            ErrorCode::BadOption(
+                "The connections require the master keys of storage.connection in the config",
+            )
+        })
+    }
+
+    fn seal(&self, options: &BTreeMap<String, String>) -> Result<Vec<u8>> {
+        let master_keys = self.master_keys()?;
+        let plain = serde_json::to_vec(options)?;
+        let seal = async move { encrypt_bytes(master_keys.as_ref(), &plain).await };
+        seal.wait_in(&self.rt, Some(MASTER_KEY_TIMEOUT))?
+    }
+
+    fn open(&self, connection: &ConnectionInfo) -> Result<BTreeMap<String, String>> {
+        // The options are never kept in plaintext, whatever is found in the meta service.
+        if !is_encrypted(&connection.sealed_options) {
+            return Err(ErrorCode::IllegalConnectionFormat(format!(
+                "The options of connection {} are not encrypted",
+                connection.name
+            )));
+        }
+
+        let master_keys = self.master_keys()?;
+        let sealed = connection.sealed_options.clone();
+        let open = async move { decrypt_bytes(master_keys.as_ref(), sealed).await };
+        let plain = open.wait_in(&self.rt, Some(MASTER_KEY_TIMEOUT))??;
+        serde_json::from_slice(&plain).map_err(|_| {
+            ErrorCode::IllegalConnectionFormat(format!(
+                "Cannot deserialize the options of connection {}",
+                connection.name
+            ))
+        })
+    }
+
+    /// Creates the connection of the storage options, which are validated by the caller.
+    pub fn create_connection(
+        &self,
+        tenant: &str,
+        name: &str,
+        options: &BTreeMap<String, String>,
+        if_not_exists: bool,
+    ) -> Result<()> {
+        let storage_type = options.get(STORAGE_OPT_KEY_TYPE).ok_or_else(|| {
+            ErrorCode::BadOption(format!(
+                "Connection {} requires the option {}",
+                name, STORAGE_OPT_KEY_TYPE
+            ))
+        })?;
+
+        let connection_info = ConnectionInfo {
+            name: name.to_lowercase(),
+            storage_type: storage_type.to_lowercase(),
+            sealed_options: self.seal(options)?,
+            created_on: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        };
+        match self.api_provider(tenant).add_connection(connection_info) {
+            Ok(_) => Ok(()),
+            Err(e)
+                if if_not_exists && e.code() == ErrorCode::ConnectionAlreadyExists("").code() =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Drops the connection, the tables referring to it can not be read until it is created
+    /// again.
+    pub fn drop_connection(&self, tenant: &str, name: &str, if_exists: bool) -> Result<()> {
+        let name = name.to_lowercase();
+        self.opened
+            .write()
+            .remove(&(tenant.to_string(), name.clone()));
+        match self.api_provider(tenant).drop_connection(name, None) {
+            Ok(_) => Ok(()),
+            Err(e) if if_exists && e.code() == ErrorCode::UnknownConnection("").code() => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The connections of the tenant by their names, without the options.
+    pub fn get_connections(&self, tenant: &str) -> Result<Vec<ConnectionInfo>> {
+        let mut connections = self
+            .api_provider(tenant)
+            .get_connections()?
+            .into_iter()
+            .map(|(_, connection_info)| connection_info)
+            .collect::<Vec<_>>();
+        connections.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(connections)
+    }
+
+    /// The decrypted storage options of the connection.
+    pub fn get_connection_options(
+        &self,
+        tenant: &str,
+        name: &str,
+    ) -> Result<BTreeMap<String, String>> {
+        let key = (tenant.to_string(), name.to_lowercase());
+        let (_, connection_info) = self
+            .api_provider(tenant)
+            .get_connection(key.1.clone(), None)?;
+
+        // Decrypted again only if the connection is created again.
+        if let Some((sealed, options)) = self.opened.read().get(&key) {
+            if *sealed == connection_info.sealed_options {
+                return Ok(options.clone());
+            }
+        }
+
+        let options = self.open(&connection_info)?;
+        self.opened
+            .write()
+            .insert(key, (connection_info.sealed_options, options.clone()));
+        Ok(options)
+    }
+
+    /// Replaces the `storage_connection` and `cold_storage_connection` options with the options
+    /// of the connections of the tenant, the options given along with them win.
+    pub fn resolve_storage_options(
+        &self,
+        tenant: &str,
+        options: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        let mut resolved = options.clone();
+        for prefix in ["", COLD_STORAGE_OPT_KEY_PREFIX] {
+            let key = format!("{}{}", prefix, STORAGE_OPT_KEY_CONNECTION);
+            if let Some(name) = resolved.remove(&key) {
+                for (key, value) in self.get_connection_options(tenant, &name)? {
+                    resolved
+                        .entry(format!("{}{}", prefix, key))
+                        .or_insert(value);
+                }
+            }
+        }
+        Ok(resolved)
+    }
+}
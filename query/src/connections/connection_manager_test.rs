@@ -0,0 +1,99 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::configs::Config;
+use crate::connections::ConnectionManager;
+
+fn s3_options(bucket: &str) -> BTreeMap<String, String> {
+    let mut options = BTreeMap::new();
+    options.insert("storage_type".to_string(), "s3".to_string());
+    options.insert("storage_s3_bucket".to_string(), bucket.to_string());
+    options.insert(
+        "storage_s3_secret_access_key".to_string(),
+        "s3cr3t".to_string(),
+    );
+    options
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_connection_manager() -> Result<()> {
+    let mut config = Config::default();
+    config.storage.connection.master_keys =
+        "1:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".to_string();
+    let connections = ConnectionManager::create_global(config).await?;
+
+    connections.create_connection("t1", "Warehouse", &s3_options("hot"), false)?;
+    connections.create_connection("t1", "archive", &s3_options("cold"), false)?;
+    let r = connections.create_connection("t1", "warehouse", &s3_options("x"), false);
+    assert_eq!(
+        r.unwrap_err().code(),
+        ErrorCode::ConnectionAlreadyExists("").code()
+    );
+    connections.create_connection("t1", "warehouse", &s3_options("x"), true)?;
+
+    // The options are sealed in the meta service, and decrypted on use.
+    let listed = connections.get_connections("t1")?;
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[1].name, "warehouse");
+    assert_eq!(listed[1].storage_type, "s3");
+    assert!(!listed[1].sealed_options.windows(6).any(|w| w == b"s3cr3t"));
+    assert_eq!(
+        connections.get_connection_options("t1", "WAREHOUSE")?,
+        s3_options("hot")
+    );
+    assert!(connections.get_connections("t2")?.is_empty());
+
+    // The options of the table win, the cold connection gives the cold ones.
+    let mut options = HashMap::new();
+    options.insert("storage_connection".to_string(), "warehouse".to_string());
+    options.insert("cold_storage_connection".to_string(), "archive".to_string());
+    options.insert("storage_s3_bucket".to_string(), "mine".to_string());
+    options.insert("comment".to_string(), "x".to_string());
+    let resolved = connections.resolve_storage_options("t1", &options)?;
+    assert_eq!(resolved.get("storage_connection"), None);
+    assert_eq!(resolved["storage_s3_bucket"], "mine");
+    assert_eq!(resolved["storage_s3_secret_access_key"], "s3cr3t");
+    assert_eq!(resolved["cold_storage_s3_bucket"], "cold");
+    assert_eq!(resolved["comment"], "x");
+
+    let r = connections.resolve_storage_options("t2", &options);
+    assert_eq!(
+        r.unwrap_err().code(),
+        ErrorCode::UnknownConnection("").code()
+    );
+
+    connections.drop_connection("t1", "warehouse", false)?;
+    let r = connections.drop_connection("t1", "warehouse", false);
+    assert_eq!(
+        r.unwrap_err().code(),
+        ErrorCode::UnknownConnection("").code()
+    );
+    connections.drop_connection("t1", "warehouse", true)?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_connection_manager_without_master_keys() -> Result<()> {
+    let connections = ConnectionManager::create_global(Config::default()).await?;
+    let r = connections.create_connection("t1", "c1", &s3_options("b"), false);
+    assert_eq!(r.unwrap_err().code(), ErrorCode::BadOption("").code());
+    Ok(())
+}
@@ -0,0 +1,87 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_planners::Expression;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+
+/// The table option which keeps the defaults of the columns added by
+/// `ALTER TABLE ADD COLUMN ... DEFAULT`, in json.
+pub const TBL_OPT_KEY_COLUMN_DEFAULTS: &str = "column_defaults";
+
+/// The values of the columns read from the blocks which have no such column, e.g. the blocks
+/// written before the column was added. The columns without a default are read as NULL.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ColumnDefaults {
+    pub columns: BTreeMap<String, DataValue>,
+}
+
+impl ColumnDefaults {
+    pub fn from_options(options: &HashMap<String, String>) -> Result<ColumnDefaults> {
+        match options.get(TBL_OPT_KEY_COLUMN_DEFAULTS) {
+            None => Ok(ColumnDefaults::default()),
+            Some(value) => serde_json::from_str(value)
+                .map_err_to_code(ErrorCode::BadOption, || {
+                    format!("Invalid table option {}", TBL_OPT_KEY_COLUMN_DEFAULTS)
+                }),
+        }
+    }
+
+    pub fn to_option(&self) -> Result<String> {
+        serde_json::to_string(self).map_err_to_code(ErrorCode::LogicalError, || {
+            "Cannot serialize the column defaults"
+        })
+    }
+
+    /// The value of a column missing in a block.
+    pub fn missing_value(&self, field: &DataField) -> DataValue {
+        match self.columns.get(field.name()) {
+            Some(value) => value.clone(),
+            None => DataValue::from(field.data_type()),
+        }
+    }
+
+    /// Evaluates the constant default of a column once, cast to the type of the column.
+    pub fn eval_default(field: &DataField, expr: &Expression) -> Result<DataValue> {
+        let expr = Expression::Cast {
+            expr: Box::new(expr.clone()),
+            data_type: field.data_type().clone(),
+            is_try: false,
+        };
+
+        let input_schema =
+            DataSchemaRefExt::create(vec![DataField::new("_dummy", DataType::UInt8, false)]);
+        let output_schema = DataSchemaRefExt::create(vec![expr.to_data_field(&input_schema)?]);
+        let executor = ExpressionExecutor::try_create(
+            "column default executor",
+            input_schema.clone(),
+            output_schema,
+            vec![expr],
+            false,
+        )?;
+        executor.validate()?;
+
+        let dummy_columns = vec![DataColumn::Constant(DataValue::UInt8(Some(1)), 1)];
+        let block = executor.execute(&DataBlock::create(input_schema, dummy_columns))?;
+        block.column(0).try_get(0)
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::add;
+use common_planners::lit;
+
+use crate::datasources::common::ColumnDefaults;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_DEFAULTS;
+
+#[test]
+fn test_column_defaults_eval() -> Result<()> {
+    // The default is cast to the type of the column.
+    let field = DataField::new("c", DataType::Int32, true);
+    let value = ColumnDefaults::eval_default(&field, &add(lit(1u8), lit(2u8)))?;
+    assert_eq!(value, DataValue::Int32(Some(3)));
+
+    let field = DataField::new("s", DataType::String, true);
+    let value = ColumnDefaults::eval_default(&field, &lit(7i64))?;
+    assert_eq!(value, DataValue::String(Some("7".as_bytes().to_vec())));
+
+    Ok(())
+}
+
+#[test]
+fn test_column_defaults_options() -> Result<()> {
+    let mut defaults = ColumnDefaults::from_options(&HashMap::new())?;
+    assert_eq!(defaults, ColumnDefaults::default());
+
+    let field = DataField::new("c", DataType::Int32, true);
+    assert_eq!(defaults.missing_value(&field), DataValue::Int32(None));
+
+    defaults
+        .columns
+        .insert("c".to_string(), DataValue::Int32(Some(3)));
+    let mut options = HashMap::new();
+    options.insert(
+        TBL_OPT_KEY_COLUMN_DEFAULTS.to_string(),
+        defaults.to_option()?,
+    );
+    let defaults = ColumnDefaults::from_options(&options)?;
+    assert_eq!(defaults.missing_value(&field), DataValue::Int32(Some(3)));
+
+    options.insert(TBL_OPT_KEY_COLUMN_DEFAULTS.to_string(), "{".to_string());
+    let r = ColumnDefaults::from_options(&options);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    Ok(())
+}
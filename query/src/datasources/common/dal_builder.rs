@@ -15,27 +15,94 @@
 
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
+use common_base::FaultInjector;
+use common_dal::AzureBlobAccessor;
+use common_dal::AzureBlobCredential;
+use common_dal::CommandKeyProvider;
 use common_dal::DataAccessor;
 use common_dal::DataAccessorBuilder;
+use common_dal::EncryptionAccessor;
+use common_dal::FaultyAccessor;
+use common_dal::HdfsAccessor;
+use common_dal::HdfsCredential;
+use common_dal::IOPriority;
+use common_dal::IOScheduler;
+use common_dal::KeyProvider;
 use common_dal::Local;
+use common_dal::RetryAccessor;
+use common_dal::RetryPolicy;
+use common_dal::ScheduledAccessor;
+use common_dal::StaticKeyProvider;
 use common_dal::StorageScheme;
+use common_dal::Throttle;
+use common_dal::ThrottleAccessor;
 use common_dal::S3;
 
 use crate::configs::StorageConfig;
 
+/// The keys of the `version:base64 key` pairs or of the key command, None if neither is set.
+/// The key command wins over the static keys.
+pub fn build_key_provider(
+    keys: &str,
+    key_command: &str,
+) -> common_exception::Result<Option<Arc<dyn KeyProvider>>> {
+    if !key_command.is_empty() {
+        return Ok(Some(Arc::new(CommandKeyProvider::create(key_command))));
+    }
+    if !keys.is_empty() {
+        return Ok(Some(Arc::new(StaticKeyProvider::parse(keys)?)));
+    }
+    Ok(None)
+}
+
 pub struct ContextDalBuilder {
     storage_conf: StorageConfig,
+    fault_injector: Option<Arc<FaultInjector>>,
+    io_scheduler: Option<(Arc<IOScheduler>, IOPriority)>,
+    throttles: Vec<Arc<Throttle>>,
+    key_provider: Option<Arc<dyn KeyProvider>>,
 }
 
 impl ContextDalBuilder {
     pub fn new(storage_conf: StorageConfig) -> Self {
-        Self { storage_conf }
+        Self {
+            storage_conf,
+            fault_injector: None,
+            io_scheduler: None,
+            throttles: vec![],
+            key_provider: None,
+        }
     }
-}
 
-impl DataAccessorBuilder for ContextDalBuilder {
-    fn build(&self) -> common_exception::Result<Arc<dyn DataAccessor>> {
+    pub fn with_fault_injector(mut self, fault_injector: Option<Arc<FaultInjector>>) -> Self {
+        self.fault_injector = fault_injector;
+        self
+    }
+
+    pub fn with_io_scheduler(mut self, scheduler: Arc<IOScheduler>, priority: IOPriority) -> Self {
+        self.io_scheduler = Some((scheduler, priority));
+        self
+    }
+
+    /// Caps the throughput of the accessor by the throttle, which may be shared with other
+    /// accessors, e.g. the ones of the same query or node. All the throttles apply.
+    pub fn with_throttle(mut self, throttle: Arc<Throttle>) -> Self {
+        if !throttle.is_unlimited() {
+            self.throttles.push(throttle);
+        }
+        self
+    }
+
+    /// Encrypts the objects with the keys of the provider, e.g. the one of the node which caches
+    /// the keys. If not given, the keys are of the encryption config of the storage.
+    pub fn with_key_provider(mut self, key_provider: Option<Arc<dyn KeyProvider>>) -> Self {
+        self.key_provider = key_provider;
+        self
+    }
+
+    fn build_accessor(&self) -> common_exception::Result<Arc<dyn DataAccessor>> {
         let conf = &self.storage_conf;
         let scheme_name = &conf.storage_type;
         let scheme = StorageScheme::from_str(scheme_name)?;
@@ -49,7 +116,79 @@ impl DataAccessorBuilder for ContextDalBuilder {
                     &conf.secret_access_key,
                 )?))
             }
+            StorageScheme::AzureBlob => {
+                let conf = &conf.azure_blob;
+                // The SAS token is scoped and expires, it wins over the account key.
+                let credential = match conf.sas_token.is_empty() {
+                    true => AzureBlobCredential::SharedKey(conf.account_key.clone()),
+                    false => AzureBlobCredential::SasToken(conf.sas_token.clone()),
+                };
+                Ok(Arc::new(AzureBlobAccessor::with_credential(
+                    &conf.account,
+                    &conf.container,
+                    &credential,
+                )?))
+            }
+            StorageScheme::Hdfs => {
+                let conf = &conf.hdfs;
+                let credential = if !conf.kerberos_principal.is_empty() {
+                    HdfsCredential::Kerberos {
+                        principal: conf.kerberos_principal.clone(),
+                        keytab: conf.kerberos_keytab.clone(),
+                    }
+                } else if !conf.delegation_token.is_empty() {
+                    HdfsCredential::DelegationToken(conf.delegation_token.clone())
+                } else {
+                    HdfsCredential::Simple {
+                        user: conf.user.clone(),
+                    }
+                };
+                Ok(Arc::new(HdfsAccessor::with_credential(
+                    &conf.name_node,
+                    &conf.root,
+                    &credential,
+                )?))
+            }
             StorageScheme::LocalFs => Ok(Arc::new(Local::new(conf.disk.data_path.as_str()))),
         }
     }
 }
+
+impl DataAccessorBuilder for ContextDalBuilder {
+    fn build(&self) -> common_exception::Result<Arc<dyn DataAccessor>> {
+        let mut accessor = self.build_accessor()?;
+        if let Some(injector) = &self.fault_injector {
+            accessor = Arc::new(FaultyAccessor::create(accessor, injector.clone()));
+        }
+        if let Some((scheduler, priority)) = &self.io_scheduler {
+            accessor = Arc::new(ScheduledAccessor::create(
+                accessor,
+                scheduler.clone(),
+                *priority,
+            ));
+        }
+        // Outside of the scheduler, so that no permit is held while waiting for the throttles.
+        for throttle in &self.throttles {
+            accessor = Arc::new(ThrottleAccessor::create(accessor, throttle.clone()));
+        }
+        // Outside of the scheduler, so that no permit is held while backing off. The retries
+        // are throttled as the other requests.
+        let conf = &self.storage_conf;
+        if conf.io_max_retries > 0 {
+            accessor = Arc::new(RetryAccessor::create(accessor, RetryPolicy {
+                max_retries: conf.io_max_retries,
+                initial_backoff: Duration::from_millis(conf.io_retry_initial_backoff_ms),
+                max_backoff: Duration::from_millis(conf.io_retry_max_backoff_ms),
+            }));
+        }
+        // Outside of the retries, so that a retried `put` writes the same ciphertext.
+        let key_provider = match &self.key_provider {
+            Some(key_provider) => Some(key_provider.clone()),
+            None => build_key_provider(&conf.encryption.keys, &conf.encryption.key_command)?,
+        };
+        if let Some(key_provider) = key_provider {
+            accessor = Arc::new(EncryptionAccessor::create(accessor, key_provider));
+        }
+        Ok(accessor)
+    }
+}
@@ -13,9 +13,23 @@
 //  limitations under the License.
 //
 
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_base::Fault;
+use common_base::FaultInjector;
+use common_base::FaultRates;
 use common_dal::DataAccessorBuilder;
+use common_dal::Throttle;
+use common_dal::ThrottleLimits;
+use common_exception::ErrorCode;
 
+use crate::configs::AzureBlobStorageConfig;
+use crate::configs::ConnectionStorageConfig;
 use crate::configs::DiskStorageConfig;
+use crate::configs::EncryptionStorageConfig;
+use crate::configs::HdfsStorageConfig;
 use crate::configs::S3StorageConfig;
 use crate::configs::StorageConfig;
 use crate::datasources::common::ContextDalBuilder;
@@ -24,6 +38,16 @@ use crate::datasources::common::ContextDalBuilder;
 fn test_dal_builder() -> common_exception::Result<()> {
     let mut storage_config = StorageConfig {
         storage_type: "disk".to_string(),
+        storage_policy_interval_secs: 0,
+        purge_interval_secs: 0,
+        io_background_max_requests: 4,
+        io_background_max_wait_ms: 1000,
+        column_cache_size_mb: 0,
+        io_max_retries: 3,
+        io_retry_initial_backoff_ms: 1,
+        io_retry_max_backoff_ms: 10,
+        io_max_bytes_per_second: 0,
+        io_max_requests_per_second: 0,
         disk: DiskStorageConfig {
             data_path: "/tmp".to_string(),
         },
@@ -33,6 +57,10 @@ fn test_dal_builder() -> common_exception::Result<()> {
             secret_access_key: "".to_string(),
             bucket: "".to_string(),
         },
+        azure_blob: AzureBlobStorageConfig::default(),
+        hdfs: HdfsStorageConfig::default(),
+        encryption: EncryptionStorageConfig::default(),
+        connection: ConnectionStorageConfig::default(),
     };
 
     let dal = ContextDalBuilder::new(storage_config.clone()).build();
@@ -44,3 +72,98 @@ fn test_dal_builder() -> common_exception::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_dal_builder_with_fault_injector() -> common_exception::Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let storage_config = StorageConfig {
+        storage_type: "disk".to_string(),
+        storage_policy_interval_secs: 0,
+        purge_interval_secs: 0,
+        io_background_max_requests: 4,
+        io_background_max_wait_ms: 1000,
+        column_cache_size_mb: 0,
+        io_max_retries: 3,
+        io_retry_initial_backoff_ms: 1,
+        io_retry_max_backoff_ms: 10,
+        io_max_bytes_per_second: 0,
+        io_max_requests_per_second: 0,
+        disk: DiskStorageConfig {
+            data_path: tmp_dir.path().to_str().unwrap().to_string(),
+        },
+        s3: S3StorageConfig::default(),
+        azure_blob: AzureBlobStorageConfig::default(),
+        hdfs: HdfsStorageConfig::default(),
+        encryption: EncryptionStorageConfig::default(),
+        connection: ConnectionStorageConfig::default(),
+    };
+
+    let injector = FaultInjector::create(FaultRates::default());
+    let dal = ContextDalBuilder::new(storage_config)
+        .with_fault_injector(Some(injector.clone()))
+        .build()?;
+
+    injector.inject("put", Fault::Error);
+    let res = dal.put("obj", vec![1, 2, 3]).await;
+    assert_eq!(
+        ErrorCode::DALTransportError("").code(),
+        res.unwrap_err().code()
+    );
+    dal.put("obj", vec![1, 2, 3]).await?;
+    assert_eq!(vec![1, 2, 3], dal.get("obj").await?);
+
+    // The lost responses are retried.
+    injector.inject("get", Fault::LostResponse);
+    injector.inject("get", Fault::LostResponse);
+    assert_eq!(vec![1, 2, 3], dal.get("obj").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dal_builder_with_throttle() -> common_exception::Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let mut storage_config = StorageConfig::default();
+    storage_config.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+
+    // The stricter throttle wins.
+    let dal = ContextDalBuilder::new(storage_config)
+        .with_throttle(Throttle::create(ThrottleLimits::default()))
+        .with_throttle(Throttle::create(ThrottleLimits {
+            bytes_per_second: 1024,
+            requests_per_second: 0,
+        }))
+        .build()?;
+
+    let start = Instant::now();
+    dal.put("obj", vec![1; 1024]).await?;
+    assert!(start.elapsed() < Duration::from_millis(500));
+    dal.put("obj", vec![1; 512]).await?;
+    assert!(start.elapsed() >= Duration::from_millis(400));
+    assert_eq!(vec![1; 512], dal.get("obj").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dal_builder_with_encryption() -> common_exception::Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let mut storage_config = StorageConfig::default();
+    storage_config.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let plain = ContextDalBuilder::new(storage_config.clone()).build()?;
+
+    storage_config.encryption.keys = "1:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".to_string();
+    let dal = ContextDalBuilder::new(storage_config.clone()).build()?;
+    dal.put("obj", vec![1, 2, 3]).await?;
+    assert_eq!(vec![1, 2, 3], dal.get("obj").await?);
+    assert_ne!(vec![1, 2, 3], plain.get("obj").await?);
+
+    storage_config.encryption.keys = "1:invalid".to_string();
+    let dal = ContextDalBuilder::new(storage_config).build();
+    assert_eq!(
+        ErrorCode::DALEncryptionError("").code(),
+        dal.err().unwrap().code()
+    );
+
+    Ok(())
+}
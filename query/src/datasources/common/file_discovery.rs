@@ -0,0 +1,103 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_dal::DataAccessor;
+use common_exception::Result;
+
+/// A data file found under an external location.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredFile {
+    pub path: String,
+    pub size: u64,
+    /// Hive-style `key=value` directories between the location and the file.
+    pub partition_values: Vec<(String, String)>,
+}
+
+/// Recursively lists the files under `location`.
+///
+/// Hidden files and the markers left by other engines (names starting with `.` or `_`,
+/// such as `_SUCCESS` or `.part-0.crc`) are skipped.
+pub async fn discover_files(
+    dal: Arc<dyn DataAccessor>,
+    location: &str,
+) -> Result<Vec<DiscoveredFile>> {
+    let objects = dal.list(location).await?;
+
+    let mut files = Vec::with_capacity(objects.len());
+    for object in objects {
+        let relative = relative_to_location(location, &object.path);
+        let file_name = relative.rsplit('/').next().unwrap_or(relative);
+        if file_name.is_empty() || file_name.starts_with('.') || file_name.starts_with('_') {
+            continue;
+        }
+
+        files.push(DiscoveredFile {
+            partition_values: extract_partition_values(relative),
+            path: object.path,
+            size: object.size,
+        });
+    }
+    Ok(files)
+}
+
+/// Extracts hive-style partition columns from the directories of `path`,
+/// e.g. `year=2021/month=09/part-0.csv` gives `[(year, 2021), (month, 09)]`.
+///
+/// Directories which are not of the form `key=value` are ignored.
+pub fn extract_partition_values(path: &str) -> Vec<(String, String)> {
+    let mut dirs = path.split('/').collect::<Vec<_>>();
+    // the last component is the file name
+    dirs.pop();
+
+    dirs.into_iter()
+        .filter_map(|dir| match dir.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Some((key.to_string(), value.to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Groups the files into at most `workers` groups of roughly the same total size.
+///
+/// Files are assigned greedily, largest first, to the group with the smallest total size,
+/// which keeps one big file from being packed together with many others.
+pub fn balance_files(mut files: Vec<DiscoveredFile>, workers: usize) -> Vec<Vec<DiscoveredFile>> {
+    let workers = workers.max(1).min(files.len());
+    if workers == 0 {
+        return vec![];
+    }
+
+    files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+
+    let mut groups = vec![vec![]; workers];
+    let mut group_sizes = vec![0u64; workers];
+    for file in files {
+        let (idx, _) = group_sizes
+            .iter()
+            .enumerate()
+            .min_by_key(|(idx, size)| (**size, *idx))
+            .expect("workers is greater than zero");
+        group_sizes[idx] += file.size;
+        groups[idx].push(file);
+    }
+    groups
+}
+
+fn relative_to_location<'a>(location: &str, path: &'a str) -> &'a str {
+    path.strip_prefix(location.trim_end_matches('/'))
+        .unwrap_or(path)
+        .trim_start_matches('/')
+}
@@ -0,0 +1,122 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_dal::DataAccessor;
+use common_dal::Local;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+use crate::datasources::common::balance_files;
+use crate::datasources::common::discover_files;
+use crate::datasources::common::extract_partition_values;
+use crate::datasources::common::DiscoveredFile;
+
+fn file(path: &str, size: u64) -> DiscoveredFile {
+    DiscoveredFile {
+        path: path.to_string(),
+        size,
+        partition_values: vec![],
+    }
+}
+
+#[test]
+fn test_extract_partition_values() -> Result<()> {
+    assert_eq!(
+        vec![
+            ("year".to_string(), "2021".to_string()),
+            ("month".to_string(), "09".to_string())
+        ],
+        extract_partition_values("year=2021/month=09/part-0.csv")
+    );
+
+    // plain directories and the file name are not partitions
+    assert_eq!(
+        vec![("k".to_string(), "".to_string())],
+        extract_partition_values("data/k=/a=b.csv")
+    );
+    assert!(extract_partition_values("=x/part-0.csv").is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_balance_files() -> Result<()> {
+    let files = vec![
+        file("a", 10),
+        file("b", 70),
+        file("c", 20),
+        file("d", 30),
+        file("e", 40),
+    ];
+
+    let groups = balance_files(files.clone(), 2);
+    let paths = groups
+        .iter()
+        .map(|g| g.iter().map(|f| f.path.as_str()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![vec!["b", "c"], vec!["e", "d", "a"]], paths);
+
+    // never more groups than files
+    assert_eq!(5, balance_files(files.clone(), 8).len());
+    assert_eq!(1, balance_files(files, 0).len());
+    assert!(balance_files(vec![], 4).is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_discover_files() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let dal: Arc<dyn DataAccessor> = Arc::new(Local::with_path(tmp_dir.path().to_owned()));
+
+    dal.put("ext/year=2021/month=08/part-0.csv", b"1,2".to_vec())
+        .await?;
+    dal.put("ext/year=2021/month=09/part-0.csv", b"3,4,5".to_vec())
+        .await?;
+    dal.put("ext/year=2021/month=09/_SUCCESS", vec![]).await?;
+    dal.put("ext/.hidden.csv", vec![]).await?;
+    dal.put("other/part-0.csv", vec![]).await?;
+
+    let files = discover_files(dal.clone(), "ext/").await?;
+    assert_eq!(
+        vec![
+            DiscoveredFile {
+                path: "ext/year=2021/month=08/part-0.csv".to_string(),
+                size: 3,
+                partition_values: vec![
+                    ("year".to_string(), "2021".to_string()),
+                    ("month".to_string(), "08".to_string())
+                ],
+            },
+            DiscoveredFile {
+                path: "ext/year=2021/month=09/part-0.csv".to_string(),
+                size: 5,
+                partition_values: vec![
+                    ("year".to_string(), "2021".to_string()),
+                    ("month".to_string(), "09".to_string())
+                ],
+            },
+        ],
+        files
+    );
+
+    // discovered paths can be read back through the same accessor
+    assert_eq!(b"3,4,5".to_vec(), dal.get(&files[1].path).await?);
+
+    // a missing location has no files
+    assert!(discover_files(dal, "not_exists/").await?.is_empty());
+    Ok(())
+}
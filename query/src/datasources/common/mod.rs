@@ -13,17 +13,89 @@
 // limitations under the License.
 //
 
+pub use column_defaults::ColumnDefaults;
+pub use column_defaults::TBL_OPT_KEY_COLUMN_DEFAULTS;
+pub use dal_builder::build_key_provider;
 pub use dal_builder::ContextDalBuilder;
+pub use file_discovery::balance_files;
+pub use file_discovery::discover_files;
+pub use file_discovery::extract_partition_values;
+pub use file_discovery::DiscoveredFile;
 pub use line::count_lines;
 pub use part::generate_parts;
+pub use schema_evolution::adapt_block_to_schema;
+pub use schema_evolution::ColumnIds;
+pub use schema_evolution::SCHEMA_META_KEY_COLUMN_IDS;
+pub use storage_options::check_disk_data_path;
+pub use storage_options::cold_storage_config_with_options;
+pub use storage_options::inherit_storage_options;
+pub use storage_options::is_secret_storage_option;
+pub use storage_options::storage_config_with_options;
+pub use storage_options::COLD_STORAGE_OPT_KEY_PREFIX;
+pub use storage_options::STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT;
+pub use storage_options::STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT_KEY;
+pub use storage_options::STORAGE_OPT_KEY_AZURE_BLOB_CONTAINER;
+pub use storage_options::STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN;
+pub use storage_options::STORAGE_OPT_KEY_CONNECTION;
+pub use storage_options::STORAGE_OPT_KEY_DISK_DATA_PATH;
+pub use storage_options::STORAGE_OPT_KEY_HDFS_DELEGATION_TOKEN;
+pub use storage_options::STORAGE_OPT_KEY_HDFS_NAME_NODE;
+pub use storage_options::STORAGE_OPT_KEY_HDFS_ROOT;
+pub use storage_options::STORAGE_OPT_KEY_HDFS_USER;
+pub use storage_options::STORAGE_OPT_KEY_S3_ACCESS_KEY_ID;
+pub use storage_options::STORAGE_OPT_KEY_S3_BUCKET;
+pub use storage_options::STORAGE_OPT_KEY_S3_REGION;
+pub use storage_options::STORAGE_OPT_KEY_S3_SECRET_ACCESS_KEY;
+pub use storage_options::STORAGE_OPT_KEY_TYPE;
+pub use table_collations::Collation;
+pub use table_collations::TableCollations;
+pub use table_collations::TBL_OPT_KEY_COLLATION;
+pub use table_collations::TBL_OPT_KEY_COLUMN_COLLATIONS;
+pub use table_constraints::TableConstraints;
+pub use table_constraints::TBL_OPT_KEY_PRIMARY_KEY;
+pub use table_constraints::TBL_OPT_KEY_UNIQUE_KEYS;
+pub use table_statistics::add_table_changed_rows;
+pub use table_statistics::count_table_changed_rows;
+pub use table_statistics::table_changed_rows;
+pub use table_statistics::ColumnStatistics;
+pub use table_statistics::Histogram;
+pub use table_statistics::HistogramBucket;
+pub use table_statistics::TableStatistics;
+pub use table_statistics::TBL_OPT_KEY_CHANGED_ROWS;
+pub use table_statistics::TBL_OPT_KEY_COLUMN_STATISTICS;
+pub use virtual_columns::append_virtual_columns;
 
+#[cfg(test)]
+mod column_defaults_test;
 #[cfg(test)]
 mod dal_builder_test;
 #[cfg(test)]
+mod file_discovery_test;
+#[cfg(test)]
 mod line_test;
 #[cfg(test)]
 mod part_test;
+#[cfg(test)]
+mod schema_evolution_test;
+#[cfg(test)]
+mod storage_options_test;
+#[cfg(test)]
+mod table_collations_test;
+#[cfg(test)]
+mod table_constraints_test;
+#[cfg(test)]
+mod table_statistics_test;
+#[cfg(test)]
+mod virtual_columns_test;
 
+mod column_defaults;
 mod dal_builder;
+mod file_discovery;
 mod line;
 mod part;
+mod schema_evolution;
+mod storage_options;
+mod table_collations;
+mod table_constraints;
+mod table_statistics;
+mod virtual_columns;
@@ -0,0 +1,102 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+
+use crate::datasources::common::ColumnDefaults;
+
+/// The key of the schema meta which keeps the ids of the columns added by
+/// `ALTER TABLE ADD COLUMN`, in json. The blocks keep the ids of the schema they are written with.
+pub const SCHEMA_META_KEY_COLUMN_IDS: &str = "column_ids";
+
+/// The ids of the columns added by `ALTER TABLE ADD COLUMN`, the other columns are of id 0.
+/// A column added again after it is dropped gets a new id, so that the values of the dropped
+/// one in the blocks written before are not read as its values.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ColumnIds {
+    pub last_id: u64,
+    pub columns: BTreeMap<String, u64>,
+}
+
+impl ColumnIds {
+    pub fn from_meta(meta: &HashMap<String, String>) -> Result<ColumnIds> {
+        match meta.get(SCHEMA_META_KEY_COLUMN_IDS) {
+            None => Ok(ColumnIds::default()),
+            Some(value) => ColumnIds::from_json(value),
+        }
+    }
+
+    pub fn from_json(value: &str) -> Result<ColumnIds> {
+        serde_json::from_str(value).map_err_to_code(ErrorCode::LogicalError, || {
+            format!("Invalid schema meta {}", SCHEMA_META_KEY_COLUMN_IDS)
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err_to_code(ErrorCode::LogicalError, || {
+            "Cannot serialize the column ids"
+        })
+    }
+
+    pub fn id(&self, name: &str) -> u64 {
+        self.columns.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn add_column(&mut self, name: &str) {
+        self.last_id += 1;
+        self.columns.insert(name.to_string(), self.last_id);
+    }
+
+    pub fn drop_column(&mut self, name: &str) {
+        self.columns.remove(name);
+    }
+}
+
+/// Reads a block written before an `ALTER TABLE ADD/DROP COLUMN` with the current table schema.
+/// The columns are matched by name and id, a column missing in the block, or of another type,
+/// is its default, or NULL without a default.
+pub fn adapt_block_to_schema(
+    block: DataBlock,
+    schema: &DataSchemaRef,
+    defaults: &ColumnDefaults,
+) -> Result<DataBlock> {
+    let block_ids = ColumnIds::from_meta(block.schema().meta())?;
+    let ids = ColumnIds::from_meta(schema.meta())?;
+    if block.schema().fields() == schema.fields() && block_ids == ids {
+        return Ok(block);
+    }
+
+    let num_rows = block.num_rows();
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for field in schema.fields() {
+        let column = match block.schema().field_with_name(field.name()) {
+            Ok(f)
+                if f.data_type() == field.data_type()
+                    && block_ids.id(field.name()) == ids.id(field.name()) =>
+            {
+                block.try_column_by_name(field.name())?.clone()
+            }
+            _ => DataColumn::Constant(defaults.missing_value(field), num_rows),
+        };
+        columns.push(column);
+    }
+    Ok(DataBlock::create(schema.clone(), columns))
+}
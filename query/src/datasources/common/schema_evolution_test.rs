@@ -0,0 +1,143 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::datasources::common::adapt_block_to_schema;
+use crate::datasources::common::ColumnDefaults;
+use crate::datasources::common::ColumnIds;
+use crate::datasources::common::SCHEMA_META_KEY_COLUMN_IDS;
+
+#[test]
+fn test_adapt_block_to_schema() -> Result<()> {
+    let old_schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(old_schema.clone(), vec![
+        Series::new(vec![1i64, 2]),
+        Series::new(vec!["x", "y"]),
+    ]);
+
+    let mut defaults = ColumnDefaults::default();
+
+    // Same schema, the block is kept as it is.
+    let adapted = adapt_block_to_schema(block.clone(), &old_schema, &defaults)?;
+    assert_eq!(adapted.schema(), &old_schema);
+
+    // `b` dropped, `c` added.
+    let new_schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("c", DataType::UInt8, true),
+    ]);
+    let adapted = adapt_block_to_schema(block.clone(), &new_schema, &defaults)?;
+    assert_eq!(adapted.schema(), &new_schema);
+    assert_blocks_eq(
+        vec![
+            "+---+------+",
+            "| a | c    |",
+            "+---+------+",
+            "| 1 | NULL |",
+            "| 2 | NULL |",
+            "+---+------+",
+        ],
+        &[adapted],
+    );
+
+    // `b` dropped and added back with another type.
+    let new_schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Int32, true),
+    ]);
+    let adapted = adapt_block_to_schema(block.clone(), &new_schema, &defaults)?;
+    assert_eq!(adapted.num_rows(), 2);
+    assert_eq!(
+        adapted.try_column_by_name("b")?.data_type(),
+        DataType::Int32
+    );
+
+    // `c` added with a default.
+    let new_schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("c", DataType::UInt8, true),
+    ]);
+    defaults
+        .columns
+        .insert("c".to_string(), DataValue::UInt8(Some(7)));
+    let adapted = adapt_block_to_schema(block, &new_schema, &defaults)?;
+    assert_blocks_eq(
+        vec![
+            "+---+---+",
+            "| a | c |",
+            "+---+---+",
+            "| 1 | 7 |",
+            "| 2 | 7 |",
+            "+---+---+",
+        ],
+        &[adapted],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_adapt_block_to_schema_column_added_again() -> Result<()> {
+    let mut ids = ColumnIds::default();
+    ids.add_column("b");
+    let schema_with_ids = |ids: &ColumnIds| -> Result<DataSchemaRef> {
+        let meta = vec![(SCHEMA_META_KEY_COLUMN_IDS.to_string(), ids.to_json()?)];
+        Ok(Arc::new(DataSchema::new_from(
+            vec![
+                DataField::new("a", DataType::Int64, false),
+                DataField::new("b", DataType::Int64, true),
+            ],
+            meta.into_iter().collect(),
+        )))
+    };
+    let old_schema = schema_with_ids(&ids)?;
+    let block = DataBlock::create_by_array(old_schema.clone(), vec![
+        Series::new(vec![1i64, 2]),
+        Series::new(vec![10i64, 20]),
+    ]);
+    let defaults = ColumnDefaults::default();
+
+    // Same ids, the column is read from the block.
+    let adapted = adapt_block_to_schema(block.clone(), &old_schema, &defaults)?;
+    assert_eq!(adapted.schema(), &old_schema);
+
+    // `b` dropped and added back with the same type, it gets a new id.
+    ids.drop_column("b");
+    ids.add_column("b");
+    assert_eq!(ids.id("b"), 2);
+    let new_schema = schema_with_ids(&ids)?;
+    let adapted = adapt_block_to_schema(block, &new_schema, &defaults)?;
+    assert_blocks_eq(
+        vec![
+            "+---+------+",
+            "| a | b    |",
+            "+---+------+",
+            "| 1 | NULL |",
+            "| 2 | NULL |",
+            "+---+------+",
+        ],
+        &[adapted],
+    );
+
+    Ok(())
+}
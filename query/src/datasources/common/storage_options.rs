@@ -0,0 +1,164 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::path::Component;
+use std::path::Path;
+use std::str::FromStr;
+
+use common_dal::StorageScheme;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::configs::StorageConfig;
+
+/// Options of `CREATE DATABASE` and `CREATE TABLE` which override the storage config of
+/// the server, so that the data of a database or a table can live in its own storage.
+pub const STORAGE_OPT_KEY_TYPE: &str = "storage_type";
+pub const STORAGE_OPT_KEY_DISK_DATA_PATH: &str = "storage_disk_data_path";
+pub const STORAGE_OPT_KEY_S3_REGION: &str = "storage_s3_region";
+pub const STORAGE_OPT_KEY_S3_BUCKET: &str = "storage_s3_bucket";
+pub const STORAGE_OPT_KEY_S3_ACCESS_KEY_ID: &str = "storage_s3_access_key_id";
+pub const STORAGE_OPT_KEY_S3_SECRET_ACCESS_KEY: &str = "storage_s3_secret_access_key";
+pub const STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT: &str = "storage_azure_blob_account";
+pub const STORAGE_OPT_KEY_AZURE_BLOB_CONTAINER: &str = "storage_azure_blob_container";
+pub const STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT_KEY: &str = "storage_azure_blob_account_key";
+pub const STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN: &str = "storage_azure_blob_sas_token";
+pub const STORAGE_OPT_KEY_HDFS_NAME_NODE: &str = "storage_hdfs_name_node";
+pub const STORAGE_OPT_KEY_HDFS_ROOT: &str = "storage_hdfs_root";
+pub const STORAGE_OPT_KEY_HDFS_USER: &str = "storage_hdfs_user";
+pub const STORAGE_OPT_KEY_HDFS_DELEGATION_TOKEN: &str = "storage_hdfs_delegation_token";
+/// The connection of `CREATE CONNECTION` whose options are taken as the storage options,
+/// they are resolved into the options before the storage config is built.
+pub const STORAGE_OPT_KEY_CONNECTION: &str = "storage_connection";
+
+const STORAGE_OPT_KEY_PREFIX: &str = "storage_";
+
+/// The storage options prefixed by `cold_` give the cold tier of a table, e.g. `cold_storage_type`.
+pub const COLD_STORAGE_OPT_KEY_PREFIX: &str = "cold_";
+
+/// Returns the storage config overridden by the storage options,
+/// or None if there is no storage option.
+pub fn storage_config_with_options(
+    conf: &StorageConfig,
+    options: &HashMap<String, String>,
+) -> Result<Option<StorageConfig>> {
+    let mut conf = conf.clone();
+    let mut overridden = false;
+
+    for (key, value) in options {
+        let field = match key.as_str() {
+            // The storage is of the connection, even if it is not resolved yet.
+            STORAGE_OPT_KEY_CONNECTION => {
+                overridden = true;
+                continue;
+            }
+            STORAGE_OPT_KEY_TYPE => {
+                StorageScheme::from_str(value)?;
+                &mut conf.storage_type
+            }
+            STORAGE_OPT_KEY_DISK_DATA_PATH => &mut conf.disk.data_path,
+            STORAGE_OPT_KEY_S3_REGION => &mut conf.s3.region,
+            STORAGE_OPT_KEY_S3_BUCKET => &mut conf.s3.bucket,
+            STORAGE_OPT_KEY_S3_ACCESS_KEY_ID => &mut conf.s3.access_key_id,
+            STORAGE_OPT_KEY_S3_SECRET_ACCESS_KEY => &mut conf.s3.secret_access_key,
+            STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT => &mut conf.azure_blob.account,
+            STORAGE_OPT_KEY_AZURE_BLOB_CONTAINER => &mut conf.azure_blob.container,
+            STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT_KEY => &mut conf.azure_blob.account_key,
+            STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN => &mut conf.azure_blob.sas_token,
+            STORAGE_OPT_KEY_HDFS_NAME_NODE => &mut conf.hdfs.name_node,
+            STORAGE_OPT_KEY_HDFS_ROOT => &mut conf.hdfs.root,
+            STORAGE_OPT_KEY_HDFS_USER => &mut conf.hdfs.user,
+            STORAGE_OPT_KEY_HDFS_DELEGATION_TOKEN => &mut conf.hdfs.delegation_token,
+            _ if key.starts_with(STORAGE_OPT_KEY_PREFIX) => {
+                return Err(ErrorCode::BadOption(format!(
+                    "Unknown storage option: {}",
+                    key
+                )));
+            }
+            _ => continue,
+        };
+
+        *field = value.clone();
+        overridden = true;
+    }
+
+    Ok(match overridden {
+        true => Some(conf),
+        false => None,
+    })
+}
+
+/// Checks the data path given by the `storage_disk_data_path` option is under the data path of
+/// the disk storage of the server, so that a database or a table can not reach the other files
+/// of the server.
+pub fn check_disk_data_path(root: &str, path: &str) -> Result<()> {
+    let (root, path) = (Path::new(root), Path::new(path));
+    let escapes = path.components().any(|c| c == Component::ParentDir);
+    if escapes || path.is_absolute() != root.is_absolute() || !path.starts_with(root) {
+        return Err(ErrorCode::BadOption(format!(
+            "The storage option {} must be under {}, the data path of the server, got {}",
+            STORAGE_OPT_KEY_DISK_DATA_PATH,
+            root.display(),
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Whether the value of the storage option(or of its cold tier one) is a credential,
+/// which is never printed.
+pub fn is_secret_storage_option(key: &str) -> bool {
+    let key = key.strip_prefix(COLD_STORAGE_OPT_KEY_PREFIX).unwrap_or(key);
+    matches!(
+        key,
+        STORAGE_OPT_KEY_S3_ACCESS_KEY_ID
+            | STORAGE_OPT_KEY_S3_SECRET_ACCESS_KEY
+            | STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT_KEY
+            | STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN
+            | STORAGE_OPT_KEY_HDFS_DELEGATION_TOKEN
+    )
+}
+
+/// Returns the storage config of the cold tier given by the `cold_storage_*` options,
+/// or None if there is no such option.
+pub fn cold_storage_config_with_options(
+    conf: &StorageConfig,
+    options: &HashMap<String, String>,
+) -> Result<Option<StorageConfig>> {
+    let cold_options = options
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(COLD_STORAGE_OPT_KEY_PREFIX)
+                .filter(|key| key.starts_with(STORAGE_OPT_KEY_PREFIX))
+                .map(|key| (key.to_string(), value.clone()))
+        })
+        .collect::<HashMap<_, _>>();
+
+    storage_config_with_options(conf, &cold_options)
+}
+
+/// Copy the storage options(including the cold tier ones) of a database to a table created in it,
+/// the options of the table itself win.
+pub fn inherit_storage_options(
+    db_options: &HashMap<String, String>,
+    table_options: &mut HashMap<String, String>,
+) {
+    for (key, value) in db_options {
+        let cold_key = key.strip_prefix(COLD_STORAGE_OPT_KEY_PREFIX).unwrap_or(key);
+        if cold_key.starts_with(STORAGE_OPT_KEY_PREFIX) && !table_options.contains_key(key) {
+            table_options.insert(key.clone(), value.clone());
+        }
+    }
+}
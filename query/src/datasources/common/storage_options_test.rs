@@ -0,0 +1,164 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::configs::StorageConfig;
+use crate::datasources::common::check_disk_data_path;
+use crate::datasources::common::inherit_storage_options;
+use crate::datasources::common::is_secret_storage_option;
+use crate::datasources::common::storage_config_with_options;
+use crate::datasources::common::STORAGE_OPT_KEY_AZURE_BLOB_CONTAINER;
+use crate::datasources::common::STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN;
+use crate::datasources::common::STORAGE_OPT_KEY_CONNECTION;
+use crate::datasources::common::STORAGE_OPT_KEY_HDFS_NAME_NODE;
+use crate::datasources::common::STORAGE_OPT_KEY_HDFS_ROOT;
+use crate::datasources::common::STORAGE_OPT_KEY_S3_BUCKET;
+use crate::datasources::common::STORAGE_OPT_KEY_S3_REGION;
+use crate::datasources::common::STORAGE_OPT_KEY_TYPE;
+
+#[test]
+fn test_storage_config_with_options() -> Result<()> {
+    let conf = StorageConfig::default();
+
+    // no storage option, the storage of the server is used
+    let mut options = HashMap::new();
+    options.insert("location".to_string(), "foo.csv".to_string());
+    assert!(storage_config_with_options(&conf, &options)?.is_none());
+
+    options.insert(STORAGE_OPT_KEY_TYPE.to_string(), "s3".to_string());
+    options.insert(STORAGE_OPT_KEY_S3_BUCKET.to_string(), "cold".to_string());
+    let overridden = storage_config_with_options(&conf, &options)?.unwrap();
+    assert_eq!(overridden.storage_type, "s3");
+    assert_eq!(overridden.s3.bucket, "cold");
+    assert_eq!(overridden.disk, conf.disk);
+
+    let mut azure_options = HashMap::new();
+    azure_options.insert(STORAGE_OPT_KEY_TYPE.to_string(), "azblob".to_string());
+    azure_options.insert(
+        STORAGE_OPT_KEY_AZURE_BLOB_CONTAINER.to_string(),
+        "cold".to_string(),
+    );
+    azure_options.insert(
+        STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN.to_string(),
+        "sv=2020-08-04&sig=x".to_string(),
+    );
+    let overridden = storage_config_with_options(&conf, &azure_options)?.unwrap();
+    assert_eq!(overridden.storage_type, "azblob");
+    assert_eq!(overridden.azure_blob.container, "cold");
+    assert_eq!(overridden.azure_blob.sas_token, "sv=2020-08-04&sig=x");
+    assert_eq!(overridden.s3, conf.s3);
+
+    let mut hdfs_options = HashMap::new();
+    hdfs_options.insert(STORAGE_OPT_KEY_TYPE.to_string(), "hdfs".to_string());
+    hdfs_options.insert(
+        STORAGE_OPT_KEY_HDFS_NAME_NODE.to_string(),
+        "http://nn:9870".to_string(),
+    );
+    hdfs_options.insert(
+        STORAGE_OPT_KEY_HDFS_ROOT.to_string(),
+        "/warehouse".to_string(),
+    );
+    let overridden = storage_config_with_options(&conf, &hdfs_options)?.unwrap();
+    assert_eq!(overridden.storage_type, "hdfs");
+    assert_eq!(overridden.hdfs.name_node, "http://nn:9870");
+    assert_eq!(overridden.hdfs.root, "/warehouse");
+    assert_eq!(overridden.azure_blob, conf.azure_blob);
+
+    // unknown storage type
+    options.insert(STORAGE_OPT_KEY_TYPE.to_string(), "tape".to_string());
+    let r = storage_config_with_options(&conf, &options);
+    assert_eq!(
+        ErrorCode::UnknownStorageSchemeName("").code(),
+        r.unwrap_err().code()
+    );
+
+    // unknown storage option
+    let mut options = HashMap::new();
+    options.insert("storage_s3_endpoint".to_string(), "x".to_string());
+    let r = storage_config_with_options(&conf, &options);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    // the storage of a connection is its own, even before it is resolved
+    let mut options = HashMap::new();
+    options.insert(
+        STORAGE_OPT_KEY_CONNECTION.to_string(),
+        "s3_conn".to_string(),
+    );
+    assert_eq!(storage_config_with_options(&conf, &options)?, Some(conf));
+
+    Ok(())
+}
+
+#[test]
+fn test_check_disk_data_path() -> Result<()> {
+    check_disk_data_path("/var/lib/databend", "/var/lib/databend")?;
+    check_disk_data_path("/var/lib/databend", "/var/lib/databend/db1")?;
+    check_disk_data_path("_data", "_data/db1")?;
+    check_disk_data_path("", "db1")?;
+
+    let code = ErrorCode::BadOption("").code();
+    for (root, path) in [
+        ("/var/lib/databend", "/etc"),
+        ("/var/lib/databend", "/var/lib/databend2"),
+        ("/var/lib/databend", "/var/lib/databend/../../../etc"),
+        ("/var/lib/databend", "db1"),
+        ("_data", "/tmp"),
+        ("", "/tmp"),
+        ("", "../db1"),
+    ] {
+        let r = check_disk_data_path(root, path);
+        assert_eq!(code, r.unwrap_err().code(), "{} under {}", path, root);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_is_secret_storage_option() {
+    assert!(is_secret_storage_option("storage_s3_secret_access_key"));
+    assert!(is_secret_storage_option(
+        "cold_storage_azure_blob_sas_token"
+    ));
+    assert!(!is_secret_storage_option("storage_s3_bucket"));
+    assert!(!is_secret_storage_option(STORAGE_OPT_KEY_CONNECTION));
+}
+
+#[test]
+fn test_inherit_storage_options() -> Result<()> {
+    let mut db_options = HashMap::new();
+    db_options.insert(STORAGE_OPT_KEY_S3_BUCKET.to_string(), "cold".to_string());
+    db_options.insert(
+        STORAGE_OPT_KEY_S3_REGION.to_string(),
+        "us-east-2".to_string(),
+    );
+    db_options.insert("comment".to_string(), "not a storage option".to_string());
+
+    let mut table_options = HashMap::new();
+    table_options.insert(STORAGE_OPT_KEY_S3_BUCKET.to_string(), "hot".to_string());
+    inherit_storage_options(&db_options, &mut table_options);
+
+    let mut expected = HashMap::new();
+    expected.insert(STORAGE_OPT_KEY_S3_BUCKET.to_string(), "hot".to_string());
+    expected.insert(
+        STORAGE_OPT_KEY_S3_REGION.to_string(),
+        "us-east-2".to_string(),
+    );
+    assert_eq!(table_options, expected);
+
+    Ok(())
+}
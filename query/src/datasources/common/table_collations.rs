@@ -0,0 +1,146 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Options of `CREATE TABLE` which keep the collations of the string columns,
+/// e.g. `collation = 'utf8_general_ci'` for all of them and `column_collations = 'a:utf8_bin'`.
+pub const TBL_OPT_KEY_COLLATION: &str = "collation";
+pub const TBL_OPT_KEY_COLUMN_COLLATIONS: &str = "column_collations";
+
+/// How the strings are compared, only the case sensitivity is supported for now.
+/// A collation name ends with `_ci` for case-insensitive and `_cs` or `_bin` for binary,
+/// the charset prefix is ignored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Collation {
+    Binary,
+    CaseInsensitive,
+}
+
+impl Collation {
+    pub fn from_name(name: &str) -> Result<Self> {
+        let name = name.trim_matches(|s| s == '\'' || s == '"' || s == '`');
+        let lower_name = name.to_lowercase();
+        if lower_name == "binary" || lower_name.ends_with("_bin") || lower_name.ends_with("_cs") {
+            Ok(Collation::Binary)
+        } else if lower_name.ends_with("_ci") {
+            Ok(Collation::CaseInsensitive)
+        } else {
+            Err(ErrorCode::BadArguments(format!(
+                "Unknown collation: {}, expect a name ending with _ci, _cs or _bin",
+                name
+            )))
+        }
+    }
+}
+
+/// The collations of the string columns of a table, the unlisted ones use the table collation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableCollations {
+    pub collation: Option<String>,
+    pub columns: Vec<(String, String)>,
+}
+
+impl TableCollations {
+    pub fn from_options(options: &HashMap<String, String>) -> Self {
+        let collation = options
+            .get(TBL_OPT_KEY_COLLATION)
+            .map(|collation| collation.trim().to_string())
+            .filter(|collation| !collation.is_empty());
+        let columns = options
+            .get(TBL_OPT_KEY_COLUMN_COLLATIONS)
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .filter_map(|column| column.split_once(':'))
+                    .map(|(column, collation)| {
+                        (column.trim().to_string(), collation.trim().to_string())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        TableCollations { collation, columns }
+    }
+
+    pub fn to_options(&self, options: &mut HashMap<String, String>) {
+        if let Some(collation) = &self.collation {
+            options.insert(TBL_OPT_KEY_COLLATION.to_string(), collation.clone());
+        }
+        if !self.columns.is_empty() {
+            let columns = self
+                .columns
+                .iter()
+                .map(|(column, collation)| format!("{}:{}", column, collation))
+                .collect::<Vec<_>>();
+            options.insert(TBL_OPT_KEY_COLUMN_COLLATIONS.to_string(), columns.join(","));
+        }
+    }
+
+    /// Checks that the collations are known and only set on the string columns of the schema.
+    pub fn validate(&self, schema: &DataSchema) -> Result<()> {
+        if let Some(collation) = &self.collation {
+            Collation::from_name(collation)?;
+        }
+        for (column, collation) in self.columns.iter() {
+            Collation::from_name(collation)?;
+            match schema.field_with_name(column) {
+                Ok(field) if field.data_type() == &DataType::String => {}
+                Ok(field) => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "COLLATE is only allowed on String columns, column {} is {}",
+                        column,
+                        field.data_type()
+                    )))
+                }
+                Err(_) => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unknown column {} in the column collations",
+                        column
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the explicit collation name of the column.
+    pub fn column_collation_name(&self, column: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, collation)| collation.as_str())
+    }
+
+    /// Returns the collation of a column, binary if it is not a string column of the schema.
+    pub fn column_collation(&self, schema: &DataSchema, column: &str) -> Result<Collation> {
+        match schema.field_with_name(column) {
+            Ok(field) if field.data_type() == &DataType::String => {
+                match self
+                    .column_collation_name(column)
+                    .or(self.collation.as_deref())
+                {
+                    Some(collation) => Collation::from_name(collation),
+                    None => Ok(Collation::Binary),
+                }
+            }
+            _ => Ok(Collation::Binary),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::common::Collation;
+use crate::datasources::common::TableCollations;
+use crate::datasources::common::TBL_OPT_KEY_COLLATION;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_COLLATIONS;
+
+#[test]
+fn test_collation_from_name() -> Result<()> {
+    assert_eq!(
+        Collation::from_name("utf8_general_ci")?,
+        Collation::CaseInsensitive
+    );
+    assert_eq!(
+        Collation::from_name("'UTF8MB4_UNICODE_CI'")?,
+        Collation::CaseInsensitive
+    );
+    assert_eq!(Collation::from_name("utf8_bin")?, Collation::Binary);
+    assert_eq!(Collation::from_name("ascii_cs")?, Collation::Binary);
+    assert_eq!(Collation::from_name("binary")?, Collation::Binary);
+
+    let r = Collation::from_name("utf8");
+    assert_eq!(ErrorCode::BadArguments("").code(), r.unwrap_err().code());
+    Ok(())
+}
+
+#[test]
+fn test_table_collations() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::String, false),
+        DataField::new("b", DataType::String, true),
+        DataField::new("c", DataType::Int64, false),
+    ]);
+
+    let collations = TableCollations {
+        collation: Some("utf8_general_ci".to_string()),
+        columns: vec![("b".to_string(), "utf8_bin".to_string())],
+    };
+    let mut options = HashMap::new();
+    collations.to_options(&mut options);
+    assert_eq!(
+        options.get(TBL_OPT_KEY_COLLATION).unwrap(),
+        "utf8_general_ci"
+    );
+    assert_eq!(
+        options.get(TBL_OPT_KEY_COLUMN_COLLATIONS).unwrap(),
+        "b:utf8_bin"
+    );
+    assert_eq!(TableCollations::from_options(&options), collations);
+    collations.validate(&schema)?;
+
+    // the table collation only applies to the string columns
+    assert_eq!(
+        collations.column_collation(&schema, "a")?,
+        Collation::CaseInsensitive
+    );
+    assert_eq!(
+        collations.column_collation(&schema, "b")?,
+        Collation::Binary
+    );
+    assert_eq!(
+        collations.column_collation(&schema, "c")?,
+        Collation::Binary
+    );
+    assert_eq!(
+        collations.column_collation(&schema, "x")?,
+        Collation::Binary
+    );
+    assert_eq!(collations.column_collation_name("b"), Some("utf8_bin"));
+    assert_eq!(collations.column_collation_name("a"), None);
+
+    // not a string column
+    options.insert(
+        TBL_OPT_KEY_COLUMN_COLLATIONS.to_string(),
+        "c:utf8_general_ci".to_string(),
+    );
+    let r = TableCollations::from_options(&options).validate(&schema);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    // unknown column
+    options.insert(
+        TBL_OPT_KEY_COLUMN_COLLATIONS.to_string(),
+        "x:utf8_general_ci".to_string(),
+    );
+    let r = TableCollations::from_options(&options).validate(&schema);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    // unknown collation
+    options.insert(
+        TBL_OPT_KEY_COLUMN_COLLATIONS.to_string(),
+        "a:utf8".to_string(),
+    );
+    let r = TableCollations::from_options(&options).validate(&schema);
+    assert_eq!(ErrorCode::BadArguments("").code(), r.unwrap_err().code());
+
+    Ok(())
+}
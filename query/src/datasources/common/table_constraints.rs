@@ -0,0 +1,120 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Options of `CREATE TABLE` which keep the informational constraints of a table,
+/// e.g. `primary_key = 'a,b'` and `unique_keys = 'c;d,e'`.
+pub const TBL_OPT_KEY_PRIMARY_KEY: &str = "primary_key";
+pub const TBL_OPT_KEY_UNIQUE_KEYS: &str = "unique_keys";
+
+/// The PRIMARY KEY and UNIQUE constraints of a table.
+/// They are not enforced on writes, the optimizer trusts the user that the keys are unique.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableConstraints {
+    pub primary_key: Option<Vec<String>>,
+    pub unique_keys: Vec<Vec<String>>,
+}
+
+impl TableConstraints {
+    pub fn from_options(options: &HashMap<String, String>) -> Self {
+        let parse_key = |key: &str| -> Vec<String> {
+            key.split(',')
+                .map(|column| column.trim().to_string())
+                .filter(|column| !column.is_empty())
+                .collect()
+        };
+
+        let primary_key = options
+            .get(TBL_OPT_KEY_PRIMARY_KEY)
+            .map(|key| parse_key(key))
+            .filter(|key| !key.is_empty());
+        let unique_keys = options
+            .get(TBL_OPT_KEY_UNIQUE_KEYS)
+            .map(|keys| {
+                keys.split(';')
+                    .map(parse_key)
+                    .filter(|key| !key.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        TableConstraints {
+            primary_key,
+            unique_keys,
+        }
+    }
+
+    pub fn to_options(&self, options: &mut HashMap<String, String>) {
+        if let Some(primary_key) = &self.primary_key {
+            options.insert(TBL_OPT_KEY_PRIMARY_KEY.to_string(), primary_key.join(","));
+        }
+        if !self.unique_keys.is_empty() {
+            let unique_keys = self
+                .unique_keys
+                .iter()
+                .map(|key| key.join(","))
+                .collect::<Vec<_>>();
+            options.insert(TBL_OPT_KEY_UNIQUE_KEYS.to_string(), unique_keys.join(";"));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.primary_key.is_none() && self.unique_keys.is_empty()
+    }
+
+    /// Checks that the keys are made of distinct columns of the schema.
+    pub fn validate(&self, schema: &DataSchema) -> Result<()> {
+        for key in self.primary_key.iter().chain(self.unique_keys.iter()) {
+            for (i, column) in key.iter().enumerate() {
+                if schema.field_with_name(column).is_err() {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unknown column {} in the key ({})",
+                        column,
+                        key.join(", ")
+                    )));
+                }
+                if key[..i].contains(column) {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Duplicate column {} in the key ({})",
+                        column,
+                        key.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the keys whose values identify a row, the primary key first.
+    /// A UNIQUE key over a nullable column is left out, it may hold many NULLs.
+    pub fn keys(&self, schema: &DataSchema) -> Vec<Vec<String>> {
+        let not_null = |key: &&Vec<String>| {
+            key.iter().all(|column| {
+                schema
+                    .field_with_name(column)
+                    .map(|field| !field.is_nullable())
+                    .unwrap_or(false)
+            })
+        };
+
+        let mut keys = self.primary_key.iter().cloned().collect::<Vec<_>>();
+        keys.extend(self.unique_keys.iter().filter(not_null).cloned());
+        keys
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::common::TableConstraints;
+use crate::datasources::common::TBL_OPT_KEY_PRIMARY_KEY;
+use crate::datasources::common::TBL_OPT_KEY_UNIQUE_KEYS;
+
+#[test]
+fn test_table_constraints_options() -> Result<()> {
+    let constraints = TableConstraints {
+        primary_key: Some(vec!["a".to_string(), "b".to_string()]),
+        unique_keys: vec![vec!["c".to_string()], vec![
+            "d".to_string(),
+            "e".to_string(),
+        ]],
+    };
+
+    let mut options = HashMap::new();
+    constraints.to_options(&mut options);
+    assert_eq!(options.get(TBL_OPT_KEY_PRIMARY_KEY).unwrap(), "a,b");
+    assert_eq!(options.get(TBL_OPT_KEY_UNIQUE_KEYS).unwrap(), "c;d,e");
+    assert_eq!(TableConstraints::from_options(&options), constraints);
+
+    // no constraint
+    let constraints = TableConstraints::from_options(&HashMap::new());
+    assert!(constraints.is_empty());
+    let mut options = HashMap::new();
+    constraints.to_options(&mut options);
+    assert!(options.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_table_constraints_keys() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Int64, false),
+        DataField::new("c", DataType::String, true),
+    ]);
+
+    let mut options = HashMap::new();
+    options.insert(TBL_OPT_KEY_PRIMARY_KEY.to_string(), "a".to_string());
+    options.insert(TBL_OPT_KEY_UNIQUE_KEYS.to_string(), "b;c".to_string());
+    let constraints = TableConstraints::from_options(&options);
+    constraints.validate(&schema)?;
+
+    // the UNIQUE key over the nullable column is left out
+    assert_eq!(constraints.keys(&schema), vec![
+        vec!["a".to_string()],
+        vec!["b".to_string()]
+    ]);
+
+    // unknown column
+    options.insert(TBL_OPT_KEY_PRIMARY_KEY.to_string(), "x".to_string());
+    let r = TableConstraints::from_options(&options).validate(&schema);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    // duplicate column
+    options.insert(TBL_OPT_KEY_PRIMARY_KEY.to_string(), "a,a".to_string());
+    let r = TableConstraints::from_options(&options).validate(&schema);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    Ok(())
+}
@@ -0,0 +1,265 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::series::Series;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_meta_types::TableInfo;
+
+use crate::catalogs::impls::DatabaseCatalog;
+use crate::catalogs::Catalog;
+use crate::sessions::DatabendQueryContext;
+
+/// The table option which keeps the statistics collected by `ANALYZE TABLE`, in json.
+pub const TBL_OPT_KEY_COLUMN_STATISTICS: &str = "column_statistics";
+
+/// The table option which counts the rows appended to or removed from the table.
+/// It only counts the writes to the tables which are analyzed.
+pub const TBL_OPT_KEY_CHANGED_ROWS: &str = "changed_rows";
+
+const CHANGED_ROWS_MAX_RETRIES: usize = 10;
+
+/// The statistics of the columns of a table, as of the table version they are collected on.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TableStatistics {
+    pub rows: u64,
+    /// The changed rows counter of the table version the statistics are collected on.
+    #[serde(default)]
+    pub changed_rows: u64,
+    /// The number of buckets the histograms are built with.
+    #[serde(default)]
+    pub buckets: usize,
+    pub columns: BTreeMap<String, ColumnStatistics>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ColumnStatistics {
+    pub null_count: u64,
+    pub min: DataValue,
+    pub max: DataValue,
+    pub histogram: Option<Histogram>,
+}
+
+/// An equi-height histogram, the buckets hold about the same number of values.
+/// The NULLs are not in the histogram.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub buckets: Vec<HistogramBucket>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct HistogramBucket {
+    pub lower: DataValue,
+    pub upper: DataValue,
+    pub count: u64,
+    pub distinct: u64,
+}
+
+impl TableStatistics {
+    /// Collects the statistics of all the columns of the block,
+    /// and the histograms of the given columns.
+    pub fn collect(
+        block: &DataBlock,
+        histogram_columns: &[String],
+        buckets: usize,
+    ) -> Result<TableStatistics> {
+        let mut columns = BTreeMap::new();
+        for field in block.schema().fields() {
+            let column = block.try_array_by_name(field.name())?;
+            let histogram = match histogram_columns.contains(field.name()) {
+                true => Some(Histogram::build(&column, buckets)?),
+                false => None,
+            };
+
+            columns.insert(field.name().clone(), ColumnStatistics {
+                null_count: column.null_count() as u64,
+                min: column.min()?,
+                max: column.max()?,
+                histogram,
+            });
+        }
+
+        Ok(TableStatistics {
+            rows: block.num_rows() as u64,
+            changed_rows: 0,
+            buckets,
+            columns,
+        })
+    }
+
+    /// Returns None if the table has never been analyzed.
+    pub fn from_options(options: &HashMap<String, String>) -> Result<Option<TableStatistics>> {
+        match options.get(TBL_OPT_KEY_COLUMN_STATISTICS) {
+            None => Ok(None),
+            Some(value) => serde_json::from_str(value)
+                .map(Some)
+                .map_err_to_code(ErrorCode::BadOption, || {
+                    format!("Invalid table option {}", TBL_OPT_KEY_COLUMN_STATISTICS)
+                }),
+        }
+    }
+
+    pub fn to_option(&self) -> Result<String> {
+        serde_json::to_string(self).map_err_to_code(ErrorCode::LogicalError, || {
+            "Cannot serialize the table statistics"
+        })
+    }
+
+    /// The columns the histograms are built for.
+    pub fn histogram_columns(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .filter(|(_, column)| column.histogram.is_some())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// The rows changed since the statistics are collected, relative to the rows collected.
+    pub fn changed_ratio(&self, options: &HashMap<String, String>) -> f64 {
+        let changed = table_changed_rows(options).saturating_sub(self.changed_rows);
+        changed as f64 / self.rows.max(1) as f64
+    }
+}
+
+/// The changed rows counter of the table, 0 if the table is never written since analyzed.
+pub fn table_changed_rows(options: &HashMap<String, String>) -> u64 {
+    options
+        .get(TBL_OPT_KEY_CHANGED_ROWS)
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(0)
+}
+
+/// Adds the rows written by a committed change of the table to its changed rows counter.
+/// The counter is bumped in a new table version, retried if the table is changed meanwhile.
+/// Nothing is counted if the table is not analyzed as of `table_info`.
+pub fn add_table_changed_rows(
+    catalog: &DatabaseCatalog,
+    table_info: &TableInfo,
+    rows: u64,
+) -> Result<()> {
+    if rows == 0
+        || !table_info
+            .options
+            .contains_key(TBL_OPT_KEY_COLUMN_STATISTICS)
+    {
+        return Ok(());
+    }
+
+    for _ in 0..CHANGED_ROWS_MAX_RETRIES {
+        let table = catalog.get_table_by_id(table_info.table_id, None)?;
+        let latest = table.get_table_info();
+        let changed = table_changed_rows(&latest.options) + rows;
+        match catalog.upsert_table_option(
+            latest.table_id,
+            latest.version,
+            TBL_OPT_KEY_CHANGED_ROWS.to_string(),
+            changed.to_string(),
+        ) {
+            Err(cause) if cause.code() == ErrorCode::CommitTableError("").code() => continue,
+            Err(cause) => return Err(cause),
+            Ok(_) => return Ok(()),
+        }
+    }
+
+    Err(ErrorCode::CommitTableError(format!(
+        "Cannot count the changed rows of table {}, it is changed by others concurrently",
+        table_info.name
+    )))
+}
+
+/// Counts the rows written by a table engine, the write is already done when it is counted,
+/// so a failure is only logged.
+pub fn count_table_changed_rows(io_ctx: &TableIOContext, table_info: &TableInfo, rows: u64) {
+    let counted = io_ctx
+        .get_user_data::<DatabendQueryContext>()
+        .and_then(|ctx| {
+            let ctx = ctx.expect("DatabendQueryContext should not be None");
+            add_table_changed_rows(ctx.get_catalog().as_ref(), table_info, rows)
+        });
+    if let Err(cause) = counted {
+        log::warn!(
+            "Cannot count the changed rows of table {}, cause {}",
+            table_info.name,
+            cause
+        );
+    }
+}
+
+impl Histogram {
+    /// Splits the sorted non-null values of the column into at most `buckets` buckets,
+    /// the same values always fall into one bucket.
+    pub fn build(column: &Series, buckets: usize) -> Result<Histogram> {
+        let schema = DataSchemaRefExt::create(vec![DataField::new(
+            "value",
+            column.data_type().clone(),
+            true,
+        )]);
+        let block = DataBlock::create_by_array(schema, vec![column.clone()]);
+        let sorted = DataBlock::sort_block(
+            &block,
+            &[SortColumnDescription {
+                column_name: "value".to_string(),
+                asc: true,
+                nulls_first: true,
+            }],
+            None,
+        )?;
+        let values = sorted
+            .try_array_by_name("value")?
+            .slice(column.null_count(), column.len() - column.null_count())
+            .to_values()?;
+
+        let buckets = buckets.max(1);
+        let mut histogram = Histogram { buckets: vec![] };
+        let mut start = 0;
+        for bucket in 1..=buckets {
+            let mut end = values.len() * bucket / buckets;
+            if end <= start {
+                continue;
+            }
+            while end < values.len() && values[end] == values[end - 1] {
+                end += 1;
+            }
+
+            let distinct = 1
+                + (start + 1..end)
+                    .filter(|i| values[*i] != values[*i - 1])
+                    .count();
+            histogram.buckets.push(HistogramBucket {
+                lower: values[start].clone(),
+                upper: values[end - 1].clone(),
+                count: (end - start) as u64,
+                distinct: distinct as u64,
+            });
+            start = end;
+        }
+        Ok(histogram)
+    }
+
+    /// The number of values in the histogram.
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().map(|bucket| bucket.count).sum()
+    }
+}
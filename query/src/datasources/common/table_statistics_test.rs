@@ -0,0 +1,101 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::datasources::common::table_changed_rows;
+use crate::datasources::common::HistogramBucket;
+use crate::datasources::common::TableStatistics;
+use crate::datasources::common::TBL_OPT_KEY_CHANGED_ROWS;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_STATISTICS;
+
+#[test]
+fn test_table_statistics_collect() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![7i64, 1, 6, 1, 2, 3, 4, 5, 1, 1]),
+        Series::new(vec!["x", "y", "x", "y", "x", "y", "x", "y", "x", "z"]),
+    ]);
+
+    let stats = TableStatistics::collect(&block, &["a".to_string()], 4)?;
+    assert_eq!(stats.rows, 10);
+
+    let a = &stats.columns["a"];
+    assert_eq!(a.null_count, 0);
+    assert_eq!(a.min, DataValue::Int64(Some(1)));
+    assert_eq!(a.max, DataValue::Int64(Some(7)));
+
+    // the four 1s do not straddle two buckets
+    let bucket = |lower: i64, upper: i64, count: u64, distinct: u64| HistogramBucket {
+        lower: DataValue::Int64(Some(lower)),
+        upper: DataValue::Int64(Some(upper)),
+        count,
+        distinct,
+    };
+    let histogram = a.histogram.as_ref().unwrap();
+    assert_eq!(histogram.buckets, vec![
+        bucket(1, 1, 4, 1),
+        bucket(2, 2, 1, 1),
+        bucket(3, 4, 2, 2),
+        bucket(5, 7, 3, 3),
+    ]);
+    assert_eq!(histogram.count(), 10);
+
+    let b = &stats.columns["b"];
+    assert_eq!(b.min, DataValue::String(Some("x".as_bytes().to_vec())));
+    assert_eq!(b.max, DataValue::String(Some("z".as_bytes().to_vec())));
+    assert!(b.histogram.is_none());
+
+    // round trip through the table options
+    let mut options = HashMap::new();
+    assert_eq!(TableStatistics::from_options(&options)?, None);
+    options.insert(
+        TBL_OPT_KEY_COLUMN_STATISTICS.to_string(),
+        stats.to_option()?,
+    );
+    assert_eq!(TableStatistics::from_options(&options)?, Some(stats));
+
+    Ok(())
+}
+
+#[test]
+fn test_table_statistics_changed_ratio() -> Result<()> {
+    let stats = TableStatistics {
+        rows: 10,
+        changed_rows: 5,
+        buckets: 100,
+        columns: Default::default(),
+    };
+
+    let mut options = HashMap::new();
+    assert_eq!(table_changed_rows(&options), 0);
+    assert_eq!(stats.changed_ratio(&options), 0.0);
+
+    options.insert(TBL_OPT_KEY_CHANGED_ROWS.to_string(), "8".to_string());
+    assert_eq!(table_changed_rows(&options), 8);
+    assert_eq!(stats.changed_ratio(&options), 0.3);
+
+    // analyzed when empty
+    let stats = TableStatistics { rows: 0, ..stats };
+    assert_eq!(stats.changed_ratio(&options), 3.0);
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::VIRTUAL_COLUMN_ROW_ID;
+
+/// Appends the virtual column `location_column`, the file or block the rows are read from,
+/// and `_row_id`, the positions of the rows in it starting at `first_row_id`, to the block.
+pub fn append_virtual_columns(
+    block: DataBlock,
+    location_column: &str,
+    location: &str,
+    first_row_id: u64,
+) -> Result<DataBlock> {
+    let num_rows = block.num_rows();
+    let mut fields = block.schema().fields().clone();
+    let mut columns = block.columns().to_vec();
+
+    fields.push(DataField::new(location_column, DataType::String, false));
+    let location = DataValue::String(Some(location.as_bytes().to_vec()));
+    columns.push(DataColumn::Constant(location, num_rows));
+
+    fields.push(DataField::new(
+        VIRTUAL_COLUMN_ROW_ID,
+        DataType::UInt64,
+        false,
+    ));
+    let row_ids = (first_row_id..first_row_id + num_rows as u64).collect::<Vec<_>>();
+    columns.push(DataColumn::Array(Series::new(row_ids)));
+
+    Ok(DataBlock::create(DataSchemaRefExt::create(fields), columns))
+}
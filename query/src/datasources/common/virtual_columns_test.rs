@@ -0,0 +1,46 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::datasources::common::append_virtual_columns;
+
+#[test]
+fn test_append_virtual_columns() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+    let block = DataBlock::create_by_array(schema, vec![Series::new(vec![1i64, 2, 3])]);
+
+    let block = append_virtual_columns(block, "_file_name", "data/t.csv", 10)?;
+    assert_eq!(block.num_columns(), 3);
+    assert_eq!(
+        block.schema().field_with_name("_row_id")?.data_type(),
+        &DataType::UInt64
+    );
+    assert_blocks_eq(
+        vec![
+            "+---+------------+---------+",
+            "| a | _file_name | _row_id |",
+            "+---+------------+---------+",
+            "| 1 | data/t.csv | 10      |",
+            "| 2 | data/t.csv | 11      |",
+            "| 3 | data/t.csv | 12      |",
+            "+---+------------+---------+",
+        ],
+        &[block],
+    );
+    Ok(())
+}
@@ -13,16 +13,26 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
+
 use crate::catalogs::Database;
 
 pub struct DefaultDatabase {
     db_name: String,
+    tenant: String,
+    options: HashMap<String, String>,
 }
 
 impl DefaultDatabase {
-    pub fn new(db_name: impl Into<String>) -> Self {
+    pub fn new(
+        db_name: impl Into<String>,
+        tenant: impl Into<String>,
+        options: HashMap<String, String>,
+    ) -> Self {
         Self {
             db_name: db_name.into(),
+            tenant: tenant.into(),
+            options,
         }
     }
 }
@@ -31,4 +41,12 @@ impl Database for DefaultDatabase {
     fn name(&self) -> &str {
         &self.db_name
     }
+
+    fn tenant(&self) -> Option<&str> {
+        Some(&self.tenant)
+    }
+
+    fn options(&self) -> HashMap<String, String> {
+        self.options.clone()
+    }
 }
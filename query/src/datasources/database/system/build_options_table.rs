@@ -0,0 +1,113 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::configs::DATABEND_BUILD_FEATURES;
+use crate::servers::CLICKHOUSE_TCP_PROTOCOL_VERSION;
+
+pub struct BuildOptionsTable {
+    table_info: TableInfo,
+}
+
+impl BuildOptionsTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", DataType::String, false),
+            DataField::new("value", DataType::String, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "build_options".to_string(),
+            table_id,
+            schema,
+            engine: "SystemBuildOptions".to_string(),
+
+            ..Default::default()
+        };
+        BuildOptionsTable { table_info }
+    }
+
+    /// The options of the running binary, the values missing at build time are empty.
+    pub fn build_options() -> Vec<(&'static str, String)> {
+        let env = |value: Option<&str>| value.unwrap_or_default().to_string();
+        vec![
+            ("version", env(option_env!("VERGEN_BUILD_SEMVER"))),
+            ("git_sha", env(option_env!("VERGEN_GIT_SHA_SHORT"))),
+            (
+                "git_commit_timestamp",
+                env(option_env!("VERGEN_GIT_COMMIT_TIMESTAMP")),
+            ),
+            (
+                "build_timestamp",
+                env(option_env!("VERGEN_BUILD_TIMESTAMP")),
+            ),
+            ("build_profile", env(option_env!("VERGEN_CARGO_PROFILE"))),
+            (
+                "build_target",
+                env(option_env!("VERGEN_CARGO_TARGET_TRIPLE")),
+            ),
+            ("rustc_version", env(option_env!("VERGEN_RUSTC_SEMVER"))),
+            ("features", DATABEND_BUILD_FEATURES.join(",")),
+            ("allocator", "jemalloc".to_string()),
+            (
+                "clickhouse_tcp_protocol_version",
+                CLICKHOUSE_TCP_PROTOCOL_VERSION.to_string(),
+            ),
+        ]
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for BuildOptionsTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        _io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let options = Self::build_options();
+        let names: Vec<&[u8]> = options.iter().map(|(name, _)| name.as_bytes()).collect();
+        let values: Vec<&[u8]> = options.iter().map(|(_, value)| value.as_bytes()).collect();
+        let block = DataBlock::create_by_array(self.table_info.schema.clone(), vec![
+            Series::new(names),
+            Series::new(values),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.table_info.schema.clone(),
+            None,
+            vec![block],
+        )))
+    }
+}
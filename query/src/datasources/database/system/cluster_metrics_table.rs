@@ -0,0 +1,118 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::datasources::database::system::MetricsTable;
+use crate::metrics::collect_cluster_metrics;
+use crate::metrics::sum_cluster_metrics;
+use crate::metrics::CLUSTER_TOTAL_NODE;
+use crate::sessions::DatabendQueryContext;
+
+/// The metrics of every node of the cluster, and the totals of them on the node `cluster`.
+pub struct ClusterMetricsTable {
+    table_info: TableInfo,
+}
+
+impl ClusterMetricsTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("node", DataType::String, false),
+            DataField::new("metric", DataType::String, false),
+            DataField::new("kind", DataType::String, false),
+            DataField::new("labels", DataType::String, false),
+            DataField::new("value", DataType::String, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "cluster_metrics".to_string(),
+            table_id,
+            schema,
+            engine: "SystemClusterMetrics".to_string(),
+            ..Default::default()
+        };
+
+        ClusterMetricsTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ClusterMetricsTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        let cluster = ctx.get_cluster();
+        let config = ctx.get_config();
+        let timeout = ctx.get_settings().get_flight_client_timeout()?;
+        let mut nodes_samples = collect_cluster_metrics(&cluster, &config, timeout).await?;
+        let totals = sum_cluster_metrics(&nodes_samples);
+        nodes_samples.push((CLUSTER_TOTAL_NODE.to_string(), totals));
+
+        let mut nodes: Vec<Vec<u8>> = vec![];
+        let mut metrics: Vec<Vec<u8>> = vec![];
+        let mut kinds: Vec<Vec<u8>> = vec![];
+        let mut labels: Vec<Vec<u8>> = vec![];
+        let mut values: Vec<Vec<u8>> = vec![];
+        for (node, samples) in nodes_samples {
+            for sample in samples {
+                nodes.push(node.clone().into_bytes());
+                metrics.push(sample.name.into_bytes());
+                kinds.push(sample.kind.into_bytes());
+                labels.push(MetricsTable::display_sample_labels(&sample.labels)?.into_bytes());
+                values.push(MetricsTable::display_sample_value(&sample.value)?.into_bytes());
+            }
+        }
+
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(nodes),
+            Series::new(metrics),
+            Series::new(kinds),
+            Series::new(labels),
+            Series::new(values),
+        ]);
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::common::TableStatistics;
+use crate::sessions::DatabendQueryContext;
+
+/// The column statistics collected by `ANALYZE TABLE`, one row per column of the analyzed tables.
+pub struct ColumnStatisticsTable {
+    table_info: TableInfo,
+}
+
+impl ColumnStatisticsTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("database", DataType::String, false),
+            DataField::new("table", DataType::String, false),
+            DataField::new("column", DataType::String, false),
+            DataField::new("rows", DataType::UInt64, false),
+            DataField::new("null_count", DataType::UInt64, false),
+            DataField::new("min", DataType::String, false),
+            DataField::new("max", DataType::String, false),
+            DataField::new("buckets", DataType::UInt64, false),
+            DataField::new("histogram", DataType::String, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "column_statistics".to_string(),
+            table_id,
+            schema,
+            engine: "SystemColumnStatistics".to_string(),
+
+            ..Default::default()
+        };
+
+        ColumnStatisticsTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ColumnStatisticsTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        let mut databases: Vec<Vec<u8>> = vec![];
+        let mut tables: Vec<Vec<u8>> = vec![];
+        let mut columns: Vec<Vec<u8>> = vec![];
+        let mut rows: Vec<u64> = vec![];
+        let mut null_counts: Vec<u64> = vec![];
+        let mut mins: Vec<Vec<u8>> = vec![];
+        let mut maxs: Vec<Vec<u8>> = vec![];
+        let mut buckets: Vec<u64> = vec![];
+        let mut histograms: Vec<Vec<u8>> = vec![];
+
+        let catalog = ctx.get_catalog();
+        for database in ctx.get_databases()? {
+            for table in catalog.get_tables(database.name())? {
+                let statistics =
+                    match TableStatistics::from_options(&table.get_table_info().options)? {
+                        None => continue,
+                        Some(statistics) => statistics,
+                    };
+
+                for (column, column_statistics) in statistics.columns.iter() {
+                    let histogram = column_statistics
+                        .histogram
+                        .iter()
+                        .flat_map(|histogram| histogram.buckets.iter())
+                        .map(|bucket| {
+                            format!("[{}, {}]:{}", bucket.lower, bucket.upper, bucket.count)
+                        })
+                        .collect::<Vec<_>>();
+
+                    databases.push(database.name().as_bytes().to_vec());
+                    tables.push(table.name().as_bytes().to_vec());
+                    columns.push(column.clone().into_bytes());
+                    rows.push(statistics.rows);
+                    null_counts.push(column_statistics.null_count);
+                    mins.push(column_statistics.min.to_string().into_bytes());
+                    maxs.push(column_statistics.max.to_string().into_bytes());
+                    buckets.push(histogram.len() as u64);
+                    histograms.push(histogram.join(", ").into_bytes());
+                }
+            }
+        }
+
+        let block = DataBlock::create_by_array(self.table_info.schema.clone(), vec![
+            Series::new(databases),
+            Series::new(tables),
+            Series::new(columns),
+            Series::new(rows),
+            Series::new(null_counts),
+            Series::new(mins),
+            Series::new(maxs),
+            Series::new(buckets),
+            Series::new(histograms),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.table_info.schema.clone(),
+            None,
+            vec![block],
+        )))
+    }
+}
@@ -44,7 +44,7 @@ async fn test_configs_table() -> Result<()> {
     let result = stream.try_collect::<Vec<_>>().await?;
     let block = &result[0];
     assert_eq!(block.num_columns(), 4);
-    assert_eq!(block.num_rows(), 26);
+    assert_eq!(block.num_rows(), 33);
 
     let expected = vec![
         "+-----------------------------------+----------------+-------+-------------+",
@@ -55,26 +55,38 @@ async fn test_configs_table() -> Result<()> {
         "| api_tls_server_root_ca_cert       |                | query |             |",
         "| clickhouse_handler_host           | 127.0.0.1      | query |             |",
         "| clickhouse_handler_port           | 9000           | query |             |",
+        "| cluster_heartbeat_timeout_secs    | 10             | query |             |",
         "| flight_api_address                | 127.0.0.1:9090 | query |             |",
         "| http_api_address                  | 127.0.0.1:8080 | query |             |",
+        "| idle_session_timeout_secs         | 3600           | query |             |",
         "| log_dir                           | ./_logs        | log   |             |",
         "| log_level                         | INFO           | log   |             |",
         "| max_active_sessions               | 256            | query |             |",
+        "| max_running_queries               | 0              | query |             |",
+        "| max_running_queries_per_user      | 0              | query |             |",
+        "| max_server_memory_usage_mb        | 0              | query |             |",
         "| meta_address                      |                | meta  |             |",
         "| meta_client_timeout_in_second     | 10             | meta  |             |",
         "| meta_password                     |                | meta  |             |",
         "| meta_username                     | root           | meta  |             |",
         "| metric_api_address                | 127.0.0.1:7070 | query |             |",
+        "| multi_tenant                      | false          | query |             |",
         "| mysql_handler_host                | 127.0.0.1      | query |             |",
         "| mysql_handler_port                | 3307           | query |             |",
         "| namespace                         |                | query |             |",
         "| num_cpus                          | 8              | query |             |",
+        "| query_log_table                   |                | query |             |",
+        "| query_pages_size_mb               | 256            | query |             |",
+        "| queued_query_timeout_secs         | 60             | query |             |",
+        "| result_cache_size_mb              | 256            | query |             |",
         "| rpc_tls_meta_server_root_ca_cert  |                | meta  |             |",
         "| rpc_tls_meta_service_domain_name  | localhost      | meta  |             |",
         "| rpc_tls_query_server_root_ca_cert |                | query |             |",
         "| rpc_tls_query_service_domain_name | localhost      | query |             |",
         "| rpc_tls_server_cert               |                | query |             |",
         "| rpc_tls_server_key                |                | query |             |",
+        "| statistics_refresh_changed_ratio  | 0.2            | query |             |",
+        "| statistics_refresh_interval_secs  | 300            | query |             |",
         "| tenant                            |                | query |             |",
         "+-----------------------------------+----------------+-------+-------------+",
     ];
@@ -0,0 +1,103 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::sessions::DatabendQueryContext;
+
+/// The connections of the tenant of the session, the options are never shown.
+pub struct ConnectionsTable {
+    table_info: TableInfo,
+}
+
+impl ConnectionsTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", DataType::String, false),
+            DataField::new("storage_type", DataType::String, false),
+            DataField::new("created_on", DataType::UInt64, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "connections".to_string(),
+            table_id,
+            schema,
+            engine: "SystemConnections".to_string(),
+
+            ..Default::default()
+        };
+        ConnectionsTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ConnectionsTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        let connections = ctx
+            .get_sessions_manager()
+            .get_connection_manager()
+            .get_connections(&ctx.get_tenant())?;
+
+        let mut names = Vec::with_capacity(connections.len());
+        let mut storage_types = Vec::with_capacity(connections.len());
+        let mut created_ons = Vec::with_capacity(connections.len());
+
+        for connection in &connections {
+            names.push(connection.name.clone().into_bytes());
+            storage_types.push(connection.storage_type.clone().into_bytes());
+            created_ons.push(connection.created_on);
+        }
+
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(names),
+            Series::new(storage_types),
+            Series::new(created_ons),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
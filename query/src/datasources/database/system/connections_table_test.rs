@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::catalogs::Table;
+use crate::catalogs::ToReadDataSourcePlan;
+use crate::datasources::database::system::ConnectionsTable;
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_connections_table() -> Result<()> {
+    let sessions = SessionManagerBuilder::create()
+        .connection_master_keys("1:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=")
+        .build()?;
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+
+    let mut options = BTreeMap::new();
+    options.insert("storage_type".to_string(), "s3".to_string());
+    options.insert("storage_s3_secret_access_key".to_string(), "sk".to_string());
+    let connections = sessions.get_connection_manager();
+    connections.create_connection(&ctx.get_tenant(), "warehouse", &options, false)?;
+    connections.create_connection("other", "archive", &options, false)?;
+
+    let table: Arc<dyn Table> = Arc::new(ConnectionsTable::create(1));
+    let io_ctx = ctx.get_single_node_table_io_context()?;
+    let io_ctx = Arc::new(io_ctx);
+    let source_plan = table.read_plan(
+        io_ctx.clone(),
+        None,
+        Some(ctx.get_settings().get_max_threads()? as usize),
+    )?;
+
+    let stream = table.read(io_ctx, &source_plan.push_downs).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 3);
+    assert_eq!(block.num_rows(), 1);
+
+    // Only the connections of the tenant of the session are listed.
+    assert_eq!(block.column(0).to_values()?, vec![DataValue::String(Some(
+        b"warehouse".to_vec()
+    ))]);
+    assert_eq!(block.column(1).to_values()?, vec![DataValue::String(Some(
+        b"s3".to_vec()
+    ))]);
+
+    Ok(())
+}
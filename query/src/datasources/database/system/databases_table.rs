@@ -25,7 +25,6 @@ use common_planners::Extras;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
-use crate::catalogs::Catalog;
 use crate::catalogs::Table;
 use crate::sessions::DatabendQueryContext;
 
@@ -71,8 +70,7 @@ impl Table for DatabasesTable {
             .get_user_data()?
             .expect("DatabendQueryContext should not be None");
 
-        ctx.get_catalog()
-            .get_databases()
+        ctx.get_databases()
             .map(|databases_name| -> SendableDataBlockStream {
                 let databases_name_str: Vec<&[u8]> = databases_name
                     .iter()
@@ -33,7 +33,7 @@ struct LogEntry {
     time: String,
 }
 
-pub struct TracingTableStream {
+pub struct LogsTableStream {
     schema: DataSchemaRef,
     file_idx: usize,
     log_files: Vec<String>,
@@ -41,9 +41,9 @@ pub struct TracingTableStream {
     limit_offset: usize,
 }
 
-impl TracingTableStream {
+impl LogsTableStream {
     pub fn try_create(schema: DataSchemaRef, log_files: Vec<String>, limit: usize) -> Result<Self> {
-        Ok(TracingTableStream {
+        Ok(LogsTableStream {
             schema,
             log_files,
             file_idx: 0,
@@ -108,7 +108,7 @@ impl TracingTableStream {
     }
 }
 
-impl Stream for TracingTableStream {
+impl Stream for LogsTableStream {
     type Item = Result<DataBlock>;
 
     fn poll_next(
@@ -0,0 +1,103 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::sessions::DatabendQueryContext;
+
+pub struct MallocStatsTable {
+    table_info: TableInfo,
+}
+
+impl MallocStatsTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", DataType::String, false),
+            DataField::new("value", DataType::UInt64, false),
+            DataField::new("description", DataType::String, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "malloc_stats".to_string(),
+            table_id,
+            schema,
+            engine: "SystemMallocStats".to_string(),
+
+            ..Default::default()
+        };
+        MallocStatsTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for MallocStatsTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        // The memory is of the node, shared by all the tenants.
+        let stats = match ctx.is_default_tenant() {
+            true => ctx.get_sessions_manager().memory_usage(),
+            false => vec![],
+        };
+
+        let mut names = Vec::with_capacity(stats.len());
+        let mut values = Vec::with_capacity(stats.len());
+        let mut descriptions = Vec::with_capacity(stats.len());
+        for stat in stats {
+            names.push(stat.name.into_bytes());
+            values.push(stat.value);
+            descriptions.push(stat.description.into_bytes());
+        }
+
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(names),
+            Series::new(values),
+            Series::new(descriptions),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -59,7 +59,7 @@ impl MetricsTable {
         MetricsTable { table_info }
     }
 
-    fn display_sample_labels(&self, labels: &HashMap<String, String>) -> Result<String> {
+    pub(crate) fn display_sample_labels(labels: &HashMap<String, String>) -> Result<String> {
         serde_json::to_string(labels).map_err(|err| {
             ErrorCode::UnexpectedError(format!(
                 "Dump prometheus metrics on display labels: {}",
@@ -68,7 +68,7 @@ impl MetricsTable {
         })
     }
 
-    fn display_sample_value(&self, value: &MetricValue) -> Result<String> {
+    pub(crate) fn display_sample_value(value: &MetricValue) -> Result<String> {
         match value {
             MetricValue::Counter(v) => serde_json::to_string(v),
             MetricValue::Gauge(v) => serde_json::to_string(v),
@@ -112,8 +112,8 @@ impl Table for MetricsTable {
         for sample in samples.into_iter() {
             metrics.push(sample.name.clone().into_bytes());
             kinds.push(sample.kind.clone().into_bytes());
-            labels.push(self.display_sample_labels(&sample.labels)?.into_bytes());
-            values.push(self.display_sample_value(&sample.value)?.into_bytes());
+            labels.push(Self::display_sample_labels(&sample.labels)?.into_bytes());
+            values.push(Self::display_sample_value(&sample.value)?.into_bytes());
         }
 
         let block = DataBlock::create_by_array(self.table_info.schema.clone(), vec![
@@ -23,6 +23,8 @@ use futures::TryStreamExt;
 use crate::catalogs::Table;
 use crate::catalogs::ToReadDataSourcePlan;
 use crate::datasources::database::system::MetricsTable;
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_metrics_table() -> Result<()> {
@@ -52,3 +54,25 @@ async fn test_metrics_table() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_metrics_table_scans() -> Result<()> {
+    init_default_metrics_recorder();
+    let ctx = crate::tests::try_create_context()?;
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql("select * from numbers_mt(10)")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?.try_collect::<Vec<_>>().await?;
+
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("select metric, labels from system.metrics where metric like 'scan_%'")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let result = executor.execute().await?.try_collect::<Vec<_>>().await?;
+    let output = pretty_format_blocks(result.as_slice())?;
+    assert!(output.contains("scan_tables"));
+    assert!(output.contains("scan_rows"));
+    assert!(output.contains("scan_bytes"));
+    assert!(output.contains("SystemNumbersMt"));
+
+    Ok(())
+}
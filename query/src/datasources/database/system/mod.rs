@@ -12,26 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use build_options_table::BuildOptionsTable;
+pub use cluster_metrics_table::ClusterMetricsTable;
 pub use clusters_table::ClustersTable;
+pub use column_statistics_table::ColumnStatisticsTable;
 pub use configs_table::ConfigsTable;
+pub use connections_table::ConnectionsTable;
 pub use contributors_table::ContributorsTable;
 pub use credits_table::CreditsTable;
 pub use databases_table::DatabasesTable;
 pub use functions_table::FunctionsTable;
+pub use logs_table::LogsTable;
+pub use logs_table_stream::LogsTableStream;
+pub use malloc_stats_table::MallocStatsTable;
 pub use metrics_table::MetricsTable;
 pub use one_table::OneTable;
 pub use processes_table::ProcessesTable;
+pub use purges_table::PurgesTable;
+pub use query_cache_table::QueryCacheTable;
+pub use query_log_table::QueryLogTable;
+pub use query_tag_usage_table::QueryTagUsageTable;
 pub use settings_table::SettingsTable;
 pub use system_database::SystemDatabase;
 pub use tables_table::TablesTable;
 pub use tracing_table::TracingTable;
-pub use tracing_table_stream::TracingTableStream;
 
+#[cfg(test)]
+mod build_options_table_test;
 #[cfg(test)]
 mod clusters_table_test;
 #[cfg(test)]
+mod column_statistics_table_test;
+#[cfg(test)]
 mod configs_table_test;
 #[cfg(test)]
+mod connections_table_test;
+#[cfg(test)]
 mod contributors_table_test;
 #[cfg(test)]
 mod credits_table_test;
@@ -40,27 +56,47 @@ mod databases_table_test;
 #[cfg(test)]
 mod functions_table_test;
 #[cfg(test)]
+mod logs_table_test;
+#[cfg(test)]
+mod malloc_stats_table_test;
+#[cfg(test)]
 mod metrics_table_test;
 #[cfg(test)]
+mod purges_table_test;
+#[cfg(test)]
+mod query_cache_table_test;
+#[cfg(test)]
+mod query_log_table_test;
+#[cfg(test)]
 mod settings_table_test;
 #[cfg(test)]
 mod tables_table_test;
 #[cfg(test)]
 mod tracing_table_test;
 
+mod build_options_table;
+mod cluster_metrics_table;
 mod clusters_table;
+mod column_statistics_table;
 mod configs_table;
+mod connections_table;
 mod contributors_table;
 mod credits_table;
 mod databases_table;
 mod functions_table;
+mod logs_table;
+mod logs_table_stream;
+mod malloc_stats_table;
 mod metrics_table;
 mod one_table;
 mod processes_table;
+mod purges_table;
+mod query_cache_table;
+mod query_log_table;
+mod query_tag_usage_table;
 mod settings_table;
 mod system_database;
 mod tables_table;
 mod tracing_table;
-mod tracing_table_stream;
 
 // TODO introduce A "base" type VTable, to de-duplicate codes of system tables
@@ -31,7 +31,7 @@ use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Table;
 use crate::sessions::DatabendQueryContext;
-use crate::sessions::ProcessInfo;
+use crate::sessions::NodeProcessInfo;
 
 pub struct ProcessesTable {
     table_info: TableInfo,
@@ -46,6 +46,7 @@ impl ProcessesTable {
             DataField::new("state", DataType::String, false),
             DataField::new("database", DataType::String, false),
             DataField::new("extra_info", DataType::String, true),
+            DataField::new("node", DataType::String, false),
         ]);
 
         let table_info = TableInfo {
@@ -60,16 +61,29 @@ impl ProcessesTable {
         ProcessesTable { table_info }
     }
 
-    fn process_host(process_info: &ProcessInfo) -> Option<Vec<u8>> {
-        let client_address = process_info.client_address;
-        client_address.as_ref().map(|s| s.to_string().into_bytes())
-    }
-
-    fn process_extra_info(process_info: &ProcessInfo) -> Option<Vec<u8>> {
-        process_info
-            .session_extra_info
-            .clone()
-            .map(|s| s.into_bytes())
+    // The processes of the other nodes, a node which cannot be reached is skipped.
+    async fn remote_processes_info(ctx: &DatabendQueryContext) -> Result<Vec<NodeProcessInfo>> {
+        let cluster = ctx.get_cluster();
+        let config = ctx.get_config();
+        let timeout = ctx.get_settings().get_flight_client_timeout()?;
+
+        let mut processes_info = vec![];
+        for node in cluster.get_nodes() {
+            if cluster.is_local(&node) {
+                continue;
+            }
+
+            let node_processes_info = match cluster.create_node_conn(&node.id, &config).await {
+                Ok(mut client) => client.list_processes(timeout).await,
+                Err(cause) => Err(cause),
+            };
+
+            match node_processes_info {
+                Ok(node_processes_info) => processes_info.extend(node_processes_info),
+                Err(cause) => log::warn!("Cannot list processes of node {}: {}", node.id, cause),
+            }
+        }
+        Ok(processes_info)
     }
 }
 
@@ -93,7 +107,11 @@ impl Table for ProcessesTable {
             .expect("DatabendQueryContext should not be None");
 
         let sessions_manager = ctx.get_sessions_manager();
-        let processes_info = sessions_manager.processes_info();
+        let mut processes_info = sessions_manager.node_processes_info();
+        processes_info.extend(Self::remote_processes_info(&ctx).await?);
+        // The processes of the other tenants are never listed.
+        let tenant = ctx.get_tenant();
+        processes_info.retain(|process_info| process_info.tenant == tenant);
 
         let mut processes_id = Vec::with_capacity(processes_info.len());
         let mut processes_type = Vec::with_capacity(processes_info.len());
@@ -101,14 +119,16 @@ impl Table for ProcessesTable {
         let mut processes_state = Vec::with_capacity(processes_info.len());
         let mut processes_database = Vec::with_capacity(processes_info.len());
         let mut processes_extra_info = Vec::with_capacity(processes_info.len());
-
-        for process_info in &processes_info {
-            processes_id.push(process_info.id.clone().into_bytes());
-            processes_type.push(process_info.typ.clone().into_bytes());
-            processes_state.push(process_info.state.clone().into_bytes());
-            processes_database.push(process_info.database.clone().into_bytes());
-            processes_host.push(ProcessesTable::process_host(process_info));
-            processes_extra_info.push(ProcessesTable::process_extra_info(process_info));
+        let mut processes_node = Vec::with_capacity(processes_info.len());
+
+        for process_info in processes_info {
+            processes_id.push(process_info.id.into_bytes());
+            processes_type.push(process_info.typ.into_bytes());
+            processes_state.push(process_info.state.into_bytes());
+            processes_database.push(process_info.database.into_bytes());
+            processes_host.push(process_info.host.map(String::into_bytes));
+            processes_extra_info.push(process_info.extra_info.map(String::into_bytes));
+            processes_node.push(process_info.node.into_bytes());
         }
 
         let schema = self.table_info.schema.clone();
@@ -119,6 +139,7 @@ impl Table for ProcessesTable {
             Series::new(processes_state),
             Series::new(processes_database),
             Series::new(processes_extra_info),
+            Series::new(processes_node),
         ]);
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
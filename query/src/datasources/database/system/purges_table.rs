@@ -0,0 +1,118 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::sessions::DatabendQueryContext;
+
+/// The progress of removing the data of the dropped tables.
+pub struct PurgesTable {
+    table_info: TableInfo,
+}
+
+impl PurgesTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("database", DataType::String, false),
+            DataField::new("table", DataType::String, false),
+            DataField::new("table_id", DataType::UInt64, false),
+            DataField::new("removed_objects", DataType::UInt64, false),
+            DataField::new("finished", DataType::Boolean, false),
+            DataField::new("last_error", DataType::String, true),
+            DataField::new("created_on", DataType::UInt64, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "purges".to_string(),
+            table_id,
+            schema,
+            engine: "SystemPurges".to_string(),
+
+            ..Default::default()
+        };
+        PurgesTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for PurgesTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        let mut tasks = ctx.get_sessions_manager().get_purge_manager().get_tasks()?;
+        tasks.sort_by_key(|(_, task)| task.table_id);
+
+        let mut databases = Vec::with_capacity(tasks.len());
+        let mut tables = Vec::with_capacity(tasks.len());
+        let mut table_ids = Vec::with_capacity(tasks.len());
+        let mut removed_objects = Vec::with_capacity(tasks.len());
+        let mut finished = Vec::with_capacity(tasks.len());
+        let mut last_errors = Vec::with_capacity(tasks.len());
+        let mut created_ons = Vec::with_capacity(tasks.len());
+
+        for (_, task) in &tasks {
+            databases.push(task.db.clone().into_bytes());
+            tables.push(task.table.clone().into_bytes());
+            table_ids.push(task.table_id);
+            removed_objects.push(task.removed_objects);
+            finished.push(task.is_finished());
+            last_errors.push(task.last_error.clone().map(|e| e.into_bytes()));
+            created_ons.push(task.created_on);
+        }
+
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(databases),
+            Series::new(tables),
+            Series::new(table_ids),
+            Series::new(removed_objects),
+            Series::new(finished),
+            Series::new(last_errors),
+            Series::new(created_ons),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
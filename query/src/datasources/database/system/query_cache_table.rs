@@ -0,0 +1,119 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::sessions::DatabendQueryContext;
+
+pub struct QueryCacheTable {
+    table_info: TableInfo,
+}
+
+impl QueryCacheTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("key", DataType::String, false),
+            DataField::new("tables", DataType::String, false),
+            DataField::new("snapshot", DataType::String, true),
+            DataField::new("bytes", DataType::UInt64, false),
+            DataField::new("hits", DataType::UInt64, false),
+            DataField::new("age_seconds", DataType::UInt64, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "query_cache".to_string(),
+            table_id,
+            schema,
+            engine: "SystemQueryCache".to_string(),
+
+            ..Default::default()
+        };
+        QueryCacheTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QueryCacheTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        // The results computed from the tables of the other tenants are never listed.
+        let mut entries = ctx.get_sessions_manager().get_query_cache().entries();
+        entries.retain(|entry| {
+            entry.tables.iter().all(|table| {
+                let db = table.split_once('.').map_or(table.as_str(), |(db, _)| db);
+                ctx.get_database(db).is_ok()
+            })
+        });
+
+        let mut keys = Vec::with_capacity(entries.len());
+        let mut tables = Vec::with_capacity(entries.len());
+        let mut snapshots = Vec::with_capacity(entries.len());
+        let mut bytes = Vec::with_capacity(entries.len());
+        let mut hits = Vec::with_capacity(entries.len());
+        let mut ages = Vec::with_capacity(entries.len());
+
+        for entry in &entries {
+            keys.push(entry.key.clone().into_bytes());
+            tables.push(entry.tables.join(", ").into_bytes());
+            snapshots.push(entry.snapshot.clone().map(|s| s.into_bytes()));
+            bytes.push(entry.bytes);
+            hits.push(entry.hits());
+            ages.push(entry.age().as_secs());
+        }
+
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(keys),
+            Series::new(tables),
+            Series::new(snapshots),
+            Series::new(bytes),
+            Series::new(hits),
+            Series::new(ages),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
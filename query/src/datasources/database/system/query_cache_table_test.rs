@@ -0,0 +1,61 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::catalogs::Table;
+use crate::catalogs::ToReadDataSourcePlan;
+use crate::datasources::database::system::QueryCacheTable;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_cache_table() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let query_cache = ctx.get_sessions_manager().get_query_cache();
+    query_cache.put(
+        "select * from default.a",
+        vec!["default.a".to_string()],
+        Some("snapshot_1".to_string()),
+        vec![],
+    );
+    assert!(query_cache.get("select * from default.a").is_some());
+
+    let table: Arc<dyn Table> = Arc::new(QueryCacheTable::create(1));
+    let io_ctx = ctx.get_single_node_table_io_context()?;
+    let io_ctx = Arc::new(io_ctx);
+    let source_plan = table.read_plan(
+        io_ctx.clone(),
+        None,
+        Some(ctx.get_settings().get_max_threads()? as usize),
+    )?;
+
+    let stream = table.read(io_ctx, &source_plan.push_downs).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 6);
+
+    let expected = vec![
+        "+-------------------------+-----------+------------+-------+------+-------------+",
+        "| key                     | tables    | snapshot   | bytes | hits | age_seconds |",
+        "+-------------------------+-----------+------------+-------+------+-------------+",
+        "| select * from default.a | default.a | snapshot_1 | 0     | 1    | 0           |",
+        "+-------------------------+-----------+------------+-------+------+-------------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
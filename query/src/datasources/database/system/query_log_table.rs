@@ -0,0 +1,82 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::sessions::DatabendQueryContext;
+use crate::sessions::QueryLogEntry;
+
+pub struct QueryLogTable {
+    table_info: TableInfo,
+}
+
+impl QueryLogTable {
+    pub fn create(table_id: u64) -> Self {
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "query_log".to_string(),
+            table_id,
+            schema: QueryLogEntry::schema(),
+            engine: "SystemQueryLog".to_string(),
+
+            ..Default::default()
+        };
+        QueryLogTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QueryLogTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        // The queries of the other tenants are never listed.
+        let sessions = ctx.get_sessions_manager();
+        let tenant = ctx.get_tenant();
+        let blocks = match sessions.read_persisted_query_log(&tenant).await? {
+            Some(blocks) => blocks,
+            None => {
+                let entries = sessions.get_query_log().entries(&tenant);
+                vec![QueryLogEntry::to_block(&entries)]
+            }
+        };
+
+        let schema = self.table_info.schema.clone();
+        Ok(Box::pin(DataBlockStream::create(schema, None, blocks)))
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::TimeZone;
+use chrono::Utc;
+use common_base::tokio;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::catalogs::Table;
+use crate::catalogs::ToReadDataSourcePlan;
+use crate::datasources::database::system::QueryLogTable;
+use crate::datasources::database::system::QueryTagUsageTable;
+use crate::sessions::QueryLogEntry;
+use crate::sessions::QueryLogStatus;
+
+fn create_entry(
+    query_id: &str,
+    tenant: &str,
+    query_tag: &str,
+    status: QueryLogStatus,
+) -> QueryLogEntry {
+    QueryLogEntry {
+        status,
+        event_time: Utc.timestamp(1630000000, 0),
+        query_id: query_id.to_string(),
+        tenant: tenant.to_string(),
+        user: "root".to_string(),
+        query_tag: query_tag.to_string(),
+        query_text: "select * from t".to_string(),
+        scan_rows: 10,
+        scan_bytes: 80,
+        cpu_time: Duration::from_millis(3),
+        duration: Duration::from_millis(5),
+        error: "".to_string(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_log_table() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let tenant = ctx.get_tenant();
+    let query_log = ctx.get_sessions_manager().get_query_log();
+    query_log.append(create_entry("q1", &tenant, "etl", QueryLogStatus::Finish));
+    query_log.append(create_entry("q2", &tenant, "etl", QueryLogStatus::Error));
+    query_log.append(create_entry("q3", &tenant, "bi", QueryLogStatus::Finish));
+    // The started queries are listed, but not accounted until they finish.
+    query_log.append(create_entry("q4", &tenant, "bi", QueryLogStatus::Start));
+    // The queries of the other tenants are never listed.
+    query_log.append(create_entry("q5", "tenant2", "etl", QueryLogStatus::Finish));
+
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let max_threads = Some(ctx.get_settings().get_max_threads()? as usize);
+
+    let table: Arc<dyn Table> = Arc::new(QueryLogTable::create(1));
+    let source_plan = table.read_plan(io_ctx.clone(), None, max_threads)?;
+    let stream = table.read(io_ctx.clone(), &source_plan.push_downs).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+----------+--------+------------+--------+------+-----------+-----------------+-----------+------------+-------------+-------------+-------+",
+        "| query_id | status | event_time | tenant | user | query_tag | query_text      | scan_rows | scan_bytes | cpu_time_ms | duration_ms | error |",
+        "+----------+--------+------------+--------+------+-----------+-----------------+-----------+------------+-------------+-------------+-------+",
+        "| q1       | Finish | 1630000000 |        | root | etl       | select * from t | 10        | 80         | 3           | 5           |       |",
+        "| q2       | Error  | 1630000000 |        | root | etl       | select * from t | 10        | 80         | 3           | 5           |       |",
+        "| q3       | Finish | 1630000000 |        | root | bi        | select * from t | 10        | 80         | 3           | 5           |       |",
+        "| q4       | Start  | 1630000000 |        | root | bi        | select * from t | 10        | 80         | 3           | 5           |       |",
+        "+----------+--------+------------+--------+------+-----------+-----------------+-----------+------------+-------------+-------------+-------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let table: Arc<dyn Table> = Arc::new(QueryTagUsageTable::create(2));
+    let source_plan = table.read_plan(io_ctx.clone(), None, max_threads)?;
+    let stream = table.read(io_ctx, &source_plan.push_downs).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+-----------+---------+-----------+------------+-------------+",
+        "| query_tag | queries | scan_rows | scan_bytes | cpu_time_ms |",
+        "+-----------+---------+-----------+------------+-------------+",
+        "| bi        | 1       | 10        | 80         | 3           |",
+        "| etl       | 2       | 20        | 160        | 6           |",
+        "+-----------+---------+-----------+------------+-------------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::sessions::DatabendQueryContext;
+
+/// The usage of the queries of this node by the query tag, for the chargeback.
+pub struct QueryTagUsageTable {
+    table_info: TableInfo,
+}
+
+impl QueryTagUsageTable {
+    pub fn create(table_id: u64) -> Self {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("query_tag", DataType::String, false),
+            DataField::new("queries", DataType::UInt64, false),
+            DataField::new("scan_rows", DataType::UInt64, false),
+            DataField::new("scan_bytes", DataType::UInt64, false),
+            DataField::new("cpu_time_ms", DataType::UInt64, false),
+        ]);
+
+        let table_info = TableInfo {
+            db: "system".to_string(),
+            name: "query_tag_usage".to_string(),
+            table_id,
+            schema,
+            engine: "SystemQueryTagUsage".to_string(),
+
+            ..Default::default()
+        };
+        QueryTagUsageTable { table_info }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QueryTagUsageTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        let query_log = ctx.get_sessions_manager().get_query_log();
+        let usage = query_log.usage(&ctx.get_tenant());
+
+        let mut query_tags = Vec::with_capacity(usage.len());
+        let mut queries = Vec::with_capacity(usage.len());
+        let mut scan_rows = Vec::with_capacity(usage.len());
+        let mut scan_bytes = Vec::with_capacity(usage.len());
+        let mut cpu_times = Vec::with_capacity(usage.len());
+
+        for (query_tag, tag_usage) in usage {
+            query_tags.push(query_tag.into_bytes());
+            queries.push(tag_usage.queries);
+            scan_rows.push(tag_usage.scan_rows);
+            scan_bytes.push(tag_usage.scan_bytes);
+            cpu_times.push(tag_usage.cpu_time.as_millis() as u64);
+        }
+
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(query_tags),
+            Series::new(queries),
+            Series::new(scan_rows),
+            Series::new(scan_bytes),
+            Series::new(cpu_times),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
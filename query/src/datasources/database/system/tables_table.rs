@@ -75,7 +75,7 @@ impl Table for TablesTable {
             .expect("DatabendQueryContext should not be None");
 
         let cata = ctx.get_catalog();
-        let databases = cata.get_databases()?;
+        let databases = ctx.get_databases()?;
 
         let mut database_tables = vec![];
         for database in databases {
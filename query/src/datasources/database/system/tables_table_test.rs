@@ -40,22 +40,32 @@ async fn test_tables_table() -> Result<()> {
     assert_eq!(block.num_columns(), 3);
 
     let expected = vec![
-        "+----------+--------------+--------------------+",
-        "| database | name         | engine             |",
-        "+----------+--------------+--------------------+",
-        "| system   | clusters     | SystemClusters     |",
-        "| system   | configs      | SystemConfigs      |",
-        "| system   | contributors | SystemContributors |",
-        "| system   | credits      | SystemCredits      |",
-        "| system   | databases    | SystemDatabases    |",
-        "| system   | functions    | SystemFunctions    |",
-        "| system   | metrics      | SystemMetrics      |",
-        "| system   | one          | SystemOne          |",
-        "| system   | processes    | SystemProcesses    |",
-        "| system   | settings     | SystemSettings     |",
-        "| system   | tables       | SystemTables       |",
-        "| system   | tracing      | SystemTracing      |",
-        "+----------+--------------+--------------------+",
+        "+----------+-------------------+------------------------+",
+        "| database | name              | engine                 |",
+        "+----------+-------------------+------------------------+",
+        "| system   | build_options     | SystemBuildOptions     |",
+        "| system   | cluster_metrics   | SystemClusterMetrics   |",
+        "| system   | clusters          | SystemClusters         |",
+        "| system   | column_statistics | SystemColumnStatistics |",
+        "| system   | configs           | SystemConfigs          |",
+        "| system   | connections       | SystemConnections      |",
+        "| system   | contributors      | SystemContributors     |",
+        "| system   | credits           | SystemCredits          |",
+        "| system   | databases         | SystemDatabases        |",
+        "| system   | functions         | SystemFunctions        |",
+        "| system   | logs              | SystemLogs             |",
+        "| system   | malloc_stats      | SystemMallocStats      |",
+        "| system   | metrics           | SystemMetrics          |",
+        "| system   | one               | SystemOne              |",
+        "| system   | processes         | SystemProcesses        |",
+        "| system   | purges            | SystemPurges           |",
+        "| system   | query_cache       | SystemQueryCache       |",
+        "| system   | query_log         | SystemQueryLog         |",
+        "| system   | query_tag_usage   | SystemQueryTagUsage    |",
+        "| system   | settings          | SystemSettings         |",
+        "| system   | tables            | SystemTables           |",
+        "| system   | tracing           | SystemTracing          |",
+        "+----------+-------------------+------------------------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
 
@@ -15,21 +15,27 @@
 use std::any::Any;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
 use common_context::IOContext;
 use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
-use common_exception::ErrorCode;
+use common_datavalues::DataValue;
 use common_exception::Result;
 use common_meta_types::TableInfo;
+use common_planners::Expression;
 use common_planners::Extras;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
-use common_tracing::tracing;
-use walkdir::WalkDir;
+use common_tracing::SpanBuffer;
 
 use crate::catalogs::Table;
-use crate::datasources::database::system::TracingTableStream;
 use crate::sessions::DatabendQueryContext;
 
 pub struct TracingTable {
@@ -38,16 +44,16 @@ pub struct TracingTable {
 
 impl TracingTable {
     pub fn create(table_id: u64) -> Self {
-        // {"v":0,"name":"databend-query","msg":"Group by partial cost: 9.071158ms","level":20,"hostname":"databend","pid":56776,"time":"2021-06-24T02:17:28.679642889+00:00"}
-
         let schema = DataSchemaRefExt::create(vec![
-            DataField::new("v", DataType::Int64, false),
+            DataField::new("query_id", DataType::String, false),
+            DataField::new("span_id", DataType::UInt64, false),
+            DataField::new("parent_id", DataType::UInt64, false),
             DataField::new("name", DataType::String, false),
-            DataField::new("msg", DataType::String, false),
-            DataField::new("level", DataType::Int8, false),
-            DataField::new("hostname", DataType::String, false),
-            DataField::new("pid", DataType::Int64, false),
-            DataField::new("time", DataType::String, false),
+            DataField::new("target", DataType::String, false),
+            DataField::new("level", DataType::String, false),
+            DataField::new("start_time", DataType::String, false),
+            DataField::new("duration_us", DataType::UInt64, false),
+            DataField::new("fields", DataType::String, false),
         ]);
 
         let table_info = TableInfo {
@@ -61,6 +67,30 @@ impl TracingTable {
 
         TracingTable { table_info }
     }
+
+    /// The query id of a `query_id = '...'` filter, to only copy the spans of this query.
+    fn query_id_filter(push_downs: &Option<Extras>) -> Option<String> {
+        let extras = push_downs.as_ref()?;
+        extras.filters.iter().find_map(|filter| match filter {
+            Expression::BinaryExpression { op, left, right } if op == "=" => {
+                match (left.as_ref(), right.as_ref()) {
+                    (Expression::Column(name), Expression::Literal { value, .. })
+                    | (Expression::Literal { value, .. }, Expression::Column(name))
+                        if name == "query_id" =>
+                    {
+                        match value {
+                            DataValue::String(Some(id)) => {
+                                Some(String::from_utf8_lossy(id).to_string())
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -78,35 +108,59 @@ impl Table for TracingTable {
         io_ctx: Arc<TableIOContext>,
         push_downs: &Option<Extras>,
     ) -> Result<SendableDataBlockStream> {
-        let mut log_files = vec![];
-
         let ctx: Arc<DatabendQueryContext> = io_ctx
             .get_user_data()?
             .expect("DatabendQueryContext should not be None");
 
-        for entry in WalkDir::new(ctx.get_config().log.log_dir.as_str())
-            .sort_by_key(|file| file.file_name().to_owned())
-        {
-            let entry = entry.map_err(|e| ErrorCode::UnknownException(format!("{}", e)))?;
-            if !entry.path().is_dir() {
-                log_files.push(entry.path().display().to_string());
+        // The spans are of the queries of all the tenants.
+        let spans = match ctx.is_default_tenant() {
+            true => {
+                let query_id = Self::query_id_filter(push_downs);
+                SpanBuffer::instance().spans(query_id.as_deref())
             }
-        }
-
-        // Default limit.
-        let mut limit = 100000000_usize;
-        tracing::debug!("read push_down:{:?}", push_downs);
+            false => vec![],
+        };
 
-        if let Some(extras) = push_downs {
-            if let Some(limit_push_down) = extras.limit {
-                limit = limit_push_down;
-            }
+        let mut query_ids = Vec::with_capacity(spans.len());
+        let mut span_ids = Vec::with_capacity(spans.len());
+        let mut parent_ids = Vec::with_capacity(spans.len());
+        let mut names = Vec::with_capacity(spans.len());
+        let mut targets = Vec::with_capacity(spans.len());
+        let mut levels = Vec::with_capacity(spans.len());
+        let mut start_times = Vec::with_capacity(spans.len());
+        let mut durations = Vec::with_capacity(spans.len());
+        let mut fields = Vec::with_capacity(spans.len());
+
+        for span in spans {
+            let start_time = DateTime::<Utc>::from(span.start_time);
+            query_ids.push(span.query_id.into_bytes());
+            span_ids.push(span.span_id);
+            parent_ids.push(span.parent_id);
+            names.push(span.name.into_bytes());
+            targets.push(span.target.into_bytes());
+            levels.push(span.level.into_bytes());
+            start_times.push(
+                start_time
+                    .to_rfc3339_opts(SecondsFormat::Micros, true)
+                    .into_bytes(),
+            );
+            durations.push(span.duration.as_micros() as u64);
+            fields.push(span.fields.into_bytes());
         }
 
-        Ok(Box::pin(TracingTableStream::try_create(
-            self.table_info.schema.clone(),
-            log_files,
-            limit,
-        )?))
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(query_ids),
+            Series::new(span_ids),
+            Series::new(parent_ids),
+            Series::new(names),
+            Series::new(targets),
+            Series::new(levels),
+            Series::new(start_times),
+            Series::new(durations),
+            Series::new(fields),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
     }
 }
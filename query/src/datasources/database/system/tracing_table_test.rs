@@ -13,40 +13,56 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use common_base::tokio;
 use common_exception::Result;
+use common_planners::*;
+use common_tracing::SpanBuffer;
+use common_tracing::SpanRecord;
 use futures::TryStreamExt;
 
 use crate::catalogs::Table;
-use crate::catalogs::ToReadDataSourcePlan;
 use crate::datasources::database::system::TracingTable;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_tracing_table() -> Result<()> {
     let ctx = crate::tests::try_create_context()?;
     let table: Arc<dyn Table> = Arc::new(TracingTable::create(1));
-    let io_ctx = ctx.get_single_node_table_io_context()?;
-    let io_ctx = Arc::new(io_ctx);
-    let source_plan = table.read_plan(
-        io_ctx.clone(),
-        None,
-        Some(ctx.get_settings().get_max_threads()? as usize),
-    )?;
-
-    let stream = table.read(io_ctx, &source_plan.push_downs).await?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+
+    let span = |query_id: &str, span_id: u64, parent_id: u64, name: &str| SpanRecord {
+        query_id: query_id.to_string(),
+        span_id,
+        parent_id,
+        name: name.to_string(),
+        target: "databend_query::interpreters".to_string(),
+        level: "DEBUG".to_string(),
+        start_time: SystemTime::UNIX_EPOCH,
+        duration: Duration::from_micros(1500),
+        fields: "".to_string(),
+    };
+    SpanBuffer::instance().push(span("tracing-table-test", 2, 1, "read"));
+    SpanBuffer::instance().push(span("tracing-table-test", 1, 0, "execute"));
+    SpanBuffer::instance().push(span("tracing-table-test-other", 3, 0, "execute"));
+
+    let push_downs = Some(Extras {
+        filters: vec![col("query_id").eq(lit("tracing-table-test".as_bytes()))],
+        ..Extras::default()
+    });
+    let stream = table.read(io_ctx, &push_downs).await?;
     let result = stream.try_collect::<Vec<_>>().await?;
     let block = &result[0];
-    assert_eq!(block.num_columns(), 7);
-    assert_eq!(block.num_rows(), 2);
+    assert_eq!(block.num_columns(), 9);
 
     let expected = vec![
-            "+---+----------------+---------------------------------------------+-------+----------+--------+-------------------------------------+",
-            "| v | name           | msg                                         | level | hostname | pid    | time                                |",
-            "+---+----------------+---------------------------------------------+-------+----------+--------+-------------------------------------+",
-            "| 0 | databend-query | signal received, starting graceful shutdown | 20    | thinkpad | 121242 | 2021-06-25T04:57:49.243264399+00:00 |",
-            "| 0 | databend-query | signal received, starting graceful shutdown | 20    | thinkpad | 121242 | 2021-06-25T04:57:49.243264399+00:00 |",
-            "+---+----------------+---------------------------------------------+-------+----------+--------+-------------------------------------+",
+        "+--------------------+---------+-----------+---------+------------------------------+-------+-----------------------------+-------------+--------+",
+        "| query_id           | span_id | parent_id | name    | target                       | level | start_time                  | duration_us | fields |",
+        "+--------------------+---------+-----------+---------+------------------------------+-------+-----------------------------+-------------+--------+",
+        "| tracing-table-test | 1       | 0         | execute | databend_query::interpreters | DEBUG | 1970-01-01T00:00:00.000000Z | 1500        |        |",
+        "| tracing-table-test | 2       | 1         | read    | databend_query::interpreters | DEBUG | 1970-01-01T00:00:00.000000Z | 1500        |        |",
+        "+--------------------+---------+-----------+---------+------------------------------+-------+-----------------------------+-------------+--------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
 
@@ -41,6 +41,7 @@ async fn test_datasource() -> Result<()> {
             if_not_exists: false,
             db: "test_db".to_string(),
             options: Default::default(),
+            tables: vec![],
         })?;
 
         // Check
@@ -51,6 +52,7 @@ async fn test_datasource() -> Result<()> {
         catalog.drop_database(DropDatabasePlan {
             if_exists: false,
             db: "test_db".to_string(),
+            cascade: false,
         })?;
 
         // Check.
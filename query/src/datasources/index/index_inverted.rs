@@ -0,0 +1,64 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use common_datavalues::columns::DataColumn;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_functions::scalars::StringMatchFunction;
+
+use crate::datasources::index::IndexSchemaVersion;
+
+/// Inverted index of a string column in one block: the distinct tokens of the values, sorted.
+/// The postings are the block itself, a block without all the tokens of a `MATCH` query
+/// has no rows matching it.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InvertedIndex {
+    pub col: String,
+    pub tokens: Vec<String>,
+    pub version: IndexSchemaVersion,
+}
+
+impl InvertedIndex {
+    pub fn typ(&self) -> &str {
+        "inverted"
+    }
+
+    /// Create index for the column of one block, the values are tokenized like `MATCH` does.
+    pub fn create_index(col: &str, column: &DataColumn) -> Result<InvertedIndex> {
+        let series = column
+            .to_minimal_array()?
+            .cast_with_type(&DataType::String)?;
+        let mut tokens = BTreeSet::new();
+        for value in series.string()?.into_iter().flatten() {
+            tokens.extend(StringMatchFunction::tokenize(value));
+        }
+
+        Ok(InvertedIndex {
+            col: col.to_string(),
+            tokens: tokens.into_iter().collect(),
+            version: IndexSchemaVersion::V1,
+        })
+    }
+
+    /// Whether some rows of the block may match the query of `MATCH`.
+    pub fn may_match(&self, query: &[u8]) -> bool {
+        let query_tokens = StringMatchFunction::tokenize(query);
+        !query_tokens.is_empty()
+            && query_tokens
+                .iter()
+                .all(|token| self.tokens.binary_search(token).is_ok())
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::index::IndexSchemaVersion;
+use crate::datasources::index::InvertedIndex;
+
+#[test]
+fn test_inverted_index() -> Result<()> {
+    let column: DataColumn = Series::new(vec![
+        Some("GET /api/users 200"),
+        None,
+        Some("POST /api/Users 500"),
+    ])
+    .into();
+
+    let index = InvertedIndex::create_index("msg", &column)?;
+    assert_eq!(index, InvertedIndex {
+        col: "msg".to_string(),
+        tokens: ["200", "500", "api", "get", "post", "users"]
+            .iter()
+            .map(|token| token.to_string())
+            .collect(),
+        version: IndexSchemaVersion::V1,
+    });
+
+    assert!(index.may_match(b"users"));
+    assert!(index.may_match(b"POST 500"));
+    // All the tokens are in the block, though not in the same row.
+    assert!(index.may_match(b"get 500"));
+    assert!(!index.may_match(b"put"));
+    assert!(!index.may_match(b"users 404"));
+    assert!(!index.may_match(b"/"));
+    Ok(())
+}
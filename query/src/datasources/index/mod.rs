@@ -13,6 +13,8 @@
 // limitations under the License.
 //
 
+#[cfg(test)]
+mod index_inverted_test;
 #[cfg(test)]
 mod index_min_max_test;
 #[cfg(test)]
@@ -20,11 +22,13 @@ mod index_sparse_test;
 #[cfg(test)]
 mod range_filter_test;
 
+mod index_inverted;
 mod index_min_max;
 mod index_sparse;
 #[allow(dead_code)]
 pub mod range_filter;
 
+pub use index_inverted::InvertedIndex;
 pub use index_min_max::MinMaxIndex;
 pub use index_sparse::SparseIndex;
 pub use index_sparse::SparseIndexValue;
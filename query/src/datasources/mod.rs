@@ -22,3 +22,4 @@ pub(crate) mod table_engine_registry;
 pub(crate) mod table_func;
 pub(crate) mod table_func_engine;
 pub(crate) mod table_func_engine_registry;
+pub(crate) mod table_options;
@@ -20,18 +20,23 @@ use std::sync::Arc;
 use common_context::DataContext;
 use common_context::IOContext;
 use common_context::TableIOContext;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableInfo;
 use common_planners::Extras;
 use common_planners::Partitions;
 use common_planners::Statistics;
+use common_planners::VIRTUAL_COLUMN_FILE_NAME;
+use common_planners::VIRTUAL_COLUMN_ROW_ID;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Table;
 use crate::datasources::common::count_lines;
 use crate::datasources::common::generate_parts;
 use crate::datasources::table::csv::csv_table_stream::CsvTableStream;
+use crate::datasources::table_options::parse_bool_option;
 use crate::sessions::DatabendQueryContext;
 
 pub struct CsvTable {
@@ -46,7 +51,10 @@ impl CsvTable {
         _data_ctx: Arc<dyn DataContext<u64>>,
     ) -> Result<Box<dyn Table>> {
         let options = &table_info.options;
-        let has_header = options.get("has_header").is_some();
+        let has_header = options
+            .get("has_header")
+            .and_then(|v| parse_bool_option(v))
+            .unwrap_or(false);
         let file = match options.get("location") {
             None => {
                 return Result::Err(ErrorCode::BadOption(
@@ -74,6 +82,13 @@ impl Table for CsvTable {
         &self.table_info
     }
 
+    fn virtual_columns(&self) -> Vec<DataField> {
+        vec![
+            DataField::new(VIRTUAL_COLUMN_FILE_NAME, DataType::String, false),
+            DataField::new(VIRTUAL_COLUMN_ROW_ID, DataType::UInt64, false),
+        ]
+    }
+
     fn read_partitions(
         &self,
         io_ctx: Arc<TableIOContext>,
@@ -96,16 +111,22 @@ impl Table for CsvTable {
     async fn read(
         &self,
         io_ctx: Arc<TableIOContext>,
-        _push_downs: &Option<Extras>,
+        push_downs: &Option<Extras>,
     ) -> Result<SendableDataBlockStream> {
         let ctx: Arc<DatabendQueryContext> = io_ctx
             .get_user_data()?
             .expect("DatabendQueryContext should not be None");
+        let virtual_columns = match push_downs {
+            Some(push_downs) => !push_downs.virtual_columns.is_empty(),
+            None => false,
+        };
 
         Ok(Box::pin(CsvTableStream::try_create(
             ctx,
             self.table_info.schema.clone(),
             self.file.clone(),
+            self.has_header,
+            virtual_columns,
         )?))
     }
 }
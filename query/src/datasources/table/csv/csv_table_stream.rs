@@ -21,14 +21,19 @@ use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::VIRTUAL_COLUMN_FILE_NAME;
 use futures::Stream;
 
+use crate::datasources::common::append_virtual_columns;
 use crate::sessions::DatabendQueryContextRef;
 
 pub struct CsvTableStream {
     ctx: DatabendQueryContextRef,
     file: String,
     schema: DataSchemaRef,
+    has_header: bool,
+    /// Appends `_file_name` and `_row_id` to the blocks
+    virtual_columns: bool,
 }
 
 impl CsvTableStream {
@@ -36,8 +41,16 @@ impl CsvTableStream {
         ctx: DatabendQueryContextRef,
         schema: DataSchemaRef,
         file: String,
+        has_header: bool,
+        virtual_columns: bool,
     ) -> Result<Self> {
-        Ok(CsvTableStream { ctx, file, schema })
+        Ok(CsvTableStream {
+            ctx,
+            file,
+            schema,
+            has_header,
+            virtual_columns,
+        })
     }
 
     pub fn try_get_one_block(&self) -> Result<Option<DataBlock>> {
@@ -71,6 +84,14 @@ impl CsvTableStream {
         )?;
 
         let block = DataBlock::try_from(record)?;
+        if !self.virtual_columns {
+            return Ok(Some(block));
+        }
+
+        // The rows are numbered from the first line after the header.
+        let first_row_id = (begin - self.has_header as usize) as u64;
+        let block =
+            append_virtual_columns(block, VIRTUAL_COLUMN_FILE_NAME, &self.file, first_row_id)?;
         Ok(Some(block))
     }
 }
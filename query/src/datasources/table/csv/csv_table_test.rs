@@ -183,3 +183,77 @@ async fn test_csv_table_parse_error() -> Result<()> {
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn test_csv_table_virtual_columns() -> Result<()> {
+    let location = env::current_dir()?
+        .join("../tests/data/sample.csv")
+        .display()
+        .to_string();
+    let options: TableOptions = [("location".to_string(), location.clone())]
+        .iter()
+        .cloned()
+        .collect();
+
+    let ctx = crate::tests::try_create_context()?;
+    let table = CsvTable::try_create(
+        TableInfo {
+            database_id: 0,
+            db: "default".into(),
+            name: "test_csv".into(),
+            schema: DataSchemaRefExt::create(vec![DataField::new(
+                "column1",
+                DataType::UInt64,
+                false,
+            )]),
+            engine: "Csv".to_string(),
+            options,
+            table_id: 0,
+            version: 0,
+        },
+        Arc::new(TableDataContext::default()),
+    )?;
+
+    let push_downs = Extras {
+        virtual_columns: vec![VIRTUAL_COLUMN_FILE_NAME.to_string()],
+        ..Extras::default()
+    };
+    let partitions = ctx.get_settings().get_max_threads()? as usize;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let source_plan = table.read_plan(io_ctx.clone(), Some(push_downs), Some(partitions))?;
+    assert_eq!(
+        source_plan.schema().field(1).name(),
+        VIRTUAL_COLUMN_FILE_NAME
+    );
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+
+    let stream = table.read(io_ctx, &source_plan.push_downs).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let mut rows = 0;
+    for block in result.iter() {
+        assert_eq!(block.num_columns(), 3);
+        let values = block.column(0).to_values()?;
+        let file_names = block
+            .try_column_by_name(VIRTUAL_COLUMN_FILE_NAME)?
+            .to_values()?;
+        let row_ids = block
+            .try_column_by_name(VIRTUAL_COLUMN_ROW_ID)?
+            .to_values()?;
+        for i in 0..block.num_rows() {
+            // The first column of sample.csv is the line number, counted from 1.
+            let row_id = match &row_ids[i] {
+                DataValue::UInt64(Some(row_id)) => *row_id,
+                other => panic!("unexpected row id {:?}", other),
+            };
+            assert_eq!(values[i], DataValue::UInt64(Some(row_id + 1)));
+            assert_eq!(
+                file_names[i],
+                DataValue::String(Some(location.as_bytes().to_vec()))
+            );
+        }
+        rows += block.num_rows();
+    }
+    assert_eq!(rows, 6);
+
+    Ok(())
+}
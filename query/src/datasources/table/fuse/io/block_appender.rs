@@ -14,24 +14,37 @@
 //
 use std::sync::Arc;
 
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::compute::cast::can_cast_types;
+use common_arrow::arrow::compute::cast::cast;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
 use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::io::parquet::write::WriteOptions;
 use common_arrow::arrow::io::parquet::write::*;
 use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::parquet::encoding::Encoding;
+use common_arrow::parquet::metadata::KeyValue;
 use common_dal::DataAccessor;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchema;
+use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use futures::StreamExt;
 use rusoto_core::ByteStream;
 
+use super::BlockWriteOptions;
+use crate::datasources::common::SCHEMA_META_KEY_COLUMN_IDS;
 use crate::datasources::table::fuse::util;
 use crate::datasources::table::fuse::SegmentInfo;
 use crate::datasources::table::fuse::Stats;
 
-pub type BlockStream =
-    std::pin::Pin<Box<dyn futures::stream::Stream<Item = DataBlock> + Sync + Send + 'static>>;
+pub type BlockStream = std::pin::Pin<
+    Box<dyn futures::stream::Stream<Item = Result<DataBlock>> + Sync + Send + 'static>,
+>;
 
 /// dummy struct, namespace placeholder
 pub struct BlockAppender;
@@ -42,16 +55,23 @@ impl BlockAppender {
         data_accessor: Arc<dyn DataAccessor>,
         mut stream: BlockStream,
         data_schema: &DataSchema,
+        write_options: &BlockWriteOptions,
     ) -> Result<SegmentInfo> {
         let mut stats_acc = util::StatisticsAccumulator::new();
         let mut block_meta_acc = util::BlockMetaAccumulator::new();
 
         // accumulate the stats and save the blocks
         while let Some(block) = stream.next().await {
+            let block = block?;
             stats_acc.acc(&block)?;
-            let schema = block.schema().to_arrow();
+            stats_acc.acc_indexes(&block, &write_options.skipping_indexes)?;
+            // The blocks keep the ids of the columns of the table, see `adapt_block_to_schema`.
+            let schema =
+                DataSchema::new_from(block.schema().fields().clone(), data_schema.meta().clone())
+                    .to_arrow();
             let location = util::gen_unique_block_location();
-            let file_size = Self::save_block(&schema, block, &data_accessor, &location).await?;
+            let file_size =
+                Self::save_block(&schema, block, &data_accessor, &location, write_options).await?;
             block_meta_acc.acc(file_size, location, &mut stats_acc);
         }
 
@@ -68,6 +88,8 @@ impl BlockAppender {
                 compressed_byte_size: stats_acc.file_size,
                 col_stats: summary,
             },
+            created_on: Some(util::unix_timestamp_secs()),
+            moved_on: None,
         };
         Ok(segment_info)
     }
@@ -77,6 +99,7 @@ impl BlockAppender {
         block: DataBlock,
         data_accessor: impl AsRef<dyn DataAccessor>,
         location: &str,
+        write_options: &BlockWriteOptions,
     ) -> Result<u64> {
         let data_accessor = data_accessor.as_ref();
         let options = WriteOptions {
@@ -84,17 +107,79 @@ impl BlockAppender {
             compression: Compression::Lz4, // let's begin with lz4
             version: Version::V2,
         };
-        let batch = RecordBatch::try_from(block)?;
-        let encodings: Vec<_> = arrow_schema
+
+        // The dictionary encoded columns are written as arrow dictionaries, their parquet types
+        // are the same as the plain ones, so the readers see the data types of the table.
+        let dictionaries = arrow_schema
+            .fields()
+            .iter()
+            .map(|f| {
+                let dict_type = ArrowDataType::Dictionary(
+                    Box::new(ArrowDataType::Int32),
+                    Box::new(f.data_type.clone()),
+                );
+                let dictionary = write_options.use_dictionary(&DataType::from(&f.data_type))
+                    && can_cast_types(&f.data_type, &dict_type);
+                dictionary.then(|| dict_type)
+            })
+            .collect::<Vec<_>>();
+        let fields = arrow_schema
+            .fields()
+            .iter()
+            .zip(dictionaries.iter())
+            .map(|(f, dict_type)| match dict_type {
+                Some(dict_type) => ArrowField::new(&f.name, dict_type.clone(), f.nullable),
+                None => f.clone(),
+            })
+            .collect::<Vec<_>>();
+        let write_schema = ArrowSchema::new(fields);
+
+        // A struct is written as a group with a column per field. The row groups are encoded
+        // from the leaf columns, which have the same levels as in the group: a struct is a
+        // required group, so that only the NULLs of its fields are kept.
+        let parquet_schema = to_parquet_schema(&ArrowSchema::new(
+            write_schema.fields().iter().map(group_field).collect(),
+        ))?;
+        let mut leaves = vec![];
+        for f in write_schema.fields() {
+            leaf_fields(f, "", &mut leaves);
+        }
+        let leaf_schema = Arc::new(ArrowSchema::new(leaves));
+
+        let encodings: Vec<_> = leaf_schema
             .fields()
             .iter()
-            .map(|f| util::col_encoding(&f.data_type))
+            .map(|f| match f.data_type {
+                ArrowDataType::Dictionary(_, _) => Encoding::RleDictionary,
+                _ => util::col_encoding(&f.data_type),
+            })
             .collect();
 
-        let iter = vec![Ok(batch)];
+        // Each batch is written as a row group.
+        let rows = block.num_rows();
+        let row_group_rows = write_options.row_group_rows(&block);
+        let mut batches = vec![];
+        for offset in (0..rows.max(1)).step_by(row_group_rows) {
+            let length = row_group_rows.min(rows - offset);
+            let batch = RecordBatch::try_from(block.slice(offset, length))?;
+            let columns = batch
+                .columns()
+                .iter()
+                .zip(dictionaries.iter())
+                .map(|(column, dict_type)| match dict_type {
+                    Some(dict_type) => Ok(Arc::from(cast(column.as_ref(), dict_type)?)),
+                    None => Ok(column.clone()),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let mut leaves = vec![];
+            for column in columns.iter() {
+                leaf_arrays(column, &mut leaves)?;
+            }
+            batches.push(Ok(RecordBatch::try_new(leaf_schema.clone(), leaves)?));
+        }
+
         let row_groups =
-            RowGroupIterator::try_new(iter.into_iter(), arrow_schema, options, encodings)?;
-        let parquet_schema = row_groups.parquet_schema().clone();
+            RowGroupIterator::try_new(batches.into_iter(), &leaf_schema, options, encodings)?;
 
         // PutObject in S3 need to know the content-length in advance
         // multipart upload may intimidate this, but let's fit things together first
@@ -104,13 +189,23 @@ impl BlockAppender {
         // we need a configuration of block size threshold here
         let mut writer = Vec::with_capacity(10 * 1024 * 1024).writer();
 
+        let key_value_metadata =
+            arrow_schema
+                .metadata()
+                .get(SCHEMA_META_KEY_COLUMN_IDS)
+                .map(|ids| {
+                    vec![KeyValue {
+                        key: SCHEMA_META_KEY_COLUMN_IDS.to_string(),
+                        value: Some(ids.clone()),
+                    }]
+                });
         let len = common_arrow::parquet::write::write_file(
             &mut writer,
             row_groups,
             parquet_schema,
             options,
             None,
-            None,
+            key_value_metadata,
         )
         .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
 
@@ -124,3 +219,47 @@ impl BlockAppender {
         Ok(len)
     }
 }
+
+/// The struct fields are required groups, the NULLs of the structs are not kept.
+fn group_field(field: &ArrowField) -> ArrowField {
+    match &field.data_type {
+        ArrowDataType::Struct(fields) => {
+            let fields = fields.iter().map(group_field).collect();
+            ArrowField::new(&field.name, ArrowDataType::Struct(fields), false)
+        }
+        _ => field.clone(),
+    }
+}
+
+/// The leaf columns of the field, named by their paths, in the order of the parquet schema.
+fn leaf_fields(field: &ArrowField, prefix: &str, leaves: &mut Vec<ArrowField>) {
+    let name = format!("{}{}", prefix, field.name);
+    match &field.data_type {
+        ArrowDataType::Struct(fields) => {
+            let prefix = format!("{}.", name);
+            for f in fields {
+                leaf_fields(f, &prefix, leaves);
+            }
+        }
+        data_type => leaves.push(ArrowField::new(&name, data_type.clone(), field.nullable)),
+    }
+}
+
+/// The NULL structs are rejected, a required group can not keep them, they would be read as
+/// the structs of NULL fields.
+fn leaf_arrays(array: &ArrayRef, leaves: &mut Vec<ArrayRef>) -> Result<()> {
+    match array.as_any().downcast_ref::<StructArray>() {
+        Some(array) => {
+            if array.null_count() > 0 {
+                return Err(ErrorCode::UnImplement(
+                    "Cannot write NULL structs to FUSE tables, only the NULLs of their fields are kept",
+                ));
+            }
+            for values in array.values() {
+                leaf_arrays(values, leaves)?;
+            }
+        }
+        None => leaves.push(array.clone()),
+    }
+    Ok(())
+}
@@ -25,6 +25,7 @@ use common_datavalues::DataType;
 use tempfile::TempDir;
 
 use crate::datasources::table::fuse::BlockAppender;
+use crate::datasources::table::fuse::BlockWriteOptions;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_fuse_table_block_appender() {
@@ -32,9 +33,13 @@ async fn test_fuse_table_block_appender() {
     let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
     let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
     let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1, 2, 3])]);
-    let block_stream = futures::stream::iter(vec![block]);
-    let r =
-        BlockAppender::append_blocks(Arc::new(local_fs), Box::pin(block_stream), schema.as_ref())
-            .await;
+    let block_stream = futures::stream::iter(vec![Ok(block)]);
+    let r = BlockAppender::append_blocks(
+        Arc::new(local_fs),
+        Box::pin(block_stream),
+        schema.as_ref(),
+        &BlockWriteOptions::default(),
+    )
+    .await;
     assert!(r.is_ok())
 }
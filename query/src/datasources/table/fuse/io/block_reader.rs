@@ -13,97 +13,316 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::compute::concat::concatenate;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
 use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::io::parquet::read::decompress;
+use common_arrow::arrow::io::parquet::read::get_schema;
 use common_arrow::arrow::io::parquet::read::page_stream_to_array;
 use common_arrow::arrow::io::parquet::read::read_metadata_async;
+use common_arrow::parquet::metadata::FileMetaData;
 use common_arrow::parquet::read::get_page_stream;
 use common_dal::DataAccessor;
 use common_datablocks::DataBlock;
 use common_datavalues::columns::DataColumn;
 use common_datavalues::prelude::IntoSeries;
+use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Part;
+use common_planners::VIRTUAL_COLUMN_BLOCK_PATH;
 use futures::StreamExt;
 
-// TODO move these to a dedicated crate
-mod cache_keys {
-    use std::sync::Arc;
+use super::ColumnCache;
+use super::ColumnCacheKey;
+use super::Prewhere;
+use crate::datasources::common::append_virtual_columns;
+use crate::datasources::common::ColumnDefaults;
+use crate::datasources::common::ColumnIds;
+use crate::datasources::common::SCHEMA_META_KEY_COLUMN_IDS;
 
-    use common_cache::LruCache;
-    use common_infallible::Mutex;
-
-    #[derive(PartialEq, Eq, Hash)]
-    #[allow(dead_code)]
-    pub struct BlockMetaCacheKey {
-        block_id: String,
-    }
-
-    #[derive(PartialEq, Eq, Hash)]
-    #[allow(dead_code)]
-    pub struct BlockColKey {
-        block_id: String,
-        col_id: u32,
-    }
+// TODO can we return a stream of DataBlock instead?
+pub async fn do_read(
+    part: Part,
+    data_accessor: Arc<dyn DataAccessor>,
+    projection: Vec<usize>,
+    arrow_schema: ArrowSchema,
+    defaults: Arc<ColumnDefaults>,
+    column_cache: Arc<ColumnCache>,
+) -> Result<DataBlock> {
+    let metadata = read_metadata(&part, &data_accessor).await?;
+    let data_cols = read_columns(
+        &part,
+        &data_accessor,
+        &metadata,
+        &projection,
+        &arrow_schema,
+        &defaults,
+        &column_cache,
+    )
+    .await?;
 
-    #[allow(dead_code)]
-    pub type BlockColCache = Arc<Mutex<LruCache<BlockColKey, Vec<u8>>>>;
-    #[allow(dead_code)]
-    pub type BlockMetaCache = Arc<Mutex<LruCache<BlockMetaCacheKey, Vec<u8>>>>;
+    let block = DataBlock::create(Arc::new(DataSchema::from(arrow_schema)), data_cols);
+    Ok(block)
 }
 
-// TODO can we return a stream of DataBlock instead?
-pub async fn do_read(
+/// Reads the columns of the PREWHERE conditions first, the other columns are read only
+/// if some rows of the block are left. With `virtual_columns`, the virtual columns are appended
+/// before the rows are filtered, so that the rows keep their positions in the block.
+pub async fn do_read_with_prewhere(
     part: Part,
     data_accessor: Arc<dyn DataAccessor>,
     projection: Vec<usize>,
     arrow_schema: ArrowSchema,
+    defaults: Arc<ColumnDefaults>,
+    column_cache: Arc<ColumnCache>,
+    prewhere: Arc<Prewhere>,
+    virtual_columns: bool,
 ) -> Result<DataBlock> {
-    let loc = &part.name;
-    let col_num = projection.len();
+    let metadata = read_metadata(&part, &data_accessor).await?;
+    let prewhere_cols = read_columns(
+        &part,
+        &data_accessor,
+        &metadata,
+        &prewhere.columns,
+        &arrow_schema,
+        &defaults,
+        &column_cache,
+    )
+    .await?;
+
+    let schema = Arc::new(DataSchema::from(arrow_schema.clone()));
+    let prewhere_block = DataBlock::create(prewhere.schema(), prewhere_cols.clone());
+    let filter = prewhere.filter(&prewhere_block)?;
+    if DataBlock::filter_block(&prewhere_block, filter.clone())?.is_empty() {
+        let block = DataBlock::empty_with_schema(schema);
+        return match virtual_columns {
+            true => with_virtual_columns(block, &part),
+            false => Ok(block),
+        };
+    }
+
+    let remaining = projection
+        .iter()
+        .filter(|idx| !prewhere.columns.contains(idx))
+        .copied()
+        .collect::<Vec<_>>();
+    let remaining_cols = read_columns(
+        &part,
+        &data_accessor,
+        &metadata,
+        &remaining,
+        &arrow_schema,
+        &defaults,
+        &column_cache,
+    )
+    .await?;
+
+    let cols = prewhere
+        .columns
+        .iter()
+        .copied()
+        .zip(prewhere_cols)
+        .chain(remaining.into_iter().zip(remaining_cols))
+        .collect::<HashMap<_, _>>();
+    let data_cols = projection
+        .iter()
+        .map(|idx| cols[idx].clone())
+        .collect::<Vec<_>>();
+
+    let mut block = DataBlock::create(schema, data_cols);
+    if virtual_columns {
+        block = with_virtual_columns(block, &part)?;
+    }
+    DataBlock::filter_block(&block, filter)
+}
+
+/// Appends `_block_path` and `_row_id` to the block read from the part.
+pub fn with_virtual_columns(block: DataBlock, part: &Part) -> Result<DataBlock> {
+    append_virtual_columns(block, VIRTUAL_COLUMN_BLOCK_PATH, &part.name, 0)
+}
+
+async fn read_metadata(part: &Part, data_accessor: &Arc<dyn DataAccessor>) -> Result<FileMetaData> {
     // TODO pass in parquet file len
-    let mut reader = data_accessor.get_input_stream(loc, None)?;
+    let mut reader = data_accessor.get_input_stream(&part.name, None)?;
 
     // TODO cache parquet meta
-    let metadata = read_metadata_async(&mut reader)
+    read_metadata_async(&mut reader)
         .await
-        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))
+}
 
-    // we only put one page in the a parquet file (reference xxx)
-    let row_group = 0;
-    let cols = projection
-        .into_iter()
-        .map(|idx| (metadata.row_groups[row_group].column(idx).clone(), idx));
+async fn read_columns(
+    part: &Part,
+    data_accessor: &Arc<dyn DataAccessor>,
+    metadata: &FileMetaData,
+    projection: &[usize],
+    arrow_schema: &ArrowSchema,
+    defaults: &ColumnDefaults,
+    column_cache: &Arc<ColumnCache>,
+) -> Result<Vec<DataColumn>> {
+    let loc = &part.name;
+    let version = part.version;
+    let col_num = projection.len();
+    if col_num == 0 {
+        return Ok(vec![]);
+    }
+
+    // The blocks are split into row groups by `parquet_row_group_rows` and `parquet_page_bytes`,
+    // a column of the block is the concatenation of its column chunks.
+    let num_rows = metadata
+        .row_groups
+        .iter()
+        .map(|row_group| row_group.num_rows() as usize)
+        .sum();
 
+    // The columns of the table are looked up in the file by name and id, since the block may be
+    // written before the columns were altered. A column not in the file is read as its
+    // default, or NULL without a default.
+    // A struct is stored as a column per field, the columns of the file are the leaves.
+    let file_schema = get_schema(metadata).map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    let file_ids = match metadata.key_value_metadata() {
+        Some(key_values) => match key_values
+            .iter()
+            .find(|kv| kv.key == SCHEMA_META_KEY_COLUMN_IDS)
+            .and_then(|kv| kv.value.as_ref())
+        {
+            Some(value) => ColumnIds::from_json(value)?,
+            None => ColumnIds::default(),
+        },
+        None => ColumnIds::default(),
+    };
+    let ids = ColumnIds::from_meta(arrow_schema.metadata())?;
     let fields = arrow_schema.fields();
+    let cols = projection.iter().map(|idx| {
+        let field = &fields[*idx];
+        let file_idx = file_schema.fields().iter().position(|f| {
+            f.name == field.name
+                && same_type(&f.data_type, &field.data_type)
+                && file_ids.id(&f.name) == ids.id(&field.name)
+        });
+        let leaf_idx = file_idx.map(|file_idx| {
+            file_schema.fields()[..file_idx]
+                .iter()
+                .map(|f| leaf_count(&f.data_type))
+                .sum::<usize>()
+        });
+        (leaf_idx, *idx)
+    });
 
     use futures::TryStreamExt;
-    let stream = futures::stream::iter(cols).map(|(col_meta, idx)| {
-        let a = (metadata.row_groups[0].columns()[idx]).clone();
+    let stream = futures::stream::iter(cols).map(|(leaf_idx, idx)| {
         let data_accessor = data_accessor.clone();
+        let column_cache = column_cache.clone();
         async move {
-            let mut reader = data_accessor.get_input_stream(loc, None)?;
-            // TODO cache block column
-            let col_pages = get_page_stream(&col_meta, &mut reader, vec![], Arc::new(|_, _| true))
-                .await
-                .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
-            let pages = col_pages.map(|compressed_page| decompress(compressed_page?, &mut vec![]));
-            // QUOTE(from arrow2): deserialize the pages. This is CPU bounded and SHOULD be done in a dedicated thread pool (e.g. Rayon)
-            let array = page_stream_to_array(pages, &a, fields[idx].data_type.clone()).await?;
-            let array: Arc<dyn common_arrow::arrow::array::Array> = array.into();
-            Ok::<_, ErrorCode>(DataColumn::Array(array.into_series()))
+            let leaf_idx = match leaf_idx {
+                Some(leaf_idx) => leaf_idx,
+                None => {
+                    let value = defaults.missing_value(&DataField::from(&fields[idx]));
+                    return Ok(DataColumn::Constant(value, num_rows));
+                }
+            };
+            let cache_key = ColumnCacheKey::create(loc, leaf_idx, version);
+            if let Some(series) = column_cache.get(&cache_key) {
+                return Ok(DataColumn::Array(series));
+            }
+
+            let mut leaf_types = vec![];
+            leaf_data_types(&fields[idx].data_type, &mut leaf_types);
+
+            let mut leaves = Vec::with_capacity(leaf_types.len());
+            for (i, leaf_type) in leaf_types.into_iter().enumerate() {
+                let mut arrays = Vec::with_capacity(metadata.row_groups.len());
+                for row_group in metadata.row_groups.iter() {
+                    let col_meta = row_group.column(leaf_idx + i).clone();
+                    let a = col_meta.clone();
+
+                    let mut reader = data_accessor.get_input_stream(loc, None)?;
+                    let col_pages =
+                        get_page_stream(&col_meta, &mut reader, vec![], Arc::new(|_, _| true))
+                            .await
+                            .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+                    let pages =
+                        col_pages.map(|compressed_page| decompress(compressed_page?, &mut vec![]));
+                    // QUOTE(from arrow2): deserialize the pages. This is CPU bounded and SHOULD be done in a dedicated thread pool (e.g. Rayon)
+                    let array = page_stream_to_array(pages, &a, leaf_type.clone()).await?;
+                    arrays.push(array);
+                }
+                let array: Arc<dyn Array> = match arrays.len() {
+                    1 => arrays.remove(0).into(),
+                    _ => {
+                        let arrays = arrays.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+                        concatenate(&arrays)?.into()
+                    }
+                };
+                leaves.push(array);
+            }
+            let array = assemble_leaves(&fields[idx].data_type, &mut leaves.into_iter());
+            let series = array.into_series();
+            column_cache.put(cache_key, series.clone());
+            Ok::<_, ErrorCode>(DataColumn::Array(series))
         }
     });
 
     // TODO configuration of the buffer size
     let buffer_size = 10;
     let n = std::cmp::min(buffer_size, col_num);
-    let data_cols = stream.buffered(n).try_collect().await?;
+    stream.buffered(n).try_collect().await
+}
 
-    let block = DataBlock::create(Arc::new(DataSchema::from(arrow_schema)), data_cols);
-    Ok(block)
+/// The number of the parquet columns of the field, a struct has a column per field.
+fn leaf_count(data_type: &ArrowDataType) -> usize {
+    match data_type {
+        ArrowDataType::Struct(fields) => fields.iter().map(|f| leaf_count(&f.data_type)).sum(),
+        _ => 1,
+    }
+}
+
+fn leaf_data_types(data_type: &ArrowDataType, leaves: &mut Vec<ArrowDataType>) {
+    match data_type {
+        ArrowDataType::Struct(fields) => {
+            for f in fields {
+                leaf_data_types(&f.data_type, leaves);
+            }
+        }
+        data_type => leaves.push(data_type.clone()),
+    }
+}
+
+/// Builds the array of the field from its leaf arrays, in the order of `leaf_data_types`.
+fn assemble_leaves(
+    data_type: &ArrowDataType,
+    leaves: &mut impl Iterator<Item = Arc<dyn Array>>,
+) -> Arc<dyn Array> {
+    match data_type {
+        ArrowDataType::Struct(fields) => {
+            let values = fields
+                .iter()
+                .map(|f| assemble_leaves(&f.data_type, leaves))
+                .collect();
+            Arc::new(StructArray::from_data(data_type.clone(), values, None))
+        }
+        _ => leaves.next().unwrap(),
+    }
+}
+
+/// The type of the file matches the column, the nullability and the metadata of the
+/// struct fields are not kept in the parquet schema.
+fn same_type(file_type: &ArrowDataType, data_type: &ArrowDataType) -> bool {
+    match (file_type, data_type) {
+        (ArrowDataType::Struct(file_fields), ArrowDataType::Struct(fields)) => {
+            file_fields.len() == fields.len()
+                && file_fields
+                    .iter()
+                    .zip(fields.iter())
+                    .all(|(a, b)| a.name == b.name && same_type(&a.data_type, &b.data_type))
+        }
+        _ => file_type == data_type,
+    }
 }
@@ -15,6 +15,8 @@
 
 use std::sync::Arc;
 
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::bitmap::Bitmap;
 use common_base::tokio;
 use common_dal::DataAccessor;
 use common_datablocks::assert_blocks_sorted_eq;
@@ -34,16 +36,27 @@ use common_datablocks::DataBlock;
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 //
+use common_datavalues::prelude::DFStructArray;
+use common_datavalues::prelude::IntoSeries;
 use common_datavalues::prelude::SeriesFrom;
 use common_datavalues::series::Series;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::Extras;
 use common_planners::Part;
 use tempfile::TempDir;
 
 use super::super::util;
 use super::block_appender::BlockAppender;
+use super::BlockWriteOptions;
+use super::ColumnCache;
+use super::Prewhere;
+use crate::datasources::common::ColumnDefaults;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_block_reader_read() -> common_exception::Result<()> {
@@ -55,20 +68,310 @@ async fn test_block_reader_read() -> common_exception::Result<()> {
     let arrow_scheme = block.schema().to_arrow();
     let location = util::gen_unique_block_location();
 
-    let _r = BlockAppender::save_block(&arrow_scheme, block.clone(), &da, &location).await?;
+    let write_options = BlockWriteOptions::default();
+    let _r =
+        BlockAppender::save_block(&arrow_scheme, block.clone(), &da, &location, &write_options)
+            .await?;
 
     let part = Part {
         name: location.to_string(),
         version: 0,
     };
 
-    let proj = (0..arrow_scheme.fields().len()).collect();
-    let got = super::block_reader::do_read(part, da, proj, arrow_scheme).await;
+    let proj: Vec<usize> = (0..arrow_scheme.fields().len()).collect();
+    let column_cache = ColumnCache::create(1024 * 1024);
+    let got = super::block_reader::do_read(
+        part.clone(),
+        da.clone(),
+        proj.clone(),
+        arrow_scheme.clone(),
+        Arc::new(ColumnDefaults::default()),
+        column_cache.clone(),
+    )
+    .await;
     assert!(got.is_ok());
+    assert_eq!(column_cache.len(), 1);
+    assert_eq!(column_cache.misses(), 1);
 
     let input_block_as_string = pretty_format_blocks(&[block]).unwrap();
-    let lines_of_input_block = input_block_as_string.lines().collect();
+    let lines_of_input_block: Vec<&str> = input_block_as_string.lines().collect();
 
-    assert_blocks_sorted_eq(lines_of_input_block, &[got.unwrap()]);
+    assert_blocks_sorted_eq(lines_of_input_block.clone(), &[got.unwrap()]);
+
+    // The second read is served by the column cache.
+    let defaults = Arc::new(ColumnDefaults::default());
+    let got =
+        super::block_reader::do_read(part, da, proj, arrow_scheme, defaults, column_cache.clone())
+            .await?;
+    assert_eq!(column_cache.hits(), 1);
+    assert_blocks_sorted_eq(lines_of_input_block, &[got]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_reader_read_with_prewhere() -> common_exception::Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let da: Arc<dyn DataAccessor> = Arc::new(local_fs);
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1, 2, 3]),
+        Series::new(vec!["x", "y", "z"]),
+    ]);
+    let arrow_scheme = block.schema().to_arrow();
+    let location = util::gen_unique_block_location();
+
+    let write_options = BlockWriteOptions::default();
+    let _r =
+        BlockAppender::save_block(&arrow_scheme, block.clone(), &da, &location, &write_options)
+            .await?;
+
+    let part = Part {
+        name: location.to_string(),
+        version: 0,
+    };
+    let proj: Vec<usize> = (0..arrow_scheme.fields().len()).collect();
+    let column_cache = ColumnCache::create(1024 * 1024);
+
+    let mut push_downs = Extras::default();
+    push_downs.prewhere = vec![col("a").gt(lit(1i32))];
+    let prewhere = Prewhere::try_create(&schema, &proj, &push_downs, None, None)?.unwrap();
+    let got = super::block_reader::do_read_with_prewhere(
+        part.clone(),
+        da.clone(),
+        proj.clone(),
+        arrow_scheme.clone(),
+        Arc::new(ColumnDefaults::default()),
+        column_cache.clone(),
+        Arc::new(prewhere),
+        false,
+    )
+    .await?;
+    assert_blocks_sorted_eq(
+        vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 2 | y |",
+            "| 3 | z |",
+            "+---+---+",
+        ],
+        &[got],
+    );
+
+    // No rows left, the other columns are not read.
+    push_downs.prewhere = vec![col("a").gt(lit(5i32))];
+    let prewhere = Prewhere::try_create(&schema, &proj, &push_downs, None, None)?.unwrap();
+    let column_cache = ColumnCache::create(1024 * 1024);
+    let got = super::block_reader::do_read_with_prewhere(
+        part,
+        da,
+        proj,
+        arrow_scheme,
+        Arc::new(ColumnDefaults::default()),
+        column_cache.clone(),
+        Arc::new(prewhere),
+        false,
+    )
+    .await?;
+    assert!(got.is_empty());
+    assert_eq!(column_cache.len(), 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_reader_read_row_groups() -> common_exception::Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let da: Arc<dyn DataAccessor> = Arc::new(local_fs);
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1, 2, 3, 4, 5]),
+        Series::new(vec!["x", "y", "x", "y", "z"]),
+    ]);
+    let arrow_scheme = block.schema().to_arrow();
+    let location = util::gen_unique_block_location();
+
+    // 3 row groups, the strings are dictionary encoded.
+    let write_options = BlockWriteOptions {
+        row_group_rows: 2,
+        page_bytes: 0,
+        dictionary_types: vec!["string".to_string()],
+        skipping_indexes: vec![],
+    };
+    let _r =
+        BlockAppender::save_block(&arrow_scheme, block.clone(), &da, &location, &write_options)
+            .await?;
+
+    let part = Part {
+        name: location.to_string(),
+        version: 0,
+    };
+    let proj: Vec<usize> = (0..arrow_scheme.fields().len()).collect();
+    let column_cache = ColumnCache::create(1024 * 1024);
+    let defaults = Arc::new(ColumnDefaults::default());
+    let got =
+        super::block_reader::do_read(part, da, proj, arrow_scheme, defaults, column_cache).await?;
+    assert_eq!(got.num_rows(), 5);
+
+    let input_block_as_string = pretty_format_blocks(&[block]).unwrap();
+    let lines_of_input_block: Vec<&str> = input_block_as_string.lines().collect();
+    assert_blocks_sorted_eq(lines_of_input_block, &[got]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_reader_read_missing_columns() -> common_exception::Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let da: Arc<dyn DataAccessor> = Arc::new(local_fs);
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1, 2])]);
+    let location = util::gen_unique_block_location();
+
+    let write_options = BlockWriteOptions::default();
+    let _r = BlockAppender::save_block(
+        &block.schema().to_arrow(),
+        block.clone(),
+        &da,
+        &location,
+        &write_options,
+    )
+    .await?;
+
+    // `b` and `c` are added after the block is written, `c` with a default.
+    let altered = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::Int32, true),
+        DataField::new("c", DataType::Int32, true),
+    ]);
+    let mut defaults = ColumnDefaults::default();
+    defaults
+        .columns
+        .insert("c".to_string(), DataValue::Int32(Some(7)));
+
+    let part = Part {
+        name: location.to_string(),
+        version: 0,
+    };
+    let got = super::block_reader::do_read(
+        part,
+        da,
+        vec![0, 1, 2],
+        altered.to_arrow(),
+        Arc::new(defaults),
+        ColumnCache::create(1024 * 1024),
+    )
+    .await?;
+    assert_blocks_sorted_eq(
+        vec![
+            "+---+------+---+",
+            "| a | b    | c |",
+            "+---+------+---+",
+            "| 1 | NULL | 7 |",
+            "| 2 | NULL | 7 |",
+            "+---+------+---+",
+        ],
+        &[got],
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_reader_read_struct() -> common_exception::Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let da: Arc<dyn DataAccessor> = Arc::new(local_fs);
+
+    let struct_type = DataType::Struct(vec![
+        DataField::new("x", DataType::Int64, true),
+        DataField::new("y", DataType::String, true),
+    ]);
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("s", struct_type.clone(), false),
+        DataField::new("b", DataType::Int32, false),
+    ]);
+    let array = StructArray::from_data(
+        struct_type.to_arrow(),
+        vec![
+            Series::new(vec![1_i64, 2]).get_array_ref(),
+            Series::new(vec!["p", "q"]).get_array_ref(),
+        ],
+        None,
+    );
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1, 2]),
+        DFStructArray::from(array).into_series(),
+        Series::new(vec![3, 4]),
+    ]);
+    let arrow_scheme = block.schema().to_arrow();
+    let location = util::gen_unique_block_location();
+    let write_options = BlockWriteOptions::default();
+    BlockAppender::save_block(&arrow_scheme, block, &da, &location, &write_options).await?;
+
+    // The struct is stored as a column per field, the columns after it are found past them.
+    let part = Part {
+        name: location.to_string(),
+        version: 0,
+    };
+    let got = super::block_reader::do_read(
+        part,
+        da,
+        vec![1, 2],
+        arrow_scheme,
+        Arc::new(ColumnDefaults::default()),
+        ColumnCache::create(1024 * 1024),
+    )
+    .await?;
+
+    assert_eq!(got.column(0).data_type(), struct_type);
+    assert_eq!(got.column(0).to_values()?, vec![
+        DataValue::Struct(vec![
+            DataValue::Int64(Some(1)),
+            DataValue::String(Some(b"p".to_vec()))
+        ]),
+        DataValue::Struct(vec![
+            DataValue::Int64(Some(2)),
+            DataValue::String(Some(b"q".to_vec()))
+        ]),
+    ]);
+    assert_eq!(got.column(1).to_values()?, vec![
+        DataValue::Int32(Some(3)),
+        DataValue::Int32(Some(4)),
+    ]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_appender_reject_null_struct() -> common_exception::Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let da: Arc<dyn DataAccessor> = Arc::new(local_fs);
+
+    let struct_type = DataType::Struct(vec![DataField::new("x", DataType::Int64, true)]);
+    let schema = DataSchemaRefExt::create(vec![DataField::new("s", struct_type.clone(), true)]);
+    // The second struct is NULL.
+    let array = StructArray::from_data(
+        struct_type.to_arrow(),
+        vec![Series::new(vec![1_i64, 2]).get_array_ref()],
+        Some(Bitmap::from_u8_slice(&[0b01], 2)),
+    );
+    let block = DataBlock::create_by_array(schema, vec![DFStructArray::from(array).into_series()]);
+    let arrow_scheme = block.schema().to_arrow();
+    let location = util::gen_unique_block_location();
+    let write_options = BlockWriteOptions::default();
+    let result =
+        BlockAppender::save_block(&arrow_scheme, block, &da, &location, &write_options).await;
+    assert_eq!(
+        result.unwrap_err().code(),
+        ErrorCode::UnImplement("").code()
+    );
     Ok(())
 }
@@ -0,0 +1,107 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::table::fuse::util::SkippingIndex;
+use crate::datasources::table::fuse::util::SkippingIndexes;
+use crate::sessions::Settings;
+
+/// Table options of the parquet writer, they override the settings of the same names.
+pub const TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS: &str = "parquet_row_group_rows";
+pub const TBL_OPT_KEY_PARQUET_PAGE_BYTES: &str = "parquet_page_bytes";
+pub const TBL_OPT_KEY_PARQUET_DICTIONARY_TYPES: &str = "parquet_dictionary_types";
+
+/// How the blocks are written to parquet files.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BlockWriteOptions {
+    /// The max rows of a row group, 0 means a block is a single row group.
+    pub row_group_rows: usize,
+    /// The max bytes of a page, 0 means unlimited.
+    /// A column chunk is written as a single page, the row groups are split to keep
+    /// the pages of the widest column below it.
+    pub page_bytes: usize,
+    /// The data types, e.g. `String`, whose columns are dictionary encoded, in lower case.
+    pub dictionary_types: Vec<String>,
+    /// The skipping indexes evaluated on the blocks, their values are kept in the block metas.
+    pub skipping_indexes: Vec<SkippingIndex>,
+}
+
+impl BlockWriteOptions {
+    pub fn create(options: &HashMap<String, String>, settings: &Settings) -> Result<Self> {
+        let row_group_rows = match options.get(TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS) {
+            Some(v) => Self::parse_u64(TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS, v)?,
+            None => settings.get_parquet_row_group_rows()?,
+        };
+        let page_bytes = match options.get(TBL_OPT_KEY_PARQUET_PAGE_BYTES) {
+            Some(v) => Self::parse_u64(TBL_OPT_KEY_PARQUET_PAGE_BYTES, v)?,
+            None => settings.get_parquet_page_bytes()?,
+        };
+        let dictionary_types = match options.get(TBL_OPT_KEY_PARQUET_DICTIONARY_TYPES) {
+            Some(v) => v.clone(),
+            None => settings.get_parquet_dictionary_types()?,
+        };
+
+        Ok(BlockWriteOptions {
+            row_group_rows: row_group_rows as usize,
+            page_bytes: page_bytes as usize,
+            dictionary_types: dictionary_types
+                .split(',')
+                .map(|typ| typ.trim().to_lowercase())
+                .filter(|typ| !typ.is_empty())
+                .collect(),
+            skipping_indexes: SkippingIndexes::from_options(options)?.indexes,
+        })
+    }
+
+    fn parse_u64(key: &str, value: &str) -> Result<u64> {
+        value.trim().parse::<u64>().map_err(|_| {
+            ErrorCode::BadOption(format!(
+                "Invalid value of table option {}: {}, expect UInt64",
+                key, value
+            ))
+        })
+    }
+
+    pub fn use_dictionary(&self, data_type: &DataType) -> bool {
+        let name = data_type.to_string().to_lowercase();
+        self.dictionary_types.iter().any(|typ| *typ == name)
+    }
+
+    /// The rows of each row group the block is written in.
+    pub fn row_group_rows(&self, block: &DataBlock) -> usize {
+        let rows = block.num_rows();
+        let mut row_group_rows = match self.row_group_rows {
+            0 => rows,
+            n => n.min(rows),
+        };
+        if self.page_bytes > 0 && rows > 0 {
+            let widest = block
+                .columns()
+                .iter()
+                .map(|column| column.get_array_memory_size())
+                .max()
+                .unwrap_or(0);
+            let page_rows =
+                (self.page_bytes as u128 * rows as u128 / widest.max(1) as u128) as usize;
+            row_group_rows = row_group_rows.min(page_rows);
+        }
+        row_group_rows.max(1)
+    }
+}
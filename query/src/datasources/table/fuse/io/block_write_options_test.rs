@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::SeriesFrom;
+use common_datavalues::series::Series;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+
+use super::BlockWriteOptions;
+use super::TBL_OPT_KEY_PARQUET_DICTIONARY_TYPES;
+use super::TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS;
+use crate::sessions::Settings;
+
+#[test]
+fn test_block_write_options_create() -> Result<()> {
+    let settings = Settings::try_create()?;
+    let options = BlockWriteOptions::create(&HashMap::new(), &settings)?;
+    assert_eq!(options, BlockWriteOptions::default());
+
+    // The table options override the settings.
+    settings.set_parquet_row_group_rows(1000)?;
+    settings.set_parquet_page_bytes(1024)?;
+    settings.set_parquet_dictionary_types("Int32".to_string())?;
+    let mut table_options = HashMap::new();
+    table_options.insert(
+        TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS.to_string(),
+        "100".to_string(),
+    );
+    table_options.insert(
+        TBL_OPT_KEY_PARQUET_DICTIONARY_TYPES.to_string(),
+        "String, Date16".to_string(),
+    );
+    let options = BlockWriteOptions::create(&table_options, &settings)?;
+    assert_eq!(options, BlockWriteOptions {
+        row_group_rows: 100,
+        page_bytes: 1024,
+        dictionary_types: vec!["string".to_string(), "date16".to_string()],
+        skipping_indexes: vec![],
+    });
+    assert!(options.use_dictionary(&DataType::String));
+    assert!(!options.use_dictionary(&DataType::Int32));
+
+    table_options.insert(
+        TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS.to_string(),
+        "-1".to_string(),
+    );
+    assert!(BlockWriteOptions::create(&table_options, &settings).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_block_write_options_row_group_rows() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::Int64, false),
+    ]);
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new((0..100).collect::<Vec<i32>>()),
+        Series::new((0..100).collect::<Vec<i64>>()),
+    ]);
+
+    let mut options = BlockWriteOptions::default();
+    assert_eq!(options.row_group_rows(&block), 100);
+
+    options.row_group_rows = 30;
+    assert_eq!(options.row_group_rows(&block), 30);
+
+    // The pages of the widest column are kept below page_bytes.
+    let widest = block.column(1).get_array_memory_size();
+    options.page_bytes = widest;
+    assert_eq!(options.row_group_rows(&block), 30);
+    options.page_bytes = widest / 5;
+    let rows = options.row_group_rows(&block);
+    assert!((19..=20).contains(&rows));
+
+    options.page_bytes = 1;
+    assert_eq!(options.row_group_rows(&block), 1);
+    Ok(())
+}
@@ -0,0 +1,216 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_cache::DefaultHashBuilder;
+use common_cache::LruCache;
+use common_cache::Meter;
+use common_datavalues::prelude::*;
+use common_infallible::Mutex;
+use common_infallible::RwLock;
+use metrics::counter;
+use metrics::gauge;
+
+pub static METRIC_COLUMN_CACHE_BYTES: &str = "fuse.column_cache_bytes";
+pub static METRIC_COLUMN_CACHE_HITS: &str = "fuse.column_cache_hits";
+pub static METRIC_COLUMN_CACHE_MISSES: &str = "fuse.column_cache_misses";
+
+/// Identifies a decoded column, `version` is the version of the part it was read from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColumnCacheKey {
+    pub location: String,
+    pub column: usize,
+    pub version: u64,
+}
+
+impl ColumnCacheKey {
+    pub fn create(location: &str, column: usize, version: u64) -> ColumnCacheKey {
+        ColumnCacheKey {
+            location: location.to_string(),
+            column,
+            version,
+        }
+    }
+}
+
+/// The share of the cache capacity the string dictionary may take.
+const DICTIONARY_SHARE: u64 = 8;
+
+/// A cached column, the string columns of few distinct values are kept as
+/// codes of the dictionary of the cache.
+#[derive(Clone)]
+pub enum CachedColumn {
+    Plain(Series),
+    Dictionary(DictionaryStringArray),
+}
+
+impl CachedColumn {
+    pub fn memory_size(&self) -> usize {
+        match self {
+            CachedColumn::Plain(column) => column.get_array_memory_size(),
+            CachedColumn::Dictionary(column) => column.memory_size(),
+        }
+    }
+
+    pub fn decode(&self) -> Series {
+        match self {
+            CachedColumn::Plain(column) => column.clone(),
+            CachedColumn::Dictionary(column) => column.decode().into_series(),
+        }
+    }
+}
+
+/// Measures the cached columns by the memory of their arrays.
+pub struct ColumnMeter;
+
+impl Meter<ColumnCacheKey, CachedColumn> for ColumnMeter {
+    type Measure = usize;
+
+    fn measure<Q: ?Sized>(&self, _: &Q, value: &CachedColumn) -> usize
+    where ColumnCacheKey: Borrow<Q> {
+        value.memory_size()
+    }
+}
+
+type ColumnLruCache = LruCache<ColumnCacheKey, CachedColumn, DefaultHashBuilder, ColumnMeter>;
+
+/// Decoded columns shared by all the sessions of this node, so the hot blocks
+/// are not decompressed and deserialized again by every query.
+///
+/// The repeated strings of the columns are interned in a dictionary shared by
+/// all of them, which is emptied only when the cache is cleared.
+pub struct ColumnCache {
+    capacity: AtomicU64,
+    columns: Mutex<ColumnLruCache>,
+    dictionary: RwLock<StringDictionaryRef>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ColumnCache {
+    /// Creates a cache holding at most `capacity` bytes, 0 disables the cache.
+    pub fn create(capacity: u64) -> Arc<ColumnCache> {
+        Arc::new(ColumnCache {
+            capacity: AtomicU64::new(capacity),
+            columns: Mutex::new(LruCache::with_meter(capacity, ColumnMeter)),
+            dictionary: RwLock::new(StringDictionary::create()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
+    }
+
+    pub fn get(&self, key: &ColumnCacheKey) -> Option<Series> {
+        if self.capacity() == 0 {
+            return None;
+        }
+
+        let column = self.columns.lock().get(key).cloned();
+        match column {
+            Some(_) => {
+                counter!(METRIC_COLUMN_CACHE_HITS, 1);
+                self.hits.fetch_add(1, Ordering::Relaxed)
+            }
+            None => {
+                counter!(METRIC_COLUMN_CACHE_MISSES, 1);
+                self.misses.fetch_add(1, Ordering::Relaxed)
+            }
+        };
+        column.map(|column| column.decode())
+    }
+
+    pub fn put(&self, key: ColumnCacheKey, column: Series) {
+        // A column larger than the whole cache would evict everything and itself.
+        if column.get_array_memory_size() as u64 > self.capacity() {
+            return;
+        }
+
+        let column = self.encode(column);
+        let size = {
+            let mut columns = self.columns.lock();
+            columns.put(key, column);
+            columns.size()
+        };
+        gauge!(
+            METRIC_COLUMN_CACHE_BYTES,
+            (size + self.dictionary_size()) as f64
+        );
+    }
+
+    pub fn clear(&self) {
+        let mut columns = self.columns.lock();
+        columns.clear();
+        *self.dictionary.write() = StringDictionary::create();
+        gauge!(METRIC_COLUMN_CACHE_BYTES, 0.0);
+    }
+
+    fn encode(&self, column: Series) -> CachedColumn {
+        if column.data_type() != &DataType::String {
+            return CachedColumn::Plain(column);
+        }
+
+        let dictionary = self.dictionary.read().clone();
+        let max_bytes = (self.capacity() / DICTIONARY_SHARE) as usize;
+        let encoded = column
+            .string()
+            .ok()
+            .and_then(|array| DictionaryStringArray::try_encode(array, &dictionary, max_bytes));
+        match encoded {
+            Some((array, _)) => CachedColumn::Dictionary(array),
+            None => CachedColumn::Plain(column),
+        }
+    }
+
+    fn dictionary_size(&self) -> u64 {
+        let dictionary = self.dictionary.read().clone();
+        let size = dictionary.read().memory_size();
+        size as u64
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Resizes the cache, the least recently used columns over the new capacity are evicted.
+    pub fn set_capacity(&self, capacity: u64) {
+        let mut columns = self.columns.lock();
+        columns.set_capacity(capacity);
+        self.capacity.store(capacity, Ordering::Relaxed);
+    }
+
+    /// The bytes of the cached columns and their dictionary.
+    pub fn size(&self) -> u64 {
+        self.columns.lock().size() + self.dictionary_size()
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
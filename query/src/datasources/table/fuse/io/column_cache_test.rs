@@ -0,0 +1,94 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+
+use super::ColumnCache;
+use super::ColumnCacheKey;
+
+#[test]
+fn test_column_cache() -> common_exception::Result<()> {
+    let column = Series::new(vec![1i64, 2, 3, 4]);
+    let column_size = column.get_array_memory_size() as u64;
+
+    // Room for two columns.
+    let cache = ColumnCache::create(column_size * 2);
+    let key_a = ColumnCacheKey::create("a", 0, 0);
+    let key_b = ColumnCacheKey::create("b", 0, 0);
+    let key_c = ColumnCacheKey::create("c", 0, 0);
+
+    assert!(cache.get(&key_a).is_none());
+    cache.put(key_a.clone(), column.clone());
+    cache.put(key_b.clone(), column.clone());
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.size(), column_size * 2);
+
+    // The least recently used one is evicted.
+    assert!(cache.get(&key_a).is_some());
+    cache.put(key_c.clone(), column.clone());
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&key_b).is_none());
+    assert!(cache.get(&key_c).is_some());
+
+    // Another version of the block is another entry.
+    assert!(cache.get(&ColumnCacheKey::create("a", 0, 1)).is_none());
+    assert_eq!(cache.hits(), 2);
+    assert_eq!(cache.misses(), 3);
+
+    // Larger than the whole cache.
+    cache.put(
+        ColumnCacheKey::create("d", 0, 0),
+        Series::new(vec![1i64; 1024]),
+    );
+    assert_eq!(cache.len(), 2);
+
+    // Shrunk to one column, the least recently used one is evicted.
+    cache.set_capacity(column_size);
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get(&key_a).is_none());
+    assert!(cache.get(&key_c).is_some());
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.size(), 0);
+
+    // Disabled.
+    let cache = ColumnCache::create(0);
+    cache.put(key_a.clone(), column);
+    assert!(cache.get(&key_a).is_none());
+    assert!(cache.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_column_cache_string_dictionary() -> common_exception::Result<()> {
+    let values: Vec<&str> = (0..1000).map(|i| ["beijing", "shanghai"][i % 2]).collect();
+    let column = Series::new(values);
+    let column_size = column.get_array_memory_size() as u64;
+
+    let cache = ColumnCache::create(column_size * 16);
+    let key_a = ColumnCacheKey::create("a", 0, 0);
+    let key_b = ColumnCacheKey::create("b", 0, 0);
+    cache.put(key_a.clone(), column.clone());
+    cache.put(key_b.clone(), column.clone());
+
+    // The columns share the values in the dictionary.
+    assert!(cache.size() < column_size);
+    let cached = cache.get(&key_a).unwrap();
+    assert_eq!(cached.to_values()?, column.to_values()?);
+
+    cache.clear();
+    assert_eq!(cache.size(), 0);
+    Ok(())
+}
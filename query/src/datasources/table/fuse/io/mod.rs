@@ -15,6 +15,11 @@
 
 pub(crate) use block_appender::*;
 pub use block_reader::*;
+pub use block_write_options::*;
+pub use column_cache::*;
+pub use prewhere::has_subquery;
+pub use prewhere::may_match;
+pub use prewhere::Prewhere;
 pub use segment_reader::*;
 
 // consider remove these, read_util seems to be enough (type could be inferred)
@@ -23,9 +28,18 @@ mod segment_reader;
 
 mod block_appender;
 mod block_reader;
+mod block_write_options;
+mod column_cache;
 pub(crate) mod meta_info_reader;
+mod prewhere;
 
 #[cfg(test)]
 mod block_appender_test;
 #[cfg(test)]
 mod block_reader_test;
+#[cfg(test)]
+mod block_write_options_test;
+#[cfg(test)]
+mod column_cache_test;
+#[cfg(test)]
+mod prewhere_test;
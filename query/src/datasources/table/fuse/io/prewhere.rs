@@ -0,0 +1,487 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use common_datablocks::DataBlock;
+use common_datavalues::is_date_or_date_time;
+use common_datavalues::is_numeric;
+use common_datavalues::series::Series;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::ExpressionVisitor;
+use common_planners::Extras;
+use common_planners::Recursion;
+
+use crate::datasources::common::Histogram;
+use crate::datasources::common::TableStatistics;
+use crate::datasources::index::InvertedIndex;
+use crate::datasources::table::fuse::ColStats;
+use crate::datasources::table::fuse::ColumnId;
+use crate::pipelines::transforms::ExpressionExecutor;
+
+/// The conditions of WHERE are read first only if they are estimated to keep
+/// at most this fraction of the rows.
+const MAX_PREWHERE_SELECTIVITY: f64 = 0.5;
+
+/// The conditions of a fuse scan evaluated on a few columns of each block,
+/// before the other columns of the block are read.
+pub struct Prewhere {
+    /// The columns read first, as indices of the table schema.
+    pub columns: Vec<usize>,
+    schema: DataSchemaRef,
+    executor: ExpressionExecutor,
+}
+
+impl Prewhere {
+    /// Uses the PREWHERE conditions of the push downs if any, otherwise picks the conditions
+    /// of WHERE with the column statistics of the snapshot, if they are given.
+    /// The histograms collected by `ANALYZE TABLE` refine the estimation of the range conditions.
+    pub fn try_create(
+        table_schema: &DataSchemaRef,
+        projection: &[usize],
+        push_downs: &Extras,
+        col_stats: Option<&HashMap<ColumnId, ColStats>>,
+        table_stats: Option<&TableStatistics>,
+    ) -> Result<Option<Prewhere>> {
+        let conditions = match (push_downs.prewhere.is_empty(), col_stats) {
+            (false, _) => Self::explicit_conditions(table_schema, &push_downs.prewhere)?,
+            (true, Some(col_stats)) => {
+                let stats = Statistics {
+                    col_stats,
+                    table_stats,
+                };
+                Self::choose_conditions(table_schema, projection, &push_downs.filters, &stats)
+            }
+            (true, None) => vec![],
+        };
+
+        let mut columns = conditions
+            .iter()
+            .flat_map(|(_, columns)| columns.iter().copied())
+            .collect::<Vec<_>>();
+        columns.sort_unstable();
+        columns.dedup();
+
+        let predicate = match conditions
+            .into_iter()
+            .map(|(expr, _)| expr)
+            .reduce(|l, r| l.and(r))
+        {
+            None => return Ok(None),
+            Some(predicate) => predicate,
+        };
+
+        let schema = DataSchemaRefExt::create(
+            columns
+                .iter()
+                .map(|idx| table_schema.field(*idx).clone())
+                .collect(),
+        );
+        let predicate_field = predicate.to_data_field(&schema)?;
+        let executor = ExpressionExecutor::try_create(
+            "prewhere expression executor",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![predicate_field]),
+            vec![predicate],
+            false,
+        )?;
+        executor.validate()?;
+
+        Ok(Some(Prewhere {
+            columns,
+            schema,
+            executor,
+        }))
+    }
+
+    /// The schema of the columns read first.
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    /// Evaluates the conditions on the block of the columns read first.
+    pub fn filter(&self, block: &DataBlock) -> Result<Series> {
+        let filter_block = self.executor.execute(block)?;
+        filter_block.column(0).to_array()
+    }
+
+    fn explicit_conditions(
+        table_schema: &DataSchemaRef,
+        conditions: &[Expression],
+    ) -> Result<Vec<(Expression, Vec<usize>)>> {
+        let mut explicit_conditions = Vec::with_capacity(conditions.len());
+        for condition in conditions {
+            let visitor = condition.accept(ConditionColumnsVisitor::default())?;
+            if visitor.has_subquery {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Subqueries are not allowed in PREWHERE: {:?}",
+                    condition
+                )));
+            }
+
+            // A constant condition is left to the filter after the scan.
+            if let Some(columns) = visitor.column_indices(table_schema)? {
+                explicit_conditions.push((condition.clone(), columns));
+            }
+        }
+        Ok(explicit_conditions)
+    }
+
+    /// Picks the most selective condition of WHERE on the cheap columns, along with the other
+    /// conditions on the same columns.
+    fn choose_conditions(
+        table_schema: &DataSchemaRef,
+        projection: &[usize],
+        conditions: &[Expression],
+        stats: &Statistics,
+    ) -> Vec<(Expression, Vec<usize>)> {
+        let mut candidates = conditions
+            .iter()
+            .filter_map(|condition| {
+                let visitor = condition.accept(ConditionColumnsVisitor::default()).ok()?;
+                if visitor.has_subquery {
+                    return None;
+                }
+
+                // Conditions on the columns derived by the query are skipped too.
+                let columns = visitor.column_indices(table_schema).ok()??;
+                let cheap = columns
+                    .iter()
+                    .all(|idx| is_cheap_type(table_schema.field(*idx).data_type()));
+                match cheap {
+                    false => None,
+                    true => {
+                        let selectivity = estimate_selectivity(table_schema, condition, stats);
+                        Some((condition.clone(), columns, selectivity))
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by(|l, r| l.2.partial_cmp(&r.2).unwrap_or(Ordering::Equal));
+
+        let best_columns = match candidates.first() {
+            Some((_, columns, selectivity)) if *selectivity <= MAX_PREWHERE_SELECTIVITY => {
+                columns.clone()
+            }
+            _ => return vec![],
+        };
+
+        // Nothing is saved if all the columns are read first anyway.
+        if projection.iter().all(|idx| best_columns.contains(idx)) {
+            return vec![];
+        }
+
+        candidates
+            .into_iter()
+            .filter(|(_, columns, _)| columns.iter().all(|idx| best_columns.contains(idx)))
+            .map(|(condition, columns, _)| (condition, columns))
+            .collect()
+    }
+}
+
+/// Whether the condition has subqueries, which can not be evaluated on the blocks.
+pub fn has_subquery(condition: &Expression) -> Result<bool> {
+    let visitor = condition.accept(ConditionColumnsVisitor::default())?;
+    Ok(visitor.has_subquery)
+}
+
+/// Whether some rows of a block may satisfy the condition, judged by the min and max values
+/// of the columns and the skipping index expressions of the block, and by the tokens of
+/// the inverted indexes for `MATCH`. The conditions other than the comparisons of a column or
+/// an indexed expression with a literal, and the `MATCH` of an indexed column may always match.
+pub fn may_match(
+    table_schema: &DataSchemaRef,
+    condition: &Expression,
+    col_stats: &HashMap<ColumnId, ColStats>,
+    index_stats: &HashMap<String, ColStats>,
+    inverted_indexes: &HashMap<String, InvertedIndex>,
+) -> bool {
+    let (op, left, right) = match condition {
+        Expression::BinaryExpression { op, left, right } => (op.to_lowercase(), left, right),
+        Expression::ScalarFunction { op, args } if op.to_lowercase() == "match" => {
+            return match args.as_slice() {
+                [Expression::Column(name), Expression::Literal {
+                    value: DataValue::String(Some(query)),
+                    ..
+                }] => inverted_indexes
+                    .get(name)
+                    .map_or(true, |index| index.may_match(query)),
+                _ => true,
+            };
+        }
+        _ => return true,
+    };
+
+    let matches =
+        |expr: &Expression| may_match(table_schema, expr, col_stats, index_stats, inverted_indexes);
+    let (expr, op, value) = match (op.as_str(), left.as_ref(), right.as_ref()) {
+        ("and", _, _) => return matches(left) && matches(right),
+        ("or", _, _) => return matches(left) || matches(right),
+        (_, Expression::Literal { .. }, Expression::Literal { .. }) => return true,
+        (_, expr, Expression::Literal { value, .. }) => (expr, op.as_str(), value),
+        (_, Expression::Literal { value, .. }, expr) => (expr, flip_comparison(&op), value),
+        _ => return true,
+    };
+
+    let stats = match expr {
+        Expression::Column(name) => table_schema
+            .index_of(name)
+            .ok()
+            .and_then(|idx| col_stats.get(&(idx as ColumnId))),
+        _ => index_stats.get(&expr.column_name()),
+    };
+    let stats = match stats {
+        None => return true,
+        Some(stats) => stats,
+    };
+    let (min, max) = match (
+        compare_values(&stats.min, value),
+        compare_values(&stats.max, value),
+    ) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return true,
+    };
+
+    match op {
+        "=" => min != Ordering::Greater && max != Ordering::Less,
+        "<" => min == Ordering::Less,
+        "<=" => min != Ordering::Greater,
+        ">" => max == Ordering::Greater,
+        ">=" => max != Ordering::Less,
+        _ => true,
+    }
+}
+
+/// The statistics the selectivity of the conditions is estimated with.
+struct Statistics<'a> {
+    col_stats: &'a HashMap<ColumnId, ColStats>,
+    table_stats: Option<&'a TableStatistics>,
+}
+
+#[derive(Default)]
+struct ConditionColumnsVisitor {
+    columns: HashSet<String>,
+    has_subquery: bool,
+}
+
+impl ConditionColumnsVisitor {
+    /// The indices of the columns in the table schema, None if there are no columns.
+    fn column_indices(&self, table_schema: &DataSchemaRef) -> Result<Option<Vec<usize>>> {
+        if self.columns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut indices = self
+            .columns
+            .iter()
+            .map(|name| table_schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        indices.sort_unstable();
+        Ok(Some(indices))
+    }
+}
+
+impl ExpressionVisitor for ConditionColumnsVisitor {
+    fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
+        match expr {
+            Expression::Column(name) => {
+                self.columns.insert(name.clone());
+            }
+            Expression::Subquery { .. } | Expression::ScalarSubquery { .. } => {
+                self.has_subquery = true;
+            }
+            _ => {}
+        }
+        Ok(Recursion::Continue(self))
+    }
+}
+
+/// The fixed size columns, which are cheap to read and compare.
+fn is_cheap_type(data_type: &DataType) -> bool {
+    is_numeric(data_type) || is_date_or_date_time(data_type) || *data_type == DataType::Boolean
+}
+
+/// The estimated fraction of the rows the condition keeps. The histogram of a column is used
+/// if there is one, otherwise the values are assumed to be evenly distributed between
+/// the min and max values of the column.
+fn estimate_selectivity(
+    table_schema: &DataSchemaRef,
+    condition: &Expression,
+    stats: &Statistics,
+) -> f64 {
+    let (op, left, right) = match condition {
+        Expression::BinaryExpression { op, left, right } => (op.to_lowercase(), left, right),
+        _ => return 1.0,
+    };
+
+    let comparison = match (left.as_ref(), right.as_ref()) {
+        (Expression::Column(name), Expression::Literal { value, .. }) => {
+            Some((name, op.as_str(), value))
+        }
+        (Expression::Literal { value, .. }, Expression::Column(name)) => {
+            Some((name, flip_comparison(&op), value))
+        }
+        _ => None,
+    };
+
+    match (op.as_str(), comparison) {
+        ("and", _) => {
+            estimate_selectivity(table_schema, left, stats)
+                * estimate_selectivity(table_schema, right, stats)
+        }
+        ("or", _) => (estimate_selectivity(table_schema, left, stats)
+            + estimate_selectivity(table_schema, right, stats))
+        .min(1.0),
+        (_, Some((name, op, value))) => {
+            let histogram_selectivity = stats.table_stats.and_then(|table_stats| {
+                let column_stats = table_stats.columns.get(name)?;
+                let selectivity =
+                    histogram_selectivity(column_stats.histogram.as_ref()?, op, value)?;
+                // The NULLs are not in the histogram, and are never kept by a comparison.
+                let rows = table_stats.rows.max(1) as f64;
+                Some(selectivity * (rows - column_stats.null_count as f64) / rows)
+            });
+            histogram_selectivity
+                .or_else(|| {
+                    table_schema
+                        .index_of(name)
+                        .ok()
+                        .and_then(|idx| stats.col_stats.get(&(idx as ColumnId)))
+                        .and_then(|stats| comparison_selectivity(stats, op, value))
+                })
+                .unwrap_or(1.0)
+        }
+        _ => 1.0,
+    }
+}
+
+/// The fraction of the values of the histogram the comparison keeps, the values of a bucket
+/// are assumed to be evenly distributed between its bounds.
+fn histogram_selectivity(histogram: &Histogram, op: &str, value: &DataValue) -> Option<f64> {
+    let value = value_as_f64(value)?;
+    let total = histogram.count();
+    if total == 0 {
+        return Some(0.0);
+    }
+
+    // The number of values less than, and equal to the value.
+    let mut less = 0.0;
+    let mut equal = 0.0;
+    for bucket in histogram.buckets.iter() {
+        let lower = value_as_f64(&bucket.lower)?;
+        let upper = value_as_f64(&bucket.upper)?;
+        let count = bucket.count as f64;
+        if value > upper {
+            less += count;
+            continue;
+        }
+        if value >= lower {
+            equal = count / bucket.distinct.max(1) as f64;
+            if upper > lower {
+                less += (count - equal) * (value - lower) / (upper - lower);
+            }
+        }
+        break;
+    }
+
+    let less = less / total as f64;
+    let equal = equal / total as f64;
+    let selectivity = match op {
+        "=" => equal,
+        "<>" | "!=" => 1.0 - equal,
+        "<" => less,
+        "<=" => less + equal,
+        ">" => 1.0 - less - equal,
+        ">=" => 1.0 - less,
+        _ => return None,
+    };
+    Some(selectivity.max(0.0).min(1.0))
+}
+
+fn comparison_selectivity(stats: &ColStats, op: &str, value: &DataValue) -> Option<f64> {
+    let min = value_as_f64(&stats.min)?;
+    let max = value_as_f64(&stats.max)?;
+    let value = value_as_f64(value)?;
+
+    let range = max - min;
+    if range <= 0.0 {
+        // A single value in the column, the condition keeps all the rows or none of them.
+        let keep = match op {
+            "=" => min == value,
+            "<>" | "!=" => min != value,
+            "<" => min < value,
+            "<=" => min <= value,
+            ">" => min > value,
+            ">=" => min >= value,
+            _ => return None,
+        };
+        return Some(if keep { 1.0 } else { 0.0 });
+    }
+
+    let selectivity = match op {
+        "=" if value < min || value > max => 0.0,
+        "=" => 1.0 / (range + 1.0),
+        "<>" | "!=" => 1.0,
+        "<" | "<=" => (value - min) / range,
+        ">" | ">=" => (max - value) / range,
+        _ => return None,
+    };
+    Some(selectivity.max(0.0).min(1.0))
+}
+
+fn flip_comparison(op: &str) -> &str {
+    match op {
+        "<" => ">",
+        "<=" => ">=",
+        ">" => "<",
+        ">=" => "<=",
+        other => other,
+    }
+}
+
+/// Compares the integers exactly, the other numbers as floats and the strings by bytes.
+fn compare_values(l: &DataValue, r: &DataValue) -> Option<Ordering> {
+    if let (DataValue::String(Some(l)), DataValue::String(Some(r))) = (l, r) {
+        return Some(l.cmp(r));
+    }
+    match (value_as_i128(l), value_as_i128(r)) {
+        (Some(l), Some(r)) => Some(l.cmp(&r)),
+        _ => value_as_f64(l)?.partial_cmp(&value_as_f64(r)?),
+    }
+}
+
+fn value_as_i128(value: &DataValue) -> Option<i128> {
+    match value {
+        DataValue::Float32(_) | DataValue::Float64(_) => None,
+        DataValue::UInt64(Some(v)) => Some(*v as i128),
+        other => other.as_i64().ok().map(|v| v as i128),
+    }
+}
+
+fn value_as_f64(value: &DataValue) -> Option<f64> {
+    match value {
+        DataValue::Float32(Some(v)) => Some(*v as f64),
+        DataValue::Float64(Some(v)) => Some(*v),
+        DataValue::UInt64(Some(v)) => Some(*v as f64),
+        other => other.as_i64().ok().map(|v| v as f64),
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::SeriesFrom;
+use common_datavalues::series::Series;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::Expression;
+use common_planners::Extras;
+
+use super::may_match;
+use super::Prewhere;
+use crate::datasources::common::ColumnStatistics;
+use crate::datasources::common::Histogram;
+use crate::datasources::common::HistogramBucket;
+use crate::datasources::common::TableStatistics;
+use crate::datasources::index::InvertedIndex;
+use crate::datasources::table::fuse::ColStats;
+
+#[test]
+fn test_prewhere_choose_conditions() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, false),
+        DataField::new("c", DataType::UInt64, false),
+    ]);
+    let projection = vec![0, 1, 2];
+    let stats = |min: DataValue, max: DataValue| ColStats {
+        min,
+        max,
+        null_count: 0,
+    };
+    let col_stats = vec![
+        (
+            0,
+            stats(DataValue::Int32(Some(0)), DataValue::Int32(Some(100))),
+        ),
+        (
+            1,
+            stats(
+                DataValue::String(Some(vec![])),
+                DataValue::String(Some(vec![])),
+            ),
+        ),
+        (
+            2,
+            stats(DataValue::UInt64(Some(0)), DataValue::UInt64(Some(10))),
+        ),
+    ]
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+
+    let mut push_downs = Extras::default();
+
+    // The most selective condition on a cheap column, with the other one on the same column.
+    push_downs.filters = vec![
+        col("c").lt(lit(5u64)),
+        col("a").gt(lit(90i32)),
+        col("b").eq(lit("x".as_bytes())),
+        lit(10i32).gt(col("a")).or(col("a").eq(lit(50i32))),
+    ];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, Some(&col_stats), None)?;
+    assert_eq!(prewhere.map(|p| p.columns), Some(vec![0]));
+
+    // Not selective enough.
+    push_downs.filters = vec![col("a").gt(lit(10i32)), col("c").gt_eq(lit(1u64))];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, Some(&col_stats), None)?;
+    assert!(prewhere.is_none());
+
+    // Out of the range of the column.
+    push_downs.filters = vec![col("a").eq(lit(200i32)), col("b").eq(lit("x".as_bytes()))];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, Some(&col_stats), None)?;
+    assert_eq!(prewhere.map(|p| p.columns), Some(vec![0]));
+
+    // Columns derived by the query.
+    push_downs.filters = vec![col("d").eq(lit(1i32))];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, Some(&col_stats), None)?;
+    assert!(prewhere.is_none());
+
+    // Without the statistics.
+    push_downs.filters = vec![col("a").gt(lit(90i32))];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, None, None)?;
+    assert!(prewhere.is_none());
+
+    // Nothing left to read after the condition columns.
+    let prewhere = Prewhere::try_create(&schema, &[0], &push_downs, Some(&col_stats), None)?;
+    assert!(prewhere.is_none());
+
+    // Not selective by the min/max values, but the histogram shows most values are small.
+    push_downs.filters = vec![col("a").gt(lit(10i32))];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, Some(&col_stats), None)?;
+    assert!(prewhere.is_none());
+
+    let bucket = |lower: i32, upper: i32, count: u64, distinct: u64| HistogramBucket {
+        lower: DataValue::Int32(Some(lower)),
+        upper: DataValue::Int32(Some(upper)),
+        count,
+        distinct,
+    };
+    let mut table_stats = TableStatistics {
+        rows: 100,
+        columns: BTreeMap::new(),
+    };
+    table_stats
+        .columns
+        .insert("a".to_string(), ColumnStatistics {
+            null_count: 0,
+            min: DataValue::Int32(Some(0)),
+            max: DataValue::Int32(Some(100)),
+            histogram: Some(Histogram {
+                buckets: vec![bucket(0, 5, 90, 6), bucket(6, 100, 10, 10)],
+            }),
+        });
+    let prewhere = Prewhere::try_create(
+        &schema,
+        &projection,
+        &push_downs,
+        Some(&col_stats),
+        Some(&table_stats),
+    )?;
+    assert_eq!(prewhere.map(|p| p.columns), Some(vec![0]));
+
+    Ok(())
+}
+
+#[test]
+fn test_prewhere_explicit_conditions() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let projection = vec![0, 1];
+
+    let mut push_downs = Extras::default();
+    push_downs.filters = vec![col("a").gt(lit(1i32)), col("b").eq(lit("y".as_bytes()))];
+    push_downs.prewhere = vec![col("b").eq(lit("y".as_bytes()))];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, None, None)?.unwrap();
+    assert_eq!(prewhere.columns, vec![1]);
+
+    let block =
+        DataBlock::create_by_array(prewhere.schema(), vec![Series::new(vec!["x", "y", "z"])]);
+    let filter = prewhere.filter(&block)?;
+    let filtered = DataBlock::filter_block(&block, filter)?;
+    assert_eq!(filtered.num_rows(), 1);
+
+    push_downs.prewhere = vec![col("d").eq(lit(1i32))];
+    let prewhere = Prewhere::try_create(&schema, &projection, &push_downs, None, None);
+    assert!(prewhere.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_may_match() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("url", DataType::String, false),
+    ]);
+    let stats = |min: DataValue, max: DataValue| ColStats {
+        min,
+        max,
+        null_count: 0,
+    };
+    let col_stats = vec![(
+        0,
+        stats(DataValue::Int32(Some(10)), DataValue::Int32(Some(20))),
+    )]
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+    let lower_url = Expression::ScalarFunction {
+        op: "lower".to_string(),
+        args: vec![col("url")],
+    };
+    let index_stats = vec![(
+        lower_url.column_name(),
+        stats(
+            DataValue::String(Some("a.com".as_bytes().to_vec())),
+            DataValue::String(Some("c.com".as_bytes().to_vec())),
+        ),
+    )]
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+
+    let msg: DataColumn = Series::new(vec!["GET /api/users 200", "POST /api/users 500"]).into();
+    let msg_index = InvertedIndex::create_index("msg", &msg)?;
+    let inverted_indexes = vec![("msg".to_string(), msg_index)]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    let matches = |condition: Expression| {
+        may_match(
+            &schema,
+            &condition,
+            &col_stats,
+            &index_stats,
+            &inverted_indexes,
+        )
+    };
+    assert!(matches(col("a").eq(lit(15i32))));
+    assert!(!matches(col("a").gt(lit(20i32))));
+    assert!(!matches(lit(5i32).gt(col("a"))));
+    assert!(matches(lower_url.eq(lit("b.com".as_bytes()))));
+    assert!(!matches(lower_url.eq(lit("d.com".as_bytes()))));
+    assert!(!matches(
+        col("a")
+            .gt(lit(20i32))
+            .or(lower_url.lt(lit("a.com".as_bytes())))
+    ));
+    assert!(!matches(
+        col("a")
+            .eq(lit(15i32))
+            .and(lower_url.gt(lit("c.com".as_bytes())))
+    ));
+
+    // No values of the expressions not indexed.
+    let upper_url = Expression::ScalarFunction {
+        op: "upper".to_string(),
+        args: vec![col("url")],
+    };
+    assert!(matches(upper_url.eq(lit("D.COM".as_bytes()))));
+    assert!(matches(col("url").eq(lit("d.com".as_bytes()))));
+
+    let match_msg = |column: &str, query: &str| Expression::ScalarFunction {
+        op: "match".to_string(),
+        args: vec![col(column), lit(query.as_bytes())],
+    };
+    assert!(matches(match_msg("msg", "users 500")));
+    assert!(!matches(match_msg("msg", "users 404")));
+    assert!(!matches(
+        match_msg("msg", "put").and(col("a").eq(lit(15i32)))
+    ));
+    assert!(matches(match_msg("msg", "put").or(col("a").eq(lit(15i32)))));
+    // No tokens of the columns not indexed.
+    assert!(matches(match_msg("url", "put")));
+    Ok(())
+}
@@ -18,10 +18,13 @@ use common_arrow::parquet::statistics::Statistics;
 use common_base::uuid;
 use common_datavalues::DataSchema;
 use common_datavalues::DataValue;
+use common_meta_types::MetaId;
 use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::datasources::index::InvertedIndex;
+
 pub type SnapshotId = Uuid; // TODO String might be better
 pub type ColumnId = u32;
 pub type Location = String;
@@ -49,7 +52,10 @@ pub struct TableSnapshot {
 }
 
 impl TableSnapshot {
+    /// A new snapshot derived from this one, with the segment appended.
     pub fn append_segment(mut self, location: Location) -> TableSnapshot {
+        self.prev_snapshot_id = Some(self.snapshot_id);
+        self.snapshot_id = Uuid::new_v4();
         self.segments.push(location);
         self
     }
@@ -65,6 +71,29 @@ pub struct SegmentInfo {
 
     /// summary statistics
     pub summary: Stats,
+
+    /// unix timestamp(in seconds) of the segment being written, by which the lifecycle rules
+    /// of the table apply. None for the segments written before it was kept.
+    #[serde(default)]
+    pub created_on: Option<u64>,
+
+    /// unix timestamp(in seconds) of the segment being moved to the cold tier, after which its
+    /// hot copy is purged. None for the segments not moved.
+    #[serde(default)]
+    pub moved_on: Option<u64>,
+}
+
+/// A segment written but not committed yet, kept under the staging prefix. The commit of the
+/// snapshot publishes it to `segment_location`, the GC cleans it up if the commit is abandoned.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct StagedSegment {
+    pub table_id: MetaId,
+    /// The snapshot which refers to the segment
+    pub snapshot_location: Location,
+    pub segment_location: Location,
+    pub segment: SegmentInfo,
+    /// The blocks written for the segment, the others are shared with the committed segments
+    pub written_blocks: Vec<Location>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
@@ -86,6 +115,14 @@ pub struct BlockMeta {
     pub row_count: u64,
     pub block_size: u64,
     pub col_stats: HashMap<ColumnId, ColStats>,
+    /// The min and max values of the expressions of the skipping indexes, by the keys of
+    /// the indexes. Empty for the blocks written before the indexes were created.
+    #[serde(default)]
+    pub index_stats: HashMap<String, ColStats>,
+    /// The inverted indexes of the columns, by the names of the columns. Empty for the blocks
+    /// written before the indexes were created.
+    #[serde(default)]
+    pub inverted_indexes: HashMap<String, InvertedIndex>,
     pub location: BlockLocation,
 }
 
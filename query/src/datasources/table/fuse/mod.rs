@@ -17,9 +17,17 @@ pub(crate) mod io;
 mod meta;
 mod table;
 mod table_do_append;
+mod table_do_apply_storage_policy;
+mod table_do_delete;
+mod table_do_fsck;
+mod table_do_gc;
+mod table_do_materialize;
+mod table_do_merge;
+mod table_do_purge;
 mod table_do_read;
 mod table_do_read_partitions;
 mod table_do_truncate;
+mod table_do_update;
 pub(crate) mod util;
 
 #[cfg(test)]
@@ -30,3 +38,4 @@ mod table_test_fixture;
 pub(crate) use io::*;
 pub(crate) use meta::*;
 pub(crate) use table::FuseTable;
+pub(crate) use table_do_fsck::FsckIssue;
@@ -21,6 +21,10 @@ use common_context::DataContext;
 use common_context::IOContext;
 use common_context::TableIOContext;
 use common_dal::read_obj;
+use common_dal::DataAccessor;
+use common_dal::TieredAccessor;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
 use common_exception::Result;
 use common_meta_types::TableInfo;
 use common_planners::Extras;
@@ -29,12 +33,22 @@ use common_planners::Part;
 use common_planners::Partitions;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::VIRTUAL_COLUMN_BLOCK_PATH;
+use common_planners::VIRTUAL_COLUMN_ROW_ID;
 use common_streams::SendableDataBlockStream;
 
 use super::util;
 use crate::catalogs::Table;
+use crate::datasources::common::check_disk_data_path;
+use crate::datasources::common::cold_storage_config_with_options;
+use crate::datasources::common::storage_config_with_options;
+use crate::datasources::common::COLD_STORAGE_OPT_KEY_PREFIX;
+use crate::datasources::common::STORAGE_OPT_KEY_DISK_DATA_PATH;
 use crate::datasources::table::fuse::BlockMeta;
+use crate::datasources::table::fuse::BlockWriteOptions;
 use crate::datasources::table::fuse::TableSnapshot;
+use crate::sessions::DatabendQueryContext;
+use crate::sessions::Settings;
 
 pub struct FuseTable {
     pub(crate) table_info: TableInfo,
@@ -63,6 +77,13 @@ impl Table for FuseTable {
         &self.table_info
     }
 
+    fn virtual_columns(&self) -> Vec<DataField> {
+        vec![
+            DataField::new(VIRTUAL_COLUMN_BLOCK_PATH, DataType::String, false),
+            DataField::new(VIRTUAL_COLUMN_ROW_ID, DataType::UInt64, false),
+        ]
+    }
+
     fn read_partitions(
         &self,
         io_ctx: Arc<TableIOContext>,
@@ -101,7 +122,7 @@ impl FuseTable {
     pub fn table_snapshot(&self, io_ctx: &TableIOContext) -> Result<Option<TableSnapshot>> {
         let option = &self.table_info.options;
         if let Some(loc) = option.get(util::TBL_OPT_KEY_SNAPSHOT_LOC) {
-            let da = io_ctx.get_data_accessor()?;
+            let da = self.get_data_accessor(io_ctx)?;
             let r = read_obj(da, loc.to_string()).wait_in(&io_ctx.get_runtime(), None)??;
             Ok(Some(r))
         } else {
@@ -110,6 +131,60 @@ impl FuseTable {
         }
     }
 
+    /// The data accessor of the table, a table with storage options lives in its own storage.
+    /// If the table has a cold tier, the objects moved to it are accessed transparently.
+    pub(crate) fn get_data_accessor(
+        &self,
+        io_ctx: &TableIOContext,
+    ) -> Result<Arc<dyn DataAccessor>> {
+        let ctx = match io_ctx.get_user_data::<DatabendQueryContext>()? {
+            Some(ctx) => ctx,
+            None => return io_ctx.get_data_accessor(),
+        };
+
+        let conf = ctx.get_config();
+        let options =
+            &ctx.resolve_storage_connections(&self.table_info.db, &self.table_info.options)?;
+        let cold_key = format!(
+            "{}{}",
+            COLD_STORAGE_OPT_KEY_PREFIX, STORAGE_OPT_KEY_DISK_DATA_PATH
+        );
+        for key in [STORAGE_OPT_KEY_DISK_DATA_PATH, cold_key.as_str()] {
+            if let Some(path) = options.get(key) {
+                check_disk_data_path(&conf.storage.disk.data_path, path)?;
+            }
+        }
+        let (hot_storage, hot) = match storage_config_with_options(&conf.storage, options)? {
+            Some(storage) => (storage.clone(), ctx.get_storage_data_accessor(storage)?),
+            None => (conf.storage, io_ctx.get_data_accessor()?),
+        };
+
+        // The cold tier takes what it does not set from the hot one, e.g. the credentials.
+        match cold_storage_config_with_options(&hot_storage, options)? {
+            None => Ok(hot),
+            Some(cold_storage) => {
+                let cold = ctx.get_storage_data_accessor(cold_storage)?;
+                Ok(Arc::new(TieredAccessor::create(
+                    hot,
+                    cold,
+                    util::COLD_TIER_PREFIX,
+                )))
+            }
+        }
+    }
+
+    /// How the blocks of the table are written, the table options override the settings.
+    pub(crate) fn get_block_write_options(
+        &self,
+        io_ctx: &TableIOContext,
+    ) -> Result<BlockWriteOptions> {
+        let settings = match io_ctx.get_user_data::<DatabendQueryContext>()? {
+            Some(ctx) => ctx.get_settings(),
+            None => Settings::try_create()?,
+        };
+        BlockWriteOptions::create(&self.table_info.options, &settings)
+    }
+
     pub(crate) fn to_partitions(&self, blocks_metas: &[BlockMeta]) -> (Statistics, Partitions) {
         blocks_metas.iter().fold(
             (Statistics::default(), Partitions::default()),
@@ -17,6 +17,8 @@ use std::sync::Arc;
 
 use common_context::IOContext;
 use common_context::TableIOContext;
+use common_dal::read_obj;
+use common_dal::DataAccessor;
 use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -25,14 +27,23 @@ use common_meta_types::MetaVersion;
 use common_planners::InsertIntoPlan;
 use uuid::Uuid;
 
+use super::table_do_purge::object_exists;
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::common::count_table_changed_rows;
 use crate::datasources::table::fuse::util;
 use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
 use crate::datasources::table::fuse::BlockAppender;
+use crate::datasources::table::fuse::BlockStream;
 use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::Location;
 use crate::datasources::table::fuse::SegmentInfo;
+use crate::datasources::table::fuse::StagedSegment;
 use crate::datasources::table::fuse::TableSnapshot;
 use crate::sessions::DatabendQueryContext;
 
+pub(super) const COMMIT_MAX_RETRIES: usize = 10;
+
 impl FuseTable {
     #[inline]
     pub async fn do_append(
@@ -49,47 +60,283 @@ impl FuseTable {
             }
         };
 
-        let da = io_ctx.get_data_accessor()?;
+        let (snapshot_loc, rows) = self
+            .do_append_uncommitted(io_ctx.as_ref(), block_stream)
+            .await?;
+
+        // 5. commit
+        self.do_commit(io_ctx.as_ref(), snapshot_loc, rows).await
+    }
+
+    /// Writes the blocks and a new snapshot with them, returns the location of the snapshot
+    /// and the number of the rows appended.
+    /// The table does not see the blocks until the snapshot is committed by `do_commit`.
+    pub(crate) async fn do_append_uncommitted(
+        &self,
+        io_ctx: &TableIOContext,
+        block_stream: BlockStream,
+    ) -> Result<(String, u64)> {
+        let da = self.get_data_accessor(io_ctx)?;
+        let write_options = self.get_block_write_options(io_ctx)?;
 
         // 2. Append blocks to storage
-        let segment_info =
-            BlockAppender::append_blocks(da.clone(), block_stream, self.table_info.schema.as_ref())
-                .await?;
+        let segment_info = BlockAppender::append_blocks(
+            da.clone(),
+            block_stream,
+            self.table_info.schema.as_ref(),
+            &write_options,
+        )
+        .await?;
 
-        // 3. save segment info
+        // 3. new snapshot
         let seg_loc = util::gen_segment_info_location();
-        let bytes = serde_json::to_vec(&segment_info)?;
-        da.put(&seg_loc, bytes).await?;
-
-        // 4. new snapshot
-        let prev_snapshot = self.table_snapshot(io_ctx.as_ref())?;
-
-        // TODO backoff retry this block
-        {
-            let new_snapshot = merge_snapshot(
-                self.table_info.schema.as_ref(),
-                prev_snapshot,
-                (segment_info, seg_loc),
-            )?;
-
-            // 4.1 save the new snapshot
-            let uuid = new_snapshot.snapshot_id;
-            let snapshot_loc = util::snapshot_location(uuid.to_simple().to_string().as_str());
-            let bytes = serde_json::to_vec(&new_snapshot)?;
-            da.put(&snapshot_loc, bytes).await?;
-
-            // 5. commit
-            let table_id = insert_plan.tbl_id;
-            commit(&io_ctx, table_id, self.table_info.version, snapshot_loc)?;
+        let rows = segment_info.summary.row_count;
+        let prev_snapshot = self.table_snapshot(io_ctx)?;
+        let new_snapshot = merge_snapshot(
+            self.table_info.schema.as_ref(),
+            prev_snapshot,
+            (&segment_info, seg_loc.clone()),
+        )?;
+        let uuid = new_snapshot.snapshot_id;
+        let snapshot_loc = util::snapshot_location(uuid.to_simple().to_string().as_str());
+
+        // 4. stage segment info, it is published by the commit
+        let written_blocks = segment_info
+            .blocks
+            .iter()
+            .map(|block| block.location.location.clone())
+            .collect();
+        self.stage_segment(
+            da.clone(),
+            segment_info,
+            seg_loc,
+            written_blocks,
+            &snapshot_loc,
+        )
+        .await?;
+
+        // 4.1 save the new snapshot
+        let bytes = serde_json::to_vec(&new_snapshot)?;
+        da.put(&snapshot_loc, bytes).await?;
+        Ok((snapshot_loc, rows))
+    }
+
+    /// Writes the segment to its staging location, with the blocks written for it. The snapshot
+    /// referring to it can be committed only after it is published by `publish_staged_segments`.
+    pub(super) async fn stage_segment(
+        &self,
+        da: Arc<dyn DataAccessor>,
+        segment: SegmentInfo,
+        segment_location: Location,
+        written_blocks: Vec<Location>,
+        snapshot_location: &str,
+    ) -> Result<()> {
+        let staged_loc = util::staged_segment_location(&segment_location);
+        let staged = StagedSegment {
+            table_id: self.get_id(),
+            snapshot_location: snapshot_location.to_string(),
+            segment_location,
+            segment,
+            written_blocks,
+        };
+        da.put(&staged_loc, serde_json::to_vec(&staged)?).await
+    }
+
+    /// Commits the snapshot written by `do_append_uncommitted`.
+    ///
+    /// The snapshot pointer of the table is swapped only if the table is still of the version
+    /// the snapshot is based on. If the table is changed by others meanwhile, the appended
+    /// segments are rebased onto the latest snapshot of the table and the commit is retried,
+    /// the location of the snapshot is kept, so it can still be found by `is_snapshot_committed`.
+    /// The segments staged for the snapshot are published before the commit, and removed from the
+    /// staging location after it.
+    pub(crate) async fn do_commit(
+        &self,
+        io_ctx: &TableIOContext,
+        snapshot_loc: String,
+        rows: u64,
+    ) -> Result<()> {
+        self.do_commit_if(io_ctx, snapshot_loc, rows, || Ok(()))
+            .await
+    }
+
+    /// Commits the snapshot like `do_commit`, the guard is checked right before every attempt
+    /// to swap the snapshot pointer, the commit is given up if it fails.
+    pub(crate) async fn do_commit_if(
+        &self,
+        io_ctx: &TableIOContext,
+        snapshot_loc: String,
+        rows: u64,
+        guard: impl Fn() -> Result<()>,
+    ) -> Result<()> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+        let catalog = ctx.get_catalog();
+        let da = self.get_data_accessor(io_ctx)?;
+        let staged = publish_staged_segments(da.clone(), &snapshot_loc).await?;
+
+        let mut table_info = self.table_info.clone();
+        for _ in 0..COMMIT_MAX_RETRIES {
+            guard()?;
+            match commit(
+                io_ctx,
+                table_info.table_id,
+                table_info.version,
+                snapshot_loc.clone(),
+            ) {
+                Err(cause) if cause.code() == ErrorCode::CommitTableError("").code() => {
+                    let table = catalog.get_table_by_id(table_info.table_id, None)?;
+                    let latest = match table.as_any().downcast_ref::<FuseTable>() {
+                        Some(latest) => latest,
+                        None => return Err(cause),
+                    };
+                    self.rebase(io_ctx, latest, &snapshot_loc).await?;
+                    table_info = latest.table_info.clone();
+                }
+                Err(cause) => return Err(cause),
+                Ok(_) => {
+                    count_table_changed_rows(io_ctx, &table_info, rows);
+                    remove_staged_segments(da.as_ref(), &staged).await;
+                    return Ok(());
+                }
+            }
+        }
+
+        Err(ErrorCode::CommitTableError(format!(
+            "Cannot commit the append to table {}, it is changed by others concurrently",
+            self.table_info.name
+        )))
+    }
+
+    /// Rewrites the uncommitted snapshot, so that it appends the same segments to the latest
+    /// snapshot of the table, instead of the one it is based on.
+    async fn rebase(
+        &self,
+        io_ctx: &TableIOContext,
+        latest: &FuseTable,
+        snapshot_loc: &str,
+    ) -> Result<()> {
+        let schema = self.table_info.schema.as_ref();
+        if latest.table_info.schema.as_ref() != schema {
+            return Err(ErrorCode::CommitTableError(format!(
+                "Cannot commit the append to table {}, its schema is changed concurrently",
+                self.table_info.name
+            )));
         }
+
+        let da = self.get_data_accessor(io_ctx)?;
+        let mut snapshot: TableSnapshot = read_obj(da.clone(), snapshot_loc.to_string()).await?;
+
+        let appended = new_segments(da.clone(), &snapshot).await?;
+
+        let (prev_snapshot_id, mut segments, mut summary) = match latest.table_snapshot(io_ctx)? {
+            None => (None, vec![], None),
+            Some(s) => (Some(s.snapshot_id), s.segments, Some(s.summary)),
+        };
+        for loc in appended.iter() {
+            let segment: SegmentInfo = read_obj(da.clone(), loc.clone()).await?;
+            summary = match summary {
+                None => Some(segment.summary),
+                Some(s) => Some(util::merge_stats(schema, &s, &segment.summary)?),
+            };
+        }
+        segments.extend(appended);
+
+        snapshot.prev_snapshot_id = prev_snapshot_id;
+        snapshot.segments = segments;
+        snapshot.summary = summary.unwrap_or_default();
+        let bytes = serde_json::to_vec(&snapshot)?;
+        da.put(snapshot_loc, bytes).await?;
         Ok(())
     }
+
+    /// Whether the snapshot is the current one of the table or one of its ancestors.
+    pub(crate) async fn is_snapshot_committed(
+        &self,
+        io_ctx: &TableIOContext,
+        snapshot_loc: &str,
+    ) -> Result<bool> {
+        let mut loc = match self.table_info.options.get(TBL_OPT_KEY_SNAPSHOT_LOC) {
+            None => return Ok(false),
+            Some(loc) => loc.clone(),
+        };
+
+        let da = self.get_data_accessor(io_ctx)?;
+        loop {
+            if loc == snapshot_loc {
+                return Ok(true);
+            }
+
+            let snapshot: TableSnapshot = read_obj(da.clone(), loc).await?;
+            match snapshot.prev_snapshot_id {
+                None => return Ok(false),
+                Some(prev_id) => {
+                    loc = util::snapshot_location(prev_id.to_simple().to_string().as_str())
+                }
+            }
+        }
+    }
+}
+
+// The segments of the snapshot which are not in the one it is based on.
+async fn new_segments(
+    da: Arc<dyn DataAccessor>,
+    snapshot: &TableSnapshot,
+) -> Result<Vec<Location>> {
+    let base_segments = match snapshot.prev_snapshot_id {
+        None => vec![],
+        Some(prev_id) => {
+            let loc = util::snapshot_location(prev_id.to_simple().to_string().as_str());
+            let base: TableSnapshot = read_obj(da, loc).await?;
+            base.segments
+        }
+    };
+    Ok(snapshot
+        .segments
+        .iter()
+        .filter(|loc| !base_segments.contains(loc))
+        .cloned()
+        .collect())
+}
+
+/// Publishes the segments staged for the snapshot to their locations, returns the staging
+/// locations of them. It is fine to publish them again, a staged segment is never changed.
+pub(super) async fn publish_staged_segments(
+    da: Arc<dyn DataAccessor>,
+    snapshot_loc: &str,
+) -> Result<Vec<String>> {
+    let snapshot: TableSnapshot = read_obj(da.clone(), snapshot_loc.to_string()).await?;
+    let mut staged_locs = vec![];
+    for seg_loc in new_segments(da.clone(), &snapshot).await? {
+        // Not staged if it is published and removed by an earlier commit of the snapshot.
+        let staged_loc = util::staged_segment_location(&seg_loc);
+        if !object_exists(da.as_ref(), &staged_loc).await? {
+            continue;
+        }
+
+        let staged: StagedSegment = read_obj(da.clone(), staged_loc.clone()).await?;
+        da.put(&seg_loc, serde_json::to_vec(&staged.segment)?)
+            .await?;
+        staged_locs.push(staged_loc);
+    }
+    Ok(staged_locs)
+}
+
+/// Removes the segments staged for a committed snapshot. The ones failed to remove are left to
+/// `do_gc_staged`, the commit is done anyway.
+pub(super) async fn remove_staged_segments(da: &dyn DataAccessor, staged_locs: &[String]) {
+    for loc in staged_locs {
+        if let Err(cause) = da.remove(loc).await {
+            log::warn!("Cannot remove the staged segment {}, cause {}", loc, cause);
+        }
+    }
 }
 
 fn merge_snapshot(
     schema: &DataSchema,
     pre: Option<TableSnapshot>,
-    (seg_info, loc): (SegmentInfo, String),
+    (seg_info, loc): (&SegmentInfo, String),
 ) -> Result<TableSnapshot> {
     if let Some(s) = pre {
         let mut new_snapshot = s.append_segment(loc);
@@ -101,19 +348,18 @@ fn merge_snapshot(
             snapshot_id: Uuid::new_v4(),
             prev_snapshot_id: None,
             schema: schema.clone(),
-            summary: seg_info.summary,
+            summary: seg_info.summary.clone(),
             segments: vec![loc],
         })
     }
 }
 
-fn commit(
+pub(super) fn commit(
     io_ctx: &TableIOContext,
     table_id: MetaId,
     table_version: MetaVersion,
     new_snapshot_location: String,
 ) -> Result<()> {
-    use crate::catalogs::Catalog;
     let ctx: Arc<DatabendQueryContext> = io_ctx
         .get_user_data()?
         .expect("DatabendQueryContext should not be None");
@@ -0,0 +1,181 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_dal::read_obj;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use uuid::Uuid;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::configs::StorageConfig;
+use crate::datasources::common::cold_storage_config_with_options;
+use crate::datasources::table::fuse::util;
+use crate::datasources::table::fuse::util::StoragePolicy;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::SegmentInfo;
+use crate::sessions::DatabendQueryContext;
+
+impl FuseTable {
+    /// Moves the segments(and their blocks) older than the lifecycle rule to the cold tier,
+    /// returns the number of the moved segments.
+    pub async fn do_apply_storage_policy(&self, io_ctx: Arc<TableIOContext>) -> Result<usize> {
+        let policy = match StoragePolicy::from_options(&self.table_info.options)? {
+            None => return Ok(0),
+            Some(policy) => policy,
+        };
+
+        let options = &self.table_info.options;
+        if cold_storage_config_with_options(&StorageConfig::default(), options)?.is_none() {
+            return Err(ErrorCode::BadOption(format!(
+                "Table {}.{} has no cold storage, set it by the cold_storage_* options",
+                self.table_info.db, self.table_info.name
+            )));
+        }
+
+        let prev_snapshot = match self.table_snapshot(&io_ctx)? {
+            None => return Ok(0),
+            Some(snapshot) => snapshot,
+        };
+
+        let da = self.get_data_accessor(&io_ctx)?;
+        let now = util::unix_timestamp_secs();
+        let hot_to_cold_after = policy.hot_to_cold_after.as_secs();
+
+        let mut moved = 0;
+        let mut segments = Vec::with_capacity(prev_snapshot.segments.len());
+        for seg_loc in &prev_snapshot.segments {
+            if util::is_cold_location(seg_loc) {
+                segments.push(seg_loc.clone());
+                continue;
+            }
+
+            let mut segment: SegmentInfo = read_obj(da.clone(), seg_loc.clone()).await?;
+            match segment.created_on {
+                Some(created_on) if now.saturating_sub(created_on) >= hot_to_cold_after => {}
+                _ => {
+                    segments.push(seg_loc.clone());
+                    continue;
+                }
+            }
+
+            // The segments rewritten from the cold ones keep their cold blocks.
+            for block in segment.blocks.iter_mut() {
+                if util::is_cold_location(&block.location.location) {
+                    continue;
+                }
+                let bytes = da.read(&block.location.location).await?;
+                let cold_loc = util::cold_location(&block.location.location);
+                da.put(&cold_loc, bytes).await?;
+                block.location.location = cold_loc;
+            }
+            segment.moved_on = Some(now);
+
+            let cold_seg_loc = util::cold_location(seg_loc);
+            da.put(&cold_seg_loc, serde_json::to_vec(&segment)?).await?;
+            segments.push(cold_seg_loc);
+            moved += 1;
+        }
+
+        if moved == 0 {
+            return Ok(0);
+        }
+
+        // The snapshots stay in the hot tier, the hot copies are purged by `do_purge_hot_copies`.
+        let prev_id = prev_snapshot.snapshot_id;
+        let mut new_snapshot = prev_snapshot;
+        new_snapshot.snapshot_id = Uuid::new_v4();
+        new_snapshot.prev_snapshot_id = Some(prev_id);
+        new_snapshot.segments = segments;
+        let new_snapshot_loc =
+            util::snapshot_location(new_snapshot.snapshot_id.to_simple().to_string().as_str());
+        da.put(&new_snapshot_loc, serde_json::to_vec(&new_snapshot)?)
+            .await?;
+
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+        let catalog = ctx.get_catalog();
+        catalog.upsert_table_option(
+            self.get_id(),
+            self.table_info.version,
+            TBL_OPT_KEY_SNAPSHOT_LOC.to_string(),
+            new_snapshot_loc,
+        )?;
+
+        Ok(moved)
+    }
+
+    /// Removes the hot copies of the segments(and their blocks) moved to the cold tier more than
+    /// `grace_secs` ago, returns the number of the removed objects.
+    ///
+    /// The queries read the current snapshot only, the older snapshots which refer to the hot
+    /// copies are kept as the history of the table. The younger copies may still be read by the
+    /// queries started before the segments were moved.
+    pub async fn do_purge_hot_copies(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        grace_secs: u64,
+    ) -> Result<u64> {
+        let snapshot = match self.table_snapshot(&io_ctx)? {
+            None => return Ok(0),
+            Some(snapshot) => snapshot,
+        };
+
+        let da = self.get_data_accessor(&io_ctx)?;
+        let now = util::unix_timestamp_secs();
+        let mut removed = 0;
+        for seg_loc in snapshot.segments.iter() {
+            if !util::is_cold_location(seg_loc) {
+                continue;
+            }
+
+            let hot_seg_loc = util::hot_location(seg_loc);
+            if snapshot.segments.contains(&hot_seg_loc) {
+                continue;
+            }
+            let purged = !da
+                .list(&hot_seg_loc)
+                .await?
+                .iter()
+                .any(|object| object.path == hot_seg_loc);
+            if purged {
+                continue;
+            }
+
+            let segment: SegmentInfo = read_obj(da.clone(), seg_loc.clone()).await?;
+            match segment.moved_on {
+                Some(moved_on) if now.saturating_sub(moved_on) >= grace_secs => {}
+                _ => continue,
+            }
+
+            // The hot segment may keep the cold blocks of the segment it is rewritten from.
+            let hot_segment: SegmentInfo = read_obj(da.clone(), hot_seg_loc.clone()).await?;
+            for block in hot_segment.blocks.iter() {
+                if !util::is_cold_location(&block.location.location) {
+                    da.remove(&block.location.location).await?;
+                    removed += 1;
+                }
+            }
+            da.remove(&hot_seg_loc).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
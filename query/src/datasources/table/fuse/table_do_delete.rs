@@ -0,0 +1,492 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_dal::read_obj;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_planners::Expression;
+use common_planners::Part;
+use common_planners::TruncateTablePlan;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use super::table_do_append::commit;
+use super::table_do_append::publish_staged_segments;
+use super::table_do_append::remove_staged_segments;
+use super::table_do_append::COMMIT_MAX_RETRIES;
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::common::count_table_changed_rows;
+use crate::datasources::common::ColumnDefaults;
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::util;
+use crate::datasources::table::fuse::BlockAppender;
+use crate::datasources::table::fuse::BlockMeta;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::SegmentInfo;
+use crate::datasources::table::fuse::Stats;
+use crate::datasources::table::fuse::TableSnapshot;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::DatabendQueryContext;
+
+/// How the rows matching the predicate of `mutate` are changed.
+pub(super) enum Mutation<'a> {
+    Delete,
+    /// The executor computes the new values of the matched rows, in the columns of the table.
+    Update(&'a ExpressionExecutor),
+}
+
+/// Rewrites the blocks of a table by `FuseTable::rewrite`.
+pub(super) trait BlockRewriter: Send {
+    /// The blocks which can not match the predicate by their column statistics are kept as is.
+    fn predicate(&self) -> Option<&Expression>;
+
+    /// Returns the number of the changed rows of the block and the blocks replacing it, the
+    /// block is kept as is if no row is changed.
+    fn rewrite(&mut self, block: &DataBlock) -> Result<(u64, Vec<DataBlock>)>;
+
+    /// The blocks appended to the table after all the blocks are rewritten, their rows are
+    /// counted as changed.
+    fn appended(&mut self) -> Result<Vec<DataBlock>>;
+}
+
+struct PredicateRewriter<'a> {
+    schema: DataSchemaRef,
+    predicate: &'a Expression,
+    executor: ExpressionExecutor,
+    mutation: Mutation<'a>,
+}
+
+impl<'a> BlockRewriter for PredicateRewriter<'a> {
+    fn predicate(&self) -> Option<&Expression> {
+        Some(self.predicate)
+    }
+
+    fn rewrite(&mut self, block: &DataBlock) -> Result<(u64, Vec<DataBlock>)> {
+        // The rows are kept unless the predicate is true.
+        let predicate_values = self.executor.execute(block)?.column(0).to_array()?;
+        let matched = true_values(&predicate_values)?;
+        let keep = matched.iter().map(|v| !v).collect::<Vec<_>>();
+        let kept_rows = keep.iter().filter(|v| **v).count();
+        if kept_rows == block.num_rows() {
+            return Ok((0, vec![]));
+        }
+
+        let mut blocks = vec![];
+        if let Mutation::Update(update_executor) = &self.mutation {
+            let matched = DataBlock::filter_block(block, Series::new(matched))?;
+            let updated = update_executor.execute(&matched)?;
+            blocks.push(DataBlock::create(
+                self.schema.clone(),
+                updated.columns().to_vec(),
+            ));
+        }
+        if kept_rows > 0 {
+            blocks.push(DataBlock::filter_block(block, Series::new(keep))?);
+        }
+        Ok(((block.num_rows() - kept_rows) as u64, blocks))
+    }
+
+    fn appended(&mut self) -> Result<Vec<DataBlock>> {
+        Ok(vec![])
+    }
+}
+
+/// A segment of the previous snapshot after its blocks are rewritten.
+enum RewrittenSegment {
+    /// No row of the segment is changed, it is shared with the previous snapshot.
+    Kept(Stats),
+    /// All the rows of the segment are changed, and no block is left.
+    Removed(u64),
+    /// The number of the changed rows, the new segment and the locations of its written blocks.
+    Rewritten(u64, SegmentInfo, Vec<String>),
+}
+
+/// Whether the values of a condition are true, NULL is not.
+pub(super) fn true_values(values: &Series) -> Result<Vec<bool>> {
+    Ok(values
+        .cast_with_type(&DataType::Boolean)?
+        .bool()?
+        .collect_values()
+        .into_iter()
+        .map(|v| v == Some(true))
+        .collect())
+}
+
+impl FuseTable {
+    /// Deletes the rows satisfying the predicate, or all the rows if there is no predicate,
+    /// returns the number of the deleted rows.
+    pub async fn do_delete(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        predicate: &Option<Expression>,
+    ) -> Result<u64> {
+        let prev_snapshot = match self.table_snapshot(&io_ctx)? {
+            None => return Ok(0),
+            Some(snapshot) => snapshot,
+        };
+
+        let predicate = match predicate {
+            Some(predicate) => predicate,
+            None => {
+                let rows = prev_snapshot.summary.row_count;
+                let plan = TruncateTablePlan {
+                    db: self.table_info.db.clone(),
+                    table: self.table_info.name.clone(),
+                };
+                self.do_truncate(io_ctx, plan).await?;
+                return Ok(rows);
+            }
+        };
+        if io::has_subquery(predicate)? {
+            return Err(ErrorCode::SyntaxException(format!(
+                "Subqueries are not allowed in DELETE: {:?}",
+                predicate
+            )));
+        }
+
+        self.mutate(io_ctx, predicate, Mutation::Delete).await
+    }
+
+    /// Mutates the rows satisfying the predicate, returns the number of the matched rows.
+    pub(super) async fn mutate(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        predicate: &Expression,
+        mutation: Mutation<'_>,
+    ) -> Result<u64> {
+        let schema = self.table_info.schema.clone();
+        let predicate_field = predicate.to_data_field(&schema)?;
+        let executor = ExpressionExecutor::try_create(
+            "mutation predicate executor",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![predicate_field]),
+            vec![predicate.clone()],
+            false,
+        )?;
+        executor.validate()?;
+
+        let mut rewriter = PredicateRewriter {
+            schema,
+            predicate,
+            executor,
+            mutation,
+        };
+        self.rewrite(io_ctx, &mut rewriter).await
+    }
+
+    /// Rewrites the blocks of the table by the rewriter, returns the number of the changed rows.
+    ///
+    /// The blocks which can not match the predicate of the rewriter by their column statistics
+    /// are skipped, the others are read and rewritten if some rows are changed. Only the
+    /// segments of the rewritten blocks are rewritten, the other segments are shared with the
+    /// previous snapshot, the rewritten ones and the one of the appended blocks are staged
+    /// until the commit. Up to `max_threads` segments are rewritten concurrently, their new
+    /// segments are merged in the order of the previous snapshot. The new snapshot fails to
+    /// commit if the data of the table is changed meanwhile, the commit is retried if only the
+    /// other options of the table are changed.
+    pub(super) async fn rewrite<R: BlockRewriter>(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        rewriter: &mut R,
+    ) -> Result<u64> {
+        let prev_snapshot = self.table_snapshot(&io_ctx)?;
+        let schema = self.table_info.schema.clone();
+
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+        let column_cache = ctx.get_sessions_manager().get_column_cache();
+        let da = self.get_data_accessor(&io_ctx)?;
+        let write_options = self.get_block_write_options(&io_ctx)?;
+        let arrow_schema = schema.to_arrow();
+        let defaults = Arc::new(ColumnDefaults::from_options(&self.table_info.options)?);
+        let projection = (0..schema.fields().len()).collect::<Vec<_>>();
+
+        // The column statistics are kept by the positions of the columns, they are not
+        // trusted once the columns are altered. The skipping indexes are kept by their
+        // expressions, they are always trusted.
+        let prunable = match &prev_snapshot {
+            Some(snapshot) => snapshot.schema == *schema,
+            None => false,
+        };
+        let no_col_stats = HashMap::new();
+        let prev_segments = match &prev_snapshot {
+            Some(snapshot) => snapshot.segments.clone(),
+            None => vec![],
+        };
+
+        let max_threads = (ctx.get_settings().get_max_threads()? as usize).max(1);
+        let predicate = rewriter.predicate().cloned();
+        let shared = Mutex::new(&mut *rewriter);
+        let rewrite_segment = |seg_loc: String| async {
+            let segment: SegmentInfo = read_obj(da.clone(), seg_loc).await?;
+            let uncompressed_byte_size = segment.summary.uncompressed_byte_size;
+            let compressed_byte_size = segment.summary.compressed_byte_size;
+
+            let mut kept_blocks = Vec::with_capacity(segment.blocks.len());
+            let mut rewritten_blocks = vec![];
+            let mut segment_changed = 0;
+            for block_meta in segment.blocks {
+                if let Some(predicate) = &predicate {
+                    let col_stats = if prunable {
+                        &block_meta.col_stats
+                    } else {
+                        &no_col_stats
+                    };
+                    if !io::may_match(
+                        &schema,
+                        predicate,
+                        col_stats,
+                        &block_meta.index_stats,
+                        &block_meta.inverted_indexes,
+                    ) {
+                        kept_blocks.push(block_meta);
+                        continue;
+                    }
+                }
+
+                let part = Part {
+                    name: block_meta.location.location.clone(),
+                    version: 0,
+                };
+                let block = io::do_read(
+                    part,
+                    da.clone(),
+                    projection.clone(),
+                    arrow_schema.clone(),
+                    defaults.clone(),
+                    column_cache.clone(),
+                )
+                .await?;
+
+                let (changed, blocks) = shared.lock().rewrite(&block)?;
+                if changed == 0 {
+                    kept_blocks.push(block_meta);
+                    continue;
+                }
+                segment_changed += changed;
+                rewritten_blocks.extend(blocks.into_iter().filter(|b| b.num_rows() > 0));
+            }
+
+            if segment_changed == 0 {
+                return Ok(RewrittenSegment::Kept(segment.summary));
+            }
+            if kept_blocks.is_empty() && rewritten_blocks.is_empty() {
+                return Ok(RewrittenSegment::Removed(segment_changed));
+            }
+
+            // The file sizes of the kept blocks are not in their metas, they are
+            // estimated by their share of the segment.
+            let kept_uncompressed = kept_blocks.iter().map(|b| b.block_size).sum::<u64>();
+            let kept_compressed = (compressed_byte_size as f64 * kept_uncompressed as f64
+                / uncompressed_byte_size.max(1) as f64) as u64;
+
+            let stream = futures::stream::iter(rewritten_blocks.into_iter().map(Ok));
+            let rewritten =
+                BlockAppender::append_blocks(da.clone(), Box::pin(stream), &schema, &write_options)
+                    .await?;
+            let written_blocks = rewritten
+                .blocks
+                .iter()
+                .map(|block| block.location.location.clone())
+                .collect::<Vec<_>>();
+            kept_blocks.extend(rewritten.blocks);
+
+            let segment = SegmentInfo {
+                summary: blocks_summary(
+                    &schema,
+                    &kept_blocks,
+                    kept_compressed + rewritten.summary.compressed_byte_size,
+                )?,
+                blocks: kept_blocks,
+                created_on: segment.created_on,
+                moved_on: None,
+            };
+            Ok(RewrittenSegment::Rewritten(
+                segment_changed,
+                segment,
+                written_blocks,
+            ))
+        };
+        let segment_rewrites = futures::stream::iter(prev_segments.iter().cloned())
+            .map(rewrite_segment)
+            .buffered(max_threads)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut changed_rows = 0;
+        let mut segments = Vec::with_capacity(prev_segments.len() + 1);
+        let mut rewritten_segments = vec![];
+        let mut summary = Stats::default();
+        for (seg_loc, segment_rewrite) in prev_segments.iter().zip(segment_rewrites) {
+            let segment_summary = match segment_rewrite {
+                RewrittenSegment::Kept(segment_summary) => {
+                    segments.push(seg_loc.clone());
+                    segment_summary
+                }
+                RewrittenSegment::Removed(changed) => {
+                    changed_rows += changed;
+                    continue;
+                }
+                RewrittenSegment::Rewritten(changed, segment, written_blocks) => {
+                    changed_rows += changed;
+                    let new_seg_loc = util::gen_segment_info_location();
+                    let segment_summary = segment.summary.clone();
+                    segments.push(new_seg_loc.clone());
+                    rewritten_segments.push((new_seg_loc, segment, written_blocks));
+                    segment_summary
+                }
+            };
+            summary = util::merge_stats(&schema, &summary, &segment_summary)?;
+        }
+
+        let appended = rewriter
+            .appended()?
+            .into_iter()
+            .filter(|block| block.num_rows() > 0)
+            .collect::<Vec<_>>();
+        if !appended.is_empty() {
+            let stream = futures::stream::iter(appended.into_iter().map(Ok));
+            let segment =
+                BlockAppender::append_blocks(da.clone(), Box::pin(stream), &schema, &write_options)
+                    .await?;
+            let written_blocks = segment
+                .blocks
+                .iter()
+                .map(|block| block.location.location.clone())
+                .collect::<Vec<_>>();
+            let new_seg_loc = util::gen_segment_info_location();
+            changed_rows += segment.summary.row_count;
+            summary = util::merge_stats(&schema, &summary, &segment.summary)?;
+            segments.push(new_seg_loc.clone());
+            rewritten_segments.push((new_seg_loc, segment, written_blocks));
+        }
+
+        if changed_rows == 0 {
+            return Ok(0);
+        }
+
+        // The schema of the previous snapshot is kept, as the statistics of the kept blocks are.
+        let new_snapshot = match prev_snapshot {
+            Some(mut snapshot) => {
+                snapshot.prev_snapshot_id = Some(snapshot.snapshot_id);
+                snapshot.snapshot_id = Uuid::new_v4();
+                snapshot.segments = segments;
+                snapshot.summary = summary;
+                snapshot
+            }
+            None => TableSnapshot {
+                snapshot_id: Uuid::new_v4(),
+                prev_snapshot_id: None,
+                schema: schema.as_ref().clone(),
+                summary,
+                segments,
+            },
+        };
+        let snapshot_loc =
+            util::snapshot_location(new_snapshot.snapshot_id.to_simple().to_string().as_str());
+        for (seg_loc, segment, written_blocks) in rewritten_segments {
+            self.stage_segment(da.clone(), segment, seg_loc, written_blocks, &snapshot_loc)
+                .await?;
+        }
+        da.put(&snapshot_loc, serde_json::to_vec(&new_snapshot)?)
+            .await?;
+
+        let staged = publish_staged_segments(da.clone(), &snapshot_loc).await?;
+
+        // The version of the table is also bumped by the changes of its other options, e.g. the
+        // counting of the changed rows, the rewrite is still valid if the snapshot it is based on
+        // is still the current one, and the commit is retried then.
+        let catalog = ctx.get_catalog();
+        let prev_snapshot_loc = self.table_info.options.get(util::TBL_OPT_KEY_SNAPSHOT_LOC);
+        let mut table_info = self.table_info.clone();
+        for _ in 0..COMMIT_MAX_RETRIES {
+            match commit(
+                &io_ctx,
+                table_info.table_id,
+                table_info.version,
+                snapshot_loc.clone(),
+            ) {
+                Err(cause) if cause.code() == ErrorCode::CommitTableError("").code() => {
+                    let table = catalog.get_table_by_id(table_info.table_id, None)?;
+                    let latest = table.get_table_info();
+                    if latest.options.get(util::TBL_OPT_KEY_SNAPSHOT_LOC) != prev_snapshot_loc
+                        || latest.schema != self.table_info.schema
+                    {
+                        return Err(ErrorCode::CommitTableError(format!(
+                            "Cannot rewrite table {}, its data is changed by others concurrently",
+                            self.table_info.name
+                        )));
+                    }
+                    table_info = latest.clone();
+                }
+                Err(cause) => return Err(cause),
+                Ok(_) => {
+                    remove_staged_segments(da.as_ref(), &staged).await;
+                    count_table_changed_rows(&io_ctx, &table_info, changed_rows);
+                    return Ok(changed_rows);
+                }
+            }
+        }
+
+        Err(ErrorCode::CommitTableError(format!(
+            "Cannot commit the rewrite of table {}, it is changed by others concurrently",
+            self.table_info.name
+        )))
+    }
+}
+
+/// The summary of the blocks of a segment, with the compressed size of the blocks, which is
+/// not kept in the metas of the blocks.
+fn blocks_summary(
+    schema: &DataSchema,
+    blocks: &[BlockMeta],
+    compressed_byte_size: u64,
+) -> Result<Stats> {
+    // The statistics of the columns dropped since the block is written are left out.
+    let col_stats = blocks
+        .iter()
+        .map(|block| {
+            block
+                .col_stats
+                .iter()
+                .filter(|(id, _)| (**id as usize) < schema.fields().len())
+                .map(|(id, stats)| (*id, stats.clone()))
+                .collect::<HashMap<_, _>>()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Stats {
+        row_count: blocks.iter().map(|block| block.row_count).sum(),
+        block_count: blocks.len() as u64,
+        uncompressed_byte_size: blocks.iter().map(|block| block.block_size).sum(),
+        compressed_byte_size,
+        col_stats: util::column_stats_reduce_with_schema(&col_stats, schema)?,
+    })
+}
@@ -0,0 +1,238 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_dal::read_obj;
+use common_dal::DataAccessor;
+use common_dal::ObjectMeta;
+use common_exception::Result;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::configs::StorageConfig;
+use crate::datasources::common::cold_storage_config_with_options;
+use crate::datasources::common::storage_config_with_options;
+use crate::datasources::table::fuse::util;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_DEGRADED;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::SegmentInfo;
+use crate::datasources::table::fuse::TableSnapshot;
+use crate::sessions::DatabendQueryContext;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FsckIssue {
+    /// An object which no snapshot refers to, e.g. written by a failed commit.
+    /// `removed` if it is removed by the repair.
+    Orphan { location: String, removed: bool },
+    /// An object which the table refers to, but is gone from the storage.
+    Missing { location: String },
+}
+
+impl FuseTable {
+    /// Cross-checks the objects referred to by the snapshots against the listed ones.
+    ///
+    /// The tables of the same storage share the object prefixes, so an object is an orphan only
+    /// if none of them refers to it. With `repair`, the orphans older than `orphan_grace_secs`
+    /// are removed, the younger ones may belong to an ongoing append. The table is marked
+    /// degraded if it has missing objects, they can not be recovered from here.
+    pub async fn do_fsck(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        repair: bool,
+        orphan_grace_secs: u64,
+    ) -> Result<Vec<FsckIssue>> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+        let conf = ctx.get_config();
+        let options =
+            ctx.resolve_storage_connections(&self.table_info.db, &self.table_info.options)?;
+        let (hot, cold) = table_storages(&conf.storage, &options)?;
+
+        // The tables are read before listing, the objects they refer to are all listed.
+        // The ones committed in between are taken as orphans, which are too young to remove.
+        let catalog = ctx.get_catalog();
+        let mut tables = vec![];
+        for db in catalog.get_databases()? {
+            tables.extend(catalog.get_tables(db.name())?);
+        }
+
+        let da = self.get_data_accessor(&io_ctx)?;
+        let mut objects = HashMap::new();
+        for prefix in util::object_prefixes() {
+            for object in da.list(&prefix).await? {
+                objects.insert(object.path.clone(), object);
+            }
+        }
+
+        let mut referred = HashSet::new();
+        let mut missing = vec![];
+        for table in tables {
+            let fuse_table = match table.as_any().downcast_ref::<FuseTable>() {
+                Some(fuse_table) => fuse_table,
+                None => continue,
+            };
+
+            let table_info = &fuse_table.table_info;
+            let options = ctx.resolve_storage_connections(&table_info.db, &table_info.options)?;
+            let (table_hot, table_cold) = table_storages(&conf.storage, &options)?;
+            let shares = |s: &StorageConfig| *s == hot || cold.as_ref() == Some(s);
+            if !shares(&table_hot) && !table_cold.as_ref().map_or(false, shares) {
+                continue;
+            }
+
+            // An object is known to be missing only if its tier is listed.
+            let listed = Listed {
+                objects: &objects,
+                hot: table_hot == hot,
+                cold: table_cold.is_some() && table_cold == cold,
+            };
+            let table_da = fuse_table.get_data_accessor(&io_ctx)?;
+            let table_missing =
+                collect_referred(table_da, &listed, &table_info.options, &mut referred).await?;
+            if fuse_table.get_id() == self.get_id() {
+                missing = table_missing;
+            }
+        }
+
+        let now = util::unix_timestamp_secs();
+        let mut issues = vec![];
+        let mut orphans = objects
+            .into_values()
+            .filter(|object| !referred.contains(&object.path))
+            .collect::<Vec<_>>();
+        orphans.sort_by(|a, b| a.path.cmp(&b.path));
+        for object in orphans {
+            let removed = repair && is_older_than(&object, now, orphan_grace_secs);
+            if removed {
+                da.remove(&object.path).await?;
+            }
+            issues.push(FsckIssue::Orphan {
+                location: object.path,
+                removed,
+            });
+        }
+
+        if repair && !missing.is_empty() {
+            catalog.upsert_table_option(
+                self.get_id(),
+                self.table_info.version,
+                TBL_OPT_KEY_DEGRADED.to_string(),
+                format!(
+                    "{} objects missing, found by FSCK at {}",
+                    missing.len(),
+                    now
+                ),
+            )?;
+        }
+        issues.extend(
+            missing
+                .into_iter()
+                .map(|location| FsckIssue::Missing { location }),
+        );
+        Ok(issues)
+    }
+}
+
+// The hot storage and the cold one(if any) of a table.
+fn table_storages(
+    conf: &StorageConfig,
+    options: &HashMap<String, String>,
+) -> Result<(StorageConfig, Option<StorageConfig>)> {
+    let hot = storage_config_with_options(conf, options)?.unwrap_or_else(|| conf.clone());
+    let cold = cold_storage_config_with_options(&hot, options)?;
+    Ok((hot, cold))
+}
+
+struct Listed<'a> {
+    objects: &'a HashMap<String, ObjectMeta>,
+    hot: bool,
+    cold: bool,
+}
+
+impl Listed<'_> {
+    fn is_missing(&self, location: &str) -> bool {
+        let tier_listed = match util::is_cold_location(location) {
+            true => self.cold,
+            false => self.hot,
+        };
+        tier_listed && !self.objects.contains_key(location)
+    }
+}
+
+// Walks the snapshots of the table, from the current one to the oldest one, and returns the
+// referred locations which are missing.
+async fn collect_referred(
+    da: Arc<dyn DataAccessor>,
+    listed: &Listed<'_>,
+    options: &HashMap<String, String>,
+    referred: &mut HashSet<String>,
+) -> Result<Vec<String>> {
+    let mut missing = vec![];
+    let mut snapshot_loc = options.get(TBL_OPT_KEY_SNAPSHOT_LOC).cloned();
+    while let Some(loc) = snapshot_loc.take() {
+        if listed.is_missing(&loc) {
+            missing.push(loc);
+            break;
+        }
+
+        let snapshot: TableSnapshot = read_obj(da.clone(), loc.clone()).await?;
+        referred.insert(loc);
+        for seg_loc in &snapshot.segments {
+            // The snapshots share the segments.
+            if !referred.insert(seg_loc.clone()) {
+                continue;
+            }
+            // The hot copy of a segment moved to the cold tier is purged after a while, while the
+            // older snapshots still refer to it. The newer ones, walked before, refer to the cold
+            // copy.
+            let moved = !util::is_cold_location(seg_loc)
+                && referred.contains(&util::cold_location(seg_loc));
+            if listed.is_missing(seg_loc) {
+                if !moved {
+                    missing.push(seg_loc.clone());
+                }
+                continue;
+            }
+
+            let segment: SegmentInfo = read_obj(da.clone(), seg_loc.clone()).await?;
+            for block in segment.blocks {
+                let block_loc = block.location.location;
+                if listed.is_missing(&block_loc) && !moved {
+                    missing.push(block_loc.clone());
+                }
+                referred.insert(block_loc);
+            }
+        }
+
+        snapshot_loc = snapshot
+            .prev_snapshot_id
+            .map(|id| util::snapshot_location(id.to_simple().to_string().as_str()));
+    }
+    Ok(missing)
+}
+
+fn is_older_than(object: &ObjectMeta, now: u64, secs: u64) -> bool {
+    match object.last_modified {
+        Some(last_modified) => now.saturating_sub(last_modified) >= secs,
+        None => false,
+    }
+}
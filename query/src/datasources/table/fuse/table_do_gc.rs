@@ -0,0 +1,86 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_dal::read_obj;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::table::fuse::util;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::StagedSegment;
+use crate::sessions::DatabendQueryContext;
+
+impl FuseTable {
+    /// Cleans up the staged segments left by the abandoned commits, e.g. of a crashed node,
+    /// returns the number of the removed objects.
+    ///
+    /// The staged segments of this table and of the dropped tables are checked, if they are older
+    /// than `grace_secs`, the younger ones may belong to an ongoing commit. The staged segment of
+    /// a committed snapshot is published already, only the staged one is removed. Otherwise the
+    /// snapshot, the segment and the blocks written for it are removed too, nothing committed
+    /// refers to them.
+    /// The blocks written before the segment is staged are left to FSCK.
+    pub async fn do_gc_staged(&self, io_ctx: Arc<TableIOContext>, grace_secs: u64) -> Result<u64> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+        let catalog = ctx.get_catalog();
+        let da = self.get_data_accessor(&io_ctx)?;
+
+        let now = util::unix_timestamp_secs();
+        let mut removed = 0;
+        for object in da.list(&util::staged_prefix()).await? {
+            let expired = match object.last_modified {
+                Some(last_modified) => now.saturating_sub(last_modified) >= grace_secs,
+                None => false,
+            };
+            if !expired {
+                continue;
+            }
+
+            let staged: StagedSegment = read_obj(da.clone(), object.path.clone()).await?;
+            let committed = if staged.table_id == self.get_id() {
+                self.is_snapshot_committed(&io_ctx, &staged.snapshot_location)
+                    .await?
+            } else {
+                match catalog.get_table_by_id(staged.table_id, None) {
+                    // Left to the GC of its own table.
+                    Ok(_) => continue,
+                    // The purge of a dropped table only sees its committed snapshots.
+                    Err(cause) if cause.code() == ErrorCode::UnknownTable("").code() => false,
+                    Err(cause) => return Err(cause),
+                }
+            };
+
+            if !committed {
+                for block_loc in staged.written_blocks.iter() {
+                    da.remove(block_loc).await?;
+                }
+                da.remove(&staged.segment_location).await?;
+                da.remove(&staged.snapshot_location).await?;
+                removed += staged.written_blocks.len() as u64 + 2;
+            }
+            da.remove(&object.path).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
@@ -0,0 +1,74 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::columns::DataColumn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+
+use super::table_do_delete::BlockRewriter;
+use crate::datasources::table::fuse::FuseTable;
+
+/// Rewrites the blocks which have no such column, the column of such a block is read as its
+/// default.
+struct MaterializeRewriter {
+    column: usize,
+}
+
+impl BlockRewriter for MaterializeRewriter {
+    fn predicate(&self) -> Option<&Expression> {
+        None
+    }
+
+    fn rewrite(&mut self, block: &DataBlock) -> Result<(u64, Vec<DataBlock>)> {
+        // The columns in the file are read as arrays. The other columns missing in a rewritten
+        // block are written with their defaults too.
+        match block.column(self.column) {
+            DataColumn::Constant(_, _) => Ok((block.num_rows() as u64, vec![block.clone()])),
+            DataColumn::Array(_) => Ok((0, vec![])),
+        }
+    }
+
+    fn appended(&mut self) -> Result<Vec<DataBlock>> {
+        Ok(vec![])
+    }
+}
+
+impl FuseTable {
+    /// Writes the default of the column into the blocks written before the column was added,
+    /// returns the number of the rewritten rows.
+    pub async fn do_materialize_column(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        column: &str,
+    ) -> Result<u64> {
+        let (column, _) = self
+            .table_info
+            .schema
+            .column_with_name(column)
+            .ok_or_else(|| {
+                ErrorCode::BadArguments(format!(
+                    "Unknown column {} in table {}.{}",
+                    column, self.table_info.db, self.table_info.name
+                ))
+            })?;
+        let mut rewriter = MaterializeRewriter { column };
+        self.rewrite(io_ctx, &mut rewriter).await
+    }
+}
@@ -0,0 +1,337 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::series::Series;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::MergeMatchedAction;
+use common_planners::MergePlan;
+
+use super::table_do_delete::true_values;
+use super::table_do_delete::BlockRewriter;
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::FuseTable;
+use crate::pipelines::transforms::ExpressionExecutor;
+
+impl FuseTable {
+    /// Merges the rows of the source into the table by the keys of the plan, returns the
+    /// number of the updated, deleted and inserted rows.
+    ///
+    /// The source block is of the columns of the source of the plan, a row of the table may
+    /// match at most one row of the source. The changes are committed in one snapshot.
+    pub async fn do_merge(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        plan: &MergePlan,
+        source: DataBlock,
+    ) -> Result<u64> {
+        for expr in plan_exprs(plan) {
+            if io::has_subquery(expr)? {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Subqueries are not allowed in MERGE: {:?}",
+                    expr
+                )));
+            }
+        }
+
+        let mut rewriter = MergeRewriter::try_create(self.table_info.schema.clone(), plan, source)?;
+        self.rewrite(io_ctx, &mut rewriter).await
+    }
+}
+
+fn plan_exprs(plan: &MergePlan) -> Vec<&Expression> {
+    let mut exprs = vec![];
+    for (target_key, source_key) in plan.keys.iter() {
+        exprs.push(target_key);
+        exprs.push(source_key);
+    }
+    for clause in plan.matched.iter() {
+        exprs.extend(clause.condition.iter());
+        if let MergeMatchedAction::Update(assignments) = &clause.action {
+            exprs.extend(assignments.iter().map(|(_, expr)| expr));
+        }
+    }
+    for clause in plan.not_matched.iter() {
+        exprs.extend(clause.condition.iter());
+        exprs.extend(clause.values.iter());
+    }
+    exprs
+}
+
+fn create_executor(
+    description: &str,
+    schema: &DataSchemaRef,
+    exprs: Vec<Expression>,
+) -> Result<ExpressionExecutor> {
+    let fields = exprs
+        .iter()
+        .map(|expr| expr.to_data_field(schema))
+        .collect::<Result<Vec<_>>>()?;
+    let executor = ExpressionExecutor::try_create(
+        description,
+        schema.clone(),
+        DataSchemaRefExt::create(fields),
+        exprs,
+        false,
+    )?;
+    executor.validate()?;
+    Ok(executor)
+}
+
+/// Whether the rows satisfy the condition, all the rows do if there is no condition.
+fn satisfied(condition: &Option<ExpressionExecutor>, block: &DataBlock) -> Result<Vec<bool>> {
+    match condition {
+        None => Ok(vec![true; block.num_rows()]),
+        Some(executor) => true_values(&executor.execute(block)?.column(0).to_array()?),
+    }
+}
+
+/// The serialized keys of the rows, the rows with NULL keys match nothing.
+fn build_keys(executor: &ExpressionExecutor, block: &DataBlock) -> Result<Vec<Option<Vec<u8>>>> {
+    let keys = executor.execute(block)?;
+    let columns = keys.columns().iter().collect::<Vec<_>>();
+    let arrays = columns
+        .iter()
+        .map(|column| column.to_array())
+        .collect::<Result<Vec<_>>>()?;
+    let serialized = HashMethodSerializer::default().build_keys(&columns, block.num_rows())?;
+    Ok(serialized
+        .into_iter()
+        .enumerate()
+        .map(
+            |(row, key)| match arrays.iter().any(|array| array.is_null(row)) {
+                true => None,
+                false => Some(key),
+            },
+        )
+        .collect())
+}
+
+struct MergeRewriter {
+    schema: DataSchemaRef,
+    source: DataBlock,
+    /// The rows of the source by their keys.
+    source_rows: HashMap<Vec<u8>, Vec<u32>>,
+    /// Whether the rows of the source match some rows of the table.
+    source_matched: Vec<bool>,
+    target_keys: ExpressionExecutor,
+    /// The schema of the matched rows of the table, followed by the matched rows of the source.
+    merged_schema: DataSchemaRef,
+    /// The conditions of the matched clauses, with the executors computing the updated rows,
+    /// the matched rows are deleted if there is no executor.
+    matched: Vec<(Option<ExpressionExecutor>, Option<ExpressionExecutor>)>,
+    /// The conditions of the not matched clauses, with the executors computing the inserted
+    /// rows.
+    not_matched: Vec<(Option<ExpressionExecutor>, ExpressionExecutor)>,
+}
+
+impl MergeRewriter {
+    fn try_create(schema: DataSchemaRef, plan: &MergePlan, source: DataBlock) -> Result<Self> {
+        let source_schema = source.schema().clone();
+        let mut fields = schema.fields().clone();
+        fields.extend(source_schema.fields().iter().cloned());
+        let merged_schema = DataSchemaRefExt::create(fields);
+
+        let target_keys = plan.keys.iter().map(|(key, _)| key.clone()).collect();
+        let target_keys = create_executor("merge target key executor", &schema, target_keys)?;
+        let source_keys = plan.keys.iter().map(|(_, key)| key.clone()).collect();
+        let source_keys =
+            create_executor("merge source key executor", &source_schema, source_keys)?;
+        let mut source_rows: HashMap<Vec<u8>, Vec<u32>> = HashMap::new();
+        for (row, key) in build_keys(&source_keys, &source)?.into_iter().enumerate() {
+            if let Some(key) = key {
+                source_rows.entry(key).or_default().push(row as u32);
+            }
+        }
+
+        let condition = |condition: &Option<Expression>, schema: &DataSchemaRef| match condition {
+            None => Ok(None),
+            Some(expr) => {
+                create_executor("merge condition executor", schema, vec![expr.clone()]).map(Some)
+            }
+        };
+
+        let mut matched = Vec::with_capacity(plan.matched.len());
+        for clause in plan.matched.iter() {
+            let action = match &clause.action {
+                MergeMatchedAction::Delete => None,
+                MergeMatchedAction::Update(assignments) => {
+                    // The columns are computed by their positions, as those of `do_update`.
+                    let exprs = schema
+                        .fields()
+                        .iter()
+                        .map(|field| {
+                            match assignments.iter().find(|(name, _)| name == field.name()) {
+                                Some((_, expr)) => expr.clone(),
+                                None => Expression::Column(field.name().clone()),
+                            }
+                        })
+                        .collect();
+                    Some(create_executor(
+                        "merge update executor",
+                        &merged_schema,
+                        exprs,
+                    )?)
+                }
+            };
+            matched.push((condition(&clause.condition, &merged_schema)?, action));
+        }
+
+        let mut not_matched = Vec::with_capacity(plan.not_matched.len());
+        for clause in plan.not_matched.iter() {
+            let values = clause.values.clone();
+            not_matched.push((
+                condition(&clause.condition, &source_schema)?,
+                create_executor("merge insert executor", &source_schema, values)?,
+            ));
+        }
+
+        Ok(MergeRewriter {
+            schema,
+            source_matched: vec![false; source.num_rows()],
+            source,
+            source_rows,
+            target_keys,
+            merged_schema,
+            matched,
+            not_matched,
+        })
+    }
+
+    /// Applies the first clause satisfied by each of the rows, returns the rows computed by
+    /// the clauses, and whether the rows are taken by some clause.
+    fn apply<'a, I>(&self, block: &DataBlock, clauses: I) -> Result<(Vec<DataBlock>, Vec<bool>)>
+    where I: Iterator<
+            Item = (
+                &'a Option<ExpressionExecutor>,
+                Option<&'a ExpressionExecutor>,
+            ),
+        > {
+        let mut blocks = vec![];
+        let mut taken = vec![false; block.num_rows()];
+        for (condition, executor) in clauses {
+            let rows = satisfied(condition, block)?
+                .into_iter()
+                .zip(taken.iter())
+                .map(|(satisfied, taken)| satisfied && !taken)
+                .collect::<Vec<_>>();
+            if !rows.iter().any(|v| *v) {
+                continue;
+            }
+
+            if let Some(executor) = executor {
+                let rows = DataBlock::filter_block(block, Series::new(rows.clone()))?;
+                let computed = executor.execute(&rows)?;
+                blocks.push(DataBlock::create(
+                    self.schema.clone(),
+                    computed.columns().to_vec(),
+                ));
+            }
+            for (taken, row) in taken.iter_mut().zip(rows.into_iter()) {
+                *taken |= row;
+            }
+        }
+        Ok((blocks, taken))
+    }
+}
+
+impl BlockRewriter for MergeRewriter {
+    fn predicate(&self) -> Option<&Expression> {
+        None
+    }
+
+    fn rewrite(&mut self, block: &DataBlock) -> Result<(u64, Vec<DataBlock>)> {
+        let mut target_rows = vec![];
+        let mut source_rows = vec![];
+        for (row, key) in build_keys(&self.target_keys, block)?.iter().enumerate() {
+            let matched = match key {
+                Some(key) => self.source_rows.get(key),
+                None => None,
+            };
+            match matched.map(|rows| rows.as_slice()) {
+                None | Some([]) => {}
+                Some([source_row]) => {
+                    target_rows.push(row as u32);
+                    source_rows.push(*source_row);
+                    self.source_matched[*source_row as usize] = true;
+                }
+                Some(_) => {
+                    return Err(ErrorCode::BadArguments(
+                        "A row of the table matches more than one row of the MERGE source",
+                    ))
+                }
+            }
+        }
+        if target_rows.is_empty() || self.matched.is_empty() {
+            return Ok((0, vec![]));
+        }
+
+        let target = DataBlock::block_take_by_indices(block, &[], &target_rows)?;
+        let source = DataBlock::block_take_by_indices(&self.source, &[], &source_rows)?;
+        let mut columns = target.columns().to_vec();
+        columns.extend(source.columns().iter().cloned());
+        let merged = DataBlock::create(self.merged_schema.clone(), columns);
+
+        let clauses = self
+            .matched
+            .iter()
+            .map(|(condition, executor)| (condition, executor.as_ref()));
+        let (mut blocks, taken) = self.apply(&merged, clauses)?;
+        let changed = taken.iter().filter(|v| **v).count();
+        if changed == 0 {
+            return Ok((0, vec![]));
+        }
+
+        let mut keep = vec![true; block.num_rows()];
+        for (row, taken) in target_rows.iter().zip(taken.iter()) {
+            keep[*row as usize] = !taken;
+        }
+        if changed < block.num_rows() {
+            blocks.push(DataBlock::filter_block(block, Series::new(keep))?);
+        }
+        Ok((changed as u64, blocks))
+    }
+
+    fn appended(&mut self) -> Result<Vec<DataBlock>> {
+        let rows = self
+            .source_matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| !**matched)
+            .map(|(row, _)| row as u32)
+            .collect::<Vec<_>>();
+        if rows.is_empty() || self.not_matched.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let source = DataBlock::block_take_by_indices(&self.source, &[], &rows)?;
+        let clauses = self
+            .not_matched
+            .iter()
+            .map(|(condition, executor)| (condition, Some(executor)));
+        let (blocks, _) = self.apply(&source, clauses)?;
+        Ok(blocks)
+    }
+}
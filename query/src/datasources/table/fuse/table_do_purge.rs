@@ -0,0 +1,78 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use std::sync::Arc;
+
+use common_context::TableIOContext;
+use common_dal::read_obj;
+use common_dal::DataAccessor;
+use common_exception::Result;
+use common_management::PurgeTask;
+
+use crate::datasources::table::fuse::util;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::SegmentInfo;
+use crate::datasources::table::fuse::TableSnapshot;
+
+impl FuseTable {
+    /// Removes the objects of the dropped table, from the snapshot `task.snapshot_location` to
+    /// the oldest one. The progress is saved by `save_progress` before removing each snapshot,
+    /// so a failed task is resumed from where it stopped.
+    pub async fn do_purge(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        task: &mut PurgeTask,
+        mut save_progress: impl FnMut(&PurgeTask) -> Result<()> + Send,
+    ) -> Result<()> {
+        let da = self.get_data_accessor(&io_ctx)?;
+        while let Some(snapshot_loc) = task.snapshot_location.clone() {
+            // The chain ends at a missing snapshot.
+            if !object_exists(da.as_ref(), &snapshot_loc).await? {
+                task.snapshot_location = None;
+                save_progress(task)?;
+                break;
+            }
+
+            let snapshot: TableSnapshot = read_obj(da.clone(), snapshot_loc.clone()).await?;
+            for seg_loc in &snapshot.segments {
+                // The snapshots share the segments, the ones removed with a newer snapshot
+                // are skipped.
+                if !object_exists(da.as_ref(), seg_loc).await? {
+                    continue;
+                }
+
+                let segment: SegmentInfo = read_obj(da.clone(), seg_loc.clone()).await?;
+                for block in &segment.blocks {
+                    da.remove(&block.location.location).await?;
+                }
+                da.remove(seg_loc).await?;
+                task.removed_objects += segment.blocks.len() as u64 + 1;
+            }
+
+            // Saved before removing the snapshot, it is the only way to the older ones.
+            task.snapshot_location = snapshot
+                .prev_snapshot_id
+                .map(|id| util::snapshot_location(id.to_simple().to_string().as_str()));
+            task.removed_objects += 1;
+            save_progress(task)?;
+            da.remove(&snapshot_loc).await?;
+        }
+        Ok(())
+    }
+}
+
+pub(super) async fn object_exists(da: &dyn DataAccessor, path: &str) -> Result<bool> {
+    let objects = da.list(path).await?;
+    Ok(objects.iter().any(|object| object.path == path))
+}
@@ -17,13 +17,19 @@ use std::sync::Arc;
 
 use common_context::IOContext;
 use common_context::TableIOContext;
+use common_dal::read_obj;
+use common_dal::DataAccessor;
 use common_exception::Result;
 use common_planners::Extras;
 use common_streams::SendableDataBlockStream;
 use futures::StreamExt;
 
 use super::io;
+use super::util;
+use crate::datasources::common::TableStatistics;
+use crate::datasources::table::fuse::io::Prewhere;
 use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TableSnapshot;
 use crate::sessions::DatabendQueryContext;
 
 impl FuseTable {
@@ -53,6 +59,29 @@ impl FuseTable {
             default_proj()
         };
 
+        let da = self.get_data_accessor(io_ctx.as_ref())?;
+        let arrow_schema = self.table_info.schema.to_arrow();
+        let defaults = Arc::new(ColumnDefaults::from_options(&self.table_info.options)?);
+        let column_cache = ctx.get_sessions_manager().get_column_cache();
+        let settings = ctx.get_settings();
+        let auto_prewhere = settings.get_optimize_move_to_prewhere()? != 0;
+        // The sources of the scan share the I/O concurrency of the node, each of them reads its
+        // share of the blocks at a time.
+        let sources = settings.get_effective_scan_decode_parallelism()?.max(1);
+        let io_concurrency = settings.get_effective_scan_io_concurrency()?;
+        let io_concurrency = ((io_concurrency + sources - 1) / sources).max(1);
+        let virtual_columns = match push_downs {
+            Some(push_downs) => !push_downs.virtual_columns.is_empty(),
+            None => false,
+        };
+        let prewhere = match push_downs {
+            Some(push_downs) => {
+                self.prewhere(&da, &projection, push_downs, auto_prewhere)
+                    .await?
+            }
+            None => None,
+        };
+
         // TODO we need a configuration to specify the unit of dequeue operation
         let bite_size = 1;
         let iter = {
@@ -63,13 +92,83 @@ impl FuseTable {
             })
             .flatten()
         };
-        let da = io_ctx.get_data_accessor()?;
-        let arrow_schema = self.table_info.schema.to_arrow();
 
         let stream = futures::stream::iter(iter);
-        let stream = stream.then(move |part| {
-            io::do_read(part, da.clone(), projection.clone(), arrow_schema.clone())
+        let stream = stream.map(move |part| {
+            let da = da.clone();
+            let projection = projection.clone();
+            let arrow_schema = arrow_schema.clone();
+            let defaults = defaults.clone();
+            let column_cache = column_cache.clone();
+            let prewhere = prewhere.clone();
+            async move {
+                match prewhere {
+                    Some(prewhere) => {
+                        io::do_read_with_prewhere(
+                            part,
+                            da,
+                            projection,
+                            arrow_schema,
+                            defaults,
+                            column_cache,
+                            prewhere,
+                            virtual_columns,
+                        )
+                        .await
+                    }
+                    None if virtual_columns => {
+                        let block = io::do_read(
+                            part.clone(),
+                            da,
+                            projection,
+                            arrow_schema,
+                            defaults,
+                            column_cache,
+                        )
+                        .await?;
+                        io::with_virtual_columns(block, &part)
+                    }
+                    None => {
+                        io::do_read(part, da, projection, arrow_schema, defaults, column_cache)
+                            .await
+                    }
+                }
+            }
         });
-        Ok(Box::pin(stream))
+        Ok(Box::pin(stream.buffered(io_concurrency)))
+    }
+
+    /// The explicit PREWHERE conditions, or the ones picked from the WHERE conditions by the
+    /// column statistics of the current snapshot, if `auto_prewhere` is enabled.
+    async fn prewhere(
+        &self,
+        da: &Arc<dyn DataAccessor>,
+        projection: &[usize],
+        push_downs: &Extras,
+        auto_prewhere: bool,
+    ) -> Result<Option<Arc<Prewhere>>> {
+        let schema = &self.table_info.schema;
+        if !push_downs.prewhere.is_empty() {
+            let prewhere = Prewhere::try_create(schema, projection, push_downs, None, None)?;
+            return Ok(prewhere.map(Arc::new));
+        }
+
+        let snapshot_loc = self.table_info.options.get(util::TBL_OPT_KEY_SNAPSHOT_LOC);
+        match snapshot_loc {
+            Some(loc) if auto_prewhere && !push_downs.filters.is_empty() => {
+                let snapshot: TableSnapshot = read_obj(da.clone(), loc.clone()).await?;
+                let col_stats = Some(&snapshot.summary.col_stats);
+                let table_stats = TableStatistics::from_options(&self.table_info.options)?;
+                let prewhere = Prewhere::try_create(
+                    schema,
+                    projection,
+                    push_downs,
+                    col_stats,
+                    table_stats.as_ref(),
+                )?;
+                Ok(prewhere.map(Arc::new))
+            }
+            _ => Ok(None),
+        }
     }
 }
@@ -33,9 +33,10 @@ impl FuseTable {
     ) -> Result<(Statistics, Partitions)> {
         let tbl_snapshot = self.table_snapshot(io_ctx)?;
         if let Some(snapshot) = tbl_snapshot {
-            let da = io_ctx.get_data_accessor()?;
+            let da = self.get_data_accessor(io_ctx)?;
             let meta_reader = MetaInfoReader::new(da, io_ctx.get_runtime());
-            let block_metas = util::range_filter(&snapshot, &push_downs, meta_reader)?;
+            let block_metas =
+                util::range_filter(&snapshot, &self.table_info.schema, &push_downs, meta_reader)?;
             let (statistics, parts) = self.to_partitions(&block_metas);
             Ok((statistics, parts))
         } else {
@@ -23,6 +23,7 @@ use uuid::Uuid;
 
 use crate::catalogs::Catalog;
 use crate::catalogs::Table;
+use crate::datasources::common::count_table_changed_rows;
 use crate::datasources::table::fuse::util;
 use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
 use crate::datasources::table::fuse::FuseTable;
@@ -37,6 +38,7 @@ impl FuseTable {
     ) -> Result<()> {
         if let Some(prev_snapshot) = self.table_snapshot(&io_ctx)? {
             let prev_id = prev_snapshot.snapshot_id;
+            let prev_rows = prev_snapshot.summary.row_count;
             let mut new_snapshot = prev_snapshot;
             new_snapshot.segments = vec![];
             new_snapshot.prev_snapshot_id = Some(prev_id);
@@ -47,7 +49,7 @@ impl FuseTable {
             new_snapshot.snapshot_id = Uuid::new_v4();
             let new_snapshot_loc =
                 util::snapshot_location(new_snapshot.snapshot_id.to_simple().to_string().as_str()); // TODO refine this
-            let da = io_ctx.get_data_accessor()?;
+            let da = self.get_data_accessor(io_ctx.as_ref())?;
             let bytes = serde_json::to_vec(&new_snapshot)?;
             da.put(&new_snapshot_loc, bytes).await?;
 
@@ -59,7 +61,8 @@ impl FuseTable {
                 self.table_info.version,
                 TBL_OPT_KEY_SNAPSHOT_LOC.to_string(),
                 new_snapshot_loc,
-            )?
+            )?;
+            count_table_changed_rows(&io_ctx, &self.table_info, prev_rows);
         }
 
         Ok(())
@@ -0,0 +1,83 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_context::TableIOContext;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::lit;
+use common_planners::Expression;
+
+use super::table_do_delete::Mutation;
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::FuseTable;
+use crate::pipelines::transforms::ExpressionExecutor;
+
+impl FuseTable {
+    /// Sets the columns of the rows satisfying the predicate, or of all the rows if there is no
+    /// predicate, to the values of the assigned expressions, evaluated on the rows before the
+    /// update. Returns the number of the updated rows.
+    ///
+    /// The assigned expressions are expected to be of the types of the columns.
+    pub async fn do_update(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        assignments: &[(String, Expression)],
+        predicate: &Option<Expression>,
+    ) -> Result<u64> {
+        let predicate = predicate.clone().unwrap_or_else(|| lit(true));
+        for expr in assignments
+            .iter()
+            .map(|(_, expr)| expr)
+            .chain(std::iter::once(&predicate))
+        {
+            if io::has_subquery(expr)? {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Subqueries are not allowed in UPDATE: {:?}",
+                    expr
+                )));
+            }
+        }
+
+        // The columns are computed by their positions, the assigned expressions may be of the
+        // same names as the columns, or of each other.
+        let schema = self.table_info.schema.clone();
+        let mut exprs = Vec::with_capacity(schema.fields().len());
+        for field in schema.fields() {
+            let assigned = assignments.iter().find(|(name, _)| name == field.name());
+            exprs.push(match assigned {
+                Some((_, expr)) => expr.clone(),
+                None => Expression::Column(field.name().clone()),
+            });
+        }
+        let fields = exprs
+            .iter()
+            .map(|expr| expr.to_data_field(&schema))
+            .collect::<Result<Vec<_>>>()?;
+        let executor = ExpressionExecutor::try_create(
+            "update expression executor",
+            schema.clone(),
+            DataSchemaRefExt::create(fields),
+            exprs,
+            false,
+        )?;
+        executor.validate()?;
+
+        self.mutate(io_ctx, &predicate, Mutation::Update(&executor))
+            .await
+    }
+}
@@ -13,16 +13,41 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_base::tokio;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_management::PurgeTask;
+use common_planners::add;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::CreateDatabasePlan;
+use common_planners::Expression;
+use common_planners::Extras;
 use common_planners::TruncateTablePlan;
+use common_planners::VIRTUAL_COLUMN_BLOCK_PATH;
+use common_planners::VIRTUAL_COLUMN_ROW_ID;
 use futures::TryStreamExt;
 
 use crate::catalogs::Catalog;
 use crate::catalogs::ToReadDataSourcePlan;
+use crate::configs::FaultInjectionConfig;
+use crate::datasources::common::COLD_STORAGE_OPT_KEY_PREFIX;
+use crate::datasources::common::STORAGE_OPT_KEY_DISK_DATA_PATH;
 use crate::datasources::table::fuse::table_test_fixture::TestFixture;
+use crate::datasources::table::fuse::util::SkippingIndex;
+use crate::datasources::table::fuse::util::SkippingIndexes;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_DEGRADED;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_HOT_TO_COLD_AFTER;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SKIPPING_INDEXES;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::datasources::table::fuse::FsckIssue;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::StagedSegment;
 
 #[tokio::test]
 async fn test_fuse_table_simple_case() -> Result<()> {
@@ -76,6 +101,623 @@ async fn test_fuse_table_simple_case() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_fuse_table_virtual_columns() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+    let catalog = ctx.get_catalog();
+    catalog.create_table(TestFixture::default_crate_table_plan())?;
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 2);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+
+    // The row ids are the positions in the blocks, also with PREWHERE filtering the rows.
+    for prewhere in [vec![], vec![col("id").gt(lit(1i32))]] {
+        let push_downs = Some(Extras {
+            prewhere: prewhere.clone(),
+            virtual_columns: vec![VIRTUAL_COLUMN_ROW_ID.to_string()],
+            ..Extras::default()
+        });
+        let source_plan = table.read_plan(io_ctx.clone(), push_downs, None)?;
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+
+        let stream = table.read(io_ctx.clone(), &source_plan.push_downs).await?;
+        let blocks = stream.try_collect::<Vec<_>>().await?;
+        let mut block_paths = vec![];
+        for block in blocks.iter().filter(|block| !block.is_empty()) {
+            let ids = block.try_column_by_name("id")?.to_values()?;
+            let row_ids = block
+                .try_column_by_name(VIRTUAL_COLUMN_ROW_ID)?
+                .to_values()?;
+            for (id, row_id) in ids.iter().zip(row_ids.iter()) {
+                // The ids of the blocks are 1, 2, 3.
+                let id = id.as_i64()? as u64;
+                assert_eq!(row_id, &DataValue::UInt64(Some(id - 1)));
+            }
+            block_paths.push(
+                block
+                    .try_column_by_name(VIRTUAL_COLUMN_BLOCK_PATH)?
+                    .try_get(0)?,
+            );
+        }
+        block_paths.dedup();
+        assert_eq!(block_paths.len(), 2);
+
+        let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+        assert_eq!(rows, if prewhere.is_empty() { 6 } else { 4 });
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_append_with_storage_faults() -> Result<()> {
+    let fixture = TestFixture::with_fault_injection(FaultInjectionConfig {
+        fault_targets: "dal".to_string(),
+        fault_error_rate: 1.0,
+        ..FaultInjectionConfig::default()
+    });
+    let ctx = fixture.ctx();
+
+    let crate_table_plan = TestFixture::default_crate_table_plan();
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+
+    // the blocks can not be written, nothing is committed
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 10);
+    let prev_version = table.get_table_info().version;
+    let r = table.append_data(io_ctx, insert_into_plan).await;
+    assert_eq!(
+        ErrorCode::DALTransportError("").code(),
+        r.unwrap_err().code()
+    );
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    assert_eq!(prev_version, table.get_table_info().version);
+
+    let injected = ctx.get_dal_fault_injector().unwrap().injected();
+    assert!(!injected.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_concurrent_append() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    let crate_table_plan = TestFixture::default_crate_table_plan();
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    // both of the appends are based on the same version of the table
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    for _ in 0..2 {
+        let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 10);
+        table.append_data(io_ctx.clone(), insert_into_plan).await?;
+    }
+
+    // the later one is rebased onto the snapshot committed by the former one
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let (stats, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(parts.len(), 2 * 10);
+    assert_eq!(stats.read_rows, 2 * 10 * 3);
+
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let snapshot = fuse_table.table_snapshot(&io_ctx)?.unwrap();
+    assert_eq!(snapshot.summary.row_count, 2 * 10 * 3);
+    assert!(snapshot.prev_snapshot_id.is_some());
+
+    // an append based on the version before the truncation keeps only its own blocks
+    let truncate_plan = TruncateTablePlan {
+        db: "".to_string(),
+        table: "".to_string(),
+    };
+    table.truncate(io_ctx.clone(), truncate_plan).await?;
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 5);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let (stats, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(parts.len(), 5);
+    assert_eq!(stats.read_rows, 5 * 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_delete() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    let crate_table_plan = TestFixture::default_crate_table_plan();
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 10);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+
+    let latest_table = || {
+        catalog.get_table(
+            TestFixture::default_db().as_str(),
+            TestFixture::default_table().as_str(),
+        )
+    };
+
+    // the blocks are rewritten without the deleted rows
+    let table = latest_table()?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let deleted = fuse_table
+        .do_delete(io_ctx.clone(), &Some(col("id").eq(lit(2i32))))
+        .await?;
+    assert_eq!(deleted, 10);
+
+    let table = latest_table()?;
+    let (stats, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(parts.len(), 10);
+    assert_eq!(stats.read_rows, 10 * 2);
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx.clone(), &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let ids = blocks
+        .iter()
+        .map(|block| {
+            block
+                .try_column_by_name("id")?
+                .to_array()?
+                .i32()?
+                .collect_values()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    assert!(ids
+        .into_iter()
+        .flatten()
+        .all(|id| id == Some(1) || id == Some(3)));
+
+    // nothing matches, the blocks are pruned by their statistics and no snapshot is committed
+    let prev_version = table.get_table_info().version;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let deleted = fuse_table
+        .do_delete(io_ctx.clone(), &Some(col("id").gt(lit(100i32))))
+        .await?;
+    assert_eq!(deleted, 0);
+    assert_eq!(prev_version, latest_table()?.get_table_info().version);
+
+    // the blocks without rows left are removed
+    let deleted = fuse_table
+        .do_delete(
+            io_ctx.clone(),
+            &Some(col("id").eq(lit(1i32)).or(col("id").eq(lit(3i32)))),
+        )
+        .await?;
+    assert_eq!(deleted, 20);
+
+    let table = latest_table()?;
+    let (stats, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(parts.len(), 0);
+    assert_eq!(stats.read_rows, 0);
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let snapshot = fuse_table.table_snapshot(&io_ctx)?.unwrap();
+    assert!(snapshot.segments.is_empty());
+    assert_eq!(snapshot.summary.row_count, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_delete_segments_concurrently() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+    ctx.get_settings().set_max_threads(3)?;
+
+    let crate_table_plan = TestFixture::default_crate_table_plan();
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let latest_table = || {
+        catalog.get_table(
+            TestFixture::default_db().as_str(),
+            TestFixture::default_table().as_str(),
+        )
+    };
+
+    // each append adds a segment of 2 blocks
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    for _ in 0..5 {
+        let table = latest_table()?;
+        let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 2);
+        table.append_data(io_ctx.clone(), insert_into_plan).await?;
+    }
+
+    let table = latest_table()?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let prev_segments = fuse_table.table_snapshot(&io_ctx)?.unwrap().segments;
+    assert_eq!(prev_segments.len(), 5);
+    let deleted = fuse_table
+        .do_delete(io_ctx.clone(), &Some(col("id").eq(lit(2i32))))
+        .await?;
+    assert_eq!(deleted, 10);
+
+    // all the segments are rewritten, and none of them is lost
+    let table = latest_table()?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let snapshot = fuse_table.table_snapshot(&io_ctx)?.unwrap();
+    assert_eq!(snapshot.segments.len(), 5);
+    assert!(snapshot.segments.iter().all(|s| !prev_segments.contains(s)));
+    assert_eq!(snapshot.summary.row_count, 20);
+    assert_eq!(snapshot.summary.block_count, 10);
+
+    // the segments without rows left are removed
+    let deleted = fuse_table
+        .do_delete(
+            io_ctx.clone(),
+            &Some(col("id").eq(lit(1i32)).or(col("id").eq(lit(3i32)))),
+        )
+        .await?;
+    assert_eq!(deleted, 20);
+    let table = latest_table()?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let snapshot = fuse_table.table_snapshot(&io_ctx)?.unwrap();
+    assert!(snapshot.segments.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_concurrent_delete() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    let crate_table_plan = TestFixture::default_crate_table_plan();
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 10);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+
+    let latest_table = || {
+        catalog.get_table(
+            TestFixture::default_db().as_str(),
+            TestFixture::default_table().as_str(),
+        )
+    };
+
+    // the version is bumped by another option, the snapshot is still the current one
+    let table = latest_table()?;
+    let table_info = table.get_table_info();
+    catalog.upsert_table_option(
+        table_info.table_id,
+        table_info.version,
+        "k".to_string(),
+        "v".to_string(),
+    )?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let deleted = fuse_table
+        .do_delete(io_ctx.clone(), &Some(col("id").eq(lit(2i32))))
+        .await?;
+    assert_eq!(deleted, 10);
+
+    let table = latest_table()?;
+    let (stats, _) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(stats.read_rows, 10 * 2);
+    assert_eq!(
+        table.get_table_info().options.get("k"),
+        Some(&"v".to_string())
+    );
+
+    // the snapshot is replaced by another delete, the stale one can not be committed
+    let stale_table = latest_table()?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    fuse_table
+        .do_delete(io_ctx.clone(), &Some(col("id").eq(lit(1i32))))
+        .await?;
+    let fuse_table = stale_table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let result = fuse_table
+        .do_delete(io_ctx.clone(), &Some(col("id").eq(lit(3i32))))
+        .await;
+    assert_eq!(
+        result.unwrap_err().code(),
+        ErrorCode::CommitTableError("").code()
+    );
+
+    let table = latest_table()?;
+    let (stats, _) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(stats.read_rows, 10);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_skipping_index() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    let index_expr = add(col("id"), lit(1i32));
+    let indexes = SkippingIndexes {
+        indexes: vec![SkippingIndex {
+            name: "idx1".to_string(),
+            expr: index_expr.clone(),
+            inverted: false,
+        }],
+    };
+    let mut crate_table_plan = TestFixture::default_crate_table_plan();
+    crate_table_plan.options.insert(
+        TBL_OPT_KEY_SKIPPING_INDEXES.to_string(),
+        indexes.to_option()?,
+    );
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 10);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let read_parts = |filter: Expression| {
+        let push_downs = Some(Extras {
+            filters: vec![filter],
+            ..Extras::default()
+        });
+        table
+            .read_partitions(io_ctx.clone(), push_downs, None)
+            .map(|(_, parts)| parts.len())
+    };
+
+    // the values of the blocks are 1, 2 and 3
+    assert_eq!(read_parts(index_expr.eq(lit(3i32)))?, 10);
+    assert_eq!(read_parts(index_expr.gt(lit(4i32)))?, 0);
+    assert_eq!(read_parts(lit(2i32).gt(index_expr.clone()))?, 0);
+    assert_eq!(read_parts(col("id").gt(lit(100i32)))?, 0);
+    // no values of the expressions not indexed
+    let other_expr = add(col("id"), lit(2i32));
+    assert_eq!(read_parts(other_expr.gt(lit(100i32)))?, 10);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_update() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    let crate_table_plan = TestFixture::default_crate_table_plan();
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let table = catalog.get_table(
+        TestFixture::default_db().as_str(),
+        TestFixture::default_table().as_str(),
+    )?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 10);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+
+    let latest_table = || {
+        catalog.get_table(
+            TestFixture::default_db().as_str(),
+            TestFixture::default_table().as_str(),
+        )
+    };
+
+    // the matched rows of each block are rewritten to a block of their own
+    let table = latest_table()?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let value = Expression::Cast {
+        expr: Box::new(Expression::BinaryExpression {
+            op: "+".to_string(),
+            left: Box::new(col("id")),
+            right: Box::new(lit(18i32)),
+        }),
+        data_type: DataType::Int32,
+        is_try: false,
+    };
+    let updated = fuse_table
+        .do_update(
+            io_ctx.clone(),
+            &[("id".to_string(), value)],
+            &Some(col("id").eq(lit(2i32))),
+        )
+        .await?;
+    assert_eq!(updated, 10);
+
+    let table = latest_table()?;
+    let (stats, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(parts.len(), 20);
+    assert_eq!(stats.read_rows, 10 * 3);
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx.clone(), &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let mut ids = blocks
+        .iter()
+        .map(|block| {
+            block
+                .try_column_by_name("id")?
+                .to_array()?
+                .i32()?
+                .collect_values()
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids, vec![Some(1), Some(3), Some(20)]);
+
+    // nothing matches, no snapshot is committed
+    let prev_version = table.get_table_info().version;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let updated = fuse_table
+        .do_update(
+            io_ctx.clone(),
+            &[("id".to_string(), lit(0i32))],
+            &Some(col("id").eq(lit(2i32))),
+        )
+        .await?;
+    assert_eq!(updated, 0);
+    assert_eq!(prev_version, latest_table()?.get_table_info().version);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_with_storage_options() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    // the tables of the database live in their own storage, under the data path of the server
+    let cold_dir = tempfile::TempDir::new_in(&ctx.get_config().storage.disk.data_path)?;
+    let cold_path = cold_dir.path().to_str().unwrap().to_string();
+    let mut options = HashMap::new();
+    options.insert(STORAGE_OPT_KEY_DISK_DATA_PATH.to_string(), cold_path);
+    let catalog = ctx.get_catalog();
+    catalog.create_database(CreateDatabasePlan {
+        if_not_exists: false,
+        db: "cold".to_string(),
+        options,
+        tables: vec![],
+    })?;
+
+    let mut crate_table_plan = TestFixture::default_crate_table_plan();
+    crate_table_plan.db = "cold".to_string();
+    catalog.create_table(crate_table_plan)?;
+
+    let table = catalog.get_table("cold", TestFixture::default_table().as_str())?;
+    assert!(table
+        .get_table_info()
+        .options
+        .contains_key(STORAGE_OPT_KEY_DISK_DATA_PATH));
+
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 2);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+    assert!(std::fs::read_dir(cold_dir.path())?.next().is_some());
+
+    let table = catalog.get_table("cold", TestFixture::default_table().as_str())?;
+    let (stats, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(parts.len(), 2);
+    assert_eq!(stats.read_rows, 2 * 3);
+
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx, &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 2 * 3);
+
+    // unknown storage options are rejected
+    let mut crate_table_plan = TestFixture::default_crate_table_plan();
+    crate_table_plan.table = "bad_tbl".to_string();
+    crate_table_plan
+        .options
+        .insert("storage_s3_endpoint".to_string(), "".to_string());
+    let r = catalog.create_table(crate_table_plan);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_apply_storage_policy() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    let cold_dir = tempfile::TempDir::new_in(&ctx.get_config().storage.disk.data_path)?;
+    let cold_path = cold_dir.path().to_str().unwrap().to_string();
+    let mut crate_table_plan = TestFixture::default_crate_table_plan();
+    crate_table_plan.options.insert(
+        format!(
+            "{}{}",
+            COLD_STORAGE_OPT_KEY_PREFIX, STORAGE_OPT_KEY_DISK_DATA_PATH
+        ),
+        cold_path,
+    );
+    crate_table_plan
+        .options
+        .insert(TBL_OPT_KEY_HOT_TO_COLD_AFTER.to_string(), "0s".to_string());
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let db = TestFixture::default_db();
+    let tbl = TestFixture::default_table();
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 2);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+    assert!(std::fs::read_dir(cold_dir.path())?.next().is_none());
+
+    // the segments are moved to the cold tier
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let moved = fuse_table.do_apply_storage_policy(io_ctx.clone()).await?;
+    assert_eq!(moved, 1);
+    assert!(std::fs::read_dir(cold_dir.path())?.next().is_some());
+
+    // nothing left to move
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let moved = fuse_table.do_apply_storage_policy(io_ctx.clone()).await?;
+    assert_eq!(moved, 0);
+
+    // the hot copies are purged after the grace period, the segment and its 2 blocks
+    let purged = fuse_table.do_purge_hot_copies(io_ctx.clone(), 3600).await?;
+    assert_eq!(purged, 0);
+    let purged = fuse_table.do_purge_hot_copies(io_ctx.clone(), 0).await?;
+    assert_eq!(purged, 3);
+    let purged = fuse_table.do_purge_hot_copies(io_ctx.clone(), 0).await?;
+    assert_eq!(purged, 0);
+
+    // the cold data is still queryable
+    let (stats, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    assert_eq!(parts.len(), 2);
+    assert_eq!(stats.read_rows, 2 * 3);
+
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx, &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 2 * 3);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_fuse_table_truncate() -> Result<()> {
     let fixture = TestFixture::new();
@@ -149,3 +791,239 @@ async fn test_fuse_table_truncate() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fuse_table_purge() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    // the table lives in its own storage, nothing is left in it once purged
+    let data_dir = tempfile::TempDir::new_in(&ctx.get_config().storage.disk.data_path)?;
+    let data_path = data_dir.path().to_str().unwrap().to_string();
+    let mut crate_table_plan = TestFixture::default_crate_table_plan();
+    crate_table_plan
+        .options
+        .insert(STORAGE_OPT_KEY_DISK_DATA_PATH.to_string(), data_path);
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let db = TestFixture::default_db();
+    let tbl = TestFixture::default_table();
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+
+    // 2 + 3 blocks in 2 segments, 3 snapshots with the truncated one
+    for block_num in vec![2, 3] {
+        let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+        let insert_into_plan =
+            TestFixture::insert_plan_for_default_table(table.as_ref(), block_num);
+        table.append_data(io_ctx.clone(), insert_into_plan).await?;
+    }
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    table
+        .truncate(io_ctx.clone(), TruncateTablePlan {
+            db: db.clone(),
+            table: tbl.clone(),
+        })
+        .await?;
+
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let da = fuse_table.get_data_accessor(&io_ctx)?;
+    assert_eq!(da.list("").await?.len(), 10);
+
+    let table_info = table.get_table_info();
+    let mut task = PurgeTask {
+        db: db.clone(),
+        table: tbl.clone(),
+        table_id: table_info.table_id,
+        table_options: table_info.options.clone(),
+        snapshot_location: table_info.options.get(TBL_OPT_KEY_SNAPSHOT_LOC).cloned(),
+        ..Default::default()
+    };
+    let mut saved = vec![];
+    fuse_table
+        .do_purge(io_ctx.clone(), &mut task, |progress| {
+            saved.push(progress.clone());
+            Ok(())
+        })
+        .await?;
+
+    assert!(task.is_finished());
+    assert_eq!(task.removed_objects, 10);
+    assert_eq!(saved.len(), 3);
+    assert_eq!(saved.last(), Some(&task));
+    assert!(da.list("").await?.is_empty());
+
+    // purging again is a no-op
+    fuse_table.do_purge(io_ctx, &mut task, |_| Ok(())).await?;
+    assert_eq!(task.removed_objects, 10);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_fsck() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    // the table lives in its own storage, no other table refers to the objects in it
+    let data_dir = tempfile::TempDir::new_in(&ctx.get_config().storage.disk.data_path)?;
+    let data_path = data_dir.path().to_str().unwrap().to_string();
+    let mut crate_table_plan = TestFixture::default_crate_table_plan();
+    crate_table_plan
+        .options
+        .insert(STORAGE_OPT_KEY_DISK_DATA_PATH.to_string(), data_path);
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let db = TestFixture::default_db();
+    let tbl = TestFixture::default_table();
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 2);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    assert!(fuse_table
+        .do_fsck(io_ctx.clone(), false, 0)
+        .await?
+        .is_empty());
+
+    // a block left by a failed commit, and a block lost
+    let da = fuse_table.get_data_accessor(&io_ctx)?;
+    let lost = da.list("_b/").await?[0].path.clone();
+    da.remove(&lost).await?;
+    let orphan = "_b/orphan.parquet".to_string();
+    da.put(&orphan, b"orphan".to_vec()).await?;
+
+    let issues = fuse_table.do_fsck(io_ctx.clone(), false, 0).await?;
+    assert_eq!(issues, vec![
+        FsckIssue::Orphan {
+            location: orphan.clone(),
+            removed: false,
+        },
+        FsckIssue::Missing {
+            location: lost.clone(),
+        },
+    ]);
+    assert!(table
+        .get_table_info()
+        .options
+        .get(TBL_OPT_KEY_DEGRADED)
+        .is_none());
+
+    // the orphan is too young to be removed
+    let issues = fuse_table.do_fsck(io_ctx.clone(), true, 3600).await?;
+    assert_eq!(issues[0], FsckIssue::Orphan {
+        location: orphan.clone(),
+        removed: false,
+    });
+    assert_eq!(da.list(&orphan).await?.len(), 1);
+
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    assert!(table
+        .get_table_info()
+        .options
+        .get(TBL_OPT_KEY_DEGRADED)
+        .is_some());
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let issues = fuse_table.do_fsck(io_ctx.clone(), true, 0).await?;
+    assert_eq!(issues[0], FsckIssue::Orphan {
+        location: orphan.clone(),
+        removed: true,
+    });
+    assert!(da.list(&orphan).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_gc_staged() -> Result<()> {
+    let fixture = TestFixture::new();
+    let ctx = fixture.ctx();
+
+    // the table lives in its own storage
+    let data_dir = tempfile::TempDir::new_in(&ctx.get_config().storage.disk.data_path)?;
+    let data_path = data_dir.path().to_str().unwrap().to_string();
+    let mut crate_table_plan = TestFixture::default_crate_table_plan();
+    crate_table_plan
+        .options
+        .insert(STORAGE_OPT_KEY_DISK_DATA_PATH.to_string(), data_path);
+    let catalog = ctx.get_catalog();
+    catalog.create_table(crate_table_plan)?;
+
+    let db = TestFixture::default_db();
+    let tbl = TestFixture::default_table();
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let insert_into_plan = TestFixture::insert_plan_for_default_table(table.as_ref(), 2);
+    table.append_data(io_ctx.clone(), insert_into_plan).await?;
+
+    // nothing is left staged by a commit
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let da = fuse_table.get_data_accessor(&io_ctx)?;
+    assert!(da.list("_stg/").await?.is_empty());
+    assert_eq!(da.list("_sg/").await?.len(), 1);
+
+    // an abandoned commit, its segment is only staged
+    let blocks = TestFixture::gen_block_stream(3);
+    let stream = Box::pin(futures::stream::iter(blocks.into_iter().map(Ok)));
+    let (abandoned_loc, _) = fuse_table.do_append_uncommitted(&io_ctx, stream).await?;
+    assert_eq!(da.list("_stg/").await?.len(), 1);
+    assert_eq!(da.list("_sg/").await?.len(), 1);
+    assert_eq!(da.list("_b/").await?.len(), 5);
+
+    // a committed one, whose staged segment is failed to remove
+    let blocks = TestFixture::gen_block_stream(1);
+    let stream = Box::pin(futures::stream::iter(blocks.into_iter().map(Ok)));
+    let (snapshot_loc, rows) = fuse_table.do_append_uncommitted(&io_ctx, stream).await?;
+    let staged = da
+        .list("_stg/")
+        .await?
+        .into_iter()
+        .map(|object| object.path)
+        .collect::<Vec<_>>();
+    let mut left = None;
+    for loc in staged {
+        let bytes = da.read(&loc).await?;
+        let staged: StagedSegment = serde_json::from_slice(&bytes)?;
+        if staged.snapshot_location == snapshot_loc {
+            left = Some((loc, bytes));
+        }
+    }
+    let (left_loc, left_bytes) = left.unwrap();
+    fuse_table
+        .do_commit(&io_ctx, snapshot_loc.clone(), rows)
+        .await?;
+    assert_eq!(da.list("_stg/").await?.len(), 1);
+    da.put(&left_loc, left_bytes).await?;
+
+    // too young to clean up
+    let table = catalog.get_table(db.as_str(), tbl.as_str())?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    assert_eq!(fuse_table.do_gc_staged(io_ctx.clone(), 3600).await?, 0);
+    assert_eq!(da.list("_stg/").await?.len(), 2);
+
+    // the abandoned snapshot, segment and 3 blocks are removed with the staged segments
+    assert_eq!(fuse_table.do_gc_staged(io_ctx.clone(), 0).await?, 7);
+    assert!(da.list("_stg/").await?.is_empty());
+    assert!(da.list(&abandoned_loc).await?.is_empty());
+    assert_eq!(da.list("_sg/").await?.len(), 2);
+    assert_eq!(da.list("_b/").await?.len(), 3);
+    assert!(
+        fuse_table
+            .is_snapshot_committed(&io_ctx, &snapshot_loc)
+            .await?
+    );
+
+    let (_, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx, &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 3 * 3);
+
+    Ok(())
+}
@@ -29,6 +29,7 @@ use tempfile::TempDir;
 
 use crate::catalogs::Table;
 use crate::configs::Config;
+use crate::configs::FaultInjectionConfig;
 use crate::sessions::DatabendQueryContextRef;
 
 pub struct TestFixture {
@@ -38,8 +39,14 @@ pub struct TestFixture {
 
 impl TestFixture {
     pub fn new() -> TestFixture {
+        Self::with_fault_injection(FaultInjectionConfig::default())
+    }
+
+    /// The calls to the storage fail as configured by `fault_injection`.
+    pub fn with_fault_injection(fault_injection: FaultInjectionConfig) -> TestFixture {
         let tmp_dir = TempDir::new().unwrap();
         let mut config = Config::default();
+        config.fault_injection = fault_injection;
         // make sure we are suing `Disk` storage
         config.storage.storage_type = "Disk".to_string();
         // use `TempDir` as root path (auto clean)
@@ -75,6 +82,8 @@ impl TestFixture {
             schema: TestFixture::default_schema(),
             engine: "FUSE".to_string(),
             options: Default::default(),
+            temporary: false,
+            as_select: None,
         }
     }
 
@@ -84,8 +93,9 @@ impl TestFixture {
             tbl_name: TestFixture::default_table(),
             tbl_id: table.get_id(),
             schema: TestFixture::default_schema(),
+            select_plan: None,
             input_stream: Arc::new(Mutex::new(Some(Box::pin(futures::stream::iter(
-                TestFixture::gen_block_stream(block_num),
+                TestFixture::gen_block_stream(block_num).into_iter().map(Ok),
             ))))),
         }
     }
@@ -94,8 +104,7 @@ impl TestFixture {
         (0..num)
             .into_iter()
             .map(|_v| {
-                let schema =
-                    DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+                let schema = TestFixture::default_schema();
                 DataBlock::create_by_array(schema, vec![Series::new(vec![1, 2, 3])])
             })
             .collect()
@@ -14,3 +14,6 @@
 //
 
 pub const TBL_OPT_KEY_SNAPSHOT_LOC: &str = "SNAPSHOT_LOC";
+
+/// Set by `FSCK TABLE .. REPAIR` if some objects of the table are missing.
+pub const TBL_OPT_KEY_DEGRADED: &str = "DEGRADED";
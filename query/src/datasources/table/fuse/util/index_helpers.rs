@@ -13,10 +13,14 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
+
 use common_base::BlockingWait;
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_planners::Extras;
 
+use crate::datasources::table::fuse::io;
 use crate::datasources::table::fuse::util;
 use crate::datasources::table::fuse::BlockMeta;
 use crate::datasources::table::fuse::MetaInfoReader;
@@ -26,6 +30,10 @@ use crate::datasources::table::fuse::TableSnapshot;
 struct TableSparseIndex {
     table_snapshot_loc: String,
     meta_reader: MetaInfoReader,
+    table_schema: DataSchemaRef,
+    /// The column statistics are kept by the positions of the columns, they are not
+    /// trusted once the columns are altered.
+    col_stats_prunable: bool,
 }
 
 struct CacheMgr;
@@ -33,6 +41,7 @@ struct CacheMgr;
 impl TableSparseIndex {
     pub fn open(
         table_snapshot: &TableSnapshot,
+        table_schema: &DataSchemaRef,
         meta_reader: &MetaInfoReader,
         _cache_mgr: &CacheMgr,
     ) -> Result<Self> {
@@ -42,12 +51,14 @@ impl TableSparseIndex {
                 table_snapshot.snapshot_id.to_simple().to_string().as_str(), // TODO refine this
             ),
             meta_reader: meta_reader.clone(),
+            table_schema: table_schema.clone(),
+            col_stats_prunable: table_snapshot.schema == **table_schema,
         };
         Ok(r)
     }
 
     // Returns an iterator or stream would be better
-    pub fn apply(&self, _expression: &Option<Extras>) -> Result<Vec<BlockMeta>> {
+    pub fn apply(&self, push_downs: &Option<Extras>) -> Result<Vec<BlockMeta>> {
         // FAKED, to be integrate with the real indexing layer
         let snapshot: TableSnapshot = common_dal::read_obj(
             self.meta_reader.data_accessor(),
@@ -66,16 +77,44 @@ impl TableSparseIndex {
                 Ok(segment.blocks)
             })
             .collect::<Result<Vec<_>>>()?;
-        Ok(metas.into_iter().flatten().collect())
+        let metas = metas.into_iter().flatten();
+
+        // The blocks are pruned by the min and max values of the columns and the skipping
+        // indexes, the filters are still applied to the rows of the blocks read.
+        let filters = match push_downs {
+            Some(extras) if !extras.filters.is_empty() => &extras.filters,
+            _ => return Ok(metas.collect()),
+        };
+        let no_col_stats = HashMap::new();
+        Ok(metas
+            .filter(|meta| {
+                let col_stats = if self.col_stats_prunable {
+                    &meta.col_stats
+                } else {
+                    &no_col_stats
+                };
+                filters.iter().all(|filter| {
+                    io::may_match(
+                        &self.table_schema,
+                        filter,
+                        col_stats,
+                        &meta.index_stats,
+                        &meta.inverted_indexes,
+                    )
+                })
+            })
+            .collect())
     }
 }
 
 pub fn range_filter(
     table_snapshot: &TableSnapshot,
+    table_schema: &DataSchemaRef,
     push_down: &Option<Extras>,
     meta_reader: MetaInfoReader,
 ) -> Result<Vec<BlockMeta>> {
     let cache_mgr = CacheMgr; // TODO passed in from context
-    let range_index = TableSparseIndex::open(table_snapshot, &meta_reader, &cache_mgr)?;
+    let range_index =
+        TableSparseIndex::open(table_snapshot, table_schema, &meta_reader, &cache_mgr)?;
     range_index.apply(push_down)
 }
@@ -15,9 +15,12 @@
 
 use uuid::Uuid;
 
+use crate::datasources::table::fuse::util;
+
 const FUSE_TBL_BLOCK_PREFIX: &str = "_b";
 const FUSE_TBL_SEGMENT_PREFIX: &str = "_sg";
 const FUSE_TBL_SNAPSHOT_PREFIX: &str = "_ss";
+const FUSE_TBL_STAGED_PREFIX: &str = "_stg";
 
 pub fn gen_unique_block_location() -> String {
     let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
@@ -32,3 +35,31 @@ pub fn gen_segment_info_location() -> String {
 pub fn snapshot_location(name: &str) -> String {
     format!("{}/{}", FUSE_TBL_SNAPSHOT_PREFIX, name)
 }
+
+/// The location the segment is staged at, until the snapshot referring to it is committed.
+pub fn staged_segment_location(segment_location: &str) -> String {
+    let name = segment_location
+        .rsplit('/')
+        .next()
+        .unwrap_or(segment_location);
+    format!("{}/{}", FUSE_TBL_STAGED_PREFIX, name)
+}
+
+pub fn staged_prefix() -> String {
+    format!("{}/", FUSE_TBL_STAGED_PREFIX)
+}
+
+/// The prefixes of all the objects of the fuse tables, in both tiers.
+pub fn object_prefixes() -> Vec<String> {
+    [
+        FUSE_TBL_BLOCK_PREFIX,
+        FUSE_TBL_SEGMENT_PREFIX,
+        FUSE_TBL_SNAPSHOT_PREFIX,
+    ]
+    .iter()
+    .flat_map(|prefix| {
+        let prefix = format!("{}/", prefix);
+        vec![util::cold_location(&prefix), prefix]
+    })
+    .collect()
+}
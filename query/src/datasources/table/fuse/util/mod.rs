@@ -16,15 +16,24 @@
 mod col_encoding;
 mod index_helpers;
 mod location_gen;
+mod skipping_indexes;
 mod statistic_helper;
+mod storage_policy;
 
 mod constants;
 
 pub use col_encoding::*;
+pub use constants::TBL_OPT_KEY_DEGRADED;
 pub use constants::TBL_OPT_KEY_SNAPSHOT_LOC;
 pub use index_helpers::*;
 pub use location_gen::*;
+pub use skipping_indexes::*;
 pub use statistic_helper::*;
+pub use storage_policy::*;
 
+#[cfg(test)]
+mod skipping_indexes_test;
 #[cfg(test)]
 mod statistic_helper_test;
+#[cfg(test)]
+mod storage_policy_test;
@@ -0,0 +1,75 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+
+/// Table option of the skipping indexes, set by `CREATE INDEX` and `DROP INDEX`.
+pub const TBL_OPT_KEY_SKIPPING_INDEXES: &str = "skipping_indexes";
+
+/// An index on an expression of the columns, e.g. `lower(url)`. The min and max values of
+/// the expression are kept in the metas of the blocks written after the index is created,
+/// the blocks are pruned by them for the predicates on the same expression.
+///
+/// An inverted index is on a string column instead, the tokens of the column are kept and
+/// the blocks are pruned by them for `MATCH(column, 'query')`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct SkippingIndex {
+    pub name: String,
+    pub expr: Expression,
+    #[serde(default)]
+    pub inverted: bool,
+}
+
+impl SkippingIndex {
+    /// The key of the values in the block metas, the indexes on the same expression share it.
+    pub fn key(&self) -> String {
+        self.expr.column_name()
+    }
+}
+
+/// The skipping indexes of a table, kept as JSON in the table option.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SkippingIndexes {
+    pub indexes: Vec<SkippingIndex>,
+}
+
+impl SkippingIndexes {
+    pub fn from_options(options: &HashMap<String, String>) -> Result<Self> {
+        let indexes = match options.get(TBL_OPT_KEY_SKIPPING_INDEXES) {
+            Some(indexes) if !indexes.trim().is_empty() => {
+                serde_json::from_str(indexes).map_err(|e| {
+                    ErrorCode::BadOption(format!(
+                        "Invalid value of table option {}: {}",
+                        TBL_OPT_KEY_SKIPPING_INDEXES, e
+                    ))
+                })?
+            }
+            _ => vec![],
+        };
+        Ok(SkippingIndexes { indexes })
+    }
+
+    /// The value of the table option.
+    pub fn to_option(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.indexes)?)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SkippingIndex> {
+        self.indexes.iter().find(|index| index.name == name)
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::Result;
+use common_planners::add;
+use common_planners::col;
+use common_planners::Expression;
+
+use crate::datasources::table::fuse::util::SkippingIndex;
+use crate::datasources::table::fuse::util::SkippingIndexes;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SKIPPING_INDEXES;
+
+#[test]
+fn test_skipping_indexes_options() -> Result<()> {
+    let empty = SkippingIndexes::from_options(&HashMap::new())?;
+    assert!(empty.indexes.is_empty());
+
+    let indexes = SkippingIndexes {
+        indexes: vec![
+            SkippingIndex {
+                name: "idx1".to_string(),
+                expr: Expression::ScalarFunction {
+                    op: "lower".to_string(),
+                    args: vec![col("url")],
+                },
+                inverted: false,
+            },
+            SkippingIndex {
+                name: "idx2".to_string(),
+                expr: add(col("a"), col("b")),
+                inverted: false,
+            },
+            SkippingIndex {
+                name: "msg_idx".to_string(),
+                expr: col("msg"),
+                inverted: true,
+            },
+        ],
+    };
+    let mut options = HashMap::new();
+    options.insert(
+        TBL_OPT_KEY_SKIPPING_INDEXES.to_string(),
+        indexes.to_option()?,
+    );
+    let parsed = SkippingIndexes::from_options(&options)?;
+    assert_eq!(parsed, indexes);
+
+    assert_eq!(
+        parsed.get("idx1").map(|index| index.key()),
+        Some("lower(url)".to_string())
+    );
+    assert_eq!(
+        parsed.get("idx2").map(|index| index.key()),
+        Some("(a + b)".to_string())
+    );
+    assert!(parsed.get("msg_idx").unwrap().inverted);
+    assert!(parsed.get("idx3").is_none());
+
+    // The indexes created before the inverted ones are not inverted.
+    options.insert(
+        TBL_OPT_KEY_SKIPPING_INDEXES.to_string(),
+        r#"[{"name":"idx1","expr":{"Column":"a"}}]"#.to_string(),
+    );
+    assert!(!SkippingIndexes::from_options(&options)?.indexes[0].inverted);
+
+    options.insert(
+        TBL_OPT_KEY_SKIPPING_INDEXES.to_string(),
+        "lower(url)".to_string(),
+    );
+    assert!(SkippingIndexes::from_options(&options).is_err());
+    Ok(())
+}
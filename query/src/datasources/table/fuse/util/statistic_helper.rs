@@ -19,17 +19,26 @@ use std::collections::HashMap;
 use common_datablocks::DataBlock;
 use common_datavalues::columns::DataColumn;
 use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::Result;
+use common_planners::Expression;
 
+use crate::datasources::index::InvertedIndex;
 use crate::datasources::table::fuse::util;
+use crate::datasources::table::fuse::util::SkippingIndex;
 use crate::datasources::table::fuse::BlockLocation;
 use crate::datasources::table::fuse::BlockMeta;
 use crate::datasources::table::fuse::ColStats;
 use crate::datasources::table::fuse::ColumnId;
 use crate::datasources::table::fuse::Stats;
+use crate::pipelines::transforms::ExpressionExecutor;
 
 // TODO move this to other crate
 pub type BlockStats = HashMap<ColumnId, ColStats>;
+pub type IndexStats = HashMap<String, ColStats>;
+pub type InvertedIndexes = HashMap<String, InvertedIndex>;
 
 #[derive(Default)]
 pub struct StatisticsAccumulator {
@@ -42,6 +51,8 @@ pub struct StatisticsAccumulator {
     last_block_rows: u64,
     last_block_size: u64,
     last_block_col_stats: Option<HashMap<ColumnId, ColStats>>,
+    last_block_index_stats: Option<IndexStats>,
+    last_block_inverted_indexes: Option<InvertedIndexes>,
 }
 
 impl StatisticsAccumulator {
@@ -65,6 +76,13 @@ impl StatisticsAccumulator {
         self.blocks_stats.push(block_stats);
         Ok(())
     }
+
+    /// Evaluates the skipping indexes on the last block accumulated.
+    pub fn acc_indexes(&mut self, block: &DataBlock, indexes: &[SkippingIndex]) -> Result<()> {
+        self.last_block_index_stats = Some(index_stats(block, indexes)?);
+        self.last_block_inverted_indexes = Some(inverted_indexes(block, indexes)?);
+        Ok(())
+    }
 }
 
 #[derive(Default)]
@@ -89,6 +107,8 @@ impl BlockMetaAccumulator {
             row_count: stats.last_block_rows,
             block_size: stats.last_block_size,
             col_stats: stats.last_block_col_stats.take().unwrap_or_default(),
+            index_stats: stats.last_block_index_stats.take().unwrap_or_default(),
+            inverted_indexes: stats.last_block_inverted_indexes.take().unwrap_or_default(),
         };
         self.blocks_metas.push(block_meta);
     }
@@ -101,37 +121,99 @@ pub(super) fn block_stats(data_block: &DataBlock) -> Result<BlockStats> {
     (0..)
         .into_iter()
         .zip(data_block.columns().iter())
-        .map(|(idx, col)| {
-            let min = match col {
-                DataColumn::Array(s) => s.min(),
-                DataColumn::Constant(v, _) => Ok(v.clone()),
-            }?;
-
-            let max = match col {
-                DataColumn::Array(s) => s.max(),
-                DataColumn::Constant(v, _) => Ok(v.clone()),
-            }?;
-
-            let null_count = match col {
-                DataColumn::Array(s) => s.null_count(),
-                DataColumn::Constant(v, _) => {
-                    if v.is_null() {
-                        1
-                    } else {
-                        0
-                    }
-                }
-            };
+        .map(|(idx, col)| Ok((idx, column_stats(col)?)))
+        .collect()
+}
 
-            let col_stats = ColStats {
-                min,
-                max,
-                null_count,
-            };
+/// The min and max values of the expressions of the skipping indexes on the block, by the keys
+/// of the indexes. The indexes on the columns not in the block, e.g. dropped, are skipped.
+pub(super) fn index_stats(data_block: &DataBlock, indexes: &[SkippingIndex]) -> Result<IndexStats> {
+    let schema = data_block.schema();
+    let mut stats = IndexStats::with_capacity(indexes.len());
+    for index in indexes.iter().filter(|index| !index.inverted) {
+        let key = index.key();
+        if stats.contains_key(&key) {
+            continue;
+        }
+        let field = match index.expr.to_data_field(schema) {
+            Ok(field) => field,
+            Err(_) => continue,
+        };
 
-            Ok((idx, col_stats))
-        })
-        .collect()
+        let executor = ExpressionExecutor::try_create(
+            "skipping index expression executor",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![field]),
+            vec![index.expr.clone()],
+            false,
+        )?;
+        executor.validate()?;
+        let block = executor.execute(data_block)?;
+        stats.insert(key, column_stats(block.column(0))?);
+    }
+    Ok(stats)
+}
+
+/// The tokens of the columns of the inverted indexes on the block, by the names of the columns.
+/// The indexes on the columns not in the block are skipped.
+pub(super) fn inverted_indexes(
+    data_block: &DataBlock,
+    indexes: &[SkippingIndex],
+) -> Result<InvertedIndexes> {
+    let mut inverted_indexes = InvertedIndexes::new();
+    for index in indexes.iter().filter(|index| index.inverted) {
+        let name = match &index.expr {
+            Expression::Column(name) => name,
+            _ => continue,
+        };
+        if inverted_indexes.contains_key(name) {
+            continue;
+        }
+        let column = match data_block.try_column_by_name(name) {
+            Ok(column) => column,
+            Err(_) => continue,
+        };
+        inverted_indexes.insert(name.clone(), InvertedIndex::create_index(name, column)?);
+    }
+    Ok(inverted_indexes)
+}
+
+fn column_stats(col: &DataColumn) -> Result<ColStats> {
+    // The struct values are not ordered, there is no min/max to prune the blocks with.
+    if let DataType::Struct(_) = col.data_type() {
+        return Ok(ColStats {
+            min: DataValue::Null,
+            max: DataValue::Null,
+            null_count: col.get_array_ref()?.null_count(),
+        });
+    }
+
+    let min = match col {
+        DataColumn::Array(s) => s.min(),
+        DataColumn::Constant(v, _) => Ok(v.clone()),
+    }?;
+
+    let max = match col {
+        DataColumn::Array(s) => s.max(),
+        DataColumn::Constant(v, _) => Ok(v.clone()),
+    }?;
+
+    let null_count = match col {
+        DataColumn::Array(s) => s.null_count(),
+        DataColumn::Constant(v, _) => {
+            if v.is_null() {
+                1
+            } else {
+                0
+            }
+        }
+    };
+
+    Ok(ColStats {
+        min,
+        max,
+        null_count,
+    })
 }
 
 pub fn column_stats_reduce_with_schema(
@@ -177,6 +259,14 @@ pub fn column_stats_reduce_with_schema(
 
             // TODO panic
             let data_type = schema.field((*id) as usize).data_type();
+            if let DataType::Struct(_) = data_type {
+                acc.insert(*id, ColStats {
+                    min: DataValue::Null,
+                    max: DataValue::Null,
+                    null_count,
+                });
+                return Ok(acc);
+            }
 
             // TODO
             // for some data types, we shall balance the accuracy and the length
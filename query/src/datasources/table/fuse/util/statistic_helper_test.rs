@@ -21,8 +21,11 @@ use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
 use common_datavalues::DataValue;
 use common_exception::ErrorCode;
+use common_planners::add;
+use common_planners::col;
 
 use super::statistic_helper;
+use super::SkippingIndex;
 
 struct TestFixture {}
 
@@ -83,3 +86,85 @@ fn test_ft_stats_accumulator() -> common_exception::Result<()> {
     // TODO more cases here pls
     Ok(())
 }
+
+#[test]
+fn test_ft_stats_index_stats() -> common_exception::Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::Int32, false),
+    ]);
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1, 2, 3]),
+        Series::new(vec![10, 20, 30]),
+    ]);
+    let indexes = vec![
+        SkippingIndex {
+            name: "idx1".to_string(),
+            expr: add(col("a"), col("b")),
+            inverted: false,
+        },
+        // The same expression shares the values.
+        SkippingIndex {
+            name: "idx2".to_string(),
+            expr: add(col("a"), col("b")),
+            inverted: false,
+        },
+        // The column is not in the block.
+        SkippingIndex {
+            name: "idx3".to_string(),
+            expr: add(col("c"), col("b")),
+            inverted: false,
+        },
+    ];
+
+    let r = statistic_helper::index_stats(&block, &indexes)?;
+    assert_eq!(1, r.len());
+    // No inverted indexes.
+    assert!(statistic_helper::inverted_indexes(&block, &indexes)?.is_empty());
+    let stats = r.get("(a + b)").unwrap();
+    assert_eq!(stats.min, DataValue::Int64(Some(11)));
+    assert_eq!(stats.max, DataValue::Int64(Some(33)));
+    assert_eq!(stats.null_count, 0);
+    Ok(())
+}
+
+#[test]
+fn test_ft_stats_inverted_indexes() -> common_exception::Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("msg", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1, 2]),
+        Series::new(vec!["Connection reset", "connection refused"]),
+    ]);
+    let indexes = vec![
+        SkippingIndex {
+            name: "idx1".to_string(),
+            expr: col("msg"),
+            inverted: true,
+        },
+        // The column is not in the block.
+        SkippingIndex {
+            name: "idx2".to_string(),
+            expr: col("url"),
+            inverted: true,
+        },
+        // Not inverted.
+        SkippingIndex {
+            name: "idx3".to_string(),
+            expr: col("a"),
+            inverted: false,
+        },
+    ];
+
+    let r = statistic_helper::inverted_indexes(&block, &indexes)?;
+    assert_eq!(1, r.len());
+    assert_eq!(r.get("msg").unwrap().tokens, vec![
+        "connection",
+        "refused",
+        "reset"
+    ]);
+    assert_eq!(1, statistic_helper::index_stats(&block, &indexes)?.len());
+    Ok(())
+}
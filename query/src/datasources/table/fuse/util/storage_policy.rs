@@ -0,0 +1,88 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Table option of the lifecycle rule: the segments older than it are moved to the cold tier.
+pub const TBL_OPT_KEY_HOT_TO_COLD_AFTER: &str = "hot_to_cold_after";
+
+/// Location prefix of the objects in the cold tier.
+pub const COLD_TIER_PREFIX: &str = "_cold/";
+
+/// Lifecycle rules of a table, given by `ALTER TABLE t SET STORAGE_POLICY ...`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoragePolicy {
+    pub hot_to_cold_after: Duration,
+}
+
+impl StoragePolicy {
+    /// Returns None if the table has no lifecycle rule.
+    pub fn from_options(options: &HashMap<String, String>) -> Result<Option<StoragePolicy>> {
+        match options.get(TBL_OPT_KEY_HOT_TO_COLD_AFTER) {
+            None => Ok(None),
+            Some(v) => Ok(Some(StoragePolicy {
+                hot_to_cold_after: parse_lifecycle_duration(v)?,
+            })),
+        }
+    }
+}
+
+/// Parses durations like `30d`, `12h`, `10m` or `60s`, a number without unit is in days.
+pub fn parse_lifecycle_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (number, unit_secs) = match s.chars().last() {
+        Some('d') | Some('D') => (&s[..s.len() - 1], 24 * 60 * 60),
+        Some('h') | Some('H') => (&s[..s.len() - 1], 60 * 60),
+        Some('m') | Some('M') => (&s[..s.len() - 1], 60),
+        Some('s') | Some('S') => (&s[..s.len() - 1], 1),
+        _ => (s, 24 * 60 * 60),
+    };
+
+    match number.trim().parse::<u64>() {
+        Ok(n) => Ok(Duration::from_secs(n * unit_secs)),
+        Err(_) => Err(ErrorCode::BadOption(format!(
+            "Invalid lifecycle duration: {}, expect something like 30d, 12h, 10m or 60s",
+            s
+        ))),
+    }
+}
+
+pub fn unix_timestamp_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn is_cold_location(location: &str) -> bool {
+    location.starts_with(COLD_TIER_PREFIX)
+}
+
+pub fn cold_location(location: &str) -> String {
+    format!("{}{}", COLD_TIER_PREFIX, location)
+}
+
+/// The location of the hot copy of an object moved to the cold tier.
+pub fn hot_location(cold_location: &str) -> String {
+    cold_location
+        .strip_prefix(COLD_TIER_PREFIX)
+        .unwrap_or(cold_location)
+        .to_string()
+}
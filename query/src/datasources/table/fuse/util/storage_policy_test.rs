@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::table::fuse::util::cold_location;
+use crate::datasources::table::fuse::util::is_cold_location;
+use crate::datasources::table::fuse::util::parse_lifecycle_duration;
+use crate::datasources::table::fuse::util::StoragePolicy;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_HOT_TO_COLD_AFTER;
+
+#[test]
+fn test_parse_lifecycle_duration() -> Result<()> {
+    let day = 24 * 60 * 60;
+    assert_eq!(
+        parse_lifecycle_duration("30d")?,
+        Duration::from_secs(30 * day)
+    );
+    assert_eq!(
+        parse_lifecycle_duration("30")?,
+        Duration::from_secs(30 * day)
+    );
+    assert_eq!(
+        parse_lifecycle_duration("12h")?,
+        Duration::from_secs(12 * 60 * 60)
+    );
+    assert_eq!(
+        parse_lifecycle_duration("10m")?,
+        Duration::from_secs(10 * 60)
+    );
+    assert_eq!(parse_lifecycle_duration(" 0s ")?, Duration::from_secs(0));
+
+    for invalid in ["", "d", "-1d", "1w", "1.5d"] {
+        let r = parse_lifecycle_duration(invalid);
+        assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_storage_policy_from_options() -> Result<()> {
+    let mut options = HashMap::new();
+    assert_eq!(StoragePolicy::from_options(&options)?, None);
+
+    options.insert(TBL_OPT_KEY_HOT_TO_COLD_AFTER.to_string(), "1h".to_string());
+    assert_eq!(
+        StoragePolicy::from_options(&options)?,
+        Some(StoragePolicy {
+            hot_to_cold_after: Duration::from_secs(60 * 60)
+        })
+    );
+
+    let location = cold_location("_sg/abc");
+    assert_eq!(location, "_cold/_sg/abc");
+    assert!(is_cold_location(&location));
+    assert!(!is_cold_location("_sg/abc"));
+    Ok(())
+}
@@ -19,8 +19,12 @@ use std::sync::Arc;
 use common_context::DataContext;
 use common_context::IOContext;
 use common_context::TableIOContext;
+use common_dal::EncodedBlock;
+use common_dal::InMemoryBlock;
 use common_dal::InMemoryData;
+use common_dal::InMemoryTableData;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
@@ -31,10 +35,17 @@ use common_planners::Partitions;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
+use futures::stream::Stream;
 use futures::stream::StreamExt;
 
 use crate::catalogs::Table;
+use crate::datasources::common::count_table_changed_rows;
 use crate::datasources::common::generate_parts;
+use crate::datasources::common::ColumnDefaults;
+use crate::datasources::table::memory::memory_table_spill::spill_block;
+use crate::datasources::table::memory::memory_table_spill::spill_dir;
+use crate::datasources::table::memory::memory_table_spill::MemoryTableLimits;
+use crate::datasources::table::memory::memory_table_spill::MemoryTableOverflow;
 use crate::datasources::table::memory::memory_table_stream::MemoryTableStream;
 use crate::sessions::DatabendQueryContext;
 
@@ -46,7 +57,7 @@ pub struct MemoryTable {
     #[allow(dead_code)]
     in_memory_data: Arc<RwLock<InMemoryData<u64>>>,
 
-    blocks: Arc<RwLock<Vec<DataBlock>>>,
+    data: Arc<RwLock<InMemoryTableData>>,
 }
 
 impl MemoryTable {
@@ -57,27 +68,78 @@ impl MemoryTable {
         let table_id = &table_info.table_id;
         let in_memory_data = data_ctx.get_in_memory_data()?;
 
-        let blocks = {
+        let data = {
             let mut in_mem_data = in_memory_data.write();
-
-            let x = in_mem_data.get(table_id);
-            match x {
-                None => {
-                    let blocks = Arc::new(RwLock::new(vec![]));
-                    in_mem_data.insert(*table_id, blocks.clone());
-                    blocks
-                }
-                Some(blocks) => blocks.clone(),
-            }
+            in_mem_data.entry(*table_id).or_default().clone()
         };
 
         let table = Self {
             table_info,
             in_memory_data,
-            blocks,
+            data,
         };
         Ok(Box::new(table))
     }
+
+    fn snapshot(&self) -> Arc<Vec<InMemoryBlock>> {
+        self.data.read().blocks.clone()
+    }
+
+    /// Reserves the memory of a block, returns false if it is over the limit.
+    fn try_reserve(&self, bytes: usize, max_bytes: usize) -> bool {
+        let mut data = self.data.write();
+        if max_bytes > 0 && data.memory_bytes + bytes > max_bytes {
+            return false;
+        }
+        data.memory_bytes += bytes;
+        true
+    }
+
+    fn release(&self, bytes: usize) {
+        let mut data = self.data.write();
+        data.memory_bytes = data.memory_bytes.saturating_sub(bytes);
+    }
+
+    async fn collect_blocks(
+        &self,
+        mut stream: impl Stream<Item = Result<DataBlock>> + Unpin + Send,
+        limits: &MemoryTableLimits,
+        reserved: &mut usize,
+    ) -> Result<Vec<InMemoryBlock>> {
+        let dictionaries = self.data.write().dictionaries(&self.table_info.schema);
+        let mut blocks = vec![];
+        while let Some(block) = stream.next().await {
+            // The blocks keep the ids of the columns of the table, see `adapt_block_to_schema`.
+            let block = block?;
+            let schema = DataSchema::new_from(
+                block.schema().fields().clone(),
+                self.table_info.schema.meta().clone(),
+            );
+            let block = DataBlock::create(Arc::new(schema), block.columns().to_vec());
+            let encoded = EncodedBlock::encode(&block, &dictionaries)?;
+            let bytes = encoded.memory_size();
+            if self.try_reserve(bytes, limits.max_bytes) {
+                *reserved += bytes;
+                blocks.push(InMemoryBlock::Memory(encoded));
+                continue;
+            }
+
+            match limits.overflow {
+                MemoryTableOverflow::Spill => {
+                    let dir = spill_dir(self.table_info.table_id);
+                    let spilled = spill_block(&dir, block)?;
+                    blocks.push(InMemoryBlock::Spilled(Arc::new(spilled)));
+                }
+                MemoryTableOverflow::Throw => {
+                    return Err(ErrorCode::QuotaExceeded(format!(
+                        "Memory table {}.{} is over its limit of {} bytes",
+                        self.table_info.db, self.table_info.name, limits.max_bytes
+                    )));
+                }
+            }
+        }
+        Ok(blocks)
+    }
 }
 
 #[async_trait::async_trait]
@@ -96,10 +158,10 @@ impl Table for MemoryTable {
         _push_downs: Option<Extras>,
         _partition_num_hint: Option<usize>,
     ) -> Result<(Statistics, Partitions)> {
-        let blocks = self.blocks.read();
+        let blocks = self.snapshot();
 
         let rows = blocks.iter().map(|block| block.num_rows()).sum();
-        let bytes = blocks.iter().map(|block| block.memory_size()).sum();
+        let bytes = blocks.iter().map(|block| block.data_size()).sum();
 
         let statistics = Statistics::new_exact(rows, bytes);
         let parts = generate_parts(0, io_ctx.get_max_threads() as u64, blocks.len() as u64);
@@ -115,19 +177,20 @@ impl Table for MemoryTable {
             .get_user_data()?
             .expect("DatabendQueryContext should not be None");
 
-        let blocks = self.blocks.read();
         Ok(Box::pin(MemoryTableStream::try_create(
             ctx,
-            blocks.clone(),
+            self.table_info.schema.clone(),
+            ColumnDefaults::from_options(&self.table_info.options)?,
+            self.snapshot(),
         )?))
     }
 
     async fn append_data(
         &self,
-        _io_ctx: Arc<TableIOContext>,
+        io_ctx: Arc<TableIOContext>,
         _insert_plan: InsertIntoPlan,
     ) -> Result<()> {
-        let mut s = {
+        let s = {
             let mut inner = _insert_plan.input_stream.lock();
             (*inner).take()
         }
@@ -137,20 +200,48 @@ impl Table for MemoryTable {
             return Err(ErrorCode::BadArguments("DataBlock schema mismatch"));
         }
 
-        while let Some(block) = s.next().await {
-            let mut blocks = self.blocks.write();
-            blocks.push(block);
+        // The blocks become visible together when the whole input is consumed,
+        // the memory they reserved is given back if the insert fails.
+        let limits = MemoryTableLimits::from_options(&self.table_info.options)?;
+        let mut reserved = 0;
+        let blocks = match self.collect_blocks(s, &limits, &mut reserved).await {
+            Ok(blocks) => blocks,
+            Err(cause) => {
+                self.release(reserved);
+                return Err(cause);
+            }
+        };
+
+        let rows = blocks.iter().map(|block| block.num_rows() as u64).sum();
+        {
+            let mut data = self.data.write();
+            Arc::make_mut(&mut data.blocks).extend(blocks);
         }
+        count_table_changed_rows(&io_ctx, &self.table_info, rows);
         Ok(())
     }
 
     async fn truncate(
         &self,
-        _io_ctx: Arc<TableIOContext>,
+        io_ctx: Arc<TableIOContext>,
         _truncate_plan: TruncateTablePlan,
     ) -> Result<()> {
-        let mut blocks = self.blocks.write();
-        blocks.clear();
+        // The spilled files and the dictionaries are released once the running reads
+        // drop their snapshots.
+        let rows = {
+            let mut data = self.data.write();
+            let rows = data
+                .blocks
+                .iter()
+                .map(|block| block.num_rows() as u64)
+                .sum();
+            let bytes: usize = data.blocks.iter().map(|block| block.memory_size()).sum();
+            data.memory_bytes = data.memory_bytes.saturating_sub(bytes);
+            data.blocks = Arc::new(vec![]);
+            data.dictionaries.clear();
+            rows
+        };
+        count_table_changed_rows(&io_ctx, &self.table_info, rows);
         Ok(())
     }
 }
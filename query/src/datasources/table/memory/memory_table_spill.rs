@@ -0,0 +1,124 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::Path;
+use std::path::PathBuf;
+
+use common_arrow::arrow::io::ipc::read::read_file_metadata;
+use common_arrow::arrow::io::ipc::read::FileReader;
+use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_dal::SpilledBlock;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Table option of the max bytes a memory table holds in memory, 0 means unlimited.
+pub const TBL_OPT_KEY_MAX_BYTES: &str = "max_bytes";
+
+/// Table option of what to do with the inserts over `max_bytes`: `throw` or `spill`.
+pub const TBL_OPT_KEY_OVERFLOW: &str = "overflow";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MemoryTableOverflow {
+    /// Reject the insert, none of its blocks are kept.
+    Throw,
+    /// Write the blocks over the limit to the local disk.
+    Spill,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryTableLimits {
+    pub max_bytes: usize,
+    pub overflow: MemoryTableOverflow,
+}
+
+impl MemoryTableLimits {
+    pub fn from_options(options: &HashMap<String, String>) -> Result<MemoryTableLimits> {
+        let max_bytes = match options.get(TBL_OPT_KEY_MAX_BYTES) {
+            None => 0,
+            Some(v) => v.trim().parse::<usize>().map_err(|_| {
+                ErrorCode::BadOption(format!(
+                    "Invalid value of memory table option {}: {}, expect a number of bytes",
+                    TBL_OPT_KEY_MAX_BYTES, v
+                ))
+            })?,
+        };
+
+        let overflow = match options.get(TBL_OPT_KEY_OVERFLOW) {
+            None => MemoryTableOverflow::Throw,
+            Some(v) => match v.to_lowercase().as_str() {
+                "throw" => MemoryTableOverflow::Throw,
+                "spill" => MemoryTableOverflow::Spill,
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Invalid value of memory table option {}: {}, expect throw or spill",
+                        TBL_OPT_KEY_OVERFLOW, v
+                    )))
+                }
+            },
+        };
+
+        Ok(MemoryTableLimits {
+            max_bytes,
+            overflow,
+        })
+    }
+}
+
+/// Directory of the spilled blocks of a memory table.
+pub fn spill_dir(table_id: u64) -> PathBuf {
+    std::env::temp_dir()
+        .join("databend_memory_spill")
+        .join(table_id.to_string())
+}
+
+/// Writes the block to an arrow IPC file in the dir.
+pub fn spill_block(dir: &Path, block: DataBlock) -> Result<SpilledBlock> {
+    std::fs::create_dir_all(dir)?;
+    let name = format!("{}.arrow", uuid::Uuid::new_v4().to_simple());
+
+    // Created before the file is written, so a partially written file is removed on errors.
+    let spilled = SpilledBlock {
+        path: dir.join(name),
+        rows: block.num_rows(),
+        bytes: block.memory_size(),
+    };
+
+    let batch = RecordBatch::try_from(block)?;
+    let file = BufWriter::new(File::create(&spilled.path)?);
+    let mut writer = FileWriter::try_new(file, batch.schema(), IpcWriteOptions::default())?;
+    writer.write(&batch)?;
+    writer.finish()?;
+    Ok(spilled)
+}
+
+pub fn read_spilled_block(spilled: &SpilledBlock) -> Result<DataBlock> {
+    let mut file = BufReader::new(File::open(&spilled.path)?);
+    let metadata = read_file_metadata(&mut file)?;
+    let mut reader = FileReader::new(file, metadata, None);
+    match reader.next() {
+        Some(batch) => DataBlock::try_from(batch?),
+        None => Err(ErrorCode::CannotReadFile(format!(
+            "Spilled block {} is empty",
+            spilled.path.display()
+        ))),
+    }
+}
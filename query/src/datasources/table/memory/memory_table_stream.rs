@@ -12,14 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 use std::usize;
 
+use common_dal::InMemoryBlock;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use futures::stream::Stream;
 
+use crate::datasources::common::adapt_block_to_schema;
+use crate::datasources::common::ColumnDefaults;
+use crate::datasources::table::memory::memory_table_spill::read_spilled_block;
 use crate::sessions::DatabendQueryContextRef;
 
 #[derive(Debug, Clone)]
@@ -30,15 +36,24 @@ struct BlockRange {
 
 pub struct MemoryTableStream {
     ctx: DatabendQueryContextRef,
+    schema: DataSchemaRef,
+    defaults: ColumnDefaults,
     block_index: usize,
     block_ranges: Vec<usize>,
-    blocks: Vec<DataBlock>,
+    blocks: Arc<Vec<InMemoryBlock>>,
 }
 
 impl MemoryTableStream {
-    pub fn try_create(ctx: DatabendQueryContextRef, blocks: Vec<DataBlock>) -> Result<Self> {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        schema: DataSchemaRef,
+        defaults: ColumnDefaults,
+        blocks: Arc<Vec<InMemoryBlock>>,
+    ) -> Result<Self> {
         Ok(Self {
             ctx,
+            schema,
+            defaults,
             block_index: 0,
             block_ranges: vec![],
             blocks,
@@ -46,6 +61,25 @@ impl MemoryTableStream {
     }
 
     fn try_get_one_block(&mut self) -> Result<Option<DataBlock>> {
+        loop {
+            let current = match self.try_get_block_index()? {
+                None => return Ok(None),
+                Some(current) => current,
+            };
+
+            // The partitions may come from an older snapshot which had more blocks,
+            // when the table was truncated between reading the partitions and the data.
+            let block = match self.blocks.get(current) {
+                None => continue,
+                Some(InMemoryBlock::Memory(block)) => block.decode(),
+                Some(InMemoryBlock::Spilled(block)) => read_spilled_block(block)?,
+            };
+            // The block may be written before the columns of the table were altered.
+            return adapt_block_to_schema(block, &self.schema, &self.defaults).map(Some);
+        }
+    }
+
+    fn try_get_block_index(&mut self) -> Result<Option<usize>> {
         if (self.block_index as usize) == self.block_ranges.len() {
             let partitions = self.ctx.try_get_partitions(1)?;
             if partitions.is_empty() {
@@ -74,7 +108,7 @@ impl MemoryTableStream {
         }
         let current = self.block_ranges[self.block_index];
         self.block_index += 1;
-        Ok(Some(self.blocks[current].clone()))
+        Ok(Some(current))
     }
 }
 
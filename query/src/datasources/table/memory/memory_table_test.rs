@@ -20,6 +20,7 @@ use common_context::TableDataContext;
 use common_datablocks::assert_blocks_sorted_eq;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
 use common_meta_types::TableInfo;
@@ -65,12 +66,13 @@ async fn test_memorytable() -> Result<()> {
         ]);
         let blocks = vec![block, block2];
 
-        let input_stream = futures::stream::iter::<Vec<DataBlock>>(blocks.clone());
+        let input_stream = futures::stream::iter(blocks.clone().into_iter().map(Ok));
         let insert_plan = InsertIntoPlan {
             db_name: "default".to_string(),
             tbl_name: "a".to_string(),
             tbl_id: 0,
             schema,
+            select_plan: None,
             input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
         };
         table
@@ -128,3 +130,151 @@ async fn test_memorytable() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_memorytable_limits() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1u64, 2])]);
+
+    let create_table = |table_id: u64, overflow: &str| {
+        let mut options = TableOptions::default();
+        options.insert("max_bytes".into(), block.memory_size().to_string());
+        options.insert("overflow".into(), overflow.into());
+        MemoryTable::try_create(
+            TableInfo {
+                database_id: 0,
+                db: "default".into(),
+                name: "a".into(),
+                schema: schema.clone(),
+                engine: "Memory".to_string(),
+                options,
+                table_id,
+                version: 0,
+            },
+            Arc::new(TableDataContext::default()),
+        )
+    };
+    let insert_plan = |blocks: Vec<DataBlock>| InsertIntoPlan {
+        db_name: "default".to_string(),
+        tbl_name: "a".to_string(),
+        tbl_id: 0,
+        schema: schema.clone(),
+        select_plan: None,
+        input_stream: Arc::new(Mutex::new(Some(Box::pin(futures::stream::iter(
+            blocks.into_iter().map(Ok),
+        ))))),
+    };
+
+    // throw: the insert over the limit keeps none of its blocks.
+    {
+        let table = create_table(1, "throw")?;
+        table
+            .append_data(io_ctx.clone(), insert_plan(vec![block.clone()]))
+            .await?;
+        let result = table
+            .append_data(io_ctx.clone(), insert_plan(vec![block.clone()]))
+            .await;
+        assert_eq!(
+            result.unwrap_err().code(),
+            ErrorCode::QuotaExceeded("").code()
+        );
+
+        let source_plan = table.read_plan(io_ctx.clone(), None, Some(1))?;
+        assert_eq!(source_plan.statistics.read_rows, 2);
+
+        // truncate gives the memory back.
+        let truncate_plan = TruncateTablePlan {
+            db: "default".to_string(),
+            table: "a".to_string(),
+        };
+        table.truncate(io_ctx.clone(), truncate_plan).await?;
+        table
+            .append_data(io_ctx.clone(), insert_plan(vec![block.clone()]))
+            .await?;
+    }
+
+    // spill: the blocks over the limit are read back from the disk.
+    {
+        let table = create_table(2, "spill")?;
+        let block2 = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![3u64, 4])]);
+        table
+            .append_data(io_ctx.clone(), insert_plan(vec![block.clone(), block2]))
+            .await?;
+
+        let source_plan = table.read_plan(io_ctx.clone(), None, Some(1))?;
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+        let stream = table.read(io_ctx.clone(), &source_plan.push_downs).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert_blocks_sorted_eq(
+            vec![
+                "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "+---+",
+            ],
+            &result,
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_memorytable_string_dictionary() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let schema = DataSchemaRefExt::create(vec![DataField::new("city", DataType::String, true)]);
+    let table = MemoryTable::try_create(
+        TableInfo {
+            database_id: 0,
+            db: "default".into(),
+            name: "a".into(),
+            schema: schema.clone(),
+            engine: "Memory".to_string(),
+            options: TableOptions::default(),
+            table_id: 3,
+            version: 0,
+        },
+        Arc::new(TableDataContext::default()),
+    )?;
+
+    // The small block is encoded by the values the large one put in the dictionary.
+    let cities: Vec<Option<&str>> = (0..1000)
+        .map(|i| [None, Some("beijing"), Some("shanghai")][i % 3])
+        .collect();
+    let blocks = vec![
+        DataBlock::create_by_array(schema.clone(), vec![Series::new(cities.clone())]),
+        DataBlock::create_by_array(schema.clone(), vec![Series::new(vec!["beijing"])]),
+    ];
+    let bytes: usize = blocks.iter().map(|block| block.memory_size()).sum();
+    let insert_plan = InsertIntoPlan {
+        db_name: "default".to_string(),
+        tbl_name: "a".to_string(),
+        tbl_id: 3,
+        schema: schema.clone(),
+        select_plan: None,
+        input_stream: Arc::new(Mutex::new(Some(Box::pin(futures::stream::iter(
+            blocks.into_iter().map(Ok),
+        ))))),
+    };
+    table.append_data(io_ctx.clone(), insert_plan).await?;
+
+    // The statistics are of the decoded blocks.
+    let source_plan = table.read_plan(io_ctx.clone(), None, Some(1))?;
+    assert_eq!(source_plan.statistics.read_rows, 1001);
+    assert_eq!(source_plan.statistics.read_bytes, bytes);
+
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+    let stream = table.read(io_ctx.clone(), &source_plan.push_downs).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let mut values = vec![];
+    for block in result {
+        values.extend(block.column(0).to_values()?);
+    }
+    let mut expected: Vec<DataValue> = cities
+        .into_iter()
+        .map(|city| DataValue::from(city.map(str::as_bytes)))
+        .collect();
+    expected.push(DataValue::from("beijing".as_bytes()));
+    assert_eq!(values, expected);
+    Ok(())
+}
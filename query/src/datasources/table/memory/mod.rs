@@ -14,6 +14,7 @@
 //
 
 pub mod memory_table;
+pub mod memory_table_spill;
 pub mod memory_table_stream;
 #[cfg(test)]
 mod memory_table_test;
@@ -15,6 +15,7 @@
 
 pub mod fuse;
 mod prelude;
+pub mod view;
 
 mod csv;
 mod memory;
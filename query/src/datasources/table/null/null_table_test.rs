@@ -62,12 +62,13 @@ async fn test_null_table() -> Result<()> {
         ]);
         let blocks = vec![block];
 
-        let input_stream = futures::stream::iter::<Vec<DataBlock>>(blocks.clone());
+        let input_stream = futures::stream::iter(blocks.clone().into_iter().map(Ok));
         let insert_plan = InsertIntoPlan {
             db_name: "default".to_string(),
             tbl_name: "a".to_string(),
             tbl_id: 0,
             schema: schema.clone(),
+            select_plan: None,
             input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
         };
         table
@@ -22,10 +22,14 @@ use common_base::tokio::task;
 use common_context::DataContext;
 use common_context::TableIOContext;
 use common_datablocks::DataBlock;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableInfo;
 use common_planners::Extras;
+use common_planners::VIRTUAL_COLUMN_FILE_NAME;
+use common_planners::VIRTUAL_COLUMN_ROW_ID;
 use common_streams::ParquetStream;
 use common_streams::SendableDataBlockStream;
 use crossbeam::channel::bounded;
@@ -33,6 +37,7 @@ use crossbeam::channel::Receiver;
 use crossbeam::channel::Sender;
 
 use crate::catalogs::Table;
+use crate::datasources::common::append_virtual_columns;
 
 pub struct ParquetTable {
     table_info: TableInfo,
@@ -65,14 +70,23 @@ fn read_file(
     file: &str,
     tx: Sender<Option<Result<DataBlock>>>,
     projection: &[usize],
+    virtual_columns: bool,
 ) -> Result<()> {
     let reader = File::open(file)?;
     let reader = read::RecordReader::try_new(reader, Some(projection.to_vec()), None, None, None)?;
 
+    let mut next_row_id = 0;
     for maybe_batch in reader {
         match maybe_batch {
             Ok(batch) => {
-                tx.send(Some(Ok(batch.try_into()?)))
+                let mut block: DataBlock = batch.try_into()?;
+                if virtual_columns {
+                    let rows = block.num_rows() as u64;
+                    block =
+                        append_virtual_columns(block, VIRTUAL_COLUMN_FILE_NAME, file, next_row_id)?;
+                    next_row_id += rows;
+                }
+                tx.send(Some(Ok(block)))
                     .map_err(|e| ErrorCode::UnknownException(e.to_string()))?;
             }
             Err(e) => {
@@ -101,10 +115,17 @@ impl Table for ParquetTable {
         &self.table_info
     }
 
+    fn virtual_columns(&self) -> Vec<DataField> {
+        vec![
+            DataField::new(VIRTUAL_COLUMN_FILE_NAME, DataType::String, false),
+            DataField::new(VIRTUAL_COLUMN_ROW_ID, DataType::UInt64, false),
+        ]
+    }
+
     async fn read(
         &self,
         _io_ctx: Arc<TableIOContext>,
-        _push_downs: &Option<Extras>,
+        push_downs: &Option<Extras>,
     ) -> Result<SendableDataBlockStream> {
         type BlockSender = Sender<Option<Result<DataBlock>>>;
         type BlockReceiver = Receiver<Option<Result<DataBlock>>>;
@@ -113,8 +134,12 @@ impl Table for ParquetTable {
 
         let file = self.file.clone();
         let projection: Vec<usize> = (0..self.table_info.schema.fields().len()).collect();
+        let virtual_columns = match push_downs {
+            Some(push_downs) => !push_downs.virtual_columns.is_empty(),
+            None => false,
+        };
         task::spawn_blocking(move || {
-            if let Err(e) = read_file(&file, response_tx, &projection) {
+            if let Err(e) = read_file(&file, response_tx, &projection, virtual_columns) {
                 println!("Parquet reader thread terminated due to error: {:?}", e);
             }
         });
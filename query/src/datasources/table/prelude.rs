@@ -15,12 +15,40 @@
 
 use common_exception::Result;
 
+use crate::datasources::common::STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT;
+use crate::datasources::common::STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT_KEY;
+use crate::datasources::common::STORAGE_OPT_KEY_AZURE_BLOB_CONTAINER;
+use crate::datasources::common::STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN;
+use crate::datasources::common::STORAGE_OPT_KEY_CONNECTION;
+use crate::datasources::common::STORAGE_OPT_KEY_DISK_DATA_PATH;
+use crate::datasources::common::STORAGE_OPT_KEY_HDFS_DELEGATION_TOKEN;
+use crate::datasources::common::STORAGE_OPT_KEY_HDFS_NAME_NODE;
+use crate::datasources::common::STORAGE_OPT_KEY_HDFS_ROOT;
+use crate::datasources::common::STORAGE_OPT_KEY_HDFS_USER;
+use crate::datasources::common::STORAGE_OPT_KEY_S3_ACCESS_KEY_ID;
+use crate::datasources::common::STORAGE_OPT_KEY_S3_BUCKET;
+use crate::datasources::common::STORAGE_OPT_KEY_S3_REGION;
+use crate::datasources::common::STORAGE_OPT_KEY_S3_SECRET_ACCESS_KEY;
+use crate::datasources::common::STORAGE_OPT_KEY_TYPE;
 use crate::datasources::table::csv::csv_table::CsvTable;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_DEGRADED;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_HOT_TO_COLD_AFTER;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SKIPPING_INDEXES;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
 use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TBL_OPT_KEY_PARQUET_DICTIONARY_TYPES;
+use crate::datasources::table::fuse::TBL_OPT_KEY_PARQUET_PAGE_BYTES;
+use crate::datasources::table::fuse::TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS;
 use crate::datasources::table::memory::memory_table::MemoryTable;
+use crate::datasources::table::memory::memory_table_spill::TBL_OPT_KEY_MAX_BYTES;
+use crate::datasources::table::memory::memory_table_spill::TBL_OPT_KEY_OVERFLOW;
 use crate::datasources::table::null::null_table::NullTable;
 use crate::datasources::table::parquet::parquet_table::ParquetTable;
+use crate::datasources::table::view::ViewTable;
+use crate::datasources::table::view::VIEW_OPT_KEY_QUERY;
 use crate::datasources::table_engine_registry::TableEngineRegistry;
+use crate::datasources::table_options::TableOptionDef;
+use crate::datasources::table_options::TableOptionType;
 
 pub fn register_prelude_tbl_engines(registry: &TableEngineRegistry) -> Result<()> {
     registry.register("CSV", std::sync::Arc::new(CsvTable::try_create))?;
@@ -28,5 +56,181 @@ pub fn register_prelude_tbl_engines(registry: &TableEngineRegistry) -> Result<()
     registry.register("NULL", std::sync::Arc::new(NullTable::try_create))?;
     registry.register("MEMORY", std::sync::Arc::new(MemoryTable::try_create))?;
     registry.register("FUSE", std::sync::Arc::new(FuseTable::try_create))?;
+    registry.register("VIEW", std::sync::Arc::new(ViewTable::try_create))?;
+
+    registry.register_options("CSV", vec![
+        TableOptionDef::new(
+            "location",
+            TableOptionType::String,
+            "The path of the CSV file",
+        ),
+        TableOptionDef::new(
+            "has_header",
+            TableOptionType::Bool,
+            "Whether the first line is the header",
+        ),
+    ]);
+    registry.register_options("PARQUET", vec![TableOptionDef::new(
+        "location",
+        TableOptionType::String,
+        "The path of the Parquet file",
+    )]);
+    registry.register_options("MEMORY", vec![
+        TableOptionDef::new(
+            TBL_OPT_KEY_MAX_BYTES,
+            TableOptionType::UInt64,
+            "The max bytes held in memory, 0 means unlimited",
+        )
+        .alterable(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_OVERFLOW,
+            TableOptionType::Enum(&["throw", "spill"]),
+            "What to do with the inserts over max_bytes",
+        )
+        .alterable(),
+    ]);
+    registry.register_options("FUSE", fuse_table_options());
+    registry.register_options("VIEW", vec![TableOptionDef::new(
+        VIEW_OPT_KEY_QUERY,
+        TableOptionType::String,
+        "The query of the view",
+    )]);
     Ok(())
 }
+
+fn fuse_table_options() -> Vec<TableOptionDef> {
+    let mut options = vec![
+        TableOptionDef::new(
+            TBL_OPT_KEY_HOT_TO_COLD_AFTER,
+            TableOptionType::Duration,
+            "The segments older than it are moved to the cold storage",
+        )
+        .alterable(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_SNAPSHOT_LOC,
+            TableOptionType::String,
+            "The location of the current snapshot",
+        )
+        .internal(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_DEGRADED,
+            TableOptionType::String,
+            "Why the table is degraded by FSCK TABLE",
+        )
+        .internal(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_SKIPPING_INDEXES,
+            TableOptionType::String,
+            "The skipping indexes created by CREATE INDEX",
+        )
+        .internal(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_PARQUET_ROW_GROUP_ROWS,
+            TableOptionType::UInt64,
+            "The max rows of a row group of the blocks, 0 means a block is a single row group",
+        )
+        .alterable(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_PARQUET_PAGE_BYTES,
+            TableOptionType::UInt64,
+            "The max bytes of a page of the blocks, 0 means unlimited",
+        )
+        .alterable(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_PARQUET_DICTIONARY_TYPES,
+            TableOptionType::String,
+            "The data types of the dictionary encoded columns, separated by commas",
+        )
+        .alterable(),
+    ];
+
+    // The hot storage is fixed once the table is created, the cold one can be changed later.
+    let storage_options = [
+        (
+            STORAGE_OPT_KEY_TYPE,
+            "cold_storage_type",
+            TableOptionType::StorageType,
+        ),
+        (
+            STORAGE_OPT_KEY_CONNECTION,
+            "cold_storage_connection",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_DISK_DATA_PATH,
+            "cold_storage_disk_data_path",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_S3_REGION,
+            "cold_storage_s3_region",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_S3_BUCKET,
+            "cold_storage_s3_bucket",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT,
+            "cold_storage_azure_blob_account",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_AZURE_BLOB_CONTAINER,
+            "cold_storage_azure_blob_container",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_HDFS_NAME_NODE,
+            "cold_storage_hdfs_name_node",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_HDFS_ROOT,
+            "cold_storage_hdfs_root",
+            TableOptionType::String,
+        ),
+        (
+            STORAGE_OPT_KEY_HDFS_USER,
+            "cold_storage_hdfs_user",
+            TableOptionType::String,
+        ),
+    ];
+    for (hot, cold, typ) in storage_options.iter() {
+        options.push(TableOptionDef::new(hot, *typ, "The storage of the table"));
+        options.push(TableOptionDef::new(cold, *typ, "The cold storage of the table").alterable());
+    }
+    let secret_options = [
+        (
+            STORAGE_OPT_KEY_S3_ACCESS_KEY_ID,
+            "cold_storage_s3_access_key_id",
+        ),
+        (
+            STORAGE_OPT_KEY_S3_SECRET_ACCESS_KEY,
+            "cold_storage_s3_secret_access_key",
+        ),
+        (
+            STORAGE_OPT_KEY_AZURE_BLOB_ACCOUNT_KEY,
+            "cold_storage_azure_blob_account_key",
+        ),
+        (
+            STORAGE_OPT_KEY_AZURE_BLOB_SAS_TOKEN,
+            "cold_storage_azure_blob_sas_token",
+        ),
+        (
+            STORAGE_OPT_KEY_HDFS_DELEGATION_TOKEN,
+            "cold_storage_hdfs_delegation_token",
+        ),
+    ];
+    for (hot, cold) in secret_options.iter() {
+        let typ = TableOptionType::String;
+        options.push(TableOptionDef::new(hot, typ, "The storage of the table").secret());
+        options.push(
+            TableOptionDef::new(cold, typ, "The cold storage of the table")
+                .alterable()
+                .secret(),
+        );
+    }
+    options
+}
@@ -0,0 +1,83 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+//
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::DataContext;
+use common_context::TableIOContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Extras;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+
+/// The table option which keeps the SQL text of the view query.
+pub const VIEW_OPT_KEY_QUERY: &str = "query";
+
+/// A view stores nothing but its query, the planner replaces every reference
+/// to a view with the plan of that query, so a view is never read directly.
+pub struct ViewTable {
+    table_info: TableInfo,
+    query: String,
+}
+
+impl ViewTable {
+    pub fn try_create(
+        table_info: TableInfo,
+        _data_ctx: Arc<dyn DataContext<u64>>,
+    ) -> Result<Box<dyn Table>> {
+        let query = table_info
+            .options
+            .get(VIEW_OPT_KEY_QUERY)
+            .cloned()
+            .ok_or_else(|| {
+                ErrorCode::BadOption(format!(
+                    "View {}.{} has no query option",
+                    table_info.db, table_info.name
+                ))
+            })?;
+        Ok(Box::new(Self { table_info, query }))
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ViewTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read(
+        &self,
+        _io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        Err(ErrorCode::LogicalError(format!(
+            "View {}.{} must be expanded by the planner before reading",
+            self.table_info.db, self.table_info.name
+        )))
+    }
+}
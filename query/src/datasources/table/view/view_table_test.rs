@@ -0,0 +1,73 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_context::TableDataContext;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::*;
+
+use crate::datasources::table::view::ViewTable;
+use crate::datasources::table::view::VIEW_OPT_KEY_QUERY;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_view_table() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let mut table_info = TableInfo {
+        database_id: 0,
+        db: "default".into(),
+        name: "v".into(),
+        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]),
+        engine: "VIEW".to_string(),
+        options: TableOptions::default(),
+        table_id: 0,
+        version: 0,
+    };
+
+    // A view without query.
+    {
+        let result =
+            ViewTable::try_create(table_info.clone(), Arc::new(TableDataContext::default()));
+        assert_eq!(
+            result.err().unwrap().message(),
+            "View default.v has no query option"
+        );
+    }
+
+    table_info.options.insert(
+        VIEW_OPT_KEY_QUERY.to_string(),
+        "SELECT number AS a FROM system.numbers(3)".to_string(),
+    );
+    let table = ViewTable::try_create(table_info, Arc::new(TableDataContext::default()))?;
+    assert_eq!(table.engine(), "VIEW");
+    let view = table.as_any().downcast_ref::<ViewTable>().unwrap();
+    assert_eq!(view.query(), "SELECT number AS a FROM system.numbers(3)");
+
+    // A view is expanded by the planner and never read.
+    {
+        let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+        let result = table.read(io_ctx, &None).await;
+        assert_eq!(
+            result.err().unwrap().message(),
+            "View default.v must be expanded by the planner before reading"
+        );
+    }
+
+    Ok(())
+}
@@ -22,16 +22,20 @@ use common_exception::Result;
 use common_infallible::RwLock;
 
 use crate::datasources::table_engine::TableEngine;
+use crate::datasources::table_options::common_table_options;
+use crate::datasources::table_options::TableOptionDef;
 
 /// Registry of Table Providers
 pub struct TableEngineRegistry {
     engines: RwLock<HashMap<String, Arc<dyn TableEngine>>>,
+    options: RwLock<HashMap<String, Vec<TableOptionDef>>>,
 }
 
 impl TableEngineRegistry {
     pub fn new() -> Self {
         Self {
             engines: Default::default(),
+            options: Default::default(),
         }
     }
 
@@ -54,6 +58,30 @@ impl TableEngineRegistry {
         }
     }
 
+    /// Registers the options of the tables of the engine, besides the common ones.
+    pub fn register_options(&self, engine: impl Into<String>, options: Vec<TableOptionDef>) {
+        let engine_name = engine.into().to_uppercase();
+        self.options
+            .write()
+            .entry(engine_name)
+            .or_default()
+            .extend(options);
+    }
+
+    /// The options the tables of the engine may have, None if the engine is unknown.
+    pub fn get_table_options(&self, table_engine: impl AsRef<str>) -> Option<Vec<TableOptionDef>> {
+        let name = table_engine.as_ref().to_uppercase();
+        if !self.engines.read().contains_key(&name) {
+            return None;
+        }
+
+        let mut options = common_table_options();
+        if let Some(engine_options) = self.options.read().get(&name) {
+            options.extend(engine_options.iter().cloned());
+        }
+        Some(options)
+    }
+
     pub fn get_table_factory(&self, table_engine: impl AsRef<str>) -> Option<Arc<dyn TableEngine>> {
         let name = table_engine.as_ref().to_uppercase();
         self.engines.read().get(&name).cloned()
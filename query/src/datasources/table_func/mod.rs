@@ -14,9 +14,13 @@
 //
 
 pub use numbers_table::NumbersTable;
+pub use stage_files_table::StageFilesTable;
 
 mod numbers_stream;
 mod numbers_table;
 #[cfg(test)]
 mod numbers_table_test;
 pub mod prelude;
+mod stage_files_table;
+#[cfg(test)]
+mod stage_files_table_test;
@@ -0,0 +1,162 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_context::IOContext;
+use common_context::TableIOContext;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::catalogs::TableFunction;
+use crate::datasources::table_func_engine::TableArgs;
+use crate::sessions::DatabendQueryContext;
+
+/// `stage_files('location')`, the files under the location of the storage of the query,
+/// the same location as the one of `COPY INTO t FROM 'location'`.
+pub struct StageFilesTable {
+    table_info: TableInfo,
+    location: String,
+}
+
+impl StageFilesTable {
+    pub fn create(
+        database_name: &str,
+        table_func_name: &str,
+        table_id: u64,
+        table_args: TableArgs,
+    ) -> Result<Arc<dyn TableFunction>> {
+        let location = match table_args.as_deref() {
+            Some(
+                [Expression::Literal {
+                    value: DataValue::String(Some(location)),
+                    ..
+                }],
+            ) => String::from_utf8_lossy(location).to_string(),
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Must have exactly one location string argument for table function.{}",
+                    table_func_name
+                )))
+            }
+        };
+
+        let table_info = TableInfo {
+            database_id: 0,
+            table_id,
+            version: 0,
+            db: database_name.to_string(),
+            name: table_func_name.to_string(),
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("name", DataType::String, false),
+                DataField::new("size", DataType::UInt64, false),
+                DataField::new("last_modified", DataType::UInt64, true),
+                DataField::new("etag", DataType::String, true),
+            ]),
+            engine: "StageFiles".to_string(),
+            options: Default::default(),
+        };
+
+        Ok(Arc::new(StageFilesTable {
+            table_info,
+            location,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for StageFilesTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn table_args(&self) -> Option<Vec<Expression>> {
+        Some(vec![Expression::create_literal(DataValue::String(Some(
+            self.location.clone().into_bytes(),
+        )))])
+    }
+
+    async fn read(
+        &self,
+        io_ctx: Arc<TableIOContext>,
+        _push_downs: &Option<Extras>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx: Arc<DatabendQueryContext> = io_ctx
+            .get_user_data()?
+            .expect("DatabendQueryContext should not be None");
+
+        let location = ctx.get_tenant_location(&self.location)?;
+        let objects = io_ctx.get_data_accessor()?.list(&location).await?;
+
+        // The names are relative to the storage of the tenant, as the locations of `COPY`.
+        let tenant_prefix = ctx.get_tenant_location("")?;
+        let mut names = Vec::with_capacity(objects.len());
+        let mut sizes = Vec::with_capacity(objects.len());
+        let mut last_modifieds = Vec::with_capacity(objects.len());
+        let mut etags = Vec::with_capacity(objects.len());
+        for object in objects {
+            let name = object
+                .path
+                .strip_prefix(&tenant_prefix)
+                .unwrap_or(&object.path);
+            names.push(name.as_bytes().to_vec());
+            sizes.push(object.size);
+            last_modifieds.push(object.last_modified);
+            etags.push(object.etag.map(|etag| etag.into_bytes()));
+        }
+
+        let schema = self.table_info.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(names),
+            Series::new(sizes),
+            Series::new(last_modifieds),
+            Series::new(etags),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
+
+impl TableFunction for StageFilesTable {
+    fn function_name(&self) -> &str {
+        self.name()
+    }
+
+    fn db(&self) -> &str {
+        self.get_table_info().db.as_str()
+    }
+
+    fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
+    where Self: 'a {
+        self
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::configs::Config;
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_stage_files_table() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    std::fs::create_dir_all(tmp_dir.path().join("data/events/2021"))?;
+    std::fs::write(tmp_dir.path().join("data/events/a.csv"), "1\n2\n")?;
+    std::fs::write(tmp_dir.path().join("data/events/2021/b.csv"), "1\n")?;
+    std::fs::write(tmp_dir.path().join("data/other.csv"), "1\n")?;
+
+    for sql in [
+        "list @data/events",
+        "list 'data/events'",
+        "select name, size from stage_files('data/events') order by name",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+
+        let block = &result[0];
+        assert_eq!(block.column(0).to_values()?, vec![
+            DataValue::String(Some(b"data/events/2021/b.csv".to_vec())),
+            DataValue::String(Some(b"data/events/a.csv".to_vec())),
+        ]);
+        assert_eq!(block.column(1).to_values()?, vec![
+            DataValue::UInt64(Some(2)),
+            DataValue::UInt64(Some(4)),
+        ]);
+    }
+
+    let sql = "select * from stage_files(1)";
+    let r = PlanParser::create(ctx.clone()).build_from_sql(sql);
+    assert_eq!(ErrorCode::BadArguments("").code(), r.unwrap_err().code());
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use common_dal::StorageScheme;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::common::TBL_OPT_KEY_CHANGED_ROWS;
+use crate::datasources::common::TBL_OPT_KEY_COLLATION;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_COLLATIONS;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_DEFAULTS;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_STATISTICS;
+use crate::datasources::common::TBL_OPT_KEY_PRIMARY_KEY;
+use crate::datasources::common::TBL_OPT_KEY_UNIQUE_KEYS;
+use crate::datasources::table::fuse::util::parse_lifecycle_duration;
+
+/// The type of the values of a table option, the values are kept as strings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableOptionType {
+    String,
+    /// `true`, `false`, `1` or `0`.
+    Bool,
+    UInt64,
+    /// Durations like `30d`, see `parse_lifecycle_duration`.
+    Duration,
+    /// The storage types like `disk` or `s3`.
+    StorageType,
+    /// One of the values, case insensitive.
+    Enum(&'static [&'static str]),
+}
+
+impl std::fmt::Display for TableOptionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableOptionType::String => write!(f, "String"),
+            TableOptionType::Bool => write!(f, "Bool"),
+            TableOptionType::UInt64 => write!(f, "UInt64"),
+            TableOptionType::Duration => write!(f, "Duration"),
+            TableOptionType::StorageType => write!(f, "StorageType"),
+            TableOptionType::Enum(values) => write!(f, "Enum({})", values.join(", ")),
+        }
+    }
+}
+
+/// The definition of an option of the tables of an engine, registered to the
+/// `TableEngineRegistry` along with the engine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableOptionDef {
+    pub name: &'static str,
+    pub typ: TableOptionType,
+    pub description: &'static str,
+    /// Whether the option can be changed by `ALTER TABLE ... SET OPTIONS`.
+    pub alterable: bool,
+    /// Whether the option is maintained by the server, the users can not set it.
+    pub internal: bool,
+    /// Whether the value is hidden from `SHOW TABLE OPTIONS`.
+    pub secret: bool,
+}
+
+impl TableOptionDef {
+    pub fn new(name: &'static str, typ: TableOptionType, description: &'static str) -> Self {
+        TableOptionDef {
+            name,
+            typ,
+            description,
+            alterable: false,
+            internal: false,
+            secret: false,
+        }
+    }
+
+    pub fn alterable(mut self) -> Self {
+        self.alterable = true;
+        self
+    }
+
+    pub fn internal(mut self) -> Self {
+        self.internal = true;
+        self
+    }
+
+    pub fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+
+    fn check_value(&self, value: &str) -> Result<()> {
+        let valid = match self.typ {
+            TableOptionType::String => true,
+            TableOptionType::Bool => parse_bool_option(value).is_some(),
+            TableOptionType::UInt64 => value.trim().parse::<u64>().is_ok(),
+            TableOptionType::Duration => parse_lifecycle_duration(value).is_ok(),
+            TableOptionType::StorageType => StorageScheme::from_str(value).is_ok(),
+            TableOptionType::Enum(values) => values.iter().any(|v| v.eq_ignore_ascii_case(value)),
+        };
+        match valid {
+            true => Ok(()),
+            false => Err(ErrorCode::BadOption(format!(
+                "Invalid value of table option {}: {}, expect {}",
+                self.name, value, self.typ
+            ))),
+        }
+    }
+}
+
+/// Parses the values of the `Bool` options.
+pub fn parse_bool_option(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// The options every table may have, besides the ones of its engine.
+pub fn common_table_options() -> Vec<TableOptionDef> {
+    vec![
+        TableOptionDef::new(
+            TBL_OPT_KEY_PRIMARY_KEY,
+            TableOptionType::String,
+            "The PRIMARY KEY columns",
+        ),
+        TableOptionDef::new(
+            TBL_OPT_KEY_UNIQUE_KEYS,
+            TableOptionType::String,
+            "The UNIQUE constraints",
+        ),
+        TableOptionDef::new(
+            TBL_OPT_KEY_COLLATION,
+            TableOptionType::String,
+            "The default collation",
+        ),
+        TableOptionDef::new(
+            TBL_OPT_KEY_COLUMN_COLLATIONS,
+            TableOptionType::String,
+            "The collations of the columns",
+        ),
+        TableOptionDef::new(
+            TBL_OPT_KEY_COLUMN_STATISTICS,
+            TableOptionType::String,
+            "The statistics collected by ANALYZE TABLE",
+        )
+        .internal(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_CHANGED_ROWS,
+            TableOptionType::String,
+            "The rows changed since ANALYZE TABLE",
+        )
+        .internal(),
+        TableOptionDef::new(
+            TBL_OPT_KEY_COLUMN_DEFAULTS,
+            TableOptionType::String,
+            "The defaults of the columns added by ALTER TABLE ADD COLUMN",
+        )
+        .internal(),
+    ]
+}
+
+/// Checks the options given to a table of the engine when it is created, or altered if
+/// `alter` is true. The unknown options are rejected with the most similar known one.
+pub fn check_table_options(
+    engine: &str,
+    defs: &[TableOptionDef],
+    options: &HashMap<String, String>,
+    alter: bool,
+) -> Result<()> {
+    let engine = engine.to_uppercase();
+    let mut names = options.keys().collect::<Vec<_>>();
+    names.sort();
+    for name in names {
+        let def = match defs.iter().find(|def| def.name.eq_ignore_ascii_case(name)) {
+            Some(def) => def,
+            None => {
+                let suggestion = defs
+                    .iter()
+                    .filter(|def| !def.internal)
+                    .map(|def| (edit_distance(&def.name.to_lowercase(), name), def.name))
+                    .filter(|(distance, _)| *distance <= 2.max(name.len() / 3))
+                    .min();
+                return Err(ErrorCode::BadOption(match suggestion {
+                    Some((_, similar)) => format!(
+                        "Unknown option {} of {} tables, did you mean {}?",
+                        name, engine, similar
+                    ),
+                    None => format!("Unknown option {} of {} tables", name, engine),
+                }));
+            }
+        };
+
+        if def.internal {
+            return Err(ErrorCode::BadOption(format!(
+                "Table option {} is maintained by the server, it can not be set",
+                def.name
+            )));
+        }
+        if alter && !def.alterable {
+            return Err(ErrorCode::BadOption(format!(
+                "Table option {} can not be changed after the table is created",
+                def.name
+            )));
+        }
+        def.check_value(&options[name])?;
+    }
+    Ok(())
+}
+
+/// The Levenshtein distance between the names.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + (ca != *cb) as usize;
+            cur[j + 1] = substitution.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
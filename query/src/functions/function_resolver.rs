@@ -0,0 +1,113 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+
+use crate::functions::SessionFunction;
+use crate::sessions::DatabendQueryContextRef;
+
+/// `system.name` is always the built-in function, even if it is shadowed.
+pub const BUILTIN_FUNCTION_NAMESPACE: &str = "system";
+
+/// The name of a function in the namespace, e.g. `mylib.score`, or the name itself.
+pub fn qualified_function_name(namespace: &Option<String>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{}.{}", namespace, name),
+        None => name.to_string(),
+    }
+}
+
+pub enum ResolvedFunction {
+    /// A function created by `CREATE [EXTERNAL] FUNCTION`.
+    Udf(SessionFunction),
+    /// A built-in function, by its name without namespace.
+    Builtin(String),
+}
+
+/// Resolves the function names of the queries through the layers of functions, the first
+/// layer which has the name wins:
+/// 1. the functions of the session, `CREATE FUNCTION name`;
+/// 2. the functions of the tenant in the namespaces of the setting `function_search_path`,
+///    in order, so a tenant may shadow the built-in functions for its own sessions;
+/// 3. the built-in functions.
+///
+/// A qualified name `ns.name` is resolved in the namespace of the tenant only, except
+/// `system.name`, which is the built-in function. The internal built-in functions, such as
+/// `external_string`, are unknown to the queries.
+pub struct FunctionResolver {
+    ctx: DatabendQueryContextRef,
+}
+
+impl FunctionResolver {
+    pub fn create(ctx: DatabendQueryContextRef) -> Self {
+        FunctionResolver { ctx }
+    }
+
+    pub fn resolve(&self, names: &[&str]) -> Result<ResolvedFunction> {
+        match names {
+            [namespace, name] if namespace.eq_ignore_ascii_case(BUILTIN_FUNCTION_NAMESPACE) => {
+                Self::builtin(name)
+            }
+            [namespace, name] => {
+                let qualified_name = format!("{}.{}", namespace, name).to_lowercase();
+                match self.get_tenant_function(&qualified_name)? {
+                    Some(function) => Ok(ResolvedFunction::Udf(function)),
+                    None => Err(ErrorCode::UnknownFunction(format!(
+                        "Unknown function: {}",
+                        qualified_name
+                    ))),
+                }
+            }
+            [name] => {
+                if let Some(function) = self.ctx.get_session_functions().get_function(name) {
+                    return Ok(ResolvedFunction::Udf(function));
+                }
+
+                let search_path = self.ctx.get_settings().get_function_search_path()?;
+                for namespace in search_path.split(',').map(str::trim) {
+                    if namespace.is_empty() {
+                        continue;
+                    }
+                    let qualified_name = format!("{}.{}", namespace, name).to_lowercase();
+                    if let Some(function) = self.get_tenant_function(&qualified_name)? {
+                        return Ok(ResolvedFunction::Udf(function));
+                    }
+                }
+                Self::builtin(name)
+            }
+            // Unsupported by the built-in functions either.
+            _ => Ok(ResolvedFunction::Builtin(names.join("."))),
+        }
+    }
+
+    fn builtin(name: &str) -> Result<ResolvedFunction> {
+        let factory = FunctionFactory::instance();
+        match factory.get_features(name) {
+            Ok(features) if features.is_internal => Err(ErrorCode::UnknownFunction(format!(
+                "Unknown function: {}",
+                name
+            ))),
+            _ => Ok(ResolvedFunction::Builtin(name.to_string())),
+        }
+    }
+
+    fn get_tenant_function(&self, name: &str) -> Result<Option<SessionFunction>> {
+        self.ctx
+            .get_sessions_manager()
+            .get_tenant_functions()
+            .get_function(&self.ctx.get_tenant(), name)
+    }
+}
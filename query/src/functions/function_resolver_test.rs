@@ -0,0 +1,85 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+
+async fn execute_sql(ctx: &crate::sessions::DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_function_resolver() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let sql =
+        "create external function mylib.lower(String) returns String as 'http://127.0.0.1:1/'";
+    execute_sql(&ctx, sql).await?;
+
+    let column_name = |sql: &str| -> Result<String> {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        Ok(plan.schema().field(0).name().clone())
+    };
+
+    // The qualified name is the function of the tenant.
+    assert_eq!(
+        column_name("select mylib.lower(name) from system.tables")?,
+        "external_string(mylib.lower, name)"
+    );
+    assert_eq!(
+        column_name("select lower(name) from system.tables")?,
+        "lower(name)"
+    );
+
+    // It shadows the built-in function once its namespace is in the search path.
+    ctx.get_settings()
+        .set_function_search_path("other, MyLib".to_string())?;
+    assert_eq!(
+        column_name("select LOWER(name) from system.tables")?,
+        "external_string(mylib.lower, name)"
+    );
+    assert_eq!(
+        column_name("select system.lower(name) from system.tables")?,
+        "lower(name)"
+    );
+
+    let r = column_name("select other.lower(name) from system.tables");
+    assert_eq!(r.unwrap_err().code(), ErrorCode::UnknownFunction("").code());
+
+    // The endpoints only come from the functions created by CREATE EXTERNAL FUNCTION.
+    for sql in [
+        "select external_string('{}', name) from system.tables",
+        "select system.external_int64('{}', 1)",
+    ] {
+        let r = column_name(sql);
+        assert_eq!(r.unwrap_err().code(), ErrorCode::UnknownFunction("").code());
+    }
+
+    // The namespace of the built-in functions is reserved.
+    let r = execute_sql(
+        &ctx,
+        "create external function system.f(Int64) returns Int64 as 'http://127.0.0.1:1/'",
+    )
+    .await;
+    assert_eq!(r.unwrap_err().code(), ErrorCode::BadArguments("").code());
+
+    Ok(())
+}
@@ -14,7 +14,20 @@
 
 #[cfg(test)]
 mod context_function_test;
+#[cfg(test)]
+mod function_resolver_test;
 
 mod context_function;
+mod function_resolver;
+mod session_functions;
+mod tenant_functions;
 
 pub use context_function::ContextFunction;
+pub use function_resolver::qualified_function_name;
+pub use function_resolver::FunctionResolver;
+pub use function_resolver::ResolvedFunction;
+pub use function_resolver::BUILTIN_FUNCTION_NAMESPACE;
+pub use session_functions::SessionFunction;
+pub use session_functions::SessionFunctions;
+pub use tenant_functions::TenantFunctions;
+pub use tenant_functions::TenantFunctionsRef;
@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_functions::scalars::FunctionFactory;
+use common_infallible::RwLock;
+
+/// A function created by `CREATE FUNCTION` or `CREATE EXTERNAL FUNCTION`.
+#[derive(Clone, Debug)]
+pub struct SessionFunction {
+    pub name: String,
+    /// The first argument of each call: the WASM module, or the JSON encoded
+    /// `ExternalFunctionDefinition` of an external function.
+    pub definition: Vec<u8>,
+    /// The scalar function which runs the module, such as `wasm_i64`.
+    pub function_name: String,
+    pub arg_types: Vec<DataType>,
+}
+
+/// Functions created by `CREATE FUNCTION` and `CREATE EXTERNAL FUNCTION`.
+///
+/// They are never persisted: they belong to the session which created them and are
+/// released by `clear` when the session is closed.
+#[derive(Default)]
+pub struct SessionFunctions {
+    functions: RwLock<HashMap<String, SessionFunction>>,
+}
+
+impl SessionFunctions {
+    /// Builtin functions can not be shadowed.
+    pub fn check_name(name: &str) -> Result<()> {
+        if FunctionFactory::instance().check(name)
+            || AggregateFunctionFactory::instance().check(name)
+        {
+            return Err(ErrorCode::FunctionAlreadyExists(format!(
+                "Function: '{}' already exists.",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn create_function(&self, function: SessionFunction, if_not_exists: bool) -> Result<()> {
+        Self::check_name(&function.name)?;
+
+        let mut functions = self.functions.write();
+        let key = function.name.to_lowercase();
+        if functions.contains_key(&key) {
+            return match if_not_exists {
+                true => Ok(()),
+                false => Err(ErrorCode::FunctionAlreadyExists(format!(
+                    "Function: '{}' already exists.",
+                    function.name
+                ))),
+            };
+        }
+
+        functions.insert(key, function);
+        Ok(())
+    }
+
+    pub fn get_function(&self, name: &str) -> Option<SessionFunction> {
+        self.functions.read().get(&name.to_lowercase()).cloned()
+    }
+
+    pub fn drop_function(&self, name: &str, if_exists: bool) -> Result<()> {
+        match self.functions.write().remove(&name.to_lowercase()) {
+            None if !if_exists => Err(ErrorCode::UnknownFunction(format!(
+                "Unknown function: {}",
+                name
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.functions.write().clear();
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+use common_management::FunctionInfo;
+use common_management::FunctionMgr;
+use common_management::FunctionMgrApi;
+use common_meta_api::KVApi;
+
+use crate::common::MetaClientProvider;
+use crate::configs::Config;
+use crate::functions::SessionFunction;
+use crate::functions::BUILTIN_FUNCTION_NAMESPACE;
+
+pub type TenantFunctionsRef = Arc<TenantFunctions>;
+
+/// Functions created by `CREATE [EXTERNAL] FUNCTION ns.name`, kept in the meta service.
+///
+/// They are shared by all the sessions of the tenant which created them, and unknown to
+/// the other tenants. The names are qualified by their namespace, e.g. `mylib.score`.
+pub struct TenantFunctions {
+    kv_api: Arc<dyn KVApi>,
+    /// Each function manager has its own runtime, so they are kept for the tenants.
+    tenants: RwLock<HashMap<String, Arc<dyn FunctionMgrApi>>>,
+}
+
+impl TenantFunctions {
+    async fn create_kv_client(cfg: &Config) -> Result<Arc<dyn KVApi>> {
+        let store_api_provider = MetaClientProvider::from(cfg);
+        match store_api_provider.try_get_kv_client().await {
+            Ok(client) => Ok(client),
+            Err(cause) => Err(cause.add_message_back("(while create function api).")),
+        }
+    }
+
+    pub async fn create_global(cfg: Config) -> Result<TenantFunctionsRef> {
+        let kv_api = TenantFunctions::create_kv_client(&cfg).await?;
+        Ok(Arc::new(TenantFunctions {
+            kv_api,
+            tenants: Default::default(),
+        }))
+    }
+
+    fn api_provider(&self, tenant: &str) -> Arc<dyn FunctionMgrApi> {
+        if let Some(api_provider) = self.tenants.read().get(tenant) {
+            return api_provider.clone();
+        }
+
+        self.tenants
+            .write()
+            .entry(tenant.to_string())
+            .or_insert_with(|| Arc::new(FunctionMgr::new(self.kv_api.clone(), tenant)))
+            .clone()
+    }
+
+    pub fn create_function(
+        &self,
+        tenant: &str,
+        function: SessionFunction,
+        if_not_exists: bool,
+    ) -> Result<()> {
+        let name = function.name.to_lowercase();
+        match name.split_once('.') {
+            Some((namespace, _)) if namespace != BUILTIN_FUNCTION_NAMESPACE => {}
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Invalid function name: {}, the namespace {} is reserved for the built-in functions",
+                    function.name, BUILTIN_FUNCTION_NAMESPACE
+                )))
+            }
+        }
+
+        let function_info = FunctionInfo {
+            name,
+            definition: function.definition,
+            function_name: function.function_name,
+            arg_types: function.arg_types,
+        };
+        match self.api_provider(tenant).add_function(function_info) {
+            Ok(_) => Ok(()),
+            Err(e) if if_not_exists && e.code() == ErrorCode::FunctionAlreadyExists("").code() => {
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get_function(&self, tenant: &str, name: &str) -> Result<Option<SessionFunction>> {
+        match self
+            .api_provider(tenant)
+            .get_function(name.to_lowercase(), None)
+        {
+            Ok((_, function_info)) => Ok(Some(SessionFunction {
+                name: function_info.name,
+                definition: function_info.definition,
+                function_name: function_info.function_name,
+                arg_types: function_info.arg_types,
+            })),
+            Err(e) if e.code() == ErrorCode::UnknownFunction("").code() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn drop_function(&self, tenant: &str, name: &str, if_exists: bool) -> Result<()> {
+        match self
+            .api_provider(tenant)
+            .drop_function(name.to_lowercase(), None)
+        {
+            Err(e) if if_exists && e.code() == ErrorCode::UnknownFunction("").code() => Ok(()),
+            res => res,
+        }
+    }
+}
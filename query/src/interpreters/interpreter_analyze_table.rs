@@ -0,0 +1,107 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AnalyzeTablePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures::TryStreamExt;
+
+use crate::catalogs::Catalog;
+use crate::datasources::common::table_changed_rows;
+use crate::datasources::common::TableStatistics;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_STATISTICS;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sql::PlanParser;
+
+/// Scans the whole table and keeps the statistics of its columns in the table options.
+pub struct AnalyzeTableInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: AnalyzeTablePlan,
+}
+
+impl AnalyzeTableInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: AnalyzeTablePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AnalyzeTableInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AnalyzeTableInterpreter {
+    fn name(&self) -> &str {
+        "AnalyzeTableInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        if self
+            .ctx
+            .get_temporary_tables()
+            .get_table(db, table_name)
+            .is_some()
+        {
+            return Err(ErrorCode::BadArguments(format!(
+                "Cannot analyze the temporary table {}.{}",
+                db, table_name
+            )));
+        }
+
+        let table = self.ctx.get_table(db, table_name)?;
+        let schema = table.schema();
+        for column in self.plan.histogram_columns.iter() {
+            schema.field_with_name(column)?;
+        }
+
+        // The statistics are kept only if the table is not changed during the scan.
+        let table_version = table.get_table_info().version;
+        let changed_rows = table_changed_rows(&table.get_table_info().options);
+        let query = format!("SELECT * FROM {}.{}", db, table_name);
+        let plan = PlanParser::create(self.ctx.clone()).build_from_sql(&query)?;
+        let stream = InterpreterFactory::get(self.ctx.clone(), plan)?
+            .execute()
+            .await?;
+        let blocks = stream.try_collect::<Vec<_>>().await?;
+        let block = match blocks.is_empty() {
+            true => DataBlock::empty_with_schema(schema),
+            false => DataBlock::concat_blocks(&blocks)?,
+        };
+
+        let mut statistics =
+            TableStatistics::collect(&block, &self.plan.histogram_columns, self.plan.buckets)?;
+        statistics.changed_rows = changed_rows;
+        self.ctx.get_catalog().upsert_table_option(
+            table.get_id(),
+            table_version,
+            TBL_OPT_KEY_COLUMN_STATISTICS.to_string(),
+            statistics.to_option()?,
+        )?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
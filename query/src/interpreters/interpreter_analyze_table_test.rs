@@ -0,0 +1,98 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_analyze_table_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create table.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.a(a bigint, b String) Engine = Memory")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Insert into.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.a values(1, 'x'), (2, 'y'), (2, 'z'), (3, 'x')")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Analyze table.
+    {
+        if let PlanNode::AnalyzeTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("analyze table default.a update histogram on a with 2 buckets")?
+        {
+            let executor = AnalyzeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "AnalyzeTableInterpreter");
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+    }
+
+    // Select the statistics.
+    {
+        if let PlanNode::Select(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("select * from system.column_statistics where database = 'default'")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+----------+-------+--------+------+------------+-----+-----+---------+--------------------+",
+                "| database | table | column | rows | null_count | min | max | buckets | histogram          |",
+                "+----------+-------+--------+------+------------+-----+-----+---------+--------------------+",
+                "| default  | a     | a      | 4    | 0          | 1   | 3   | 2       | [1, 2]:3, [3, 3]:1 |",
+                "| default  | a     | b      | 4    | 0          | x   | z   | 0       |                    |",
+                "+----------+-------+--------+------+------------+-----+-----+---------+--------------------+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // Unknown histogram column.
+    {
+        if let PlanNode::AnalyzeTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("analyze table default.a update histogram on c")?
+        {
+            let executor = AnalyzeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let r = executor.execute().await;
+            assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
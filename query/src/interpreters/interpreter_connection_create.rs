@@ -0,0 +1,62 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::CreateConnectionPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct CreateConnectionInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CreateConnectionPlan,
+}
+
+impl CreateConnectionInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CreateConnectionPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateConnectionInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateConnectionInterpreter {
+    fn name(&self) -> &str {
+        "CreateConnectionInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_sessions_manager()
+            .get_connection_manager()
+            .create_connection(
+                &self.ctx.get_tenant(),
+                &self.plan.name,
+                &self.plan.options,
+                self.plan.if_not_exists,
+            )?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
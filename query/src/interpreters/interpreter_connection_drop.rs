@@ -0,0 +1,57 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::DropConnectionPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct DropConnectionInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: DropConnectionPlan,
+}
+
+impl DropConnectionInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: DropConnectionPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(DropConnectionInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropConnectionInterpreter {
+    fn name(&self) -> &str {
+        "DropConnectionInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.ctx
+            .get_sessions_manager()
+            .get_connection_manager()
+            .drop_connection(&self.ctx.get_tenant(), &self.plan.name, self.plan.if_exists)?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_connection_interpreter() -> Result<()> {
+    let sessions = SessionManagerBuilder::create()
+        .connection_master_keys("1:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=")
+        .build()?;
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+
+    let query = "create connection warehouse storage_type = 's3', storage_s3_bucket = 'b', storage_s3_secret_access_key = 'sk'";
+    if let PlanNode::CreateConnection(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql(query)?
+    {
+        let executor = CreateConnectionInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "CreateConnectionInterpreter");
+        executor.execute().await?;
+
+        // Exists.
+        let executor = CreateConnectionInterpreter::try_create(ctx.clone(), plan.clone())?;
+        let r = executor.execute().await;
+        assert_eq!(
+            ErrorCode::ConnectionAlreadyExists("").code(),
+            r.err().unwrap().code()
+        );
+    } else {
+        panic!()
+    }
+
+    let connections = sessions.get_connection_manager();
+    let options = connections.get_connection_options(&ctx.get_tenant(), "warehouse")?;
+    assert_eq!(
+        options.get("storage_s3_secret_access_key"),
+        Some(&"sk".to_string())
+    );
+
+    // The tables refer to the connection by name.
+    let query = "create table default.t(a bigint) Engine = Fuse storage_connection = 'warehouse'";
+    if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(query)? {
+        assert_eq!(
+            plan.options.get("storage_connection"),
+            Some(&"warehouse".to_string())
+        );
+        ctx.check_tenant_storage_options(&plan.options)?;
+    } else {
+        panic!()
+    }
+
+    if let PlanNode::DropConnection(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("drop connection warehouse")?
+    {
+        let executor = DropConnectionInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "DropConnectionInterpreter");
+        executor.execute().await?;
+
+        let executor = DropConnectionInterpreter::try_create(ctx.clone(), plan.clone())?;
+        let r = executor.execute().await;
+        assert_eq!(
+            ErrorCode::UnknownConnection("").code(),
+            r.err().unwrap().code()
+        );
+    } else {
+        panic!()
+    }
+    assert!(connections.get_connections(&ctx.get_tenant())?.is_empty());
+
+    // The tables can not refer to the dropped connections.
+    let mut options = std::collections::HashMap::new();
+    options.insert(
+        "cold_storage_connection".to_string(),
+        "warehouse".to_string(),
+    );
+    let r = ctx.check_tenant_storage_options(&options);
+    assert_eq!(
+        ErrorCode::UnknownConnection("").code(),
+        r.unwrap_err().code()
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_connection_without_master_key() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let query = "create connection c storage_type = 's3', storage_s3_secret_access_key = 'sk'";
+    if let PlanNode::CreateConnection(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql(query)?
+    {
+        let executor = CreateConnectionInterpreter::try_create(ctx.clone(), plan.clone())?;
+        let r = executor.execute().await;
+        assert_eq!(ErrorCode::BadOption("").code(), r.err().unwrap().code());
+    } else {
+        panic!()
+    }
+
+    // Only the storage options can be kept in a connection.
+    let query = "create connection c storage_type = 's3', max_threads = 2";
+    let r = PlanParser::create(ctx.clone()).build_from_sql(query);
+    assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+
+    Ok(())
+}
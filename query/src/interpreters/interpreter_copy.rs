@@ -0,0 +1,113 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::series::Series;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CopyPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+const COPY_OPT_KEY_FORMAT: &str = "format";
+const COPY_OPT_KEY_CHUNK_MAX_ROWS: &str = "chunk_max_rows";
+
+pub struct CopyInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CopyPlan,
+}
+
+impl CopyInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: CopyPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CopyInterpreter { ctx, plan }))
+    }
+
+    // Returns the max rows of a chunk, which is committed at once.
+    fn chunk_max_rows(&self) -> Result<usize> {
+        let mut chunk_max_rows = 100000;
+        for (key, value) in self.plan.options.iter() {
+            match key.as_str() {
+                COPY_OPT_KEY_FORMAT if value.to_lowercase() == "csv" => {}
+                COPY_OPT_KEY_FORMAT => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unsupported copy format: {}, only CSV is supported",
+                        value
+                    )))
+                }
+                COPY_OPT_KEY_CHUNK_MAX_ROWS => match value.parse::<usize>() {
+                    Ok(n) if n > 0 => chunk_max_rows = n,
+                    _ => {
+                        return Err(ErrorCode::BadOption(format!(
+                            "Invalid value of copy option {}: {}, expect a positive integer",
+                            key, value
+                        )))
+                    }
+                },
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unknown copy option: {}",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(chunk_max_rows)
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CopyInterpreter {
+    fn name(&self) -> &str {
+        "CopyInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let chunk_max_rows = self.chunk_max_rows()?;
+        let location = self.ctx.get_tenant_location(&self.plan.location)?;
+        self.ctx.get_table(&self.plan.db, &self.plan.table)?;
+
+        let load_manager = self.ctx.get_sessions_manager().get_load_manager();
+        let loader =
+            load_manager.create_loader(&self.plan.db, &self.plan.table, &location, chunk_max_rows);
+        let results = loader.load(self.ctx.clone()).await?;
+
+        let mut files = Vec::with_capacity(results.len());
+        let mut statuses = Vec::with_capacity(results.len());
+        let mut rows = Vec::with_capacity(results.len());
+        let mut bytes = Vec::with_capacity(results.len());
+        for result in results {
+            files.push(result.path.into_bytes());
+            statuses.push(result.status.to_string().into_bytes());
+            rows.push(result.rows);
+            bytes.push(result.bytes);
+        }
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(files),
+            Series::new(statuses),
+            Series::new(rows),
+            Series::new(bytes),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::series::Series;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CopyIntoLocationPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures::TryStreamExt;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterPtr;
+use crate::loads::FileUnloader;
+use crate::sessions::DatabendQueryContextRef;
+
+const COPY_OPT_KEY_FORMAT: &str = "format";
+const COPY_OPT_KEY_MAX_FILE_SIZE: &str = "max_file_size";
+
+pub struct CopyIntoLocationInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CopyIntoLocationPlan,
+}
+
+impl CopyIntoLocationInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CopyIntoLocationPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CopyIntoLocationInterpreter { ctx, plan }))
+    }
+
+    // Returns the max bytes of a file, a partition continues in a new file once it is reached.
+    fn max_file_size(&self) -> Result<usize> {
+        let mut max_file_size = 16 * 1024 * 1024;
+        for (key, value) in self.plan.options.iter() {
+            match key.as_str() {
+                COPY_OPT_KEY_FORMAT if value.to_lowercase() == "csv" => {}
+                COPY_OPT_KEY_FORMAT => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unsupported copy format: {}, only CSV is supported",
+                        value
+                    )))
+                }
+                COPY_OPT_KEY_MAX_FILE_SIZE => match value.parse::<usize>() {
+                    Ok(n) if n > 0 => max_file_size = n,
+                    _ => {
+                        return Err(ErrorCode::BadOption(format!(
+                            "Invalid value of copy option {}: {}, expect a positive integer",
+                            key, value
+                        )))
+                    }
+                },
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unknown copy option: {}",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(max_file_size)
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CopyIntoLocationInterpreter {
+    fn name(&self) -> &str {
+        "CopyIntoLocationInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let max_file_size = self.max_file_size()?;
+        let location = self.ctx.get_tenant_location(&self.plan.location)?;
+        let dal = self
+            .ctx
+            .get_single_node_table_io_context()?
+            .get_data_accessor()?;
+
+        let mut unloader = FileUnloader::create(
+            dal,
+            &location,
+            &self.ctx.get_id(),
+            self.plan.partition_by.clone(),
+            max_file_size,
+        );
+
+        // The rows are written as they are computed, only the open files are kept in memory.
+        let interpreter = InterpreterFactory::get(self.ctx.clone(), (*self.plan.input).clone())?;
+        let mut stream = interpreter.execute().await?;
+        while let Some(block) = stream.try_next().await? {
+            unloader.write(block).await?;
+        }
+        let results = unloader.finish().await?;
+
+        let mut files = Vec::with_capacity(results.len());
+        let mut rows = Vec::with_capacity(results.len());
+        let mut bytes = Vec::with_capacity(results.len());
+        for result in results {
+            files.push(result.path.into_bytes());
+            rows.push(result.rows);
+            bytes.push(result.bytes);
+        }
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(files),
+            Series::new(rows),
+            Series::new(bytes),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
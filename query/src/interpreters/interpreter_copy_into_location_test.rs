@@ -0,0 +1,86 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::configs::Config;
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_copy_into_location_interpreter() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    for sql in [
+        "create table default.a(k int, v varchar) Engine = Memory",
+        "insert into default.a values (1, 'x'), (2, 'y'), (1, 'z')",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    let sql = "copy into 'exports/a' from default.a partition by (k, k % 2 = 0 as even)";
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    assert_eq!(executor.name(), "CopyIntoLocationInterpreter");
+    let stream = executor.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    // the file names end with the query id
+    let id = ctx.get_id();
+    let file_1 = format!("exports/a/k=1/even=false/part-00000-{}.csv", id);
+    let file_2 = format!("exports/a/k=2/even=true/part-00000-{}.csv", id);
+    assert_eq!(1, result.len());
+    assert_eq!(result[0].column(0).to_values()?, vec![
+        DataValue::String(Some(file_1.clone().into_bytes())),
+        DataValue::String(Some(file_2.into_bytes())),
+    ]);
+    assert_eq!(result[0].column(1).to_values()?, vec![
+        DataValue::UInt64(Some(2)),
+        DataValue::UInt64(Some(1)),
+    ]);
+
+    let content = std::fs::read_to_string(tmp_dir.path().join(file_1))?;
+    assert_eq!(content, "\"x\"\n\"z\"\n");
+
+    // Bad options are rejected before anything is written.
+    for sql in [
+        "copy into 'exports/b' from default.a (format = 'parquet')",
+        "copy into 'exports/b' from default.a (max_file_size = 0)",
+        "copy into 'exports/b' from default.a (compression = 'gzip')",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert_eq!(ErrorCode::BadOption("").code(), r.err().unwrap().code());
+    }
+    assert!(!tmp_dir.path().join("exports/b").exists());
+
+    // The partition keys are computed from the columns of the rows.
+    let sql = "copy into 'exports/c' from (select v from default.a) partition by (k)";
+    assert!(PlanParser::create(ctx.clone()).build_from_sql(sql).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_copy_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.a(a bigint) Engine = Fuse",
+        "create table default.b(a bigint) Engine = Memory",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // Bad options and tables are rejected before anything is loaded.
+    for sql in [
+        "copy into default.a from 'data' (format = 'parquet')",
+        "copy into default.a from 'data' (chunk_max_rows = 0)",
+        "copy into default.a from 'data' (partition = 1)",
+        "copy into default.b from 'data'",
+    ] {
+        if let PlanNode::Copy(plan) = PlanParser::create(ctx.clone()).build_from_sql(sql)? {
+            let executor = CopyInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "CopyInterpreter");
+            let r = executor.execute().await;
+            assert_eq!(ErrorCode::BadOption("").code(), r.err().unwrap().code());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::CreateDatabasePlan;
 use common_streams::DataBlockStream;
@@ -21,6 +22,7 @@ use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
 use crate::catalogs::Catalog;
+use crate::catalogs::DB_OPT_KEY_TENANT;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -48,8 +50,24 @@ impl Interpreter for CreateDatabaseInterpreter {
 
     #[tracing::instrument(level = "info", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
     async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let mut plan = self.plan.clone();
+        if plan.options.contains_key(DB_OPT_KEY_TENANT) {
+            return Err(ErrorCode::BadOption(format!(
+                "Database option {} is given by the login",
+                DB_OPT_KEY_TENANT
+            )));
+        }
+        self.ctx.check_tenant_storage_options(&plan.options)?;
+        for table in &plan.tables {
+            self.ctx.check_tenant_storage_options(&table.options)?;
+        }
+        if !self.ctx.is_default_tenant() {
+            plan.options
+                .insert(DB_OPT_KEY_TENANT.to_string(), self.ctx.get_tenant());
+        }
+
         let datasource = self.ctx.get_catalog();
-        datasource.create_database(self.plan.clone())?;
+        datasource.create_database(plan)?;
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
@@ -14,12 +14,18 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_management::PurgeTask;
 use common_planners::DropDatabasePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::catalogs::DB_OPT_KEY_TENANT;
+use crate::datasources::table::fuse::util;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::datasources::table::fuse::FuseTable;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -36,6 +42,35 @@ impl DropDatabaseInterpreter {
     ) -> Result<InterpreterPtr> {
         Ok(Arc::new(DropDatabaseInterpreter { ctx, plan }))
     }
+
+    /// The purge tasks of the fuse tables having data, taken before the tables are dropped.
+    fn purge_tasks(&self) -> Result<Vec<PurgeTask>> {
+        let created_on = util::unix_timestamp_secs();
+        let mut tasks = vec![];
+        for table in self.ctx.get_catalog().get_tables(&self.plan.db)? {
+            if table.as_any().downcast_ref::<FuseTable>().is_none() {
+                continue;
+            }
+
+            let table_info = table.get_table_info();
+            if let Some(loc) = table_info.options.get(TBL_OPT_KEY_SNAPSHOT_LOC) {
+                // The connections of the table are of the tenant, which is unknown once the
+                // database is dropped.
+                let mut table_options = table_info.options.clone();
+                table_options.insert(DB_OPT_KEY_TENANT.to_string(), self.ctx.get_tenant());
+                tasks.push(PurgeTask {
+                    db: table_info.db.clone(),
+                    table: table_info.name.clone(),
+                    table_id: table_info.table_id,
+                    table_options,
+                    snapshot_location: Some(loc.clone()),
+                    created_on,
+                    ..Default::default()
+                });
+            }
+        }
+        Ok(tasks)
+    }
 }
 
 #[async_trait::async_trait]
@@ -45,8 +80,24 @@ impl Interpreter for DropDatabaseInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let datasource = self.ctx.get_catalog();
-        datasource.drop_database(self.plan.clone())?;
+        // The databases of the other tenants are unknown to the session.
+        match self.ctx.get_database(&self.plan.db) {
+            Ok(_) if self.plan.cascade => {
+                let tasks = self.purge_tasks()?;
+                self.ctx.get_catalog().drop_database(self.plan.clone())?;
+
+                // The data is removed by the background purge job of the servers.
+                let purge_manager = self.ctx.get_sessions_manager().get_purge_manager();
+                for task in tasks {
+                    purge_manager.add_task(task)?;
+                }
+            }
+            Ok(_) => self.ctx.get_catalog().drop_database(self.plan.clone())?,
+            Err(cause)
+                if self.plan.if_exists && cause.code() == ErrorCode::UnknownDatabase("").code() => {
+            }
+            Err(cause) => return Err(cause),
+        }
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
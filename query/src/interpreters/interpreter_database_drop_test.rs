@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_dal::DataAccessor;
+use common_dal::Local;
 use common_exception::Result;
 use common_planners::*;
 use futures::TryStreamExt;
@@ -20,6 +22,7 @@ use pretty_assertions::assert_eq;
 
 use crate::interpreters::*;
 use crate::sql::*;
+use crate::tests::SessionManagerBuilder;
 
 #[tokio::test]
 async fn test_drop_database_interpreter() -> Result<()> {
@@ -40,3 +43,64 @@ async fn test_drop_database_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_drop_database_cascade_interpreter() -> Result<()> {
+    // The table lives in its own storage, under the data path of the server.
+    let root_dir = tempfile::TempDir::new()?;
+    let sessions = SessionManagerBuilder::create()
+        .disk_data_path(root_dir.path().to_str().unwrap())
+        .build()?;
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+    let data_dir = tempfile::TempDir::new_in(root_dir.path())?;
+    let data_path = data_dir.path().to_str().unwrap().to_string();
+
+    let queries = vec![
+        "create database db1".to_string(),
+        format!(
+            "create table db1.t(a int) Engine = Fuse storage_disk_data_path = '{}'",
+            data_path
+        ),
+        "create table db1.m(a int) Engine = Memory".to_string(),
+        "insert into db1.t values(1), (2)".to_string(),
+        "insert into db1.t values(3)".to_string(),
+    ];
+    for query in queries {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(&query)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    let dal = Local::with_path(data_dir.path().to_owned());
+    assert!(!dal.list("").await?.is_empty());
+
+    if let PlanNode::DropDatabase(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("drop database db1 cascade")?
+    {
+        assert!(plan.cascade);
+        let executor = DropDatabaseInterpreter::try_create(ctx.clone(), plan.clone())?;
+        let _ = executor.execute().await?;
+    } else {
+        panic!()
+    }
+
+    // Only the fuse table is purged.
+    let sessions = ctx.get_sessions_manager();
+    let tasks = sessions.get_purge_manager().get_tasks()?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].1.table, "t");
+    assert!(!tasks[0].1.is_finished());
+
+    // The background job removes the data.
+    let removed = sessions.purge_dropped_tables().await?;
+    assert!(removed > 0);
+    assert!(dal.list("").await?.is_empty());
+
+    let tasks = sessions.get_purge_manager().get_tasks()?;
+    assert!(tasks[0].1.is_finished());
+    assert_eq!(tasks[0].1.removed_objects, removed);
+    assert_eq!(sessions.purge_dropped_tables().await?, 0);
+
+    Ok(())
+}
@@ -21,6 +21,8 @@ use common_planners::ExplainPlan;
 use common_planners::ExplainType;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
+use futures::future::try_join_all;
+use futures::StreamExt;
 
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
@@ -46,6 +48,7 @@ impl Interpreter for ExplainInterpreter {
             ExplainType::Graph => self.explain_graph(),
             ExplainType::Syntax => self.explain_syntax(),
             ExplainType::Pipeline => self.explain_pipeline(),
+            ExplainType::Analyze => self.explain_analyze().await,
         }?;
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -101,4 +104,31 @@ impl ExplainInterpreter {
         );
         Ok(DataBlock::create_by_array(schema, vec![formatted_pipeline]))
     }
+
+    async fn explain_analyze(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let plan = Optimizers::without_scatters(self.ctx.clone()).optimize(&self.explain.input)?;
+        let pipeline_builder = PipelineBuilder::create(self.ctx.clone()).with_profiling();
+        let pipeline = pipeline_builder.build(&plan)?;
+
+        // The outputs are pulled in parallel without merging them, the result is dropped and only
+        // the profile of the pipeline is displayed.
+        let outputs = pipeline.last_pipe()?.processors();
+        try_join_all(outputs.into_iter().map(|output| async move {
+            let mut stream = output.execute().await?;
+            while let Some(block) = stream.next().await {
+                block?;
+            }
+            Result::Ok(())
+        }))
+        .await?;
+
+        let formatted_pipeline = Series::new(
+            format!("{}", pipeline.display_analyze())
+                .lines()
+                .map(|s| s.as_bytes())
+                .collect::<Vec<_>>(),
+        );
+        Ok(DataBlock::create_by_array(schema, vec![formatted_pipeline]))
+    }
 }
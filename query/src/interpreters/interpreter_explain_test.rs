@@ -54,3 +54,42 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_analyze_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::Explain(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("explain analyze select number from numbers_mt(10) where (number+1)=4")?
+    {
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let block = &result[0];
+        assert_eq!(block.num_columns(), 1);
+
+        // The elapsed time varies in each run.
+        let lines = block
+            .column(0)
+            .to_values()?
+            .iter()
+            .map(|line| {
+                let line = line.to_string();
+                match line.find(", elapsed: ") {
+                    Some(pos) => line[..pos].to_string(),
+                    None => line,
+                }
+            })
+            .collect::<Vec<_>>();
+        let expected = vec![
+            "ProjectionTransform × 8 processors, rows: 1, bytes: 8",
+            "  FilterTransform × 8 processors, rows: 1, bytes: 8",
+            "    SourceTransform × 8 processors, rows: 10, bytes: 80",
+        ];
+        assert_eq!(lines, expected);
+    } else {
+        panic!()
+    }
+
+    Ok(())
+}
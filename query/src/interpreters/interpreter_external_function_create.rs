@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::ExternalFunction;
+use common_functions::scalars::ExternalFunctionDefinition;
+use common_planners::CreateExternalFunctionPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::functions::qualified_function_name;
+use crate::functions::SessionFunction;
+use crate::functions::SessionFunctions;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+const OPT_KEY_TIMEOUT_MS: &str = "timeout_ms";
+const OPT_KEY_MAX_RETRIES: &str = "max_retries";
+const OPT_KEY_MAX_BATCH_ROWS: &str = "max_batch_rows";
+
+pub struct CreateExternalFunctionInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CreateExternalFunctionPlan,
+}
+
+impl CreateExternalFunctionInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CreateExternalFunctionPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateExternalFunctionInterpreter { ctx, plan }))
+    }
+
+    fn definition(&self) -> Result<ExternalFunctionDefinition> {
+        let endpoint = &self.plan.endpoint;
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err(ErrorCode::BadOption(format!(
+                "Unsupported external function endpoint: {}, expect an HTTP URL",
+                endpoint
+            )));
+        }
+
+        let mut definition = ExternalFunctionDefinition {
+            endpoint: endpoint.clone(),
+            arg_types: self.plan.arg_types.clone(),
+            timeout_ms: 10000,
+            max_retries: 3,
+            max_batch_rows: 1000,
+        };
+        for (key, value) in self.plan.options.iter() {
+            match key.as_str() {
+                OPT_KEY_TIMEOUT_MS => definition.timeout_ms = Self::parse_integer(key, value, 1)?,
+                OPT_KEY_MAX_RETRIES => definition.max_retries = Self::parse_integer(key, value, 0)?,
+                OPT_KEY_MAX_BATCH_ROWS => {
+                    definition.max_batch_rows = Self::parse_integer(key, value, 1)?
+                }
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unknown external function option: {}",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(definition)
+    }
+
+    #[cfg(feature = "external-function")]
+    fn check_enabled() -> Result<()> {
+        Ok(())
+    }
+
+    #[cfg(not(feature = "external-function"))]
+    fn check_enabled() -> Result<()> {
+        Err(ErrorCode::UnImplement(
+            "External functions are disabled, databend-query must be built with external-function",
+        ))
+    }
+
+    fn parse_integer(key: &str, value: &str, min: u64) -> Result<u64> {
+        match value.parse::<u64>() {
+            Ok(n) if n >= min => Ok(n),
+            _ => Err(ErrorCode::BadOption(format!(
+                "Invalid value of external function option {}: {}, expect an integer >= {}",
+                key, value, min
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateExternalFunctionInterpreter {
+    fn name(&self) -> &str {
+        "CreateExternalFunctionInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        Self::check_enabled()?;
+        if self.plan.namespace.is_none() {
+            SessionFunctions::check_name(&self.plan.name)?;
+        }
+
+        let function_name = ExternalFunction::function_name(&self.plan.return_type)?;
+        let definition = self.definition()?;
+
+        let function = SessionFunction {
+            name: qualified_function_name(&self.plan.namespace, &self.plan.name),
+            definition: serde_json::to_vec(&definition)?,
+            function_name: function_name.to_string(),
+            arg_types: self.plan.arg_types.clone(),
+        };
+        match self.plan.namespace {
+            Some(_) => self
+                .ctx
+                .get_sessions_manager()
+                .get_tenant_functions()
+                .create_function(&self.ctx.get_tenant(), function, self.plan.if_not_exists)?,
+            None => self
+                .ctx
+                .get_session_functions()
+                .create_function(function, self.plan.if_not_exists)?,
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
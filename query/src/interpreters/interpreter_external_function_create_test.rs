@@ -0,0 +1,112 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_create_external_function_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let sql = "create external function score(Float64, Int64) returns Float64 as 'http://127.0.0.1:1/score' (max_batch_rows = 10)";
+    if let PlanNode::CreateExternalFunction(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql(sql)?
+    {
+        let executor = CreateExternalFunctionInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "CreateExternalFunctionInterpreter");
+        executor.execute().await?;
+    } else {
+        panic!()
+    }
+
+    // The call is planned as the function which posts the rows to the endpoint.
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("select score(number, number) from numbers_mt(3)")?;
+    assert_eq!(
+        plan.schema().field(0).name(),
+        "external_float64(score, number, number)"
+    );
+
+    for (sql, code) in [
+        (
+            "create external function f(Int64) returns Int64 as 'http://127.0.0.1:1/' (retries = 1)",
+            ErrorCode::BadOption("").code(),
+        ),
+        (
+            "create external function f(Int64) returns Int64 as 'http://127.0.0.1:1/' (timeout_ms = 0)",
+            ErrorCode::BadOption("").code(),
+        ),
+        (
+            "create external function f(Int64) returns Int64 as 'flight://127.0.0.1:1/'",
+            ErrorCode::BadOption("").code(),
+        ),
+        (
+            "create external function f(Int64) returns Date16 as 'http://127.0.0.1:1/'",
+            ErrorCode::IllegalDataType("").code(),
+        ),
+        (
+            "create external function sum(Int64) returns Int64 as 'http://127.0.0.1:1/'",
+            ErrorCode::FunctionAlreadyExists("").code(),
+        ),
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert_eq!(code, r.err().unwrap().code(), "{}", sql);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_external_function_call() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let sql = "create external function f(Int64) returns Int64 as 'http://127.0.0.1:1/' (max_retries = 0)";
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    InterpreterFactory::get(ctx.clone(), plan)?
+        .execute()
+        .await?;
+
+    // The requests run on the blocking threads, even under a current thread runtime.
+    for sql in [
+        "select f(number) from numbers(2)",
+        "select number from numbers(2) where f(number) > 0",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let stream = InterpreterFactory::get(ctx.clone(), plan)?
+            .execute()
+            .await?;
+        let r = stream.try_collect::<Vec<_>>().await;
+        assert_eq!(
+            ErrorCode::CannotConnectNode("").code(),
+            r.unwrap_err().code(),
+            "{}",
+            sql
+        );
+    }
+
+    // The endpoints only come from the functions created by CREATE EXTERNAL FUNCTION.
+    let sql = "select external_int64('{}', number) from numbers(2)";
+    let r = PlanParser::create(ctx.clone()).build_from_sql(sql);
+    assert_eq!(r.unwrap_err().code(), ErrorCode::UnknownFunction("").code());
+
+    Ok(())
+}
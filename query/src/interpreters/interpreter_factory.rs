@@ -19,18 +19,45 @@ use common_exception::Result;
 use common_planners::PlanNode;
 
 use crate::interpreters::interpreter_kill::KillInterpreter;
+use crate::interpreters::AlterTableInterpreter;
+use crate::interpreters::AlterUserInterpreter;
+use crate::interpreters::AnalyzeTableInterpreter;
+use crate::interpreters::CopyInterpreter;
+use crate::interpreters::CopyIntoLocationInterpreter;
+use crate::interpreters::CreateConnectionInterpreter;
 use crate::interpreters::CreateDatabaseInterpreter;
+use crate::interpreters::CreateExternalFunctionInterpreter;
+use crate::interpreters::CreateFunctionInterpreter;
+use crate::interpreters::CreateIndexInterpreter;
+use crate::interpreters::CreatePipeInterpreter;
+use crate::interpreters::CreateProfileInterpreter;
 use crate::interpreters::CreateTableInterpreter;
+use crate::interpreters::CreateViewInterpreter;
+use crate::interpreters::DeleteInterpreter;
 use crate::interpreters::DescribeTableInterpreter;
+use crate::interpreters::DropConnectionInterpreter;
 use crate::interpreters::DropDatabaseInterpreter;
+use crate::interpreters::DropFunctionInterpreter;
+use crate::interpreters::DropIndexInterpreter;
+use crate::interpreters::DropPipeInterpreter;
+use crate::interpreters::DropProfileInterpreter;
+use crate::interpreters::DropQueryCacheInterpreter;
 use crate::interpreters::DropTableInterpreter;
+use crate::interpreters::DropViewInterpreter;
 use crate::interpreters::ExplainInterpreter;
+use crate::interpreters::FsckTableInterpreter;
 use crate::interpreters::InsertIntoInterpreter;
 use crate::interpreters::Interpreter;
+use crate::interpreters::MergeInterpreter;
 use crate::interpreters::SelectInterpreter;
+use crate::interpreters::SetStoragePolicyInterpreter;
+use crate::interpreters::SetTableOptionsInterpreter;
 use crate::interpreters::SettingInterpreter;
+use crate::interpreters::ShowCreateDatabaseInterpreter;
 use crate::interpreters::ShowCreateTableInterpreter;
+use crate::interpreters::ShowTableOptionsInterpreter;
 use crate::interpreters::TruncateTableInterpreter;
+use crate::interpreters::UpdateInterpreter;
 use crate::interpreters::UseDatabaseInterpreter;
 use crate::sessions::DatabendQueryContextRef;
 
@@ -45,13 +72,42 @@ impl InterpreterFactory {
             PlanNode::DropDatabase(v) => DropDatabaseInterpreter::try_create(ctx, v),
             PlanNode::CreateTable(v) => CreateTableInterpreter::try_create(ctx, v),
             PlanNode::DropTable(v) => DropTableInterpreter::try_create(ctx, v),
+            PlanNode::CreateView(v) => CreateViewInterpreter::try_create(ctx, v),
+            PlanNode::DropView(v) => DropViewInterpreter::try_create(ctx, v),
             PlanNode::DescribeTable(v) => DescribeTableInterpreter::try_create(ctx, v),
             PlanNode::TruncateTable(v) => TruncateTableInterpreter::try_create(ctx, v),
             PlanNode::UseDatabase(v) => UseDatabaseInterpreter::try_create(ctx, v),
             PlanNode::SetVariable(v) => SettingInterpreter::try_create(ctx, v),
             PlanNode::InsertInto(v) => InsertIntoInterpreter::try_create(ctx, v),
             PlanNode::ShowCreateTable(v) => ShowCreateTableInterpreter::try_create(ctx, v),
+            PlanNode::ShowCreateDatabase(v) => ShowCreateDatabaseInterpreter::try_create(ctx, v),
             PlanNode::Kill(v) => KillInterpreter::try_create(ctx, v),
+            PlanNode::AnalyzeTable(v) => AnalyzeTableInterpreter::try_create(ctx, v),
+            PlanNode::FsckTable(v) => FsckTableInterpreter::try_create(ctx, v),
+            PlanNode::Delete(v) => DeleteInterpreter::try_create(ctx, v),
+            PlanNode::Update(v) => UpdateInterpreter::try_create(ctx, v),
+            PlanNode::Merge(v) => MergeInterpreter::try_create(ctx, v),
+            PlanNode::CreateExternalFunction(v) => {
+                CreateExternalFunctionInterpreter::try_create(ctx, v)
+            }
+            PlanNode::CreateFunction(v) => CreateFunctionInterpreter::try_create(ctx, v),
+            PlanNode::DropFunction(v) => DropFunctionInterpreter::try_create(ctx, v),
+            PlanNode::Copy(v) => CopyInterpreter::try_create(ctx, v),
+            PlanNode::CopyIntoLocation(v) => CopyIntoLocationInterpreter::try_create(ctx, v),
+            PlanNode::DropPipe(v) => DropPipeInterpreter::try_create(ctx, v),
+            PlanNode::CreatePipe(v) => CreatePipeInterpreter::try_create(ctx, v),
+            PlanNode::SetStoragePolicy(v) => SetStoragePolicyInterpreter::try_create(ctx, v),
+            PlanNode::SetTableOptions(v) => SetTableOptionsInterpreter::try_create(ctx, v),
+            PlanNode::ShowTableOptions(v) => ShowTableOptionsInterpreter::try_create(ctx, v),
+            PlanNode::AlterTable(v) => AlterTableInterpreter::try_create(ctx, v),
+            PlanNode::CreateIndex(v) => CreateIndexInterpreter::try_create(ctx, v),
+            PlanNode::DropIndex(v) => DropIndexInterpreter::try_create(ctx, v),
+            PlanNode::DropQueryCache(v) => DropQueryCacheInterpreter::try_create(ctx, v),
+            PlanNode::CreateProfile(v) => CreateProfileInterpreter::try_create(ctx, v),
+            PlanNode::DropProfile(v) => DropProfileInterpreter::try_create(ctx, v),
+            PlanNode::AlterUser(v) => AlterUserInterpreter::try_create(ctx, v),
+            PlanNode::CreateConnection(v) => CreateConnectionInterpreter::try_create(ctx, v),
+            PlanNode::DropConnection(v) => DropConnectionInterpreter::try_create(ctx, v),
             _ => Result::Err(ErrorCode::UnknownTypeOfQuery(format!(
                 "Can't get the interpreter by plan:{}",
                 plan.name()
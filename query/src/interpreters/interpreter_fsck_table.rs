@@ -0,0 +1,111 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::FsckTablePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::fuse::FsckIssue;
+use crate::datasources::table::fuse::FuseTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+/// The orphans younger than this may belong to an append which is not committed yet.
+const ORPHAN_GRACE_SECS: u64 = 3600;
+
+/// Checks the objects of a fuse table against its snapshots, see `FuseTable::do_fsck`.
+pub struct FsckTableInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: FsckTablePlan,
+}
+
+impl FsckTableInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: FsckTablePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(FsckTableInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for FsckTableInterpreter {
+    fn name(&self) -> &str {
+        "FsckTableInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        if self
+            .ctx
+            .get_temporary_tables()
+            .get_table(db, table_name)
+            .is_some()
+        {
+            return Err(ErrorCode::BadArguments(format!(
+                "Cannot fsck the temporary table {}.{}",
+                db, table_name
+            )));
+        }
+
+        self.ctx.get_database(db)?;
+
+        // Not the cached one of the query, the repair marks the table with its latest version.
+        let table = self.ctx.get_catalog().get_table(db, table_name)?;
+        let fuse_table = match table.as_any().downcast_ref::<FuseTable>() {
+            Some(fuse_table) => fuse_table,
+            None => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "FSCK only supports FUSE tables, table {}.{} is {}",
+                    db,
+                    table_name,
+                    table.engine()
+                )))
+            }
+        };
+
+        let io_ctx = Arc::new(self.ctx.get_single_node_table_io_context()?);
+        let issues = fuse_table
+            .do_fsck(io_ctx, self.plan.repair, ORPHAN_GRACE_SECS)
+            .await?;
+
+        let mut kinds = Vec::with_capacity(issues.len());
+        let mut locations = Vec::with_capacity(issues.len());
+        let mut repaired = Vec::with_capacity(issues.len());
+        for issue in issues {
+            let (kind, location, removed) = match issue {
+                FsckIssue::Orphan { location, removed } => ("orphan", location, removed),
+                FsckIssue::Missing { location } => ("missing", location, false),
+            };
+            kinds.push(kind.as_bytes());
+            locations.push(location);
+            repaired.push(removed);
+        }
+        let locations: Vec<&[u8]> = locations.iter().map(|x| x.as_bytes()).collect();
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(kinds),
+            Series::new(locations),
+            Series::new(repaired),
+        ]);
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CreateFunctionPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::functions::qualified_function_name;
+use crate::functions::SessionFunction;
+use crate::functions::SessionFunctions;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct CreateFunctionInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CreateFunctionPlan,
+}
+
+impl CreateFunctionInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CreateFunctionPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateFunctionInterpreter { ctx, plan }))
+    }
+
+    #[cfg(feature = "wasm")]
+    fn compile(&self) -> Result<SessionFunction> {
+        use common_functions::scalars::WasmFunction;
+
+        let module = self.plan.body.as_bytes();
+        let (arg_types, return_type) = WasmFunction::signature(module, &self.plan.name)?;
+        Ok(SessionFunction {
+            name: qualified_function_name(&self.plan.namespace, &self.plan.name),
+            definition: module.to_vec(),
+            function_name: WasmFunction::function_name(&return_type)?.to_string(),
+            arg_types,
+        })
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    fn compile(&self) -> Result<SessionFunction> {
+        Err(ErrorCode::UnImplement(
+            "WASM functions are disabled, databend-query must be built with the wasm feature",
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateFunctionInterpreter {
+    fn name(&self) -> &str {
+        "CreateFunctionInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        if self.plan.language != "wasm" {
+            return Err(ErrorCode::UnImplement(format!(
+                "Unsupported function language: {}, only WASM is supported",
+                self.plan.language
+            )));
+        }
+
+        // The functions of the tenant may shadow the built-in ones, see `FunctionResolver`.
+        if self.plan.namespace.is_some() {
+            let name = qualified_function_name(&self.plan.namespace, &self.plan.name);
+            let tenant = self.ctx.get_tenant();
+            let functions = self.ctx.get_sessions_manager().get_tenant_functions();
+            match functions.get_function(&tenant, &name)? {
+                Some(_) if self.plan.if_not_exists => {}
+                _ => {
+                    functions.create_function(&tenant, self.compile()?, self.plan.if_not_exists)?
+                }
+            }
+        } else {
+            SessionFunctions::check_name(&self.plan.name)?;
+
+            let functions = self.ctx.get_session_functions();
+            match functions.get_function(&self.plan.name) {
+                Some(_) if self.plan.if_not_exists => {}
+                _ => functions.create_function(self.compile()?, self.plan.if_not_exists)?,
+            }
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
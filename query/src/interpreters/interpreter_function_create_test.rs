@@ -0,0 +1,97 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_create_function_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Only WASM is supported, and builtin functions can not be shadowed.
+    for (sql, code) in [
+        (
+            "create function f language python as 'def f(): pass'",
+            ErrorCode::UnImplement("").code(),
+        ),
+        (
+            "create function sum language wasm as '(module)'",
+            ErrorCode::FunctionAlreadyExists("").code(),
+        ),
+    ] {
+        if let PlanNode::CreateFunction(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql(sql)?
+        {
+            let executor = CreateFunctionInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "CreateFunctionInterpreter");
+            let r = executor.execute().await;
+            assert_eq!(code, r.err().unwrap().code());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+#[tokio::test]
+async fn test_create_wasm_function_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let sql = "create function add_one language wasm as '(module (func (export \"add_one\") (param i64) (result i64) local.get 0 i64.const 1 i64.add))'";
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    executor.execute().await?;
+
+    // The call is planned as the function which runs the module.
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("select add_one(number) from numbers_mt(3)")?;
+    assert_eq!(plan.schema().field(0).name(), "wasm_i64(add_one, number)");
+
+    let r = PlanParser::create(ctx.clone()).build_from_sql("select add_one(1, 2)");
+    assert_eq!(
+        ErrorCode::NumberArgumentsNotMatch("").code(),
+        r.unwrap_err().code()
+    );
+
+    // The module does not export the function.
+    let sql = "create function add_two language wasm as '(module)'";
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let r = executor.execute().await;
+    assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+
+    Ok(())
+}
+
+#[cfg(not(feature = "wasm"))]
+#[tokio::test]
+async fn test_create_wasm_function_interpreter_disabled() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("create function add_one language wasm as '(module)'")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let r = executor.execute().await;
+    assert_eq!(ErrorCode::UnImplement("").code(), r.err().unwrap().code());
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::DropFunctionPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::functions::qualified_function_name;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct DropFunctionInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: DropFunctionPlan,
+}
+
+impl DropFunctionInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: DropFunctionPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(DropFunctionInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropFunctionInterpreter {
+    fn name(&self) -> &str {
+        "DropFunctionInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        match self.plan.namespace {
+            Some(_) => {
+                let name = qualified_function_name(&self.plan.namespace, &self.plan.name);
+                self.ctx
+                    .get_sessions_manager()
+                    .get_tenant_functions()
+                    .drop_function(&self.ctx.get_tenant(), &name, self.plan.if_exists)?
+            }
+            None => self
+                .ctx
+                .get_session_functions()
+                .drop_function(&self.plan.name, self.plan.if_exists)?,
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+async fn execute_sql(ctx: &crate::sessions::DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drop_function_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    for sql in [
+        "create external function score(Int64) returns Int64 as 'http://127.0.0.1:1/'",
+        "create external function mylib.score(Int64) returns Int64 as 'http://127.0.0.1:1/'",
+    ] {
+        execute_sql(&ctx, sql).await?;
+    }
+
+    for sql in ["drop function score", "drop function mylib.score"] {
+        if let PlanNode::DropFunction(plan) = PlanParser::create(ctx.clone()).build_from_sql(sql)? {
+            let executor = DropFunctionInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "DropFunctionInterpreter");
+            executor.execute().await?;
+        } else {
+            panic!()
+        }
+    }
+
+    for sql in [
+        "select score(number) from numbers_mt(3)",
+        "select mylib.score(number) from numbers_mt(3)",
+        "drop function score",
+        "drop function mylib.score",
+    ] {
+        let r = execute_sql(&ctx, sql).await;
+        assert_eq!(r.unwrap_err().code(), ErrorCode::UnknownFunction("").code());
+    }
+
+    execute_sql(&ctx, "drop function if exists score").await?;
+    execute_sql(&ctx, "drop function if exists mylib.score").await?;
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CreateIndexPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::util::SkippingIndex;
+use crate::datasources::table::fuse::util::SkippingIndexes;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SKIPPING_INDEXES;
+use crate::datasources::table::fuse::FuseTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct CreateIndexInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CreateIndexPlan,
+}
+
+impl CreateIndexInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CreateIndexPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateIndexInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateIndexInterpreter {
+    fn name(&self) -> &str {
+        "CreateIndexInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        if self
+            .ctx
+            .get_temporary_tables()
+            .get_table(db, table_name)
+            .is_some()
+        {
+            return Err(ErrorCode::UnImplement(format!(
+                "CREATE INDEX is not supported by temporary table {}.{}",
+                db, table_name
+            )));
+        }
+        if io::has_subquery(&self.plan.expr)? {
+            return Err(ErrorCode::BadArguments(format!(
+                "Subqueries are not allowed in index expression: {:?}",
+                self.plan.expr
+            )));
+        }
+
+        // The tables of the context may be cached before the indexes were changed.
+        let catalog = self.ctx.get_catalog();
+        let table = catalog.get_table(db, table_name)?;
+        if table.as_any().downcast_ref::<FuseTable>().is_none() {
+            return Err(ErrorCode::UnImplement(format!(
+                "Skipping indexes are only supported by FUSE tables, table {}.{} is {}",
+                db,
+                table_name,
+                table.engine()
+            )));
+        }
+
+        let table_info = table.get_table_info();
+        let mut indexes = SkippingIndexes::from_options(&table_info.options)?;
+        match indexes.get(&self.plan.name) {
+            Some(_) if self.plan.if_not_exists => {}
+            Some(_) => {
+                return Err(ErrorCode::IndexAlreadyExists(format!(
+                    "Index {} already exists on table {}.{}",
+                    self.plan.name, db, table_name
+                )))
+            }
+            None => {
+                // Only the blocks written from now on have the values of the index.
+                indexes.indexes.push(SkippingIndex {
+                    name: self.plan.name.clone(),
+                    expr: self.plan.expr.clone(),
+                    inverted: self.plan.inverted,
+                });
+                catalog.upsert_table_option(
+                    table.get_id(),
+                    table_info.version,
+                    TBL_OPT_KEY_SKIPPING_INDEXES.to_string(),
+                    indexes.to_option()?,
+                )?;
+            }
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::DropIndexPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::fuse::util::SkippingIndexes;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SKIPPING_INDEXES;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct DropIndexInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: DropIndexPlan,
+}
+
+impl DropIndexInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: DropIndexPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(DropIndexInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropIndexInterpreter {
+    fn name(&self) -> &str {
+        "DropIndexInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+
+        self.ctx.get_database(db)?;
+
+        // The tables of the context may be cached before the indexes were changed.
+        let catalog = self.ctx.get_catalog();
+        let table = catalog.get_table(db, table_name)?;
+        let table_info = table.get_table_info();
+        let mut indexes = SkippingIndexes::from_options(&table_info.options)?;
+        match indexes.get(&self.plan.name) {
+            None if self.plan.if_exists => {}
+            None => {
+                return Err(ErrorCode::UnknownIndex(format!(
+                    "Unknown index {} on table {}.{}",
+                    self.plan.name, db, table_name
+                )))
+            }
+            Some(_) => {
+                // The values of the expression kept in the metas of the blocks written before are
+                // left as they are, they stay true to the rows of the blocks.
+                indexes.indexes.retain(|index| index.name != self.plan.name);
+                catalog.upsert_table_option(
+                    table.get_id(),
+                    table_info.version,
+                    TBL_OPT_KEY_SKIPPING_INDEXES.to_string(),
+                    indexes.to_option()?,
+                )?;
+            }
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
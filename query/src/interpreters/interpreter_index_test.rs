@@ -0,0 +1,113 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::fuse::util::SkippingIndexes;
+use crate::interpreters::*;
+use crate::sql::*;
+
+async fn execute_sql(ctx: &crate::sessions::DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?;
+    Ok(())
+}
+
+fn index_names(ctx: &crate::sessions::DatabendQueryContextRef) -> Result<Vec<String>> {
+    let table = ctx.get_catalog().get_table("default", "a")?;
+    let indexes = SkippingIndexes::from_options(&table.get_table_info().options)?;
+    Ok(indexes
+        .indexes
+        .into_iter()
+        .map(|index| index.name)
+        .collect())
+}
+
+#[tokio::test]
+async fn test_index_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    execute_sql(
+        &ctx,
+        "create table default.a(a bigint, url varchar) Engine = Fuse",
+    )
+    .await?;
+    execute_sql(&ctx, "create table default.b(a bigint) Engine = Memory").await?;
+
+    // Create index.
+    {
+        let plan = PlanParser::create(ctx.clone())
+            .build_from_sql("create index idx1 on default.a (lower(url))")?;
+        if let PlanNode::CreateIndex(plan) = plan {
+            assert_eq!(plan.name, "idx1");
+            assert_eq!(plan.expr.column_name(), "lower(url)");
+            let executor = CreateIndexInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "CreateIndexInterpreter");
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+
+        execute_sql(&ctx, "create index idx2 on a (a + 1)").await?;
+        execute_sql(&ctx, "create index if not exists idx1 on a (upper(url))").await?;
+        execute_sql(&ctx, "create inverted index idx_url on a (url)").await?;
+        assert_eq!(index_names(&ctx)?, vec!["idx1", "idx2", "idx_url"]);
+
+        let r = execute_sql(&ctx, "create index idx1 on a (upper(url))").await;
+        assert_eq!(
+            ErrorCode::IndexAlreadyExists("").code(),
+            r.unwrap_err().code()
+        );
+    }
+
+    // Drop index.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("drop index idx1 on a")?;
+        if let PlanNode::DropIndex(plan) = plan {
+            let executor = DropIndexInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "DropIndexInterpreter");
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+        assert_eq!(index_names(&ctx)?, vec!["idx2", "idx_url"]);
+
+        execute_sql(&ctx, "drop index if exists idx1 on a").await?;
+        let r = execute_sql(&ctx, "drop index idx1 on a").await;
+        assert_eq!(ErrorCode::UnknownIndex("").code(), r.unwrap_err().code());
+    }
+
+    // Only FUSE tables are supported.
+    let r = execute_sql(&ctx, "create index idx1 on b (a + 1)").await;
+    assert_eq!(ErrorCode::UnImplement("").code(), r.unwrap_err().code());
+
+    // Unknown columns, constants and aggregate functions are rejected by the planner.
+    for sql in [
+        "create index idx3 on a (lower(x))",
+        "create index idx3 on a (1 + 1)",
+        "create index idx3 on a (sum(a))",
+        "create inverted index idx3 on a (a)",
+        "create inverted index idx3 on a (lower(url))",
+    ] {
+        let r = PlanParser::create(ctx.clone()).build_from_sql(sql);
+        assert!(r.is_err(), "{}", sql);
+    }
+
+    Ok(())
+}
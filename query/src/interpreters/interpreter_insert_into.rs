@@ -12,15 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
 
+use common_datablocks::DataBlock;
 use common_exception::Result;
+use common_infallible::Mutex;
 use common_planners::InsertIntoPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
 
-use crate::catalogs::Catalog;
 use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
 
@@ -45,12 +52,19 @@ impl Interpreter for InsertIntoInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let catalog = self.ctx.get_catalog();
-        let table = catalog.get_table_by_id(self.plan.tbl_id, None)?;
+        let table = self.ctx.get_table_by_id(self.plan.tbl_id, None)?;
 
         let io_ctx = self.ctx.get_cluster_table_io_context()?;
         let io_ctx = Arc::new(io_ctx);
 
+        // The rows of the SELECT are appended as they are computed, never all kept in memory.
+        if let Some(select_plan) = &self.plan.select_plan {
+            let interpreter = InterpreterFactory::get(self.ctx.clone(), *select_plan.clone())?;
+            let stream = interpreter.execute().await?;
+            self.plan
+                .set_input_stream(Box::pin(SyncBlockStream::create(stream)));
+        }
+
         table.append_data(io_ctx, self.plan.clone()).await?;
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
@@ -59,3 +73,24 @@ impl Interpreter for InsertIntoInterpreter {
         )))
     }
 }
+
+/// The input stream of a table is `Sync`, a stream which is only `Send` is polled behind a mutex.
+pub struct SyncBlockStream {
+    input: Mutex<SendableDataBlockStream>,
+}
+
+impl SyncBlockStream {
+    pub fn create(input: SendableDataBlockStream) -> Self {
+        SyncBlockStream {
+            input: Mutex::new(input),
+        }
+    }
+}
+
+impl Stream for SyncBlockStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.input.lock().poll_next_unpin(ctx)
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_insert_into_select_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.a(a bigint, b String) Engine = Memory",
+        "create table default.b(a Int32, b String) Engine = Memory",
+        "insert into default.a values(1, 'x'), (2, 'y'), (3, 'z')",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // The columns are matched by position and cast to the inserted ones.
+    {
+        let plan = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.b select a + 10, b from default.a")?;
+        if let PlanNode::InsertInto(insert) = &plan {
+            assert!(insert.select_plan.is_some());
+        } else {
+            panic!()
+        }
+
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        assert_eq!(executor.name(), "InsertIntoInterpreter");
+        let _ = executor.execute().await?;
+    }
+
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("select * from default.b")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+----+---+",
+            "| a  | b |",
+            "+----+---+",
+            "| 11 | x |",
+            "| 12 | y |",
+            "| 13 | z |",
+            "+----+---+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    // The SELECT returns the wrong number of columns.
+    {
+        let r = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.b select a from default.a");
+        assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+    }
+
+    Ok(())
+}
@@ -21,6 +21,7 @@ use common_planners::KillPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
+use crate::api::CancelAction;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -34,6 +35,42 @@ impl KillInterpreter {
     pub fn try_create(ctx: DatabendQueryContextRef, plan: KillPlan) -> Result<InterpreterPtr> {
         Ok(Arc::new(KillInterpreter { ctx, plan }))
     }
+
+    /// Kills the query on all the nodes, its stages run on the other nodes of the cluster,
+    /// returns whether a node ran it. A node which cannot be reached is skipped.
+    async fn kill_cluster_query(&self, tenant: &str, query_id: &str) -> Result<bool> {
+        let sessions = self.ctx.get_sessions_manager();
+        let mut killed = sessions.kill_query(tenant, query_id);
+
+        let cluster = self.ctx.get_cluster();
+        let config = self.ctx.get_config();
+        let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
+        for node in cluster.get_nodes() {
+            if cluster.is_local(&node) {
+                continue;
+            }
+
+            let action = CancelAction {
+                query_id: query_id.to_string(),
+                tenant: tenant.to_string(),
+            };
+            let node_killed = match cluster.create_node_conn(&node.id, &config).await {
+                Ok(mut client) => client.kill_query(action, timeout).await,
+                Err(cause) => Err(cause),
+            };
+
+            match node_killed {
+                Ok(node_killed) => killed |= node_killed,
+                Err(cause) => log::warn!(
+                    "Cannot kill query {} on node {}: {}",
+                    query_id,
+                    node.id,
+                    cause
+                ),
+            }
+        }
+        Ok(killed)
+    }
 }
 
 #[async_trait::async_trait]
@@ -44,21 +81,42 @@ impl Interpreter for KillInterpreter {
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         let id = &self.plan.id;
-        match self.ctx.get_sessions_manager().get_session(id) {
-            None => Err(ErrorCode::UnknownSession(format!(
-                "Not found session id {}",
-                id
-            ))),
+        let tenant = self.ctx.get_tenant();
+        // The sessions of the other tenants are unknown to this one.
+        let kill_session = self
+            .ctx
+            .get_sessions_manager()
+            .get_session(id)
+            .filter(|session| session.get_tenant() == tenant);
+        match kill_session {
+            None if self.plan.kill_connection => {
+                return Err(ErrorCode::UnknownSession(format!(
+                    "Not found session id {}",
+                    id
+                )));
+            }
             Some(kill_session) if self.plan.kill_connection => {
                 kill_session.force_kill_session();
-                let schema = Arc::new(DataSchema::empty());
-                Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
             }
             Some(kill_session) => {
+                // The running query of the session, its stages may run on the other nodes.
+                if let Some(query_id) = kill_session.get_query_id() {
+                    self.kill_cluster_query(&tenant, &query_id).await?;
+                }
                 kill_session.force_kill_query();
-                let schema = Arc::new(DataSchema::empty());
-                Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
+            }
+            // KILL QUERY also takes the id of a query, which may run on any node.
+            None => {
+                if !self.kill_cluster_query(&tenant, id).await? {
+                    return Err(ErrorCode::UnknownSession(format!(
+                        "Not found session id or query id {}",
+                        id
+                    )));
+                }
             }
         }
+
+        let schema = Arc::new(DataSchema::empty());
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
     }
 }
@@ -0,0 +1,88 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::MergePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures::TryStreamExt;
+
+use crate::datasources::table::fuse::FuseTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+/// Merges the rows of the source query into a fuse table, see `FuseTable::do_merge`.
+pub struct MergeInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: MergePlan,
+}
+
+impl MergeInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: MergePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(MergeInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for MergeInterpreter {
+    fn name(&self) -> &str {
+        "MergeInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        let table = self.ctx.get_table(db, table_name)?;
+        let fuse_table = match table.as_any().downcast_ref::<FuseTable>() {
+            Some(fuse_table) => fuse_table,
+            None => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "MERGE only supports FUSE tables, table {}.{} is {}",
+                    db,
+                    table_name,
+                    table.engine()
+                )))
+            }
+        };
+
+        // The source is read in full before the table is, the rows of the table are matched
+        // against it block by block.
+        let source = InterpreterFactory::get(self.ctx.clone(), *self.plan.source.clone())?;
+        let blocks = source
+            .execute()
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .filter(|block| block.num_rows() > 0)
+            .collect::<Vec<_>>();
+        if !blocks.is_empty() {
+            let source = DataBlock::concat_blocks(&blocks)?;
+            let io_ctx = Arc::new(self.ctx.get_single_node_table_io_context()?);
+            fuse_table.do_merge(io_ctx, &self.plan, source).await?;
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_merge_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.t(id bigint, v varchar) Engine = Fuse",
+        "create table default.s(id bigint, v varchar) Engine = Fuse",
+        "create table default.m(id bigint, v varchar) Engine = Memory",
+        "insert into default.t values(1, 'a'), (2, 'b'), (3, 'c')",
+        "insert into default.s values(2, 'x'), (3, 'y'), (4, 'z'), (5, 'w')",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    {
+        if let PlanNode::Merge(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "merge into default.t using default.s on t.id = s.id \
+             when matched and s.id = 3 then delete \
+             when matched then update set v = s.v \
+             when not matched and s.id < 5 then insert values (s.id, s.v)",
+        )? {
+            assert_eq!(plan.table, "t");
+            assert_eq!(plan.keys.len(), 1);
+            assert_eq!(plan.matched.len(), 2);
+            assert_eq!(plan.not_matched.len(), 1);
+            let executor = MergeInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "MergeInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("select * from default.t")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+----+---+",
+            "| id | v |",
+            "+----+---+",
+            "| 1  | a |",
+            "| 2  | x |",
+            "| 4  | z |",
+            "+----+---+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    // A row of the table matching more than one row of the source is rejected.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(
+            "merge into default.t using (select number % 1 + 1 as k from numbers(2)) as d \
+             on id = d.k when matched then update set v = 'dup'",
+        )?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+    }
+
+    // Only FUSE tables are supported.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(
+            "merge into default.m using default.s on m.id = s.id when matched then delete",
+        )?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+    }
+
+    // Unknown columns, ON conditions other than equalities between the target and the source,
+    // and VALUES not matching the columns are rejected by the planner.
+    for sql in [
+        "merge into default.t using default.s on t.id = s.id when matched then update set c = 1",
+        "merge into default.t using default.s on t.id > s.id when matched then delete",
+        "merge into default.t using default.s on t.id = s.id \
+         when not matched then insert values (1)",
+        "merge into default.t using default.t on t.id = t.id when matched then delete",
+    ] {
+        let r = PlanParser::create(ctx.clone()).build_from_sql(sql);
+        assert!(r.is_err(), "{}", sql);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::KafkaSourceInfo;
+use common_management::PipeInfo;
+use common_management::PipeSourceInfo;
+use common_planners::CreatePipePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::table::fuse::FuseTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+const KAFKA_OPT_KEY_BROKERS: &str = "brokers";
+const KAFKA_OPT_KEY_TOPIC: &str = "topic";
+const KAFKA_OPT_KEY_FORMAT: &str = "format";
+const KAFKA_OPT_KEY_BATCH_MAX_MESSAGES: &str = "batch_max_messages";
+const KAFKA_OPT_KEY_BATCH_INTERVAL_MS: &str = "batch_interval_ms";
+
+pub struct CreatePipeInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CreatePipePlan,
+}
+
+impl CreatePipeInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CreatePipePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreatePipeInterpreter { ctx, plan }))
+    }
+
+    fn source_info(&self) -> Result<PipeSourceInfo> {
+        if self.plan.source != "kafka" {
+            return Err(ErrorCode::BadOption(format!(
+                "Unsupported pipe source: {}, only KAFKA is supported",
+                self.plan.source
+            )));
+        }
+
+        let mut kafka = KafkaSourceInfo {
+            brokers: "".to_string(),
+            topic: "".to_string(),
+            format: "csv".to_string(),
+            batch_max_messages: 10000,
+            batch_interval_ms: 1000,
+        };
+        for (key, value) in self.plan.source_options.iter() {
+            match key.as_str() {
+                KAFKA_OPT_KEY_BROKERS => kafka.brokers = value.clone(),
+                KAFKA_OPT_KEY_TOPIC => kafka.topic = value.clone(),
+                KAFKA_OPT_KEY_FORMAT => kafka.format = value.to_lowercase(),
+                KAFKA_OPT_KEY_BATCH_MAX_MESSAGES => {
+                    kafka.batch_max_messages = Self::parse_positive(key, value)?
+                }
+                KAFKA_OPT_KEY_BATCH_INTERVAL_MS => {
+                    kafka.batch_interval_ms = Self::parse_positive(key, value)?
+                }
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Unknown kafka pipe option: {}",
+                        key
+                    )))
+                }
+            }
+        }
+
+        if kafka.brokers.is_empty() || kafka.topic.is_empty() {
+            return Err(ErrorCode::BadOption(
+                "Kafka pipe requires the brokers and topic options",
+            ));
+        }
+        if kafka.format != "csv" {
+            return Err(ErrorCode::BadOption(format!(
+                "Unsupported pipe format: {}, only CSV is supported",
+                kafka.format
+            )));
+        }
+        Ok(PipeSourceInfo::Kafka(kafka))
+    }
+
+    fn parse_positive(key: &str, value: &str) -> Result<u64> {
+        match value.parse::<u64>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(ErrorCode::BadOption(format!(
+                "Invalid value of kafka pipe option {}: {}, expect a positive integer",
+                key, value
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreatePipeInterpreter {
+    fn name(&self) -> &str {
+        "CreatePipeInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let source = self.source_info()?;
+
+        let table = self
+            .ctx
+            .get_table(self.plan.db.as_str(), self.plan.table.as_str())?;
+        if table.as_any().downcast_ref::<FuseTable>().is_none() {
+            return Err(ErrorCode::BadOption(format!(
+                "Pipe can only ingest into FUSE tables, table {}.{} is {}",
+                self.plan.db,
+                self.plan.table,
+                table.engine()
+            )));
+        }
+
+        let sessions = self.ctx.get_sessions_manager();
+        let pipe_manager = sessions.get_pipe_manager();
+        let exists = pipe_manager.get_pipe(&self.plan.name).is_ok();
+        if !(exists && self.plan.if_not_exists) {
+            pipe_manager.add_pipe(sessions, PipeInfo {
+                name: self.plan.name.clone(),
+                db: self.plan.db.clone(),
+                table: self.plan.table.clone(),
+                source,
+            })?;
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
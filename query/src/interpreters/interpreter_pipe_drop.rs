@@ -0,0 +1,58 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::DropPipePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct DropPipeInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: DropPipePlan,
+}
+
+impl DropPipeInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: DropPipePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(DropPipeInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropPipeInterpreter {
+    fn name(&self) -> &str {
+        "DropPipeInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let pipe_manager = self.ctx.get_sessions_manager().get_pipe_manager();
+        match pipe_manager.drop_pipe(&self.plan.name) {
+            Err(cause)
+                if cause.code() == ErrorCode::UnknownPipe("").code() && self.plan.if_exists => {}
+            res => res?,
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
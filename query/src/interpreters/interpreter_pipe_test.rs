@@ -0,0 +1,84 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_create_pipe_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.a(a bigint) Engine = Fuse",
+        "create table default.b(a bigint) Engine = Memory",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // Bad sources, options and tables are rejected before the pipe is created.
+    for sql in [
+        "create pipe p as copy into default.a from s3 (topic = 't')",
+        "create pipe p as copy into default.a from kafka (topic = 't')",
+        "create pipe p as copy into default.a from kafka (brokers = 'b', topic = 't', format = 'parquet')",
+        "create pipe p as copy into default.a from kafka (brokers = 'b', topic = 't', batch_max_messages = 0)",
+        "create pipe p as copy into default.a from kafka (brokers = 'b', topic = 't', partition = 1)",
+        "create pipe p as copy into default.b from kafka (brokers = 'b', topic = 't')",
+    ] {
+        if let PlanNode::CreatePipe(plan) = PlanParser::create(ctx.clone()).build_from_sql(sql)? {
+            let executor = CreatePipeInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "CreatePipeInterpreter");
+            let r = executor.execute().await;
+            assert_eq!(ErrorCode::BadOption("").code(), r.err().unwrap().code());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drop_pipe_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::DropPipe(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("drop pipe p")?
+    {
+        let executor = DropPipeInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "DropPipeInterpreter");
+        let r = executor.execute().await;
+        assert_eq!(ErrorCode::UnknownPipe("").code(), r.err().unwrap().code());
+    } else {
+        panic!()
+    }
+
+    if let PlanNode::DropPipe(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("drop pipe if exists p")?
+    {
+        let executor = DropPipeInterpreter::try_create(ctx.clone(), plan.clone())?;
+        executor.execute().await?;
+    } else {
+        panic!()
+    }
+
+    Ok(())
+}
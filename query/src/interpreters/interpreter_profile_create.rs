@@ -0,0 +1,94 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::ProfileInfo;
+use common_planners::CreateProfilePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::Settings;
+
+pub struct CreateProfileInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CreateProfilePlan,
+}
+
+impl CreateProfileInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CreateProfilePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateProfileInterpreter { ctx, plan }))
+    }
+
+    // The names and values are checked here, not when the sessions are logged in.
+    fn check_settings(&self) -> Result<()> {
+        let settings = Settings::try_create()?;
+        for (name, value) in self.plan.settings.iter() {
+            settings
+                .update_settings(name, value.clone())
+                .map_err(|cause| {
+                    ErrorCode::IllegalSettingsProfileFormat(format!(
+                        "Invalid setting {} = {} of settings profile {}: {}",
+                        name,
+                        value,
+                        self.plan.name,
+                        cause.message()
+                    ))
+                })?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateProfileInterpreter {
+    fn name(&self) -> &str {
+        "CreateProfileInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.check_settings()?;
+
+        let user_mgr = self
+            .ctx
+            .get_sessions_manager()
+            .get_user_manager()
+            .for_tenant(&self.ctx.get_tenant());
+        match user_mgr.add_profile(ProfileInfo {
+            name: self.plan.name.clone(),
+            settings: self.plan.settings.clone(),
+        }) {
+            Err(cause)
+                if cause.code() == ErrorCode::SettingsProfileAlreadyExists("").code()
+                    && self.plan.if_not_exists => {}
+            res => {
+                res?;
+            }
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::DropProfilePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct DropProfileInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: DropProfilePlan,
+}
+
+impl DropProfileInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: DropProfilePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(DropProfileInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropProfileInterpreter {
+    fn name(&self) -> &str {
+        "DropProfileInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let user_mgr = self
+            .ctx
+            .get_sessions_manager()
+            .get_user_manager()
+            .for_tenant(&self.ctx.get_tenant());
+        match user_mgr.drop_profile(&self.plan.name) {
+            Err(cause)
+                if cause.code() == ErrorCode::UnknownSettingsProfile("").code()
+                    && self.plan.if_exists => {}
+            res => res?,
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
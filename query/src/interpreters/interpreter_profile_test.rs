@@ -0,0 +1,126 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_create_profile_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::CreateProfile(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("create settings profile etl settings max_threads = 2, query_tag = 'etl'")?
+    {
+        let executor = CreateProfileInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "CreateProfileInterpreter");
+        executor.execute().await?;
+
+        // Exists.
+        let executor = CreateProfileInterpreter::try_create(ctx.clone(), plan.clone())?;
+        let r = executor.execute().await;
+        assert_eq!(
+            ErrorCode::SettingsProfileAlreadyExists("").code(),
+            r.err().unwrap().code()
+        );
+    } else {
+        panic!()
+    }
+
+    let user_mgr = ctx
+        .get_sessions_manager()
+        .get_user_manager()
+        .for_tenant(&ctx.get_tenant());
+    let profile = user_mgr.get_profile("etl")?;
+    assert_eq!(profile.settings.get("max_threads"), Some(&"2".to_string()));
+    assert_eq!(profile.settings.get("query_tag"), Some(&"etl".to_string()));
+
+    // Unknown settings and bad values are rejected.
+    for sql in [
+        "create settings profile p1 settings no_such_setting = 1",
+        "create settings profile p2 settings max_threads = 'many'",
+    ] {
+        if let PlanNode::CreateProfile(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql(sql)?
+        {
+            let executor = CreateProfileInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let r = executor.execute().await;
+            assert_eq!(
+                ErrorCode::IllegalSettingsProfileFormat("").code(),
+                r.err().unwrap().code()
+            );
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drop_profile_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::DropProfile(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("drop settings profile p")?
+    {
+        let executor = DropProfileInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "DropProfileInterpreter");
+        let r = executor.execute().await;
+        assert_eq!(
+            ErrorCode::UnknownSettingsProfile("").code(),
+            r.err().unwrap().code()
+        );
+    } else {
+        panic!()
+    }
+
+    if let PlanNode::DropProfile(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("drop settings profile if exists p")?
+    {
+        let executor = DropProfileInterpreter::try_create(ctx.clone(), plan.clone())?;
+        executor.execute().await?;
+    } else {
+        panic!()
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_alter_user_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // The profile must exist.
+    if let PlanNode::AlterUser(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("alter user 'test' settings profile nonexistent")?
+    {
+        let executor = AlterUserInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "AlterUserInterpreter");
+        let r = executor.execute().await;
+        assert_eq!(
+            ErrorCode::UnknownSettingsProfile("").code(),
+            r.err().unwrap().code()
+        );
+    } else {
+        panic!()
+    }
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::DropQueryCachePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct DropQueryCacheInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: DropQueryCachePlan,
+}
+
+impl DropQueryCacheInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: DropQueryCachePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(DropQueryCacheInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropQueryCacheInterpreter {
+    fn name(&self) -> &str {
+        "DropQueryCacheInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let query_cache = self.ctx.get_sessions_manager().get_query_cache();
+        let dropped = match &self.plan.table {
+            None => query_cache.drop_all(),
+            Some((db, table)) => query_cache.drop_table(db, table),
+        };
+        log::info!("Dropped {} query cache entries", dropped);
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_drop_query_cache_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let query_cache = ctx.get_sessions_manager().get_query_cache();
+    query_cache.put("q1", vec!["default.a".to_string()], None, vec![]);
+    query_cache.put("q2", vec!["default.b".to_string()], None, vec![]);
+    query_cache.put("q3", vec!["system.one".to_string()], None, vec![]);
+
+    // Drop the entries of one table.
+    {
+        if let PlanNode::DropQueryCache(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("system drop query cache for table a")?
+        {
+            assert_eq!(plan.table, Some(("default".to_string(), "a".to_string())));
+            let executor = DropQueryCacheInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "DropQueryCacheInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+            assert!(query_cache.get("q1").is_none());
+            assert!(query_cache.get("q2").is_some());
+        } else {
+            panic!()
+        }
+    }
+
+    // Drop all.
+    {
+        if let PlanNode::DropQueryCache(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("system drop query cache")?
+        {
+            let executor = DropQueryCacheInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+            assert!(query_cache.entries().is_empty());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
@@ -17,27 +17,41 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::Context;
+use std::time::Instant;
 
 use common_base::tokio::macros::support::Pin;
 use common_base::tokio::macros::support::Poll;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
 use common_meta_types::NodeInfo;
+use common_planners::Expression;
+use common_planners::ExpressionVisitor;
+use common_planners::PlanVisitor;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Recursion;
 use common_planners::SelectPlan;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use futures::Stream;
 use futures::StreamExt;
+use metrics::counter;
+use metrics::histogram;
 
 use crate::api::CancelAction;
 use crate::api::FlightAction;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::interpreters::plan_estimator::PlanEstimator;
 use crate::interpreters::plan_scheduler::PlanScheduler;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::optimizers::Optimizers;
 use crate::pipelines::processors::PipelineBuilder;
 use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::QueryCache;
 
 pub struct SelectInterpreter {
     ctx: DatabendQueryContextRef,
@@ -58,11 +72,34 @@ impl Interpreter for SelectInterpreter {
 
     #[tracing::instrument(level = "info", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
     async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.check_result_rows()?;
+
+        let cached = self.query_cache_key()?;
+        if let Some(cached) = &cached {
+            let query_cache = self.ctx.get_sessions_manager().get_query_cache();
+            if let Some(entry) = query_cache.get(&cached.key) {
+                return Ok(Box::pin(DataBlockStream::create(
+                    self.select.schema(),
+                    None,
+                    entry.blocks.clone(),
+                )));
+            }
+        }
+
         // TODO: maybe panic?
         let mut scheduled = Scheduled::new();
         let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
         match self.schedule_query(&mut scheduled).await {
-            Ok(stream) => Ok(ScheduledStream::create(scheduled, stream, self.ctx.clone())),
+            Ok(stream) => {
+                let stream = ScheduledStream::create(scheduled, stream, self.ctx.clone());
+                match cached {
+                    None => Ok(stream),
+                    Some(cached) => {
+                        let query_cache = self.ctx.get_sessions_manager().get_query_cache();
+                        Ok(QueryCacheStream::create(query_cache, cached, stream))
+                    }
+                }
+            }
             Err(error) => {
                 Self::error_handler(scheduled, &self.ctx, timeout).await;
                 Err(error)
@@ -78,9 +115,100 @@ impl Interpreter for SelectInterpreter {
 type Scheduled = HashMap<String, Arc<NodeInfo>>;
 
 impl SelectInterpreter {
+    /// The key of the result in the query cache, if the result of the query can be cached:
+    /// a SELECT of the client, reading only FUSE tables, by deterministic functions.
+    fn query_cache_key(&self) -> Result<Option<CachedQuery>> {
+        let settings = self.ctx.get_settings();
+        if settings.get_use_query_cache()? == 0 {
+            return Ok(None);
+        }
+
+        let query_cache = self.ctx.get_sessions_manager().get_query_cache();
+        let query = match self.ctx.get_query_str() {
+            Some(query) if query_cache.capacity() > 0 && Self::is_result(&query) => query,
+            _ => return Ok(None),
+        };
+
+        let mut visitor = QueryCacheVisitor::default();
+        visitor.visit_plan_node(&self.select.input)?;
+        if !visitor.cacheable || visitor.tables.is_empty() {
+            return Ok(None);
+        }
+
+        let tables = visitor
+            .tables
+            .iter()
+            .map(|(table, _)| table.clone())
+            .collect::<Vec<_>>();
+        let snapshots = visitor
+            .tables
+            .iter()
+            .map(|(table, snapshot)| format!("{}@{}", table, snapshot))
+            .collect::<Vec<_>>();
+        let key = QueryCache::key(
+            &self.ctx.get_tenant(),
+            &self.ctx.get_current_database(),
+            &query,
+            &snapshots,
+            &settings,
+        );
+        Ok(Some(CachedQuery {
+            key,
+            tables,
+            snapshot: snapshots.join(", "),
+        }))
+    }
+
+    /// Applies `large_result_mode` to a result estimated over `large_result_rows` rows, before
+    /// the query runs. A query with a LIMIT asks for its rows, it is not checked.
+    fn check_result_rows(&self) -> Result<()> {
+        let settings = self.ctx.get_settings();
+        let max_rows = settings.get_large_result_rows()?;
+        if max_rows == 0 || PlanEstimator::has_limit(&self.select.input) {
+            return Ok(());
+        }
+        match self.ctx.get_query_str() {
+            Some(query) if Self::is_result(&query) => {}
+            _ => return Ok(()),
+        }
+
+        let rows = match PlanEstimator::estimate_rows(&self.select.input) {
+            Some(rows) if rows > max_rows => rows,
+            _ => return Ok(()),
+        };
+        let mode = settings.get_large_result_mode()?;
+        match mode.to_lowercase().as_str() {
+            "warn" => {
+                let warning = format!(
+                    "The result is estimated at {} rows, over large_result_rows {}, add a LIMIT to return less",
+                    rows, max_rows
+                );
+                log::warn!("{}, query id: {}", warning, self.ctx.get_id());
+                self.ctx.attach_query_warning(warning);
+                Ok(())
+            }
+            "throw" => Err(ErrorCode::LargeResultNotAllowed(format!(
+                "The result is estimated at {} rows, over large_result_rows {}. Add a LIMIT, or SET large_result_mode = 'warn' to run it anyway",
+                rows, max_rows
+            ))),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Invalid large_result_mode: {}, expect 'warn' or 'throw'",
+                mode
+            ))),
+        }
+    }
+
+    /// Whether the query of the client returns the result of the plan.
+    /// The SELECT of INSERT SELECT, CREATE TABLE AS SELECT and such is not a result.
+    fn is_result(query: &str) -> bool {
+        let lowercase = query.trim_start().to_lowercase();
+        lowercase.starts_with("select") || lowercase.starts_with("with")
+    }
+
     async fn schedule_query(&self, scheduled: &mut Scheduled) -> Result<SendableDataBlockStream> {
         let optimized_plan = Optimizers::create(self.ctx.clone()).optimize(&self.select.input)?;
 
+        let start = Instant::now();
         let scheduler = PlanScheduler::try_create(self.ctx.clone())?;
         let scheduled_tasks = scheduler.reschedule(&optimized_plan)?;
         let remote_stage_actions = scheduled_tasks.get_tasks()?;
@@ -94,15 +222,30 @@ impl SelectInterpreter {
 
             executing_action.await?;
             scheduled.insert(node.id.clone(), node.clone());
+            counter!(super::metrics::METRIC_PIPELINE_REMOTE_STAGES, 1);
         }
 
         let pipeline_builder = PipelineBuilder::create(self.ctx.clone());
         let mut in_local_pipeline = pipeline_builder.build(&scheduled_tasks.get_local_task())?;
+        let processors = in_local_pipeline
+            .pipes()
+            .iter()
+            .map(|pipe| pipe.nums())
+            .sum::<usize>();
+        counter!(
+            super::metrics::METRIC_PIPELINE_PROCESSORS,
+            processors as u64
+        );
+        histogram!(
+            super::metrics::METRIC_PIPELINE_SCHEDULE_USEDTIME,
+            start.elapsed()
+        );
         in_local_pipeline.execute().await
     }
 
     async fn error_handler(scheduled: Scheduled, context: &DatabendQueryContextRef, timeout: u64) {
         let query_id = context.get_id();
+        let tenant = context.get_tenant();
         let config = context.get_config();
         let cluster = context.get_cluster();
 
@@ -116,7 +259,8 @@ impl SelectInterpreter {
                     );
                 }
                 Ok(mut flight_client) => {
-                    let cancel_action = Self::cancel_flight_action(query_id.clone());
+                    let cancel_action =
+                        Self::cancel_flight_action(query_id.clone(), tenant.clone());
                     let executing_action = flight_client.execute_action(cancel_action, timeout);
                     if let Err(cause) = executing_action.await {
                         log::error!(
@@ -130,8 +274,8 @@ impl SelectInterpreter {
         }
     }
 
-    fn cancel_flight_action(query_id: String) -> FlightAction {
-        FlightAction::CancelAction(CancelAction { query_id })
+    fn cancel_flight_action(query_id: String, tenant: String) -> FlightAction {
+        FlightAction::CancelAction(CancelAction { query_id, tenant })
     }
 }
 
@@ -188,3 +332,134 @@ impl Stream for ScheduledStream {
         })
     }
 }
+
+struct CachedQuery {
+    key: String,
+    tables: Vec<String>,
+    snapshot: String,
+}
+
+/// Collects the tables read by a plan, and whether its result can be cached.
+struct QueryCacheVisitor {
+    cacheable: bool,
+    tables: Vec<(String, String)>,
+}
+
+impl Default for QueryCacheVisitor {
+    fn default() -> Self {
+        QueryCacheVisitor {
+            cacheable: true,
+            tables: vec![],
+        }
+    }
+}
+
+impl PlanVisitor for QueryCacheVisitor {
+    fn visit_expr(&mut self, expr: &Expression) -> Result<()> {
+        match expr {
+            Expression::Subquery { query_plan, .. } => self.visit_subquery_plan(query_plan),
+            Expression::ScalarSubquery { query_plan, .. } => self.visit_subquery_plan(query_plan),
+            _ => {
+                let visitor = expr.accept(DeterministicVisitor(true))?;
+                self.cacheable &= visitor.0;
+                Ok(())
+            }
+        }
+    }
+
+    fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
+        let table_info = &plan.table_info;
+        // The other engines do not version their data by snapshots.
+        if !table_info.engine.eq_ignore_ascii_case("FUSE") {
+            self.cacheable = false;
+            return Ok(());
+        }
+
+        let table = format!("{}.{}", table_info.db, table_info.name);
+        let snapshot = table_info
+            .options
+            .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+            .cloned()
+            .unwrap_or_default();
+        self.tables.push((table, snapshot));
+        Ok(())
+    }
+}
+
+/// Whether all the functions of an expression return the same result for the same arguments.
+struct DeterministicVisitor(bool);
+
+impl ExpressionVisitor for DeterministicVisitor {
+    fn pre_visit(self, expr: &Expression) -> Result<Recursion<Self>> {
+        match expr {
+            Expression::ScalarFunction { op, .. } => {
+                let factory = FunctionFactory::instance();
+                // Functions of the session, such as SQL UDFs, are not in the factory.
+                if factory.check(op) && !factory.get_features(op)?.is_deterministic {
+                    return Ok(Recursion::Stop(DeterministicVisitor(false)));
+                }
+                Ok(Recursion::Continue(self))
+            }
+            _ => Ok(Recursion::Continue(self)),
+        }
+    }
+}
+
+/// Passes the blocks of a query through, and keeps its result in the query cache once all the
+/// blocks are read. Results larger than the cache are not kept.
+struct QueryCacheStream {
+    query_cache: Arc<QueryCache>,
+    cached: Option<CachedQuery>,
+    blocks: Vec<DataBlock>,
+    bytes: u64,
+    inner: SendableDataBlockStream,
+}
+
+impl QueryCacheStream {
+    pub fn create(
+        query_cache: Arc<QueryCache>,
+        cached: CachedQuery,
+        inner: SendableDataBlockStream,
+    ) -> SendableDataBlockStream {
+        Box::pin(QueryCacheStream {
+            query_cache,
+            cached: Some(cached),
+            blocks: vec![],
+            bytes: 0,
+            inner,
+        })
+    }
+}
+
+impl Stream for QueryCacheStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let polled = self.inner.poll_next_unpin(cx);
+        match &polled {
+            Poll::Ready(Some(Ok(block))) if self.cached.is_some() => {
+                self.bytes += block.memory_size() as u64;
+                if self.bytes > self.query_cache.capacity() {
+                    self.cached = None;
+                    self.blocks = vec![];
+                } else {
+                    self.blocks.push(block.clone());
+                }
+            }
+            Poll::Ready(Some(Err(_))) => {
+                self.cached = None;
+                self.blocks = vec![];
+            }
+            Poll::Ready(None) => {
+                if let Some(cached) = self.cached.take() {
+                    let blocks = std::mem::take(&mut self.blocks);
+                    let snapshot = Some(cached.snapshot);
+                    self.query_cache
+                        .put(cached.key, cached.tables, snapshot, blocks);
+                }
+            }
+            _ => {}
+        }
+        polled
+    }
+}
@@ -40,6 +40,24 @@ async fn test_setting_interpreter() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_setting_interpreter_string() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::SetVariable(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("set query_tag='etl'")?
+    {
+        let executor = SettingInterpreter::try_create(ctx.clone(), plan)?;
+        let mut stream = executor.execute().await?;
+        while let Some(_block) = stream.next().await {}
+    } else {
+        panic!()
+    }
+    assert_eq!(ctx.get_settings().get_query_tag()?, "etl");
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_setting_interpreter_error() -> Result<()> {
     let ctx = crate::tests::try_create_context()?;
@@ -0,0 +1,82 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::series::Series;
+use common_exception::Result;
+use common_planners::ShowCreateDatabasePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use log::debug;
+
+use crate::catalogs::DB_OPT_KEY_TENANT;
+use crate::datasources::common::is_secret_storage_option;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct ShowCreateDatabaseInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: ShowCreateDatabasePlan,
+}
+
+impl ShowCreateDatabaseInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: ShowCreateDatabasePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(ShowCreateDatabaseInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for ShowCreateDatabaseInterpreter {
+    fn name(&self) -> &str {
+        "ShowCreateDatabaseInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let database = self.ctx.get_database(&self.plan.db)?;
+        let name = database.name();
+
+        let mut database_info = format!("CREATE DATABASE `{}`", name);
+        // The tenant is given by the login, the statement is replayed by the one of the session.
+        let options = database.options();
+        let mut keys = options
+            .keys()
+            .filter(|key| key.as_str() != DB_OPT_KEY_TENANT)
+            .collect::<Vec<_>>();
+        keys.sort();
+        for key in keys {
+            let value = match is_secret_storage_option(key) {
+                true => "******",
+                false => options[key].as_str(),
+            };
+            let option = format!(" {}='{}'", key.to_uppercase(), value);
+            database_info.push_str(option.as_str());
+        }
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![name.as_bytes()]),
+            Series::new(vec![database_info.into_bytes()]),
+        ]);
+        debug!("Show create database executor result: {:?}", block);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn interpreter_show_create_database_test() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create database.
+    {
+        if let PlanNode::CreateDatabase(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create database db1 storage_type = 's3', storage_s3_bucket = 'bucket', storage_s3_secret_access_key = 'sk'")?
+        {
+            let executor = CreateDatabaseInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Show create database.
+    {
+        if let PlanNode::ShowCreateDatabase(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("show create database db1")?
+        {
+            let executor = ShowCreateDatabaseInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "ShowCreateDatabaseInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+----------+----------------------------------------------------------------------------------------------------------+",
+                "| Database | Create Database                                                                                          |",
+                "+----------+----------------------------------------------------------------------------------------------------------+",
+                "| db1      | CREATE DATABASE `db1` STORAGE_S3_BUCKET='bucket' STORAGE_S3_SECRET_ACCESS_KEY='******' STORAGE_TYPE='s3' |",
+                "+----------+----------------------------------------------------------------------------------------------------------+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // Unknown database.
+    {
+        if let PlanNode::ShowCreateDatabase(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("show create database db2")?
+        {
+            let executor = ShowCreateDatabaseInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert!(executor.execute().await.is_err());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
@@ -27,6 +27,12 @@ use common_streams::SendableDataBlockStream;
 use log::debug;
 
 use crate::catalogs::Catalog;
+use crate::datasources::common::TableCollations;
+use crate::datasources::common::TableConstraints;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_COLLATIONS;
+use crate::datasources::common::TBL_OPT_KEY_PRIMARY_KEY;
+use crate::datasources::common::TBL_OPT_KEY_UNIQUE_KEYS;
+use crate::datasources::table::view::ViewTable;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -52,22 +58,84 @@ impl Interpreter for ShowCreateTableInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let catalog = self.ctx.get_catalog();
-
-        let table = catalog.get_table(&self.plan.db, &self.plan.table)?;
+        let table = self.ctx.get_table(&self.plan.db, &self.plan.table)?;
+        let temporary = self
+            .ctx
+            .get_temporary_tables()
+            .get_table(&self.plan.db, &self.plan.table)
+            .is_some();
 
         let name = table.name();
         let engine = table.engine();
         let schema = table.schema();
 
-        let mut table_info = format!("CREATE TABLE `{}` (\n", name);
+        if let Some(view) = table.as_any().downcast_ref::<ViewTable>() {
+            let view_info = format!("CREATE VIEW `{}` AS {}", name, view.query());
+            return Self::show_create_stream(name, view_info);
+        }
+
+        let mut table_info = match temporary {
+            true => format!("CREATE TEMPORARY TABLE `{}` (\n", name),
+            false => format!("CREATE TABLE `{}` (\n", name),
+        };
+        let collations = TableCollations::from_options(&table.get_table_info().options);
         for field in schema.fields().iter() {
-            let column = format!("  `{}` {},\n", field.name(), field.data_type());
+            let column = match collations.column_collation_name(field.name()) {
+                Some(collation) => format!(
+                    "  `{}` {} COLLATE {},\n",
+                    field.name(),
+                    field.data_type(),
+                    collation
+                ),
+                None => format!("  `{}` {},\n", field.name(), field.data_type()),
+            };
             table_info.push_str(column.as_str());
         }
+        let constraints = TableConstraints::from_options(&table.get_table_info().options);
+        let format_key = |key: &Vec<String>| {
+            key.iter()
+                .map(|column| format!("`{}`", column))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if let Some(primary_key) = &constraints.primary_key {
+            let constraint = format!("  PRIMARY KEY ({}),\n", format_key(primary_key));
+            table_info.push_str(constraint.as_str());
+        }
+        for unique_key in constraints.unique_keys.iter() {
+            let constraint = format!("  UNIQUE ({}),\n", format_key(unique_key));
+            table_info.push_str(constraint.as_str());
+        }
         let table_engine = format!(") ENGINE={}", engine);
         table_info.push_str(table_engine.as_str());
 
+        // The options of the engine, so that the table can be created again by the statement.
+        // The constraints and the column collations are already in the column list.
+        let options = &table.get_table_info().options;
+        let mut defs = self.ctx.get_catalog().get_table_options(engine);
+        defs.retain(|def| {
+            !def.internal
+                && def.name != TBL_OPT_KEY_PRIMARY_KEY
+                && def.name != TBL_OPT_KEY_UNIQUE_KEYS
+                && def.name != TBL_OPT_KEY_COLUMN_COLLATIONS
+        });
+        defs.sort_by_key(|def| def.name);
+        for def in defs.iter() {
+            let value = match options.get(def.name) {
+                Some(_) if def.secret => "******",
+                Some(value) => value.as_str(),
+                None => continue,
+            };
+            let option = format!(" {}='{}'", def.name.to_uppercase(), value);
+            table_info.push_str(option.as_str());
+        }
+
+        Self::show_create_stream(name, table_info)
+    }
+}
+
+impl ShowCreateTableInterpreter {
+    fn show_create_stream(name: &str, table_info: String) -> Result<SendableDataBlockStream> {
         let show_fields = vec![
             DataField::new("Table", DataType::String, false),
             DataField::new("Create Table", DataType::String, false),
@@ -28,7 +28,7 @@ async fn interpreter_show_create_table_test() -> Result<()> {
     // Create table.
     {
         if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
-            .build_from_sql("create table default.a(a bigint, b int, c varchar(255), d smallint, e Date ) Engine = Null")?
+            .build_from_sql("create table default.a(a bigint primary key, b int, c varchar(255) collate utf8_general_ci, d smallint, e Date, unique (b, d) ) Engine = Null")?
         {
             let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
             let _ = executor.execute().await?;
@@ -45,17 +45,52 @@ async fn interpreter_show_create_table_test() -> Result<()> {
             let stream = executor.execute().await?;
             let result = stream.try_collect::<Vec<_>>().await?;
             let expected = vec![
-                "+-------+--------------------+",
-                "| Table | Create Table       |",
-                "+-------+--------------------+",
-                "| a     | CREATE TABLE `a` ( |",
-                "|       |   `a` Int64,       |",
-                "|       |   `b` Int32,       |",
-                "|       |   `c` String,      |",
-                "|       |   `d` Int16,       |",
-                "|       |   `e` Date16,      |",
-                "|       | ) ENGINE=Null      |",
-                "+-------+--------------------+",
+                "+-------+---------------------------------------+",
+                "| Table | Create Table                          |",
+                "+-------+---------------------------------------+",
+                "| a     | CREATE TABLE `a` (                    |",
+                "|       |   `a` Int64,                          |",
+                "|       |   `b` Int32,                          |",
+                "|       |   `c` String COLLATE utf8_general_ci, |",
+                "|       |   `d` Int16,                          |",
+                "|       |   `e` Date16,                         |",
+                "|       |   PRIMARY KEY (`a`),                  |",
+                "|       |   UNIQUE (`b`, `d`),                  |",
+                "|       | ) ENGINE=Null                         |",
+                "+-------+---------------------------------------+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // Create table with options.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.b(a bigint) Engine = Memory overflow = 'spill', collation = 'utf8_bin', max_bytes = 1024")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Show create table with options.
+    {
+        if let PlanNode::ShowCreateTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("show create table b")?
+        {
+            let executor = ShowCreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+-------+------------------------------------------------------------------------+",
+                "| Table | Create Table                                                           |",
+                "+-------+------------------------------------------------------------------------+",
+                "| b     | CREATE TABLE `b` (                                                     |",
+                "|       |   `a` Int64,                                                           |",
+                "|       | ) ENGINE=Memory COLLATION='utf8_bin' MAX_BYTES='1024' OVERFLOW='spill' |",
+                "+-------+------------------------------------------------------------------------+",
             ];
             common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
         } else {
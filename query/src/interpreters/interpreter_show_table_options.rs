@@ -0,0 +1,86 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::series::Series;
+use common_exception::Result;
+use common_planners::ShowTableOptionsPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use log::debug;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct ShowTableOptionsInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: ShowTableOptionsPlan,
+}
+
+impl ShowTableOptionsInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: ShowTableOptionsPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(ShowTableOptionsInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for ShowTableOptionsInterpreter {
+    fn name(&self) -> &str {
+        "ShowTableOptionsInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let table = self.ctx.get_table(&self.plan.db, &self.plan.table)?;
+        let options = &table.get_table_info().options;
+        let defs = self.ctx.get_catalog().get_table_options(table.engine());
+
+        let mut names = vec![];
+        let mut values = vec![];
+        let mut types = vec![];
+        let mut alterables = vec![];
+        let mut descriptions = vec![];
+        for def in defs.iter().filter(|def| !def.internal) {
+            let value = match options.get(def.name) {
+                Some(_) if def.secret => "******".to_string(),
+                Some(value) => value.clone(),
+                None => "".to_string(),
+            };
+            names.push(def.name.as_bytes().to_vec());
+            values.push(value.into_bytes());
+            types.push(def.typ.to_string().into_bytes());
+            alterables.push(def.alterable.to_string().into_bytes());
+            descriptions.push(def.description.as_bytes().to_vec());
+        }
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(names),
+            Series::new(values),
+            Series::new(types),
+            Series::new(alterables),
+            Series::new(descriptions),
+        ]);
+        debug!("Show table options executor result: {:?}", block);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::SetStoragePolicyPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::configs::StorageConfig;
+use crate::datasources::common::cold_storage_config_with_options;
+use crate::datasources::common::COLD_STORAGE_OPT_KEY_PREFIX;
+use crate::datasources::table::fuse::util::parse_lifecycle_duration;
+use crate::datasources::table::fuse::util::TBL_OPT_KEY_HOT_TO_COLD_AFTER;
+use crate::datasources::table::fuse::FuseTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct SetStoragePolicyInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: SetStoragePolicyPlan,
+}
+
+impl SetStoragePolicyInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: SetStoragePolicyPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(SetStoragePolicyInterpreter { ctx, plan }))
+    }
+
+    fn check_options(&self) -> Result<()> {
+        for (key, value) in self.plan.options.iter() {
+            if key == TBL_OPT_KEY_HOT_TO_COLD_AFTER {
+                parse_lifecycle_duration(value)?;
+            } else if !key.starts_with(COLD_STORAGE_OPT_KEY_PREFIX) {
+                return Err(ErrorCode::BadOption(format!(
+                    "Unknown storage policy option: {}",
+                    key
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for SetStoragePolicyInterpreter {
+    fn name(&self) -> &str {
+        "SetStoragePolicyInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.check_options()?;
+        self.ctx.check_tenant_storage_options(&self.plan.options)?;
+
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        let table = self.ctx.get_table(db, table_name)?;
+        if table.as_any().downcast_ref::<FuseTable>().is_none() {
+            return Err(ErrorCode::BadOption(format!(
+                "Storage policy is only supported by FUSE tables, table {}.{} is {}",
+                db,
+                table_name,
+                table.engine()
+            )));
+        }
+
+        let mut options = table.get_table_info().options.clone();
+        options.extend(self.plan.options.clone());
+        if cold_storage_config_with_options(&StorageConfig::default(), &options)?.is_none() {
+            return Err(ErrorCode::BadOption(format!(
+                "Table {}.{} has no cold storage, set the cold_storage_* options first",
+                db, table_name
+            )));
+        }
+
+        let catalog = self.ctx.get_catalog();
+        let table_id = table.get_id();
+        for (key, value) in self.plan.options.iter() {
+            // Each upsert bumps the table version, so fetch the latest one every time.
+            let table = catalog.get_table_by_id(table_id, None)?;
+            catalog.upsert_table_option(
+                table_id,
+                table.get_table_info().version,
+                key.clone(),
+                value.clone(),
+            )?;
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::*;
+use crate::sql::*;
+
+async fn execute_sql(ctx: &crate::sessions::DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_storage_policy_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    execute_sql(&ctx, "create table default.a(a bigint) Engine = Fuse").await?;
+    execute_sql(&ctx, "create table default.b(a bigint) Engine = Memory").await?;
+
+    let sql = "alter table default.a set storage_policy hot_to_cold_after = 30d, cold_storage_disk_data_path = 'cold'";
+    if let PlanNode::SetStoragePolicy(plan) = PlanParser::create(ctx.clone()).build_from_sql(sql)? {
+        assert_eq!(
+            plan.options.get("hot_to_cold_after"),
+            Some(&"30d".to_string())
+        );
+        let executor = SetStoragePolicyInterpreter::try_create(ctx.clone(), plan.clone())?;
+        assert_eq!(executor.name(), "SetStoragePolicyInterpreter");
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec!["++", "++"];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+        let table = ctx.get_catalog().get_table("default", "a")?;
+        let options = &table.get_table_info().options;
+        assert_eq!(options.get("hot_to_cold_after"), Some(&"30d".to_string()));
+        assert!(options.contains_key("cold_storage_disk_data_path"));
+    } else {
+        panic!()
+    }
+
+    // Bad durations, unknown options, paths out of the server data path and non-FUSE tables
+    // are rejected.
+    for sql in [
+        "alter table default.a set storage_policy hot_to_cold_after = 30y",
+        "alter table default.a set storage_policy cold_after = 30d",
+        "alter table default.a set storage_policy hot_to_cold_after = 30d, cold_storage_disk_data_path = '/tmp'",
+        "alter table default.b set storage_policy hot_to_cold_after = 30d, cold_storage_type = 'disk'",
+    ] {
+        let r = execute_sql(&ctx, sql).await;
+        assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+    }
+
+    Ok(())
+}
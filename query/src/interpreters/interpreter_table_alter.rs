@@ -0,0 +1,237 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AlterTableOperation;
+use common_planners::AlterTablePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::common::ColumnDefaults;
+use crate::datasources::common::ColumnIds;
+use crate::datasources::common::TableConstraints;
+use crate::datasources::common::SCHEMA_META_KEY_COLUMN_IDS;
+use crate::datasources::common::TBL_OPT_KEY_COLUMN_DEFAULTS;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::memory::memory_table::MemoryTable;
+use crate::datasources::table::null::null_table::NullTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct AlterTableInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: AlterTablePlan,
+}
+
+impl AlterTableInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: AlterTablePlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AlterTableInterpreter { ctx, plan }))
+    }
+
+    /// The engines which read the blocks written before by the column names.
+    fn check_engine(&self, table: &dyn Table) -> Result<()> {
+        let any = table.as_any();
+        if any.downcast_ref::<FuseTable>().is_none()
+            && any.downcast_ref::<MemoryTable>().is_none()
+            && any.downcast_ref::<NullTable>().is_none()
+        {
+            return Err(ErrorCode::UnImplement(format!(
+                "ALTER TABLE ADD/DROP COLUMN is only supported by FUSE, MEMORY and NULL tables, table {}.{} is {}",
+                self.plan.db,
+                self.plan.table,
+                table.engine()
+            )));
+        }
+        Ok(())
+    }
+
+    fn new_schema(&self, table: &dyn Table) -> Result<DataSchema> {
+        let schema = table.schema();
+        let mut fields = schema.fields().clone();
+        let mut ids = ColumnIds::from_meta(schema.meta())?;
+        match &self.plan.operation {
+            AlterTableOperation::AddColumn { field, .. } => {
+                if schema.column_with_name(field.name()).is_some() {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Column {} already exists in table {}.{}",
+                        field.name(),
+                        self.plan.db,
+                        self.plan.table
+                    )));
+                }
+                fields.push(field.clone());
+                ids.add_column(field.name());
+            }
+            AlterTableOperation::DropColumn(name) => {
+                let (idx, _) = schema.column_with_name(name).ok_or_else(|| {
+                    ErrorCode::BadArguments(format!(
+                        "Unknown column {} in table {}.{}",
+                        name, self.plan.db, self.plan.table
+                    ))
+                })?;
+                if fields.len() == 1 {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Cannot drop the only column {} of table {}.{}",
+                        name, self.plan.db, self.plan.table
+                    )));
+                }
+
+                let constraints = TableConstraints::from_options(&table.get_table_info().options);
+                let in_key = constraints
+                    .primary_key
+                    .iter()
+                    .chain(constraints.unique_keys.iter())
+                    .any(|key| key.contains(name));
+                if in_key {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Cannot drop column {} of table {}.{}, it is in a PRIMARY KEY or UNIQUE constraint",
+                        name, self.plan.db, self.plan.table
+                    )));
+                }
+                fields.remove(idx);
+                ids.drop_column(name);
+            }
+            AlterTableOperation::MaterializeColumn(_) => {}
+        }
+
+        let mut meta = schema.meta().clone();
+        meta.insert(SCHEMA_META_KEY_COLUMN_IDS.to_string(), ids.to_json()?);
+        Ok(DataSchema::new_from(fields, meta))
+    }
+
+    /// The defaults of the columns after the columns are altered. A column added again after
+    /// it is dropped does not inherit the default of the dropped one.
+    fn new_defaults(&self, table: &dyn Table) -> Result<ColumnDefaults> {
+        let mut defaults = ColumnDefaults::from_options(&table.get_table_info().options)?;
+        match &self.plan.operation {
+            AlterTableOperation::AddColumn {
+                field,
+                default: Some(expr),
+            } => {
+                let value = ColumnDefaults::eval_default(field, expr)?;
+                defaults.columns.insert(field.name().clone(), value);
+            }
+            AlterTableOperation::AddColumn {
+                field,
+                default: None,
+            } => {
+                defaults.columns.remove(field.name());
+            }
+            AlterTableOperation::DropColumn(name) => {
+                defaults.columns.remove(name);
+            }
+            AlterTableOperation::MaterializeColumn(_) => {}
+        }
+        Ok(defaults)
+    }
+
+    /// Writes the default of the column into the blocks written before the column was added,
+    /// so that they no longer depend on the default kept in the table options.
+    async fn materialize_column(&self, table: &dyn Table, name: &str) -> Result<()> {
+        let fuse_table = match table.as_any().downcast_ref::<FuseTable>() {
+            Some(fuse_table) => fuse_table,
+            None => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "ALTER TABLE MATERIALIZE COLUMN only supports FUSE tables, table {}.{} is {}",
+                    self.plan.db,
+                    self.plan.table,
+                    table.engine()
+                )))
+            }
+        };
+        if table.schema().column_with_name(name).is_none() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Unknown column {} in table {}.{}",
+                name, self.plan.db, self.plan.table
+            )));
+        }
+
+        let io_ctx = Arc::new(self.ctx.get_single_node_table_io_context()?);
+        fuse_table.do_materialize_column(io_ctx, name).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterTableInterpreter {
+    fn name(&self) -> &str {
+        "AlterTableInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        if self
+            .ctx
+            .get_temporary_tables()
+            .get_table(db, table_name)
+            .is_some()
+        {
+            return Err(ErrorCode::UnImplement(format!(
+                "ALTER TABLE is not supported by temporary table {}.{}",
+                db, table_name
+            )));
+        }
+
+        self.ctx.get_database(db)?;
+
+        // The tables of the context may be cached before the columns were altered.
+        let catalog = self.ctx.get_catalog();
+        let table = catalog.get_table(db, table_name)?;
+        if let AlterTableOperation::MaterializeColumn(name) = &self.plan.operation {
+            self.materialize_column(table.as_ref(), name).await?;
+            return Ok(Box::pin(DataBlockStream::create(
+                self.plan.schema(),
+                None,
+                vec![],
+            )));
+        }
+
+        self.check_engine(table.as_ref())?;
+        let schema = self.new_schema(table.as_ref())?;
+
+        // The defaults are kept before the column is added, so that the column is never read
+        // without its default.
+        let mut version = table.get_table_info().version;
+        let defaults = self.new_defaults(table.as_ref())?;
+        if defaults != ColumnDefaults::from_options(&table.get_table_info().options)? {
+            catalog.upsert_table_option(
+                table.get_id(),
+                version,
+                TBL_OPT_KEY_COLUMN_DEFAULTS.to_string(),
+                defaults.to_option()?,
+            )?;
+            version = catalog.get_table(db, table_name)?.get_table_info().version;
+        }
+
+        // The blocks are kept as they are, the readers match their columns by name and id.
+        catalog.update_table_schema(table.get_id(), version, Arc::new(schema))?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::catalogs::Catalog;
+use crate::datasources::common::ColumnDefaults;
+use crate::interpreters::*;
+use crate::sql::*;
+
+async fn execute_sql(ctx: &crate::sessions::DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_alter_table_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    execute_sql(
+        &ctx,
+        "create table default.a(a bigint, b String) Engine = Memory",
+    )
+    .await?;
+    execute_sql(&ctx, "create table default.c(a bigint) Engine = Memory").await?;
+    execute_sql(
+        &ctx,
+        "create table default.d(a bigint) Engine = Parquet location = 'foo.parquet'",
+    )
+    .await?;
+
+    // Add column.
+    {
+        let plan = PlanParser::create(ctx.clone())
+            .build_from_sql("alter table default.a add column c Int32")?;
+        if let PlanNode::AlterTable(plan) = plan {
+            assert_eq!(plan.operation, AlterTableOperation::AddColumn {
+                field: DataField::new("c", DataType::Int32, true),
+                default: None,
+            });
+            let executor = AlterTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "AlterTableInterpreter");
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+
+        let table = ctx.get_catalog().get_table("default", "a")?;
+        let names = table.schema().fields().iter().map(|f| f.name().clone());
+        assert_eq!(names.collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    }
+
+    // Drop column.
+    {
+        execute_sql(&ctx, "alter table default.a drop column b").await?;
+
+        let table = ctx.get_catalog().get_table("default", "a")?;
+        let names = table.schema().fields().iter().map(|f| f.name().clone());
+        assert_eq!(names.collect::<Vec<_>>(), vec!["a", "c"]);
+    }
+
+    // Add column with a default, it is evaluated once and kept until the column is dropped.
+    {
+        execute_sql(
+            &ctx,
+            "alter table default.a add column d Int32 default 1 + 1",
+        )
+        .await?;
+
+        let table = ctx.get_catalog().get_table("default", "a")?;
+        let defaults = ColumnDefaults::from_options(&table.get_table_info().options)?;
+        let expected = [("d".to_string(), DataValue::Int32(Some(2)))];
+        assert_eq!(defaults.columns, expected.into_iter().collect());
+
+        execute_sql(&ctx, "alter table default.a drop column d").await?;
+        let table = ctx.get_catalog().get_table("default", "a")?;
+        let defaults = ColumnDefaults::from_options(&table.get_table_info().options)?;
+        assert!(defaults.columns.is_empty());
+    }
+
+    // Duplicate or unknown columns, and the only column of a table are rejected.
+    for sql in [
+        "alter table default.c add column a Int32",
+        "alter table default.c drop column x",
+        "alter table default.c drop column a",
+        "alter table default.c add column b Int32 default a + 1",
+        "alter table default.c materialize column a",
+    ] {
+        let r = execute_sql(&ctx, sql).await;
+        assert_eq!(ErrorCode::BadArguments("").code(), r.unwrap_err().code());
+    }
+
+    // The engines which cannot read the blocks written before are rejected.
+    let r = execute_sql(&ctx, "alter table default.d add column b Int32").await;
+    assert_eq!(ErrorCode::UnImplement("").code(), r.unwrap_err().code());
+
+    Ok(())
+}
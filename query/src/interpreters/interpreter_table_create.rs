@@ -14,12 +14,18 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::CreateTablePlan;
+use common_planners::DropTablePlan;
+use common_planners::InsertIntoPlan;
+use common_planners::PlanNode;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::interpreters::InsertIntoInterpreter;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -36,6 +42,51 @@ impl CreateTableInterpreter {
     ) -> Result<InterpreterPtr> {
         Ok(Arc::new(CreateTableInterpreter { ctx, plan }))
     }
+
+    fn get_table(&self) -> Result<Arc<dyn Table>> {
+        let (db, table) = (self.plan.db.as_str(), self.plan.table.as_str());
+        match self.plan.temporary {
+            true => self
+                .ctx
+                .get_temporary_tables()
+                .get_table(db, table)
+                .ok_or_else(|| {
+                    ErrorCode::UnknownTable(format!("Unknown table: '{}.{}'", db, table))
+                }),
+            false => self.ctx.get_catalog().get_table(db, table),
+        }
+    }
+
+    fn drop_table(&self) -> Result<()> {
+        let (db, table) = (self.plan.db.as_str(), self.plan.table.as_str());
+        match self.plan.temporary {
+            true => {
+                self.ctx.get_temporary_tables().drop_table(db, table);
+                Ok(())
+            }
+            false => self.ctx.get_catalog().drop_table(DropTablePlan {
+                if_exists: true,
+                db: db.to_string(),
+                table: table.to_string(),
+            }),
+        }
+    }
+
+    async fn insert_select(&self, select_plan: &PlanNode) -> Result<()> {
+        let table = self.get_table()?;
+        let insert_plan = InsertIntoPlan {
+            db_name: self.plan.db.clone(),
+            tbl_name: self.plan.table.clone(),
+            tbl_id: table.get_id(),
+            schema: table.schema(),
+            select_plan: Some(Box::new(select_plan.clone())),
+            input_stream: InsertIntoPlan::empty_stream(),
+        };
+        InsertIntoInterpreter::try_create(self.ctx.clone(), insert_plan)?
+            .execute()
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -45,8 +96,38 @@ impl Interpreter for CreateTableInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let catalog = self.ctx.get_catalog();
-        catalog.create_table(self.plan.clone())?;
+        self.ctx.check_tenant_storage_options(&self.plan.options)?;
+
+        // The rows are not appended to an existing table.
+        let as_select = match &self.plan.as_select {
+            Some(_) if self.plan.if_not_exists && self.get_table().is_ok() => None,
+            as_select => as_select.as_ref(),
+        };
+
+        let plan = CreateTablePlan {
+            as_select: None,
+            ..self.plan.clone()
+        };
+        match self.plan.temporary {
+            true => {
+                self.ctx.get_database(&self.plan.db)?;
+                let temporary_tables = self.ctx.get_temporary_tables();
+                temporary_tables.create_table(plan)?;
+            }
+            false => {
+                self.ctx.get_database(&self.plan.db)?;
+                let catalog = self.ctx.get_catalog();
+                catalog.create_table(plan)?;
+            }
+        }
+
+        // The table is created only if the rows are all appended.
+        if let Some(select_plan) = as_select {
+            if let Err(cause) = self.insert_select(select_plan).await {
+                self.drop_table()?;
+                return Err(cause);
+            }
+        }
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema.clone(),
@@ -14,11 +14,14 @@
 
 use common_base::tokio;
 use common_datavalues::DataType;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use futures::stream::StreamExt;
+use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
+use crate::catalogs::Catalog;
 use crate::interpreters::*;
 use crate::sql::*;
 
@@ -46,3 +49,200 @@ async fn test_create_table_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_temporary_table_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create temporary table.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "create temporary table default.t(a bigint, b varchar) Engine = Memory",
+        )? {
+            assert!(plan.temporary);
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+    }
+
+    // The metadata lives in the session only.
+    assert!(ctx
+        .get_temporary_tables()
+        .get_table("default", "t")
+        .is_some());
+    assert!(ctx.get_catalog().get_table("default", "t").is_err());
+
+    // Insert into.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.t values(1, 'x'), (2, 'y')")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+    }
+
+    // Select.
+    {
+        if let PlanNode::Select(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("select * from default.t")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+---+---+",
+                "| a | b |",
+                "+---+---+",
+                "| 1 | x |",
+                "| 2 | y |",
+                "+---+---+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // Engines committing through the meta service are not supported.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create temporary table default.f(a bigint) Engine = Fuse")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert!(result.is_err());
+        } else {
+            panic!()
+        }
+    }
+
+    // The database must exist.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create temporary table db_not_exists.t(a bigint) Engine = Memory")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let r = executor.execute().await;
+            assert_eq!(
+                ErrorCode::UnknownDatabase("").code(),
+                r.err().unwrap().code()
+            );
+        } else {
+            panic!()
+        }
+    }
+
+    // Drop temporary table.
+    {
+        if let PlanNode::DropTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("drop table default.t")?
+        {
+            let executor = DropTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+            assert!(ctx
+                .get_temporary_tables()
+                .get_table("default", "t")
+                .is_none());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_table_as_select_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.a(a bigint, b varchar) Engine = Memory",
+        "insert into default.a values(1, 'x'), (2, 'y'), (3, 'z')",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let _ = InterpreterFactory::get(ctx.clone(), plan)?
+            .execute()
+            .await?;
+    }
+
+    // The schema is the output of the SELECT.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table default.b Engine = Memory as select a + 10 as x, b from default.a",
+        )? {
+            assert!(plan.as_select.is_some());
+            assert_eq!(plan.schema().fields().len(), 2);
+            assert_eq!(plan.schema().field(0).name(), "x");
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+    }
+
+    // The columns of the SELECT are cast to the given ones by position.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table default.c(c Int32, d varchar) Engine = Memory as select a, b from default.a",
+        )? {
+            assert_eq!(plan.schema().field_with_name("c")?.data_type(), &DataType::Int32);
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+    }
+
+    // Nothing is appended to an existing table.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table if not exists default.b Engine = Memory as select a + 20 as x, b from default.a",
+        )?;
+        let _ = InterpreterFactory::get(ctx.clone(), plan)?
+            .execute()
+            .await?;
+    }
+
+    for (sql, expected) in [
+        ("select * from default.b", vec![
+            "+----+---+",
+            "| x  | b |",
+            "+----+---+",
+            "| 11 | x |",
+            "| 12 | y |",
+            "| 13 | z |",
+            "+----+---+",
+        ]),
+        ("select * from default.c", vec![
+            "+---+---+",
+            "| c | d |",
+            "+---+---+",
+            "| 1 | x |",
+            "| 2 | y |",
+            "| 3 | z |",
+            "+---+---+",
+        ]),
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let stream = InterpreterFactory::get(ctx.clone(), plan)?
+            .execute()
+            .await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    // The SELECT returns the wrong number of columns.
+    {
+        let r = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table default.d(a bigint) Engine = Memory as select a, b from default.a",
+        );
+        assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+    }
+
+    Ok(())
+}
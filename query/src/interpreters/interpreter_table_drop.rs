@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::DropTablePlan;
 use common_streams::DataBlockStream;
@@ -42,8 +43,18 @@ impl Interpreter for DropTableInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let catalog = self.ctx.get_catalog();
-        catalog.drop_table(self.plan.clone())?;
+        // Temporary tables shadow the persistent tables, so they are dropped first.
+        let temporary_tables = self.ctx.get_temporary_tables();
+        if !temporary_tables.drop_table(&self.plan.db, &self.plan.table) {
+            // The databases of the other tenants are unknown to the session.
+            match self.ctx.get_database(&self.plan.db) {
+                Ok(_) => self.ctx.get_catalog().drop_table(self.plan.clone())?,
+                Err(cause)
+                    if self.plan.if_exists
+                        && cause.code() == ErrorCode::UnknownDatabase("").code() => {}
+                Err(cause) => return Err(cause),
+            }
+        }
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::SetTableOptionsPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table_options::check_table_options;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct SetTableOptionsInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: SetTableOptionsPlan,
+}
+
+impl SetTableOptionsInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: SetTableOptionsPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(SetTableOptionsInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for SetTableOptionsInterpreter {
+    fn name(&self) -> &str {
+        "SetTableOptionsInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        if self
+            .ctx
+            .get_temporary_tables()
+            .get_table(db, table_name)
+            .is_some()
+        {
+            return Err(ErrorCode::UnImplement(format!(
+                "ALTER TABLE SET OPTIONS is not supported by temporary table {}.{}",
+                db, table_name
+            )));
+        }
+
+        self.ctx.get_database(db)?;
+
+        // The tables of the context may be cached before the options were altered.
+        let catalog = self.ctx.get_catalog();
+        let table = catalog.get_table(db, table_name)?;
+        let engine = table.engine();
+        let defs = catalog.get_table_options(engine);
+        check_table_options(engine, &defs, &self.plan.options, true)?;
+        self.ctx.check_tenant_storage_options(&self.plan.options)?;
+
+        // Every upsert bumps the table version by one. The versions are derived from the one
+        // the options were checked against, so a concurrent change fails the upsert instead of
+        // being overwritten.
+        let mut keys = self.plan.options.keys().collect::<Vec<_>>();
+        keys.sort();
+        let version = table.get_table_info().version;
+        for (i, key) in keys.into_iter().enumerate() {
+            catalog.upsert_table_option(
+                table.get_id(),
+                version + i as u64,
+                key.clone(),
+                self.plan.options[key].clone(),
+            )?;
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
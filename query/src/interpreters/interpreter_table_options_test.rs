@@ -0,0 +1,110 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::*;
+use crate::sql::*;
+
+async fn execute_sql(ctx: &crate::sessions::DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_options_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    execute_sql(
+        &ctx,
+        "create table default.a(a bigint) Engine = Memory max_bytes = 1024",
+    )
+    .await?;
+
+    // Set options.
+    {
+        let sql = "alter table default.a set options (max_bytes = 1048576, overflow = 'spill')";
+        if let PlanNode::SetTableOptions(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql(sql)?
+        {
+            let executor = SetTableOptionsInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "SetTableOptionsInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+            let table = ctx.get_catalog().get_table("default", "a")?;
+            let options = &table.get_table_info().options;
+            assert_eq!(options.get("max_bytes"), Some(&"1048576".to_string()));
+            assert_eq!(options.get("overflow"), Some(&"spill".to_string()));
+        } else {
+            panic!()
+        }
+    }
+
+    // Show options.
+    {
+        if let PlanNode::ShowTableOptions(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("show table options from a")?
+        {
+            let executor = ShowTableOptionsInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "ShowTableOptionsInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+-------------------+---------+--------------------+-----------+-------------------------------------------------+",
+                "| Option            | Value   | Type               | Alterable | Description                                     |",
+                "+-------------------+---------+--------------------+-----------+-------------------------------------------------+",
+                "| collation         |         | String             | false     | The default collation                           |",
+                "| column_collations |         | String             | false     | The collations of the columns                   |",
+                "| max_bytes         | 1048576 | UInt64             | true      | The max bytes held in memory, 0 means unlimited |",
+                "| overflow          | spill   | Enum(throw, spill) | true      | What to do with the inserts over max_bytes      |",
+                "| primary_key       |         | String             | false     | The PRIMARY KEY columns                         |",
+                "| unique_keys       |         | String             | false     | The UNIQUE constraints                          |",
+                "+-------------------+---------+--------------------+-----------+-------------------------------------------------+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // Unknown options, bad values and the options fixed at creation are rejected.
+    for sql in [
+        "create table default.b(a bigint) Engine = Memory max_byte = 1024",
+        "create table default.b(a bigint) Engine = Memory changed_rows = 0",
+        "alter table default.a set options (max_bytes = -1)",
+        "alter table default.a set options (overflow = 'drop')",
+        "alter table default.a set options (primary_key = 'a')",
+    ] {
+        let r = execute_sql(&ctx, sql).await;
+        assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+    }
+
+    let r = execute_sql(&ctx, "alter table default.a set options (max_byte = 1)").await;
+    assert_eq!(
+        r.unwrap_err().message(),
+        "Unknown option max_byte of MEMORY tables, did you mean max_bytes?"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::UpdatePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::table::fuse::FuseTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+/// Updates the rows of a fuse table, see `FuseTable::do_update`.
+pub struct UpdateInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: UpdatePlan,
+}
+
+impl UpdateInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: UpdatePlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(UpdateInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for UpdateInterpreter {
+    fn name(&self) -> &str {
+        "UpdateInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let db = self.plan.db.as_str();
+        let table_name = self.plan.table.as_str();
+        let table = self.ctx.get_table(db, table_name)?;
+        let fuse_table = match table.as_any().downcast_ref::<FuseTable>() {
+            Some(fuse_table) => fuse_table,
+            None => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "UPDATE only supports FUSE tables, table {}.{} is {}",
+                    db,
+                    table_name,
+                    table.engine()
+                )))
+            }
+        };
+
+        let io_ctx = Arc::new(self.ctx.get_single_node_table_io_context()?);
+        fuse_table
+            .do_update(io_ctx, &self.plan.assignments, &self.plan.selection)
+            .await?;
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_update_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.a(a bigint, b varchar) Engine = Fuse",
+        "create table default.b(a bigint) Engine = Memory",
+        "insert into default.a values(1, 'x'), (2, 'y'), (3, 'z')",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    {
+        if let PlanNode::Update(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("update default.a set a = a * 10, b = 'w' where a > 1")?
+        {
+            assert_eq!(plan.table, "a");
+            assert_eq!(plan.assignments.len(), 2);
+            assert_eq!(plan.assignments[0].0, "a");
+            assert!(plan.selection.is_some());
+            let executor = UpdateInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "UpdateInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("select * from default.a")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+----+---+",
+            "| a  | b |",
+            "+----+---+",
+            "| 1  | x |",
+            "| 20 | w |",
+            "| 30 | w |",
+            "+----+---+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    // Only FUSE tables are supported.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("update default.b set a = 1")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+    }
+
+    // Unknown columns, columns assigned twice and aggregate functions are rejected by the planner.
+    for sql in [
+        "update default.a set c = 1",
+        "update default.a set a = 1 where c = 1",
+        "update default.a set a = 1, a = 2",
+        "update default.a set a = sum(a)",
+    ] {
+        let r = PlanParser::create(ctx.clone()).build_from_sql(sql);
+        assert!(r.is_err(), "{}", sql);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::AlterUserPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct AlterUserInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: AlterUserPlan,
+}
+
+impl AlterUserInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: AlterUserPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AlterUserInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterUserInterpreter {
+    fn name(&self) -> &str {
+        "AlterUserInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        // The profile takes effect from the next login of the user.
+        let user_mgr = self
+            .ctx
+            .get_sessions_manager()
+            .get_user_manager()
+            .for_tenant(&self.ctx.get_tenant());
+        user_mgr.set_user_profile(&self.plan.name, self.plan.profile.clone())?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::CreateTablePlan;
+use common_planners::CreateViewPlan;
+use common_planners::TableOptions;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::view::VIEW_OPT_KEY_QUERY;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct CreateViewInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: CreateViewPlan,
+}
+
+impl CreateViewInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: CreateViewPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CreateViewInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateViewInterpreter {
+    fn name(&self) -> &str {
+        "CreateViewInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.ctx.get_database(&self.plan.db)?;
+
+        // A view is kept in the catalog as a table of the VIEW engine with the query as option.
+        let mut options = TableOptions::new();
+        options.insert(VIEW_OPT_KEY_QUERY.to_string(), self.plan.query.clone());
+        let catalog = self.ctx.get_catalog();
+        catalog.create_table(CreateTablePlan {
+            if_not_exists: self.plan.if_not_exists,
+            db: self.plan.db.clone(),
+            table: self.plan.view.clone(),
+            schema: self.plan.schema.clone(),
+            engine: "VIEW".to_string(),
+            options,
+            temporary: false,
+            as_select: None,
+        })?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
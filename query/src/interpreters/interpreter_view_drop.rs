@@ -0,0 +1,79 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::DropTablePlan;
+use common_planners::DropViewPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::view::ViewTable;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct DropViewInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: DropViewPlan,
+}
+
+impl DropViewInterpreter {
+    pub fn try_create(ctx: DatabendQueryContextRef, plan: DropViewPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(DropViewInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropViewInterpreter {
+    fn name(&self) -> &str {
+        "DropViewInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let catalog = self.ctx.get_catalog();
+        let table = self
+            .ctx
+            .get_database(&self.plan.db)
+            .and_then(|_| catalog.get_table(&self.plan.db, &self.plan.view));
+
+        match table {
+            Ok(table) if table.as_any().downcast_ref::<ViewTable>().is_none() => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "{}.{} is not a view, use DROP TABLE to drop a table",
+                    self.plan.db, self.plan.view
+                )));
+            }
+            Ok(_) => catalog.drop_table(DropTablePlan {
+                if_exists: self.plan.if_exists,
+                db: self.plan.db.clone(),
+                table: self.plan.view.clone(),
+            })?,
+            Err(cause)
+                if self.plan.if_exists
+                    && (cause.code() == ErrorCode::UnknownDatabase("").code()
+                        || cause.code() == ErrorCode::UnknownTable("").code()) => {}
+            Err(cause) => return Err(cause),
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
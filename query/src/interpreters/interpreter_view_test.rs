@@ -0,0 +1,206 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_create_view_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.a(a bigint, b String) Engine = Memory",
+        "insert into default.a values(1, 'x'), (2, 'y'), (3, 'z')",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // Create view.
+    {
+        if let PlanNode::CreateView(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create view v1 as select a * 10 as c, b from a where a > 1")?
+        {
+            assert_eq!(plan.db, "default");
+            assert_eq!(plan.view, "v1");
+            assert_eq!(plan.query, "SELECT a * 10 AS c, b FROM a WHERE a > 1");
+            let names = plan.schema.fields().iter().map(|f| f.name().as_str());
+            assert_eq!(names.collect::<Vec<_>>(), vec!["c", "b"]);
+
+            let executor = CreateViewInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "CreateViewInterpreter");
+            let _ = executor.execute().await?;
+        } else {
+            panic!()
+        }
+    }
+
+    // A view on the view.
+    {
+        let plan = PlanParser::create(ctx.clone())
+            .build_from_sql("create view default.v2 as select c + 1 as d from v1")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // Select from the views.
+    {
+        if let PlanNode::Select(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("select b, c from v1 where c < 30")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+---+----+",
+                "| b | c  |",
+                "+---+----+",
+                "| y | 20 |",
+                "+---+----+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+
+        if let PlanNode::Select(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("select d from default.v2")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+----+", //
+                "| d  |", "+----+", "| 21 |", "| 31 |", "+----+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // Show create view.
+    {
+        if let PlanNode::ShowCreateTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("show create table v1")?
+        {
+            let executor = ShowCreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+-------+--------------------------------------------------------------+",
+                "| Table | Create Table                                                 |",
+                "+-------+--------------------------------------------------------------+",
+                "| v1    | CREATE VIEW `v1` AS SELECT a * 10 AS c, b FROM a WHERE a > 1 |",
+                "+-------+--------------------------------------------------------------+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // The name is taken.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("create view a as select 1")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert_eq!(
+            ErrorCode::TableAlreadyExists("").code(),
+            r.err().unwrap().code()
+        );
+
+        let plan = PlanParser::create(ctx.clone())
+            .build_from_sql("create view if not exists v1 as select 1")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // Bad view queries and engines are rejected by the planner.
+    {
+        let r = PlanParser::create(ctx.clone()).build_from_sql("create view v3 as select * from t");
+        assert_eq!(ErrorCode::UnknownTable("").code(), r.unwrap_err().code());
+
+        let r = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.t(a bigint) Engine = View");
+        assert_eq!(ErrorCode::BadOption("").code(), r.unwrap_err().code());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drop_view_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create table default.a(a bigint) Engine = Memory",
+        "create view default.v as select a from a",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // Tables are not dropped as views.
+    {
+        if let PlanNode::DropView(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("drop view a")?
+        {
+            let executor = DropViewInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "DropViewInterpreter");
+            let r = executor.execute().await;
+            assert_eq!(ErrorCode::BadArguments("").code(), r.err().unwrap().code());
+        } else {
+            panic!()
+        }
+    }
+
+    // Drop view.
+    {
+        if let PlanNode::DropView(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("drop view default.v")?
+        {
+            let executor = DropViewInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // Drop it again.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("drop view v")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert_eq!(ErrorCode::UnknownTable("").code(), r.err().unwrap().code());
+
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("drop view if exists v")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    Ok(())
+}
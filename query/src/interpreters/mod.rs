@@ -12,61 +12,158 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod interpreter_connection_test;
+#[cfg(test)]
+mod interpreter_copy_into_location_test;
+#[cfg(test)]
+mod interpreter_copy_test;
 #[cfg(test)]
 mod interpreter_database_create_test;
 #[cfg(test)]
 mod interpreter_database_drop_test;
 #[cfg(test)]
+mod interpreter_delete_test;
+#[cfg(test)]
 mod interpreter_describe_table_test;
 #[cfg(test)]
 mod interpreter_explain_test;
 #[cfg(test)]
+mod interpreter_external_function_create_test;
+#[cfg(test)]
+mod interpreter_function_create_test;
+#[cfg(test)]
+mod interpreter_function_drop_test;
+#[cfg(test)]
+mod interpreter_index_test;
+#[cfg(test)]
+mod interpreter_insert_into_test;
+#[cfg(test)]
+mod interpreter_merge_test;
+#[cfg(test)]
+mod interpreter_pipe_test;
+#[cfg(test)]
+mod interpreter_profile_test;
+#[cfg(test)]
+mod interpreter_query_cache_drop_test;
+#[cfg(test)]
 mod interpreter_select_test;
 #[cfg(test)]
 mod interpreter_setting_test;
 #[cfg(test)]
+mod interpreter_show_create_database_test;
+#[cfg(test)]
 mod interpreter_show_create_table_test;
 #[cfg(test)]
+mod interpreter_storage_policy_set_test;
+#[cfg(test)]
+mod interpreter_table_alter_test;
+#[cfg(test)]
 mod interpreter_table_create_test;
 #[cfg(test)]
 mod interpreter_table_drop_test;
 #[cfg(test)]
+mod interpreter_table_options_test;
+#[cfg(test)]
 mod interpreter_truncate_table_test;
 #[cfg(test)]
+mod interpreter_update_test;
+#[cfg(test)]
 mod interpreter_use_database_test;
 #[cfg(test)]
+mod interpreter_view_test;
+#[cfg(test)]
+mod plan_estimator_test;
+#[cfg(test)]
 mod plan_scheduler_test;
 
 mod interpreter;
+mod interpreter_analyze_table;
+mod interpreter_connection_create;
+mod interpreter_connection_drop;
+mod interpreter_copy;
+mod interpreter_copy_into_location;
 mod interpreter_database_create;
 mod interpreter_database_drop;
+mod interpreter_delete;
 mod interpreter_describe_table;
 mod interpreter_explain;
+mod interpreter_external_function_create;
 mod interpreter_factory;
+mod interpreter_fsck_table;
+mod interpreter_function_create;
+mod interpreter_function_drop;
+mod interpreter_index_create;
+mod interpreter_index_drop;
 mod interpreter_insert_into;
 mod interpreter_kill;
+mod interpreter_merge;
+mod interpreter_pipe_create;
+mod interpreter_pipe_drop;
+mod interpreter_profile_create;
+mod interpreter_profile_drop;
+mod interpreter_query_cache_drop;
 mod interpreter_select;
 mod interpreter_setting;
+mod interpreter_show_create_database;
 mod interpreter_show_create_table;
+mod interpreter_show_table_options;
+mod interpreter_storage_policy_set;
+mod interpreter_table_alter;
 mod interpreter_table_create;
 mod interpreter_table_drop;
+mod interpreter_table_options_set;
 mod interpreter_truncate_table;
+mod interpreter_update;
 mod interpreter_use_database;
+mod interpreter_user_alter;
+mod interpreter_view_create;
+mod interpreter_view_drop;
+mod metrics;
+mod plan_estimator;
 #[allow(clippy::needless_range_loop)]
 mod plan_scheduler;
 
 pub use interpreter::Interpreter;
 pub use interpreter::InterpreterPtr;
+pub use interpreter_analyze_table::AnalyzeTableInterpreter;
+pub use interpreter_connection_create::CreateConnectionInterpreter;
+pub use interpreter_connection_drop::DropConnectionInterpreter;
+pub use interpreter_copy::CopyInterpreter;
+pub use interpreter_copy_into_location::CopyIntoLocationInterpreter;
 pub use interpreter_database_create::CreateDatabaseInterpreter;
 pub use interpreter_database_drop::DropDatabaseInterpreter;
+pub use interpreter_delete::DeleteInterpreter;
 pub use interpreter_describe_table::DescribeTableInterpreter;
 pub use interpreter_explain::ExplainInterpreter;
+pub use interpreter_external_function_create::CreateExternalFunctionInterpreter;
 pub use interpreter_factory::InterpreterFactory;
+pub use interpreter_fsck_table::FsckTableInterpreter;
+pub use interpreter_function_create::CreateFunctionInterpreter;
+pub use interpreter_function_drop::DropFunctionInterpreter;
+pub use interpreter_index_create::CreateIndexInterpreter;
+pub use interpreter_index_drop::DropIndexInterpreter;
 pub use interpreter_insert_into::InsertIntoInterpreter;
+pub use interpreter_insert_into::SyncBlockStream;
+pub use interpreter_merge::MergeInterpreter;
+pub use interpreter_pipe_create::CreatePipeInterpreter;
+pub use interpreter_pipe_drop::DropPipeInterpreter;
+pub use interpreter_profile_create::CreateProfileInterpreter;
+pub use interpreter_profile_drop::DropProfileInterpreter;
+pub use interpreter_query_cache_drop::DropQueryCacheInterpreter;
 pub use interpreter_select::SelectInterpreter;
 pub use interpreter_setting::SettingInterpreter;
+pub use interpreter_show_create_database::ShowCreateDatabaseInterpreter;
 pub use interpreter_show_create_table::ShowCreateTableInterpreter;
+pub use interpreter_show_table_options::ShowTableOptionsInterpreter;
+pub use interpreter_storage_policy_set::SetStoragePolicyInterpreter;
+pub use interpreter_table_alter::AlterTableInterpreter;
 pub use interpreter_table_create::CreateTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
+pub use interpreter_table_options_set::SetTableOptionsInterpreter;
 pub use interpreter_truncate_table::TruncateTableInterpreter;
+pub use interpreter_update::UpdateInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
+pub use interpreter_user_alter::AlterUserInterpreter;
+pub use interpreter_view_create::CreateViewInterpreter;
+pub use interpreter_view_drop::DropViewInterpreter;
@@ -0,0 +1,76 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_planners::PlanNode;
+
+/// The share of the rows a filter is guessed to keep, the selectivity most planners assume for
+/// a predicate they know nothing about.
+const FILTER_SELECTIVITY: f64 = 0.1;
+
+/// Estimates the rows of the result of a plan from the statistics of the tables it reads,
+/// before the query runs.
+pub struct PlanEstimator;
+
+impl PlanEstimator {
+    /// The estimated rows of the result, None if they can't be told, e.g. of a GROUP BY.
+    pub fn estimate_rows(plan: &PlanNode) -> Option<u64> {
+        match plan {
+            PlanNode::ReadSource(plan) => {
+                let rows = plan.statistics.read_rows as u64;
+                match plan.push_downs.as_ref().and_then(|extras| extras.limit) {
+                    Some(limit) => Some(rows.min(limit as u64)),
+                    None => Some(rows),
+                }
+            }
+            PlanNode::Filter(plan) => Self::estimate_rows(&plan.input).map(Self::filtered),
+            PlanNode::Having(plan) => Self::estimate_rows(&plan.input).map(Self::filtered),
+            PlanNode::Limit(plan) => {
+                let rows = Self::estimate_rows(&plan.input)
+                    .map(|rows| rows.saturating_sub(plan.offset as u64));
+                match (rows, plan.n) {
+                    (Some(rows), Some(n)) => Some(rows.min(n as u64)),
+                    (None, Some(n)) => Some(n as u64),
+                    (rows, None) => rows,
+                }
+            }
+            PlanNode::AggregatorFinal(plan) if plan.group_expr.is_empty() => Some(1),
+            PlanNode::Select(plan) => Self::estimate_rows(&plan.input),
+            PlanNode::Projection(plan) => Self::estimate_rows(&plan.input),
+            PlanNode::Expression(plan) => Self::estimate_rows(&plan.input),
+            PlanNode::Sort(plan) => Self::estimate_rows(&plan.input),
+            PlanNode::Window(plan) => Self::estimate_rows(&plan.input),
+            PlanNode::SubQueryExpression(plan) => Self::estimate_rows(&plan.input),
+            PlanNode::AggregatorPartial(plan) => Self::estimate_rows(&plan.input),
+            // At most the rows of the input.
+            PlanNode::LimitBy(plan) => Self::estimate_rows(&plan.input),
+            _ => None,
+        }
+    }
+
+    /// Whether the rows of the result are capped by a LIMIT of the outermost query.
+    pub fn has_limit(plan: &PlanNode) -> bool {
+        match plan {
+            PlanNode::Limit(plan) => plan.n.is_some(),
+            PlanNode::Select(plan) => Self::has_limit(&plan.input),
+            PlanNode::Projection(plan) => Self::has_limit(&plan.input),
+            PlanNode::Expression(plan) => Self::has_limit(&plan.input),
+            PlanNode::Sort(plan) => Self::has_limit(&plan.input),
+            _ => false,
+        }
+    }
+
+    fn filtered(rows: u64) -> u64 {
+        (rows as f64 * FILTER_SELECTIVITY).ceil() as u64
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::plan_estimator::PlanEstimator;
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_plan_estimator() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let tests = vec![
+        ("select number from numbers(1000)", Some(1000), false),
+        (
+            "select number from numbers(1000) where number > 1",
+            Some(100),
+            false,
+        ),
+        (
+            "select number from numbers(1000) order by number limit 10",
+            Some(10),
+            true,
+        ),
+        (
+            "select number from numbers(1000) limit 10 offset 995",
+            Some(5),
+            true,
+        ),
+        ("select count() from numbers(1000)", Some(1), false),
+        (
+            "select number % 3 from numbers(1000) group by number % 3",
+            None,
+            false,
+        ),
+    ];
+
+    for (query, expected_rows, expected_limit) in tests {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        assert_eq!(
+            PlanEstimator::estimate_rows(&plan),
+            expected_rows,
+            "{}",
+            query
+        );
+        assert_eq!(PlanEstimator::has_limit(&plan), expected_limit, "{}", query);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_large_result_check() -> Result<()> {
+    async fn run(query: &str, mode: &str) -> Result<Vec<String>> {
+        let ctx = crate::tests::try_create_context()?;
+        ctx.get_settings().set_large_result_rows(100)?;
+        ctx.get_settings().set_large_result_mode(mode.to_string())?;
+        ctx.attach_query_str(query);
+
+        if let PlanNode::Select(plan) = PlanParser::create(ctx.clone()).build_from_sql(query)? {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan)?;
+            let stream = executor.execute().await?;
+            stream.try_collect::<Vec<_>>().await?;
+            Ok(ctx.get_query_warnings())
+        } else {
+            panic!()
+        }
+    }
+
+    // Warn.
+    {
+        let warnings = run("select number from numbers(1000)", "warn").await?;
+        assert_eq!(warnings, vec![
+            "The result is estimated at 1000 rows, over large_result_rows 100, add a LIMIT to return less"
+        ]);
+    }
+
+    // Throw.
+    {
+        let result = run("select number from numbers(1000)", "throw").await;
+        assert_eq!(result.unwrap_err().code(), 67);
+    }
+
+    // A LIMIT, or a result under large_result_rows, is not checked.
+    {
+        let warnings = run("select number from numbers(1000) limit 500", "throw").await?;
+        assert!(warnings.is_empty());
+
+        let warnings = run("select number from numbers(50)", "throw").await?;
+        assert!(warnings.is_empty());
+    }
+
+    // Unknown mode.
+    {
+        let result = run("select number from numbers(1000)", "ignore").await;
+        assert!(result
+            .unwrap_err()
+            .message()
+            .contains("Invalid large_result_mode: ignore"));
+    }
+
+    Ok(())
+}
@@ -36,6 +36,7 @@ use common_planners::Partitions;
 use common_planners::PlanNode;
 use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
+use common_planners::RecursiveCtePlan;
 use common_planners::RemotePlan;
 use common_planners::ScanPlan;
 use common_planners::SelectPlan;
@@ -43,6 +44,8 @@ use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::UnnestPlan;
+use common_planners::WindowPlan;
 use common_tracing::tracing;
 
 use crate::api::BroadcastAction;
@@ -305,6 +308,9 @@ impl PlanScheduler {
             PlanNode::Sort(plan) => self.visit_sort(plan, tasks),
             PlanNode::Limit(plan) => self.visit_limit(plan, tasks),
             PlanNode::LimitBy(plan) => self.visit_limit_by(plan, tasks),
+            PlanNode::Unnest(plan) => self.visit_unnest(plan, tasks),
+            PlanNode::Window(plan) => self.visit_window(plan, tasks),
+            PlanNode::RecursiveCte(plan) => self.visit_recursive_cte(plan, tasks),
             PlanNode::ReadSource(plan) => self.visit_data_source(plan, tasks),
             PlanNode::Select(plan) => self.visit_select(plan, tasks),
             PlanNode::Stage(plan) => self.visit_stage(plan, tasks),
@@ -782,6 +788,69 @@ impl PlanScheduler {
         }
     }
 
+    fn visit_unnest(&mut self, plan: &UnnestPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref(), tasks)?;
+        match self.running_mode {
+            RunningMode::Cluster => self.visit_cluster_unnest(plan),
+            RunningMode::Standalone => self.visit_local_unnest(plan),
+        };
+        Ok(())
+    }
+
+    fn visit_local_unnest(&mut self, plan: &UnnestPlan) {
+        self.nodes_plan[self.local_pos] = PlanNode::Unnest(UnnestPlan {
+            column: plan.column.clone(),
+            alias: plan.alias.clone(),
+            schema: plan.schema.clone(),
+            input: Arc::new(self.nodes_plan[self.local_pos].clone()),
+        });
+    }
+
+    fn visit_cluster_unnest(&mut self, plan: &UnnestPlan) {
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] = PlanNode::Unnest(UnnestPlan {
+                column: plan.column.clone(),
+                alias: plan.alias.clone(),
+                schema: plan.schema.clone(),
+                input: Arc::new(self.nodes_plan[index].clone()),
+            });
+        }
+    }
+
+    fn visit_window(&mut self, plan: &WindowPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref(), tasks)?;
+        match self.running_mode {
+            RunningMode::Cluster => self.visit_cluster_window(plan),
+            RunningMode::Standalone => self.visit_local_window(plan),
+        };
+        Ok(())
+    }
+
+    fn visit_local_window(&mut self, plan: &WindowPlan) {
+        self.nodes_plan[self.local_pos] = PlanNode::Window(WindowPlan {
+            window_exprs: plan.window_exprs.clone(),
+            schema: plan.schema.clone(),
+            input: Arc::new(self.nodes_plan[self.local_pos].clone()),
+        });
+    }
+
+    fn visit_cluster_window(&mut self, plan: &WindowPlan) {
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] = PlanNode::Window(WindowPlan {
+                window_exprs: plan.window_exprs.clone(),
+                schema: plan.schema.clone(),
+                input: Arc::new(self.nodes_plan[index].clone()),
+            });
+        }
+    }
+
+    fn visit_recursive_cte(&mut self, plan: &RecursiveCtePlan, _: &mut Tasks) -> Result<()> {
+        // The terms are executed again and again in the local node
+        self.running_mode = RunningMode::Standalone;
+        self.nodes_plan[self.local_pos] = PlanNode::RecursiveCte(plan.clone());
+        Ok(())
+    }
+
     fn visit_data_source(&mut self, plan: &ReadDataSourcePlan, _: &mut Tasks) -> Result<()> {
         let table = if plan.tbl_args.is_none() {
             self.query_context
@@ -71,6 +71,9 @@ async fn test_scheduler_plan_with_one_convergent_stage() -> Result<()> {
         match remote_action {
             FlightAction::CancelAction(_) => panic!(),
             FlightAction::BroadcastAction(_) => panic!(),
+            FlightAction::FetchQueryPageAction(_) => panic!(),
+            FlightAction::ListProcessesAction(_) => panic!(),
+            FlightAction::ListMetricsAction(_) => panic!(),
             FlightAction::PrepareShuffleAction(action) => remote_actions.push((node, action)),
         }
     }
@@ -149,6 +152,9 @@ async fn test_scheduler_plan_with_convergent_and_expansive_stage() -> Result<()>
         match remote_action {
             FlightAction::CancelAction(_) => panic!(),
             FlightAction::BroadcastAction(_) => panic!(),
+            FlightAction::FetchQueryPageAction(_) => panic!(),
+            FlightAction::ListProcessesAction(_) => panic!(),
+            FlightAction::ListMetricsAction(_) => panic!(),
             FlightAction::PrepareShuffleAction(action) => remote_actions.push((node, action)),
         }
     }
@@ -249,6 +255,9 @@ async fn test_scheduler_plan_with_convergent_and_normal_stage() -> Result<()> {
         match remote_action {
             FlightAction::CancelAction(_) => panic!(),
             FlightAction::BroadcastAction(_) => panic!(),
+            FlightAction::FetchQueryPageAction(_) => panic!(),
+            FlightAction::ListProcessesAction(_) => panic!(),
+            FlightAction::ListMetricsAction(_) => panic!(),
             FlightAction::PrepareShuffleAction(action) => remote_actions.push((node, action)),
         }
     }
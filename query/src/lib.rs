@@ -24,12 +24,16 @@ pub mod catalogs;
 pub mod clusters;
 pub mod common;
 pub mod configs;
+pub mod connections;
 pub mod datasources;
 pub mod functions;
 pub mod interpreters;
+pub mod loads;
 pub mod metrics;
 pub mod optimizers;
 pub mod pipelines;
+pub mod pipes;
+pub mod purges;
 pub mod servers;
 pub mod sessions;
 pub mod sql;
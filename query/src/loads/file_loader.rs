@@ -0,0 +1,407 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::io::Cursor;
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use common_base::tokio::sync::Mutex;
+use common_context::IOContext;
+use common_dal::InputStream;
+use common_datablocks::DataBlock;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::LoadFileProgress;
+use common_management::LoadMgrApi;
+use common_management::LoadPendingChunk;
+use common_management::LoadProgress;
+use common_streams::CsvSource;
+use common_streams::Source;
+use futures::io::BufReader;
+use futures::AsyncBufReadExt;
+use futures::AsyncSeekExt;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::common::balance_files;
+use crate::datasources::common::discover_files;
+use crate::datasources::common::DiscoveredFile;
+use crate::datasources::table::fuse::FuseTable;
+use crate::loads::unescape_partition_value;
+use crate::sessions::DatabendQueryContextRef;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FileLoadStatus {
+    /// Loaded from the beginning.
+    Loaded,
+    /// Loaded from where an interrupted load stopped.
+    Resumed,
+    /// Loaded by a previous load.
+    Skipped,
+}
+
+impl fmt::Display for FileLoadStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileLoadStatus::Loaded => write!(f, "LOADED"),
+            FileLoadStatus::Resumed => write!(f, "RESUMED"),
+            FileLoadStatus::Skipped => write!(f, "SKIPPED"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileLoadResult {
+    pub path: String,
+    pub status: FileLoadStatus,
+    /// The rows and bytes of the file in the table, including those of the previous loads.
+    pub rows: u64,
+    pub bytes: u64,
+}
+
+/// Loads the CSV files of a location into a FUSE table by chunks of lines.
+///
+/// The columns of the table named after the hive-style `key=value` directories of a file,
+/// e.g. the ones written by `COPY INTO '<location>' ... PARTITION BY`, take their values from
+/// the directories instead of the file.
+///
+/// Every chunk is written to the table as a new snapshot, and the progress of its file is
+/// saved once the snapshot is committed. Like the micro-batches of the pipes, the chunk is
+/// recorded as pending before it is committed, so a load interrupted at any point is resumed
+/// by loading the location again: the finished files are skipped and the others continue
+/// from the end of their last committed chunk.
+///
+/// The files are grouped by size into `max_threads` groups which are loaded concurrently, the
+/// files of a group one after another. Only the commits of the chunks are taken in turn, so
+/// that one chunk at most is pending.
+pub struct FileLoader {
+    api: Arc<dyn LoadMgrApi>,
+    db: String,
+    table: String,
+    location: String,
+    chunk_max_rows: usize,
+}
+
+/// The progress of the location, shared by the groups of files loaded concurrently.
+struct LoadState {
+    progress: LoadProgress,
+    seq: u64,
+}
+
+impl FileLoader {
+    pub fn create(
+        api: Arc<dyn LoadMgrApi>,
+        db: String,
+        table: String,
+        location: String,
+        chunk_max_rows: usize,
+    ) -> FileLoader {
+        FileLoader {
+            api,
+            db,
+            table,
+            location,
+            chunk_max_rows,
+        }
+    }
+
+    /// Loads the files of the location which are not loaded yet, sorted by path.
+    pub async fn load(&self, ctx: DatabendQueryContextRef) -> Result<Vec<FileLoadResult>> {
+        let io_ctx = ctx.get_single_node_table_io_context()?;
+        let table = ctx.get_catalog().get_table(&self.db, &self.table)?;
+        let fuse_table = self.fuse_table(table.as_ref())?;
+        let table_id = table.get_id();
+
+        let (seq, progress) = self
+            .api
+            .get_load_progress(table_id, self.location.clone())?;
+        let mut state = LoadState { progress, seq };
+        if let Some(pending) = state.progress.pending.take() {
+            if fuse_table
+                .is_snapshot_committed(&io_ctx, &pending.snapshot_location)
+                .await?
+            {
+                state.progress.files.insert(pending.path, pending.progress);
+            }
+            self.save_progress(table_id, &mut state)?;
+        }
+
+        let files = discover_files(io_ctx.get_data_accessor()?, &self.location).await?;
+        let mut results = Vec::with_capacity(files.len());
+        let mut unfinished = vec![];
+        for file in files {
+            match self.loaded_progress(&state, &file) {
+                Some(loaded) if loaded.is_finished() => results.push(FileLoadResult {
+                    path: file.path,
+                    status: FileLoadStatus::Skipped,
+                    rows: loaded.rows,
+                    bytes: loaded.bytes,
+                }),
+                _ => unfinished.push(file),
+            }
+        }
+
+        let state = Mutex::new(state);
+        let workers = ctx.get_settings().get_max_threads()? as usize;
+        let loads = balance_files(unfinished, workers)
+            .into_iter()
+            .map(|files| self.load_files(&ctx, table_id, files, &state));
+        for loaded in futures::future::try_join_all(loads).await? {
+            results.extend(loaded);
+        }
+
+        results.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(results)
+    }
+
+    // A file changed since it was loaded is loaded again from the beginning.
+    fn loaded_progress(
+        &self,
+        state: &LoadState,
+        file: &DiscoveredFile,
+    ) -> Option<LoadFileProgress> {
+        state
+            .progress
+            .files
+            .get(&file.path)
+            .filter(|loaded| loaded.size == file.size)
+            .cloned()
+    }
+
+    // Loads the files one after another.
+    async fn load_files(
+        &self,
+        ctx: &DatabendQueryContextRef,
+        table_id: u64,
+        files: Vec<DiscoveredFile>,
+        state: &Mutex<LoadState>,
+    ) -> Result<Vec<FileLoadResult>> {
+        let mut results = Vec::with_capacity(files.len());
+        for file in files {
+            let loaded = self.loaded_progress(&*state.lock().await, &file);
+            let (status, loaded) = match loaded {
+                Some(loaded) => (FileLoadStatus::Resumed, loaded),
+                None => (FileLoadStatus::Loaded, LoadFileProgress {
+                    size: file.size,
+                    bytes: 0,
+                    rows: 0,
+                }),
+            };
+
+            let file_progress = self.load_file(ctx, table_id, &file, loaded, state).await?;
+            results.push(FileLoadResult {
+                path: file.path,
+                status,
+                rows: file_progress.rows,
+                bytes: file_progress.bytes,
+            });
+        }
+        Ok(results)
+    }
+
+    // Loads the file from its committed bytes to the end, returns its progress.
+    async fn load_file(
+        &self,
+        ctx: &DatabendQueryContextRef,
+        table_id: u64,
+        file: &DiscoveredFile,
+        mut file_progress: LoadFileProgress,
+        state: &Mutex<LoadState>,
+    ) -> Result<LoadFileProgress> {
+        let io_ctx = ctx.get_single_node_table_io_context()?;
+        let catalog = ctx.get_catalog();
+        let block_size = ctx.get_settings().get_max_block_size()? as usize;
+        let lenient_cast = ctx.get_settings().get_lenient_insert_cast()? != 0;
+
+        let dal = io_ctx.get_data_accessor()?;
+        let mut input_stream = dal.get_input_stream(&file.path, Some(file.size))?;
+        input_stream
+            .seek(SeekFrom::Start(file_progress.bytes))
+            .await?;
+        let mut reader = BufReader::new(input_stream);
+
+        loop {
+            let chunk = self.read_chunk(&mut reader).await?;
+            if chunk.is_empty() {
+                break;
+            }
+
+            let table = catalog.get_table(&self.db, &self.table)?;
+            let fuse_table = self.fuse_table(table.as_ref())?;
+            let blocks = Self::read_blocks(&chunk, file, table.schema(), block_size, lenient_cast)
+                .map_err(|cause| cause.add_message_back(format!(" (while load {})", file.path)))?;
+            let rows = blocks
+                .iter()
+                .map(|block| block.num_rows() as u64)
+                .sum::<u64>();
+            let chunk_progress = LoadFileProgress {
+                size: file.size,
+                bytes: file_progress.bytes + chunk.len() as u64,
+                rows: file_progress.rows + rows,
+            };
+
+            let uncommitted = match blocks.is_empty() {
+                true => None,
+                false => {
+                    let blocks = blocks.into_iter().map(Ok);
+                    let (snapshot_location, _) = fuse_table
+                        .do_append_uncommitted(&io_ctx, Box::pin(futures::stream::iter(blocks)))
+                        .await?;
+                    Some(snapshot_location)
+                }
+            };
+
+            // The commits conflicting with the ones of the other files are rebased.
+            let mut locked = state.lock().await;
+            if let Some(snapshot_location) = uncommitted {
+                locked.progress.pending = Some(LoadPendingChunk {
+                    path: file.path.clone(),
+                    progress: chunk_progress.clone(),
+                    snapshot_location: snapshot_location.clone(),
+                });
+                self.save_progress(table_id, &mut locked)?;
+                fuse_table
+                    .do_commit(&io_ctx, snapshot_location, rows)
+                    .await?;
+                locked.progress.pending = None;
+            }
+
+            locked
+                .progress
+                .files
+                .insert(file.path.clone(), chunk_progress.clone());
+            self.save_progress(table_id, &mut locked)?;
+            file_progress = chunk_progress;
+        }
+
+        // An empty file has no chunk, it is finished as soon as it is found.
+        let mut locked = state.lock().await;
+        if locked.progress.files.get(&file.path) != Some(&file_progress) {
+            locked
+                .progress
+                .files
+                .insert(file.path.clone(), file_progress.clone());
+            self.save_progress(table_id, &mut locked)?;
+        }
+        Ok(file_progress)
+    }
+
+    // Reads at most `chunk_max_rows` records, a chunk always ends at the end of a record.
+    // A newline in a quoted field does not end the record. The quotes in a field are escaped by
+    // doubling them, so a record ends at the first newline after an even number of quotes.
+    async fn read_chunk(&self, reader: &mut BufReader<InputStream>) -> Result<Vec<u8>> {
+        let mut chunk = vec![];
+        let mut rows = 0;
+        let mut in_quotes = false;
+        while rows < self.chunk_max_rows {
+            let start = chunk.len();
+            if reader.read_until(b'\n', &mut chunk).await? == 0 {
+                break;
+            }
+            let quotes = chunk[start..].iter().filter(|b| **b == b'"').count();
+            in_quotes ^= quotes % 2 == 1;
+            if !in_quotes {
+                rows += 1;
+            }
+        }
+        Ok(chunk)
+    }
+
+    fn read_blocks(
+        chunk: &[u8],
+        file: &DiscoveredFile,
+        schema: DataSchemaRef,
+        block_size: usize,
+        lenient_cast: bool,
+    ) -> Result<Vec<DataBlock>> {
+        let file_schema = Self::file_schema(&schema, &file.partition_values);
+        let mut source = CsvSource::new(Cursor::new(chunk), file_schema, block_size)
+            .with_lenient_cast(lenient_cast);
+
+        let mut blocks = vec![];
+        while let Some(block) = source.read()? {
+            blocks.push(Self::fill_partition_columns(
+                block,
+                &schema,
+                &file.partition_values,
+            )?);
+        }
+        Ok(blocks)
+    }
+
+    // The columns named after the `key=value` directories of the file are not in the file.
+    fn file_schema(schema: &DataSchemaRef, partition_values: &[(String, String)]) -> DataSchemaRef {
+        if partition_values.is_empty() {
+            return schema.clone();
+        }
+
+        let fields = schema
+            .fields()
+            .iter()
+            .filter(|field| !partition_values.iter().any(|(key, _)| key == field.name()))
+            .cloned()
+            .collect();
+        DataSchemaRefExt::create(fields)
+    }
+
+    // Adds the columns of the partition values to the block read with `file_schema`.
+    fn fill_partition_columns(
+        block: DataBlock,
+        schema: &DataSchemaRef,
+        partition_values: &[(String, String)],
+    ) -> Result<DataBlock> {
+        if block.num_columns() == schema.fields().len() {
+            return Ok(block);
+        }
+
+        let num_rows = block.num_rows();
+        let mut columns = Vec::with_capacity(schema.fields().len());
+        for field in schema.fields() {
+            let column = match partition_values.iter().find(|(key, _)| key == field.name()) {
+                Some((_, value)) => {
+                    let value = unescape_partition_value(value).map(String::into_bytes);
+                    DataColumn::Constant(DataValue::String(value), num_rows)
+                        .cast_with_type(field.data_type())?
+                }
+                None => block.try_column_by_name(field.name())?.clone(),
+            };
+            columns.push(column);
+        }
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    fn save_progress(&self, table_id: u64, state: &mut LoadState) -> Result<()> {
+        let progress = state.progress.clone();
+        state.seq =
+            self.api
+                .upsert_load_progress(table_id, self.location.clone(), progress, state.seq)?;
+        Ok(())
+    }
+
+    fn fuse_table<'a>(&self, table: &'a dyn Table) -> Result<&'a FuseTable> {
+        table.as_any().downcast_ref::<FuseTable>().ok_or_else(|| {
+            ErrorCode::BadOption(format!(
+                "Copy can only load into FUSE tables, table {}.{} is {}",
+                self.db,
+                self.table,
+                table.engine()
+            ))
+        })
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_management::LoadFileProgress;
+use common_management::LoadMgr;
+use common_management::LoadMgrApi;
+use common_management::LoadPendingChunk;
+use common_meta_embedded::MetaEmbedded;
+use common_planners::CreateTablePlan;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::catalogs::Catalog;
+use crate::configs::Config;
+use crate::datasources::table::fuse::FuseTable;
+use crate::loads::FileLoadResult;
+use crate::loads::FileLoadStatus;
+use crate::loads::FileLoader;
+use crate::sessions::DatabendQueryContextRef;
+
+async fn count_rows(ctx: &DatabendQueryContextRef) -> Result<usize> {
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let table = ctx.get_catalog().get_table("default", "t")?;
+    let (_, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx, &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    Ok(blocks.iter().map(|block| block.num_rows()).sum())
+}
+
+fn result(path: &str, status: FileLoadStatus, rows: u64, bytes: u64) -> FileLoadResult {
+    FileLoadResult {
+        path: path.to_string(),
+        status,
+        rows,
+        bytes,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_file_loader_resume() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    ctx.get_catalog().create_table(CreateTablePlan {
+        if_not_exists: false,
+        db: "default".to_string(),
+        table: "t".to_string(),
+        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]),
+        engine: "FUSE".to_string(),
+        options: Default::default(),
+        temporary: false,
+        as_select: None,
+    })?;
+    let table_id = ctx.get_catalog().get_table("default", "t")?.get_id();
+
+    let data_dir = tmp_dir.path().join("data");
+    std::fs::create_dir_all(&data_dir)?;
+    std::fs::write(data_dir.join("a.csv"), "1\n2\n3\n")?;
+    std::fs::write(data_dir.join("b.csv"), "4\n5")?;
+
+    let api: Arc<dyn LoadMgrApi> = Arc::new(LoadMgr::new(
+        Arc::new(MetaEmbedded::new_temp().await?),
+        "test",
+    ));
+    let loader = FileLoader::create(
+        api.clone(),
+        "default".to_string(),
+        "t".to_string(),
+        "data".to_string(),
+        2,
+    );
+
+    // 1. load all the files
+    assert_eq!(loader.load(ctx.clone()).await?, vec![
+        result("data/a.csv", FileLoadStatus::Loaded, 3, 6),
+        result("data/b.csv", FileLoadStatus::Loaded, 2, 3),
+    ]);
+    assert_eq!(count_rows(&ctx).await?, 5);
+
+    // 2. the loaded files are skipped
+    assert_eq!(loader.load(ctx.clone()).await?, vec![
+        result("data/a.csv", FileLoadStatus::Skipped, 3, 6),
+        result("data/b.csv", FileLoadStatus::Skipped, 2, 3),
+    ]);
+    assert_eq!(count_rows(&ctx).await?, 5);
+
+    // 3. a load interrupted after its first chunk of c.csv is committed resumes from the chunk
+    std::fs::write(data_dir.join("c.csv"), "6\n7\n8\n9\n")?;
+    let io_ctx = ctx.get_single_node_table_io_context()?;
+    let table = ctx.get_catalog().get_table("default", "t")?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let block = DataBlock::create_by_array(table.schema(), vec![Series::new(vec![6i32, 7])]);
+    let (snapshot_location, rows) = fuse_table
+        .do_append_uncommitted(&io_ctx, Box::pin(futures::stream::iter(vec![Ok(block)])))
+        .await?;
+    let (seq, mut progress) = api.get_load_progress(table_id, "data".to_string())?;
+    progress.pending = Some(LoadPendingChunk {
+        path: "data/c.csv".to_string(),
+        progress: LoadFileProgress {
+            size: 8,
+            bytes: 4,
+            rows: 2,
+        },
+        snapshot_location: snapshot_location.clone(),
+    });
+    api.upsert_load_progress(table_id, "data".to_string(), progress, seq)?;
+    fuse_table
+        .do_commit(&io_ctx, snapshot_location, rows)
+        .await?;
+
+    assert_eq!(loader.load(ctx.clone()).await?, vec![
+        result("data/a.csv", FileLoadStatus::Skipped, 3, 6),
+        result("data/b.csv", FileLoadStatus::Skipped, 2, 3),
+        result("data/c.csv", FileLoadStatus::Resumed, 4, 8),
+    ]);
+    assert_eq!(count_rows(&ctx).await?, 9);
+
+    // 4. a chunk failed before committed is loaded again
+    std::fs::write(data_dir.join("d.csv"), "10\n")?;
+    let (seq, mut progress) = api.get_load_progress(table_id, "data".to_string())?;
+    progress.pending = Some(LoadPendingChunk {
+        path: "data/d.csv".to_string(),
+        progress: LoadFileProgress {
+            size: 3,
+            bytes: 3,
+            rows: 1,
+        },
+        snapshot_location: "_ss/not_committed".to_string(),
+    });
+    api.upsert_load_progress(table_id, "data".to_string(), progress, seq)?;
+
+    let results = loader.load(ctx.clone()).await?;
+    assert_eq!(
+        results[3],
+        result("data/d.csv", FileLoadStatus::Loaded, 1, 3)
+    );
+    assert_eq!(count_rows(&ctx).await?, 10);
+
+    // 5. a changed file is loaded again from the beginning
+    std::fs::write(data_dir.join("b.csv"), "4\n5\n")?;
+    let results = loader.load(ctx.clone()).await?;
+    assert_eq!(
+        results[1],
+        result("data/b.csv", FileLoadStatus::Loaded, 2, 4)
+    );
+    assert_eq!(count_rows(&ctx).await?, 12);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_file_loader_partitions() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    ctx.get_catalog().create_table(CreateTablePlan {
+        if_not_exists: false,
+        db: "default".to_string(),
+        table: "t".to_string(),
+        schema: DataSchemaRefExt::create(vec![
+            DataField::new("a", DataType::Int32, false),
+            DataField::new("year", DataType::Int32, true),
+        ]),
+        engine: "FUSE".to_string(),
+        options: Default::default(),
+        temporary: false,
+        as_select: None,
+    })?;
+
+    // The partition columns are only in the directories, as written by COPY ... PARTITION BY.
+    let data_dir = tmp_dir.path().join("data");
+    std::fs::create_dir_all(data_dir.join("year=2021"))?;
+    std::fs::create_dir_all(data_dir.join("year=__HIVE_DEFAULT_PARTITION__"))?;
+    std::fs::write(data_dir.join("year=2021/part-0.csv"), "1\n2\n")?;
+    std::fs::write(
+        data_dir.join("year=__HIVE_DEFAULT_PARTITION__/part-0.csv"),
+        "3\n",
+    )?;
+
+    let api: Arc<dyn LoadMgrApi> = Arc::new(LoadMgr::new(
+        Arc::new(MetaEmbedded::new_temp().await?),
+        "test",
+    ));
+    let loader = FileLoader::create(
+        api,
+        "default".to_string(),
+        "t".to_string(),
+        "data".to_string(),
+        100,
+    );
+    loader.load(ctx.clone()).await?;
+
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let table = ctx.get_catalog().get_table("default", "t")?;
+    let (_, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx, &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+---+------+",
+        "| a | year |",
+        "+---+------+",
+        "| 1 | 2021 |",
+        "| 2 | 2021 |",
+        "| 3 | NULL |",
+        "+---+------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_file_loader_quoted_newlines() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    ctx.get_catalog().create_table(CreateTablePlan {
+        if_not_exists: false,
+        db: "default".to_string(),
+        table: "t".to_string(),
+        schema: DataSchemaRefExt::create(vec![
+            DataField::new("a", DataType::Int32, false),
+            DataField::new("s", DataType::String, false),
+        ]),
+        engine: "FUSE".to_string(),
+        options: Default::default(),
+        temporary: false,
+        as_select: None,
+    })?;
+
+    // The chunks of one row end at the end of the records, not at the newlines in the fields.
+    let data_dir = tmp_dir.path().join("data");
+    std::fs::create_dir_all(&data_dir)?;
+    let data = "1,\"x\ny\"\n2,\"say \"\"hi\n\"\"\"\n3,z\n";
+    std::fs::write(data_dir.join("a.csv"), data)?;
+
+    let api: Arc<dyn LoadMgrApi> = Arc::new(LoadMgr::new(
+        Arc::new(MetaEmbedded::new_temp().await?),
+        "test",
+    ));
+    let loader = FileLoader::create(
+        api,
+        "default".to_string(),
+        "t".to_string(),
+        "data".to_string(),
+        1,
+    );
+    assert_eq!(loader.load(ctx.clone()).await?, vec![result(
+        "data/a.csv",
+        FileLoadStatus::Loaded,
+        3,
+        data.len() as u64
+    )]);
+    assert_eq!(count_rows(&ctx).await?, 3);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_file_loader_parallel() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+    ctx.get_settings().set_max_threads(3)?;
+
+    ctx.get_catalog().create_table(CreateTablePlan {
+        if_not_exists: false,
+        db: "default".to_string(),
+        table: "t".to_string(),
+        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]),
+        engine: "FUSE".to_string(),
+        options: Default::default(),
+        temporary: false,
+        as_select: None,
+    })?;
+    let table_id = ctx.get_catalog().get_table("default", "t")?.get_id();
+
+    // The file i has i + 1 rows, loaded by chunks of 2 rows in 3 groups of files.
+    let data_dir = tmp_dir.path().join("data");
+    std::fs::create_dir_all(&data_dir)?;
+    for i in 0..8 {
+        std::fs::write(
+            data_dir.join(format!("{}.csv", i)),
+            format!("{}\n", i).repeat(i + 1),
+        )?;
+    }
+
+    let api: Arc<dyn LoadMgrApi> = Arc::new(LoadMgr::new(
+        Arc::new(MetaEmbedded::new_temp().await?),
+        "test",
+    ));
+    let loader = FileLoader::create(
+        api.clone(),
+        "default".to_string(),
+        "t".to_string(),
+        "data".to_string(),
+        2,
+    );
+    let expected = (0..8)
+        .map(|i| {
+            let path = format!("data/{}.csv", i);
+            result(&path, FileLoadStatus::Loaded, i + 1, 2 * (i + 1))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(loader.load(ctx.clone()).await?, expected);
+    assert_eq!(count_rows(&ctx).await?, 36);
+
+    // The commits of the groups are taken in turn, none is left pending.
+    let (_, progress) = api.get_load_progress(table_id, "data".to_string())?;
+    assert!(progress.pending.is_none());
+    assert_eq!(progress.files.len(), 8);
+
+    Ok(())
+}
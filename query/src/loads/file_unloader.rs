@@ -0,0 +1,266 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_dal::DataAccessor;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::api::http::v1::output_format::OutputFormat;
+use crate::pipelines::transforms::ExpressionExecutor;
+
+/// The directory of the rows whose partition value is NULL or empty, named as Hive does.
+pub const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileUnloadResult {
+    pub path: String,
+    pub rows: u64,
+    pub bytes: u64,
+}
+
+/// The file being written for a partition, kept in memory until it is put to the storage.
+struct PartitionWriter {
+    next_file: usize,
+    buffer: Vec<u8>,
+    rows: u64,
+}
+
+/// Writes blocks into CSV files under a location.
+///
+/// With `partition_by`, the rows are written in hive-style directories, e.g.
+/// `exports/year=2021/month=9/part-00000-<query id>.csv`, with one writer for each
+/// partition. The columns used as partition keys are only kept in the directory names,
+/// the way Spark and Athena expect them.
+///
+/// A writer starts a new file once its file reaches `max_file_size`. The files are cut
+/// between blocks, so a file can be larger by the size of one block.
+pub struct FileUnloader {
+    dal: Arc<dyn DataAccessor>,
+    location: String,
+    file_suffix: String,
+    partition_by: Vec<Expression>,
+    max_file_size: usize,
+    writers: BTreeMap<String, PartitionWriter>,
+    results: Vec<FileUnloadResult>,
+}
+
+impl FileUnloader {
+    /// `file_suffix` keeps the files of different unloads apart, e.g. the query id.
+    pub fn create(
+        dal: Arc<dyn DataAccessor>,
+        location: &str,
+        file_suffix: &str,
+        partition_by: Vec<Expression>,
+        max_file_size: usize,
+    ) -> Self {
+        FileUnloader {
+            dal,
+            location: location.trim_end_matches('/').to_string(),
+            file_suffix: file_suffix.to_string(),
+            partition_by,
+            max_file_size,
+            writers: BTreeMap::new(),
+            results: vec![],
+        }
+    }
+
+    pub async fn write(&mut self, block: DataBlock) -> Result<()> {
+        if block.num_rows() == 0 {
+            return Ok(());
+        }
+        if self.partition_by.is_empty() {
+            return self.write_partition(String::new(), &block).await;
+        }
+
+        let schema = block.schema().clone();
+        let keys = self
+            .partition_by
+            .iter()
+            .map(|expr| expr.column_name())
+            .collect::<Vec<_>>();
+
+        let key_fields = self
+            .partition_by
+            .iter()
+            .map(|expr| expr.to_data_field(&schema))
+            .collect::<Result<Vec<_>>>()?;
+        let executor = ExpressionExecutor::try_create(
+            "partition key executor",
+            schema.clone(),
+            DataSchemaRefExt::create(key_fields.clone()),
+            self.partition_by.clone(),
+            false,
+        )?;
+        executor.validate()?;
+        let key_block = executor.execute(&block)?;
+
+        // the directories of each row
+        let mut dirs = vec![String::new(); block.num_rows()];
+        for (key, field) in keys.iter().zip(key_fields.iter()) {
+            let column = key_block.try_column_by_name(field.name())?;
+            let values = field
+                .data_type()
+                .create_serializer(0)?
+                .serialize_strings(column)?;
+            for (row, value) in values.into_iter().enumerate() {
+                let value = match column.try_get(row)?.is_null() {
+                    true => HIVE_DEFAULT_PARTITION.to_string(),
+                    false => escape_partition_value(&value),
+                };
+                dirs[row].push_str(&format!("{}={}/", escape_partition_value(key), value));
+            }
+        }
+
+        let mut partitions: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for (row, dir) in dirs.into_iter().enumerate() {
+            partitions.entry(dir).or_default().push(row as u32);
+        }
+
+        let data_fields = schema
+            .fields()
+            .iter()
+            .filter(|field| !keys.contains(field.name()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let data_schema = DataSchemaRefExt::create(data_fields);
+        for (dir, rows) in partitions {
+            let taken = DataBlock::block_take_by_indices(&block, &[], &rows)?;
+            let columns = data_schema
+                .fields()
+                .iter()
+                .map(|field| taken.try_column_by_name(field.name()).map(|c| c.clone()))
+                .collect::<Result<Vec<_>>>()?;
+            let data_block = DataBlock::create(data_schema.clone(), columns);
+            self.write_partition(dir, &data_block).await?;
+        }
+        Ok(())
+    }
+
+    /// Puts the files still being written, returns all the files written sorted by path.
+    pub async fn finish(mut self) -> Result<Vec<FileUnloadResult>> {
+        let dirs = self.writers.keys().cloned().collect::<Vec<_>>();
+        for dir in dirs {
+            self.put_file(&dir).await?;
+        }
+
+        self.results.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(self.results)
+    }
+
+    async fn write_partition(&mut self, dir: String, block: &DataBlock) -> Result<()> {
+        let csv = OutputFormat::CSV.format(block.schema(), &[block.clone()])?;
+        let writer = self.writers.entry(dir.clone()).or_insert(PartitionWriter {
+            next_file: 0,
+            buffer: vec![],
+            rows: 0,
+        });
+        writer.buffer.extend_from_slice(csv.as_bytes());
+        writer.rows += block.num_rows() as u64;
+
+        if writer.buffer.len() >= self.max_file_size {
+            self.put_file(&dir).await?;
+        }
+        Ok(())
+    }
+
+    async fn put_file(&mut self, dir: &str) -> Result<()> {
+        let writer = match self.writers.get_mut(dir) {
+            Some(writer) if writer.rows > 0 => writer,
+            _ => return Ok(()),
+        };
+
+        let path = format!(
+            "{}/{}part-{:05}-{}.csv",
+            self.location, dir, writer.next_file, self.file_suffix
+        );
+        let content = std::mem::take(&mut writer.buffer);
+        let result = FileUnloadResult {
+            path: path.clone(),
+            rows: writer.rows,
+            bytes: content.len() as u64,
+        };
+        writer.next_file += 1;
+        writer.rows = 0;
+
+        self.dal.put(&path, content).await?;
+        self.results.push(result);
+        Ok(())
+    }
+}
+
+/// Escapes the characters not allowed in the directory names of hive partitions as `%XX`,
+/// e.g. `a/b` gives `a%2Fb`.
+pub fn escape_partition_value(value: &str) -> String {
+    if value.is_empty() {
+        return HIVE_DEFAULT_PARTITION.to_string();
+    }
+
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\u{01}'..='\u{1F}'
+            | '"'
+            | '#'
+            | '%'
+            | '\''
+            | '*'
+            | '/'
+            | ':'
+            | '='
+            | '?'
+            | '\\'
+            | '\u{7F}'
+            | '{'
+            | '['
+            | ']'
+            | '^' => escaped.push_str(&format!("%{:02X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverses `escape_partition_value`, the default partition is None.
+pub fn unescape_partition_value(value: &str) -> Option<String> {
+    if value == HIVE_DEFAULT_PARTITION {
+        return None;
+    }
+
+    let bytes = value.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let hex_digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let decoded = match bytes.get(idx..idx + 3) {
+            Some([b'%', hi, lo]) => hex_digit(*hi).zip(hex_digit(*lo)),
+            _ => None,
+        };
+        match decoded {
+            Some((hi, lo)) => {
+                unescaped.push(hi << 4 | lo);
+                idx += 3;
+            }
+            None => {
+                unescaped.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+    }
+    Some(String::from_utf8_lossy(&unescaped).into_owned())
+}
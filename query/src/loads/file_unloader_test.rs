@@ -0,0 +1,123 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_dal::Local;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::Expression;
+use pretty_assertions::assert_eq;
+
+use crate::loads::escape_partition_value;
+use crate::loads::unescape_partition_value;
+use crate::loads::FileUnloadResult;
+use crate::loads::FileUnloader;
+use crate::loads::HIVE_DEFAULT_PARTITION;
+
+fn result(path: &str, rows: u64, bytes: u64) -> FileUnloadResult {
+    FileUnloadResult {
+        path: path.to_string(),
+        rows,
+        bytes,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_file_unloader_partitions() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let dal = Arc::new(Local::with_path(tmp_dir.path().to_path_buf()));
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("year", DataType::Int32, true),
+        DataField::new("name", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![Some(2021i32), Some(2020), None, Some(2021)]),
+        Series::new(vec!["a", "b", "c", "d"]),
+    ]);
+
+    let mut unloader = FileUnloader::create(
+        dal.clone(),
+        "exports/",
+        "q1",
+        vec![
+            col("year"),
+            Expression::Alias(
+                "tag".to_string(),
+                Box::new(Expression::create_literal(DataValue::String(Some(
+                    b"x/y".to_vec(),
+                )))),
+            ),
+        ],
+        1024,
+    );
+    unloader.write(block.clone()).await?;
+    let results = unloader.finish().await?;
+    assert_eq!(results, vec![
+        result(
+            &format!(
+                "exports/year={}/tag=x%2Fy/part-00000-q1.csv",
+                HIVE_DEFAULT_PARTITION
+            ),
+            1,
+            4
+        ),
+        result("exports/year=2020/tag=x%2Fy/part-00000-q1.csv", 1, 4),
+        result("exports/year=2021/tag=x%2Fy/part-00000-q1.csv", 2, 8),
+    ]);
+
+    // the partition keys are only kept in the directories
+    let content = std::fs::read_to_string(
+        tmp_dir
+            .path()
+            .join("exports/year=2021/tag=x%2Fy/part-00000-q1.csv"),
+    )?;
+    assert_eq!(content, "\"a\"\n\"d\"\n");
+
+    // rotation by the file size
+    let mut unloader = FileUnloader::create(dal, "rotated", "q2", vec![], 10);
+    unloader.write(block.clone()).await?;
+    unloader.write(block).await?;
+    let results = unloader.finish().await?;
+    assert_eq!(results, vec![
+        result("rotated/part-00000-q2.csv", 4, 34),
+        result("rotated/part-00001-q2.csv", 4, 34),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_escape_partition_value() -> Result<()> {
+    assert_eq!(escape_partition_value("2021-09-01"), "2021-09-01");
+    assert_eq!(escape_partition_value("a/b=c"), "a%2Fb%3Dc");
+    assert_eq!(escape_partition_value("100%"), "100%25");
+    assert_eq!(escape_partition_value("new\nline"), "new%0Aline");
+    assert_eq!(escape_partition_value(""), HIVE_DEFAULT_PARTITION);
+
+    for value in ["2021-09-01", "a/b=c", "100%", "new\nline"] {
+        assert_eq!(
+            unescape_partition_value(&escape_partition_value(value)),
+            Some(value.to_string())
+        );
+    }
+    assert_eq!(unescape_partition_value("50%"), Some("50%".to_string()));
+    assert_eq!(unescape_partition_value("%zz"), Some("%zz".to_string()));
+    assert_eq!(unescape_partition_value(HIVE_DEFAULT_PARTITION), None);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_management::LoadMgr;
+use common_management::LoadMgrApi;
+use common_meta_api::KVApi;
+
+use crate::common::MetaClientProvider;
+use crate::configs::Config;
+use crate::loads::FileLoader;
+
+pub type LoadManagerRef = Arc<LoadManager>;
+
+/// Keeps the load progress of the COPY statements in the meta service.
+pub struct LoadManager {
+    api_provider: Arc<dyn LoadMgrApi>,
+}
+
+impl LoadManager {
+    async fn create_kv_client(cfg: &Config) -> Result<Arc<dyn KVApi>> {
+        let store_api_provider = MetaClientProvider::from(cfg);
+        match store_api_provider.try_get_kv_client().await {
+            Ok(client) => Ok(client),
+            Err(cause) => Err(cause.add_message_back("(while create load api).")),
+        }
+    }
+
+    pub async fn create_global(cfg: Config) -> Result<LoadManagerRef> {
+        let client = LoadManager::create_kv_client(&cfg).await?;
+        let load_manager = LoadMgr::new(client, &cfg.query.tenant);
+
+        Ok(Arc::new(LoadManager {
+            api_provider: Arc::new(load_manager),
+        }))
+    }
+
+    pub fn create_loader(
+        &self,
+        db: &str,
+        table: &str,
+        location: &str,
+        chunk_max_rows: usize,
+    ) -> FileLoader {
+        FileLoader::create(
+            self.api_provider.clone(),
+            db.to_string(),
+            table.to_string(),
+            location.to_string(),
+            chunk_max_rows,
+        )
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod file_loader_test;
+#[cfg(test)]
+mod file_unloader_test;
+
+mod file_loader;
+mod file_unloader;
+mod load_manager;
+
+pub use file_loader::FileLoadResult;
+pub use file_loader::FileLoadStatus;
+pub use file_loader::FileLoader;
+pub use file_unloader::escape_partition_value;
+pub use file_unloader::unescape_partition_value;
+pub use file_unloader::FileUnloadResult;
+pub use file_unloader::FileUnloader;
+pub use file_unloader::HIVE_DEFAULT_PARTITION;
+pub use load_manager::LoadManager;
+pub use load_manager::LoadManagerRef;
@@ -0,0 +1,138 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_metrics::HistogramCount;
+use common_metrics::MetricSample;
+use common_metrics::MetricValue;
+
+use crate::clusters::ClusterRef;
+use crate::configs::Config;
+
+/// The node of the samples summed up over all the nodes.
+pub const CLUSTER_TOTAL_NODE: &str = "cluster";
+
+/// The metric samples of a node, by the node id.
+pub type NodeMetricSamples = (String, Vec<MetricSample>);
+
+/// The metric samples of every node of the cluster, the local node first.
+/// A node which cannot be reached is skipped.
+pub async fn collect_cluster_metrics(
+    cluster: &ClusterRef,
+    config: &Config,
+    timeout: u64,
+) -> Result<Vec<NodeMetricSamples>> {
+    let prometheus_handle = common_metrics::try_handle().ok_or_else(|| {
+        ErrorCode::InitPrometheusFailure("Prometheus recorder is not initialized yet.")
+    })?;
+    let local_samples = common_metrics::dump_metric_samples(prometheus_handle)?;
+    let mut nodes_samples = vec![(cluster.local_id(), local_samples)];
+
+    for node in cluster.get_nodes() {
+        if cluster.is_local(&node) {
+            continue;
+        }
+
+        let node_samples = match cluster.create_node_conn(&node.id, config).await {
+            Ok(mut client) => client.list_metrics(timeout).await,
+            Err(cause) => Err(cause),
+        };
+
+        match node_samples {
+            Ok(node_samples) => nodes_samples.push((node.id.clone(), node_samples)),
+            Err(cause) => log::warn!("Cannot list metrics of node {}: {}", node.id, cause),
+        }
+    }
+    Ok(nodes_samples)
+}
+
+/// Sums the samples of the same name and labels up over the nodes. The quantiles
+/// of the summaries cannot be summed up, their `_sum` and `_count` samples are.
+pub fn sum_cluster_metrics(nodes_samples: &[NodeMetricSamples]) -> Vec<MetricSample> {
+    let mut totals: Vec<MetricSample> = vec![];
+    let mut positions = HashMap::new();
+    for (_, samples) in nodes_samples {
+        for sample in samples {
+            if let MetricValue::Summary(_) = sample.value {
+                continue;
+            }
+
+            let mut labels = sample.labels.iter().collect::<Vec<_>>();
+            labels.sort();
+            let key = format!("{}{:?}", sample.name, labels);
+            let position = match positions.get(&key) {
+                Some(position) => *position,
+                None => {
+                    positions.insert(key, totals.len());
+                    totals.push(sample.clone());
+                    continue;
+                }
+            };
+
+            let total = &mut totals[position].value;
+            match (total, &sample.value) {
+                (MetricValue::Counter(total), MetricValue::Counter(v))
+                | (MetricValue::Gauge(total), MetricValue::Gauge(v))
+                | (MetricValue::Untyped(total), MetricValue::Untyped(v)) => *total += v,
+                (MetricValue::Histogram(total), MetricValue::Histogram(buckets)) => {
+                    sum_histogram(total, buckets)
+                }
+                // The kind of the metric differs between the versions of the nodes.
+                _ => log::warn!("Cannot sum the metric {} up over the nodes", sample.name),
+            }
+        }
+    }
+    totals
+}
+
+fn sum_histogram(total: &mut Vec<HistogramCount>, buckets: &[HistogramCount]) {
+    for bucket in buckets {
+        match total
+            .iter_mut()
+            .find(|t| t.less_than.to_bits() == bucket.less_than.to_bits())
+        {
+            Some(total_bucket) => total_bucket.count += bucket.count,
+            None => total.push(bucket.clone()),
+        }
+    }
+    total.sort_by(|a, b| {
+        a.less_than
+            .partial_cmp(&b.less_than)
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+/// All the samples labeled by the node, with the totals of the cluster labeled by
+/// `CLUSTER_TOTAL_NODE`. The samples of a metric are kept together for the rendering.
+pub fn label_cluster_metrics(nodes_samples: &[NodeMetricSamples]) -> Vec<MetricSample> {
+    let totals = (
+        CLUSTER_TOTAL_NODE.to_string(),
+        sum_cluster_metrics(nodes_samples),
+    );
+
+    let mut labeled = vec![];
+    for (node, samples) in nodes_samples.iter().chain(std::iter::once(&totals)) {
+        for sample in samples {
+            let mut sample = sample.clone();
+            sample.labels.insert("node".to_string(), node.clone());
+            labeled.push(sample);
+        }
+    }
+    labeled.sort_by(|a, b| a.name.cmp(&b.name));
+    labeled
+}
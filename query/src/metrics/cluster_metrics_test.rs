@@ -0,0 +1,106 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::Result;
+use common_metrics::MetricSample;
+use common_metrics::MetricValue;
+use common_metrics::SummaryCount;
+
+use crate::metrics::label_cluster_metrics;
+use crate::metrics::sum_cluster_metrics;
+
+fn sample(name: &str, labels: &[(&str, &str)], value: MetricValue) -> MetricSample {
+    MetricSample {
+        name: name.to_string(),
+        kind: value.kind(),
+        labels: labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+        value,
+    }
+}
+
+#[test]
+fn test_sum_cluster_metrics() -> Result<()> {
+    let quantiles = vec![SummaryCount {
+        quantile: 0.5,
+        count: 1.0,
+    }];
+    let nodes_samples = vec![
+        ("n1".to_string(), vec![
+            sample(
+                "query_count",
+                &[("type", "select")],
+                MetricValue::Counter(3.0),
+            ),
+            sample(
+                "query_count",
+                &[("type", "insert")],
+                MetricValue::Counter(1.0),
+            ),
+            sample("cache_bytes", &[], MetricValue::Gauge(100.0)),
+            sample("io_seconds", &[], MetricValue::Summary(quantiles.clone())),
+        ]),
+        ("n2".to_string(), vec![
+            sample(
+                "query_count",
+                &[("type", "select")],
+                MetricValue::Counter(2.0),
+            ),
+            sample("cache_bytes", &[], MetricValue::Gauge(50.0)),
+            sample("io_seconds", &[], MetricValue::Summary(quantiles)),
+        ]),
+    ];
+
+    let totals = sum_cluster_metrics(&nodes_samples)
+        .into_iter()
+        .map(|s| (format!("{}{:?}", s.name, s.labels.get("type")), s.value))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(totals.len(), 3);
+    assert_eq!(
+        totals.get("query_countSome(\"select\")"),
+        Some(&MetricValue::Counter(5.0))
+    );
+    assert_eq!(
+        totals.get("query_countSome(\"insert\")"),
+        Some(&MetricValue::Counter(1.0))
+    );
+    assert_eq!(
+        totals.get("cache_bytesNone"),
+        Some(&MetricValue::Gauge(150.0))
+    );
+
+    // The samples of a metric are kept together, labeled by the node.
+    let labeled = label_cluster_metrics(&nodes_samples)
+        .into_iter()
+        .map(|s| format!("{}:{}", s.name, s.labels.get("node").unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(labeled, vec![
+        "cache_bytes:n1",
+        "cache_bytes:n2",
+        "cache_bytes:cluster",
+        "io_seconds:n1",
+        "io_seconds:n2",
+        "query_count:n1",
+        "query_count:n1",
+        "query_count:n2",
+        "query_count:cluster",
+        "query_count:cluster",
+    ]);
+
+    Ok(())
+}
@@ -20,6 +20,7 @@ use axum::body::Full;
 use axum::extract::Extension;
 use axum::handler::get;
 use axum::http::Response;
+use axum::http::StatusCode;
 use axum::response::Html;
 use axum::response::IntoResponse;
 use axum::AddExtensionLayer;
@@ -31,10 +32,16 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_metrics::PrometheusHandle;
 
+use crate::metrics::collect_cluster_metrics;
+use crate::metrics::label_cluster_metrics;
 use crate::servers::Server;
 use crate::sessions::SessionManagerRef;
 
+// Timeout in seconds of listing the metrics of a node.
+const LIST_METRICS_TIMEOUT: u64 = 10;
+
 pub struct MetricService {
+    sessions: SessionManagerRef,
     join_handle: Option<JoinHandle<std::io::Result<()>>>,
     abort_handler: Handle,
 }
@@ -57,19 +64,63 @@ pub async fn metric_handler(prom_extension: Extension<PrometheusHandle>) -> Metr
     MetricTemplate { prom }
 }
 
+pub struct ClusterMetricTemplate {
+    result: Result<String>,
+}
+
+impl IntoResponse for ClusterMetricTemplate {
+    type Body = Full<Bytes>;
+    type BodyError = Infallible;
+
+    fn into_response(self) -> Response<Self::Body> {
+        match self.result {
+            Ok(text) => Html(text).into_response(),
+            Err(cause) => Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Full::from(format!(
+                    "Failed to collect cluster metrics. Error: {}",
+                    cause
+                )))
+                .unwrap(),
+        }
+    }
+}
+
+// The metrics of all the nodes labeled by the node, with the totals of the cluster
+// labeled by `node="cluster"`, so that a single scrape covers the whole cluster.
+pub async fn cluster_metric_handler(
+    sessions_extension: Extension<SessionManagerRef>,
+) -> ClusterMetricTemplate {
+    let sessions = sessions_extension.0;
+    ClusterMetricTemplate {
+        result: render_cluster_metrics(sessions).await,
+    }
+}
+
+async fn render_cluster_metrics(sessions: SessionManagerRef) -> Result<String> {
+    let cluster = sessions.get_cluster_discovery().discover().await?;
+    let config = sessions.get_conf();
+    let nodes_samples = collect_cluster_metrics(&cluster, &config, LIST_METRICS_TIMEOUT).await?;
+    let samples = label_cluster_metrics(&nodes_samples);
+    Ok(common_metrics::render_metric_samples(&samples))
+}
+
 // build axum router for metric server
 macro_rules! build_router {
-    ($prometheus: expr) => {
+    ($prometheus: expr, $sessions: expr) => {
         Router::new()
             .route("/metrics", get(metric_handler))
+            .route("/metrics/cluster", get(cluster_metric_handler))
             .layer(AddExtensionLayer::new($prometheus.clone()))
+            .layer(AddExtensionLayer::new($sessions.clone()))
     };
 }
 
 impl MetricService {
     // TODO add session tls handler
-    pub fn create(_sessions: SessionManagerRef) -> Box<MetricService> {
+    pub fn create(sessions: SessionManagerRef) -> Box<MetricService> {
         Box::new(MetricService {
+            sessions,
             join_handle: None,
             abort_handler: axum_server::Handle::new(),
         })
@@ -79,7 +130,7 @@ impl MetricService {
         let prometheus_handle = common_metrics::try_handle().ok_or_else(|| {
             ErrorCode::InitPrometheusFailure("Prometheus recorder has not been initialized yet.")
         })?;
-        let app = build_router!(prometheus_handle);
+        let app = build_router!(prometheus_handle, self.sessions);
         let server = axum_server::bind(listening.to_string())
             .handle(self.abort_handler.clone())
             .serve(app);
@@ -46,5 +46,12 @@ async fn test_metric_server() -> common_exception::Result<()> {
     let output = resp.text().await.unwrap();
     assert!(output.contains("metrics_test 1"));
 
+    // The metrics of the cluster are labeled by the node, with the totals.
+    let url = format!("http://{}/metrics/cluster", listening);
+    let resp = client.get(url).send().await.unwrap();
+    assert!(resp.status().is_success());
+    let output = resp.text().await.unwrap();
+    assert!(output.contains("metrics_test{node=\"cluster\"} 1"));
+
     Ok(())
 }
@@ -12,9 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod cluster_metrics_test;
 #[cfg(test)]
 mod metric_service_test;
 
+mod cluster_metrics;
 mod metric_service;
 
+pub use cluster_metrics::collect_cluster_metrics;
+pub use cluster_metrics::label_cluster_metrics;
+pub use cluster_metrics::sum_cluster_metrics;
+pub use cluster_metrics::NodeMetricSamples;
+pub use cluster_metrics::CLUSTER_TOTAL_NODE;
 pub use metric_service::MetricService;
@@ -15,6 +15,8 @@
 #[cfg(test)]
 mod optimizer_constant_folding_test;
 #[cfg(test)]
+mod optimizer_constraints_test;
+#[cfg(test)]
 mod optimizer_expression_transform_test;
 #[cfg(test)]
 mod optimizer_projection_push_down_test;
@@ -28,6 +30,7 @@ mod optimizer_test;
 mod metrics;
 mod optimizer;
 mod optimizer_constant_folding;
+mod optimizer_constraints;
 mod optimizer_expression_transform;
 mod optimizer_projection_push_down;
 mod optimizer_scatters;
@@ -37,6 +40,7 @@ mod utils;
 pub use optimizer::Optimizer;
 pub use optimizer::Optimizers;
 pub use optimizer_constant_folding::ConstantFoldingOptimizer;
+pub use optimizer_constraints::ConstraintsOptimizer;
 pub use optimizer_expression_transform::ExprTransformOptimizer;
 pub use optimizer_projection_push_down::ProjectionPushDownOptimizer;
 pub use optimizer_scatters::ScattersOptimizer;
@@ -20,6 +20,7 @@ use metrics::histogram;
 
 use crate::optimizers::optimizer_scatters::ScattersOptimizer;
 use crate::optimizers::ConstantFoldingOptimizer;
+use crate::optimizers::ConstraintsOptimizer;
 use crate::optimizers::ExprTransformOptimizer;
 use crate::optimizers::ProjectionPushDownOptimizer;
 use crate::optimizers::StatisticsExactOptimizer;
@@ -48,6 +49,7 @@ impl Optimizers {
             inner: vec![
                 Box::new(ConstantFoldingOptimizer::create(ctx.clone())),
                 Box::new(ExprTransformOptimizer::create(ctx.clone())),
+                Box::new(ConstraintsOptimizer::create(ctx.clone())),
                 Box::new(ProjectionPushDownOptimizer::create(ctx.clone())),
                 Box::new(StatisticsExactOptimizer::create(ctx)),
             ],
@@ -261,13 +261,18 @@ impl PlanRewriter for ConstantFoldingImpl {
                     )
                 }
             },
-            Expression::Cast { expr, data_type } => {
+            Expression::Cast {
+                expr,
+                data_type,
+                is_try,
+            } => {
                 let new_expr = self.rewrite_expr(schema, expr)?;
 
                 if matches!(&new_expr, Expression::Literal { .. }) {
                     let optimize_expr = Expression::Cast {
                         expr: Box::new(new_expr),
                         data_type: data_type.clone(),
+                        is_try: *is_try,
                     };
 
                     return Self::execute_expression(optimize_expr, origin.column_name());
@@ -276,6 +281,7 @@ impl PlanRewriter for ConstantFoldingImpl {
                 Ok(Expression::Cast {
                     expr: Box::new(new_expr),
                     data_type: data_type.clone(),
+                    is_try: *is_try,
                 })
             }
             Expression::Sort {
@@ -0,0 +1,163 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_planners::col;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+
+use crate::datasources::common::TableConstraints;
+use crate::optimizers::Optimizer;
+use crate::sessions::DatabendQueryContextRef;
+
+/// Simplifies the aggregations over the informational PRIMARY KEY and UNIQUE keys of a table:
+/// - `SELECT a, b FROM t GROUP BY a, b` reads the rows as they are if `a` is a key of `t`,
+///   every group holds a single row.
+/// - `SELECT count(DISTINCT a) FROM t` is `SELECT count(a) FROM t` if `a` is a key of `t`.
+pub struct ConstraintsOptimizer {}
+
+struct ConstraintsImpl {}
+
+impl ConstraintsImpl {
+    /// Returns the keys of the table read by the plan which are still unique in its output.
+    fn unique_keys(plan: &PlanNode) -> Vec<Vec<String>> {
+        match plan {
+            PlanNode::ReadSource(plan) => TableConstraints::from_options(&plan.table_info.options)
+                .keys(&plan.table_info.schema),
+            PlanNode::Filter(plan) => Self::unique_keys(&plan.input),
+            PlanNode::Sort(plan) => Self::unique_keys(&plan.input),
+            PlanNode::Limit(plan) => Self::unique_keys(&plan.input),
+            PlanNode::Expression(plan) => {
+                // The input columns pass through unless an expression shadows them.
+                let mut keys = Self::unique_keys(&plan.input);
+                keys.retain(|key| {
+                    key.iter().all(|column| {
+                        plan.exprs
+                            .iter()
+                            .all(|expr| expr.column_name() != *column || expr == &col(column))
+                    })
+                });
+                keys
+            }
+            PlanNode::Projection(plan) => {
+                let mut keys = Self::unique_keys(&plan.input);
+                keys.retain(|key| key.iter().all(|column| plan.expr.contains(&col(column))));
+                keys
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Whether the columns among the expressions make up one of the keys.
+    fn cover_key(keys: &[Vec<String>], exprs: &[Expression]) -> bool {
+        keys.iter().any(|key| {
+            key.iter()
+                .all(|column| exprs.iter().any(|expr| expr == &col(column)))
+        })
+    }
+}
+
+impl PlanRewriter for ConstraintsImpl {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        let keys = match &new_input {
+            PlanNode::AggregatorPartial(partial) => Self::unique_keys(&partial.input),
+            _ => vec![],
+        };
+
+        if !keys.is_empty() {
+            let input = new_input.input(0);
+
+            if plan.aggr_expr.is_empty() && Self::cover_key(&keys, &plan.group_expr) {
+                return PlanBuilder::from(input.as_ref())
+                    .expression(&plan.group_expr, "Group by unique key")?
+                    .build();
+            }
+
+            let mut new_aggr_expr: Vec<Expression> = vec![];
+            let mut aliases = vec![];
+            for expr in plan.aggr_expr.iter() {
+                let new_expr = match expr {
+                    Expression::AggregateFunction {
+                        op,
+                        distinct: true,
+                        params,
+                        args,
+                    } if Self::cover_key(&keys, args) => {
+                        let new_expr = Expression::AggregateFunction {
+                            op: op.clone(),
+                            distinct: false,
+                            params: params.clone(),
+                            args: args.clone(),
+                        };
+                        aliases.push(col(&new_expr.column_name()).alias(&expr.column_name()));
+                        new_expr
+                    }
+                    _ => expr.clone(),
+                };
+                if !new_aggr_expr.contains(&new_expr) {
+                    new_aggr_expr.push(new_expr);
+                }
+            }
+
+            if !aliases.is_empty() {
+                return PlanBuilder::from(input.as_ref())
+                    .aggregate_partial(&new_aggr_expr, &plan.group_expr)?
+                    .aggregate_final(
+                        plan.schema_before_group_by.clone(),
+                        &new_aggr_expr,
+                        &plan.group_expr,
+                    )?
+                    .expression(&aliases, "Distinct on unique key")?
+                    .build();
+            }
+        }
+
+        PlanBuilder::from(&new_input)
+            .aggregate_final(
+                plan.schema_before_group_by.clone(),
+                &plan.aggr_expr,
+                &plan.group_expr,
+            )?
+            .build()
+    }
+}
+
+impl Optimizer for ConstraintsOptimizer {
+    fn name(&self) -> &str {
+        "Constraints"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = ConstraintsImpl {};
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl ConstraintsOptimizer {
+    pub fn create(_ctx: DatabendQueryContextRef) -> Self {
+        ConstraintsOptimizer {}
+    }
+}
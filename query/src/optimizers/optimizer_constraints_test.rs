@@ -0,0 +1,122 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::optimizer_test::*;
+use crate::optimizers::*;
+
+fn read_plan(primary_key: &str) -> PlanNode {
+    let mut table_info = TableInfo::simple(
+        "default",
+        "t",
+        DataSchemaRefExt::create(vec![
+            DataField::new("a", DataType::Int64, false),
+            DataField::new("b", DataType::String, false),
+        ]),
+    );
+    table_info
+        .options
+        .insert("primary_key".to_string(), primary_key.to_string());
+
+    PlanNode::ReadSource(ReadDataSourcePlan {
+        table_info,
+        parts: generate_partitions(1, 10),
+        statistics: Statistics::default(),
+        description: "".to_string(),
+        scan_plan: Arc::new(ScanPlan::empty()),
+        tbl_args: None,
+        push_downs: None,
+    })
+}
+
+#[test]
+fn test_constraints_optimizer_group_by() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // SELECT a, b FROM t GROUP BY a, b
+    let source_plan = read_plan("a");
+    let group_expr = vec![col("a"), col("b")];
+    let plan = PlanBuilder::from(&source_plan)
+        .expression(&group_expr, "Before GroupBy")?
+        .aggregate_partial(&[], &group_expr)?
+        .aggregate_final(source_plan.schema(), &[], &group_expr)?
+        .project(&group_expr)?
+        .build()?;
+
+    let mut optimizer = ConstraintsOptimizer::create(ctx.clone());
+    let optimized = optimizer.optimize(&plan)?;
+
+    let expect = "\
+    Projection: a:Int64, b:String\
+    \n  Expression: a:Int64, b:String (Group by unique key)\
+    \n    Expression: a:Int64, b:String (Before GroupBy)\
+    \n      ReadDataSource: scan partitions: [1], scan schema: [a:Int64, b:String], statistics: [read_rows: 0, read_bytes: 0]";
+    assert_eq!(expect, format!("{:?}", optimized));
+
+    // b is not a key, the groups may hold many rows
+    let source_plan = read_plan("b");
+    let group_expr = vec![col("a")];
+    let plan = PlanBuilder::from(&source_plan)
+        .expression(&group_expr, "Before GroupBy")?
+        .aggregate_partial(&[], &group_expr)?
+        .aggregate_final(source_plan.schema(), &[], &group_expr)?
+        .project(&group_expr)?
+        .build()?;
+
+    let optimized = optimizer.optimize(&plan)?;
+    assert_eq!(format!("{:?}", plan), format!("{:?}", optimized));
+
+    Ok(())
+}
+
+#[test]
+fn test_constraints_optimizer_distinct() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // SELECT count(DISTINCT a), b FROM t GROUP BY b
+    let source_plan = read_plan("a");
+    let aggr_expr = Expression::AggregateFunction {
+        op: "count".to_string(),
+        distinct: true,
+        params: vec![],
+        args: vec![col("a")],
+    };
+    let plan = PlanBuilder::from(&source_plan)
+        .expression(&[col("b"), col("a")], "Before GroupBy")?
+        .aggregate_partial(&[aggr_expr.clone()], &[col("b")])?
+        .aggregate_final(source_plan.schema(), &[aggr_expr], &[col("b")])?
+        .project(&[col("count(distinct a)"), col("b")])?
+        .build()?;
+
+    let mut optimizer = ConstraintsOptimizer::create(ctx);
+    let optimized = optimizer.optimize(&plan)?;
+
+    let expect = "\
+    Projection: count(distinct a):UInt64, b:String\
+    \n  Expression: count(a) as count(distinct a):UInt64 (Distinct on unique key)\
+    \n    AggregatorFinal: groupBy=[[b]], aggr=[[count(a)]]\
+    \n      AggregatorPartial: groupBy=[[b]], aggr=[[count(a)]]\
+    \n        Expression: b:String, a:Int64 (Before GroupBy)\
+    \n          ReadDataSource: scan partitions: [1], scan schema: [a:Int64, b:String], statistics: [read_rows: 0, read_bytes: 0]";
+    assert_eq!(expect, format!("{:?}", optimized));
+
+    Ok(())
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
@@ -31,7 +32,10 @@ use common_planners::PlanNode;
 use common_planners::PlanRewriter;
 use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
+use common_planners::RecursiveCtePlan;
 use common_planners::SortPlan;
+use common_planners::UnnestPlan;
+use common_planners::WindowPlan;
 
 use crate::optimizers::Optimizer;
 use crate::optimizers::RequireColumnsVisitor;
@@ -116,6 +120,32 @@ impl PlanRewriter for ProjectionPushDownImpl {
             .build()
     }
 
+    fn rewrite_unnest(&mut self, plan: &UnnestPlan) -> Result<PlanNode> {
+        self.required_columns.insert(plan.column.clone());
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .unnest(&plan.column, &plan.alias)?
+            .build()
+    }
+
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        self.collect_column_names_from_expr_vec(&plan.window_exprs)?;
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .window(&plan.window_exprs)?
+            .build()
+    }
+
+    fn rewrite_recursive_cte(&mut self, plan: &RecursiveCtePlan) -> Result<PlanNode> {
+        // The terms are queries of their own, all the columns of them are required
+        Ok(PlanNode::RecursiveCte(RecursiveCtePlan {
+            name: plan.name.clone(),
+            schema: plan.schema.clone(),
+            anchor: Arc::new(ProjectionPushDownImpl::new().rewrite_plan_node(&plan.anchor)?),
+            recursive: Arc::new(ProjectionPushDownImpl::new().rewrite_plan_node(&plan.recursive)?),
+        }))
+    }
+
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
         // TODO: rewrite scan
         self.get_projected_schema(plan.table_info.schema.as_ref())
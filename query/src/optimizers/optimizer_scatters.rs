@@ -28,9 +28,11 @@ use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
 use common_planners::ReadDataSourcePlan;
+use common_planners::RecursiveCtePlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
+use common_planners::WindowPlan;
 
 use crate::optimizers::Optimizer;
 use crate::sessions::DatabendQueryContext;
@@ -175,6 +177,27 @@ impl ScattersOptimizerImpl {
         }
     }
 
+    fn cluster_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        // Window we convergent it in local node
+        self.running_mode = RunningMode::Standalone;
+
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Cluster window input is None")),
+            Some(input) => Self::convergent_shuffle_stage_builder(input)
+                .window(&plan.window_exprs)?
+                .build(),
+        }
+    }
+
+    fn standalone_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Standalone window input is None")),
+            Some(input) => PlanBuilder::from(input.as_ref())
+                .window(&plan.window_exprs)?
+                .build(),
+        }
+    }
+
     fn convergent_shuffle_stage_builder(input: Arc<PlanNode>) -> PlanBuilder {
         PlanBuilder::from(&PlanNode::Stage(StagePlan {
             kind: StageKind::Convergent,
@@ -279,6 +302,21 @@ impl PlanRewriter for ScattersOptimizerImpl {
         }
     }
 
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        self.input = Some(Arc::new(self.rewrite_plan_node(plan.input.as_ref())?));
+
+        match self.running_mode {
+            RunningMode::Cluster => self.cluster_window(plan),
+            RunningMode::Standalone => self.standalone_window(plan),
+        }
+    }
+
+    fn rewrite_recursive_cte(&mut self, plan: &RecursiveCtePlan) -> Result<PlanNode> {
+        // The terms are executed again and again in the local node
+        self.running_mode = RunningMode::Standalone;
+        Ok(PlanNode::RecursiveCte(plan.clone()))
+    }
+
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
         let context = self.ctx.clone();
         let select_table = if plan.tbl_args.is_none() {
@@ -22,16 +22,35 @@ use super::MixedProcessor;
 use crate::pipelines::processors::MergeProcessor;
 use crate::pipelines::processors::Pipe;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::processors::ProfilingProcessor;
 use crate::sessions::DatabendQueryContextRef;
 
 pub struct Pipeline {
     ctx: DatabendQueryContextRef,
     pipes: Vec<Pipe>,
+    /// If the processors are wrapped in ProfilingProcessor, for EXPLAIN ANALYZE
+    profiling: bool,
 }
 
 impl Pipeline {
     pub fn create(ctx: DatabendQueryContextRef) -> Self {
-        Pipeline { ctx, pipes: vec![] }
+        Pipeline {
+            ctx,
+            pipes: vec![],
+            profiling: false,
+        }
+    }
+
+    /// Profiles the processors added after it.
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+    }
+
+    fn profile(&self, processor: Arc<dyn Processor>) -> Arc<dyn Processor> {
+        match self.profiling {
+            true => Arc::new(ProfilingProcessor::create(processor)),
+            false => processor,
+        }
     }
 
     /// Reset the pipeline.
@@ -63,6 +82,7 @@ impl Pipeline {
     }
 
     pub fn add_source(&mut self, source: Arc<dyn Processor>) -> Result<()> {
+        let source = self.profile(source);
         if self.pipes.first().is_none() {
             let mut first = Pipe::create();
             first.add(source);
@@ -90,7 +110,7 @@ impl Pipeline {
         for x in last_pipe.processors() {
             let mut p = f()?;
             p.connect_to(x.clone())?;
-            new_pipe.add(Arc::from(p));
+            new_pipe.add(self.profile(Arc::from(p)));
         }
         self.pipes.push(new_pipe);
         Ok(())
@@ -112,7 +132,7 @@ impl Pipeline {
                 merge.connect_to(x.clone())?;
             }
             let mut new_pipe = Pipe::create();
-            new_pipe.add(Arc::from(merge));
+            new_pipe.add(self.profile(Arc::from(merge)));
             self.pipes.push(new_pipe);
         }
         Ok(())
@@ -145,9 +165,9 @@ impl Pipeline {
         let mut new_pipe = Pipe::create();
         for _i in 0..n - 1 {
             let processor = processor.share()?;
-            new_pipe.add(Arc::from(processor));
+            new_pipe.add(self.profile(Arc::from(processor)));
         }
-        new_pipe.add(Arc::from(processor));
+        new_pipe.add(self.profile(Arc::from(processor)));
         self.pipes.push(new_pipe);
 
         Ok(())
@@ -18,6 +18,7 @@ use common_exception::Result;
 use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
 use common_planners::BroadcastPlan;
+use common_planners::Expression;
 use common_planners::ExpressionPlan;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
@@ -26,42 +27,74 @@ use common_planners::LimitPlan;
 use common_planners::PlanNode;
 use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
+use common_planners::RecursiveCtePlan;
 use common_planners::RemotePlan;
 use common_planners::SelectPlan;
 use common_planners::SortPlan;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::UnnestPlan;
+use common_planners::WindowPlan;
+use common_planners::WorkingTablePlan;
 use common_tracing::tracing;
 
 use crate::api::FlightTicket;
 use crate::pipelines::processors::Pipeline;
 use crate::pipelines::transforms::AggregatorFinalTransform;
 use crate::pipelines::transforms::AggregatorPartialTransform;
+use crate::pipelines::transforms::AggregatorSpill;
 use crate::pipelines::transforms::CreateSetsTransform;
 use crate::pipelines::transforms::ExpressionTransform;
 use crate::pipelines::transforms::GroupByFinalTransform;
 use crate::pipelines::transforms::GroupByPartialTransform;
+use crate::pipelines::transforms::GroupBySortedTransform;
 use crate::pipelines::transforms::HavingTransform;
 use crate::pipelines::transforms::LimitByTransform;
 use crate::pipelines::transforms::LimitTransform;
 use crate::pipelines::transforms::ProjectionTransform;
+use crate::pipelines::transforms::RecursiveCteTransform;
 use crate::pipelines::transforms::RemoteTransform;
 use crate::pipelines::transforms::SortMergeTransform;
 use crate::pipelines::transforms::SortPartialTransform;
 use crate::pipelines::transforms::SourceTransform;
 use crate::pipelines::transforms::SubQueriesPuller;
+use crate::pipelines::transforms::UnnestTransform;
 use crate::pipelines::transforms::WhereTransform;
+use crate::pipelines::transforms::WindowTransform;
+use crate::pipelines::transforms::WorkingTableSource;
+use crate::pipelines::transforms::WorkingTables;
 use crate::sessions::DatabendQueryContextRef;
 
 pub struct PipelineBuilder {
     ctx: DatabendQueryContextRef,
 
     limit: Option<usize>,
+
+    /// The working tables readable in the recursive terms being executed
+    working_tables: WorkingTables,
+
+    /// If the processors are profiled, for EXPLAIN ANALYZE
+    profiling: bool,
 }
 
 impl PipelineBuilder {
     pub fn create(ctx: DatabendQueryContextRef) -> PipelineBuilder {
-        PipelineBuilder { ctx, limit: None }
+        PipelineBuilder {
+            ctx,
+            limit: None,
+            working_tables: WorkingTables::new(),
+            profiling: false,
+        }
+    }
+
+    pub fn with_working_tables(mut self, working_tables: WorkingTables) -> PipelineBuilder {
+        self.working_tables = working_tables;
+        self
+    }
+
+    pub fn with_profiling(mut self) -> PipelineBuilder {
+        self.profiling = true;
+        self
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -72,6 +105,12 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn create_pipeline(&self) -> Pipeline {
+        let mut pipeline = Pipeline::create(self.ctx.clone());
+        pipeline.set_profiling(self.profiling);
+        pipeline
+    }
+
     fn visit(&mut self, node: &PlanNode) -> Result<Pipeline> {
         match node {
             PlanNode::Select(node) => self.visit_select(node),
@@ -87,6 +126,10 @@ impl PipelineBuilder {
             PlanNode::Sort(node) => self.visit_sort(node),
             PlanNode::Limit(node) => self.visit_limit(node),
             PlanNode::LimitBy(node) => self.visit_limit_by(node),
+            PlanNode::Unnest(node) => self.visit_unnest(node),
+            PlanNode::Window(node) => self.visit_window(node),
+            PlanNode::RecursiveCte(node) => self.visit_recursive_cte(node),
+            PlanNode::WorkingTable(node) => self.visit_working_table(node),
             PlanNode::ReadSource(node) => self.visit_read_data_source(node),
             PlanNode::SubQueryExpression(node) => self.visit_create_sets(node),
             other => Result::Err(ErrorCode::UnknownPlan(format!(
@@ -113,7 +156,7 @@ impl PipelineBuilder {
     }
 
     fn visit_remote(&self, plan: &RemotePlan) -> Result<Pipeline> {
-        let mut pipeline = Pipeline::create(self.ctx.clone());
+        let mut pipeline = self.create_pipeline();
 
         for fetch_node in &plan.fetch_nodes {
             let flight_ticket =
@@ -166,19 +209,35 @@ impl PipelineBuilder {
                 )?))
             })?;
         } else {
+            let spill = AggregatorSpill::try_create(&self.ctx)?;
             pipeline.add_simple_transform(|| {
-                Ok(Box::new(GroupByPartialTransform::create(
-                    node.schema(),
-                    node.input.schema(),
-                    node.aggr_expr.clone(),
-                    node.group_expr.clone(),
-                )))
+                Ok(Box::new(
+                    GroupByPartialTransform::create(
+                        node.schema(),
+                        node.input.schema(),
+                        node.aggr_expr.clone(),
+                        node.group_expr.clone(),
+                    )
+                    .with_spill(spill.clone()),
+                ))
             })?;
         }
         Ok(pipeline)
     }
 
     fn visit_aggregator_final(&mut self, node: &AggregatorFinalPlan) -> Result<Pipeline> {
+        if let PlanNode::AggregatorPartial(partial) = node.input.as_ref() {
+            let group_cols = node
+                .group_expr
+                .iter()
+                .map(|expr| expr.column_name())
+                .collect::<Vec<_>>();
+
+            if !group_cols.is_empty() && Self::is_sorted_by(&partial.input, &group_cols) {
+                return self.visit_group_by_sorted(node, partial);
+            }
+        }
+
         let mut pipeline = self.visit(&*node.input)?;
         pipeline.merge_processor()?;
 
@@ -192,20 +251,91 @@ impl PipelineBuilder {
             })?;
         } else {
             let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+            let spill = AggregatorSpill::try_create(&self.ctx)?;
             pipeline.add_simple_transform(|| {
-                Ok(Box::new(GroupByFinalTransform::create(
-                    node.schema(),
-                    max_block_size,
-                    node.schema_before_group_by.clone(),
-                    node.aggr_expr.clone(),
-                    node.group_expr.clone(),
-                )))
+                Ok(Box::new(
+                    GroupByFinalTransform::create(
+                        node.schema(),
+                        max_block_size,
+                        node.schema_before_group_by.clone(),
+                        node.aggr_expr.clone(),
+                        node.group_expr.clone(),
+                    )
+                    .with_spill(spill.clone()),
+                ))
             })?;
             pipeline.mixed_processor(self.ctx.get_settings().get_max_threads()? as usize)?;
         }
         Ok(pipeline)
     }
 
+    // The rows of a group are adjacent if the input is sorted by the group columns,
+    // so the groups can be emitted one by one without the partial and final group by.
+    fn visit_group_by_sorted(
+        &mut self,
+        node: &AggregatorFinalPlan,
+        partial: &AggregatorPartialPlan,
+    ) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*partial.input)?;
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(GroupBySortedTransform::create(
+                node.schema(),
+                node.schema_before_group_by.clone(),
+                node.aggr_expr.clone(),
+                node.group_expr.clone(),
+            )))
+        })?;
+        Ok(pipeline)
+    }
+
+    /// Whether the output of the plan is one stream sorted by the columns, in any order of them.
+    fn is_sorted_by(plan: &PlanNode, columns: &[String]) -> bool {
+        match plan {
+            PlanNode::Sort(node) => {
+                node.order_by.len() >= columns.len()
+                    && node.order_by[..columns.len()]
+                        .iter()
+                        .all(|expr| columns.contains(&expr.column_name()))
+            }
+            PlanNode::Expression(node) => {
+                let columns = columns
+                    .iter()
+                    .map(|column| {
+                        Self::input_column(&node.exprs, column).unwrap_or_else(|| column.clone())
+                    })
+                    .collect::<Vec<_>>();
+                Self::is_sorted_by(&node.input, &columns)
+            }
+            PlanNode::Projection(node) => {
+                let columns = columns
+                    .iter()
+                    .map(|column| Self::input_column(&node.expr, column))
+                    .collect::<Option<Vec<_>>>();
+                match columns {
+                    None => false,
+                    Some(columns) => Self::is_sorted_by(&node.input, &columns),
+                }
+            }
+            PlanNode::Select(node) => Self::is_sorted_by(&node.input, columns),
+            PlanNode::Filter(node) => Self::is_sorted_by(&node.input, columns),
+            PlanNode::Having(node) => Self::is_sorted_by(&node.input, columns),
+            PlanNode::Limit(node) => Self::is_sorted_by(&node.input, columns),
+            PlanNode::LimitBy(node) => Self::is_sorted_by(&node.input, columns),
+            _ => false,
+        }
+    }
+
+    // The input column name of the output column, alias is resolved to the aliased expression.
+    fn input_column(exprs: &[Expression], column: &str) -> Option<String> {
+        exprs
+            .iter()
+            .find(|expr| expr.column_name() == column)
+            .map(|expr| match expr {
+                Expression::Alias(_, inner) => inner.column_name(),
+                other => other.column_name(),
+            })
+    }
+
     fn visit_filter(&mut self, node: &FilterPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*node.input)?;
         pipeline.add_simple_transform(|| {
@@ -217,6 +347,63 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn visit_unnest(&mut self, node: &UnnestPlan) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*node.input)?;
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(UnnestTransform::create(
+                node.column.clone(),
+                node.schema(),
+            )))
+        })?;
+        Ok(pipeline)
+    }
+
+    fn visit_window(&mut self, node: &WindowPlan) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*node.input)?;
+
+        // The windows are computed over all the rows
+        if pipeline.last_pipe()?.nums() > 1 {
+            pipeline.merge_processor()?;
+        }
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(
+                WindowTransform::create(
+                    node.schema(),
+                    node.input.schema(),
+                    node.window_exprs.clone(),
+                )
+                .with_memory_tracker(self.ctx.get_memory_tracker()),
+            ))
+        })?;
+        Ok(pipeline)
+    }
+
+    fn visit_recursive_cte(&mut self, node: &RecursiveCtePlan) -> Result<Pipeline> {
+        let mut pipeline = self.create_pipeline();
+        pipeline.add_source(Arc::new(RecursiveCteTransform::create(
+            self.ctx.clone(),
+            node.clone(),
+            self.working_tables.clone(),
+        )))?;
+        Ok(pipeline)
+    }
+
+    fn visit_working_table(&mut self, node: &WorkingTablePlan) -> Result<Pipeline> {
+        let blocks = self.working_tables.get(&node.name).ok_or_else(|| {
+            ErrorCode::LogicalError(format!(
+                "Working table {} is only readable in the recursive term of its query",
+                node.name
+            ))
+        })?;
+
+        let mut pipeline = self.create_pipeline();
+        pipeline.add_source(Arc::new(WorkingTableSource::create(
+            node.schema(),
+            blocks.clone(),
+        )))?;
+        Ok(pipeline)
+    }
+
     fn visit_having(&mut self, node: &HavingPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*node.input)?;
         pipeline.add_simple_transform(|| {
@@ -246,11 +433,10 @@ impl PipelineBuilder {
         // processor 2: [sorted blocks ...] ---> merge to one sorted block
         // processor 3: [sorted blocks ...] ---> merge to one sorted block
         pipeline.add_simple_transform(|| {
-            Ok(Box::new(SortMergeTransform::try_create(
-                plan.schema(),
-                plan.order_by.clone(),
-                self.limit,
-            )?))
+            Ok(Box::new(
+                SortMergeTransform::try_create(plan.schema(), plan.order_by.clone(), self.limit)?
+                    .with_memory_tracker(self.ctx.get_memory_tracker()),
+            ))
         })?;
 
         // processor1 sorted block --
@@ -261,11 +447,14 @@ impl PipelineBuilder {
         if pipeline.last_pipe()?.nums() > 1 {
             pipeline.merge_processor()?;
             pipeline.add_simple_transform(|| {
-                Ok(Box::new(SortMergeTransform::try_create(
-                    plan.schema(),
-                    plan.order_by.clone(),
-                    self.limit,
-                )?))
+                Ok(Box::new(
+                    SortMergeTransform::try_create(
+                        plan.schema(),
+                        plan.order_by.clone(),
+                        self.limit,
+                    )?
+                    .with_memory_tracker(self.ctx.get_memory_tracker()),
+                ))
             })?;
         }
         Ok(pipeline)
@@ -298,10 +487,15 @@ impl PipelineBuilder {
         // Bind plan partitions to context.
         self.ctx.try_set_partitions(plan.parts.clone())?;
 
-        let mut pipeline = Pipeline::create(self.ctx.clone());
-        let max_threads = self.ctx.get_settings().get_max_threads()? as usize;
-        let max_threads = std::cmp::min(max_threads, plan.parts.len());
-        let workers = std::cmp::max(max_threads, 1);
+        let mut pipeline = self.create_pipeline();
+        // The sources of a scan are decoupled from the threads of the query, which process the
+        // blocks after the scan.
+        let parallelism = self
+            .ctx
+            .get_settings()
+            .get_effective_scan_decode_parallelism()?;
+        let parallelism = std::cmp::min(parallelism, plan.parts.len());
+        let workers = std::cmp::max(parallelism, 1);
 
         for _i in 0..workers {
             let source = SourceTransform::try_create(self.ctx.clone(), plan.clone())?;
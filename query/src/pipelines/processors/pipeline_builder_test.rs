@@ -160,3 +160,44 @@ async fn test_local_pipeline_builds() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_group_by_sorted_pipeline_builds() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // The input is sorted by the group column.
+    {
+        let query = "select c1, count() as c2 from (select number % 3 as c1 from numbers_mt(10) order by c1) group by c1";
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        let pipeline_builder = PipelineBuilder::create(ctx.clone());
+        let mut pipeline = pipeline_builder.build(&plan)?;
+        let actual_pipeline = format!("{:?}", pipeline);
+        assert!(actual_pipeline.contains("GroupBySortedTransform × 1 processor"));
+        assert!(!actual_pipeline.contains("GroupByFinalTransform"));
+
+        let stream = pipeline.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+----+----+",
+            "| c1 | c2 |",
+            "+----+----+",
+            "| 0  | 4  |",
+            "| 1  | 3  |",
+            "| 2  | 3  |",
+            "+----+----+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    // The input is not sorted.
+    {
+        let query = "select number % 3 as c1, count() as c2 from numbers_mt(10) group by c1";
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        let pipeline_builder = PipelineBuilder::create(ctx.clone());
+        let pipeline = pipeline_builder.build(&plan)?;
+        let actual_pipeline = format!("{:?}", pipeline);
+        assert!(!actual_pipeline.contains("GroupBySortedTransform"));
+    }
+
+    Ok(())
+}
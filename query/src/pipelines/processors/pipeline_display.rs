@@ -14,12 +14,38 @@
 
 use std::fmt;
 use std::fmt::Display;
+use std::time::Duration;
 
+use crate::pipelines::processors::Pipe;
 use crate::pipelines::processors::Pipeline;
+use crate::pipelines::processors::ProfilingProcessor;
 
 impl Pipeline {
     pub fn display_indent(&self) -> impl fmt::Display + '_ {
-        struct Wrapper<'a>(&'a Pipeline);
+        self.display_indent_with_profile(false)
+    }
+
+    /// Displays the pipeline with the rows, the bytes and the elapsed time of the blocks pulled
+    /// from each pipe, summed over its processors. The pipeline needs to be profiled and executed.
+    pub fn display_analyze(&self) -> impl fmt::Display + '_ {
+        self.display_indent_with_profile(true)
+    }
+
+    fn pipe_profile(pipe: &Pipe) -> (usize, usize, Duration) {
+        let mut profile = (0, 0, Duration::default());
+        for processor in pipe.processors() {
+            if let Some(processor) = processor.as_any().downcast_ref::<ProfilingProcessor>() {
+                let processor_profile = processor.profile();
+                profile.0 += processor_profile.rows();
+                profile.1 += processor_profile.bytes();
+                profile.2 += processor_profile.elapsed();
+            }
+        }
+        profile
+    }
+
+    fn display_indent_with_profile(&self, with_profile: bool) -> impl fmt::Display + '_ {
+        struct Wrapper<'a>(&'a Pipeline, bool);
         impl<'a> fmt::Display for Wrapper<'a> {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                 let mut indent = 0;
@@ -122,13 +148,22 @@ impl Pipeline {
                         }
                     }
 
+                    if self.1 && processor.name() != "EmptyProcessor" {
+                        let (rows, bytes, elapsed) = Pipeline::pipe_profile(pipe);
+                        write!(
+                            f,
+                            ", rows: {}, bytes: {}, elapsed: {:?}",
+                            rows, bytes, elapsed
+                        )?;
+                    }
+
                     index += 1;
                     Result::<bool, fmt::Error>::Ok(true)
                 })?;
                 Ok(())
             }
         }
-        Wrapper(self)
+        Wrapper(self, with_profile)
     }
 
     pub fn display_graphviz(&self) -> impl fmt::Display + '_ {
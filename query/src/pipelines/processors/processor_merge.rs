@@ -21,6 +21,8 @@ use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use common_tracing::tracing::Instrument;
 use log::error;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
@@ -53,36 +55,40 @@ impl MergeProcessor {
         for i in 0..len {
             let processor = self.inputs[i].clone();
             let sender = sender.clone();
-            self.ctx.try_spawn(async move {
-                let mut stream = match processor.execute().await {
-                    Err(e) => {
-                        if let Err(error) = sender.send(Result::Err(e)).await {
-                            error!("Merge processor cannot push data: {}", error);
+            let span = tracing::info_span!("processor", name = processor.name());
+            self.ctx.try_spawn(
+                async move {
+                    let mut stream = match processor.execute().await {
+                        Err(e) => {
+                            if let Err(error) = sender.send(Result::Err(e)).await {
+                                error!("Merge processor cannot push data: {}", error);
+                            }
+                            return;
                         }
-                        return;
-                    }
-                    Ok(stream) => stream,
-                };
+                        Ok(stream) => stream,
+                    };
 
-                while let Some(item) = stream.next().await {
-                    match item {
-                        Ok(item) => {
-                            if let Err(error) = sender.send(Ok(item)).await {
+                    while let Some(item) = stream.next().await {
+                        match item {
+                            Ok(item) => {
+                                if let Err(error) = sender.send(Ok(item)).await {
+                                    // Stop pulling data
+                                    error!("Merge processor cannot push data: {}", error);
+                                    return;
+                                }
+                            }
+                            Err(error) => {
                                 // Stop pulling data
-                                error!("Merge processor cannot push data: {}", error);
+                                if let Err(error) = sender.send(Err(error)).await {
+                                    error!("Merge processor cannot push data: {}", error);
+                                }
                                 return;
                             }
                         }
-                        Err(error) => {
-                            // Stop pulling data
-                            if let Err(error) = sender.send(Err(error)).await {
-                                error!("Merge processor cannot push data: {}", error);
-                            }
-                            return;
-                        }
                     }
                 }
-            })?;
+                .instrument(span),
+            )?;
         }
         Ok(Box::pin(ReceiverStream::new(receiver)))
     }
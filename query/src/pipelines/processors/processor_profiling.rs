@@ -0,0 +1,72 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::ProfilingStream;
+use common_streams::SendableDataBlockStream;
+use common_streams::StreamProfile;
+
+use crate::pipelines::processors::Processor;
+
+/// Profiles the blocks of the processor it wraps, for EXPLAIN ANALYZE.
+pub struct ProfilingProcessor {
+    processor: Arc<dyn Processor>,
+    profile: Arc<StreamProfile>,
+}
+
+impl ProfilingProcessor {
+    pub fn create(processor: Arc<dyn Processor>) -> Self {
+        ProfilingProcessor {
+            processor,
+            profile: Arc::new(StreamProfile::default()),
+        }
+    }
+
+    pub fn profile(&self) -> Arc<StreamProfile> {
+        self.profile.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for ProfilingProcessor {
+    fn name(&self) -> &str {
+        self.processor.name()
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Result::Err(ErrorCode::IllegalTransformConnectionState(
+            "Cannot call ProfilingProcessor connect_to",
+        ))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        self.processor.inputs()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let stream = self.processor.execute().await?;
+        Ok(Box::pin(ProfilingStream::create(
+            stream,
+            self.profile.clone(),
+        )))
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_dal::SpilledBlock;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
 use common_datavalues::arrays::StringArrayBuilder;
@@ -19,12 +20,10 @@ use common_datavalues::columns::DataColumn;
 use common_datavalues::prelude::IntoSeries;
 use common_datavalues::prelude::Series;
 use common_datavalues::DataSchemaRef;
-use common_datavalues::DataSchemaRefExt;
 use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 use common_io::prelude::BytesMut;
-use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use futures::StreamExt;
 
@@ -33,6 +32,7 @@ use crate::pipelines::transforms::group_by::aggregator_params::AggregatorParams;
 use crate::pipelines::transforms::group_by::aggregator_params::AggregatorParamsRef;
 use crate::pipelines::transforms::group_by::aggregator_state::AggregatorState;
 use crate::pipelines::transforms::group_by::aggregator_state_entity::StateEntity;
+use crate::pipelines::transforms::group_by::AggregatorSpill;
 use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
 
 pub struct Aggregator<Method: HashMethod> {
@@ -52,40 +52,46 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
         &self,
         group_cols: Vec<String>,
         mut stream: SendableDataBlockStream,
-    ) -> Result<Method::State> {
+        spill: &AggregatorSpill,
+        schema: &DataSchemaRef,
+    ) -> Result<SendableDataBlockStream> {
         // This may be confusing
         // It will help us improve performance ~10% when we declare local references for them.
         let hash_method = &self.method;
         let aggregator_params = self.params.as_ref();
 
         let mut state = hash_method.aggregate_state();
+        let mut spilled = vec![];
+        let mut tracked = spill.tracked();
 
-        match aggregator_params.aggregate_functions.is_empty() {
-            true => {
-                while let Some(block) = stream.next().await {
-                    let block = block?;
-
-                    // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
-                    let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
-                    self.lookup_key(group_keys, &mut state);
-                }
-            }
-            false => {
-                while let Some(block) = stream.next().await {
-                    let block = block?;
+        while let Some(block) = stream.next().await {
+            let block = block?;
 
-                    // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
-                    let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
+            // 1.1 and 1.2.
+            let group_columns = Self::group_columns(&group_cols, &block)?;
+            let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
 
+            match aggregator_params.aggregate_functions.is_empty() {
+                true => self.lookup_key(group_keys, &mut state),
+                false => {
                     let places = self.lookup_state(group_keys, &mut state);
                     Self::execute(aggregator_params, &block, &places)?;
                 }
             }
+
+            // The final aggregation merges the spilled states with the others of the same keys.
+            tracked.set(state.allocated_bytes());
+            if spill.should_spill() {
+                if let Some(block) = self.serialize_states(&state, schema)? {
+                    spilled.push(spill.spill(block)?);
+                }
+                state = hash_method.aggregate_state();
+                tracked.set(state.allocated_bytes());
+            }
+            tracked.check()?;
         }
 
-        Ok(state)
+        self.aggregate_finalized(&state, spilled, schema)
     }
 
     #[inline(always)]
@@ -175,17 +181,27 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
     }
 
     #[inline(never)]
-    pub fn aggregate_finalized(
+    fn aggregate_finalized(
         &self,
         groups: &Method::State,
-        schema: DataSchemaRef,
+        spilled: Vec<SpilledBlock>,
+        schema: &DataSchemaRef,
     ) -> Result<SendableDataBlockStream> {
+        let blocks = match self.serialize_states(groups, schema)? {
+            None => vec![],
+            Some(block) => vec![block],
+        };
+        Ok(AggregatorSpill::stream(spilled, blocks))
+    }
+
+    /// Serializes the keys and the aggregate function states of the groups into a block.
+    fn serialize_states(
+        &self,
+        groups: &Method::State,
+        schema: &DataSchemaRef,
+    ) -> Result<Option<DataBlock>> {
         if groups.len() == 0 {
-            return Ok(Box::pin(DataBlockStream::create(
-                DataSchemaRefExt::create(vec![]),
-                None,
-                vec![],
-            )));
+            return Ok(None);
         }
 
         let aggregator_params = self.params.as_ref();
@@ -221,7 +237,6 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
 
         columns.push(group_key_builder.finish());
 
-        let block = DataBlock::create_by_array(schema.clone(), columns);
-        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+        Ok(Some(DataBlock::create_by_array(schema.clone(), columns)))
     }
 }
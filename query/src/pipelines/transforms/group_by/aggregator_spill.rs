@@ -0,0 +1,87 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common_dal::SpilledBlock;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+use crate::datasources::table::memory::memory_table_spill::read_spilled_block;
+use crate::datasources::table::memory::memory_table_spill::spill_block;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::MemoryTracker;
+use crate::sessions::TrackedMemory;
+
+/// Partitions of the groups of the final aggregation once it spills, they are merged one by one.
+pub const SPILL_PARTITIONS: usize = 16;
+
+/// When the aggregations of a query write their states to the local disk.
+///
+/// The partial aggregations write out their hash tables and start over, the final aggregation
+/// partitions the groups by their keys and merges a partition at a time.
+#[derive(Clone)]
+pub struct AggregatorSpill {
+    max_bytes: usize,
+    tracker: Arc<MemoryTracker>,
+    dir: PathBuf,
+}
+
+impl AggregatorSpill {
+    pub fn try_create(ctx: &DatabendQueryContextRef) -> Result<AggregatorSpill> {
+        let settings = ctx.get_settings();
+        Ok(AggregatorSpill {
+            max_bytes: settings.get_max_bytes_before_external_group_by()? as usize,
+            tracker: ctx.get_memory_tracker(),
+            dir: std::env::temp_dir().join("databend_group_by_spill"),
+        })
+    }
+
+    /// Never spills, the states are only tracked.
+    pub fn disabled() -> AggregatorSpill {
+        AggregatorSpill {
+            max_bytes: 0,
+            tracker: MemoryTracker::create(),
+            dir: PathBuf::new(),
+        }
+    }
+
+    /// Tracks the bytes held by an aggregation in the memory tracker of the query.
+    pub fn tracked(&self) -> TrackedMemory {
+        self.tracker.tracked()
+    }
+
+    /// Whether the aggregations of the query hold more than `max_bytes_before_external_group_by`.
+    pub fn should_spill(&self) -> bool {
+        self.max_bytes > 0 && self.tracker.used() > self.max_bytes
+    }
+
+    pub fn spill(&self, block: DataBlock) -> Result<SpilledBlock> {
+        spill_block(&self.dir, block)
+    }
+
+    pub fn read(spilled: &SpilledBlock) -> Result<DataBlock> {
+        read_spilled_block(spilled)
+    }
+
+    /// Reads the spilled blocks back one by one, followed by the blocks in memory.
+    pub fn stream(spilled: Vec<SpilledBlock>, blocks: Vec<DataBlock>) -> SendableDataBlockStream {
+        let spilled = futures::stream::iter(spilled).map(|spilled| Self::read(&spilled));
+        let blocks = futures::stream::iter(blocks.into_iter().map(Ok));
+        Box::pin(spilled.chain(blocks))
+    }
+}
@@ -16,6 +16,7 @@ mod aggregator;
 mod aggregator_keys_builder;
 mod aggregator_params;
 mod aggregator_polymorphic_keys;
+mod aggregator_spill;
 mod aggregator_state;
 mod aggregator_state_entity;
 mod aggregator_state_iterator;
@@ -25,4 +26,6 @@ pub use aggregator::Aggregator;
 pub use aggregator_params::AggregatorParams;
 pub use aggregator_params::AggregatorParamsRef;
 pub use aggregator_polymorphic_keys::PolymorphicKeysHelper;
+pub use aggregator_spill::AggregatorSpill;
+pub use aggregator_spill::SPILL_PARTITIONS;
 pub use aggregator_state::AggregatorState;
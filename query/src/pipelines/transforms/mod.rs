@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use group_by::AggregatorSpill;
 pub use transform_aggregator_final::AggregatorFinalTransform;
 pub use transform_aggregator_partial::AggregatorPartialTransform;
 pub use transform_create_sets::CreateSetsTransform;
@@ -22,13 +23,20 @@ pub use transform_filter::HavingTransform;
 pub use transform_filter::WhereTransform;
 pub use transform_group_by_final::GroupByFinalTransform;
 pub use transform_group_by_partial::GroupByPartialTransform;
+pub use transform_group_by_sorted::GroupBySortedTransform;
 pub use transform_limit::LimitTransform;
 pub use transform_limit_by::LimitByTransform;
+pub use transform_predicate_executor::PredicateExecutor;
 pub use transform_projection::ProjectionTransform;
+pub use transform_recursive_cte::RecursiveCteTransform;
+pub use transform_recursive_cte::WorkingTableSource;
+pub use transform_recursive_cte::WorkingTables;
 pub use transform_remote::RemoteTransform;
 pub use transform_sort_merge::SortMergeTransform;
 pub use transform_sort_partial::SortPartialTransform;
 pub use transform_source::SourceTransform;
+pub use transform_unnest::UnnestTransform;
+pub use transform_window::WindowTransform;
 
 #[cfg(test)]
 mod transform_aggregator_final_test;
@@ -43,16 +51,25 @@ mod transform_group_by_final_test;
 #[cfg(test)]
 mod transform_group_by_partial_test;
 #[cfg(test)]
+mod transform_group_by_sorted_test;
+#[cfg(test)]
 mod transform_limit_by_test;
 #[cfg(test)]
 mod transform_limit_test;
 #[cfg(test)]
+mod transform_predicate_executor_test;
+#[cfg(test)]
 mod transform_projection_test;
 #[cfg(test)]
+mod transform_recursive_cte_test;
+#[cfg(test)]
 mod transform_sort_test;
 #[cfg(test)]
 mod transform_source_test;
+#[cfg(test)]
+mod transform_window_test;
 
+mod metrics;
 mod transform_aggregator_final;
 mod transform_aggregator_partial;
 mod transform_create_sets;
@@ -61,12 +78,17 @@ mod transform_expression_executor;
 mod transform_filter;
 mod transform_group_by_final;
 mod transform_group_by_partial;
+mod transform_group_by_sorted;
 mod transform_limit;
 mod transform_limit_by;
+mod transform_predicate_executor;
 mod transform_projection;
+mod transform_recursive_cte;
 mod transform_remote;
 mod transform_sort_merge;
 mod transform_sort_partial;
 mod transform_source;
+mod transform_unnest;
+mod transform_window;
 
 mod group_by;
@@ -20,7 +20,7 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_planners::Expression;
 use common_streams::SendableDataBlockStream;
-use tokio_stream::StreamExt;
+use futures::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
@@ -90,12 +90,10 @@ impl Processor for ExpressionTransform {
         let executor = self.executor.clone();
         let input_stream = self.input.execute().await?;
 
-        let executor_fn = |executor: &ExpressionExecutor,
-                           block: Result<DataBlock>|
-         -> Result<DataBlock> { executor.execute(&block?) };
-
-        let stream =
-            input_stream.filter_map(move |v| executor_fn(&executor, v).map(Some).transpose());
+        let stream = input_stream.then(move |block: Result<DataBlock>| {
+            let executor = executor.clone();
+            async move { executor.execute_async(block?).await }
+        });
 
         Ok(Box::pin(stream))
     }
@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_base::tokio;
 use common_datablocks::DataBlock;
 use common_datavalues::columns::DataColumn;
 use common_datavalues::prelude::DataColumnWithField;
@@ -22,6 +23,7 @@ use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
 use common_planners::Expression;
 use common_planners::ExpressionAction;
 use common_planners::ExpressionChain;
@@ -38,10 +40,10 @@ pub struct ExpressionExecutor {
     chain: Arc<ExpressionChain>,
     // whether to perform alias action in executor
     alias_project: bool,
+    // whether a function waits on the network, e.g. an external function
+    blocking: bool,
 }
 
-pub type ExpressionExecutorRef = Arc<ExpressionExecutor>;
-
 impl ExpressionExecutor {
     pub fn try_create(
         description: &str,
@@ -51,6 +53,13 @@ impl ExpressionExecutor {
         alias_project: bool,
     ) -> Result<Self> {
         let chain = ExpressionChain::try_create(input_schema.clone(), &exprs)?;
+        let blocking = chain.actions.iter().any(|action| match action {
+            ExpressionAction::Function(f) => FunctionFactory::instance()
+                .get_features(&f.func_name)
+                .map(|features| features.is_blocking)
+                .unwrap_or(false),
+            _ => false,
+        });
 
         Ok(Self {
             description: description.to_string(),
@@ -58,13 +67,30 @@ impl ExpressionExecutor {
             output_schema,
             chain: Arc::new(chain),
             alias_project,
+            blocking,
         })
     }
 
+    pub fn is_blocking(&self) -> bool {
+        self.blocking
+    }
+
     pub fn validate(&self) -> Result<()> {
         Ok(())
     }
 
+    /// Executes on the blocking threads if a function waits on the network, in place otherwise.
+    pub async fn execute_async(&self, block: DataBlock) -> Result<DataBlock> {
+        if !self.blocking {
+            return self.execute(&block);
+        }
+
+        let executor = self.clone();
+        tokio::task::spawn_blocking(move || executor.execute(&block))
+            .await
+            .map_err(|e| ErrorCode::TokioError(format!("Cannot execute expressions: {}", e)))?
+    }
+
     pub fn execute(&self, block: &DataBlock) -> Result<DataBlock> {
         tracing::debug!(
             "({:#}) execute, actions: {:?}",
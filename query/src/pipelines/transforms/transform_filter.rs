@@ -16,19 +16,20 @@ use std::any::Any;
 use std::sync::Arc;
 use std::time::Instant;
 
+use common_base::tokio;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Expression;
 use common_streams::CorrectWithSchemaStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
-use tokio_stream::StreamExt;
+use futures::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
-use crate::pipelines::transforms::transform_expression_executor::ExpressionExecutorRef;
-use crate::pipelines::transforms::ExpressionExecutor;
+use crate::pipelines::transforms::PredicateExecutor;
 
 pub type HavingTransform = FilterTransform<true>;
 pub type WhereTransform = FilterTransform<false>;
@@ -36,13 +37,12 @@ pub type WhereTransform = FilterTransform<false>;
 pub struct FilterTransform<const HAVING: bool> {
     schema: DataSchemaRef,
     input: Arc<dyn Processor>,
-    executor: Arc<ExpressionExecutor>,
+    executor: Arc<PredicateExecutor>,
 }
 
 impl<const HAVING: bool> FilterTransform<HAVING> {
     pub fn try_create(schema: DataSchemaRef, predicate: Expression) -> Result<Self> {
-        let predicate_executor = Self::expr_executor(&schema, &predicate)?;
-        predicate_executor.validate()?;
+        let predicate_executor = PredicateExecutor::try_create(&schema, &predicate)?;
 
         Ok(FilterTransform {
             schema,
@@ -51,32 +51,31 @@ impl<const HAVING: bool> FilterTransform<HAVING> {
         })
     }
 
-    fn expr_executor(schema: &DataSchemaRef, expr: &Expression) -> Result<ExpressionExecutor> {
-        let expr_field = expr.to_data_field(schema)?;
-        let expr_schema = DataSchemaRefExt::create(vec![expr_field]);
-
-        ExpressionExecutor::try_create(
-            "filter expression executor",
-            schema.clone(),
-            expr_schema,
-            vec![expr.clone()],
-            false,
-        )
-    }
-
-    fn filter(executor: Arc<ExpressionExecutor>, data: DataBlock) -> Result<DataBlock> {
-        let filter_block = executor.execute(&data)?;
-        let filter_array = filter_block.column(0).to_array()?;
-        DataBlock::filter_block(&data, filter_array)
-    }
-
-    fn filter_map(executor: ExpressionExecutorRef, data: DataBlock) -> Option<Result<DataBlock>> {
-        match Self::filter(executor, data) {
+    fn filter_map(executor: Arc<PredicateExecutor>, data: DataBlock) -> Option<Result<DataBlock>> {
+        match executor.filter(&data) {
             Err(error) => Some(Err(error)),
             Ok(data_block) if data_block.is_empty() => None,
             Ok(data_block) => Some(Ok(data_block)),
         }
     }
+
+    // On the blocking threads if the predicate waits on the network, e.g. an external function.
+    async fn filter_map_async(
+        executor: Arc<PredicateExecutor>,
+        data: DataBlock,
+    ) -> Option<Result<DataBlock>> {
+        if !executor.is_blocking() {
+            return Self::filter_map(executor, data);
+        }
+
+        match tokio::task::spawn_blocking(move || Self::filter_map(executor, data)).await {
+            Ok(res) => res,
+            Err(e) => Some(Err(ErrorCode::TokioError(format!(
+                "Cannot execute filter: {}",
+                e
+            )))),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -105,15 +104,20 @@ impl<const HAVING: bool> Processor for FilterTransform<HAVING> {
         let input_stream = self.input.execute().await?;
         let executor = self.executor.clone();
 
-        let stream = input_stream.filter_map(move |data_block| match data_block {
-            Ok(data_block) if data_block.is_empty() => None,
-            Err(fail) => Some(Err(fail)),
-            Ok(data_block) => {
-                tracing::debug!("execute...");
-                let start = Instant::now();
-                let res = Self::filter_map(executor.clone(), data_block);
-                tracing::debug!("Filter cost: {:?}", start.elapsed());
-                res
+        let stream = input_stream.filter_map(move |data_block| {
+            let executor = executor.clone();
+            async move {
+                match data_block {
+                    Ok(data_block) if data_block.is_empty() => None,
+                    Err(fail) => Some(Err(fail)),
+                    Ok(data_block) => {
+                        tracing::debug!("execute...");
+                        let start = Instant::now();
+                        let res = Self::filter_map_async(executor, data_block).await;
+                        tracing::debug!("Filter cost: {:?}", start.elapsed());
+                        res
+                    }
+                }
             }
         });
 
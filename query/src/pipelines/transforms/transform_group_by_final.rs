@@ -13,18 +13,23 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::sync::Arc;
 use std::time::Instant;
 
 use bumpalo::Bump;
+use common_dal::SpilledBlock;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethodKind;
+use common_datavalues::arrays::StringArrayBuilder;
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_functions::aggregates::get_layout_offsets;
 use common_functions::aggregates::StateAddr;
-use common_infallible::RwLock;
+use common_io::prelude::*;
 use common_planners::Expression;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -33,6 +38,8 @@ use futures::stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::group_by::AggregatorSpill;
+use crate::pipelines::transforms::group_by::SPILL_PARTITIONS;
 
 pub struct GroupByFinalTransform {
     max_block_size: usize,
@@ -40,6 +47,7 @@ pub struct GroupByFinalTransform {
     group_exprs: Vec<Expression>,
     schema: DataSchemaRef,
     schema_before_group_by: DataSchemaRef,
+    spill: AggregatorSpill,
     input: Arc<dyn Processor>,
 }
 
@@ -57,9 +65,15 @@ impl GroupByFinalTransform {
             group_exprs,
             schema,
             schema_before_group_by,
+            spill: AggregatorSpill::disabled(),
             input: Arc::new(EmptyProcessor::create()),
         }
     }
+
+    pub fn with_spill(mut self, spill: AggregatorSpill) -> Self {
+        self.spill = spill;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -105,7 +119,7 @@ impl Processor for GroupByFinalTransform {
             .collect::<Result<Vec<_>>>()?;
 
         let start = Instant::now();
-        let arena = Bump::new();
+        let spill = &self.spill;
 
         let mut stream = self.input.execute().await?;
         let sample_block = DataBlock::empty_with_schema(self.schema_before_group_by.clone());
@@ -114,14 +128,15 @@ impl Processor for GroupByFinalTransform {
         let (layout, offsets_aggregate_states) = unsafe { get_layout_offsets(&funcs) };
 
         macro_rules! apply {
-            ($hash_method: ident, $key_array_type: ty, $downcast_fn: ident, $group_func_table: ty) => {{
-                type GroupFuncTable = $group_func_table;
-                let groups_locker = GroupFuncTable::default();
-
-                while let Some(block) = stream.next().await {
-                    let mut groups = groups_locker.write();
-                    let block = block?;
-
+            ($hash_method: ident, $key_array_type: ty, $downcast_fn: ident, $key_type: ty) => {{
+                type GroupFuncTable = HashMap<$key_type, usize, ahash::RandomState>;
+                let entity_bytes = std::mem::size_of::<($key_type, usize)>();
+
+                // Merges the states of the block into the groups, returns the bytes of new keys.
+                let merge_block = |groups: &mut GroupFuncTable,
+                                   arena: &Bump,
+                                   block: &DataBlock|
+                 -> Result<usize> {
                     let key_array = block.column(aggr_funcs_len).to_array()?;
                     let key_array: $key_array_type = key_array.$downcast_fn()?;
 
@@ -136,10 +151,12 @@ impl Processor for GroupByFinalTransform {
                         states_binary_arrays.push(aggr_array);
                     }
 
+                    let mut key_bytes = 0;
                     for row in 0..block.num_rows() {
                         let group_key = $hash_method.get_key(&key_array, row);
                         match groups.get(&group_key) {
                             None => {
+                                key_bytes += group_key.heap_bytes();
                                 if aggr_funcs_len == 0 {
                                     groups.insert(group_key, 0usize);
                                 } else {
@@ -171,52 +188,160 @@ impl Processor for GroupByFinalTransform {
                             }
                         };
                     }
-                }
-                let delta = start.elapsed();
-                tracing::debug!("Group by final cost: {:?}", delta);
+                    Ok(key_bytes)
+                };
 
-                // Collect the merge states.
-                let groups = groups_locker.read();
+                // Serializes the groups back into a block of the partial aggregation.
+                let serialize = |groups: &GroupFuncTable,
+                                 schema: &DataSchemaRef|
+                 -> Result<DataBlock> {
+                    let mut columns = Vec::with_capacity(aggr_funcs_len + 1);
+                    let mut bytes = BytesMut::new();
+                    for (idx, func) in funcs.iter().enumerate() {
+                        let mut builder = StringArrayBuilder::with_capacity(groups.len() * 4);
+                        for place in groups.values() {
+                            let place: StateAddr = (*place).into();
+                            func.serialize(place.next(offsets_aggregate_states[idx]), &mut bytes)?;
+                            builder.append_value(&bytes[..]);
+                            bytes.clear();
+                        }
+                        columns.push(builder.finish().into_series());
+                    }
+
+                    let keys = groups.keys().cloned().collect::<Vec<_>>();
+                    columns.push(Series::new(keys));
+                    Ok(DataBlock::create_by_array(schema.clone(), columns))
+                };
+
+                // Spills the rows of the block to the partitions of their keys.
+                let partition = |block: &DataBlock,
+                                 partitions: &mut [Vec<SpilledBlock>]|
+                 -> Result<()> {
+                    let key_array = block.column(aggr_funcs_len).to_array()?;
+                    let key_array: $key_array_type = key_array.$downcast_fn()?;
 
-                let mut aggr_values: Vec<Vec<DataValue>> = {
-                    let mut values = vec![];
-                    for _i in 0..aggr_funcs_len {
-                        values.push(vec![])
+                    let indices = (0..block.num_rows())
+                        .map(|row| {
+                            let mut hasher = DefaultHasher::new();
+                            $hash_method.get_key(&key_array, row).hash(&mut hasher);
+                            hasher.finish() % SPILL_PARTITIONS as u64
+                        })
+                        .collect::<Vec<_>>();
+                    let indices = DataColumn::Array(Series::new(indices));
+
+                    let scattered = DataBlock::scatter_block(block, &indices, SPILL_PARTITIONS)?;
+                    for (index, block) in scattered.into_iter().enumerate() {
+                        if block.num_rows() > 0 {
+                            partitions[index].push(spill.spill(block)?);
+                        }
                     }
-                    values
+                    Ok(())
                 };
-                let mut keys = Vec::with_capacity(groups.len());
-                for (key, place) in groups.iter() {
-                    keys.push(key.clone());
 
-                    let place: StateAddr = (*place).into();
-                    for (idx, func) in funcs.iter().enumerate() {
-                        let arg_place = place.next(offsets_aggregate_states[idx]);
-                        let merge = func.merge_result(arg_place)?;
-                        aggr_values[idx].push(merge);
+                // Builds the final blocks of the merged states.
+                let finalize = |groups: &GroupFuncTable| -> Result<Vec<DataBlock>> {
+                    let mut aggr_values: Vec<Vec<DataValue>> = {
+                        let mut values = vec![];
+                        for _i in 0..aggr_funcs_len {
+                            values.push(vec![])
+                        }
+                        values
+                    };
+                    let mut keys = Vec::with_capacity(groups.len());
+                    for (key, place) in groups.iter() {
+                        keys.push(key.clone());
+
+                        let place: StateAddr = (*place).into();
+                        for (idx, func) in funcs.iter().enumerate() {
+                            let arg_place = place.next(offsets_aggregate_states[idx]);
+                            let merge = func.merge_result(arg_place)?;
+                            aggr_values[idx].push(merge);
+                        }
                     }
-                }
 
-                // Build final state block.
-                let mut columns: Vec<Series> = Vec::with_capacity(aggr_funcs_len + group_expr_len);
+                    // Build final state block.
+                    let mut columns: Vec<Series> =
+                        Vec::with_capacity(aggr_funcs_len + group_expr_len);
 
-                for (i, value) in aggr_values.iter().enumerate() {
-                    columns.push(DataValue::try_into_data_array(
-                        value.as_slice(),
-                        &self.aggr_exprs[i].to_data_type(&self.schema_before_group_by)?,
-                    )?);
-                }
+                    for (i, value) in aggr_values.iter().enumerate() {
+                        columns.push(DataValue::try_into_data_array(
+                            value.as_slice(),
+                            &self.aggr_exprs[i].to_data_type(&self.schema_before_group_by)?,
+                        )?);
+                    }
 
-                {
-                    let group_columns = $hash_method.de_group_columns(keys, &group_fields)?;
-                    columns.extend_from_slice(&group_columns);
-                }
+                    {
+                        let group_columns = $hash_method.de_group_columns(keys, &group_fields)?;
+                        columns.extend_from_slice(&group_columns);
+                    }
 
-                let mut blocks = vec![];
-                if !columns.is_empty() {
-                    let block = DataBlock::create_by_array(self.schema.clone(), columns);
-                    blocks = DataBlock::split_block_by_size(&block, self.max_block_size)?;
+                    let mut blocks = vec![];
+                    if !columns.is_empty() {
+                        let block = DataBlock::create_by_array(self.schema.clone(), columns);
+                        blocks = DataBlock::split_block_by_size(&block, self.max_block_size)?;
+                    }
+                    Ok(blocks)
+                };
+
+                let mut groups = GroupFuncTable::default();
+                let mut arena = Bump::new();
+                let mut key_bytes = 0;
+                let mut tracked = spill.tracked();
+                let mut partitions: Option<Vec<Vec<SpilledBlock>>> = None;
+
+                while let Some(block) = stream.next().await {
+                    let block = block?;
+                    if let Some(partitions) = partitions.as_mut() {
+                        partition(&block, partitions)?;
+                        continue;
+                    }
+
+                    key_bytes += merge_block(&mut groups, &arena, &block)?;
+                    let bytes =
+                        arena.allocated_bytes() + groups.capacity() * entity_bytes + key_bytes;
+                    tracked.set(bytes);
+
+                    // The groups in memory and the rest of the blocks are partitioned.
+                    if spill.should_spill() {
+                        let mut spilled = (0..SPILL_PARTITIONS).map(|_| vec![]).collect::<Vec<_>>();
+                        partition(&serialize(&groups, block.schema())?, &mut spilled)?;
+                        groups = GroupFuncTable::default();
+                        arena = Bump::new();
+                        key_bytes = 0;
+                        tracked.set(0);
+                        partitions = Some(spilled);
+                    }
+                    tracked.check()?;
                 }
+                let delta = start.elapsed();
+                tracing::debug!("Group by final cost: {:?}", delta);
+
+                // Collect the merge states.
+                let blocks = match partitions {
+                    None => finalize(&groups)?,
+                    Some(partitions) => {
+                        let mut blocks = vec![];
+                        for spilled_blocks in partitions {
+                            let mut groups = GroupFuncTable::default();
+                            let arena = Bump::new();
+                            let mut key_bytes = 0;
+                            for spilled in spilled_blocks {
+                                let block = AggregatorSpill::read(&spilled)?;
+                                key_bytes += merge_block(&mut groups, &arena, &block)?;
+                            }
+                            let bytes = arena.allocated_bytes()
+                                + groups.capacity() * entity_bytes
+                                + key_bytes;
+                            tracked.set(bytes);
+                            tracked.check()?;
+                            if !groups.is_empty() {
+                                blocks.extend(finalize(&groups)?);
+                            }
+                        }
+                        blocks
+                    }
+                };
+                drop(tracked);
 
                 Ok(Box::pin(DataBlockStream::create(
                     self.schema.clone(),
@@ -230,19 +355,19 @@ impl Processor for GroupByFinalTransform {
             ($method: ident, $apply: ident) => {{
                 match $method {
                     HashMethodKind::Serializer(hash_method) => {
-                        apply! { hash_method,  &DFStringArray, string, RwLock<HashMap<Vec<u8>, usize, ahash::RandomState>>}
+                        apply! { hash_method,  &DFStringArray, string, Vec<u8> }
                     }
                     HashMethodKind::KeysU8(hash_method) => {
-                        apply! { hash_method , &DFUInt8Array, u8, RwLock<HashMap<u8, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt8Array, u8, u8 }
                     }
                     HashMethodKind::KeysU16(hash_method) => {
-                        apply! { hash_method , &DFUInt16Array, u16, RwLock<HashMap<u16, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt16Array, u16, u16 }
                     }
                     HashMethodKind::KeysU32(hash_method) => {
-                        apply! { hash_method , &DFUInt32Array, u32, RwLock<HashMap<u32, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt32Array, u32, u32 }
                     }
                     HashMethodKind::KeysU64(hash_method) => {
-                        apply! { hash_method , &DFUInt64Array, u64, RwLock<HashMap<u64, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt64Array, u64, u64 }
                     }
                 }
             }};
@@ -251,3 +376,21 @@ impl Processor for GroupByFinalTransform {
         match_hash_method_and_apply! {method, apply}
     }
 }
+
+/// Bytes of a group key outside of the hash table.
+trait GroupKeyBytes {
+    fn heap_bytes(&self) -> usize {
+        0
+    }
+}
+
+impl GroupKeyBytes for u8 {}
+impl GroupKeyBytes for u16 {}
+impl GroupKeyBytes for u32 {}
+impl GroupKeyBytes for u64 {}
+
+impl GroupKeyBytes for Vec<u8> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity()
+    }
+}
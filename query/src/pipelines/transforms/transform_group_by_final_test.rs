@@ -92,3 +92,39 @@ async fn test_transform_final_group_by() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_final_group_by_spill() -> Result<()> {
+    use crate::interpreters::InterpreterFactory;
+    use crate::sql::PlanParser;
+
+    let query = "select number % 100 as k, count() as c, sum(number) as s \
+        from numbers_mt(10000) group by k";
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_block_size(1000)?;
+
+    let mut results = vec![];
+    for max_bytes in [0, 1] {
+        ctx.get_settings()
+            .set_max_bytes_before_external_group_by(max_bytes)?;
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+        let result = interpreter.execute().await?.try_collect::<Vec<_>>().await?;
+        results.push(common_datablocks::pretty_format_blocks(&result)?);
+    }
+
+    // Every state is spilled and merged back, by both the partial and the final aggregation.
+    let mut in_memory = results[0].lines().collect::<Vec<_>>();
+    let mut spilled = results[1].lines().collect::<Vec<_>>();
+    in_memory.sort_unstable();
+    spilled.sort_unstable();
+    assert_eq!(in_memory.len(), 104);
+    assert_eq!(in_memory, spilled);
+
+    // The tracked states are released once the aggregations finish.
+    let tracker = ctx.get_memory_tracker();
+    assert_eq!(tracker.used(), 0);
+    assert!(tracker.peak() > 0);
+
+    Ok(())
+}
@@ -29,6 +29,7 @@ use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::group_by::Aggregator;
 use crate::pipelines::transforms::group_by::AggregatorParams;
+use crate::pipelines::transforms::group_by::AggregatorSpill;
 use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
 
 pub struct GroupByPartialTransform {
@@ -37,6 +38,7 @@ pub struct GroupByPartialTransform {
 
     schema: DataSchemaRef,
     schema_before_group_by: DataSchemaRef,
+    spill: AggregatorSpill,
     input: Arc<dyn Processor>,
 }
 
@@ -52,10 +54,16 @@ impl GroupByPartialTransform {
             group_exprs,
             schema,
             schema_before_group_by,
+            spill: AggregatorSpill::disabled(),
             input: Arc::new(EmptyProcessor::create()),
         }
     }
 
+    pub fn with_spill(mut self, spill: AggregatorSpill) -> Self {
+        self.spill = spill;
+        self
+    }
+
     fn extract_group_columns(&self) -> Vec<String> {
         self.group_exprs
             .iter()
@@ -77,13 +85,14 @@ impl GroupByPartialTransform {
         let aggregator_params = AggregatorParams::try_create(schema, aggr_exprs)?;
 
         let aggregator = Aggregator::create(method, aggregator_params);
-        let state = aggregator.aggregate(group_cols, stream).await?;
+        let finalized_schema = &self.schema;
+        let stream = aggregator
+            .aggregate(group_cols, stream, &self.spill, finalized_schema)
+            .await?;
 
         let delta = start.elapsed();
         tracing::debug!("Group by partial cost: {:?}", delta);
-
-        let finalized_schema = self.schema.clone();
-        aggregator.aggregate_finalized(&state, finalized_schema)
+        Ok(stream)
     }
 }
 
@@ -125,6 +134,9 @@ impl Processor for GroupByPartialTransform {
     ///  3, 1 -> state1
     ///  4, 2 -> state2
     /// 1.2)  serialize the state to the output block
+    ///
+    /// Once the aggregations of the query hold more than `max_bytes_before_external_group_by`,
+    /// the states are serialized to the local disk and the hash table starts over.
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         tracing::debug!("execute...");
         let group_cols = self.extract_group_columns();
@@ -0,0 +1,269 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+use common_planners::Expression;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::group_by::AggregatorParams;
+use crate::pipelines::transforms::group_by::AggregatorParamsRef;
+
+/// Group by for the input sorted by the group columns, where the rows of a group are adjacent.
+///
+/// The partial and final group by emit nothing until the whole input is consumed, this one emits
+/// a group as soon as the first row of the next group is seen, and only keeps the states of the
+/// current group.
+pub struct GroupBySortedTransform {
+    aggr_exprs: Vec<Expression>,
+    group_exprs: Vec<Expression>,
+    schema: DataSchemaRef,
+    schema_before_group_by: DataSchemaRef,
+    input: Arc<dyn Processor>,
+}
+
+impl GroupBySortedTransform {
+    pub fn create(
+        schema: DataSchemaRef,
+        schema_before_group_by: DataSchemaRef,
+        aggr_exprs: Vec<Expression>,
+        group_exprs: Vec<Expression>,
+    ) -> Self {
+        Self {
+            aggr_exprs,
+            group_exprs,
+            schema,
+            schema_before_group_by,
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for GroupBySortedTransform {
+    fn name(&self) -> &str {
+        "GroupBySortedTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let schema_before_group_by = &self.schema_before_group_by;
+        let params =
+            AggregatorParams::try_create(schema_before_group_by.clone(), &self.aggr_exprs)?;
+        let aggr_types = self
+            .aggr_exprs
+            .iter()
+            .map(|expr| expr.to_data_type(schema_before_group_by))
+            .collect::<Result<Vec<_>>>()?;
+        let group_cols = self
+            .group_exprs
+            .iter()
+            .map(|expr| expr.column_name())
+            .collect::<Vec<_>>();
+        let group_types = self
+            .group_exprs
+            .iter()
+            .map(|expr| expr.to_data_type(schema_before_group_by))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Box::pin(GroupBySortedStream {
+            input: self.input.execute().await?,
+            schema: self.schema.clone(),
+            params,
+            aggr_types,
+            group_cols,
+            group_types,
+            current: None,
+            arena: Bump::new(),
+            is_finished: false,
+        }))
+    }
+}
+
+struct SortedGroup {
+    key: Vec<u8>,
+    place: StateAddr,
+    /// The values of the group columns.
+    values: Vec<DataValue>,
+}
+
+struct GroupBySortedStream {
+    input: SendableDataBlockStream,
+    schema: DataSchemaRef,
+    params: AggregatorParamsRef,
+    aggr_types: Vec<DataType>,
+    group_cols: Vec<String>,
+    group_types: Vec<DataType>,
+
+    // The group which may go on in the next block, its states are allocated in the arena.
+    current: Option<SortedGroup>,
+    arena: Bump,
+    is_finished: bool,
+}
+
+impl GroupBySortedStream {
+    // Aggregates the block, returns the groups finished by it.
+    fn aggregate(&mut self, block: &DataBlock) -> Result<Option<DataBlock>> {
+        let rows = block.num_rows();
+        let group_columns = self
+            .group_cols
+            .iter()
+            .map(|name| block.try_column_by_name(name))
+            .collect::<Result<Vec<_>>>()?;
+        let keys = HashMethodSerializer::default().build_keys(&group_columns, rows)?;
+
+        let arena = Bump::new();
+        let mut allocated = false;
+        let mut finished = vec![];
+        let mut places = Vec::with_capacity(rows);
+        for (row, key) in keys.into_iter().enumerate() {
+            let is_current = matches!(&self.current, Some(group) if group.key == key);
+            if !is_current {
+                let values = group_columns
+                    .iter()
+                    .map(|column| column.try_get(row))
+                    .collect::<Result<Vec<_>>>()?;
+                let place = Self::alloc_states(&self.params, &arena);
+                allocated = true;
+                finished.extend(self.current.replace(SortedGroup { key, place, values }));
+            }
+
+            if let Some(group) = &self.current {
+                places.push(group.place);
+            }
+        }
+
+        let params = self.params.as_ref();
+        for (idx, func) in params.aggregate_functions.iter().enumerate() {
+            let arguments = params.aggregate_functions_arguments_name[idx]
+                .iter()
+                .map(|name| block.try_column_by_name(name)?.to_array())
+                .collect::<Result<Vec<_>>>()?;
+            func.accumulate_keys(
+                &places,
+                params.offsets_aggregate_states[idx],
+                &arguments,
+                rows,
+            )?;
+        }
+
+        if finished.is_empty() {
+            // Only the first group may start without finishing one, the old arena keeps no
+            // states then, but the new one keeps the states of the current group.
+            if allocated {
+                self.arena = arena;
+            }
+            return Ok(None);
+        }
+
+        // The finished groups live in the old arena or the new one, drop the old one only
+        // after they are finalized.
+        let block = self.finalize(&finished)?;
+        self.arena = arena;
+        Ok(Some(block))
+    }
+
+    fn alloc_states(params: &AggregatorParams, arena: &Bump) -> StateAddr {
+        let place: StateAddr = arena.alloc_layout(params.layout).into();
+        for (idx, func) in params.aggregate_functions.iter().enumerate() {
+            func.init_state(place.next(params.offsets_aggregate_states[idx]));
+        }
+        place
+    }
+
+    fn finalize(&self, groups: &[SortedGroup]) -> Result<DataBlock> {
+        let params = self.params.as_ref();
+        let mut columns = Vec::with_capacity(self.schema.fields().len());
+        for (idx, func) in params.aggregate_functions.iter().enumerate() {
+            let offset = params.offsets_aggregate_states[idx];
+            let values = groups
+                .iter()
+                .map(|group| func.merge_result(group.place.next(offset)))
+                .collect::<Result<Vec<_>>>()?;
+            columns.push(DataValue::try_into_data_array(
+                &values,
+                &self.aggr_types[idx],
+            )?);
+        }
+
+        for (idx, data_type) in self.group_types.iter().enumerate() {
+            let values = groups
+                .iter()
+                .map(|group| group.values[idx].clone())
+                .collect::<Vec<_>>();
+            columns.push(DataValue::try_into_data_array(&values, data_type)?);
+        }
+
+        Ok(DataBlock::create_by_array(self.schema.clone(), columns))
+    }
+}
+
+impl Stream for GroupBySortedStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.is_finished {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match self.input.poll_next_unpin(ctx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(Err(cause))) => return Poll::Ready(Some(Err(cause))),
+                Poll::Ready(Some(Ok(block))) => match self.aggregate(&block) {
+                    Ok(None) => continue,
+                    other => return Poll::Ready(other.transpose()),
+                },
+                // The last group is finished by the end of the input.
+                Poll::Ready(None) => {
+                    self.is_finished = true;
+                    return match self.current.take() {
+                        None => Poll::Ready(None),
+                        Some(group) => Poll::Ready(Some(self.finalize(&[group]))),
+                    };
+                }
+            }
+        }
+    }
+}
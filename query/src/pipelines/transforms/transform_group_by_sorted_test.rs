@@ -0,0 +1,84 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use common_planners::{self};
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+use crate::pipelines::transforms::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_sorted_group_by_across_blocks() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_threads(1)?;
+    ctx.get_settings().set_max_block_size(3)?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+    let source_schema = test_source.number_schema_for_test()?;
+
+    // The blocks are [0, 1, 2], [3, 4, 5], [6, 7, 8] and [9], the group of (number > 5) = false
+    // is the only one of the first block and goes on in the second one, the other group spans
+    // the last two blocks.
+    let group_exprs = vec![col("number").gt(lit(5u64))];
+    let aggr_exprs = vec![sum(col("number")), avg(col("number"))];
+    let expression = PlanBuilder::create(source_schema.clone())
+        .expression(&[group_exprs[0].clone(), col("number")], "")?
+        .build()?;
+    let expression_schema = expression.schema();
+    let aggr_final = PlanBuilder::create(expression_schema.clone())
+        .aggregate_final(expression_schema.clone(), &aggr_exprs, &group_exprs)?
+        .build()?;
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(10)?;
+    pipeline.add_source(Arc::new(source))?;
+    if let PlanNode::Expression(plan) = expression {
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(ExpressionTransform::try_create(
+                plan.input.schema(),
+                plan.schema.clone(),
+                plan.exprs.clone(),
+            )?))
+        })?;
+    }
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(GroupBySortedTransform::create(
+            aggr_final.schema(),
+            expression_schema.clone(),
+            aggr_exprs.clone(),
+            group_exprs.clone(),
+        )))
+    })?;
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 2);
+
+    let expected = vec![
+        "+-------------+-------------+--------------+",
+        "| sum(number) | avg(number) | (number > 5) |",
+        "+-------------+-------------+--------------+",
+        "| 15          | 2.5         | false        |",
+        "| 30          | 7.5         | true         |",
+        "+-------------+-------------+--------------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    Ok(())
+}
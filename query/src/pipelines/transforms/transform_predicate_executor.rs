@@ -0,0 +1,174 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+
+/// PredicateExecutor evaluates the `AND`/`OR` chains of a filter predicate with short-circuit.
+/// Each operand is evaluated only on the rows still undecided by the operands on its left:
+/// the rows passed all of them for `AND`, the rows passed none of them for `OR`.
+/// The rows are tracked by a selection vector of their indices in the input block,
+/// so an expensive operand (regex, UDF) after a selective one only sees the surviving rows.
+#[derive(Debug, Clone)]
+pub enum PredicateExecutor {
+    And(Vec<PredicateExecutor>),
+    Or(Vec<PredicateExecutor>),
+    Leaf(ExpressionExecutor),
+}
+
+impl PredicateExecutor {
+    pub fn try_create(schema: &DataSchemaRef, predicate: &Expression) -> Result<Self> {
+        match predicate {
+            Expression::BinaryExpression { op, .. } if op.eq_ignore_ascii_case("and") => {
+                let operands = Self::flatten(predicate, "and")
+                    .iter()
+                    .map(|operand| Self::try_create(schema, operand))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PredicateExecutor::And(operands))
+            }
+            Expression::BinaryExpression { op, .. } if op.eq_ignore_ascii_case("or") => {
+                let operands = Self::flatten(predicate, "or")
+                    .iter()
+                    .map(|operand| Self::try_create(schema, operand))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PredicateExecutor::Or(operands))
+            }
+            _ => {
+                let field = predicate.to_data_field(schema)?;
+                let executor = ExpressionExecutor::try_create(
+                    "filter expression executor",
+                    schema.clone(),
+                    DataSchemaRefExt::create(vec![field]),
+                    vec![predicate.clone()],
+                    false,
+                )?;
+                executor.validate()?;
+                Ok(PredicateExecutor::Leaf(executor))
+            }
+        }
+    }
+
+    /// Whether an operand calls a function which waits on the network.
+    pub fn is_blocking(&self) -> bool {
+        match self {
+            PredicateExecutor::And(operands) | PredicateExecutor::Or(operands) => {
+                operands.iter().any(|operand| operand.is_blocking())
+            }
+            PredicateExecutor::Leaf(executor) => executor.is_blocking(),
+        }
+    }
+
+    /// Splits a chain of the same logic operator, a AND (b AND c) ---> [a, b, c].
+    fn flatten(expr: &Expression, logic_op: &str) -> Vec<Expression> {
+        match expr {
+            Expression::BinaryExpression { op, left, right }
+                if op.eq_ignore_ascii_case(logic_op) =>
+            {
+                let mut operands = Self::flatten(left, logic_op);
+                operands.extend(Self::flatten(right, logic_op));
+                operands
+            }
+            _ => vec![expr.clone()],
+        }
+    }
+
+    /// Returns the block of the rows where the predicate is true.
+    pub fn filter(&self, block: &DataBlock) -> Result<DataBlock> {
+        match self {
+            // Nothing to short-circuit, filter by the whole predicate column.
+            PredicateExecutor::Leaf(executor) => {
+                let predicate = executor.execute(block)?.column(0).to_array()?;
+                DataBlock::filter_block(block, predicate)
+            }
+            _ => {
+                let selection = (0..block.num_rows() as u32).collect::<Vec<_>>();
+                let selection = self.select(block, selection)?;
+                match selection.len() == block.num_rows() {
+                    true => Ok(block.clone()),
+                    false => DataBlock::block_take_by_indices(block, &[], &selection),
+                }
+            }
+        }
+    }
+
+    /// Returns the rows of the ascending `selection` where the predicate is true.
+    pub fn select(&self, block: &DataBlock, selection: Vec<u32>) -> Result<Vec<u32>> {
+        if selection.is_empty() {
+            return Ok(selection);
+        }
+
+        match self {
+            PredicateExecutor::And(operands) => {
+                let mut selection = selection;
+                for operand in operands.iter() {
+                    selection = operand.select(block, selection)?;
+                    if selection.is_empty() {
+                        break;
+                    }
+                }
+                Ok(selection)
+            }
+            PredicateExecutor::Or(operands) => {
+                let mut passed = vec![];
+                let mut remaining = selection;
+                for operand in operands.iter() {
+                    let selected = operand.select(block, remaining.clone())?;
+                    remaining = Self::difference(&remaining, &selected);
+                    passed.extend(selected);
+                    if remaining.is_empty() {
+                        break;
+                    }
+                }
+                passed.sort_unstable();
+                Ok(passed)
+            }
+            PredicateExecutor::Leaf(executor) => {
+                let input = match selection.len() == block.num_rows() {
+                    true => block.clone(),
+                    false => DataBlock::block_take_by_indices(block, &[], &selection)?,
+                };
+
+                let predicate = executor
+                    .execute(&input)?
+                    .column(0)
+                    .to_array()?
+                    .cast_with_type(&DataType::Boolean)?;
+                let selected = predicate
+                    .bool()?
+                    .into_iter()
+                    .zip(selection.into_iter())
+                    .filter(|(value, _)| value.unwrap_or(false))
+                    .map(|(_, row)| row)
+                    .collect();
+                Ok(selected)
+            }
+        }
+    }
+
+    /// Returns the rows of the ascending `rows` which are not in the ascending `removed`.
+    fn difference(rows: &[u32], removed: &[u32]) -> Vec<u32> {
+        let mut removed = removed.iter().peekable();
+        rows.iter()
+            .filter(|row| {
+                while removed.next_if(|removed_row| removed_row < row).is_some() {}
+                removed.next_if_eq(row).is_none()
+            })
+            .copied()
+            .collect()
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::transforms::PredicateExecutor;
+
+#[test]
+fn test_predicate_executor() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("number", DataType::UInt64, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(
+        (0..10_u64).collect::<Vec<_>>(),
+    )]);
+
+    // sleep(5) fails once it is evaluated, so it must be short-circuited.
+    let sleep = Expression::ScalarFunction {
+        op: "sleep".to_string(),
+        args: vec![lit(5_u64)],
+    }
+    .eq(lit(0_u64));
+    let even = Expression::BinaryExpression {
+        op: "%".to_string(),
+        left: Box::new(col("number")),
+        right: Box::new(lit(2_u64)),
+    }
+    .eq(lit(0_u64));
+
+    struct Test {
+        name: &'static str,
+        predicate: Expression,
+        expect: Vec<u32>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "and",
+            predicate: col("number")
+                .gt(lit(5_u64))
+                .and(col("number").lt(lit(8_u64))),
+            expect: vec![6, 7],
+        },
+        Test {
+            name: "or",
+            predicate: col("number")
+                .lt(lit(2_u64))
+                .or(col("number").gt(lit(7_u64))),
+            expect: vec![0, 1, 8, 9],
+        },
+        Test {
+            name: "and-over-or",
+            predicate: col("number")
+                .lt(lit(3_u64))
+                .or(col("number").gt(lit(8_u64)))
+                .and(even),
+            expect: vec![0, 2],
+        },
+        Test {
+            name: "and-short-circuit",
+            predicate: col("number").gt(lit(100_u64)).and(sleep.clone()),
+            expect: vec![],
+        },
+        Test {
+            name: "or-short-circuit",
+            predicate: col("number").lt(lit(100_u64)).or(sleep.clone()),
+            expect: (0..10).collect(),
+        },
+    ];
+
+    for t in tests {
+        let executor = PredicateExecutor::try_create(&schema, &t.predicate)?;
+        let selection = executor.select(&block, (0..10).collect())?;
+        assert_eq!(selection, t.expect, "{}", t.name);
+
+        let filtered = executor.filter(&block)?;
+        assert_eq!(filtered.num_rows(), t.expect.len(), "{}", t.name);
+    }
+
+    // The rows surviving the left side still evaluate the right side.
+    let predicate = col("number").gt(lit(5_u64)).and(sleep);
+    let executor = PredicateExecutor::try_create(&schema, &predicate)?;
+    assert!(executor.filter(&block).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::PlanNode;
+use common_planners::RecursiveCtePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::TryStreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::pipelines::processors::Processor;
+use crate::sessions::DatabendQueryContext;
+use crate::sessions::DatabendQueryContextRef;
+
+/// The blocks of the working tables, by the names of their recursive common table expressions.
+pub type WorkingTables = HashMap<String, Arc<Vec<DataBlock>>>;
+
+/// Executes a recursive common table expression.
+///
+/// The anchor term is executed once, its rows are the first working table. The recursive term is
+/// executed over the working table again and again, its rows are the next working table, until it
+/// is empty. Both terms are executed in their own context, like the subqueries.
+pub struct RecursiveCteTransform {
+    ctx: DatabendQueryContextRef,
+    plan: RecursiveCtePlan,
+    /// The working tables of the outer recursive common table expressions
+    working_tables: WorkingTables,
+}
+
+impl RecursiveCteTransform {
+    pub fn create(
+        ctx: DatabendQueryContextRef,
+        plan: RecursiveCtePlan,
+        working_tables: WorkingTables,
+    ) -> Self {
+        RecursiveCteTransform {
+            ctx,
+            plan,
+            working_tables,
+        }
+    }
+
+    async fn execute_term(
+        &self,
+        plan: &PlanNode,
+        working_table: Option<Vec<DataBlock>>,
+    ) -> Result<Vec<DataBlock>> {
+        let mut working_tables = self.working_tables.clone();
+        if let Some(blocks) = working_table {
+            working_tables.insert(self.plan.name.clone(), Arc::new(blocks));
+        }
+
+        let ctx = DatabendQueryContext::new(self.ctx.clone());
+        let mut pipeline = PipelineBuilder::create(ctx)
+            .with_working_tables(working_tables)
+            .build(plan)?;
+        let blocks = pipeline.execute().await?.try_collect::<Vec<_>>().await?;
+        Ok(blocks
+            .into_iter()
+            .filter(|block| !block.is_empty())
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for RecursiveCteTransform {
+    fn name(&self) -> &str {
+        "RecursiveCteTransform"
+    }
+
+    fn connect_to(&mut self, _input: Arc<dyn Processor>) -> Result<()> {
+        Result::Err(ErrorCode::LogicalError(
+            "Cannot call RecursiveCteTransform connect_to",
+        ))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![Arc::new(EmptyProcessor::create())]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let max_iterations = self.ctx.get_settings().get_max_recursive_iterations()?;
+        let mut working_table = self.execute_term(&self.plan.anchor, None).await?;
+        let mut results = working_table.clone();
+        let mut iterations = 0;
+        while !working_table.is_empty() {
+            if iterations == max_iterations {
+                return Err(ErrorCode::TooManyRecursiveIterations(format!(
+                    "Recursive query {} is not finished after {} iterations, see the setting max_recursive_iterations",
+                    self.plan.name, max_iterations
+                )));
+            }
+
+            iterations += 1;
+            working_table = self
+                .execute_term(&self.plan.recursive, Some(working_table))
+                .await?;
+            results.extend(working_table.iter().cloned());
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            results,
+        )))
+    }
+}
+
+/// Reads the working table of a recursive common table expression in its recursive term.
+pub struct WorkingTableSource {
+    schema: DataSchemaRef,
+    blocks: Arc<Vec<DataBlock>>,
+}
+
+impl WorkingTableSource {
+    pub fn create(schema: DataSchemaRef, blocks: Arc<Vec<DataBlock>>) -> Self {
+        WorkingTableSource { schema, blocks }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for WorkingTableSource {
+    fn name(&self) -> &str {
+        "WorkingTableSource"
+    }
+
+    fn connect_to(&mut self, _input: Arc<dyn Processor>) -> Result<()> {
+        Result::Err(ErrorCode::LogicalError(
+            "Cannot call WorkingTableSource connect_to",
+        ))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![Arc::new(EmptyProcessor::create())]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            self.blocks.as_ref().clone(),
+        )))
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::pipelines::processors::*;
+use crate::sql::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_recursive_cte() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let query = "WITH RECURSIVE t(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t WHERE n < 5) SELECT n FROM t";
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let mut pipeline = PipelineBuilder::create(ctx.clone()).build(&plan)?;
+    assert!(format!("{:?}", pipeline).contains("RecursiveCteTransform × 1 processor"));
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+---+", "| n |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "| 5 |", "+---+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    // The recursive term never returns no row.
+    ctx.get_settings().set_max_recursive_iterations(3)?;
+    let query = "WITH RECURSIVE t(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t) SELECT n FROM t";
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let mut pipeline = PipelineBuilder::create(ctx.clone()).build(&plan)?;
+    let result = match pipeline.execute().await {
+        Ok(stream) => stream.try_collect::<Vec<_>>().await,
+        Err(cause) => Err(cause),
+    };
+    assert_eq!(
+        ErrorCode::TooManyRecursiveIterations("").code(),
+        result.unwrap_err().code()
+    );
+
+    Ok(())
+}
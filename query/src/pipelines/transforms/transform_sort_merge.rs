@@ -29,11 +29,13 @@ use futures::StreamExt;
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::transform_sort_partial::get_sort_descriptions;
+use crate::sessions::MemoryTracker;
 
 pub struct SortMergeTransform {
     schema: DataSchemaRef,
     exprs: Vec<Expression>,
     limit: Option<usize>,
+    tracker: Arc<MemoryTracker>,
     input: Arc<dyn Processor>,
 }
 
@@ -47,9 +49,16 @@ impl SortMergeTransform {
             schema,
             exprs,
             limit,
+            tracker: MemoryTracker::create(),
             input: Arc::new(EmptyProcessor::create()),
         })
     }
+
+    /// Tracks the buffered blocks in the memory tracker of the query.
+    pub fn with_memory_tracker(mut self, tracker: Arc<MemoryTracker>) -> Self {
+        self.tracker = tracker;
+        self
+    }
 }
 
 #[async_trait]
@@ -76,10 +85,16 @@ impl Processor for SortMergeTransform {
 
         let sort_columns_descriptions = get_sort_descriptions(&self.schema, &self.exprs)?;
         let mut blocks = vec![];
+        let mut tracked = self.tracker.tracked();
+        let mut bytes = 0;
         let mut stream = self.input.execute().await?;
 
         while let Some(block) = stream.next().await {
-            blocks.push(block?);
+            let block = block?;
+            bytes += block.memory_size();
+            tracked.set(bytes);
+            tracked.check()?;
+            blocks.push(block);
         }
 
         let results = match blocks.len() {
@@ -15,13 +15,18 @@
 use std::any::Any;
 use std::sync::Arc;
 
+use common_base::ProgressCallback;
+use common_base::ProgressValues;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::is_virtual_column;
+use common_planners::Extras;
 use common_planners::ReadDataSourcePlan;
 use common_streams::CorrectWithSchemaStream;
 use common_streams::ProgressStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
+use metrics::counter;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
@@ -55,13 +60,54 @@ impl SourceTransform {
         // TODO(xp): get_single_node_table_io_context() or
         //           get_cluster_table_io_context()?
         let io_ctx = Arc::new(self.ctx.get_cluster_table_io_context()?);
-        let table_stream = table.read(io_ctx, &self.source_plan.push_downs);
+        let push_downs = self.push_downs();
+        let table_stream = table.read(io_ctx, &push_downs);
         let progress_stream =
-            ProgressStream::try_create(table_stream.await?, self.ctx.progress_callback()?)?;
+            ProgressStream::try_create(table_stream.await?, self.scan_callback(table.engine())?)?;
+        let quota_stream = self.ctx.try_create_scan_quota(Box::pin(progress_stream))?;
 
-        Ok(Box::pin(
-            self.ctx.try_create_abortable(Box::pin(progress_stream))?,
-        ))
+        Ok(Box::pin(self.ctx.try_create_abortable(quota_stream)?))
+    }
+
+    /// The progress callback of the query, which also counts the scanned rows and bytes
+    /// of the node by the engine of the table.
+    fn scan_callback(&self, engine: &str) -> Result<ProgressCallback> {
+        let engine = engine.to_string();
+        counter!(super::metrics::METRIC_SCAN_TABLES, 1, "engine" => engine.clone());
+
+        let mut progress_callback = self.ctx.progress_callback()?;
+        Ok(Box::new(move |values: &ProgressValues| {
+            let (rows, bytes) = (values.read_rows as u64, values.read_bytes as u64);
+            counter!(super::metrics::METRIC_SCAN_ROWS, rows, "engine" => engine.clone());
+            counter!(super::metrics::METRIC_SCAN_BYTES, bytes, "engine" => engine.clone());
+            progress_callback(values);
+        }))
+    }
+
+    /// The push downs of the plan, with the virtual columns left in the schema after the
+    /// projection push down.
+    fn push_downs(&self) -> Option<Extras> {
+        let virtual_columns = self
+            .source_plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name())
+            .filter(|name| is_virtual_column(name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match (&self.source_plan.push_downs, virtual_columns.is_empty()) {
+            (push_downs, true) => push_downs.clone(),
+            (Some(push_downs), false) => Some(Extras {
+                virtual_columns,
+                ..push_downs.clone()
+            }),
+            (None, false) => Some(Extras {
+                virtual_columns,
+                ..Extras::default()
+            }),
+        }
     }
 }
 
@@ -0,0 +1,85 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use tokio_stream::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+
+/// Emits one row per element of the array column, with the element appended as the last column.
+pub struct UnnestTransform {
+    column: String,
+    schema: DataSchemaRef,
+    input: Arc<dyn Processor>,
+}
+
+impl UnnestTransform {
+    pub fn create(column: String, schema: DataSchemaRef) -> Self {
+        UnnestTransform {
+            column,
+            schema,
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for UnnestTransform {
+    fn name(&self) -> &str {
+        "UnnestTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let column = self.column.clone();
+        let schema = self.schema.clone();
+        let input_stream = self.input.execute().await?;
+
+        let stream = input_stream.filter_map(move |block| {
+            let block = match block {
+                Ok(block) => block,
+                Err(cause) => return Some(Err(cause)),
+            };
+
+            match DataBlock::unnest_block(&block, &column, schema.clone()) {
+                Ok(block) if block.num_rows() == 0 => None,
+                other => Some(other),
+            }
+        });
+
+        Ok(Box::pin(stream))
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+use common_planners::Expression;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::transform_sort_partial::get_sort_descriptions;
+use crate::sessions::MemoryTracker;
+
+/// Computes the window functions over all the rows of the input, so the input has to be merged
+/// into one processor beforehand.
+///
+/// For each window function, the rows are sorted by its partition and order keys, the result of
+/// a row is computed over its partition, up to the last peer of the row if ordered, and appended
+/// to the rows as a new column.
+pub struct WindowTransform {
+    window_exprs: Vec<Expression>,
+    schema: DataSchemaRef,
+    input_schema: DataSchemaRef,
+    tracker: Arc<MemoryTracker>,
+    input: Arc<dyn Processor>,
+}
+
+impl WindowTransform {
+    pub fn create(
+        schema: DataSchemaRef,
+        input_schema: DataSchemaRef,
+        window_exprs: Vec<Expression>,
+    ) -> Self {
+        WindowTransform {
+            window_exprs,
+            schema,
+            input_schema,
+            tracker: MemoryTracker::create(),
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+
+    /// Tracks the buffered blocks in the memory tracker of the query.
+    pub fn with_memory_tracker(mut self, tracker: Arc<MemoryTracker>) -> Self {
+        self.tracker = tracker;
+        self
+    }
+
+    /// Sorts the rows by the window of `expr`, then appends the results of `expr` as the column
+    /// `index` of the output schema.
+    fn window_block(
+        &self,
+        block: &DataBlock,
+        expr: &Expression,
+        index: usize,
+    ) -> Result<DataBlock> {
+        let (op, partition_by, order_by) = match expr {
+            Expression::WindowFunction {
+                op,
+                partition_by,
+                order_by,
+                ..
+            } => (op.to_lowercase(), partition_by, order_by),
+            _ => {
+                return Err(ErrorCode::BadTransformType(format!(
+                    "Window expression must be Expression::WindowFunction, but got: {:?}",
+                    expr
+                )))
+            }
+        };
+
+        let mut sort_exprs = partition_by
+            .iter()
+            .map(|expr| Expression::Sort {
+                expr: Box::new(expr.clone()),
+                asc: true,
+                nulls_first: true,
+            })
+            .collect::<Vec<_>>();
+        sort_exprs.extend_from_slice(order_by);
+        let block = match sort_exprs.is_empty() {
+            true => block.clone(),
+            false => {
+                let descriptions = get_sort_descriptions(block.schema(), &sort_exprs)?;
+                DataBlock::sort_block(block, &descriptions, None)?
+            }
+        };
+
+        let partition_keys = Self::key_columns(&block, partition_by)?;
+        let order_keys = Self::key_columns(&block, order_by)?;
+        let values = match op.as_str() {
+            "row_number" | "rank" | "dense_rank" => {
+                Self::rank(&op, block.num_rows(), &partition_keys, &order_keys)?
+            }
+            _ => self.aggregate(&block, expr, &partition_keys, &order_keys)?,
+        };
+
+        let field = self.schema.field(index);
+        let mut columns = block.columns().to_vec();
+        columns.push(DataValue::try_into_data_array(&values, field.data_type())?.into());
+        let schema = DataSchemaRefExt::create(self.schema.fields()[..=index].to_vec());
+        Ok(DataBlock::create(schema, columns))
+    }
+
+    fn key_columns(block: &DataBlock, exprs: &[Expression]) -> Result<Vec<DataColumn>> {
+        // The column name of a sort expression is the one of the sorted expression.
+        exprs
+            .iter()
+            .map(|expr| block.try_column_by_name(&expr.column_name()).cloned())
+            .collect()
+    }
+
+    fn same_keys(keys: &[DataColumn], lhs: usize, rhs: usize) -> Result<bool> {
+        for key in keys {
+            if key.try_get(lhs)? != key.try_get(rhs)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn rank(
+        op: &str,
+        rows: usize,
+        partition_keys: &[DataColumn],
+        order_keys: &[DataColumn],
+    ) -> Result<Vec<DataValue>> {
+        let mut values = Vec::with_capacity(rows);
+        let (mut row_number, mut rank, mut dense_rank) = (0u64, 0u64, 0u64);
+        for row in 0..rows {
+            let new_partition = row == 0 || !Self::same_keys(partition_keys, row - 1, row)?;
+            if new_partition {
+                row_number = 0;
+                dense_rank = 0;
+            }
+
+            row_number += 1;
+            if new_partition || !Self::same_keys(order_keys, row - 1, row)? {
+                rank = row_number;
+                dense_rank += 1;
+            }
+
+            let value = match op {
+                "row_number" => row_number,
+                "rank" => rank,
+                _ => dense_rank,
+            };
+            values.push(DataValue::UInt64(Some(value)));
+        }
+        Ok(values)
+    }
+
+    fn aggregate(
+        &self,
+        block: &DataBlock,
+        expr: &Expression,
+        partition_keys: &[DataColumn],
+        order_keys: &[DataColumn],
+    ) -> Result<Vec<DataValue>> {
+        let func = expr.to_aggregate_function(&self.input_schema)?;
+        let arg_columns = expr
+            .to_aggregate_function_names()?
+            .iter()
+            .map(|name| block.try_array_by_name(name))
+            .collect::<Result<Vec<_>>>()?;
+
+        let arena = Bump::new();
+        let rows = block.num_rows();
+        let mut values = Vec::with_capacity(rows);
+        let mut start = 0;
+        while start < rows {
+            let mut end = start + 1;
+            while end < rows && Self::same_keys(partition_keys, start, end)? {
+                end += 1;
+            }
+
+            // The peers of a row share the same result, which accumulates the rows up to them.
+            let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+            func.init_state(place);
+            let mut peer = start;
+            while peer < end {
+                let mut peer_end = peer + 1;
+                while peer_end < end && Self::same_keys(order_keys, peer, peer_end)? {
+                    peer_end += 1;
+                }
+
+                let arrays = arg_columns
+                    .iter()
+                    .map(|column| column.slice(peer, peer_end - peer))
+                    .collect::<Vec<_>>();
+                func.accumulate(place, &arrays, peer_end - peer)?;
+
+                let value = func.merge_result(place)?;
+                values.extend(std::iter::repeat(value).take(peer_end - peer));
+                peer = peer_end;
+            }
+            start = end;
+        }
+        Ok(values)
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for WindowTransform {
+    fn name(&self) -> &str {
+        "WindowTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let mut blocks = vec![];
+        let mut tracked = self.tracker.tracked();
+        let mut bytes = 0;
+        let mut stream = self.input.execute().await?;
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            if !block.is_empty() {
+                bytes += block.memory_size();
+                tracked.set(bytes);
+                tracked.check()?;
+                blocks.push(block);
+            }
+        }
+
+        let mut results = vec![];
+        if !blocks.is_empty() {
+            let mut block = DataBlock::concat_blocks(&blocks)?;
+            let input_fields = self.input_schema.fields().len();
+            for (idx, expr) in self.window_exprs.iter().enumerate() {
+                block = self.window_block(&block, expr, input_fields + idx)?;
+            }
+            results.push(block);
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            results,
+        )))
+    }
+}
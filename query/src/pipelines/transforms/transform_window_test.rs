@@ -0,0 +1,92 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+use crate::pipelines::transforms::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_window() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // Pipeline.
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let a = test_source.number_source_transform_for_test(8)?;
+    pipeline.add_source(Arc::new(a))?;
+
+    let window_exprs = vec![
+        Expression::WindowFunction {
+            op: "row_number".to_string(),
+            params: vec![],
+            args: vec![],
+            partition_by: vec![],
+            order_by: vec![sort("number", false, true)],
+        },
+        Expression::WindowFunction {
+            op: "sum".to_string(),
+            params: vec![],
+            args: vec![col("number")],
+            partition_by: vec![],
+            order_by: vec![sort("number", true, true)],
+        },
+    ];
+    let input_schema = test_source.number_schema_for_test()?;
+    let plan = PlanBuilder::create(input_schema.clone())
+        .window(&window_exprs)?
+        .build()?;
+
+    if pipeline.last_pipe()?.nums() > 1 {
+        pipeline.merge_processor()?;
+    }
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(WindowTransform::create(
+            plan.schema(),
+            input_schema.clone(),
+            window_exprs.clone(),
+        )))
+    })?;
+
+    // Result.
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 3);
+
+    // The rows are in the order of the last window.
+    let expected = vec![
+        "+--------+------------------------------------------+------------------------------------+",
+        "| number | row_number() OVER (ORDER BY number DESC) | sum(number) OVER (ORDER BY number) |",
+        "+--------+------------------------------------------+------------------------------------+",
+        "| 0      | 8                                        | 0                                  |",
+        "| 1      | 7                                        | 1                                  |",
+        "| 2      | 6                                        | 3                                  |",
+        "| 3      | 5                                        | 6                                  |",
+        "| 4      | 4                                        | 10                                 |",
+        "| 5      | 3                                        | 15                                 |",
+        "| 6      | 2                                        | 21                                 |",
+        "| 7      | 1                                        | 28                                 |",
+        "+--------+------------------------------------------+------------------------------------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    Ok(())
+}
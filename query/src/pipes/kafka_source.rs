@@ -0,0 +1,119 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use common_management::KafkaSourceInfo;
+use rdkafka::consumer::Consumer;
+use rdkafka::consumer::StreamConsumer;
+use rdkafka::ClientConfig;
+use rdkafka::Message;
+use rdkafka::Offset;
+use rdkafka::TopicPartitionList;
+
+use crate::pipes::PipeMessage;
+use crate::pipes::PipeSource;
+
+const KAFKA_METADATA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Consumes a kafka topic. The offsets are managed by the pipe, nothing is committed to kafka.
+pub struct KafkaSource {
+    topic: String,
+    consumer: StreamConsumer,
+}
+
+impl KafkaSource {
+    pub fn try_create(info: &KafkaSourceInfo, group_id: &str) -> Result<KafkaSource> {
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &info.brokers)
+            .set("group.id", group_id)
+            .set("enable.auto.commit", "false")
+            .set("enable.partition.eof", "false")
+            .create()
+            .map_err_to_code(ErrorCode::PipeSourceError, || {
+                format!("Cannot create kafka consumer of {}", info.brokers)
+            })?;
+
+        Ok(KafkaSource {
+            topic: info.topic.clone(),
+            consumer,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PipeSource for KafkaSource {
+    fn seek(&mut self, offsets: &BTreeMap<i32, i64>) -> Result<()> {
+        let metadata = self
+            .consumer
+            .fetch_metadata(Some(&self.topic), KAFKA_METADATA_TIMEOUT)
+            .map_err_to_code(ErrorCode::PipeSourceError, || {
+                format!("Cannot fetch the metadata of kafka topic {}", self.topic)
+            })?;
+
+        let mut assignment = TopicPartitionList::new();
+        for topic in metadata.topics() {
+            for partition in topic.partitions() {
+                let offset = match offsets.get(&partition.id()) {
+                    None => Offset::Beginning,
+                    Some(offset) => Offset::Offset(*offset),
+                };
+                assignment
+                    .add_partition_offset(&self.topic, partition.id(), offset)
+                    .map_err_to_code(ErrorCode::PipeSourceError, || {
+                        format!("Cannot seek kafka topic {}", self.topic)
+                    })?;
+            }
+        }
+
+        self.consumer
+            .assign(&assignment)
+            .map_err_to_code(ErrorCode::PipeSourceError, || {
+                format!("Cannot assign kafka topic {}", self.topic)
+            })
+    }
+
+    async fn poll(&mut self, max_messages: usize, timeout: Duration) -> Result<Vec<PipeMessage>> {
+        let deadline = Instant::now() + timeout;
+        let mut messages = Vec::with_capacity(max_messages);
+        while messages.len() < max_messages {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            match tokio::time::timeout(remaining, self.consumer.recv()).await {
+                Err(_) => break,
+                Ok(Err(cause)) => {
+                    return Err(ErrorCode::PipeSourceError(format!(
+                        "Cannot consume kafka topic {}, cause {}",
+                        self.topic, cause
+                    )))
+                }
+                Ok(Ok(message)) => messages.push(PipeMessage {
+                    partition: message.partition(),
+                    offset: message.offset(),
+                    payload: message.payload().unwrap_or_default().to_vec(),
+                }),
+            }
+        }
+        Ok(messages)
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod pipe_worker_test;
+
+#[cfg(feature = "kafka")]
+mod kafka_source;
+mod pipe_manager;
+mod pipe_source;
+mod pipe_worker;
+
+#[cfg(feature = "kafka")]
+pub use kafka_source::KafkaSource;
+pub use pipe_manager::PipeManager;
+pub use pipe_manager::PipeManagerRef;
+pub use pipe_source::PipeMessage;
+pub use pipe_source::PipeSource;
+pub use pipe_worker::PipeWorker;
@@ -0,0 +1,177 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::tokio::task::JoinHandle;
+#[cfg(not(feature = "kafka"))]
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_management::KafkaSourceInfo;
+use common_management::PipeInfo;
+use common_management::PipeMgr;
+use common_management::PipeMgrApi;
+use common_management::PipeSourceInfo;
+use common_meta_api::KVApi;
+
+use crate::common::MetaClientProvider;
+use crate::configs::Config;
+use crate::pipes::PipeSource;
+use crate::pipes::PipeWorker;
+use crate::sessions::SessionManagerRef;
+
+pub type PipeManagerRef = Arc<PipeManager>;
+
+/// Keeps the pipes in the meta service and runs their workers on this node.
+///
+/// The workers run on the leader of the tenant only, they are started when the node becomes the
+/// leader and stopped when it is not any more. A worker of the previous leader may still be
+/// running meanwhile, the workers of a pipe fence each other by the seq of the checkpoint.
+pub struct PipeManager {
+    tenant: String,
+    api_provider: Arc<dyn PipeMgrApi>,
+    // Whether the workers run on this node, the new pipes are only started then.
+    running: Mutex<bool>,
+    workers: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl PipeManager {
+    async fn create_kv_client(cfg: &Config) -> Result<Arc<dyn KVApi>> {
+        let store_api_provider = MetaClientProvider::from(cfg);
+        match store_api_provider.try_get_kv_client().await {
+            Ok(client) => Ok(client),
+            Err(cause) => Err(cause.add_message_back("(while create pipe api).")),
+        }
+    }
+
+    pub async fn create_global(cfg: Config) -> Result<PipeManagerRef> {
+        let client = PipeManager::create_kv_client(&cfg).await?;
+        let tenant = &cfg.query.tenant;
+        let pipe_manager = PipeMgr::new(client, tenant);
+
+        Ok(Arc::new(PipeManager {
+            tenant: tenant.clone(),
+            api_provider: Arc::new(pipe_manager),
+            running: Mutex::new(false),
+            workers: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    pub fn is_running(&self) -> bool {
+        *self.running.lock()
+    }
+
+    pub fn get_pipe(&self, name: &str) -> Result<PipeInfo> {
+        Ok(self.api_provider.get_pipe(name.to_string(), None)?.1)
+    }
+
+    pub fn get_pipes(&self) -> Result<Vec<PipeInfo>> {
+        let pipes = self.api_provider.get_pipes()?;
+        Ok(pipes.into_iter().map(|(_, pipe)| pipe).collect())
+    }
+
+    // Add a new pipe and start to ingest if the workers run on this node.
+    pub fn add_pipe(&self, sessions: SessionManagerRef, pipe_info: PipeInfo) -> Result<()> {
+        // The source is created first, a pipe which can not run is not added.
+        let worker = self.create_worker(&pipe_info)?;
+        self.api_provider.add_pipe(pipe_info.clone())?;
+        let running = self.running.lock();
+        if *running {
+            self.spawn_worker(sessions, pipe_info.name, worker);
+        }
+        Ok(())
+    }
+
+    // Stop ingesting and drop the pipe.
+    pub fn drop_pipe(&self, name: &str) -> Result<()> {
+        self.stop_pipe(name);
+        self.api_provider.drop_pipe(name.to_string(), None)
+    }
+
+    /// Starts the workers of all the pipes, called when the node becomes the leader.
+    pub fn start_pipes(&self, sessions: SessionManagerRef) -> Result<()> {
+        let pipes = self.get_pipes()?;
+        let mut running = self.running.lock();
+        *running = true;
+        for pipe_info in pipes {
+            let name = pipe_info.name.clone();
+            if let Err(cause) = self.start_pipe(sessions.clone(), pipe_info) {
+                log::error!("Cannot start pipe {}, cause {}", name, cause);
+            }
+        }
+        Ok(())
+    }
+
+    fn start_pipe(&self, sessions: SessionManagerRef, pipe_info: PipeInfo) -> Result<()> {
+        let worker = self.create_worker(&pipe_info)?;
+        self.spawn_worker(sessions, pipe_info.name, worker);
+        Ok(())
+    }
+
+    fn create_worker(&self, pipe_info: &PipeInfo) -> Result<PipeWorker> {
+        match &pipe_info.source {
+            PipeSourceInfo::Kafka(kafka) => {
+                let group_id = format!("databend-pipe-{}-{}", self.tenant, pipe_info.name);
+                let source = Self::create_kafka_source(kafka, &group_id)?;
+                Ok(PipeWorker::create(
+                    pipe_info.clone(),
+                    self.api_provider.clone(),
+                    source,
+                    kafka.batch_max_messages as usize,
+                    Duration::from_millis(kafka.batch_interval_ms),
+                ))
+            }
+        }
+    }
+
+    #[cfg(feature = "kafka")]
+    fn create_kafka_source(kafka: &KafkaSourceInfo, group_id: &str) -> Result<Box<dyn PipeSource>> {
+        let source = crate::pipes::KafkaSource::try_create(kafka, group_id)?;
+        Ok(Box::new(source))
+    }
+
+    #[cfg(not(feature = "kafka"))]
+    fn create_kafka_source(_: &KafkaSourceInfo, _: &str) -> Result<Box<dyn PipeSource>> {
+        Err(ErrorCode::UnImplement(
+            "Kafka pipes are disabled, databend-query must be built with the kafka feature",
+        ))
+    }
+
+    fn spawn_worker(&self, sessions: SessionManagerRef, name: String, worker: PipeWorker) {
+        let handle = tokio::spawn(worker.run(sessions));
+        if let Some(prev) = self.workers.lock().insert(name, handle) {
+            prev.abort();
+        }
+    }
+
+    /// Aborting a worker at any point is safe, the uncommitted batch is discarded or
+    /// checkpointed by the next worker of the pipe.
+    pub fn stop_pipe(&self, name: &str) {
+        if let Some(handle) = self.workers.lock().remove(name) {
+            handle.abort();
+        }
+    }
+
+    /// Stops the workers of all the pipes, called when the node is not the leader any more.
+    pub fn stop_pipes(&self) {
+        *self.running.lock() = false;
+        for (_, handle) in self.workers.lock().drain() {
+            handle.abort();
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use common_exception::Result;
+
+/// A message consumed from a pipe source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PipeMessage {
+    pub partition: i32,
+    pub offset: i64,
+    pub payload: Vec<u8>,
+}
+
+/// A partitioned source a pipe ingests from, e.g. a kafka topic.
+/// The messages of one partition are polled in the order of their offsets.
+#[async_trait::async_trait]
+pub trait PipeSource: Send {
+    /// Consumes from the offsets(partition id -> the next offset to consume) on,
+    /// the partitions not in the offsets are consumed from the beginning.
+    fn seek(&mut self, offsets: &BTreeMap<i32, i64>) -> Result<()>;
+
+    /// Polls at most `max_messages` messages, waits no longer than `timeout` for them.
+    async fn poll(&mut self, max_messages: usize, timeout: Duration) -> Result<Vec<PipeMessage>>;
+}
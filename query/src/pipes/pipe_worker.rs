@@ -0,0 +1,282 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::PipeCheckpoint;
+use common_management::PipeInfo;
+use common_management::PipeMgrApi;
+use common_management::PipePendingBatch;
+use common_management::PipeSourceInfo;
+use common_streams::CsvSource;
+use common_streams::Source;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::table::fuse::FuseTable;
+use crate::pipes::PipeMessage;
+use crate::pipes::PipeSource;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::SessionManagerRef;
+
+/// Ingests the messages of a pipe source into a FUSE table by micro-batches.
+///
+/// A micro-batch is written to the table as a new snapshot, which is recorded as pending in the
+/// checkpoint of the pipe before it is committed. After a failure, the pending batch is either
+/// found in the table(its offsets are checkpointed) or discarded(its messages are consumed again),
+/// so every message of a partition is ingested exactly once.
+///
+/// A message which can not be decoded would fail its batch on every retry and stall the pipe,
+/// it is logged and skipped instead.
+pub struct PipeWorker {
+    pipe: PipeInfo,
+    api: Arc<dyn PipeMgrApi>,
+    source: Box<dyn PipeSource>,
+    batch_max_messages: usize,
+    batch_interval: Duration,
+
+    // The source may be ahead of the checkpoint, it has to seek before polling.
+    need_seek: bool,
+}
+
+impl PipeWorker {
+    pub fn create(
+        pipe: PipeInfo,
+        api: Arc<dyn PipeMgrApi>,
+        source: Box<dyn PipeSource>,
+        batch_max_messages: usize,
+        batch_interval: Duration,
+    ) -> PipeWorker {
+        PipeWorker {
+            pipe,
+            api,
+            source,
+            batch_max_messages,
+            batch_interval,
+            need_seek: true,
+        }
+    }
+
+    /// Runs the micro-batches until the task is aborted.
+    pub async fn run(mut self, sessions: SessionManagerRef) {
+        loop {
+            let res = match sessions.create_session("PipeSession") {
+                Err(cause) => Err(cause),
+                Ok(session) => match session.create_context().await {
+                    Err(cause) => Err(cause),
+                    Ok(ctx) => self.run_batch(ctx).await,
+                },
+            };
+
+            if let Err(cause) = res {
+                log::error!("Pipe {} failed to ingest, cause {}", self.pipe.name, cause);
+                tokio::time::sleep(self.batch_interval).await;
+            }
+        }
+    }
+
+    /// Ingests one micro-batch, returns the number of the ingested messages.
+    pub async fn run_batch(&mut self, ctx: DatabendQueryContextRef) -> Result<usize> {
+        let io_ctx = ctx.get_single_node_table_io_context()?;
+        let catalog = ctx.get_catalog();
+        let name = self.pipe.name.clone();
+
+        let (mut seq, mut checkpoint) = self.api.get_checkpoint(name.clone())?;
+        if let Some(pending) = checkpoint.pending.take() {
+            let table = catalog.get_table(&self.pipe.db, &self.pipe.table)?;
+            let fuse_table = self.fuse_table(table.as_ref())?;
+            if fuse_table
+                .is_snapshot_committed(&io_ctx, &pending.snapshot_location)
+                .await?
+            {
+                checkpoint.offsets = pending.offsets;
+            }
+            seq = self
+                .api
+                .upsert_checkpoint(name.clone(), checkpoint.clone(), seq)?;
+            self.need_seek = true;
+        }
+
+        if self.need_seek {
+            self.source.seek(&checkpoint.offsets)?;
+            self.need_seek = false;
+        }
+
+        let messages = self
+            .source
+            .poll(self.batch_max_messages, self.batch_interval)
+            .await?;
+        if messages.is_empty() {
+            return Ok(0);
+        }
+
+        // Until the checkpoint is saved, a failure leaves the source ahead of it.
+        self.need_seek = true;
+
+        let mut offsets = checkpoint.offsets.clone();
+        for message in messages.iter() {
+            offsets.insert(message.partition, message.offset + 1);
+        }
+
+        let table = catalog.get_table(&self.pipe.db, &self.pipe.table)?;
+        let fuse_table = self.fuse_table(table.as_ref())?;
+        let settings = ctx.get_settings();
+        let blocks = self.read_blocks(
+            &messages,
+            table.schema(),
+            settings.get_max_block_size()? as usize,
+            settings.get_lenient_insert_cast()? != 0,
+        )?;
+        if blocks.is_empty() {
+            let committed = PipeCheckpoint {
+                offsets,
+                pending: None,
+            };
+            self.api.upsert_checkpoint(name, committed, seq)?;
+            self.need_seek = false;
+            return Ok(messages.len());
+        }
+
+        let (snapshot_location, rows) = fuse_table
+            .do_append_uncommitted(
+                &io_ctx,
+                Box::pin(futures::stream::iter(blocks.into_iter().map(Ok))),
+            )
+            .await?;
+
+        let pending = PipeCheckpoint {
+            offsets: checkpoint.offsets,
+            pending: Some(PipePendingBatch {
+                offsets: offsets.clone(),
+                snapshot_location: snapshot_location.clone(),
+            }),
+        };
+        let seq = self.api.upsert_checkpoint(name.clone(), pending, seq)?;
+
+        // Another worker of the pipe which finds the batch pending and not committed discards it,
+        // the batch is committed only while the pending checkpoint is still the one saved here.
+        let api = self.api.clone();
+        let owned = || {
+            let (current, _) = api.get_checkpoint(name.clone())?;
+            match current == seq {
+                true => Ok(()),
+                false => Err(ErrorCode::PipeCheckpointConflict(format!(
+                    "Pending batch of pipe {} is discarded by others",
+                    name
+                ))),
+            }
+        };
+        fuse_table
+            .do_commit_if(&io_ctx, snapshot_location, rows, owned)
+            .await?;
+
+        let committed = PipeCheckpoint {
+            offsets,
+            pending: None,
+        };
+        self.api.upsert_checkpoint(name, committed, seq)?;
+        self.need_seek = false;
+        Ok(messages.len())
+    }
+
+    fn fuse_table<'a>(&self, table: &'a dyn Table) -> Result<&'a FuseTable> {
+        table.as_any().downcast_ref::<FuseTable>().ok_or_else(|| {
+            ErrorCode::BadOption(format!(
+                "Pipe {} can only ingest into FUSE tables, table {}.{} is {}",
+                self.pipe.name,
+                self.pipe.db,
+                self.pipe.table,
+                table.engine()
+            ))
+        })
+    }
+
+    fn read_blocks(
+        &self,
+        messages: &[PipeMessage],
+        schema: DataSchemaRef,
+        block_size: usize,
+        lenient_cast: bool,
+    ) -> Result<Vec<DataBlock>> {
+        let format = self.pipe_format();
+        if format != "csv" {
+            return Err(ErrorCode::BadOption(format!(
+                "Unsupported pipe format: {}",
+                format
+            )));
+        }
+
+        if let Ok(blocks) = Self::read_csv(messages, schema.clone(), block_size, lenient_cast) {
+            return Ok(blocks);
+        }
+
+        // Some messages of the batch are bad, decode them one by one to skip the bad ones.
+        let mut blocks = vec![];
+        for message in messages {
+            let decoded = Self::read_csv(
+                std::slice::from_ref(message),
+                schema.clone(),
+                block_size,
+                lenient_cast,
+            );
+            match decoded {
+                Ok(decoded) => blocks.extend(decoded),
+                Err(cause) => log::error!(
+                    "Pipe {} skipped the message at offset {} of partition {}, cause {}",
+                    self.pipe.name,
+                    message.offset,
+                    message.partition,
+                    cause
+                ),
+            }
+        }
+        Ok(blocks)
+    }
+
+    fn read_csv(
+        messages: &[PipeMessage],
+        schema: DataSchemaRef,
+        block_size: usize,
+        lenient_cast: bool,
+    ) -> Result<Vec<DataBlock>> {
+        let mut data = vec![];
+        for message in messages {
+            data.extend_from_slice(&message.payload);
+            if !message.payload.ends_with(b"\n") {
+                data.push(b'\n');
+            }
+        }
+
+        let mut source =
+            CsvSource::new(Cursor::new(data), schema, block_size).with_lenient_cast(lenient_cast);
+        let mut blocks = vec![];
+        while let Some(block) = source.read()? {
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    fn pipe_format(&self) -> String {
+        match &self.pipe.source {
+            PipeSourceInfo::Kafka(kafka) => kafka.format.to_lowercase(),
+        }
+    }
+}
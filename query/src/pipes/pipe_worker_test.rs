@@ -0,0 +1,245 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_management::KafkaSourceInfo;
+use common_management::PipeCheckpoint;
+use common_management::PipeInfo;
+use common_management::PipeMgr;
+use common_management::PipeMgrApi;
+use common_management::PipePendingBatch;
+use common_management::PipeSourceInfo;
+use common_meta_embedded::MetaEmbedded;
+use common_planners::CreateTablePlan;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::catalogs::Catalog;
+use crate::configs::Config;
+use crate::datasources::table::fuse::FuseTable;
+use crate::pipes::PipeMessage;
+use crate::pipes::PipeSource;
+use crate::pipes::PipeWorker;
+use crate::sessions::DatabendQueryContextRef;
+
+/// The messages of all the partitions, seeks and polls like kafka.
+struct MemorySource {
+    messages: Arc<Mutex<Vec<PipeMessage>>>,
+    positions: BTreeMap<i32, i64>,
+}
+
+#[async_trait::async_trait]
+impl PipeSource for MemorySource {
+    fn seek(&mut self, offsets: &BTreeMap<i32, i64>) -> Result<()> {
+        self.positions = offsets.clone();
+        Ok(())
+    }
+
+    async fn poll(&mut self, max_messages: usize, _: Duration) -> Result<Vec<PipeMessage>> {
+        let mut polled = vec![];
+        for message in self.messages.lock().iter() {
+            let position = self.positions.entry(message.partition).or_insert(0);
+            if polled.len() < max_messages && message.offset >= *position {
+                *position = message.offset + 1;
+                polled.push(message.clone());
+            }
+        }
+        Ok(polled)
+    }
+}
+
+fn message(partition: i32, offset: i64, payload: &str) -> PipeMessage {
+    PipeMessage {
+        partition,
+        offset,
+        payload: payload.as_bytes().to_vec(),
+    }
+}
+
+async fn count_rows(ctx: &DatabendQueryContextRef) -> Result<usize> {
+    let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+    let table = ctx.get_catalog().get_table("default", "t")?;
+    let (_, parts) = table.read_partitions(io_ctx.clone(), None, None)?;
+    ctx.try_set_partitions(parts)?;
+    let stream = table.read(io_ctx, &None).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    Ok(blocks.iter().map(|block| block.num_rows()).sum())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pipe_worker_exactly_once() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    ctx.get_catalog().create_table(CreateTablePlan {
+        if_not_exists: false,
+        db: "default".to_string(),
+        table: "t".to_string(),
+        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]),
+        engine: "FUSE".to_string(),
+        options: Default::default(),
+        temporary: false,
+        as_select: None,
+    })?;
+
+    let pipe = PipeInfo {
+        name: "p".to_string(),
+        db: "default".to_string(),
+        table: "t".to_string(),
+        source: PipeSourceInfo::Kafka(KafkaSourceInfo {
+            brokers: "".to_string(),
+            topic: "".to_string(),
+            format: "csv".to_string(),
+            batch_max_messages: 100,
+            batch_interval_ms: 10,
+        }),
+    };
+    let api: Arc<dyn PipeMgrApi> = Arc::new(PipeMgr::new(
+        Arc::new(MetaEmbedded::new_temp().await?),
+        "test",
+    ));
+    api.add_pipe(pipe.clone())?;
+
+    let messages = Arc::new(Mutex::new(vec![
+        message(0, 0, "1"),
+        message(0, 1, "2"),
+        message(1, 0, "3\n4\n"),
+    ]));
+    let source = MemorySource {
+        messages: messages.clone(),
+        positions: BTreeMap::new(),
+    };
+    let mut worker = PipeWorker::create(
+        pipe,
+        api.clone(),
+        Box::new(source),
+        100,
+        Duration::from_millis(10),
+    );
+
+    // 1. ingest a batch
+    assert_eq!(worker.run_batch(ctx.clone()).await?, 3);
+    assert_eq!(count_rows(&ctx).await?, 4);
+    let (_, checkpoint) = api.get_checkpoint("p".to_string())?;
+    let offsets: BTreeMap<i32, i64> = vec![(0, 2), (1, 1)].into_iter().collect();
+    assert_eq!(checkpoint, PipeCheckpoint {
+        offsets: offsets.clone(),
+        pending: None,
+    });
+
+    // 2. nothing new
+    assert_eq!(worker.run_batch(ctx.clone()).await?, 0);
+
+    // 3. a batch failed before committed is consumed again
+    messages.lock().push(message(0, 2, "5"));
+    let (seq, _) = api.get_checkpoint("p".to_string())?;
+    api.upsert_checkpoint(
+        "p".to_string(),
+        PipeCheckpoint {
+            offsets: offsets.clone(),
+            pending: Some(PipePendingBatch {
+                offsets: vec![(0, 3), (1, 1)].into_iter().collect(),
+                snapshot_location: "_ss/not_committed".to_string(),
+            }),
+        },
+        seq,
+    )?;
+    assert_eq!(worker.run_batch(ctx.clone()).await?, 1);
+    assert_eq!(count_rows(&ctx).await?, 5);
+
+    // 4. a batch failed after committed is not consumed again
+    messages.lock().push(message(0, 3, "6"));
+    let io_ctx = ctx.get_single_node_table_io_context()?;
+    let table = ctx.get_catalog().get_table("default", "t")?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let block = DataBlock::create_by_array(table.schema(), vec![Series::new(vec![6i32])]);
+    let (snapshot_location, rows) = fuse_table
+        .do_append_uncommitted(&io_ctx, Box::pin(futures::stream::iter(vec![Ok(block)])))
+        .await?;
+    let (seq, checkpoint) = api.get_checkpoint("p".to_string())?;
+    let committed_offsets: BTreeMap<i32, i64> = vec![(0, 4), (1, 1)].into_iter().collect();
+    api.upsert_checkpoint(
+        "p".to_string(),
+        PipeCheckpoint {
+            offsets: checkpoint.offsets,
+            pending: Some(PipePendingBatch {
+                offsets: committed_offsets.clone(),
+                snapshot_location: snapshot_location.clone(),
+            }),
+        },
+        seq,
+    )?;
+    fuse_table
+        .do_commit(&io_ctx, snapshot_location, rows)
+        .await?;
+
+    assert_eq!(worker.run_batch(ctx.clone()).await?, 0);
+    assert_eq!(count_rows(&ctx).await?, 6);
+    let (_, checkpoint) = api.get_checkpoint("p".to_string())?;
+    assert_eq!(checkpoint, PipeCheckpoint {
+        offsets: committed_offsets,
+        pending: None,
+    });
+
+    // 5. a message which can not be decoded is skipped, the others of the batch are ingested
+    messages.lock().push(message(1, 1, "x"));
+    messages.lock().push(message(1, 2, "7"));
+    assert_eq!(worker.run_batch(ctx.clone()).await?, 2);
+    assert_eq!(count_rows(&ctx).await?, 7);
+    let (_, checkpoint) = api.get_checkpoint("p".to_string())?;
+    assert_eq!(checkpoint, PipeCheckpoint {
+        offsets: vec![(0, 4), (1, 3)].into_iter().collect(),
+        pending: None,
+    });
+
+    // 6. a batch whose pending checkpoint is discarded by another worker is not committed
+    let table = ctx.get_catalog().get_table("default", "t")?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let block = DataBlock::create_by_array(table.schema(), vec![Series::new(vec![8i32])]);
+    let (snapshot_location, rows) = fuse_table
+        .do_append_uncommitted(&io_ctx, Box::pin(futures::stream::iter(vec![Ok(block)])))
+        .await?;
+    let discarded = || {
+        Err(ErrorCode::PipeCheckpointConflict(
+            "Pending batch of pipe p is discarded by others",
+        ))
+    };
+    let result = fuse_table
+        .do_commit_if(&io_ctx, snapshot_location.clone(), rows, discarded)
+        .await;
+    assert_eq!(
+        result.unwrap_err().code(),
+        ErrorCode::PipeCheckpointConflict("").code()
+    );
+    assert!(
+        !fuse_table
+            .is_snapshot_committed(&io_ctx, &snapshot_location)
+            .await?
+    );
+    assert_eq!(count_rows(&ctx).await?, 7);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_management::PurgeMgr;
+use common_management::PurgeMgrApi;
+use common_management::PurgeTask;
+use common_meta_api::KVApi;
+use common_meta_types::SeqValue;
+
+use crate::common::MetaClientProvider;
+use crate::configs::Config;
+
+pub type PurgeManagerRef = Arc<PurgeManager>;
+
+/// Keeps the tasks removing the data of the dropped tables in the meta service.
+pub struct PurgeManager {
+    api_provider: Arc<dyn PurgeMgrApi>,
+}
+
+impl PurgeManager {
+    async fn create_kv_client(cfg: &Config) -> Result<Arc<dyn KVApi>> {
+        let store_api_provider = MetaClientProvider::from(cfg);
+        match store_api_provider.try_get_kv_client().await {
+            Ok(client) => Ok(client),
+            Err(cause) => Err(cause.add_message_back("(while create purge api).")),
+        }
+    }
+
+    pub async fn create_global(cfg: Config) -> Result<PurgeManagerRef> {
+        let client = PurgeManager::create_kv_client(&cfg).await?;
+        let purge_manager = PurgeMgr::new(client, &cfg.query.tenant);
+
+        Ok(Arc::new(PurgeManager {
+            api_provider: Arc::new(purge_manager),
+        }))
+    }
+
+    pub fn add_task(&self, task: PurgeTask) -> Result<u64> {
+        self.api_provider.add_purge_task(task)
+    }
+
+    pub fn get_tasks(&self) -> Result<Vec<SeqValue<PurgeTask>>> {
+        self.api_provider.get_purge_tasks()
+    }
+
+    pub fn update_task(&self, task: PurgeTask, seq: u64) -> Result<u64> {
+        self.api_provider.update_purge_task(task, seq)
+    }
+}
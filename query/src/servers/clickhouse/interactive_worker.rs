@@ -25,6 +25,10 @@ use crate::servers::clickhouse::writers::to_clickhouse_err;
 use crate::servers::clickhouse::writers::QueryWriter;
 use crate::sessions::SessionRef;
 
+/// The ClickHouse TCP protocol revision the handler speaks,
+/// the MIN_SERVER_REVISION for suggestions is 54406.
+pub const CLICKHOUSE_TCP_PROTOCOL_VERSION: u64 = 54405;
+
 pub struct InteractiveWorker {
     session: SessionRef,
 }
@@ -44,11 +48,15 @@ impl ClickHouseSession for InteractiveWorker {
     ) -> common_clickhouse_srv::errors::Result<()> {
         let start = Instant::now();
 
-        let mut query_writer = QueryWriter::create(ctx.client_revision, conn);
-
         let session = self.session.clone();
-        let get_query_result = InteractiveWorkerBase::do_query(ctx, session);
-        if let Err(cause) = query_writer.write(get_query_result.await).await {
+        let query_result = InteractiveWorkerBase::do_query(ctx, session).await;
+        let write_result = QueryWriter::create(ctx, conn).write(query_result).await;
+
+        if ctx.state.is_cancelled {
+            self.session.force_kill_query();
+        }
+
+        if let Err(cause) = write_result {
             let new_error = cause.add_message(&ctx.state.query);
             return Err(to_clickhouse_err(new_error));
         }
@@ -90,15 +98,27 @@ impl ClickHouseSession for InteractiveWorker {
         "UTC"
     }
 
-    // TODO: remove it
-    // the MIN_SERVER_REVISION for suggestions is 54406
     fn dbms_tcp_protocol_version(&self) -> u64 {
-        54405
+        CLICKHOUSE_TCP_PROTOCOL_VERSION
     }
 
     fn authenticate(&self, user: &str, password: &[u8], client_addr: &str) -> bool {
-        let user_mgr = self.session.get_user_manager();
-        if let Ok(res) = user_mgr.auth_user(user, password, client_addr) {
+        let (tenant, user_name) = self.session.get_user_manager().split_login(user);
+        let user_mgr = self.session.get_user_manager().for_tenant(&tenant);
+        if let Ok(res) = user_mgr.auth_user(&user_name, password, client_addr) {
+            if res {
+                // The quota and the settings profiles must be applied, or the user would run
+                // without any limits.
+                let applied = user_mgr.get_user(&user_name).and_then(|user| {
+                    self.session.set_user_quota(user.quota);
+                    self.session.apply_settings_profile(&user_mgr, &user)
+                });
+                if applied.is_err() {
+                    return false;
+                }
+                self.session.set_user(user_name);
+                self.session.set_tenant(tenant);
+            }
             return res;
         }
         log::error!(
@@ -27,9 +27,11 @@ use common_clickhouse_srv::types::Block as ClickHouseBlock;
 use common_clickhouse_srv::CHContext;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::InsertIntoPlan;
 use common_planners::PlanNode;
+use common_tracing::tracing;
 use futures::channel::mpsc;
 use futures::channel::mpsc::Receiver;
 use futures::SinkExt;
@@ -42,6 +44,7 @@ use super::writers::from_clickhouse_block;
 use crate::interpreters::InterpreterFactory;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sessions::SessionRef;
+use crate::sql::redact_secrets;
 use crate::sql::PlanParser;
 
 pub struct InteractiveWorkerBase;
@@ -54,26 +57,44 @@ pub enum BlockItem {
 }
 
 impl InteractiveWorkerBase {
+    #[tracing::instrument(level = "info", skip(ch_ctx, session), fields(query = redact_secrets(&ch_ctx.state.query).as_str()))]
     pub async fn do_query(
         ch_ctx: &mut CHContext,
         session: SessionRef,
     ) -> Result<Receiver<BlockItem>> {
         let query = &ch_ctx.state.query;
-        log::debug!("{}", query);
+        log::debug!("{}", redact_secrets(query));
+
+        Self::apply_settings(ch_ctx, &session)?;
 
         let ctx = session.create_context().await?;
         ctx.attach_query_str(query);
 
-        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        let query_result = Self::process_query(ch_ctx, ctx.clone()).await;
+        if let Err(cause) = &query_result {
+            ctx.attach_query_error(cause);
+        }
+        query_result
+    }
+
+    async fn process_query(
+        ch_ctx: &mut CHContext,
+        ctx: DatabendQueryContextRef,
+    ) -> Result<Receiver<BlockItem>> {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(&ch_ctx.state.query)?;
+        ctx.wait_for_admission().await?;
 
         match plan {
-            PlanNode::InsertInto(insert) => Self::process_insert_query(insert, ch_ctx, ctx).await,
+            // The client sends the data of INSERT VALUES, not the data of INSERT SELECT.
+            PlanNode::InsertInto(insert) if insert.select_plan.is_none() => {
+                Self::process_insert_query(insert, ch_ctx, ctx).await
+            }
             _ => {
                 let start = Instant::now();
                 let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
                 let name = interpreter.name().to_string();
-                let async_data_stream = interpreter.execute();
-                let mut data_stream = async_data_stream.await?;
+                let async_data_stream = ctx.with_cpu_time(interpreter.execute());
+                let mut data_stream = ctx.try_create_result_quota(async_data_stream.await?)?;
                 histogram!(
                     super::clickhouse_metrics::METRIC_INTERPRETER_USEDTIME,
                     start.elapsed(),
@@ -95,8 +116,12 @@ impl InteractiveWorkerBase {
                     }
                 });
 
+                let error_ctx = ctx.clone();
                 ctx.try_spawn(async move {
                     while let Some(block) = data_stream.next().await {
+                        if let Err(cause) = &block {
+                            error_ctx.attach_query_error(cause);
+                        }
                         tx2.send(BlockItem::Block(block)).await.ok();
                     }
 
@@ -108,6 +133,21 @@ impl InteractiveWorkerBase {
         }
     }
 
+    fn apply_settings(ch_ctx: &CHContext, session: &SessionRef) -> Result<()> {
+        let settings = session.get_settings();
+        for (name, value) in &ch_ctx.state.settings {
+            match settings.update_settings(name, value.to_string()) {
+                Ok(_) => {}
+                // clickhouse-client sends its own settings too, skip the ones we don't have.
+                Err(error) if error.code() == ErrorCode::UnknownVariable("").code() => {
+                    log::debug!("Ignore unknown setting {} from clickhouse client", name);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+
     pub async fn process_insert_query(
         insert: InsertIntoPlan,
         ch_ctx: &mut CHContext,
@@ -152,27 +192,14 @@ pub struct FromClickHouseBlockStream {
 }
 
 impl futures::stream::Stream for FromClickHouseBlockStream {
-    type Item = DataBlock;
+    type Item = Result<DataBlock>;
 
     fn poll_next(
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        self.input.poll_next_unpin(cx).map(|x| match x {
-            Some(v) => {
-                let block = from_clickhouse_block(self.schema.clone(), v);
-                match block {
-                    Ok(block) => Some(block),
-                    Err(e) => {
-                        log::error!(
-                            "failed to convert ClickHouseBlock to block , breaking out, {:?}",
-                            e
-                        );
-                        None
-                    }
-                }
-            }
-            _ => None,
-        })
+        self.input
+            .poll_next_unpin(cx)
+            .map(|x| x.map(|v| from_clickhouse_block(self.schema.clone(), v)))
     }
 }
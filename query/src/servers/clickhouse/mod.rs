@@ -25,3 +25,4 @@ mod interactive_worker_base;
 mod reject_connection;
 
 pub use clickhouse_handler::ClickHouseHandler;
+pub use interactive_worker::CLICKHOUSE_TCP_PROTOCOL_VERSION;
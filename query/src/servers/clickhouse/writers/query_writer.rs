@@ -17,14 +17,18 @@ use std::borrow::Cow;
 use chrono::Date;
 use chrono::DateTime;
 use chrono_tz::Tz;
+use common_base::tokio;
 use common_base::ProgressValues;
 use common_clickhouse_srv::connection::Connection;
 use common_clickhouse_srv::errors::Error as CHError;
 use common_clickhouse_srv::errors::Result as CHResult;
 use common_clickhouse_srv::errors::ServerError;
+use common_clickhouse_srv::protocols::Packet;
 use common_clickhouse_srv::types::Block;
 use common_clickhouse_srv::types::DateTimeType;
+use common_clickhouse_srv::types::ProfileInfo;
 use common_clickhouse_srv::types::SqlType;
+use common_clickhouse_srv::CHContext;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
@@ -35,15 +39,17 @@ use futures::StreamExt;
 use crate::servers::clickhouse::interactive_worker_base::BlockItem;
 
 pub struct QueryWriter<'a> {
-    client_version: u64,
+    ctx: &'a mut CHContext,
     conn: &'a mut Connection,
+    profile_info: ProfileInfo,
 }
 
 impl<'a> QueryWriter<'a> {
-    pub fn create(version: u64, conn: &'a mut Connection) -> QueryWriter {
+    pub fn create(ctx: &'a mut CHContext, conn: &'a mut Connection) -> QueryWriter<'a> {
         QueryWriter {
+            ctx,
             conn,
-            client_version: version,
+            profile_info: ProfileInfo::default(),
         }
     }
 
@@ -61,10 +67,10 @@ impl<'a> QueryWriter<'a> {
         let progress = common_clickhouse_srv::types::Progress {
             rows: values.read_rows as u64,
             bytes: values.read_bytes as u64,
-            total_rows: 0,
+            total_rows: values.total_rows_to_read as u64,
         };
 
-        let version = self.client_version;
+        let version = self.ctx.client_revision;
         match self.conn.write_progress(progress, version).await {
             Ok(_) => Ok(()),
             Err(error) => Err(ErrorCode::UnknownException(format!(
@@ -86,7 +92,23 @@ impl<'a> QueryWriter<'a> {
         }
     }
 
+    async fn write_profile_info(&mut self) -> Result<()> {
+        match self.conn.write_profile_info(self.profile_info).await {
+            Ok(_) => Ok(()),
+            Err(error) => Err(ErrorCode::UnknownException(format!(
+                "Cannot send profile info {:?}",
+                error
+            ))),
+        }
+    }
+
     async fn write_block(&mut self, block: DataBlock) -> Result<()> {
+        if block.num_rows() > 0 {
+            self.profile_info.rows += block.num_rows() as u64;
+            self.profile_info.bytes += block.memory_size() as u64;
+            self.profile_info.blocks += 1;
+        }
+
         let block = to_clickhouse_block(block)?;
 
         match self.conn.write_block(&block).await {
@@ -95,9 +117,35 @@ impl<'a> QueryWriter<'a> {
         }
     }
 
+    // Waits for the next item of the query, a cancel packet from the client
+    // (e.g. Ctrl+C in clickhouse-client) ends the query with `None`.
+    async fn next_item(&mut self, receiver: &mut Receiver<BlockItem>) -> Result<Option<BlockItem>> {
+        loop {
+            tokio::select! {
+                biased;
+                item = receiver.next() => return Ok(item),
+                packet = self.conn.read_packet(self.ctx) => match packet {
+                    Ok(Some(Packet::Cancel)) => {
+                        self.ctx.state.is_cancelled = true;
+                        return Ok(None);
+                    }
+                    Ok(Some(packet)) => {
+                        log::warn!("Ignore packet {:?} while the query is running", packet);
+                    }
+                    Ok(None) => {
+                        self.ctx.state.is_cancelled = true;
+                        self.ctx.state.is_connection_closed = true;
+                        return Ok(None);
+                    }
+                    Err(error) => return Err(from_clickhouse_err(error)),
+                },
+            }
+        }
+    }
+
     async fn write_data(&mut self, mut receiver: Receiver<BlockItem>) -> Result<()> {
         loop {
-            match receiver.next().await {
+            match self.next_item(&mut receiver).await? {
                 None => {
                     return Ok(());
                 }
@@ -126,16 +174,22 @@ impl<'a> QueryWriter<'a> {
     }
 
     async fn write_tail_data(&mut self, mut receiver: Receiver<BlockItem>) -> Result<()> {
-        while let Some(item) = receiver.next().await {
+        while let Some(item) = self.next_item(&mut receiver).await? {
             match item {
                 BlockItem::Block(Ok(block)) => self.write_block(block).await?,
-                BlockItem::Block(Err(error)) => self.write_error(error).await?,
+                BlockItem::Block(Err(error)) => {
+                    self.write_error(error).await?;
+                    return Ok(());
+                }
                 BlockItem::InsertSample(block) => self.write_block(block).await?,
                 BlockItem::ProgressTicker(values) => self.write_progress(values).await?,
             };
         }
 
-        Ok(())
+        if self.ctx.state.is_connection_closed {
+            return Ok(());
+        }
+        self.write_profile_info().await
     }
 }
 
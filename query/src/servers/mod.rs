@@ -15,6 +15,7 @@
 // The servers module used for external communication with user, such as MySQL wired protocol, etc.
 
 pub use clickhouse::ClickHouseHandler;
+pub use clickhouse::CLICKHOUSE_TCP_PROTOCOL_VERSION;
 pub use server::Server;
 pub use server::ShutdownHandle;
 
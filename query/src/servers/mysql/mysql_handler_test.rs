@@ -22,6 +22,7 @@ use common_base::tokio;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
+use mysql::consts::ColumnType;
 use mysql::prelude::FromRow;
 use mysql::prelude::Queryable;
 use mysql::Conn;
@@ -48,6 +49,83 @@ async fn test_use_database_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_column_types_with_on_query() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+
+    let column_types = query_column_types(
+        &mut connection,
+        "SELECT toInt8(number), toUInt8(number), number, toFloat32(number), toFloat64(number), 'a', NULL FROM numbers(1)",
+    )?;
+    assert_eq!(column_types, vec![
+        ColumnType::MYSQL_TYPE_TINY,
+        ColumnType::MYSQL_TYPE_TINY,
+        ColumnType::MYSQL_TYPE_LONGLONG,
+        ColumnType::MYSQL_TYPE_FLOAT,
+        ColumnType::MYSQL_TYPE_DOUBLE,
+        ColumnType::MYSQL_TYPE_VAR_STRING,
+        ColumnType::MYSQL_TYPE_NULL,
+    ]);
+
+    // UInt64 values over i64::MAX are sent as DECIMAL.
+    let query_str = "SELECT number + 9223372036854775807 FROM numbers(2)";
+    let column_types = query_column_types(&mut connection, query_str)?;
+    assert_eq!(column_types, vec![ColumnType::MYSQL_TYPE_NEWDECIMAL]);
+    let received_data: Vec<String> = query(&mut connection, query_str)?;
+    assert_eq!(received_data, vec![
+        "9223372036854775807",
+        "9223372036854775808"
+    ]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_prepared_statement_with_on_execute() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+
+    let statement = connection
+        .prep("SELECT number, ?, '?' FROM numbers(?) WHERE number > ? -- ?")
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare error")?;
+    assert_eq!(statement.num_params(), 3);
+
+    let received_data: Vec<(u64, String, String)> = connection
+        .exec(&statement, ("it's", 3u64, 0i64))
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute error")?;
+    assert_eq!(received_data, vec![
+        (1, "it's".to_string(), "?".to_string()),
+        (2, "it's".to_string(), "?".to_string()),
+    ]);
+
+    // Parameters are bound as values, never as SQL.
+    let received_data: Vec<String> = connection
+        .exec("SELECT ?", ("1' OR '1' = '1",))
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute error")?;
+    assert_eq!(received_data, vec!["1' OR '1' = '1"]);
+
+    // Backslashes are not escapes in the literals.
+    let received_data: Vec<String> = connection
+        .exec("SELECT ?", ("C:\\data\\' OR '1' = '1",))
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute error")?;
+    assert_eq!(received_data, vec!["C:\\data\\' OR '1' = '1"]);
+
+    connection
+        .close(statement)
+        .map_err_to_code(ErrorCode::UnknownException, || "Close error")?;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_rejected_session_with_sequence() -> Result<()> {
     let mut handler =
@@ -152,6 +230,14 @@ fn query<T: FromRow>(connection: &mut Conn, query: &str) -> Result<Vec<T>> {
         .map_err_to_code(ErrorCode::UnknownException, || "Query error")
 }
 
+fn query_column_types(connection: &mut Conn, query: &str) -> Result<Vec<ColumnType>> {
+    let result = connection
+        .query_iter(query)
+        .map_err_to_code(ErrorCode::UnknownException, || "Query error")?;
+    let columns = result.columns();
+    Ok(columns.as_ref().iter().map(|c| c.column_type()).collect())
+}
+
 fn create_connection(port: u16) -> Result<mysql::Conn> {
     let uri = &format!("mysql://127.0.0.1:{}?user=default", port);
     let opts = mysql::Opts::from_url(uri).unwrap();
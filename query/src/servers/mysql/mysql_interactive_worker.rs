@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::time::Instant;
 
@@ -21,6 +22,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::*;
 use common_planners::PlanNode;
+use common_tracing::tracing;
 use metrics::histogram;
 use msql_srv::ErrorKind;
 use msql_srv::InitWriter;
@@ -32,14 +34,18 @@ use rand::RngCore;
 use tokio_stream::StreamExt;
 
 use crate::interpreters::InterpreterFactory;
+use crate::servers::mysql::mysql_prepared_statement::PreparedStatement;
 use crate::servers::mysql::writers::DFInitResultWriter;
 use crate::servers::mysql::writers::DFQueryResultWriter;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sessions::SessionRef;
+use crate::sql::redact_secrets;
 use crate::sql::PlanParser;
 
 struct InteractiveWorkerBase<W: std::io::Write> {
     session: SessionRef,
+    statement_id: u32,
+    statements: HashMap<u32, PreparedStatement>,
     generic_hold: PhantomData<W>,
 }
 
@@ -81,9 +87,10 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
         salt: &[u8],
         auth_data: &[u8],
     ) -> bool {
-        let user_mgr = self.session.get_user_manager();
-        let user_name = String::from_utf8_lossy(username);
-        if let Ok(user) = user_mgr.get_user(user_name.as_ref()) {
+        let login = String::from_utf8_lossy(username);
+        let (tenant, user_name) = self.session.get_user_manager().split_login(&login);
+        let user_mgr = self.session.get_user_manager().for_tenant(&tenant);
+        if let Ok(user) = user_mgr.get_user(&user_name) {
             let encode_password = match auth_plugin {
                 "mysql_native_password" => {
                     if auth_data.is_empty() {
@@ -113,9 +120,20 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
                 _ => auth_data.to_vec(),
             };
 
-            if let Ok(res) =
-                user_mgr.auth_user(user_name.as_ref(), encode_password, &self.client_addr)
-            {
+            if let Ok(res) = user_mgr.auth_user(&user_name, encode_password, &self.client_addr) {
+                if res {
+                    // The quota and the settings profiles must be applied, or the user would
+                    // run without any limits.
+                    let applied = user_mgr.get_user(&user_name).and_then(|user| {
+                        self.session.set_user_quota(user.quota);
+                        self.session.apply_settings_profile(&user_mgr, &user)
+                    });
+                    if applied.is_err() {
+                        return false;
+                    }
+                    self.session.set_user(user_name);
+                    self.session.set_tenant(tenant);
+                }
                 return res;
             }
         }
@@ -188,7 +206,7 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
                 let mut write_result = writer.write(blocks);
 
                 if let Err(cause) = write_result {
-                    let suffix = format!("(while in query {})", query);
+                    let suffix = format!("(while in query {})", redact_secrets(query));
                     write_result = Err(cause.add_message_back(suffix));
                 }
 
@@ -220,31 +238,62 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
 }
 
 impl<W: std::io::Write> InteractiveWorkerBase<W> {
-    fn do_prepare(&mut self, _: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Prepare is not support in Databend.".as_bytes(),
-        )?;
+    fn do_prepare(&mut self, query: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
+        log::debug!("Prepare: {}", redact_secrets(query));
+
+        let statement = PreparedStatement::create(query);
+        self.statement_id = self.statement_id.wrapping_add(1);
+        writer.reply(self.statement_id, &statement.params(), &[])?;
+        self.statements.insert(self.statement_id, statement);
         Ok(())
     }
 
     fn do_execute(
         &mut self,
-        _: u32,
-        _: ParamParser<'_>,
+        id: u32,
+        params: ParamParser<'_>,
         writer: QueryResultWriter<'_, W>,
     ) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Execute is not support in Databend.".as_bytes(),
-        )?;
-        Ok(())
+        let mut writer = DFQueryResultWriter::create(writer);
+
+        let query = match self.statements.get(&id) {
+            None => Err(ErrorCode::UnknownPreparedStatement(format!(
+                "Unknown prepared statement id: {}",
+                id
+            ))),
+            Some(statement) => statement.bind(params),
+        };
+
+        match (query, Self::build_runtime()) {
+            (Err(error), _) | (_, Err(error)) => writer.write(Err(error)),
+            (Ok(query), Ok(runtime)) => {
+                let instant = Instant::now();
+                let blocks = runtime.block_on(self.do_query(&query));
+
+                let mut write_result = writer.write(blocks);
+
+                if let Err(cause) = write_result {
+                    let suffix = format!("(while in query {})", redact_secrets(query));
+                    write_result = Err(cause.add_message_back(suffix));
+                }
+
+                histogram!(
+                    super::mysql_metrics::METRIC_MYSQL_PROCESSOR_REQUEST_DURATION,
+                    instant.elapsed()
+                );
+
+                write_result
+            }
+        }
     }
 
-    fn do_close(&mut self, _: u32) {}
+    fn do_close(&mut self, id: u32) {
+        self.statements.remove(&id);
+    }
 
+    #[tracing::instrument(level = "info", skip(self, query), fields(query = redact_secrets(query).as_str()))]
     async fn do_query(&mut self, query: &str) -> Result<(Vec<DataBlock>, String)> {
-        log::debug!("{}", query);
+        log::debug!("{}", redact_secrets(query));
 
         let context = self.session.create_context().await?;
         context.attach_query_str(query);
@@ -252,13 +301,18 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
         let query_parser = PlanParser::create(context.clone());
         let (plan, hints) = query_parser.build_with_hint_from_sql(query);
 
+        let query_result = Self::exec_query(plan, &context).await;
+        if let Err(cause) = &query_result {
+            context.attach_query_error(cause);
+        }
+
         match hints
             .iter()
             .find(|v| v.error_code.is_some())
             .and_then(|x| x.error_code)
         {
-            None => Self::exec_query(plan, &context).await,
-            Some(hint_error_code) => match Self::exec_query(plan, &context).await {
+            None => query_result,
+            Some(hint_error_code) => match query_result {
                 Ok(_) => Err(ErrorCode::UnexpectedError(format!(
                     "Expected server error code: {} but got: Ok.",
                     hint_error_code
@@ -284,29 +338,36 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
     ) -> Result<(Vec<DataBlock>, String)> {
         let instant = Instant::now();
 
-        let interpreter = InterpreterFactory::get(context.clone(), plan?)?;
-        let data_stream = interpreter.execute().await?;
+        let plan = plan?;
+        context.wait_for_admission().await?;
+        let interpreter = InterpreterFactory::get(context.clone(), plan)?;
+        let data_stream = context.with_cpu_time(interpreter.execute()).await?;
+        let data_stream = context.try_create_result_quota(data_stream)?;
         histogram!(
             super::mysql_metrics::METRIC_INTERPRETER_USEDTIME,
             instant.elapsed()
         );
 
         let collector = data_stream.collect::<Result<Vec<DataBlock>>>();
-        let query_result = collector.await;
+        let query_result = context.with_cpu_time(collector).await;
         query_result.map(|data| (data, Self::extra_info(context, instant)))
     }
 
     fn extra_info(context: &DatabendQueryContextRef, instant: Instant) -> String {
         let progress = context.get_progress_value();
         let seconds = instant.elapsed().as_nanos() as f64 / 1e9f64;
-        format!(
+        let mut info = format!(
             "Read {} rows, {} in {:.3} sec., {} rows/sec., {}/sec.",
             progress.read_rows,
             convert_byte_size(progress.read_bytes as f64),
             seconds,
             convert_number_size((progress.read_rows as f64) / (seconds as f64)),
             convert_byte_size((progress.read_bytes as f64) / (seconds as f64)),
-        )
+        );
+        for warning in context.get_query_warnings() {
+            info.push_str(&format!(" Warning: {}.", warning));
+        }
+        info
     }
 
     fn do_init(&mut self, database_name: &str) -> Result<()> {
@@ -348,6 +409,8 @@ impl<W: std::io::Write> InteractiveWorker<W> {
             session: session.clone(),
             base: InteractiveWorkerBase::<W> {
                 session,
+                statement_id: 0,
+                statements: HashMap::new(),
                 generic_hold: PhantomData::default(),
             },
             salt: scramble,
@@ -0,0 +1,155 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use msql_srv::Column;
+use msql_srv::ColumnFlags;
+use msql_srv::ColumnType;
+use msql_srv::ParamParser;
+use msql_srv::Value;
+use msql_srv::ValueInner;
+
+use crate::sql::SQLCommon;
+
+/// A statement prepared by COM_STMT_PREPARE.
+///
+/// Databend has no server side plan cache, so the statement only keeps the query split
+/// at its `?` placeholders. COM_STMT_EXECUTE renders the bound parameters as SQL literals
+/// and runs the resulting query like COM_QUERY does.
+pub struct PreparedStatement {
+    segments: Vec<String>,
+}
+
+impl PreparedStatement {
+    pub fn create(query: &str) -> PreparedStatement {
+        PreparedStatement {
+            segments: split_placeholders(query),
+        }
+    }
+
+    pub fn params_count(&self) -> usize {
+        self.segments.len() - 1
+    }
+
+    /// The parameter definitions sent back in the COM_STMT_PREPARE response.
+    /// Parameter types are unknown until execution, so all of them are declared as strings.
+    pub fn params(&self) -> Vec<Column> {
+        (0..self.params_count())
+            .map(|index| Column {
+                table: "".to_string(),
+                column: format!("?{}", index),
+                coltype: ColumnType::MYSQL_TYPE_VAR_STRING,
+                colflags: ColumnFlags::empty(),
+            })
+            .collect()
+    }
+
+    pub fn bind(&self, params: ParamParser<'_>) -> Result<String> {
+        let mut values = Vec::with_capacity(self.params_count());
+        for param in params {
+            values.push(to_sql_literal(param.value)?);
+        }
+
+        if values.len() != self.params_count() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Prepared statement expects {} parameters, but got {}",
+                self.params_count(),
+                values.len()
+            )));
+        }
+
+        let mut query = self.segments[0].clone();
+        for (value, segment) in values.iter().zip(self.segments[1..].iter()) {
+            query.push_str(value);
+            query.push_str(segment);
+        }
+        Ok(query)
+    }
+}
+
+/// Split the query at the `?` placeholders outside of quotes and comments. The quotes are
+/// escaped by doubling them, which needs no special case, as the tokenizer of the query does.
+fn split_placeholders(query: &str) -> Vec<String> {
+    let mut segments = vec![];
+    let mut segment = String::new();
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '?' => {
+                segments.push(std::mem::take(&mut segment));
+            }
+            '\'' | '"' | '`' => {
+                segment.push(c);
+                for n in chars.by_ref() {
+                    segment.push(n);
+                    if n == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                segment.push(c);
+                for n in chars.by_ref() {
+                    segment.push(n);
+                    if n == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                segment.push(c);
+                segment.extend(chars.next());
+                let mut last = ' ';
+                for n in chars.by_ref() {
+                    segment.push(n);
+                    if last == '*' && n == '/' {
+                        break;
+                    }
+                    last = n;
+                }
+            }
+            _ => segment.push(c),
+        }
+    }
+    segments.push(segment);
+    segments
+}
+
+fn to_sql_literal(value: Value<'_>) -> Result<String> {
+    match value.into_inner() {
+        ValueInner::NULL => Ok("NULL".to_string()),
+        ValueInner::Int(v) => Ok(v.to_string()),
+        ValueInner::UInt(v) => Ok(v.to_string()),
+        ValueInner::Double(v) => Ok(v.to_string()),
+        ValueInner::Bytes(v) => Ok(SQLCommon::quote_string_literal(&String::from_utf8_lossy(v))),
+        ValueInner::Date(_) => {
+            let date: NaiveDate = value.into();
+            Ok(SQLCommon::quote_string_literal(
+                &date.format("%Y-%m-%d").to_string(),
+            ))
+        }
+        ValueInner::Datetime(_) => {
+            let date_time: NaiveDateTime = value.into();
+            Ok(SQLCommon::quote_string_literal(
+                &date_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            ))
+        }
+        ValueInner::Time(_) => Err(ErrorCode::BadArguments(
+            "Unsupported TIME parameter, Databend has no TIME type",
+        )),
+    }
+}
@@ -60,24 +60,33 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
             return Ok(());
         }
 
-        fn convert_field_type(field: &DataField) -> Result<ColumnType> {
+        fn convert_field_type(
+            field: &DataField,
+            exceeds_i64: bool,
+        ) -> Result<(ColumnType, ColumnFlags)> {
+            let signed = ColumnFlags::empty();
+            let unsigned = ColumnFlags::UNSIGNED_FLAG;
             match field.data_type() {
-                DataType::Int8 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::Int16 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::Int32 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::Int64 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::UInt8 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::UInt16 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::UInt32 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::UInt64 => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::Float32 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
-                DataType::Float64 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
-                DataType::String => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
-                DataType::Boolean => Ok(ColumnType::MYSQL_TYPE_SHORT),
-                DataType::Date16 | DataType::Date32 => Ok(ColumnType::MYSQL_TYPE_DATE),
-                DataType::DateTime32(_) => Ok(ColumnType::MYSQL_TYPE_DATETIME),
-                DataType::Null => Ok(ColumnType::MYSQL_TYPE_NULL),
-                DataType::Interval(_) => Ok(ColumnType::MYSQL_TYPE_LONG),
+                DataType::Int8 => Ok((ColumnType::MYSQL_TYPE_TINY, signed)),
+                DataType::Int16 => Ok((ColumnType::MYSQL_TYPE_SHORT, signed)),
+                DataType::Int32 => Ok((ColumnType::MYSQL_TYPE_LONG, signed)),
+                DataType::Int64 => Ok((ColumnType::MYSQL_TYPE_LONGLONG, signed)),
+                DataType::UInt8 => Ok((ColumnType::MYSQL_TYPE_TINY, unsigned)),
+                DataType::UInt16 => Ok((ColumnType::MYSQL_TYPE_SHORT, unsigned)),
+                DataType::UInt32 => Ok((ColumnType::MYSQL_TYPE_LONG, unsigned)),
+                // Some clients ignore the unsigned flag and read BIGINT as i64,
+                // values they cannot hold are sent as DECIMAL instead.
+                DataType::UInt64 if exceeds_i64 => Ok((ColumnType::MYSQL_TYPE_NEWDECIMAL, signed)),
+                DataType::UInt64 => Ok((ColumnType::MYSQL_TYPE_LONGLONG, unsigned)),
+                DataType::Float32 => Ok((ColumnType::MYSQL_TYPE_FLOAT, signed)),
+                DataType::Float64 => Ok((ColumnType::MYSQL_TYPE_DOUBLE, signed)),
+                DataType::String => Ok((ColumnType::MYSQL_TYPE_VAR_STRING, signed)),
+                DataType::Boolean => Ok((ColumnType::MYSQL_TYPE_TINY, signed)),
+                DataType::Date16 | DataType::Date32 => Ok((ColumnType::MYSQL_TYPE_DATE, signed)),
+                DataType::DateTime32(_) => Ok((ColumnType::MYSQL_TYPE_DATETIME, signed)),
+                DataType::Null => Ok((ColumnType::MYSQL_TYPE_NULL, signed)),
+                DataType::Interval(_) => Ok((ColumnType::MYSQL_TYPE_LONGLONG, signed)),
+                DataType::Struct(_) => Ok((ColumnType::MYSQL_TYPE_VAR_STRING, signed)),
                 _ => Err(ErrorCode::UnImplement(format!(
                     "Unsupported column type:{:?}",
                     field.data_type()
@@ -85,22 +94,47 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
             }
         }
 
-        fn make_column_from_field(field: &DataField) -> Result<Column> {
-            convert_field_type(field).map(|column_type| Column {
+        fn exceeds_i64(blocks: &[DataBlock], col_index: usize) -> Result<bool> {
+            for block in blocks {
+                if let DataValue::UInt64(Some(max)) = block.column(col_index).to_array()?.max()? {
+                    if max > i64::MAX as u64 {
+                        return Ok(true);
+                    }
+                }
+            }
+            Ok(false)
+        }
+
+        fn make_column_from_field(
+            blocks: &[DataBlock],
+            col_index: usize,
+            field: &DataField,
+        ) -> Result<Column> {
+            let exceeds_i64 = match field.data_type() {
+                DataType::UInt64 => exceeds_i64(blocks, col_index)?,
+                _ => false,
+            };
+
+            convert_field_type(field, exceeds_i64).map(|(column_type, column_flags)| Column {
                 table: "".to_string(),
                 column: field.name().to_string(),
                 coltype: column_type,
-                colflags: ColumnFlags::empty(),
+                colflags: column_flags,
             })
         }
 
-        fn convert_schema(schema: &DataSchemaRef) -> Result<Vec<Column>> {
-            schema.fields().iter().map(make_column_from_field).collect()
+        fn convert_schema(schema: &DataSchemaRef, blocks: &[DataBlock]) -> Result<Vec<Column>> {
+            schema
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(col_index, field)| make_column_from_field(blocks, col_index, field))
+                .collect()
         }
 
         let block = blocks[0].clone();
         let utc: Tz = "UTC".parse().unwrap();
-        match convert_schema(block.schema()) {
+        match convert_schema(block.schema(), &blocks) {
             Err(error) => Self::err(&error, dataset_writer),
             Ok(columns) => {
                 let columns_size = block.num_columns();
@@ -141,6 +175,12 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                                 (DataType::UInt32, DataValue::UInt32(Some(v))) => {
                                     row_writer.write_col(v)?
                                 }
+                                (DataType::UInt64, DataValue::UInt64(Some(v)))
+                                    if columns[col_index].coltype
+                                        == ColumnType::MYSQL_TYPE_NEWDECIMAL =>
+                                {
+                                    row_writer.write_col(v.to_string())?
+                                }
                                 (DataType::UInt64, DataValue::UInt64(Some(v))) => {
                                     row_writer.write_col(v)?
                                 }
@@ -154,17 +194,30 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                                     row_writer.write_col(v.to_date(&utc).naive_local())?
                                 }
                                 (DataType::Date32, DataValue::UInt32(Some(v))) => {
-                                    row_writer.write_col(v.to_date(&utc).naive_local())?
+                                    // Days before 1970 are stored as negative i32.
+                                    let days = v as i32;
+                                    row_writer.write_col(days.to_date(&utc).naive_local())?
                                 }
                                 (DataType::DateTime32(tz), DataValue::UInt32(Some(v))) => {
                                     let tz = tz.clone();
                                     let tz = tz.unwrap_or_else(|| "UTC".to_string());
-                                    let tz: Tz = tz.parse().unwrap();
+                                    let tz: Tz = tz.parse().map_err(|_| {
+                                        ErrorCode::BadDataValueType(format!(
+                                            "Unsupported time zone:{}",
+                                            tz
+                                        ))
+                                    })?;
                                     row_writer.write_col(v.to_date_time(&tz).naive_local())?
                                 }
                                 (DataType::String, DataValue::String(Some(v))) => {
                                     row_writer.write_col(v)?
                                 }
+                                (DataType::Interval(_), DataValue::Int64(Some(v))) => {
+                                    row_writer.write_col(v)?
+                                }
+                                (DataType::Struct(_), v @ DataValue::Struct(_)) => {
+                                    row_writer.write_col(v.to_string())?
+                                }
                                 (_, v) => {
                                     return Err(ErrorCode::BadDataValueType(format!(
                                         "Unsupported column type:{:?}",
@@ -0,0 +1,122 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_base::tokio::sync::OwnedSemaphorePermit;
+use common_base::tokio::sync::Semaphore;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_infallible::RwLock;
+
+/// Caps the queries of the clients running on a node, in total and by user.
+///
+/// The queries over the caps are queued, first come first served, until a running query
+/// finishes. A query still queued after `queue_timeout` fails.
+///
+/// The caps may be changed while the queries run, the queries already running or queued keep
+/// counting against the caps they were admitted by.
+pub struct AdmissionController {
+    /// None if the queries are not capped.
+    running: RwLock<Option<Arc<Semaphore>>>,
+    /// 0 means unlimited.
+    max_running_per_user: AtomicUsize,
+    /// The semaphores of the users are kept until the caps change, they are a few bytes each.
+    running_by_user: Mutex<HashMap<String, Arc<Semaphore>>>,
+    queue_timeout: Duration,
+}
+
+/// The query is running until the permit is dropped.
+pub struct AdmissionPermit {
+    _user_permit: Option<OwnedSemaphorePermit>,
+    _running_permit: Option<OwnedSemaphorePermit>,
+}
+
+impl AdmissionController {
+    /// `max_running` and `max_running_per_user` of 0 mean unlimited.
+    pub fn create(
+        max_running: usize,
+        max_running_per_user: usize,
+        queue_timeout: Duration,
+    ) -> Arc<AdmissionController> {
+        Arc::new(AdmissionController {
+            running: RwLock::new(Self::running_semaphore(max_running)),
+            max_running_per_user: AtomicUsize::new(max_running_per_user),
+            running_by_user: Mutex::new(HashMap::new()),
+            queue_timeout,
+        })
+    }
+
+    /// Changes the caps for the queries admitted from now on, 0 means unlimited.
+    pub fn set_caps(&self, max_running: usize, max_running_per_user: usize) {
+        *self.running.write() = Self::running_semaphore(max_running);
+        self.max_running_per_user
+            .store(max_running_per_user, Ordering::Relaxed);
+        self.running_by_user.lock().clear();
+    }
+
+    fn running_semaphore(max_running: usize) -> Option<Arc<Semaphore>> {
+        match max_running {
+            0 => None,
+            n => Some(Arc::new(Semaphore::new(n))),
+        }
+    }
+
+    /// Waits until the query of the user may run.
+    pub async fn admit(&self, user: &str) -> Result<AdmissionPermit> {
+        let user_semaphore = match self.max_running_per_user.load(Ordering::Relaxed) {
+            0 => None,
+            n => {
+                let mut running_by_user = self.running_by_user.lock();
+                let semaphore = running_by_user
+                    .entry(user.to_string())
+                    .or_insert_with(|| Arc::new(Semaphore::new(n)));
+                Some(semaphore.clone())
+            }
+        };
+
+        let running_semaphore = self.running.read().clone();
+
+        // A query waits for the cap of its user first, so that it doesn't hold a slot of the
+        // node while the other queries of its user are running. The semaphores are never closed.
+        let admitted = tokio::time::timeout(self.queue_timeout, async {
+            let user_permit = match user_semaphore {
+                None => None,
+                Some(semaphore) => semaphore.acquire_owned().await.ok(),
+            };
+            let running_permit = match running_semaphore {
+                None => None,
+                Some(semaphore) => semaphore.acquire_owned().await.ok(),
+            };
+            AdmissionPermit {
+                _user_permit: user_permit,
+                _running_permit: running_permit,
+            }
+        })
+        .await;
+
+        admitted.map_err(|_| {
+            ErrorCode::QueryQueueTimeout(format!(
+                "The query is still queued after {} seconds, too many queries are running",
+                self.queue_timeout.as_secs()
+            ))
+        })
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sessions::AdmissionController;
+
+#[tokio::test]
+async fn test_admission_controller() -> Result<()> {
+    let controller = AdmissionController::create(2, 1, Duration::from_millis(100));
+
+    let root_1 = controller.admit("root").await?;
+    let alice_1 = controller.admit("alice").await?;
+
+    // Over the cap of the user.
+    let r = controller.admit("root").await;
+    assert_eq!(
+        r.err().map(|e| e.code()),
+        Some(ErrorCode::QueryQueueTimeout("").code())
+    );
+
+    // Over the cap of the node.
+    drop(root_1);
+    let _bob_1 = controller.admit("bob").await?;
+    let r = controller.admit("root").await;
+    assert!(r.is_err());
+
+    // The queued query runs once a running query finishes.
+    let queued = controller.admit("root");
+    drop(alice_1);
+    let _root_2 = queued.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_admission_controller_set_caps() -> Result<()> {
+    let controller = AdmissionController::create(1, 0, Duration::from_millis(100));
+    let root_1 = controller.admit("root").await?;
+    assert!(controller.admit("root").await.is_err());
+
+    // Raised, the running query is counted by the former cap.
+    controller.set_caps(2, 0);
+    let root_2 = controller.admit("root").await?;
+    let _root_3 = controller.admit("root").await?;
+    assert!(controller.admit("root").await.is_err());
+
+    // Capped by user.
+    drop(root_1);
+    drop(root_2);
+    controller.set_caps(0, 1);
+    let _alice_1 = controller.admit("alice").await?;
+    assert!(controller.admit("alice").await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_admission_controller_unlimited() -> Result<()> {
+    let controller = AdmissionController::create(0, 0, Duration::from_secs(0));
+    let _permits = vec![
+        controller.admit("root").await?,
+        controller.admit("root").await?,
+        controller.admit("root").await?,
+    ];
+    Ok(())
+}
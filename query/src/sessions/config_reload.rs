@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::configs;
+use crate::configs::Config;
+use crate::sessions::SessionManager;
+
+/// Config keys which can be changed without restarting the server.
+/// Everything else is reported as `requires_restart` and left untouched.
+const RELOADABLE_KEYS: &[&str] = &[
+    "log.log_level",
+    "query.max_active_sessions",
+    "query.idle_session_timeout_secs",
+    "query.max_server_memory_usage_mb",
+    "query.max_running_queries",
+    "query.max_running_queries_per_user",
+    "query.result_cache_size_mb",
+    "storage.column_cache_size_mb",
+    "storage.s3.access_key_id",
+    "storage.s3.secret_access_key",
+    "storage.azure_blob.account_key",
+    "storage.azure_blob.sas_token",
+    "storage.hdfs.delegation_token",
+];
+
+/// The outcome of a config reload, only key names are reported, never values.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+pub struct ConfigReloadReport {
+    pub applied: Vec<String>,
+    pub requires_restart: Vec<String>,
+}
+
+impl SessionManager {
+    /// Reload the config from the file the server was started with.
+    pub fn reload_config_from_file(self: &Arc<Self>) -> Result<ConfigReloadReport> {
+        let config_file = self.get_conf().config_file;
+        if config_file.is_empty() {
+            return Err(ErrorCode::BadArguments(
+                "Cannot reload config, the server was not started with a config file",
+            ));
+        }
+
+        let new_conf = Config::load_from_env(&Config::load_from_toml(&config_file)?)?;
+        self.reload_config(new_conf)
+    }
+
+    /// Apply the reloadable settings of `new_conf`.
+    /// Queries started after the reload see the new config.
+    pub fn reload_config(self: &Arc<Self>, new_conf: Config) -> Result<ConfigReloadReport> {
+        let mut conf = self.conf.write();
+        let changed = changed_keys(&conf, &new_conf)?;
+
+        let mut report = ConfigReloadReport::default();
+        for key in changed {
+            match RELOADABLE_KEYS.contains(&key.as_str()) {
+                true => report.applied.push(key),
+                false => report.requires_restart.push(key),
+            }
+        }
+
+        // Validate before applying anything, a reload is all or nothing.
+        let log_level = log::LevelFilter::from_str(&new_conf.log.log_level).map_err(|_| {
+            ErrorCode::BadArguments(format!(
+                "Cannot reload config, invalid log level: {}",
+                new_conf.log.log_level
+            ))
+        })?;
+
+        log::set_max_level(log_level);
+        self.max_sessions.store(
+            new_conf.query.max_active_sessions as usize,
+            Ordering::Relaxed,
+        );
+        self.column_cache
+            .set_capacity(new_conf.storage.column_cache_size_mb * 1024 * 1024);
+        self.query_cache
+            .set_capacity(new_conf.query.result_cache_size_mb * 1024 * 1024);
+        self.memory_tracker
+            .set_limit(new_conf.query.max_server_memory_usage_mb as usize * 1024 * 1024);
+        // New caps count the queries from zero, so they are only set when they change.
+        if new_conf.query.max_running_queries != conf.query.max_running_queries
+            || new_conf.query.max_running_queries_per_user
+                != conf.query.max_running_queries_per_user
+        {
+            self.admission.set_caps(
+                new_conf.query.max_running_queries as usize,
+                new_conf.query.max_running_queries_per_user as usize,
+            );
+        }
+
+        conf.log.log_level = new_conf.log.log_level;
+        conf.query.max_active_sessions = new_conf.query.max_active_sessions;
+        conf.query.idle_session_timeout_secs = new_conf.query.idle_session_timeout_secs;
+        conf.storage.column_cache_size_mb = new_conf.storage.column_cache_size_mb;
+        conf.query.result_cache_size_mb = new_conf.query.result_cache_size_mb;
+        conf.query.max_server_memory_usage_mb = new_conf.query.max_server_memory_usage_mb;
+        conf.query.max_running_queries = new_conf.query.max_running_queries;
+        conf.query.max_running_queries_per_user = new_conf.query.max_running_queries_per_user;
+        conf.storage.s3.access_key_id = new_conf.storage.s3.access_key_id;
+        conf.storage.s3.secret_access_key = new_conf.storage.s3.secret_access_key;
+        conf.storage.azure_blob.account_key = new_conf.storage.azure_blob.account_key;
+        conf.storage.azure_blob.sas_token = new_conf.storage.azure_blob.sas_token;
+        conf.storage.hdfs.delegation_token = new_conf.storage.hdfs.delegation_token;
+        Ok(report)
+    }
+}
+
+fn changed_keys(old: &Config, new: &Config) -> Result<Vec<String>> {
+    let old = flatten_config(old)?;
+    let new = flatten_config(new)?;
+
+    let mut keys = vec![];
+    for (key, value) in &new {
+        if old.get(key) != Some(value) {
+            keys.push(key.clone());
+        }
+    }
+    Ok(keys)
+}
+
+fn flatten_config(conf: &Config) -> Result<BTreeMap<String, toml::Value>> {
+    let mut flattened = configs::flatten_config(conf)?;
+    // The config file path is where the config comes from, not a setting.
+    flattened.remove("config_file");
+    Ok(flattened)
+}
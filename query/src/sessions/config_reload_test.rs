@@ -0,0 +1,170 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reload_config() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().max_sessions(8).build()?;
+
+    let mut new_conf = sessions.get_conf();
+    new_conf.query.max_active_sessions = 1;
+    new_conf.query.mysql_handler_port = 3308;
+    new_conf.storage.s3.secret_access_key = "new_secret".to_string();
+
+    let report = sessions.reload_config(new_conf)?;
+    assert_eq!(report.applied, vec![
+        "query.max_active_sessions".to_string(),
+        "storage.s3.secret_access_key".to_string(),
+    ]);
+    assert_eq!(report.requires_restart, vec![
+        "query.mysql_handler_port".to_string()
+    ]);
+
+    let conf = sessions.get_conf();
+    assert_eq!(conf.query.max_active_sessions, 1);
+    assert_eq!(conf.storage.s3.secret_access_key, "new_secret");
+    assert_ne!(conf.query.mysql_handler_port, 3308);
+
+    // The new session limit takes effect at once.
+    let _session = sessions.create_session("TestSession")?;
+    let second = sessions.create_session("TestSession");
+    assert!(second.is_err());
+    assert_eq!(
+        second.err().unwrap().code(),
+        ErrorCode::TooManyUserConnections("").code()
+    );
+
+    // Nothing changed, nothing reported.
+    let report = sessions.reload_config(sessions.get_conf())?;
+    assert!(report.applied.is_empty());
+    assert!(report.requires_restart.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reload_config_column_cache_size() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+
+    let mut new_conf = sessions.get_conf();
+    new_conf.storage.column_cache_size_mb = 1;
+    let report = sessions.reload_config(new_conf)?;
+    assert_eq!(report.applied, vec![
+        "storage.column_cache_size_mb".to_string()
+    ]);
+
+    // The cache is resized at once.
+    assert_eq!(sessions.get_column_cache().capacity(), 1024 * 1024);
+    assert_eq!(sessions.get_conf().storage.column_cache_size_mb, 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reload_config_result_cache_size() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+
+    let mut new_conf = sessions.get_conf();
+    new_conf.query.result_cache_size_mb = 0;
+    let report = sessions.reload_config(new_conf)?;
+    assert_eq!(report.applied, vec![
+        "query.result_cache_size_mb".to_string()
+    ]);
+
+    // The cache is disabled at once.
+    assert_eq!(sessions.get_query_cache().capacity(), 0);
+    assert_eq!(sessions.get_conf().query.result_cache_size_mb, 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reload_config_max_server_memory_usage() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let mut tracked = sessions.get_memory_tracker().tracked();
+    tracked.set(2 * 1024 * 1024);
+    tracked.check()?;
+
+    let mut new_conf = sessions.get_conf();
+    new_conf.query.max_server_memory_usage_mb = 1;
+    let report = sessions.reload_config(new_conf)?;
+    assert_eq!(report.applied, vec![
+        "query.max_server_memory_usage_mb".to_string()
+    ]);
+
+    // The running queries are limited at once.
+    assert_eq!(
+        tracked.check().err().unwrap().code(),
+        ErrorCode::MemoryLimitExceeded("").code()
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reload_config_max_running_queries() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+
+    let mut new_conf = sessions.get_conf();
+    new_conf.query.max_running_queries = 1;
+    let report = sessions.reload_config(new_conf)?;
+    assert_eq!(
+        report.applied,
+        vec!["query.max_running_queries".to_string()]
+    );
+
+    // The queries are capped at once, the second one is queued.
+    let admission = sessions.get_admission_controller();
+    let _permit = admission.admit("root").await?;
+    let queued = tokio::time::timeout(Duration::from_millis(100), admission.admit("root")).await;
+    assert!(queued.is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reload_config_with_invalid_log_level() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().max_sessions(8).build()?;
+
+    let mut new_conf = sessions.get_conf();
+    new_conf.log.log_level = "LOUD".to_string();
+    new_conf.query.max_active_sessions = 1;
+
+    let result = sessions.reload_config(new_conf);
+    assert!(result.is_err());
+    assert_eq!(
+        result.err().unwrap().message(),
+        "Cannot reload config, invalid log level: LOUD"
+    );
+
+    // A failed reload applies nothing.
+    assert_eq!(sessions.get_conf().query.max_active_sessions, 8);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reload_config_without_config_file() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let result = sessions.reload_config_from_file();
+    assert!(result.is_err());
+    assert_eq!(
+        result.err().unwrap().code(),
+        ErrorCode::BadArguments("").code()
+    );
+
+    Ok(())
+}
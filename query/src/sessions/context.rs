@@ -12,21 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::future::Future;
 use std::sync::atomic::Ordering;
 use std::sync::atomic::Ordering::Acquire;
 use std::sync::Arc;
+use std::time::Duration;
 
+use chrono::Utc;
 use common_base::tokio::task::JoinHandle;
+use common_base::CpuTimeFuture;
+use common_base::FaultInjector;
+use common_base::Progress;
 use common_base::ProgressCallback;
 use common_base::ProgressValues;
 use common_base::Runtime;
 use common_base::TrySpawn;
 use common_context::TableIOContext;
+use common_dal::DataAccessor;
+use common_dal::DataAccessorBuilder;
+use common_dal::IOPriority;
+use common_dal::IOScheduler;
+use common_dal::KeyProvider;
+use common_dal::Throttle;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
+use common_management::UserQuota;
 use common_meta_types::MetaId;
 use common_meta_types::MetaVersion;
 use common_meta_types::NodeInfo;
@@ -35,20 +48,38 @@ use common_planners::Partitions;
 use common_planners::PlanNode;
 use common_planners::Statistics;
 use common_streams::AbortStream;
+use common_streams::QuotaLimit;
+use common_streams::QuotaStream;
 use common_streams::SendableDataBlockStream;
+use common_tracing::tracing::Instrument;
 
 use crate::catalogs::impls::DatabaseCatalog;
+use crate::catalogs::impls::TemporaryTables;
 use crate::catalogs::Catalog;
+use crate::catalogs::Database;
 use crate::catalogs::Table;
 use crate::catalogs::TableFunction;
+use crate::catalogs::DB_OPT_KEY_TENANT;
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
+use crate::configs::StorageConfig;
+use crate::datasources::common::check_disk_data_path;
 use crate::datasources::common::ContextDalBuilder;
+use crate::datasources::common::COLD_STORAGE_OPT_KEY_PREFIX;
+use crate::datasources::common::STORAGE_OPT_KEY_CONNECTION;
+use crate::datasources::common::STORAGE_OPT_KEY_DISK_DATA_PATH;
 use crate::datasources::table_func_engine::TableArgs;
+use crate::functions::SessionFunctions;
 use crate::sessions::context_shared::DatabendQueryContextShared;
+use crate::sessions::MemoryTracker;
+use crate::sessions::QueryLogEntry;
+use crate::sessions::QueryLogStatus;
 use crate::sessions::SessionManagerRef;
 use crate::sessions::Settings;
 
+/// The directory of the storage of the server where the files of a tenant are loaded from.
+const TENANT_LOCATION_PREFIX: &str = "_tenants";
+
 pub struct DatabendQueryContext {
     statistics: Arc<RwLock<Statistics>>,
     partition_queue: Arc<RwLock<VecDeque<Part>>>,
@@ -146,6 +177,20 @@ impl DatabendQueryContext {
         self.shared.get_catalog()
     }
 
+    /// Get a database of the tenant of the session, the ones of the other tenants are unknown.
+    pub fn get_database(&self, database: &str) -> Result<Arc<dyn Database>> {
+        self.shared.get_database(database)
+    }
+
+    /// Get the databases of the tenant of the session and the shared ones.
+    pub fn get_databases(&self) -> Result<Vec<Arc<dyn Database>>> {
+        let databases = self.get_catalog().get_databases()?;
+        Ok(databases
+            .into_iter()
+            .filter(|database| self.shared.is_tenant_database(database.as_ref()))
+            .collect())
+    }
+
     pub fn get_table(&self, database: &str, table: &str) -> Result<Arc<dyn Table>> {
         self.shared.get_table(database, table)
     }
@@ -155,7 +200,118 @@ impl DatabendQueryContext {
         table_id: MetaId,
         table_ver: Option<MetaVersion>,
     ) -> Result<Arc<dyn Table>> {
-        self.get_catalog().get_table_by_id(table_id, table_ver)
+        match self.get_temporary_tables().get_table_by_id(table_id) {
+            Some(temporary_table) => Ok(temporary_table),
+            None => self.get_catalog().get_table_by_id(table_id, table_ver),
+        }
+    }
+
+    /// The tenant the session logged in to.
+    pub fn get_tenant(&self) -> String {
+        self.shared.session.get_tenant()
+    }
+
+    /// Whether the session is of the tenant of `--tenant`, which owns the storage of the server.
+    pub fn is_default_tenant(&self) -> bool {
+        self.get_tenant() == self.shared.conf.query.tenant
+    }
+
+    /// The disk of the server is only available to the default tenant, the others keep the data
+    /// of their databases and tables in the storage of the server or in their own buckets.
+    /// The default tenant keeps the data on the disk under the data path of the server.
+    /// The connections are only available to the tenant which created them.
+    pub fn check_tenant_storage_options(&self, options: &HashMap<String, String>) -> Result<()> {
+        let connections = self
+            .shared
+            .session
+            .get_sessions_manager()
+            .get_connection_manager();
+        for (key, value) in options {
+            let key = key.strip_prefix(COLD_STORAGE_OPT_KEY_PREFIX).unwrap_or(key);
+            if key == STORAGE_OPT_KEY_CONNECTION {
+                connections.get_connection_options(&self.get_tenant(), value)?;
+            }
+            if key == STORAGE_OPT_KEY_DISK_DATA_PATH && self.is_default_tenant() {
+                check_disk_data_path(&self.shared.conf.storage.disk.data_path, value)?;
+            }
+        }
+
+        if self.is_default_tenant() {
+            return Ok(());
+        }
+
+        for key in options.keys() {
+            let key = key.strip_prefix(COLD_STORAGE_OPT_KEY_PREFIX).unwrap_or(key);
+            if key == STORAGE_OPT_KEY_DISK_DATA_PATH {
+                return Err(ErrorCode::BadOption(format!(
+                    "The storage option {} is not available to tenant {}",
+                    key,
+                    self.get_tenant()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The storage options of a table of the database, with the connections they refer to
+    /// resolved. The connections are of the tenant owning the database, not of the session,
+    /// e.g. the background jobs reach the tables of all the tenants. The options of a purged
+    /// table keep the tenant of its dropped database.
+    pub fn resolve_storage_connections(
+        &self,
+        database: &str,
+        options: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        let refers = options.keys().any(|key| {
+            key.strip_prefix(COLD_STORAGE_OPT_KEY_PREFIX).unwrap_or(key)
+                == STORAGE_OPT_KEY_CONNECTION
+        });
+        if !refers {
+            return Ok(options.clone());
+        }
+
+        let tenant = match options.get(DB_OPT_KEY_TENANT) {
+            Some(tenant) => tenant.clone(),
+            None => match self.get_catalog().get_database(database)?.tenant() {
+                Some(tenant) => tenant.to_string(),
+                None => self.shared.conf.query.tenant.clone(),
+            },
+        };
+        self.shared
+            .session
+            .get_sessions_manager()
+            .get_connection_manager()
+            .resolve_storage_options(&tenant, options)
+    }
+
+    /// The location of the files of the tenant in the storage of the server, the other tenants
+    /// than the default one only reach their own directory.
+    pub fn get_tenant_location(&self, location: &str) -> Result<String> {
+        if self.is_default_tenant() {
+            return Ok(location.to_string());
+        }
+
+        if location.split('/').any(|component| component == "..") {
+            return Err(ErrorCode::BadArguments(format!(
+                "Location {} is out of the directory of tenant {}",
+                location,
+                self.get_tenant()
+            )));
+        }
+        Ok(format!(
+            "{}/{}/{}",
+            TENANT_LOCATION_PREFIX,
+            self.get_tenant(),
+            location.trim_start_matches('/')
+        ))
+    }
+
+    pub fn get_temporary_tables(&self) -> Arc<TemporaryTables> {
+        self.shared.get_temporary_tables()
+    }
+
+    pub fn get_session_functions(&self) -> Arc<SessionFunctions> {
+        self.shared.get_session_functions()
     }
 
     pub fn get_table_function(
@@ -177,12 +333,77 @@ impl DatabendQueryContext {
         Ok(abort_stream)
     }
 
+    /// The limits of the query, the tighter one of the user quota and the setting,
+    /// so that the users can lower their quota with SET but never raise it.
+    pub fn get_quota(&self) -> Result<UserQuota> {
+        let tighter = |quota: u64, setting: u64| match (quota, setting) {
+            (0, setting) => setting,
+            (quota, 0) => quota,
+            (quota, setting) => quota.min(setting),
+        };
+
+        let quota = self.shared.session.get_user_quota();
+        let settings = self.get_settings();
+        Ok(UserQuota {
+            max_rows_returned: tighter(quota.max_rows_returned, settings.get_max_rows_returned()?),
+            max_bytes_scanned: tighter(quota.max_bytes_scanned, settings.get_max_bytes_scanned()?),
+            max_result_bytes: tighter(quota.max_result_bytes, settings.get_max_result_bytes()?),
+        })
+    }
+
+    /// Counts what the scans of the query read, for the query log, and fails the query
+    /// once they read more than `max_bytes_scanned`.
+    pub fn try_create_scan_quota(
+        &self,
+        input: SendableDataBlockStream,
+    ) -> Result<SendableDataBlockStream> {
+        let limits = match self.get_quota()?.max_bytes_scanned {
+            0 => vec![],
+            max => vec![QuotaLimit::Bytes("max_bytes_scanned", max as usize)],
+        };
+
+        let progress = self.shared.scan_progress.clone();
+        Ok(Box::pin(QuotaStream::try_create(input, progress, limits)?))
+    }
+
+    /// Fails the query once the result returned to the client goes over
+    /// `max_rows_returned` or `max_result_bytes`.
+    pub fn try_create_result_quota(
+        &self,
+        input: SendableDataBlockStream,
+    ) -> Result<SendableDataBlockStream> {
+        let quota = self.get_quota()?;
+        let mut limits = vec![];
+        if quota.max_rows_returned > 0 {
+            let max = quota.max_rows_returned as usize;
+            limits.push(QuotaLimit::Rows("max_rows_returned", max));
+        }
+        if quota.max_result_bytes > 0 {
+            let max = quota.max_result_bytes as usize;
+            limits.push(QuotaLimit::Bytes("max_result_bytes", max));
+        }
+
+        match limits.is_empty() {
+            true => Ok(input),
+            false => {
+                let progress = Arc::new(Progress::create());
+                Ok(Box::pin(QuotaStream::try_create(input, progress, limits)?))
+            }
+        }
+    }
+
+    /// Accounts the CPU time of the future to the query, for the work done outside
+    /// the tasks spawned by the query, e.g. by the handlers consuming the result.
+    pub fn with_cpu_time<F: Future>(&self, future: F) -> CpuTimeFuture<F> {
+        CpuTimeFuture::create(future, self.shared.cpu_time_ns.clone())
+    }
+
     pub fn get_current_database(&self) -> String {
         self.shared.get_current_database()
     }
 
     pub fn set_current_database(&self, new_database_name: String) -> Result<()> {
-        match self.get_catalog().get_database(new_database_name.as_str()) {
+        match self.get_database(new_database_name.as_str()) {
             Ok(_) => self.shared.set_current_database(new_database_name),
             Err(_) => {
                 return Err(ErrorCode::UnknownDatabase(format!(
@@ -207,15 +428,94 @@ impl DatabendQueryContext {
         self.shared.conf.clone()
     }
 
+    /// Build a data accessor of the given storage, for the tables which live in their own storage.
+    pub fn get_storage_data_accessor(
+        &self,
+        storage: StorageConfig,
+    ) -> Result<Arc<dyn DataAccessor>> {
+        ContextDalBuilder::new(storage)
+            .with_fault_injector(self.get_dal_fault_injector())
+            .with_io_scheduler(self.get_io_scheduler(), self.get_io_priority())
+            .with_throttle(self.get_io_throttle())
+            .with_throttle(self.get_dal_throttle())
+            .with_key_provider(self.get_key_provider())
+            .build()
+    }
+
+    /// The scheduler of the storage requests, shared by all the sessions.
+    pub fn get_io_scheduler(&self) -> Arc<IOScheduler> {
+        self.shared
+            .session
+            .get_sessions_manager()
+            .get_io_scheduler()
+    }
+
+    /// The storage throttle of the node, shared by all the sessions.
+    pub fn get_io_throttle(&self) -> Arc<Throttle> {
+        self.shared.session.get_sessions_manager().get_io_throttle()
+    }
+
+    /// The keys of the encrypted storage of the node, None if it is not encrypted.
+    pub fn get_key_provider(&self) -> Option<Arc<dyn KeyProvider>> {
+        self.shared
+            .session
+            .get_sessions_manager()
+            .get_key_provider()
+    }
+
+    /// The storage throttle of the query, by its `max_storage_io_*_per_second` settings.
+    pub fn get_dal_throttle(&self) -> Arc<Throttle> {
+        self.shared.dal_throttle.clone()
+    }
+
+    pub fn get_io_priority(&self) -> IOPriority {
+        self.shared.session.get_io_priority()
+    }
+
+    /// The injector of the storage faults, if enabled by `fault_injection` config.
+    pub fn get_dal_fault_injector(&self) -> Option<Arc<FaultInjector>> {
+        self.shared.dal_fault_injector.clone()
+    }
+
     pub fn get_subquery_name(&self, _query: &PlanNode) -> String {
         let index = self.shared.subquery_index.fetch_add(1, Ordering::Relaxed);
         format!("_subquery_{}", index)
     }
 
+    /// Attaches the text of the query of a client, the query is logged once it starts and
+    /// once it finishes.
     pub fn attach_query_str(&self, query: &str) {
         self.shared.attach_query_str(query);
     }
 
+    /// The query is logged as failed with the error once it finishes.
+    pub fn attach_query_error(&self, error: &ErrorCode) {
+        self.shared.attach_query_error(error);
+    }
+
+    /// The warning is returned to the client with the result of the query.
+    pub fn attach_query_warning(&self, warning: String) {
+        self.shared.attach_query_warning(warning);
+    }
+
+    pub fn get_query_warnings(&self) -> Vec<String> {
+        self.shared.query_warnings.read().clone()
+    }
+
+    /// Queues the query of a client while too many queries are running.
+    pub async fn wait_for_admission(&self) -> Result<()> {
+        self.shared.wait_for_admission().await
+    }
+
+    /// The bytes held by the operators of the query, shared by its subqueries.
+    pub fn get_memory_tracker(&self) -> Arc<MemoryTracker> {
+        self.shared.memory_tracker.clone()
+    }
+
+    pub fn get_query_str(&self) -> Option<String> {
+        self.shared.running_query.read().clone()
+    }
+
     pub fn attach_query_plan(&self, query_plan: &PlanNode) {
         self.shared.attach_query_plan(query_plan);
     }
@@ -240,7 +540,7 @@ impl DatabendQueryContext {
 
         Ok(TableIOContext::new(
             self.get_shared_runtime()?,
-            Arc::new(ContextDalBuilder::new(self.get_config().storage)),
+            self.get_dal_builder(),
             max_threads,
             nodes,
             Some(self.clone()),
@@ -256,22 +556,35 @@ impl DatabendQueryContext {
 
         Ok(TableIOContext::new(
             self.get_shared_runtime()?,
-            Arc::new(ContextDalBuilder::new(self.get_config().storage)),
+            self.get_dal_builder(),
             max_threads,
             nodes,
             Some(self.clone()),
         ))
     }
+
+    fn get_dal_builder(self: &Arc<Self>) -> Arc<ContextDalBuilder> {
+        Arc::new(
+            ContextDalBuilder::new(self.get_config().storage)
+                .with_fault_injector(self.get_dal_fault_injector())
+                .with_io_scheduler(self.get_io_scheduler(), self.get_io_priority())
+                .with_throttle(self.get_io_throttle())
+                .with_throttle(self.get_dal_throttle())
+                .with_key_provider(self.get_key_provider()),
+        )
+    }
 }
 
 impl TrySpawn for DatabendQueryContext {
     /// Spawns a new asynchronous task, returning a tokio::JoinHandle for it.
     /// The task will run in the current context thread_pool not the global.
+    /// It stays in the span of the caller, so the spans of a query are chained end to end.
     fn try_spawn<T>(&self, task: T) -> Result<JoinHandle<T::Output>>
     where
         T: Future + Send + 'static,
         T::Output: Send + 'static,
     {
+        let task = CpuTimeFuture::create(task.in_current_span(), self.shared.cpu_time_ns.clone());
         Ok(self.shared.try_get_runtime()?.spawn(task))
     }
 }
@@ -293,6 +606,7 @@ impl DatabendQueryContextShared {
         if self.ref_count.fetch_sub(1, Ordering::Release) == 1 {
             std::sync::atomic::fence(Acquire);
             log::info!("Destroy DatabendQueryContext");
+            self.append_query_log(self.finish_status());
             self.session.destroy_context_shared();
         }
     }
@@ -300,4 +614,40 @@ impl DatabendQueryContextShared {
     pub(in crate::sessions) fn increment_ref_count(&self) {
         self.ref_count.fetch_add(1, Ordering::Relaxed);
     }
+
+    fn finish_status(&self) -> QueryLogStatus {
+        match self.query_error.read().is_some() {
+            true => QueryLogStatus::Error,
+            false => QueryLogStatus::Finish,
+        }
+    }
+
+    // Only the queries of the clients are logged, not the contexts of the internal jobs.
+    pub(in crate::sessions) fn append_query_log(&self, status: QueryLogStatus) {
+        let query_text = match self.running_query.read().clone() {
+            None => return,
+            Some(query_text) => query_text,
+        };
+
+        let scan = self.scan_progress.get_values();
+        let cpu_time_ns = self.cpu_time_ns.load(Ordering::Relaxed);
+        let entry = QueryLogEntry {
+            status,
+            event_time: Utc::now(),
+            query_id: self.init_query_id.read().clone(),
+            tenant: self.session.get_tenant(),
+            user: self.session.get_user(),
+            query_tag: self.get_settings().get_query_tag().unwrap_or_default(),
+            query_text,
+            scan_rows: scan.read_rows as u64,
+            scan_bytes: scan.read_bytes as u64,
+            cpu_time: Duration::from_nanos(cpu_time_ns),
+            duration: self.created_on.elapsed(),
+            error: self.query_error.read().clone().unwrap_or_default(),
+        };
+        self.session
+            .get_sessions_manager()
+            .get_query_log()
+            .append(entry);
+    }
 }
@@ -14,11 +14,19 @@
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
+use common_base::FaultInjector;
 use common_base::Progress;
 use common_base::Runtime;
+use common_dal::Throttle;
+use common_dal::ThrottleLimits;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
 use common_infallible::RwLock;
@@ -27,12 +35,19 @@ use futures::future::AbortHandle;
 use uuid::Uuid;
 
 use crate::catalogs::impls::DatabaseCatalog;
+use crate::catalogs::impls::TemporaryTables;
 use crate::catalogs::Catalog;
+use crate::catalogs::Database;
 use crate::catalogs::Table;
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
+use crate::functions::SessionFunctions;
+use crate::sessions::AdmissionPermit;
+use crate::sessions::MemoryTracker;
+use crate::sessions::QueryLogStatus;
 use crate::sessions::Session;
 use crate::sessions::Settings;
+use crate::sql::redact_secrets;
 
 type DatabaseAndTable = (String, String);
 
@@ -48,6 +63,12 @@ type DatabaseAndTable = (String, String);
 pub struct DatabendQueryContextShared {
     pub(in crate::sessions) conf: Config,
     pub(in crate::sessions) progress: Arc<Progress>,
+    /// What the scans read, it is never reset by the progress reports of the clients.
+    pub(in crate::sessions) scan_progress: Arc<Progress>,
+    /// The CPU time of the tasks of the query, in nanoseconds.
+    pub(in crate::sessions) cpu_time_ns: Arc<AtomicU64>,
+    pub(in crate::sessions) memory_tracker: Arc<MemoryTracker>,
+    pub(in crate::sessions) created_on: Instant,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
@@ -56,8 +77,18 @@ pub struct DatabendQueryContextShared {
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
     pub(in crate::sessions) subquery_index: Arc<AtomicUsize>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
+    pub(in crate::sessions) query_error: Arc<RwLock<Option<String>>>,
+    /// Warnings returned to the client with the result, e.g. of a large result.
+    pub(in crate::sessions) query_warnings: Arc<RwLock<Vec<String>>>,
     pub(in crate::sessions) running_plan: Arc<RwLock<Option<PlanNode>>>,
     pub(in crate::sessions) tables_refs: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
+    pub(in crate::sessions) dal_fault_injector: Option<Arc<FaultInjector>>,
+    /// Caps the storage throughput of the query, shared by all its data accessors.
+    pub(in crate::sessions) dal_throttle: Arc<Throttle>,
+    /// The query waits for the admission controller, shown as `Queued` by the processlist.
+    pub(in crate::sessions) queued: Arc<AtomicBool>,
+    /// Released when the query finishes, that is when the shared context is dropped.
+    pub(in crate::sessions) admission_permit: Arc<Mutex<Option<AdmissionPermit>>>,
 }
 
 impl DatabendQueryContextShared {
@@ -66,10 +97,37 @@ impl DatabendQueryContextShared {
         session: Arc<Session>,
         cluster_cache: ClusterRef,
     ) -> Arc<DatabendQueryContextShared> {
+        // Each query has its own injector, so that the faults of a query only depend on the seed.
+        let dal_fault_rates = conf.fault_injection.rates("dal");
+        let dal_fault_injector = match dal_fault_rates.is_enabled() {
+            true => Some(FaultInjector::create(dal_fault_rates)),
+            false => None,
+        };
+
+        // The settings are initialized with their defaults, the lookup does not fail.
+        let max_memory_usage = session.get_settings().get_max_memory_usage().unwrap_or(0);
+        let memory_tracker = MemoryTracker::create_child(
+            &session.get_sessions_manager().get_memory_tracker(),
+            "max_memory_usage",
+            max_memory_usage as usize,
+        );
+
+        let settings = session.get_settings();
+        let dal_throttle = Throttle::create(ThrottleLimits {
+            bytes_per_second: settings.get_max_storage_io_bytes_per_second().unwrap_or(0),
+            requests_per_second: settings
+                .get_max_storage_io_requests_per_second()
+                .unwrap_or(0),
+        });
+
         Arc::new(DatabendQueryContextShared {
             conf,
             init_query_id: Arc::new(RwLock::new(Uuid::new_v4().to_string())),
             progress: Arc::new(Progress::create()),
+            scan_progress: Arc::new(Progress::create()),
+            cpu_time_ns: Arc::new(AtomicU64::new(0)),
+            memory_tracker,
+            created_on: Instant::now(),
             session,
             cluster_cache,
             runtime: Arc::new(RwLock::new(None)),
@@ -77,8 +135,14 @@ impl DatabendQueryContextShared {
             ref_count: Arc::new(AtomicUsize::new(0)),
             subquery_index: Arc::new(AtomicUsize::new(1)),
             running_query: Arc::new(RwLock::new(None)),
+            query_error: Arc::new(RwLock::new(None)),
+            query_warnings: Arc::new(RwLock::new(vec![])),
             running_plan: Arc::new(RwLock::new(None)),
             tables_refs: Arc::new(Mutex::new(HashMap::new())),
+            dal_fault_injector,
+            dal_throttle,
+            queued: Arc::new(AtomicBool::new(false)),
+            admission_permit: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -92,6 +156,24 @@ impl DatabendQueryContextShared {
         // TODO: Wait for the query to be processed (write out the last error)
     }
 
+    /// Waits until the admission controller lets the query of the client run.
+    pub async fn wait_for_admission(&self) -> Result<()> {
+        if self.admission_permit.lock().is_some() {
+            return Ok(());
+        }
+
+        let admission = self
+            .session
+            .get_sessions_manager()
+            .get_admission_controller();
+        self.queued.store(true, Ordering::Relaxed);
+        let permit = admission.admit(&self.session.get_user()).await;
+        self.queued.store(false, Ordering::Relaxed);
+
+        *self.admission_permit.lock() = Some(permit?);
+        Ok(())
+    }
+
     pub fn get_cluster(&self) -> ClusterRef {
         self.cluster_cache.clone()
     }
@@ -112,6 +194,32 @@ impl DatabendQueryContextShared {
         self.session.get_catalog()
     }
 
+    pub fn get_temporary_tables(&self) -> Arc<TemporaryTables> {
+        self.session.get_temporary_tables()
+    }
+
+    pub fn get_session_functions(&self) -> Arc<SessionFunctions> {
+        self.session.get_session_functions()
+    }
+
+    pub fn is_tenant_database(&self, database: &dyn Database) -> bool {
+        match database.tenant() {
+            Some(tenant) => tenant == self.session.get_tenant(),
+            None => true,
+        }
+    }
+
+    pub fn get_database(&self, database: &str) -> Result<Arc<dyn Database>> {
+        let db = self.get_catalog().get_database(database)?;
+        match self.is_tenant_database(db.as_ref()) {
+            true => Ok(db),
+            false => Err(ErrorCode::UnknownDatabase(format!(
+                "Unknown database: '{}'",
+                database
+            ))),
+        }
+    }
+
     pub fn get_table(&self, database: &str, table: &str) -> Result<Arc<dyn Table>> {
         // Always get same table metadata in the same query
         let table_meta_key = (database.to_string(), table.to_string());
@@ -121,8 +229,14 @@ impl DatabendQueryContextShared {
         Ok(match tables_refs.entry(table_meta_key) {
             Entry::Occupied(entry) => entry.get().clone(),
             Entry::Vacant(entry) => {
-                let catalog = self.get_catalog();
-                let table = catalog.get_table(database, table)?;
+                // Temporary tables shadow the persistent tables with the same name.
+                let table = match self.get_temporary_tables().get_table(database, table) {
+                    Some(temporary_table) => temporary_table,
+                    None => {
+                        self.get_database(database)?;
+                        self.get_catalog().get_table(database, table)?
+                    }
+                };
                 entry.insert(table).clone()
             }
         })
@@ -144,9 +258,22 @@ impl DatabendQueryContextShared {
         }
     }
 
+    /// The query is kept with the credentials in it masked, see `redact_secrets`.
     pub fn attach_query_str(&self, query: &str) {
-        let mut running_query = self.running_query.write();
-        *running_query = Some(query.to_string());
+        {
+            let mut running_query = self.running_query.write();
+            *running_query = Some(redact_secrets(query));
+        }
+        self.append_query_log(QueryLogStatus::Start);
+    }
+
+    pub fn attach_query_error(&self, error: &ErrorCode) {
+        let mut query_error = self.query_error.write();
+        *query_error = Some(error.to_string());
+    }
+
+    pub fn attach_query_warning(&self, warning: String) {
+        self.query_warnings.write().push(warning);
     }
 
     pub fn attach_query_plan(&self, plan: &PlanNode) {
@@ -163,6 +290,7 @@ impl DatabendQueryContextShared {
 impl Session {
     pub(in crate::sessions) fn destroy_context_shared(&self) {
         let mut mutable_state = self.mutable_state.lock();
+        mutable_state.last_active = Instant::now();
         mutable_state.context_shared.take();
     }
 }
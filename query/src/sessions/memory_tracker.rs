@@ -0,0 +1,146 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Bytes held by the operators of a query, such as the hash tables of the aggregations and the
+/// blocks buffered by the sorts.
+///
+/// The operators report what they hold, the allocations of the rest of the query are not
+/// tracked. The tracker of a query is a child of the one of the server, the bytes of the query
+/// are counted by both, and the query fails once either holds more than its limit.
+#[derive(Default)]
+pub struct MemoryTracker {
+    used: AtomicUsize,
+    peak: AtomicUsize,
+    /// What the limit is named by in the errors, e.g. the setting it comes from.
+    limit_name: &'static str,
+    /// 0 means unlimited.
+    limit: AtomicUsize,
+    parent: Option<Arc<MemoryTracker>>,
+}
+
+impl MemoryTracker {
+    pub fn create() -> Arc<MemoryTracker> {
+        Arc::new(MemoryTracker::default())
+    }
+
+    pub fn create_with_limit(limit_name: &'static str, limit: usize) -> Arc<MemoryTracker> {
+        Arc::new(MemoryTracker {
+            limit_name,
+            limit: AtomicUsize::new(limit),
+            ..Default::default()
+        })
+    }
+
+    /// A tracker whose bytes are counted by the parent too.
+    pub fn create_child(
+        parent: &Arc<MemoryTracker>,
+        limit_name: &'static str,
+        limit: usize,
+    ) -> Arc<MemoryTracker> {
+        Arc::new(MemoryTracker {
+            limit_name,
+            limit: AtomicUsize::new(limit),
+            parent: Some(parent.clone()),
+            ..Default::default()
+        })
+    }
+
+    /// The bytes already held over the new limit fail the next check.
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit, Ordering::Relaxed);
+    }
+
+    pub fn alloc(&self, bytes: usize) {
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak.fetch_max(used, Ordering::Relaxed);
+        if let Some(parent) = &self.parent {
+            parent.alloc(bytes);
+        }
+    }
+
+    pub fn free(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, Ordering::Relaxed);
+        if let Some(parent) = &self.parent {
+            parent.free(bytes);
+        }
+    }
+
+    /// Fails if the tracker or one of its ancestors holds more than its limit. The operators
+    /// check it after they have given back what they can, e.g. by spilling.
+    pub fn check(&self) -> Result<()> {
+        let used = self.used();
+        let limit = self.limit.load(Ordering::Relaxed);
+        if limit > 0 && used > limit {
+            return Err(ErrorCode::MemoryLimitExceeded(format!(
+                "Memory limit exceeded: {} bytes are held, more than {} of {} bytes",
+                used, self.limit_name, limit
+            )));
+        }
+        match &self.parent {
+            Some(parent) => parent.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// Tracks the bytes held by an operator, they are freed once the `TrackedMemory` is dropped.
+    pub fn tracked(self: &Arc<Self>) -> TrackedMemory {
+        TrackedMemory {
+            tracker: self.clone(),
+            bytes: 0,
+        }
+    }
+
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+pub struct TrackedMemory {
+    tracker: Arc<MemoryTracker>,
+    bytes: usize,
+}
+
+impl TrackedMemory {
+    /// Updates the bytes held by the operator.
+    pub fn set(&mut self, bytes: usize) {
+        if bytes > self.bytes {
+            self.tracker.alloc(bytes - self.bytes);
+        } else {
+            self.tracker.free(self.bytes - bytes);
+        }
+        self.bytes = bytes;
+    }
+
+    /// Fails if the query holds more than its limit, see `MemoryTracker::check`.
+    pub fn check(&self) -> Result<()> {
+        self.tracker.check()
+    }
+}
+
+impl Drop for TrackedMemory {
+    fn drop(&mut self) {
+        self.tracker.free(self.bytes);
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sessions::MemoryTracker;
+
+#[test]
+fn test_memory_tracker_limits() -> Result<()> {
+    let server = MemoryTracker::create_with_limit("max_server_memory_usage_mb", 1000);
+    let query_1 = MemoryTracker::create_child(&server, "max_memory_usage", 100);
+    let query_2 = MemoryTracker::create_child(&server, "max_memory_usage", 0);
+
+    // The bytes of the queries are counted by the server too.
+    let mut tracked_1 = query_1.tracked();
+    tracked_1.set(80);
+    tracked_1.check()?;
+    let mut tracked_2 = query_2.tracked();
+    tracked_2.set(500);
+    tracked_2.check()?;
+    assert_eq!(server.used(), 580);
+
+    // Over the limit of the query, the other query is not affected.
+    tracked_1.set(120);
+    let e = tracked_1.check().unwrap_err();
+    assert_eq!(e.code(), ErrorCode::MemoryLimitExceeded("").code());
+    assert!(e.message().contains("max_memory_usage"));
+    tracked_2.check()?;
+
+    // Over the limit of the server.
+    tracked_1.set(0);
+    tracked_2.set(1200);
+    let e = tracked_2.check().unwrap_err();
+    assert!(e.message().contains("max_server_memory_usage_mb"));
+
+    // Under the raised limit of the server.
+    server.set_limit(2000);
+    tracked_2.check()?;
+
+    // The bytes are freed once the operators are done.
+    drop(tracked_1);
+    drop(tracked_2);
+    assert_eq!(server.used(), 0);
+    assert_eq!(server.peak(), 1200);
+    assert_eq!(query_1.peak(), 120);
+    server.check()
+}
@@ -14,3 +14,6 @@
 
 pub static METRIC_SESSION_CONNECT_NUMBERS: &str = "session.connect_numbers";
 pub static METRIC_SESSION_CLOSE_NUMBERS: &str = "session.close_numbers";
+pub static METRIC_QUERY_CACHE_HITS: &str = "query_cache.hits";
+pub static METRIC_QUERY_CACHE_MISSES: &str = "query_cache.misses";
+pub static METRIC_QUERY_CACHE_BYTES: &str = "query_cache.bytes";
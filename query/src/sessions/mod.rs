@@ -15,24 +15,74 @@
 #[macro_use]
 mod macros;
 
+mod admission_controller;
+#[cfg(test)]
+mod admission_controller_test;
+mod config_reload;
+#[cfg(test)]
+mod config_reload_test;
 mod context;
 mod context_shared;
+mod memory_tracker;
+#[cfg(test)]
+mod memory_tracker_test;
 mod metrics;
+mod query_cache;
+#[cfg(test)]
+mod query_cache_test;
+mod query_log;
+mod query_pages;
+#[cfg(test)]
+mod query_pages_test;
 mod session;
 mod session_info;
 mod session_ref;
+#[cfg(test)]
 mod session_test;
 #[allow(clippy::module_inception)]
 mod sessions;
+mod sessions_idle;
+#[cfg(test)]
+mod sessions_idle_test;
 mod sessions_info;
+mod sessions_kill;
+#[cfg(test)]
+mod sessions_kill_test;
+mod sessions_memory;
+#[cfg(test)]
+mod sessions_memory_test;
+mod sessions_purge;
+mod sessions_query_log;
+#[cfg(test)]
+mod sessions_query_log_test;
+mod sessions_statistics;
+#[cfg(test)]
+mod sessions_statistics_test;
+mod sessions_storage_policy;
 mod settings;
 
+pub use admission_controller::AdmissionController;
+pub use admission_controller::AdmissionPermit;
+pub use config_reload::ConfigReloadReport;
 pub use context::DatabendQueryContext;
 pub use context::DatabendQueryContextRef;
 pub use context_shared::DatabendQueryContextShared;
+pub use memory_tracker::MemoryTracker;
+pub use memory_tracker::TrackedMemory;
+pub use query_cache::QueryCache;
+pub use query_cache::QueryCacheEntry;
+pub use query_log::QueryLog;
+pub use query_log::QueryLogEntry;
+pub use query_log::QueryLogStatus;
+pub use query_log::QueryTagUsage;
+pub use query_pages::QueryPage;
+pub use query_pages::QueryPages;
+pub use query_pages::QueryPagesOwner;
 pub use session::Session;
+pub use session_info::NodeProcessInfo;
 pub use session_info::ProcessInfo;
 pub use session_ref::SessionRef;
 pub use sessions::SessionManager;
 pub use sessions::SessionManagerRef;
+pub use sessions_query_log::parse_query_log_table;
 pub use settings::Settings;
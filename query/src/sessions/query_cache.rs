@@ -0,0 +1,253 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_cache::Cache;
+use common_cache::DefaultHashBuilder;
+use common_cache::LruCache;
+use common_cache::Meter;
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_infallible::Mutex;
+use metrics::counter;
+use metrics::gauge;
+
+use crate::sessions::Settings;
+
+/// A query result kept in the `QueryCache`.
+pub struct QueryCacheEntry {
+    pub key: String,
+    /// The tables which the result was computed from, as `database.table`.
+    pub tables: Vec<String>,
+    /// The snapshot of the tables which the result was computed against, if any.
+    pub snapshot: Option<String>,
+    pub blocks: Vec<DataBlock>,
+    pub bytes: u64,
+    hits: AtomicU64,
+    created_on: Instant,
+}
+
+impl QueryCacheEntry {
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn age(&self) -> Duration {
+        self.created_on.elapsed()
+    }
+}
+
+/// Measures the cached results by the memory of their blocks.
+struct QueryCacheMeter;
+
+impl Meter<String, Arc<QueryCacheEntry>> for QueryCacheMeter {
+    type Measure = usize;
+
+    fn measure<Q: ?Sized>(&self, _: &Q, value: &Arc<QueryCacheEntry>) -> usize
+    where String: Borrow<Q> {
+        value.bytes as usize
+    }
+}
+
+type QueryLruCache = LruCache<String, Arc<QueryCacheEntry>, DefaultHashBuilder, QueryCacheMeter>;
+
+/// Query results shared by all the sessions of this node, the least recently used results
+/// are evicted once they exceed the capacity.
+pub struct QueryCache {
+    capacity: AtomicU64,
+    entries: Mutex<QueryLruCache>,
+}
+
+impl QueryCache {
+    /// Creates a cache holding at most `capacity` bytes, 0 disables the cache.
+    pub fn create(capacity: u64) -> Arc<QueryCache> {
+        Arc::new(QueryCache {
+            capacity: AtomicU64::new(capacity),
+            entries: Mutex::new(LruCache::with_meter(capacity, QueryCacheMeter)),
+        })
+    }
+
+    /// The key of the result of a query: the query with its whitespace collapsed, followed by
+    /// a digest of the tenant, the current database, the snapshots of the tables and the settings
+    /// it ran with. The same query may read other tables in another database.
+    pub fn key(
+        tenant: &str,
+        database: &str,
+        query: &str,
+        snapshots: &[String],
+        settings: &Settings,
+    ) -> String {
+        let mut settings = settings
+            .iter()
+            .filter_map(|setting| match setting {
+                DataValue::Struct(values) if values.len() > 1 => {
+                    Some(format!("{}={}", values[0], values[1]))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        settings.sort();
+
+        let mut hasher = DefaultHasher::new();
+        tenant.hash(&mut hasher);
+        database.hash(&mut hasher);
+        snapshots.hash(&mut hasher);
+        settings.hash(&mut hasher);
+        format!("{} /* {:016x} */", normalize_query(query), hasher.finish())
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Resizes the cache, the least recently used results over the new capacity are evicted.
+    pub fn set_capacity(&self, capacity: u64) {
+        let mut entries = self.entries.lock();
+        entries.set_capacity(capacity);
+        self.capacity.store(capacity, Ordering::Relaxed);
+    }
+
+    pub fn get(&self, key: &str) -> Option<Arc<QueryCacheEntry>> {
+        if self.capacity() == 0 {
+            return None;
+        }
+
+        let entry = self.entries.lock().get(key).cloned();
+        match &entry {
+            Some(entry) => {
+                counter!(super::metrics::METRIC_QUERY_CACHE_HITS, 1);
+                entry.hits.fetch_add(1, Ordering::Relaxed);
+            }
+            None => counter!(super::metrics::METRIC_QUERY_CACHE_MISSES, 1),
+        }
+        entry
+    }
+
+    pub fn put(
+        &self,
+        key: impl Into<String>,
+        tables: Vec<String>,
+        snapshot: Option<String>,
+        blocks: Vec<DataBlock>,
+    ) {
+        let key = key.into();
+        let bytes = blocks.iter().map(|b| b.memory_size() as u64).sum();
+        // A result larger than the whole cache would evict everything and itself.
+        if bytes > self.capacity() {
+            return;
+        }
+
+        let entry = QueryCacheEntry {
+            key: key.clone(),
+            tables,
+            snapshot,
+            blocks,
+            bytes,
+            hits: AtomicU64::new(0),
+            created_on: Instant::now(),
+        };
+        let mut entries = self.entries.lock();
+        entries.put(key, Arc::new(entry));
+        gauge!(
+            super::metrics::METRIC_QUERY_CACHE_BYTES,
+            entries.size() as f64
+        );
+    }
+
+    pub fn entries(&self) -> Vec<Arc<QueryCacheEntry>> {
+        self.entries
+            .lock()
+            .iter()
+            .map(|(_, entry)| entry.clone())
+            .collect()
+    }
+
+    /// The bytes of the cached results.
+    pub fn size(&self) -> u64 {
+        self.entries.lock().size()
+    }
+
+    /// Drops all the entries, returns the number of dropped entries.
+    pub fn drop_all(&self) -> usize {
+        let mut entries = self.entries.lock();
+        let dropped = entries.len();
+        entries.clear();
+        gauge!(super::metrics::METRIC_QUERY_CACHE_BYTES, 0.0);
+        dropped
+    }
+
+    /// Drops the entries computed from the table, returns the number of dropped entries.
+    pub fn drop_table(&self, db: &str, table: &str) -> usize {
+        let name = format!("{}.{}", db, table);
+        let mut entries = self.entries.lock();
+        let keys = entries
+            .iter()
+            .filter(|(_, entry)| entry.tables.contains(&name))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in keys.iter() {
+            entries.pop(key);
+        }
+        gauge!(
+            super::metrics::METRIC_QUERY_CACHE_BYTES,
+            entries.size() as f64
+        );
+        keys.len()
+    }
+}
+
+/// Collapses the whitespace outside of the quotes, and drops the trailing semicolons.
+fn normalize_query(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut quote = None;
+    let mut escaped = false;
+    for c in query.chars() {
+        match quote {
+            Some(q) => {
+                normalized.push(c);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c.is_whitespace() => {
+                if !normalized.ends_with(' ') {
+                    normalized.push(' ');
+                }
+            }
+            None => {
+                if c == '\'' || c == '"' || c == '`' {
+                    quote = Some(c);
+                }
+                normalized.push(c);
+            }
+        }
+    }
+    normalized
+        .trim_end_matches(|c: char| c == ';' || c.is_whitespace())
+        .trim_start()
+        .to_string()
+}
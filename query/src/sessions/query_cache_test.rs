@@ -0,0 +1,214 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::QueryCache;
+use crate::sessions::Settings;
+use crate::sql::PlanParser;
+
+fn block(rows: usize) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let values = (0..rows as u64).collect::<Vec<_>>();
+    DataBlock::create_by_array(schema, vec![Series::new(values)])
+}
+
+#[test]
+fn test_query_cache_lru() -> Result<()> {
+    let bytes = block(100).memory_size() as u64;
+    let query_cache = QueryCache::create(bytes * 2);
+
+    query_cache.put("q1", vec![], None, vec![block(100)]);
+    query_cache.put("q2", vec![], None, vec![block(100)]);
+    assert_eq!(query_cache.size(), bytes * 2);
+
+    // q1 is used more recently than q2, q2 is evicted by q3.
+    assert!(query_cache.get("q1").is_some());
+    query_cache.put("q3", vec![], None, vec![block(100)]);
+    assert!(query_cache.get("q1").is_some());
+    assert!(query_cache.get("q2").is_none());
+    assert!(query_cache.get("q3").is_some());
+    assert_eq!(query_cache.get("q1").unwrap().hits(), 3);
+
+    // Results larger than the cache are not kept.
+    query_cache.put("q4", vec![], None, vec![block(300)]);
+    assert!(query_cache.get("q4").is_none());
+    assert_eq!(query_cache.entries().len(), 2);
+
+    // Shrunk to one result, the least recently used one is evicted.
+    query_cache.set_capacity(bytes);
+    assert!(query_cache.get("q3").is_none());
+    assert!(query_cache.get("q1").is_some());
+
+    // A cache of 0 bytes is disabled.
+    let query_cache = QueryCache::create(0);
+    query_cache.put("q1", vec![], None, vec![]);
+    assert!(query_cache.get("q1").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_query_cache_key() -> Result<()> {
+    let settings = Settings::try_create()?;
+    let snapshots = vec!["default.t@s1".to_string()];
+    let key = QueryCache::key(
+        "t1",
+        "default",
+        "select a from t where b = 'x  y'",
+        &snapshots,
+        &settings,
+    );
+
+    for (tenant, database, query, snapshot, same) in [
+        (
+            "t1",
+            "default",
+            "  select a\n  from t\twhere b = 'x  y' ;",
+            "default.t@s1",
+            true,
+        ),
+        (
+            "t1",
+            "default",
+            "select a from t where b = 'x y'",
+            "default.t@s1",
+            false,
+        ),
+        (
+            "t1",
+            "default",
+            "SELECT a FROM t WHERE b = 'x  y'",
+            "default.t@s1",
+            false,
+        ),
+        (
+            "t1",
+            "default",
+            "select a from t where b = 'x  y'",
+            "default.t@s2",
+            false,
+        ),
+        (
+            "t2",
+            "default",
+            "select a from t where b = 'x  y'",
+            "default.t@s1",
+            false,
+        ),
+        (
+            "t1",
+            "db1",
+            "select a from t where b = 'x  y'",
+            "default.t@s1",
+            false,
+        ),
+    ] {
+        let other = QueryCache::key(tenant, database, query, &[snapshot.to_string()], &settings);
+        assert_eq!(key == other, same, "{}", query);
+    }
+
+    settings.set_max_block_size(1)?;
+    let other = QueryCache::key(
+        "t1",
+        "default",
+        "select a from t where b = 'x  y'",
+        &snapshots,
+        &settings,
+    );
+    assert_ne!(key, other);
+
+    Ok(())
+}
+
+async fn execute(ctx: &DatabendQueryContextRef, query: &str) -> Result<Vec<DataBlock>> {
+    ctx.attach_query_str(query);
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+    interpreter.execute().await?.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_cache_select() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let query_cache = ctx.get_sessions_manager().get_query_cache();
+
+    execute(&ctx, "create table default.t(a bigint) Engine = Fuse").await?;
+    execute(&ctx, "create table default.m(a bigint) Engine = Memory").await?;
+    execute(&ctx, "insert into default.t values(1), (2)").await?;
+
+    let expected = vec![
+        "+--------+",
+        "| sum(a) |",
+        "+--------+",
+        "| 3      |",
+        "+--------+",
+    ];
+    let result = execute(&ctx, "select sum(a) from default.t").await?;
+    common_datablocks::assert_blocks_eq(expected.clone(), &result);
+    assert_eq!(query_cache.entries().len(), 1);
+
+    // The same query, formatted differently, is answered by the cache.
+    let result = execute(&ctx, "select sum(a)\n  from default.t;").await?;
+    common_datablocks::assert_blocks_eq(expected, &result);
+    let entry = query_cache.entries().pop().unwrap();
+    assert_eq!(entry.hits(), 1);
+    assert_eq!(entry.tables, vec!["default.t".to_string()]);
+
+    // The query reads the new snapshot of the table after the insert.
+    execute(&ctx, "insert into default.t values(3)").await?;
+    let expected = vec![
+        "+--------+",
+        "| sum(a) |",
+        "+--------+",
+        "| 6      |",
+        "+--------+",
+    ];
+    let result = execute(&ctx, "select sum(a) from default.t").await?;
+    common_datablocks::assert_blocks_eq(expected, &result);
+    assert_eq!(query_cache.entries().len(), 2);
+
+    // The same query run in another database is not answered by the result of the first one.
+    execute(&ctx, "create database db1").await?;
+    for (database, row) in [("default", "| default    |"), ("db1", "| db1        |")] {
+        ctx.set_current_database(database.to_string())?;
+        let expected = vec![
+            "+------------+",
+            "| database() |",
+            "+------------+",
+            row,
+            row,
+            row,
+            "+------------+",
+        ];
+        let result = execute(&ctx, "select database() from default.t").await?;
+        common_datablocks::assert_blocks_eq(expected, &result);
+    }
+    assert_eq!(query_cache.entries().len(), 4);
+
+    // Not cached: other engines, functions of the time, and the disabled setting.
+    execute(&ctx, "select sum(a) from default.m").await?;
+    execute(&ctx, "select now() from default.t").await?;
+    ctx.get_settings().set_use_query_cache(0)?;
+    execute(&ctx, "select count() from default.t").await?;
+    assert_eq!(query_cache.entries().len(), 4);
+
+    Ok(())
+}
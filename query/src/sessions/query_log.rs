@@ -0,0 +1,223 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_infallible::Mutex;
+use common_infallible::RwLock;
+
+/// The event of a query an entry of the `QueryLog` is about.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueryLogStatus {
+    Start,
+    Finish,
+    Error,
+}
+
+impl fmt::Display for QueryLogStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryLogStatus::Start => write!(f, "Start"),
+            QueryLogStatus::Finish => write!(f, "Finish"),
+            QueryLogStatus::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// A started or finished query kept in the `QueryLog`.
+#[derive(Clone, Debug)]
+pub struct QueryLogEntry {
+    pub status: QueryLogStatus,
+    /// When the query started or finished.
+    pub event_time: DateTime<Utc>,
+    pub query_id: String,
+    pub tenant: String,
+    /// The user the query ran as, empty if the session is not authenticated.
+    pub user: String,
+    /// The `query_tag` setting of the query, which the usage is accounted to.
+    pub query_tag: String,
+    pub query_text: String,
+    /// What the scans of the query read on this node.
+    pub scan_rows: u64,
+    pub scan_bytes: u64,
+    /// The CPU time the query consumed on this node.
+    pub cpu_time: Duration,
+    pub duration: Duration,
+    /// The error the query failed with, empty unless the status is `Error`.
+    pub error: String,
+}
+
+impl QueryLogEntry {
+    /// The schema of `system.query_log`, and of the FUSE table it is persisted into.
+    pub fn schema() -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("query_id", DataType::String, false),
+            DataField::new("status", DataType::String, false),
+            DataField::new("event_time", DataType::DateTime32(None), false),
+            DataField::new("tenant", DataType::String, false),
+            DataField::new("user", DataType::String, false),
+            DataField::new("query_tag", DataType::String, false),
+            DataField::new("query_text", DataType::String, false),
+            DataField::new("scan_rows", DataType::UInt64, false),
+            DataField::new("scan_bytes", DataType::UInt64, false),
+            DataField::new("cpu_time_ms", DataType::UInt64, false),
+            DataField::new("duration_ms", DataType::UInt64, false),
+            DataField::new("error", DataType::String, false),
+        ])
+    }
+
+    pub fn to_block(entries: &[QueryLogEntry]) -> DataBlock {
+        let mut query_ids = Vec::with_capacity(entries.len());
+        let mut statuses = Vec::with_capacity(entries.len());
+        let mut event_times = Vec::with_capacity(entries.len());
+        let mut tenants = Vec::with_capacity(entries.len());
+        let mut users = Vec::with_capacity(entries.len());
+        let mut query_tags = Vec::with_capacity(entries.len());
+        let mut query_texts = Vec::with_capacity(entries.len());
+        let mut scan_rows = Vec::with_capacity(entries.len());
+        let mut scan_bytes = Vec::with_capacity(entries.len());
+        let mut cpu_times = Vec::with_capacity(entries.len());
+        let mut durations = Vec::with_capacity(entries.len());
+        let mut errors = Vec::with_capacity(entries.len());
+
+        for entry in entries {
+            query_ids.push(entry.query_id.as_bytes());
+            statuses.push(entry.status.to_string().into_bytes());
+            event_times.push(entry.event_time.timestamp() as u32);
+            tenants.push(entry.tenant.as_bytes());
+            users.push(entry.user.as_bytes());
+            query_tags.push(entry.query_tag.as_bytes());
+            query_texts.push(entry.query_text.as_bytes());
+            scan_rows.push(entry.scan_rows);
+            scan_bytes.push(entry.scan_bytes);
+            cpu_times.push(entry.cpu_time.as_millis() as u64);
+            durations.push(entry.duration.as_millis() as u64);
+            errors.push(entry.error.as_bytes());
+        }
+
+        DataBlock::create_by_array(Self::schema(), vec![
+            Series::new(query_ids),
+            Series::new(statuses),
+            Series::new(event_times),
+            Series::new(tenants),
+            Series::new(users),
+            Series::new(query_tags),
+            Series::new(query_texts),
+            Series::new(scan_rows),
+            Series::new(scan_bytes),
+            Series::new(cpu_times),
+            Series::new(durations),
+            Series::new(errors),
+        ])
+    }
+}
+
+/// The usage of all the queries with a tag since the server started.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueryTagUsage {
+    pub queries: u64,
+    pub scan_rows: u64,
+    pub scan_bytes: u64,
+    pub cpu_time: Duration,
+}
+
+/// The latest started and finished queries of this node, with the usage of the finished ones
+/// summed up by the tenant and the query tag, so that the teams sharing the cluster are billed.
+///
+/// If the query log is persisted, the entries are also kept until the `QueryLogWriter` takes
+/// them to append them to the FUSE table.
+pub struct QueryLog {
+    capacity: usize,
+    persisted: bool,
+    entries: RwLock<VecDeque<QueryLogEntry>>,
+    unpersisted: Mutex<Vec<QueryLogEntry>>,
+    usage: RwLock<HashMap<(String, String), QueryTagUsage>>,
+}
+
+impl QueryLog {
+    pub fn create(capacity: usize, persisted: bool) -> Arc<QueryLog> {
+        Arc::new(QueryLog {
+            capacity,
+            persisted,
+            entries: RwLock::new(VecDeque::new()),
+            unpersisted: Mutex::new(Vec::new()),
+            usage: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub fn append(&self, entry: QueryLogEntry) {
+        if self.persisted {
+            let mut unpersisted = self.unpersisted.lock();
+            // The entries are dropped rather than the memory if the table can't be written.
+            if unpersisted.len() < self.capacity {
+                unpersisted.push(entry.clone());
+            }
+        }
+
+        if entry.status != QueryLogStatus::Start {
+            let mut usage = self.usage.write();
+            let key = (entry.tenant.clone(), entry.query_tag.clone());
+            let tag_usage = usage.entry(key).or_default();
+            tag_usage.queries += 1;
+            tag_usage.scan_rows += entry.scan_rows;
+            tag_usage.scan_bytes += entry.scan_bytes;
+            tag_usage.cpu_time += entry.cpu_time;
+        }
+
+        // The usage is kept even if the entry is evicted.
+        let mut entries = self.entries.write();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Takes the entries not persisted yet, the earliest first.
+    pub fn take_unpersisted(&self) -> Vec<QueryLogEntry> {
+        std::mem::take(&mut *self.unpersisted.lock())
+    }
+
+    /// The entries of the tenant, the earliest first.
+    pub fn entries(&self, tenant: &str) -> Vec<QueryLogEntry> {
+        let entries = self.entries.read();
+        entries
+            .iter()
+            .filter(|entry| entry.tenant == tenant)
+            .cloned()
+            .collect()
+    }
+
+    /// The usage of the tenant by the query tag.
+    pub fn usage(&self, tenant: &str) -> Vec<(String, QueryTagUsage)> {
+        let usage = self.usage.read();
+        usage
+            .iter()
+            .filter(|((usage_tenant, _), _)| usage_tenant == tenant)
+            .map(|((_, tag), tag_usage)| (tag.clone(), tag_usage.clone()))
+            .collect()
+    }
+}
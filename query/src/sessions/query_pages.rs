@@ -0,0 +1,141 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+
+use crate::sessions::MemoryTracker;
+use crate::sessions::TrackedMemory;
+
+/// Results which are not read to the end are dropped after this.
+const QUERY_PAGES_TTL: Duration = Duration::from_secs(600);
+
+/// One page of a paginated HTTP query result, already rendered in the output format.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct QueryPage {
+    pub content_type: String,
+    pub body: String,
+    pub next_page: Option<usize>,
+}
+
+/// The tenant and the name of the user who ran the query, the only one who can read its pages.
+pub type QueryPagesOwner = (String, String);
+
+struct PagedResult {
+    owner: QueryPagesOwner,
+    content_type: String,
+    pages: Vec<String>,
+    created_on: Instant,
+    // The bytes of the pages, freed once the result is dropped.
+    _memory: TrackedMemory,
+}
+
+/// The pages of the HTTP query results kept on the node which ran the query.
+///
+/// The bytes of the pages are counted by a child of the memory tracker of the server, a result
+/// over `query_pages_size_mb` or `max_server_memory_usage_mb` is rejected.
+pub struct QueryPages {
+    results: RwLock<HashMap<String, PagedResult>>,
+    memory_tracker: Arc<MemoryTracker>,
+}
+
+impl QueryPages {
+    pub fn create(server_tracker: &Arc<MemoryTracker>, max_bytes: usize) -> Arc<QueryPages> {
+        Arc::new(QueryPages {
+            results: RwLock::new(HashMap::new()),
+            memory_tracker: MemoryTracker::create_child(
+                server_tracker,
+                "query_pages_size_mb",
+                max_bytes,
+            ),
+        })
+    }
+
+    pub fn add(
+        &self,
+        query_id: impl Into<String>,
+        owner: QueryPagesOwner,
+        content_type: &str,
+        pages: Vec<String>,
+    ) -> Result<()> {
+        let mut results = self.results.write();
+        results.retain(|_, result| result.created_on.elapsed() < QUERY_PAGES_TTL);
+
+        let mut memory = self.memory_tracker.tracked();
+        memory.set(pages.iter().map(|page| page.len()).sum());
+        memory.check()?;
+
+        results.insert(query_id.into(), PagedResult {
+            owner,
+            content_type: content_type.to_string(),
+            pages,
+            created_on: Instant::now(),
+            _memory: memory,
+        });
+        Ok(())
+    }
+
+    /// The bytes of the pages kept.
+    pub fn size(&self) -> usize {
+        self.memory_tracker.used()
+    }
+
+    /// Gets the page of the query result, the result is dropped once its last page is read.
+    /// The results of the other users are unknown.
+    pub fn get_page(
+        &self,
+        query_id: &str,
+        owner: &QueryPagesOwner,
+        page: usize,
+    ) -> Result<QueryPage> {
+        let mut results = self.results.write();
+        let result = results
+            .get(query_id)
+            .filter(|result| result.created_on.elapsed() < QUERY_PAGES_TTL)
+            .filter(|result| &result.owner == owner)
+            .ok_or_else(|| {
+                ErrorCode::UnknownQueryResult(format!("Unknown query result: {}", query_id))
+            })?;
+
+        let body = result.pages.get(page).cloned().ok_or_else(|| {
+            ErrorCode::UnknownQueryResult(format!(
+                "Unknown page {} of query result: {}, it has {} pages",
+                page,
+                query_id,
+                result.pages.len()
+            ))
+        })?;
+
+        let content_type = result.content_type.clone();
+        let next_page = match page + 1 < result.pages.len() {
+            true => Some(page + 1),
+            false => {
+                results.remove(query_id);
+                None
+            }
+        };
+
+        Ok(QueryPage {
+            content_type,
+            body,
+            next_page,
+        })
+    }
+}
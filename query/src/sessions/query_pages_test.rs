@@ -0,0 +1,53 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::ErrorCode;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::sessions::MemoryTracker;
+use crate::sessions::QueryPages;
+
+#[test]
+fn test_query_pages() -> Result<()> {
+    let server = MemoryTracker::create_with_limit("max_server_memory_usage_mb", 0);
+    let query_pages = QueryPages::create(&server, 10);
+    let owner = ("tenant".to_string(), "user".to_string());
+    let pages = vec!["0\n1\n".to_string(), "2\n".to_string()];
+    query_pages.add("q1", owner.clone(), "text/csv", pages)?;
+
+    // The pages are counted by the server too.
+    assert_eq!(query_pages.size(), 6);
+    assert_eq!(server.used(), 6);
+
+    // Over the limit, the result is rejected and its bytes are not kept.
+    let e = query_pages
+        .add("q2", owner.clone(), "text/csv", vec![
+            "3\n4\n5\n".to_string()
+        ])
+        .unwrap_err();
+    assert_eq!(e.code(), ErrorCode::MemoryLimitExceeded("").code());
+    assert_eq!(server.used(), 6);
+
+    // Only the user who ran the query reads its pages.
+    let other = ("tenant".to_string(), "other".to_string());
+    let e = query_pages.get_page("q1", &other, 0).unwrap_err();
+    assert_eq!(e.code(), ErrorCode::UnknownQueryResult("").code());
+    assert_eq!(query_pages.get_page("q1", &owner, 0)?.next_page, Some(1));
+
+    // The bytes are freed once the last page is read.
+    assert_eq!(query_pages.get_page("q1", &owner, 1)?.body, "2\n");
+    assert_eq!(server.used(), 0);
+
+    Ok(())
+}
@@ -15,26 +15,39 @@
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
+use common_dal::IOPriority;
 use common_exception::Result;
 use common_infallible::Mutex;
+use common_management::UserInfo;
+use common_management::UserQuota;
 use common_mem_allocator::malloc_size;
 use common_mem_derive::*;
 use futures::channel::oneshot::Sender;
 use futures::channel::*;
 
 use crate::catalogs::impls::DatabaseCatalog;
-use crate::configs::Config;
+use crate::catalogs::impls::TemporaryTables;
+use crate::functions::SessionFunctions;
 use crate::sessions::context_shared::DatabendQueryContextShared;
 use crate::sessions::DatabendQueryContext;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sessions::SessionManagerRef;
 use crate::sessions::Settings;
+use crate::users::UserManager;
 use crate::users::UserManagerRef;
 
 #[derive(MallocSizeOf)]
 pub(in crate::sessions) struct MutableStatus {
     pub(in crate::sessions) abort: bool,
+    /// The tenant the session logged in to, it only accesses the databases of the tenant.
+    pub(in crate::sessions) tenant: String,
+    /// The user the session logged in as, empty if it is not authenticated.
+    pub(in crate::sessions) user: String,
+    /// The quota of the user the session logged in as.
+    pub(in crate::sessions) user_quota: UserQuota,
     pub(in crate::sessions) current_database: String,
     pub(in crate::sessions) session_settings: Arc<Settings>,
     #[ignore_malloc_size_of = "insignificant"]
@@ -43,6 +56,15 @@ pub(in crate::sessions) struct MutableStatus {
     pub(in crate::sessions) io_shutdown_tx: Option<Sender<Sender<()>>>,
     #[ignore_malloc_size_of = "insignificant"]
     pub(in crate::sessions) context_shared: Option<Arc<DatabendQueryContextShared>>,
+    #[ignore_malloc_size_of = "insignificant"]
+    pub(in crate::sessions) temporary_tables: Arc<TemporaryTables>,
+    #[ignore_malloc_size_of = "insignificant"]
+    pub(in crate::sessions) session_functions: Arc<SessionFunctions>,
+    /// When the session was created, or its last query was started or finished.
+    #[ignore_malloc_size_of = "insignificant"]
+    pub(in crate::sessions) last_active: Instant,
+    #[ignore_malloc_size_of = "insignificant"]
+    pub(in crate::sessions) io_priority: IOPriority,
 }
 
 #[derive(Clone, MallocSizeOf)]
@@ -50,8 +72,6 @@ pub struct Session {
     pub(in crate::sessions) id: String,
     pub(in crate::sessions) typ: String,
     #[ignore_malloc_size_of = "insignificant"]
-    pub(in crate::sessions) config: Config,
-    #[ignore_malloc_size_of = "insignificant"]
     pub(in crate::sessions) sessions: SessionManagerRef,
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
     pub(in crate::sessions) mutable_state: Arc<Mutex<MutableStatus>>,
@@ -59,24 +79,30 @@ pub struct Session {
 
 impl Session {
     pub fn try_create(
-        config: Config,
         id: String,
         typ: String,
         sessions: SessionManagerRef,
     ) -> Result<Arc<Session>> {
+        let tenant = sessions.get_conf().query.tenant;
         Ok(Arc::new(Session {
             id,
             typ,
-            config,
             sessions,
             ref_count: Arc::new(AtomicUsize::new(0)),
             mutable_state: Arc::new(Mutex::new(MutableStatus {
                 abort: false,
+                tenant,
+                user: String::new(),
+                user_quota: UserQuota::default(),
                 current_database: String::from("default"),
                 session_settings: Settings::try_create()?,
                 client_host: None,
                 io_shutdown_tx: None,
                 context_shared: None,
+                temporary_tables: Arc::new(TemporaryTables::try_create()?),
+                session_functions: Arc::new(SessionFunctions::default()),
+                last_active: Instant::now(),
+                io_priority: IOPriority::Foreground,
             })),
         }))
     }
@@ -133,7 +159,8 @@ impl Session {
         Ok(match context_shared.as_ref() {
             Some(shared) => DatabendQueryContext::from_shared(shared.clone()),
             None => {
-                let config = self.config.clone();
+                // The config may be reloaded, a query always runs with the latest one.
+                let config = self.sessions.get_conf();
                 let discovery = self.sessions.get_cluster_discovery();
 
                 let session = self.clone();
@@ -145,6 +172,7 @@ impl Session {
                 match mutable_state.context_shared.as_ref() {
                     Some(shared) => DatabendQueryContext::from_shared(shared.clone()),
                     None => {
+                        mutable_state.last_active = Instant::now();
                         mutable_state.context_shared = Some(shared.clone());
                         DatabendQueryContext::from_shared(shared)
                     }
@@ -153,6 +181,34 @@ impl Session {
         })
     }
 
+    /// How long the session has not run any query, None if a query is running.
+    pub fn get_idle_duration(self: &Arc<Self>) -> Option<Duration> {
+        let mutable_state = self.mutable_state.lock();
+        match mutable_state.context_shared {
+            Some(_) => None,
+            None => Some(mutable_state.last_active.elapsed()),
+        }
+    }
+
+    /// The id of the running query, None if the session is idle.
+    pub fn get_query_id(self: &Arc<Self>) -> Option<String> {
+        let mutable_state = self.mutable_state.lock();
+        mutable_state
+            .context_shared
+            .as_ref()
+            .map(|shared| shared.init_query_id.read().clone())
+    }
+
+    /// The id of the running query and the bytes held by its operators, None if the session
+    /// is idle.
+    pub fn get_query_memory_usage(self: &Arc<Self>) -> Option<(String, usize)> {
+        let mutable_state = self.mutable_state.lock();
+        mutable_state.context_shared.as_ref().map(|shared| {
+            let query_id = shared.init_query_id.read().clone();
+            (query_id, shared.memory_tracker.used())
+        })
+    }
+
     pub fn attach<F>(self: &Arc<Self>, host: Option<SocketAddr>, io_shutdown: F)
     where F: FnOnce() + Send + 'static {
         let (tx, rx) = futures::channel::oneshot::channel();
@@ -168,6 +224,46 @@ impl Session {
         });
     }
 
+    /// Set once the user is authenticated, by the tenant of the login.
+    pub fn set_tenant(self: &Arc<Self>, tenant: String) {
+        self.mutable_state.lock().tenant = tenant;
+    }
+
+    pub fn get_tenant(self: &Arc<Self>) -> String {
+        self.mutable_state.lock().tenant.clone()
+    }
+
+    /// Set once the user is authenticated, by the name of the user.
+    pub fn set_user(self: &Arc<Self>, user: String) {
+        self.mutable_state.lock().user = user;
+    }
+
+    pub fn get_user(self: &Arc<Self>) -> String {
+        self.mutable_state.lock().user.clone()
+    }
+
+    /// Set once the user is authenticated, by the quota of the user.
+    pub fn set_user_quota(self: &Arc<Self>, quota: UserQuota) {
+        self.mutable_state.lock().user_quota = quota;
+    }
+
+    pub fn get_user_quota(self: &Arc<Self>) -> UserQuota {
+        self.mutable_state.lock().user_quota
+    }
+
+    /// Set once the user is authenticated, by the settings profiles of the user in its tenant.
+    pub fn apply_settings_profile(
+        self: &Arc<Self>,
+        user_mgr: &UserManager,
+        user: &UserInfo,
+    ) -> Result<()> {
+        let settings = self.get_settings();
+        for (name, value) in user_mgr.get_user_settings(user)? {
+            settings.update_settings(&name, value)?;
+        }
+        Ok(())
+    }
+
     pub fn set_current_database(self: &Arc<Self>, database_name: String) {
         let mut inner = self.mutable_state.lock();
         inner.current_database = database_name;
@@ -178,10 +274,27 @@ impl Session {
         inner.current_database.clone()
     }
 
+    /// Sessions of the background jobs set their storage requests to `IOPriority::Background`.
+    pub fn set_io_priority(self: &Arc<Self>, io_priority: IOPriority) {
+        self.mutable_state.lock().io_priority = io_priority;
+    }
+
+    pub fn get_io_priority(self: &Arc<Self>) -> IOPriority {
+        self.mutable_state.lock().io_priority
+    }
+
     pub fn get_settings(self: &Arc<Self>) -> Arc<Settings> {
         self.mutable_state.lock().session_settings.clone()
     }
 
+    pub fn get_temporary_tables(self: &Arc<Self>) -> Arc<TemporaryTables> {
+        self.mutable_state.lock().temporary_tables.clone()
+    }
+
+    pub fn get_session_functions(self: &Arc<Self>) -> Arc<SessionFunctions> {
+        self.mutable_state.lock().session_functions.clone()
+    }
+
     pub fn get_sessions_manager(self: &Arc<Self>) -> SessionManagerRef {
         self.sessions.clone()
     }
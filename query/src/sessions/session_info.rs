@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use crate::sessions::session::MutableStatus;
@@ -22,6 +23,7 @@ use crate::sessions::Settings;
 pub struct ProcessInfo {
     pub id: String,
     pub typ: String,
+    pub tenant: String,
     pub state: String,
     pub database: String,
     #[allow(unused)]
@@ -30,6 +32,35 @@ pub struct ProcessInfo {
     pub session_extra_info: Option<String>,
 }
 
+/// The process info of a node, as listed to the other nodes of the cluster.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct NodeProcessInfo {
+    pub id: String,
+    pub typ: String,
+    #[serde(default)]
+    pub tenant: String,
+    pub host: Option<String>,
+    pub state: String,
+    pub database: String,
+    pub extra_info: Option<String>,
+    pub node: String,
+}
+
+impl ProcessInfo {
+    pub fn to_node_process_info(&self, node: &str) -> NodeProcessInfo {
+        NodeProcessInfo {
+            id: self.id.clone(),
+            typ: self.typ.clone(),
+            tenant: self.tenant.clone(),
+            host: self.client_address.map(|address| address.to_string()),
+            state: self.state.clone(),
+            database: self.database.clone(),
+            extra_info: self.session_extra_info.clone(),
+            node: node.to_string(),
+        }
+    }
+}
+
 impl Session {
     pub fn process_info(self: &Arc<Self>) -> ProcessInfo {
         let session_mutable_state = self.mutable_state.lock();
@@ -40,6 +71,7 @@ impl Session {
         ProcessInfo {
             id: self.id.clone(),
             typ: self.typ.clone(),
+            tenant: status.tenant.clone(),
             state: self.process_state(status),
             database: status.current_database.clone(),
             settings: status.session_settings.clone(),
@@ -52,6 +84,7 @@ impl Session {
         match status.context_shared {
             _ if status.abort => String::from("Aborting"),
             None => String::from("Idle"),
+            Some(ref shared) if shared.queued.load(Ordering::Relaxed) => String::from("Queued"),
             Some(_) => String::from("Query"),
         }
     }
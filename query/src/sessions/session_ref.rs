@@ -57,6 +57,8 @@ impl Session {
         if self.ref_count.fetch_sub(1, Ordering::Release) == 1 {
             std::sync::atomic::fence(Acquire);
             log::debug!("Destroy session {}", self.id);
+            self.get_temporary_tables().clear();
+            self.get_session_functions().clear();
             self.sessions.destroy_session(&self.id);
         }
     }
@@ -13,6 +13,16 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::UserQuota;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::SessionRef;
+use crate::sql::PlanParser;
+use crate::tests::SessionManagerBuilder;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_session_mem_usage() {
@@ -24,10 +34,9 @@ async fn test_session_mem_usage() {
 
     let conf = Config::load_from_args();
 
-    let session_manager = SessionManager::from_conf(conf.clone()).await.unwrap();
+    let session_manager = SessionManager::from_conf(conf).await.unwrap();
 
     let session = Session::try_create(
-        conf.clone(),
         String::from("test-001"),
         String::from("test-type"),
         session_manager,
@@ -39,3 +48,138 @@ async fn test_session_mem_usage() {
     assert!(session_size > 3000);
     assert_eq!(session_size, session.get_memory_usage());
 }
+
+async fn execute(session: &SessionRef, query: &str) -> Result<Vec<DataBlock>> {
+    let ctx = session.create_context().await?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let interpreter = InterpreterFactory::get(ctx, plan)?;
+    interpreter.execute().await?.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_session_tenant() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session1 = sessions.create_session("TestSession")?;
+    let session2 = sessions.create_session("TestSession")?;
+    session2.set_tenant("tenant2".to_string());
+
+    execute(&session1, "create database db1").await?;
+    execute(&session1, "create table db1.t(a bigint) Engine = Memory").await?;
+    execute(&session2, "create database db2").await?;
+    execute(&session2, "create table db2.t(a bigint) Engine = Memory").await?;
+    execute(&session2, "insert into db2.t values(1)").await?;
+
+    // The databases of the other tenants are never listed.
+    let query = "select name from system.databases";
+    let result = execute(&session1, query).await?;
+    common_datablocks::assert_blocks_sorted_eq(
+        vec![
+            "+---------+",
+            "| name    |",
+            "+---------+",
+            "| db1     |",
+            "| default |",
+            "| system  |",
+            "+---------+",
+        ],
+        result.as_slice(),
+    );
+    let result = execute(&session2, query).await?;
+    common_datablocks::assert_blocks_sorted_eq(
+        vec![
+            "+--------+",
+            "| name   |",
+            "+--------+",
+            "| db2    |",
+            "| system |",
+            "+--------+",
+        ],
+        result.as_slice(),
+    );
+
+    // Nor accessed.
+    assert!(execute(&session1, "select * from db2.t").await.is_err());
+    assert!(execute(&session2, "select * from db1.t").await.is_err());
+    assert!(execute(&session2, "use db1").await.is_err());
+    assert!(
+        execute(&session2, "create table db1.t2(a bigint) Engine = Memory")
+            .await
+            .is_err()
+    );
+    assert!(execute(&session2, "drop table db1.t").await.is_err());
+    assert!(execute(&session2, "drop database db1").await.is_err());
+    execute(&session2, "drop database if exists db1").await?;
+    execute(&session1, "select * from db1.t").await?;
+    execute(&session2, "select * from db2.t").await?;
+
+    // Neither the tenant nor the disk of the server is given by the options.
+    assert!(execute(&session1, "create database db3 tenant = 'tenant2'")
+        .await
+        .is_err());
+    assert!(execute(
+        &session2,
+        "create database db3 storage_disk_data_path = '/'"
+    )
+    .await
+    .is_err());
+    assert!(execute(
+        &session1,
+        "create database db3 storage_disk_data_path = '/tmp'"
+    )
+    .await
+    .is_err());
+    execute(
+        &session1,
+        "create database db3 storage_disk_data_path = 'db3'",
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn execute_with_quota(session: &SessionRef, query: &str) -> Result<Vec<DataBlock>> {
+    let ctx = session.create_context().await?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+    let stream = ctx.try_create_result_quota(interpreter.execute().await?)?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_session_quota() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+    let quota_exceeded = ErrorCode::QuotaExceeded("").code();
+
+    execute_with_quota(&session, "select * from numbers_mt(10000)").await?;
+
+    // The quota of the user.
+    session.set_user_quota(UserQuota {
+        max_rows_returned: 100,
+        max_bytes_scanned: 0,
+        max_result_bytes: 0,
+    });
+    let result = execute_with_quota(&session, "select * from numbers_mt(10000)").await;
+    assert_eq!(result.unwrap_err().code(), quota_exceeded);
+    execute_with_quota(&session, "select * from numbers_mt(10000) limit 100").await?;
+
+    // The settings lower the quota, but never raise it.
+    execute(&session, "set max_rows_returned = 10000").await?;
+    let result = execute_with_quota(&session, "select * from numbers_mt(10000)").await;
+    assert_eq!(result.unwrap_err().code(), quota_exceeded);
+    execute(&session, "set max_rows_returned = 10").await?;
+    let result = execute_with_quota(&session, "select * from numbers_mt(10000) limit 100").await;
+    assert_eq!(result.unwrap_err().code(), quota_exceeded);
+
+    // The scans are limited even if the result is small.
+    session.set_user_quota(UserQuota {
+        max_rows_returned: 0,
+        max_bytes_scanned: 1024,
+        max_result_bytes: 0,
+    });
+    let result = execute(&session, "select sum(number) from numbers_mt(10000)").await;
+    assert_eq!(result.unwrap_err().code(), quota_exceeded);
+    execute(&session, "select sum(number) from numbers_mt(10)").await?;
+
+    Ok(())
+}
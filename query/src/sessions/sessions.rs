@@ -16,11 +16,17 @@ use std::collections::hash_map::Entry::Occupied;
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::HashMap;
 use std::future::Future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 use common_base::tokio;
 use common_base::SignalStream;
+use common_dal::IOScheduler;
+use common_dal::KeyProvider;
+use common_dal::Throttle;
+use common_dal::ThrottleLimits;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
@@ -32,18 +38,53 @@ use crate::catalogs::impls::DatabaseCatalog;
 use crate::clusters::ClusterDiscovery;
 use crate::clusters::ClusterDiscoveryRef;
 use crate::configs::Config;
+use crate::connections::ConnectionManager;
+use crate::connections::ConnectionManagerRef;
+use crate::datasources::common::build_key_provider;
+use crate::datasources::table::fuse::ColumnCache;
+use crate::functions::TenantFunctions;
+use crate::functions::TenantFunctionsRef;
+use crate::loads::LoadManager;
+use crate::loads::LoadManagerRef;
+use crate::pipes::PipeManager;
+use crate::pipes::PipeManagerRef;
+use crate::purges::PurgeManager;
+use crate::purges::PurgeManagerRef;
+use crate::sessions::parse_query_log_table;
 use crate::sessions::session::Session;
 use crate::sessions::session_ref::SessionRef;
+use crate::sessions::AdmissionController;
+use crate::sessions::MemoryTracker;
+use crate::sessions::QueryCache;
+use crate::sessions::QueryLog;
+use crate::sessions::QueryPages;
 use crate::users::UserManager;
 use crate::users::UserManagerRef;
 
+// The started and finished queries kept by the query log of a node.
+const QUERY_LOG_CAPACITY: usize = 1024;
+
 pub struct SessionManager {
-    pub(in crate::sessions) conf: Config,
+    pub(in crate::sessions) conf: RwLock<Config>,
     pub(in crate::sessions) discovery: ClusterDiscoveryRef,
     pub(in crate::sessions) catalog: Arc<DatabaseCatalog>,
     pub(in crate::sessions) user: UserManagerRef,
-
-    pub(in crate::sessions) max_sessions: usize,
+    pub(in crate::sessions) pipes: PipeManagerRef,
+    pub(in crate::sessions) functions: TenantFunctionsRef,
+    pub(in crate::sessions) loads: LoadManagerRef,
+    pub(in crate::sessions) purges: PurgeManagerRef,
+    pub(in crate::sessions) connections: ConnectionManagerRef,
+    pub(in crate::sessions) query_cache: Arc<QueryCache>,
+    pub(in crate::sessions) query_log: Arc<QueryLog>,
+    pub(in crate::sessions) query_pages: Arc<QueryPages>,
+    pub(in crate::sessions) io_scheduler: Arc<IOScheduler>,
+    pub(in crate::sessions) io_throttle: Arc<Throttle>,
+    pub(in crate::sessions) key_provider: Option<Arc<dyn KeyProvider>>,
+    pub(in crate::sessions) column_cache: Arc<ColumnCache>,
+    pub(in crate::sessions) memory_tracker: Arc<MemoryTracker>,
+    pub(in crate::sessions) admission: Arc<AdmissionController>,
+
+    pub(in crate::sessions) max_sessions: AtomicUsize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
 }
 
@@ -59,19 +100,93 @@ impl SessionManager {
         // User manager and init the default users.
         let user = UserManager::create_global(conf.clone()).await?;
 
+        // Pipe manager, the workers are started when the server is ready.
+        let pipes = PipeManager::create_global(conf.clone()).await?;
+
+        // Functions of the tenants, created by `CREATE FUNCTION ns.name`.
+        let functions = TenantFunctions::create_global(conf.clone()).await?;
+
+        // Load manager, keeps the progress of the COPY statements.
+        let loads = LoadManager::create_global(conf.clone()).await?;
+
+        // Purge manager, keeps the tasks removing the data of the dropped tables.
+        let purges = PurgeManager::create_global(conf.clone()).await?;
+
+        // Connection manager, keeps the credentials of the storages encrypted in the meta service.
+        let connections = ConnectionManager::create_global(conf.clone()).await?;
+
+        // Storage requests of all the sessions, the background jobs yield to the queries.
+        let io_scheduler = Arc::new(IOScheduler::create(
+            conf.storage.io_background_max_requests as usize,
+            Duration::from_millis(conf.storage.io_background_max_wait_ms),
+        ));
+
+        // Storage throughput of all the sessions and background jobs of the node.
+        let io_throttle = Throttle::create(ThrottleLimits {
+            bytes_per_second: conf.storage.io_max_bytes_per_second,
+            requests_per_second: conf.storage.io_max_requests_per_second,
+        });
+
+        // Keys of the encrypted storage, the invalid keys fail the startup.
+        let encryption = &conf.storage.encryption;
+        let key_provider = build_key_provider(&encryption.keys, &encryption.key_command)?;
+
+        // Decoded columns of the fuse tables, shared by the queries.
+        let column_cache = ColumnCache::create(conf.storage.column_cache_size_mb * 1024 * 1024);
+
+        // Results of the repeated queries, shared by the sessions.
+        let query_cache = QueryCache::create(conf.query.result_cache_size_mb * 1024 * 1024);
+
+        // The latest queries, appended to a FUSE table too if it is configured.
+        let persisted = parse_query_log_table(&conf.query.query_log_table)?.is_some();
+        let query_log = QueryLog::create(QUERY_LOG_CAPACITY, persisted);
+
+        // Bytes held by the queries, the trackers of the queries are its children.
+        let memory_tracker = MemoryTracker::create_with_limit(
+            "max_server_memory_usage_mb",
+            conf.query.max_server_memory_usage_mb as usize * 1024 * 1024,
+        );
+
+        // The pages of the HTTP query results, counted by the tracker of the server.
+        let query_pages = QueryPages::create(
+            &memory_tracker,
+            conf.query.query_pages_size_mb as usize * 1024 * 1024,
+        );
+
+        // Running queries of the clients, the queries over the limits are queued.
+        let admission = AdmissionController::create(
+            conf.query.max_running_queries as usize,
+            conf.query.max_running_queries_per_user as usize,
+            Duration::from_secs(conf.query.queued_query_timeout_secs),
+        );
+
         let max_active_sessions = conf.query.max_active_sessions as usize;
         Ok(Arc::new(SessionManager {
             catalog,
-            conf,
+            conf: RwLock::new(conf),
             discovery,
             user,
-            max_sessions: max_active_sessions,
+            pipes,
+            functions,
+            loads,
+            purges,
+            connections,
+            query_cache,
+            query_log,
+            query_pages,
+            io_scheduler,
+            io_throttle,
+            key_provider,
+            column_cache,
+            memory_tracker,
+            admission,
+            max_sessions: AtomicUsize::new(max_active_sessions),
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_active_sessions))),
         }))
     }
 
-    pub fn get_conf(&self) -> &Config {
-        &self.conf
+    pub fn get_conf(&self) -> Config {
+        self.conf.read().clone()
     }
 
     pub fn get_cluster_discovery(self: &Arc<Self>) -> ClusterDiscoveryRef {
@@ -83,21 +198,76 @@ impl SessionManager {
         self.user.clone()
     }
 
+    pub fn get_pipe_manager(self: &Arc<Self>) -> PipeManagerRef {
+        self.pipes.clone()
+    }
+
+    pub fn get_tenant_functions(self: &Arc<Self>) -> TenantFunctionsRef {
+        self.functions.clone()
+    }
+
+    pub fn get_load_manager(self: &Arc<Self>) -> LoadManagerRef {
+        self.loads.clone()
+    }
+
+    pub fn get_purge_manager(self: &Arc<Self>) -> PurgeManagerRef {
+        self.purges.clone()
+    }
+
+    pub fn get_connection_manager(self: &Arc<Self>) -> ConnectionManagerRef {
+        self.connections.clone()
+    }
+
     pub fn get_catalog(self: &Arc<Self>) -> Arc<DatabaseCatalog> {
         self.catalog.clone()
     }
 
+    pub fn get_query_cache(self: &Arc<Self>) -> Arc<QueryCache> {
+        self.query_cache.clone()
+    }
+
+    pub fn get_query_log(self: &Arc<Self>) -> Arc<QueryLog> {
+        self.query_log.clone()
+    }
+
+    pub fn get_query_pages(self: &Arc<Self>) -> Arc<QueryPages> {
+        self.query_pages.clone()
+    }
+
+    pub fn get_io_scheduler(self: &Arc<Self>) -> Arc<IOScheduler> {
+        self.io_scheduler.clone()
+    }
+
+    pub fn get_io_throttle(self: &Arc<Self>) -> Arc<Throttle> {
+        self.io_throttle.clone()
+    }
+
+    pub fn get_key_provider(self: &Arc<Self>) -> Option<Arc<dyn KeyProvider>> {
+        self.key_provider.clone()
+    }
+
+    pub fn get_column_cache(self: &Arc<Self>) -> Arc<ColumnCache> {
+        self.column_cache.clone()
+    }
+
+    pub fn get_memory_tracker(self: &Arc<Self>) -> Arc<MemoryTracker> {
+        self.memory_tracker.clone()
+    }
+
+    pub fn get_admission_controller(self: &Arc<Self>) -> Arc<AdmissionController> {
+        self.admission.clone()
+    }
+
     pub fn create_session(self: &Arc<Self>, typ: impl Into<String>) -> Result<SessionRef> {
         counter!(super::metrics::METRIC_SESSION_CONNECT_NUMBERS, 1);
 
         let mut sessions = self.active_sessions.write();
-        match sessions.len() == self.max_sessions {
+        match sessions.len() >= self.max_sessions.load(Ordering::Relaxed) {
             true => Err(ErrorCode::TooManyUserConnections(
                 "The current accept connection has exceeded mysql_handler_thread_num config",
             )),
             false => {
                 let session = Session::try_create(
-                    self.conf.clone(),
                     uuid::Uuid::new_v4().to_string(),
                     typ.into(),
                     self.clone(),
@@ -119,7 +289,6 @@ impl SessionManager {
             Vacant(_) if aborted => return Err(ErrorCode::AbortedSession("Aborting server.")),
             Vacant(entry) => {
                 let session = Session::try_create(
-                    self.conf.clone(),
                     entry.key().clone(),
                     String::from("RPCSession"),
                     self.clone(),
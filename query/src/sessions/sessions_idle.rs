@@ -0,0 +1,49 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::sessions::SessionManager;
+
+impl SessionManager {
+    /// Closes the sessions which have not run any query for longer than `timeout`,
+    /// returns the number of the closed sessions.
+    /// The connection is shut down and the session state (temporary tables, functions) is
+    /// released right away, without waiting for the client which may never come back.
+    pub fn destroy_expired_sessions(self: &Arc<Self>, timeout: Duration) -> usize {
+        // Collect first, destroying a session takes the write lock.
+        let expired = self
+            .active_sessions
+            .read()
+            .values()
+            .filter(|session| matches!(session.get_idle_duration(), Some(idle) if idle > timeout))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for session in &expired {
+            log::info!(
+                "Close session {} of {}, idle for more than {:?}",
+                session.get_id(),
+                session.get_type(),
+                timeout
+            );
+            session.force_kill_session();
+            session.get_temporary_tables().clear();
+            session.get_session_functions().clear();
+            self.destroy_session(&session.get_id());
+        }
+        expired.len()
+    }
+}
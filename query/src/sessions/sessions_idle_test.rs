@@ -0,0 +1,47 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::tokio;
+use common_exception::Result;
+
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_destroy_expired_sessions() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+    let id = session.get_id();
+
+    // The session is not idle while it runs a query.
+    let ctx = session.create_context().await?;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(session.get_idle_duration(), None);
+    assert_eq!(sessions.destroy_expired_sessions(Duration::ZERO), 0);
+    drop(ctx);
+
+    assert_eq!(
+        sessions.destroy_expired_sessions(Duration::from_secs(3600)),
+        0
+    );
+    assert!(sessions.get_session(&id).is_some());
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(sessions.destroy_expired_sessions(Duration::ZERO), 1);
+    assert!(sessions.get_session(&id).is_none());
+    assert!(session.is_aborting());
+
+    Ok(())
+}
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use crate::sessions::NodeProcessInfo;
 use crate::sessions::ProcessInfo;
 use crate::sessions::Session;
 use crate::sessions::SessionManager;
@@ -26,4 +27,13 @@ impl SessionManager {
             .map(Session::process_info)
             .collect::<Vec<_>>()
     }
+
+    /// The processes of this node, tagged with the node id.
+    pub fn node_processes_info(self: &Arc<Self>) -> Vec<NodeProcessInfo> {
+        let node = self.discovery.local_id();
+        self.processes_info()
+            .iter()
+            .map(|process_info| process_info.to_node_process_info(&node))
+            .collect::<Vec<_>>()
+    }
 }
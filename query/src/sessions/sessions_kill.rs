@@ -0,0 +1,46 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use crate::sessions::SessionManager;
+
+impl SessionManager {
+    /// Kills the query `query_id` on this node, returns whether the node ran it.
+    /// The stages of a cluster query run in the RPC sessions named after the query id, the
+    /// query itself runs in the session of the client, which is kept unless it is of `tenant`.
+    pub fn kill_query(self: &Arc<Self>, tenant: &str, query_id: &str) -> bool {
+        let running = self
+            .active_sessions
+            .read()
+            .values()
+            .filter(|session| match session.get_type().as_str() {
+                "RPCSession" => session.get_id() == query_id,
+                _ => {
+                    session.get_tenant() == tenant
+                        && session.get_query_id().as_deref() == Some(query_id)
+                }
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for session in &running {
+            match session.get_type().as_str() {
+                "RPCSession" => session.force_kill_session(),
+                _ => session.force_kill_query(),
+            }
+        }
+        !running.is_empty()
+    }
+}
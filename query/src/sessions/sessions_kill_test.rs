@@ -0,0 +1,44 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_kill_query() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+    let tenant = session.get_tenant();
+
+    let ctx = session.create_context().await?;
+    let query_id = ctx.get_id();
+    assert!(!sessions.kill_query(&tenant, "unknown_query_id"));
+    assert!(!sessions.kill_query("other_tenant", &query_id));
+    assert!(session.get_idle_duration().is_none());
+
+    // The query is killed, the session is kept.
+    assert!(sessions.kill_query(&tenant, &query_id));
+    assert!(session.get_idle_duration().is_some());
+    assert!(!session.is_aborting());
+    drop(ctx);
+
+    // The stage of a cluster query.
+    let rpc_session = sessions.create_rpc_session("query_id_1".to_string(), false)?;
+    assert!(sessions.kill_query(&tenant, "query_id_1"));
+    assert!(rpc_session.is_aborting());
+
+    Ok(())
+}
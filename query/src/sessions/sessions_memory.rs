@@ -0,0 +1,83 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_mem_allocator::malloc_stats;
+use common_mem_allocator::MallocStat;
+
+use crate::sessions::SessionManager;
+
+impl SessionManager {
+    /// The statistics of the allocator, followed by the memory of the node by subsystem and the
+    /// bytes held by each running query.
+    pub fn memory_usage(self: &Arc<Self>) -> Vec<MallocStat> {
+        let mut stats = malloc_stats();
+
+        let sessions = self
+            .active_sessions
+            .read()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        let sessions_bytes = sessions
+            .iter()
+            .map(|session| session.get_memory_usage())
+            .sum::<usize>();
+
+        stats.extend(vec![
+            MallocStat::create(
+                "memory.column_cache",
+                self.column_cache.size(),
+                "Bytes of the decoded columns in the column cache",
+            ),
+            MallocStat::create(
+                "memory.query_cache",
+                self.query_cache.size(),
+                "Bytes of the query results in the query cache",
+            ),
+            MallocStat::create(
+                "memory.query_pages",
+                self.query_pages.size() as u64,
+                "Bytes of the HTTP query result pages not read yet",
+            ),
+            MallocStat::create(
+                "memory.sessions",
+                sessions_bytes as u64,
+                "Bytes of the states of the active sessions",
+            ),
+            MallocStat::create(
+                "memory.executor_buffers",
+                self.memory_tracker.used() as u64,
+                "Bytes held by the operators of the running queries and by the query pages",
+            ),
+            MallocStat::create(
+                "memory.executor_buffers_peak",
+                self.memory_tracker.peak() as u64,
+                "The most bytes held by the operators of the queries at once",
+            ),
+        ]);
+
+        for session in &sessions {
+            if let Some((query_id, bytes)) = session.get_query_memory_usage() {
+                stats.push(MallocStat::create(
+                    format!("memory.query.{}", query_id),
+                    bytes as u64,
+                    "Bytes held by the operators of the running query",
+                ));
+            }
+        }
+        stats
+    }
+}
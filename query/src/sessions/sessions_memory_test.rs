@@ -0,0 +1,52 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_memory_usage() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+    let tracked = {
+        let mut tracked = ctx.get_memory_tracker().tracked();
+        tracked.set(1024);
+        tracked
+    };
+
+    let stats = sessions.memory_usage();
+    let stat = |name: &str| {
+        stats
+            .iter()
+            .find(|stat| stat.name == name)
+            .map(|stat| stat.value)
+    };
+    assert!(stat("jemalloc.stats.allocated").unwrap() > 0);
+    assert!(stat("memory.sessions").unwrap() > 0);
+    assert_eq!(stat("memory.executor_buffers"), Some(1024));
+    assert_eq!(stat(&format!("memory.query.{}", ctx.get_id())), Some(1024));
+
+    // The query is done.
+    drop(tracked);
+    drop(ctx);
+    let stats = sessions.memory_usage();
+    assert!(stats
+        .iter()
+        .all(|stat| !stat.name.starts_with("memory.query.")));
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_dal::IOPriority;
+use common_exception::Result;
+use common_meta_types::TableInfo;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::SessionManager;
+
+/// The staged segments younger than this may belong to a commit which is not done yet.
+const STAGED_SEGMENT_GRACE_SECS: u64 = 3600;
+
+impl SessionManager {
+    /// Removes the data of the dropped tables, returns the number of the removed objects.
+    /// A failed task keeps its error and is tried again by the next round. The nodes running
+    /// the same task conflict on its seq, the losers stop until the next round.
+    pub async fn purge_dropped_tables(self: &Arc<Self>) -> Result<u64> {
+        let purges = self.get_purge_manager();
+        let tasks = purges.get_tasks()?;
+        if tasks.iter().all(|(_, task)| task.is_finished()) {
+            return Ok(0);
+        }
+
+        let session = self.create_session("Purge")?;
+        session.set_io_priority(IOPriority::Background);
+        let ctx = session.create_context().await?;
+        let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+
+        let mut removed = 0;
+        for (seq, task) in tasks {
+            if task.is_finished() {
+                continue;
+            }
+
+            // Only the options are needed to locate the data.
+            let table = FuseTable {
+                table_info: TableInfo {
+                    table_id: task.table_id,
+                    db: task.db.clone(),
+                    name: task.table.clone(),
+                    engine: "FUSE".to_string(),
+                    options: task.table_options.clone(),
+                    ..Default::default()
+                },
+            };
+
+            let removed_before = task.removed_objects;
+            let mut saved = (seq, task.clone());
+            let mut progress = task;
+            progress.last_error = None;
+            let res = table
+                .do_purge(io_ctx.clone(), &mut progress, |to_save| {
+                    saved.0 = purges.update_task(to_save.clone(), saved.0)?;
+                    saved.1 = to_save.clone();
+                    Ok(())
+                })
+                .await;
+
+            let (seq, mut saved_task) = saved;
+            removed += saved_task.removed_objects - removed_before;
+            if let Err(cause) = res {
+                log::warn!(
+                    "Cannot purge the data of the dropped table {}.{}, cause {}",
+                    saved_task.db,
+                    saved_task.table,
+                    cause
+                );
+                saved_task.last_error = Some(cause.to_string());
+                if let Err(cause) = purges.update_task(saved_task, seq) {
+                    log::warn!("Cannot save the purge error, cause {}", cause);
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Cleans up the segments staged by the abandoned commits to the fuse tables, returns the
+    /// number of the removed objects.
+    pub async fn gc_staged_segments(self: &Arc<Self>) -> Result<u64> {
+        let session = self.create_session("StagedGC")?;
+        session.set_io_priority(IOPriority::Background);
+        let ctx = session.create_context().await?;
+        let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+        let catalog = ctx.get_catalog();
+
+        let mut removed = 0;
+        for db in catalog.get_databases()? {
+            for table in catalog.get_tables(db.name())? {
+                let fuse_table = match table.as_any().downcast_ref::<FuseTable>() {
+                    None => continue,
+                    Some(fuse_table) => fuse_table,
+                };
+
+                match fuse_table
+                    .do_gc_staged(io_ctx.clone(), STAGED_SEGMENT_GRACE_SECS)
+                    .await
+                {
+                    Ok(n) => removed += n,
+                    Err(cause) => log::warn!(
+                        "Cannot clean up the staged segments of table {}.{}, cause {}",
+                        db.name(),
+                        table.name(),
+                        cause
+                    ),
+                }
+            }
+        }
+        Ok(removed)
+    }
+}
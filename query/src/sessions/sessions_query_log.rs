@@ -0,0 +1,201 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_dal::IOPriority;
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+use common_planners::DatabaseOptions;
+use common_planners::InsertIntoPlan;
+use common_planners::TableOptions;
+use common_streams::DataBlockStream;
+use futures::TryStreamExt;
+
+use crate::catalogs::Table;
+use crate::catalogs::ToReadDataSourcePlan;
+use crate::interpreters::CreateDatabaseInterpreter;
+use crate::interpreters::CreateTableInterpreter;
+use crate::interpreters::InsertIntoInterpreter;
+use crate::interpreters::Interpreter;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::QueryLogEntry;
+use crate::sessions::SessionManager;
+
+/// The database and the table of `query.query_log_table`, none if the query log is not persisted.
+pub fn parse_query_log_table(query_log_table: &str) -> Result<Option<(String, String)>> {
+    if query_log_table.is_empty() {
+        return Ok(None);
+    }
+
+    match query_log_table.split_once('.') {
+        Some((database, table))
+            if !database.is_empty() && !table.is_empty() && !table.contains('.') =>
+        {
+            Ok(Some((database.to_string(), table.to_string())))
+        }
+        _ => Err(ErrorCode::BadArguments(format!(
+            "Invalid query log table: {}, expect database.table",
+            query_log_table
+        ))),
+    }
+}
+
+impl SessionManager {
+    /// Appends the entries of the query log taken since the last flush to the FUSE table of
+    /// `query.query_log_table`, which is created if it doesn't exist. Returns the number of the
+    /// entries appended, the entries are lost if they can't be appended.
+    pub async fn flush_query_log(self: &Arc<Self>) -> Result<usize> {
+        let query_log_table = self.get_conf().query.query_log_table;
+        let (database, table_name) = match parse_query_log_table(&query_log_table)? {
+            None => return Ok(0),
+            Some(database_and_table) => database_and_table,
+        };
+
+        let entries = self.get_query_log().take_unpersisted();
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        // The contexts of this session are not logged, or the flushes would log themselves.
+        let session = self.create_session("QueryLogWriter")?;
+        session.set_io_priority(IOPriority::Background);
+        let ctx = session.create_context().await?;
+        let table = match Self::get_query_log_table(&ctx, &database, &table_name)? {
+            Some(table) => table,
+            None => Self::create_query_log_table(&ctx, &database, &table_name).await?,
+        };
+
+        let block = QueryLogEntry::to_block(&entries);
+        let plan = InsertIntoPlan {
+            db_name: database,
+            tbl_name: table_name,
+            tbl_id: table.get_id(),
+            schema: table.schema(),
+            select_plan: None,
+            input_stream: InsertIntoPlan::empty_stream(),
+        };
+        plan.set_input_stream(Box::pin(DataBlockStream::create(
+            block.schema().clone(),
+            None,
+            vec![block],
+        )));
+        InsertIntoInterpreter::try_create(ctx, plan)?
+            .execute()
+            .await?;
+        Ok(entries.len())
+    }
+
+    /// The entries of the tenant in the FUSE table of `query.query_log_table`, none if the query
+    /// log is not persisted.
+    pub async fn read_persisted_query_log(
+        self: &Arc<Self>,
+        tenant: &str,
+    ) -> Result<Option<Vec<DataBlock>>> {
+        let query_log_table = self.get_conf().query.query_log_table;
+        let (database, table_name) = match parse_query_log_table(&query_log_table)? {
+            None => return Ok(None),
+            Some(database_and_table) => database_and_table,
+        };
+
+        // The table belongs to the default tenant, the session reading it is not the one of the
+        // query, whose tenant may not reach the table.
+        let session = self.create_session("QueryLogReader")?;
+        let ctx = session.create_context().await?;
+        let table = match Self::get_query_log_table(&ctx, &database, &table_name)? {
+            Some(table) => table,
+            None => return Ok(Some(vec![])),
+        };
+
+        let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+        let source_plan = table.read_plan(io_ctx.clone(), None, None)?;
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+        let stream = table.read(io_ctx, &source_plan.push_downs).await?;
+        let blocks = stream.try_collect::<Vec<_>>().await?;
+
+        let mut tenant_blocks = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let tenants = block.try_column_by_name("tenant")?.to_array()?;
+            let predicate = tenants
+                .string()?
+                .into_no_null_iter()
+                .map(|block_tenant| block_tenant == tenant.as_bytes())
+                .collect::<Vec<_>>();
+            tenant_blocks.push(DataBlock::filter_block(&block, Series::new(predicate))?);
+        }
+        Ok(Some(tenant_blocks))
+    }
+
+    fn get_query_log_table(
+        ctx: &DatabendQueryContextRef,
+        database: &str,
+        table_name: &str,
+    ) -> Result<Option<Arc<dyn Table>>> {
+        let table = match ctx.get_table(database, table_name) {
+            Ok(table) => table,
+            Err(cause)
+                if cause.code() == ErrorCode::UnknownDatabase("").code()
+                    || cause.code() == ErrorCode::UnknownTable("").code() =>
+            {
+                return Ok(None)
+            }
+            Err(cause) => return Err(cause),
+        };
+
+        // The rows of another table would be listed as the queries.
+        if table.schema() != QueryLogEntry::schema() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Table {}.{} does not have the schema of system.query_log",
+                database, table_name
+            )));
+        }
+        Ok(Some(table))
+    }
+
+    async fn create_query_log_table(
+        ctx: &DatabendQueryContextRef,
+        database: &str,
+        table_name: &str,
+    ) -> Result<Arc<dyn Table>> {
+        let plan = CreateDatabasePlan {
+            if_not_exists: true,
+            db: database.to_string(),
+            options: DatabaseOptions::new(),
+            tables: vec![],
+        };
+        CreateDatabaseInterpreter::try_create(ctx.clone(), plan)?
+            .execute()
+            .await?;
+
+        let plan = CreateTablePlan {
+            if_not_exists: true,
+            db: database.to_string(),
+            table: table_name.to_string(),
+            schema: QueryLogEntry::schema(),
+            engine: "FUSE".to_string(),
+            options: TableOptions::new(),
+            temporary: false,
+            as_select: None,
+        };
+        CreateTableInterpreter::try_create(ctx.clone(), plan)?
+            .execute()
+            .await?;
+        ctx.get_table(database, table_name)
+    }
+}
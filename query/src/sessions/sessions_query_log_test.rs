@@ -0,0 +1,108 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use chrono::TimeZone;
+use chrono::Utc;
+use common_base::tokio;
+use common_exception::Result;
+use tempfile::TempDir;
+
+use crate::configs::Config;
+use crate::sessions::parse_query_log_table;
+use crate::sessions::QueryLogEntry;
+use crate::sessions::QueryLogStatus;
+
+fn create_entry(query_id: &str, tenant: &str, status: QueryLogStatus) -> QueryLogEntry {
+    QueryLogEntry {
+        status,
+        event_time: Utc.timestamp(1630000000, 0),
+        query_id: query_id.to_string(),
+        tenant: tenant.to_string(),
+        user: "root".to_string(),
+        query_tag: "etl".to_string(),
+        query_text: "select * from t".to_string(),
+        scan_rows: 10,
+        scan_bytes: 80,
+        cpu_time: Duration::from_millis(3),
+        duration: Duration::from_millis(5),
+        error: "".to_string(),
+    }
+}
+
+#[test]
+fn test_parse_query_log_table() -> Result<()> {
+    assert_eq!(parse_query_log_table("")?, None);
+    assert_eq!(
+        parse_query_log_table("history.query_log")?,
+        Some(("history".to_string(), "query_log".to_string()))
+    );
+
+    for invalid in ["query_log", ".query_log", "history.", "a.b.c"] {
+        let result = parse_query_log_table(invalid);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().message(),
+            format!(
+                "Invalid query log table: {}, expect database.table",
+                invalid
+            )
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flush_query_log() -> Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    config.query.query_log_table = "history.query_log".to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+    let sessions = ctx.get_sessions_manager();
+    let tenant = ctx.get_tenant();
+
+    // Nothing to append, the table is not created.
+    assert_eq!(sessions.flush_query_log().await?, 0);
+    let blocks = sessions.read_persisted_query_log(&tenant).await?;
+    assert_eq!(blocks.map(|blocks| blocks.len()), Some(0));
+
+    let query_log = sessions.get_query_log();
+    query_log.append(create_entry("q1", &tenant, QueryLogStatus::Start));
+    query_log.append(create_entry("q1", &tenant, QueryLogStatus::Finish));
+    // The queries of the other tenants are never listed.
+    query_log.append(create_entry("q2", "tenant2", QueryLogStatus::Start));
+    assert_eq!(sessions.flush_query_log().await?, 3);
+    assert_eq!(sessions.flush_query_log().await?, 0);
+
+    let mut failed = create_entry("q3", &tenant, QueryLogStatus::Error);
+    failed.error = "Code: 25, displayText = Unknown table: 't'.".to_string();
+    query_log.append(failed);
+    assert_eq!(sessions.flush_query_log().await?, 1);
+
+    let blocks = sessions.read_persisted_query_log(&tenant).await?.unwrap();
+    let expected = vec![
+        "+----------+--------+------------+--------+------+-----------+-----------------+-----------+------------+-------------+-------------+---------------------------------------------+",
+        "| query_id | status | event_time | tenant | user | query_tag | query_text      | scan_rows | scan_bytes | cpu_time_ms | duration_ms | error                                       |",
+        "+----------+--------+------------+--------+------+-----------+-----------------+-----------+------------+-------------+-------------+---------------------------------------------+",
+        "| q1       | Finish | 1630000000 |        | root | etl       | select * from t | 10        | 80         | 3           | 5           |                                             |",
+        "| q1       | Start  | 1630000000 |        | root | etl       | select * from t | 10        | 80         | 3           | 5           |                                             |",
+        "| q3       | Error  | 1630000000 |        | root | etl       | select * from t | 10        | 80         | 3           | 5           | Code: 25, displayText = Unknown table: 't'. |",
+        "+----------+--------+------------+--------+------+-----------+-----------------+-----------+------------+-------------+-------------+---------------------------------------------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+    Ok(())
+}
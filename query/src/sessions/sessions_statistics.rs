@@ -0,0 +1,83 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_dal::IOPriority;
+use common_exception::Result;
+use common_planners::AnalyzeTablePlan;
+use futures::TryStreamExt;
+
+use crate::catalogs::Catalog;
+use crate::datasources::common::TableStatistics;
+use crate::interpreters::AnalyzeTableInterpreter;
+use crate::interpreters::Interpreter;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::SessionManager;
+use crate::sql::DEFAULT_HISTOGRAM_BUCKETS;
+
+impl SessionManager {
+    /// Analyzes again the tables whose statistics are stale, that is the rows changed since
+    /// the last ANALYZE exceed `query.statistics_refresh_changed_ratio` of the rows analyzed.
+    /// The histograms are rebuilt for the same columns. Returns the number of the tables
+    /// analyzed, a table failing to be analyzed does not stop the others.
+    pub async fn refresh_table_statistics(self: &Arc<Self>) -> Result<usize> {
+        let changed_ratio = self.get_conf().query.statistics_refresh_changed_ratio;
+        let session = self.create_session("StatisticsRefresh")?;
+        session.set_io_priority(IOPriority::Background);
+        let catalog = self.get_catalog();
+
+        let mut refreshed = 0;
+        for db in catalog.get_databases()? {
+            for table in catalog.get_tables(db.name())? {
+                let options = &table.get_table_info().options;
+                let statistics = match TableStatistics::from_options(options) {
+                    Ok(Some(statistics)) => statistics,
+                    _ => continue,
+                };
+                if statistics.changed_ratio(options) < changed_ratio {
+                    continue;
+                }
+
+                let buckets = match statistics.buckets {
+                    0 => DEFAULT_HISTOGRAM_BUCKETS as usize,
+                    buckets => buckets,
+                };
+                let plan = AnalyzeTablePlan {
+                    db: db.name().to_string(),
+                    table: table.name().to_string(),
+                    histogram_columns: statistics.histogram_columns(),
+                    buckets,
+                };
+                let ctx = session.create_context().await?;
+                match Self::analyze_table(ctx, plan).await {
+                    Ok(_) => refreshed += 1,
+                    Err(cause) => log::warn!(
+                        "Cannot refresh the statistics of table {}.{}, cause {}",
+                        db.name(),
+                        table.name(),
+                        cause
+                    ),
+                }
+            }
+        }
+        Ok(refreshed)
+    }
+
+    async fn analyze_table(ctx: DatabendQueryContextRef, plan: AnalyzeTablePlan) -> Result<()> {
+        let interpreter = AnalyzeTableInterpreter::try_create(ctx, plan)?;
+        interpreter.execute().await?.try_collect::<Vec<_>>().await?;
+        Ok(())
+    }
+}
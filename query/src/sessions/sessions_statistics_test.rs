@@ -0,0 +1,86 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::catalogs::Catalog;
+use crate::datasources::common::TableStatistics;
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::SessionRef;
+use crate::sql::PlanParser;
+use crate::tests::SessionManagerBuilder;
+
+async fn execute(session: &SessionRef, query: &str) -> Result<Vec<DataBlock>> {
+    let ctx = session.create_context().await?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let interpreter = InterpreterFactory::get(ctx, plan)?;
+    interpreter.execute().await?.try_collect::<Vec<_>>().await
+}
+
+fn statistics(session: &SessionRef) -> Result<TableStatistics> {
+    let table = session.get_catalog().get_table("default", "t")?;
+    Ok(TableStatistics::from_options(&table.get_table_info().options)?.unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_refresh_table_statistics() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+
+    execute(&session, "create table default.t(a bigint) Engine = Memory").await?;
+    execute(
+        &session,
+        "insert into default.t values(1), (2), (3), (4), (5), (6), (7), (8), (9), (10)",
+    )
+    .await?;
+
+    // The tables never analyzed are not refreshed.
+    assert_eq!(sessions.refresh_table_statistics().await?, 0);
+
+    execute(
+        &session,
+        "analyze table default.t update histogram on a with 4 buckets",
+    )
+    .await?;
+    let stats = statistics(&session)?;
+    assert_eq!(stats.rows, 10);
+    assert_eq!(stats.changed_rows, 0);
+
+    // 1 of 10 rows changed, below the default ratio 0.2.
+    execute(&session, "insert into default.t values(11)").await?;
+    assert_eq!(sessions.refresh_table_statistics().await?, 0);
+    assert_eq!(statistics(&session)?, stats);
+
+    // 3 of 10 rows changed.
+    execute(&session, "insert into default.t values(12), (13)").await?;
+    assert_eq!(sessions.refresh_table_statistics().await?, 1);
+    let stats = statistics(&session)?;
+    assert_eq!(stats.rows, 13);
+    assert_eq!(stats.changed_rows, 3);
+    assert_eq!(stats.buckets, 4);
+    assert_eq!(stats.histogram_columns(), vec!["a".to_string()]);
+    assert_eq!(sessions.refresh_table_statistics().await?, 0);
+
+    // A truncate changes all the rows.
+    execute(&session, "truncate table default.t").await?;
+    assert_eq!(sessions.refresh_table_statistics().await?, 1);
+    let stats = statistics(&session)?;
+    assert_eq!(stats.rows, 0);
+    assert_eq!(stats.changed_rows, 16);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_dal::IOPriority;
+use common_exception::Result;
+
+use crate::catalogs::Catalog;
+use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::SessionManager;
+
+/// The hot copies of the segments moved younger than this may be read by the queries started
+/// before the segments were moved.
+const HOT_COPY_GRACE_SECS: u64 = 3600;
+
+impl SessionManager {
+    /// Applies the storage policies of all the tables, returns the number of the segments moved
+    /// to the cold tier. The hot copies of the segments moved by the former rounds are purged.
+    /// A table failing to apply its policy does not stop the others.
+    pub async fn apply_storage_policies(self: &Arc<Self>) -> Result<usize> {
+        let session = self.create_session("StoragePolicy")?;
+        session.set_io_priority(IOPriority::Background);
+        let ctx = session.create_context().await?;
+        let io_ctx = Arc::new(ctx.get_single_node_table_io_context()?);
+        let catalog = ctx.get_catalog();
+
+        let mut moved = 0;
+        for db in catalog.get_databases()? {
+            for table in catalog.get_tables(db.name())? {
+                let fuse_table = match table.as_any().downcast_ref::<FuseTable>() {
+                    None => continue,
+                    Some(fuse_table) => fuse_table,
+                };
+
+                match fuse_table.do_apply_storage_policy(io_ctx.clone()).await {
+                    Ok(n) => moved += n,
+                    Err(cause) => log::warn!(
+                        "Cannot apply the storage policy of table {}.{}, cause {}",
+                        db.name(),
+                        table.name(),
+                        cause
+                    ),
+                }
+
+                let purged = fuse_table
+                    .do_purge_hot_copies(io_ctx.clone(), HOT_COPY_GRACE_SECS)
+                    .await;
+                if let Err(cause) = purged {
+                    log::warn!(
+                        "Cannot purge the hot copies of table {}.{}, cause {}",
+                        db.name(),
+                        table.name(),
+                        cause
+                    );
+                }
+            }
+        }
+        Ok(moved)
+    }
+}
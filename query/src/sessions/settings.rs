@@ -31,7 +31,29 @@ impl Settings {
         ("max_threads", u64, 16, "The maximum number of threads to execute the request. By default, it is determined automatically."),
         ("flight_client_timeout", u64, 60, "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds"),
         ("min_distributed_rows", u64, 100000000, "Minimum distributed read rows. In cluster mode, when read rows exceeds this value, the local table converted to distributed query."),
-        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.")
+        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query."),
+        ("unquoted_ident_case_sensitive", u64, 1, "Case sensitivity of unquoted identifiers. 0 folds them to lower case, 1 keeps them as written."),
+        ("quoted_ident_case_sensitive", u64, 1, "Case sensitivity of quoted identifiers. 0 folds them to lower case too, so that all the identifiers are compared case-insensitively."),
+        ("optimize_move_to_prewhere", u64, 1, "Read the columns of the most selective cheap conditions of WHERE first in fuse scans, as if they were in PREWHERE."),
+        ("max_rows_returned", u64, 0, "Maximum rows a query returns to the client, 0 means unlimited. The quota of the user is not raised by it."),
+        ("max_bytes_scanned", u64, 0, "Maximum bytes a query reads from the tables, 0 means unlimited. The quota of the user is not raised by it."),
+        ("max_result_bytes", u64, 0, "Maximum bytes of the result a query returns to the client, 0 means unlimited. The quota of the user is not raised by it."),
+        ("lenient_insert_cast", u64, 0, "Cast the values of INSERT VALUES, INSERT SELECT, COPY and pipes leniently. 1 writes NULL for a value which can't be cast to its column type, 0 fails the whole insert."),
+        ("query_tag", String, String::new(), "Tag of the queries, their usage is accounted to it in system.query_log and system.query_tag_usage."),
+        ("function_search_path", String, String::new(), "Namespaces of the functions of the tenant, separated by commas. The unqualified function names are resolved in them before the built-in functions."),
+        ("max_recursive_iterations", u64, 1000, "Maximum iterations of the recursive term of a WITH RECURSIVE query, the query fails if its recursive term still returns rows after them."),
+        ("parquet_row_group_rows", u64, 0, "Maximum rows of a row group of the parquet files written by fuse tables, 0 means a block is written as a single row group. Overridden by the table option of the same name."),
+        ("parquet_page_bytes", u64, 0, "Maximum bytes of a page of the parquet files written by fuse tables, 0 means unlimited. The row groups are split to keep the pages below it. Overridden by the table option of the same name."),
+        ("parquet_dictionary_types", String, String::new(), "Data types whose columns are dictionary encoded in the parquet files written by fuse tables, separated by commas, e.g. 'String,Int32'. Overridden by the table option of the same name."),
+        ("use_query_cache", u64, 1, "Return the cached result of a SELECT if the same query was run on the same snapshots of the FUSE tables with the same settings. 0 always runs the query, the result is not cached either."),
+        ("max_bytes_before_external_group_by", u64, 0, "Spill the states of GROUP BY to the local disk once the aggregations of a query hold more bytes than it, 0 never spills."),
+        ("max_memory_usage", u64, 0, "Fail a query once its operators hold more bytes than it, 0 means unlimited. The states of the aggregations and the blocks buffered by ORDER BY and the window functions are counted."),
+        ("scan_io_concurrency", u64, 0, "Blocks the fuse scans of a query read from the storage at a time on a node, 0 follows max_threads. The reads wait on the storage without holding a thread, so it may be raised far over max_threads for high-latency storages like S3."),
+        ("scan_decode_parallelism", u64, 0, "Sources of a scan on a node, which decode the blocks they read in parallel, 0 follows max_threads. The blocks are decoded on the threads of the query, which max_threads limits."),
+        ("max_storage_io_bytes_per_second", u64, 0, "Maximum bytes a query reads from and writes to the storage a second, 0 means unlimited. The limit of the node applies too."),
+        ("max_storage_io_requests_per_second", u64, 0, "Maximum storage requests a query sends a second, 0 means unlimited. The limit of the node applies too."),
+        ("large_result_rows", u64, 10000000, "Rows of the result of a SELECT without LIMIT estimated from the statistics of the tables, over which large_result_mode applies before the query runs, 0 disables the check."),
+        ("large_result_mode", String, "warn".to_string(), "What to do with a SELECT whose result is estimated over large_result_rows: 'warn' runs it with a warning in the result, 'throw' fails it, the query needs a LIMIT or large_result_mode = 'warn' to run then.")
     }
 
     pub fn try_create() -> Result<Arc<Settings>> {
@@ -45,6 +67,22 @@ impl Settings {
         Ok(settings)
     }
 
+    /// `scan_io_concurrency`, or `max_threads` if it is 0.
+    pub fn get_effective_scan_io_concurrency(&self) -> Result<usize> {
+        match self.get_scan_io_concurrency()? {
+            0 => Ok(self.get_max_threads()? as usize),
+            n => Ok(n as usize),
+        }
+    }
+
+    /// `scan_decode_parallelism`, or `max_threads` if it is 0.
+    pub fn get_effective_scan_decode_parallelism(&self) -> Result<usize> {
+        match self.get_scan_decode_parallelism()? {
+            0 => Ok(self.get_max_threads()? as usize),
+            n => Ok(n as usize),
+        }
+    }
+
     pub fn iter(&self) -> SettingsIterator {
         SettingsIterator {
             settings: self.inner.get_settings(),
@@ -214,21 +252,19 @@ impl SettingsBase {
         )))
     }
 
-    #[allow(unused)]
-    pub fn try_set_string(&self, key: &'static str, val: &str, desc: &str) -> Result<()> {
+    pub fn try_set_string(&self, key: &'static str, val: String, desc: &str) -> Result<()> {
         let mut settings = self.settings.write();
-        let default_value = val;
+        let default_value = val.clone();
         let setting_val = DataValue::Struct(vec![
-            DataValue::String(Some(val.as_bytes().to_vec())),
-            DataValue::String(Some(default_value.as_bytes().to_vec())),
+            DataValue::String(Some(val.into_bytes())),
+            DataValue::String(Some(default_value.into_bytes())),
             DataValue::String(Some(desc.as_bytes().to_vec())),
         ]);
         settings.insert(key, setting_val);
         Ok(())
     }
 
-    #[allow(unused)]
-    pub fn try_update_string(&self, key: &'static str, val: &str) -> Result<()> {
+    pub fn try_update_string(&self, key: &'static str, val: String) -> Result<()> {
         let mut settings = self.settings.write();
         let setting_val = settings
             .get(key)
@@ -236,7 +272,7 @@ impl SettingsBase {
 
         if let DataValue::Struct(values) = setting_val {
             let v = DataValue::Struct(vec![
-                DataValue::String(Some(val.as_bytes().to_vec())),
+                DataValue::String(Some(val.into_bytes())),
                 values[1].clone(),
                 values[2].clone(),
             ]);
@@ -245,8 +281,7 @@ impl SettingsBase {
         Ok(())
     }
 
-    #[allow(unused)]
-    pub fn try_get_string(&self, key: &str) -> Result<Vec<u8>> {
+    pub fn try_get_string(&self, key: &str) -> Result<String> {
         let settings = self.settings.read();
         let setting_val = settings
             .get(key)
@@ -254,7 +289,7 @@ impl SettingsBase {
 
         if let DataValue::Struct(values) = setting_val {
             if let DataValue::String(Some(result)) = values[0].clone() {
-                return Ok(result);
+                return Ok(String::from_utf8(result)?);
             }
         }
 
@@ -16,15 +16,22 @@
 mod plan_parser_test;
 #[cfg(test)]
 mod sql_parser_test;
+#[cfg(test)]
+mod sql_redact_test;
 
 mod metrics;
 mod parser;
 mod plan_parser;
 mod sql_common;
 mod sql_parser;
+mod sql_redact;
 mod sql_statement;
 
 pub use plan_parser::PlanParser;
+pub use plan_parser::DEFAULT_HISTOGRAM_BUCKETS;
 pub use sql_common::SQLCommon;
 pub use sql_parser::DfParser;
+pub use sql_parser::IdentCase;
+pub use sql_parser::PREWHERE_FUNCTION;
+pub use sql_redact::redact_secrets;
 pub use sql_statement::*;
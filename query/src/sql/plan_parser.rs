@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -23,78 +23,280 @@ use common_functions::aggregates::AggregateFunctionFactory;
 use common_infallible::Mutex;
 use common_planners::expand_aggregate_arg_exprs;
 use common_planners::expand_wildcard;
+use common_planners::expand_window_arg_exprs;
 use common_planners::expr_as_column_expr;
 use common_planners::extract_aliases;
 use common_planners::find_aggregate_exprs;
+use common_planners::find_column_exprs;
 use common_planners::find_columns_not_satisfy_exprs;
+use common_planners::find_window_exprs;
+use common_planners::is_ranking_function;
+use common_planners::is_virtual_column;
 use common_planners::rebase_expr;
 use common_planners::rebase_expr_from_input;
+use common_planners::replace_non_aggregated_exprs;
 use common_planners::resolve_aliases_to_exprs;
 use common_planners::sort_to_inner_expr;
+use common_planners::split_conjunctions;
 use common_planners::unwrap_alias_exprs;
+use common_planners::AlterTableOperation;
+use common_planners::AlterTablePlan;
+use common_planners::AlterUserPlan;
+use common_planners::AnalyzeTablePlan;
+use common_planners::CopyIntoLocationPlan;
+use common_planners::CopyPlan;
+use common_planners::CreateConnectionPlan;
 use common_planners::CreateDatabasePlan;
+use common_planners::CreateExternalFunctionPlan;
+use common_planners::CreateFunctionPlan;
+use common_planners::CreateIndexPlan;
+use common_planners::CreatePipePlan;
+use common_planners::CreateProfilePlan;
 use common_planners::CreateTablePlan;
+use common_planners::CreateViewPlan;
+use common_planners::DeletePlan;
 use common_planners::DescribeTablePlan;
+use common_planners::DropConnectionPlan;
 use common_planners::DropDatabasePlan;
+use common_planners::DropFunctionPlan;
+use common_planners::DropIndexPlan;
+use common_planners::DropPipePlan;
+use common_planners::DropProfilePlan;
+use common_planners::DropQueryCachePlan;
 use common_planners::DropTablePlan;
+use common_planners::DropViewPlan;
 use common_planners::ExplainPlan;
 use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::FsckTablePlan;
 use common_planners::InsertIntoPlan;
 use common_planners::KillPlan;
+use common_planners::MergeInsertClause;
+use common_planners::MergeMatchedAction;
+use common_planners::MergeMatchedClause;
+use common_planners::MergePlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
+use common_planners::RecursiveCtePlan;
 use common_planners::SelectPlan;
+use common_planners::SetStoragePolicyPlan;
+use common_planners::SetTableOptionsPlan;
 use common_planners::SettingPlan;
+use common_planners::ShowCreateDatabasePlan;
 use common_planners::ShowCreateTablePlan;
+use common_planners::ShowTableOptionsPlan;
 use common_planners::TableScanInfo;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
 use common_planners::UseDatabasePlan;
 use common_planners::VarValue;
+use common_planners::WorkingTablePlan;
 use common_streams::Source;
 use common_streams::ValueSource;
 use common_tracing::tracing;
 use nom::FindSubstring;
+use sqlparser::ast::BinaryOperator;
+use sqlparser::ast::ColumnOption;
+use sqlparser::ast::Cte;
 use sqlparser::ast::FunctionArg;
 use sqlparser::ast::Ident;
 use sqlparser::ast::ObjectName;
 use sqlparser::ast::OrderByExpr;
 use sqlparser::ast::Query;
+use sqlparser::ast::SetExpr;
+use sqlparser::ast::SetOperator;
 use sqlparser::ast::Statement;
+use sqlparser::ast::TableConstraint;
 use sqlparser::ast::TableFactor;
 use sqlparser::ast::UnaryOperator;
+use sqlparser::ast::WindowSpec;
+use sqlparser::ast::With;
 
+use crate::catalogs::Table;
 use crate::catalogs::ToReadDataSourcePlan;
+use crate::configs::StorageConfig;
+use crate::datasources::common::storage_config_with_options;
+use crate::datasources::common::Collation;
+use crate::datasources::common::TableCollations;
+use crate::datasources::common::TableConstraints;
+use crate::datasources::common::STORAGE_OPT_KEY_CONNECTION;
+use crate::datasources::table::view::ViewTable;
 use crate::functions::ContextFunction;
+use crate::functions::FunctionResolver;
+use crate::functions::ResolvedFunction;
+use crate::functions::SessionFunction;
 use crate::sessions::DatabendQueryContextRef;
+use crate::sql::redact_secrets;
 use crate::sql::sql_statement::DfCreateTable;
 use crate::sql::sql_statement::DfDropDatabase;
 use crate::sql::sql_statement::DfUseDatabase;
+use crate::sql::DfAlterTable;
+use crate::sql::DfAlterTableAction;
+use crate::sql::DfAlterUser;
+use crate::sql::DfAnalyzeTable;
+use crate::sql::DfCopy;
+use crate::sql::DfCopyIntoLocation;
+use crate::sql::DfCopySource;
+use crate::sql::DfCreateConnection;
 use crate::sql::DfCreateDatabase;
+use crate::sql::DfCreateExternalFunction;
+use crate::sql::DfCreateFunction;
+use crate::sql::DfCreateIndex;
+use crate::sql::DfCreatePipe;
+use crate::sql::DfCreateProfile;
+use crate::sql::DfCreateView;
+use crate::sql::DfDelete;
 use crate::sql::DfDescribeTable;
+use crate::sql::DfDropConnection;
+use crate::sql::DfDropFunction;
+use crate::sql::DfDropIndex;
+use crate::sql::DfDropPipe;
+use crate::sql::DfDropProfile;
+use crate::sql::DfDropQueryCache;
 use crate::sql::DfDropTable;
+use crate::sql::DfDropView;
 use crate::sql::DfExplain;
+use crate::sql::DfFsckTable;
 use crate::sql::DfHint;
 use crate::sql::DfKillStatement;
+use crate::sql::DfList;
+use crate::sql::DfMerge;
+use crate::sql::DfMergeClause;
 use crate::sql::DfParser;
+use crate::sql::DfShowCreateDatabase;
 use crate::sql::DfShowCreateTable;
 use crate::sql::DfShowDatabases;
+use crate::sql::DfShowTableOptions;
 use crate::sql::DfShowTables;
 use crate::sql::DfStatement;
 use crate::sql::DfTruncateTable;
+use crate::sql::DfUpdate;
+use crate::sql::IdentCase;
 use crate::sql::SQLCommon;
+use crate::sql::PREWHERE_FUNCTION;
+
+/// The number of buckets of the histograms built by `ANALYZE TABLE`, if not given.
+pub const DEFAULT_HISTOGRAM_BUCKETS: u64 = 100;
+const MAX_HISTOGRAM_BUCKETS: u64 = 1024;
+/// Views may be built on other views, but no deeper than this.
+const MAX_VIEW_DEPTH: usize = 32;
+
+/// A common table expression of a `WITH` clause, its query is planned in place of its references.
+#[derive(Clone)]
+struct CommonTableExpr {
+    cte: Cte,
+    /// If it is in `WITH RECURSIVE`
+    recursive: bool,
+}
+
+impl CommonTableExpr {
+    fn name(&self) -> &str {
+        &self.cte.alias.name.value
+    }
+}
 
 pub struct PlanParser {
     ctx: DatabendQueryContextRef,
+    /// The database of the view being expanded, unqualified table names of its query belong to it
+    view_database: Option<String>,
+    /// The number of views being expanded
+    view_depth: usize,
+    /// The common table expressions of the `WITH` clauses in scope, the innermost last
+    ctes: Vec<CommonTableExpr>,
+    /// The recursive common table expression whose recursive term is being planned, by its
+    /// position in `ctes`, the references of it read its working table
+    working_table: Option<(usize, WorkingTablePlan)>,
 }
 
 impl PlanParser {
     pub fn create(ctx: DatabendQueryContextRef) -> Self {
-        Self { ctx }
+        Self {
+            ctx,
+            view_database: None,
+            view_depth: 0,
+            ctes: vec![],
+            working_table: None,
+        }
+    }
+
+    /// Creates a parser planning the query of a view in the database `db`.
+    fn create_for_view(&self, db: &str) -> Self {
+        Self {
+            ctx: self.ctx.clone(),
+            view_database: Some(db.to_string()),
+            view_depth: self.view_depth + 1,
+            ctes: vec![],
+            working_table: None,
+        }
+    }
+
+    /// Creates a parser planning the body of a query with the `WITH` clause `with`.
+    fn create_for_with(&self, with: &With) -> Result<Self> {
+        let mut ctes = self.ctes.clone();
+        for (index, cte) in with.cte_tables.iter().enumerate() {
+            let name = &cte.alias.name.value;
+            if with.cte_tables[..index]
+                .iter()
+                .any(|other| &other.alias.name.value == name)
+            {
+                return Result::Err(ErrorCode::SyntaxException(format!(
+                    "WITH query name {} specified more than once",
+                    name
+                )));
+            }
+
+            ctes.push(CommonTableExpr {
+                cte: cte.clone(),
+                recursive: with.recursive,
+            });
+        }
+
+        Ok(Self {
+            ctx: self.ctx.clone(),
+            view_database: self.view_database.clone(),
+            view_depth: self.view_depth,
+            ctes,
+            working_table: self.working_table.clone(),
+        })
+    }
+
+    /// Creates a parser planning the query of the common table expression at `index` of `ctes`,
+    /// which sees the ones before it. The recursive term of a recursive one sees itself as
+    /// `working_table` too.
+    fn create_for_cte(&self, index: usize, working_table: Option<WorkingTablePlan>) -> Self {
+        let (scope, working_table) = match working_table {
+            Some(working_table) => (index + 1, Some((index, working_table))),
+            None => (
+                index,
+                self.working_table.clone().filter(|(pos, _)| *pos < index),
+            ),
+        };
+
+        Self {
+            ctx: self.ctx.clone(),
+            view_database: self.view_database.clone(),
+            view_depth: self.view_depth,
+            ctes: self.ctes[..scope].to_vec(),
+            working_table,
+        }
+    }
+
+    /// The position in `ctes` of the innermost common table expression named `name`.
+    fn find_cte(&self, name: &str) -> Option<usize> {
+        self.ctes.iter().rposition(|cte| cte.name() == name)
+    }
+
+    /// The database of the unqualified table names in a query.
+    fn current_database(&self) -> String {
+        match &self.view_database {
+            Some(db) => db.clone(),
+            None => self.ctx.get_current_database(),
+        }
     }
 
     pub fn build_from_sql(&self, query: &str) -> Result<PlanNode> {
-        tracing::debug!(query);
-        DfParser::parse_sql(query).and_then(|(stmts, _)| {
+        tracing::debug!(query = redact_secrets(query).as_str());
+        DfParser::parse_sql_with_ident_case(query, self.ident_case()?).and_then(|(stmts, _)| {
             stmts
                 .first()
                 .map(|statement| self.statement_to_plan(statement))
@@ -105,8 +307,10 @@ impl PlanParser {
     }
 
     pub fn build_with_hint_from_sql(&self, query: &str) -> (Result<PlanNode>, Vec<DfHint>) {
-        tracing::debug!(query);
-        let stmt_hints = DfParser::parse_sql(query);
+        tracing::debug!(query = redact_secrets(query).as_str());
+        let stmt_hints = self
+            .ident_case()
+            .and_then(|ident_case| DfParser::parse_sql_with_ident_case(query, ident_case));
         match stmt_hints {
             Ok((stmts, hints)) => match stmts.first() {
                 Some(stmt) => (self.statement_to_plan(stmt), hints),
@@ -119,19 +323,47 @@ impl PlanParser {
         }
     }
 
+    fn ident_case(&self) -> Result<IdentCase> {
+        let settings = self.ctx.get_settings();
+        Ok(IdentCase {
+            unquoted_case_sensitive: settings.get_unquoted_ident_case_sensitive()? != 0,
+            quoted_case_sensitive: settings.get_quoted_ident_case_sensitive()? != 0,
+        })
+    }
+
     pub fn statement_to_plan(&self, statement: &DfStatement) -> Result<PlanNode> {
         match statement {
             DfStatement::Statement(v) => self.sql_statement_to_plan(v),
             DfStatement::Explain(v) => self.sql_explain_to_plan(v),
             DfStatement::ShowDatabases(v) => self.sql_show_databases_to_plan(v),
+            DfStatement::ShowCreateDatabase(v) => self.sql_show_create_database_to_plan(v),
             DfStatement::CreateDatabase(v) => self.sql_create_database_to_plan(v),
             DfStatement::DropDatabase(v) => self.sql_drop_database_to_plan(v),
             DfStatement::CreateTable(v) => self.sql_create_table_to_plan(v),
             DfStatement::DescribeTable(v) => self.sql_describe_table_to_plan(v),
             DfStatement::DropTable(v) => self.sql_drop_table_to_plan(v),
+            DfStatement::CreateView(v) => self.sql_create_view_to_plan(v),
+            DfStatement::DropView(v) => self.sql_drop_view_to_plan(v),
             DfStatement::TruncateTable(v) => self.sql_truncate_table_to_plan(v),
+            DfStatement::AlterTable(v) => self.sql_alter_table_to_plan(v),
+            DfStatement::CreateIndex(v) => self.sql_create_index_to_plan(v),
+            DfStatement::DropIndex(v) => self.sql_drop_index_to_plan(v),
+            DfStatement::AnalyzeTable(v) => self.sql_analyze_table_to_plan(v),
+            DfStatement::FsckTable(v) => self.sql_fsck_table_to_plan(v),
+            DfStatement::Delete(v) => self.sql_delete_to_plan(v),
+            DfStatement::Update(v) => self.sql_update_to_plan(v),
+            DfStatement::Merge(v) => self.sql_merge_to_plan(v),
+            DfStatement::CreatePipe(v) => self.sql_create_pipe_to_plan(v),
+            DfStatement::DropPipe(v) => self.sql_drop_pipe_to_plan(v),
+            DfStatement::Copy(v) => self.sql_copy_to_plan(v),
+            DfStatement::CopyIntoLocation(v) => self.sql_copy_into_location_to_plan(v),
+            DfStatement::List(v) => self.sql_list_to_plan(v),
+            DfStatement::CreateFunction(v) => self.sql_create_function_to_plan(v),
+            DfStatement::CreateExternalFunction(v) => self.sql_create_external_function_to_plan(v),
+            DfStatement::DropFunction(v) => self.sql_drop_function_to_plan(v),
             DfStatement::UseDatabase(v) => self.sql_use_database_to_plan(v),
             DfStatement::ShowCreateTable(v) => self.sql_show_create_table_to_plan(v),
+            DfStatement::ShowTableOptions(v) => self.sql_show_table_options_to_plan(v),
             DfStatement::ShowTables(df) => {
                 let show_sql = match df {
                     DfShowTables::All => {
@@ -167,8 +399,14 @@ impl PlanParser {
             DfStatement::ShowProcessList(_) => {
                 self.build_from_sql("SELECT * FROM system.processes")
             }
+            DfStatement::CreateProfile(v) => self.sql_create_profile_to_plan(v),
+            DfStatement::DropProfile(v) => self.sql_drop_profile_to_plan(v),
+            DfStatement::AlterUser(v) => self.sql_alter_user_to_plan(v),
+            DfStatement::CreateConnection(v) => self.sql_create_connection_to_plan(v),
+            DfStatement::DropConnection(v) => self.sql_drop_connection_to_plan(v),
             DfStatement::KillQuery(v) => self.sql_kill_query_to_plan(v),
             DfStatement::KillConn(v) => self.sql_kill_connection_to_plan(v),
+            DfStatement::DropQueryCache(v) => self.sql_drop_query_cache_to_plan(v),
         }
     }
 
@@ -218,13 +456,60 @@ impl PlanParser {
 
         let mut options = HashMap::new();
         for p in create.options.iter() {
-            options.insert(p.name.value.to_lowercase(), p.value.to_string());
+            options.insert(
+                p.name.value.to_lowercase(),
+                p.value
+                    .to_string()
+                    .trim_matches(|s| s == '\'' || s == '"')
+                    .to_string(),
+            );
+        }
+
+        let mut tables: Vec<CreateTablePlan> = vec![];
+        for table in create.tables.iter() {
+            let plan = match self.sql_create_table_to_plan(table)? {
+                PlanNode::CreateTable(plan) => plan,
+                _ => unreachable!(),
+            };
+            if tables.iter().any(|t| t.table == plan.table) {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Duplicate table {} in CREATE DATABASE {}",
+                    plan.table, name
+                )));
+            }
+            tables.push(plan);
         }
 
         Ok(PlanNode::CreateDatabase(CreateDatabasePlan {
             if_not_exists: create.if_not_exists,
             db: name,
             options,
+            tables,
+        }))
+    }
+
+    /// DfShowCreateDatabase to plan.
+    #[tracing::instrument(level = "info", skip(self, show_create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_show_create_database_to_plan(
+        &self,
+        show_create: &DfShowCreateDatabase,
+    ) -> Result<PlanNode> {
+        if show_create.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(
+                "Show create database name is empty",
+            ));
+        }
+        let db = show_create.name.0[0].value.clone();
+
+        let fields = vec![
+            DataField::new("Database", DataType::String, false),
+            DataField::new("Create Database", DataType::String, false),
+        ];
+
+        let schema = DataSchemaRefExt::create(fields);
+        Ok(PlanNode::ShowCreateDatabase(ShowCreateDatabasePlan {
+            db,
+            schema,
         }))
     }
 
@@ -256,6 +541,7 @@ impl PlanParser {
         Ok(PlanNode::DropDatabase(DropDatabasePlan {
             if_exists: drop.if_exists,
             db: name,
+            cascade: drop.cascade,
         }))
     }
 
@@ -283,6 +569,22 @@ impl PlanParser {
         }))
     }
 
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_query_cache_to_plan(&self, drop: &DfDropQueryCache) -> Result<PlanNode> {
+        let table = match &drop.table {
+            None => None,
+            Some(name) if name.0.is_empty() => {
+                return Result::Err(ErrorCode::SyntaxException("Table name is empty"));
+            }
+            Some(name) if name.0.len() > 1 => {
+                Some((name.0[0].value.clone(), name.0[1].value.clone()))
+            }
+            Some(name) => Some((self.ctx.get_current_database(), name.0[0].value.clone())),
+        };
+
+        Ok(PlanNode::DropQueryCache(DropQueryCachePlan { table }))
+    }
+
     #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
     pub fn sql_create_table_to_plan(&self, create: &DfCreateTable) -> Result<PlanNode> {
         let mut db = self.ctx.get_current_database();
@@ -295,6 +597,12 @@ impl PlanParser {
             table = create.name.0[1].value.clone();
         }
 
+        if create.engine.eq_ignore_ascii_case("VIEW") {
+            return Result::Err(ErrorCode::BadOption(
+                "Table engine VIEW is reserved, use CREATE VIEW to create a view",
+            ));
+        }
+
         let fields = create
             .columns
             .iter()
@@ -315,7 +623,37 @@ impl PlanParser {
             );
         }
 
-        let schema = DataSchemaRefExt::create(fields);
+        // Without the columns, the schema is the output of the SELECT.
+        let mut schema = DataSchemaRefExt::create(fields);
+        let as_select = match &create.query {
+            None => None,
+            Some(query) if create.columns.is_empty() => {
+                let plan = self.query_to_plan(query)?;
+                schema = plan.schema();
+                Some(Box::new(plan))
+            }
+            Some(query) => Some(Box::new(self.insert_select_to_plan(query, &schema)?)),
+        };
+        for field in schema.fields() {
+            Self::check_column_name(field.name())?;
+        }
+
+        let mut constraints = TableConstraints::from_options(&options);
+        self.create_table_constraints(create, &mut constraints)?;
+        constraints.validate(&schema)?;
+        constraints.to_options(&mut options);
+
+        let mut collations = TableCollations::from_options(&options);
+        for column in create.columns.iter() {
+            if let Some(collation) = &column.collation {
+                let name = column.name.value.clone();
+                collations.columns.retain(|(other, _)| other != &name);
+                collations.columns.push((name, collation.to_string()));
+            }
+        }
+        collations.validate(&schema)?;
+        collations.to_options(&mut options);
+
         Ok(PlanNode::CreateTable(CreateTablePlan {
             if_not_exists: create.if_not_exists,
             db,
@@ -323,9 +661,69 @@ impl PlanParser {
             schema,
             engine: create.engine.clone(),
             options,
+            temporary: create.temporary,
+            as_select,
         }))
     }
 
+    /// The names of the virtual columns are reserved, the tables cannot have such columns.
+    fn check_column_name(name: &str) -> Result<()> {
+        match is_virtual_column(name) {
+            true => Err(ErrorCode::BadArguments(format!(
+                "Column name {} is reserved for the virtual column",
+                name
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    /// Collects the PRIMARY KEY and UNIQUE constraints of the columns and the table.
+    fn create_table_constraints(
+        &self,
+        create: &DfCreateTable,
+        constraints: &mut TableConstraints,
+    ) -> Result<()> {
+        let mut keys = vec![];
+        for column in create.columns.iter() {
+            for option in column.options.iter() {
+                if let ColumnOption::Unique { is_primary } = option.option {
+                    keys.push((vec![column.name.value.clone()], is_primary));
+                }
+            }
+        }
+        for constraint in create.constraints.iter() {
+            match constraint {
+                TableConstraint::Unique {
+                    columns,
+                    is_primary,
+                    ..
+                } => keys.push((
+                    columns.iter().map(|column| column.value.clone()).collect(),
+                    *is_primary,
+                )),
+                _ => {
+                    return Err(ErrorCode::UnImplement(format!(
+                    "Unsupported table constraint: {}, only PRIMARY KEY and UNIQUE are supported",
+                    constraint
+                )))
+                }
+            }
+        }
+
+        for (key, is_primary) in keys {
+            match is_primary {
+                true if constraints.primary_key.is_some() => {
+                    return Err(ErrorCode::SyntaxException(
+                        "Multiple primary keys are not allowed",
+                    ))
+                }
+                true => constraints.primary_key = Some(key),
+                false => constraints.unique_keys.push(key),
+            }
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "info", skip(self, show_create), fields(ctx.id = self.ctx.get_id().as_str()))]
     pub fn sql_show_create_table_to_plan(
         &self,
@@ -401,6 +799,51 @@ impl PlanParser {
         }))
     }
 
+    /// DfCreateView to plan.
+    #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_create_view_to_plan(&self, create: &DfCreateView) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if create.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Create view name is empty"));
+        }
+        let mut view = create.name.0[0].value.clone();
+        if create.name.0.len() > 1 {
+            db = view;
+            view = create.name.0[1].value.clone();
+        }
+
+        // Plan the query as it will be expanded, which checks it and gives the view schema.
+        let plan = self.create_for_view(&db).query_to_plan(&create.query)?;
+
+        Ok(PlanNode::CreateView(CreateViewPlan {
+            if_not_exists: create.if_not_exists,
+            db,
+            view,
+            query: create.query.to_string(),
+            schema: plan.schema(),
+        }))
+    }
+
+    /// DfDropView to plan.
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_view_to_plan(&self, drop: &DfDropView) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if drop.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Drop view name is empty"));
+        }
+        let mut view = drop.name.0[0].value.clone();
+        if drop.name.0.len() > 1 {
+            db = view;
+            view = drop.name.0[1].value.clone();
+        }
+
+        Ok(PlanNode::DropView(DropViewPlan {
+            if_exists: drop.if_exists,
+            db,
+            view,
+        }))
+    }
+
     // DfTruncateTable to plan.
     #[tracing::instrument(level = "info", skip(self, truncate), fields(ctx.id = self.ctx.get_id().as_str()))]
     pub fn sql_truncate_table_to_plan(&self, truncate: &DfTruncateTable) -> Result<PlanNode> {
@@ -419,124 +862,1286 @@ impl PlanParser {
         Ok(PlanNode::TruncateTable(TruncateTablePlan { db, table }))
     }
 
-    #[tracing::instrument(level = "info", skip(self, table_name, columns, source), fields(ctx.id = self.ctx.get_id().as_str()))]
-    fn insert_to_plan(
+    #[tracing::instrument(level = "info", skip(self, show_options), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_show_table_options_to_plan(
         &self,
-        table_name: &ObjectName,
-        columns: &[Ident],
-        source: &Option<Box<Query>>,
-        format_sql: &str,
+        show_options: &DfShowTableOptions,
     ) -> Result<PlanNode> {
-        let mut db_name = self.ctx.get_current_database();
-        let mut tbl_name = table_name.0[0].value.clone();
-
-        if table_name.0.len() > 1 {
-            db_name = tbl_name;
-            tbl_name = table_name.0[1].value.clone();
+        let mut db = self.ctx.get_current_database();
+        if show_options.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(
+                "Show table options name is empty",
+            ));
+        }
+        let mut table = show_options.name.0[0].value.clone();
+        if show_options.name.0.len() > 1 {
+            db = table;
+            table = show_options.name.0[1].value.clone();
         }
 
-        let table = self.ctx.get_table(&db_name, &tbl_name)?;
-        let mut schema = table.schema();
-        let tbl_id = table.get_id();
-
-        if !columns.is_empty() {
-            let fields = columns
-                .iter()
-                .map(|ident| schema.field_with_name(&ident.value).map(|v| v.clone()))
-                .collect::<Result<Vec<_>>>()?;
+        Ok(PlanNode::ShowTableOptions(ShowTableOptionsPlan {
+            db,
+            table,
+        }))
+    }
 
-            schema = DataSchemaRefExt::create(fields);
+    #[tracing::instrument(level = "info", skip(self, alter), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_alter_table_to_plan(&self, alter: &DfAlterTable) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if alter.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Alter table name is empty"));
+        }
+        let mut table = alter.name.0[0].value.clone();
+        if alter.name.0.len() > 1 {
+            db = table;
+            table = alter.name.0[1].value.clone();
         }
 
-        let mut input_stream = futures::stream::iter::<Vec<DataBlock>>(vec![]);
+        match &alter.action {
+            DfAlterTableAction::SetStoragePolicy(sql_options) => {
+                let mut options = HashMap::new();
+                for p in sql_options.iter() {
+                    options.insert(
+                        p.name.value.to_lowercase(),
+                        p.value
+                            .to_string()
+                            .trim_matches(|s| s == '\'' || s == '"')
+                            .to_string(),
+                    );
+                }
 
-        if let Some(source) = source {
-            if let sqlparser::ast::SetExpr::Values(_vs) = &source.body {
-                tracing::debug!("{:?}", format_sql);
-                let index = format_sql.find_substring(" VALUES ").unwrap();
-                let values = &format_sql[index + " VALUES ".len()..];
+                Ok(PlanNode::SetStoragePolicy(SetStoragePolicyPlan {
+                    db,
+                    table,
+                    options,
+                }))
+            }
+            DfAlterTableAction::SetOptions(sql_options) => {
+                let mut options = HashMap::new();
+                for p in sql_options.iter() {
+                    options.insert(
+                        p.name.value.to_lowercase(),
+                        p.value
+                            .to_string()
+                            .trim_matches(|s| s == '\'' || s == '"')
+                            .to_string(),
+                    );
+                }
 
-                let block_size = self.ctx.get_settings().get_max_block_size()? as usize;
-                let mut source = ValueSource::new(values.as_bytes(), schema.clone(), block_size);
-                let mut blocks = vec![];
-                loop {
-                    let block = source.read()?;
-                    match block {
-                        Some(b) => blocks.push(b),
-                        None => break,
-                    }
+                Ok(PlanNode::SetTableOptions(SetTableOptionsPlan {
+                    db,
+                    table,
+                    options,
+                }))
+            }
+            DfAlterTableAction::AddColumn(column) => {
+                if column.collation.is_some() {
+                    return Err(ErrorCode::UnImplement(
+                        "COLLATE is not supported in ALTER TABLE ADD COLUMN",
+                    ));
                 }
-                input_stream = futures::stream::iter(blocks);
+                Self::check_column_name(&column.name.value)?;
+                // The rows written before have no value of the new column, they read the
+                // default, or NULL without a default.
+                let data_type = SQLCommon::make_data_type(&column.data_type)?;
+                let field = DataField::new(&column.name.value, data_type, true);
+                let default = column.options.iter().find_map(|def| match &def.option {
+                    ColumnOption::Default(expr) => Some(expr),
+                    _ => None,
+                });
+                let default = match default {
+                    None => None,
+                    Some(expr) => {
+                        let expr = self.sql_to_rex(expr, &DataSchema::empty(), None)?;
+                        if !find_column_exprs(&[expr.clone()]).is_empty() {
+                            return Err(ErrorCode::BadArguments(format!(
+                                "The default of column {} must be a constant expression",
+                                column.name.value
+                            )));
+                        }
+                        Some(expr)
+                    }
+                };
+                Ok(PlanNode::AlterTable(AlterTablePlan {
+                    db,
+                    table,
+                    operation: AlterTableOperation::AddColumn { field, default },
+                }))
+            }
+            DfAlterTableAction::DropColumn(column) => Ok(PlanNode::AlterTable(AlterTablePlan {
+                db,
+                table,
+                operation: AlterTableOperation::DropColumn(column.value.clone()),
+            })),
+            DfAlterTableAction::MaterializeColumn(column) => {
+                Ok(PlanNode::AlterTable(AlterTablePlan {
+                    db,
+                    table,
+                    operation: AlterTableOperation::MaterializeColumn(column.value.clone()),
+                }))
             }
         }
-
-        let plan_node = InsertIntoPlan {
-            db_name,
-            tbl_name,
-            tbl_id,
-            schema,
-            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
-        };
-        Ok(PlanNode::InsertInto(plan_node))
     }
 
-    /// Generate a logic plan from an SQL query
-    pub fn query_to_plan(&self, query: &sqlparser::ast::Query) -> Result<PlanNode> {
-        if query.with.is_some() {
-            return Result::Err(ErrorCode::UnImplement("CTE is not yet implement"));
+    #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_create_index_to_plan(&self, create: &DfCreateIndex) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if create.table_name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(
+                "Create index table name is empty",
+            ));
+        }
+        let mut table = create.table_name.0[0].value.clone();
+        if create.table_name.0.len() > 1 {
+            db = table;
+            table = create.table_name.0[1].value.clone();
         }
 
-        match &query.body {
-            sqlparser::ast::SetExpr::Select(s) => {
-                self.select_to_plan(s.as_ref(), &query.limit, &query.offset, &query.order_by)
+        let schema = self.ctx.get_table(&db, &table)?.schema();
+        let expr = self.sql_to_rex(&create.expr, &schema, None)?;
+        let exprs = [expr.clone()];
+        if !find_aggregate_exprs(&exprs).is_empty() || !find_window_exprs(&exprs).is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(format!(
+                "Aggregate and window functions are not allowed in index expression: {:?}",
+                expr
+            )));
+        }
+        if find_column_exprs(&exprs).is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(format!(
+                "Index expression must refer to the columns of the table: {:?}",
+                expr
+            )));
+        }
+        // Fails if the columns are not in the table.
+        let field = expr.to_data_field(&schema)?;
+        if create.inverted {
+            match (&expr, field.data_type()) {
+                (Expression::Column(_), DataType::String) => {}
+                _ => {
+                    return Result::Err(ErrorCode::SyntaxException(format!(
+                        "Inverted index must be on a String column: {:?}",
+                        expr
+                    )))
+                }
             }
-            _ => Result::Err(ErrorCode::UnImplement(format!(
-                "Query {} is not yet implemented",
-                query.body
-            ))),
         }
+
+        Ok(PlanNode::CreateIndex(CreateIndexPlan {
+            if_not_exists: create.if_not_exists,
+            inverted: create.inverted,
+            name: create.name.value.clone(),
+            db,
+            table,
+            expr,
+        }))
     }
 
-    /// Generate a logic plan from an SQL select
-    /// For example:
-    /// "select sum(number+1)+2, number%3 as id from numbers(10) where number>1 group by id having id>1 order by id desc limit 3"
-    #[tracing::instrument(level = "info", skip(self, select, limit, order_by))]
-    fn select_to_plan(
-        &self,
-        select: &sqlparser::ast::Select,
-        limit: &Option<sqlparser::ast::Expr>,
-        offset: &Option<sqlparser::ast::Offset>,
-        order_by: &[OrderByExpr],
-    ) -> Result<PlanNode> {
-        // Filter expression
-        // In example: Filter=(number > 1)
-        let plan = self
-            .plan_tables_with_joins(&select.from)
-            .and_then(|input| self.filter(&input, &select.selection, Some(select)))?;
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_index_to_plan(&self, drop: &DfDropIndex) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if drop.table_name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Drop index table name is empty"));
+        }
+        let mut table = drop.table_name.0[0].value.clone();
+        if drop.table_name.0.len() > 1 {
+            db = table;
+            table = drop.table_name.0[1].value.clone();
+        }
 
-        // Projection expression
-        // In example: Projection=[(sum((number + 1)) + 2), (number % 3) as id]
-        let projection_exprs = select
-            .projection
-            .iter()
-            .map(|e| self.sql_select_to_rex(e, &plan.schema(), Some(select)))
-            .collect::<Result<Vec<Expression>>>()?
-            .iter()
-            .flat_map(|expr| expand_wildcard(expr, &plan.schema()))
-            .collect::<Vec<Expression>>();
+        Ok(PlanNode::DropIndex(DropIndexPlan {
+            if_exists: drop.if_exists,
+            name: drop.name.value.clone(),
+            db,
+            table,
+        }))
+    }
 
-        // Aliases replacement for group by, having, sorting
-        // In example: Aliases=[("id", (number % 3))]
-        let aliases = extract_aliases(&projection_exprs);
+    #[tracing::instrument(level = "info", skip(self, analyze), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_analyze_table_to_plan(&self, analyze: &DfAnalyzeTable) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if analyze.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Analyze table name is empty"));
+        }
+        let mut table = analyze.name.0[0].value.clone();
+        if analyze.name.0.len() > 1 {
+            db = table;
+            table = analyze.name.0[1].value.clone();
+        }
 
-        // Group By expression after against aliases
-        // In example: GroupBy=[(number % 3)]
-        let group_by_exprs = select
-            .group_by
-            .iter()
-            .map(|e| {
-                self.sql_to_rex(e, &plan.schema(), Some(select))
-                    .and_then(|expr| resolve_aliases_to_exprs(&expr, &aliases))
+        let buckets = analyze.buckets.unwrap_or(DEFAULT_HISTOGRAM_BUCKETS);
+        if buckets == 0 || buckets > MAX_HISTOGRAM_BUCKETS {
+            return Result::Err(ErrorCode::BadArguments(format!(
+                "The number of histogram buckets must be between 1 and {}, but got {}",
+                MAX_HISTOGRAM_BUCKETS, buckets
+            )));
+        }
+
+        Ok(PlanNode::AnalyzeTable(AnalyzeTablePlan {
+            db,
+            table,
+            histogram_columns: analyze
+                .histogram_columns
+                .iter()
+                .map(|column| column.value.clone())
+                .collect(),
+            buckets: buckets as usize,
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, fsck), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_fsck_table_to_plan(&self, fsck: &DfFsckTable) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if fsck.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Fsck table name is empty"));
+        }
+        let mut table = fsck.name.0[0].value.clone();
+        if fsck.name.0.len() > 1 {
+            db = table;
+            table = fsck.name.0[1].value.clone();
+        }
+
+        Ok(PlanNode::FsckTable(FsckTablePlan {
+            db,
+            table,
+            repair: fsck.repair,
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, delete), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_delete_to_plan(&self, delete: &DfDelete) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if delete.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Delete table name is empty"));
+        }
+        let mut table = delete.name.0[0].value.clone();
+        if delete.name.0.len() > 1 {
+            db = table;
+            table = delete.name.0[1].value.clone();
+        }
+
+        let selection = match &delete.selection {
+            None => None,
+            Some(expr) => {
+                let schema = self.ctx.get_table(&db, &table)?.schema();
+                let selection = self.sql_to_rex(expr, &schema, None)?;
+                if !find_aggregate_exprs(&[selection.clone()]).is_empty() {
+                    return Result::Err(ErrorCode::SyntaxException(format!(
+                        "Aggregate functions are not allowed in DELETE: {:?}",
+                        selection
+                    )));
+                }
+                // Fails if the columns are not in the table.
+                selection.to_data_field(&schema)?;
+                Some(selection)
+            }
+        };
+
+        Ok(PlanNode::Delete(DeletePlan {
+            db,
+            table,
+            selection,
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, update), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_update_to_plan(&self, update: &DfUpdate) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if update.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Update table name is empty"));
+        }
+        let mut table = update.name.0[0].value.clone();
+        if update.name.0.len() > 1 {
+            db = table;
+            table = update.name.0[1].value.clone();
+        }
+
+        let schema = self.ctx.get_table(&db, &table)?.schema();
+        let mut assignments: Vec<(String, Expression)> = vec![];
+        for (column, expr) in update.assignments.iter() {
+            let field = schema.field_with_name(&column.value)?;
+            if assignments.iter().any(|(name, _)| name == field.name()) {
+                return Result::Err(ErrorCode::SyntaxException(format!(
+                    "Column {} is assigned more than once in UPDATE",
+                    field.name()
+                )));
+            }
+
+            let mut value = self.sql_to_rex(expr, &schema, None)?;
+            if !find_aggregate_exprs(&[value.clone()]).is_empty() {
+                return Result::Err(ErrorCode::SyntaxException(format!(
+                    "Aggregate functions are not allowed in UPDATE: {:?}",
+                    value
+                )));
+            }
+            if value.to_data_type(&schema)? != *field.data_type() {
+                value = Expression::Cast {
+                    expr: Box::new(value),
+                    data_type: field.data_type().clone(),
+                    is_try: false,
+                };
+            }
+            assignments.push((field.name().clone(), value));
+        }
+
+        let selection = match &update.selection {
+            None => None,
+            Some(expr) => {
+                let selection = self.sql_to_rex(expr, &schema, None)?;
+                if !find_aggregate_exprs(&[selection.clone()]).is_empty() {
+                    return Result::Err(ErrorCode::SyntaxException(format!(
+                        "Aggregate functions are not allowed in UPDATE: {:?}",
+                        selection
+                    )));
+                }
+                // Fails if the columns are not in the table.
+                selection.to_data_field(&schema)?;
+                Some(selection)
+            }
+        };
+
+        Ok(PlanNode::Update(UpdatePlan {
+            db,
+            table,
+            assignments,
+            selection,
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, merge), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_merge_to_plan(&self, merge: &DfMerge) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if merge.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Merge table name is empty"));
+        }
+        let mut table = merge.name.0[0].value.clone();
+        if merge.name.0.len() > 1 {
+            db = table;
+            table = merge.name.0[1].value.clone();
+        }
+        let schema = self.ctx.get_table(&db, &table)?.schema();
+
+        let target = match &merge.alias {
+            Some(alias) => alias.value.clone(),
+            None => table.clone(),
+        };
+        let source = match &merge.source {
+            TableFactor::Table {
+                alias: Some(alias), ..
+            }
+            | TableFactor::Derived {
+                alias: Some(alias), ..
+            } => alias.name.value.clone(),
+            TableFactor::Table { name, .. } => name.0[name.0.len() - 1].value.clone(),
+            _ => {
+                return Result::Err(ErrorCode::SyntaxException(
+                    "The source of MERGE must be a table or a subquery with an alias",
+                ))
+            }
+        };
+        if target == source {
+            return Result::Err(ErrorCode::SyntaxException(format!(
+                "The target and the source of MERGE are both named {}",
+                target
+            )));
+        }
+
+        // The columns of the source are renamed to `<source>.<name>`, see `MergeNames`.
+        let input = match self.create_relation(&merge.source)? {
+            PlanNode::Select(SelectPlan { input }) => input,
+            plan => Arc::new(plan),
+        };
+        let source_columns = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .filter(|name| !is_virtual_column(name))
+            .collect::<Vec<_>>();
+        let exprs = source_columns
+            .iter()
+            .map(|name| {
+                let column = Box::new(Expression::Column(name.clone()));
+                Expression::Alias(format!("{}.{}", source, name), column)
+            })
+            .collect::<Vec<_>>();
+        let source_plan = PlanBuilder::from(&input).project(&exprs)?.build()?;
+        let source_schema = source_plan.schema();
+
+        let names = MergeNames {
+            target,
+            target_columns: schema.fields().iter().map(|f| f.name().clone()).collect(),
+            source,
+            source_columns,
+        };
+        let mut fields = schema.fields().clone();
+        fields.extend(source_schema.fields().iter().cloned());
+        let merged_schema = DataSchemaRefExt::create(fields);
+
+        let on = self.sql_to_rex(&names.resolve(&merge.on)?, &merged_schema, None)?;
+        let mut keys = vec![];
+        for conjunction in split_conjunctions(&on) {
+            let key = match &conjunction {
+                Expression::BinaryExpression { op, left, right } if op == "=" => {
+                    let left_side = Self::merge_side(left, &schema, &source_schema);
+                    let right_side = Self::merge_side(right, &schema, &source_schema);
+                    match (left_side, right_side) {
+                        (Some(true), Some(false)) => Some((left, right)),
+                        (Some(false), Some(true)) => Some((right, left)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            let (target_key, source_key) = match key {
+                Some(key) => key,
+                None => {
+                    return Result::Err(ErrorCode::UnImplement(format!(
+                        "MERGE only supports ON target_expr = source_expr [AND ...], got {:?}",
+                        conjunction
+                    )))
+                }
+            };
+
+            let target_type = target_key.to_data_type(&schema)?;
+            let source_type = source_key.to_data_type(&source_schema)?;
+            let key_type = equal_coercion(&target_type, &source_type)?;
+            keys.push((
+                Self::cast_to(target_key.as_ref().clone(), &target_type, &key_type),
+                Self::cast_to(source_key.as_ref().clone(), &source_type, &key_type),
+            ));
+        }
+
+        let mut matched = vec![];
+        let mut not_matched = vec![];
+        for clause in merge.clauses.iter() {
+            match clause {
+                DfMergeClause::MatchedUpdate {
+                    condition,
+                    assignments,
+                } => {
+                    let mut resolved: Vec<(String, Expression)> = vec![];
+                    for (column, expr) in assignments.iter() {
+                        let field = schema.field_with_name(&column.value)?;
+                        if resolved.iter().any(|(name, _)| name == field.name()) {
+                            return Result::Err(ErrorCode::SyntaxException(format!(
+                                "Column {} is assigned more than once in MERGE",
+                                field.name()
+                            )));
+                        }
+                        let value = self.merge_expr(&names, expr, &merged_schema)?;
+                        let value_type = value.to_data_type(&merged_schema)?;
+                        let value = Self::cast_to(value, &value_type, field.data_type());
+                        resolved.push((field.name().clone(), value));
+                    }
+                    matched.push(MergeMatchedClause {
+                        condition: self.merge_condition(&names, condition, &merged_schema)?,
+                        action: MergeMatchedAction::Update(resolved),
+                    });
+                }
+                DfMergeClause::MatchedDelete { condition } => {
+                    matched.push(MergeMatchedClause {
+                        condition: self.merge_condition(&names, condition, &merged_schema)?,
+                        action: MergeMatchedAction::Delete,
+                    });
+                }
+                DfMergeClause::NotMatched {
+                    condition,
+                    columns,
+                    values,
+                } => {
+                    let columns = match columns.is_empty() {
+                        true => names.target_columns.clone(),
+                        false => columns.iter().map(|c| c.value.clone()).collect(),
+                    };
+                    if columns.len() != values.len() {
+                        return Result::Err(ErrorCode::BadArguments(format!(
+                            "Expect {} values in MERGE INSERT, but got {}",
+                            columns.len(),
+                            values.len()
+                        )));
+                    }
+
+                    for column in columns.iter() {
+                        schema.field_with_name(column)?;
+                    }
+
+                    // The columns not inserted are NULL.
+                    let mut inserted = vec![];
+                    for field in schema.fields() {
+                        let value = match columns.iter().position(|c| c == field.name()) {
+                            Some(i) => self.merge_expr(&names, &values[i], &source_schema)?,
+                            None => Expression::create_literal(DataValue::Null),
+                        };
+                        let value_type = value.to_data_type(&source_schema)?;
+                        inserted.push(Self::cast_to(value, &value_type, field.data_type()));
+                    }
+                    not_matched.push(MergeInsertClause {
+                        condition: self.merge_condition(&names, condition, &source_schema)?,
+                        values: inserted,
+                    });
+                }
+            }
+        }
+
+        Ok(PlanNode::Merge(MergePlan {
+            db,
+            table,
+            source: Box::new(PlanNode::Select(SelectPlan {
+                input: Arc::new(source_plan),
+            })),
+            keys,
+            matched,
+            not_matched,
+        }))
+    }
+
+    /// Whether the expression is of the target columns only, or of the source columns only.
+    fn merge_side(
+        expr: &Expression,
+        target: &DataSchemaRef,
+        source: &DataSchemaRef,
+    ) -> Option<bool> {
+        let columns = find_column_exprs(&[expr.clone()])
+            .into_iter()
+            .map(|column| column.column_name())
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            None
+        } else if columns.iter().all(|c| target.field_with_name(c).is_ok()) {
+            Some(true)
+        } else if columns.iter().all(|c| source.field_with_name(c).is_ok()) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Converts an expression of a MERGE clause, which is validated against the schema.
+    fn merge_expr(
+        &self,
+        names: &MergeNames,
+        expr: &sqlparser::ast::Expr,
+        schema: &DataSchemaRef,
+    ) -> Result<Expression> {
+        let expr = self.sql_to_rex(&names.resolve(expr)?, schema, None)?;
+        if !find_aggregate_exprs(&[expr.clone()]).is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(format!(
+                "Aggregate functions are not allowed in MERGE: {:?}",
+                expr
+            )));
+        }
+        expr.to_data_field(schema)?;
+        Ok(expr)
+    }
+
+    fn merge_condition(
+        &self,
+        names: &MergeNames,
+        condition: &Option<sqlparser::ast::Expr>,
+        schema: &DataSchemaRef,
+    ) -> Result<Option<Expression>> {
+        match condition {
+            None => Ok(None),
+            Some(condition) => self.merge_expr(names, condition, schema).map(Some),
+        }
+    }
+
+    fn cast_to(expr: Expression, from: &DataType, to: &DataType) -> Expression {
+        match from == to {
+            true => expr,
+            false => Expression::Cast {
+                expr: Box::new(expr),
+                data_type: to.clone(),
+                is_try: false,
+            },
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_create_pipe_to_plan(&self, create: &DfCreatePipe) -> Result<PlanNode> {
+        if create.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Create pipe name is empty"));
+        }
+        let name = create.name.0[0].value.clone();
+
+        let mut db = self.ctx.get_current_database();
+        if create.table_name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Pipe table name is empty"));
+        }
+        let mut table = create.table_name.0[0].value.clone();
+        if create.table_name.0.len() > 1 {
+            db = table;
+            table = create.table_name.0[1].value.clone();
+        }
+
+        let mut source_options = HashMap::new();
+        for p in create.options.iter() {
+            source_options.insert(
+                p.name.value.to_lowercase(),
+                p.value
+                    .to_string()
+                    .trim_matches(|s| s == '\'' || s == '"')
+                    .to_string(),
+            );
+        }
+
+        Ok(PlanNode::CreatePipe(CreatePipePlan {
+            if_not_exists: create.if_not_exists,
+            name,
+            db,
+            table,
+            source: create.source.value.to_lowercase(),
+            source_options,
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_pipe_to_plan(&self, drop: &DfDropPipe) -> Result<PlanNode> {
+        if drop.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Drop pipe name is empty"));
+        }
+
+        Ok(PlanNode::DropPipe(DropPipePlan {
+            if_exists: drop.if_exists,
+            name: drop.name.0[0].value.clone(),
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_create_profile_to_plan(&self, create: &DfCreateProfile) -> Result<PlanNode> {
+        let name = create.name.value.to_lowercase();
+        // `ALTER USER u SETTINGS PROFILE NONE` unsets the profile of the user.
+        if name == "none" {
+            return Result::Err(ErrorCode::IllegalSettingsProfileFormat(
+                "Settings profile name `none` is reserved",
+            ));
+        }
+
+        let mut settings = BTreeMap::new();
+        for p in create.settings.iter() {
+            settings.insert(
+                p.name.value.to_lowercase(),
+                p.value
+                    .to_string()
+                    .trim_matches(|s| s == '\'' || s == '"')
+                    .to_string(),
+            );
+        }
+
+        Ok(PlanNode::CreateProfile(CreateProfilePlan {
+            if_not_exists: create.if_not_exists,
+            name,
+            settings,
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_profile_to_plan(&self, drop: &DfDropProfile) -> Result<PlanNode> {
+        Ok(PlanNode::DropProfile(DropProfilePlan {
+            if_exists: drop.if_exists,
+            name: drop.name.value.to_lowercase(),
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, alter), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_alter_user_to_plan(&self, alter: &DfAlterUser) -> Result<PlanNode> {
+        Ok(PlanNode::AlterUser(AlterUserPlan {
+            name: alter.name.value.clone(),
+            profile: alter.profile.as_ref().map(|p| p.value.to_lowercase()),
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_create_connection_to_plan(&self, create: &DfCreateConnection) -> Result<PlanNode> {
+        let mut options = HashMap::new();
+        for p in create.options.iter() {
+            let key = p.name.value.to_lowercase();
+            // A connection is of one storage, it never refers to another connection.
+            if !key.starts_with("storage_") || key == STORAGE_OPT_KEY_CONNECTION {
+                return Result::Err(ErrorCode::BadOption(format!(
+                    "Invalid connection option: {}, expect the storage options",
+                    key
+                )));
+            }
+            options.insert(
+                key,
+                p.value
+                    .to_string()
+                    .trim_matches(|s| s == '\'' || s == '"')
+                    .to_string(),
+            );
+        }
+        storage_config_with_options(&StorageConfig::default(), &options)?;
+
+        Ok(PlanNode::CreateConnection(CreateConnectionPlan {
+            if_not_exists: create.if_not_exists,
+            name: create.name.value.to_lowercase(),
+            options: options.into_iter().collect(),
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_connection_to_plan(&self, drop: &DfDropConnection) -> Result<PlanNode> {
+        Ok(PlanNode::DropConnection(DropConnectionPlan {
+            if_exists: drop.if_exists,
+            name: drop.name.value.to_lowercase(),
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_create_function_to_plan(&self, create: &DfCreateFunction) -> Result<PlanNode> {
+        let (namespace, name) = Self::function_name(&create.name)?;
+        Ok(PlanNode::CreateFunction(CreateFunctionPlan {
+            if_not_exists: create.if_not_exists,
+            namespace,
+            name,
+            language: create.language.value.to_lowercase(),
+            body: create.body.clone(),
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_create_external_function_to_plan(
+        &self,
+        create: &DfCreateExternalFunction,
+    ) -> Result<PlanNode> {
+        let (namespace, name) = Self::function_name(&create.name)?;
+        let arg_types = create
+            .arg_types
+            .iter()
+            .map(SQLCommon::make_data_type)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut options = HashMap::new();
+        for p in create.options.iter() {
+            options.insert(
+                p.name.value.to_lowercase(),
+                p.value
+                    .to_string()
+                    .trim_matches(|s| s == '\'' || s == '"')
+                    .to_string(),
+            );
+        }
+
+        Ok(PlanNode::CreateExternalFunction(
+            CreateExternalFunctionPlan {
+                if_not_exists: create.if_not_exists,
+                namespace,
+                name,
+                arg_types,
+                return_type: SQLCommon::make_data_type(&create.return_type)?,
+                endpoint: create.endpoint.clone(),
+                options,
+            },
+        ))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_function_to_plan(&self, drop: &DfDropFunction) -> Result<PlanNode> {
+        let (namespace, name) = Self::function_name(&drop.name)?;
+        Ok(PlanNode::DropFunction(DropFunctionPlan {
+            if_exists: drop.if_exists,
+            namespace,
+            name,
+        }))
+    }
+
+    /// Splits `[ns.]name` of `CREATE FUNCTION` and `DROP FUNCTION` into the namespace of the
+    /// tenant, in lower case, and the name.
+    fn function_name(name: &ObjectName) -> Result<(Option<String>, String)> {
+        match name.0.as_slice() {
+            [name] => Ok((None, name.value.clone())),
+            [namespace, name] => Ok((Some(namespace.value.to_lowercase()), name.value.clone())),
+            _ => Result::Err(ErrorCode::SyntaxException(format!(
+                "Invalid function name: {}",
+                name
+            ))),
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self, copy), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_copy_to_plan(&self, copy: &DfCopy) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if copy.table_name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Copy table name is empty"));
+        }
+        let mut table = copy.table_name.0[0].value.clone();
+        if copy.table_name.0.len() > 1 {
+            db = table;
+            table = copy.table_name.0[1].value.clone();
+        }
+
+        let mut options = HashMap::new();
+        for p in copy.options.iter() {
+            options.insert(
+                p.name.value.to_lowercase(),
+                p.value
+                    .to_string()
+                    .trim_matches(|s| s == '\'' || s == '"')
+                    .to_string(),
+            );
+        }
+
+        Ok(PlanNode::Copy(CopyPlan {
+            db,
+            table,
+            location: copy.location.clone(),
+            options,
+        }))
+    }
+
+    #[tracing::instrument(level = "info", skip(self, copy), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_copy_into_location_to_plan(&self, copy: &DfCopyIntoLocation) -> Result<PlanNode> {
+        let input = match &copy.source {
+            DfCopySource::Table(name) => self.build_from_sql(&format!("SELECT * FROM {}", name))?,
+            DfCopySource::Query(query) => self.query_to_plan(query)?,
+        };
+        let schema = input.schema();
+
+        let mut partition_by = Vec::with_capacity(copy.partition_by.len());
+        for item in copy.partition_by.iter() {
+            let expr = match item {
+                sqlparser::ast::SelectItem::UnnamedExpr(expr) => {
+                    self.sql_to_rex(expr, &schema, None)?
+                }
+                sqlparser::ast::SelectItem::ExprWithAlias { expr, alias } => Expression::Alias(
+                    alias.value.clone(),
+                    Box::new(self.sql_to_rex(expr, &schema, None)?),
+                ),
+                _ => {
+                    return Err(ErrorCode::SyntaxException(format!(
+                        "Invalid partition expression: {}",
+                        item
+                    )))
+                }
+            };
+            // fails if the expression is not computed from the columns of the rows
+            expr.to_data_field(&schema)?;
+
+            let key = expr.column_name();
+            if partition_by
+                .iter()
+                .any(|e: &Expression| e.column_name() == key)
+            {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Duplicate partition key: {}",
+                    key
+                )));
+            }
+            partition_by.push(expr);
+        }
+
+        let mut options = HashMap::new();
+        for p in copy.options.iter() {
+            options.insert(
+                p.name.value.to_lowercase(),
+                p.value
+                    .to_string()
+                    .trim_matches(|s| s == '\'' || s == '"')
+                    .to_string(),
+            );
+        }
+
+        Ok(PlanNode::CopyIntoLocation(CopyIntoLocationPlan {
+            location: copy.location.clone(),
+            input: Arc::new(input),
+            partition_by,
+            options,
+        }))
+    }
+
+    /// `LIST` is a query of the `stage_files` table function.
+    #[tracing::instrument(level = "info", skip(self, list), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_list_to_plan(&self, list: &DfList) -> Result<PlanNode> {
+        let sql = format!(
+            "SELECT * FROM stage_files({}) ORDER BY name",
+            SQLCommon::quote_string_literal(&list.location)
+        );
+        self.build_from_sql(&sql)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, table_name, columns, source), fields(ctx.id = self.ctx.get_id().as_str()))]
+    fn insert_to_plan(
+        &self,
+        table_name: &ObjectName,
+        columns: &[Ident],
+        source: &Option<Box<Query>>,
+        format_sql: &str,
+    ) -> Result<PlanNode> {
+        let mut db_name = self.ctx.get_current_database();
+        let mut tbl_name = table_name.0[0].value.clone();
+
+        if table_name.0.len() > 1 {
+            db_name = tbl_name;
+            tbl_name = table_name.0[1].value.clone();
+        }
+
+        let table = self.ctx.get_table(&db_name, &tbl_name)?;
+        let mut schema = table.schema();
+        let tbl_id = table.get_id();
+
+        if !columns.is_empty() {
+            let fields = columns
+                .iter()
+                .map(|ident| schema.field_with_name(&ident.value).map(|v| v.clone()))
+                .collect::<Result<Vec<_>>>()?;
+
+            schema = DataSchemaRefExt::create(fields);
+        }
+
+        let mut blocks = vec![];
+        let mut select_plan = None;
+
+        if let Some(source) = source {
+            if let sqlparser::ast::SetExpr::Values(_vs) = &source.body {
+                tracing::debug!("{:?}", format_sql);
+                let index = format_sql.find_substring(" VALUES ").unwrap();
+                let values = &format_sql[index + " VALUES ".len()..];
+
+                let settings = self.ctx.get_settings();
+                let block_size = settings.get_max_block_size()? as usize;
+                let lenient_cast = settings.get_lenient_insert_cast()? != 0;
+                let mut source = ValueSource::new(values.as_bytes(), schema.clone(), block_size)
+                    .with_lenient_cast(lenient_cast);
+                loop {
+                    let block = source.read()?;
+                    match block {
+                        Some(b) => blocks.push(b),
+                        None => break,
+                    }
+                }
+            } else {
+                select_plan = Some(Box::new(self.insert_select_to_plan(source, &schema)?));
+            }
+        }
+
+        let input_stream = futures::stream::iter(blocks.into_iter().map(Ok));
+        let plan_node = InsertIntoPlan {
+            db_name,
+            tbl_name,
+            tbl_id,
+            schema,
+            select_plan,
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        };
+        Ok(PlanNode::InsertInto(plan_node))
+    }
+
+    /// The SELECT of `INSERT INTO ... SELECT` and `CREATE TABLE ... AS SELECT`,
+    /// its columns are cast to the inserted ones.
+    fn insert_select_to_plan(&self, query: &Query, schema: &DataSchemaRef) -> Result<PlanNode> {
+        let input = match self.query_to_plan(query)? {
+            PlanNode::Select(SelectPlan { input }) => input,
+            plan => Arc::new(plan),
+        };
+
+        let input_schema = input.schema();
+        if input_schema.fields().len() != schema.fields().len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Expect {} columns, but the SELECT returns {} columns",
+                schema.fields().len(),
+                input_schema.fields().len()
+            )));
+        }
+
+        let lenient_cast = self.ctx.get_settings().get_lenient_insert_cast()? != 0;
+        let exprs = input_schema
+            .fields()
+            .iter()
+            .zip(schema.fields())
+            .map(|(from, to)| {
+                let mut expr = Expression::Column(from.name().clone());
+                if from.data_type() != to.data_type() {
+                    expr = Expression::Cast {
+                        expr: Box::new(expr),
+                        data_type: to.data_type().clone(),
+                        is_try: lenient_cast,
+                    };
+                }
+                Expression::Alias(to.name().clone(), Box::new(expr))
+            })
+            .collect::<Vec<_>>();
+
+        let plan = PlanBuilder::from(&input).project(&exprs)?.build()?;
+        Ok(PlanNode::Select(SelectPlan {
+            input: Arc::new(plan),
+        }))
+    }
+
+    /// Generate a logic plan from an SQL query
+    pub fn query_to_plan(&self, query: &sqlparser::ast::Query) -> Result<PlanNode> {
+        let with_parser;
+        let parser = match &query.with {
+            Some(with) => {
+                with_parser = self.create_for_with(with)?;
+                &with_parser
+            }
+            None => self,
+        };
+
+        match &query.body {
+            SetExpr::Select(s) => {
+                parser.select_to_plan(s.as_ref(), &query.limit, &query.offset, &query.order_by)
+            }
+            _ => Result::Err(ErrorCode::UnImplement(format!(
+                "Query {} is not yet implemented",
+                query.body
+            ))),
+        }
+    }
+
+    /// Generate a logic plan from a term of a `UNION`
+    fn set_expr_to_plan(&self, body: &SetExpr) -> Result<PlanNode> {
+        match body {
+            SetExpr::Select(s) => self.select_to_plan(s.as_ref(), &None, &None, &[]),
+            SetExpr::Query(query) => self.query_to_plan(query),
+            _ => Result::Err(ErrorCode::UnImplement(format!(
+                "Query {} is not yet implemented",
+                body
+            ))),
+        }
+    }
+
+    /// Plans the common table expression `name` in place of a reference of it, if it is in scope.
+    fn cte_to_plan(&self, name: &str) -> Result<Option<PlanNode>> {
+        let index = match self.find_cte(name) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        if let Some((pos, working_table)) = &self.working_table {
+            if *pos == index {
+                return Ok(Some(PlanNode::WorkingTable(working_table.clone())));
+            }
+        }
+
+        let cte = &self.ctes[index];
+        let plan = match (cte.recursive, &cte.cte.query.body) {
+            // Such as WITH RECURSIVE t(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t WHERE n < 10)
+            (
+                true,
+                SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    all,
+                    left,
+                    right,
+                },
+            ) => {
+                if !*all {
+                    return Result::Err(ErrorCode::UnImplement(format!(
+                        "Recursive query {} only supports UNION ALL",
+                        name
+                    )));
+                }
+                self.recursive_cte_to_plan(index, left, right)?
+            }
+            _ => {
+                let plan = self
+                    .create_for_cte(index, None)
+                    .query_to_plan(&cte.cte.query)?;
+                Self::rename_columns(name, &plan, &cte.cte.alias.columns)?
+            }
+        };
+        Ok(Some(plan))
+    }
+
+    /// Plans the recursive common table expression at `index` of `ctes`, `anchor UNION ALL
+    /// recursive`. Its columns are typed to hold the rows of both terms, e.g. the column `n` of
+    /// `SELECT 1 AS n UNION ALL SELECT n + 1 FROM t` is UInt16.
+    fn recursive_cte_to_plan(
+        &self,
+        index: usize,
+        anchor: &SetExpr,
+        recursive: &SetExpr,
+    ) -> Result<PlanNode> {
+        let cte = &self.ctes[index].cte;
+        let name = cte.alias.name.value.clone();
+        if !cte.query.order_by.is_empty() || cte.query.limit.is_some() || cte.query.offset.is_some()
+        {
+            return Result::Err(ErrorCode::UnImplement(format!(
+                "ORDER BY, LIMIT and OFFSET are not supported in recursive query {}",
+                name
+            )));
+        }
+
+        let anchor = self.create_for_cte(index, None).set_expr_to_plan(anchor)?;
+        let anchor = Self::rename_columns(&name, &anchor, &cte.alias.columns)?;
+        let recursive_to_plan = |schema: &DataSchemaRef| {
+            let working_table = WorkingTablePlan {
+                name: name.clone(),
+                schema: schema.clone(),
+            };
+            self.create_for_cte(index, Some(working_table))
+                .set_expr_to_plan(recursive)
+        };
+
+        let recursive_plan = recursive_to_plan(&anchor.schema())?;
+        if !Self::reads_working_table(&recursive_plan) {
+            return Result::Err(ErrorCode::UnImplement(format!(
+                "UNION ALL is only supported in recursive query {} reading itself",
+                name
+            )));
+        }
+        let anchor_fields = anchor.schema().fields().clone();
+        let recursive_fields = recursive_plan.schema().fields().clone();
+        if anchor_fields.len() != recursive_fields.len() {
+            return Result::Err(ErrorCode::BadArguments(format!(
+                "The terms of recursive query {} have {} and {} columns",
+                name,
+                anchor_fields.len(),
+                recursive_fields.len()
+            )));
+        }
+
+        let fields = anchor_fields
+            .iter()
+            .zip(recursive_fields.iter())
+            .map(|(left, right)| {
+                let data_type = merge_types(left.data_type(), right.data_type())?;
+                let nullable = left.is_nullable() || right.is_nullable();
+                Ok(DataField::new(left.name(), data_type, nullable))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let schema = DataSchemaRefExt::create(fields);
+
+        // The recursive term reads the working table of the wider columns.
+        let recursive_plan = match schema.fields() == &anchor_fields {
+            true => recursive_plan,
+            false => recursive_to_plan(&schema)?,
+        };
+        Ok(PlanNode::RecursiveCte(RecursiveCtePlan {
+            name,
+            schema: schema.clone(),
+            anchor: Arc::new(Self::cast_columns(&anchor, &schema)?),
+            recursive: Arc::new(Self::cast_columns(&recursive_plan, &schema)?),
+        }))
+    }
+
+    fn reads_working_table(plan: &PlanNode) -> bool {
+        match plan {
+            PlanNode::WorkingTable(_) => true,
+            PlanNode::RecursiveCte(_) => false,
+            _ => plan
+                .inputs()
+                .iter()
+                .any(|input| Self::reads_working_table(input)),
+        }
+    }
+
+    /// Renames the columns of the query of the common table expression `name`, such as `t(a, b)`.
+    fn rename_columns(name: &str, plan: &PlanNode, columns: &[Ident]) -> Result<PlanNode> {
+        if columns.is_empty() {
+            return Ok(plan.clone());
+        }
+
+        let schema = plan.schema();
+        if schema.fields().len() != columns.len() {
+            return Result::Err(ErrorCode::BadArguments(format!(
+                "Query {} has {} columns, but {} column names are specified",
+                name,
+                schema.fields().len(),
+                columns.len()
+            )));
+        }
+
+        let fields = schema
+            .fields()
+            .iter()
+            .zip(columns.iter())
+            .map(|(field, column)| {
+                DataField::new(
+                    &column.value,
+                    field.data_type().clone(),
+                    field.is_nullable(),
+                )
+            })
+            .collect::<Vec<_>>();
+        Self::cast_columns(plan, &DataSchemaRefExt::create(fields))
+    }
+
+    /// Casts the columns of the plan to the fields of `schema` by position.
+    fn cast_columns(plan: &PlanNode, schema: &DataSchemaRef) -> Result<PlanNode> {
+        let exprs = plan
+            .schema()
+            .fields()
+            .iter()
+            .zip(schema.fields().iter())
+            .map(|(from, to)| {
+                let column = Expression::Column(from.name().clone());
+                match Self::cast_to(column, from.data_type(), to.data_type()) {
+                    Expression::Column(name) if &name == to.name() => Expression::Column(name),
+                    expr => expr.alias(to.name()),
+                }
+            })
+            .collect::<Vec<_>>();
+        PlanBuilder::from(plan).project(&exprs)?.build()
+    }
+
+    /// Generate a logic plan from an SQL select
+    /// For example:
+    /// "select sum(number+1)+2, number%3 as id from numbers(10) where number>1 group by id having id>1 order by id desc limit 3"
+    #[tracing::instrument(level = "info", skip(self, select, limit, order_by))]
+    fn select_to_plan(
+        &self,
+        select: &sqlparser::ast::Select,
+        limit: &Option<sqlparser::ast::Expr>,
+        offset: &Option<sqlparser::ast::Offset>,
+        order_by: &[OrderByExpr],
+    ) -> Result<PlanNode> {
+        // Filter expression
+        // In example: Filter=(number > 1)
+        let plan = self
+            .plan_tables_with_joins(&select.from)
+            .and_then(|input| self.filter(&input, &select.selection, Some(select)))?;
+
+        // Projection expression
+        // In example: Projection=[(sum((number + 1)) + 2), (number % 3) as id]
+        let projection_exprs = select
+            .projection
+            .iter()
+            .map(|e| self.sql_select_to_rex(e, &plan.schema(), Some(select)))
+            .collect::<Result<Vec<Expression>>>()?
+            .iter()
+            .flat_map(|expr| expand_wildcard(expr, &plan.schema()))
+            .collect::<Vec<Expression>>();
+
+        // Aliases replacement for group by, having, sorting
+        // In example: Aliases=[("id", (number % 3))]
+        let aliases = extract_aliases(&projection_exprs);
+
+        // Group By expression after against aliases
+        // In example: GroupBy=[(number % 3)]
+        let mut group_by_exprs = select
+            .group_by
+            .iter()
+            .map(|e| {
+                self.sql_to_rex(e, &plan.schema(), Some(select))
+                    .and_then(|expr| resolve_aliases_to_exprs(&expr, &aliases))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Case-insensitive group keys are grouped by their collation keys, the other references
+        // to them out of the aggregate functions take the smallest value of the group.
+        // In example: GroupBy=[lower(name)], Projection=[min(name) as name]
+        let mut collated_exprs = vec![];
+        for (sql_expr, expr) in select.group_by.iter().zip(group_by_exprs.iter_mut()) {
+            let (collation, _) = self.sql_select_collation(sql_expr, &plan.schema(), select)?;
+            if collation == Collation::CaseInsensitive {
+                collated_exprs.push(expr.clone());
+                *expr = Self::collation_key(expr.clone(), collation);
+            }
+        }
+        let projection_exprs = projection_exprs
+            .iter()
+            .map(|expr| {
+                let rewritten =
+                    Self::rewrite_collated_group_by(expr, &group_by_exprs, &collated_exprs)?;
+                match expr {
+                    Expression::Alias(..) => Ok(rewritten),
+                    _ if &rewritten != expr => {
+                        Ok(Expression::Alias(expr.column_name(), Box::new(rewritten)))
+                    }
+                    _ => Ok(rewritten),
+                }
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -549,20 +2154,26 @@ impl PlanParser {
                 let having_expr = self.sql_to_rex(having_expr, &plan.schema(), Some(select))?;
                 let having_expr = resolve_aliases_to_exprs(&having_expr, &aliases)?;
 
-                Ok(having_expr)
+                Self::rewrite_collated_group_by(&having_expr, &group_by_exprs, &collated_exprs)
             })
             .transpose()?;
 
-        // OrderBy expression after against aliases
+        // OrderBy expression after against aliases, sorted by the collation keys
         // In example: Sort=(number % 3)
         let order_by_exprs = order_by
             .iter()
             .map(|e| -> Result<Expression> {
+                let expr = self
+                    .sql_to_rex(&e.expr, &plan.schema(), Some(select))
+                    .and_then(|expr| resolve_aliases_to_exprs(&expr, &aliases))?;
+                let (collation, _) = self.sql_select_collation(&e.expr, &plan.schema(), select)?;
+                let expr = Self::collation_key(expr, collation);
                 Ok(Expression::Sort {
-                    expr: Box::new(
-                        self.sql_to_rex(&e.expr, &plan.schema(), Some(select))
-                            .and_then(|expr| resolve_aliases_to_exprs(&expr, &aliases))?,
-                    ),
+                    expr: Box::new(Self::rewrite_collated_group_by(
+                        &expr,
+                        &group_by_exprs,
+                        &collated_exprs,
+                    )?),
                     asc: e.asc.unwrap_or(true),
                     nulls_first: e.nulls_first.unwrap_or(true),
                 })
@@ -580,6 +2191,15 @@ impl PlanParser {
             expression_exprs.push(having_expr.clone());
         }
 
+        // Window functions are computed over the rows after the aggregation and HAVING.
+        let mut grouping_exprs = group_by_exprs.clone();
+        grouping_exprs.extend(having_expr_opt.iter().cloned());
+        if !find_window_exprs(&grouping_exprs).is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(
+                "Window functions are not allowed in GROUP BY or HAVING",
+            ));
+        }
+
         // All of the aggregate expressions (deduplicated).
         // In example: aggr=[[sum((number + 1))]]
         let aggr_exprs = find_aggregate_exprs(&expression_exprs);
@@ -643,7 +2263,21 @@ impl PlanParser {
 
             (plan, having_expr_post_aggr_opt)
         } else {
-            (plan, having_expr_opt)
+            (plan, having_expr_opt)
+        };
+
+        // Window functions, after the HAVING which filters the rows of the windows.
+        // For example: Window=[rank() OVER (ORDER BY sum((number + 1)))]
+        let window_exprs = find_window_exprs(&expression_with_sort);
+        let (plan, having_expr_post_aggr_opt) = if window_exprs.is_empty() {
+            (plan, having_expr_post_aggr_opt)
+        } else {
+            let window_arg_exprs = expand_window_arg_exprs(&window_exprs);
+            let plan = self
+                .having(&plan, having_expr_post_aggr_opt)
+                .and_then(|input| self.expression(&input, &window_arg_exprs, "Before Window"))
+                .and_then(|input| self.window(&input, &window_exprs))?;
+            (plan, None)
         };
 
         let stage_phase = if order_by_exprs.is_empty() {
@@ -689,16 +2323,201 @@ impl PlanParser {
         }
     }
 
+    /// Rewrites the references to the case-insensitive group keys out of the aggregate functions
+    /// as `min(key)`, the keys of `group_by_exprs` are kept.
+    fn rewrite_collated_group_by(
+        expr: &Expression,
+        group_by_exprs: &[Expression],
+        collated_exprs: &[Expression],
+    ) -> Result<Expression> {
+        if collated_exprs.is_empty() {
+            return Ok(expr.clone());
+        }
+
+        replace_non_aggregated_exprs(expr, group_by_exprs, &|nest_expr| {
+            if !collated_exprs.contains(nest_expr) {
+                return None;
+            }
+            Some(Expression::AggregateFunction {
+                op: "min".to_string(),
+                distinct: false,
+                params: vec![],
+                args: vec![nest_expr.clone()],
+            })
+        })
+    }
+
+    /// Returns the key which compares as the collation, case-folded if case-insensitive.
+    fn collation_key(expr: Expression, collation: Collation) -> Expression {
+        match collation {
+            Collation::Binary => expr,
+            Collation::CaseInsensitive => Expression::ScalarFunction {
+                op: "lower".to_string(),
+                args: vec![expr],
+            },
+        }
+    }
+
+    /// Returns the collation of a SQL expression and whether it is explicit,
+    /// the `COLLATE` clause takes precedence over the collation of the column.
+    fn sql_collation(
+        &self,
+        expr: &sqlparser::ast::Expr,
+        schema: &DataSchema,
+        select: Option<&sqlparser::ast::Select>,
+    ) -> Result<(Collation, bool)> {
+        let column = match expr {
+            sqlparser::ast::Expr::Collate { collation, .. } => {
+                return Ok((Collation::from_name(&collation.to_string())?, true));
+            }
+            sqlparser::ast::Expr::Nested(e) => return self.sql_collation(e, schema, select),
+            sqlparser::ast::Expr::Identifier(id) => &id.value,
+            sqlparser::ast::Expr::CompoundIdentifier(ids) if ids.len() == 2 => &ids[1].value,
+            _ => return Ok((Collation::Binary, false)),
+        };
+        match schema.field_with_name(column) {
+            Ok(field) if field.data_type() == &DataType::String => {}
+            _ => return Ok((Collation::Binary, false)),
+        }
+
+        let collation = self
+            .from_table_collations(select)
+            .column_collation(schema, column)?;
+        Ok((collation, false))
+    }
+
+    /// Returns the collation of a GROUP BY or ORDER BY expression,
+    /// which may be an alias of the projection.
+    fn sql_select_collation(
+        &self,
+        expr: &sqlparser::ast::Expr,
+        schema: &DataSchema,
+        select: &sqlparser::ast::Select,
+    ) -> Result<(Collation, bool)> {
+        if let sqlparser::ast::Expr::Identifier(id) = expr {
+            for item in select.projection.iter() {
+                if let sqlparser::ast::SelectItem::ExprWithAlias { expr, alias } = item {
+                    if alias.value == id.value {
+                        return self.sql_collation(expr, schema, Some(select));
+                    }
+                }
+            }
+        }
+        self.sql_collation(expr, schema, Some(select))
+    }
+
+    /// Returns the collations of the table in the FROM clause, the default if it is not a table.
+    fn from_table_collations(&self, select: Option<&sqlparser::ast::Select>) -> TableCollations {
+        let relation = match select.map(|select| select.from.as_slice()) {
+            Some([table]) | Some([table, _]) => &table.relation,
+            _ => return TableCollations::default(),
+        };
+
+        match relation {
+            TableFactor::Table { name, args, .. } if args.is_empty() => {
+                let (db_name, table_name) = match name.0.as_slice() {
+                    [table] if self.find_cte(&table.value).is_some() => {
+                        return TableCollations::default()
+                    }
+                    [table] => (self.current_database(), table.value.clone()),
+                    [db, table] => (db.value.clone(), table.value.clone()),
+                    _ => return TableCollations::default(),
+                };
+                self.ctx
+                    .get_table(&db_name, &table_name)
+                    .map(|table| TableCollations::from_options(&table.get_table_info().options))
+                    .unwrap_or_default()
+            }
+            _ => TableCollations::default(),
+        }
+    }
+
+    /// Generates the operands of a comparison, which compares their collation keys if either side
+    /// is case-insensitive. The explicit collations of both sides must be the same.
+    fn sql_comparison_operands(
+        &self,
+        left: &sqlparser::ast::Expr,
+        right: &sqlparser::ast::Expr,
+        schema: &DataSchema,
+        select: Option<&sqlparser::ast::Select>,
+    ) -> Result<(Expression, Expression)> {
+        let left_rex = self.sql_to_rex(left, schema, select)?;
+        let right_rex = self.sql_to_rex(right, schema, select)?;
+
+        let left_collation = self.sql_collation(left, schema, select)?;
+        let right_collation = self.sql_collation(right, schema, select)?;
+        let collation = match (left_collation, right_collation) {
+            ((l, true), (r, true)) if l != r => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Illegal mix of collations for the comparison of {} and {}",
+                    left, right
+                )))
+            }
+            ((l, true), _) => l,
+            (_, (r, true)) => r,
+            ((Collation::CaseInsensitive, _), _) | (_, (Collation::CaseInsensitive, _)) => {
+                Collation::CaseInsensitive
+            }
+            _ => Collation::Binary,
+        };
+
+        Ok((
+            Self::collation_key(left_rex, collation),
+            Self::collation_key(right_rex, collation),
+        ))
+    }
+
     fn plan_tables_with_joins(&self, from: &[sqlparser::ast::TableWithJoins]) -> Result<PlanNode> {
         match from.len() {
             0 => self.plan_with_dummy_source(),
             1 => self.plan_table_with_joins(&from[0]),
+            // Such as SELECT * FROM t, UNNEST(t.arr) AS u(elem);
+            2 if from[1].joins.is_empty() => match Self::unnest_relation(&from[1].relation)? {
+                Some((column, alias)) => {
+                    let input = self.plan_table_with_joins(&from[0])?;
+                    PlanBuilder::from(&input).unnest(&column, &alias)?.build()
+                }
+                None => Result::Err(ErrorCode::SyntaxException("Cannot SELECT multiple tables")),
+            },
             // Such as SELECT * FROM t1, t2;
             // It's not `JOIN` clause.
             _ => Result::Err(ErrorCode::SyntaxException("Cannot SELECT multiple tables")),
         }
     }
 
+    /// Returns the array column and the element column name if the relation is `UNNEST(col)`.
+    fn unnest_relation(relation: &TableFactor) -> Result<Option<(String, String)>> {
+        match relation {
+            TableFactor::Table {
+                name, args, alias, ..
+            } if name.0.len() == 1 && name.0[0].value.eq_ignore_ascii_case("unnest") => {
+                let column = match args.as_slice() {
+                    [FunctionArg::Unnamed(sqlparser::ast::Expr::Identifier(id))] => {
+                        id.value.clone()
+                    }
+                    [FunctionArg::Unnamed(sqlparser::ast::Expr::CompoundIdentifier(ids))]
+                        if ids.len() == 2 =>
+                    {
+                        ids[1].value.clone()
+                    }
+                    _ => {
+                        return Result::Err(ErrorCode::UnImplement(
+                            "UNNEST only supports a single array column argument",
+                        ))
+                    }
+                };
+
+                let alias = alias
+                    .as_ref()
+                    .and_then(|alias| alias.columns.first())
+                    .map(|column| column.value.clone())
+                    .unwrap_or_else(|| "unnest".to_string());
+                Ok(Some((column, alias)))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn plan_with_dummy_source(&self) -> Result<PlanNode> {
         let db_name = "system";
         let table_name = "one";
@@ -741,7 +2560,14 @@ impl PlanParser {
     fn create_relation(&self, relation: &sqlparser::ast::TableFactor) -> Result<PlanNode> {
         match relation {
             TableFactor::Table { name, args, .. } => {
-                let mut db_name = self.ctx.get_current_database();
+                // The common table expressions shadow the tables.
+                if args.is_empty() && name.0.len() == 1 {
+                    if let Some(plan) = self.cte_to_plan(&name.0[0].value)? {
+                        return Ok(plan);
+                    }
+                }
+
+                let mut db_name = self.current_database();
                 let mut table_name = name.to_string();
                 if name.0.len() == 2 {
                     db_name = name.0[0].to_string();
@@ -784,6 +2610,9 @@ impl PlanParser {
                     table = table_func.as_table();
                 } else {
                     table = self.ctx.get_table(&db_name, &table_name)?;
+                    if let Some(view) = table.as_any().downcast_ref::<ViewTable>() {
+                        return self.view_to_plan(&db_name, view);
+                    }
                     meta_id = table.get_id();
                     meta_version = table.get_table_info().version;
                 }
@@ -828,6 +2657,67 @@ impl PlanParser {
             }
         }
     }
+
+    /// Plans the query of a view in place of the view.
+    fn view_to_plan(&self, db: &str, view: &ViewTable) -> Result<PlanNode> {
+        if self.view_depth >= MAX_VIEW_DEPTH {
+            return Result::Err(ErrorCode::BadArguments(format!(
+                "View {}.{} is nested more than {} levels, it may reference itself",
+                db,
+                view.name(),
+                MAX_VIEW_DEPTH
+            )));
+        }
+
+        let (statements, _) =
+            DfParser::parse_sql_with_ident_case(view.query(), self.ident_case()?)?;
+        match statements.as_slice() {
+            [DfStatement::Statement(Statement::Query(query))] => {
+                self.create_for_view(db).query_to_plan(query)
+            }
+            _ => Result::Err(ErrorCode::LogicalError(format!(
+                "View {}.{} has an invalid query: {}",
+                db,
+                view.name(),
+                view.query()
+            ))),
+        }
+    }
+
+    /// `s.a.b` reads the fields of the struct column `s`, also written as `t.s.a.b`,
+    /// each step is planned into `tupleElement`.
+    fn struct_field_access(
+        &self,
+        ids: &[Ident],
+        schema: &DataSchema,
+        select: Option<&sqlparser::ast::Select>,
+    ) -> Result<Option<Expression>> {
+        let is_struct = |id: &Ident| match schema.field_with_name(&id.value) {
+            Ok(field) => matches!(field.data_type(), DataType::Struct(_)),
+            Err(_) => false,
+        };
+
+        let (column, fields) = if is_struct(&ids[0]) {
+            (Expression::Column(ids[0].value.clone()), &ids[1..])
+        } else if ids.len() > 2 && is_struct(&ids[1]) {
+            (self.process_compound_ident(&ids[..2], select)?, &ids[2..])
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(fields.iter().fold(column, |expr, field| {
+            Expression::ScalarFunction {
+                op: "tupleElement".to_string(),
+                args: vec![
+                    expr,
+                    Expression::create_literal(DataValue::String(Some(
+                        field.value.as_bytes().to_vec(),
+                    ))),
+                ],
+            }
+        })))
+    }
+
     fn process_compound_ident(
         &self,
         ids: &[Ident],
@@ -845,9 +2735,22 @@ impl PlanParser {
         }
 
         let table_name = &var_names[0];
-        let from = &select.unwrap().from;
+        let from = &select.unwrap().from[..];
         let obj_table_name = ObjectName(vec![Ident::new(table_name)]);
 
+        // In `FROM t, UNNEST(t.arr) AS u(elem)` the identifier may refer to either relation.
+        let from = match from {
+            [table, unnest] if Self::unnest_relation(&unnest.relation)?.is_some() => {
+                if let TableFactor::Table { alias: Some(a), .. } = &unnest.relation {
+                    if a.name == ids[0] {
+                        return Ok(Expression::Column(var_names.pop().unwrap()));
+                    }
+                }
+                std::slice::from_ref(table)
+            }
+            _ => from,
+        };
+
         match from.len() {
             0 => Err(ErrorCode::SyntaxException(
                 "Missing table in the select clause",
@@ -905,6 +2808,152 @@ impl PlanParser {
         }
     }
 
+    /// `f(a, b)` calls the function created by `CREATE [EXTERNAL] FUNCTION [ns.]f`, whose
+    /// definition is passed as the first argument, a literal named after the function.
+    fn session_function_to_rex(
+        &self,
+        function: &SessionFunction,
+        e: &sqlparser::ast::Function,
+        schema: &DataSchema,
+        select: Option<&sqlparser::ast::Select>,
+    ) -> Result<Expression> {
+        if e.args.len() != function.arg_types.len() {
+            return Result::Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                "Function {} expect to have {} arguments, but got {}",
+                function.name,
+                function.arg_types.len(),
+                e.args.len()
+            )));
+        }
+
+        let mut args = Vec::with_capacity(e.args.len() + 1);
+        args.push(Expression::Literal {
+            value: DataValue::String(Some(function.definition.clone())),
+            column_name: Some(function.name.clone()),
+            data_type: DataType::String,
+        });
+        for arg in &e.args {
+            match &arg {
+                FunctionArg::Named { arg, .. } => args.push(self.sql_to_rex(arg, schema, select)?),
+                FunctionArg::Unnamed(arg) => args.push(self.sql_to_rex(arg, schema, select)?),
+            }
+        }
+
+        Ok(Expression::ScalarFunction {
+            op: function.function_name.clone(),
+            args,
+        })
+    }
+
+    /// The parameters of an aggregate function, e.g. `windowFunnel(60)(t, c1, c2)`.
+    fn function_params(e: &sqlparser::ast::Function) -> Result<Vec<DataValue>> {
+        e.params
+            .iter()
+            .map(|v| {
+                let expr = Self::value_to_rex(v);
+                if let Ok(Expression::Literal { value, .. }) = expr {
+                    Ok(value)
+                } else {
+                    Result::Err(ErrorCode::SyntaxException(format!(
+                        "Unsupported value expression: {:?}, must be datavalue",
+                        expr
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+    }
+
+    /// A ranking function, `row_number()`, `rank()` or `dense_rank()`, or an aggregate function
+    /// over a window. Only the default frame of the window is supported: the partition of the
+    /// row, up to the last peer of the row if the window is ordered.
+    fn window_function_to_rex(
+        &self,
+        op: &str,
+        e: &sqlparser::ast::Function,
+        window: &WindowSpec,
+        schema: &DataSchema,
+        select: Option<&sqlparser::ast::Select>,
+    ) -> Result<Expression> {
+        if window.window_frame.is_some() {
+            return Result::Err(ErrorCode::UnImplement(format!(
+                "Window frame is not supported: {}",
+                e
+            )));
+        }
+        if e.distinct {
+            return Result::Err(ErrorCode::UnImplement(format!(
+                "DISTINCT is not supported in window function: {}",
+                e
+            )));
+        }
+
+        let mut args = Vec::with_capacity(e.args.len());
+        for arg in &e.args {
+            match &arg {
+                FunctionArg::Named { arg, .. } => args.push(self.sql_to_rex(arg, schema, select)?),
+                FunctionArg::Unnamed(arg) => args.push(self.sql_to_rex(arg, schema, select)?),
+            }
+        }
+
+        if is_ranking_function(op) {
+            if !args.is_empty() {
+                return Result::Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                    "Function {} expect to have 0 arguments, but got {}",
+                    op,
+                    args.len()
+                )));
+            }
+        } else if AggregateFunctionFactory::instance().check(op) {
+            if op.eq_ignore_ascii_case("count") {
+                args = args
+                    .into_iter()
+                    .map(|c| match c {
+                        Expression::Wildcard => common_planners::lit(0i64),
+                        _ => c,
+                    })
+                    .collect();
+            }
+        } else {
+            return Result::Err(ErrorCode::UnknownFunction(format!(
+                "Unsupported window function: {}, expect a ranking or aggregate function",
+                op
+            )));
+        }
+
+        let partition_by = window
+            .partition_by
+            .iter()
+            .map(|expr| self.sql_to_rex(expr, schema, select))
+            .collect::<Result<Vec<_>>>()?;
+        let order_by = window
+            .order_by
+            .iter()
+            .map(|expr| {
+                Ok(Expression::Sort {
+                    expr: Box::new(self.sql_to_rex(&expr.expr, schema, select)?),
+                    asc: expr.asc.unwrap_or(true),
+                    nulls_first: expr.nulls_first.unwrap_or(true),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let nested = args.iter().chain(&partition_by).chain(&order_by);
+        if !find_window_exprs(&nested.cloned().collect::<Vec<_>>()).is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(format!(
+                "Window functions can not be nested: {}",
+                e
+            )));
+        }
+
+        Ok(Expression::WindowFunction {
+            op: op.to_string(),
+            params: Self::function_params(e)?,
+            args,
+            partition_by,
+            order_by,
+        })
+    }
+
     fn interval_to_day_time(days: i32, ms: i32) -> Result<Expression> {
         let data_type = DataType::Interval(IntervalUnit::DayTime);
         let milliseconds_per_day = 24 * 3600 * 1000;
@@ -942,6 +2991,31 @@ impl PlanParser {
         }
     }
 
+    // The interval of string like '1 hour' or '3 days'.
+    fn interval_str_to_rex(value: &sqlparser::ast::Value, interval: &str) -> Result<Expression> {
+        let parts = interval.split_whitespace().collect::<Vec<_>>();
+        let num = match parts.as_slice() {
+            [num, _] => num.parse::<i32>().ok(),
+            _ => None,
+        };
+        let unit = parts.get(1).map(|unit| unit.to_lowercase());
+
+        match (num, unit.as_deref().map(|unit| unit.trim_end_matches('s'))) {
+            (Some(num), Some("year")) => Self::interval_to_year_month(num * 12),
+            (Some(num), Some("quarter")) => Self::interval_to_year_month(num * 3),
+            (Some(num), Some("month")) => Self::interval_to_year_month(num),
+            (Some(num), Some("week")) => Self::interval_to_day_time(num * 7, 0),
+            (Some(num), Some("day")) => Self::interval_to_day_time(num, 0),
+            (Some(num), Some("hour")) => Self::interval_to_day_time(0, num * 3600 * 1000),
+            (Some(num), Some("minute")) => Self::interval_to_day_time(0, num * 60 * 1000),
+            (Some(num), Some("second")) => Self::interval_to_day_time(0, num * 1000),
+            _ => Result::Err(ErrorCode::SyntaxException(format!(
+                "Unsupported interval expression: {}.",
+                value
+            ))),
+        }
+    }
+
     fn value_to_rex(value: &sqlparser::ast::Value) -> Result<Expression> {
         match value {
             sqlparser::ast::Value::Number(ref n, _) => {
@@ -972,15 +3046,10 @@ impl PlanParser {
                 }
 
                 // When the input is like "interval '1 hour'", leading_field will be None and value_expr will be '1 hour'.
-                // We may want to support this pattern in native paser (sqlparser-rs), to have a parsing result that leading_field is Some(Hour) and value_expr is number '1'.
-                if leading_field.is_none() {
-                    //TODO: support parsing literal interval like '1 hour'
-                    return Result::Err(ErrorCode::SyntaxException(format!(
-                        "Unsupported interval expression: {}.",
-                        value
-                    )));
+                match leading_field {
+                    None => Self::interval_str_to_rex(value, value_expr),
+                    Some(leading_field) => Self::interval_to_rex(value_expr, leading_field.clone()),
                 }
-                Self::interval_to_rex(value_expr, leading_field.clone().unwrap())
             }
             sqlparser::ast::Value::Null => Ok(Expression::create_literal(DataValue::Null)),
             other => Result::Err(ErrorCode::SyntaxException(format!(
@@ -1000,13 +3069,29 @@ impl PlanParser {
         match expr {
             sqlparser::ast::Expr::Value(value) => Self::value_to_rex(value),
             sqlparser::ast::Expr::Identifier(ref v) => Ok(Expression::Column(v.clone().value)),
-            sqlparser::ast::Expr::BinaryOp { left, op, right } => {
-                Ok(Expression::BinaryExpression {
+            sqlparser::ast::Expr::BinaryOp { left, op, right } => match op {
+                BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+                | BinaryOperator::Like
+                | BinaryOperator::NotLike => {
+                    let (left, right) =
+                        self.sql_comparison_operands(left, right, schema, select)?;
+                    Ok(Expression::BinaryExpression {
+                        op: format!("{}", op),
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    })
+                }
+                _ => Ok(Expression::BinaryExpression {
                     op: format!("{}", op),
                     left: Box::new(self.sql_to_rex(left, schema, select)?),
                     right: Box::new(self.sql_to_rex(right, schema, select)?),
-                })
-            }
+                }),
+            },
             sqlparser::ast::Expr::UnaryOp { op, expr } => match op {
                 UnaryOperator::Plus => self.sql_to_rex(expr, schema, select),
                 _ => Ok(Expression::UnaryExpression {
@@ -1022,6 +3107,30 @@ impl PlanParser {
                 op: "isnotnull".to_owned(),
                 args: vec![self.sql_to_rex(expr, schema, select)?],
             }),
+            sqlparser::ast::Expr::IsNotDistinctFrom(left, right) => {
+                let (left, right) = self.sql_comparison_operands(left, right, schema, select)?;
+                Ok(Expression::BinaryExpression {
+                    op: "<=>".to_string(),
+                    left: Box::new(left),
+                    right: Box::new(right),
+                })
+            }
+            sqlparser::ast::Expr::IsDistinctFrom(left, right) => {
+                let (left, right) = self.sql_comparison_operands(left, right, schema, select)?;
+                Ok(Expression::UnaryExpression {
+                    op: "not".to_string(),
+                    expr: Box::new(Expression::BinaryExpression {
+                        op: "<=>".to_string(),
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    }),
+                })
+            }
+            // The collation only changes how the value compares, see `sql_comparison_operands`.
+            sqlparser::ast::Expr::Collate { expr, collation } => {
+                Collation::from_name(&collation.to_string())?;
+                self.sql_to_rex(expr, schema, select)
+            }
             sqlparser::ast::Expr::Exists(q) => Ok(Expression::ScalarFunction {
                 op: "EXISTS".to_lowercase(),
                 args: vec![self.subquery_to_rex(q)?],
@@ -1029,17 +3138,40 @@ impl PlanParser {
             sqlparser::ast::Expr::Subquery(q) => Ok(self.scalar_subquery_to_rex(q)?),
             sqlparser::ast::Expr::Nested(e) => self.sql_to_rex(e, schema, select),
             sqlparser::ast::Expr::CompoundIdentifier(ids) => {
-                self.process_compound_ident(ids.as_slice(), select)
+                match self.struct_field_access(ids.as_slice(), schema, select)? {
+                    Some(expr) => Ok(expr),
+                    None => self.process_compound_ident(ids.as_slice(), select),
+                }
             }
             sqlparser::ast::Expr::Function(e) => {
+                let names = e
+                    .name
+                    .0
+                    .iter()
+                    .map(|i| i.value.as_str())
+                    .collect::<Vec<_>>();
+                let op = match FunctionResolver::create(self.ctx.clone()).resolve(&names)? {
+                    ResolvedFunction::Udf(function) if e.over.is_some() => {
+                        return Result::Err(ErrorCode::UnImplement(format!(
+                            "Function {} can not be a window function",
+                            function.name
+                        )));
+                    }
+                    ResolvedFunction::Udf(function) => {
+                        return self.session_function_to_rex(&function, e, schema, select);
+                    }
+                    ResolvedFunction::Builtin(op) => op,
+                };
+
+                if let Some(window) = &e.over {
+                    return self.window_function_to_rex(&op, e, window, schema, select);
+                }
+
                 let mut args = Vec::with_capacity(e.args.len());
 
                 // 1. Get the args from context by function name. such as SELECT database()
                 // common::ScalarFunctions::udf::database arg is ctx.get_default()
-                let ctx_args = ContextFunction::build_args_from_ctx(
-                    e.name.to_string().as_str(),
-                    self.ctx.clone(),
-                )?;
+                let ctx_args = ContextFunction::build_args_from_ctx(op.as_str(), self.ctx.clone())?;
                 if !ctx_args.is_empty() {
                     args.extend_from_slice(ctx_args.as_slice());
                 }
@@ -1056,7 +3188,6 @@ impl PlanParser {
                     }
                 }
 
-                let op = e.name.to_string();
                 if AggregateFunctionFactory::instance().check(&op) {
                     let args = match op.to_lowercase().as_str() {
                         "count" => args
@@ -1069,26 +3200,10 @@ impl PlanParser {
                         _ => args,
                     };
 
-                    let params = e
-                        .params
-                        .iter()
-                        .map(|v| {
-                            let expr = Self::value_to_rex(v);
-                            if let Ok(Expression::Literal { value, .. }) = expr {
-                                Ok(value)
-                            } else {
-                                Result::Err(ErrorCode::SyntaxException(format!(
-                                    "Unsupported value expression: {:?}, must be datavalue",
-                                    expr
-                                )))
-                            }
-                        })
-                        .collect::<Result<Vec<_>>>()?;
-
                     return Ok(Expression::AggregateFunction {
                         op,
                         distinct: e.distinct,
-                        params,
+                        params: Self::function_params(e)?,
                         args,
                     });
                 }
@@ -1102,14 +3217,28 @@ impl PlanParser {
                         value.clone().into_bytes(),
                     )))),
                     data_type,
+                    is_try: false,
                 })
             }
             sqlparser::ast::Expr::Cast { expr, data_type } => self
                 .sql_to_rex(expr, schema, select)
                 .map(Box::from)
                 .and_then(|expr| {
-                    SQLCommon::make_data_type(data_type)
-                        .map(|data_type| Expression::Cast { expr, data_type })
+                    SQLCommon::make_data_type(data_type).map(|data_type| Expression::Cast {
+                        expr,
+                        data_type,
+                        is_try: false,
+                    })
+                }),
+            sqlparser::ast::Expr::TryCast { expr, data_type } => self
+                .sql_to_rex(expr, schema, select)
+                .map(Box::from)
+                .and_then(|expr| {
+                    SQLCommon::make_data_type(data_type).map(|data_type| Expression::Cast {
+                        expr,
+                        data_type,
+                        is_try: true,
+                    })
                 }),
             sqlparser::ast::Expr::Substring {
                 expr,
@@ -1186,6 +3315,10 @@ impl PlanParser {
             let variable = variable.value.clone();
             let value = match value {
                 sqlparser::ast::SetVariableValue::Ident(v) => v.value.clone(),
+                // The quotes are not a part of the string settings, e.g. `SET query_tag = 'etl'`.
+                sqlparser::ast::SetVariableValue::Literal(
+                    sqlparser::ast::Value::SingleQuotedString(v),
+                ) => v.clone(),
                 sqlparser::ast::SetVariableValue::Literal(v) => v.to_string(),
             };
             vars.push(VarValue { variable, value });
@@ -1193,22 +3326,94 @@ impl PlanParser {
         Ok(PlanNode::SetVariable(SettingPlan { vars }))
     }
 
-    /// Apply a filter to the plan
+    /// Apply a filter to the plan, the filter is also pushed down to the table being read
     fn filter(
         &self,
         plan: &PlanNode,
         predicate: &Option<sqlparser::ast::Expr>,
         select: Option<&sqlparser::ast::Select>,
     ) -> Result<PlanNode> {
-        match *predicate {
-            Some(ref predicate_expr) => self
-                .sql_to_rex(predicate_expr, &plan.schema(), select)
-                .and_then(|filter_expr| {
-                    PlanBuilder::from(plan)
-                        .filter(filter_expr)
-                        .and_then(|builder| builder.build())
-                }),
-            _ => Ok(plan.clone()),
+        let (prewhere, predicate) = Self::split_prewhere(predicate);
+        let prewhere = prewhere
+            .map(|expr| self.sql_to_rex(&expr, &plan.schema(), select))
+            .transpose()?;
+        let predicate = predicate
+            .map(|expr| self.sql_to_rex(&expr, &plan.schema(), select))
+            .transpose()?;
+
+        let filter_expr = match (prewhere.clone(), predicate) {
+            (Some(prewhere), Some(predicate)) => prewhere.and(predicate),
+            (Some(filter_expr), None) | (None, Some(filter_expr)) => filter_expr,
+            (None, None) => return Ok(plan.clone()),
+        };
+        if !find_window_exprs(&[filter_expr.clone()]).is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(
+                "Window functions are not allowed in WHERE",
+            ));
+        }
+
+        let plan = Self::push_down_filter(plan, &filter_expr, prewhere);
+        PlanBuilder::from(&plan)
+            .filter(filter_expr)
+            .and_then(|builder| builder.build())
+    }
+
+    /// Take the condition of the PREWHERE clause out of the marker function which the
+    /// DfParser rewrites it to, returns it with the remaining predicate of the WHERE clause.
+    fn split_prewhere(
+        predicate: &Option<sqlparser::ast::Expr>,
+    ) -> (Option<sqlparser::ast::Expr>, Option<sqlparser::ast::Expr>) {
+        let prewhere_condition = |expr: &sqlparser::ast::Expr| match expr {
+            sqlparser::ast::Expr::Function(f)
+                if f.name.to_string().eq_ignore_ascii_case(PREWHERE_FUNCTION) =>
+            {
+                match f.args.as_slice() {
+                    [FunctionArg::Unnamed(condition)] => Some(condition.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        match predicate {
+            Some(sqlparser::ast::Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            }) => match prewhere_condition(left) {
+                Some(prewhere) => (Some(prewhere), Some(right.as_ref().clone())),
+                None => (None, predicate.clone()),
+            },
+            Some(expr) => match prewhere_condition(expr) {
+                Some(prewhere) => (Some(prewhere), None),
+                None => (None, predicate.clone()),
+            },
+            None => (None, None),
+        }
+    }
+
+    /// Hand the filter over to the table being read, so that it may skip reading data.
+    /// The filter is applied after reading all the same.
+    fn push_down_filter(
+        plan: &PlanNode,
+        filter_expr: &Expression,
+        prewhere: Option<Expression>,
+    ) -> PlanNode {
+        match plan {
+            PlanNode::ReadSource(read_source) => {
+                let mut read_source = read_source.clone();
+                let mut push_downs = read_source
+                    .push_downs
+                    .take()
+                    .unwrap_or_else(Extras::default);
+                push_downs.filters = split_conjunctions(filter_expr);
+                push_downs.prewhere = prewhere
+                    .map(|prewhere| split_conjunctions(&prewhere))
+                    .unwrap_or_default();
+                read_source.push_downs = Some(push_downs);
+                PlanNode::ReadSource(read_source)
+            }
+            _ => plan.clone(),
         }
     }
 
@@ -1263,6 +3468,18 @@ impl PlanParser {
             .and_then(|builder| builder.build())
     }
 
+    /// Wrap a plan for the window functions
+    fn window(&self, input: &PlanNode, window_exprs: &[Expression]) -> Result<PlanNode> {
+        let window_exprs = window_exprs
+            .iter()
+            .map(|expr| rebase_expr_from_input(expr, &input.schema()))
+            .collect::<Result<Vec<_>>>()?;
+
+        PlanBuilder::from(input)
+            .window(&window_exprs)
+            .and_then(|builder| builder.build())
+    }
+
     fn sort(&self, input: &PlanNode, order_by_exprs: &[Expression]) -> Result<PlanNode> {
         if order_by_exprs.is_empty() {
             return Ok(input.clone());
@@ -1358,3 +3575,95 @@ impl PlanParser {
             .and_then(|builder| builder.build())
     }
 }
+
+/// The names visible to the expressions of `MERGE`, the source columns are renamed to
+/// `<source>.<name>` so that they never clash with the target columns.
+struct MergeNames {
+    target: String,
+    target_columns: Vec<String>,
+    source: String,
+    source_columns: Vec<String>,
+}
+
+impl MergeNames {
+    fn resolve_ident(&self, column: &str) -> sqlparser::ast::Expr {
+        match self.target_columns.iter().any(|c| c == column)
+            || !self.source_columns.iter().any(|c| c == column)
+        {
+            true => sqlparser::ast::Expr::Identifier(Ident::new(column)),
+            false => {
+                sqlparser::ast::Expr::Identifier(Ident::new(format!("{}.{}", self.source, column)))
+            }
+        }
+    }
+
+    fn resolve(&self, expr: &sqlparser::ast::Expr) -> Result<sqlparser::ast::Expr> {
+        use sqlparser::ast::Expr;
+
+        let resolve = |expr: &Expr| self.resolve(expr).map(Box::new);
+        Ok(match expr {
+            Expr::Identifier(ident) => self.resolve_ident(&ident.value),
+            Expr::CompoundIdentifier(ids) if ids.len() == 2 => {
+                if ids[0].value == self.target {
+                    Expr::Identifier(ids[1].clone())
+                } else if ids[0].value == self.source {
+                    Expr::Identifier(Ident::new(format!("{}.{}", self.source, ids[1].value)))
+                } else {
+                    return Result::Err(ErrorCode::SyntaxException(format!(
+                        "Unknown table {} in MERGE, expect {} or {}",
+                        ids[0].value, self.target, self.source
+                    )));
+                }
+            }
+            Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
+                left: resolve(left)?,
+                op: op.clone(),
+                right: resolve(right)?,
+            },
+            Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+                op: op.clone(),
+                expr: resolve(expr)?,
+            },
+            Expr::Nested(expr) => Expr::Nested(resolve(expr)?),
+            Expr::IsNull(expr) => Expr::IsNull(resolve(expr)?),
+            Expr::IsNotNull(expr) => Expr::IsNotNull(resolve(expr)?),
+            Expr::IsDistinctFrom(left, right) => {
+                Expr::IsDistinctFrom(resolve(left)?, resolve(right)?)
+            }
+            Expr::IsNotDistinctFrom(left, right) => {
+                Expr::IsNotDistinctFrom(resolve(left)?, resolve(right)?)
+            }
+            Expr::Cast { expr, data_type } => Expr::Cast {
+                expr: resolve(expr)?,
+                data_type: data_type.clone(),
+            },
+            Expr::TryCast { expr, data_type } => Expr::TryCast {
+                expr: resolve(expr)?,
+                data_type: data_type.clone(),
+            },
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => Expr::Between {
+                expr: resolve(expr)?,
+                negated: *negated,
+                low: resolve(low)?,
+                high: resolve(high)?,
+            },
+            Expr::Function(function) => {
+                let mut function = function.clone();
+                for arg in function.args.iter_mut() {
+                    match arg {
+                        FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => {
+                            *arg = self.resolve(arg)?
+                        }
+                    }
+                }
+                Expr::Function(function)
+            }
+            other => other.clone(),
+        })
+    }
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use common_exception::Result;
+use common_planners::Expression;
+use common_planners::PlanNode;
 use pretty_assertions::assert_eq;
 
 use crate::sql::PlanParser;
@@ -39,6 +41,18 @@ fn test_plan_parser() -> Result<()> {
             expect: "Create database db1, if_not_exists:true, option: {}",
             error: "",
         },
+        Test {
+            name: "create-database-with-tables-passed",
+            sql: "CREATE DATABASE db1 WITH TABLES (t1 (c1 int) ENGINE = FUSE, t2 (c1 int) ENGINE = FUSE)",
+            expect: "Create database db1, if_not_exists:false, option: {}, tables: [\"t1\", \"t2\"]",
+            error: "",
+        },
+        Test {
+            name: "create-database-with-duplicate-tables",
+            sql: "CREATE DATABASE db1 WITH TABLES (t1 (c1 int) ENGINE = FUSE, t1 (c2 int) ENGINE = FUSE)",
+            expect: "",
+            error: "Code: 5, displayText = Duplicate table t1 in CREATE DATABASE db1.",
+        },
         Test {
             name: "drop-database-passed",
             sql: "DROP DATABASE db1",
@@ -63,6 +77,54 @@ fn test_plan_parser() -> Result<()> {
             expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false }, DataField { name: \"c2\", data_type: Int64, nullable: false }, DataField { name: \"c3\", data_type: String, nullable: false }, engine: Parquet, if_not_exists:true, option: {\"location\": \"foo.parquet\"}",
             error: "",
         },
+        Test {
+            name: "create-table-primary-key-passed",
+            sql: "CREATE TABLE t(c1 int, c2 bigint, PRIMARY KEY (c1, c2)) ENGINE = FUSE",
+            expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false }, DataField { name: \"c2\", data_type: Int64, nullable: false }, engine: FUSE, if_not_exists:false, option: {\"primary_key\": \"c1,c2\"}",
+            error: "",
+        },
+        Test {
+            name: "create-table-multiple-primary-keys",
+            sql: "CREATE TABLE t(c1 int PRIMARY KEY, c2 bigint PRIMARY KEY) ENGINE = FUSE",
+            expect: "",
+            error: "Code: 5, displayText = Multiple primary keys are not allowed.",
+        },
+        Test {
+            name: "create-table-unique-unknown-column",
+            sql: "CREATE TABLE t(c1 int, UNIQUE (c3)) ENGINE = FUSE",
+            expect: "",
+            error: "Code: 22, displayText = Unknown column c3 in the key (c3).",
+        },
+        Test {
+            name: "create-table-collate-passed",
+            sql: "CREATE TABLE t(c1 int, c2 varchar(255) COLLATE utf8_general_ci) ENGINE = Memory",
+            expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false }, DataField { name: \"c2\", data_type: String, nullable: false }, engine: Memory, if_not_exists:false, option: {\"column_collations\": \"c2:utf8_general_ci\"}",
+            error: "",
+        },
+        Test {
+            name: "create-table-collate-not-string",
+            sql: "CREATE TABLE t(c1 int COLLATE utf8_general_ci) ENGINE = Memory",
+            expect: "",
+            error: "Code: 22, displayText = COLLATE is only allowed on String columns, column c1 is Int32.",
+        },
+        Test {
+            name: "collate-unknown-collation",
+            sql: "select number from numbers(10) where number = 1 collate utf8",
+            expect: "",
+            error: "Code: 6, displayText = Unknown collation: utf8, expect a name ending with _ci, _cs or _bin.",
+        },
+        Test {
+            name: "analyze-table-passed",
+            sql: "ANALYZE TABLE t1 UPDATE HISTOGRAM ON c1",
+            expect: "",
+            error: "",
+        },
+        Test {
+            name: "analyze-table-buckets",
+            sql: "ANALYZE TABLE t1 UPDATE HISTOGRAM ON c1 WITH 0 BUCKETS",
+            expect: "",
+            error: "Code: 6, displayText = The number of histogram buckets must be between 1 and 1024, but got 0.",
+        },
         Test {
             name: "drop-table-passed",
             sql: "DROP TABLE t1",
@@ -129,6 +191,54 @@ fn test_plan_parser() -> Result<()> {
             expect: "Projection: 12:Interval(YearMonth), 1:Interval(YearMonth), 86400000:Interval(DayTime), 3600000:Interval(DayTime), 60000:Interval(DayTime), 1000:Interval(DayTime)\n  Expression: 12:Interval(YearMonth), 1:Interval(YearMonth), 86400000:Interval(DayTime), 3600000:Interval(DayTime), 60000:Interval(DayTime), 1000:Interval(DayTime) (Before Projection)\n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
             error: "",
         },
+        Test {
+            name: "interval-string-passed",
+            sql: "SELECT INTERVAL '2 weeks', INTERVAL '1 quarter', INTERVAL '3 DAY'",
+            expect: "Projection: 1209600000:Interval(DayTime), 3:Interval(YearMonth), 259200000:Interval(DayTime)\n  Expression: 1209600000:Interval(DayTime), 3:Interval(YearMonth), 259200000:Interval(DayTime) (Before Projection)\n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
+            error: "",
+        },
+        Test {
+            name: "interval-string-unsupported",
+            sql: "SELECT INTERVAL '1 year 1 day'",
+            expect: "",
+            error: "Code: 5, displayText = Unsupported interval expression: INTERVAL '1 year 1 day'.",
+        },
+        Test {
+            name: "create-function-bad-name",
+            sql: "CREATE FUNCTION db.f LANGUAGE wasm AS '(module)'",
+            expect: "",
+            error: "Code: 5, displayText = Invalid function name: db.f.",
+        },
+        Test {
+            name: "unnest-not-array",
+            sql: "SELECT elem FROM numbers_mt(10), UNNEST(number) AS u(elem)",
+            expect: "",
+            error: "Code: 7, displayText = UNNEST expects an array column, but column number is UInt64.",
+        },
+        Test {
+            name: "unnest-unsupported-argument",
+            sql: "SELECT * FROM numbers_mt(10), UNNEST(number + 1)",
+            expect: "",
+            error: "Code: 2, displayText = UNNEST only supports a single array column argument.",
+        },
+        Test {
+            name: "window-function-in-where",
+            sql: "SELECT number FROM numbers_mt(10) WHERE row_number() OVER (ORDER BY number) > 1",
+            expect: "",
+            error: "Code: 5, displayText = Window functions are not allowed in WHERE.",
+        },
+        Test {
+            name: "window-frame-unsupported",
+            sql: "SELECT sum(number) OVER (ORDER BY number ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) FROM numbers_mt(10)",
+            expect: "",
+            error: "Code: 2, displayText = Window frame is not supported: sum(number) OVER (ORDER BY number ROWS BETWEEN 1 PRECEDING AND CURRENT ROW).",
+        },
+        Test {
+            name: "multiple-tables-unsupported",
+            sql: "SELECT * FROM numbers_mt(10), numbers_mt(10)",
+            expect: "",
+            error: "Code: 5, displayText = Cannot SELECT multiple tables.",
+        },
         // Test {
         //     name: "interval-unsupported",
         //     sql: "SELECT INTERVAL '1 year 1 day'",
@@ -177,10 +287,32 @@ fn test_plan_parser() -> Result<()> {
         },
 
         Test {
-            name: "unimplemented-cte",
-            sql: "with t as ( select sum(number) n from numbers_mt(1000) )select * from t",
+            name: "cte",
+            sql: "with t as (select number from numbers_mt(10)) select * from t where number > 1",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: (number > 1)\
+            \n    Projection: number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "cte-duplicate-name",
+            sql: "with t as (select 1), t as (select 2) select * from t",
             expect: "",
-            error: "Code: 2, displayText = CTE is not yet implement.",
+            error: "Code: 5, displayText = WITH query name t specified more than once.",
+        },
+        Test {
+            name: "cte-column-names-mismatch",
+            sql: "with t(a, b) as (select number from numbers_mt(10)) select * from t",
+            expect: "",
+            error: "Code: 6, displayText = Query t has 1 columns, but 2 column names are specified.",
+        },
+        Test {
+            name: "recursive-cte-union-unsupported",
+            sql: "with recursive t(n) as (select 1 union select n + 1 from t) select * from t",
+            expect: "",
+            error: "Code: 2, displayText = Recursive query t only supports UNION ALL.",
         },
         Test {
             name: "kleene-logic-null",
@@ -199,6 +331,24 @@ fn test_plan_parser() -> Result<()> {
             \n  Filter: (NULL AND true)\
             \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
             error: "",
+        },
+        Test {
+            name: "prewhere",
+            sql: "select * from numbers(10) prewhere number > 1 where number < 5 or number = 8",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: ((number > 1) and ((number < 5) OR (number = 8)))\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "prewhere-only",
+            sql: "select * from numbers(10) prewhere number > 1",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: (number > 1)\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
         }
     ];
 
@@ -217,3 +367,55 @@ fn test_plan_parser() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_plan_parser_ident_case() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let build = |sql: &str| PlanParser::create(ctx.clone()).build_from_sql(sql);
+
+    // Identifiers are kept as written by default.
+    assert!(build("SELECT Number FROM numbers(3)").is_err());
+
+    // Unquoted identifiers are folded to lower case, quoted ones are kept.
+    ctx.get_settings().set_unquoted_ident_case_sensitive(0)?;
+    let plan = build("SELECT Dummy FROM SYSTEM.ONE")?;
+    assert_eq!(plan.schema().field(0).name(), "dummy");
+    assert!(build("SELECT \"Number\" FROM numbers(3)").is_err());
+
+    // All the identifiers are folded to lower case.
+    ctx.get_settings().set_quoted_ident_case_sensitive(0)?;
+    let plan = build("SELECT \"Number\" FROM numbers(3)")?;
+    assert_eq!(plan.schema().field(0).name(), "number");
+
+    Ok(())
+}
+
+#[test]
+fn test_plan_parser_push_down_filter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let plan = PlanParser::create(ctx).build_from_sql(
+        "SELECT * FROM numbers(10) PREWHERE number > 1 WHERE number < 5 AND number <> 3",
+    )?;
+
+    // Projection -> Filter -> ReadDataSource
+    let read_source = plan.input(0).input(0);
+    let push_downs = match read_source.as_ref() {
+        PlanNode::ReadSource(read_source) => read_source.push_downs.clone().unwrap(),
+        other => panic!("Expect ReadDataSource, but got {:?}", other),
+    };
+
+    let format = |exprs: &[Expression]| {
+        exprs
+            .iter()
+            .map(|expr| format!("{:?}", expr))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(format(&push_downs.prewhere), vec!["(number > 1)"]);
+    assert_eq!(format(&push_downs.filters), vec![
+        "(number > 1)",
+        "(number < 5)",
+        "(number <> 3)"
+    ]);
+
+    Ok(())
+}
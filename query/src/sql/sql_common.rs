@@ -17,9 +17,17 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use sqlparser::ast::DataType as SQLDataType;
 
+use crate::sql::DfParser;
+
 pub struct SQLCommon;
 
 impl SQLCommon {
+    /// Quotes the value as a SQL string literal. The quotes in it are doubled, which is the only
+    /// escape known by the tokenizer, the backslashes are kept as they are.
+    pub fn quote_string_literal(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
     /// Maps the SQL type to the corresponding Arrow `DataType`
     pub fn make_data_type(sql_type: &SQLDataType) -> Result<DataType> {
         match sql_type {
@@ -40,6 +48,26 @@ impl SQLCommon {
 
             //custom types for databend
             // Custom(ObjectName([Ident { value: "uint8", quote_style: None }])
+            // STRUCT(name type, ...) is named by its text, see DfParser::parse_data_type.
+            SQLDataType::Custom(obj)
+                if obj.0.len() == 1 && obj.0[0].value.starts_with("STRUCT(") =>
+            {
+                match DfParser::parse_struct_fields(&obj.0[0].value)? {
+                    Some(fields) => Ok(DataType::Struct(
+                        fields
+                            .iter()
+                            .map(|(name, data_type)| {
+                                let data_type = Self::make_data_type(data_type)?;
+                                Ok(DataField::new(&name.value, data_type, true))
+                            })
+                            .collect::<Result<Vec<_>>>()?,
+                    )),
+                    None => Result::Err(ErrorCode::IllegalDataType(format!(
+                        "The SQL data type {:?} is not implemented",
+                        sql_type
+                    ))),
+                }
+            }
             SQLDataType::Custom(obj) if !obj.0.is_empty() => {
                 match obj.0[0].value.to_uppercase().as_str() {
                     "UINT8" => Ok(DataType::UInt8),
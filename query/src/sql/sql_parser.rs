@@ -23,8 +23,11 @@ use metrics::histogram;
 use sqlparser::ast::BinaryOperator;
 use sqlparser::ast::ColumnDef;
 use sqlparser::ast::ColumnOptionDef;
+use sqlparser::ast::DataType as SQLDataType;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
+use sqlparser::ast::SelectItem;
 use sqlparser::ast::SqlOption;
 use sqlparser::ast::TableConstraint;
 use sqlparser::ast::Value;
@@ -37,21 +40,50 @@ use sqlparser::tokenizer::Token;
 use sqlparser::tokenizer::Tokenizer;
 use sqlparser::tokenizer::Whitespace;
 
+use crate::sql::DfAlterTable;
+use crate::sql::DfAlterTableAction;
+use crate::sql::DfAlterUser;
+use crate::sql::DfAnalyzeTable;
+use crate::sql::DfCopy;
+use crate::sql::DfCopyIntoLocation;
+use crate::sql::DfCopySource;
+use crate::sql::DfCreateConnection;
 use crate::sql::DfCreateDatabase;
+use crate::sql::DfCreateExternalFunction;
+use crate::sql::DfCreateFunction;
+use crate::sql::DfCreateIndex;
+use crate::sql::DfCreatePipe;
+use crate::sql::DfCreateProfile;
 use crate::sql::DfCreateTable;
+use crate::sql::DfCreateView;
+use crate::sql::DfDelete;
 use crate::sql::DfDescribeTable;
+use crate::sql::DfDropConnection;
 use crate::sql::DfDropDatabase;
+use crate::sql::DfDropFunction;
+use crate::sql::DfDropIndex;
+use crate::sql::DfDropPipe;
+use crate::sql::DfDropProfile;
+use crate::sql::DfDropQueryCache;
 use crate::sql::DfDropTable;
+use crate::sql::DfDropView;
 use crate::sql::DfExplain;
+use crate::sql::DfFsckTable;
 use crate::sql::DfHint;
 use crate::sql::DfKillStatement;
+use crate::sql::DfList;
+use crate::sql::DfMerge;
+use crate::sql::DfMergeClause;
+use crate::sql::DfShowCreateDatabase;
 use crate::sql::DfShowCreateTable;
 use crate::sql::DfShowDatabases;
 use crate::sql::DfShowProcessList;
 use crate::sql::DfShowSettings;
+use crate::sql::DfShowTableOptions;
 use crate::sql::DfShowTables;
 use crate::sql::DfStatement;
 use crate::sql::DfTruncateTable;
+use crate::sql::DfUpdate;
 use crate::sql::DfUseDatabase;
 
 // Use `Parser::expected` instead, if possible
@@ -61,6 +93,31 @@ macro_rules! parser_err {
     };
 }
 
+/// The function marking the conditions of a rewritten PREWHERE clause in the WHERE clause.
+pub const PREWHERE_FUNCTION: &str = "prewhere";
+
+/// The keywords starting the clauses after PREWHERE.
+const PREWHERE_FOLLOWING_CLAUSES: [&str; 7] = [
+    "where", "group", "having", "order", "limit", "offset", "union",
+];
+
+/// Whether the identifiers are kept as written, or folded to lower case.
+/// Driven by the `unquoted_ident_case_sensitive` and `quoted_ident_case_sensitive` settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdentCase {
+    pub unquoted_case_sensitive: bool,
+    pub quoted_case_sensitive: bool,
+}
+
+impl Default for IdentCase {
+    fn default() -> Self {
+        IdentCase {
+            unquoted_case_sensitive: true,
+            quoted_case_sensitive: true,
+        }
+    }
+}
+
 /// SQL Parser
 pub struct DfParser<'a> {
     parser: Parser<'a>,
@@ -75,19 +132,217 @@ impl<'a> DfParser<'a> {
 
     /// Parse the specified tokens with dialect
     pub fn new_with_dialect(sql: &str, dialect: &'a dyn Dialect) -> Result<Self, ParserError> {
+        DfParser::new_with_ident_case(sql, dialect, IdentCase::default())
+    }
+
+    /// Parse the specified tokens with dialect, folding the case insensitive identifiers
+    pub fn new_with_ident_case(
+        sql: &str,
+        dialect: &'a dyn Dialect,
+        ident_case: IdentCase,
+    ) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let tokens = tokenizer.tokenize()?;
+        let tokens = Self::fold_identifiers(tokenizer.tokenize()?, ident_case);
+        let tokens = Self::rewrite_aggregate_filter(tokens);
+        let tokens = Self::rewrite_prewhere(tokens);
 
         Ok(DfParser {
             parser: Parser::new(tokens, dialect),
         })
     }
 
+    /// Fold the words which are not case sensitive to lower case, so that names are resolved
+    /// the same way everywhere after parsing: catalog lookups, columns, and the names stored
+    /// by DDL. Keywords are matched on their own, so they are not affected.
+    fn fold_identifiers(tokens: Vec<Token>, ident_case: IdentCase) -> Vec<Token> {
+        if ident_case == IdentCase::default() {
+            return tokens;
+        }
+
+        tokens
+            .into_iter()
+            .map(|token| match token {
+                Token::Word(mut w) => {
+                    let case_sensitive = match w.quote_style {
+                        None => ident_case.unquoted_case_sensitive,
+                        Some(_) => ident_case.quoted_case_sensitive,
+                    };
+                    if !case_sensitive {
+                        w.value = w.value.to_lowercase();
+                    }
+                    Token::Word(w)
+                }
+                other => other,
+            })
+            .collect()
+    }
+
+    /// Rewrite `agg(args) FILTER (WHERE cond)` to the If combinator `aggIf(args, cond)`,
+    /// the sql parser doesn't know the FILTER clause.
+    fn rewrite_aggregate_filter(tokens: Vec<Token>) -> Vec<Token> {
+        let next_token = |tokens: &[Token], from: usize| {
+            (from..tokens.len()).find(|idx| !matches!(tokens[*idx], Token::Whitespace(_)))
+        };
+        let prev_token = |tokens: &[Token], to: usize| {
+            (0..to)
+                .rev()
+                .find(|idx| !matches!(tokens[*idx], Token::Whitespace(_)))
+        };
+        // The index of the paren matching the one at `from`, searching forward or backward.
+        let matching_paren = |tokens: &[Token], from: usize, forward: bool| {
+            let mut depth = 0;
+            let mut idx = from;
+            loop {
+                match &tokens[idx] {
+                    Token::LParen => depth += 1,
+                    Token::RParen => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    return Some(idx);
+                }
+                match forward {
+                    true if idx + 1 < tokens.len() => idx += 1,
+                    false if idx > 0 => idx -= 1,
+                    _ => return None,
+                }
+            }
+        };
+
+        let mut rewritten: Vec<Token> = Vec::with_capacity(tokens.len());
+        let mut idx = 0;
+        while idx < tokens.len() {
+            if Self::is_word(&tokens[idx], "filter") {
+                let args_end = prev_token(&rewritten, rewritten.len());
+                let filter_start = next_token(&tokens, idx + 1);
+                let where_idx = filter_start.and_then(|start| next_token(&tokens, start + 1));
+
+                if let (Some(args_end), Some(filter_start), Some(where_idx)) =
+                    (args_end, filter_start, where_idx)
+                {
+                    let args_start = match rewritten[args_end] {
+                        Token::RParen => matching_paren(&rewritten, args_end, false),
+                        _ => None,
+                    };
+                    let name_idx = args_start.and_then(|start| prev_token(&rewritten, start));
+                    let filter_end = match tokens[filter_start] {
+                        Token::LParen if Self::is_word(&tokens[where_idx], "where") => {
+                            matching_paren(&tokens, filter_start, true)
+                        }
+                        _ => None,
+                    };
+
+                    if let (Some(args_start), Some(name_idx), Some(filter_end)) =
+                        (args_start, name_idx, filter_end)
+                    {
+                        if let Token::Word(name) = &rewritten[name_idx] {
+                            rewritten[name_idx] =
+                                Token::make_word(&format!("{}If", name.value), name.quote_style);
+
+                            let no_args = prev_token(&rewritten, args_end) == Some(args_start);
+                            rewritten.truncate(args_end);
+                            if !no_args {
+                                rewritten.push(Token::Comma);
+                            }
+                            rewritten.extend(Self::rewrite_aggregate_filter(
+                                tokens[where_idx + 1..filter_end].to_vec(),
+                            ));
+                            rewritten.push(Token::RParen);
+
+                            idx = filter_end + 1;
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            rewritten.push(tokens[idx].clone());
+            idx += 1;
+        }
+        rewritten
+    }
+
+    /// Rewrite `PREWHERE cond [WHERE other]` to `WHERE prewhere(cond) [AND (other)]`,
+    /// the sql parser doesn't know the PREWHERE clause. The planner takes the conditions
+    /// out of the marker function again and hands them over to the table scan.
+    fn rewrite_prewhere(tokens: Vec<Token>) -> Vec<Token> {
+        // The start of the next clause of the query, or the end of the (sub)query.
+        let clause_end = |tokens: &[Token], from: usize| {
+            let mut depth = 0;
+            for (idx, token) in tokens.iter().enumerate().skip(from) {
+                match token {
+                    Token::LParen => depth += 1,
+                    Token::RParen if depth == 0 => return idx,
+                    Token::RParen => depth -= 1,
+                    Token::SemiColon if depth == 0 => return idx,
+                    token
+                        if depth == 0
+                            && PREWHERE_FOLLOWING_CLAUSES
+                                .iter()
+                                .any(|clause| Self::is_word(token, clause)) =>
+                    {
+                        return idx
+                    }
+                    _ => {}
+                }
+            }
+            tokens.len()
+        };
+
+        let mut rewritten: Vec<Token> = Vec::with_capacity(tokens.len());
+        let mut idx = 0;
+        while idx < tokens.len() {
+            if !Self::is_word(&tokens[idx], "prewhere") {
+                rewritten.push(tokens[idx].clone());
+                idx += 1;
+                continue;
+            }
+
+            let prewhere_end = clause_end(&tokens, idx + 1);
+            rewritten.push(Token::make_keyword("WHERE"));
+            rewritten.push(Token::Whitespace(Whitespace::Space));
+            rewritten.push(Token::make_word(PREWHERE_FUNCTION, None));
+            rewritten.push(Token::LParen);
+            rewritten.extend(Self::rewrite_prewhere(
+                tokens[idx + 1..prewhere_end].to_vec(),
+            ));
+            rewritten.push(Token::RParen);
+            idx = prewhere_end;
+
+            if idx < tokens.len() && Self::is_word(&tokens[idx], "where") {
+                let where_end = clause_end(&tokens, idx + 1);
+                rewritten.push(Token::Whitespace(Whitespace::Space));
+                rewritten.push(Token::make_keyword("AND"));
+                rewritten.push(Token::Whitespace(Whitespace::Space));
+                rewritten.push(Token::LParen);
+                rewritten.extend(Self::rewrite_prewhere(tokens[idx + 1..where_end].to_vec()));
+                rewritten.push(Token::RParen);
+                idx = where_end;
+            }
+        }
+        rewritten
+    }
+
+    fn is_word(token: &Token, word: &str) -> bool {
+        match token {
+            Token::Word(w) => w.quote_style.is_none() && w.value.eq_ignore_ascii_case(word),
+            _ => false,
+        }
+    }
+
     /// Parse a SQL statement and produce a set of statements with dialect
     pub fn parse_sql(sql: &str) -> Result<(Vec<DfStatement>, Vec<DfHint>), ErrorCode> {
+        DfParser::parse_sql_with_ident_case(sql, IdentCase::default())
+    }
+
+    /// Parse a SQL statement, folding the case insensitive identifiers to lower case
+    pub fn parse_sql_with_ident_case(
+        sql: &str,
+        ident_case: IdentCase,
+    ) -> Result<(Vec<DfStatement>, Vec<DfHint>), ErrorCode> {
         let dialect = &GenericDialect {};
         let start = Instant::now();
-        let result = DfParser::parse_sql_with_dialect(sql, dialect)?;
+        let result = DfParser::parse_sql_with_options(sql, dialect, ident_case)?;
         histogram!(super::metrics::METRIC_PARSER_USEDTIME, start.elapsed());
         Ok(result)
     }
@@ -97,7 +352,15 @@ impl<'a> DfParser<'a> {
         sql: &str,
         dialect: &dyn Dialect,
     ) -> Result<(Vec<DfStatement>, Vec<DfHint>), ParserError> {
-        let mut parser = DfParser::new_with_dialect(sql, dialect)?;
+        DfParser::parse_sql_with_options(sql, dialect, IdentCase::default())
+    }
+
+    fn parse_sql_with_options(
+        sql: &str,
+        dialect: &dyn Dialect,
+        ident_case: IdentCase,
+    ) -> Result<(Vec<DfStatement>, Vec<DfHint>), ParserError> {
+        let mut parser = DfParser::new_with_ident_case(sql, dialect, ident_case)?;
         let mut stmts = Vec::new();
 
         let mut expecting_statement_delimiter = false;
@@ -145,6 +408,15 @@ impl<'a> DfParser<'a> {
         match self.parser.peek_token() {
             Token::Word(w) => {
                 match w.keyword {
+                    // MERGE may not be a keyword of the SQL parser.
+                    _ if w.quote_style.is_none() && w.value.eq_ignore_ascii_case("MERGE") => {
+                        self.parser.next_token();
+                        self.parse_merge()
+                    }
+                    _ if w.quote_style.is_none() && w.value.eq_ignore_ascii_case("LIST") => {
+                        self.parser.next_token();
+                        self.parse_list()
+                    }
                     Keyword::CREATE => {
                         self.parser.next_token();
                         self.parse_create()
@@ -193,6 +465,8 @@ impl<'a> DfParser<'a> {
                             Ok(DfStatement::ShowSettings(DfShowSettings))
                         } else if self.consume_token("CREATE") {
                             self.parse_show_create()
+                        } else if self.consume_token("TABLE") {
+                            self.parse_show_table_options()
                         } else if self.consume_token("PROCESSLIST") {
                             Ok(DfStatement::ShowProcessList(DfShowProcessList))
                         } else {
@@ -203,10 +477,32 @@ impl<'a> DfParser<'a> {
                         self.parser.next_token();
                         self.parse_truncate()
                     }
+                    Keyword::ALTER => {
+                        self.parser.next_token();
+                        self.parse_alter()
+                    }
+                    Keyword::ANALYZE => {
+                        self.parser.next_token();
+                        self.parse_analyze()
+                    }
+                    Keyword::COPY => {
+                        self.parser.next_token();
+                        self.parse_copy()
+                    }
+                    Keyword::DELETE => {
+                        self.parser.next_token();
+                        self.parse_delete()
+                    }
+                    Keyword::UPDATE => {
+                        self.parser.next_token();
+                        self.parse_update()
+                    }
                     Keyword::NoKeyword => match w.value.to_uppercase().as_str() {
                         // Use database
                         "USE" => self.parse_use_database(),
                         "KILL" => self.parse_kill_query(),
+                        "SYSTEM" => self.parse_system(),
+                        "FSCK" => self.parse_fsck(),
                         _ => self.expected("Keyword", self.parser.peek_token()),
                     },
                     _ => {
@@ -236,6 +532,10 @@ impl<'a> DfParser<'a> {
                     self.parser.next_token();
                     ExplainType::Graph
                 }
+                "ANALYZE" => {
+                    self.parser.next_token();
+                    ExplainType::Analyze
+                }
                 _ => ExplainType::Syntax,
             },
             _ => ExplainType::Syntax,
@@ -344,7 +644,7 @@ impl<'a> DfParser<'a> {
 
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
         let name = self.parser.parse_identifier()?;
-        let data_type = self.parser.parse_data_type()?;
+        let data_type = self.parse_data_type()?;
         let collation = if self.parser.parse_keyword(Keyword::COLLATE) {
             Some(self.parser.parse_object_name()?)
         } else {
@@ -376,11 +676,83 @@ impl<'a> DfParser<'a> {
         })
     }
 
+    /// Parses a data type, where `STRUCT(name type, ...)` or its alias `TUPLE(...)` becomes
+    /// a custom type named by the text of the struct, see `parse_struct_fields`.
+    fn parse_data_type(&mut self) -> Result<SQLDataType, ParserError> {
+        match self.parse_struct_type()? {
+            None => self.parser.parse_data_type(),
+            Some(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, data_type)| format!("{} {}", name, data_type))
+                    .collect::<Vec<_>>();
+                let name = format!("STRUCT({})", fields.join(", "));
+                Ok(SQLDataType::Custom(ObjectName(vec![Ident::new(name)])))
+            }
+        }
+    }
+
+    fn parse_struct_type(&mut self) -> Result<Option<Vec<(Ident, SQLDataType)>>, ParserError> {
+        if !self.consume_token("STRUCT") && !self.consume_token("TUPLE") {
+            return Ok(None);
+        }
+
+        self.parser.expect_token(&Token::LParen)?;
+        let mut fields = vec![];
+        loop {
+            let name = self.parser.parse_identifier()?;
+            let data_type = self.parse_data_type()?;
+            fields.push((name, data_type));
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.parser.expect_token(&Token::RParen)?;
+        Ok(Some(fields))
+    }
+
+    /// Parses the fields of a struct type back from its custom type name.
+    pub fn parse_struct_fields(
+        data_type: &str,
+    ) -> Result<Option<Vec<(Ident, SQLDataType)>>, ParserError> {
+        let dialect = GenericDialect {};
+        let mut parser = DfParser::new_with_dialect(data_type, &dialect)?;
+        let fields = parser.parse_struct_type()?;
+        match parser.parser.peek_token() {
+            Token::EOF => Ok(fields),
+            _ => Ok(None),
+        }
+    }
+
     fn parse_create(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => match w.keyword {
-                Keyword::TABLE => self.parse_create_table(),
+                Keyword::TABLE => self.parse_create_table(false),
+                Keyword::TEMPORARY => {
+                    self.parser.expect_keyword(Keyword::TABLE)?;
+                    self.parse_create_table(true)
+                }
                 Keyword::DATABASE => self.parse_create_database(),
+                Keyword::VIEW => self.parse_create_view(),
+                _ if w.value.to_uppercase() == "PIPE" => self.parse_create_pipe(),
+                _ if w.value.to_uppercase() == "INDEX" => self.parse_create_index(false),
+                _ if w.value.to_uppercase() == "INVERTED" => {
+                    match self.parser.next_token() {
+                        Token::Word(w) if w.value.to_uppercase() == "INDEX" => {}
+                        unexpected => return self.expected("INDEX", unexpected),
+                    }
+                    self.parse_create_index(true)
+                }
+                _ if w.value.to_uppercase() == "FUNCTION" => self.parse_create_function(),
+                _ if w.value.to_uppercase() == "SETTINGS" => self.parse_create_profile(),
+                _ if w.value.to_uppercase() == "CONNECTION" => self.parse_create_connection(),
+                _ if w.value.to_uppercase() == "EXTERNAL" => {
+                    match self.parser.next_token() {
+                        Token::Word(w) if w.value.to_uppercase() == "FUNCTION" => {}
+                        unexpected => return self.expected("FUNCTION", unexpected),
+                    }
+                    self.parse_create_external_function()
+                }
                 _ => self.expected("create statement", Token::Word(w)),
             },
             unexpected => self.expected("create statement", unexpected),
@@ -392,42 +764,417 @@ impl<'a> DfParser<'a> {
             self.parser
                 .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let db_name = self.parser.parse_object_name()?;
+        let options = self.parse_options()?;
+        let tables = match self.parser.parse_keyword(Keyword::WITH) {
+            true => self.parse_database_tables(&db_name)?,
+            false => vec![],
+        };
 
         let create = DfCreateDatabase {
             if_not_exists,
             name: db_name,
-            options: vec![],
+            options,
+            tables,
         };
 
         Ok(DfStatement::CreateDatabase(create))
     }
 
+    /// Parses the tables of `CREATE DATABASE db WITH TABLES (t1 (...) ENGINE = x, t2 (...))`.
+    fn parse_database_tables(
+        &mut self,
+        db_name: &ObjectName,
+    ) -> Result<Vec<DfCreateTable>, ParserError> {
+        if !self.consume_token("TABLES") {
+            return self.expected("TABLES after WITH", self.parser.peek_token());
+        }
+        self.parser.expect_token(&Token::LParen)?;
+
+        let mut tables = vec![];
+        loop {
+            let table_name = self.parser.parse_identifier()?;
+            let (columns, constraints) = self.parse_columns()?;
+            let engine = self.parse_table_engine()?;
+            // The comma after the last option is eaten by parse_options.
+            let options = self.parse_options()?;
+            tables.push(DfCreateTable {
+                if_not_exists: false,
+                temporary: false,
+                name: ObjectName([db_name.0.clone(), vec![table_name]].concat()),
+                columns,
+                constraints,
+                engine,
+                options,
+                query: None,
+            });
+
+            self.parser.consume_token(&Token::Comma);
+            if self.parser.consume_token(&Token::RParen) {
+                break;
+            }
+        }
+
+        Ok(tables)
+    }
+
     fn parse_describe(&mut self) -> Result<DfStatement, ParserError> {
         let table_name = self.parser.parse_object_name()?;
         let desc = DfDescribeTable { name: table_name };
         Ok(DfStatement::DescribeTable(desc))
     }
 
-    /// Drop database/table.
+    /// Drop database/table/view/function.
     fn parse_drop(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => match w.keyword {
                 Keyword::DATABASE => self.parse_drop_database(),
                 Keyword::TABLE => self.parse_drop_table(),
+                Keyword::VIEW => self.parse_drop_view(),
+                Keyword::FUNCTION => self.parse_drop_function(),
+                _ if w.value.to_uppercase() == "PIPE" => self.parse_drop_pipe(),
+                _ if w.value.to_uppercase() == "INDEX" => self.parse_drop_index(),
+                _ if w.value.to_uppercase() == "SETTINGS" => self.parse_drop_profile(),
+                _ if w.value.to_uppercase() == "CONNECTION" => self.parse_drop_connection(),
                 _ => self.expected("drop statement", Token::Word(w)),
             },
             unexpected => self.expected("drop statement", unexpected),
         }
     }
 
+    /// Drop function.
+    fn parse_drop_function(&mut self) -> Result<DfStatement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+
+        Ok(DfStatement::DropFunction(DfDropFunction {
+            if_exists,
+            name,
+        }))
+    }
+
+    /// Create index.
+    fn parse_create_index(&mut self, inverted: bool) -> Result<DfStatement, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::ON)?;
+        let table_name = self.parser.parse_object_name()?;
+        self.parser.expect_token(&Token::LParen)?;
+        let expr = self.parser.parse_expr()?;
+        self.parser.expect_token(&Token::RParen)?;
+
+        let create = DfCreateIndex {
+            if_not_exists,
+            inverted,
+            name,
+            table_name,
+            expr,
+        };
+
+        Ok(DfStatement::CreateIndex(create))
+    }
+
+    /// Drop index.
+    fn parse_drop_index(&mut self) -> Result<DfStatement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::ON)?;
+        let table_name = self.parser.parse_object_name()?;
+
+        Ok(DfStatement::DropIndex(DfDropIndex {
+            if_exists,
+            name,
+            table_name,
+        }))
+    }
+
+    /// Create pipe.
+    fn parse_create_pipe(&mut self) -> Result<DfStatement, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+        self.parser
+            .expect_keywords(&[Keyword::AS, Keyword::COPY, Keyword::INTO])?;
+        let table_name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let source = self.parser.parse_identifier()?;
+        self.parser.expect_token(&Token::LParen)?;
+        let options = self.parse_options()?;
+        self.parser.expect_token(&Token::RParen)?;
+
+        let create = DfCreatePipe {
+            if_not_exists,
+            name,
+            table_name,
+            source,
+            options,
+        };
+
+        Ok(DfStatement::CreatePipe(create))
+    }
+
+    /// Create function.
+    fn parse_create_function(&mut self) -> Result<DfStatement, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+        match self.parser.next_token() {
+            Token::Word(w) if w.value.to_uppercase() == "LANGUAGE" => {}
+            unexpected => return self.expected("LANGUAGE", unexpected),
+        }
+        let language = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::AS)?;
+        let body = match self.parser.next_token() {
+            Token::SingleQuotedString(body) => body,
+            unexpected => return self.expected("function body string", unexpected),
+        };
+
+        let create = DfCreateFunction {
+            if_not_exists,
+            name,
+            language,
+            body,
+        };
+
+        Ok(DfStatement::CreateFunction(create))
+    }
+
+    /// Create external function.
+    fn parse_create_external_function(&mut self) -> Result<DfStatement, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+
+        let mut arg_types = vec![];
+        self.parser.expect_token(&Token::LParen)?;
+        if !self.parser.consume_token(&Token::RParen) {
+            loop {
+                arg_types.push(self.parser.parse_data_type()?);
+                if !self.parser.consume_token(&Token::Comma) {
+                    self.parser.expect_token(&Token::RParen)?;
+                    break;
+                }
+            }
+        }
+
+        match self.parser.next_token() {
+            Token::Word(w) if w.value.to_uppercase() == "RETURNS" => {}
+            unexpected => return self.expected("RETURNS", unexpected),
+        }
+        let return_type = self.parser.parse_data_type()?;
+
+        self.parser.expect_keyword(Keyword::AS)?;
+        let endpoint = match self.parser.next_token() {
+            Token::SingleQuotedString(endpoint) => endpoint,
+            unexpected => return self.expected("endpoint string", unexpected),
+        };
+
+        let mut options = vec![];
+        if self.parser.consume_token(&Token::LParen) {
+            options = self.parse_options()?;
+            self.parser.expect_token(&Token::RParen)?;
+        }
+
+        let create = DfCreateExternalFunction {
+            if_not_exists,
+            name,
+            arg_types,
+            return_type,
+            endpoint,
+            options,
+        };
+
+        Ok(DfStatement::CreateExternalFunction(create))
+    }
+
+    /// Create settings profile.
+    fn parse_create_profile(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("PROFILE") {
+            return self.expected("PROFILE", self.parser.peek_token());
+        }
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+        if !self.consume_token("SETTINGS") {
+            return self.expected("SETTINGS", self.parser.peek_token());
+        }
+        let settings = self.parse_options()?;
+
+        Ok(DfStatement::CreateProfile(DfCreateProfile {
+            if_not_exists,
+            name,
+            settings,
+        }))
+    }
+
+    /// Drop settings profile.
+    fn parse_drop_profile(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("PROFILE") {
+            return self.expected("PROFILE", self.parser.peek_token());
+        }
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+
+        Ok(DfStatement::DropProfile(DfDropProfile { if_exists, name }))
+    }
+
+    /// Create connection, the options are the storage options.
+    fn parse_create_connection(&mut self) -> Result<DfStatement, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+        let options = self.parse_options()?;
+
+        Ok(DfStatement::CreateConnection(DfCreateConnection {
+            if_not_exists,
+            name,
+            options,
+        }))
+    }
+
+    /// Drop connection.
+    fn parse_drop_connection(&mut self) -> Result<DfStatement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_identifier()?;
+
+        Ok(DfStatement::DropConnection(DfDropConnection {
+            if_exists,
+            name,
+        }))
+    }
+
+    /// Alter user, only its settings profile for now.
+    fn parse_alter_user(&mut self) -> Result<DfStatement, ParserError> {
+        let name = self.parse_identifier_or_string()?;
+        if !self.consume_token("SETTINGS") || !self.consume_token("PROFILE") {
+            return self.expected("SETTINGS PROFILE", self.parser.peek_token());
+        }
+        let profile = match self.consume_token("NONE") {
+            true => None,
+            false => Some(self.parser.parse_identifier()?),
+        };
+
+        Ok(DfStatement::AlterUser(DfAlterUser { name, profile }))
+    }
+
+    /// Drop pipe.
+    fn parse_drop_pipe(&mut self) -> Result<DfStatement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+
+        Ok(DfStatement::DropPipe(DfDropPipe { if_exists, name }))
+    }
+
+    /// Copy into table, or into location if a location string follows `INTO`.
+    fn parse_copy(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::INTO)?;
+        if let Token::SingleQuotedString(location) = self.parser.peek_token() {
+            self.parser.next_token();
+            return self.parse_copy_into_location(location);
+        }
+
+        let table_name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let location = match self.parser.next_token() {
+            Token::SingleQuotedString(location) => location,
+            unexpected => return self.expected("location string", unexpected),
+        };
+
+        let mut options = vec![];
+        if self.parser.consume_token(&Token::LParen) {
+            options = self.parse_options()?;
+            self.parser.expect_token(&Token::RParen)?;
+        }
+
+        let copy = DfCopy {
+            table_name,
+            location,
+            options,
+        };
+
+        Ok(DfStatement::Copy(copy))
+    }
+
+    /// List the files of `@location` or of a location string.
+    fn parse_list(&mut self) -> Result<DfStatement, ParserError> {
+        let location = match self.parser.next_token() {
+            Token::SingleQuotedString(location) => location,
+            // The location after `@` is not quoted, it's tokenized as words and operators,
+            // e.g. `@data/events/`.
+            token if token.to_string().starts_with('@') => {
+                let mut location = token.to_string()[1..].to_string();
+                while !matches!(self.parser.peek_token(), Token::EOF | Token::SemiColon) {
+                    location.push_str(&self.parser.next_token().to_string());
+                }
+                location
+            }
+            unexpected => return self.expected("@location or location string", unexpected),
+        };
+        Ok(DfStatement::List(DfList { location }))
+    }
+
+    /// Copy the rows of a table or a query into files of the location.
+    fn parse_copy_into_location(&mut self, location: String) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let source = match self.parser.consume_token(&Token::LParen) {
+            true => {
+                let query = self.parser.parse_query()?;
+                self.parser.expect_token(&Token::RParen)?;
+                DfCopySource::Query(Box::new(query))
+            }
+            false => DfCopySource::Table(self.parser.parse_object_name()?),
+        };
+
+        let mut partition_by = vec![];
+        if self
+            .parser
+            .parse_keywords(&[Keyword::PARTITION, Keyword::BY])
+        {
+            self.parser.expect_token(&Token::LParen)?;
+            partition_by =
+                self.parser
+                    .parse_comma_separated(|parser| match parser.parse_expr()? {
+                        expr if parser.parse_keyword(Keyword::AS) => {
+                            Ok(SelectItem::ExprWithAlias {
+                                expr,
+                                alias: parser.parse_identifier()?,
+                            })
+                        }
+                        expr => Ok(SelectItem::UnnamedExpr(expr)),
+                    })?;
+            self.parser.expect_token(&Token::RParen)?;
+        }
+
+        let mut options = vec![];
+        if self.parser.consume_token(&Token::LParen) {
+            options = self.parse_options()?;
+            self.parser.expect_token(&Token::RParen)?;
+        }
+
+        Ok(DfStatement::CopyIntoLocation(DfCopyIntoLocation {
+            location,
+            source,
+            partition_by,
+            options,
+        }))
+    }
+
     /// Drop database.
     fn parse_drop_database(&mut self) -> Result<DfStatement, ParserError> {
         let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
         let db_name = self.parser.parse_object_name()?;
+        let cascade = self.parser.parse_keyword(Keyword::CASCADE);
 
         let drop = DfDropDatabase {
             if_exists,
             name: db_name,
+            cascade,
         };
 
         Ok(DfStatement::DropDatabase(drop))
@@ -446,6 +1193,31 @@ impl<'a> DfParser<'a> {
         Ok(DfStatement::DropTable(drop))
     }
 
+    /// Create view.
+    fn parse_create_view(&mut self) -> Result<DfStatement, ParserError> {
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::AS)?;
+        let query = Box::new(self.parser.parse_query()?);
+
+        let create = DfCreateView {
+            if_not_exists,
+            name,
+            query,
+        };
+
+        Ok(DfStatement::CreateView(create))
+    }
+
+    fn parse_drop_view(&mut self) -> Result<DfStatement, ParserError> {
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parser.parse_object_name()?;
+
+        Ok(DfStatement::DropView(DfDropView { if_exists, name }))
+    }
+
     // Parse 'use database' db name.
     fn parse_use_database(&mut self) -> Result<DfStatement, ParserError> {
         if !self.consume_token("USE") {
@@ -459,11 +1231,23 @@ impl<'a> DfParser<'a> {
     // Parse 'KILL statement'.
     fn parse_kill<F>(&mut self, f: F) -> Result<DfStatement, ParserError>
     where F: Fn(DfKillStatement) -> DfStatement {
+        // The query ids are uuids, they are quoted.
         Ok(f(DfKillStatement {
-            object_id: self.parser.parse_identifier()?,
+            object_id: self.parse_identifier_or_string()?,
         }))
     }
 
+    /// Parses an identifier, or a quoted string for the names which are not identifiers.
+    fn parse_identifier_or_string(&mut self) -> Result<Ident, ParserError> {
+        match self.parser.peek_token() {
+            Token::SingleQuotedString(s) => {
+                self.parser.next_token();
+                Ok(Ident::new(s))
+            }
+            _ => self.parser.parse_identifier(),
+        }
+    }
+
     // Parse 'KILL statement'.
     fn parse_kill_query(&mut self) -> Result<DfStatement, ParserError> {
         match self.consume_token("KILL") {
@@ -474,37 +1258,77 @@ impl<'a> DfParser<'a> {
         }
     }
 
-    fn parse_create_table(&mut self) -> Result<DfStatement, ParserError> {
+    // Parse 'SYSTEM statement'.
+    fn parse_system(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("SYSTEM") {
+            return self.expected("Must SYSTEM", self.parser.peek_token());
+        }
+
+        self.parser.expect_keyword(Keyword::DROP)?;
+        if !self.consume_token("QUERY") || !self.consume_token("CACHE") {
+            return self.expected("QUERY CACHE", self.parser.peek_token());
+        }
+
+        let table = match self.parser.parse_keywords(&[Keyword::FOR, Keyword::TABLE]) {
+            true => Some(self.parser.parse_object_name()?),
+            false => None,
+        };
+        Ok(DfStatement::DropQueryCache(DfDropQueryCache { table }))
+    }
+
+    fn parse_create_table(&mut self, temporary: bool) -> Result<DfStatement, ParserError> {
         let if_not_exists =
             self.parser
                 .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let table_name = self.parser.parse_object_name()?;
-        let (columns, _) = self.parse_columns()?;
+        let (columns, constraints) = self.parse_columns()?;
         let engine = self.parse_table_engine()?;
 
-        let mut table_properties = vec![];
-
         // parse table options: https://dev.mysql.com/doc/refman/8.0/en/create-table.html
-        if self.consume_token("LOCATION") {
-            self.parser.expect_token(&Token::Eq)?;
-            let value = self.parse_value()?;
-            table_properties.push(SqlOption {
-                name: Ident::new("LOCATION"),
-                value,
-            })
-        }
+        let table_properties = self.parse_options()?;
+
+        let query = match self.parser.parse_keyword(Keyword::AS) {
+            true => Some(Box::new(self.parser.parse_query()?)),
+            false => None,
+        };
 
         let create = DfCreateTable {
             if_not_exists,
+            temporary,
             name: table_name,
             columns,
+            constraints,
             engine,
             options: table_properties,
+            query,
         };
 
         Ok(DfStatement::CreateTable(create))
     }
 
+    /// Parses `name = value` options, e.g. `LOCATION = 'foo.csv' STORAGE_TYPE = 's3'`.
+    /// Option names are upper-cased, the commas between options are optional.
+    fn parse_options(&mut self) -> Result<Vec<SqlOption>, ParserError> {
+        let mut options = vec![];
+        loop {
+            match self.parser.next_token() {
+                Token::Word(w) if self.parser.peek_token() == Token::Eq => {
+                    self.parser.expect_token(&Token::Eq)?;
+                    let value = self.parse_value()?;
+                    options.push(SqlOption {
+                        name: Ident::new(w.value.to_uppercase()),
+                        value,
+                    });
+                    self.parser.consume_token(&Token::Comma);
+                }
+                _ => {
+                    self.parser.prev_token();
+                    return Ok(options);
+                }
+            }
+        }
+    }
+
     /// Parses the set of valid formats
     fn parse_table_engine(&mut self) -> Result<String, ParserError> {
         // TODO make ENGINE as a keyword
@@ -525,12 +1349,103 @@ impl<'a> DfParser<'a> {
                     let show_create_table = DfShowCreateTable { name: table_name };
                     Ok(DfStatement::ShowCreateTable(show_create_table))
                 }
+                Keyword::DATABASE => {
+                    let db_name = self.parser.parse_object_name()?;
+
+                    let show_create_database = DfShowCreateDatabase { name: db_name };
+                    Ok(DfStatement::ShowCreateDatabase(show_create_database))
+                }
                 _ => self.expected("show create statement", Token::Word(w)),
             },
             unexpected => self.expected("show create statement", unexpected),
         }
     }
 
+    fn parse_show_table_options(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("OPTIONS") {
+            return self.expected("OPTIONS", self.parser.peek_token());
+        }
+        let _ = self
+            .parser
+            .parse_one_of_keywords(&[Keyword::FROM, Keyword::IN]);
+
+        let name = self.parser.parse_object_name()?;
+        Ok(DfStatement::ShowTableOptions(DfShowTableOptions { name }))
+    }
+
+    fn parse_alter(&mut self) -> Result<DfStatement, ParserError> {
+        if self.consume_token("USER") {
+            return self.parse_alter_user();
+        }
+        if !self.parser.parse_keyword(Keyword::TABLE) {
+            return self.expected("TABLE or USER", self.parser.peek_token());
+        }
+
+        let name = self.parser.parse_object_name()?;
+        let action = if self.parser.parse_keyword(Keyword::SET) {
+            if self.consume_token("OPTIONS") {
+                self.parser.expect_token(&Token::LParen)?;
+                let options = self.parse_storage_policy()?;
+                self.parser.expect_token(&Token::RParen)?;
+                DfAlterTableAction::SetOptions(options)
+            } else if self.consume_token("STORAGE_POLICY") {
+                DfAlterTableAction::SetStoragePolicy(self.parse_storage_policy()?)
+            } else {
+                return self.expected("OPTIONS or STORAGE_POLICY", self.parser.peek_token());
+            }
+        } else if self.parser.parse_keyword(Keyword::ADD) {
+            let _ = self.parser.parse_keyword(Keyword::COLUMN);
+            DfAlterTableAction::AddColumn(self.parse_column_def()?)
+        } else if self.parser.parse_keyword(Keyword::DROP) {
+            let _ = self.parser.parse_keyword(Keyword::COLUMN);
+            DfAlterTableAction::DropColumn(self.parser.parse_identifier()?)
+        } else if self.consume_token("MATERIALIZE") {
+            self.parser.expect_keyword(Keyword::COLUMN)?;
+            DfAlterTableAction::MaterializeColumn(self.parser.parse_identifier()?)
+        } else {
+            return self.expected("alter table action", self.parser.peek_token());
+        };
+
+        Ok(DfStatement::AlterTable(DfAlterTable { name, action }))
+    }
+
+    /// Parses `hot_to_cold_after = 30d, cold_storage_type = 's3'`, used by both
+    /// `SET STORAGE_POLICY` and `SET OPTIONS`.
+    /// The durations may be written without quotes, e.g. `30d`.
+    fn parse_storage_policy(&mut self) -> Result<Vec<SqlOption>, ParserError> {
+        let mut options = vec![];
+        loop {
+            let name = self.parser.parse_identifier()?;
+            self.parser.expect_token(&Token::Eq)?;
+
+            let value = match self.parser.next_token() {
+                Token::Number(n, _) => match self.parser.peek_token() {
+                    Token::Word(unit) if unit.quote_style.is_none() => {
+                        self.parser.next_token();
+                        Value::SingleQuotedString(format!("{}{}", n, unit.value))
+                    }
+                    _ => {
+                        self.parser.prev_token();
+                        self.parse_value()?
+                    }
+                },
+                _ => {
+                    self.parser.prev_token();
+                    self.parse_value()?
+                }
+            };
+
+            options.push(SqlOption {
+                name: Ident::new(name.value.to_uppercase()),
+                value,
+            });
+
+            if !self.parser.consume_token(&Token::Comma) {
+                return Ok(options);
+            }
+        }
+    }
+
     fn parse_truncate(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => match w.keyword {
@@ -545,6 +1460,164 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    fn parse_analyze(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let name = self.parser.parse_object_name()?;
+
+        let mut histogram_columns = vec![];
+        let mut buckets = None;
+        if self.parser.parse_keyword(Keyword::UPDATE) {
+            if !self.consume_token("HISTOGRAM") {
+                return self.expected("HISTOGRAM", self.parser.peek_token());
+            }
+            self.parser.expect_keyword(Keyword::ON)?;
+            histogram_columns = self
+                .parser
+                .parse_comma_separated(Parser::parse_identifier)?;
+
+            if self.parser.parse_keyword(Keyword::WITH) {
+                buckets = Some(self.parser.parse_literal_uint()?);
+                if !self.consume_token("BUCKETS") {
+                    return self.expected("BUCKETS", self.parser.peek_token());
+                }
+            }
+        }
+
+        Ok(DfStatement::AnalyzeTable(DfAnalyzeTable {
+            name,
+            histogram_columns,
+            buckets,
+        }))
+    }
+
+    // Parse 'DELETE FROM t [WHERE expr]'.
+    fn parse_delete(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let name = self.parser.parse_object_name()?;
+        let selection = match self.parser.parse_keyword(Keyword::WHERE) {
+            true => Some(self.parser.parse_expr()?),
+            false => None,
+        };
+        Ok(DfStatement::Delete(DfDelete { name, selection }))
+    }
+
+    // Parse 'UPDATE t SET c1 = expr1, c2 = expr2 [WHERE expr]'.
+    fn parse_update(&mut self) -> Result<DfStatement, ParserError> {
+        let name = self.parser.parse_object_name()?;
+        self.parser.expect_keyword(Keyword::SET)?;
+        let assignments = self.parser.parse_comma_separated(|parser| {
+            let column = parser.parse_identifier()?;
+            parser.expect_token(&Token::Eq)?;
+            Ok((column, parser.parse_expr()?))
+        })?;
+        let selection = match self.parser.parse_keyword(Keyword::WHERE) {
+            true => Some(self.parser.parse_expr()?),
+            false => None,
+        };
+        Ok(DfStatement::Update(DfUpdate {
+            name,
+            assignments,
+            selection,
+        }))
+    }
+
+    // Parse 'MERGE INTO t [[AS] alias] USING source ON expr WHEN [NOT] MATCHED ...'.
+    fn parse_merge(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::INTO)?;
+        let name = self.parser.parse_object_name()?;
+        let alias = match self.parser.parse_keyword(Keyword::AS) {
+            true => Some(self.parser.parse_identifier()?),
+            false => match self.parser.peek_token() {
+                Token::Word(w) if w.keyword != Keyword::USING => {
+                    Some(self.parser.parse_identifier()?)
+                }
+                _ => None,
+            },
+        };
+        self.parser.expect_keyword(Keyword::USING)?;
+        let source = self.parser.parse_table_factor()?;
+        self.parser.expect_keyword(Keyword::ON)?;
+        let on = self.parser.parse_expr()?;
+
+        let mut clauses = vec![];
+        while self.parser.parse_keyword(Keyword::WHEN) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+        if clauses.is_empty() {
+            return self.expected("WHEN", self.parser.peek_token());
+        }
+
+        Ok(DfStatement::Merge(DfMerge {
+            name,
+            alias,
+            source,
+            on,
+            clauses,
+        }))
+    }
+
+    // Parse '[NOT] MATCHED [AND expr] THEN action' after WHEN.
+    fn parse_merge_clause(&mut self) -> Result<DfMergeClause, ParserError> {
+        let matched = !self.parser.parse_keyword(Keyword::NOT);
+        if !self.consume_token("MATCHED") {
+            return self.expected("MATCHED", self.parser.peek_token());
+        }
+        let condition = match self.parser.parse_keyword(Keyword::AND) {
+            true => Some(self.parser.parse_expr()?),
+            false => None,
+        };
+        self.parser.expect_keyword(Keyword::THEN)?;
+
+        if !matched {
+            self.parser.expect_keyword(Keyword::INSERT)?;
+            let mut columns = vec![];
+            if self.parser.consume_token(&Token::LParen) {
+                columns = self
+                    .parser
+                    .parse_comma_separated(|parser| parser.parse_identifier())?;
+                self.parser.expect_token(&Token::RParen)?;
+            }
+            self.parser.expect_keyword(Keyword::VALUES)?;
+            self.parser.expect_token(&Token::LParen)?;
+            let values = self.parser.parse_comma_separated(Parser::parse_expr)?;
+            self.parser.expect_token(&Token::RParen)?;
+            return Ok(DfMergeClause::NotMatched {
+                condition,
+                columns,
+                values,
+            });
+        }
+
+        if self.parser.parse_keyword(Keyword::DELETE) {
+            return Ok(DfMergeClause::MatchedDelete { condition });
+        }
+        if !self.parser.parse_keyword(Keyword::UPDATE) {
+            return self.expected("UPDATE or DELETE", self.parser.peek_token());
+        }
+        self.parser.expect_keyword(Keyword::SET)?;
+        let assignments = self.parser.parse_comma_separated(|parser| {
+            let column = parser.parse_identifier()?;
+            parser.expect_token(&Token::Eq)?;
+            Ok((column, parser.parse_expr()?))
+        })?;
+        Ok(DfMergeClause::MatchedUpdate {
+            condition,
+            assignments,
+        })
+    }
+
+    // Parse 'FSCK TABLE t [REPAIR]'.
+    fn parse_fsck(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("FSCK") {
+            return self.expected("Must FSCK", self.parser.peek_token());
+        }
+
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let name = self.parser.parse_object_name()?;
+        let repair = self.consume_token("REPAIR");
+        Ok(DfStatement::FsckTable(DfFsckTable { name, repair }))
+    }
+
     fn consume_token(&mut self, expected: &str) -> bool {
         if self.parser.peek_token().to_string().to_uppercase() == *expected.to_uppercase() {
             self.parser.next_token();
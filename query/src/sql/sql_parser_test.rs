@@ -51,6 +51,7 @@ fn create_database() -> Result<()> {
             if_not_exists: false,
             name: ObjectName(vec![Ident::new("db1")]),
             options: vec![],
+            tables: vec![],
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -61,10 +62,62 @@ fn create_database() -> Result<()> {
             if_not_exists: true,
             name: ObjectName(vec![Ident::new("db1")]),
             options: vec![],
+            tables: vec![],
         });
         expect_parse_ok(sql, expected)?;
     }
 
+    {
+        let sql = "CREATE DATABASE db1 storage_type = 's3', storage_s3_bucket = 'cold'";
+        let expected = DfStatement::CreateDatabase(DfCreateDatabase {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("db1")]),
+            options: vec![
+                SqlOption {
+                    name: Ident::new("STORAGE_TYPE".to_string()),
+                    value: Value::SingleQuotedString("s3".into()),
+                },
+                SqlOption {
+                    name: Ident::new("STORAGE_S3_BUCKET".to_string()),
+                    value: Value::SingleQuotedString("cold".into()),
+                },
+            ],
+            tables: vec![],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE DATABASE db1 WITH TABLES (t1 (c1 int) ENGINE = FUSE, t2 (c1 int) ENGINE = CSV location = '/data/33.csv', t3 (c1 int))";
+        let table = |name: &str, engine: &str, options: Vec<SqlOption>| DfCreateTable {
+            if_not_exists: false,
+            temporary: false,
+            name: ObjectName(vec![Ident::new("db1"), Ident::new(name)]),
+            columns: vec![make_column_def("c1", DataType::Int(None))],
+            constraints: vec![],
+            engine: engine.to_string(),
+            options,
+            query: None,
+        };
+        let expected = DfStatement::CreateDatabase(DfCreateDatabase {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("db1")]),
+            options: vec![],
+            tables: vec![
+                table("t1", "FUSE", vec![]),
+                table("t2", "CSV", vec![SqlOption {
+                    name: Ident::new("LOCATION".to_string()),
+                    value: Value::SingleQuotedString("/data/33.csv".into()),
+                }]),
+                table("t3", "NULL", vec![]),
+            ],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("CREATE DATABASE db1 WITH t1 (c1 int)").is_err());
+    assert!(DfParser::parse_sql("CREATE DATABASE db1 WITH TABLES (t1 (c1 int)").is_err());
+
     Ok(())
 }
 
@@ -75,6 +128,7 @@ fn drop_database() -> Result<()> {
         let expected = DfStatement::DropDatabase(DfDropDatabase {
             if_exists: false,
             name: ObjectName(vec![Ident::new("db1")]),
+            cascade: false,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -83,6 +137,16 @@ fn drop_database() -> Result<()> {
         let expected = DfStatement::DropDatabase(DfDropDatabase {
             if_exists: true,
             name: ObjectName(vec![Ident::new("db1")]),
+            cascade: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+    {
+        let sql = "DROP DATABASE IF EXISTS db1 CASCADE";
+        let expected = DfStatement::DropDatabase(DfDropDatabase {
+            if_exists: true,
+            name: ObjectName(vec![Ident::new("db1")]),
+            cascade: true,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -96,13 +160,16 @@ fn create_table() -> Result<()> {
     let sql = "CREATE TABLE t(c1 int) ENGINE = CSV location = '/data/33.csv' ";
     let expected = DfStatement::CreateTable(DfCreateTable {
         if_not_exists: false,
+        temporary: false,
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![make_column_def("c1", DataType::Int(None))],
+        constraints: vec![],
         engine: "CSV".to_string(),
         options: vec![SqlOption {
             name: Ident::new("LOCATION".to_string()),
             value: Value::SingleQuotedString("/data/33.csv".into()),
         }],
+        query: None,
     });
     expect_parse_ok(sql, expected)?;
 
@@ -110,17 +177,124 @@ fn create_table() -> Result<()> {
     let sql = "CREATE TABLE t(c1 int, c2 bigint, c3 varchar(255) ) ENGINE = Parquet location = 'foo.parquet' ";
     let expected = DfStatement::CreateTable(DfCreateTable {
         if_not_exists: false,
+        temporary: false,
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![
             make_column_def("c1", DataType::Int(None)),
             make_column_def("c2", DataType::BigInt(None)),
             make_column_def("c3", DataType::Varchar(Some(255))),
         ],
+        constraints: vec![],
         engine: "Parquet".to_string(),
         options: vec![SqlOption {
             name: Ident::new("LOCATION".to_string()),
             value: Value::SingleQuotedString("foo.parquet".into()),
         }],
+        query: None,
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // positive case: storage options
+    let sql = "CREATE TABLE t(c1 int) ENGINE = FUSE STORAGE_TYPE = 'disk' STORAGE_DISK_DATA_PATH = '/cold'";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        temporary: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![make_column_def("c1", DataType::Int(None))],
+        constraints: vec![],
+        engine: "FUSE".to_string(),
+        options: vec![
+            SqlOption {
+                name: Ident::new("STORAGE_TYPE".to_string()),
+                value: Value::SingleQuotedString("disk".into()),
+            },
+            SqlOption {
+                name: Ident::new("STORAGE_DISK_DATA_PATH".to_string()),
+                value: Value::SingleQuotedString("/cold".into()),
+            },
+        ],
+        query: None,
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // positive case: nested struct columns, TUPLE is an alias of STRUCT
+    let sql = "CREATE TABLE t(c1 STRUCT(a int, b TUPLE(x bigint, y uint8))) ENGINE = FUSE";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        temporary: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![make_column_def(
+            "c1",
+            DataType::Custom(ObjectName(vec![Ident::new(
+                "STRUCT(a INT, b STRUCT(x BIGINT, y uint8))",
+            )])),
+        )],
+        constraints: vec![],
+        engine: "FUSE".to_string(),
+        options: vec![],
+        query: None,
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // negative case: the fields of a struct must be named
+    assert!(DfParser::parse_sql("CREATE TABLE t(c1 STRUCT(int)) ENGINE = FUSE").is_err());
+
+    // positive case: temporary table
+    let sql = "CREATE TEMPORARY TABLE IF NOT EXISTS t(c1 int) ENGINE = Memory";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: true,
+        temporary: true,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![make_column_def("c1", DataType::Int(None))],
+        constraints: vec![],
+        engine: "Memory".to_string(),
+        options: vec![],
+        query: None,
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // positive case: constraints
+    let sql = "CREATE TABLE t(c1 int PRIMARY KEY, c2 int, c3 int, UNIQUE (c2, c3)) ENGINE = FUSE";
+    let mut c1 = make_column_def("c1", DataType::Int(None));
+    c1.options = vec![ColumnOptionDef {
+        name: None,
+        option: ColumnOption::Unique { is_primary: true },
+    }];
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        temporary: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![
+            c1,
+            make_column_def("c2", DataType::Int(None)),
+            make_column_def("c3", DataType::Int(None)),
+        ],
+        constraints: vec![TableConstraint::Unique {
+            name: None,
+            columns: vec![Ident::new("c2"), Ident::new("c3")],
+            is_primary: false,
+        }],
+        engine: "FUSE".to_string(),
+        options: vec![],
+        query: None,
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // positive case: create table as select
+    let sql = "CREATE TABLE t ENGINE = FUSE AS SELECT a, b FROM t2";
+    let query = match DfParser::parse_sql("SELECT a, b FROM t2")?.0.remove(0) {
+        DfStatement::Statement(Statement::Query(query)) => query,
+        _ => unreachable!(),
+    };
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        temporary: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![],
+        constraints: vec![],
+        engine: "FUSE".to_string(),
+        options: vec![],
+        query: Some(query),
     });
     expect_parse_ok(sql, expected)?;
 
@@ -272,6 +446,704 @@ fn truncate_table() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn analyze_table() -> Result<()> {
+    {
+        let sql = "ANALYZE TABLE t1";
+        let expected = DfStatement::AnalyzeTable(DfAnalyzeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            histogram_columns: vec![],
+            buckets: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ANALYZE TABLE db1.t1 UPDATE HISTOGRAM ON c1, c2 WITH 16 BUCKETS";
+        let expected = DfStatement::AnalyzeTable(DfAnalyzeTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            histogram_columns: vec![Ident::new("c1"), Ident::new("c2")],
+            buckets: Some(16),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn delete_from() -> Result<()> {
+    {
+        let sql = "DELETE FROM t1";
+        let expected = DfStatement::Delete(DfDelete {
+            name: ObjectName(vec![Ident::new("t1")]),
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "delete from db1.t1 where a > 1";
+        let expected = DfStatement::Delete(DfDelete {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            selection: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+            }),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn update() -> Result<()> {
+    {
+        let sql = "UPDATE db1.t1 SET a = a + 1, b = 'x' WHERE a > 1";
+        let expected = DfStatement::Update(DfUpdate {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            assignments: vec![
+                (Ident::new("a"), Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(Ident::new("a"))),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+                }),
+                (
+                    Ident::new("b"),
+                    Expr::Value(Value::SingleQuotedString("x".to_string())),
+                ),
+            ],
+            selection: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+            }),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "update t1 set a = 1";
+        let expected = DfStatement::Update(DfUpdate {
+            name: ObjectName(vec![Ident::new("t1")]),
+            assignments: vec![(
+                Ident::new("a"),
+                Expr::Value(Value::Number("1".to_string(), false)),
+            )],
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("update t1 a = 1").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn merge() -> Result<()> {
+    let ident = |names: &[&str]| {
+        Expr::CompoundIdentifier(names.iter().map(|name| Ident::new(*name)).collect())
+    };
+    let number = |n: &str| Expr::Value(Value::Number(n.to_string(), false));
+
+    {
+        let sql = "MERGE INTO db1.t1 AS t USING src s ON t.id = s.id \
+                   WHEN MATCHED AND s.op = 'd' THEN DELETE \
+                   WHEN MATCHED THEN UPDATE SET v = s.v \
+                   WHEN NOT MATCHED THEN INSERT (id, v) VALUES (s.id, s.v)";
+        let expected = DfStatement::Merge(DfMerge {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            alias: Some(Ident::new("t")),
+            source: TableFactor::Table {
+                name: ObjectName(vec![Ident::new("src")]),
+                alias: Some(TableAlias {
+                    name: Ident::new("s"),
+                    columns: vec![],
+                }),
+                args: vec![],
+                with_hints: vec![],
+            },
+            on: Expr::BinaryOp {
+                left: Box::new(ident(&["t", "id"])),
+                op: BinaryOperator::Eq,
+                right: Box::new(ident(&["s", "id"])),
+            },
+            clauses: vec![
+                DfMergeClause::MatchedDelete {
+                    condition: Some(Expr::BinaryOp {
+                        left: Box::new(ident(&["s", "op"])),
+                        op: BinaryOperator::Eq,
+                        right: Box::new(Expr::Value(Value::SingleQuotedString("d".to_string()))),
+                    }),
+                },
+                DfMergeClause::MatchedUpdate {
+                    condition: None,
+                    assignments: vec![(Ident::new("v"), ident(&["s", "v"]))],
+                },
+                DfMergeClause::NotMatched {
+                    condition: None,
+                    columns: vec![Ident::new("id"), Ident::new("v")],
+                    values: vec![ident(&["s", "id"]), ident(&["s", "v"])],
+                },
+            ],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "merge into t1 using src on t1.id = src.id \
+                   when not matched and src.id > 1 then insert values (src.id, 1)";
+        let expected = DfStatement::Merge(DfMerge {
+            name: ObjectName(vec![Ident::new("t1")]),
+            alias: None,
+            source: TableFactor::Table {
+                name: ObjectName(vec![Ident::new("src")]),
+                alias: None,
+                args: vec![],
+                with_hints: vec![],
+            },
+            on: Expr::BinaryOp {
+                left: Box::new(ident(&["t1", "id"])),
+                op: BinaryOperator::Eq,
+                right: Box::new(ident(&["src", "id"])),
+            },
+            clauses: vec![DfMergeClause::NotMatched {
+                condition: Some(Expr::BinaryOp {
+                    left: Box::new(ident(&["src", "id"])),
+                    op: BinaryOperator::Gt,
+                    right: Box::new(number("1")),
+                }),
+                columns: vec![],
+                values: vec![ident(&["src", "id"]), number("1")],
+            }],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    // No clauses.
+    assert!(DfParser::parse_sql("MERGE INTO t1 USING src ON t1.id = src.id").is_err());
+    // A matched row can not be inserted.
+    assert!(DfParser::parse_sql(
+        "MERGE INTO t1 USING src ON t1.id = src.id WHEN MATCHED THEN INSERT VALUES (1)"
+    )
+    .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn fsck_table() -> Result<()> {
+    {
+        let sql = "FSCK TABLE t1";
+        let expected = DfStatement::FsckTable(DfFsckTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            repair: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "fsck table db1.t1 repair";
+        let expected = DfStatement::FsckTable(DfFsckTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            repair: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn alter_table_set_storage_policy() -> Result<()> {
+    {
+        let sql = "ALTER TABLE db1.t1 SET STORAGE_POLICY hot_to_cold_after = 30d, cold_storage_type = 'disk'";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            action: DfAlterTableAction::SetStoragePolicy(vec![
+                SqlOption {
+                    name: Ident::new("HOT_TO_COLD_AFTER"),
+                    value: Value::SingleQuotedString("30d".into()),
+                },
+                SqlOption {
+                    name: Ident::new("COLD_STORAGE_TYPE"),
+                    value: Value::SingleQuotedString("disk".into()),
+                },
+            ]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 SET STORAGE_POLICY hot_to_cold_after = '12h'";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::SetStoragePolicy(vec![SqlOption {
+                name: Ident::new("HOT_TO_COLD_AFTER"),
+                value: Value::SingleQuotedString("12h".into()),
+            }]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("ALTER TABLE t1 SET hot_to_cold_after = 30d").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn table_options() -> Result<()> {
+    {
+        let sql = "ALTER TABLE db1.t1 SET OPTIONS (max_bytes = 1048576, overflow = 'spill')";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            action: DfAlterTableAction::SetOptions(vec![
+                SqlOption {
+                    name: Ident::new("MAX_BYTES"),
+                    value: Value::Number("1048576".to_string(), false),
+                },
+                SqlOption {
+                    name: Ident::new("OVERFLOW"),
+                    value: Value::SingleQuotedString("spill".into()),
+                },
+            ]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    for sql in [
+        "SHOW TABLE OPTIONS db1.t1",
+        "SHOW TABLE OPTIONS FROM db1.t1",
+        "SHOW TABLE OPTIONS IN db1.t1",
+    ] {
+        let expected = DfStatement::ShowTableOptions(DfShowTableOptions {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("ALTER TABLE t1 SET OPTIONS max_bytes = 1").is_err());
+    assert!(DfParser::parse_sql("SHOW TABLE t1").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn alter_table_add_drop_column() -> Result<()> {
+    {
+        let sql = "ALTER TABLE db1.t1 ADD COLUMN c2 BIGINT";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            action: DfAlterTableAction::AddColumn(make_column_def("c2", DataType::BigInt(None))),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 ADD c2 INT";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::AddColumn(make_column_def("c2", DataType::Int(None))),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 ADD COLUMN c2 INT DEFAULT 1";
+        let mut column = make_column_def("c2", DataType::Int(None));
+        column.options.push(ColumnOptionDef {
+            name: None,
+            option: ColumnOption::Default(Expr::Value(Value::Number("1".to_string(), false))),
+        });
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::AddColumn(column),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    for sql in ["ALTER TABLE t1 DROP COLUMN c2", "ALTER TABLE t1 DROP c2"] {
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::DropColumn(Ident::new("c2")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "ALTER TABLE t1 MATERIALIZE COLUMN c2";
+        let expected = DfStatement::AlterTable(DfAlterTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            action: DfAlterTableAction::MaterializeColumn(Ident::new("c2")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("ALTER TABLE t1 ADD COLUMN c2").is_err());
+    assert!(DfParser::parse_sql("ALTER TABLE t1 MATERIALIZE c2").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn create_drop_index() -> Result<()> {
+    {
+        let sql = "CREATE INDEX IF NOT EXISTS idx1 ON db1.t1 (a + 1)";
+        let expected = DfStatement::CreateIndex(DfCreateIndex {
+            if_not_exists: true,
+            inverted: false,
+            name: Ident::new("idx1"),
+            table_name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            expr: Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Plus,
+                right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+            },
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE INVERTED INDEX idx2 ON t1 (msg)";
+        let expected = DfStatement::CreateIndex(DfCreateIndex {
+            if_not_exists: false,
+            inverted: true,
+            name: Ident::new("idx2"),
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            expr: Expr::Identifier(Ident::new("msg")),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DROP INDEX idx1 ON t1";
+        let expected = DfStatement::DropIndex(DfDropIndex {
+            if_exists: false,
+            name: Ident::new("idx1"),
+            table_name: ObjectName(vec![Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("CREATE INDEX idx1 ON t1 a").is_err());
+    assert!(DfParser::parse_sql("CREATE INVERTED idx1 ON t1 (msg)").is_err());
+    assert!(DfParser::parse_sql("DROP INDEX idx1").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn create_drop_pipe() -> Result<()> {
+    {
+        let sql = "CREATE PIPE IF NOT EXISTS p1 AS COPY INTO db1.t1 FROM KAFKA (brokers = 'localhost:9092', topic = 'events', format = 'csv')";
+        let expected = DfStatement::CreatePipe(DfCreatePipe {
+            if_not_exists: true,
+            name: ObjectName(vec![Ident::new("p1")]),
+            table_name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            source: Ident::new("KAFKA"),
+            options: vec![
+                SqlOption {
+                    name: Ident::new("BROKERS"),
+                    value: Value::SingleQuotedString("localhost:9092".into()),
+                },
+                SqlOption {
+                    name: Ident::new("TOPIC"),
+                    value: Value::SingleQuotedString("events".into()),
+                },
+                SqlOption {
+                    name: Ident::new("FORMAT"),
+                    value: Value::SingleQuotedString("csv".into()),
+                },
+            ],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DROP PIPE IF EXISTS p1";
+        let expected = DfStatement::DropPipe(DfDropPipe {
+            if_exists: true,
+            name: ObjectName(vec![Ident::new("p1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(
+        DfParser::parse_sql("CREATE PIPE p1 COPY INTO t1 FROM KAFKA (topic = 'events')").is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn create_drop_view() -> Result<()> {
+    {
+        let sql = "CREATE VIEW IF NOT EXISTS db1.v1 AS SELECT a, b FROM t1 WHERE a > 1";
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        match &statements[0] {
+            DfStatement::CreateView(create) => {
+                assert!(create.if_not_exists);
+                assert_eq!(
+                    create.name,
+                    ObjectName(vec![Ident::new("db1"), Ident::new("v1")])
+                );
+                assert_eq!(create.query.to_string(), "SELECT a, b FROM t1 WHERE a > 1");
+            }
+            other => panic!("Expected CreateView, got {:?}", other),
+        }
+    }
+
+    {
+        let sql = "DROP VIEW IF EXISTS v1";
+        let expected = DfStatement::DropView(DfDropView {
+            if_exists: true,
+            name: ObjectName(vec![Ident::new("v1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("CREATE VIEW v1 SELECT 1").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn create_function() -> Result<()> {
+    {
+        let sql = "CREATE FUNCTION IF NOT EXISTS add_one LANGUAGE wasm AS '(module)'";
+        let expected = DfStatement::CreateFunction(DfCreateFunction {
+            if_not_exists: true,
+            name: ObjectName(vec![Ident::new("add_one")]),
+            language: Ident::new("wasm"),
+            body: "(module)".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE FUNCTION mylib.add_one LANGUAGE wasm AS '(module)'";
+        let expected = DfStatement::CreateFunction(DfCreateFunction {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("mylib"), Ident::new("add_one")]),
+            language: Ident::new("wasm"),
+            body: "(module)".to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("CREATE FUNCTION add_one AS '(module)'").is_err());
+    assert!(DfParser::parse_sql("CREATE FUNCTION add_one LANGUAGE wasm AS add_two").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn create_external_function() -> Result<()> {
+    {
+        let sql = "CREATE EXTERNAL FUNCTION score(Float64, BIGINT) RETURNS Float64 AS 'http://127.0.0.1:8080/score' (max_retries = 1)";
+        let expected = DfStatement::CreateExternalFunction(DfCreateExternalFunction {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("score")]),
+            arg_types: vec![
+                DataType::Custom(ObjectName(vec![Ident::new("Float64")])),
+                DataType::BigInt(None),
+            ],
+            return_type: DataType::Custom(ObjectName(vec![Ident::new("Float64")])),
+            endpoint: "http://127.0.0.1:8080/score".to_string(),
+            options: vec![SqlOption {
+                name: Ident::new("MAX_RETRIES"),
+                value: Value::Number("1".into(), false),
+            }],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "CREATE EXTERNAL FUNCTION IF NOT EXISTS now_ms() RETURNS Int64 AS 'http://127.0.0.1:8080/now'";
+        let expected = DfStatement::CreateExternalFunction(DfCreateExternalFunction {
+            if_not_exists: true,
+            name: ObjectName(vec![Ident::new("now_ms")]),
+            arg_types: vec![],
+            return_type: DataType::Custom(ObjectName(vec![Ident::new("Int64")])),
+            endpoint: "http://127.0.0.1:8080/now".to_string(),
+            options: vec![],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("CREATE EXTERNAL FUNCTION f(Int64) AS 'http://h/'").is_err());
+    assert!(DfParser::parse_sql("CREATE EXTERNAL FUNCTION f(Int64) RETURNS Int64").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn drop_function() -> Result<()> {
+    {
+        let sql = "DROP FUNCTION add_one";
+        let expected = DfStatement::DropFunction(DfDropFunction {
+            if_exists: false,
+            name: ObjectName(vec![Ident::new("add_one")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DROP FUNCTION IF EXISTS mylib.add_one";
+        let expected = DfStatement::DropFunction(DfDropFunction {
+            if_exists: true,
+            name: ObjectName(vec![Ident::new("mylib"), Ident::new("add_one")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn copy_into() -> Result<()> {
+    {
+        let sql = "COPY INTO db1.t1 FROM 'data/events/' (format = 'csv', chunk_max_rows = 1000)";
+        let expected = DfStatement::Copy(DfCopy {
+            table_name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            location: "data/events/".to_string(),
+            options: vec![
+                SqlOption {
+                    name: Ident::new("FORMAT"),
+                    value: Value::SingleQuotedString("csv".into()),
+                },
+                SqlOption {
+                    name: Ident::new("CHUNK_MAX_ROWS"),
+                    value: Value::Number("1000".into(), false),
+                },
+            ],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "COPY INTO t1 FROM 'data/events/'";
+        let expected = DfStatement::Copy(DfCopy {
+            table_name: ObjectName(vec![Ident::new("t1")]),
+            location: "data/events/".to_string(),
+            options: vec![],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "COPY INTO 'exports/' FROM db1.t1 PARTITION BY (k, d AS y) (max_file_size = 100)";
+        let expected = DfStatement::CopyIntoLocation(DfCopyIntoLocation {
+            location: "exports/".to_string(),
+            source: DfCopySource::Table(ObjectName(vec![Ident::new("db1"), Ident::new("t1")])),
+            partition_by: vec![
+                SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("k"))),
+                SelectItem::ExprWithAlias {
+                    expr: Expr::Identifier(Ident::new("d")),
+                    alias: Ident::new("y"),
+                },
+            ],
+            options: vec![SqlOption {
+                name: Ident::new("MAX_FILE_SIZE"),
+                value: Value::Number("100".into(), false),
+            }],
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "COPY INTO 'exports/' FROM (SELECT a FROM t1)";
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        match &statements[0] {
+            DfStatement::CopyIntoLocation(copy) => {
+                assert!(matches!(copy.source, DfCopySource::Query(_)));
+                assert!(copy.partition_by.is_empty());
+            }
+            other => panic!("Unexpected statement: {:?}", other),
+        }
+    }
+
+    assert!(DfParser::parse_sql("COPY INTO 'exports/' FROM t1 PARTITION BY k").is_err());
+    assert!(DfParser::parse_sql("COPY INTO t1 FROM data").is_err());
+    assert!(DfParser::parse_sql("COPY t1 FROM 'data/events/'").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn list() -> Result<()> {
+    for (sql, location) in [
+        ("LIST @data/events-2021/", "data/events-2021/"),
+        ("LIST @data/part-0.csv;", "data/part-0.csv"),
+        ("list 'data/my events/'", "data/my events/"),
+    ] {
+        let expected = DfStatement::List(DfList {
+            location: location.to_string(),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    assert!(DfParser::parse_sql("LIST data/events/").is_err());
+    assert!(DfParser::parse_sql("LIST").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn aggregate_filter() -> Result<()> {
+    let tests = vec![
+        (
+            "SELECT sum(a) FILTER (WHERE b > 1) FROM t",
+            "SELECT sumIf(a, b > 1) FROM t",
+        ),
+        (
+            "SELECT count() filter (where (a + 1) > 2), count(*) FILTER(WHERE b) FROM t",
+            "SELECT countIf((a + 1) > 2), countIf(*, b) FROM t",
+        ),
+        (
+            "SELECT count(DISTINCT a, b) FILTER (WHERE c = 'x') AS c FROM t GROUP BY d",
+            "SELECT countIf(DISTINCT a, b, c = 'x') AS c FROM t GROUP BY d",
+        ),
+    ];
+
+    for (sql, expected) in tests {
+        let (expected, _) = DfParser::parse_sql(expected)?;
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        assert_eq!(statements, expected, "{}", sql);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn prewhere() -> Result<()> {
+    let tests = vec![
+        (
+            "SELECT a FROM t PREWHERE b > 1",
+            "SELECT a FROM t WHERE prewhere(b > 1)",
+        ),
+        (
+            "SELECT a FROM t prewhere b > 1 OR c WHERE d = 1 OR e ORDER BY a LIMIT 3",
+            "SELECT a FROM t WHERE prewhere(b > 1 OR c) AND (d = 1 OR e) ORDER BY a LIMIT 3",
+        ),
+        (
+            "SELECT a FROM (SELECT a FROM t PREWHERE (b + 1) > 2) WHERE a > 1 GROUP BY a",
+            "SELECT a FROM (SELECT a FROM t WHERE prewhere((b + 1) > 2)) WHERE a > 1 GROUP BY a",
+        ),
+    ];
+
+    for (sql, expected) in tests {
+        let (expected, _) = DfParser::parse_sql(expected)?;
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        assert_eq!(statements, expected, "{}", sql);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn hint_test() -> Result<()> {
     {
@@ -375,3 +1247,161 @@ fn show_databases_test() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn show_create_test() -> Result<()> {
+    expect_parse_ok(
+        "SHOW CREATE TABLE db1.t1",
+        DfStatement::ShowCreateTable(DfShowCreateTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "SHOW CREATE DATABASE db1",
+        DfStatement::ShowCreateDatabase(DfShowCreateDatabase {
+            name: ObjectName(vec![Ident::new("db1")]),
+        }),
+    )?;
+
+    assert!(DfParser::parse_sql("SHOW CREATE PIPE p1").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn system_drop_query_cache_test() -> Result<()> {
+    expect_parse_ok(
+        "SYSTEM DROP QUERY CACHE",
+        DfStatement::DropQueryCache(DfDropQueryCache { table: None }),
+    )?;
+
+    expect_parse_ok(
+        "SYSTEM DROP QUERY CACHE FOR TABLE db1.t1",
+        DfStatement::DropQueryCache(DfDropQueryCache {
+            table: Some(ObjectName(vec![Ident::new("db1"), Ident::new("t1")])),
+        }),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn kill_test() -> Result<()> {
+    expect_parse_ok(
+        "KILL QUERY 'f0e1c2b3-a4d5-4e6f-8a9b-0c1d2e3f4a5b'",
+        DfStatement::KillQuery(DfKillStatement {
+            object_id: Ident::new("f0e1c2b3-a4d5-4e6f-8a9b-0c1d2e3f4a5b"),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "KILL CONNECTION session_1",
+        DfStatement::KillConn(DfKillStatement {
+            object_id: Ident::new("session_1"),
+        }),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn settings_profile_test() -> Result<()> {
+    expect_parse_ok(
+        "CREATE SETTINGS PROFILE IF NOT EXISTS etl SETTINGS max_threads = 4, query_tag = 'etl'",
+        DfStatement::CreateProfile(DfCreateProfile {
+            if_not_exists: true,
+            name: Ident::new("etl"),
+            settings: vec![
+                SqlOption {
+                    name: Ident::new("MAX_THREADS".to_string()),
+                    value: Value::Number("4".to_string(), false),
+                },
+                SqlOption {
+                    name: Ident::new("QUERY_TAG".to_string()),
+                    value: Value::SingleQuotedString("etl".into()),
+                },
+            ],
+        }),
+    )?;
+
+    expect_parse_ok(
+        "DROP SETTINGS PROFILE etl",
+        DfStatement::DropProfile(DfDropProfile {
+            if_exists: false,
+            name: Ident::new("etl"),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "ALTER USER 'test' SETTINGS PROFILE etl",
+        DfStatement::AlterUser(DfAlterUser {
+            name: Ident::new("test"),
+            profile: Some(Ident::new("etl")),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "ALTER USER test SETTINGS PROFILE NONE",
+        DfStatement::AlterUser(DfAlterUser {
+            name: Ident::new("test"),
+            profile: None,
+        }),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn connection_test() -> Result<()> {
+    expect_parse_ok(
+        "CREATE CONNECTION IF NOT EXISTS s3_conn STORAGE_TYPE = 's3' STORAGE_S3_SECRET_ACCESS_KEY = 'x'",
+        DfStatement::CreateConnection(DfCreateConnection {
+            if_not_exists: true,
+            name: Ident::new("s3_conn"),
+            options: vec![
+                SqlOption {
+                    name: Ident::new("STORAGE_TYPE".to_string()),
+                    value: Value::SingleQuotedString("s3".into()),
+                },
+                SqlOption {
+                    name: Ident::new("STORAGE_S3_SECRET_ACCESS_KEY".to_string()),
+                    value: Value::SingleQuotedString("x".into()),
+                },
+            ],
+        }),
+    )?;
+
+    expect_parse_ok(
+        "DROP CONNECTION IF EXISTS s3_conn",
+        DfStatement::DropConnection(DfDropConnection {
+            if_exists: true,
+            name: Ident::new("s3_conn"),
+        }),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn fold_identifiers() -> Result<()> {
+    let ident_case = IdentCase {
+        unquoted_case_sensitive: false,
+        quoted_case_sensitive: true,
+    };
+    let (statements, _) = DfParser::parse_sql_with_ident_case("DROP TABLE Db1.\"T1\"", ident_case)?;
+    let expected = DfStatement::DropTable(DfDropTable {
+        if_exists: false,
+        name: ObjectName(vec![Ident::new("db1"), Ident::with_quote('"', "T1")]),
+    });
+    assert_eq!(statements[0], expected);
+
+    let (statements, _) = DfParser::parse_sql("DROP TABLE Db1.\"T1\"")?;
+    let expected = DfStatement::DropTable(DfDropTable {
+        if_exists: false,
+        name: ObjectName(vec![Ident::new("Db1"), Ident::with_quote('"', "T1")]),
+    });
+    assert_eq!(statements[0], expected);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use sqlparser::dialect::GenericDialect;
+use sqlparser::tokenizer::Token;
+use sqlparser::tokenizer::Tokenizer;
+
+use crate::datasources::common::is_secret_storage_option;
+
+const REDACTED: &str = "******";
+
+/// Masks the credentials of the storage options in the query text, e.g.
+/// `storage_s3_secret_access_key = 'sk'` becomes `storage_s3_secret_access_key = '******'`,
+/// before the query is logged or shown in the processes and the query log.
+pub fn redact_secrets(query: &str) -> String {
+    let dialect = GenericDialect {};
+    let mut tokens = match Tokenizer::new(&dialect, query).tokenize() {
+        Ok(tokens) => tokens,
+        // Not a query we can make sense of, keep what is before the first credential.
+        Err(_) => return truncate_at_secret(query),
+    };
+
+    // 0: nothing, 1: the name of a secret option, 2: the name and `=`.
+    let mut state = 0;
+    let mut redacted = false;
+    for token in tokens.iter_mut() {
+        match token {
+            Token::Whitespace(_) => {}
+            Token::Word(w) if is_secret_storage_option(&w.value.to_lowercase()) => state = 1,
+            Token::Eq if state == 1 => state = 2,
+            Token::SingleQuotedString(value) if state == 2 => {
+                *value = REDACTED.to_string();
+                redacted = true;
+                state = 0;
+            }
+            _ => state = 0,
+        }
+    }
+
+    if !redacted {
+        return query.to_string();
+    }
+    tokens.iter().map(|token| token.to_string()).collect()
+}
+
+fn truncate_at_secret(query: &str) -> String {
+    let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut word_start = None;
+    for (idx, c) in query
+        .char_indices()
+        .chain(std::iter::once((query.len(), ' ')))
+    {
+        match (word_start, is_ident_char(c)) {
+            (None, true) => word_start = Some(idx),
+            (Some(start), false) => {
+                if is_secret_storage_option(&query[start..idx].to_lowercase()) {
+                    return format!("{} {}", &query[..idx], REDACTED);
+                }
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    query.to_string()
+}
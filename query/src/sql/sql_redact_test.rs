@@ -0,0 +1,44 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use pretty_assertions::assert_eq;
+
+use crate::sql::redact_secrets;
+
+#[test]
+fn test_redact_secrets() {
+    let tests = vec![
+        (
+            "create connection c storage_type = 's3', storage_s3_secret_access_key = 'sk'",
+            "create connection c storage_type = 's3', storage_s3_secret_access_key = '******'",
+        ),
+        (
+            "CREATE DATABASE db STORAGE_S3_ACCESS_KEY_ID='ak' COLD_STORAGE_AZURE_BLOB_ACCOUNT_KEY = 'k'",
+            "CREATE DATABASE db STORAGE_S3_ACCESS_KEY_ID='******' COLD_STORAGE_AZURE_BLOB_ACCOUNT_KEY = '******'",
+        ),
+        // Only the values of the secret options.
+        (
+            "select 'storage_s3_secret_access_key', storage_s3_bucket = 'b' from t",
+            "select 'storage_s3_secret_access_key', storage_s3_bucket = 'b' from t",
+        ),
+        // A query which can not be tokenized is cut at the first credential.
+        (
+            "create connection c storage_s3_secret_access_key = 'sk",
+            "create connection c storage_s3_secret_access_key ******",
+        ),
+    ];
+
+    for (query, expect) in tests {
+        assert_eq!(redact_secrets(query), expect, "{}", query);
+    }
+}
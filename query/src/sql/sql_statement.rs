@@ -20,11 +20,16 @@ use nom::character::complete::multispace0;
 use nom::character::complete::multispace1;
 use nom::IResult;
 use sqlparser::ast::ColumnDef;
+use sqlparser::ast::DataType;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
 use sqlparser::ast::ObjectName;
+use sqlparser::ast::Query;
+use sqlparser::ast::SelectItem;
 use sqlparser::ast::SqlOption;
 use sqlparser::ast::Statement as SQLStatement;
+use sqlparser::ast::TableConstraint;
+use sqlparser::ast::TableFactor;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DfShowTables {
@@ -56,14 +61,30 @@ pub struct DfShowCreateTable {
     pub name: ObjectName,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfShowCreateDatabase {
+    pub name: ObjectName,
+}
+
+/// `SHOW TABLE OPTIONS [FROM | IN] t`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfShowTableOptions {
+    pub name: ObjectName,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateTable {
     pub if_not_exists: bool,
+    pub temporary: bool,
     /// Table name
     pub name: ObjectName,
     pub columns: Vec<ColumnDef>,
+    /// Table level PRIMARY KEY and UNIQUE constraints, the column level ones are in `columns`
+    pub constraints: Vec<TableConstraint>,
     pub engine: String,
     pub options: Vec<SqlOption>,
+    /// The query of `CREATE TABLE ... AS SELECT`
+    pub query: Option<Box<Query>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -82,17 +103,97 @@ pub struct DfTruncateTable {
     pub name: ObjectName,
 }
 
+/// `CREATE [INVERTED] INDEX [IF NOT EXISTS] idx ON t (expr)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateIndex {
+    pub if_not_exists: bool,
+    pub inverted: bool,
+    pub name: Ident,
+    pub table_name: ObjectName,
+    pub expr: Expr,
+}
+
+/// `DROP INDEX [IF EXISTS] idx ON t`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropIndex {
+    pub if_exists: bool,
+    pub name: Ident,
+    pub table_name: ObjectName,
+}
+
+/// `ANALYZE TABLE t [UPDATE HISTOGRAM ON c1, c2 [WITH 100 BUCKETS]]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfAnalyzeTable {
+    pub name: ObjectName,
+    pub histogram_columns: Vec<Ident>,
+    pub buckets: Option<u64>,
+}
+
+/// `FSCK TABLE t [REPAIR]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfFsckTable {
+    pub name: ObjectName,
+    pub repair: bool,
+}
+
+/// `DELETE FROM t [WHERE expr]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDelete {
+    pub name: ObjectName,
+    pub selection: Option<Expr>,
+}
+
+/// `UPDATE t SET c1 = expr1, c2 = expr2 [WHERE expr]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfUpdate {
+    pub name: ObjectName,
+    pub assignments: Vec<(Ident, Expr)>,
+    pub selection: Option<Expr>,
+}
+
+/// A `WHEN [NOT] MATCHED` clause of `MERGE`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfMergeClause {
+    /// `WHEN MATCHED [AND expr] THEN UPDATE SET c1 = expr1, c2 = expr2`
+    MatchedUpdate {
+        condition: Option<Expr>,
+        assignments: Vec<(Ident, Expr)>,
+    },
+    /// `WHEN MATCHED [AND expr] THEN DELETE`
+    MatchedDelete { condition: Option<Expr> },
+    /// `WHEN NOT MATCHED [AND expr] THEN INSERT [(c1, c2)] VALUES (expr1, expr2)`
+    NotMatched {
+        condition: Option<Expr>,
+        columns: Vec<Ident>,
+        values: Vec<Expr>,
+    },
+}
+
+/// `MERGE INTO t [[AS] alias] USING source ON expr WHEN [NOT] MATCHED ...`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfMerge {
+    pub name: ObjectName,
+    pub alias: Option<Ident>,
+    /// The source table or subquery, with its alias
+    pub source: TableFactor,
+    pub on: Expr,
+    pub clauses: Vec<DfMergeClause>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateDatabase {
     pub if_not_exists: bool,
     pub name: ObjectName,
     pub options: Vec<SqlOption>,
+    /// The tables of `WITH TABLES (...)`, named by `database.table`
+    pub tables: Vec<DfCreateTable>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfDropDatabase {
     pub if_exists: bool,
     pub name: ObjectName,
+    pub cascade: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -105,6 +206,154 @@ pub struct DfKillStatement {
     pub object_id: Ident,
 }
 
+/// `CREATE PIPE p AS COPY INTO t FROM KAFKA (brokers = '..', topic = '..', format = 'csv')`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreatePipe {
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub table_name: ObjectName,
+    pub source: Ident,
+    pub options: Vec<SqlOption>,
+}
+
+/// `COPY INTO t FROM 'data/events/' (format = 'csv')`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCopy {
+    pub table_name: ObjectName,
+    pub location: String,
+    pub options: Vec<SqlOption>,
+}
+
+/// `COPY INTO 'exports/events/' FROM t PARTITION BY (year, month) (format = 'csv')`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCopyIntoLocation {
+    pub location: String,
+    pub source: DfCopySource,
+    /// The expressions giving the `key=value/` directories, `expr [AS key]`.
+    pub partition_by: Vec<SelectItem>,
+    pub options: Vec<SqlOption>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfCopySource {
+    Table(ObjectName),
+    Query(Box<Query>),
+}
+
+/// `LIST @data/events/` or `LIST 'data/events/'`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfList {
+    pub location: String,
+}
+
+/// `CREATE VIEW v AS SELECT ...`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateView {
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub query: Box<Query>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropView {
+    pub if_exists: bool,
+    pub name: ObjectName,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropPipe {
+    pub if_exists: bool,
+    pub name: ObjectName,
+}
+
+/// `CREATE SETTINGS PROFILE p SETTINGS max_threads = 4, query_tag = 'etl'`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateProfile {
+    pub if_not_exists: bool,
+    pub name: Ident,
+    pub settings: Vec<SqlOption>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropProfile {
+    pub if_exists: bool,
+    pub name: Ident,
+}
+
+/// `CREATE CONNECTION c STORAGE_TYPE = 's3' STORAGE_S3_BUCKET = 'b' ...`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateConnection {
+    pub if_not_exists: bool,
+    pub name: Ident,
+    pub options: Vec<SqlOption>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropConnection {
+    pub if_exists: bool,
+    pub name: Ident,
+}
+
+/// `ALTER USER u SETTINGS PROFILE {p | NONE}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfAlterUser {
+    pub name: Ident,
+    pub profile: Option<Ident>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfAlterTableAction {
+    /// `SET STORAGE_POLICY hot_to_cold_after = 30d, cold_storage_type = 's3'`
+    SetStoragePolicy(Vec<SqlOption>),
+    /// `SET OPTIONS (max_bytes = 1048576, overflow = 'spill')`
+    SetOptions(Vec<SqlOption>),
+    /// `ADD [COLUMN] c type [DEFAULT expr]`
+    AddColumn(ColumnDef),
+    /// `DROP [COLUMN] c`
+    DropColumn(Ident),
+    /// `MATERIALIZE COLUMN c`
+    MaterializeColumn(Ident),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfAlterTable {
+    pub name: ObjectName,
+    pub action: DfAlterTableAction,
+}
+
+/// `CREATE FUNCTION [ns.]f LANGUAGE wasm AS '(module ...)'`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateFunction {
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub language: Ident,
+    pub body: String,
+}
+
+/// `CREATE EXTERNAL FUNCTION [ns.]f(Float64, Float64) RETURNS Float64 AS 'http://host/score' (max_retries = 3)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCreateExternalFunction {
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub arg_types: Vec<DataType>,
+    pub return_type: DataType,
+    pub endpoint: String,
+    pub options: Vec<SqlOption>,
+}
+
+/// `DROP FUNCTION [IF EXISTS] [ns.]f`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropFunction {
+    pub if_exists: bool,
+    pub name: ObjectName,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropQueryCache {
+    /// Only drop the results computed from the table if present
+    pub table: Option<ObjectName>,
+}
+
 /// Tokens parsed by `DFParser` are converted into these values.
 #[derive(Debug, Clone, PartialEq)]
 pub enum DfStatement {
@@ -114,6 +363,7 @@ pub enum DfStatement {
 
     // Databases.
     ShowDatabases(DfShowDatabases),
+    ShowCreateDatabase(DfShowCreateDatabase),
     CreateDatabase(DfCreateDatabase),
     DropDatabase(DfDropDatabase),
     UseDatabase(DfUseDatabase),
@@ -121,13 +371,51 @@ pub enum DfStatement {
     // Tables.
     ShowTables(DfShowTables),
     ShowCreateTable(DfShowCreateTable),
+    ShowTableOptions(DfShowTableOptions),
     CreateTable(DfCreateTable),
     DescribeTable(DfDescribeTable),
     DropTable(DfDropTable),
     TruncateTable(DfTruncateTable),
+    AlterTable(DfAlterTable),
+    CreateIndex(DfCreateIndex),
+    DropIndex(DfDropIndex),
+    AnalyzeTable(DfAnalyzeTable),
+    FsckTable(DfFsckTable),
+
+    // Rows.
+    Delete(DfDelete),
+    Update(DfUpdate),
+    Merge(DfMerge),
+
+    // Views.
+    CreateView(DfCreateView),
+    DropView(DfDropView),
+
+    // Pipes.
+    CreatePipe(DfCreatePipe),
+    DropPipe(DfDropPipe),
+
+    // Loads.
+    Copy(DfCopy),
+    CopyIntoLocation(DfCopyIntoLocation),
+    List(DfList),
+
+    // Functions.
+    CreateFunction(DfCreateFunction),
+    CreateExternalFunction(DfCreateExternalFunction),
+    DropFunction(DfDropFunction),
 
     // Settings.
     ShowSettings(DfShowSettings),
+    CreateProfile(DfCreateProfile),
+    DropProfile(DfDropProfile),
+
+    // Users.
+    AlterUser(DfAlterUser),
+
+    // Connections.
+    CreateConnection(DfCreateConnection),
+    DropConnection(DfDropConnection),
 
     // ProcessList
     ShowProcessList(DfShowProcessList),
@@ -135,6 +423,9 @@ pub enum DfStatement {
     // Kill
     KillQuery(DfKillStatement),
     KillConn(DfKillStatement),
+
+    // System
+    DropQueryCache(DfDropQueryCache),
 }
 
 /// Comment hints from SQL.
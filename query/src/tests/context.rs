@@ -29,7 +29,7 @@ pub fn try_create_context() -> Result<DatabendQueryContextRef> {
     let dummy_session = sessions.create_session("TestSession")?;
 
     let context = DatabendQueryContext::from_shared(DatabendQueryContextShared::try_create(
-        sessions.get_conf().clone(),
+        sessions.get_conf(),
         Arc::new(dummy_session.as_ref().clone()),
         Cluster::empty(),
     ));
@@ -96,7 +96,7 @@ pub fn try_create_cluster_context(desc: ClusterDescriptor) -> Result<DatabendQue
     let nodes = desc.cluster_nodes_list;
 
     let context = DatabendQueryContext::from_shared(DatabendQueryContextShared::try_create(
-        sessions.get_conf().clone(),
+        sessions.get_conf(),
         Arc::new(dummy_session.as_ref().clone()),
         Cluster::create(nodes, local_id),
     ));
@@ -84,6 +84,24 @@ impl SessionManagerBuilder {
         SessionManagerBuilder::inner_create(new_config)
     }
 
+    pub fn multi_tenant(self, value: bool) -> SessionManagerBuilder {
+        let mut new_config = self.config;
+        new_config.query.multi_tenant = value;
+        SessionManagerBuilder::inner_create(new_config)
+    }
+
+    pub fn connection_master_keys(self, value: impl Into<String>) -> SessionManagerBuilder {
+        let mut new_config = self.config;
+        new_config.storage.connection.master_keys = value.into();
+        SessionManagerBuilder::inner_create(new_config)
+    }
+
+    pub fn disk_data_path(self, value: impl Into<String>) -> SessionManagerBuilder {
+        let mut new_config = self.config;
+        new_config.storage.disk.data_path = value.into();
+        SessionManagerBuilder::inner_create(new_config)
+    }
+
     pub fn log_dir_with_relative(self, path: impl Into<String>) -> SessionManagerBuilder {
         let mut new_config = self.config;
         new_config.log.log_dir = env::current_dir()
@@ -15,6 +15,7 @@
 
 use common_management::AuthType;
 use common_management::UserInfo;
+use common_management::UserQuota;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct User {
@@ -39,6 +40,8 @@ impl From<&User> for UserInfo {
             name: user.name.clone(),
             password: Vec::from(user.password.clone()),
             auth_type: user.auth_type.clone(),
+            quota: UserQuota::default(),
+            profile: None,
         }
     }
 }
@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_management::AuthType;
+use common_management::ProfileInfo;
+use common_management::ProfileMgr;
+use common_management::ProfileMgrApi;
 use common_management::UserInfo;
 use common_management::UserMgr;
 use common_management::UserMgrApi;
@@ -29,12 +34,19 @@ use crate::users::User;
 pub type UserManagerRef = Arc<UserManager>;
 
 pub struct UserManager {
+    kv_api: Arc<dyn KVApi>,
+    /// The tenant the users belong to.
+    tenant: String,
+    /// The tenant of `--tenant`, which has the built-in users.
+    default_tenant: String,
+    multi_tenant: bool,
     api_provider: Arc<dyn UserMgrApi>,
+    profile_api_provider: Arc<dyn ProfileMgrApi>,
 }
 
 impl UserManager {
     async fn create_kv_client(cfg: &Config) -> Result<Arc<dyn KVApi>> {
-        let store_api_provider = MetaClientProvider::new(cfg);
+        let store_api_provider = MetaClientProvider::from(cfg);
         match store_api_provider.try_get_kv_client().await {
             Ok(client) => Ok(client),
             Err(cause) => Err(cause.add_message_back("(while create user api).")),
@@ -44,18 +56,50 @@ impl UserManager {
     pub async fn create_global(cfg: Config) -> Result<UserManagerRef> {
         let client = UserManager::create_kv_client(&cfg).await?;
         let tenant = &cfg.query.tenant;
-        let user_manager = UserMgr::new(client, tenant);
+        let user_manager = UserMgr::new(client.clone(), tenant);
+        let profile_manager = ProfileMgr::new(client.clone(), tenant);
 
         Ok(Arc::new(UserManager {
+            kv_api: client,
+            tenant: tenant.clone(),
+            default_tenant: tenant.clone(),
+            multi_tenant: cfg.query.multi_tenant,
             api_provider: Arc::new(user_manager),
+            profile_api_provider: Arc::new(profile_manager),
         }))
     }
 
+    /// Splits a login into the tenant and the user name. With `--multi-tenant`, `user@tenant`
+    /// logs in to the tenant, any other login is to the tenant of `--tenant`.
+    pub fn split_login(&self, login: &str) -> (String, String) {
+        if self.multi_tenant {
+            if let Some((user, tenant)) = login.rsplit_once('@') {
+                if !user.is_empty() && !tenant.is_empty() {
+                    return (tenant.to_string(), user.to_string());
+                }
+            }
+        }
+        (self.default_tenant.clone(), login.to_string())
+    }
+
+    /// The manager of the users of another tenant.
+    pub fn for_tenant(&self, tenant: &str) -> UserManagerRef {
+        Arc::new(UserManager {
+            kv_api: self.kv_api.clone(),
+            tenant: tenant.to_string(),
+            default_tenant: self.default_tenant.clone(),
+            multi_tenant: self.multi_tenant,
+            api_provider: Arc::new(UserMgr::new(self.kv_api.clone(), tenant)),
+            profile_api_provider: Arc::new(ProfileMgr::new(self.kv_api.clone(), tenant)),
+        })
+    }
+
     // Get one user from by tenant.
     pub fn get_user(&self, user: &str) -> Result<UserInfo> {
         match user {
             // TODO(BohuTANG): Mock, need removed.
-            "default" | "" | "root" => {
+            // The other tenants never log in without their own users.
+            "default" | "" | "root" if self.tenant == self.default_tenant => {
                 let user = User::new(user, "", AuthType::None);
                 Ok(user.into())
             }
@@ -113,4 +157,44 @@ impl UserManager {
     pub fn drop_user(&self, user: &str) -> Result<()> {
         self.api_provider.drop_user(user.to_string(), None)
     }
+
+    // Attach a settings profile to the user, None detaches it.
+    pub fn set_user_profile(&self, user: &str, profile: Option<String>) -> Result<u64> {
+        if let Some(profile) = &profile {
+            self.get_profile(profile)?;
+        }
+        self.api_provider
+            .set_user_profile(user.to_string(), profile, None)
+    }
+
+    pub fn get_profile(&self, profile: &str) -> Result<ProfileInfo> {
+        Ok(self
+            .profile_api_provider
+            .get_profile(profile.to_string(), None)?
+            .1)
+    }
+
+    pub fn add_profile(&self, profile_info: ProfileInfo) -> Result<u64> {
+        self.profile_api_provider.add_profile(profile_info)
+    }
+
+    pub fn drop_profile(&self, profile: &str) -> Result<()> {
+        self.profile_api_provider
+            .drop_profile(profile.to_string(), None)
+    }
+
+    /// The settings the sessions of the user start with: the `default` profile of the tenant,
+    /// overridden by the profile of the user. A profile which is dropped is skipped.
+    pub fn get_user_settings(&self, user: &UserInfo) -> Result<BTreeMap<String, String>> {
+        let mut settings = BTreeMap::new();
+        let profiles = std::iter::once("default").chain(user.profile.as_deref());
+        for profile in profiles {
+            match self.get_profile(profile) {
+                Ok(profile_info) => settings.extend(profile_info.settings),
+                Err(cause) if cause.code() == ErrorCode::UnknownSettingsProfile("").code() => {}
+                Err(cause) => return Err(cause),
+            }
+        }
+        Ok(settings)
+    }
 }
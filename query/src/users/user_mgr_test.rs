@@ -57,3 +57,30 @@ async fn test_user_manager() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_user_manager_multi_tenant() -> Result<()> {
+    let mut config = Config::default();
+    config.query.tenant = "tenant1".to_string();
+    config.query.multi_tenant = true;
+    let user_mgr = UserManager::create_global(config).await?;
+
+    let login = |tenant: &str, user: &str| (tenant.to_string(), user.to_string());
+    assert_eq!(
+        login("tenant2", "user1"),
+        user_mgr.split_login("user1@tenant2")
+    );
+    assert_eq!(login("tenant1", "user1"), user_mgr.split_login("user1"));
+    assert_eq!(login("tenant1", "user1@"), user_mgr.split_login("user1@"));
+
+    let tenant2 = user_mgr.for_tenant("tenant2");
+    tenant2.add_user(User::new("user1", "pwd", AuthType::PlainText).into())?;
+    assert!(tenant2.auth_user("user1", "pwd", "")?);
+
+    // Neither the users of another tenant nor the built-in ones.
+    assert!(user_mgr.get_user("user1").is_err());
+    assert!(tenant2.get_user("root").is_err());
+    assert!(user_mgr.get_user("root").is_ok());
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio::runtime::Runtime;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::interpreters::InterpreterFactory;
+use databend_query::sessions::SessionManager;
+use databend_query::sessions::SessionRef;
+use databend_query::sql::PlanParser;
+use futures::TryStreamExt;
+use mysql::prelude::Queryable;
+use mysql::Conn;
+use mysql::Value;
+
+/// The rows of a query result, each value formatted as a string, `NULL` for null.
+pub type Rows = Vec<Vec<String>>;
+
+/// A protocol which the SQL is sent through.
+pub trait Backend {
+    fn name(&self) -> &str;
+
+    fn query(&mut self, sql: &str) -> Result<Rows>;
+}
+
+/// Runs the SQL in a session of the embedded server, without any protocol in between.
+pub struct LocalBackend {
+    runtime: Arc<Runtime>,
+    session: SessionRef,
+}
+
+impl LocalBackend {
+    pub fn create(runtime: Arc<Runtime>, sessions: Arc<SessionManager>) -> Result<Self> {
+        let session = sessions.create_session("SQLLogicTest")?;
+        Ok(LocalBackend { runtime, session })
+    }
+
+    async fn execute(session: &SessionRef, sql: &str) -> Result<Vec<DataBlock>> {
+        let context = session.create_context().await?;
+        context.attach_query_str(sql);
+
+        let plan = PlanParser::create(context.clone()).build_from_sql(sql)?;
+        let interpreter = InterpreterFactory::get(context, plan)?;
+        interpreter.execute().await?.try_collect::<Vec<_>>().await
+    }
+}
+
+impl Backend for LocalBackend {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn query(&mut self, sql: &str) -> Result<Rows> {
+        let blocks = self.runtime.block_on(Self::execute(&self.session, sql))?;
+
+        let mut rows = vec![];
+        for block in &blocks {
+            let columns = block
+                .columns()
+                .iter()
+                .map(|c| c.to_array())
+                .collect::<Result<Vec<_>>>()?;
+
+            for row in 0..block.num_rows() {
+                let values = columns
+                    .iter()
+                    .map(|c| c.try_get(row).map(|v| v.to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                rows.push(values);
+            }
+        }
+        Ok(rows)
+    }
+}
+
+/// Sends the SQL through the MySQL handler of the embedded server.
+pub struct MySQLBackend {
+    conn: Conn,
+}
+
+impl MySQLBackend {
+    pub fn create(port: u16) -> Result<Self> {
+        let uri = &format!("mysql://127.0.0.1:{}", port);
+        let opts = mysql::Opts::from_url(uri).map_err(ErrorCode::from_std_error)?;
+        let conn = Conn::new(opts).map_err(ErrorCode::from_std_error)?;
+        Ok(MySQLBackend { conn })
+    }
+
+    fn format_value(value: Value) -> String {
+        match value {
+            Value::NULL => "NULL".to_string(),
+            Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+            Value::Int(v) => v.to_string(),
+            Value::UInt(v) => v.to_string(),
+            Value::Float(v) => v.to_string(),
+            Value::Double(v) => v.to_string(),
+            other => other.as_sql(true),
+        }
+    }
+
+    /// The server reports errors as `Code: {code}, displayText = {message}.`
+    fn server_error_code(message: &str) -> Option<u16> {
+        message
+            .strip_prefix("Code: ")
+            .and_then(|s| s.split(',').next())
+            .and_then(|code| code.parse::<u16>().ok())
+    }
+}
+
+impl Backend for MySQLBackend {
+    fn name(&self) -> &str {
+        "mysql"
+    }
+
+    fn query(&mut self, sql: &str) -> Result<Rows> {
+        let result = self.conn.query_iter(sql).map_err(|error| match error {
+            mysql::Error::MySqlError(e) => match Self::server_error_code(&e.message) {
+                Some(code) => ErrorCode::create(code, e.message, None),
+                None => ErrorCode::UnknownException(e.message),
+            },
+            other => ErrorCode::from_std_error(other),
+        })?;
+
+        let mut rows = vec![];
+        for row in result {
+            let row = row.map_err(ErrorCode::from_std_error)?;
+            rows.push(row.unwrap().into_iter().map(Self::format_value).collect());
+        }
+        Ok(rows)
+    }
+}
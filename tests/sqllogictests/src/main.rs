@@ -0,0 +1,165 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod backends;
+mod parser;
+mod runner;
+
+#[cfg(test)]
+mod parser_test;
+#[cfg(test)]
+mod runner_test;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common_base::tokio::runtime::Runtime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::configs::Config;
+use databend_query::servers::MySQLHandler;
+use databend_query::servers::Server;
+use databend_query::sessions::SessionManager;
+use databend_query::sessions::SessionManagerRef;
+use structopt::StructOpt;
+use walkdir::WalkDir;
+
+use crate::backends::Backend;
+use crate::backends::LocalBackend;
+use crate::backends::MySQLBackend;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "databend-sqllogictests")]
+struct Args {
+    #[structopt(
+        long,
+        default_value = "tests/sqllogictests/suites",
+        help = "Directory of the .slt files"
+    )]
+    suites: PathBuf,
+
+    #[structopt(
+        long,
+        default_value = "local,mysql",
+        use_delimiter = true,
+        help = "Backends to run the files with <local|mysql>"
+    )]
+    backends: Vec<String>,
+
+    #[structopt(long, help = "Only run the files whose path contains the pattern")]
+    pattern: Option<String>,
+}
+
+/// The databend-query started in this process, with its MySQL handler listening on a random port.
+struct EmbeddedServer {
+    runtime: Arc<Runtime>,
+    sessions: SessionManagerRef,
+    mysql_port: u16,
+    _mysql_handler: Box<dyn Server>,
+}
+
+impl EmbeddedServer {
+    fn start() -> Result<Self> {
+        let runtime = Arc::new(Runtime::new()?);
+        let (sessions, mysql_handler, mysql_port) = runtime.block_on(async {
+            let conf = Config::default();
+            let sessions = SessionManager::from_conf(conf.clone()).await?;
+            let cluster_discovery = sessions.get_cluster_discovery();
+            cluster_discovery.register_to_metastore(&conf).await?;
+
+            let mut handler = MySQLHandler::create(sessions.clone());
+            let listening = handler.start("127.0.0.1:0".parse()?).await?;
+            Result::Ok((sessions, handler, listening.port()))
+        })?;
+
+        Ok(EmbeddedServer {
+            runtime,
+            sessions,
+            mysql_port,
+            _mysql_handler: mysql_handler,
+        })
+    }
+
+    fn create_backend(&self, name: &str) -> Result<Box<dyn Backend>> {
+        match name {
+            "local" => Ok(Box::new(LocalBackend::create(
+                self.runtime.clone(),
+                self.sessions.clone(),
+            )?)),
+            "mysql" => Ok(Box::new(MySQLBackend::create(self.mysql_port)?)),
+            other => Err(ErrorCode::BadArguments(format!(
+                "Unknown sqllogictest backend: {}",
+                other
+            ))),
+        }
+    }
+}
+
+fn collect_files(args: &Args) -> Vec<PathBuf> {
+    let mut files = WalkDir::new(&args.suites)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().map(|ext| ext == "slt").unwrap_or(false))
+        .filter(|path| match &args.pattern {
+            None => true,
+            Some(pattern) => path.to_string_lossy().contains(pattern.as_str()),
+        })
+        .collect::<Vec<_>>();
+
+    files.sort();
+    files
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let files = collect_files(&args);
+    let server = EmbeddedServer::start()?;
+
+    let mut failed = 0;
+    for backend_name in &args.backends {
+        for file in &files {
+            let script = std::fs::read_to_string(file).map_err(ErrorCode::from_std_error)?;
+            let records = parser::parse_records(&script)?;
+
+            // Every file runs with a new session(connection), the tables of the previous
+            // files are dropped by the files themselves.
+            let mut backend = server.create_backend(backend_name)?;
+            let failures = runner::run_records(&records, backend.as_mut());
+
+            match failures.is_empty() {
+                true => println!("[{}] {} ... ok", backend_name, file.display()),
+                false => println!("[{}] {} ... FAILED", backend_name, file.display()),
+            }
+
+            for failure in &failures {
+                println!(
+                    "  {}:{}\n  {}\n  {}",
+                    file.display(),
+                    failure.line,
+                    failure.sql,
+                    failure.message.replace('\n', "\n  ")
+                );
+            }
+            failed += failures.len();
+        }
+    }
+
+    if failed != 0 {
+        println!("sqllogictests: {} record(s) failed", failed);
+        std::process::exit(1);
+    }
+    println!("sqllogictests: all passed");
+    Ok(())
+}
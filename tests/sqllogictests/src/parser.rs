@@ -0,0 +1,198 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Restricts a record to some of the backends.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    SkipIf(String),
+    OnlyIf(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortMode {
+    /// Compares the rows in the order they are returned.
+    NoSort,
+    /// Sorts the rows before comparing them.
+    RowSort,
+    /// Sorts all the values, regardless of the rows they belong to.
+    ValueSort,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementExpect {
+    Ok,
+    /// The statement fails, with the given error code if any.
+    Error(Option<u16>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Record {
+    Statement {
+        line: usize,
+        conditions: Vec<Condition>,
+        sql: String,
+        expect: StatementExpect,
+    },
+    Query {
+        line: usize,
+        conditions: Vec<Condition>,
+        sql: String,
+        /// One character per column, e.g. `ITR` for (integer, text, real).
+        types: String,
+        sort_mode: SortMode,
+        /// The lines after the `----` separator.
+        expected: Vec<String>,
+    },
+    /// Stops running the file.
+    Halt { line: usize },
+}
+
+impl Record {
+    pub fn line(&self) -> usize {
+        match self {
+            Record::Statement { line, .. } => *line,
+            Record::Query { line, .. } => *line,
+            Record::Halt { line } => *line,
+        }
+    }
+
+    /// Whether the record applies to the backend.
+    pub fn should_run(&self, backend: &str) -> bool {
+        let conditions = match self {
+            Record::Statement { conditions, .. } => conditions,
+            Record::Query { conditions, .. } => conditions,
+            Record::Halt { .. } => return true,
+        };
+
+        conditions.iter().all(|condition| match condition {
+            Condition::SkipIf(name) => !name.eq_ignore_ascii_case(backend),
+            Condition::OnlyIf(name) => name.eq_ignore_ascii_case(backend),
+        })
+    }
+}
+
+/// Parses the content of a `.slt` file.
+///
+/// Records are separated by blank lines, lines starting with `#` are comments.
+/// ```text
+/// statement ok
+/// CREATE TABLE t(a Int32) Engine = Memory
+///
+/// statement error 1006
+/// SELECT 1 / 'a'
+///
+/// query IT rowsort
+/// SELECT a, b FROM t
+/// ----
+/// 1 x
+/// 2 y
+/// ```
+pub fn parse_records(script: &str) -> Result<Vec<Record>> {
+    let mut records = vec![];
+    let mut conditions = vec![];
+    let mut lines = script.lines().enumerate().peekable();
+
+    while let Some((idx, line)) = lines.next() {
+        let line_no = idx + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        match tokens.as_slice() {
+            ["skipif", name] => conditions.push(Condition::SkipIf(name.to_string())),
+            ["onlyif", name] => conditions.push(Condition::OnlyIf(name.to_string())),
+            ["halt"] => records.push(Record::Halt { line: line_no }),
+            ["hash-threshold", ..] => {}
+            ["statement", expect @ ..] => {
+                let expect = match expect {
+                    ["ok"] => StatementExpect::Ok,
+                    ["error"] => StatementExpect::Error(None),
+                    ["error", code] => StatementExpect::Error(Some(parse_code(code, line_no)?)),
+                    _ => return Err(syntax_error(line_no, line)),
+                };
+
+                let mut sql = vec![];
+                while let Some((_, line)) = lines.next_if(|(_, l)| !l.trim().is_empty()) {
+                    sql.push(line);
+                }
+
+                records.push(Record::Statement {
+                    line: line_no,
+                    conditions: std::mem::take(&mut conditions),
+                    sql: sql_text(sql, line_no)?,
+                    expect,
+                });
+            }
+            ["query", types, options @ ..] => {
+                let sort_mode = match options.first() {
+                    None | Some(&"nosort") => SortMode::NoSort,
+                    Some(&"rowsort") => SortMode::RowSort,
+                    Some(&"valuesort") => SortMode::ValueSort,
+                    Some(_) => return Err(syntax_error(line_no, line)),
+                };
+
+                let mut sql = vec![];
+                while let Some((_, line)) =
+                    lines.next_if(|(_, l)| !l.trim().is_empty() && l.trim() != "----")
+                {
+                    sql.push(line);
+                }
+
+                let mut expected = vec![];
+                if lines.next_if(|(_, l)| l.trim() == "----").is_some() {
+                    while let Some((_, line)) = lines.next_if(|(_, l)| !l.trim().is_empty()) {
+                        expected.push(line.trim().to_string());
+                    }
+                }
+
+                records.push(Record::Query {
+                    line: line_no,
+                    conditions: std::mem::take(&mut conditions),
+                    sql: sql_text(sql, line_no)?,
+                    types: types.to_string(),
+                    sort_mode,
+                    expected,
+                });
+            }
+            _ => return Err(syntax_error(line_no, line)),
+        }
+    }
+
+    Ok(records)
+}
+
+fn sql_text(lines: Vec<&str>, line_no: usize) -> Result<String> {
+    match lines.is_empty() {
+        true => Err(ErrorCode::SyntaxException(format!(
+            "Missing SQL of the record at line {}",
+            line_no
+        ))),
+        false => Ok(lines.join("\n")),
+    }
+}
+
+fn parse_code(code: &str, line_no: usize) -> Result<u16> {
+    code.parse::<u16>().map_err(|_| {
+        ErrorCode::SyntaxException(format!("Bad error code {} at line {}", code, line_no))
+    })
+}
+
+fn syntax_error(line_no: usize, line: &str) -> ErrorCode {
+    ErrorCode::SyntaxException(format!("Unexpected record at line {}: {}", line_no, line))
+}
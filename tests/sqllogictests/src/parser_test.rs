@@ -0,0 +1,108 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::parser::*;
+
+#[test]
+fn test_parse_records() -> Result<()> {
+    let script = r#"
+# comments are ignored
+statement ok
+CREATE TABLE t(a Int64, b String) Engine = Memory
+
+skipif mysql
+statement error 25
+SELECT * FROM
+  not_exists
+
+query IT rowsort
+SELECT a, b FROM t
+----
+1 x
+2 y
+
+query I
+SELECT count(*) FROM t
+
+halt
+"#;
+
+    let records = parse_records(script)?;
+    assert_eq!(records, vec![
+        Record::Statement {
+            line: 3,
+            conditions: vec![],
+            sql: "CREATE TABLE t(a Int64, b String) Engine = Memory".to_string(),
+            expect: StatementExpect::Ok,
+        },
+        Record::Statement {
+            line: 7,
+            conditions: vec![Condition::SkipIf("mysql".to_string())],
+            sql: "SELECT * FROM\n  not_exists".to_string(),
+            expect: StatementExpect::Error(Some(25)),
+        },
+        Record::Query {
+            line: 11,
+            conditions: vec![],
+            sql: "SELECT a, b FROM t".to_string(),
+            types: "IT".to_string(),
+            sort_mode: SortMode::RowSort,
+            expected: vec!["1 x".to_string(), "2 y".to_string()],
+        },
+        Record::Query {
+            line: 17,
+            conditions: vec![],
+            sql: "SELECT count(*) FROM t".to_string(),
+            types: "I".to_string(),
+            sort_mode: SortMode::NoSort,
+            expected: vec![],
+        },
+        Record::Halt { line: 20 },
+    ]);
+
+    assert!(!records[1].should_run("mysql"));
+    assert!(records[1].should_run("local"));
+    Ok(())
+}
+
+#[test]
+fn test_parse_records_error() -> Result<()> {
+    let cases = vec![
+        (
+            "statement maybe\nSELECT 1",
+            "Code: 5, displayText = Unexpected record at line 1: statement maybe.",
+        ),
+        (
+            "statement error abc\nSELECT 1",
+            "Code: 5, displayText = Bad error code abc at line 1.",
+        ),
+        (
+            "query I\n----\n1",
+            "Code: 5, displayText = Missing SQL of the record at line 1.",
+        ),
+        (
+            "query I sometimes\nSELECT 1",
+            "Code: 5, displayText = Unexpected record at line 1: query I sometimes.",
+        ),
+    ];
+
+    for (script, expect) in cases {
+        let result = parse_records(script);
+        assert_eq!(expect, result.unwrap_err().to_string(), "{}", script);
+    }
+    Ok(())
+}
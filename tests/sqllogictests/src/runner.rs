@@ -0,0 +1,142 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::backends::Backend;
+use crate::backends::Rows;
+use crate::parser::Record;
+use crate::parser::SortMode;
+use crate::parser::StatementExpect;
+
+/// A record whose result is not the expected one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub line: usize,
+    pub sql: String,
+    pub message: String,
+}
+
+/// Runs the records one by one, returns the failed ones.
+pub fn run_records(records: &[Record], backend: &mut dyn Backend) -> Vec<Failure> {
+    let mut failures = vec![];
+    for record in records {
+        if !record.should_run(backend.name()) {
+            continue;
+        }
+
+        let result = match record {
+            Record::Halt { .. } => break,
+            Record::Statement { sql, expect, .. } => {
+                run_statement(backend, sql, expect).map_err(|message| (sql, message))
+            }
+            Record::Query {
+                sql,
+                types,
+                sort_mode,
+                expected,
+                ..
+            } => run_query(backend, sql, types, *sort_mode, expected)
+                .map_err(|message| (sql, message)),
+        };
+
+        if let Err((sql, message)) = result {
+            failures.push(Failure {
+                line: record.line(),
+                sql: sql.clone(),
+                message,
+            });
+        }
+    }
+    failures
+}
+
+fn run_statement(
+    backend: &mut dyn Backend,
+    sql: &str,
+    expect: &StatementExpect,
+) -> std::result::Result<(), String> {
+    match (backend.query(sql), expect) {
+        (Ok(_), StatementExpect::Ok) => Ok(()),
+        (Ok(_), StatementExpect::Error(_)) => Err("expected an error, but got ok".to_string()),
+        (Err(error), StatementExpect::Ok) => Err(format!("expected ok, but got {}", error)),
+        (Err(_), StatementExpect::Error(None)) => Ok(()),
+        (Err(error), StatementExpect::Error(Some(code))) if error.code() == *code => Ok(()),
+        (Err(error), StatementExpect::Error(Some(code))) => {
+            Err(format!("expected error code {}, but got {}", code, error))
+        }
+    }
+}
+
+fn run_query(
+    backend: &mut dyn Backend,
+    sql: &str,
+    types: &str,
+    sort_mode: SortMode,
+    expected: &[String],
+) -> std::result::Result<(), String> {
+    let rows = backend
+        .query(sql)
+        .map_err(|error| format!("query failed, {}", error))?;
+
+    if let Some(row) = rows.iter().find(|row| row.len() != types.len()) {
+        return Err(format!(
+            "expected {} columns ({}), but got {}",
+            types.len(),
+            types,
+            row.len()
+        ));
+    }
+
+    let actual = normalize_rows(rows, sort_mode);
+    let expected = normalize_expected(expected, sort_mode);
+    match actual == expected {
+        true => Ok(()),
+        false => Err(format!(
+            "result mismatch\nexpected:\n{}\nactual:\n{}",
+            expected.join("\n"),
+            actual.join("\n")
+        )),
+    }
+}
+
+/// Formats the rows as the lines of a `.slt` result, values separated by a space.
+pub fn normalize_rows(rows: Rows, sort_mode: SortMode) -> Vec<String> {
+    let mut lines = match sort_mode {
+        SortMode::ValueSort => rows.into_iter().flatten().collect::<Vec<_>>(),
+        _ => rows.into_iter().map(|row| row.join(" ")).collect(),
+    };
+
+    if sort_mode != SortMode::NoSort {
+        lines.sort();
+    }
+    lines
+}
+
+/// Normalizes the whitespaces of the expected lines, so that they can be aligned.
+pub fn normalize_expected(expected: &[String], sort_mode: SortMode) -> Vec<String> {
+    let mut lines = match sort_mode {
+        SortMode::ValueSort => expected
+            .iter()
+            .flat_map(|line| line.split_whitespace().map(|v| v.to_string()))
+            .collect::<Vec<_>>(),
+        _ => expected
+            .iter()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect(),
+    };
+
+    if sort_mode != SortMode::NoSort {
+        lines.sort();
+    }
+    lines
+}
@@ -0,0 +1,151 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::backends::Backend;
+use crate::backends::Rows;
+use crate::parser::parse_records;
+use crate::parser::SortMode;
+use crate::runner::*;
+
+/// Answers the SQL from a fixed map, unknown SQL fails with `UnknownTable`.
+struct MockBackend {
+    results: HashMap<&'static str, Rows>,
+}
+
+impl Backend for MockBackend {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn query(&mut self, sql: &str) -> Result<Rows> {
+        self.results
+            .get(sql)
+            .cloned()
+            .ok_or_else(|| ErrorCode::UnknownTable(sql.to_string()))
+    }
+}
+
+fn rows(values: Vec<Vec<&str>>) -> Rows {
+    values
+        .into_iter()
+        .map(|row| row.into_iter().map(|v| v.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_normalize() -> Result<()> {
+    let actual = rows(vec![vec!["2", "y"], vec!["1", "x"]]);
+    let expected = vec!["1   x".to_string(), "2 y".to_string()];
+
+    assert_eq!(normalize_rows(actual.clone(), SortMode::NoSort), vec![
+        "2 y", "1 x"
+    ]);
+    assert_eq!(normalize_rows(actual.clone(), SortMode::RowSort), vec![
+        "1 x", "2 y"
+    ]);
+    assert_eq!(normalize_rows(actual, SortMode::ValueSort), vec![
+        "1", "2", "x", "y"
+    ]);
+    assert_eq!(normalize_expected(&expected, SortMode::NoSort), vec![
+        "1 x", "2 y"
+    ]);
+    assert_eq!(normalize_expected(&expected, SortMode::ValueSort), vec![
+        "1", "2", "x", "y"
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_run_records() -> Result<()> {
+    let mut backend = MockBackend {
+        results: vec![
+            ("CREATE TABLE t", rows(vec![])),
+            (
+                "SELECT a, b FROM t",
+                rows(vec![vec!["2", "y"], vec!["1", "x"]]),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+    };
+
+    let script = r#"
+statement ok
+CREATE TABLE t
+
+statement error 25
+SELECT * FROM not_exists
+
+query IT rowsort
+SELECT a, b FROM t
+----
+1 x
+2 y
+
+# Failed: the rows are not sorted.
+query IT
+SELECT a, b FROM t
+----
+1 x
+2 y
+
+# Failed: wrong column count.
+query I rowsort
+SELECT a, b FROM t
+----
+1 x
+2 y
+
+# Failed: wrong error code.
+statement error 1
+SELECT * FROM not_exists
+
+# Failed: no error.
+statement error
+CREATE TABLE t
+
+skipif mock
+statement ok
+SELECT * FROM not_exists
+
+halt
+
+statement ok
+SELECT * FROM not_exists
+"#;
+
+    let records = parse_records(script)?;
+    let failures = run_records(&records, &mut backend);
+    let failures = failures
+        .iter()
+        .map(|f| (f.line, f.message.lines().next().unwrap_or("")))
+        .collect::<Vec<_>>();
+
+    assert_eq!(failures, vec![
+        (15, "result mismatch"),
+        (22, "expected 1 columns (I), but got 2"),
+        (
+            29,
+            "expected error code 1, but got Code: 25, displayText = SELECT * FROM not_exists."
+        ),
+        (33, "expected an error, but got ok"),
+    ]);
+    Ok(())
+}
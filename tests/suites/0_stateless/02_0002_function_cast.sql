@@ -19,3 +19,8 @@ SELECT  toDateTime('2021-03-05 01:01:01') + 1 = toDateTime('2021-03-05 01:01:02'
 SELECT  toDate('2021-03-05') + 1 = toDate('2021-03-06');
 SELECT  toString(toDate('2021-03-05') + 1) = '2021-03-06';
 SELECT toDateTime(toDate('2021-03-05')) = toDateTime('2021-03-05 00:00:00');
+SELECT '===TRY_CAST/FORMAT===';
+SELECT TRY_CAST(toDate('2021-03-05') AS Boolean) IS NULL;
+SELECT TRY_CAST('12' AS UInt8) = 12;
+SELECT toDate('05/03/2021', '%d/%m/%Y') = toDate('2021-03-05');
+SELECT toString(toDateTime('2021-03-05 01:01:01'), '%Y%m%d%H%M%S');
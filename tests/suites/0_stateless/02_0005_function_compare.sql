@@ -14,4 +14,6 @@ select * from system.databases where name not like '%sys%' order by name;
 select * from system.databases where name not like '_ef_ul_' order by name;
 
 select * from numbers(10) where null = true;
-select * from numbers(10) where null and true;
\ No newline at end of file
+select * from numbers(10) where null and true;
+select null <=> null, 1 <=> null, 1 <=> 1, 1 <=> 2, 1 is distinct from null, null is not distinct from null;
+select number from numbers(3) where number <=> 1;
\ No newline at end of file
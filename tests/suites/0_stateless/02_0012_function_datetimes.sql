@@ -167,3 +167,21 @@ select '===toSecond===';
 select '===toMonday===';
 select toMonday(toDateTime(1634614318))  =  toDate('2021-10-18');
 select '===toMonday===';
+
+select '===date_add===';
+select date_add(toDate(18321), interval '1' year); -- 2020-2-29 + 1 year
+select date_sub(toDateTime(1582970400), interval '1 month'); -- 2020-2-29T10:00:00 - 1 month
+select toDate(18321) + interval '2 weeks';
+select '===date_add===';
+
+select '===date_diff===';
+select date_diff('month', toDate(18321), toDate(18321) + interval '1' year);
+select date_diff('day', toDateTime(1582970400), toDateTime(1582970400) + interval '25' hour);
+select date_diff('hour', toDateTime(1582970400), toDateTime(1582970400) + interval '25' hour);
+select '===date_diff===';
+
+select '===date_trunc===';
+select date_trunc('month', toDate(18321));
+select date_trunc('week', toDateTime(1582970400)); -- 2020-2-29 is a Saturday
+select date_trunc('quarter', toDateTime(1582970400)) = toDateTime(1577836800);
+select '===date_trunc===';
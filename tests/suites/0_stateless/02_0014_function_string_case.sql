@@ -0,0 +1,3 @@
+SELECT lower('AbC'), upper('AbC');
+SELECT lcase('ÀÉÎ'), ucase('àéî');
+SELECT lower(toString(number)) FROM numbers(2);
@@ -0,0 +1,5 @@
+SELECT MATCH('GET /api/v1/users 200', 'Users 200');
+SELECT MATCH('GET /api/v1/users 200', 'user');
+SELECT MATCH('GET /api/v1/users 200', '');
+SELECT MATCH(NULL, 'users');
+SELECT number FROM numbers(30) WHERE MATCH(toString(number), '2') ORDER BY number;
@@ -7,3 +7,8 @@ select sumDistinct(number) /  countDistinct(number) = avgDistinct(number) from (
 select sumIf(number, number >= 100000 - 1) from numbers(100000);
 select sumIf(number, number > 100) /  countIf(number,  number > 100) = avgIf(number,  number > 100) from numbers(100000);
 select countIf(number, number>9) from numbers(10);
+
+-- filter
+select sum(number) filter (where number % 2 = 0), count() filter (where number > 5) from numbers(10);
+select count(distinct number % 3) filter (where number < 5) from numbers(10);
+select count(distinct number % 3, number % 2) from numbers(10);
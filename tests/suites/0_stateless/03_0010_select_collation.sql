@@ -0,0 +1,18 @@
+DROP TABLE IF EXISTS t1;
+CREATE TABLE t1(name varchar(255) COLLATE utf8_general_ci, code varchar(255)) ENGINE = Memory;
+INSERT INTO TABLE t1 values('apple', 'a'), ('Apple', 'A'), ('banana', 'b'), ('APPLE', 'a');
+
+SELECT 'case-insensitive column';
+SELECT count() FROM t1 WHERE name = 'APPLE';
+SELECT count() FROM t1 WHERE name = 'apple' COLLATE utf8_bin;
+SELECT name, code FROM t1 WHERE name = 'banana';
+SELECT name, count() FROM t1 GROUP BY name ORDER BY name;
+SELECT name FROM t1 ORDER BY name DESC LIMIT 1;
+
+SELECT 'explicit collation';
+SELECT count() FROM t1 WHERE code = 'A';
+SELECT count() FROM t1 WHERE code = 'A' COLLATE utf8_general_ci;
+SELECT count() FROM t1 WHERE code COLLATE utf8_bin = 'a' COLLATE utf8_general_ci; -- {ErrorCode 6}
+SELECT count() FROM t1 WHERE code = 'A' COLLATE utf8; -- {ErrorCode 6}
+
+DROP TABLE IF EXISTS t1;
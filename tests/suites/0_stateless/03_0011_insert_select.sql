@@ -0,0 +1,16 @@
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS t2;
+
+CREATE TABLE t1(a bigint, b varchar) ENGINE = Fuse;
+CREATE TABLE t2(a int, b varchar) ENGINE = Fuse;
+
+INSERT INTO t1 SELECT number, toString(number % 3) FROM numbers(100000);
+SELECT count(), sum(a) FROM t1;
+
+INSERT INTO t2 SELECT a + 1, b FROM t1 WHERE b = '1';
+SELECT count(), min(a), max(a) FROM t2;
+
+INSERT INTO t2 SELECT a FROM t1; -- {ErrorCode 6}
+
+DROP TABLE t1;
+DROP TABLE t2;
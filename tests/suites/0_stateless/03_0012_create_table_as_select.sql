@@ -0,0 +1,16 @@
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS t2;
+
+CREATE TABLE t1 ENGINE = Fuse AS SELECT number AS a, toString(number % 3) AS b FROM numbers(100000);
+SELECT count(), sum(a) FROM t1;
+
+CREATE TABLE t2(a int, b varchar) ENGINE = Fuse AS SELECT a + 1, b FROM t1 WHERE b = '1';
+SELECT count(), min(a), max(a) FROM t2;
+
+CREATE TABLE IF NOT EXISTS t2 ENGINE = Fuse AS SELECT a, b FROM t1;
+SELECT count() FROM t2;
+
+CREATE TABLE t3(a int) ENGINE = Fuse AS SELECT a, b FROM t1; -- {ErrorCode 6}
+
+DROP TABLE t1;
+DROP TABLE t2;
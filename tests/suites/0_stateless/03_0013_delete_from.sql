@@ -0,0 +1,22 @@
+DROP TABLE IF EXISTS t1;
+
+CREATE TABLE t1(a int, b varchar) ENGINE = Fuse;
+INSERT INTO t1 SELECT number, toString(number % 3) FROM numbers(1000);
+INSERT INTO t1 SELECT number + 1000, toString(number % 3) FROM numbers(1000);
+
+DELETE FROM t1 WHERE a >= 1500;
+SELECT count(), max(a) FROM t1;
+
+DELETE FROM t1 WHERE b = '1';
+SELECT count() FROM t1 WHERE b = '1';
+SELECT count() FROM t1;
+
+DELETE FROM t1 WHERE a > 100000;
+SELECT count() FROM t1;
+
+DELETE FROM t1 WHERE sum(a) > 1; -- {ErrorCode 5}
+
+DELETE FROM t1;
+SELECT count() FROM t1;
+
+DROP TABLE t1;
@@ -0,0 +1,22 @@
+DROP TABLE IF EXISTS t1;
+
+CREATE TABLE t1(a int, b varchar) ENGINE = Fuse;
+INSERT INTO t1 SELECT number, toString(number % 3) FROM numbers(1000);
+
+UPDATE t1 SET b = 'x' WHERE a >= 500;
+SELECT count() FROM t1 WHERE b = 'x';
+
+UPDATE t1 SET a = a + 1000, b = '3' WHERE b = 'x' AND a < 600;
+SELECT count(), min(a), max(a) FROM t1 WHERE b = '3';
+SELECT count(), sum(a) FROM t1;
+
+UPDATE t1 SET a = 0 WHERE a > 100000;
+SELECT count() FROM t1 WHERE a = 0;
+
+UPDATE t1 SET a = sum(a); -- {ErrorCode 5}
+UPDATE t1 SET a = 1, a = 2; -- {ErrorCode 5}
+
+UPDATE t1 SET b = 'y';
+SELECT count() FROM t1 WHERE b = 'y';
+
+DROP TABLE t1;
@@ -0,0 +1,16 @@
+DROP TABLE IF EXISTS t1;
+
+CREATE TABLE t1(a int) ENGINE = Fuse;
+INSERT INTO t1 VALUES (10), (11), (12);
+INSERT INTO t1 VALUES (20), (21);
+
+SELECT * FROM t1 ORDER BY a LIMIT 1;
+SELECT a, _row_id FROM t1 ORDER BY a;
+SELECT count(DISTINCT _block_path) FROM t1;
+SELECT a FROM t1 WHERE _row_id = 1 ORDER BY a;
+SELECT count() FROM t1 WHERE _file_name = ''; -- {ErrorCode 6}
+
+CREATE TABLE t2(_row_id int) ENGINE = Fuse; -- {ErrorCode 6}
+ALTER TABLE t1 ADD COLUMN _block_path varchar; -- {ErrorCode 6}
+
+DROP TABLE t1;
@@ -0,0 +1,30 @@
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS s1;
+
+CREATE TABLE t1(id int, v varchar) ENGINE = Fuse;
+CREATE TABLE s1(id int, v varchar) ENGINE = Fuse;
+INSERT INTO t1 SELECT number, 'old' FROM numbers(10);
+INSERT INTO s1 SELECT number + 5, toString(number + 5) FROM numbers(10);
+
+MERGE INTO t1 USING s1 ON t1.id = s1.id
+    WHEN MATCHED AND s1.id >= 8 THEN DELETE
+    WHEN MATCHED THEN UPDATE SET v = s1.v
+    WHEN NOT MATCHED THEN INSERT VALUES (s1.id, 'new');
+SELECT count() FROM t1;
+SELECT count() FROM t1 WHERE v = 'old';
+SELECT id, v FROM t1 WHERE v != 'old' ORDER BY id;
+
+MERGE INTO t1 AS t USING (SELECT number AS id FROM numbers(3)) AS d ON t.id = d.id
+    WHEN MATCHED THEN UPDATE SET v = 'd';
+SELECT count() FROM t1 WHERE v = 'd';
+
+MERGE INTO t1 USING s1 ON t1.id = s1.id + 100
+    WHEN NOT MATCHED AND s1.id < 7 THEN INSERT (id) VALUES (s1.id + 100);
+SELECT count() FROM t1 WHERE id > 100;
+
+MERGE INTO t1 USING s1 ON t1.id > s1.id WHEN MATCHED THEN DELETE; -- {ErrorCode 2}
+MERGE INTO t1 USING s1 ON t1.id = s1.id WHEN MATCHED THEN UPDATE SET v = 'a', v = 'b'; -- {ErrorCode 5}
+MERGE INTO t1 USING s1 ON t1.id = s1.id WHEN NOT MATCHED THEN INSERT VALUES (1); -- {ErrorCode 6}
+
+DROP TABLE t1;
+DROP TABLE s1;
@@ -0,0 +1,8 @@
+SELECT number % 3 AS p, number, row_number() OVER (PARTITION BY number % 3 ORDER BY number) AS rn FROM numbers(7) ORDER BY p, number;
+SELECT number % 3 AS v, rank() OVER (ORDER BY number % 3) AS r, dense_rank() OVER (ORDER BY number % 3) AS d FROM numbers(6) ORDER BY v;
+SELECT number, sum(number) OVER (PARTITION BY number % 2) AS total, sum(number) OVER (PARTITION BY number % 2 ORDER BY number) AS running FROM numbers(6) ORDER BY number;
+SELECT count(*) OVER () FROM numbers(3);
+
+SELECT number FROM numbers(3) WHERE rank() OVER (ORDER BY number) > 1; -- {ErrorCode 5}
+SELECT sum(number) OVER (ORDER BY number ROWS 1 PRECEDING) FROM numbers(3); -- {ErrorCode 2}
+SELECT row_number(number) OVER () FROM numbers(3); -- {ErrorCode 28}
@@ -0,0 +1,8 @@
+WITH t AS (SELECT number AS n FROM numbers(5)) SELECT n FROM t WHERE n > 2 ORDER BY n;
+WITH a AS (SELECT number FROM numbers(3)), b(x) AS (SELECT number * 10 FROM a) SELECT x FROM b ORDER BY x;
+WITH RECURSIVE t(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t WHERE n < 5) SELECT sum(n) FROM t;
+WITH RECURSIVE fib(a, b) AS (SELECT 0, 1 UNION ALL SELECT b, a + b FROM fib WHERE b < 50) SELECT a FROM fib ORDER BY a;
+
+WITH t AS (SELECT 1), t AS (SELECT 2) SELECT * FROM t; -- {ErrorCode 5}
+SET max_recursive_iterations = 10;
+WITH RECURSIVE t(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t) SELECT n FROM t; -- {ErrorCode 61}
@@ -0,0 +1,6 @@
+SET max_bytes_before_external_group_by = 1;
+SELECT count(), sum(c), sum(s) FROM (SELECT number % 1000 AS k, count() AS c, sum(number) AS s FROM numbers_mt(100000) GROUP BY k);
+SELECT k, c, s FROM (SELECT number % 1000 AS k, count() AS c, sum(number) AS s FROM numbers_mt(100000) GROUP BY k) ORDER BY k LIMIT 3;
+SELECT count() FROM (SELECT toString(number) AS k FROM numbers_mt(100000) GROUP BY k);
+SET max_bytes_before_external_group_by = 0;
+SELECT count(), sum(c), sum(s) FROM (SELECT number % 1000 AS k, count() AS c, sum(number) AS s FROM numbers_mt(100000) GROUP BY k);
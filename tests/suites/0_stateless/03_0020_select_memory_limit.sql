@@ -0,0 +1,8 @@
+SET max_memory_usage = 1;
+SELECT number FROM numbers(10) ORDER BY number; -- {ErrorCode 65}
+SELECT number % 3 AS k, count() FROM numbers(10) GROUP BY k; -- {ErrorCode 65}
+SELECT count(*) OVER () FROM numbers(3); -- {ErrorCode 65}
+SELECT count() FROM numbers(10);
+SET max_memory_usage = 0;
+SELECT number FROM numbers(3) ORDER BY number DESC;
+SELECT count(*) OVER () FROM numbers(3);
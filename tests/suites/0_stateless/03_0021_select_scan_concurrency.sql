@@ -0,0 +1,19 @@
+DROP TABLE IF EXISTS t;
+CREATE TABLE t(a int) ENGINE = Fuse;
+INSERT INTO t SELECT number FROM numbers(100);
+INSERT INTO t SELECT number FROM numbers(100);
+INSERT INTO t SELECT number FROM numbers(100);
+INSERT INTO t SELECT number FROM numbers(100);
+
+SET scan_io_concurrency = 64;
+SET scan_decode_parallelism = 1;
+SELECT count(), sum(a) FROM t;
+SET scan_decode_parallelism = 3;
+SELECT count(), sum(a) FROM t WHERE a > 49;
+SET scan_io_concurrency = 1;
+SELECT count(), sum(a) FROM t;
+
+SET scan_io_concurrency = 0;
+SET scan_decode_parallelism = 0;
+SELECT count(), sum(a) FROM t;
+DROP TABLE t;
@@ -0,0 +1,22 @@
+DROP VIEW IF EXISTS v1;
+DROP VIEW IF EXISTS v2;
+DROP TABLE IF EXISTS t1;
+CREATE TABLE t1(a int, b varchar) ENGINE = Memory;
+INSERT INTO t1 VALUES(1, 'x'), (2, 'y'), (3, 'z');
+
+CREATE VIEW v1 AS SELECT a * 10 AS c, b FROM t1 WHERE a > 1;
+CREATE VIEW IF NOT EXISTS v1 AS SELECT 1;
+CREATE VIEW v1 AS SELECT 1; -- {ErrorCode 4003}
+CREATE VIEW v2 AS SELECT c + 1 AS d FROM v1;
+SELECT b, c FROM v1 ORDER BY c;
+SELECT d FROM v2 ORDER BY d;
+
+INSERT INTO t1 VALUES(4, 'w');
+SELECT count() FROM v2;
+
+DROP VIEW t1; -- {ErrorCode 6}
+DROP VIEW v2;
+DROP VIEW v2; -- {ErrorCode 25}
+DROP VIEW IF EXISTS v2;
+DROP VIEW v1;
+DROP TABLE t1;
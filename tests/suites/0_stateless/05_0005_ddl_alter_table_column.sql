@@ -0,0 +1,32 @@
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS t2;
+
+CREATE TABLE t1(a int, b varchar) ENGINE = Fuse;
+INSERT INTO t1 VALUES(1, 'x'), (2, 'y');
+ALTER TABLE t1 ADD COLUMN c bigint;
+INSERT INTO t1 VALUES(3, 'z', 30);
+SELECT a, b, c FROM t1 ORDER BY a;
+ALTER TABLE t1 DROP COLUMN b;
+SELECT * FROM t1 ORDER BY a;
+ALTER TABLE t1 ADD COLUMN b int;
+SELECT a, b FROM t1 ORDER BY a;
+
+-- A column added again with the same type does not read the values of the dropped one.
+ALTER TABLE t1 DROP COLUMN c;
+ALTER TABLE t1 ADD COLUMN c bigint;
+SELECT a, c FROM t1 ORDER BY a;
+
+ALTER TABLE t1 ADD COLUMN a int; -- {ErrorCode 6}
+ALTER TABLE t1 DROP COLUMN x; -- {ErrorCode 6}
+
+CREATE TABLE t2(a int) ENGINE = Memory;
+INSERT INTO t2 VALUES(1);
+ALTER TABLE t2 ADD b varchar;
+INSERT INTO t2 VALUES(2, 'y');
+SELECT a, b FROM t2 ORDER BY a;
+ALTER TABLE t2 DROP a;
+SELECT count(), count(b) FROM t2;
+ALTER TABLE t2 DROP b; -- {ErrorCode 6}
+
+DROP TABLE t1;
+DROP TABLE t2;
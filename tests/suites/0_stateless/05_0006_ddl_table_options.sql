@@ -0,0 +1,13 @@
+DROP TABLE IF EXISTS t1;
+
+CREATE TABLE t1(a int) ENGINE = Memory max_byte = 1024; -- {ErrorCode 22}
+CREATE TABLE t1(a int) ENGINE = Memory overflow = 'drop'; -- {ErrorCode 22}
+CREATE TABLE t1(a int) ENGINE = Memory max_bytes = 1024;
+ALTER TABLE t1 SET OPTIONS (max_bytes = 1048576, overflow = 'spill');
+SHOW TABLE OPTIONS FROM t1;
+
+ALTER TABLE t1 SET OPTIONS (max_bytes = -1); -- {ErrorCode 22}
+ALTER TABLE t1 SET OPTIONS (primary_key = 'a'); -- {ErrorCode 22}
+ALTER TABLE t1 SET OPTIONS (changed_rows = 0); -- {ErrorCode 22}
+
+DROP TABLE t1;
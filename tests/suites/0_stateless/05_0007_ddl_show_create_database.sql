@@ -0,0 +1,7 @@
+DROP DATABASE IF EXISTS db1;
+
+CREATE DATABASE db1 storage_type = 's3', storage_s3_bucket = 'bucket', storage_s3_access_key_id = 'ak';
+SHOW CREATE DATABASE db1;
+SHOW CREATE DATABASE db2; -- {ErrorCode 3}
+
+DROP DATABASE db1;
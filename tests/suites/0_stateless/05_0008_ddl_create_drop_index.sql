@@ -0,0 +1,24 @@
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS t2;
+
+CREATE TABLE t1(a int, url varchar) ENGINE = Fuse;
+CREATE INDEX idx1 ON t1 (lower(url));
+CREATE INDEX IF NOT EXISTS idx1 ON t1 (upper(url));
+CREATE INDEX idx2 ON t1 (a + 1);
+INSERT INTO t1 VALUES(1, 'A.com'), (2, 'b.com');
+INSERT INTO t1 VALUES(3, 'C.com'), (4, 'd.com');
+SELECT a FROM t1 WHERE lower(url) = 'c.com';
+SELECT a FROM t1 WHERE lower(url) > 'x.com';
+SELECT a FROM t1 WHERE a + 1 >= 4 ORDER BY a;
+DROP INDEX idx1 ON t1;
+DROP INDEX IF EXISTS idx1 ON t1;
+SELECT a FROM t1 WHERE lower(url) = 'a.com';
+
+CREATE INDEX idx2 ON t1 (a + 2); -- {ErrorCode 63}
+DROP INDEX idx1 ON t1; -- {ErrorCode 62}
+
+CREATE TABLE t2(a int) ENGINE = Memory;
+CREATE INDEX idx1 ON t2 (a + 1); -- {ErrorCode 2}
+
+DROP TABLE t1;
+DROP TABLE t2;
@@ -0,0 +1,15 @@
+DROP TABLE IF EXISTS t1;
+
+CREATE TABLE t1(a int, msg varchar) ENGINE = Fuse;
+CREATE INVERTED INDEX idx1 ON t1 (msg);
+INSERT INTO t1 VALUES(1, 'GET /api/users 200'), (2, 'POST /api/users 500');
+INSERT INTO t1 VALUES(3, 'connection refused'), (4, 'Connection reset');
+SELECT a FROM t1 WHERE MATCH(msg, 'users 500');
+SELECT a FROM t1 WHERE MATCH(msg, 'CONNECTION') ORDER BY a;
+SELECT a FROM t1 WHERE MATCH(msg, 'connection 500');
+SELECT count(*) FROM t1 WHERE MATCH(msg, 'timeout');
+
+CREATE INVERTED INDEX idx2 ON t1 (a); -- {ErrorCode 5}
+CREATE INVERTED INDEX idx2 ON t1 (lower(msg)); -- {ErrorCode 5}
+
+DROP TABLE t1;
@@ -0,0 +1,15 @@
+DROP DATABASE IF EXISTS db_with_tables;
+
+CREATE DATABASE db_with_tables WITH TABLES (t1 (a int, b int) ENGINE = Null, t2 (c int) ENGINE = Memory);
+SELECT name FROM system.tables WHERE database = 'db_with_tables' ORDER BY name;
+INSERT INTO db_with_tables.t2 VALUES (1), (2);
+SELECT SUM(c) FROM db_with_tables.t2;
+
+-- The database exists, no table is added.
+CREATE DATABASE IF NOT EXISTS db_with_tables WITH TABLES (t3 (a int) ENGINE = Null);
+SELECT COUNT(1) FROM system.tables WHERE database = 'db_with_tables';
+CREATE DATABASE db_with_tables WITH TABLES (t3 (a int) ENGINE = Null); -- {ErrorCode 4001}
+
+CREATE DATABASE db_with_dup_tables WITH TABLES (t1 (a int) ENGINE = Null, t1 (b int) ENGINE = Null); -- {ErrorCode 5}
+
+DROP DATABASE db_with_tables;
@@ -0,0 +1,41 @@
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS t2;
+
+CREATE TABLE t1(a int) ENGINE = Fuse;
+INSERT INTO t1 VALUES(1), (2);
+ALTER TABLE t1 ADD COLUMN b bigint DEFAULT 10 * 2;
+ALTER TABLE t1 ADD COLUMN c varchar DEFAULT 'x';
+INSERT INTO t1 VALUES(3, 30, 'z');
+SELECT a, b, c FROM t1 ORDER BY a;
+SELECT sum(b) FROM t1 WHERE c = 'x';
+
+-- The blocks written before the column was added are rewritten with the default.
+ALTER TABLE t1 MATERIALIZE COLUMN b;
+SELECT a, b, c FROM t1 ORDER BY a;
+
+-- A column added again does not inherit the default of the dropped one.
+ALTER TABLE t1 DROP COLUMN c;
+ALTER TABLE t1 ADD COLUMN c int;
+SELECT a, b, c FROM t1 ORDER BY a;
+
+-- A column added again with the same type does not read the values of the dropped one.
+ALTER TABLE t1 DROP COLUMN b;
+ALTER TABLE t1 ADD COLUMN b bigint;
+INSERT INTO t1 VALUES(4, 40, 4);
+SELECT a, b, c FROM t1 ORDER BY a;
+
+ALTER TABLE t1 ADD COLUMN d int DEFAULT a + 1; -- {ErrorCode 6}
+ALTER TABLE t1 MATERIALIZE COLUMN x; -- {ErrorCode 6}
+
+CREATE TABLE t2(a int) ENGINE = Memory;
+INSERT INTO t2 VALUES(1);
+ALTER TABLE t2 ADD COLUMN b int DEFAULT -1;
+INSERT INTO t2 VALUES(2, 2);
+SELECT a, b FROM t2 ORDER BY a;
+ALTER TABLE t2 DROP COLUMN b;
+ALTER TABLE t2 ADD COLUMN b int;
+SELECT a, b FROM t2 ORDER BY a;
+ALTER TABLE t2 MATERIALIZE COLUMN b; -- {ErrorCode 6}
+
+DROP TABLE t1;
+DROP TABLE t2;